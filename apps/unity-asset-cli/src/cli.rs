@@ -32,7 +32,7 @@ pub(crate) enum Commands {
         #[arg(short, long)]
         input: PathBuf,
 
-        /// Output format (summary, detailed, json)
+        /// Output format (summary, detailed, json, msgpack, cbor); msgpack/cbor write one record per document to stdout
         #[arg(short, long, default_value = "debug")]
         format: String,
 
@@ -54,6 +54,31 @@ pub(crate) enum Commands {
         /// Unity class types to extract (GameObject, Transform, etc.)
         #[arg(long)]
         types: Vec<String>,
+
+        /// Dump raw object payloads (`{class}/{path_id}.bin` + `index.csv`) from binary files
+        #[arg(long)]
+        raw: bool,
+
+        /// With `--raw`: skip objects larger than this many bytes
+        #[arg(long, requires = "raw")]
+        max_object_size: Option<u64>,
+
+        /// With `--raw`: stop after writing this many bytes in total
+        #[arg(long, requires = "raw")]
+        max_total_size: Option<u64>,
+
+        /// Render the UV layout of every Mesh in a binary file to `<name>_<path_id>_uv<N>.png`
+        /// (requires feature `decode`)
+        #[arg(long, conflicts_with = "raw")]
+        uv_layout: bool,
+
+        /// With `--uv-layout`: UV set to draw (0 = the main UVs)
+        #[arg(long, default_value_t = 0, requires = "uv_layout")]
+        uv_channel: usize,
+
+        /// With `--uv-layout`: image size in pixels
+        #[arg(long, default_value_t = 1024, requires = "uv_layout")]
+        uv_size: u32,
     },
 
     /// Export objects from AssetBundles using the bundle `m_Container` (UnityPy-like workflow)
@@ -90,6 +115,23 @@ pub(crate) enum Commands {
         #[arg(long)]
         decode: bool,
 
+        /// With `--decode`: write a Unity `.meta` next to each decoded file, with a stable GUID
+        /// derived from the source file and path id
+        #[arg(long, requires = "decode")]
+        write_meta: bool,
+
+        /// With `--decode`: post-process decoded textures, as `<name glob>=<transform>` or
+        /// `slot:<material property>=<transform>` (can be repeated; the first match wins).
+        /// Transforms: `r`/`g`/`b`/`a` (one channel as grey), `swizzle:<rgba01 x4>`, `dxt5nm`
+        /// and `bc5` (rebuild normal maps)
+        #[arg(long, requires = "decode")]
+        texture_transform: Vec<String>,
+
+        /// Group near-duplicate textures among the exported Texture2D images: perceptual hashes
+        /// at most this many bits apart (of 64) are reported together and in the manifest
+        #[arg(long, requires = "decode", value_name = "MAX_DISTANCE")]
+        similar_textures: Option<u32>,
+
         /// Overwrite existing output files (still avoids in-run collisions)
         #[arg(long, conflicts_with = "skip_existing")]
         overwrite: bool,
@@ -160,6 +202,23 @@ pub(crate) enum Commands {
         #[arg(long)]
         decode: bool,
 
+        /// With `--decode`: write a Unity `.meta` next to each decoded file, with a stable GUID
+        /// derived from the source file and path id
+        #[arg(long, requires = "decode")]
+        write_meta: bool,
+
+        /// With `--decode`: post-process decoded textures, as `<name glob>=<transform>` or
+        /// `slot:<material property>=<transform>` (can be repeated; the first match wins).
+        /// Transforms: `r`/`g`/`b`/`a` (one channel as grey), `swizzle:<rgba01 x4>`, `dxt5nm`
+        /// and `bc5` (rebuild normal maps)
+        #[arg(long, requires = "decode")]
+        texture_transform: Vec<String>,
+
+        /// Group near-duplicate textures among the exported Texture2D images: perceptual hashes
+        /// at most this many bits apart (of 64) are reported together and in the manifest
+        #[arg(long, requires = "decode", value_name = "MAX_DISTANCE")]
+        similar_textures: Option<u32>,
+
         /// Overwrite existing output files
         #[arg(long, conflicts_with = "skip_existing")]
         overwrite: bool,
@@ -204,6 +263,24 @@ pub(crate) enum Commands {
         verbose: bool,
     },
 
+    /// Summarize AssetBundle contents by type (textures, audio, meshes, MonoBehaviours) without decoding
+    ///
+    /// A player's `globalgamemanagers` prints its BuildSettings instead: engine version and the
+    /// scenes in build order.
+    Info {
+        /// Input AssetBundle or globalgamemanagers path (directories are scanned recursively)
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Print one JSON object per bundle
+        #[arg(long)]
+        json: bool,
+
+        /// Also print per-block compression sizes, per-file compressed estimates and padding
+        #[arg(long)]
+        blocks: bool,
+    },
+
     /// List binary objects (path_id/class_id/peek_name) from SerializedFiles or bundles
     #[command(name = "list-objects")]
     ListObjects {
@@ -355,7 +432,7 @@ pub(crate) enum Commands {
         asset_index: Option<usize>,
 
         /// Object PathID inside the serialized file
-        #[arg(long)]
+        #[arg(long, allow_negative_numbers = true)]
         path_id: Option<i64>,
 
         /// Limit printed recursion depth
@@ -521,4 +598,80 @@ pub(crate) enum Commands {
         #[arg(long)]
         follow_symlinks: bool,
     },
+
+    /// Search string values (YAML scalars, TypeTree `string` fields) with a regex
+    Grep {
+        /// Input file or directory path (YAML, assets and bundles will be auto-detected)
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Regular expression matched against each line of each string value
+        #[arg(long)]
+        pattern: String,
+
+        /// Only search objects of this class name (case-insensitive). Example: `--class TextAsset`.
+        #[arg(long)]
+        class: Option<String>,
+
+        /// Print lines around each match (2 when given without a value)
+        #[arg(long, num_args = 0..=1, default_missing_value = "2", default_value_t = 0)]
+        context: usize,
+
+        /// Longest prefix of each value to search, in bytes (large TextAsset payloads are cut)
+        #[arg(long, default_value_t = 1024 * 1024)]
+        max_value_len: usize,
+    },
+
+    /// Print one object's full parsed content (binary by `--path-id`, YAML by `--anchor`)
+    Show {
+        /// Input file or directory path (YAML, assets and bundles will be auto-detected)
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Object PathID, looked up in every loaded SerializedFile
+        #[arg(
+            long,
+            allow_negative_numbers = true,
+            conflicts_with = "anchor",
+            required_unless_present = "anchor"
+        )]
+        path_id: Option<i64>,
+
+        /// YAML object anchor (the `&<id>` part of the document header)
+        #[arg(long, allow_hyphen_values = true)]
+        anchor: Option<String>,
+
+        /// Output format: tree|json|yaml|msgpack|cbor (msgpack/cbor write one binary record per object)
+        #[arg(long, default_value = "tree")]
+        format: String,
+
+        /// Dump the object bytes as hex with offsets instead of parsing them
+        #[arg(long, conflicts_with = "anchor")]
+        raw_hex: bool,
+
+        /// Bytes shown in the hex preview of byte arrays
+        #[arg(long, default_value_t = 16)]
+        max_bytes: usize,
+    },
+
+    /// Check files and directory trees for corruption (for CI gates)
+    ///
+    /// Exits with status 1 when any finding is at least as severe as `--fail-on`.
+    Validate {
+        /// Input files or directories (repeatable; directories are walked recursively)
+        #[arg(short, long, required = true)]
+        input: Vec<PathBuf>,
+
+        /// Also parse every object with a strict TypeTree (slow)
+        #[arg(long)]
+        deep: bool,
+
+        /// Print the results as one JSON document
+        #[arg(long)]
+        json: bool,
+
+        /// Lowest severity that fails the run: warn|error
+        #[arg(long, default_value = "error")]
+        fail_on: String,
+    },
 }
@@ -48,7 +48,14 @@ fn deps_analyze_and_print(
                 deps.internal_references.len(),
                 deps.dependency_graph.edges.len()
             );
-            println!("External refs: {}", deps.external_references.len());
+            println!(
+                "External refs: {} (builtin={})",
+                deps.external_references.len(),
+                deps.external_references
+                    .iter()
+                    .filter(|r| r.builtin.is_some())
+                    .count()
+            );
             println!("Roots: {}", deps.dependency_graph.root_objects.len());
             println!("Leaves: {}", deps.dependency_graph.leaf_objects.len());
             println!("Cycles: {}", deps.circular_dependencies.len());
@@ -1,8 +1,10 @@
 use crate::pattern::container_asset_path_matches_ci;
 use crate::shared::{
-    AppContext, build_environment, class_name_for_id, load_environment_input,
-    lookup_object_type_info,
+    AppContext, TextureTransforms, build_environment, class_name_for_id, load_environment_input,
+    lookup_object_type_info, print_similar_textures, similar_textures_report,
 };
+#[cfg(feature = "decode")]
+use crate::shared::{export_texture, sprite_texture_pptr, write_meta_file};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -24,7 +26,7 @@ use unity_asset_binary::{asset::class_ids, unity_version::UnityVersion};
 use unity_asset_decode::{
     audio::{AudioClipConverter, AudioProcessor},
     sprite::SpriteProcessor,
-    texture::{TextureExporter, TextureProcessor},
+    texture::TextureProcessor,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -37,6 +39,9 @@ pub(crate) fn run(
     class_name: String,
     dry_run: bool,
     decode: bool,
+    write_meta: bool,
+    texture_transform: Vec<String>,
+    similar_textures: Option<u32>,
     overwrite: bool,
     skip_existing: bool,
     manifest: Option<PathBuf>,
@@ -46,6 +51,7 @@ pub(crate) fn run(
     jobs: usize,
     ctx: &AppContext,
 ) -> Result<()> {
+    let texture_transforms = TextureTransforms::parse(&texture_transform)?;
     export_bundle_command(
         input,
         output,
@@ -55,6 +61,9 @@ pub(crate) fn run(
         class_name,
         dry_run,
         decode,
+        write_meta,
+        &texture_transforms,
+        similar_textures,
         overwrite,
         skip_existing,
         manifest,
@@ -147,25 +156,6 @@ fn text_asset_bytes(obj: &UnityObject) -> Vec<u8> {
     Vec::new()
 }
 
-#[cfg(feature = "decode")]
-fn sprite_texture_pptr(obj: &UnityObject) -> Option<(i32, i64)> {
-    let UnityValue::Object(rd) = obj.get("m_RD")? else {
-        return None;
-    };
-    let UnityValue::Object(texture) = rd.get("texture")? else {
-        return None;
-    };
-    let file_id = match texture.get("m_FileID")? {
-        UnityValue::Integer(v) => *v as i32,
-        _ => return None,
-    };
-    let path_id = match texture.get("m_PathID")? {
-        UnityValue::Integer(v) => *v,
-        _ => return None,
-    };
-    Some((file_id, path_id))
-}
-
 #[derive(Debug, Clone)]
 struct ExportJob {
     order: usize,
@@ -226,6 +216,9 @@ struct ExportManifest {
     failed: usize,
     filtered: usize,
     entries: Vec<ExportManifestEntry>,
+    /// `--similar-textures` report (see `shared::similar_textures_report`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    similar_textures: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone)]
@@ -324,6 +317,31 @@ fn read_export_manifest(path: &Path) -> Result<ExportManifest> {
     Ok(manifest)
 }
 
+/// `--similar-textures` over the Texture2D images this run exported or found in place.
+fn similar_textures_pass(
+    outcomes: &[ExportOutcome],
+    max_distance: Option<u32>,
+    show_warnings: bool,
+) -> Result<Option<serde_json::Value>> {
+    let Some(max_distance) = max_distance else {
+        return Ok(None);
+    };
+    let exports = outcomes
+        .iter()
+        .map(|o| &o.entry)
+        .filter(|e| {
+            e.type_id == Some(unity_asset_binary::asset::class_ids::TEXTURE_2D)
+                && matches!(
+                    e.status,
+                    ExportStatus::ExportedDecoded
+                        | ExportStatus::SkippedExisting
+                        | ExportStatus::Resumed
+                )
+        })
+        .filter_map(|e| Some((e.asset_path.as_str(), Path::new(e.output_path.as_deref()?))));
+    similar_textures_report(exports, max_distance, show_warnings).map(Some)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn export_bundle_command(
     input: PathBuf,
@@ -334,6 +352,9 @@ fn export_bundle_command(
     class_name: String,
     dry_run: bool,
     decode: bool,
+    write_meta: bool,
+    texture_transforms: &TextureTransforms,
+    similar_textures: Option<u32>,
     overwrite: bool,
     skip_existing: bool,
     manifest: Option<PathBuf>,
@@ -564,6 +585,7 @@ fn export_bundle_command(
                     failed: 0,
                     filtered,
                     entries: Vec::new(),
+                    similar_textures: None,
                 },
             )?;
         }
@@ -669,6 +691,7 @@ fn export_bundle_command(
                     failed: 0,
                     filtered,
                     entries: manifest_entries,
+                    similar_textures: None,
                 },
             )?;
         }
@@ -692,6 +715,7 @@ fn export_bundle_command(
     if export_jobs.is_empty() {
         let mut outcomes = pre_outcomes;
         outcomes.sort_by_key(|o| o.order);
+        let similar = similar_textures_pass(&outcomes, similar_textures, show_warnings)?;
 
         if let Some(path) = manifest.as_ref() {
             let mut entries: Vec<ExportManifestEntry> =
@@ -721,6 +745,7 @@ fn export_bundle_command(
                     failed: 0,
                     filtered,
                     entries,
+                    similar_textures: similar.clone(),
                 },
             )?;
         }
@@ -732,6 +757,9 @@ fn export_bundle_command(
             "Exported 0 entries, skipped {} (unresolved), skipped {} (existing), filtered {}, resumed {} [jobs=0]",
             skipped, resumed, filtered, resumed
         );
+        if let Some(report) = &similar {
+            print_similar_textures(report);
+        }
         return Ok(());
     }
 
@@ -787,6 +815,8 @@ fn export_bundle_command(
                         &job.key,
                         job.order,
                         decode,
+                        write_meta,
+                        texture_transforms,
                         overwrite,
                         skip_existing,
                     ) {
@@ -868,6 +898,7 @@ fn export_bundle_command(
     };
     outcomes.extend(pre_outcomes);
     outcomes.sort_by_key(|o| o.order);
+    let similar = similar_textures_pass(&outcomes, similar_textures, show_warnings)?;
 
     if let Some(path) = manifest.as_ref() {
         let mut entries: Vec<ExportManifestEntry> =
@@ -898,6 +929,7 @@ fn export_bundle_command(
                 failed: failed_count.load(Ordering::Relaxed),
                 filtered,
                 entries,
+                similar_textures: similar.clone(),
             },
         )?;
     }
@@ -910,6 +942,10 @@ fn export_bundle_command(
         println!("{}", o.message);
     }
 
+    if let Some(report) = &similar {
+        print_similar_textures(report);
+    }
+
     let failed = failed_count.load(Ordering::Relaxed);
     if continue_on_error && failed > 0 {
         println!(
@@ -950,6 +986,8 @@ fn export_one_entry(
     key: &BinaryObjectKey,
     order: usize,
     decode: bool,
+    write_meta: bool,
+    texture_transforms: &TextureTransforms,
     overwrite: bool,
     skip_existing: bool,
 ) -> Result<ExportOutcome> {
@@ -962,6 +1000,8 @@ fn export_one_entry(
     };
 
     if decode {
+        #[cfg(not(feature = "decode"))]
+        let _ = (write_meta, texture_transforms);
         #[cfg(feature = "decode")]
         match try_decode_export_best_effort(
             env,
@@ -970,10 +1010,14 @@ fn export_one_entry(
             asset_path,
             key,
             &obj,
+            texture_transforms,
             overwrite,
             skip_existing,
         ) {
             DecodeAttempt::Exported { dest, output_bytes } => {
+                if write_meta {
+                    write_meta_file(env, key, &obj, &dest)?;
+                }
                 return Ok(ExportOutcome {
                     order,
                     message: format!(
@@ -1114,6 +1158,7 @@ fn try_decode_export_best_effort(
     asset_path: &str,
     key: &BinaryObjectKey,
     obj: &UnityObject,
+    texture_transforms: &TextureTransforms,
     overwrite: bool,
     skip_existing: bool,
 ) -> DecodeAttempt {
@@ -1242,7 +1287,7 @@ fn try_decode_export_best_effort(
                         std::fs::create_dir_all(parent)?;
                     }
                     let audio_processor = AudioProcessor::new(unity_version);
-                    audio_processor.process_and_export(obj, &dest)?;
+                    audio_processor.process_and_export(obj, None, &dest)?;
                     Ok(DecodeAttempt::Exported {
                         output_bytes: file_len(&dest),
                         dest,
@@ -1263,24 +1308,7 @@ fn try_decode_export_best_effort(
             }
 
             let texture_processor = TextureProcessor::new(unity_version);
-            let mut texture = texture_processor.convert_object(obj)?;
-            if texture.image_data.is_empty() && texture.is_streamed() {
-                if let Ok(bytes) = env.read_stream_data_source(
-                    &key.source,
-                    key.source_kind,
-                    &texture.stream_info.path,
-                    texture.stream_info.offset,
-                    texture.stream_info.size,
-                ) {
-                    if !bytes.is_empty() {
-                        texture.data_size = bytes.len() as i32;
-                        texture.image_data = bytes;
-                    }
-                }
-            }
-
-            let image = texture_processor.decode_texture(&texture)?;
-            TextureExporter::export_auto(&image, &dest)?;
+            export_texture(env, key, &texture_processor, obj, &dest, texture_transforms)?;
             Ok(DecodeAttempt::Exported {
                 output_bytes: file_len(&dest),
                 dest,
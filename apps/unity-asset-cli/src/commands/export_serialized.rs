@@ -1,4 +1,7 @@
-use crate::shared::{AppContext, build_environment, load_environment_input, resolve_loaded_source};
+use crate::shared::{
+    AppContext, TextureTransforms, build_environment, load_environment_input,
+    print_similar_textures, resolve_loaded_source, similar_textures_report,
+};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
@@ -10,6 +13,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use unity_asset::environment::{BinaryObjectKey, BinarySource, BinarySourceKind, Environment};
 use unity_asset_binary::asset::SerializedFile;
 
+#[cfg(feature = "decode")]
+use crate::shared::{export_texture, sprite_texture_pptr, write_meta_file};
 #[cfg(feature = "decode")]
 use unity_asset::UnityValue;
 #[cfg(feature = "decode")]
@@ -20,7 +25,7 @@ use unity_asset_binary::{asset::class_ids, unity_version::UnityVersion};
 use unity_asset_decode::{
     audio::{AudioClipConverter, AudioProcessor},
     sprite::SpriteProcessor,
-    texture::{TextureExporter, TextureProcessor},
+    texture::TextureProcessor,
 };
 
 fn sanitize_asset_path(asset_path: &str) -> PathBuf {
@@ -99,6 +104,7 @@ struct ExportJob {
     key: BinaryObjectKey,
     dest_base: PathBuf,
     decode: bool,
+    write_meta: bool,
     overwrite: bool,
     effective_skip_existing: bool,
 }
@@ -152,6 +158,9 @@ struct ExportManifest {
     resumed: usize,
     failed: usize,
     entries: Vec<ExportManifestEntry>,
+    /// `--similar-textures` report (see `shared::similar_textures_report`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    similar_textures: Option<serde_json::Value>,
 }
 
 fn now_unix_ms() -> u128 {
@@ -181,6 +190,30 @@ fn read_export_manifest(path: &Path) -> Result<ExportManifest> {
     Ok(manifest)
 }
 
+/// `--similar-textures` over the Texture2D images this run exported or found in place.
+fn similar_textures_pass(
+    entries: &[ExportManifestEntry],
+    max_distance: Option<u32>,
+    show_warnings: bool,
+) -> Result<Option<serde_json::Value>> {
+    let Some(max_distance) = max_distance else {
+        return Ok(None);
+    };
+    let exports = entries
+        .iter()
+        .filter(|e| {
+            e.class_id == Some(unity_asset_binary::asset::class_ids::TEXTURE_2D)
+                && matches!(
+                    e.status,
+                    ExportStatus::ExportedDecoded
+                        | ExportStatus::SkippedExisting
+                        | ExportStatus::Resumed
+                )
+        })
+        .filter_map(|e| Some((e.key.as_str(), Path::new(e.output_path.as_deref()?))));
+    similar_textures_report(exports, max_distance, show_warnings).map(Some)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn run(
     input: PathBuf,
@@ -192,6 +225,9 @@ pub(crate) fn run(
     limit: Option<usize>,
     dry_run: bool,
     decode: bool,
+    write_meta: bool,
+    texture_transform: Vec<String>,
+    similar_textures: Option<u32>,
     overwrite: bool,
     skip_existing: bool,
     manifest: Option<PathBuf>,
@@ -207,6 +243,7 @@ pub(crate) fn run(
             "--decode requires compiling `unity-asset-cli` with feature `decode` (build with default features, or `--features decode`)."
         );
     }
+    let texture_transforms = Arc::new(TextureTransforms::parse(&texture_transform)?);
 
     let mut env = build_environment(ctx.strict, ctx.show_warnings, ctx.typetree_registries())?;
     load_environment_input(&mut env, &input)?;
@@ -270,6 +307,7 @@ pub(crate) fn run(
                 key,
                 dest_base,
                 decode,
+                write_meta,
                 overwrite,
                 effective_skip_existing: skip_existing,
             });
@@ -405,6 +443,7 @@ pub(crate) fn run(
                     key,
                     dest_base,
                     decode,
+                    write_meta,
                     overwrite,
                     effective_skip_existing,
                 });
@@ -436,6 +475,7 @@ pub(crate) fn run(
                 resumed,
                 failed: 0,
                 entries: Vec::new(),
+                similar_textures: None,
             };
             write_export_manifest(path.as_path(), &out)?;
         }
@@ -491,6 +531,7 @@ pub(crate) fn run(
                 resumed,
                 failed: 0,
                 entries,
+                similar_textures: None,
             };
             write_export_manifest(path.as_path(), &out)?;
         }
@@ -521,6 +562,7 @@ pub(crate) fn run(
 
     for _ in 0..threads {
         let env = Arc::clone(&env);
+        let texture_transforms = Arc::clone(&texture_transforms);
         let exported = Arc::clone(&exported);
         let skipped_existing_count = Arc::clone(&skipped_existing_count);
         let failed = Arc::clone(&failed);
@@ -540,7 +582,7 @@ pub(crate) fn run(
                     break;
                 };
 
-                match export_one_inner(&env, &job) {
+                match export_one_inner(&env, &job, &texture_transforms) {
                     Ok((dest, true, status, bytes, class_id, class_name, obj_name)) => {
                         println!("✓ {} -> {:?}", job.label, dest);
                         exported.fetch_add(1, Ordering::Relaxed);
@@ -615,9 +657,14 @@ pub(crate) fn run(
         threads
     );
 
+    let mut entries = manifest_entries.lock().unwrap().clone();
+    entries.sort_by_key(|e| e.order);
+    let similar = similar_textures_pass(&entries, similar_textures, ctx.show_warnings)?;
+    if let Some(report) = &similar {
+        print_similar_textures(report);
+    }
+
     if let Some(path) = manifest.as_ref() {
-        let mut entries = manifest_entries.lock().unwrap().clone();
-        entries.sort_by_key(|e| e.order);
         let out = ExportManifest {
             schema: 1,
             created_unix_ms: now_unix_ms(),
@@ -639,6 +686,7 @@ pub(crate) fn run(
             resumed,
             failed: failed.load(Ordering::Relaxed),
             entries,
+            similar_textures: similar,
         };
         write_export_manifest(path.as_path(), &out)?;
     }
@@ -656,7 +704,11 @@ type ExportOneInnerResult = (
     Option<String>,
 );
 
-fn export_one_inner(env: &Environment, job: &ExportJob) -> Result<ExportOneInnerResult> {
+fn export_one_inner(
+    env: &Environment,
+    job: &ExportJob,
+    texture_transforms: &TextureTransforms,
+) -> Result<ExportOneInnerResult> {
     let obj = env.read_binary_object_key(&job.key)?;
     let class_id = obj.info.type_id;
     let class_name = best_effort_class_name(
@@ -668,8 +720,15 @@ fn export_one_inner(env: &Environment, job: &ExportJob) -> Result<ExportOneInner
     let obj_name = obj.name();
 
     if job.decode {
+        #[cfg(not(feature = "decode"))]
+        let _ = (job.write_meta, texture_transforms);
         #[cfg(feature = "decode")]
-        if let Some((dest, exported, bytes)) = try_decode_export_best_effort(env, job, &obj)? {
+        if let Some((dest, exported, bytes)) =
+            try_decode_export_best_effort(env, job, &obj, texture_transforms)?
+        {
+            if exported && job.write_meta {
+                write_meta_file(env, &job.key, &obj, &dest)?;
+            }
             let status = if exported {
                 ExportStatus::ExportedDecoded
             } else {
@@ -749,30 +808,12 @@ fn text_asset_bytes(obj: &UnityObject) -> Vec<u8> {
     Vec::new()
 }
 
-#[cfg(feature = "decode")]
-fn sprite_texture_pptr(obj: &UnityObject) -> Option<(i32, i64)> {
-    let UnityValue::Object(rd) = obj.get("m_RD")? else {
-        return None;
-    };
-    let UnityValue::Object(texture) = rd.get("texture")? else {
-        return None;
-    };
-    let file_id = match texture.get("m_FileID")? {
-        UnityValue::Integer(v) => *v as i32,
-        _ => return None,
-    };
-    let path_id = match texture.get("m_PathID")? {
-        UnityValue::Integer(v) => *v,
-        _ => return None,
-    };
-    Some((file_id, path_id))
-}
-
 #[cfg(feature = "decode")]
 fn try_decode_export_best_effort(
     env: &Environment,
     job: &ExportJob,
     obj: &UnityObject,
+    texture_transforms: &TextureTransforms,
 ) -> Result<Option<(PathBuf, bool, Option<u64>)>> {
     let unity_version = env
         .binary_assets()
@@ -835,7 +876,7 @@ fn try_decode_export_best_effort(
                         std::fs::create_dir_all(parent)?;
                     }
                     let audio_processor = AudioProcessor::new(unity_version);
-                    audio_processor.process_and_export(obj, &dest)?;
+                    audio_processor.process_and_export(obj, None, &dest)?;
                     let written_len = std::fs::metadata(&dest).map(|m| m.len()).ok();
                     return Ok(Some((dest, true, written_len)));
                 }
@@ -853,24 +894,14 @@ fn try_decode_export_best_effort(
             }
 
             let texture_processor = TextureProcessor::new(unity_version);
-            let mut texture = texture_processor.convert_object(obj)?;
-            if texture.image_data.is_empty() && texture.is_streamed() {
-                if let Ok(bytes) = env.read_stream_data_source(
-                    &job.key.source,
-                    job.key.source_kind,
-                    &texture.stream_info.path,
-                    texture.stream_info.offset,
-                    texture.stream_info.size,
-                ) {
-                    if !bytes.is_empty() {
-                        texture.data_size = bytes.len() as i32;
-                        texture.image_data = bytes;
-                    }
-                }
-            }
-
-            let image = texture_processor.decode_texture(&texture)?;
-            TextureExporter::export_auto(&image, &dest)?;
+            export_texture(
+                env,
+                &job.key,
+                &texture_processor,
+                obj,
+                &dest,
+                texture_transforms,
+            )?;
             let written_len = std::fs::metadata(&dest).map(|m| m.len()).ok();
             return Ok(Some((dest, true, written_len)));
         }
@@ -2,6 +2,12 @@ use crate::shared::AppContext;
 use anyhow::Result;
 use std::path::PathBuf;
 use unity_asset::UnityDocument;
+use unity_asset_binary::asset::DumpFilter;
+use unity_asset_binary::file::{UnityFile, load_unity_file};
+#[cfg(feature = "decode")]
+use unity_asset_binary::{asset::class_ids, unity_version::UnityVersion};
+#[cfg(feature = "decode")]
+use unity_asset_decode::{mesh::MeshProcessor, texture::TextureExporter};
 
 pub(crate) fn run(
     input: PathBuf,
@@ -62,3 +68,119 @@ pub(crate) fn run(
 
     Ok(())
 }
+
+pub(crate) fn run_raw(
+    input: PathBuf,
+    output: PathBuf,
+    filter: &DumpFilter,
+    _ctx: &AppContext,
+) -> Result<()> {
+    println!("Dumping raw objects from: {:?}", input);
+    println!("Output to: {:?}", output);
+
+    let report = match load_unity_file(&input)? {
+        UnityFile::AssetBundle(bundle) => bundle.dump_raw_objects(&output, filter)?,
+        UnityFile::SerializedFile(file) => file.dump_raw_objects(&output, filter)?,
+        UnityFile::WebFile(_) => {
+            anyhow::bail!("Raw dump is not supported for WebFile containers");
+        }
+    };
+
+    println!(
+        "✓ Wrote {} objects ({} bytes)",
+        report.written.len(),
+        report.bytes_written
+    );
+    if report.skipped_oversize > 0 {
+        println!(
+            "  Skipped (over --max-object-size): {}",
+            report.skipped_oversize
+        );
+    }
+    if report.skipped_total_cap > 0 {
+        println!(
+            "  Skipped (--max-total-size reached): {}",
+            report.skipped_total_cap
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "decode")]
+pub(crate) fn run_uv_layout(
+    input: PathBuf,
+    output: PathBuf,
+    uv_channel: usize,
+    size: u32,
+    _ctx: &AppContext,
+) -> Result<()> {
+    println!("Rendering UV{} layouts from: {:?}", uv_channel, input);
+    println!("Output to: {:?}", output);
+    std::fs::create_dir_all(&output)?;
+
+    // Streamed vertex data lives in a `.resS` node of the bundle, or next to a bare `.assets`.
+    let (bundle, files) = match load_unity_file(&input)? {
+        UnityFile::AssetBundle(mut bundle) => {
+            let files = std::mem::take(&mut bundle.assets);
+            (Some(bundle), files)
+        }
+        UnityFile::SerializedFile(file) => (None, vec![file]),
+        UnityFile::WebFile(_) => {
+            anyhow::bail!("UV layouts are not supported for WebFile containers");
+        }
+    };
+    let asset_path = bundle.is_none().then_some(input.as_path());
+
+    let (mut written, mut failed) = (0usize, 0usize);
+    for file in &files {
+        let version = UnityVersion::parse_version(&file.unity_version).unwrap_or_default();
+        let processor = MeshProcessor::new(version);
+        for handle in file
+            .object_handles()
+            .filter(|h| h.class_id() == class_ids::MESH)
+        {
+            let rendered = handle.read().map_err(anyhow::Error::from).and_then(|obj| {
+                let mesh = processor
+                    .parse_mesh_with_resources(&obj, bundle.as_ref(), asset_path)?
+                    .mesh;
+                let image = processor.render_uv_layout(&mesh, uv_channel, size)?;
+                let name: String = mesh
+                    .name
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || matches!(c, '_' | '-') {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect();
+                let path = output.join(format!(
+                    "{}_{}_uv{}.png",
+                    name,
+                    handle.path_id(),
+                    uv_channel
+                ));
+                TextureExporter::export_png(&image, &path)?;
+                Ok(path)
+            });
+            match rendered {
+                Ok(path) => {
+                    println!("  Rendered: {}", path.display());
+                    written += 1;
+                }
+                Err(e) => {
+                    println!("  ⚠ Mesh {}: {}", handle.path_id(), e);
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    println!("✓ Wrote {} UV layouts", written);
+    if failed > 0 {
+        println!("  Failed: {}", failed);
+    }
+    Ok(())
+}
@@ -11,6 +11,7 @@ use unity_asset::UnityValue;
 use unity_asset::environment::{BinaryObjectKey, BinarySource, Environment};
 use unity_asset_binary::bundle::AssetBundle;
 use unity_asset_binary::object::UnityObject;
+use unity_asset_binary::string_policy::{StringPolicy, name_matches};
 use unity_asset_binary::typetree::{TypeTreeParseMode, TypeTreeParseOptions, TypeTreeRegistry};
 
 #[allow(clippy::too_many_arguments)]
@@ -131,9 +132,8 @@ fn find_object_env_fallback(
                         }
                     }
                     if !name_lc.is_empty() {
-                        let matches = match env.peek_binary_object_name(key) {
-                            Ok(Some(found)) => found.to_ascii_lowercase().contains(&name_lc),
-                            Ok(None) => false,
+                        let matches = match env.binary_object_name_matches(key, &name_lc) {
+                            Ok(matches) => matches,
                             Err(e) => {
                                 cli_warn(
                                     show_warnings,
@@ -177,9 +177,8 @@ fn find_object_env_fallback(
                     }
                 }
                 if !name_lc.is_empty() {
-                    let matches = match env.peek_binary_object_name(key) {
-                        Ok(Some(found)) => found.to_ascii_lowercase().contains(&name_lc),
-                        Ok(None) => false,
+                    let matches = match env.binary_object_name_matches(key, &name_lc) {
+                        Ok(matches) => matches,
                         Err(e) => {
                             cli_warn(
                                 show_warnings,
@@ -220,13 +219,13 @@ fn find_object_fast(
 ) -> Result<bool> {
     let registry = load_typetree_registry(typetree_registries)?;
     let typetree_options = if strict {
-        TypeTreeParseOptions {
-            mode: TypeTreeParseMode::Strict,
-        }
+        TypeTreeParseOptions::default()
+            .with_mode(TypeTreeParseMode::Strict)
+            .with_strings(StringPolicy::Utf8Strict)
     } else {
-        TypeTreeParseOptions {
-            mode: TypeTreeParseMode::Lenient,
-        }
+        TypeTreeParseOptions::default()
+            .with_mode(TypeTreeParseMode::Lenient)
+            .with_strings(StringPolicy::Utf8Lossy)
     };
 
     let candidate_paths = fast_path::collect_candidate_paths(input)?;
@@ -265,7 +264,7 @@ fn find_object_fast(
             &asset_nodes,
             &asset_names,
             registry.as_ref(),
-            typetree_options,
+            &typetree_options,
             show_warnings,
         );
 
@@ -336,9 +335,14 @@ fn find_object_fast(
                             &mut file_cache,
                             key,
                             registry.as_ref(),
-                            typetree_options,
+                            &typetree_options,
                         ) {
-                            Ok(Some(found)) => found.to_ascii_lowercase().contains(&name_lc),
+                            Ok(Some((found, raw))) => name_matches(
+                                &name_lc,
+                                &found,
+                                raw.as_deref(),
+                                &typetree_options.strings,
+                            ),
                             Ok(None) => false,
                             Err(e) => {
                                 cli_warn(
@@ -395,9 +399,14 @@ fn find_object_fast(
                         &mut file_cache,
                         key,
                         registry.as_ref(),
-                        typetree_options,
+                        &typetree_options,
                     ) {
-                        Ok(Some(found)) => found.to_ascii_lowercase().contains(&name_lc),
+                        Ok(Some((found, raw))) => name_matches(
+                            &name_lc,
+                            &found,
+                            raw.as_deref(),
+                            &typetree_options.strings,
+                        ),
                         Ok(None) => false,
                         Err(e) => {
                             cli_warn(
@@ -429,7 +438,7 @@ fn extract_bundle_container_entries_fast(
     asset_nodes: &[unity_asset_binary::bundle::DirectoryNode],
     asset_names: &[String],
     registry: Option<&Arc<dyn TypeTreeRegistry>>,
-    typetree_options: TypeTreeParseOptions,
+    typetree_options: &TypeTreeParseOptions,
     show_warnings: bool,
 ) -> Result<Vec<unity_asset::environment::BundleContainerEntry>> {
     for (asset_index, node) in asset_nodes.iter().enumerate() {
@@ -449,7 +458,7 @@ fn extract_bundle_container_entries_fast(
             }
 
             if file.enable_type_tree {
-                match object.read_with_options(typetree_options) {
+                match object.read_with_options(typetree_options.clone()) {
                     Ok(obj) => {
                         if show_warnings {
                             for w in obj.typetree_warnings() {
@@ -716,8 +725,8 @@ fn peek_object_name_fast(
     cache: &mut [Option<unity_asset_binary::asset::SerializedFile>],
     key: &BinaryObjectKey,
     registry: Option<&Arc<dyn TypeTreeRegistry>>,
-    options: TypeTreeParseOptions,
-) -> Result<Option<String>> {
+    options: &TypeTreeParseOptions,
+) -> Result<Option<(String, Option<Vec<u8>>)>> {
     if key.source_kind != unity_asset::environment::BinarySourceKind::AssetBundle {
         return Ok(None);
     }
@@ -755,7 +764,7 @@ fn peek_object_name_fast(
         )
     })?;
     handle
-        .peek_name_with_options(options)
+        .peek_name_with_raw(options.clone())
         .map_err(|e| anyhow::anyhow!(e))
 }
 
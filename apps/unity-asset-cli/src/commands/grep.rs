@@ -0,0 +1,58 @@
+use crate::shared::{AppContext, build_environment, load_environment_input};
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::path::PathBuf;
+use unity_asset::search::{GrepOptions, MatchLocation, grep};
+
+pub(crate) fn run(
+    input: PathBuf,
+    pattern: String,
+    class: Option<String>,
+    context: usize,
+    max_value_len: usize,
+    ctx: &AppContext,
+) -> Result<()> {
+    let regex = Regex::new(&pattern).with_context(|| format!("Invalid pattern: {}", pattern))?;
+    let mut env = build_environment(ctx.strict, ctx.show_warnings, ctx.typetree_registries())?;
+    load_environment_input(&mut env, &input)?;
+
+    let options = GrepOptions {
+        class_name: class,
+        context_lines: context,
+        max_value_len,
+    };
+    let matches = grep(&env, &regex, &options);
+    for (i, m) in matches.iter().enumerate() {
+        if context > 0 && i > 0 {
+            println!("--");
+        }
+        let location = match &m.location {
+            MatchLocation::Yaml { path, anchor } => {
+                format!("{} &{}", path.to_string_lossy(), anchor)
+            }
+            MatchLocation::Binary {
+                source,
+                path_id,
+                container,
+                ..
+            } => match container {
+                Some(container) => format!("{} [{}] path_id={}", source, container, path_id),
+                None => format!("{} path_id={}", source, path_id),
+            },
+        };
+        for line in &m.before {
+            println!("    {}", line);
+        }
+        println!(
+            "{} {} {}:{}: {}",
+            location, m.class_name, m.field_path, m.line_number, m.line
+        );
+        for line in &m.after {
+            println!("    {}", line);
+        }
+    }
+    if matches.is_empty() {
+        eprintln!("No matches.");
+    }
+    Ok(())
+}
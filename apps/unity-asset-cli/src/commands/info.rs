@@ -0,0 +1,223 @@
+use crate::fast_path;
+use crate::shared::AppContext;
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use unity_asset_binary::asset::SerializedFileParser;
+use unity_asset_binary::build_settings::{BuildSettings, find_build_settings};
+use unity_asset_binary::bundle::{
+    BundleLoadOptions, BundleStatistics, ContentCounts, ContentSummary,
+};
+
+#[derive(Debug, Serialize)]
+struct InfoRecord<'a> {
+    source: String,
+    summary: &'a ContentSummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    statistics: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct BuildSettingsRecord<'a> {
+    source: String,
+    build_settings: &'a BuildSettings,
+}
+
+/// Player files that hold BuildSettings (`mainData` before Unity 5.3).
+const BUILD_SETTINGS_FILE_NAMES: [&str; 2] = ["globalgamemanagers", "mainData"];
+
+pub(crate) fn run(input: PathBuf, json: bool, blocks: bool, _ctx: &AppContext) -> Result<()> {
+    let candidate_paths = fast_path::collect_candidate_paths(&input)?;
+    let mut found_any = false;
+
+    for path in candidate_paths {
+        if is_build_settings_file(&path) {
+            let Some(settings) = load_build_settings(&path) else {
+                continue;
+            };
+            found_any = true;
+            if json {
+                let record = BuildSettingsRecord {
+                    source: path.to_string_lossy().to_string(),
+                    build_settings: &settings,
+                };
+                println!("{}", serde_json::to_string(&record)?);
+            } else {
+                println!("Build settings: {}", path.to_string_lossy());
+                print_build_settings(&settings);
+            }
+            continue;
+        }
+
+        if !fast_path::is_assetbundle_path(&path) {
+            continue;
+        }
+
+        let bundle = match fast_path::load_bundle_for_list(&path, BundleLoadOptions::default()) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        found_any = true;
+
+        let summary = bundle.content_summary();
+        let statistics = blocks.then(|| bundle.statistics());
+        if json {
+            let record = InfoRecord {
+                source: path.to_string_lossy().to_string(),
+                summary: &summary,
+                statistics: statistics.as_ref().map(BundleStatistics::to_json),
+            };
+            println!("{}", serde_json::to_string(&record)?);
+            continue;
+        }
+
+        println!("Bundle: {}", path.to_string_lossy());
+        print_table(&summary);
+        if let Some(stats) = &statistics {
+            print_blocks(stats);
+        }
+    }
+
+    if !found_any && !json {
+        println!(
+            "⚠ No AssetBundles or globalgamemanagers found in {:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+fn is_build_settings_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|name| {
+            BUILD_SETTINGS_FILE_NAMES
+                .iter()
+                .any(|n| n.eq_ignore_ascii_case(name))
+        })
+        && fast_path::is_serialized_file_path(path)
+}
+
+fn load_build_settings(path: &Path) -> Option<BuildSettings> {
+    let asset = SerializedFileParser::from_bytes(std::fs::read(path).ok()?).ok()?;
+    find_build_settings(&asset)
+}
+
+fn print_build_settings(settings: &BuildSettings) {
+    println!("  version: {}", settings.version);
+    println!("  scenes: {}", settings.scenes.len());
+    for (index, scene) in settings.scenes.iter().enumerate() {
+        println!("    level{:<4} {}", index, scene);
+    }
+    if !settings.preloaded_plugins.is_empty() {
+        println!(
+            "  preloaded plugins: {}",
+            settings.preloaded_plugins.join(", ")
+        );
+    }
+    let enabled: Vec<&str> = settings
+        .flags
+        .iter()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !enabled.is_empty() {
+        println!("  flags: {}", enabled.join(", "));
+    }
+}
+
+fn print_table(summary: &ContentSummary) {
+    println!(
+        "  {:<40} {:>8} {:>8} {:>12} {:>6} {:>10} {:>6} {:>10} {:>6} {:>6}",
+        "file",
+        "objects",
+        "textures",
+        "pixels",
+        "audio",
+        "seconds",
+        "meshes",
+        "vertices",
+        "mono",
+        "other"
+    );
+    for file in &summary.files {
+        print_row(&file.name, &file.counts);
+    }
+    if summary.files.len() > 1 {
+        print_row("(total)", &summary.totals);
+    }
+
+    let formats = &summary.totals.textures.formats;
+    if !formats.is_empty() {
+        let list: Vec<String> = formats
+            .iter()
+            .map(|(format, count)| format!("{}x{}", format, count))
+            .collect();
+        println!("  texture formats (id x count): {}", list.join(", "));
+    }
+
+    let unmeasured = summary.totals.textures.unmeasured
+        + summary.totals.audio_clips.unmeasured
+        + summary.totals.meshes.unmeasured;
+    if unmeasured > 0 {
+        println!(
+            "  note: {} objects had no TypeTree fields to measure (counted, sizes omitted)",
+            unmeasured
+        );
+    }
+}
+
+fn print_row(name: &str, counts: &ContentCounts) {
+    println!(
+        "  {:<40} {:>8} {:>8} {:>12} {:>6} {:>10.2} {:>6} {:>10} {:>6} {:>6}",
+        name,
+        counts.total_objects,
+        counts.textures.count,
+        counts.textures.total_pixels,
+        counts.audio_clips.count,
+        counts.audio_clips.total_seconds,
+        counts.meshes.count,
+        counts.meshes.total_vertices,
+        counts.mono_behaviours,
+        counts.other
+    );
+}
+
+fn print_blocks(stats: &BundleStatistics) {
+    println!(
+        "  {:>6} {:<10} {:>12} {:>12} {:>12} {:>7}",
+        "block", "codec", "offset", "compressed", "uncompressed", "ratio"
+    );
+    for block in &stats.blocks {
+        println!(
+            "  {:>6} {:<10} {:>12} {:>12} {:>12} {:>7.3}{}",
+            block.index,
+            block.compression,
+            block.uncompressed_offset,
+            block.compressed_size,
+            block.uncompressed_size,
+            block.compression_ratio,
+            if block.stored_uncompressed {
+                "  (stored)"
+            } else {
+                ""
+            }
+        );
+    }
+    println!(
+        "  {:>6} {:<10} {:>12} {:>12} {:>12} {:>7.3}",
+        "total", "", "", stats.compressed_size, stats.uncompressed_size, stats.compression_ratio
+    );
+
+    for file in &stats.files {
+        println!(
+            "  {:<40} {:>12} uncompressed, ~{} compressed",
+            file.name, file.uncompressed_size, file.estimated_compressed_size
+        );
+    }
+    println!(
+        "  stored-uncompressed blocks: {}, padding: {} bytes, wasted: {} bytes",
+        stats.stored_uncompressed_blocks, stats.padding_bytes, stats.wasted_bytes
+    );
+}
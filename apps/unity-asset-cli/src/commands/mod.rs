@@ -8,14 +8,18 @@ mod export_bundle;
 mod export_serialized;
 mod extract;
 mod find_object;
+mod grep;
+mod info;
 mod inspect_object;
 mod list_bundle;
 mod list_objects;
 mod parse_yaml;
 mod project_graph;
 mod scan_pptr;
+mod show;
 mod stats;
 mod stats_pathid;
+mod validate;
 
 pub(crate) fn run(command: Commands, ctx: &AppContext) -> Result<()> {
     match command {
@@ -28,7 +32,35 @@ pub(crate) fn run(command: Commands, ctx: &AppContext) -> Result<()> {
             input,
             output,
             types,
-        } => extract::run(input, output, types, ctx),
+            raw,
+            max_object_size,
+            max_total_size,
+            uv_layout,
+            uv_channel,
+            uv_size,
+        } => {
+            if uv_layout {
+                #[cfg(feature = "decode")]
+                return extract::run_uv_layout(input, output, uv_channel, uv_size, ctx);
+                #[cfg(not(feature = "decode"))]
+                {
+                    let _ = (uv_channel, uv_size);
+                    anyhow::bail!(
+                        "--uv-layout requires compiling `unity-asset-cli` with feature `decode` (build with default features, or `--features decode`)."
+                    );
+                }
+            }
+            if raw {
+                let filter = unity_asset_binary::asset::DumpFilter {
+                    classes: types,
+                    max_object_size,
+                    max_total_size,
+                };
+                extract::run_raw(input, output, &filter, ctx)
+            } else {
+                extract::run(input, output, types, ctx)
+            }
+        }
         Commands::ExportBundle {
             input,
             output,
@@ -38,6 +70,9 @@ pub(crate) fn run(command: Commands, ctx: &AppContext) -> Result<()> {
             class_name,
             dry_run,
             decode,
+            write_meta,
+            texture_transform,
+            similar_textures,
             overwrite,
             skip_existing,
             manifest,
@@ -54,6 +89,9 @@ pub(crate) fn run(command: Commands, ctx: &AppContext) -> Result<()> {
             class_name,
             dry_run,
             decode,
+            write_meta,
+            texture_transform,
+            similar_textures,
             overwrite,
             skip_existing,
             manifest,
@@ -73,6 +111,9 @@ pub(crate) fn run(command: Commands, ctx: &AppContext) -> Result<()> {
             limit,
             dry_run,
             decode,
+            write_meta,
+            texture_transform,
+            similar_textures,
             overwrite,
             skip_existing,
             manifest,
@@ -90,6 +131,9 @@ pub(crate) fn run(command: Commands, ctx: &AppContext) -> Result<()> {
             limit,
             dry_run,
             decode,
+            write_meta,
+            texture_transform,
+            similar_textures,
             overwrite,
             skip_existing,
             manifest,
@@ -104,6 +148,11 @@ pub(crate) fn run(command: Commands, ctx: &AppContext) -> Result<()> {
             filter,
             verbose,
         } => list_bundle::run(input, filter, verbose, ctx),
+        Commands::Info {
+            input,
+            json,
+            blocks,
+        } => info::run(input, json, blocks, ctx),
         Commands::ListObjects {
             input,
             kind,
@@ -257,5 +306,26 @@ pub(crate) fn run(command: Commands, ctx: &AppContext) -> Result<()> {
             follow_symlinks,
             ctx,
         ),
+        Commands::Grep {
+            input,
+            pattern,
+            class,
+            context,
+            max_value_len,
+        } => grep::run(input, pattern, class, context, max_value_len, ctx),
+        Commands::Show {
+            input,
+            path_id,
+            anchor,
+            format,
+            raw_hex,
+            max_bytes,
+        } => show::run(input, path_id, anchor, format, raw_hex, max_bytes, ctx),
+        Commands::Validate {
+            input,
+            deep,
+            json,
+            fail_on,
+        } => validate::run(input, deep, json, fail_on, ctx),
     }
 }
@@ -2,6 +2,7 @@ use crate::shared::{AppContext, cli_warn};
 use anyhow::Result;
 use std::path::PathBuf;
 use unity_asset::UnityDocument;
+use unity_asset::interchange::{InterchangeFormat, RecordWriter};
 
 pub(crate) fn run(
     input: PathBuf,
@@ -9,9 +10,13 @@ pub(crate) fn run(
     preserve_types: bool,
     ctx: &AppContext,
 ) -> Result<()> {
-    println!("Parsing YAML file: {:?}", input);
-    println!("Output format: {}", format);
-    println!("Preserve types: {}", preserve_types);
+    // Binary formats write one record per document to stdout, with no other output.
+    let interchange = InterchangeFormat::from_name(&format);
+    if interchange.is_none() {
+        println!("Parsing YAML file: {:?}", input);
+        println!("Output format: {}", format);
+        println!("Preserve types: {}", preserve_types);
+    }
 
     let (doc, warnings) =
         unity_asset::YamlDocument::load_yaml_with_warnings(&input, preserve_types)?;
@@ -21,6 +26,15 @@ pub(crate) fn run(
         }
     }
 
+    if let Some(interchange) = interchange {
+        let mut writer = RecordWriter::new(std::io::stdout().lock(), interchange);
+        for entry in doc.entries() {
+            writer.write(entry)?;
+        }
+        let _stdout = writer.into_inner()?;
+        return Ok(());
+    }
+
     println!("✓ Successfully loaded YAML document");
     println!("  Entries: {}", doc.entries().len());
 
@@ -54,7 +68,7 @@ pub(crate) fn run(
         }
         _ => {
             println!(
-                "Unknown format: {}. Supported formats: summary, detailed, json",
+                "Unknown format: {}. Supported formats: summary, detailed, json, msgpack, cbor",
                 format
             );
         }
@@ -37,6 +37,7 @@ struct ProjectGraphJsonGraph {
     internal_edges: usize,
     external_edges: usize,
     external_edges_resolved: usize,
+    external_edges_builtin: usize,
     roots_internal: usize,
     leaves_internal: usize,
     cycles_internal: usize,
@@ -109,6 +110,12 @@ pub(crate) fn run(
                     doc_index: Some(doc_index),
                     error,
                 },
+                EnvironmentWarning::DuplicateBundleEntry { ref source, .. } => WarningLine {
+                    kind: "duplicate_bundle_entry",
+                    path: source.describe(),
+                    doc_index: None,
+                    error: w.to_string(),
+                },
             };
             writeln!(f, "{}", serde_json::to_string(&line)?)?;
         }
@@ -150,11 +157,11 @@ pub(crate) fn run(
         return Ok(());
     }
 
-    let graph = env.build_object_graph(ObjectGraphBuildOptions {
-        include_yaml: yaml,
-        include_binary: true,
-        ..ObjectGraphBuildOptions::default()
-    });
+    let graph = env.build_object_graph(
+        ObjectGraphBuildOptions::default()
+            .with_include_yaml(yaml)
+            .with_include_binary(true),
+    );
 
     let internal_edges: usize = graph
         .nodes()
@@ -175,6 +182,15 @@ pub(crate) fn run(
             ExternalObjectEdge::Yaml(y) => y.resolved.is_some(),
         })
         .count();
+    let builtin_external_edges: usize = graph
+        .nodes()
+        .iter()
+        .flat_map(|n| graph.external_refs_from(n))
+        .filter(|e| match e {
+            ExternalObjectEdge::Binary(b) => b.builtin.is_some(),
+            ExternalObjectEdge::Yaml(y) => y.builtin.is_some(),
+        })
+        .count();
 
     let roots_with_external = graph.roots(follow_external).len();
     let leaves_with_external = graph.leaves(follow_external).len();
@@ -198,6 +214,7 @@ pub(crate) fn run(
             writeln!(out, "internal_edges={}", internal_edges)?;
             writeln!(out, "external_edges={}", external_edges)?;
             writeln!(out, "external_edges_resolved={}", resolved_external_edges)?;
+            writeln!(out, "external_edges_builtin={}", builtin_external_edges)?;
             writeln!(out, "roots_internal={}", graph.roots(false).len())?;
             writeln!(out, "leaves_internal={}", graph.leaves(false).len())?;
             writeln!(out, "cycles_internal={}", graph.cycles(50, false).len())?;
@@ -221,6 +238,7 @@ pub(crate) fn run(
                     internal_edges,
                     external_edges,
                     external_edges_resolved: resolved_external_edges,
+                    external_edges_builtin: builtin_external_edges,
                     roots_internal: graph.roots(false).len(),
                     leaves_internal: graph.leaves(false).len(),
                     cycles_internal: graph.cycles(50, false).len(),
@@ -262,7 +280,21 @@ pub(crate) fn run(
                         ExternalObjectEdge::Binary(b) => b.resolved.as_ref().map(|k| k.to_string()),
                         ExternalObjectEdge::Yaml(y) => y.resolved.as_ref().map(|k| k.to_string()),
                     };
-                    if follow_external && resolved.is_some() {
+                    let builtin = match ext {
+                        ExternalObjectEdge::Binary(b) => b.builtin,
+                        ExternalObjectEdge::Yaml(y) => y.builtin,
+                    };
+                    if resolved.is_none()
+                        && let Some(builtin) = builtin
+                    {
+                        let edge = JsonlEdge {
+                            kind: "external_builtin",
+                            from: from.to_string(),
+                            to: Some(builtin.to_string()),
+                        };
+                        writeln!(out, "{}", serde_json::to_string(&edge)?)?;
+                        emitted += 1;
+                    } else if follow_external && resolved.is_some() {
                         let edge = JsonlEdge {
                             kind: "external_resolved",
                             from: from.to_string(),
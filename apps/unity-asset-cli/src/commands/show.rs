@@ -0,0 +1,411 @@
+use crate::shared::{AppContext, build_environment, class_name_for_id, load_environment_input};
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use unity_asset::environment::Environment;
+use unity_asset::interchange::{InterchangeFormat, RecordWriter};
+use unity_asset::{UnityClass, UnityDocument, UnityValue, YamlDocument};
+use unity_asset_binary::asset::SerializedFile;
+
+/// Key added to `PPtr` objects whose target was resolved in the same file.
+const TARGET_KEY: &str = "$target";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Tree,
+    Json,
+    Yaml,
+    Interchange(InterchangeFormat),
+}
+
+pub(crate) fn run(
+    input: PathBuf,
+    path_id: Option<i64>,
+    anchor: Option<String>,
+    format: String,
+    raw_hex: bool,
+    max_bytes: usize,
+    ctx: &AppContext,
+) -> Result<()> {
+    let format = match format.to_ascii_lowercase().as_str() {
+        "tree" => Format::Tree,
+        "json" => Format::Json,
+        "yaml" => Format::Yaml,
+        other => match InterchangeFormat::from_name(other) {
+            Some(format) => Format::Interchange(format),
+            None => anyhow::bail!(
+                "Unknown --format: {} (expected: tree|json|yaml|msgpack|cbor)",
+                other
+            ),
+        },
+    };
+    let mut env = build_environment(ctx.strict, ctx.show_warnings, ctx.typetree_registries())?;
+    load_environment_input(&mut env, &input)?;
+
+    let shown = match (path_id, anchor) {
+        (_, Some(anchor)) => show_yaml(&env, &anchor)?,
+        (Some(path_id), None) => show_binary(&env, path_id, raw_hex)?,
+        (None, None) => anyhow::bail!("--path-id or --anchor is required"),
+    };
+    if shown.is_empty() {
+        anyhow::bail!("No matching object in {}", input.display());
+    }
+    if let Format::Interchange(format) = format
+        && !raw_hex
+    {
+        return write_records(&shown, format);
+    }
+    print!("{}", render_all(&shown, format, max_bytes)?);
+    Ok(())
+}
+
+/// One `--format msgpack|cbor` record: the JSON location fields plus the full property map.
+#[derive(Serialize)]
+struct Record<'a> {
+    #[serde(flatten)]
+    location: &'a serde_json::Map<String, serde_json::Value>,
+    properties: UnityValue,
+}
+
+fn write_records(shown: &[Shown], format: InterchangeFormat) -> Result<()> {
+    let mut writer = RecordWriter::new(std::io::stdout().lock(), format);
+    for s in shown {
+        writer.write(&Record {
+            location: &s.location,
+            properties: UnityValue::Object(s.class.properties().clone()),
+        })?;
+    }
+    let _stdout = writer.into_inner()?;
+    Ok(())
+}
+
+/// One located object, ready to render.
+struct Shown {
+    header: String,
+    class: UnityClass,
+    /// Machine-readable location fields for `--format json`.
+    location: serde_json::Map<String, serde_json::Value>,
+    raw_hex: Option<String>,
+}
+
+fn show_binary(env: &Environment, path_id: i64, raw_hex: bool) -> Result<Vec<Shown>> {
+    let mut objects = env.find_binary_objects(path_id);
+    objects.sort_by(|a, b| (a.source, a.asset_index).cmp(&(b.source, b.asset_index)));
+
+    let mut out = Vec::new();
+    for object in objects {
+        let handle = object.object;
+        let class_name = class_name_for_id(handle.class_id());
+        let mut header = format!("{}", object.source);
+        if let Some(asset_index) = object.asset_index {
+            let _ = write!(header, " asset_index={}", asset_index);
+        }
+        let _ = write!(
+            header,
+            " path_id={} {} (class_id={}, byte_size={})",
+            path_id,
+            class_name,
+            handle.class_id(),
+            handle.byte_size()
+        );
+
+        let mut location = serde_json::Map::new();
+        location.insert("source".into(), object.source.to_string().into());
+        location.insert("asset_index".into(), object.asset_index.into());
+        location.insert("path_id".into(), path_id.into());
+        location.insert("class_id".into(), handle.class_id().into());
+        location.insert("class_name".into(), class_name.to_string().into());
+
+        if raw_hex {
+            out.push(Shown {
+                header,
+                class: UnityClass::new(handle.class_id(), class_name.into_owned(), String::new()),
+                location,
+                raw_hex: Some(hex_dump(handle.raw_data()?)),
+            });
+            continue;
+        }
+
+        let parsed = object.read()?;
+        let mut class = parsed.as_unity_class().clone();
+        class.anchor = path_id.to_string();
+        let file = handle.file();
+        let resolve = |file_id: i64, path_id: i64| resolve_binary_target(file, file_id, path_id);
+        annotate_properties(&mut class, &resolve);
+        out.push(Shown {
+            header,
+            class,
+            location,
+            raw_hex: None,
+        });
+    }
+    Ok(out)
+}
+
+fn resolve_binary_target(file: &SerializedFile, file_id: i64, path_id: i64) -> Option<String> {
+    if file_id != 0 || path_id == 0 {
+        return None;
+    }
+    let target = file.find_object_handle(path_id)?;
+    let class_name = class_name_for_id(target.class_id());
+    Some(match target.peek_name().ok().flatten() {
+        Some(name) if !name.is_empty() => format!("{} {:?}", class_name, name),
+        _ => class_name.into_owned(),
+    })
+}
+
+fn show_yaml(env: &Environment, anchor: &str) -> Result<Vec<Shown>> {
+    let mut documents: Vec<_> = env.yaml_documents().iter().collect();
+    documents.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut out = Vec::new();
+    for (path, document) in documents {
+        let entries = document.entries();
+        let Some(object) = entries.iter().find(|entry| entry.anchor == anchor) else {
+            continue;
+        };
+        let resolve = |file_id: i64, _path_id: i64| {
+            let target = entries
+                .iter()
+                .find(|entry| entry.anchor == file_id.to_string())?;
+            Some(match target.get("m_Name").and_then(UnityValue::as_str) {
+                Some(name) if !name.is_empty() => format!("{} {:?}", target.class_name, name),
+                _ => target.class_name.clone(),
+            })
+        };
+        let mut class = object.clone();
+        annotate_properties(&mut class, &resolve);
+
+        let mut location = serde_json::Map::new();
+        location.insert("source".into(), path.to_string_lossy().into_owned().into());
+        location.insert("anchor".into(), anchor.into());
+        location.insert("class_id".into(), class.class_id.into());
+        location.insert("class_name".into(), class.class_name.clone().into());
+        out.push(Shown {
+            header: format!(
+                "{} &{} {} (class_id={})",
+                path.display(),
+                anchor,
+                class.class_name,
+                class.class_id
+            ),
+            class,
+            location,
+            raw_hex: None,
+        });
+    }
+    Ok(out)
+}
+
+fn annotate_properties(class: &mut UnityClass, resolve: &dyn Fn(i64, i64) -> Option<String>) {
+    for value in class.properties_mut().values_mut() {
+        annotate(value, resolve);
+    }
+}
+
+/// Tag resolvable `PPtr`s with their target.
+fn annotate(value: &mut UnityValue, resolve: &dyn Fn(i64, i64) -> Option<String>) {
+    if let Some((file_id, path_id)) = pptr_parts(value) {
+        if let (Some(target), UnityValue::Object(fields)) = (resolve(file_id, path_id), value) {
+            fields.insert(TARGET_KEY.to_string(), UnityValue::String(target));
+        }
+        return;
+    }
+    match value {
+        UnityValue::Array(items) => {
+            for item in items {
+                annotate(item, resolve);
+            }
+        }
+        UnityValue::Object(fields) => {
+            for item in fields.values_mut() {
+                annotate(item, resolve);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replace byte arrays with a preview string (for JSON/YAML output).
+fn preview_bytes(value: &mut UnityValue, max_bytes: usize) {
+    match value {
+        UnityValue::Bytes(bytes) => {
+            *value = UnityValue::String(bytes_preview(bytes, max_bytes));
+        }
+        UnityValue::Array(items) => {
+            for item in items {
+                preview_bytes(item, max_bytes);
+            }
+        }
+        UnityValue::Object(fields) => {
+            for item in fields.values_mut() {
+                preview_bytes(item, max_bytes);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn pptr_parts(value: &UnityValue) -> Option<(i64, i64)> {
+    let UnityValue::Object(fields) = value else {
+        return None;
+    };
+    let file_id = fields.get("m_FileID").or_else(|| fields.get("fileID"))?;
+    let file_id = file_id.as_i64()?;
+    let path_id = match fields.get("m_PathID").or_else(|| fields.get("pathID")) {
+        Some(path_id) => path_id.as_i64()?,
+        // YAML references are `{fileID: <anchor>}` (plus `guid` when external).
+        None if !fields.contains_key("guid") => file_id,
+        None => return None,
+    };
+    Some((file_id, path_id))
+}
+
+fn bytes_preview(bytes: &[u8], max_bytes: usize) -> String {
+    let mut out = format!("<{} bytes", bytes.len());
+    if !bytes.is_empty() && max_bytes > 0 {
+        out.push_str(": ");
+        for b in bytes.iter().take(max_bytes) {
+            let _ = write!(out, "{:02x}", b);
+        }
+        if bytes.len() > max_bytes {
+            out.push_str("...");
+        }
+    }
+    out.push('>');
+    out
+}
+
+fn hex_dump(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (row, chunk) in bytes.chunks(16).enumerate() {
+        let _ = write!(out, "{:08x} ", row * 16);
+        for i in 0..16 {
+            if i == 8 {
+                out.push(' ');
+            }
+            match chunk.get(i) {
+                Some(b) => {
+                    let _ = write!(out, " {:02x}", b);
+                }
+                None => out.push_str("   "),
+            }
+        }
+        out.push_str("  |");
+        out.extend(chunk.iter().map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                b as char
+            } else {
+                '.'
+            }
+        }));
+        out.push_str("|\n");
+    }
+    out
+}
+
+fn render_all(shown: &[Shown], format: Format, max_bytes: usize) -> Result<String> {
+    let mut out = String::new();
+    if shown.iter().any(|s| s.raw_hex.is_some()) {
+        for s in shown {
+            let _ = writeln!(out, "# {}", s.header);
+            out.push_str(s.raw_hex.as_deref().unwrap_or_default());
+        }
+        return Ok(out);
+    }
+
+    match format {
+        Format::Tree | Format::Interchange(_) => {
+            for s in shown {
+                let _ = writeln!(out, "# {}", s.header);
+                for (key, value) in s.class.properties() {
+                    write_tree(&mut out, key, value, 0, max_bytes);
+                }
+            }
+        }
+        Format::Json => {
+            let values: Vec<serde_json::Value> = shown
+                .iter()
+                .map(|s| {
+                    let mut properties = UnityValue::Object(s.class.properties().clone());
+                    preview_bytes(&mut properties, max_bytes);
+                    let mut object = s.location.clone();
+                    object.insert("properties".into(), serde_json::to_value(&properties)?);
+                    Ok(serde_json::Value::Object(object))
+                })
+                .collect::<Result<_, serde_json::Error>>()?;
+            out.push_str(&serde_json::to_string_pretty(&values)?);
+            out.push('\n');
+        }
+        Format::Yaml => {
+            let mut document = YamlDocument::new();
+            for s in shown {
+                let _ = writeln!(out, "# {}", s.header);
+                let mut class = s.class.clone();
+                for value in class.properties_mut().values_mut() {
+                    preview_bytes(value, max_bytes);
+                }
+                document.add_entry(class);
+            }
+            out.push_str(&document.dump_yaml()?);
+        }
+    }
+    Ok(out)
+}
+
+fn write_tree(out: &mut String, key: &str, value: &UnityValue, depth: usize, max_bytes: usize) {
+    let indent = "  ".repeat(depth);
+    match value {
+        UnityValue::Object(fields) => {
+            if let Some((file_id, path_id)) = pptr_parts(value) {
+                let _ = write!(out, "{}{}: PPtr(fileID={}", indent, key, file_id);
+                if fields.contains_key("m_PathID") || fields.contains_key("pathID") {
+                    let _ = write!(out, ", pathID={}", path_id);
+                }
+                out.push(')');
+                if let Some(UnityValue::String(target)) = fields.get(TARGET_KEY) {
+                    let _ = write!(out, " -> {}", target);
+                }
+                out.push('\n');
+            } else if fields.is_empty() {
+                let _ = writeln!(out, "{}{}: {{}}", indent, key);
+            } else {
+                let _ = writeln!(out, "{}{}:", indent, key);
+                for (child, item) in fields {
+                    write_tree(out, child, item, depth + 1, max_bytes);
+                }
+            }
+        }
+        UnityValue::Array(items) => {
+            let _ = writeln!(out, "{}{}: Array(len={})", indent, key, items.len());
+            for (i, item) in items.iter().enumerate() {
+                write_tree(out, &format!("[{}]", i), item, depth + 1, max_bytes);
+            }
+        }
+        UnityValue::String(s) => {
+            let _ = writeln!(out, "{}{}: {:?}", indent, key, s);
+        }
+        UnityValue::Bytes(bytes) => {
+            let _ = writeln!(
+                out,
+                "{}{}: {}",
+                indent,
+                key,
+                bytes_preview(bytes, max_bytes)
+            );
+        }
+        UnityValue::Null => {
+            let _ = writeln!(out, "{}{}: null", indent, key);
+        }
+        UnityValue::Bool(b) => {
+            let _ = writeln!(out, "{}{}: {}", indent, key, b);
+        }
+        UnityValue::Integer(i) => {
+            let _ = writeln!(out, "{}{}: {}", indent, key, i);
+        }
+        UnityValue::Float(f) => {
+            let _ = writeln!(out, "{}{}: {}", indent, key, f);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::shared::AppContext;
+use anyhow::Result;
+use std::path::PathBuf;
+use unity_asset::validate::{Severity, ValidateOptions, ValidationRun};
+
+pub(crate) fn run(
+    input: Vec<PathBuf>,
+    deep: bool,
+    json: bool,
+    fail_on: String,
+    _ctx: &AppContext,
+) -> Result<()> {
+    let threshold = match fail_on.to_ascii_lowercase().as_str() {
+        "warn" | "warning" => Severity::Warning,
+        "error" => Severity::Error,
+        other => anyhow::bail!("Invalid --fail-on: {} (expected warn|error)", other),
+    };
+
+    let run = unity_asset::validate::run(&input, &ValidateOptions::default().with_deep(deep));
+    if json {
+        println!("{}", serde_json::to_string_pretty(&run)?);
+    } else {
+        print_report(&run);
+    }
+
+    if run.fails(threshold) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn print_report(run: &ValidationRun) {
+    for file in &run.files {
+        let status = match file.worst() {
+            None => "ok",
+            Some(Severity::Info) => "ok",
+            Some(Severity::Warning) => "WARN",
+            Some(Severity::Error) => "FAIL",
+        };
+        println!("{:<4}  {}", status, file.path.display());
+        for finding in &file.findings {
+            let severity = match finding.severity {
+                Severity::Info => "info",
+                Severity::Warning => "warning",
+                Severity::Error => "error",
+            };
+            let mut location = String::new();
+            if let Some(entry) = &finding.entry {
+                location.push_str(&format!(" [{}]", entry));
+            }
+            if let Some(path_id) = finding.path_id {
+                location.push_str(&format!(" path_id={}", path_id));
+            }
+            println!(
+                "      {}: {}{}: {}",
+                severity, finding.check, location, finding.message
+            );
+        }
+    }
+
+    let summary = &run.summary;
+    println!(
+        "\n{} files checked ({} skipped): {} with errors, {} with warnings; {} errors, {} warnings, {} infos",
+        summary.files,
+        summary.skipped,
+        summary.files_with_errors,
+        summary.files_with_warnings,
+        summary.errors,
+        summary.warnings,
+        summary.infos
+    );
+}
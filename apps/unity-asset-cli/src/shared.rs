@@ -259,3 +259,335 @@ pub(crate) fn lookup_object_type_info(env: &Environment, key: &BinaryObjectKey)
             .unwrap_or((0, 0)),
     }
 }
+
+/// The SerializedFile holding `key`'s object.
+#[cfg(feature = "decode")]
+pub(crate) fn serialized_file_for_key<'a>(
+    env: &'a Environment,
+    key: &BinaryObjectKey,
+) -> Option<&'a unity_asset_binary::asset::SerializedFile> {
+    match key.source_kind {
+        unity_asset::environment::BinarySourceKind::AssetBundle => env
+            .bundles()
+            .get(&key.source)
+            .and_then(|b| key.asset_index.and_then(|i| b.assets.get(i))),
+        unity_asset::environment::BinarySourceKind::SerializedFile => {
+            env.binary_assets().get(&key.source)
+        }
+    }
+}
+
+/// Convert a Texture2D object, pulling streamed image data from its `.resS` when needed.
+#[cfg(feature = "decode")]
+fn convert_texture(
+    env: &Environment,
+    key: &BinaryObjectKey,
+    processor: &unity_asset_decode::texture::TextureProcessor,
+    obj: &unity_asset_binary::object::UnityObject,
+) -> Result<unity_asset_decode::texture::Texture2D> {
+    let mut texture = processor.convert_object(obj)?;
+    if texture.image_data.is_empty()
+        && texture.is_streamed()
+        && let Ok(bytes) = env.read_stream_data_source(
+            &key.source,
+            key.source_kind,
+            &texture.stream_info.path,
+            texture.stream_info.offset,
+            texture.stream_info.size,
+        )
+        && !bytes.is_empty()
+    {
+        texture.data_size = bytes.len() as i32;
+        texture.image_data = bytes;
+    }
+    Ok(texture)
+}
+
+/// `--texture-transform` rules for decoded Texture2D exports, parsed once per run.
+#[derive(Debug, Default)]
+pub(crate) struct TextureTransforms {
+    #[cfg(feature = "decode")]
+    options: unity_asset_decode::texture::ExportOptions,
+}
+
+impl TextureTransforms {
+    pub(crate) fn parse(rules: &[String]) -> Result<Self> {
+        #[cfg(feature = "decode")]
+        {
+            use anyhow::Context;
+
+            let mut options = unity_asset_decode::texture::ExportOptions::default();
+            for rule in rules {
+                let rule = rule
+                    .parse()
+                    .with_context(|| format!("invalid --texture-transform '{}'", rule))?;
+                options = options.with_channel_transform(rule);
+            }
+            Ok(Self { options })
+        }
+        #[cfg(not(feature = "decode"))]
+        {
+            let _ = rules;
+            Ok(Self::default())
+        }
+    }
+
+    /// Apply the first rule matching the texture's name or the material slots binding it.
+    #[cfg(feature = "decode")]
+    fn apply(
+        &self,
+        env: &Environment,
+        key: &BinaryObjectKey,
+        name: &str,
+        image: image::RgbaImage,
+    ) -> Result<image::RgbaImage> {
+        use unity_asset_decode::texture::{TransformTarget, texture_material_slots};
+
+        let rules = &self.options.channel_transforms;
+        if rules.is_empty() {
+            return Ok(image);
+        }
+        let slots = if rules
+            .iter()
+            .any(|rule| matches!(rule.target, TransformTarget::Slot(_)))
+        {
+            serialized_file_for_key(env, key)
+                .map(|file| texture_material_slots(file, key.path_id))
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        Ok(self.options.apply_channel_transforms(image, name, &slots)?)
+    }
+}
+
+/// Decode a Texture2D object and write it to `dest`, merging in its ETC1 alpha companion when
+/// one is found and applying `transforms`.
+#[cfg(feature = "decode")]
+pub(crate) fn export_texture(
+    env: &Environment,
+    key: &BinaryObjectKey,
+    processor: &unity_asset_decode::texture::TextureProcessor,
+    obj: &unity_asset_binary::object::UnityObject,
+    dest: &Path,
+    transforms: &TextureTransforms,
+) -> Result<()> {
+    use unity_asset_decode::texture::TextureExporter;
+
+    let texture = convert_texture(env, key, processor, obj)?;
+    let companion = serialized_file_for_key(env, key)
+        .and_then(|file| unity_asset_decode::texture::find_alpha_companion(file, key.path_id));
+    if let Some(path_id) = companion {
+        let alpha_key = BinaryObjectKey {
+            path_id,
+            ..key.clone()
+        };
+        let merged = env
+            .read_binary_object_key(&alpha_key)
+            .map_err(anyhow::Error::from)
+            .and_then(|alpha_obj| convert_texture(env, &alpha_key, processor, &alpha_obj))
+            .and_then(|alpha| Ok(processor.decode_with_alpha_split(&texture, &alpha)?));
+        match merged {
+            Ok(image) => {
+                let image = transforms.apply(env, key, &texture.name, image)?;
+                return Ok(TextureExporter::export_auto(&image, dest)?);
+            }
+            Err(e) => tracing::warn!(
+                path_id = key.path_id,
+                alpha_path_id = path_id,
+                error = %e,
+                "failed to merge alpha texture; exporting RGB only"
+            ),
+        }
+    }
+    let image = processor.decode_texture(&texture)?;
+    let image = transforms.apply(env, key, &texture.name, image)?;
+    Ok(TextureExporter::export_auto(&image, dest)?)
+}
+
+/// The `m_RD.texture` pointer of a Sprite object as `(file_id, path_id)`.
+#[cfg(feature = "decode")]
+pub(crate) fn sprite_texture_pptr(
+    obj: &unity_asset_binary::object::UnityObject,
+) -> Option<(i32, i64)> {
+    use unity_asset::UnityValue;
+
+    let UnityValue::Object(rd) = obj.get("m_RD")? else {
+        return None;
+    };
+    let UnityValue::Object(texture) = rd.get("texture")? else {
+        return None;
+    };
+    let file_id = match texture.get("m_FileID")? {
+        UnityValue::Integer(v) => *v as i32,
+        _ => return None,
+    };
+    let path_id = match texture.get("m_PathID")? {
+        UnityValue::Integer(v) => *v,
+        _ => return None,
+    };
+    Some((file_id, path_id))
+}
+
+/// The GUID written to the `.meta` of an exported object.
+///
+/// Derived from the source file name (plus the bundle asset index) and the path id, so
+/// re-exporting the same build gives the same GUIDs regardless of where it was unpacked.
+#[cfg(feature = "decode")]
+pub(crate) fn meta_guid_for_key(key: &BinaryObjectKey) -> unity_asset::UnityGuid {
+    let mut source = match &key.source {
+        BinarySource::Path(p) => p
+            .file_name()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| key.source.to_string()),
+        other => other.to_string(),
+    };
+    if let Some(index) = key.asset_index {
+        source.push_str(&format!("/{index}"));
+    }
+    unity_asset::UnityGuid::derived(&source, key.path_id)
+}
+
+/// Write `<dest>.meta` for a decoded export so Unity keeps the object's GUID on import.
+///
+/// Only AudioClip, Texture2D, Sprite and TextAsset exports get one; other classes are left
+/// alone.
+#[cfg(feature = "decode")]
+pub(crate) fn write_meta_file(
+    env: &Environment,
+    key: &BinaryObjectKey,
+    obj: &unity_asset_binary::object::UnityObject,
+    dest: &Path,
+) -> Result<()> {
+    use unity_asset::environment::BinarySourceKind;
+    use unity_asset_binary::{asset::class_ids, unity_version::UnityVersion};
+    use unity_asset_decode::{
+        audio::AudioClipConverter, meta::MetaGenerator, sprite::SpriteProcessor,
+        texture::TextureProcessor,
+    };
+
+    let guid = meta_guid_for_key(key);
+    let unity_version = serialized_file_for_key(env, key)
+        .map(|f| UnityVersion::parse_version(&f.unity_version).unwrap_or_default())
+        .unwrap_or_default();
+
+    let doc = match obj.info.type_id {
+        class_ids::AUDIO_CLIP => {
+            let clip = AudioClipConverter::new(unity_version).from_unity_object(obj)?;
+            MetaGenerator::for_audio(&clip, guid)
+        }
+        class_ids::TEXTURE_2D => {
+            let texture = TextureProcessor::new(unity_version).convert_object(obj)?;
+            MetaGenerator::for_texture(&texture, guid)
+        }
+        class_ids::SPRITE => {
+            let sprite = SpriteProcessor::new(unity_version.clone())
+                .parse_sprite(obj)?
+                .sprite;
+            let (file_id, texture_path_id) =
+                sprite_texture_pptr(obj).unwrap_or((0, sprite.render_data.texture_path_id));
+            let obj_ref = match key.source_kind {
+                BinarySourceKind::AssetBundle => key.asset_index.and_then(|i| {
+                    env.find_binary_object_in_bundle_asset_source(&key.source, i, key.path_id)
+                }),
+                BinarySourceKind::SerializedFile => {
+                    env.find_binary_object_in_source_id(&key.source, key.path_id)
+                }
+            }
+            .ok_or_else(|| anyhow::anyhow!("Sprite object not found: {}", key))?;
+            let texture_obj = env.read_binary_pptr(&obj_ref, file_id, texture_path_id)?;
+            let texture = TextureProcessor::new(unity_version).convert_object(&texture_obj)?;
+            MetaGenerator::for_sprite(&sprite, &texture, guid)
+        }
+        class_ids::TEXT_ASSET => MetaGenerator::for_text(guid),
+        _ => return Ok(()),
+    };
+
+    let mut meta_path = dest.as_os_str().to_owned();
+    meta_path.push(".meta");
+    std::fs::write(PathBuf::from(meta_path), doc.dump_yaml()?)?;
+    Ok(())
+}
+
+/// `--similar-textures`: hash the images exported for Texture2D objects and group the
+/// near-duplicates. `exports` yields `(locator, output path)` pairs; outputs that cannot be read
+/// back are skipped with a warning.
+///
+/// The report is `{"max_distance", "hashed", "groups"}` with one entry per
+/// `unity_asset_decode::texture::SimilarGroup`, as stored in export manifests.
+pub(crate) fn similar_textures_report<'a>(
+    exports: impl IntoIterator<Item = (&'a str, &'a Path)>,
+    max_distance: u32,
+    show_warnings: bool,
+) -> Result<serde_json::Value> {
+    #[cfg(not(feature = "decode"))]
+    {
+        let _ = (exports.into_iter(), max_distance, show_warnings);
+        anyhow::bail!(
+            "--similar-textures requires compiling `unity-asset-cli` with feature `decode` (build with default features, or `--features decode`)."
+        );
+    }
+    #[cfg(feature = "decode")]
+    {
+        use unity_asset_decode::texture::{TextureFingerprint, find_similar_textures};
+
+        let fingerprints: Vec<TextureFingerprint> = exports
+            .into_iter()
+            .filter_map(|(locator, path)| match image::open(path) {
+                Ok(image) => Some(TextureFingerprint::new(locator, &image.to_rgba8())),
+                Err(e) => {
+                    cli_warn(
+                        show_warnings,
+                        format!("cannot hash exported texture {}: {}", path.display(), e),
+                    );
+                    None
+                }
+            })
+            .collect();
+        let hashed = fingerprints.len();
+        let groups = find_similar_textures(fingerprints, max_distance);
+        Ok(serde_json::json!({
+            "max_distance": max_distance,
+            "hashed": hashed,
+            "groups": groups,
+        }))
+    }
+}
+
+/// Print a [`similar_textures_report`].
+pub(crate) fn print_similar_textures(report: &serde_json::Value) {
+    let groups = report["groups"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    println!(
+        "Similar textures: {} group(s) among {} hashed (max distance {})",
+        groups.len(),
+        report["hashed"],
+        report["max_distance"]
+    );
+    for group in groups {
+        let members = group["members"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or(&[]);
+        let Some(largest) = members.first() else {
+            continue;
+        };
+        println!(
+            "  {} near-identical textures, largest is {}x{}",
+            members.len(),
+            largest["width"],
+            largest["height"]
+        );
+        for member in members {
+            println!(
+                "    {}x{} {} {}",
+                member["width"],
+                member["height"],
+                member["hash"].as_str().unwrap_or_default(),
+                member["locator"].as_str().unwrap_or_default()
+            );
+        }
+    }
+}
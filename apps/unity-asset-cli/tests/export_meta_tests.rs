@@ -0,0 +1,105 @@
+//! `--write-meta`: decoded exports get a Unity `.meta` with a GUID that is stable across runs.
+
+#![cfg(feature = "decode")]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use unity_asset::environment::Environment;
+use unity_asset::{UnityDocument, UnityGuid, UnityValue, YamlDocument};
+use unity_asset_write::{PackerOptions, UnityPyPacker};
+
+const CLIP_DIR: &str = "assets/torappu/dynamicassets/audio/sound_beta_2/voice/char_118_yuki";
+
+/// The yuki sample ships without its `.resource`; give `cn_001.ogg` streamed bytes so it decodes.
+fn yuki_with_streamed_clip(dir: &Path) -> PathBuf {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples/char_118_yuki.ab");
+    let mut env = Environment::new();
+    env.load_file(&path).unwrap();
+    let key = env
+        .bundle_container_entries(&path)
+        .unwrap()
+        .into_iter()
+        .find(|e| e.asset_path.ends_with("/cn_001.ogg"))
+        .and_then(|e| e.key)
+        .unwrap();
+
+    let mut session = env.edit_session();
+    session
+        .write_streamed_audio_clip_data(&key, None, b"OggS\0\x02rust")
+        .unwrap();
+    session
+        .save(
+            PackerOptions::default().with_packer(UnityPyPacker::Original),
+            dir,
+        )
+        .unwrap();
+    dir.join("char_118_yuki.ab")
+}
+
+fn export_bundle(input: &Path, output: &Path, extra: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_unity-asset"))
+        .arg("export-bundle")
+        .arg("-i")
+        .arg(input)
+        .arg("-o")
+        .arg(output)
+        .args(["--pattern", "*/cn_001.ogg", "--jobs", "1"])
+        .args(extra)
+        .output()
+        .expect("run unity-asset")
+}
+
+fn read_meta(path: &Path) -> (UnityGuid, YamlDocument) {
+    let text = std::fs::read_to_string(path).unwrap();
+    let doc = YamlDocument::from_yaml_str(&text).unwrap();
+    let guid = doc.entries()[0]
+        .get("guid")
+        .and_then(|v| v.as_str())
+        .unwrap()
+        .parse()
+        .unwrap();
+    (guid, doc)
+}
+
+#[test]
+fn export_bundle_writes_meta_next_to_decoded_files() {
+    let input = tempfile::tempdir().unwrap();
+    let bundle = yuki_with_streamed_clip(input.path());
+
+    let mut guids = Vec::new();
+    for _ in 0..2 {
+        let output = tempfile::tempdir().unwrap();
+        let result = export_bundle(&bundle, output.path(), &["--decode", "--write-meta"]);
+        assert!(
+            result.status.success(),
+            "export-bundle failed: {}",
+            String::from_utf8_lossy(&result.stderr)
+        );
+        let clip = output.path().join(CLIP_DIR).join("cn_001.ogg");
+        assert!(clip.exists());
+
+        let (guid, doc) = read_meta(&output.path().join(CLIP_DIR).join("cn_001.ogg.meta"));
+        let UnityValue::Object(importer) = doc.entries()[0].get("AudioImporter").unwrap() else {
+            panic!("AudioImporter is not a mapping");
+        };
+        let sample_settings = importer
+            .get("defaultSettings")
+            .and_then(|v| v.as_object())
+            .unwrap();
+        assert_eq!(
+            sample_settings.get("sampleRateOverride").unwrap().as_i64(),
+            Some(44100)
+        );
+        guids.push(guid);
+    }
+    assert_eq!(guids[0], guids[1]);
+}
+
+#[test]
+fn write_meta_requires_decode() {
+    let output = tempfile::tempdir().unwrap();
+    let input = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples/char_118_yuki.ab");
+    let result = export_bundle(&input, output.path(), &["--write-meta"]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("--decode"));
+}
@@ -0,0 +1,138 @@
+//! `unity-asset show`: tree/json/hex output for a bundle object and anchor lookup in YAML.
+
+use std::path::Path;
+use std::process::Command;
+use unity_asset::{UnityValue, interchange};
+
+const SPRITE_PATH_ID: &str = "-8325468307350463555";
+const TEXTURE_PATH_ID: &str = "-3875358842991402074";
+
+fn banner() -> String {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/samples/banner_1")
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn show_bytes(args: &[&str]) -> Vec<u8> {
+    let output = Command::new(env!("CARGO_BIN_EXE_unity-asset"))
+        .arg("show")
+        .args(args)
+        .output()
+        .expect("run unity-asset");
+    assert!(
+        output.status.success(),
+        "show failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output.stdout
+}
+
+fn show(args: &[&str]) -> String {
+    String::from_utf8(show_bytes(args)).unwrap()
+}
+
+#[test]
+fn tree_output_resolves_pptrs_and_previews_bytes() {
+    let out = show(&["-i", &banner(), "--path-id", SPRITE_PATH_ID]);
+    let lines: Vec<&str> = out.lines().collect();
+    assert!(lines[0].ends_with(&format!(
+        "asset_index=0 path_id={} Sprite (class_id=213, byte_size=592)",
+        SPRITE_PATH_ID
+    )));
+    assert!(lines.contains(&"m_Name: \"banner_1\""));
+    let texture = format!(
+        "  texture: PPtr(fileID=0, pathID={}) -> Texture2D \"banner_1\"",
+        TEXTURE_PATH_ID
+    );
+    assert!(lines.contains(&texture.as_str()));
+    assert!(lines.contains(&"  m_IndexBuffer: <42 bytes: 08000700060005000600070001000600...>"));
+
+    let out = show(&[
+        "-i",
+        &banner(),
+        "--path-id",
+        SPRITE_PATH_ID,
+        "--max-bytes",
+        "2",
+    ]);
+    assert!(out.contains("  m_IndexBuffer: <42 bytes: 0800...>\n"));
+}
+
+#[test]
+fn json_output_carries_location_and_target() {
+    let out = show(&[
+        "-i",
+        &banner(),
+        "--path-id",
+        SPRITE_PATH_ID,
+        "--format",
+        "json",
+    ]);
+    let json: serde_json::Value = serde_json::from_str(&out).unwrap();
+    let object = &json[0];
+    assert_eq!(object["class_name"], "Sprite");
+    assert_eq!(object["asset_index"], 0);
+    assert_eq!(object["properties"]["m_Name"], "banner_1");
+    assert_eq!(
+        object["properties"]["m_RD"]["texture"]["$target"],
+        "Texture2D \"banner_1\""
+    );
+    assert_eq!(
+        object["properties"]["m_RD"]["m_IndexBuffer"],
+        "<42 bytes: 08000700060005000600070001000600...>"
+    );
+}
+
+#[test]
+fn msgpack_output_keeps_raw_bytes() {
+    #[derive(serde::Deserialize)]
+    struct Record {
+        class_name: String,
+        path_id: i64,
+        properties: UnityValue,
+    }
+
+    let out = show_bytes(&[
+        "-i",
+        &banner(),
+        "--path-id",
+        SPRITE_PATH_ID,
+        "--format",
+        "msgpack",
+    ]);
+    let record: Record = interchange::from_msgpack(&out).unwrap();
+    assert_eq!(record.class_name, "Sprite");
+    assert_eq!(record.path_id.to_string(), SPRITE_PATH_ID);
+    let rd = record.properties.as_object().unwrap()["m_RD"]
+        .as_object()
+        .unwrap();
+    assert_eq!(rd["m_IndexBuffer"].as_bytes().map(<[u8]>::len), Some(42));
+}
+
+#[test]
+fn raw_hex_dumps_object_bytes() {
+    let out = show(&["-i", &banner(), "--path-id", TEXTURE_PATH_ID, "--raw-hex"]);
+    assert_eq!(
+        out.lines().nth(1),
+        Some("00000000  08 00 00 00 62 61 6e 6e  65 72 5f 31 04 00 00 00  |....banner_1....|")
+    );
+}
+
+#[test]
+fn anchor_selects_a_yaml_document() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Player.prefab");
+    std::fs::write(
+        &path,
+        "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n\
+         --- !u!1 &100\nGameObject:\n  m_Name: Player\n  m_Component:\n  - component: {fileID: 200}\n\
+         --- !u!4 &200\nTransform:\n  m_GameObject: {fileID: 100}\n  m_LocalPosition: {x: 1, y: 2, z: 3}\n",
+    )
+    .unwrap();
+
+    let out = show(&["-i", &path.to_string_lossy(), "--anchor", "200"]);
+    assert!(out.contains("&200 Transform (class_id=4)\n"));
+    assert!(out.contains("m_GameObject: PPtr(fileID=100) -> GameObject \"Player\"\n"));
+    assert!(out.contains("m_LocalPosition:\n  x: 1\n"));
+}
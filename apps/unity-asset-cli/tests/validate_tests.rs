@@ -0,0 +1,80 @@
+//! `unity-asset validate`: exit codes per `--fail-on` threshold and the JSON schema.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn banner() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples/banner_1")
+}
+
+fn validate(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_unity-asset"))
+        .arg("validate")
+        .args(args)
+        .output()
+        .expect("run unity-asset")
+}
+
+#[test]
+fn clean_bundle_passes() {
+    let output = validate(&["-i", banner().to_str().unwrap(), "--fail-on", "warn"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("1 files checked (0 skipped): 0 with errors"));
+}
+
+#[test]
+fn fail_on_threshold_sets_exit_code() {
+    let dir = tempfile::tempdir().unwrap();
+    let bundle = dir.path().join("banner.bundle");
+    std::fs::copy(banner(), &bundle).unwrap();
+    std::fs::write(dir.path().join("banner.bundle.manifest"), "CRC: 1\n").unwrap();
+    let input = dir.path().to_str().unwrap();
+
+    // A CRC mismatch is a warning.
+    assert!(validate(&["-i", input]).status.success());
+    assert_eq!(
+        validate(&["-i", input, "--fail-on", "warn"]).status.code(),
+        Some(1)
+    );
+
+    let data = std::fs::read(banner()).unwrap();
+    std::fs::write(dir.path().join("truncated.bundle"), &data[..1000]).unwrap();
+    assert_eq!(validate(&["-i", input]).status.code(), Some(1));
+
+    assert!(
+        !validate(&["-i", input, "--fail-on", "never"])
+            .status
+            .success()
+    );
+}
+
+#[test]
+fn json_output_follows_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    let data = std::fs::read(banner()).unwrap();
+    std::fs::write(dir.path().join("truncated.bundle"), &data[..1000]).unwrap();
+    let output = validate(&[
+        "-i",
+        dir.path().to_str().unwrap(),
+        "-i",
+        banner().to_str().unwrap(),
+        "--json",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(value["schema_version"], 1);
+    assert_eq!(value["deep"], false);
+    let files = value["files"].as_array().unwrap();
+    assert_eq!(files.len(), 2);
+    let truncated = files
+        .iter()
+        .find(|f| f["path"].as_str().unwrap().ends_with("truncated.bundle"))
+        .unwrap();
+    assert_eq!(truncated["kind"], "asset_bundle");
+    assert_eq!(truncated["findings"][0]["severity"], "error");
+    assert_eq!(truncated["findings"][0]["check"], "bundle.header");
+    assert_eq!(value["summary"]["files_with_errors"], 1);
+    assert_eq!(value["summary"]["errors"], 1);
+}
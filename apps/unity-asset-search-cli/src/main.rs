@@ -39,8 +39,8 @@ enum Cmd {
     References {
         guid: String,
 
-        #[arg(long)]
-        file_id: Option<u64>,
+        #[arg(long, allow_negative_numbers = true)]
+        file_id: Option<i64>,
 
         #[arg(long, default_value_t = 50)]
         limit: usize,
@@ -172,7 +172,7 @@ async fn suggest(base_url: &str, prefix: &str, limit: usize) -> Result<()> {
     Ok(())
 }
 
-async fn references(base_url: &str, guid: &str, file_id: Option<u64>, limit: usize) -> Result<()> {
+async fn references(base_url: &str, guid: &str, file_id: Option<i64>, limit: usize) -> Result<()> {
     let url = format!("{base_url}/v1/references");
     let mut params: Vec<(String, String)> = vec![
         ("guid".to_string(), guid.to_string()),
@@ -112,12 +112,11 @@ async fn main() -> anyhow::Result<()> {
         Some(args.scan_root.clone())
     };
     let paths = IndexPaths::for_project(args.project_root, args.index_dir, scan_roots)?;
-    let options = SearchIndexOptions {
-        index_bundle_container_entries,
-        max_bundle_container_entries_per_bundle: args.max_bundle_container_entries_per_bundle,
-        respect_ignore_files: !args.no_ignore_files,
-        respect_project_gitignore: !no_gitignore,
-    };
+    let options = SearchIndexOptions::default()
+        .with_index_bundle_container_entries(index_bundle_container_entries)
+        .with_max_bundle_container_entries_per_bundle(args.max_bundle_container_entries_per_bundle)
+        .with_respect_ignore_files(!args.no_ignore_files)
+        .with_respect_project_gitignore(!no_gitignore);
     let index = SearchIndex::open_or_create_with_options(&paths, options)?;
 
     let token = args.token.unwrap_or_else(generate_token);
@@ -291,7 +290,7 @@ async fn suggest(
 #[derive(Debug, serde::Deserialize)]
 struct ReferencesQuery {
     guid: String,
-    file_id: Option<u64>,
+    file_id: Option<i64>,
     limit: Option<usize>,
 }
 
@@ -0,0 +1,576 @@
+//! Addressables content catalogs (`catalog.json`)
+//!
+//! Addressables builds ship a content catalog mapping keys (addresses, labels, asset GUIDs) to
+//! resource locations: an internal id (asset path or bundle URL), the provider that loads it and
+//! the locations it depends on. The readable fields only hold the string tables; the key, bucket,
+//! entry and extra data are packed little-endian and base64-encoded:
+//!
+//! - `m_KeyDataString`: key count, then one tagged value per key (see [`CatalogKey`])
+//! - `m_BucketDataString`: per key, the offset of its value in the key data and the indices of its
+//!   locations
+//! - `m_EntryDataString`: per location, seven `i32`s (internal id, provider, dependency key,
+//!   dependency hash, extra data offset, primary key, resource type)
+//! - `m_ExtraDataString`: tagged values referenced by locations; bundle locations carry
+//!   `AssetBundleRequestOptions` (hash, CRC, size) as JSON
+//!
+//! [`ContentCatalog::resolve`] turns an address or label into [`ResourceLocation`]s with their
+//! bundle dependencies, and [`crate::bundle::BundleLoader::load_addressable`] loads them.
+//! The binary `catalog.bin` of Addressables 2.x is not supported.
+
+use crate::asset::SerializedFile;
+use crate::error::{BinaryError, Result};
+use crate::reader::{BinaryReader, ByteOrder};
+use crate::unity_objects::parse_pptr;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+#[cfg(feature = "fs")]
+use std::path::Path;
+use unity_asset_core::{UnityValue, class_ids};
+
+/// Provider id of bundle locations.
+pub const ASSET_BUNDLE_PROVIDER: &str =
+    "UnityEngine.ResourceManagement.ResourceProviders.AssetBundleProvider";
+
+/// Provider id of assets loaded from a bundle.
+pub const BUNDLED_ASSET_PROVIDER: &str =
+    "UnityEngine.ResourceManagement.ResourceProviders.BundledAssetProvider";
+
+/// A catalog key or extra data value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CatalogKey {
+    /// Address, label, asset GUID or bundle name (ASCII or UTF-16 on disk).
+    String(String),
+    /// `UInt16` / `UInt32` / `Int32` keys (dependency hashes in older catalogs).
+    Int(i64),
+    Hash128(String),
+    /// Type key: the type's GUID.
+    Type(String),
+    /// Serialized object, e.g. `AssetBundleRequestOptions`.
+    Json {
+        assembly_name: String,
+        class_name: String,
+        json: String,
+    },
+}
+
+impl CatalogKey {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            CatalogKey::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for CatalogKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CatalogKey::String(s) | CatalogKey::Hash128(s) | CatalogKey::Type(s) => f.write_str(s),
+            CatalogKey::Int(i) => write!(f, "{i}"),
+            CatalogKey::Json { class_name, .. } => f.write_str(class_name),
+        }
+    }
+}
+
+/// Cache validation data of a bundle location (`AssetBundleRequestOptions`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleRequestOptions {
+    /// Bundle content hash (`m_Hash`), the cache version of remote bundles.
+    pub hash: String,
+    /// CRC32 of the uncompressed bundle (`m_Crc`); `0` disables the check.
+    pub crc: u32,
+    /// Bundle name without the hash suffix (`m_BundleName`).
+    pub bundle_name: String,
+    /// Bundle file size in bytes (`m_BundleSize`).
+    pub bundle_size: u64,
+}
+
+impl BundleRequestOptions {
+    fn from_json(json: &str) -> Option<Self> {
+        let value: serde_json::Value = serde_json::from_str(json).ok()?;
+        Some(Self {
+            hash: value["m_Hash"].as_str().unwrap_or_default().to_string(),
+            crc: value["m_Crc"].as_u64().unwrap_or(0) as u32,
+            bundle_name: value["m_BundleName"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            bundle_size: value["m_BundleSize"].as_u64().unwrap_or(0),
+        })
+    }
+}
+
+/// A resolved catalog location.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceLocation {
+    /// Primary key (usually the address, or the bundle name for bundles).
+    pub primary_key: String,
+    /// Asset path (`Assets/Prefabs/Player.prefab`) or bundle path, with catalog prefixes expanded.
+    /// Runtime placeholders such as `{UnityEngine.AddressableAssets.Addressables.RuntimePath}`
+    /// are kept.
+    pub internal_id: String,
+    pub provider_id: String,
+    /// Resource type class name (`UnityEngine.GameObject`, ...).
+    pub resource_type: Option<String>,
+    /// Hash of the dependency list, `0` without dependencies.
+    pub dependency_hash: i32,
+    /// Set on bundle locations.
+    pub bundle_options: Option<BundleRequestOptions>,
+    /// Locations this one needs, as listed by the catalog. For bundled assets the containing
+    /// bundle comes first, then the bundles it depends on. Dependencies are not resolved further.
+    pub dependencies: Vec<ResourceLocation>,
+}
+
+impl ResourceLocation {
+    /// Whether this location is an AssetBundle.
+    pub fn is_bundle(&self) -> bool {
+        self.bundle_options.is_some() || self.provider_id == ASSET_BUNDLE_PROVIDER
+    }
+
+    /// Last path component of the internal id (the bundle file name for bundles).
+    pub fn file_name(&self) -> &str {
+        self.internal_id
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or(&self.internal_id)
+    }
+
+    /// The bundle holding this location: itself for bundles, else its first bundle dependency.
+    pub fn bundle(&self) -> Option<&ResourceLocation> {
+        if self.is_bundle() {
+            return Some(self);
+        }
+        self.dependencies.iter().find(|dep| dep.is_bundle())
+    }
+
+    /// File name of the bundle holding this location.
+    pub fn bundle_file_name(&self) -> Option<&str> {
+        self.bundle().map(ResourceLocation::file_name)
+    }
+
+    /// File names of the other bundles this location needs.
+    pub fn dependency_bundles(&self) -> Vec<&str> {
+        // For assets, the first bundle dependency is the containing bundle.
+        let own = usize::from(!self.is_bundle());
+        self.dependencies
+            .iter()
+            .filter(|dep| dep.is_bundle())
+            .skip(own)
+            .map(ResourceLocation::file_name)
+            .collect()
+    }
+}
+
+/// An object loaded through [`crate::bundle::BundleLoader::load_addressable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressableObject {
+    /// The location it was found for.
+    pub location: ResourceLocation,
+    /// Loader key of the bundle holding it.
+    pub bundle: String,
+    pub asset_index: usize,
+    /// `m_Container` path it is listed under (lowercase).
+    pub container_path: String,
+    pub file_id: i32,
+    pub path_id: i64,
+}
+
+/// Outcome of [`crate::bundle::BundleLoader::load_addressable`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AddressableLoad {
+    /// Loader keys of the bundles used, in load order (dependencies before the bundles that need
+    /// them).
+    pub bundles: Vec<String>,
+    /// Objects listed in the bundles' `m_Container` under the locations' internal ids. Several
+    /// objects can share a path (a texture and its sprite).
+    pub objects: Vec<AddressableObject>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawCatalog {
+    #[serde(rename = "m_LocatorId")]
+    locator_id: String,
+    #[serde(rename = "m_BuildResultHash")]
+    build_result_hash: String,
+    #[serde(rename = "m_ProviderIds")]
+    provider_ids: Vec<String>,
+    #[serde(rename = "m_InternalIds")]
+    internal_ids: Vec<String>,
+    #[serde(rename = "m_InternalIdPrefixes")]
+    internal_id_prefixes: Vec<String>,
+    #[serde(rename = "m_resourceTypes")]
+    resource_types: Vec<RawType>,
+    #[serde(rename = "m_KeyDataString")]
+    key_data: String,
+    #[serde(rename = "m_BucketDataString")]
+    bucket_data: String,
+    #[serde(rename = "m_EntryDataString")]
+    entry_data: String,
+    #[serde(rename = "m_ExtraDataString")]
+    extra_data: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawType {
+    #[serde(rename = "m_ClassName")]
+    class_name: String,
+}
+
+#[derive(Debug, Clone)]
+struct CatalogEntry {
+    internal_id: usize,
+    provider: usize,
+    dependency_key: Option<usize>,
+    dependency_hash: i32,
+    data: Option<CatalogKey>,
+    primary_key: usize,
+    resource_type: Option<usize>,
+}
+
+/// A parsed Addressables content catalog.
+#[derive(Debug, Clone)]
+pub struct ContentCatalog {
+    /// `m_LocatorId` (`AddressablesMainContentCatalog`).
+    pub locator_id: String,
+    /// `m_BuildResultHash`; empty in catalogs that predate it (the hash then lives in the sibling
+    /// `catalog.hash`).
+    pub build_result_hash: String,
+    pub provider_ids: Vec<String>,
+    /// Internal ids with `m_InternalIdPrefixes` expanded.
+    pub internal_ids: Vec<String>,
+    pub resource_types: Vec<String>,
+    keys: Vec<CatalogKey>,
+    buckets: Vec<Vec<usize>>,
+    entries: Vec<CatalogEntry>,
+    key_index: HashMap<CatalogKey, usize>,
+}
+
+impl ContentCatalog {
+    /// Parse a catalog from `catalog.json` bytes.
+    pub fn from_json_slice(data: &[u8]) -> Result<Self> {
+        let raw: RawCatalog = serde_json::from_slice(data).map_err(|e| {
+            BinaryError::invalid_data(format!("Invalid Addressables catalog JSON: {}", e))
+        })?;
+        Self::from_raw(raw)
+    }
+
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        Self::from_json_slice(json.as_bytes())
+    }
+
+    /// Read and parse a `catalog.json`.
+    #[cfg(feature = "fs")]
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let data = std::fs::read(path.as_ref()).map_err(|e| {
+            BinaryError::generic(format!("Failed to read catalog {:?}: {}", path.as_ref(), e))
+        })?;
+        Self::from_json_slice(&data)
+    }
+
+    fn from_raw(raw: RawCatalog) -> Result<Self> {
+        let key_data = decode_base64("m_KeyDataString", &raw.key_data)?;
+        let bucket_data = decode_base64("m_BucketDataString", &raw.bucket_data)?;
+        let entry_data = decode_base64("m_EntryDataString", &raw.entry_data)?;
+        let extra_data = decode_base64("m_ExtraDataString", &raw.extra_data)?;
+
+        let mut reader = BinaryReader::new(&bucket_data, ByteOrder::Little);
+        let bucket_count = read_count(&mut reader, "bucket")?;
+        let mut key_offsets = Vec::with_capacity(bucket_count);
+        let mut buckets = Vec::with_capacity(bucket_count);
+        for _ in 0..bucket_count {
+            key_offsets.push(read_count(&mut reader, "key offset")?);
+            let entry_count = read_count(&mut reader, "bucket entry")?;
+            let entries = (0..entry_count)
+                .map(|_| read_count(&mut reader, "bucket entry index"))
+                .collect::<Result<Vec<_>>>()?;
+            buckets.push(entries);
+        }
+
+        let keys = key_offsets
+            .iter()
+            .map(|&offset| read_object(&key_data, offset))
+            .collect::<Result<Vec<_>>>()?;
+
+        let internal_ids: Vec<String> = raw
+            .internal_ids
+            .iter()
+            .map(|id| expand_internal_id(&raw.internal_id_prefixes, id))
+            .collect();
+
+        let mut reader = BinaryReader::new(&entry_data, ByteOrder::Little);
+        let entry_count = read_count(&mut reader, "entry")?;
+        let mut entries = Vec::with_capacity(entry_count);
+        for index in 0..entry_count {
+            let mut fields = [0i32; 7];
+            for field in &mut fields {
+                *field = reader.read_i32()?;
+            }
+            let [
+                internal_id,
+                provider,
+                dependency_key,
+                dependency_hash,
+                data_offset,
+                primary_key,
+                resource_type,
+            ] = fields;
+
+            let index_in = |value: i32, len: usize, what: &str| -> Result<usize> {
+                usize::try_from(value)
+                    .ok()
+                    .filter(|&v| v < len)
+                    .ok_or_else(|| {
+                        BinaryError::invalid_data(format!(
+                            "Catalog entry {} has invalid {} index {}",
+                            index, what, value
+                        ))
+                    })
+            };
+            entries.push(CatalogEntry {
+                internal_id: index_in(internal_id, internal_ids.len(), "internal id")?,
+                provider: index_in(provider, raw.provider_ids.len(), "provider")?,
+                dependency_key: (dependency_key >= 0)
+                    .then(|| index_in(dependency_key, keys.len(), "dependency key"))
+                    .transpose()?,
+                dependency_hash,
+                data: (data_offset >= 0)
+                    .then(|| read_object(&extra_data, data_offset as usize))
+                    .transpose()?,
+                primary_key: index_in(primary_key, keys.len(), "primary key")?,
+                resource_type: usize::try_from(resource_type)
+                    .ok()
+                    .filter(|&v| v < raw.resource_types.len()),
+            });
+        }
+
+        for (bucket_index, bucket) in buckets.iter().enumerate() {
+            if let Some(&bad) = bucket.iter().find(|&&e| e >= entries.len()) {
+                return Err(BinaryError::invalid_data(format!(
+                    "Catalog bucket {} references missing entry {}",
+                    bucket_index, bad
+                )));
+            }
+        }
+
+        let mut key_index = HashMap::with_capacity(keys.len());
+        for (index, key) in keys.iter().enumerate() {
+            key_index.entry(key.clone()).or_insert(index);
+        }
+
+        Ok(Self {
+            locator_id: raw.locator_id,
+            build_result_hash: raw.build_result_hash,
+            provider_ids: raw.provider_ids,
+            internal_ids,
+            resource_types: raw
+                .resource_types
+                .into_iter()
+                .map(|t| t.class_name)
+                .collect(),
+            keys,
+            buckets,
+            entries,
+            key_index,
+        })
+    }
+
+    /// All keys (addresses, labels, GUIDs, bundle names, dependency keys).
+    pub fn keys(&self) -> &[CatalogKey] {
+        &self.keys
+    }
+
+    /// Number of locations in the catalog.
+    pub fn location_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Locations for an address, label, asset GUID or bundle name. Empty when the key is unknown.
+    pub fn resolve(&self, address: &str) -> Vec<ResourceLocation> {
+        self.resolve_key(&CatalogKey::String(address.to_string()))
+    }
+
+    /// Locations for any catalog key.
+    pub fn resolve_key(&self, key: &CatalogKey) -> Vec<ResourceLocation> {
+        let Some(&index) = self.key_index.get(key) else {
+            return Vec::new();
+        };
+        self.buckets[index]
+            .iter()
+            .map(|&entry| self.location(entry, true))
+            .collect()
+    }
+
+    /// Every bundle location, with its cache validation data.
+    pub fn bundle_locations(&self) -> Vec<ResourceLocation> {
+        (0..self.entries.len())
+            .map(|entry| self.location(entry, false))
+            .filter(ResourceLocation::is_bundle)
+            .collect()
+    }
+
+    fn location(&self, entry_index: usize, with_dependencies: bool) -> ResourceLocation {
+        let entry = &self.entries[entry_index];
+        let dependencies = match entry.dependency_key {
+            Some(key) if with_dependencies => self.buckets[key]
+                .iter()
+                .map(|&dep| self.location(dep, false))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let bundle_options = match &entry.data {
+            Some(CatalogKey::Json {
+                class_name, json, ..
+            }) if class_name.ends_with("AssetBundleRequestOptions") => {
+                BundleRequestOptions::from_json(json)
+            }
+            _ => None,
+        };
+
+        ResourceLocation {
+            primary_key: self.keys[entry.primary_key].to_string(),
+            internal_id: self.internal_ids[entry.internal_id].clone(),
+            provider_id: self.provider_ids[entry.provider].clone(),
+            resource_type: entry.resource_type.map(|t| self.resource_types[t].clone()),
+            dependency_hash: entry.dependency_hash,
+            bundle_options,
+            dependencies,
+        }
+    }
+}
+
+/// Expand a `<prefix index>#<rest>` internal id against `m_InternalIdPrefixes`.
+fn expand_internal_id(prefixes: &[String], id: &str) -> String {
+    if let Some((index, rest)) = id.rsplit_once('#')
+        && let Ok(index) = index.parse::<usize>()
+        && let Some(prefix) = prefixes.get(index)
+    {
+        return format!("{prefix}{rest}");
+    }
+    id.to_string()
+}
+
+fn decode_base64(field: &str, text: &str) -> Result<Vec<u8>> {
+    base64::engine::general_purpose::STANDARD
+        .decode(text)
+        .map_err(|e| BinaryError::invalid_data(format!("Invalid base64 in {}: {}", field, e)))
+}
+
+fn read_count(reader: &mut BinaryReader, what: &str) -> Result<usize> {
+    let value = reader.read_i32()?;
+    usize::try_from(value)
+        .map_err(|_| BinaryError::invalid_data(format!("Negative catalog {}: {}", what, value)))
+}
+
+/// Read one tagged value (`SerializationUtilities.ReadObjectFromByteArray`).
+fn read_object(data: &[u8], offset: usize) -> Result<CatalogKey> {
+    let mut reader = BinaryReader::new(data, ByteOrder::Little);
+    reader.set_position(offset as u64)?;
+    let ascii = |bytes: &[u8]| String::from_utf8_lossy(bytes).into_owned();
+
+    match reader.read_u8()? {
+        0 => {
+            let len = read_count(&mut reader, "string length")?;
+            Ok(CatalogKey::String(ascii(reader.read_bytes_ref(len)?)))
+        }
+        1 => {
+            let len = read_count(&mut reader, "string length")?;
+            Ok(CatalogKey::String(utf16(reader.read_bytes_ref(len)?)))
+        }
+        2 => Ok(CatalogKey::Int(reader.read_u16()? as i64)),
+        3 => Ok(CatalogKey::Int(reader.read_u32()? as i64)),
+        4 => Ok(CatalogKey::Int(reader.read_i32()? as i64)),
+        5 => {
+            let len = reader.read_u8()? as usize;
+            Ok(CatalogKey::Hash128(ascii(reader.read_bytes_ref(len)?)))
+        }
+        6 => {
+            let len = reader.read_u8()? as usize;
+            Ok(CatalogKey::Type(ascii(reader.read_bytes_ref(len)?)))
+        }
+        7 => {
+            let len = reader.read_u8()? as usize;
+            let assembly_name = ascii(reader.read_bytes_ref(len)?);
+            let len = reader.read_u8()? as usize;
+            let class_name = ascii(reader.read_bytes_ref(len)?);
+            let len = read_count(&mut reader, "JSON length")?;
+            let json = utf16(reader.read_bytes_ref(len)?);
+            Ok(CatalogKey::Json {
+                assembly_name,
+                class_name,
+                json,
+            })
+        }
+        tag => Err(BinaryError::invalid_data(format!(
+            "Unknown catalog object type {} at offset {}",
+            tag, offset
+        ))),
+    }
+}
+
+fn utf16(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// `m_Container` entries of the AssetBundle objects in `asset`, as `(path, file_id, path_id)`.
+///
+/// Read through the TypeTree when present, else with the raw layout fallback.
+pub(crate) fn container_entries(asset: &SerializedFile) -> Vec<(String, i32, i64)> {
+    let mut out = Vec::new();
+    for handle in asset.object_handles() {
+        if handle.class_id() != class_ids::ASSET_BUNDLE {
+            continue;
+        }
+        let from_typetree: Vec<_> = handle
+            .read()
+            .ok()
+            .and_then(|object| match object.class.get("m_Container") {
+                // `vector<pair<string, AssetInfo>>`
+                Some(UnityValue::Array(items)) => Some(
+                    items
+                        .iter()
+                        .filter_map(|item| {
+                            let UnityValue::Array(pair) = item else {
+                                return None;
+                            };
+                            let [path, info] = pair.as_slice() else {
+                                return None;
+                            };
+                            container_entry(path.as_str()?, info)
+                        })
+                        .collect(),
+                ),
+                // `map<string, AssetInfo>` read as a dictionary
+                Some(UnityValue::Object(map)) => Some(
+                    map.iter()
+                        .filter_map(|(path, info)| container_entry(path, info))
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .unwrap_or_default();
+        if !from_typetree.is_empty() {
+            out.extend(from_typetree);
+        } else if let Ok(raw) = asset.assetbundle_container_raw(handle.info()) {
+            out.extend(raw);
+        }
+    }
+    out
+}
+
+/// One `m_Container` value: an `AssetInfo { preloadIndex, preloadSize, asset }` or, in some
+/// versions, the `PPtr` itself.
+fn container_entry(path: &str, info: &UnityValue) -> Option<(String, i32, i64)> {
+    let pptr = match info {
+        UnityValue::Object(fields) if fields.contains_key("asset") => parse_pptr(&fields["asset"]),
+        _ => parse_pptr(info),
+    }?;
+    Some((path.to_string(), pptr.file_id, pptr.path_id))
+}
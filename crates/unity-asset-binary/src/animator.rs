@@ -0,0 +1,643 @@
+//! AnimatorController (class 91) state machine parsing.
+//!
+//! Built players store an AnimatorController as a runtime `ControllerConstant` (`m_Controller`)
+//! in which every name is a CRC32 hash; `m_TOS` maps the hashes back to strings. [`AnimatorController`]
+//! flattens the constant into layers, state machines, states, transitions, conditions and blend
+//! trees with the names resolved, and serializes to JSON for diffing controllers between builds.
+//!
+//! Arrays of the constant are `OffsetPtr`s (`{ data: ... }` in the typetree); both that form and
+//! the bare element are accepted. Layout differences between versions are handled by field
+//! presence: before 5.5 parameters carry an extra `m_TypeID` hash next to `m_Type`, blend event
+//! indices point at a per-tree `m_BlendEventArrayConstant` before 4.5 and at the controller's
+//! `m_Values` after, and 1D thresholds live on the node before 4.1 and in `m_Blend1dData` after.
+//!
+//! Hashes without a `m_TOS` entry are kept as their decimal value, so output stays stable.
+
+use crate::error::{BinaryError, Result};
+use crate::unity_objects::ObjectRef;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use unity_asset_core::UnityValue;
+
+pub(crate) type Fields = IndexMap<String, UnityValue>;
+
+/// Destination index values at or above this refer to sub-state machines, not states.
+pub const STATE_MACHINE_DESTINATION_BASE: u32 = 30000;
+
+/// AnimatorController parameter type (`AnimatorControllerParameterType`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnimatorParameterType {
+    Float,
+    Int,
+    Bool,
+    Trigger,
+    Unknown(u32),
+}
+
+impl AnimatorParameterType {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => Self::Float,
+            3 => Self::Int,
+            4 => Self::Bool,
+            9 => Self::Trigger,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+/// Default value of a parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AnimatorParameterValue {
+    Float(f32),
+    Int(i32),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimatorParameter {
+    pub name: String,
+    pub name_hash: u32,
+    pub kind: AnimatorParameterType,
+    pub default: Option<AnimatorParameterValue>,
+}
+
+/// Transition condition mode (`AnimatorConditionMode`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnimatorConditionMode {
+    If,
+    IfNot,
+    Greater,
+    Less,
+    /// Pre-5.0 exit-time condition.
+    ExitTime,
+    Equals,
+    NotEqual,
+    Unknown(u32),
+}
+
+impl AnimatorConditionMode {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => Self::If,
+            2 => Self::IfNot,
+            3 => Self::Greater,
+            4 => Self::Less,
+            5 => Self::ExitTime,
+            6 => Self::Equals,
+            7 => Self::NotEqual,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimatorCondition {
+    pub parameter: String,
+    pub mode: AnimatorConditionMode,
+    pub threshold: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimatorTransition {
+    /// Name of the destination state, when it is a state of the same state machine.
+    pub destination: Option<String>,
+    /// Raw `m_DestinationState` (see [`STATE_MACHINE_DESTINATION_BASE`]).
+    pub destination_index: u32,
+    pub conditions: Vec<AnimatorCondition>,
+    pub duration: f32,
+    pub offset: f32,
+    pub has_exit_time: bool,
+    pub exit_time: f32,
+    pub has_fixed_duration: bool,
+    pub can_transition_to_self: bool,
+}
+
+/// Blend tree type (`BlendTreeType`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlendTreeType {
+    Simple1D,
+    SimpleDirectional2D,
+    FreeformDirectional2D,
+    FreeformCartesian2D,
+    Direct,
+    Unknown(u32),
+}
+
+impl BlendTreeType {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => Self::Simple1D,
+            1 => Self::SimpleDirectional2D,
+            2 => Self::FreeformDirectional2D,
+            3 => Self::FreeformCartesian2D,
+            4 => Self::Direct,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+/// What a state (or blend tree child) plays.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnimatorMotion {
+    None,
+    /// `index` into `m_AnimationClips`; `clip` is the reference stored there.
+    Clip {
+        index: u32,
+        clip: Option<ObjectRef>,
+    },
+    BlendTree(BlendTree),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlendTree {
+    pub blend_type: BlendTreeType,
+    pub parameter: Option<String>,
+    /// Second parameter of 2D blend trees.
+    pub parameter_y: Option<String>,
+    pub children: Vec<BlendTreeChild>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlendTreeChild {
+    pub motion: AnimatorMotion,
+    /// 1D threshold.
+    pub threshold: Option<f32>,
+    /// 2D position.
+    pub position: Option<[f32; 2]>,
+    /// Direct blend parameter.
+    pub parameter: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimatorState {
+    pub name: String,
+    /// Full path, e.g. `Base Layer.Locomotion.Run`.
+    pub full_path: String,
+    pub tag: Option<String>,
+    pub speed: f32,
+    pub cycle_offset: f32,
+    pub write_defaults: bool,
+    pub motion: AnimatorMotion,
+    pub transitions: Vec<AnimatorTransition>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnimatorStateMachine {
+    pub default_state: Option<String>,
+    pub states: Vec<AnimatorState>,
+    pub any_state_transitions: Vec<AnimatorTransition>,
+}
+
+impl AnimatorStateMachine {
+    /// Transitions out of every state plus the Any State transitions.
+    pub fn transition_count(&self) -> usize {
+        self.any_state_transitions.len()
+            + self
+                .states
+                .iter()
+                .map(|s| s.transitions.len())
+                .sum::<usize>()
+    }
+
+    pub fn state(&self, name: &str) -> Option<&AnimatorState> {
+        self.states.iter().find(|s| s.name == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimatorLayer {
+    pub name: String,
+    /// `0` override, `1` additive.
+    pub blending_mode: u32,
+    pub default_weight: f32,
+    /// Index into the controller's state machine array; synced layers share their source's.
+    pub state_machine_index: u32,
+    pub state_machine: AnimatorStateMachine,
+}
+
+/// A parsed AnimatorController (class 91).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnimatorController {
+    pub name: String,
+    pub parameters: Vec<AnimatorParameter>,
+    pub layers: Vec<AnimatorLayer>,
+    pub animation_clips: Vec<ObjectRef>,
+}
+
+impl AnimatorController {
+    /// Parse AnimatorController from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        let controller = properties
+            .get("m_Controller")
+            .and_then(deref)
+            .ok_or_else(|| BinaryError::invalid_data("AnimatorController has no m_Controller"))?;
+
+        let parser = Parser {
+            tos: parse_tos(properties.get("m_TOS")),
+            clips: match properties.get("m_AnimationClips") {
+                Some(UnityValue::Array(items)) => items.iter().map(pptr).collect(),
+                _ => Vec::new(),
+            },
+            values: controller
+                .get("m_Values")
+                .and_then(deref)
+                .map(|values| {
+                    array(values, "m_ValueArray")
+                        .map(|v| uint(v, "m_ID"))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+
+        let state_machines: Vec<AnimatorStateMachine> = array(controller, "m_StateMachineArray")
+            .map(|sm| parser.state_machine(sm))
+            .collect();
+
+        let layers = array(controller, "m_LayerArray")
+            .map(|layer| {
+                let index = uint(layer, "m_StateMachineIndex");
+                AnimatorLayer {
+                    name: parser.name(uint(layer, "m_Binding")),
+                    blending_mode: uint(layer, "m_LayerBlendingMode"),
+                    default_weight: float(layer, "m_DefaultWeight"),
+                    state_machine_index: index,
+                    state_machine: state_machines
+                        .get(index as usize)
+                        .cloned()
+                        .unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            name: properties
+                .get("m_Name")
+                .and_then(UnityValue::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            parameters: parser.parameters(controller),
+            layers,
+            animation_clips: parser.clips.iter().flatten().copied().collect(),
+        })
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&AnimatorLayer> {
+        self.layers.iter().find(|l| l.name == name)
+    }
+
+    pub fn parameter(&self, name: &str) -> Option<&AnimatorParameter> {
+        self.parameters.iter().find(|p| p.name == name)
+    }
+}
+
+struct Parser {
+    tos: HashMap<u32, String>,
+    /// `m_AnimationClips`, keeping null entries so indices line up.
+    clips: Vec<Option<ObjectRef>>,
+    /// Parameter hashes in `m_Values` order, which blend event indices refer to.
+    values: Vec<u32>,
+}
+
+impl Parser {
+    fn name(&self, hash: u32) -> String {
+        self.tos
+            .get(&hash)
+            .cloned()
+            .unwrap_or_else(|| hash.to_string())
+    }
+
+    fn optional_name(&self, hash: u32) -> Option<String> {
+        (hash != 0).then(|| self.name(hash))
+    }
+
+    fn parameters(&self, controller: &Fields) -> Vec<AnimatorParameter> {
+        let defaults = controller.get("m_DefaultValues").and_then(deref);
+        let default_array = |key: &str| -> &[UnityValue] {
+            match defaults.and_then(|d| d.get(key)) {
+                Some(UnityValue::Array(items)) => items,
+                _ => &[],
+            }
+        };
+        let (floats, ints, bools) = (
+            default_array("m_FloatValues"),
+            default_array("m_IntValues"),
+            default_array("m_BoolValues"),
+        );
+
+        let Some(values) = controller.get("m_Values").and_then(deref) else {
+            return Vec::new();
+        };
+        array(values, "m_ValueArray")
+            .map(|value| {
+                let name_hash = uint(value, "m_ID");
+                let kind_raw = value
+                    .get("m_Type")
+                    .or_else(|| value.get("m_TypeID"))
+                    .and_then(UnityValue::as_i64)
+                    .unwrap_or(0) as u32;
+                let kind = AnimatorParameterType::from_u32(kind_raw);
+                let index = uint(value, "m_Index") as usize;
+                let default = match kind {
+                    AnimatorParameterType::Float => floats
+                        .get(index)
+                        .and_then(UnityValue::as_f64)
+                        .map(|v| AnimatorParameterValue::Float(v as f32)),
+                    AnimatorParameterType::Int => ints
+                        .get(index)
+                        .and_then(UnityValue::as_i64)
+                        .map(|v| AnimatorParameterValue::Int(v as i32)),
+                    AnimatorParameterType::Bool | AnimatorParameterType::Trigger => bools
+                        .get(index)
+                        .and_then(as_bool)
+                        .map(AnimatorParameterValue::Bool),
+                    AnimatorParameterType::Unknown(_) => None,
+                };
+                AnimatorParameter {
+                    name: self.name(name_hash),
+                    name_hash,
+                    kind,
+                    default,
+                }
+            })
+            .collect()
+    }
+
+    fn state_machine(&self, sm: &Fields) -> AnimatorStateMachine {
+        let state_constants: Vec<&Fields> = array(sm, "m_StateConstantArray").collect();
+        let state_names: Vec<String> = state_constants
+            .iter()
+            .map(|s| self.name(uint(s, "m_NameID")))
+            .collect();
+
+        let states = state_constants
+            .iter()
+            .zip(&state_names)
+            .map(|(state, name)| AnimatorState {
+                name: name.clone(),
+                full_path: self.name(uint(state, "m_FullPathID")),
+                tag: self.optional_name(uint(state, "m_TagID")),
+                speed: state
+                    .get("m_Speed")
+                    .and_then(UnityValue::as_f64)
+                    .unwrap_or(1.0) as f32,
+                cycle_offset: float(state, "m_CycleOffset"),
+                write_defaults: state
+                    .get("m_WriteDefaultValues")
+                    .and_then(as_bool)
+                    .unwrap_or(true),
+                motion: array(state, "m_BlendTreeConstantArray")
+                    .next()
+                    .map(|tree| self.blend_tree_constant(tree))
+                    .unwrap_or(AnimatorMotion::None),
+                transitions: array(state, "m_TransitionConstantArray")
+                    .map(|t| self.transition(t, &state_names))
+                    .collect(),
+            })
+            .collect();
+
+        let default_state = sm
+            .get("m_DefaultState")
+            .and_then(UnityValue::as_i64)
+            .and_then(|i| state_names.get(i as usize).cloned());
+
+        AnimatorStateMachine {
+            default_state,
+            states,
+            any_state_transitions: array(sm, "m_AnyStateTransitionConstantArray")
+                .map(|t| self.transition(t, &state_names))
+                .collect(),
+        }
+    }
+
+    fn transition(&self, transition: &Fields, state_names: &[String]) -> AnimatorTransition {
+        let destination_index = uint(transition, "m_DestinationState");
+        AnimatorTransition {
+            destination: (destination_index < STATE_MACHINE_DESTINATION_BASE)
+                .then(|| state_names.get(destination_index as usize).cloned())
+                .flatten(),
+            destination_index,
+            conditions: array(transition, "m_ConditionConstantArray")
+                .map(|c| AnimatorCondition {
+                    parameter: self.name(uint(c, "m_EventID")),
+                    mode: AnimatorConditionMode::from_u32(uint(c, "m_ConditionMode")),
+                    threshold: float(c, "m_EventThreshold"),
+                })
+                .collect(),
+            duration: float(transition, "m_TransitionDuration"),
+            offset: float(transition, "m_TransitionOffset"),
+            has_exit_time: transition
+                .get("m_HasExitTime")
+                .and_then(as_bool)
+                .unwrap_or(false),
+            exit_time: float(transition, "m_ExitTime"),
+            has_fixed_duration: transition
+                .get("m_HasFixedDuration")
+                .and_then(as_bool)
+                .unwrap_or(false),
+            can_transition_to_self: transition
+                .get("m_CanTransitionToSelf")
+                .and_then(as_bool)
+                .unwrap_or(true),
+        }
+    }
+
+    /// The motion of a state: node 0 of its blend tree constant is the root.
+    fn blend_tree_constant(&self, tree: &Fields) -> AnimatorMotion {
+        let nodes: Vec<&Fields> = array(tree, "m_NodeArray").collect();
+        let events: Option<Vec<u32>> =
+            tree.get("m_BlendEventArrayConstant")
+                .and_then(deref)
+                .map(|values| {
+                    array(values, "m_ValueArray")
+                        .map(|v| uint(v, "m_ID"))
+                        .collect()
+                });
+        if nodes.is_empty() {
+            return AnimatorMotion::None;
+        }
+        self.node(&nodes, events.as_deref().unwrap_or(&self.values), 0, 0)
+    }
+
+    fn node(
+        &self,
+        nodes: &[&Fields],
+        events: &[u32],
+        index: usize,
+        depth: usize,
+    ) -> AnimatorMotion {
+        let Some(node) = nodes.get(index) else {
+            return AnimatorMotion::None;
+        };
+        let children: Vec<u32> = uint_array(node.get("m_ChildIndices"));
+        if children.is_empty() || depth > nodes.len() {
+            let clip = uint(node, "m_ClipID");
+            return if clip == u32::MAX {
+                AnimatorMotion::None
+            } else {
+                AnimatorMotion::Clip {
+                    index: clip,
+                    clip: self.clips.get(clip as usize).copied().flatten(),
+                }
+            };
+        }
+
+        let event_name = |key: &str| {
+            node.get(key)
+                .and_then(UnityValue::as_i64)
+                .and_then(|i| events.get(i as usize))
+                .map(|hash| self.name(*hash))
+        };
+        let blend_type = BlendTreeType::from_u32(uint(node, "m_BlendType"));
+        let thresholds: Vec<f32> = node
+            .get("m_Blend1dData")
+            .and_then(deref)
+            .and_then(|d| d.get("m_ChildThresholdArray"))
+            .or_else(|| node.get("m_ChildThresholdArray"))
+            .map(float_array)
+            .unwrap_or_default();
+        let positions: Vec<[f32; 2]> = match node
+            .get("m_Blend2dData")
+            .and_then(deref)
+            .and_then(|d| d.get("m_ChildPositionArray"))
+        {
+            Some(UnityValue::Array(items)) => items
+                .iter()
+                .filter_map(UnityValue::as_object)
+                .map(|v| [float(v, "x"), float(v, "y")])
+                .collect(),
+            _ => Vec::new(),
+        };
+        let direct: Vec<u32> = node
+            .get("m_BlendDirectData")
+            .and_then(deref)
+            .map(|d| uint_array(d.get("m_ChildBlendEventIDArray")))
+            .unwrap_or_default();
+        let is_2d = matches!(
+            blend_type,
+            BlendTreeType::SimpleDirectional2D
+                | BlendTreeType::FreeformDirectional2D
+                | BlendTreeType::FreeformCartesian2D
+        );
+
+        AnimatorMotion::BlendTree(BlendTree {
+            blend_type,
+            parameter: event_name("m_BlendEventID"),
+            parameter_y: if is_2d {
+                event_name("m_BlendEventYID")
+            } else {
+                None
+            },
+            children: children
+                .iter()
+                .enumerate()
+                .map(|(i, &child)| BlendTreeChild {
+                    motion: self.node(nodes, events, child as usize, depth + 1),
+                    threshold: (blend_type == BlendTreeType::Simple1D)
+                        .then(|| thresholds.get(i).copied())
+                        .flatten(),
+                    position: if is_2d {
+                        positions.get(i).copied()
+                    } else {
+                        None
+                    },
+                    parameter: (blend_type == BlendTreeType::Direct)
+                        .then(|| direct.get(i).and_then(|e| events.get(*e as usize)))
+                        .flatten()
+                        .map(|hash| self.name(*hash)),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// `m_TOS` as `map<unsigned int, string>`: `[key, value]` pairs or `{first, second}`.
+pub(crate) fn parse_tos(value: Option<&UnityValue>) -> HashMap<u32, String> {
+    let Some(UnityValue::Array(pairs)) = value else {
+        return HashMap::new();
+    };
+    pairs
+        .iter()
+        .filter_map(|pair| {
+            let (key, value) = match pair {
+                UnityValue::Array(kv) if kv.len() == 2 => (&kv[0], &kv[1]),
+                UnityValue::Object(obj) => (obj.get("first")?, obj.get("second")?),
+                _ => return None,
+            };
+            Some((key.as_i64()? as u32, value.as_str()?.to_string()))
+        })
+        .collect()
+}
+
+/// The pointee of an `OffsetPtr` (`{ data: ... }`), or the object itself.
+pub(crate) fn deref(value: &UnityValue) -> Option<&Fields> {
+    let fields = value.as_object()?;
+    match fields.get("data") {
+        Some(UnityValue::Object(data)) if fields.len() == 1 => Some(data),
+        _ => Some(fields),
+    }
+}
+
+pub(crate) fn array<'a>(fields: &'a Fields, key: &str) -> impl Iterator<Item = &'a Fields> {
+    let items: &[UnityValue] = match fields.get(key) {
+        Some(UnityValue::Array(items)) => items,
+        _ => &[],
+    };
+    items.iter().filter_map(deref)
+}
+
+pub(crate) fn uint(fields: &Fields, key: &str) -> u32 {
+    fields.get(key).and_then(UnityValue::as_i64).unwrap_or(0) as u32
+}
+
+pub(crate) fn float(fields: &Fields, key: &str) -> f32 {
+    fields.get(key).and_then(UnityValue::as_f64).unwrap_or(0.0) as f32
+}
+
+pub(crate) fn as_bool(value: &UnityValue) -> Option<bool> {
+    match value {
+        UnityValue::Bool(b) => Some(*b),
+        UnityValue::Integer(i) => Some(*i != 0),
+        _ => None,
+    }
+}
+
+pub(crate) fn uint_array(value: Option<&UnityValue>) -> Vec<u32> {
+    match value {
+        Some(UnityValue::Array(items)) => items
+            .iter()
+            .filter_map(UnityValue::as_i64)
+            .map(|v| v as u32)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+pub(crate) fn float_array(value: &UnityValue) -> Vec<f32> {
+    match value {
+        UnityValue::Array(items) => items
+            .iter()
+            .filter_map(UnityValue::as_f64)
+            .map(|v| v as f32)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn pptr(value: &UnityValue) -> Option<ObjectRef> {
+    let fields = value.as_object()?;
+    let file_id = fields.get("m_FileID").and_then(UnityValue::as_i64)?;
+    let path_id = fields.get("m_PathID").and_then(UnityValue::as_i64)?;
+    let obj_ref = ObjectRef::new(file_id as i32, path_id);
+    (!obj_ref.is_null()).then_some(obj_ref)
+}
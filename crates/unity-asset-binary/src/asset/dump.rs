@@ -0,0 +1,400 @@
+//! Raw object payload dumping
+//!
+//! Writes each object's raw bytes to `{class}/{path_id}.bin` under an output directory, plus an
+//! `index.csv` describing what was written. Intended for feeding external analyzers, so no
+//! TypeTree parsing is involved. [`SerializedFile::dump_raw_objects_with_hooks`] lets
+//! [`ObjectHook`](crate::hooks::ObjectHook)s skip or replace payloads and emit extra files.
+//!
+//! ```rust,no_run
+//! use unity_asset_binary::asset::{DumpFilter, SerializedFileParser};
+//!
+//! let file = SerializedFileParser::from_bytes(std::fs::read("level0")?)?;
+//! let filter = DumpFilter {
+//!     classes: vec!["MonoBehaviour".to_string(), "49".to_string()],
+//!     max_object_size: Some(1 << 20),
+//!     ..Default::default()
+//! };
+//! let report = file.dump_raw_objects("out", &filter)?;
+//! println!("wrote {} objects ({} bytes)", report.written.len(), report.bytes_written);
+//! # Ok::<(), unity_asset_binary::error::BinaryError>(())
+//! ```
+
+use super::parser::SerializedFile;
+use crate::bundle::AssetBundle;
+use crate::error::{BinaryError, Result};
+use crate::hooks::{
+    HookAction, HookArtifact, HookOutput, HookRegistry, ObjectContext, Replacement,
+};
+use crate::object::ObjectHandle;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Name of the index written next to the dumped payloads.
+pub const DUMP_INDEX_FILE_NAME: &str = "index.csv";
+
+/// Selection and size limits for [`SerializedFile::dump_raw_objects`].
+#[derive(Debug, Clone, Default)]
+pub struct DumpFilter {
+    /// Classes to include, as class names (case-insensitive) or numeric class ids.
+    /// Empty means every class.
+    pub classes: Vec<String>,
+    /// Skip objects whose payload is larger than this many bytes.
+    pub max_object_size: Option<u64>,
+    /// Stop once writing the next object would exceed this many bytes in total.
+    pub max_total_size: Option<u64>,
+}
+
+impl DumpFilter {
+    fn matches(&self, class_id: i32, class_name: &str) -> bool {
+        self.classes.is_empty()
+            || self.classes.iter().any(|c| {
+                let c = c.trim();
+                c.eq_ignore_ascii_case(class_name) || c.parse::<i32>() == Ok(class_id)
+            })
+    }
+}
+
+/// One row of `index.csv`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpedObject {
+    pub class_id: i32,
+    pub class_name: String,
+    pub path_id: i64,
+    pub size: u64,
+    /// Offset of the payload within the SerializedFile.
+    pub offset: u64,
+    /// Lowercase hex SHA-256 of the payload.
+    pub sha256: String,
+    /// Written file, relative to the output directory.
+    pub file: PathBuf,
+}
+
+/// Outcome of a dump.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DumpReport {
+    /// Written objects, in object-table order.
+    pub written: Vec<DumpedObject>,
+    /// Objects excluded by [`DumpFilter::classes`].
+    pub skipped_by_class: usize,
+    /// Objects larger than [`DumpFilter::max_object_size`].
+    pub skipped_oversize: usize,
+    /// Matching objects not written because [`DumpFilter::max_total_size`] was reached.
+    pub skipped_total_cap: usize,
+    /// Objects a hook returned [`HookAction::Skip`] for.
+    pub skipped_by_hook: usize,
+    /// Written objects whose payload came from [`HookAction::Replace`].
+    pub replaced_by_hook: usize,
+    /// Files emitted by hooks, relative to the output directory.
+    pub artifacts: Vec<PathBuf>,
+    /// Sum of written payload sizes.
+    pub bytes_written: u64,
+}
+
+impl DumpReport {
+    fn merge(&mut self, other: DumpReport) {
+        self.written.extend(other.written);
+        self.skipped_by_class += other.skipped_by_class;
+        self.skipped_oversize += other.skipped_oversize;
+        self.skipped_total_cap += other.skipped_total_cap;
+        self.skipped_by_hook += other.skipped_by_hook;
+        self.replaced_by_hook += other.replaced_by_hook;
+        self.artifacts.extend(other.artifacts);
+        self.bytes_written += other.bytes_written;
+    }
+}
+
+impl SerializedFile {
+    /// Dump raw object payloads to `out_dir/{class}/{path_id}.bin` with an `index.csv`.
+    ///
+    /// The index has the columns `class,path_id,size,offset,sha256`. Directories are created as
+    /// needed; existing files with the same names are overwritten.
+    pub fn dump_raw_objects<P: AsRef<Path>>(
+        &self,
+        out_dir: P,
+        filter: &DumpFilter,
+    ) -> Result<DumpReport> {
+        self.dump_raw_objects_with_hooks(out_dir, filter, &mut HookRegistry::new())
+    }
+
+    /// [`dump_raw_objects`](Self::dump_raw_objects), running `hooks` on every object that
+    /// passes the class and size filters.
+    ///
+    /// [`HookAction::Skip`] leaves the object out; [`HookAction::Replace`] writes the
+    /// replacement instead of the raw payload (`.json` for [`Replacement::Value`]), and the
+    /// index row describes what was written. Files emitted through
+    /// [`ObjectContext::output`] are written under `out_dir` even when the object is skipped.
+    pub fn dump_raw_objects_with_hooks<P: AsRef<Path>>(
+        &self,
+        out_dir: P,
+        filter: &DumpFilter,
+        hooks: &mut HookRegistry,
+    ) -> Result<DumpReport> {
+        self.dump_with_hooks(out_dir.as_ref(), filter, hooks, None)
+    }
+
+    fn dump_with_hooks(
+        &self,
+        out_dir: &Path,
+        filter: &DumpFilter,
+        hooks: &mut HookRegistry,
+        asset_name: Option<&str>,
+    ) -> Result<DumpReport> {
+        std::fs::create_dir_all(out_dir)?;
+
+        let mut report = DumpReport::default();
+        let mut total_cap_reached = false;
+        let output = HookOutput::new();
+
+        for info in &self.objects {
+            let class_id = info.type_id;
+            let class_name = unity_asset_core::get_class_name(class_id)
+                .unwrap_or_else(|| format!("Class_{}", class_id));
+
+            if !filter.matches(class_id, &class_name) {
+                report.skipped_by_class += 1;
+                continue;
+            }
+
+            if filter
+                .max_object_size
+                .is_some_and(|max| info.byte_size as u64 > max)
+            {
+                report.skipped_oversize += 1;
+                continue;
+            }
+
+            let action = if hooks.is_empty() {
+                HookAction::Continue
+            } else {
+                let ctx = ObjectContext::new(ObjectHandle::new(self, info), asset_name, &output);
+                let action = hooks.dispatch(&ctx);
+                report
+                    .artifacts
+                    .extend(write_artifacts(out_dir, output.take())?);
+                action
+            };
+
+            let (bytes, extension): (Cow<'_, [u8]>, &str) = match &action {
+                HookAction::Continue => (Cow::Borrowed(self.object_bytes(info)?), "bin"),
+                HookAction::Skip => {
+                    report.skipped_by_hook += 1;
+                    continue;
+                }
+                HookAction::Replace(Replacement::Bytes(bytes)) => (Cow::Borrowed(bytes), "bin"),
+                HookAction::Replace(Replacement::Value(value)) => (
+                    Cow::Owned(
+                        serde_json::to_vec_pretty(value)
+                            .map_err(|e| BinaryError::generic(e.to_string()))?,
+                    ),
+                    "json",
+                ),
+            };
+
+            let size = bytes.len() as u64;
+            if total_cap_reached
+                || filter
+                    .max_total_size
+                    .is_some_and(|max| report.bytes_written + size > max)
+            {
+                total_cap_reached = true;
+                report.skipped_total_cap += 1;
+                continue;
+            }
+
+            if matches!(action, HookAction::Replace(_)) {
+                report.replaced_by_hook += 1;
+            }
+            let dir_name = sanitize_file_component(&class_name);
+            let file = Path::new(&dir_name).join(format!("{}.{}", info.path_id, extension));
+            std::fs::create_dir_all(out_dir.join(&dir_name))?;
+            std::fs::write(out_dir.join(&file), &bytes)?;
+
+            report.bytes_written += size;
+            report.written.push(DumpedObject {
+                class_id,
+                class_name,
+                path_id: info.path_id,
+                size,
+                offset: info.byte_start,
+                sha256: sha256_hex(&bytes),
+                file,
+            });
+        }
+
+        write_index(&out_dir.join(DUMP_INDEX_FILE_NAME), &report.written)?;
+        Ok(report)
+    }
+}
+
+impl AssetBundle {
+    /// Dump raw object payloads of every SerializedFile in the bundle.
+    ///
+    /// Each file goes to `out_dir/{asset_name}/` (suffixed when a name repeats, see
+    /// [`AssetBundle::unique_asset_names`]) with its own `index.csv` (see
+    /// [`SerializedFile::dump_raw_objects`]); [`DumpFilter::max_total_size`] applies to the
+    /// bundle as a whole. [`DumpedObject::file`] paths in the returned report are relative to
+    /// `out_dir`.
+    pub fn dump_raw_objects<P: AsRef<Path>>(
+        &self,
+        out_dir: P,
+        filter: &DumpFilter,
+    ) -> Result<DumpReport> {
+        self.dump_raw_objects_with_hooks(out_dir, filter, &mut HookRegistry::new())
+    }
+
+    /// [`dump_raw_objects`](Self::dump_raw_objects) with hooks, see
+    /// [`SerializedFile::dump_raw_objects_with_hooks`]. Hooks see the asset name through
+    /// [`ObjectContext::asset_name`]; emitted files go to the asset's directory.
+    pub fn dump_raw_objects_with_hooks<P: AsRef<Path>>(
+        &self,
+        out_dir: P,
+        filter: &DumpFilter,
+        hooks: &mut HookRegistry,
+    ) -> Result<DumpReport> {
+        let out_dir = out_dir.as_ref();
+        let mut report = DumpReport::default();
+
+        let asset_names = self.unique_asset_names();
+        for (index, asset) in self.assets.iter().enumerate() {
+            let name = asset_names
+                .get(index)
+                .map(|n| sanitize_file_component(n))
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| format!("asset_{}", index));
+
+            let remaining = filter
+                .max_total_size
+                .map(|max| max.saturating_sub(report.bytes_written));
+            let file_filter = DumpFilter {
+                max_total_size: remaining,
+                ..filter.clone()
+            };
+
+            let asset_name = asset_names.get(index).map(String::as_str);
+            let mut file_report =
+                asset.dump_with_hooks(&out_dir.join(&name), &file_filter, hooks, asset_name)?;
+            for obj in &mut file_report.written {
+                obj.file = Path::new(&name).join(&obj.file);
+            }
+            for artifact in &mut file_report.artifacts {
+                *artifact = Path::new(&name).join(&*artifact);
+            }
+            report.merge(file_report);
+        }
+
+        Ok(report)
+    }
+}
+
+fn write_index(path: &Path, rows: &[DumpedObject]) -> Result<()> {
+    let mut out = String::from("class,path_id,size,offset,sha256\n");
+    for row in rows {
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            csv_field(&row.class_name),
+            row.path_id,
+            row.size,
+            row.offset,
+            row.sha256
+        )
+        .map_err(|e| BinaryError::generic(e.to_string()))?;
+    }
+    std::fs::write(path, out)?;
+    Ok(())
+}
+
+/// Write hook artifacts under `out_dir`, returning their relative paths.
+fn write_artifacts(out_dir: &Path, artifacts: Vec<HookArtifact>) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::with_capacity(artifacts.len());
+    for artifact in artifacts {
+        let relative: PathBuf = artifact
+            .name
+            .split(['/', '\\'])
+            .map(sanitize_file_component)
+            .filter(|c| !c.is_empty())
+            .collect();
+        if relative.as_os_str().is_empty() {
+            return Err(BinaryError::invalid_data(format!(
+                "hook artifact name {:?} has no usable path component",
+                artifact.name
+            )));
+        }
+        let path = out_dir.join(&relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, &artifact.bytes)?;
+        written.push(relative);
+    }
+    Ok(written)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn sanitize_file_component(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim_start_matches('.')
+        .to_string()
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    let digest = Sha256::digest(bytes);
+    let mut out = String::with_capacity(digest.len() * 2);
+    for b in digest {
+        let _ = write!(out, "{:02x}", b);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_matches_names_and_ids() {
+        let filter = DumpFilter {
+            classes: vec!["texture2d".to_string(), " 49 ".to_string()],
+            ..Default::default()
+        };
+        assert!(filter.matches(28, "Texture2D"));
+        assert!(filter.matches(49, "TextAsset"));
+        assert!(!filter.matches(1, "GameObject"));
+        assert!(DumpFilter::default().matches(1, "GameObject"));
+    }
+
+    #[test]
+    fn sanitize_and_csv_escaping() {
+        assert_eq!(
+            sanitize_file_component("CAB-8579bc75.resource"),
+            "CAB-8579bc75.resource"
+        );
+        assert_eq!(sanitize_file_component("../x/y"), "_x_y");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("plain"), "plain");
+    }
+
+    #[test]
+    fn sha256_of_empty_input() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+}
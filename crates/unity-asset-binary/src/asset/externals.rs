@@ -0,0 +1,283 @@
+//! Editing the externals table of a SerializedFile.
+//!
+//! A `PPtr` with `m_FileID = n > 0` points into `externals[n - 1]`, and script types refer to
+//! externals the same way. Removing or moving an external changes the file ids of other slots,
+//! so every object's `PPtr`s are found with the TypeTree walker and patched in place. Patched
+//! bytes are stored in [`ObjectInfo::data`](super::ObjectInfo::data), which object reads and the
+//! writer prefer over the original file bytes.
+
+use super::parser::SerializedFile;
+use super::types::FileIdentifier;
+use crate::error::{BinaryError, Result};
+use crate::reader::ByteOrder;
+
+/// What [`SerializedFile::remove_external`] does when the removed external is still referenced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DanglingReferences {
+    /// Fail without changing anything.
+    #[default]
+    Refuse,
+    /// Rewrite the references to null (`m_FileID = 0`, `m_PathID = 0`).
+    SetNull,
+}
+
+/// A `PPtr` field rewritten by an externals edit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemappedField {
+    /// Field path within the object (`m_RD.texture`).
+    pub path: String,
+    pub old_file_id: i32,
+    /// `0` when the reference was nulled.
+    pub new_file_id: i32,
+    /// The reference pointed at a removed external and was set to null.
+    pub nulled: bool,
+}
+
+/// An object whose `PPtr`s were rewritten.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TouchedObject {
+    pub path_id: i64,
+    pub fields: Vec<RemappedField>,
+}
+
+/// Objects and script types changed by an externals edit.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExternalsEditReport {
+    pub objects: Vec<TouchedObject>,
+    /// Indices into `script_types` whose file index was rewritten.
+    pub script_types: Vec<usize>,
+}
+
+impl ExternalsEditReport {
+    /// Number of rewritten `PPtr` fields.
+    pub fn field_count(&self) -> usize {
+        self.objects.iter().map(|o| o.fields.len()).sum()
+    }
+
+    /// Whether any object field was set to null.
+    pub fn has_nulled(&self) -> bool {
+        self.objects
+            .iter()
+            .any(|o| o.fields.iter().any(|f| f.nulled))
+    }
+}
+
+/// New bytes for `objects[index]`.
+type ObjectPatch = (usize, Vec<u8>);
+
+impl SerializedFile {
+    /// Append an external with `path` (zero GUID, type `0`) and return its `m_FileID`.
+    ///
+    /// If an external with the same path exists, its file id is returned instead.
+    pub fn add_external(&mut self, path: impl Into<String>) -> i32 {
+        let path = path.into();
+        if let Some(index) = self.externals.iter().position(|e| e.path == path) {
+            return index as i32 + 1;
+        }
+        self.externals.push(FileIdentifier::new([0; 16], 0, path));
+        self.externals.len() as i32
+    }
+
+    /// Remove `externals[index]`, shifting the file ids of later externals down by one.
+    ///
+    /// References to the removed external are refused or nulled according to `dangling`.
+    pub fn remove_external(
+        &mut self,
+        index: usize,
+        dangling: DanglingReferences,
+    ) -> Result<ExternalsEditReport> {
+        self.check_external_index(index)?;
+        let removed = index as i32 + 1;
+        let map: Vec<Option<i32>> = (0..=self.externals.len() as i32)
+            .map(|file_id| match file_id {
+                id if id == removed => None,
+                id if id > removed => Some(id - 1),
+                id => Some(id),
+            })
+            .collect();
+
+        let (patches, report) = self.plan_file_id_remap(&map)?;
+        if dangling == DanglingReferences::Refuse {
+            let still_used = report
+                .objects
+                .iter()
+                .flat_map(|o| {
+                    o.fields
+                        .iter()
+                        .filter(|f| f.nulled)
+                        .map(move |f| (o.path_id, f))
+                })
+                .next();
+            if let Some((path_id, field)) = still_used {
+                return Err(BinaryError::invalid_data(format!(
+                    "External {} ({}) is still referenced (e.g. path_id={} {})",
+                    index, self.externals[index].path, path_id, field.path
+                )));
+            }
+            if let Some(script) = self
+                .script_types
+                .iter()
+                .position(|s| s.local_serialized_file_index == removed)
+            {
+                return Err(BinaryError::invalid_data(format!(
+                    "External {} ({}) is still referenced by script type {}",
+                    index, self.externals[index].path, script
+                )));
+            }
+        }
+
+        self.apply_file_id_remap(&map, patches);
+        self.externals.remove(index);
+        Ok(report)
+    }
+
+    /// Move `externals[old_index]` to `new_index` (like `Vec::remove` + `Vec::insert`) and
+    /// rewrite every reference so it still points at the same external.
+    ///
+    /// Swapping the two externals of a two-entry table is `remap_external(0, 1)`.
+    pub fn remap_external(
+        &mut self,
+        old_index: usize,
+        new_index: usize,
+    ) -> Result<ExternalsEditReport> {
+        self.check_external_index(old_index)?;
+        self.check_external_index(new_index)?;
+
+        let mut order: Vec<usize> = (0..self.externals.len()).collect();
+        let moved = order.remove(old_index);
+        order.insert(new_index, moved);
+        let mut map: Vec<Option<i32>> = vec![Some(0); self.externals.len() + 1];
+        for (new, &old) in order.iter().enumerate() {
+            map[old + 1] = Some(new as i32 + 1);
+        }
+
+        let (patches, report) = self.plan_file_id_remap(&map)?;
+        self.apply_file_id_remap(&map, patches);
+        let external = self.externals.remove(old_index);
+        self.externals.insert(new_index, external);
+        Ok(report)
+    }
+
+    fn check_external_index(&self, index: usize) -> Result<()> {
+        if index >= self.externals.len() {
+            return Err(BinaryError::invalid_data(format!(
+                "External index {} out of range ({} externals)",
+                index,
+                self.externals.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Compute patched bytes for every object with a `PPtr` whose file id changes under `map`
+    /// (`map[old] = Some(new)`, or `None` to null the reference). Nothing is modified.
+    fn plan_file_id_remap(
+        &self,
+        map: &[Option<i32>],
+    ) -> Result<(Vec<ObjectPatch>, ExternalsEditReport)> {
+        let byte_order = self.header.byte_order();
+        let mut patches = Vec::new();
+        let mut report = ExternalsEditReport::default();
+
+        for (object_index, handle) in self.object_handles().enumerate() {
+            let path_id = handle.path_id();
+            let fields = handle.scan_pptr_fields()?.ok_or_else(|| {
+                BinaryError::unsupported(format!(
+                    "Cannot remap externals: object path_id={} (class {}) has no TypeTree",
+                    path_id,
+                    handle.class_id()
+                ))
+            })?;
+            let original = handle.raw_data()?;
+
+            let mut bytes: Option<Vec<u8>> = None;
+            let mut touched = Vec::new();
+            for field in fields {
+                let Some(target) = usize::try_from(field.file_id)
+                    .ok()
+                    .and_then(|id| map.get(id))
+                else {
+                    return Err(BinaryError::invalid_data(format!(
+                        "Object path_id={} {} has m_FileID {} outside the externals table",
+                        path_id, field.path, field.file_id
+                    )));
+                };
+                if *target == Some(field.file_id) {
+                    continue;
+                }
+
+                let data = bytes.get_or_insert_with(|| original.to_vec());
+                let new_file_id = target.unwrap_or(0);
+                write_i32(
+                    &mut data[field.file_id_range.clone()],
+                    new_file_id,
+                    byte_order,
+                );
+                if target.is_none() {
+                    data[field.path_id_range.clone()].fill(0);
+                }
+                touched.push(RemappedField {
+                    path: field.path,
+                    old_file_id: field.file_id,
+                    new_file_id,
+                    nulled: target.is_none(),
+                });
+            }
+
+            if let Some(bytes) = bytes {
+                patches.push((object_index, bytes));
+                report.objects.push(TouchedObject {
+                    path_id,
+                    fields: touched,
+                });
+            }
+        }
+
+        report.script_types = self
+            .script_types
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| {
+                usize::try_from(s.local_serialized_file_index)
+                    .ok()
+                    .and_then(|id| map.get(id))
+                    .is_some_and(|target| *target != Some(s.local_serialized_file_index))
+            })
+            .map(|(i, _)| i)
+            .collect();
+        Ok((patches, report))
+    }
+
+    fn apply_file_id_remap(&mut self, map: &[Option<i32>], patches: Vec<ObjectPatch>) {
+        for (object_index, bytes) in patches {
+            self.objects[object_index].data = bytes;
+        }
+        for script in &mut self.script_types {
+            if let Some(target) = usize::try_from(script.local_serialized_file_index)
+                .ok()
+                .and_then(|id| map.get(id))
+            {
+                match target {
+                    Some(new) => script.local_serialized_file_index = *new,
+                    None => {
+                        script.local_serialized_file_index = 0;
+                        script.local_identifier_in_file = 0;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn write_i32(out: &mut [u8], value: i32, byte_order: ByteOrder) {
+    let bytes = match byte_order {
+        ByteOrder::Little => value.to_le_bytes(),
+        ByteOrder::Big => value.to_be_bytes(),
+    };
+    // `m_FileID` is an `int` in every TypeTree seen so far; narrower fields keep the low bytes.
+    let n = out.len().min(4);
+    match byte_order {
+        ByteOrder::Little => out[..n].copy_from_slice(&bytes[..n]),
+        ByteOrder::Big => out[..n].copy_from_slice(&bytes[4 - n..]),
+    }
+}
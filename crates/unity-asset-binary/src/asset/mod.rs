@@ -9,6 +9,9 @@
 //! - `header` - SerializedFile header parsing and validation
 //! - `types` - Core data structures (SerializedType, FileIdentifier, etc.)
 //! - `parser` - Main parsing logic for SerializedFile structures
+//! - `dump` - Raw object payload dumping for external tools
+//! - `externals` - Externals table editing with `PPtr` file id remapping
+//! - `rename` - Renaming objects (`m_Name` and matching container paths)
 //!
 //! # Examples
 //!
@@ -28,11 +31,18 @@
 //! # Ok::<(), unity_asset_binary::error::BinaryError>(())
 //! ```
 
+#[cfg(feature = "fs")]
+pub mod dump;
+pub mod externals;
 pub mod header;
 pub mod parser;
+pub mod rename;
 pub mod types;
 
 // Re-export main types for easy access
+#[cfg(feature = "fs")]
+pub use dump::{DUMP_INDEX_FILE_NAME, DumpFilter, DumpReport, DumpedObject};
+pub use externals::{DanglingReferences, ExternalsEditReport, RemappedField, TouchedObject};
 pub use header::{HeaderFormatInfo, HeaderValidation, SerializedFileHeader, validate_header};
 pub use parser::{FileStatistics, ParsingStats, SerializedFile, SerializedFileParser};
 pub use types::{FileIdentifier, ObjectInfo, SerializedType, TypeRegistry, class_ids};
@@ -62,13 +72,12 @@ impl AssetProcessor {
     }
 
     /// Parse SerializedFile from file path
+    #[cfg(feature = "fs")]
     pub fn parse_from_file<P: AsRef<std::path::Path>>(
         &mut self,
         path: P,
     ) -> crate::error::Result<()> {
-        let data = std::fs::read(path).map_err(|e| {
-            crate::error::BinaryError::generic(format!("Failed to read file: {}", e))
-        })?;
+        let data = crate::split::read_file_or_split(path)?;
         self.parse_from_bytes(data)
     }
 
@@ -170,11 +179,14 @@ pub fn parse_serialized_file(data: Vec<u8>) -> crate::error::Result<SerializedFi
 }
 
 /// Parse SerializedFile from file path
+///
+/// Split files (`<path>.split0`, `.split1`, ...) are reassembled when `path` is the unsplit name
+/// or one of the chunks.
+#[cfg(feature = "fs")]
 pub fn parse_serialized_file_from_path<P: AsRef<std::path::Path>>(
     path: P,
 ) -> crate::error::Result<SerializedFile> {
-    let data = std::fs::read(path)
-        .map_err(|e| crate::error::BinaryError::generic(format!("Failed to read file: {}", e)))?;
+    let data = crate::split::read_file_or_split(path)?;
     SerializedFileParser::from_bytes(data)
 }
 
@@ -185,9 +197,9 @@ pub async fn parse_serialized_file_async(data: Vec<u8>) -> crate::error::Result<
 }
 
 /// Get file information without full parsing
+#[cfg(feature = "fs")]
 pub fn get_file_info<P: AsRef<std::path::Path>>(path: P) -> crate::error::Result<AssetFileInfo> {
-    let data = std::fs::read(&path)
-        .map_err(|e| crate::error::BinaryError::generic(format!("Failed to read file: {}", e)))?;
+    let data = crate::split::read_file_or_split(&path)?;
 
     // Parse just the header and basic metadata
     let mut reader = crate::reader::BinaryReader::new(&data, crate::reader::ByteOrder::Big);
@@ -221,8 +233,9 @@ pub fn get_file_info<P: AsRef<std::path::Path>>(path: P) -> crate::error::Result
 }
 
 /// Check if a file is a valid Unity SerializedFile
+#[cfg(feature = "fs")]
 pub fn is_valid_serialized_file<P: AsRef<std::path::Path>>(path: P) -> bool {
-    match std::fs::read(path) {
+    match crate::split::read_file_or_split(path) {
         Ok(data) => {
             if data.len() < 20 {
                 return false;
@@ -273,6 +286,7 @@ pub fn get_parsing_options(version: u32) -> ParsingOptions {
 
 /// Parsing options for different Unity versions
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ParsingOptions {
     pub enable_type_tree: bool,
     pub use_big_ids: bool,
@@ -281,6 +295,45 @@ pub struct ParsingOptions {
     pub uses_extended_format: bool,
 }
 
+/// Options for the newest serialized-file format (22+).
+impl Default for ParsingOptions {
+    fn default() -> Self {
+        get_parsing_options(22)
+    }
+}
+
+impl ParsingOptions {
+    /// Set `enable_type_tree`.
+    pub fn with_enable_type_tree(mut self, enable_type_tree: bool) -> Self {
+        self.enable_type_tree = enable_type_tree;
+        self
+    }
+
+    /// Set `use_big_ids`.
+    pub fn with_use_big_ids(mut self, use_big_ids: bool) -> Self {
+        self.use_big_ids = use_big_ids;
+        self
+    }
+
+    /// Set `supports_script_types`.
+    pub fn with_supports_script_types(mut self, supports_script_types: bool) -> Self {
+        self.supports_script_types = supports_script_types;
+        self
+    }
+
+    /// Set `supports_ref_types`.
+    pub fn with_supports_ref_types(mut self, supports_ref_types: bool) -> Self {
+        self.supports_ref_types = supports_ref_types;
+        self
+    }
+
+    /// Set `uses_extended_format`.
+    pub fn with_uses_extended_format(mut self, uses_extended_format: bool) -> Self {
+        self.uses_extended_format = uses_extended_format;
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
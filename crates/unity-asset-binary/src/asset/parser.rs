@@ -6,6 +6,8 @@ use super::header::SerializedFileHeader;
 use super::types::{
     FileIdentifier, LocalSerializedObjectIdentifier, ObjectInfo, SerializedType, TypeRegistry,
 };
+use crate::bytes_preview::{BytesPreview, LenPreview};
+use crate::data_source::DataSource;
 use crate::data_view::DataView;
 use crate::error::{BinaryError, Result};
 use crate::object::ObjectHandle;
@@ -13,9 +15,11 @@ use crate::reader::{BinaryReader, ByteOrder};
 use crate::shared_bytes::SharedBytes;
 use crate::typetree::TypeTreeRegistry;
 use std::collections::HashMap;
+use std::fmt;
 use std::ops::Range;
 use std::sync::Arc;
 use std::sync::OnceLock;
+use unity_asset_core::UnityClass;
 
 /// SerializedFile parser
 ///
@@ -40,6 +44,13 @@ impl SerializedFileParser {
         Self::from_shared_range_with_options(shared, 0..len, preload_object_data)
     }
 
+    /// Parse a SerializedFile from a [`DataSource`].
+    ///
+    /// Object data is addressed by offset into the whole file, so the source is read in full.
+    pub fn from_source(source: &dyn DataSource) -> Result<SerializedFile> {
+        Self::from_bytes(source.read_all()?)
+    }
+
     /// Parse a SerializedFile from a shared backing buffer + byte range (zero-copy view).
     pub fn from_shared_range(data: SharedBytes, range: Range<usize>) -> Result<SerializedFile> {
         Self::from_shared_range_with_options(data, range, false)
@@ -381,7 +392,6 @@ impl SerializedFileParser {
 ///
 /// This structure represents a complete Unity SerializedFile with all its
 /// metadata, type information, and object data.
-#[derive(Debug)]
 pub struct SerializedFile {
     /// File header
     pub header: SerializedFileHeader,
@@ -417,6 +427,14 @@ impl SerializedFile {
         self.type_tree_registry = registry;
     }
 
+    /// Override the declared Unity version, e.g. with an inferred one when the file's version
+    /// string was stripped (see [`apply_inferred_version`](Self::apply_inferred_version)).
+    ///
+    /// TypeTree registry lookups and the decode converters read `unity_version`, so they follow.
+    pub fn set_unity_version(&mut self, version: &crate::unity_version::UnityVersion) {
+        self.unity_version = version.to_string();
+    }
+
     /// Get the raw file data
     pub fn data(&self) -> &[u8] {
         self.data.as_bytes()
@@ -478,7 +496,11 @@ impl SerializedFile {
     ///
     /// Returns a list of `(asset_path, file_id, path_id)` tuples.
     pub fn assetbundle_container_raw(&self, info: &ObjectInfo) -> Result<Vec<(String, i32, i64)>> {
-        let data = self.object_bytes(info)?;
+        let data = if info.data.is_empty() {
+            self.object_bytes(info)?
+        } else {
+            info.data.as_slice()
+        };
         let byte_order = self.header.byte_order();
 
         fn parse_pptr(reader: &mut BinaryReader) -> Result<(i32, i64)> {
@@ -677,6 +699,14 @@ impl SerializedFile {
             .map(|info| ObjectHandle::new(self, info))
     }
 
+    /// Iterate all objects parsed into [`UnityClass`] values through their TypeTree.
+    ///
+    /// Each object is parsed with [`ObjectHandle::read_typed`]; one without a TypeTree or with
+    /// data that fails to parse yields an `Err` item and iteration continues with the next.
+    pub fn objects_typed(&self) -> impl Iterator<Item = Result<UnityClass>> + '_ {
+        self.object_handles().map(|handle| handle.read_typed())
+    }
+
     /// Find an object by `path_id` and return a lightweight handle.
     pub fn find_object_handle(&self, path_id: i64) -> Option<ObjectHandle<'_>> {
         self.find_object(path_id)
@@ -727,6 +757,25 @@ impl SerializedFile {
         SerializedFileParser::validate(self)
     }
 
+    /// [`SerializedFile::validate`], then parse every object with a strict TypeTree
+    ///
+    /// Structural errors fail the call; objects that do not parse are returned with their
+    /// path id. Files without TypeTrees need a registry for their objects to parse.
+    pub fn validate_deep(&self) -> Result<Vec<(i64, BinaryError)>> {
+        self.validate()?;
+        let options = crate::typetree::TypeTreeParseOptions::default()
+            .with_mode(crate::typetree::TypeTreeParseMode::Strict);
+        Ok(self
+            .object_handles()
+            .filter_map(|handle| {
+                handle
+                    .read_with_options(options.clone())
+                    .err()
+                    .map(|e| (handle.path_id(), e))
+            })
+            .collect())
+    }
+
     fn load_object_data(&mut self) -> Result<()> {
         let backing = self.data.backing_shared();
         let start = self.data.base_offset();
@@ -762,6 +811,7 @@ fn i64_to_u64_checked(value: i64, name: &'static str) -> Result<u64> {
 
 /// Parsing statistics
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ParsingStats {
     pub version: u32,
     pub unity_version: String,
@@ -775,8 +825,51 @@ pub struct ParsingStats {
     pub big_id_enabled: bool,
 }
 
+/// One-line summary, e.g. `SerializedFile v22 unity=2020.3.0f1 platform=19 objects=120 types=14`.
+impl fmt::Display for SerializedFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SerializedFile v{} unity={} platform={} objects={} types={}",
+            self.header.version,
+            self.unity_version,
+            self.target_platform,
+            self.objects.len(),
+            self.types.len()
+        )
+    }
+}
+
+/// Tables are shown as counts and the file bytes as a [`BytesPreview`].
+impl fmt::Debug for SerializedFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SerializedFile")
+            .field("header", &self.header)
+            .field("unity_version", &self.unity_version)
+            .field("target_platform", &self.target_platform)
+            .field("enable_type_tree", &self.enable_type_tree)
+            .field(
+                "type_tree_registry",
+                &self.type_tree_registry.as_ref().map(|_| ".."),
+            )
+            .field("types", &LenPreview(self.types.len(), "types"))
+            .field("big_id_enabled", &self.big_id_enabled)
+            .field("objects", &LenPreview(self.objects.len(), "objects"))
+            .field(
+                "script_types",
+                &LenPreview(self.script_types.len(), "script types"),
+            )
+            .field("externals", &self.externals)
+            .field("ref_types", &LenPreview(self.ref_types.len(), "types"))
+            .field("user_information", &self.user_information)
+            .field("data", &BytesPreview(self.data.as_bytes()))
+            .finish_non_exhaustive()
+    }
+}
+
 /// File statistics
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct FileStatistics {
     pub version: u32,
     pub unity_version: String,
@@ -0,0 +1,227 @@
+//! Renaming objects in a SerializedFile.
+//!
+//! `m_Name` is patched in place: the stored string (length, bytes and alignment padding) is
+//! replaced by the new one padded to the same 4-byte boundary, so the fields after it still
+//! parse. Patched bytes are stored in [`ObjectInfo::data`](super::ObjectInfo::data), which object
+//! reads (`peek_name`, typed parses) and the writer prefer over the original file bytes.
+
+use super::parser::SerializedFile;
+use crate::error::{BinaryError, Result};
+use crate::object::ObjectHandle;
+use crate::reader::ByteOrder;
+use std::ops::Range;
+use unity_asset_core::class_ids;
+
+/// `NamedObject` classes whose serialized data starts with `m_Name`; used for objects without a
+/// TypeTree.
+const NAMED_OBJECT_CLASSES: &[i32] = &[
+    class_ids::MATERIAL,
+    class_ids::TEXTURE,
+    class_ids::TEXTURE_2D,
+    class_ids::MESH,
+    class_ids::SHADER,
+    class_ids::TEXT_ASSET,
+    class_ids::ANIMATION_CLIP,
+    class_ids::AUDIO_CLIP,
+    84, // RenderTexture
+    89, // Cubemap
+    class_ids::AVATAR,
+    class_ids::ANIMATOR_CONTROLLER,
+    class_ids::MONO_SCRIPT,
+    class_ids::TEXTURE_3D,
+    128, // Font
+    134, // PhysicMaterial
+    class_ids::ASSET_BUNDLE,
+    187, // Texture2DArray
+    188, // CubemapArray
+    class_ids::SHADER_VARIANT_COLLECTION,
+    class_ids::SPRITE,
+    221, // AnimatorOverrideController
+    class_ids::AUDIO_MIXER_CONTROLLER,
+    class_ids::AUDIO_MIXER_GROUP_CONTROLLER,
+    class_ids::AUDIO_MIXER_SNAPSHOT_CONTROLLER,
+    329, // VideoClip
+    class_ids::LIGHTING_DATA_ASSET,
+    class_ids::SPRITE_ATLAS,
+];
+
+/// A stored string to replace: its byte range and the new value.
+type StringPatch = (Range<usize>, String);
+
+impl SerializedFile {
+    /// Set `m_Name` of the object `path_id` to `new_name`.
+    ///
+    /// The name is located through the object's TypeTree, or taken as the leading aligned string
+    /// for TypeTree-less `NamedObject`s. `m_Container` entries of AssetBundle objects that point at
+    /// the object and whose file name is the old name are renamed too
+    /// (`assets/ui/old.png` -> `assets/ui/new.png`, lowercased when the stored path is).
+    ///
+    /// Objects without an `m_Name` field are refused; nothing is modified on error.
+    pub fn rename_object(&mut self, path_id: i64, new_name: &str) -> Result<()> {
+        let index = self
+            .objects
+            .iter()
+            .position(|o| o.path_id == path_id)
+            .ok_or_else(|| {
+                BinaryError::invalid_data(format!("Object path_id={} not found", path_id))
+            })?;
+        let byte_order = self.header.byte_order();
+
+        let handle = ObjectHandle::new(self, &self.objects[index]);
+        let (old_name, range) = name_field(&handle)?;
+        let mut patches = vec![(
+            index,
+            splice_strings(
+                handle.raw_data()?,
+                vec![(range, new_name.to_string())],
+                byte_order,
+            ),
+        )];
+
+        for (bundle_index, info) in self.objects.iter().enumerate() {
+            if info.type_id != class_ids::ASSET_BUNDLE || bundle_index == index {
+                continue;
+            }
+            let bundle = ObjectHandle::new(self, info);
+            let renames = container_renames(&bundle, path_id, &old_name, new_name)?;
+            if !renames.is_empty() {
+                patches.push((
+                    bundle_index,
+                    splice_strings(bundle.raw_data()?, renames, byte_order),
+                ));
+            }
+        }
+
+        for (object_index, bytes) in patches {
+            let info = &mut self.objects[object_index];
+            info.byte_size = bytes.len() as u32;
+            info.data = bytes;
+        }
+        Ok(())
+    }
+}
+
+/// The object's current name and the byte range of the stored `m_Name`.
+fn name_field(handle: &ObjectHandle<'_>) -> Result<(String, Range<usize>)> {
+    if let Some(fields) = handle.scan_strings(usize::MAX)? {
+        return fields
+            .into_iter()
+            .find(|f| f.path == "m_Name")
+            .map(|f| (f.value, f.range))
+            .ok_or_else(|| no_name(handle));
+    }
+
+    if !NAMED_OBJECT_CLASSES.contains(&handle.class_id()) {
+        return Err(no_name(handle));
+    }
+    let bytes = handle.raw_data()?;
+    let byte_order = handle.file().header.byte_order();
+    let len = bytes
+        .get(..4)
+        .map(|b| read_i32(b, byte_order))
+        .and_then(|len| usize::try_from(len).ok())
+        .filter(|len| 4 + len <= bytes.len())
+        .ok_or_else(|| {
+            BinaryError::invalid_data(format!(
+                "Object path_id={} does not start with an m_Name string",
+                handle.path_id()
+            ))
+        })?;
+    let name = String::from_utf8_lossy(&bytes[4..4 + len]).into_owned();
+    Ok((name, 0..align4(4 + len).min(bytes.len())))
+}
+
+fn no_name(handle: &ObjectHandle<'_>) -> BinaryError {
+    BinaryError::unsupported(format!(
+        "Object path_id={} (class {}) has no m_Name field",
+        handle.path_id(),
+        handle.class_id()
+    ))
+}
+
+/// `m_Container` paths of `bundle` pointing at local object `path_id` whose file stem is
+/// `old_name`, with their renamed values.
+fn container_renames(
+    bundle: &ObjectHandle<'_>,
+    path_id: i64,
+    old_name: &str,
+    new_name: &str,
+) -> Result<Vec<StringPatch>> {
+    let Some(pptrs) = bundle.scan_pptr_fields()? else {
+        return Ok(Vec::new());
+    };
+    let entries: Vec<&str> = pptrs
+        .iter()
+        .filter(|p| p.file_id == 0 && p.path_id == path_id)
+        .filter_map(|p| p.path.strip_suffix(".second.asset"))
+        .filter(|entry| entry.starts_with("m_Container["))
+        .collect();
+    if entries.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let strings = bundle.scan_strings(usize::MAX)?.unwrap_or_default();
+    Ok(strings
+        .into_iter()
+        .filter(|s| {
+            s.path
+                .strip_suffix(".first")
+                .is_some_and(|entry| entries.contains(&entry))
+        })
+        .filter_map(|s| {
+            let renamed = renamed_container_path(&s.value, old_name, new_name)?;
+            Some((s.range, renamed))
+        })
+        .collect())
+}
+
+/// `assets/ui/old.png` -> `assets/ui/new.png` when the file stem matches `old_name`.
+fn renamed_container_path(path: &str, old_name: &str, new_name: &str) -> Option<String> {
+    let (dir, file) = match path.rfind('/') {
+        Some(slash) => path.split_at(slash + 1),
+        None => ("", path),
+    };
+    let (stem, ext) = match file.rfind('.') {
+        Some(dot) if dot > 0 => file.split_at(dot),
+        _ => (file, ""),
+    };
+    if !stem.eq_ignore_ascii_case(old_name) {
+        return None;
+    }
+    let stem = if stem == stem.to_lowercase() {
+        new_name.to_lowercase()
+    } else {
+        new_name.to_string()
+    };
+    Some(format!("{}{}{}", dir, stem, ext))
+}
+
+/// Replace stored strings in `data`; each range covers length, bytes and alignment padding.
+fn splice_strings(data: &[u8], mut patches: Vec<StringPatch>, byte_order: ByteOrder) -> Vec<u8> {
+    // Back to front, so earlier ranges keep their offsets (and so their alignment).
+    patches.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    let mut out = data.to_vec();
+    for (range, value) in patches {
+        let mut stored = match byte_order {
+            ByteOrder::Little => (value.len() as i32).to_le_bytes(),
+            ByteOrder::Big => (value.len() as i32).to_be_bytes(),
+        }
+        .to_vec();
+        stored.extend_from_slice(value.as_bytes());
+        stored.resize(align4(range.start + stored.len()) - range.start, 0);
+        out.splice(range, stored);
+    }
+    out
+}
+
+fn read_i32(bytes: &[u8], byte_order: ByteOrder) -> i32 {
+    let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+    match byte_order {
+        ByteOrder::Little => i32::from_le_bytes(bytes),
+        ByteOrder::Big => i32::from_be_bytes(bytes),
+    }
+}
+
+fn align4(n: usize) -> usize {
+    n.div_ceil(4) * 4
+}
@@ -2,11 +2,13 @@
 //!
 //! This module defines the core data structures for Unity asset processing.
 
+use crate::bytes_preview::BytesPreview;
 use crate::error::{BinaryError, Result};
 use crate::reader::BinaryReader;
 use crate::typetree::{TypeTree, TypeTreeParser};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 
 /// Type information for Unity objects
 ///
@@ -259,7 +261,7 @@ impl FileIdentifier {
 ///
 /// Contains metadata about individual Unity objects including
 /// their location, type, and path ID.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ObjectInfo {
     /// Path ID of the object (unique within file)
     pub path_id: i64,
@@ -322,6 +324,19 @@ impl ObjectInfo {
     }
 }
 
+impl fmt::Debug for ObjectInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObjectInfo")
+            .field("path_id", &self.path_id)
+            .field("byte_start", &self.byte_start)
+            .field("byte_size", &self.byte_size)
+            .field("type_id", &self.type_id)
+            .field("type_index", &self.type_index)
+            .field("data", &BytesPreview(&self.data))
+            .finish()
+    }
+}
+
 /// Type registry for managing SerializedTypes
 ///
 /// Provides efficient lookup and management of type information
@@ -0,0 +1,182 @@
+//! The build folder's root manifest bundle.
+//!
+//! `BuildPipeline.BuildAssetBundles` writes a bundle named after the output folder
+//! (`StandaloneWindows`, `AssetBundles`, ...) holding a single AssetBundleManifest object
+//! (class 290). It lists every bundle of the build with its hash and direct dependencies, and
+//! is what `AssetBundleManifest.GetAllDependencies` reads at runtime. The text `<Folder>.manifest`
+//! next to it carries the same graph (see [`crate::bundle::AssetBundleManifest`]); this is the
+//! binary object, which is all a shipped player has. Both implement
+//! [`DependencyResolver`](crate::bundle::DependencyResolver), whose `all_dependencies` walks the
+//! graph.
+//!
+//! Its TypeTree stores `AssetBundleNames` as `map<int, string>` and `AssetBundleInfos` as
+//! `map<int, AssetBundleInfo>` keyed by the same bundle index, with dependencies as indices.
+//!
+//! [`find_asset_bundle_manifest`] parses the first AssetBundleManifest of a serialized file.
+
+use crate::asset::SerializedFile;
+use crate::bundle::{AssetBundle, ManifestBundleInfo};
+use crate::error::{BinaryError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use unity_asset_core::{UnityValue, class_ids};
+
+type Fields = indexmap::IndexMap<String, UnityValue>;
+
+/// A parsed AssetBundleManifest (class 290).
+///
+/// The vectors are indexed by bundle index: `bundle_names[i]` has hash `hashes[i]` and depends on
+/// the bundles at indices `dependencies[i]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetBundleManifestObject {
+    pub bundle_names: Vec<String>,
+    /// `AssetBundleHash` (Hash128) per bundle; zero when the info entry is missing.
+    pub hashes: Vec<[u8; 16]>,
+    /// Direct dependency indices per bundle.
+    pub dependencies: Vec<Vec<u32>>,
+    /// Indices of bundles built with a variant (`AssetBundlesWithVariant`).
+    pub bundles_with_variant: Vec<u32>,
+}
+
+impl AssetBundleManifestObject {
+    /// Parse AssetBundleManifest from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        let names = int_map(properties.get("AssetBundleNames"), "AssetBundleNames")?;
+        let infos = int_map(properties.get("AssetBundleInfos"), "AssetBundleInfos")?;
+
+        // Bundle indices are normally 0..n; map them to positions so gaps cannot misindex.
+        let position: BTreeMap<i64, u32> = names
+            .keys()
+            .enumerate()
+            .map(|(pos, &index)| (index, pos as u32))
+            .collect();
+
+        let mut manifest = Self::default();
+        for (index, name) in &names {
+            manifest
+                .bundle_names
+                .push(name.as_str().unwrap_or_default().to_string());
+            let info = infos.get(index).and_then(|info| info.as_object());
+            manifest.hashes.push(
+                info.map(|info| hash128(info.get("AssetBundleHash")))
+                    .unwrap_or_default(),
+            );
+            let dependencies = info
+                .and_then(|info| info.get("AssetBundleDependencies"))
+                .and_then(UnityValue::as_array)
+                .map(|deps| {
+                    deps.iter()
+                        .filter_map(|dep| position.get(&dep.as_i64()?).copied())
+                        .collect()
+                })
+                .unwrap_or_default();
+            manifest.dependencies.push(dependencies);
+        }
+        manifest.bundles_with_variant = properties
+            .get("AssetBundlesWithVariant")
+            .and_then(UnityValue::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|i| position.get(&i.as_i64()?).copied())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(manifest)
+    }
+
+    /// Index of a bundle by name.
+    pub fn index_of(&self, bundle: &str) -> Option<usize> {
+        self.bundle_names.iter().position(|name| name == bundle)
+    }
+
+    /// Hash of a bundle by name.
+    pub fn hash_of(&self, bundle: &str) -> Option<&[u8; 16]> {
+        self.hashes.get(self.index_of(bundle)?)
+    }
+
+    /// Direct dependencies of a bundle; empty for unknown bundles.
+    pub fn dependencies_of(&self, bundle: &str) -> Vec<&str> {
+        self.index_of(bundle)
+            .map(|index| self.names_at(&self.dependencies[index]).collect())
+            .unwrap_or_default()
+    }
+
+    /// The same graph as the text folder manifest's entries.
+    pub fn bundle_infos(&self) -> Vec<ManifestBundleInfo> {
+        self.bundle_names
+            .iter()
+            .zip(&self.dependencies)
+            .map(|(name, deps)| ManifestBundleInfo {
+                name: name.clone(),
+                dependencies: self.names_at(deps).map(str::to_string).collect(),
+            })
+            .collect()
+    }
+
+    fn names_at<'a>(&'a self, indices: &'a [u32]) -> impl Iterator<Item = &'a str> + 'a {
+        indices
+            .iter()
+            .filter_map(|&i| self.bundle_names.get(i as usize).map(String::as_str))
+    }
+}
+
+impl AssetBundle {
+    /// Whether this is the build folder's root manifest bundle (it holds an
+    /// AssetBundleManifest object).
+    pub fn is_manifest_bundle(&self) -> bool {
+        self.assets.iter().any(|asset| {
+            asset
+                .objects
+                .iter()
+                .any(|o| o.type_id == class_ids::ASSET_BUNDLE_MANIFEST)
+        })
+    }
+
+    /// The AssetBundleManifest of a root manifest bundle.
+    pub fn asset_bundle_manifest(&self) -> Option<AssetBundleManifestObject> {
+        self.assets.iter().find_map(find_asset_bundle_manifest)
+    }
+}
+
+/// Parse the first AssetBundleManifest in `asset`.
+pub fn find_asset_bundle_manifest(asset: &SerializedFile) -> Option<AssetBundleManifestObject> {
+    asset
+        .object_handles()
+        .filter(|handle| handle.class_id() == class_ids::ASSET_BUNDLE_MANIFEST)
+        .find_map(|handle| handle.read().ok()?.as_asset_bundle_manifest().ok())
+}
+
+/// A TypeTree `map<int, T>` (an array of `[first, second]` pairs), sorted by key.
+fn int_map<'a>(
+    value: Option<&'a UnityValue>,
+    field: &str,
+) -> Result<BTreeMap<i64, &'a UnityValue>> {
+    let Some(items) = value.and_then(UnityValue::as_array) else {
+        return Err(BinaryError::invalid_data(format!(
+            "AssetBundleManifest has no {} map",
+            field
+        )));
+    };
+    Ok(items
+        .iter()
+        .filter_map(|item| match item.as_array()?.as_slice() {
+            [key, value] => Some((key.as_i64()?, value)),
+            _ => None,
+        })
+        .collect())
+}
+
+/// A Hash128 stored as `bytes[0]` .. `bytes[15]`.
+fn hash128(value: Option<&UnityValue>) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    if let Some(fields) = value.and_then(UnityValue::as_object) {
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = fields
+                .get(&format!("bytes[{}]", i))
+                .and_then(UnityValue::as_i64)
+                .unwrap_or_default() as u8;
+        }
+    }
+    out
+}
@@ -0,0 +1,721 @@
+//! AudioMixer parsing: AudioMixerController (class 241), AudioMixerGroupController (243),
+//! AudioMixerEffectController (244) and AudioMixerSnapshotController (245).
+//!
+//! The runtime mixer lives in the controller's `m_MixerConstant`: flat arrays of groups,
+//! effects and snapshots that address one shared table of parameter values by index, with group
+//! and snapshot names packed into NUL-separated name buffers. The group, effect and snapshot
+//! controllers refer to the same entries by GUID. [`AudioMixerTree::build`] joins the two into a
+//! group hierarchy with effects, per-snapshot parameter values and exposed parameters.
+//!
+//! Parameters are labelled `Group/Volume`, `Group/Pitch`, `Group/Send`, `Group/Effect/Wet` and
+//! `Group/Effect/Parameter`. Effect and effect parameter names come from effect controllers,
+//! which only exist in editor data; without them effects are named `Effect <index>` and their
+//! parameters `Parameter <n>`. Exposed parameter names are likewise editor-only
+//! (`m_ExposedParameters`); built players keep just their CRC.
+
+use crate::animator::{Fields, array, as_bool, float_array, uint, uint_array};
+use crate::asset::SerializedFile;
+use crate::error::{BinaryError, Result};
+use crate::unity_objects::{ObjectRef, parse_pptr};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use unity_asset_core::{UnityValue, class_ids};
+
+/// Index value the mixer constant uses for "no parameter" / "no parent".
+pub const NO_INDEX: u32 = u32::MAX;
+
+/// A Unity GUID as stored in typetrees (`data[0]..data[3]`).
+///
+/// Formats like `.meta` files: 32 hex digits, each word written low nibble first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MixerGuid(pub [u32; 4]);
+
+impl MixerGuid {
+    fn from_value(value: Option<&UnityValue>) -> Self {
+        let Some(fields) = value.and_then(UnityValue::as_object) else {
+            return Self::default();
+        };
+        let words = match fields.get("data") {
+            Some(UnityValue::Array(words)) => {
+                std::array::from_fn(|i| words.get(i).and_then(UnityValue::as_i64).unwrap_or(0))
+            }
+            _ => std::array::from_fn(|i| {
+                fields
+                    .get(&format!("data[{i}]"))
+                    .and_then(UnityValue::as_i64)
+                    .unwrap_or(0)
+            }),
+        };
+        Self(words.map(|w| w as u32))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+}
+
+impl fmt::Display for MixerGuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for word in self.0 {
+            for nibble in 0..8 {
+                write!(f, "{:x}", (word >> (nibble * 4)) & 0xF)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for MixerGuid {
+    type Err = BinaryError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.len() != 32 || !s.is_ascii() {
+            return Err(BinaryError::invalid_data(format!("Invalid GUID: {s}")));
+        }
+        let mut words = [0u32; 4];
+        for (i, c) in s.chars().enumerate() {
+            let nibble = c
+                .to_digit(16)
+                .ok_or_else(|| BinaryError::invalid_data(format!("Invalid GUID: {s}")))?;
+            words[i / 8] |= nibble << ((i % 8) * 4);
+        }
+        Ok(Self(words))
+    }
+}
+
+impl Serialize for MixerGuid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for MixerGuid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// `AudioMixerConstant::GroupConstant`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GroupConstant {
+    /// Index of the parent group, `None` for the master group.
+    pub parent: Option<usize>,
+    pub volume_index: u32,
+    pub pitch_index: u32,
+    /// [`NO_INDEX`] when the group has no send level.
+    pub send_index: u32,
+    pub mute: bool,
+    pub solo: bool,
+    pub bypass_effects: bool,
+}
+
+/// `AudioMixerConstant::EffectConstant`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectConstant {
+    /// Effect type id (`type`).
+    pub effect_type: u32,
+    pub group_index: usize,
+    pub send_target_effect_index: u32,
+    pub wet_mix_level_index: u32,
+    pub bypass: bool,
+    pub parameter_indices: Vec<u32>,
+}
+
+/// `AudioMixerConstant::SnapshotConstant`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotConstant {
+    pub name_hash: u32,
+    /// Value of every mixer parameter, by parameter index.
+    pub values: Vec<f32>,
+}
+
+/// The runtime mixer (`m_MixerConstant`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudioMixerConstant {
+    pub groups: Vec<GroupConstant>,
+    pub group_guids: Vec<MixerGuid>,
+    pub effects: Vec<EffectConstant>,
+    pub effect_guids: Vec<MixerGuid>,
+    pub snapshots: Vec<SnapshotConstant>,
+    pub snapshot_guids: Vec<MixerGuid>,
+    pub group_names: Vec<String>,
+    pub snapshot_names: Vec<String>,
+    pub plugin_effect_names: Vec<String>,
+    /// `(name CRC, parameter index)` of each exposed parameter.
+    pub exposed_parameters: Vec<(u32, u32)>,
+}
+
+impl AudioMixerConstant {
+    fn from_fields(constant: &Fields) -> Self {
+        let guids = |key: &str| match constant.get(key) {
+            Some(UnityValue::Array(items)) => items
+                .iter()
+                .map(|g| MixerGuid::from_value(Some(g)))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let exposed_names = uint_array(constant.get("exposedParameterNames"));
+        let exposed_indices = uint_array(constant.get("exposedParameterIndices"));
+
+        Self {
+            groups: array(constant, "groups")
+                .map(|g| GroupConstant {
+                    parent: index(g, "parentConstantIndex"),
+                    volume_index: uint(g, "volumeIndex"),
+                    pitch_index: uint(g, "pitchIndex"),
+                    send_index: g
+                        .get("sendIndex")
+                        .and_then(UnityValue::as_i64)
+                        .map_or(NO_INDEX, |v| v as u32),
+                    mute: flag(g, "mute"),
+                    solo: flag(g, "solo"),
+                    bypass_effects: flag(g, "bypassEffects"),
+                })
+                .collect(),
+            group_guids: guids("groupGUIDs"),
+            effects: array(constant, "effects")
+                .map(|e| EffectConstant {
+                    effect_type: uint(e, "type"),
+                    group_index: uint(e, "groupConstantIndex") as usize,
+                    send_target_effect_index: uint(e, "sendTargetEffectIndex"),
+                    wet_mix_level_index: uint(e, "wetMixLevelIndex"),
+                    bypass: flag(e, "bypass"),
+                    parameter_indices: uint_array(e.get("parameterIndices")),
+                })
+                .collect(),
+            effect_guids: guids("effectGUIDs"),
+            snapshots: array(constant, "snapshots")
+                .map(|s| SnapshotConstant {
+                    name_hash: uint(s, "nameHash"),
+                    values: s.get("values").map(float_array).unwrap_or_default(),
+                })
+                .collect(),
+            snapshot_guids: guids("snapshotGUIDs"),
+            group_names: name_buffer(constant.get("groupNameBuffer")),
+            snapshot_names: name_buffer(constant.get("snapshotNameBuffer")),
+            plugin_effect_names: name_buffer(constant.get("pluginEffectNameBuffer")),
+            exposed_parameters: exposed_names.into_iter().zip(exposed_indices).collect(),
+        }
+    }
+}
+
+/// A parsed AudioMixerController (class 241).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudioMixerController {
+    pub name: String,
+    pub output_group: Option<ObjectRef>,
+    pub master_group: Option<ObjectRef>,
+    pub snapshots: Vec<ObjectRef>,
+    pub start_snapshot: Option<ObjectRef>,
+    /// Editor-only `m_ExposedParameters`: parameter GUID and user-facing name.
+    pub exposed_parameter_names: Vec<(MixerGuid, String)>,
+    pub constant: AudioMixerConstant,
+}
+
+impl AudioMixerController {
+    /// Parse AudioMixerController from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        let Some(constant) = properties
+            .get("m_MixerConstant")
+            .and_then(UnityValue::as_object)
+        else {
+            return Err(BinaryError::invalid_data(
+                "AudioMixerController has no m_MixerConstant",
+            ));
+        };
+        Ok(Self {
+            name: string(properties, "m_Name"),
+            output_group: properties.get("m_OutputGroup").and_then(parse_pptr),
+            master_group: properties.get("m_MasterGroup").and_then(parse_pptr),
+            snapshots: pptrs(properties, "m_Snapshots"),
+            start_snapshot: properties.get("m_StartSnapshot").and_then(parse_pptr),
+            exposed_parameter_names: array(properties, "m_ExposedParameters")
+                .map(|p| (MixerGuid::from_value(p.get("guid")), string(p, "name")))
+                .collect(),
+            constant: AudioMixerConstant::from_fields(constant),
+        })
+    }
+}
+
+/// A parsed AudioMixerGroupController (class 243).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudioMixerGroupController {
+    pub name: String,
+    pub mixer: Option<ObjectRef>,
+    pub group_id: MixerGuid,
+    pub children: Vec<ObjectRef>,
+    pub effects: Vec<ObjectRef>,
+    /// Parameter GUIDs of the group's volume, pitch and send level.
+    pub volume: MixerGuid,
+    pub pitch: MixerGuid,
+    pub send: MixerGuid,
+    pub mute: bool,
+    pub solo: bool,
+    pub bypass_effects: bool,
+}
+
+impl AudioMixerGroupController {
+    /// Parse AudioMixerGroupController from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        Ok(Self {
+            name: string(properties, "m_Name"),
+            mixer: properties.get("m_AudioMixer").and_then(parse_pptr),
+            group_id: MixerGuid::from_value(properties.get("m_GroupID")),
+            children: pptrs(properties, "m_Children"),
+            effects: pptrs(properties, "m_Effects"),
+            volume: MixerGuid::from_value(properties.get("m_Volume")),
+            pitch: MixerGuid::from_value(properties.get("m_Pitch")),
+            send: MixerGuid::from_value(properties.get("m_Send")),
+            mute: flag(properties, "m_Mute"),
+            solo: flag(properties, "m_Solo"),
+            bypass_effects: flag(properties, "m_BypassEffects"),
+        })
+    }
+}
+
+/// A named effect parameter of an AudioMixerEffectController.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectParameter {
+    pub name: String,
+    pub guid: MixerGuid,
+}
+
+/// A parsed AudioMixerEffectController (class 244, editor only).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudioMixerEffectController {
+    pub effect_id: MixerGuid,
+    pub effect_name: String,
+    /// Parameter GUID of the wet mix level.
+    pub mix_level: MixerGuid,
+    pub parameters: Vec<EffectParameter>,
+    pub send_target: Option<ObjectRef>,
+    pub enable_wet_mix: bool,
+    pub bypass: bool,
+}
+
+impl AudioMixerEffectController {
+    /// Parse AudioMixerEffectController from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        Ok(Self {
+            effect_id: MixerGuid::from_value(properties.get("m_EffectID")),
+            effect_name: string(properties, "m_EffectName"),
+            mix_level: MixerGuid::from_value(properties.get("m_MixLevel")),
+            parameters: array(properties, "m_Parameters")
+                .map(|p| EffectParameter {
+                    name: string(p, "m_ParameterName"),
+                    guid: MixerGuid::from_value(p.get("m_GUID")),
+                })
+                .collect(),
+            send_target: properties.get("m_SendTarget").and_then(parse_pptr),
+            enable_wet_mix: flag(properties, "m_EnableWetMix"),
+            bypass: flag(properties, "m_Bypass"),
+        })
+    }
+}
+
+/// A parsed AudioMixerSnapshotController (class 245).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudioMixerSnapshotController {
+    pub name: String,
+    pub mixer: Option<ObjectRef>,
+    pub snapshot_id: MixerGuid,
+}
+
+impl AudioMixerSnapshotController {
+    /// Parse AudioMixerSnapshotController from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        Ok(Self {
+            name: string(properties, "m_Name"),
+            mixer: properties.get("m_AudioMixer").and_then(parse_pptr),
+            snapshot_id: MixerGuid::from_value(properties.get("m_SnapshotID")),
+        })
+    }
+}
+
+/// An effect in a group's chain, with parameter values from the start snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MixerEffect {
+    pub name: String,
+    pub guid: MixerGuid,
+    pub bypass: bool,
+    /// Wet mix level in dB, for effects that have one.
+    pub wet_mix: Option<f32>,
+    pub parameters: Vec<(String, f32)>,
+}
+
+/// A mixer group with its effect chain and child groups.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MixerGroup {
+    pub name: String,
+    pub guid: MixerGuid,
+    pub mute: bool,
+    pub solo: bool,
+    pub bypass_effects: bool,
+    pub effects: Vec<MixerEffect>,
+    pub children: Vec<MixerGroup>,
+}
+
+/// A snapshot and the value of every labelled parameter in it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MixerSnapshot {
+    pub name: String,
+    pub guid: MixerGuid,
+    pub values: BTreeMap<String, f32>,
+}
+
+/// A parameter exposed to scripts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExposedParameter {
+    /// The script-facing name, when editor data is available.
+    pub name: Option<String>,
+    pub name_hash: u32,
+    /// Label of the parameter it drives, e.g. `Music/Volume`.
+    pub parameter: String,
+}
+
+/// The structure of one AudioMixer, ready for auditing or JSON export.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudioMixerTree {
+    pub name: String,
+    /// Top-level groups; normally just `Master`.
+    pub groups: Vec<MixerGroup>,
+    pub snapshots: Vec<MixerSnapshot>,
+    /// Name of the snapshot active on load, whose values [`MixerEffect`]s report.
+    pub start_snapshot: Option<String>,
+    pub exposed_parameters: Vec<ExposedParameter>,
+}
+
+impl AudioMixerTree {
+    /// Join a mixer's constant with its group, effect and snapshot controllers.
+    ///
+    /// Controllers are matched by GUID, so passing every controller of a file is fine.
+    pub fn build(
+        mixer: &AudioMixerController,
+        groups: &[AudioMixerGroupController],
+        effects: &[AudioMixerEffectController],
+        snapshots: &[AudioMixerSnapshotController],
+    ) -> Self {
+        let constant = &mixer.constant;
+        let group_names: Vec<String> = (0..constant.groups.len())
+            .map(|i| {
+                constant
+                    .group_names
+                    .get(i)
+                    .filter(|n| !n.is_empty())
+                    .cloned()
+                    .or_else(|| {
+                        let guid = constant.group_guids.get(i)?;
+                        groups
+                            .iter()
+                            .find(|g| g.group_id == *guid)
+                            .map(|g| g.name.clone())
+                    })
+                    .unwrap_or_else(|| format!("Group {i}"))
+            })
+            .collect();
+        let effect_controllers: Vec<Option<&AudioMixerEffectController>> =
+            (0..constant.effects.len())
+                .map(|i| {
+                    let guid = constant.effect_guids.get(i)?;
+                    effects.iter().find(|e| e.effect_id == *guid)
+                })
+                .collect();
+
+        // Label every parameter index, and the parameter GUIDs the controllers use for them.
+        let mut labels: HashMap<u32, String> = HashMap::new();
+        let mut guid_labels: HashMap<MixerGuid, String> = HashMap::new();
+        for (i, group) in constant.groups.iter().enumerate() {
+            let controller = constant
+                .group_guids
+                .get(i)
+                .and_then(|guid| groups.iter().find(|g| g.group_id == *guid));
+            for (index, suffix, guid) in [
+                (group.volume_index, "Volume", controller.map(|c| c.volume)),
+                (group.pitch_index, "Pitch", controller.map(|c| c.pitch)),
+                (group.send_index, "Send", controller.map(|c| c.send)),
+            ] {
+                if index == NO_INDEX {
+                    continue;
+                }
+                let label = format!("{}/{suffix}", group_names[i]);
+                if let Some(guid) = guid.filter(|g| !g.is_zero()) {
+                    guid_labels.insert(guid, label.clone());
+                }
+                labels.insert(index, label);
+            }
+        }
+        let mut effect_names = Vec::with_capacity(constant.effects.len());
+        let mut parameter_names = Vec::with_capacity(constant.effects.len());
+        for (i, effect) in constant.effects.iter().enumerate() {
+            let controller = effect_controllers[i];
+            let name = controller
+                .map(|c| c.effect_name.clone())
+                .unwrap_or_else(|| format!("Effect {i}"));
+            let group = group_names
+                .get(effect.group_index)
+                .map_or("?", String::as_str);
+            let prefix = format!("{group}/{name}");
+            if effect.wet_mix_level_index != NO_INDEX {
+                let label = format!("{prefix}/Wet");
+                if let Some(c) = controller.filter(|c| !c.mix_level.is_zero()) {
+                    guid_labels.insert(c.mix_level, label.clone());
+                }
+                labels.insert(effect.wet_mix_level_index, label);
+            }
+            let mut names = Vec::with_capacity(effect.parameter_indices.len());
+            for (k, &index) in effect.parameter_indices.iter().enumerate() {
+                let parameter = controller.and_then(|c| c.parameters.get(k));
+                let name = parameter.map_or_else(|| format!("Parameter {k}"), |p| p.name.clone());
+                let label = format!("{prefix}/{name}");
+                if let Some(p) = parameter {
+                    guid_labels.insert(p.guid, label.clone());
+                }
+                labels.insert(index, label);
+                names.push(name);
+            }
+            effect_names.push(name);
+            parameter_names.push(names);
+        }
+        let label = |index: u32| {
+            labels
+                .get(&index)
+                .cloned()
+                .unwrap_or_else(|| format!("#{index}"))
+        };
+
+        let snapshot_list: Vec<MixerSnapshot> = constant
+            .snapshots
+            .iter()
+            .enumerate()
+            .map(|(i, snapshot)| {
+                let guid = constant.snapshot_guids.get(i).copied().unwrap_or_default();
+                MixerSnapshot {
+                    name: constant
+                        .snapshot_names
+                        .get(i)
+                        .filter(|n| !n.is_empty())
+                        .cloned()
+                        .or_else(|| {
+                            snapshots
+                                .iter()
+                                .find(|s| s.snapshot_id == guid)
+                                .map(|s| s.name.clone())
+                        })
+                        .unwrap_or_else(|| format!("Snapshot {i}")),
+                    guid,
+                    values: snapshot
+                        .values
+                        .iter()
+                        .enumerate()
+                        .map(|(index, value)| (label(index as u32), *value))
+                        .collect(),
+                }
+            })
+            .collect();
+
+        // `m_Snapshots` and the constant list snapshots in the same order; Unity starts in the
+        // first one unless told otherwise.
+        let start = mixer
+            .start_snapshot
+            .and_then(|start| mixer.snapshots.iter().position(|r| *r == start))
+            .filter(|&i| i < snapshot_list.len())
+            .unwrap_or(0);
+        let start_values = constant.snapshots.get(start).map(|s| s.values.as_slice());
+        let value = |index: u32| {
+            start_values
+                .and_then(|v| v.get(index as usize))
+                .copied()
+                .unwrap_or(0.0)
+        };
+
+        let group_node = |i: usize| MixerGroup {
+            name: group_names[i].clone(),
+            guid: constant.group_guids.get(i).copied().unwrap_or_default(),
+            mute: constant.groups[i].mute,
+            solo: constant.groups[i].solo,
+            bypass_effects: constant.groups[i].bypass_effects,
+            effects: constant
+                .effects
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| e.group_index == i)
+                .map(|(e, effect)| MixerEffect {
+                    name: effect_names[e].clone(),
+                    guid: constant.effect_guids.get(e).copied().unwrap_or_default(),
+                    bypass: effect.bypass,
+                    wet_mix: (effect.wet_mix_level_index != NO_INDEX)
+                        .then(|| value(effect.wet_mix_level_index)),
+                    parameters: parameter_names[e]
+                        .iter()
+                        .zip(&effect.parameter_indices)
+                        .map(|(name, &index)| (name.clone(), value(index)))
+                        .collect(),
+                })
+                .collect(),
+            children: Vec::new(),
+        };
+        // Parents precede their children in the constant, so filling children from the back
+        // completes every subtree before it is moved into its parent.
+        let mut nodes: Vec<Option<MixerGroup>> = (0..constant.groups.len())
+            .map(|i| Some(group_node(i)))
+            .collect();
+        let mut roots = Vec::new();
+        for i in (0..nodes.len()).rev() {
+            match constant.groups[i].parent.filter(|&p| p < i) {
+                Some(parent) => {
+                    let child = nodes[i].take().expect("visited once");
+                    if let Some(parent) = nodes[parent].as_mut() {
+                        parent.children.insert(0, child);
+                    }
+                }
+                None => roots.push(i),
+            }
+        }
+        let groups_tree = roots
+            .into_iter()
+            .rev()
+            .filter_map(|i| nodes[i].take())
+            .collect();
+
+        let exposed_parameters = constant
+            .exposed_parameters
+            .iter()
+            .map(|&(name_hash, index)| {
+                let parameter = label(index);
+                ExposedParameter {
+                    name: mixer
+                        .exposed_parameter_names
+                        .iter()
+                        .find(|(guid, _)| guid_labels.get(guid) == Some(&parameter))
+                        .map(|(_, name)| name.clone()),
+                    name_hash,
+                    parameter,
+                }
+            })
+            .collect();
+
+        Self {
+            name: mixer.name.clone(),
+            groups: groups_tree,
+            start_snapshot: snapshot_list.get(start).map(|s| s.name.clone()),
+            snapshots: snapshot_list,
+            exposed_parameters,
+        }
+    }
+
+    /// Group names depth-first, parents before children.
+    pub fn group_names(&self) -> Vec<&str> {
+        fn walk<'a>(group: &'a MixerGroup, out: &mut Vec<&'a str>) {
+            out.push(&group.name);
+            group.children.iter().for_each(|c| walk(c, out));
+        }
+        let mut out = Vec::new();
+        self.groups.iter().for_each(|g| walk(g, &mut out));
+        out
+    }
+
+    pub fn snapshot(&self, name: &str) -> Option<&MixerSnapshot> {
+        self.snapshots.iter().find(|s| s.name == name)
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// Every AudioMixerController in `asset`, joined with the controllers stored next to it.
+pub fn audio_mixers(asset: &SerializedFile) -> Vec<AudioMixerTree> {
+    let mut mixers = Vec::new();
+    let mut groups = Vec::new();
+    let mut effects = Vec::new();
+    let mut snapshots = Vec::new();
+    for handle in asset.object_handles() {
+        let class_id = handle.class_id();
+        if !matches!(
+            class_id,
+            class_ids::AUDIO_MIXER_CONTROLLER
+                | class_ids::AUDIO_MIXER_GROUP_CONTROLLER
+                | class_ids::AUDIO_MIXER_EFFECT_CONTROLLER
+                | class_ids::AUDIO_MIXER_SNAPSHOT_CONTROLLER
+        ) {
+            continue;
+        }
+        let Ok(object) = handle.read() else {
+            continue;
+        };
+        let properties = object.class.properties();
+        match class_id {
+            class_ids::AUDIO_MIXER_CONTROLLER => {
+                if let Ok(mixer) = AudioMixerController::from_typetree(properties) {
+                    mixers.push((handle.path_id(), mixer));
+                }
+            }
+            class_ids::AUDIO_MIXER_GROUP_CONTROLLER => {
+                groups.extend(AudioMixerGroupController::from_typetree(properties));
+            }
+            class_ids::AUDIO_MIXER_EFFECT_CONTROLLER => {
+                effects.extend(AudioMixerEffectController::from_typetree(properties));
+            }
+            _ => snapshots.extend(AudioMixerSnapshotController::from_typetree(properties)),
+        }
+    }
+    mixers
+        .iter()
+        .map(|(path_id, mixer)| {
+            let own = |m: Option<ObjectRef>| m == Some(ObjectRef::new(0, *path_id));
+            let groups: Vec<_> = groups.iter().filter(|g| own(g.mixer)).cloned().collect();
+            let snapshots: Vec<_> = snapshots.iter().filter(|s| own(s.mixer)).cloned().collect();
+            AudioMixerTree::build(mixer, &groups, &effects, &snapshots)
+        })
+        .collect()
+}
+
+/// Split a NUL-separated name buffer (`vector<char>`).
+fn name_buffer(value: Option<&UnityValue>) -> Vec<String> {
+    let bytes: Vec<u8> = match value {
+        Some(UnityValue::Bytes(bytes)) => bytes.clone(),
+        Some(UnityValue::Array(items)) => items
+            .iter()
+            .filter_map(UnityValue::as_i64)
+            .map(|b| b as u8)
+            .collect(),
+        Some(UnityValue::String(s)) => s.as_bytes().to_vec(),
+        _ => return Vec::new(),
+    };
+    let mut names: Vec<String> = bytes
+        .split(|&b| b == 0)
+        .map(|name| String::from_utf8_lossy(name).into_owned())
+        .collect();
+    // The buffer ends with a NUL, which leaves one empty trailing entry.
+    if names.last().is_some_and(String::is_empty) {
+        names.pop();
+    }
+    names
+}
+
+fn index(fields: &Fields, key: &str) -> Option<usize> {
+    let value = fields.get(key)?.as_i64()?;
+    (value >= 0 && value != NO_INDEX as i64).then_some(value as usize)
+}
+
+fn flag(fields: &Fields, key: &str) -> bool {
+    fields.get(key).and_then(as_bool).unwrap_or(false)
+}
+
+fn string(fields: &Fields, key: &str) -> String {
+    fields
+        .get(key)
+        .and_then(UnityValue::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn pptrs(fields: &Fields, key: &str) -> Vec<ObjectRef> {
+    match fields.get(key) {
+        Some(UnityValue::Array(items)) => items.iter().filter_map(parse_pptr).collect(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,359 @@
+//! Avatar (class 90) parsing for humanoid bone mapping.
+//!
+//! An Avatar's runtime `AvatarConstant` (`m_Avatar`) stores the skeleton as parent indices plus
+//! CRC32 hashes of transform paths; `m_TOS` maps those hashes back to paths such as
+//! `Armature/Hips/Spine`. Humanoid avatars also carry a human skeleton whose nodes are mapped to
+//! Unity's standard bones through `m_HumanBoneIndex` (body) and `m_HandBoneIndex` (fingers).
+//!
+//! Skinned meshes store the same path hashes in `m_BoneNameHashes`, so [`Avatar::path_for_hash`]
+//! and [`Avatar::human_bone_for_hash`] label mesh joints directly.
+//!
+//! When the constant has no mapping for a bone (generic avatars, or a stripped `m_Human`), the
+//! 2019+ `m_HumanDescription` table of human names to transform names is used instead.
+
+use crate::animator::{Fields, array, deref, float, parse_tos};
+use crate::error::{BinaryError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use unity_asset_core::UnityValue;
+
+macro_rules! human_bones {
+    ($($bone:ident),* $(,)?) => {
+        /// Unity's standard human bones, in `HumanBodyBones` order.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+        pub enum HumanBone {
+            $($bone),*
+        }
+
+        impl HumanBone {
+            /// Every bone, indexed by its `HumanBodyBones` value.
+            pub const ALL: &'static [HumanBone] = &[$(HumanBone::$bone),*];
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(HumanBone::$bone => stringify!($bone)),*
+                }
+            }
+        }
+    };
+}
+
+human_bones!(
+    Hips,
+    LeftUpperLeg,
+    RightUpperLeg,
+    LeftLowerLeg,
+    RightLowerLeg,
+    LeftFoot,
+    RightFoot,
+    Spine,
+    Chest,
+    Neck,
+    Head,
+    LeftShoulder,
+    RightShoulder,
+    LeftUpperArm,
+    RightUpperArm,
+    LeftLowerArm,
+    RightLowerArm,
+    LeftHand,
+    RightHand,
+    LeftToes,
+    RightToes,
+    LeftEye,
+    RightEye,
+    Jaw,
+    LeftThumbProximal,
+    LeftThumbIntermediate,
+    LeftThumbDistal,
+    LeftIndexProximal,
+    LeftIndexIntermediate,
+    LeftIndexDistal,
+    LeftMiddleProximal,
+    LeftMiddleIntermediate,
+    LeftMiddleDistal,
+    LeftRingProximal,
+    LeftRingIntermediate,
+    LeftRingDistal,
+    LeftLittleProximal,
+    LeftLittleIntermediate,
+    LeftLittleDistal,
+    RightThumbProximal,
+    RightThumbIntermediate,
+    RightThumbDistal,
+    RightIndexProximal,
+    RightIndexIntermediate,
+    RightIndexDistal,
+    RightMiddleProximal,
+    RightMiddleIntermediate,
+    RightMiddleDistal,
+    RightRingProximal,
+    RightRingIntermediate,
+    RightRingDistal,
+    RightLittleProximal,
+    RightLittleIntermediate,
+    RightLittleDistal,
+    UpperChest,
+);
+
+impl HumanBone {
+    /// Parse a bone name, ignoring spaces (`HumanDescription` uses `Left Thumb Proximal`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name: String = name.chars().filter(|c| !c.is_whitespace()).collect();
+        Self::ALL.iter().copied().find(|bone| bone.name() == name)
+    }
+
+    /// Fingers of one hand in `m_HandBoneIndex` order.
+    fn hand(left: bool) -> &'static [HumanBone] {
+        let first = if left {
+            HumanBone::LeftThumbProximal
+        } else {
+            HumanBone::RightThumbProximal
+        } as usize;
+        &Self::ALL[first..first + 15]
+    }
+}
+
+impl fmt::Display for HumanBone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Body bones in mecanim's `m_HumanBoneIndex` order. `UpperChest` was added in 5.6; older
+/// avatars store the same list without it.
+const MECANIM_BODY: [HumanBone; 25] = [
+    HumanBone::Hips,
+    HumanBone::LeftUpperLeg,
+    HumanBone::RightUpperLeg,
+    HumanBone::LeftLowerLeg,
+    HumanBone::RightLowerLeg,
+    HumanBone::LeftFoot,
+    HumanBone::RightFoot,
+    HumanBone::Spine,
+    HumanBone::Chest,
+    HumanBone::UpperChest,
+    HumanBone::Neck,
+    HumanBone::Head,
+    HumanBone::LeftShoulder,
+    HumanBone::RightShoulder,
+    HumanBone::LeftUpperArm,
+    HumanBone::RightUpperArm,
+    HumanBone::LeftLowerArm,
+    HumanBone::RightLowerArm,
+    HumanBone::LeftHand,
+    HumanBone::RightHand,
+    HumanBone::LeftToes,
+    HumanBone::RightToes,
+    HumanBone::LeftEye,
+    HumanBone::RightEye,
+    HumanBone::Jaw,
+];
+
+/// Local transform of a skeleton node.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AvatarTransform {
+    pub translation: [f32; 3],
+    /// Quaternion as `[x, y, z, w]`.
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AvatarNode {
+    /// Transform path relative to the avatar root; empty for the root itself.
+    pub path: String,
+    pub path_hash: u32,
+    pub parent: Option<usize>,
+    pub default_pose: Option<AvatarTransform>,
+}
+
+impl AvatarNode {
+    /// Last path segment (the transform name).
+    pub fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or_default()
+    }
+}
+
+/// A parsed Avatar (class 90).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Avatar {
+    pub name: String,
+    pub nodes: Vec<AvatarNode>,
+    /// Human bone to transform path, for humanoid avatars.
+    pub human_bones: BTreeMap<HumanBone, String>,
+    /// `m_TOS`: path hash to transform path.
+    pub paths: HashMap<u32, String>,
+}
+
+impl Avatar {
+    /// Parse Avatar from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        let constant = properties
+            .get("m_Avatar")
+            .and_then(deref)
+            .ok_or_else(|| BinaryError::invalid_data("Avatar has no m_Avatar"))?;
+        let paths = parse_tos(properties.get("m_TOS"));
+        let path = |hash: u32| {
+            paths
+                .get(&hash)
+                .cloned()
+                .unwrap_or_else(|| hash.to_string())
+        };
+
+        let skeleton_ids = |skeleton: &Fields| -> Vec<u32> { ids(skeleton.get("m_ID")) };
+        let mut nodes = Vec::new();
+        if let Some(skeleton) = constant.get("m_AvatarSkeleton").and_then(deref) {
+            let poses: Vec<AvatarTransform> = constant
+                .get("m_DefaultPose")
+                .and_then(deref)
+                .map(|pose| array(pose, "m_X").map(transform).collect())
+                .unwrap_or_default();
+            nodes = array(skeleton, "m_Node")
+                .zip(skeleton_ids(skeleton))
+                .enumerate()
+                .map(|(i, (node, hash))| AvatarNode {
+                    path: path(hash),
+                    path_hash: hash,
+                    parent: index(node.get("m_ParentId")),
+                    default_pose: poses.get(i).copied(),
+                })
+                .collect();
+        }
+
+        let mut human_bones = BTreeMap::new();
+        if let Some(human) = constant.get("m_Human").and_then(deref) {
+            let human_ids: Vec<u32> = human
+                .get("m_Skeleton")
+                .and_then(deref)
+                .map(skeleton_ids)
+                .unwrap_or_default();
+            let mut map = |bone: HumanBone, value: &UnityValue| {
+                if let Some(&hash) = index(Some(value)).and_then(|i| human_ids.get(i)) {
+                    human_bones.insert(bone, path(hash));
+                }
+            };
+
+            if let Some(UnityValue::Array(body)) = human.get("m_HumanBoneIndex") {
+                let order = MECANIM_BODY
+                    .iter()
+                    .filter(|&&bone| body.len() > 24 || bone != HumanBone::UpperChest);
+                for (&bone, value) in order.zip(body) {
+                    map(bone, value);
+                }
+            }
+            for (key, left) in [("m_LeftHand", true), ("m_RightHand", false)] {
+                if let Some(UnityValue::Array(fingers)) = human
+                    .get(key)
+                    .and_then(deref)
+                    .and_then(|hand| hand.get("m_HandBoneIndex"))
+                {
+                    for (&bone, value) in HumanBone::hand(left).iter().zip(fingers) {
+                        map(bone, value);
+                    }
+                }
+            }
+        }
+
+        // `m_HumanDescription` maps human names to transform names; match them against the
+        // skeleton for bones the constant did not cover.
+        if let Some(description) = properties.get("m_HumanDescription").and_then(deref) {
+            for entry in array(description, "m_Human") {
+                let (Some(bone), Some(transform_name)) = (
+                    entry
+                        .get("m_HumanName")
+                        .and_then(UnityValue::as_str)
+                        .and_then(HumanBone::from_name),
+                    entry.get("m_BoneName").and_then(UnityValue::as_str),
+                ) else {
+                    continue;
+                };
+                if let Some(node) = nodes.iter().find(|n| n.name() == transform_name) {
+                    human_bones.entry(bone).or_insert_with(|| node.path.clone());
+                }
+            }
+        }
+
+        Ok(Self {
+            name: properties
+                .get("m_Name")
+                .and_then(UnityValue::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            nodes,
+            human_bones,
+            paths,
+        })
+    }
+
+    pub fn is_human(&self) -> bool {
+        !self.human_bones.is_empty()
+    }
+
+    /// Transform path of a standard human bone.
+    pub fn bone_path(&self, human_bone: HumanBone) -> Option<&str> {
+        self.human_bones.get(&human_bone).map(String::as_str)
+    }
+
+    pub fn human_bone_for_path(&self, path: &str) -> Option<HumanBone> {
+        self.human_bones
+            .iter()
+            .find(|(_, p)| p.as_str() == path)
+            .map(|(bone, _)| *bone)
+    }
+
+    /// Resolve a path hash, e.g. an entry of a mesh's `m_BoneNameHashes`.
+    pub fn path_for_hash(&self, hash: u32) -> Option<&str> {
+        self.paths.get(&hash).map(String::as_str)
+    }
+
+    pub fn human_bone_for_hash(&self, hash: u32) -> Option<HumanBone> {
+        self.path_for_hash(hash)
+            .and_then(|path| self.human_bone_for_path(path))
+    }
+
+    pub fn node(&self, path: &str) -> Option<&AvatarNode> {
+        self.nodes.iter().find(|n| n.path == path)
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// A signed index where `-1` means none.
+fn index(value: Option<&UnityValue>) -> Option<usize> {
+    value
+        .and_then(UnityValue::as_i64)
+        .filter(|&i| i >= 0)
+        .map(|i| i as usize)
+}
+
+fn ids(value: Option<&UnityValue>) -> Vec<u32> {
+    match value {
+        Some(UnityValue::Array(items)) => items
+            .iter()
+            .filter_map(UnityValue::as_i64)
+            .map(|v| v as u32)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// `xform { t, q, s }`; `t` and `s` are `float4` before 5.4 and `float3` after.
+fn transform(xform: &Fields) -> AvatarTransform {
+    let component = |key: &str| xform.get(key).and_then(UnityValue::as_object);
+    let vec3 = |key: &str, default: f32| {
+        component(key).map_or([default; 3], |v| {
+            [float(v, "x"), float(v, "y"), float(v, "z")]
+        })
+    };
+    AvatarTransform {
+        translation: vec3("t", 0.0),
+        rotation: component("q").map_or([0.0, 0.0, 0.0, 1.0], |q| {
+            [float(q, "x"), float(q, "y"), float(q, "z"), float(q, "w")]
+        }),
+        scale: vec3("s", 1.0),
+    }
+}
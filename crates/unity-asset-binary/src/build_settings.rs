@@ -0,0 +1,124 @@
+//! Player build settings: shipped scenes and the engine version.
+//!
+//! `globalgamemanagers` (`mainData` before Unity 5.3) holds one BuildSettings object (class 141)
+//! listing the scenes in build order and the exact engine version the player was built with.
+//! Only enabled scenes are listed. Before 5.x the list is `levels`; later it is `scenes`. The
+//! remaining boolean fields (`hasPROVersion`, `isDebugBuild`, ...) come and go between releases,
+//! so they are collected by name.
+//!
+//! [`find_build_settings`] parses the first BuildSettings of a serialized file.
+
+use crate::asset::SerializedFile;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use unity_asset_core::{UnityValue, class_ids};
+
+type Fields = indexmap::IndexMap<String, UnityValue>;
+
+/// A parsed BuildSettings (class 141).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildSettings {
+    /// Scene paths in build order (`Assets/Scenes/Main.unity`); index `N` is `levelN`.
+    pub scenes: Vec<String>,
+    /// Engine version the player was built with (`m_Version`).
+    pub version: String,
+    /// `preloadedPlugins`: native plugins loaded before the first scene.
+    pub preloaded_plugins: Vec<String>,
+    pub build_tags: Vec<String>,
+    pub enabled_vr_devices: Vec<String>,
+    /// Boolean build flags by field name (`hasPROVersion`, `isDebugBuild`, ...).
+    pub flags: BTreeMap<String, bool>,
+}
+
+impl BuildSettings {
+    /// Parse BuildSettings from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        let scenes = properties
+            .get("scenes")
+            .or_else(|| properties.get("levels"))
+            .map(scene_paths)
+            .unwrap_or_default();
+        let flags = properties
+            .iter()
+            .filter_map(|(key, value)| match value {
+                UnityValue::Bool(b) => Some((key.clone(), *b)),
+                _ => None,
+            })
+            .collect();
+
+        Ok(Self {
+            scenes,
+            version: properties
+                .get("m_Version")
+                .and_then(UnityValue::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            preloaded_plugins: strings(properties.get("preloadedPlugins")),
+            build_tags: strings(properties.get("buildTags")),
+            enabled_vr_devices: strings(properties.get("enabledVRDevices")),
+            flags,
+        })
+    }
+
+    /// A boolean build flag, by TypeTree field name.
+    pub fn flag(&self, name: &str) -> Option<bool> {
+        self.flags.get(name).copied()
+    }
+
+    /// Whether this is a development build (`isDebugBuild`).
+    pub fn is_debug_build(&self) -> bool {
+        self.flag("isDebugBuild").unwrap_or(false)
+    }
+
+    /// Scene name of `levelN`: the file name of its path without `.unity`.
+    pub fn scene_name(&self, level: usize) -> Option<&str> {
+        let path = self.scenes.get(level)?;
+        let name = path.rsplit('/').next().unwrap_or(path);
+        Some(name.strip_suffix(".unity").unwrap_or(name))
+    }
+}
+
+/// Parse the first BuildSettings in `asset` (normally `globalgamemanagers`).
+pub fn find_build_settings(asset: &SerializedFile) -> Option<BuildSettings> {
+    asset
+        .object_handles()
+        .filter(|handle| handle.class_id() == class_ids::BUILD_SETTINGS)
+        .find_map(|handle| handle.read().ok()?.as_build_settings().ok())
+}
+
+/// Scene list entries are plain paths; editor-style `{enabled, path}` entries keep enabled ones.
+fn scene_paths(value: &UnityValue) -> Vec<String> {
+    let Some(items) = value.as_array() else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| match item {
+            UnityValue::String(path) => Some(path.clone()),
+            UnityValue::Object(scene) => {
+                let enabled = match scene.get("enabled") {
+                    Some(UnityValue::Bool(b)) => *b,
+                    Some(UnityValue::Integer(i)) => *i != 0,
+                    _ => true,
+                };
+                enabled
+                    .then(|| scene.get("path").and_then(UnityValue::as_str))
+                    .flatten()
+                    .map(str::to_string)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn strings(value: Option<&UnityValue>) -> Vec<String> {
+    match value {
+        Some(UnityValue::Array(items)) => items
+            .iter()
+            .filter_map(UnityValue::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
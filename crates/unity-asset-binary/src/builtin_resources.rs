@@ -0,0 +1,235 @@
+//! Unity's built-in resource files (`unity default resources`, `unity_builtin_extra`).
+//!
+//! Engine-provided assets such as the default material, the UGUI sprites, primitive meshes and the
+//! Standard shader live in two files that ship with the editor/player rather than with the content:
+//!
+//! - `Library/unity default resources` (YAML GUID `0000000000000000e000000000000000`)
+//! - `Resources/unity_builtin_extra` (YAML GUID `0000000000000000f000000000000000`)
+//!
+//! Serialized files reference them through ordinary externals, so without special handling every
+//! such `PPtr` looks like an unresolved dependency. The `path_id`s of the well-known objects in
+//! these files have been stable across Unity releases; [`BUILTIN_RESOURCES`] lists the common ones.
+//!
+//! ```
+//! use unity_asset_binary::builtin_resources::{BuiltinReference, BuiltinResourceFile};
+//!
+//! let file = BuiltinResourceFile::from_external_path("Resources/unity_builtin_extra").unwrap();
+//! let r = BuiltinReference::new(file, 10303);
+//! assert_eq!(r.name(), "Default-Material");
+//! ```
+
+use std::fmt;
+
+/// One of the two built-in resource files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BuiltinResourceFile {
+    /// `unity default resources` (primitive meshes, Arial, legacy defaults).
+    DefaultResources,
+    /// `unity_builtin_extra` (default materials, Standard/UI shaders, UGUI sprites).
+    BuiltinExtra,
+}
+
+impl BuiltinResourceFile {
+    /// File name as it appears at the end of an external path.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            BuiltinResourceFile::DefaultResources => "unity default resources",
+            BuiltinResourceFile::BuiltinExtra => "unity_builtin_extra",
+        }
+    }
+
+    /// GUID used by YAML references (`{fileID: ..., guid: ..., type: 0}`), in text byte order.
+    pub fn guid(self) -> [u8; 16] {
+        let mut guid = [0u8; 16];
+        guid[8] = match self {
+            BuiltinResourceFile::DefaultResources => 0xe0,
+            BuiltinResourceFile::BuiltinExtra => 0xf0,
+        };
+        guid
+    }
+
+    /// Recognize a `SerializedFile` external path (e.g. `Library/unity default resources`).
+    ///
+    /// Only the file name is compared (case-insensitively), since players and the editor store
+    /// these under different directories.
+    pub fn from_external_path(path: &str) -> Option<Self> {
+        let normalized = path.replace('\\', "/");
+        let file_name = normalized.rsplit('/').next().unwrap_or("");
+        [
+            BuiltinResourceFile::DefaultResources,
+            BuiltinResourceFile::BuiltinExtra,
+        ]
+        .into_iter()
+        .find(|f| file_name.eq_ignore_ascii_case(f.file_name()))
+    }
+
+    /// Recognize a YAML reference GUID (see [`BuiltinResourceFile::guid`]).
+    pub fn from_guid(guid: &[u8; 16]) -> Option<Self> {
+        [
+            BuiltinResourceFile::DefaultResources,
+            BuiltinResourceFile::BuiltinExtra,
+        ]
+        .into_iter()
+        .find(|f| f.guid() == *guid)
+    }
+}
+
+impl fmt::Display for BuiltinResourceFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.file_name())
+    }
+}
+
+/// A well-known object inside a built-in resource file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinResource {
+    pub file: BuiltinResourceFile,
+    pub path_id: i64,
+    pub class_id: i32,
+    pub name: &'static str,
+}
+
+const fn builtin(
+    file: BuiltinResourceFile,
+    path_id: i64,
+    class_id: i32,
+    name: &'static str,
+) -> BuiltinResource {
+    BuiltinResource {
+        file,
+        path_id,
+        class_id,
+        name,
+    }
+}
+
+use BuiltinResourceFile::{BuiltinExtra, DefaultResources};
+
+/// Well-known built-in objects, keyed by (`file`, `path_id`).
+pub const BUILTIN_RESOURCES: &[BuiltinResource] = &[
+    builtin(DefaultResources, 10102, 128, "Arial"),
+    builtin(DefaultResources, 10202, 43, "Cube"),
+    builtin(DefaultResources, 10206, 43, "Cylinder"),
+    builtin(DefaultResources, 10207, 43, "Sphere"),
+    builtin(DefaultResources, 10208, 43, "Capsule"),
+    builtin(DefaultResources, 10209, 43, "Plane"),
+    builtin(DefaultResources, 10210, 43, "Quad"),
+    builtin(BuiltinExtra, 45, 48, "Standard (Specular setup)"),
+    builtin(BuiltinExtra, 46, 48, "Standard"),
+    builtin(BuiltinExtra, 10301, 21, "Default-Particle"),
+    builtin(BuiltinExtra, 10303, 21, "Default-Material"),
+    builtin(BuiltinExtra, 10304, 21, "Default-Skybox"),
+    builtin(BuiltinExtra, 10753, 48, "Sprites/Default"),
+    builtin(BuiltinExtra, 10754, 21, "Sprites-Default"),
+    builtin(BuiltinExtra, 10770, 48, "UI/Default"),
+    builtin(BuiltinExtra, 10901, 213, "Checkmark"),
+    builtin(BuiltinExtra, 10905, 213, "UISprite"),
+    builtin(BuiltinExtra, 10907, 213, "Background"),
+    builtin(BuiltinExtra, 10911, 213, "InputFieldBackground"),
+    builtin(BuiltinExtra, 10913, 213, "Knob"),
+    builtin(BuiltinExtra, 10915, 213, "DropdownArrow"),
+    builtin(BuiltinExtra, 10917, 213, "UIMask"),
+];
+
+/// Look up a well-known built-in object.
+pub fn lookup_builtin_resource(
+    file: BuiltinResourceFile,
+    path_id: i64,
+) -> Option<&'static BuiltinResource> {
+    BUILTIN_RESOURCES
+        .iter()
+        .find(|r| r.file == file && r.path_id == path_id)
+}
+
+/// A `PPtr` target inside a built-in resource file.
+///
+/// `resource` is `None` when the `path_id` is not in [`BUILTIN_RESOURCES`]; the reference is still
+/// known to point at engine data rather than at a missing dependency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinReference {
+    pub file: BuiltinResourceFile,
+    pub path_id: i64,
+    pub resource: Option<&'static BuiltinResource>,
+}
+
+impl BuiltinReference {
+    pub fn new(file: BuiltinResourceFile, path_id: i64) -> Self {
+        Self {
+            file,
+            path_id,
+            resource: lookup_builtin_resource(file, path_id),
+        }
+    }
+
+    /// Classify an external `PPtr` by the external's path.
+    pub fn from_external_path(path: &str, path_id: i64) -> Option<Self> {
+        BuiltinResourceFile::from_external_path(path).map(|file| Self::new(file, path_id))
+    }
+
+    /// Object name from the built-in table, or `"{file}#{path_id}"` for unlisted objects.
+    pub fn name(&self) -> String {
+        match self.resource {
+            Some(r) => r.name.to_string(),
+            None => format!("{}#{}", self.file, self.path_id),
+        }
+    }
+}
+
+impl fmt::Display for BuiltinReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.resource {
+            Some(r) => write!(f, "builtin:{}", r.name),
+            None => write!(f, "builtin:{}#{}", self.file, self.path_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_external_paths_and_guids() {
+        assert_eq!(
+            BuiltinResourceFile::from_external_path("Library/unity default resources"),
+            Some(BuiltinResourceFile::DefaultResources)
+        );
+        assert_eq!(
+            BuiltinResourceFile::from_external_path("resources\\Unity_Builtin_Extra"),
+            Some(BuiltinResourceFile::BuiltinExtra)
+        );
+        assert_eq!(
+            BuiltinResourceFile::from_external_path("archive:/CAB-abc/CAB-abc"),
+            None
+        );
+
+        for file in [
+            BuiltinResourceFile::DefaultResources,
+            BuiltinResourceFile::BuiltinExtra,
+        ] {
+            assert_eq!(BuiltinResourceFile::from_guid(&file.guid()), Some(file));
+        }
+        assert_eq!(BuiltinResourceFile::from_guid(&[0u8; 16]), None);
+    }
+
+    #[test]
+    fn table_has_unique_keys_and_unlisted_ids_still_classify() {
+        for (i, a) in BUILTIN_RESOURCES.iter().enumerate() {
+            assert!(
+                BUILTIN_RESOURCES[i + 1..]
+                    .iter()
+                    .all(|b| (a.file, a.path_id) != (b.file, b.path_id)),
+                "duplicate builtin entry: {:?}",
+                a
+            );
+        }
+
+        let r =
+            BuiltinReference::from_external_path("Library/unity default resources", 10202).unwrap();
+        assert_eq!(r.to_string(), "builtin:Cube");
+
+        let r = BuiltinReference::new(BuiltinResourceFile::BuiltinExtra, 1);
+        assert_eq!(r.resource, None);
+        assert_eq!(r.name(), "unity_builtin_extra#1");
+    }
+}
@@ -40,38 +40,27 @@ impl BundleCompression {
                 expected_uncompressed, limit
             )));
         }
-        let compression_type = header.flags & 0x3F; // CompressionTypeMask
+        // The blocks-info codec comes from the header flags only; each data block carries its own
+        // codec in its per-block flags (see `CompressionBlock::compression_type`). The two are
+        // independent, e.g. LZMA blocks info with LZ4HC data blocks.
+        let compression_type = header.compression_type()?;
+        if !compression_type.is_supported() {
+            return Err(BinaryError::unsupported_compression(format!(
+                "Blocks info uses unsupported compression: {}",
+                compression_type.name()
+            )));
+        }
 
-        match compression_type {
-            0 => {
-                // No compression
-                Ok(compressed_data.to_vec())
-            }
-            2 | 3 => {
-                // LZ4 or LZ4HC
-                decompress(compressed_data, CompressionType::Lz4, expected_uncompressed)
-            }
-            1 => {
-                // LZMA
-                decompress(
-                    compressed_data,
-                    CompressionType::Lzma,
-                    expected_uncompressed,
-                )
-            }
-            4 => {
-                // Brotli (newer Unity versions)
-                decompress(
-                    compressed_data,
-                    CompressionType::Brotli,
-                    expected_uncompressed,
-                )
-            }
-            _ => Err(BinaryError::unsupported(format!(
-                "Unknown compression type: {}",
-                compression_type
-            ))),
+        let data = decompress(compressed_data, compression_type, expected_uncompressed)?;
+        if data.len() < expected_uncompressed {
+            return Err(BinaryError::decompression_failed(format!(
+                "{} blocks info decompressed to {} bytes, expected {}",
+                compression_type.name(),
+                data.len(),
+                expected_uncompressed
+            )));
         }
+        Ok(data)
     }
 
     /// Parse compression blocks from decompressed blocks info
@@ -90,7 +79,7 @@ impl BundleCompression {
         let mut blocks = Vec::new();
 
         // Skip uncompressed data hash (16 bytes) - critical step
-        reader.read_bytes(16)?;
+        reader.skip_bytes(16)?;
 
         // Read compression blocks
         let block_count_i32 = reader.read_i32()?;
@@ -187,8 +176,8 @@ impl BundleCompression {
                     block.uncompressed_size, limit
                 )));
             }
-            let compressed = reader.read_bytes(block.compressed_size as usize)?;
-            let block_data = block.decompress(&compressed)?;
+            let compressed = reader.read_bytes_ref(block.compressed_size as usize)?;
+            let block_data = block.decompress(compressed)?;
             decompressed_data.extend_from_slice(&block_data);
         }
 
@@ -208,6 +197,19 @@ impl BundleCompression {
 
         let space_saved = total_uncompressed.saturating_sub(total_compressed);
 
+        let mut block_codecs: Vec<(CompressionType, usize)> = Vec::new();
+        let mut unknown_codec_blocks = 0;
+        for block in blocks {
+            match block.compression_type() {
+                Ok(codec) => match block_codecs.iter_mut().find(|(c, _)| *c == codec) {
+                    Some((_, count)) => *count += 1,
+                    None => block_codecs.push((codec, 1)),
+                },
+                Err(_) => unknown_codec_blocks += 1,
+            }
+        }
+        block_codecs.sort_by_key(|(c, _)| *c as u8);
+
         CompressionStats {
             block_count: blocks.len(),
             total_compressed_size: total_compressed,
@@ -219,6 +221,8 @@ impl BundleCompression {
             } else {
                 0
             },
+            block_codecs,
+            unknown_codec_blocks,
         }
     }
 
@@ -229,6 +233,15 @@ impl BundleCompression {
         }
 
         for (i, block) in blocks.iter().enumerate() {
+            let codec = block.compression_type()?;
+            if !codec.is_supported() {
+                return Err(BinaryError::unsupported_compression(format!(
+                    "Block {} uses unsupported compression: {}",
+                    i,
+                    codec.name()
+                )));
+            }
+
             if block.compressed_size == 0 {
                 return Err(BinaryError::invalid_data(format!(
                     "Block {} has zero compressed size",
@@ -273,18 +286,13 @@ impl BundleCompression {
 
     /// Check if compression type is supported
     pub fn is_compression_supported(compression_type: u32) -> bool {
-        match compression_type {
-            0 => true,     // None
-            1 => true,     // LZMA
-            2 | 3 => true, // LZ4/LZ4HC
-            4 => true,     // Brotli
-            _ => false,
-        }
+        CompressionType::from_flags(compression_type).is_ok_and(CompressionType::is_supported)
     }
 }
 
 /// Compression statistics
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct CompressionStats {
     pub block_count: usize,
     pub total_compressed_size: u64,
@@ -292,6 +300,10 @@ pub struct CompressionStats {
     pub compression_ratio: f64,
     pub space_saved: u64,
     pub average_block_size: u64,
+    /// Number of data blocks per codec (from per-block flags), ordered by codec id
+    pub block_codecs: Vec<(CompressionType, usize)>,
+    /// Data blocks whose flags name an unknown codec
+    pub unknown_codec_blocks: usize,
 }
 
 impl CompressionStats {
@@ -308,6 +320,7 @@ impl CompressionStats {
 
 /// Compression options for bundle processing
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct CompressionOptions {
     /// Maximum memory to use for decompression
     pub max_memory: Option<usize>,
@@ -319,17 +332,6 @@ pub struct CompressionOptions {
     pub preferred_compression: CompressionType,
 }
 
-impl Default for CompressionOptions {
-    fn default() -> Self {
-        Self {
-            max_memory: Some(1024 * 1024 * 1024), // 1GB
-            validate_blocks: true,
-            collect_stats: false,
-            preferred_compression: CompressionType::Lz4,
-        }
-    }
-}
-
 impl CompressionOptions {
     /// Create options for fast decompression (minimal validation)
     pub fn fast() -> Self {
@@ -350,6 +352,41 @@ impl CompressionOptions {
             preferred_compression: CompressionType::Lz4,
         }
     }
+
+    /// Set `max_memory`.
+    pub fn with_max_memory(mut self, max_memory: Option<usize>) -> Self {
+        self.max_memory = max_memory;
+        self
+    }
+
+    /// Set `validate_blocks`.
+    pub fn with_validate_blocks(mut self, validate_blocks: bool) -> Self {
+        self.validate_blocks = validate_blocks;
+        self
+    }
+
+    /// Set `collect_stats`.
+    pub fn with_collect_stats(mut self, collect_stats: bool) -> Self {
+        self.collect_stats = collect_stats;
+        self
+    }
+
+    /// Set `preferred_compression`.
+    pub fn with_preferred_compression(mut self, preferred_compression: CompressionType) -> Self {
+        self.preferred_compression = preferred_compression;
+        self
+    }
+}
+
+impl Default for CompressionOptions {
+    fn default() -> Self {
+        Self {
+            max_memory: Some(1024 * 1024 * 1024), // 1GB
+            validate_blocks: true,
+            collect_stats: false,
+            preferred_compression: CompressionType::Lz4,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -362,6 +399,7 @@ mod tests {
         assert!(BundleCompression::is_compression_supported(1)); // LZMA
         assert!(BundleCompression::is_compression_supported(2)); // LZ4
         assert!(BundleCompression::is_compression_supported(3)); // LZ4HC
+        assert!(!BundleCompression::is_compression_supported(4)); // LZHAM
         assert!(!BundleCompression::is_compression_supported(99)); // Unknown
     }
 
@@ -380,4 +418,33 @@ mod tests {
         assert_eq!(stats.space_saved, 1500);
         assert!(stats.is_effective());
     }
+
+    #[test]
+    fn test_compression_stats_block_codecs() {
+        let blocks = vec![
+            CompressionBlock::new(1000, 500, 3),
+            CompressionBlock::new(1000, 1000, 0x40),
+            CompressionBlock::new(1000, 400, 3),
+            CompressionBlock::new(1000, 600, 1),
+            CompressionBlock::new(1000, 600, 9),
+        ];
+
+        let stats = BundleCompression::get_compression_stats(&blocks);
+        assert_eq!(
+            stats.block_codecs,
+            vec![
+                (CompressionType::None, 1),
+                (CompressionType::Lzma, 1),
+                (CompressionType::Lz4Hc, 2),
+            ]
+        );
+        assert_eq!(stats.unknown_codec_blocks, 1);
+    }
+
+    #[test]
+    fn test_validate_blocks_rejects_unsupported_codec() {
+        let blocks = vec![CompressionBlock::new(100, 80, 4)];
+        let err = BundleCompression::validate_blocks(&blocks).unwrap_err();
+        assert!(err.to_string().contains("LZHAM"), "{err}");
+    }
 }
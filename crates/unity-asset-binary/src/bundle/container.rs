@@ -0,0 +1,75 @@
+//! Container path lookup
+//!
+//! The `AssetBundle` object (class 142) in a bundle's serialized file lists the project paths
+//! the bundle was built from, e.g. `assets/textures/hero.png`, in `m_Container`. These helpers
+//! map such paths to the objects they name, like UnityPy's `env.container`.
+
+use super::types::AssetBundle;
+use crate::addressables::container_entries;
+use crate::object::ObjectHandle;
+use indexmap::IndexMap;
+use std::path::Path;
+
+/// An object named by a container path: asset `asset_index` of the bundle, object `path_id`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContainerRef {
+    pub asset_index: usize,
+    pub path_id: i64,
+}
+
+impl AssetBundle {
+    /// Container paths of every `AssetBundle` object in the bundle, in declaration order
+    ///
+    /// Entries pointing outside this bundle are skipped; a path listed twice keeps its last
+    /// object. Built on each call, so it reflects edits to [`AssetBundle::assets`].
+    pub fn container(&self) -> IndexMap<String, ContainerRef> {
+        let mut container = IndexMap::new();
+        for (asset_index, asset) in self.assets.iter().enumerate() {
+            for (path, file_id, path_id) in container_entries(asset) {
+                let target = if file_id == 0 {
+                    Some(asset_index)
+                } else {
+                    usize::try_from(file_id - 1)
+                        .ok()
+                        .and_then(|i| asset.externals.get(i))
+                        .and_then(|external| self.asset_index_by_path(&external.path))
+                };
+                if let Some(asset_index) = target {
+                    container.insert(
+                        path,
+                        ContainerRef {
+                            asset_index,
+                            path_id,
+                        },
+                    );
+                }
+            }
+        }
+        container
+    }
+
+    /// Find the object a container path names
+    ///
+    /// Tries an exact match first, then an ASCII case-insensitive one, since Unity lowercases
+    /// container paths but callers often pass them as spelled in the project.
+    pub fn find_by_container_path(&self, path: &str) -> Option<ObjectHandle<'_>> {
+        let container = self.container();
+        let target = container.get(path).or_else(|| {
+            container
+                .iter()
+                .find(|(p, _)| p.eq_ignore_ascii_case(path))
+                .map(|(_, r)| r)
+        })?;
+        self.assets
+            .get(target.asset_index)?
+            .find_object_handle(target.path_id)
+    }
+
+    /// Index of the asset an external reference such as `archive:/CAB-x/CAB-x` points to
+    fn asset_index_by_path(&self, path: &str) -> Option<usize> {
+        let file_name = Path::new(path).file_name()?.to_str()?;
+        self.asset_names.iter().position(|name| {
+            name == path || Path::new(name).file_name().and_then(|n| n.to_str()) == Some(file_name)
+        })
+    }
+}
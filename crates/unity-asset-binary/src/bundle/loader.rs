@@ -3,12 +3,25 @@
 //! This module provides functionality for loading and managing
 //! resources from Unity AssetBundles.
 
+use super::manifest::AssetBundleManifest;
 use super::parser::BundleParser;
+use super::partial::{LoadedAsset, SingleLoadOptions};
 use super::types::{AssetBundle, BundleLoadOptions};
+use crate::addressables::{
+    AddressableLoad, AddressableObject, ContentCatalog, ResourceLocation, container_entries,
+};
 use crate::asset::Asset;
+#[cfg(feature = "fs")]
+use crate::data_source::FileSource;
+use crate::data_source::{DataSource, VirtualFileSystem};
 use crate::error::{BinaryError, Result};
+#[cfg(feature = "fs")]
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::path::Path;
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "fs")]
+use std::time::SystemTime;
 
 #[cfg(feature = "async")]
 use tokio::fs;
@@ -20,15 +33,70 @@ use tokio::fs;
 pub struct BundleLoader {
     /// Loaded bundles cache
     bundles: HashMap<String, AssetBundle>,
+    /// On-disk state of file-backed bundles, used by [`BundleLoader::refresh`]
+    #[cfg(feature = "fs")]
+    stamps: HashMap<String, FileStamp>,
     /// Loading options
     options: BundleLoadOptions,
 }
 
+/// Size, modification time and content hash of a loaded bundle file.
+#[cfg(feature = "fs")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+    sha256: [u8; 32],
+}
+
+#[cfg(feature = "fs")]
+impl FileStamp {
+    fn new(metadata: &std::fs::Metadata, data: &[u8]) -> Self {
+        Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+            sha256: Sha256::digest(data).into(),
+        }
+    }
+
+    fn matches_metadata(&self, metadata: &std::fs::Metadata) -> bool {
+        self.len == metadata.len() && self.modified == metadata.modified().ok()
+    }
+}
+
+/// Outcome of [`BundleLoader::refresh`].
+///
+/// All lists are sorted by bundle name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RefreshReport {
+    /// Bundles whose content changed and were re-parsed.
+    pub changed: Vec<String>,
+    /// Bundles whose file disappeared; they were unloaded.
+    pub removed: Vec<String>,
+    /// Bundles whose size/mtime changed but whose content hash did not (not re-parsed).
+    pub touched: Vec<String>,
+    /// Bundles that could not be re-read or re-parsed, with the error; they were unloaded.
+    pub failed: Vec<(String, String)>,
+    /// `(bundle, asset name)` pairs for parsed assets dropped by this refresh. Anything derived
+    /// from them (object handles, caches keyed by these names) is stale.
+    pub invalidated_assets: Vec<(String, String)>,
+}
+
+impl RefreshReport {
+    /// Whether any loaded bundle was re-parsed or unloaded.
+    pub fn has_changes(&self) -> bool {
+        !self.changed.is_empty() || !self.removed.is_empty() || !self.failed.is_empty()
+    }
+}
+
 impl BundleLoader {
     /// Create a new bundle loader
     pub fn new() -> Self {
         Self {
             bundles: HashMap::new(),
+            #[cfg(feature = "fs")]
+            stamps: HashMap::new(),
             options: BundleLoadOptions::default(),
         }
     }
@@ -37,11 +105,14 @@ impl BundleLoader {
     pub fn with_options(options: BundleLoadOptions) -> Self {
         Self {
             bundles: HashMap::new(),
+            #[cfg(feature = "fs")]
+            stamps: HashMap::new(),
             options,
         }
     }
 
     /// Load a bundle from file path
+    #[cfg(feature = "fs")]
     pub fn load_from_file<P: AsRef<Path>>(&mut self, path: P) -> Result<&AssetBundle> {
         let path_ref = path.as_ref();
         let path_str = path_ref.to_string_lossy().to_string();
@@ -52,17 +123,283 @@ impl BundleLoader {
         }
 
         // Read file data
-        let data = std::fs::read(path_ref)
-            .map_err(|e| BinaryError::generic(format!("Failed to read bundle file: {}", e)))?;
+        let metadata = std::fs::metadata(path_ref)?;
+        let data = std::fs::read(path_ref)?;
+        let stamp = FileStamp::new(&metadata, &data);
 
         // Parse bundle
         let bundle = BundleParser::from_bytes_with_options(data, self.options.clone())?;
 
         // Cache and return
+        self.stamps.insert(path_str.clone(), stamp);
         self.bundles.insert(path_str.clone(), bundle);
         Ok(self.bundles.get(&path_str).unwrap())
     }
 
+    /// Load one container entry (`m_Container` path, case-insensitive) of the bundle at `path`
+    /// without parsing the rest of it.
+    ///
+    /// Only the bundle header and directory, the SerializedFile metadata, the `AssetBundle`
+    /// object, the target object and its preload/`PPtr` closure (bounded by
+    /// [`SingleLoadOptions::max_depth`]) are read; UnityFS blocks are inflated only where those
+    /// bytes live. The bundle is not added to this loader's cache. See [`LoadedAsset::stats`]
+    /// for how much was touched.
+    #[cfg(feature = "fs")]
+    pub fn load_single<P: AsRef<Path>>(
+        &self,
+        path: P,
+        container: &str,
+        options: &SingleLoadOptions,
+    ) -> Result<LoadedAsset> {
+        self.load_single_from_source(FileSource::open(path)?, container, options)
+    }
+
+    /// [`BundleLoader::load_single`] for a bundle read from a [`DataSource`].
+    pub fn load_single_from_source(
+        &self,
+        source: impl DataSource + 'static,
+        container: &str,
+        options: &SingleLoadOptions,
+    ) -> Result<LoadedAsset> {
+        super::partial::load_single(source, container, &self.options, options)
+    }
+
+    /// Load a bundle from `dir` after its dependencies (recursively, each once).
+    ///
+    /// Bundle names are paths relative to `dir`, as in the build output folder. Returns the loader
+    /// keys of the loaded bundles in load order, `bundle` last. Cyclic dependencies are loaded
+    /// once, in discovery order.
+    #[cfg(feature = "fs")]
+    pub fn load_with_dependencies<P, R>(
+        &mut self,
+        dir: P,
+        bundle: &str,
+        resolver: &R,
+    ) -> Result<Vec<String>>
+    where
+        P: AsRef<Path>,
+        R: DependencyResolver + ?Sized,
+    {
+        let order = load_order(bundle, resolver);
+        let mut keys = Vec::with_capacity(order.len());
+        for name in order {
+            let path = dir.as_ref().join(&name);
+            self.load_from_file(&path)?;
+            keys.push(path.to_string_lossy().to_string());
+        }
+        Ok(keys)
+    }
+
+    /// [`BundleLoader::load_with_dependencies`] for bundles stored in `vfs` under their names.
+    ///
+    /// Loader keys are the bundle names.
+    pub fn load_with_dependencies_from_vfs<R>(
+        &mut self,
+        vfs: &VirtualFileSystem,
+        bundle: &str,
+        resolver: &R,
+    ) -> Result<Vec<String>>
+    where
+        R: DependencyResolver + ?Sized,
+    {
+        let order = load_order(bundle, resolver);
+        for name in &order {
+            self.load_from_vfs(vfs, name)?;
+        }
+        Ok(order)
+    }
+
+    /// Load the bundles an Addressables address (or label) needs and find its objects.
+    ///
+    /// `resolver` maps each bundle location of the catalog to a file on disk (catalog internal
+    /// ids usually start with a runtime placeholder such as
+    /// `{UnityEngine.AddressableAssets.Addressables.RuntimePath}`). Dependency bundles are loaded
+    /// before the bundle that needs them. Objects are looked up in the containing bundle's
+    /// `m_Container` by the location's internal id (case-insensitive).
+    #[cfg(feature = "fs")]
+    pub fn load_addressable<F>(
+        &mut self,
+        catalog: &ContentCatalog,
+        address: &str,
+        mut resolver: F,
+    ) -> Result<AddressableLoad>
+    where
+        F: FnMut(&ResourceLocation) -> Option<PathBuf>,
+    {
+        self.load_addressable_with(catalog, address, |loader, bundle| {
+            let path = resolver(bundle).ok_or_else(|| missing_bundle(bundle, address))?;
+            loader.load_from_file(&path)?;
+            Ok(path.to_string_lossy().to_string())
+        })
+    }
+
+    /// [`BundleLoader::load_addressable`] for bundles stored in `vfs`.
+    ///
+    /// `resolver` maps each bundle location to its VFS path, which is also its loader key.
+    pub fn load_addressable_from_vfs<F>(
+        &mut self,
+        vfs: &VirtualFileSystem,
+        catalog: &ContentCatalog,
+        address: &str,
+        mut resolver: F,
+    ) -> Result<AddressableLoad>
+    where
+        F: FnMut(&ResourceLocation) -> Option<String>,
+    {
+        self.load_addressable_with(catalog, address, |loader, bundle| {
+            let path = resolver(bundle).ok_or_else(|| missing_bundle(bundle, address))?;
+            loader.load_from_vfs(vfs, &path)?;
+            Ok(path)
+        })
+    }
+
+    /// Shared body of the `load_addressable*` methods; `load` loads one bundle location and
+    /// returns its loader key.
+    fn load_addressable_with<F>(
+        &mut self,
+        catalog: &ContentCatalog,
+        address: &str,
+        mut load: F,
+    ) -> Result<AddressableLoad>
+    where
+        F: FnMut(&mut Self, &ResourceLocation) -> Result<String>,
+    {
+        let locations = catalog.resolve(address);
+        if locations.is_empty() {
+            return Err(BinaryError::generic(format!(
+                "Address '{}' not found in catalog",
+                address
+            )));
+        }
+
+        let mut out = AddressableLoad::default();
+        for location in &locations {
+            let mut bundles: Vec<&ResourceLocation> = location
+                .dependencies
+                .iter()
+                .filter(|dep| dep.is_bundle())
+                .collect();
+            if location.is_bundle() {
+                bundles.push(location);
+            } else if !bundles.is_empty() {
+                let own = bundles.remove(0);
+                bundles.push(own);
+            }
+
+            let mut own_key = None;
+            for bundle in bundles {
+                let key = load(self, bundle)?;
+                if !out.bundles.contains(&key) {
+                    out.bundles.push(key.clone());
+                }
+                own_key = Some(key);
+            }
+
+            if location.is_bundle() {
+                continue;
+            }
+            let Some(key) = own_key else {
+                continue;
+            };
+            for (asset_index, asset) in self.bundles[&key].assets.iter().enumerate() {
+                for (container_path, file_id, path_id) in container_entries(asset) {
+                    if container_path.eq_ignore_ascii_case(&location.internal_id) {
+                        out.objects.push(AddressableObject {
+                            location: location.clone(),
+                            bundle: key.clone(),
+                            asset_index,
+                            container_path,
+                            file_id,
+                            path_id,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Re-check every bundle loaded via [`BundleLoader::load_from_file`] against the file system.
+    ///
+    /// Files whose size and modification time are unchanged are skipped without reading. Others
+    /// are hashed: identical content only refreshes the stored stamp, different content is
+    /// re-parsed in place. Deleted files (and files that fail to re-parse) are unloaded.
+    /// Bundles loaded from memory are never touched.
+    #[cfg(feature = "fs")]
+    pub fn refresh(&mut self) -> RefreshReport {
+        let mut report = RefreshReport::default();
+        let mut names: Vec<String> = self.stamps.keys().cloned().collect();
+        names.sort();
+
+        for name in names {
+            let path = Path::new(&name);
+            let metadata = match std::fs::metadata(path) {
+                Ok(m) => m,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    self.drop_bundle(&name, &mut report);
+                    report.removed.push(name);
+                    continue;
+                }
+                Err(e) => {
+                    self.drop_bundle(&name, &mut report);
+                    report.failed.push((name, e.to_string()));
+                    continue;
+                }
+            };
+            if self.stamps[&name].matches_metadata(&metadata) {
+                continue;
+            }
+
+            let data = match std::fs::read(path) {
+                Ok(v) => v,
+                Err(e) => {
+                    self.drop_bundle(&name, &mut report);
+                    report.failed.push((name, e.to_string()));
+                    continue;
+                }
+            };
+            let stamp = FileStamp::new(&metadata, &data);
+            if stamp.sha256 == self.stamps[&name].sha256 {
+                self.stamps.insert(name.clone(), stamp);
+                report.touched.push(name);
+                continue;
+            }
+
+            match BundleParser::from_bytes_with_options(data, self.options.clone()) {
+                Ok(bundle) => {
+                    if let Some(old) = self.bundles.insert(name.clone(), bundle) {
+                        report.invalidated_assets.extend(
+                            old.asset_names
+                                .into_iter()
+                                .map(|asset| (name.clone(), asset)),
+                        );
+                    }
+                    self.stamps.insert(name.clone(), stamp);
+                    report.changed.push(name);
+                }
+                Err(e) => {
+                    self.drop_bundle(&name, &mut report);
+                    report.failed.push((name, e.to_string()));
+                }
+            }
+        }
+
+        report
+    }
+
+    #[cfg(feature = "fs")]
+    fn drop_bundle(&mut self, name: &str, report: &mut RefreshReport) {
+        self.stamps.remove(name);
+        if let Some(old) = self.bundles.remove(name) {
+            report.invalidated_assets.extend(
+                old.asset_names
+                    .into_iter()
+                    .map(|asset| (name.to_string(), asset)),
+            );
+        }
+    }
+
     /// Load a bundle from memory
     pub fn load_from_memory(&mut self, name: String, data: Vec<u8>) -> Result<&AssetBundle> {
         // Check if already loaded
@@ -78,6 +415,31 @@ impl BundleLoader {
         Ok(self.bundles.get(&name).unwrap())
     }
 
+    /// Load a bundle from a [`DataSource`], keyed by its name.
+    ///
+    /// With lazy load options only the header and blocks info are read now; node reads fetch the
+    /// blocks they need from the source later.
+    pub fn load_from_source(&mut self, source: impl DataSource + 'static) -> Result<&AssetBundle> {
+        let name = source.name().to_string();
+        if !self.bundles.contains_key(&name) {
+            let bundle = BundleParser::from_source_with_options(source, self.options.clone())?;
+            self.bundles.insert(name.clone(), bundle);
+        }
+        Ok(self.bundles.get(&name).unwrap())
+    }
+
+    /// Load the bundle at `path` in `vfs`, keyed by `path`.
+    pub fn load_from_vfs(&mut self, vfs: &VirtualFileSystem, path: &str) -> Result<&AssetBundle> {
+        if !self.bundles.contains_key(path) {
+            let source = vfs.open(path).ok_or_else(|| {
+                BinaryError::generic(format!("Bundle not found in VFS: {}", path))
+            })?;
+            let bundle = BundleParser::from_source_with_options(source, self.options.clone())?;
+            self.bundles.insert(path.to_string(), bundle);
+        }
+        Ok(self.bundles.get(path).unwrap())
+    }
+
     /// Async load a bundle from file path
     #[cfg(feature = "async")]
     pub async fn load_from_file_async<P: AsRef<Path>>(&mut self, path: P) -> Result<&AssetBundle> {
@@ -90,14 +452,15 @@ impl BundleLoader {
         }
 
         // Read file data asynchronously
-        let data = fs::read(path_ref)
-            .await
-            .map_err(|e| BinaryError::generic(format!("Failed to read bundle file: {}", e)))?;
+        let metadata = fs::metadata(path_ref).await?;
+        let data = fs::read(path_ref).await?;
+        let stamp = FileStamp::new(&metadata, &data);
 
         // Parse bundle
         let bundle = BundleParser::from_bytes_with_options(data, self.options.clone())?;
 
         // Cache and return
+        self.stamps.insert(path_str.clone(), stamp);
         self.bundles.insert(path_str.clone(), bundle);
         Ok(self.bundles.get(&path_str).unwrap())
     }
@@ -114,11 +477,15 @@ impl BundleLoader {
 
     /// Unload a bundle
     pub fn unload_bundle(&mut self, name: &str) -> bool {
+        #[cfg(feature = "fs")]
+        self.stamps.remove(name);
         self.bundles.remove(name).is_some()
     }
 
     /// Unload all bundles
     pub fn unload_all(&mut self) {
+        #[cfg(feature = "fs")]
+        self.stamps.clear();
         self.bundles.clear();
     }
 
@@ -210,6 +577,82 @@ impl BundleLoader {
     }
 }
 
+/// Source of bundle dependency lists for [`BundleLoader::load_with_dependencies`].
+///
+/// Implemented by the build folder's [`AssetBundleManifest`] (text `.manifest`), its binary
+/// counterpart from the root manifest bundle
+/// ([`crate::asset_bundle_manifest::AssetBundleManifestObject`]) and by closures, e.g. one reading
+/// [`super::BundleManifest::dependency_names`] from each bundle's own `.manifest`.
+pub trait DependencyResolver {
+    /// Direct dependencies of `bundle`, by bundle name.
+    fn dependencies(&self, bundle: &str) -> Vec<String>;
+
+    /// All dependencies of `bundle`, each once, dependencies before their dependents.
+    ///
+    /// Cycles are cut where they close; `bundle` itself is never listed.
+    fn all_dependencies(&self, bundle: &str) -> Vec<String> {
+        let mut out = Vec::new();
+        let mut visiting = vec![bundle.to_string()];
+        collect_dependencies(self, bundle, &mut visiting, &mut out);
+        out
+    }
+}
+
+impl DependencyResolver for AssetBundleManifest {
+    fn dependencies(&self, bundle: &str) -> Vec<String> {
+        self.direct_dependencies(bundle).to_vec()
+    }
+}
+
+impl DependencyResolver for crate::asset_bundle_manifest::AssetBundleManifestObject {
+    fn dependencies(&self, bundle: &str) -> Vec<String> {
+        self.dependencies_of(bundle)
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+impl<F> DependencyResolver for F
+where
+    F: Fn(&str) -> Vec<String>,
+{
+    fn dependencies(&self, bundle: &str) -> Vec<String> {
+        self(bundle)
+    }
+}
+
+/// `bundle`'s dependencies in load order, then `bundle`.
+fn load_order<R: DependencyResolver + ?Sized>(bundle: &str, resolver: &R) -> Vec<String> {
+    let mut order = resolver.all_dependencies(bundle);
+    order.push(bundle.to_string());
+    order
+}
+
+fn missing_bundle(bundle: &ResourceLocation, address: &str) -> BinaryError {
+    BinaryError::generic(format!(
+        "No file for bundle '{}' (needed by '{}')",
+        bundle.internal_id, address
+    ))
+}
+
+fn collect_dependencies<R: DependencyResolver + ?Sized>(
+    resolver: &R,
+    bundle: &str,
+    visiting: &mut Vec<String>,
+    out: &mut Vec<String>,
+) {
+    for dep in resolver.dependencies(bundle) {
+        if out.contains(&dep) || visiting.contains(&dep) {
+            continue;
+        }
+        visiting.push(dep.clone());
+        collect_dependencies(resolver, &dep, visiting, out);
+        visiting.pop();
+        out.push(dep);
+    }
+}
+
 impl Default for BundleLoader {
     fn default() -> Self {
         Self::new()
@@ -237,6 +680,7 @@ impl BundleResourceManager {
     }
 
     /// Load a bundle with dependency tracking
+    #[cfg(feature = "fs")]
     pub fn load_bundle<P: AsRef<Path>>(
         &mut self,
         path: P,
@@ -326,6 +770,7 @@ impl Default for BundleResourceManager {
 
 /// Loader statistics
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct LoaderStatistics {
     pub bundle_count: usize,
     pub total_size: usize,
@@ -336,9 +781,9 @@ pub struct LoaderStatistics {
 
 /// Convenience functions for quick bundle loading
 /// Load a single bundle from file
+#[cfg(feature = "fs")]
 pub fn load_bundle<P: AsRef<Path>>(path: P) -> Result<AssetBundle> {
-    let data = std::fs::read(path)
-        .map_err(|e| BinaryError::generic(format!("Failed to read bundle file: {}", e)))?;
+    let data = std::fs::read(path)?;
     BundleParser::from_bytes(data)
 }
 
@@ -348,21 +793,19 @@ pub fn load_bundle_from_memory(data: Vec<u8>) -> Result<AssetBundle> {
 }
 
 /// Load a bundle with specific options
+#[cfg(feature = "fs")]
 pub fn load_bundle_with_options<P: AsRef<Path>>(
     path: P,
     options: BundleLoadOptions,
 ) -> Result<AssetBundle> {
-    let data = std::fs::read(path)
-        .map_err(|e| BinaryError::generic(format!("Failed to read bundle file: {}", e)))?;
+    let data = std::fs::read(path)?;
     BundleParser::from_bytes_with_options(data, options)
 }
 
 #[cfg(feature = "async")]
 /// Async load a single bundle from file
 pub async fn load_bundle_async<P: AsRef<Path>>(path: P) -> Result<AssetBundle> {
-    let data = fs::read(path)
-        .await
-        .map_err(|e| BinaryError::generic(format!("Failed to read bundle file: {}", e)))?;
+    let data = fs::read(path).await?;
     BundleParser::from_bytes(data)
 }
 
@@ -0,0 +1,369 @@
+//! BuildPipeline `.manifest` files
+//!
+//! `BuildPipeline.BuildAssetBundles` writes a text manifest next to every bundle (CRC, hashes,
+//! class types, contained assets, direct dependencies) and one for the output folder itself
+//! (`<Folder>.manifest`) listing every bundle with its dependencies. They look like YAML but
+//! are not Unity YAML documents (no `%YAML` header or `--- !u!` tags), so they are read with
+//! a small indentation-based parser here.
+
+use crate::error::{BinaryError, Result};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "fs")]
+use std::path::Path;
+
+/// A bundle's own manifest (`<bundle>.manifest`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub manifest_file_version: u32,
+    /// CRC32 of the uncompressed bundle content.
+    pub crc: u32,
+    pub asset_file_hash: Option<[u8; 16]>,
+    pub type_tree_hash: Option<[u8; 16]>,
+    pub incremental_build_hash: Option<[u8; 16]>,
+    /// Whether the hash was appended to the bundle file name (`AppendHashToAssetBundleName`).
+    pub hash_appended: bool,
+    pub class_types: Vec<ManifestClassType>,
+    /// Asset paths in the bundle (`Assets/...`).
+    pub assets: Vec<String>,
+    /// Direct dependencies, as written by the build (absolute bundle paths).
+    pub dependencies: Vec<String>,
+}
+
+/// A `ClassTypes` entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestClassType {
+    pub class_id: i32,
+    /// Script GUID for MonoBehaviour (114) entries.
+    pub script_guid: Option<String>,
+}
+
+/// The output folder's manifest (`AssetBundles.manifest`, `StandaloneWindows.manifest`, ...).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetBundleManifest {
+    pub manifest_file_version: u32,
+    pub crc: u32,
+    /// Every bundle of the build, in manifest order.
+    pub bundles: Vec<ManifestBundleInfo>,
+}
+
+/// One `AssetBundleInfos` entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestBundleInfo {
+    pub name: String,
+    /// Direct dependencies, by bundle name.
+    pub dependencies: Vec<String>,
+}
+
+/// Either kind of manifest, as detected by [`ManifestFile::from_path`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestFile {
+    Bundle(BundleManifest),
+    Folder(AssetBundleManifest),
+}
+
+impl ManifestFile {
+    /// Read a manifest of either kind (folder manifests have an `AssetBundleManifest` section).
+    #[cfg(feature = "fs")]
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::parse(&read_text(path.as_ref())?)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let root = parse_document(text)?;
+        if root.get("AssetBundleManifest").is_some() {
+            AssetBundleManifest::from_node(&root).map(Self::Folder)
+        } else {
+            BundleManifest::from_node(&root).map(Self::Bundle)
+        }
+    }
+}
+
+impl BundleManifest {
+    #[cfg(feature = "fs")]
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::parse(&read_text(path.as_ref())?)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        Self::from_node(&parse_document(text)?)
+    }
+
+    fn from_node(root: &Node) -> Result<Self> {
+        let hashes = root.get("Hashes");
+        let hash = |name: &str| -> Result<Option<[u8; 16]>> {
+            hashes
+                .and_then(|h| h.get(name))
+                .and_then(|h| h.get("Hash"))
+                .and_then(Node::as_str)
+                .map(parse_hash)
+                .transpose()
+        };
+        let class_types = root
+            .get("ClassTypes")
+            .map(Node::items)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|entry| {
+                let class_id = entry.get("Class")?.as_str()?.parse().ok()?;
+                let script_guid = entry
+                    .get("Script")
+                    .and_then(Node::as_str)
+                    .and_then(|s| inline_field(s, "guid"))
+                    .map(str::to_string);
+                Some(ManifestClassType {
+                    class_id,
+                    script_guid,
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            manifest_file_version: root.parse_field("ManifestFileVersion")?.unwrap_or(0),
+            crc: root.parse_field("CRC")?.unwrap_or(0),
+            asset_file_hash: hash("AssetFileHash")?,
+            type_tree_hash: hash("TypeTreeHash")?,
+            incremental_build_hash: hash("IncrementalBuildHash")?,
+            hash_appended: root.parse_field::<u32>("HashAppended")?.unwrap_or(0) != 0,
+            class_types,
+            assets: root.string_list("Assets"),
+            dependencies: root.string_list("Dependencies"),
+        })
+    }
+
+    /// Dependency bundle names (the file names of [`BundleManifest::dependencies`]).
+    pub fn dependency_names(&self) -> Vec<&str> {
+        self.dependencies
+            .iter()
+            .map(|dep| dep.rsplit(['/', '\\']).next().unwrap_or(dep))
+            .collect()
+    }
+}
+
+impl AssetBundleManifest {
+    #[cfg(feature = "fs")]
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::parse(&read_text(path.as_ref())?)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        Self::from_node(&parse_document(text)?)
+    }
+
+    fn from_node(root: &Node) -> Result<Self> {
+        let infos = root
+            .get("AssetBundleManifest")
+            .and_then(|m| m.get("AssetBundleInfos"))
+            .ok_or_else(|| {
+                BinaryError::invalid_data("Manifest has no AssetBundleManifest.AssetBundleInfos")
+            })?;
+        let bundles = infos
+            .entries()
+            .iter()
+            .filter_map(|(_, info)| {
+                let name = info.get("Name")?.as_str()?.to_string();
+                let dependencies = info
+                    .get("Dependencies")
+                    .map(|deps| {
+                        deps.entries()
+                            .iter()
+                            .filter_map(|(_, dep)| dep.as_str().map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Some(ManifestBundleInfo { name, dependencies })
+            })
+            .collect();
+
+        Ok(Self {
+            manifest_file_version: root.parse_field("ManifestFileVersion")?.unwrap_or(0),
+            crc: root.parse_field("CRC")?.unwrap_or(0),
+            bundles,
+        })
+    }
+
+    pub fn bundle(&self, name: &str) -> Option<&ManifestBundleInfo> {
+        self.bundles.iter().find(|b| b.name == name)
+    }
+
+    /// Direct dependencies of a bundle; empty for unknown bundles.
+    pub fn direct_dependencies(&self, name: &str) -> &[String] {
+        self.bundle(name)
+            .map(|b| b.dependencies.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "fs")]
+fn read_text(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| BinaryError::generic(format!("Failed to read manifest {:?}: {}", path, e)))
+}
+
+/// Parse a 32-digit hex Hash128 as written by the manifest (byte order as printed).
+fn parse_hash(text: &str) -> Result<[u8; 16]> {
+    let invalid = || BinaryError::invalid_data(format!("Invalid manifest hash: {:?}", text));
+    if text.len() != 32 || !text.is_ascii() {
+        return Err(invalid());
+    }
+    let mut out = [0u8; 16];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&text[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(out)
+}
+
+/// A field of an inline mapping: `guid` in `{fileID: 11500000, guid: 5f72..., type: 3}`.
+fn inline_field<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    text.trim()
+        .strip_prefix('{')?
+        .strip_suffix('}')?
+        .split(',')
+        .filter_map(|pair| pair.split_once(':'))
+        .find(|(key, _)| key.trim() == name)
+        .map(|(_, value)| value.trim())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Scalar(String),
+    Map(Vec<(String, Node)>),
+    List(Vec<Node>),
+}
+
+impl Node {
+    fn get(&self, key: &str) -> Option<&Node> {
+        match self {
+            Node::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Node::Scalar(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn items(&self) -> &[Node] {
+        match self {
+            Node::List(items) => items,
+            _ => &[],
+        }
+    }
+
+    fn entries(&self) -> &[(String, Node)] {
+        match self {
+            Node::Map(entries) => entries,
+            _ => &[],
+        }
+    }
+
+    fn string_list(&self, key: &str) -> Vec<String> {
+        self.get(key)
+            .map(Node::items)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect()
+    }
+
+    fn parse_field<T: std::str::FromStr>(&self, key: &str) -> Result<Option<T>> {
+        let Some(text) = self.get(key).and_then(Node::as_str) else {
+            return Ok(None);
+        };
+        text.parse().map(Some).map_err(|_| {
+            BinaryError::invalid_data(format!("Invalid manifest {} value: {:?}", key, text))
+        })
+    }
+}
+
+/// Parse the manifest's block structure: `key: value`, `key:` followed by a deeper block, and
+/// `- item` lists (which may sit at the same indentation as their key).
+fn parse_document(text: &str) -> Result<Node> {
+    let mut lines: Vec<(usize, String)> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let content = line.trim_start();
+            (line.len() - content.len(), content.trim_end().to_string())
+        })
+        .collect();
+    let mut pos = 0;
+    let root = parse_map(&mut lines, &mut pos, 0)?;
+    if let Some((_, line)) = lines.get(pos) {
+        return Err(BinaryError::invalid_data(format!(
+            "Unexpected manifest line: {:?}",
+            line
+        )));
+    }
+    Ok(root)
+}
+
+fn is_list_item(line: &str) -> bool {
+    line == "-" || line.starts_with("- ")
+}
+
+fn parse_block(lines: &mut [(usize, String)], pos: &mut usize, indent: usize) -> Result<Node> {
+    if lines.get(*pos).is_some_and(|(_, line)| is_list_item(line)) {
+        parse_list(lines, pos, indent)
+    } else {
+        parse_map(lines, pos, indent)
+    }
+}
+
+fn parse_map(lines: &mut [(usize, String)], pos: &mut usize, indent: usize) -> Result<Node> {
+    let mut entries = Vec::new();
+    while let Some((line_indent, line)) = lines.get(*pos) {
+        if *line_indent != indent || is_list_item(line) {
+            break;
+        }
+        let (key, value) = match line.split_once(": ") {
+            Some((key, value)) => (key.to_string(), value.trim().to_string()),
+            None => match line.strip_suffix(':') {
+                Some(key) => (key.to_string(), String::new()),
+                None => {
+                    return Err(BinaryError::invalid_data(format!(
+                        "Expected `key: value` in manifest, got {:?}",
+                        line
+                    )));
+                }
+            },
+        };
+        *pos += 1;
+
+        let node = match value.as_str() {
+            "{}" => Node::Map(Vec::new()),
+            "[]" => Node::List(Vec::new()),
+            "" => match lines.get(*pos) {
+                Some((next, line)) if *next > indent || (*next == indent && is_list_item(line)) => {
+                    let next = *next;
+                    parse_block(lines, pos, next)?
+                }
+                _ => Node::Scalar(String::new()),
+            },
+            _ => Node::Scalar(value),
+        };
+        entries.push((key, node));
+    }
+    Ok(Node::Map(entries))
+}
+
+fn parse_list(lines: &mut [(usize, String)], pos: &mut usize, indent: usize) -> Result<Node> {
+    let mut items = Vec::new();
+    while let Some((line_indent, line)) = lines.get(*pos) {
+        if *line_indent != indent || !is_list_item(line) {
+            break;
+        }
+        let rest = line[1..].trim_start().to_string();
+        if rest.contains(": ") || rest.ends_with(':') {
+            // `- Class: 1` starts a mapping whose other keys are indented past the dash.
+            lines[*pos] = (indent + 2, rest);
+            items.push(parse_map(lines, pos, indent + 2)?);
+        } else {
+            *pos += 1;
+            items.push(Node::Scalar(rest));
+        }
+    }
+    Ok(Node::List(items))
+}
@@ -9,8 +9,12 @@
 //! - `header` - Bundle header parsing and validation
 //! - `types` - Core data structures (AssetBundle, BundleFileInfo, etc.)
 //! - `compression` - Compression handling (LZ4, LZMA, Brotli)
+//! - `container` - Container path (`m_Container`) lookup
 //! - `parser` - Main parsing logic for different bundle formats
 //! - `loader` - Resource loading and management
+//! - `manifest` - BuildPipeline `.manifest` files (CRC, hashes, dependencies)
+//! - `partial` - Loading one container entry without parsing the rest of the bundle
+//! - `summary` - Content-type summaries for quick triage
 //!
 //! # Examples
 //!
@@ -31,30 +35,54 @@
 //! ```
 
 pub mod compression;
+pub mod container;
 pub mod header;
 pub mod loader;
+pub mod manifest;
 pub mod parser;
+pub mod partial;
+pub mod resource;
+pub mod summary;
 pub mod types;
 
 // Re-export main types for easy access
 pub use compression::{BundleCompression, CompressionOptions, CompressionStats};
+pub use container::ContainerRef;
 pub use header::{BundleFormatInfo, BundleHeader};
 pub use loader::{
-    BundleLoader, BundleResourceManager, LoaderStatistics, load_bundle, load_bundle_from_memory,
-    load_bundle_with_options,
+    BundleLoader, BundleResourceManager, DependencyResolver, LoaderStatistics, RefreshReport,
+    load_bundle_from_memory,
+};
+#[cfg(feature = "fs")]
+pub use loader::{load_bundle, load_bundle_with_options};
+pub use manifest::{
+    AssetBundleManifest, BundleManifest, ManifestBundleInfo, ManifestClassType, ManifestFile,
 };
 pub use parser::{BundleParser, ParsingComplexity};
-pub use types::{AssetBundle, BundleFileInfo, BundleLoadOptions, BundleStatistics, DirectoryNode};
+pub use partial::{LoadedAsset, PartialLoadStats, SingleLoadOptions};
+pub use resource::normalize_stream_path;
+pub use summary::{
+    AudioSummary, ContentCounts, ContentSummary, FileContentSummary, MeshSummary, TextureSummary,
+};
+pub use types::{
+    AssetBundle, BlockStatistics, BundleFileInfo, BundleLoadOptions, BundleStatistics,
+    DirectoryNode, DuplicateNodeName, NodeCompressionEstimate,
+};
 
 #[cfg(feature = "async")]
 pub use loader::load_bundle_async;
 
+use crate::hooks::{HookRegistry, HookReport, ObjectHook};
+
 /// Main bundle processing facade
 ///
 /// This struct provides a high-level interface for bundle processing,
 /// combining parsing, loading, and resource management functionality.
+/// Registered [`ObjectHook`]s run in [`run_hooks`](Self::run_hooks) and
+/// [`extract_all`](Self::extract_all).
 pub struct BundleProcessor {
     loader: BundleLoader,
+    hooks: HookRegistry,
 }
 
 impl BundleProcessor {
@@ -62,6 +90,7 @@ impl BundleProcessor {
     pub fn new() -> Self {
         Self {
             loader: BundleLoader::new(),
+            hooks: HookRegistry::new(),
         }
     }
 
@@ -69,10 +98,45 @@ impl BundleProcessor {
     pub fn with_options(options: BundleLoadOptions) -> Self {
         Self {
             loader: BundleLoader::with_options(options),
+            hooks: HookRegistry::new(),
         }
     }
 
+    /// Register a per-object hook; hooks run in registration order
+    pub fn register_hook<H: ObjectHook + 'static>(&mut self, hook: H) -> &mut Self {
+        self.hooks.register(hook);
+        self
+    }
+
+    /// Get mutable access to the registered hooks
+    pub fn hooks_mut(&mut self) -> &mut HookRegistry {
+        &mut self.hooks
+    }
+
+    /// Run the registered hooks over every object of a loaded bundle
+    pub fn run_hooks(&mut self, bundle_name: &str) -> Option<HookReport> {
+        let bundle = self.loader.get_bundle(bundle_name)?;
+        Some(self.hooks.run_bundle(bundle))
+    }
+
+    /// Dump every object of a loaded bundle to `out_dir` (see
+    /// [`AssetBundle::dump_raw_objects_with_hooks`]), letting the registered hooks skip or
+    /// replace objects and emit their own files
+    #[cfg(feature = "fs")]
+    pub fn extract_all<P: AsRef<std::path::Path>>(
+        &mut self,
+        bundle_name: &str,
+        out_dir: P,
+        filter: &crate::asset::DumpFilter,
+    ) -> crate::error::Result<crate::asset::DumpReport> {
+        let bundle = self.loader.get_bundle(bundle_name).ok_or_else(|| {
+            crate::error::BinaryError::generic(format!("Bundle not loaded: {}", bundle_name))
+        })?;
+        bundle.dump_raw_objects_with_hooks(out_dir, filter, &mut self.hooks)
+    }
+
     /// Load and process a bundle from file
+    #[cfg(feature = "fs")]
     pub fn process_file<P: AsRef<std::path::Path>>(
         &mut self,
         path: P,
@@ -139,6 +203,22 @@ impl BundleProcessor {
         })
     }
 
+    /// Whether a loaded bundle is the build folder's root manifest bundle
+    pub fn is_manifest_bundle(&self, bundle_name: &str) -> bool {
+        self.loader
+            .get_bundle(bundle_name)
+            .is_some_and(AssetBundle::is_manifest_bundle)
+    }
+
+    /// The AssetBundleManifest of a loaded root manifest bundle, usable as a
+    /// [`DependencyResolver`] for [`BundleLoader::load_with_dependencies`]
+    pub fn asset_bundle_manifest(
+        &self,
+        bundle_name: &str,
+    ) -> Option<crate::asset_bundle_manifest::AssetBundleManifestObject> {
+        self.loader.get_bundle(bundle_name)?.asset_bundle_manifest()
+    }
+
     /// Validate all loaded bundles
     pub fn validate_all(&self) -> crate::error::Result<()> {
         self.loader.validate_all()
@@ -177,10 +257,9 @@ pub fn create_processor() -> BundleProcessor {
 }
 
 /// Quick function to get bundle information
+#[cfg(feature = "fs")]
 pub fn get_bundle_info<P: AsRef<std::path::Path>>(path: P) -> crate::error::Result<BundleInfo> {
-    let data = std::fs::read(&path).map_err(|e| {
-        crate::error::BinaryError::generic(format!("Failed to read bundle file: {}", e))
-    })?;
+    let data = std::fs::read(&path)?;
 
     let complexity = BundleParser::estimate_complexity(&data)?;
     let bundle = BundleParser::from_bytes(data)?;
@@ -205,6 +284,7 @@ pub fn get_bundle_info<P: AsRef<std::path::Path>>(path: P) -> crate::error::Resu
 }
 
 /// Quick function to list bundle contents
+#[cfg(feature = "fs")]
 pub fn list_bundle_contents<P: AsRef<std::path::Path>>(
     path: P,
 ) -> crate::error::Result<Vec<String>> {
@@ -217,6 +297,7 @@ pub fn list_bundle_contents<P: AsRef<std::path::Path>>(
 }
 
 /// Quick function to extract a specific file from bundle
+#[cfg(feature = "fs")]
 pub fn extract_file_from_bundle<P: AsRef<std::path::Path>>(
     bundle_path: P,
     file_name: &str,
@@ -234,6 +315,7 @@ pub fn extract_file_from_bundle<P: AsRef<std::path::Path>>(
 }
 
 /// Check if a file is a valid Unity bundle
+#[cfg(feature = "fs")]
 pub fn is_valid_bundle<P: AsRef<std::path::Path>>(path: P) -> bool {
     match std::fs::read(path) {
         Ok(data) => {
@@ -7,12 +7,17 @@ use super::compression::BundleCompression;
 use super::header::BundleHeader;
 use super::types::{AssetBundle, BundleFileInfo, BundleLoadOptions, DirectoryNode};
 use crate::compression::CompressionType;
+use crate::data_source::{BytesSource, DataSource};
 use crate::data_view::DataView;
 use crate::error::{BinaryError, Result};
 use crate::reader::{BinaryReader, ByteOrder};
 use crate::shared_bytes::SharedBytes;
 use crate::unity_version::UnityVersion;
 use std::ops::Range;
+use std::sync::Arc;
+
+/// Bytes read up front by [`BundleParser::from_source_with_options`]; enough for any header.
+const HEADER_PREFIX_SIZE: usize = 1024;
 
 /// Main bundle parser
 ///
@@ -67,6 +72,85 @@ impl BundleParser {
         Self::from_shared_range_with_options(shared, 0..len, options)
     }
 
+    /// Parse an AssetBundle from a [`DataSource`].
+    ///
+    /// With lazy options ([`BundleLoadOptions::lazy`] / [`BundleLoadOptions::fast`]) a UnityFS
+    /// bundle only reads its header and blocks info up front; later node reads fetch and
+    /// decompress just the blocks they touch. [`BundleLoadOptions::streaming`] also parses the
+    /// assets that way. Eager options and legacy formats read the whole source.
+    pub fn from_source_with_options(
+        source: impl DataSource + 'static,
+        options: BundleLoadOptions,
+    ) -> Result<AssetBundle> {
+        let source: Arc<dyn DataSource> = Arc::new(source);
+        let prefix_len = source.len().min(HEADER_PREFIX_SIZE as u64) as usize;
+        let prefix = source.read_at(0, prefix_len)?;
+        let mut reader = BinaryReader::new(&prefix, ByteOrder::Big);
+        let header = BundleHeader::from_reader(&mut reader)?;
+
+        if header.signature != "UnityFS"
+            || (!options.streaming && (options.decompress_blocks || options.load_assets))
+        {
+            return Self::from_bytes_with_options(source.read_all()?, options);
+        }
+
+        if options.validate {
+            header.validate()?;
+            if header.size > source.len() {
+                return Err(BinaryError::invalid_data(format!(
+                    "Bundle header size {} exceeds available bytes {}",
+                    header.size,
+                    source.len()
+                )));
+            }
+        }
+
+        let mut bundle = AssetBundle::new_empty(header);
+        Self::align_blocks_info(&bundle, &mut reader)?;
+        let compressed_size = Self::checked_blocks_info_size(&bundle, &options)?;
+
+        let mut position = reader.position();
+        let blocks_info_data = if bundle.header.block_info_at_end() {
+            let offset = source
+                .len()
+                .checked_sub(compressed_size as u64)
+                .ok_or_else(|| {
+                    BinaryError::not_enough_data(compressed_size, source.len() as usize)
+                })?;
+            source.read_at(offset, compressed_size)?
+        } else {
+            let bytes = source.read_at(position, compressed_size)?;
+            position += compressed_size as u64;
+            bytes
+        };
+        Self::apply_blocks_info(&mut bundle, &blocks_info_data, &options)?;
+
+        if Self::needs_block_data_padding(&bundle.header) {
+            position = position.next_multiple_of(16);
+        }
+        let start_usize = usize::try_from(position).map_err(|_| {
+            BinaryError::ResourceLimitExceeded(
+                "UnityFS block data start does not fit in usize".to_string(),
+            )
+        })?;
+        bundle.set_lazy_unityfs_source(
+            source,
+            start_usize,
+            options.max_memory,
+            options.max_unityfs_block_cache_memory,
+            options.max_compressed_block_size,
+        )?;
+        if options.load_assets {
+            Self::load_assets(&mut bundle, &options)?;
+        }
+
+        if options.validate {
+            bundle.validate()?;
+        }
+
+        Ok(bundle)
+    }
+
     fn from_view_with_options(view: DataView, options: BundleLoadOptions) -> Result<AssetBundle> {
         let bytes = view.as_bytes();
         let mut reader = BinaryReader::new(bytes, ByteOrder::Big);
@@ -122,8 +206,8 @@ impl BundleParser {
         // Read blocks info
         let block_data_start = Self::read_blocks_info(bundle, reader, options)?;
 
-        // Decompress data blocks if requested OR if we need to load assets
-        if options.decompress_blocks || options.load_assets {
+        // Decompress data blocks if requested OR if we need to load assets (unless streaming)
+        if !options.streaming && (options.decompress_blocks || options.load_assets) {
             let blocks_data = Self::read_blocks(bundle, reader, options)?;
             Self::parse_files(bundle, blocks_data)?;
 
@@ -138,7 +222,7 @@ impl BundleParser {
                 )
             })?;
             bundle.set_lazy_unityfs_source(
-                source.clone(),
+                Arc::new(BytesSource::from_view("bundle", source.clone())),
                 start_usize,
                 options.max_memory,
                 options.max_unityfs_block_cache_memory,
@@ -146,6 +230,9 @@ impl BundleParser {
             )?;
             // Just parse directory structure without decompressing all data
             Self::parse_directory_lazy(bundle, reader)?;
+            if options.load_assets {
+                Self::load_assets(bundle, options)?;
+            }
         }
 
         Ok(())
@@ -186,25 +273,25 @@ impl BundleParser {
                 compressed_size, max
             )));
         }
-        let compressed_data = reader.read_bytes(compressed_size as usize)?;
+        let compressed_data = reader.read_bytes_ref(compressed_size as usize)?;
         let directory_data = if bundle.header.signature == "UnityWeb" {
             // UnityWeb uses LZMA compression; prefer the explicit uncompressed size when available.
             crate::compression::decompress(
-                &compressed_data,
+                compressed_data,
                 CompressionType::Lzma,
                 uncompressed_size as usize,
             )
             .or_else(|_| {
                 // Last-resort fallback for malformed headers.
                 crate::compression::decompress(
-                    &compressed_data,
+                    compressed_data,
                     CompressionType::Lzma,
                     compressed_data.len().saturating_mul(4),
                 )
             })?
         } else {
             // UnityRaw is uncompressed
-            compressed_data
+            compressed_data.to_vec()
         };
 
         // Legacy bundles store directory entries + file content in the same blob.
@@ -229,6 +316,36 @@ impl BundleParser {
         reader: &mut BinaryReader,
         options: &BundleLoadOptions,
     ) -> Result<u64> {
+        Self::align_blocks_info(bundle, reader)?;
+        let compressed_size = Self::checked_blocks_info_size(bundle, options)?;
+
+        let blocks_info_data = if bundle.header.block_info_at_end() {
+            let start = reader.position();
+            let len = reader.len();
+            if compressed_size > len {
+                return Err(BinaryError::not_enough_data(compressed_size, len));
+            }
+            let pos = (len - compressed_size) as u64;
+            reader.set_position(pos)?;
+            let bytes = reader.read_bytes_ref(compressed_size)?;
+            reader.set_position(start)?;
+            bytes
+        } else {
+            reader.read_bytes_ref(compressed_size)?
+        };
+
+        Self::apply_blocks_info(bundle, blocks_info_data, options)?;
+
+        // Some UnityFS variants require padding/alignment before block data starts.
+        if Self::needs_block_data_padding(&bundle.header) {
+            reader.align_to(16)?;
+        }
+
+        Ok(reader.position())
+    }
+
+    /// Skip the alignment between the header and the blocks info.
+    fn align_blocks_info(bundle: &AssetBundle, reader: &mut BinaryReader) -> Result<()> {
         // Apply version-specific alignment.
         // UnityFS uses 16-byte alignment in newer bundle formats (>=7).
         // For some older bundle formats, alignment may still be present (e.g. Unity 2019.4+),
@@ -239,16 +356,21 @@ impl BundleParser {
             let pre_align = reader.position();
             let pad = (16 - (pre_align % 16)) % 16;
             if pad != 0 {
-                let align_bytes = reader.read_bytes(pad as usize)?;
+                let align_bytes = reader.read_bytes_ref(pad as usize)?;
                 if align_bytes.iter().any(|&b| b != 0) {
                     reader.set_position(pre_align)?;
                 }
             }
         }
+        Ok(())
+    }
 
-        let start = reader.position();
+    /// Compressed blocks info size, checked against the configured limits.
+    fn checked_blocks_info_size(
+        bundle: &AssetBundle,
+        options: &BundleLoadOptions,
+    ) -> Result<usize> {
         let compressed_size = bundle.header.compressed_blocks_info_size as usize;
-
         if let Some(max) = options.max_compressed_blocks_info_size
             && compressed_size > max
         {
@@ -257,22 +379,6 @@ impl BundleParser {
                 compressed_size, max
             )));
         }
-
-        let blocks_info_data = if bundle.header.block_info_at_end() {
-            let len = reader.len();
-            if compressed_size > len {
-                return Err(BinaryError::not_enough_data(compressed_size, len));
-            }
-            let pos = (len - compressed_size) as u64;
-            reader.set_position(pos)?;
-            let bytes = reader.read_bytes(compressed_size)?;
-            reader.set_position(start)?;
-            bytes
-        } else {
-            reader.read_bytes(compressed_size)?
-        };
-
-        // Decompress blocks info
         if let Some(max_blocks_info_size) = options.max_blocks_info_size {
             let expected = bundle.header.uncompressed_blocks_info_size as usize;
             if expected > max_blocks_info_size {
@@ -282,9 +388,18 @@ impl BundleParser {
                 )));
             }
         }
+        Ok(compressed_size)
+    }
+
+    /// Decompress the blocks info and fill in the block table and directory.
+    fn apply_blocks_info(
+        bundle: &mut AssetBundle,
+        blocks_info_data: &[u8],
+        options: &BundleLoadOptions,
+    ) -> Result<()> {
         let uncompressed_data = BundleCompression::decompress_blocks_info_limited(
             &bundle.header,
-            &blocks_info_data,
+            blocks_info_data,
             options.max_blocks_info_size,
         )?;
 
@@ -305,17 +420,11 @@ impl BundleParser {
         bundle.set_decompressed_len(total_uncompressed);
 
         // Parse directory information from the same blocks info data
-        Self::parse_directory_from_blocks_info(bundle, &uncompressed_data, options)?;
-
-        // Some UnityFS variants require padding/alignment before block data starts.
-        if (bundle.header.flags
-            & crate::compression::ArchiveFlags::BLOCK_INFO_NEEDS_PADDING_AT_START)
-            != 0
-        {
-            reader.align_to(16)?;
-        }
+        Self::parse_directory_from_blocks_info(bundle, &uncompressed_data, options)
+    }
 
-        Ok(reader.position())
+    fn needs_block_data_padding(header: &BundleHeader) -> bool {
+        (header.flags & crate::compression::ArchiveFlags::BLOCK_INFO_NEEDS_PADDING_AT_START) != 0
     }
 
     fn should_probe_legacy_alignment(header: &BundleHeader) -> bool {
@@ -327,10 +436,7 @@ impl BundleParser {
             Ok(v) => v,
             Err(_) => return false,
         };
-        let (major, minor) = (parsed.major, parsed.minor);
-
-        // 2019.4+
-        major > 2019 || (major == 2019 && minor >= 4)
+        parsed.at_least(2019, 4)
     }
 
     /// Read and decompress all blocks
@@ -392,7 +498,7 @@ impl BundleParser {
         let mut reader = BinaryReader::new(blocks_info_data, ByteOrder::Big);
 
         // Skip uncompressed data hash (16 bytes)
-        reader.read_bytes(16)?;
+        reader.skip_bytes(16)?;
 
         // Skip compression blocks information (we already parsed them).
         let block_count_i32 = reader.read_i32()?;
@@ -551,7 +657,13 @@ impl BundleParser {
     }
 
     /// Load assets from the bundle files
+    ///
+    /// Streaming UnityFS bundles copy each asset node out of the block cache instead of
+    /// decompressing the whole bundle.
     fn load_assets(bundle: &mut AssetBundle, options: &BundleLoadOptions) -> Result<()> {
+        if options.streaming && bundle.header.is_unity_fs() {
+            return Self::load_assets_streaming(bundle, options);
+        }
         let (backing, base_offset, visible_len) = if bundle.header.is_unity_fs() {
             let backing = crate::shared_bytes::SharedBytes::from_arc(bundle.data_arc()?);
             let visible_len = backing.len() as u64;
@@ -567,7 +679,7 @@ impl BundleParser {
         // Clone nodes to avoid borrow conflicts while pushing assets.
         let nodes = bundle.nodes.clone();
 
-        for node in &nodes {
+        for (node_index, node) in nodes.iter().enumerate() {
             if !node.is_file() {
                 continue;
             }
@@ -621,12 +733,43 @@ impl BundleParser {
             })?;
 
             // Parse as a zero-copy view into the backing buffer (UnityFS decompressed buffer or legacy source).
-            if let Ok(serialized_file) = crate::asset::SerializedFileParser::from_shared_range(
+            if let Ok(mut serialized_file) = crate::asset::SerializedFileParser::from_shared_range(
                 backing.clone(),
                 abs_start..abs_end,
             ) {
-                bundle.assets.push(serialized_file);
-                bundle.asset_names.push(node.name.clone());
+                if let Some(version) = &options.unity_version_override {
+                    serialized_file.set_unity_version(version);
+                }
+                bundle.push_asset(node_index, node.name.clone(), serialized_file);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn load_assets_streaming(bundle: &mut AssetBundle, options: &BundleLoadOptions) -> Result<()> {
+        let nodes = bundle.nodes.clone();
+
+        for (node_index, node) in nodes.iter().enumerate() {
+            if !node.is_file() || node.name.ends_with(".resS") || node.name.ends_with(".resource") {
+                continue;
+            }
+
+            if let Some(max_memory) = options.max_memory
+                && node.size > max_memory as u64
+            {
+                return Err(BinaryError::ResourceLimitExceeded(format!(
+                    "Bundle node '{}' size {} exceeds max_memory {}",
+                    node.name, node.size, max_memory
+                )));
+            }
+
+            let data = bundle.extract_node_data(node)?;
+            if let Ok(mut serialized_file) = crate::asset::SerializedFileParser::from_bytes(data) {
+                if let Some(version) = &options.unity_version_override {
+                    serialized_file.set_unity_version(version);
+                }
+                bundle.push_asset(node_index, node.name.clone(), serialized_file);
             }
         }
 
@@ -0,0 +1,387 @@
+//! Loading one container entry of a bundle without parsing the rest.
+//!
+//! [`BundleLoader::load_single`](super::BundleLoader::load_single) parses the bundle header and
+//! directory, then only the header and metadata of each SerializedFile until one holds an
+//! `AssetBundle` object listing the requested container path. From there it reads the target
+//! object, the slice of `m_PreloadTable` its container entry names, and whatever those objects
+//! reference inside the same file, up to a depth limit. Object bytes are fetched with
+//! [`AssetBundle::extract_node_range`], so a lazily opened UnityFS bundle only inflates the
+//! blocks covering them. Objects that were read carry their bytes in
+//! [`ObjectInfo::data`](crate::asset::ObjectInfo::data); every other object keeps only its
+//! metadata entry.
+
+use super::parser::BundleParser;
+use super::types::{AssetBundle, BundleLoadOptions, DirectoryNode};
+use crate::asset::{SerializedFile, SerializedFileHeader, SerializedFileParser};
+use crate::data_source::DataSource;
+use crate::error::{BinaryError, Result};
+use crate::object::ObjectHandle;
+use crate::reader::{BinaryReader, ByteOrder};
+use crate::unity_objects::{ObjectRef, parse_pptr};
+use std::collections::{HashMap, HashSet, VecDeque};
+use unity_asset_core::{UnityValue, class_ids};
+
+/// Header size of SerializedFile version 22+; earlier versions use the first 20 bytes.
+const SERIALIZED_HEADER_MAX: u64 = 48;
+
+/// Options for [`BundleLoader::load_single`](super::BundleLoader::load_single).
+#[derive(Debug, Clone)]
+pub struct SingleLoadOptions {
+    /// How many `PPtr` hops to follow from the target object (`0` reads only the target).
+    /// Preload table entries count as one hop.
+    pub max_depth: usize,
+    /// Also read the objects listed in the container entry's `m_PreloadTable` slice.
+    pub follow_preload_table: bool,
+}
+
+impl SingleLoadOptions {
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn with_follow_preload_table(mut self, follow_preload_table: bool) -> Self {
+        self.follow_preload_table = follow_preload_table;
+        self
+    }
+}
+
+impl Default for SingleLoadOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 16,
+            follow_preload_table: true,
+        }
+    }
+}
+
+/// How much of the bundle a partial load touched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PartialLoadStats {
+    /// Objects in the SerializedFile holding the target.
+    pub objects_total: usize,
+    /// Objects whose bytes were read (the `AssetBundle` object, the target and its dependencies).
+    pub objects_read: usize,
+    /// UnityFS blocks in the bundle.
+    pub blocks_total: usize,
+    /// UnityFS blocks inflated, including the ones needed for headers and metadata.
+    pub blocks_inflated: usize,
+}
+
+/// One container entry loaded by [`BundleLoader::load_single`](super::BundleLoader::load_single).
+#[derive(Debug)]
+pub struct LoadedAsset {
+    /// The lazily opened bundle; further reads (e.g. streamed `.resS` data) go through it.
+    pub bundle: AssetBundle,
+    /// Name of the bundle node holding the target.
+    pub asset_name: String,
+    /// The SerializedFile holding the target. Only objects that were read have their bytes.
+    pub file: SerializedFile,
+    /// Container path as stored in `m_Container`.
+    pub container_path: String,
+    /// `path_id` of the target object.
+    pub root: i64,
+    /// `path_id`s of the objects read for the target, in discovery order (root excluded).
+    pub dependencies: Vec<i64>,
+    /// References into other files (`m_FileID > 0`) found on the way; they are not followed.
+    pub external_references: Vec<ObjectRef>,
+    pub stats: PartialLoadStats,
+}
+
+impl LoadedAsset {
+    /// Handle to the target object.
+    pub fn root(&self) -> ObjectHandle<'_> {
+        self.file
+            .find_object_handle(self.root)
+            .expect("root object is in the loaded file")
+    }
+
+    /// Handles to the resolved dependencies, in discovery order.
+    pub fn dependency_handles(&self) -> impl Iterator<Item = ObjectHandle<'_>> {
+        self.dependencies
+            .iter()
+            .filter_map(|path_id| self.file.find_object_handle(*path_id))
+    }
+
+    /// Whether the bytes of object `path_id` were read.
+    pub fn is_loaded(&self, path_id: i64) -> bool {
+        self.file
+            .find_object(path_id)
+            .is_some_and(|info| !info.data.is_empty())
+    }
+}
+
+/// A container entry of an `AssetBundle` object.
+struct ContainerEntry {
+    path: String,
+    asset: ObjectRef,
+    preload: Vec<ObjectRef>,
+}
+
+pub(crate) fn load_single(
+    source: impl DataSource + 'static,
+    container: &str,
+    bundle_options: &BundleLoadOptions,
+    options: &SingleLoadOptions,
+) -> Result<LoadedAsset> {
+    let version_override = bundle_options.unity_version_override.clone();
+    let bundle_options = bundle_options
+        .clone()
+        .with_load_assets(false)
+        .with_decompress_blocks(false);
+    let bundle = BundleParser::from_source_with_options(source, bundle_options)?;
+
+    let nodes: Vec<DirectoryNode> = bundle
+        .nodes
+        .iter()
+        .filter(|n| n.is_file() && !n.name.ends_with(".resS") && !n.name.ends_with(".resource"))
+        .cloned()
+        .collect();
+    for node in &nodes {
+        let mut partial = PartialFile::open(&bundle, node)?;
+        if let Some(version) = &version_override {
+            partial.file.set_unity_version(version);
+        }
+        let bundles: Vec<i64> = partial
+            .file
+            .objects
+            .iter()
+            .filter(|o| o.type_id == class_ids::ASSET_BUNDLE)
+            .map(|o| o.path_id)
+            .collect();
+        for path_id in bundles {
+            partial.read_object(&bundle, path_id)?;
+            let Some(entry) = find_container_entry(&partial.file, path_id, container) else {
+                continue;
+            };
+            if entry.asset.file_id != 0 {
+                return Err(BinaryError::unsupported(format!(
+                    "Container path '{}' points into external file {}",
+                    entry.path, entry.asset.file_id
+                )));
+            }
+            return partial.load_closure(bundle, entry, options);
+        }
+    }
+
+    Err(BinaryError::generic(format!(
+        "Container path '{}' not found in bundle",
+        container
+    )))
+}
+
+/// A SerializedFile parsed from its header and metadata only.
+struct PartialFile {
+    node: DirectoryNode,
+    file: SerializedFile,
+    index_by_path_id: HashMap<i64, usize>,
+    objects_read: usize,
+}
+
+impl PartialFile {
+    fn open(bundle: &AssetBundle, node: &DirectoryNode) -> Result<Self> {
+        let head = bundle.extract_node_range(node, 0, node.size.min(SERIALIZED_HEADER_MAX))?;
+        let mut reader = BinaryReader::new(&head, ByteOrder::Big);
+        reader.set_position(8)?;
+        let version = reader.read_u32()?;
+
+        // Before version 9 the metadata sits at the end of the file; read it whole.
+        let bytes = if version < 9 {
+            bundle.extract_node_data(node)?
+        } else {
+            let mut reader = BinaryReader::new(&head, ByteOrder::Big);
+            let header = SerializedFileHeader::from_reader(&mut reader)?;
+            let metadata_end = reader
+                .position()
+                .checked_add(header.metadata_size as u64)
+                .ok_or_else(|| {
+                    BinaryError::invalid_data("SerializedFile metadata size overflow")
+                })?;
+            bundle.extract_node_range(node, 0, metadata_end.min(node.size))?
+        };
+
+        let file = SerializedFileParser::from_bytes(bytes)?;
+        let index_by_path_id = file
+            .objects
+            .iter()
+            .enumerate()
+            .map(|(index, info)| (info.path_id, index))
+            .collect();
+        Ok(Self {
+            node: node.clone(),
+            file,
+            index_by_path_id,
+            objects_read: 0,
+        })
+    }
+
+    /// Read the bytes of object `path_id` into its `ObjectInfo::data`.
+    ///
+    /// Returns `false` when the file has no such object.
+    fn read_object(&mut self, bundle: &AssetBundle, path_id: i64) -> Result<bool> {
+        let Some(&index) = self.index_by_path_id.get(&path_id) else {
+            return Ok(false);
+        };
+        let info = &self.file.objects[index];
+        if !info.data.is_empty() || info.byte_size == 0 {
+            return Ok(true);
+        }
+        let bytes =
+            bundle.extract_node_range(&self.node, info.byte_start, info.byte_size as u64)?;
+        self.file.objects[index].data = bytes;
+        self.objects_read += 1;
+        Ok(true)
+    }
+
+    fn load_closure(
+        mut self,
+        bundle: AssetBundle,
+        entry: ContainerEntry,
+        options: &SingleLoadOptions,
+    ) -> Result<LoadedAsset> {
+        let root = entry.asset.path_id;
+        if !self.read_object(&bundle, root)? {
+            return Err(BinaryError::invalid_data(format!(
+                "Container path '{}' points at missing object path_id={}",
+                entry.path, root
+            )));
+        }
+
+        let mut dependencies = Vec::new();
+        let mut external_references = Vec::new();
+        let mut seen: HashSet<i64> = HashSet::from([root]);
+        let mut queue: VecDeque<(i64, usize)> = VecDeque::from([(root, 0)]);
+        let mut note = |pptr: ObjectRef,
+                        depth: usize,
+                        queue: &mut VecDeque<(i64, usize)>,
+                        externals: &mut Vec<ObjectRef>| {
+            if pptr.is_null() {
+                return;
+            }
+            if pptr.file_id != 0 {
+                if !externals.contains(&pptr) {
+                    externals.push(pptr);
+                }
+            } else if depth <= options.max_depth && seen.insert(pptr.path_id) {
+                queue.push_back((pptr.path_id, depth));
+            }
+        };
+
+        if options.follow_preload_table {
+            for pptr in &entry.preload {
+                note(*pptr, 1, &mut queue, &mut external_references);
+            }
+        }
+
+        while let Some((path_id, depth)) = queue.pop_front() {
+            if path_id != root {
+                if !self.read_object(&bundle, path_id)? {
+                    continue;
+                }
+                dependencies.push(path_id);
+            }
+            if depth >= options.max_depth {
+                continue;
+            }
+            let Some(handle) = self.file.find_object_handle(path_id) else {
+                continue;
+            };
+            let Some(scan) = handle.scan_pptrs()? else {
+                continue;
+            };
+            for target in scan.internal {
+                note(
+                    ObjectRef::new(0, target),
+                    depth + 1,
+                    &mut queue,
+                    &mut external_references,
+                );
+            }
+            for (file_id, target) in scan.external {
+                note(
+                    ObjectRef::new(file_id, target),
+                    depth + 1,
+                    &mut queue,
+                    &mut external_references,
+                );
+            }
+        }
+
+        let stats = PartialLoadStats {
+            objects_total: self.file.objects.len(),
+            objects_read: self.objects_read,
+            blocks_total: bundle.blocks.len(),
+            blocks_inflated: bundle.inflated_block_count(),
+        };
+        Ok(LoadedAsset {
+            bundle,
+            asset_name: self.node.name,
+            file: self.file,
+            container_path: entry.path,
+            root,
+            dependencies,
+            external_references,
+            stats,
+        })
+    }
+}
+
+/// Look `container` up (case-insensitively) in the `AssetBundle` object `path_id`.
+///
+/// Reads `m_Container` and `m_PreloadTable` through the TypeTree; without one, falls back to
+/// [`SerializedFile::assetbundle_container_raw`], which has no preload information.
+fn find_container_entry(
+    file: &SerializedFile,
+    path_id: i64,
+    container: &str,
+) -> Option<ContainerEntry> {
+    let handle = file.find_object_handle(path_id)?;
+    if let Ok(object) = handle.read()
+        && let Some(UnityValue::Array(items)) = object.class.get("m_Container")
+    {
+        let preload_table: Vec<ObjectRef> = match object.class.get("m_PreloadTable") {
+            Some(UnityValue::Array(items)) => items.iter().filter_map(parse_pptr).collect(),
+            _ => Vec::new(),
+        };
+        return items.iter().find_map(|item| {
+            let UnityValue::Array(pair) = item else {
+                return None;
+            };
+            let [path, info] = pair.as_slice() else {
+                return None;
+            };
+            let path = path.as_str()?;
+            if !path.eq_ignore_ascii_case(container) {
+                return None;
+            }
+            let UnityValue::Object(info) = info else {
+                return None;
+            };
+            let index = |key: &str| {
+                info.get(key)
+                    .and_then(UnityValue::as_i64)
+                    .and_then(|v| usize::try_from(v).ok())
+                    .unwrap_or(0)
+            };
+            let start = index("preloadIndex").min(preload_table.len());
+            let end = start
+                .saturating_add(index("preloadSize"))
+                .min(preload_table.len());
+            Some(ContainerEntry {
+                path: path.to_string(),
+                asset: parse_pptr(info.get("asset")?)?,
+                preload: preload_table[start..end].to_vec(),
+            })
+        });
+    }
+
+    file.assetbundle_container_raw(handle.info())
+        .ok()?
+        .into_iter()
+        .find(|(path, _, _)| path.eq_ignore_ascii_case(container))
+        .map(|(path, file_id, path_id)| ContainerEntry {
+            path,
+            asset: ObjectRef::new(file_id, path_id),
+            preload: Vec::new(),
+        })
+}
@@ -0,0 +1,117 @@
+//! Streamed resource lookup
+//!
+//! Objects such as `Texture2D`, `AudioClip` and `Mesh` keep large payloads in a sibling
+//! `.resS` / `.resource` node and reference it through `m_StreamData.path`, usually as
+//! `archive:/CAB-<hash>/CAB-<hash>.resS`. These helpers resolve such paths to bundle nodes.
+
+use super::types::{AssetBundle, DirectoryNode};
+use crate::error::{BinaryError, Result};
+use std::path::Path;
+
+/// Normalize a stream path: drop the `archive:/` scheme and `./` prefixes, use `/` separators
+pub fn normalize_stream_path(stream_path: &str) -> String {
+    let mut p = stream_path.trim().to_string();
+    if let Some(rest) = p.strip_prefix("archive:/") {
+        p = rest.to_string();
+    }
+    p = p.replace('\\', "/");
+    while p.starts_with("./") {
+        p = p.trim_start_matches("./").to_string();
+    }
+    p
+}
+
+impl AssetBundle {
+    /// Find the file node a stream path refers to
+    ///
+    /// Matches by full or suffix path first, then by file name, then by `CAB-<hash>` prefix for
+    /// resource nodes. `.resS` / `.resource` nodes count as files even without the `0x4` flag,
+    /// which Unity only sets on SerializedFile nodes.
+    pub fn find_resource_node(&self, stream_path: &str) -> Option<&DirectoryNode> {
+        let normalized = normalize_stream_path(stream_path);
+        if normalized.is_empty() {
+            return None;
+        }
+
+        let file_name = Path::new(&normalized)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|s| s.to_string());
+
+        let mut nodes: Vec<&DirectoryNode> = self
+            .nodes
+            .iter()
+            .filter(|n| n.is_file() || is_resource_name(&n.name))
+            .collect();
+        nodes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for node in &nodes {
+            let node_norm = node.name.replace('\\', "/");
+            if node_norm == normalized
+                || node_norm.ends_with(&normalized)
+                || normalized.ends_with(&node_norm)
+            {
+                return Some(*node);
+            }
+
+            if let Some(file_name) = &file_name
+                && Path::new(&node_norm).file_name().and_then(|n| n.to_str())
+                    == Some(file_name.as_str())
+            {
+                return Some(*node);
+            }
+        }
+
+        // Unity sometimes appends an index suffix to the CAB resource node name
+        // (e.g. `CAB-<hash>1.resource`) while the `StreamedResource.m_Source` path
+        // points to `CAB-<hash>.resource`. Best-effort: match by CAB prefix.
+        let cab_prefix = normalized
+            .split('/')
+            .find(|s| s.starts_with("CAB-"))
+            .and_then(|s| {
+                let hash: String = s
+                    .trim_start_matches("CAB-")
+                    .chars()
+                    .take_while(|c| c.is_ascii_hexdigit())
+                    .collect();
+                if hash.is_empty() {
+                    None
+                } else {
+                    Some(format!("CAB-{}", hash))
+                }
+            });
+
+        if let Some(cab_prefix) = cab_prefix {
+            for node in &nodes {
+                let node_norm = node.name.replace('\\', "/");
+                let is_resource = is_resource_name(&node_norm);
+                let base = Path::new(&node_norm)
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or(&node_norm);
+                if is_resource
+                    && (node_norm.starts_with(&cab_prefix) || base.starts_with(&cab_prefix))
+                {
+                    return Some(*node);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Read `size` bytes at `offset` of the resource node a stream path refers to
+    ///
+    /// Fails with [`BinaryError::ExternalResourceMissing`] when the resource lives outside this
+    /// bundle.
+    pub fn read_resource(&self, stream_path: &str, offset: u64, size: u64) -> Result<Vec<u8>> {
+        let node = self
+            .find_resource_node(stream_path)
+            .ok_or_else(|| BinaryError::external_resource_missing(stream_path))?;
+        self.extract_node_range(node, offset, size)
+    }
+}
+
+fn is_resource_name(name: &str) -> bool {
+    name.ends_with(".resS") || name.ends_with(".resource")
+}
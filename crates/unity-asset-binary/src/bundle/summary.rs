@@ -0,0 +1,201 @@
+//! Bundle content-type summary
+//!
+//! [`AssetBundle::content_summary`] answers "what is in this bundle?" without decoding anything:
+//! objects are bucketed by class id, and a handful of cheap TypeTree prefix peeks (see
+//! [`ObjectHandle::peek_fields`]) fill in texture dimensions/formats, audio durations and mesh
+//! vertex counts.
+//!
+//! # Accuracy
+//!
+//! - Counts per category are exact: they come from the object table's class ids.
+//! - Texture pixels, audio seconds and mesh vertices need a TypeTree. Objects in stripped files
+//!   (no TypeTree and no registry), or whose layout lacks the peeked fields (e.g. pre-Unity 5
+//!   `AudioClip` without `m_Length`), are still counted but reported under `unmeasured`.
+//! - Texture pixels are the base level only (`m_Width * m_Height`); mips and array slices are
+//!   not included.
+//! - MonoBehaviours are counted by class id alone, regardless of their script or whether their
+//!   TypeTree is available.
+//! - Only SerializedFiles that were loaded into [`AssetBundle::assets`] are summarized; bundles
+//!   opened with `load_assets: false` produce an empty summary.
+
+use super::types::AssetBundle;
+use crate::object::ObjectHandle;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use unity_asset_core::UnityValue;
+use unity_asset_core::class_ids;
+
+const TEXTURE_FIELDS: &[&str] = &["m_Width", "m_Height", "m_TextureFormat"];
+const AUDIO_FIELDS: &[&str] = &["m_Length"];
+const MESH_FIELDS: &[&str] = &["m_VertexData", "m_Vertices"];
+
+/// Texture totals for a summary scope.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TextureSummary {
+    pub count: usize,
+    /// Sum of `m_Width * m_Height` over measured textures.
+    pub total_pixels: u64,
+    /// Texture count per raw `TextureFormat` id.
+    pub formats: BTreeMap<i32, usize>,
+    /// Textures whose dimensions could not be peeked.
+    pub unmeasured: usize,
+}
+
+/// Audio totals for a summary scope.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudioSummary {
+    pub count: usize,
+    /// Sum of `m_Length` over measured clips.
+    pub total_seconds: f64,
+    /// Clips whose length could not be peeked.
+    pub unmeasured: usize,
+}
+
+/// Mesh totals for a summary scope.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MeshSummary {
+    pub count: usize,
+    /// Sum of vertex counts over measured meshes.
+    pub total_vertices: u64,
+    /// Meshes whose vertex count could not be peeked.
+    pub unmeasured: usize,
+}
+
+/// Per-category object counts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContentCounts {
+    pub textures: TextureSummary,
+    pub audio_clips: AudioSummary,
+    pub meshes: MeshSummary,
+    pub mono_behaviours: usize,
+    /// Objects of any other class.
+    pub other: usize,
+    pub total_objects: usize,
+}
+
+impl ContentCounts {
+    fn merge(&mut self, other: &ContentCounts) {
+        self.textures.count += other.textures.count;
+        self.textures.total_pixels += other.textures.total_pixels;
+        self.textures.unmeasured += other.textures.unmeasured;
+        for (format, count) in &other.textures.formats {
+            *self.textures.formats.entry(*format).or_default() += count;
+        }
+        self.audio_clips.count += other.audio_clips.count;
+        self.audio_clips.total_seconds += other.audio_clips.total_seconds;
+        self.audio_clips.unmeasured += other.audio_clips.unmeasured;
+        self.meshes.count += other.meshes.count;
+        self.meshes.total_vertices += other.meshes.total_vertices;
+        self.meshes.unmeasured += other.meshes.unmeasured;
+        self.mono_behaviours += other.mono_behaviours;
+        self.other += other.other;
+        self.total_objects += other.total_objects;
+    }
+
+    fn add_object(&mut self, handle: ObjectHandle<'_>) {
+        self.total_objects += 1;
+        match handle.class_id() {
+            class_ids::TEXTURE_2D => {
+                self.textures.count += 1;
+                let peeked = peek(handle, TEXTURE_FIELDS);
+                let width = peeked.as_ref().and_then(|p| int_field(p, "m_Width"));
+                let height = peeked.as_ref().and_then(|p| int_field(p, "m_Height"));
+                match (width, height) {
+                    (Some(w), Some(h)) => {
+                        self.textures.total_pixels += (w.max(0) as u64) * (h.max(0) as u64);
+                    }
+                    _ => self.textures.unmeasured += 1,
+                }
+                if let Some(format) = peeked
+                    .as_ref()
+                    .and_then(|p| int_field(p, "m_TextureFormat"))
+                {
+                    *self.textures.formats.entry(format as i32).or_default() += 1;
+                }
+            }
+            class_ids::AUDIO_CLIP => {
+                self.audio_clips.count += 1;
+                match peek(handle, AUDIO_FIELDS)
+                    .as_ref()
+                    .and_then(|p| p.get("m_Length"))
+                    .and_then(UnityValue::as_f64)
+                {
+                    Some(seconds) => self.audio_clips.total_seconds += seconds,
+                    None => self.audio_clips.unmeasured += 1,
+                }
+            }
+            class_ids::MESH => {
+                self.meshes.count += 1;
+                match peek(handle, MESH_FIELDS)
+                    .as_ref()
+                    .and_then(mesh_vertex_count)
+                {
+                    Some(vertices) => self.meshes.total_vertices += vertices,
+                    None => self.meshes.unmeasured += 1,
+                }
+            }
+            class_ids::MONO_BEHAVIOUR => self.mono_behaviours += 1,
+            _ => self.other += 1,
+        }
+    }
+}
+
+/// Summary for one SerializedFile inside a bundle.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileContentSummary {
+    pub name: String,
+    pub counts: ContentCounts,
+}
+
+/// Content-type summary of a bundle, per contained file plus bundle-wide totals.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContentSummary {
+    pub files: Vec<FileContentSummary>,
+    pub totals: ContentCounts,
+}
+
+impl AssetBundle {
+    /// Summarize the bundle's contents by type without decoding any payloads.
+    ///
+    /// See the [module docs](crate::bundle::summary) for accuracy bounds.
+    pub fn content_summary(&self) -> ContentSummary {
+        let mut summary = ContentSummary::default();
+        for (index, asset) in self.assets.iter().enumerate() {
+            let mut counts = ContentCounts::default();
+            for handle in asset.object_handles() {
+                counts.add_object(handle);
+            }
+            summary.totals.merge(&counts);
+            summary.files.push(FileContentSummary {
+                name: self.asset_names.get(index).cloned().unwrap_or_default(),
+                counts,
+            });
+        }
+        summary
+    }
+}
+
+fn peek(
+    handle: ObjectHandle<'_>,
+    fields: &[&str],
+) -> Option<indexmap::IndexMap<String, UnityValue>> {
+    handle.peek_fields(fields).ok().flatten()
+}
+
+fn int_field(props: &indexmap::IndexMap<String, UnityValue>, key: &str) -> Option<i64> {
+    props.get(key).and_then(UnityValue::as_i64)
+}
+
+fn mesh_vertex_count(props: &indexmap::IndexMap<String, UnityValue>) -> Option<u64> {
+    if let Some(UnityValue::Object(vertex_data)) = props.get("m_VertexData")
+        && let Some(count) = vertex_data
+            .get("m_VertexCount")
+            .and_then(UnityValue::as_i64)
+    {
+        return Some(count.max(0) as u64);
+    }
+    match props.get("m_Vertices") {
+        Some(UnityValue::Array(vertices)) => Some(vertices.len() as u64),
+        _ => None,
+    }
+}
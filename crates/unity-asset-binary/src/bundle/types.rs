@@ -4,19 +4,23 @@
 
 use super::header::BundleHeader;
 use crate::asset::Asset;
+use crate::bytes_preview::{BytesPreview, LenPreview};
 use crate::compression::CompressionBlock;
+use crate::data_source::DataSource;
 use crate::data_view::DataView;
 use crate::error::{BinaryError, Result};
 use crate::reader::{BinaryReader, ByteOrder};
+use crate::unity_version::UnityVersion;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::fmt;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::OnceLock;
 
 #[derive(Debug)]
 struct UnityFsBlockCache {
-    source: DataView,
+    source: Arc<dyn DataSource>,
     block_data_start: usize,
     max_memory: Option<usize>,
     max_block_cache_memory: Option<usize>,
@@ -26,6 +30,8 @@ struct UnityFsBlockCache {
     cached: Vec<Option<Arc<[u8]>>>,
     cached_bytes: usize,
     cached_blocks: usize,
+    /// Blocks inflated so far, including re-inflations after eviction.
+    inflated_blocks: usize,
     tick: u64,
     last_tick: Vec<u64>,
     lru: VecDeque<(usize, u64)>,
@@ -33,7 +39,7 @@ struct UnityFsBlockCache {
 
 #[derive(Debug, Clone)]
 struct LazyDecompress {
-    source: DataView,
+    source: Arc<dyn DataSource>,
     block_data_start: usize,
     max_memory: Option<usize>,
     max_compressed_block_size: Option<usize>,
@@ -118,11 +124,43 @@ impl DirectoryNode {
     }
 }
 
+/// A name shared by several directory nodes of one bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateNodeName {
+    pub name: String,
+    /// Indices into [`AssetBundle::nodes`], in directory order.
+    pub indices: Vec<usize>,
+}
+
+/// Suffix repeated names (`a.assets`, `a_1.assets`, ...) so none collide.
+fn unique_names<'a>(names: impl Iterator<Item = &'a str> + Clone) -> Vec<String> {
+    let mut used: std::collections::HashSet<String> = names.clone().map(str::to_string).collect();
+    let mut seen = std::collections::HashSet::new();
+    names
+        .map(|name| {
+            if seen.insert(name) {
+                return name.to_string();
+            }
+            let (stem, ext) = match name.rfind('.') {
+                Some(dot) if dot > 0 => name.split_at(dot),
+                _ => (name, ""),
+            };
+            let mut n = 1;
+            loop {
+                let candidate = format!("{stem}_{n}{ext}");
+                if used.insert(candidate.clone()) {
+                    return candidate;
+                }
+                n += 1;
+            }
+        })
+        .collect()
+}
+
 /// A Unity AssetBundle
 ///
 /// This structure represents a complete Unity AssetBundle with all its
 /// metadata, compression information, and contained assets.
-#[derive(Debug)]
 pub struct AssetBundle {
     /// Bundle header
     pub header: BundleHeader,
@@ -136,6 +174,8 @@ pub struct AssetBundle {
     pub assets: Vec<Asset>,
     /// Asset file names within the bundle (aligned with `assets` indices).
     pub asset_names: Vec<String>,
+    /// Index into `nodes` of each entry in `assets`.
+    asset_nodes: Vec<usize>,
     /// Raw source view for legacy bundles (UnityWeb/UnityRaw). UnityFS uses decompressed blocks data.
     legacy_source: Option<DataView>,
     /// Decompressed bundle data (UnityFS blocks data), initialized lazily.
@@ -160,6 +200,7 @@ impl AssetBundle {
             files: Vec::new(),
             assets: Vec::new(),
             asset_names: Vec::new(),
+            asset_nodes: Vec::new(),
             legacy_source: None,
             decompressed: lock,
             decompress_lock: Mutex::new(()),
@@ -177,6 +218,7 @@ impl AssetBundle {
             files: Vec::new(),
             assets: Vec::new(),
             asset_names: Vec::new(),
+            asset_nodes: Vec::new(),
             legacy_source: None,
             decompressed: OnceLock::new(),
             decompress_lock: Mutex::new(()),
@@ -186,6 +228,12 @@ impl AssetBundle {
         }
     }
 
+    pub(crate) fn push_asset(&mut self, node_index: usize, name: String, asset: Asset) {
+        self.assets.push(asset);
+        self.asset_names.push(name);
+        self.asset_nodes.push(node_index);
+    }
+
     pub(crate) fn set_decompressed_len(&mut self, len: u64) {
         self.decompressed_len = len;
     }
@@ -200,20 +248,20 @@ impl AssetBundle {
 
     pub(crate) fn set_lazy_unityfs_source(
         &mut self,
-        source: DataView,
+        source: Arc<dyn DataSource>,
         block_data_start: usize,
         max_memory: Option<usize>,
         max_block_cache_memory: Option<usize>,
         max_compressed_block_size: Option<usize>,
     ) -> Result<()> {
-        if block_data_start > source.len() {
+        if block_data_start as u64 > source.len() {
             return Err(BinaryError::invalid_data(format!(
                 "UnityFS block data start {} exceeds available bytes {}",
                 block_data_start,
                 source.len()
             )));
         }
-        let available_compressed = (source.len() - block_data_start) as u64;
+        let available_compressed = source.len() - block_data_start as u64;
 
         let mut guard = self.lazy.lock().unwrap();
         *guard = Some(LazyDecompress {
@@ -266,6 +314,7 @@ impl AssetBundle {
                 .collect(),
             cached_bytes: 0,
             cached_blocks: 0,
+            inflated_blocks: 0,
             tick: 0,
             last_tick: vec![0; self.blocks.len()],
             lru: VecDeque::new(),
@@ -315,13 +364,21 @@ impl AssetBundle {
 
         let mut copied = 0usize;
 
-        for (idx, block) in self.blocks.iter().enumerate() {
+        // Last block starting at or before `offset`; blocks are contiguous in uncompressed space.
+        let first = cache
+            .uncompressed_starts
+            .partition_point(|&start| start <= offset)
+            .saturating_sub(1);
+        for (idx, block) in self.blocks.iter().enumerate().skip(first) {
             let block_start = cache.uncompressed_starts[idx];
             let block_end = block_start
                 .checked_add(block.uncompressed_size as u64)
                 .ok_or_else(|| BinaryError::invalid_data("Block uncompressed range overflow"))?;
 
-            if block_end <= offset || block_start >= end {
+            if block_start >= end {
+                break;
+            }
+            if block_end <= offset {
                 continue;
             }
 
@@ -351,13 +408,21 @@ impl AssetBundle {
                     )));
                 }
 
-                let mut reader = BinaryReader::new(cache.source.as_bytes(), ByteOrder::Big);
                 let comp_start = cache.compressed_starts[idx]
                     .checked_add(cache.block_data_start as u64)
                     .ok_or_else(|| BinaryError::invalid_data("Block compressed start overflow"))?;
-                reader.set_position(comp_start)?;
-                let compressed = reader.read_bytes(block.compressed_size as usize)?;
-                let decompressed = block.decompress(&compressed)?;
+                let decompressed = match cache.source.as_bytes() {
+                    Some(bytes) => {
+                        let mut reader = BinaryReader::new(bytes, ByteOrder::Big);
+                        reader.set_position(comp_start)?;
+                        block.decompress(reader.read_bytes_ref(block.compressed_size as usize)?)?
+                    }
+                    None => block.decompress(
+                        &cache
+                            .source
+                            .read_at(comp_start, block.compressed_size as usize)?,
+                    )?,
+                };
                 let arc: Arc<[u8]> = decompressed.into();
                 let arc_len = arc.len();
                 cache.cached[idx] = Some(arc);
@@ -367,6 +432,7 @@ impl AssetBundle {
                     )
                 })?;
                 cache.cached_blocks = cache.cached_blocks.saturating_add(1);
+                cache.inflated_blocks = cache.inflated_blocks.saturating_add(1);
             }
 
             cache.tick = cache.tick.wrapping_add(1);
@@ -470,8 +536,23 @@ impl AssetBundle {
             }
         }
 
-        let mut reader = BinaryReader::new(lazy.source.as_bytes(), ByteOrder::Big);
-        reader.set_position(lazy.block_data_start as u64)?;
+        let owned;
+        let (bytes, start) = match lazy.source.as_bytes() {
+            Some(bytes) => (bytes, lazy.block_data_start as u64),
+            None => {
+                let compressed_len = self
+                    .blocks
+                    .iter()
+                    .try_fold(0usize, |acc, b| acc.checked_add(b.compressed_size as usize))
+                    .ok_or_else(|| BinaryError::invalid_data("Total compressed size overflow"))?;
+                owned = lazy
+                    .source
+                    .read_at(lazy.block_data_start as u64, compressed_len)?;
+                (owned.as_slice(), 0)
+            }
+        };
+        let mut reader = BinaryReader::new(bytes, ByteOrder::Big);
+        reader.set_position(start)?;
         let data = super::compression::BundleCompression::decompress_data_blocks_limited(
             &self.header,
             &self.blocks,
@@ -522,6 +603,18 @@ impl AssetBundle {
         }
     }
 
+    /// Number of UnityFS blocks inflated by lazy range reads so far.
+    ///
+    /// A block evicted from the cache and read again counts twice. Bundles whose blocks were
+    /// decompressed eagerly (or legacy bundles) report `0`.
+    pub fn inflated_block_count(&self) -> usize {
+        self.unityfs_cache
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(0, |cache| cache.inflated_blocks)
+    }
+
     /// Check if the bundle is compressed
     pub fn is_compressed(&self) -> bool {
         !self.blocks.is_empty()
@@ -553,6 +646,79 @@ impl AssetBundle {
         self.nodes.iter().find(|node| node.name == name)
     }
 
+    /// Find every file with the given name, in directory order.
+    ///
+    /// Bundles can contain the same name more than once; [`Self::find_file`] only returns the
+    /// first of them.
+    pub fn find_files(&self, name: &str) -> Vec<&BundleFileInfo> {
+        self.files.iter().filter(|file| file.name == name).collect()
+    }
+
+    /// Find every node with the given name, in directory order.
+    pub fn find_nodes(&self, name: &str) -> Vec<&DirectoryNode> {
+        self.nodes.iter().filter(|node| node.name == name).collect()
+    }
+
+    /// Get a file by its directory index
+    pub fn file_at(&self, index: usize) -> Option<&BundleFileInfo> {
+        self.files.get(index)
+    }
+
+    /// Get a node by its directory index
+    pub fn node_at(&self, index: usize) -> Option<&DirectoryNode> {
+        self.nodes.get(index)
+    }
+
+    /// Directory index of the node that `assets[asset_index]` was loaded from.
+    pub fn asset_node_index(&self, asset_index: usize) -> Option<usize> {
+        if let Some(index) = self.asset_nodes.get(asset_index) {
+            return Some(*index);
+        }
+        let name = self.asset_names.get(asset_index)?;
+        self.nodes.iter().position(|node| &node.name == name)
+    }
+
+    /// Number of nodes before `nodes[index]` that share its name (0 for the first occurrence).
+    pub fn node_occurrence(&self, index: usize) -> Option<usize> {
+        let name = &self.nodes.get(index)?.name;
+        Some(
+            self.nodes[..index]
+                .iter()
+                .filter(|node| &node.name == name)
+                .count(),
+        )
+    }
+
+    /// Names that appear on more than one node, with the indices of every occurrence.
+    pub fn duplicate_node_names(&self) -> Vec<DuplicateNodeName> {
+        let mut by_name: indexmap::IndexMap<&str, Vec<usize>> = indexmap::IndexMap::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            by_name.entry(node.name.as_str()).or_default().push(index);
+        }
+        by_name
+            .into_iter()
+            .filter(|(_, indices)| indices.len() > 1)
+            .map(|(name, indices)| DuplicateNodeName {
+                name: name.to_string(),
+                indices,
+            })
+            .collect()
+    }
+
+    /// Node names made unique for use as output file names.
+    ///
+    /// Repeated names get a `_1`, `_2`, ... suffix before the extension, skipping any name
+    /// already used in the bundle. Aligned with `nodes`.
+    pub fn unique_node_names(&self) -> Vec<String> {
+        unique_names(self.nodes.iter().map(|node| node.name.as_str()))
+    }
+
+    /// Asset names made unique for use as output file names; see [`Self::unique_node_names`].
+    /// Aligned with `assets`.
+    pub fn unique_asset_names(&self) -> Vec<String> {
+        unique_names(self.asset_names.iter().map(String::as_str))
+    }
+
     /// Get all file names
     pub fn file_names(&self) -> Vec<&str> {
         self.files.iter().map(|file| file.name.as_str()).collect()
@@ -623,6 +789,37 @@ impl AssetBundle {
         self.extract_range_unityfs(node.offset, node.size)
     }
 
+    /// Extract `size` bytes starting `offset` bytes into a node
+    ///
+    /// Lazily decompressed UnityFS bundles only inflate the blocks covering the range.
+    pub fn extract_node_range(
+        &self,
+        node: &DirectoryNode,
+        offset: u64,
+        size: u64,
+    ) -> crate::error::Result<Vec<u8>> {
+        let end = offset
+            .checked_add(size)
+            .ok_or_else(|| BinaryError::invalid_data("Range offset+size overflow"))?;
+        if end > node.size {
+            return Err(BinaryError::invalid_data(format!(
+                "Range {}..{} exceeds node {} (size {})",
+                offset, end, node.name, node.size
+            )));
+        }
+        let start = node
+            .offset
+            .checked_add(offset)
+            .ok_or_else(|| BinaryError::invalid_data("Node offset+range overflow"))?;
+
+        if self.decompressed.get().is_some() || self.header.is_legacy() {
+            let bytes = self.extract_node_slice(node)?;
+            return Ok(bytes[offset as usize..end as usize].to_vec());
+        }
+
+        self.extract_range_unityfs(start, size)
+    }
+
     pub fn extract_node_slice(&self, node: &DirectoryNode) -> crate::error::Result<&[u8]> {
         let end_u64 = node
             .offset
@@ -654,25 +851,97 @@ impl AssetBundle {
     }
 
     /// Get bundle statistics
+    ///
+    /// Only the block and directory tables are consulted, so this never decompresses data.
     pub fn statistics(&self) -> BundleStatistics {
         let total_compressed_size: u64 = self.blocks.iter().map(|b| b.compressed_size as u64).sum();
         let total_uncompressed_size: u64 =
             self.blocks.iter().map(|b| b.uncompressed_size as u64).sum();
 
+        let mut blocks = Vec::with_capacity(self.blocks.len());
+        let mut uncompressed_offset = 0u64;
+        let mut compressed_offset = 0u64;
+        for (index, block) in self.blocks.iter().enumerate() {
+            blocks.push(BlockStatistics {
+                index,
+                compression: block
+                    .compression_type()
+                    .map(|c| c.name().to_string())
+                    .unwrap_or_else(|_| format!("Unknown({})", block.flags & 0x3f)),
+                compressed_offset,
+                uncompressed_offset,
+                compressed_size: block.compressed_size as u64,
+                uncompressed_size: block.uncompressed_size as u64,
+                compression_ratio: ratio(
+                    block.compressed_size as u64,
+                    block.uncompressed_size as u64,
+                ),
+                stored_uncompressed: matches!(
+                    block.compression_type(),
+                    Ok(crate::compression::CompressionType::None)
+                ),
+            });
+            uncompressed_offset += block.uncompressed_size as u64;
+            compressed_offset += block.compressed_size as u64;
+        }
+
+        // Nodes address the uncompressed stream; legacy bundles without a block table use the
+        // raw data length instead.
+        let stream_len = if self.blocks.is_empty() {
+            self.size()
+        } else {
+            total_uncompressed_size
+        };
+        let mut spans: Vec<(u64, u64)> = self
+            .nodes
+            .iter()
+            .filter(|n| n.size > 0)
+            .map(|n| (n.offset, n.end_offset()))
+            .collect();
+        spans.sort_unstable();
+        let mut padding_bytes = 0u64;
+        let mut cursor = 0u64;
+        for (start, end) in spans {
+            padding_bytes += start.saturating_sub(cursor);
+            cursor = cursor.max(end);
+        }
+        padding_bytes += stream_len.saturating_sub(cursor);
+
+        let files = self
+            .nodes
+            .iter()
+            .map(|node| NodeCompressionEstimate {
+                name: node.name.clone(),
+                uncompressed_size: node.size,
+                estimated_compressed_size: estimate_compressed_span(
+                    &blocks,
+                    node.offset,
+                    node.end_offset(),
+                ),
+            })
+            .collect();
+
+        let expanded_bytes: u64 = blocks
+            .iter()
+            .map(|b| b.compressed_size.saturating_sub(b.uncompressed_size))
+            .sum();
+
         BundleStatistics {
             total_size: self.size(),
             header_size: self.header.header_size(),
             compressed_size: total_compressed_size,
             uncompressed_size: total_uncompressed_size,
-            compression_ratio: if total_uncompressed_size > 0 {
-                total_compressed_size as f64 / total_uncompressed_size as f64
-            } else {
-                1.0
-            },
+            compression_ratio: ratio(total_compressed_size, total_uncompressed_size),
             file_count: self.file_count(),
             asset_count: self.asset_count(),
             block_count: self.blocks.len(),
             node_count: self.nodes.len(),
+            stored_uncompressed_blocks: blocks.iter().filter(|b| b.stored_uncompressed).count(),
+            padding_bytes,
+            expanded_bytes,
+            wasted_bytes: padding_bytes + expanded_bytes,
+            blocks,
+            files,
         }
     }
 
@@ -717,13 +986,67 @@ impl AssetBundle {
     }
 }
 
+/// One-line summary, e.g. `AssetBundle UnityFS v6 unity=2020.3.0f1 files=2 assets=1 compressed_size=48213`.
+///
+/// `unity` is the header's revision string (the full editor version).
+impl fmt::Display for AssetBundle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AssetBundle {} v{} unity={} files={} assets={} compressed_size={}",
+            self.header.signature,
+            self.header.version,
+            // The header's version field is often a placeholder like `5.x.x`.
+            if self.header.unity_revision.is_empty() {
+                &self.header.unity_version
+            } else {
+                &self.header.unity_revision
+            },
+            self.files.len(),
+            self.assets.len(),
+            self.header.size
+        )
+    }
+}
+
+/// Block tables are shown as counts and the decompressed payload as a [`BytesPreview`].
+impl fmt::Debug for AssetBundle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AssetBundle")
+            .field("header", &self.header)
+            .field("blocks", &LenPreview(self.blocks.len(), "blocks"))
+            .field("nodes", &self.nodes)
+            .field("files", &self.files)
+            .field("assets", &self.assets)
+            .field("asset_names", &self.asset_names)
+            .field(
+                "decompressed",
+                &self.decompressed.get().map(|data| BytesPreview(data)),
+            )
+            .field("decompressed_len", &self.decompressed_len)
+            .finish_non_exhaustive()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::compression::CompressionBlock;
+    use crate::data_source::BytesSource;
     use crate::data_view::DataView;
     use crate::shared_bytes::SharedBytes;
 
+    #[test]
+    fn unique_names_suffix_repeats_before_the_extension() {
+        let names = ["CAB-a", "a.resS", "CAB-a", "a.resS", "a_1.resS", "CAB-a"];
+        assert_eq!(
+            unique_names(names.iter().copied()),
+            vec![
+                "CAB-a", "a.resS", "CAB-a_1", "a_2.resS", "a_1.resS", "CAB-a_2"
+            ]
+        );
+    }
+
     #[test]
     fn unityfs_extract_node_data_is_lazy_and_supports_cross_block_ranges() {
         let header = BundleHeader {
@@ -741,7 +1064,13 @@ mod tests {
         let bytes: Vec<u8> = (0u8..10u8).collect();
         let view = DataView::from_shared(SharedBytes::from_vec(bytes));
         bundle
-            .set_lazy_unityfs_source(view, 0, None, None, None)
+            .set_lazy_unityfs_source(
+                Arc::new(BytesSource::from_view("test", view)),
+                0,
+                None,
+                None,
+                None,
+            )
             .unwrap();
 
         let node = DirectoryNode::new("test.bin".to_string(), 3, 6, 0x4);
@@ -756,6 +1085,7 @@ mod tests {
 
 /// Bundle statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct BundleStatistics {
     pub total_size: u64,
     pub header_size: u64,
@@ -766,10 +1096,87 @@ pub struct BundleStatistics {
     pub asset_count: usize,
     pub block_count: usize,
     pub node_count: usize,
+    /// Blocks whose codec is `None` (candidates for recompression when the data is compressible).
+    #[serde(default)]
+    pub stored_uncompressed_blocks: usize,
+    /// Uncompressed-stream bytes not covered by any directory node (alignment gaps, tail padding).
+    #[serde(default)]
+    pub padding_bytes: u64,
+    /// Bytes by which blocks grew when "compressed" (compressed size above uncompressed size).
+    #[serde(default)]
+    pub expanded_bytes: u64,
+    /// `padding_bytes + expanded_bytes`.
+    #[serde(default)]
+    pub wasted_bytes: u64,
+    /// Per-block sizes, in block-table order.
+    #[serde(default)]
+    pub blocks: Vec<BlockStatistics>,
+    /// Per-node compressed size estimates, in directory order.
+    #[serde(default)]
+    pub files: Vec<NodeCompressionEstimate>,
+}
+
+impl BundleStatistics {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// Sizes of one compression block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct BlockStatistics {
+    pub index: usize,
+    /// Codec name (`"LZ4"`, `"LZMA"`, ...), or `"Unknown(n)"` for unsupported flags.
+    pub compression: String,
+    /// Offset of the block within the compressed block data.
+    pub compressed_offset: u64,
+    /// Offset of the block within the uncompressed stream.
+    pub uncompressed_offset: u64,
+    pub compressed_size: u64,
+    pub uncompressed_size: u64,
+    pub compression_ratio: f64,
+    pub stored_uncompressed: bool,
+}
+
+/// Estimated compressed footprint of one directory node.
+///
+/// Each overlapping block contributes its compressed size in proportion to the share of its
+/// uncompressed bytes that belong to the node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct NodeCompressionEstimate {
+    pub name: String,
+    pub uncompressed_size: u64,
+    pub estimated_compressed_size: u64,
+}
+
+fn ratio(compressed: u64, uncompressed: u64) -> f64 {
+    if uncompressed > 0 {
+        compressed as f64 / uncompressed as f64
+    } else {
+        1.0
+    }
+}
+
+fn estimate_compressed_span(blocks: &[BlockStatistics], start: u64, end: u64) -> u64 {
+    let estimate: f64 = blocks
+        .iter()
+        .filter(|b| b.uncompressed_size > 0)
+        .map(|b| {
+            let block_end = b.uncompressed_offset + b.uncompressed_size;
+            let overlap = end
+                .min(block_end)
+                .saturating_sub(start.max(b.uncompressed_offset));
+            overlap as f64 / b.uncompressed_size as f64 * b.compressed_size as f64
+        })
+        .sum();
+    estimate.round() as u64
 }
 
 /// Bundle loading options
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct BundleLoadOptions {
     /// Whether to load all assets immediately
     pub load_assets: bool,
@@ -777,6 +1184,13 @@ pub struct BundleLoadOptions {
     pub decompress_blocks: bool,
     /// Whether to validate the bundle structure
     pub validate: bool,
+    /// Keep UnityFS blocks compressed even when `load_assets` is set.
+    ///
+    /// Asset nodes are then parsed from range reads that inflate only the blocks they cover,
+    /// through the block cache capped by `max_unityfs_block_cache_memory`, instead of from one
+    /// fully decompressed copy of the bundle. `.resS` / `.resource` nodes are never inflated up
+    /// front. `decompress_blocks` is ignored.
+    pub streaming: bool,
     /// Maximum memory usage for decompression (in bytes)
     pub max_memory: Option<usize>,
     /// Maximum memory for caching UnityFS *decompressed blocks* during lazy range extraction.
@@ -805,35 +1219,31 @@ pub struct BundleLoadOptions {
     pub max_blocks: usize,
     /// Maximum number of directory nodes / file entries allowed in metadata.
     pub max_nodes: usize,
+    /// Unity version applied to every SerializedFile loaded from the bundle in place of the one
+    /// it declares, e.g. the `best` of an
+    /// [`InferredVersion`](crate::unity_version::InferredVersion) for stripped builds.
+    pub unity_version_override: Option<UnityVersion>,
 }
 
-impl Default for BundleLoadOptions {
-    fn default() -> Self {
+impl BundleLoadOptions {
+    /// Create options for lazy loading (validate metadata, but do not preload assets or decompress blocks).
+    pub fn lazy() -> Self {
         Self {
-            load_assets: true,
-            // Note: UnityFS must decompress blocks to load embedded assets, so `load_assets=true`
-            // implies eager decompression even when `decompress_blocks=false`.
+            load_assets: false,
             decompress_blocks: false,
             validate: true,
-            max_memory: Some(1024 * 1024 * 1024), // 1GB default limit
-            max_unityfs_block_cache_memory: Some(1024 * 1024 * 1024), // 1GB default cap
-            max_compressed_blocks_info_size: Some(64 * 1024 * 1024), // 64MB compressed metadata cap
-            max_blocks_info_size: Some(64 * 1024 * 1024), // 64MB metadata cap
-            max_legacy_directory_compressed_size: Some(64 * 1024 * 1024), // 64MB legacy dir cap
-            max_compressed_block_size: Some(1024 * 1024 * 1024), // 1GB per-block compressed cap
-            max_blocks: 1_000_000,
-            max_nodes: 1_000_000,
+            ..Default::default()
         }
     }
-}
 
-impl BundleLoadOptions {
-    /// Create options for lazy loading (validate metadata, but do not preload assets or decompress blocks).
-    pub fn lazy() -> Self {
+    /// Create options for streaming loading of huge bundles: assets are parsed, but UnityFS
+    /// blocks are only inflated on demand, with at most 256MB of them cached at a time.
+    pub fn streaming() -> Self {
         Self {
-            load_assets: false,
+            load_assets: true,
             decompress_blocks: false,
-            validate: true,
+            streaming: true,
+            max_unityfs_block_cache_memory: Some(256 * 1024 * 1024),
             ..Default::default()
         }
     }
@@ -843,6 +1253,7 @@ impl BundleLoadOptions {
         Self {
             load_assets: false,
             decompress_blocks: false,
+            streaming: false,
             validate: false,
             max_memory: None,
             max_unityfs_block_cache_memory: None,
@@ -852,6 +1263,7 @@ impl BundleLoadOptions {
             max_compressed_block_size: None,
             max_blocks: usize::MAX,
             max_nodes: usize::MAX,
+            unity_version_override: None,
         }
     }
 
@@ -860,6 +1272,7 @@ impl BundleLoadOptions {
         Self {
             load_assets: true,
             decompress_blocks: true,
+            streaming: false,
             validate: true,
             max_memory: Some(2048 * 1024 * 1024), // 2GB for complete loading
             max_unityfs_block_cache_memory: Some(2048 * 1024 * 1024), // 2GB cap
@@ -869,6 +1282,120 @@ impl BundleLoadOptions {
             max_compressed_block_size: Some(2048 * 1024 * 1024), // 2GB per-block compressed cap
             max_blocks: 2_000_000,
             max_nodes: 2_000_000,
+            unity_version_override: None,
+        }
+    }
+
+    /// Set `load_assets`.
+    pub fn with_load_assets(mut self, load_assets: bool) -> Self {
+        self.load_assets = load_assets;
+        self
+    }
+
+    /// Set `decompress_blocks`.
+    pub fn with_decompress_blocks(mut self, decompress_blocks: bool) -> Self {
+        self.decompress_blocks = decompress_blocks;
+        self
+    }
+
+    /// Set `streaming`.
+    pub fn with_streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
+    /// Set `validate`.
+    pub fn with_validate(mut self, validate: bool) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// Set `max_memory`.
+    pub fn with_max_memory(mut self, max_memory: Option<usize>) -> Self {
+        self.max_memory = max_memory;
+        self
+    }
+
+    /// Set `max_unityfs_block_cache_memory`.
+    pub fn with_max_unityfs_block_cache_memory(
+        mut self,
+        max_unityfs_block_cache_memory: Option<usize>,
+    ) -> Self {
+        self.max_unityfs_block_cache_memory = max_unityfs_block_cache_memory;
+        self
+    }
+
+    /// Set `max_compressed_blocks_info_size`.
+    pub fn with_max_compressed_blocks_info_size(
+        mut self,
+        max_compressed_blocks_info_size: Option<usize>,
+    ) -> Self {
+        self.max_compressed_blocks_info_size = max_compressed_blocks_info_size;
+        self
+    }
+
+    /// Set `max_blocks_info_size`.
+    pub fn with_max_blocks_info_size(mut self, max_blocks_info_size: Option<usize>) -> Self {
+        self.max_blocks_info_size = max_blocks_info_size;
+        self
+    }
+
+    /// Set `max_legacy_directory_compressed_size`.
+    pub fn with_max_legacy_directory_compressed_size(
+        mut self,
+        max_legacy_directory_compressed_size: Option<usize>,
+    ) -> Self {
+        self.max_legacy_directory_compressed_size = max_legacy_directory_compressed_size;
+        self
+    }
+
+    /// Set `max_compressed_block_size`.
+    pub fn with_max_compressed_block_size(
+        mut self,
+        max_compressed_block_size: Option<usize>,
+    ) -> Self {
+        self.max_compressed_block_size = max_compressed_block_size;
+        self
+    }
+
+    /// Set `max_blocks`.
+    pub fn with_max_blocks(mut self, max_blocks: usize) -> Self {
+        self.max_blocks = max_blocks;
+        self
+    }
+
+    /// Set `max_nodes`.
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// Set `unity_version_override`.
+    pub fn with_unity_version_override(mut self, version: Option<UnityVersion>) -> Self {
+        self.unity_version_override = version;
+        self
+    }
+}
+
+impl Default for BundleLoadOptions {
+    fn default() -> Self {
+        Self {
+            load_assets: true,
+            // Note: unless `streaming` is set, UnityFS decompresses every block to load embedded
+            // assets, so `load_assets=true` implies eager decompression even when
+            // `decompress_blocks=false`.
+            decompress_blocks: false,
+            streaming: false,
+            validate: true,
+            max_memory: Some(1024 * 1024 * 1024), // 1GB default limit
+            max_unityfs_block_cache_memory: Some(1024 * 1024 * 1024), // 1GB default cap
+            max_compressed_blocks_info_size: Some(64 * 1024 * 1024), // 64MB compressed metadata cap
+            max_blocks_info_size: Some(64 * 1024 * 1024), // 64MB metadata cap
+            max_legacy_directory_compressed_size: Some(64 * 1024 * 1024), // 64MB legacy dir cap
+            max_compressed_block_size: Some(1024 * 1024 * 1024), // 1GB per-block compressed cap
+            max_blocks: 1_000_000,
+            max_nodes: 1_000_000,
+            unity_version_override: None,
         }
     }
 }
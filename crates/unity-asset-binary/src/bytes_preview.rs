@@ -0,0 +1,56 @@
+//! Truncated byte formatting for `Debug`/`Display` output.
+//!
+//! Unity payloads (bundle blocks, texture data, audio samples) are routinely megabytes long.
+//! `Debug` implementations across the workspace print them through [`BytesPreview`], which
+//! shows the length and the first [`BYTES_PREVIEW_LEN`] bytes only:
+//!
+//! ```
+//! use unity_asset_binary::bytes_preview::BytesPreview;
+//!
+//! assert_eq!(format!("{:?}", BytesPreview(b"UnityFS")), "<7 bytes: 55 6e 69 74 79 46 53>");
+//! assert_eq!(format!("{:?}", BytesPreview(&[0u8; 20])), "<20 bytes: 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 ..>");
+//! assert_eq!(format!("{:?}", BytesPreview(&[])), "<0 bytes>");
+//! ```
+//!
+//! The format is part of the log output contract; keep it stable.
+
+use std::fmt;
+
+/// Number of leading bytes shown by [`BytesPreview`].
+pub const BYTES_PREVIEW_LEN: usize = 16;
+
+/// Formats a byte slice as `<N bytes: xx xx ..>`.
+#[derive(Clone, Copy)]
+pub struct BytesPreview<'a>(pub &'a [u8]);
+
+impl fmt::Debug for BytesPreview<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{} bytes", self.0.len())?;
+        if !self.0.is_empty() {
+            f.write_str(":")?;
+            for b in self.0.iter().take(BYTES_PREVIEW_LEN) {
+                write!(f, " {:02x}", b)?;
+            }
+            if self.0.len() > BYTES_PREVIEW_LEN {
+                f.write_str(" ..")?;
+            }
+        }
+        f.write_str(">")
+    }
+}
+
+impl fmt::Display for BytesPreview<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Formats a collection as its length only (`[N items]`), for `Debug` output of large tables.
+#[derive(Clone, Copy)]
+pub struct LenPreview(pub usize, pub &'static str);
+
+impl fmt::Debug for LenPreview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{} {}]", self.0, self.1)
+    }
+}
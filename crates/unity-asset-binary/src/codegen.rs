@@ -0,0 +1,511 @@
+//! C# class stubs from TypeTrees
+//!
+//! Modders rebuilding a game's data types need C# definitions that match its serialized layouts.
+//! [`csharp_from_typetree`] turns a TypeTree into a `[Serializable]` class: fields in declaration
+//! order, Unity primitive and math types mapped to their C# counterparts, `List<T>` for arrays,
+//! and a nested class per compound field type. Every field keeps a comment with the original
+//! type name, byte size and meta flags. [`csharp_scripts`] does this for each distinct
+//! MonoBehaviour script of a SerializedFile, named after its MonoScript.
+//!
+//! MonoBehaviour trees derive from `MonoBehaviour` and skip the fields Unity serializes for the
+//! base class (`m_GameObject`, `m_Enabled`, `m_Script`, `m_Name`, ...). Field and type names
+//! that are not valid C# identifiers are sanitized; the comment then records the original name.
+
+use crate::asset::SerializedFile;
+use crate::typetree::{TypeTree, TypeTreeNode};
+use std::collections::HashMap;
+use std::fmt::Write;
+use unity_asset_core::{UnityValue, class_ids};
+
+const INDENT: &str = "    ";
+
+/// Fields Unity serializes for `MonoBehaviour` itself, ahead of the script's own fields.
+const MONO_BEHAVIOUR_BASE_FIELDS: &[&str] = &[
+    "m_ObjectHideFlags",
+    "m_CorrespondingSourceObject",
+    "m_PrefabInstance",
+    "m_PrefabAsset",
+    "m_PrefabParentObject",
+    "m_PrefabInternal",
+    "m_GameObject",
+    "m_Enabled",
+    "m_EditorHideFlags",
+    "m_Script",
+    "m_Name",
+    "m_EditorClassIdentifier",
+];
+
+const CSHARP_KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "base",
+    "bool",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "checked",
+    "class",
+    "const",
+    "continue",
+    "decimal",
+    "default",
+    "delegate",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "event",
+    "explicit",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "float",
+    "for",
+    "foreach",
+    "goto",
+    "if",
+    "implicit",
+    "in",
+    "int",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "long",
+    "namespace",
+    "new",
+    "null",
+    "object",
+    "operator",
+    "out",
+    "override",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "ref",
+    "return",
+    "sbyte",
+    "sealed",
+    "short",
+    "sizeof",
+    "stackalloc",
+    "static",
+    "string",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "uint",
+    "ulong",
+    "unchecked",
+    "unsafe",
+    "ushort",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+];
+
+/// A C# source file generated for one MonoBehaviour script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CSharpScript {
+    /// `<ClassName>.cs`, made unique within one [`csharp_scripts`] call.
+    pub file_name: String,
+    pub class_name: String,
+    /// Empty for the global namespace.
+    pub namespace: String,
+    pub script_id: [u8; 16],
+    pub source: String,
+}
+
+/// C# source for a `[Serializable]` class named `class_name` with the fields of `tree`.
+pub fn csharp_from_typetree(tree: &TypeTree, class_name: &str) -> String {
+    render_file(tree, class_name, "")
+}
+
+/// One C# file per distinct MonoBehaviour script in `asset` that has a TypeTree.
+///
+/// Class names come from the MonoScript referenced by `m_Script` when it lives in `asset`;
+/// otherwise the class is named `Script_<script id prefix>`.
+pub fn csharp_scripts(asset: &SerializedFile) -> Vec<CSharpScript> {
+    let mut scripts = Vec::new();
+    let mut seen: Vec<usize> = Vec::new();
+    let mut file_names: HashMap<String, usize> = HashMap::new();
+
+    for handle in asset.object_handles() {
+        let info = handle.info();
+        if info.type_id != class_ids::MONO_BEHAVIOUR || info.type_index < 0 {
+            continue;
+        }
+        let type_index = info.type_index as usize;
+        if seen.contains(&type_index) {
+            continue;
+        }
+        seen.push(type_index);
+        let Some(typ) = asset.types.get(type_index) else {
+            continue;
+        };
+        if typ.type_tree.is_empty() {
+            continue;
+        }
+
+        let (class_name, namespace) = script_name(asset, &handle)
+            .map(|(class, namespace)| (identifier(&class), namespace))
+            .unwrap_or_else(|| (fallback_name(typ.script_id, type_index), String::new()));
+        let namespace = namespace
+            .split('.')
+            .filter(|part| !part.is_empty())
+            .map(identifier)
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let count = file_names.entry(class_name.clone()).or_insert(0);
+        *count += 1;
+        let file_name = if *count == 1 {
+            format!("{}.cs", class_name)
+        } else {
+            format!("{}_{}.cs", class_name, count)
+        };
+
+        scripts.push(CSharpScript {
+            file_name,
+            source: render_file(&typ.type_tree, &class_name, &namespace),
+            class_name,
+            namespace,
+            script_id: typ.script_id,
+        });
+    }
+    scripts
+}
+
+/// `(m_ClassName, m_Namespace)` of the MonoScript a MonoBehaviour points at, if it is local.
+pub(crate) fn script_name(
+    asset: &SerializedFile,
+    handle: &crate::object::ObjectHandle<'_>,
+) -> Option<(String, String)> {
+    let fields = handle.peek_fields(&["m_Script"]).ok()??;
+    let script = fields.get("m_Script")?.as_object()?;
+    let file_id = script.get("m_FileID").and_then(UnityValue::as_i64)?;
+    let path_id = script.get("m_PathID").and_then(UnityValue::as_i64)?;
+    if file_id != 0 {
+        return None;
+    }
+    let object = asset
+        .find_object_handle(path_id)
+        .filter(|h| h.class_id() == class_ids::MONO_SCRIPT)?
+        .read()
+        .ok()?;
+    let properties = object.class.properties();
+    let class = properties.get("m_ClassName").and_then(UnityValue::as_str)?;
+    let namespace = properties
+        .get("m_Namespace")
+        .and_then(UnityValue::as_str)
+        .unwrap_or_default();
+    (!class.is_empty()).then(|| (class.to_string(), namespace.to_string()))
+}
+
+fn fallback_name(script_id: [u8; 16], type_index: usize) -> String {
+    if script_id == [0; 16] {
+        return format!("Script_{}", type_index);
+    }
+    let mut name = String::from("Script_");
+    for byte in &script_id[..4] {
+        let _ = write!(name, "{:02x}", byte);
+    }
+    name
+}
+
+fn render_file(tree: &TypeTree, class_name: &str, namespace: &str) -> String {
+    let root = tree.nodes.first();
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "// Generated from a Unity TypeTree ({}).",
+        root.map_or("empty", |r| r.type_name.as_str())
+    );
+    out.push_str("using System;\nusing System.Collections.Generic;\nusing UnityEngine;\n\n");
+
+    let class_name = identifier(class_name);
+    let lines = match root {
+        Some(root) => {
+            let (base, fields): (Option<&str>, Vec<&TypeTreeNode>) =
+                if root.type_name == "MonoBehaviour" {
+                    let fields = root
+                        .children
+                        .iter()
+                        .filter(|f| !MONO_BEHAVIOUR_BASE_FIELDS.contains(&f.name.as_str()))
+                        .collect();
+                    (Some("MonoBehaviour"), fields)
+                } else {
+                    (None, root.children.iter().collect())
+                };
+            let mut nested = NestedClasses::new(&class_name);
+            let mut lines = render_class(&class_name, base, &fields, &mut nested);
+            let body_end = lines.len() - 1;
+            let mut rendered = Vec::new();
+            let mut next = 0;
+            while next < nested.classes.len() {
+                let (name, node) = (nested.classes[next].0.clone(), nested.classes[next].1);
+                let fields: Vec<&TypeTreeNode> = node.children.iter().collect();
+                let class = render_class(&name, None, &fields, &mut nested);
+                rendered.push(String::new());
+                rendered.extend(class.into_iter().map(|l| indent(&l)));
+                next += 1;
+            }
+            lines.splice(body_end..body_end, rendered);
+            lines
+        }
+        None => render_class(&class_name, None, &[], &mut NestedClasses::new(&class_name)),
+    };
+
+    if namespace.is_empty() {
+        for line in lines {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    } else {
+        let _ = writeln!(out, "namespace {}\n{{", namespace);
+        for line in lines {
+            out.push_str(&indent(&line));
+            out.push('\n');
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+fn indent(line: &str) -> String {
+    if line.is_empty() {
+        String::new()
+    } else {
+        format!("{}{}", INDENT, line)
+    }
+}
+
+/// `[Serializable]` class lines; the closing brace is the last line.
+fn render_class<'a>(
+    name: &str,
+    base: Option<&str>,
+    fields: &[&'a TypeTreeNode],
+    nested: &mut NestedClasses<'a>,
+) -> Vec<String> {
+    let mut lines = vec!["[Serializable]".to_string()];
+    match base {
+        Some(base) => lines.push(format!("public class {} : {}", name, base)),
+        None => lines.push(format!("public class {}", name)),
+    }
+    lines.push("{".to_string());
+
+    let mut used: HashMap<String, usize> = HashMap::new();
+    for field in fields {
+        let ty = nested.csharp_type(field);
+        let mut field_name = identifier(&field.name);
+        let count = used.entry(field_name.clone()).or_insert(0);
+        *count += 1;
+        if *count > 1 {
+            field_name = format!("{}_{}", field_name, count);
+        }
+        let mut comment = format!(
+            "{} size={} flags=0x{:x}",
+            field.type_name, field.byte_size, field.meta_flags
+        );
+        if field_name != field.name {
+            let _ = write!(comment, " name=\"{}\"", field.name);
+        }
+        lines.push(format!(
+            "{}public {} {}; // {}",
+            INDENT, ty, field_name, comment
+        ));
+    }
+    lines.push("}".to_string());
+    lines
+}
+
+/// Nested classes of one generated file, in order of first use.
+struct NestedClasses<'a> {
+    classes: Vec<(String, &'a TypeTreeNode)>,
+    outer: String,
+}
+
+impl<'a> NestedClasses<'a> {
+    fn new(outer: &str) -> Self {
+        Self {
+            classes: Vec::new(),
+            outer: outer.to_string(),
+        }
+    }
+
+    fn csharp_type(&mut self, node: &'a TypeTreeNode) -> String {
+        match node.type_name.as_str() {
+            "string" => return "string".to_string(),
+            "TypelessData" => return "byte[]".to_string(),
+            _ => {}
+        }
+        if let Some(array) = array_node(node) {
+            let element = array.children.get(1);
+            if node.type_name == "map"
+                && let Some(pair) = element
+                && let [first, second] = pair.children.as_slice()
+            {
+                return format!(
+                    "Dictionary<{}, {}>",
+                    self.csharp_type(first),
+                    self.csharp_type(second)
+                );
+            }
+            return match element {
+                Some(element) => format!("List<{}>", self.csharp_type(element)),
+                None => "List<object>".to_string(),
+            };
+        }
+        if let Some(primitive) = primitive_type(&node.type_name) {
+            return primitive.to_string();
+        }
+        if let Some(target) = node
+            .type_name
+            .strip_prefix("PPtr<")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            let target = target.trim_start_matches('$');
+            return match target {
+                "Object" | "EditorExtension" | "" => "UnityEngine.Object".to_string(),
+                other => identifier(other),
+            };
+        }
+        if let Some(unity) = unity_type(node) {
+            return unity.to_string();
+        }
+        if node.children.is_empty() {
+            return identifier(&node.type_name);
+        }
+        self.class_for(node)
+    }
+
+    /// Name of the nested class for a compound node, registering it on first use.
+    fn class_for(&mut self, node: &'a TypeTreeNode) -> String {
+        let base = identifier(&node.type_name);
+        let mut suffix = 1;
+        loop {
+            let name = if suffix == 1 {
+                base.clone()
+            } else {
+                format!("{}_{}", base, suffix)
+            };
+            suffix += 1;
+            if name == self.outer {
+                continue;
+            }
+            match self.classes.iter().find(|(n, _)| *n == name) {
+                Some((_, existing)) if same_fields(existing, node) => return name,
+                Some(_) => continue,
+                None => {
+                    self.classes.push((name.clone(), node));
+                    return name;
+                }
+            }
+        }
+    }
+}
+
+/// The `Array` node of an array-like field (or the node itself).
+fn array_node(node: &TypeTreeNode) -> Option<&TypeTreeNode> {
+    if node.type_name == "Array" {
+        return Some(node);
+    }
+    node.children.iter().find(|c| c.type_name == "Array")
+}
+
+fn same_fields(a: &TypeTreeNode, b: &TypeTreeNode) -> bool {
+    a.children.len() == b.children.len()
+        && a.children
+            .iter()
+            .zip(&b.children)
+            .all(|(a, b)| a.name == b.name && a.type_name == b.type_name && same_fields(a, b))
+}
+
+fn primitive_type(type_name: &str) -> Option<&'static str> {
+    Some(match type_name {
+        "bool" => "bool",
+        "SInt8" => "sbyte",
+        "UInt8" | "char" => "byte",
+        "SInt16" | "short" => "short",
+        "UInt16" | "unsigned short" => "ushort",
+        "SInt32" | "int" => "int",
+        "UInt32" | "unsigned int" | "Type*" => "uint",
+        "SInt64" | "long long" => "long",
+        "UInt64" | "unsigned long long" | "FileSize" => "ulong",
+        "float" => "float",
+        "double" => "double",
+        _ => return None,
+    })
+}
+
+/// UnityEngine value types Unity serializes under engine-internal names.
+fn unity_type(node: &TypeTreeNode) -> Option<&'static str> {
+    Some(match node.type_name.as_str() {
+        "Vector2f" => "Vector2",
+        "Vector3f" => "Vector3",
+        "Vector4f" => "Vector4",
+        "int2_storage" => "Vector2Int",
+        "int3_storage" => "Vector3Int",
+        "Quaternionf" => "Quaternion",
+        "ColorRGBA" if node.children.len() == 1 => "Color32",
+        "ColorRGBA" => "Color",
+        "Rectf" => "Rect",
+        "RectInt" => "RectInt",
+        "AABB" => "Bounds",
+        "BoundsInt" => "BoundsInt",
+        "Matrix4x4f" => "Matrix4x4",
+        "AnimationCurve" => "AnimationCurve",
+        "Gradient" => "Gradient",
+        "BitField" => "LayerMask",
+        "Hash128" => "Hash128",
+        _ => return None,
+    })
+}
+
+/// `raw` as a C# identifier: invalid characters become `_`, a leading digit gets a `_` prefix
+/// and keywords are escaped with `@`.
+fn identifier(raw: &str) -> String {
+    let mut out: String = raw
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    if CSHARP_KEYWORDS.contains(&out.as_str()) {
+        out.insert(0, '@');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers_are_sanitized() {
+        assert_eq!(identifier("m_Speed"), "m_Speed");
+        assert_eq!(identifier("Max Speed (m/s)"), "Max_Speed__m_s_");
+        assert_eq!(identifier("2dOffset"), "_2dOffset");
+        assert_eq!(identifier("class"), "@class");
+        assert_eq!(identifier(""), "_");
+        assert_eq!(identifier("生命值"), "生命值");
+    }
+}
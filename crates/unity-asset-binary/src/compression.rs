@@ -457,7 +457,18 @@ impl CompressionBlock {
         }
 
         let compression = self.compression_type()?;
-        decompress(data, compression, self.uncompressed_size as usize)
+        let out = decompress(data, compression, self.uncompressed_size as usize)?;
+        // Block offsets in the uncompressed stream are derived from `uncompressed_size`, so a
+        // block that decodes to any other length would shift every node after it.
+        if out.len() != self.uncompressed_size as usize {
+            return Err(BinaryError::decompression_failed(format!(
+                "{} block decompressed to {} bytes, expected {}",
+                compression.name(),
+                out.len(),
+                self.uncompressed_size
+            )));
+        }
+        Ok(out)
     }
 }
 
@@ -0,0 +1,320 @@
+//! Random-access byte sources
+//!
+//! Parsers normally take a whole file as `Vec<u8>`. A [`DataSource`] instead hands out byte
+//! ranges on request, so a lazily loaded UnityFS bundle only reads the compressed blocks it
+//! decompresses and a `.resS` lookup only reads the requested span. Sources can be files,
+//! in-memory buffers, any `Read + Seek` stream, or entries of a [`VirtualFileSystem`] that
+//! resolves Unity's logical names (`archive:/CAB-<hash>/CAB-<hash>.resS`,
+//! `library/unity default resources`) without touching the disk.
+
+use crate::bundle::normalize_stream_path;
+use crate::data_view::DataView;
+use crate::error::{BinaryError, Result};
+use crate::shared_bytes::SharedBytes;
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(feature = "fs")]
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// A named, fixed-size byte source supporting range reads.
+pub trait DataSource: Send + Sync + fmt::Debug {
+    /// Logical name (file name or VFS key), used in error messages and name lookups.
+    fn name(&self) -> &str;
+
+    /// Total length in bytes.
+    fn len(&self) -> u64;
+
+    /// Read exactly `len` bytes starting at `offset`.
+    fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>>;
+
+    /// Whether the source is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The whole content, if it is already in memory (lets parsers skip the copy).
+    fn as_bytes(&self) -> Option<&[u8]> {
+        None
+    }
+
+    /// Read the whole source.
+    fn read_all(&self) -> Result<Vec<u8>> {
+        if let Some(bytes) = self.as_bytes() {
+            return Ok(bytes.to_vec());
+        }
+        let len = usize::try_from(self.len()).map_err(|_| {
+            BinaryError::ResourceLimitExceeded(format!(
+                "Data source {} does not fit in memory",
+                self.name()
+            ))
+        })?;
+        self.read_at(0, len)
+    }
+}
+
+/// Check that `offset..offset + len` lies within a source of `total` bytes.
+pub(crate) fn check_range(name: &str, total: u64, offset: u64, len: usize) -> Result<()> {
+    let end = offset
+        .checked_add(len as u64)
+        .ok_or_else(|| BinaryError::invalid_data("Read range offset+len overflow"))?;
+    if end > total {
+        return Err(BinaryError::invalid_data(format!(
+            "Read of {} bytes at {} exceeds {} ({} bytes)",
+            len, offset, name, total
+        )));
+    }
+    Ok(())
+}
+
+/// A file on disk, read with seek + read for every range.
+#[cfg(feature = "fs")]
+#[derive(Debug)]
+pub struct FileSource {
+    name: String,
+    path: PathBuf,
+    len: u64,
+    file: Mutex<File>,
+}
+
+#[cfg(feature = "fs")]
+impl FileSource {
+    /// Open `path`; the name is its file name.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.to_string_lossy().into_owned()),
+            path: path.to_path_buf(),
+            len,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Path the source was opened from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(feature = "fs")]
+impl DataSource for FileSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn len(&self) -> u64 {
+        self.len
+    }
+
+    fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        check_range(&self.name, self.len, offset, len)?;
+        let mut file = self.file.lock().unwrap();
+        file.seek(SeekFrom::Start(offset))?;
+        let mut buffer = vec![0u8; len];
+        file.read_exact(&mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+/// Bytes already in memory.
+#[derive(Clone)]
+pub struct BytesSource {
+    name: String,
+    data: DataView,
+}
+
+impl BytesSource {
+    /// Wrap an owned buffer.
+    pub fn new(name: impl Into<String>, data: Vec<u8>) -> Self {
+        Self::from_view(name, DataView::from_shared(SharedBytes::from_vec(data)))
+    }
+
+    /// Wrap a shared buffer without copying.
+    pub fn from_view(name: impl Into<String>, data: DataView) -> Self {
+        Self {
+            name: name.into(),
+            data,
+        }
+    }
+}
+
+impl fmt::Debug for BytesSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BytesSource")
+            .field("name", &self.name)
+            .field("len", &self.data.len())
+            .finish()
+    }
+}
+
+impl DataSource for BytesSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn len(&self) -> u64 {
+        self.data.len() as u64
+    }
+
+    fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        check_range(&self.name, self.len(), offset, len)?;
+        let start = offset as usize;
+        Ok(self.data.as_bytes()[start..start + len].to_vec())
+    }
+
+    fn as_bytes(&self) -> Option<&[u8]> {
+        Some(self.data.as_bytes())
+    }
+}
+
+/// Any `Read + Seek` stream (archive entries, network-backed readers, ...).
+pub struct ReaderSource<R> {
+    name: String,
+    len: u64,
+    reader: Mutex<R>,
+}
+
+impl<R: Read + Seek> ReaderSource<R> {
+    /// Wrap `reader`; its length is found by seeking to the end.
+    pub fn new(name: impl Into<String>, mut reader: R) -> Result<Self> {
+        let len = reader.seek(SeekFrom::End(0))?;
+        Ok(Self {
+            name: name.into(),
+            len,
+            reader: Mutex::new(reader),
+        })
+    }
+}
+
+impl<R> fmt::Debug for ReaderSource<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReaderSource")
+            .field("name", &self.name)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<R: Read + Seek + Send> DataSource for ReaderSource<R> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn len(&self) -> u64 {
+        self.len
+    }
+
+    fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        check_range(&self.name, self.len, offset, len)?;
+        let mut reader = self.reader.lock().unwrap();
+        reader.seek(SeekFrom::Start(offset))?;
+        let mut buffer = vec![0u8; len];
+        reader.read_exact(&mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+impl<S: DataSource + ?Sized> DataSource for Arc<S> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn len(&self) -> u64 {
+        (**self).len()
+    }
+
+    fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        (**self).read_at(offset, len)
+    }
+
+    fn as_bytes(&self) -> Option<&[u8]> {
+        (**self).as_bytes()
+    }
+}
+
+/// Sources keyed by logical path, for loading a project or player build without a filesystem.
+///
+/// Lookups normalize the path like stream paths (`archive:/` and `./` dropped, `/` separators)
+/// and ignore case. A full-path miss falls back to the file name, so `archive:/CAB-x/CAB-x.resS`
+/// finds an entry inserted as `CAB-x.resS`.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualFileSystem {
+    entries: HashMap<String, Arc<dyn DataSource>>,
+}
+
+impl VirtualFileSystem {
+    /// Create an empty file system.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn key(path: &str) -> String {
+        normalize_stream_path(path).to_lowercase()
+    }
+
+    /// Add (or replace) an entry at `path`.
+    pub fn insert(&mut self, path: &str, source: impl DataSource + 'static) {
+        self.entries.insert(Self::key(path), Arc::new(source));
+    }
+
+    /// Add (or replace) an in-memory entry at `path`.
+    pub fn insert_bytes(&mut self, path: &str, data: Vec<u8>) {
+        self.insert(path, BytesSource::new(path, data));
+    }
+
+    /// Remove the entry at `path`.
+    pub fn remove(&mut self, path: &str) -> Option<Arc<dyn DataSource>> {
+        self.entries.remove(&Self::key(path))
+    }
+
+    /// Resolve a logical path (full path first, then file name).
+    pub fn open(&self, path: &str) -> Option<Arc<dyn DataSource>> {
+        let key = Self::key(path);
+        if let Some(source) = self.entries.get(&key) {
+            return Some(source.clone());
+        }
+        let file_name = key.rsplit('/').next()?;
+        let mut matches: Vec<(&String, &Arc<dyn DataSource>)> = self
+            .entries
+            .iter()
+            .filter(|(entry, _)| entry.rsplit('/').next() == Some(file_name))
+            .collect();
+        matches.sort_by(|a, b| a.0.cmp(b.0));
+        matches.first().map(|(_, source)| (*source).clone())
+    }
+
+    /// Whether `path` resolves to an entry.
+    pub fn contains(&self, path: &str) -> bool {
+        self.open(path).is_some()
+    }
+
+    /// Read `len` bytes at `offset` of the entry `path` resolves to.
+    pub fn read_at(&self, path: &str, offset: u64, len: usize) -> Result<Vec<u8>> {
+        self.open(path)
+            .ok_or_else(|| BinaryError::generic(format!("File not found in VFS: {}", path)))?
+            .read_at(offset, len)
+    }
+
+    /// Normalized entry paths, sorted.
+    pub fn paths(&self) -> Vec<&str> {
+        let mut paths: Vec<&str> = self.entries.keys().map(String::as_str).collect();
+        paths.sort_unstable();
+        paths
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -1,8 +1,10 @@
+use crate::bytes_preview::BytesPreview;
 use crate::error::{BinaryError, Result};
 use crate::shared_bytes::SharedBytes;
+use std::fmt;
 use std::ops::Range;
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct DataView {
     data: SharedBytes,
     start: usize,
@@ -65,3 +67,13 @@ impl DataView {
         (self.data.ptr_usize(), self.start, self.len)
     }
 }
+
+impl fmt::Debug for DataView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DataView")
+            .field("start", &self.start)
+            .field("len", &self.len)
+            .field("data", &BytesPreview(self.as_bytes()))
+            .finish()
+    }
+}
@@ -68,9 +68,18 @@ pub enum BinaryError {
     #[error("Version compatibility error: {0}")]
     VersionCompatibility(String),
 
+    /// Streamed data refers to a resource file (`archive:/CAB-<hash>/CAB-<hash>.resS`) that is
+    /// not part of the bundle or directory it was looked up in
+    #[error("External resource not found: {path}")]
+    ExternalResourceMissing { path: String },
+
     /// Generic error with context
     #[error("Error: {0}")]
     Generic(String),
+
+    /// Errors raised by `unity-asset-core` APIs (e.g. version parsing)
+    #[error(transparent)]
+    Core(#[from] unity_asset_core::UnityAssetError),
 }
 
 impl BinaryError {
@@ -127,6 +136,11 @@ impl BinaryError {
         Self::Unsupported(feature.into())
     }
 
+    /// Create a new external resource missing error
+    pub fn external_resource_missing<S: Into<String>>(path: S) -> Self {
+        Self::ExternalResourceMissing { path: path.into() }
+    }
+
     /// Create a new generic error
     pub fn generic<S: Into<String>>(msg: S) -> Self {
         Self::Generic(msg.into())
@@ -139,6 +153,16 @@ impl BinaryError {
 }
 
 // Conversion from other error types
+impl From<BinaryError> for unity_asset_core::UnityAssetError {
+    fn from(e: BinaryError) -> Self {
+        match e {
+            // Don't nest a core error inside `Binary` when it round-trips back.
+            BinaryError::Core(inner) => inner,
+            other => unity_asset_core::UnityAssetError::binary(other),
+        }
+    }
+}
+
 impl From<lz4_flex::block::DecompressError> for BinaryError {
     fn from(err: lz4_flex::block::DecompressError) -> Self {
         Self::decompression_failed(format!("LZ4 decompression failed: {}", err))
@@ -215,7 +239,9 @@ impl BinaryError {
             BinaryError::ResourceLimitExceeded(_) => true, // Might reduce limits
             BinaryError::CorruptedData(_) => true, // Might skip corrupted section
             BinaryError::VersionCompatibility(_) => true, // Might use compatibility mode
+            BinaryError::ExternalResourceMissing { .. } => true, // Load the other bundle
             BinaryError::Generic(_) => true, // Generic errors are usually recoverable
+            BinaryError::Core(_) => true,    // Usually metadata (e.g. an unparseable version)
         }
     }
 
@@ -237,7 +263,9 @@ impl BinaryError {
             BinaryError::ResourceLimitExceeded(_) => ErrorSeverity::Medium,
             BinaryError::CorruptedData(_) => ErrorSeverity::Medium,
             BinaryError::VersionCompatibility(_) => ErrorSeverity::Low,
+            BinaryError::ExternalResourceMissing { .. } => ErrorSeverity::Medium,
             BinaryError::Generic(_) => ErrorSeverity::Medium,
+            BinaryError::Core(_) => ErrorSeverity::Medium,
         }
     }
 
@@ -253,6 +281,9 @@ impl BinaryError {
             BinaryError::ResourceLimitExceeded(_) => Some("Reduce processing limits"),
             BinaryError::CorruptedData(_) => Some("Skip corrupted section"),
             BinaryError::VersionCompatibility(_) => Some("Enable compatibility mode"),
+            BinaryError::ExternalResourceMissing { .. } => {
+                Some("Load the bundle or file containing the resource")
+            }
             _ => None,
         }
     }
@@ -285,4 +316,40 @@ mod tests {
             "Invalid signature: expected UnityFS, got UnityWeb"
         );
     }
+
+    #[test]
+    fn test_binary_error_converts_into_core_error() {
+        use std::error::Error as _;
+        use unity_asset_core::UnityAssetError;
+
+        let err: UnityAssetError = BinaryError::not_enough_data(100, 50).into();
+        assert!(matches!(err, UnityAssetError::Binary { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Binary error: Not enough data: expected 100, got 50"
+        );
+        let source = err.source().and_then(|s| s.downcast_ref::<BinaryError>());
+        assert!(matches!(
+            source,
+            Some(BinaryError::NotEnoughData {
+                expected: 100,
+                actual: 50
+            })
+        ));
+    }
+
+    #[test]
+    fn test_core_error_round_trips_without_nesting() {
+        use unity_asset_core::UnityAssetError;
+
+        let err: BinaryError = UnityAssetError::version("Invalid version format: x").into();
+        assert!(matches!(
+            err,
+            BinaryError::Core(UnityAssetError::Version { .. })
+        ));
+        assert_eq!(err.to_string(), "Version error: Invalid version format: x");
+
+        let back: UnityAssetError = err.into();
+        assert!(matches!(back, UnityAssetError::Version { .. }));
+    }
 }
@@ -7,14 +7,17 @@
 //!
 //! This module provides a single entry point to parse them into a tagged enum.
 
+#[cfg(feature = "fs")]
 use crate::asset::SerializedFile;
 use crate::asset::header::SerializedFileHeader;
+#[cfg(feature = "fs")]
 use crate::bundle::{AssetBundle, BundleLoadOptions, BundleParser};
 use crate::data_view::DataView;
 use crate::error::{BinaryError, Result};
 use crate::reader::{BinaryReader, ByteOrder};
 use crate::shared_bytes::SharedBytes;
 use std::ops::Range;
+#[cfg(feature = "fs")]
 use std::path::Path;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -181,7 +184,15 @@ pub fn load_unity_file_from_shared_range(
 }
 
 /// Parse a Unity binary file from a filesystem path.
+///
+/// Split files (`<path>.split0`, `.split1`, ...) are reassembled when `path` is the unsplit name
+/// or one of the chunks.
+#[cfg(feature = "fs")]
 pub fn load_unity_file<P: AsRef<Path>>(path: P) -> Result<UnityFile> {
+    if let Some(parts) = crate::split::split_parts(path.as_ref()) {
+        return load_unity_file_from_memory(crate::split::SplitReader::new(parts)?.read_all()?);
+    }
+
     #[cfg(feature = "mmap")]
     {
         let file = std::fs::File::open(&path).map_err(|e| {
@@ -205,10 +216,16 @@ pub fn load_unity_file<P: AsRef<Path>>(path: P) -> Result<UnityFile> {
 }
 
 /// Load an AssetBundle from a filesystem path with explicit parser options.
+#[cfg(feature = "fs")]
 pub fn load_bundle_file_with_options<P: AsRef<Path>>(
     path: P,
     options: BundleLoadOptions,
 ) -> Result<AssetBundle> {
+    if let Some(parts) = crate::split::split_parts(path.as_ref()) {
+        let data = crate::split::SplitReader::new(parts)?.read_all()?;
+        return BundleParser::from_bytes_with_options(data, options);
+    }
+
     #[cfg(feature = "mmap")]
     {
         let file = std::fs::File::open(&path).map_err(|e| {
@@ -232,10 +249,19 @@ pub fn load_bundle_file_with_options<P: AsRef<Path>>(
 }
 
 /// Load a SerializedFile from a filesystem path.
+#[cfg(feature = "fs")]
 pub fn load_serialized_file<P: AsRef<Path>>(
     path: P,
     preload_object_data: bool,
 ) -> Result<SerializedFile> {
+    if let Some(parts) = crate::split::split_parts(path.as_ref()) {
+        let data = crate::split::SplitReader::new(parts)?.read_all()?;
+        return crate::asset::SerializedFileParser::from_bytes_with_options(
+            data,
+            preload_object_data,
+        );
+    }
+
     #[cfg(feature = "mmap")]
     {
         let file = std::fs::File::open(&path).map_err(|e| {
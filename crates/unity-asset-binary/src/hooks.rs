@@ -0,0 +1,338 @@
+//! Per-object hooks for game-specific processing
+//!
+//! Register [`ObjectHook`]s in a [`HookRegistry`] to see every object a pipeline visits
+//! (custom MonoBehaviour handling, decrypting TextAssets, ...) without forking the crate.
+//! Hooks run in registration order; the first one that returns something other than
+//! [`HookAction::Continue`] decides what happens to the object and later hooks don't see it.
+//!
+//! Pipelines that honour hooks: [`HookRegistry::run_file`] / [`HookRegistry::run_bundle`],
+//! [`SerializedFile::dump_raw_objects_with_hooks`] / [`AssetBundle::dump_raw_objects_with_hooks`]
+//! (and [`BundleProcessor::extract_all`](crate::bundle::BundleProcessor::extract_all)), and
+//! [`MetadataProcessor`](crate::metadata::MetadataProcessor).
+//!
+//! ```rust,no_run
+//! use unity_asset_binary::asset::{DumpFilter, SerializedFileParser};
+//! use unity_asset_binary::hooks::{HookAction, HookRegistry, ObjectContext, Replacement};
+//!
+//! let file = SerializedFileParser::from_bytes(std::fs::read("level0")?)?;
+//! let mut hooks = HookRegistry::new();
+//! hooks.register(|ctx: &ObjectContext<'_>| {
+//!     if ctx.class_name() != "TextAsset" {
+//!         return HookAction::Continue;
+//!     }
+//!     let Ok(raw) = ctx.raw_data() else {
+//!         return HookAction::Continue;
+//!     };
+//!     ctx.output().emit(format!("{}.txt", ctx.path_id()), raw.to_vec());
+//!     HookAction::Replace(Replacement::Bytes(raw.iter().map(|b| b ^ 0x5A).collect()))
+//! });
+//! let report = file.dump_raw_objects_with_hooks("out", &DumpFilter::default(), &mut hooks)?;
+//! println!("{} artifacts", report.artifacts.len());
+//! # Ok::<(), unity_asset_binary::error::BinaryError>(())
+//! ```
+
+use crate::asset::{ObjectInfo, SerializedFile};
+use crate::bundle::AssetBundle;
+use crate::error::{BinaryError, Result};
+use crate::object::{ObjectHandle, UnityObject};
+use std::cell::{OnceCell, RefCell};
+use std::fmt;
+use unity_asset_core::{UnityValue, class_ids};
+
+/// A user callback invoked for each object a pipeline visits.
+///
+/// Closures `FnMut(&ObjectContext<'_>) -> HookAction` implement this trait.
+pub trait ObjectHook: Send {
+    fn on_object(&mut self, ctx: &ObjectContext<'_>) -> HookAction;
+}
+
+impl<F> ObjectHook for F
+where
+    F: FnMut(&ObjectContext<'_>) -> HookAction + Send,
+{
+    fn on_object(&mut self, ctx: &ObjectContext<'_>) -> HookAction {
+        self(ctx)
+    }
+}
+
+/// What a pipeline does with an object after the hooks ran.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HookAction {
+    /// Let the next hook (or the default processing) handle the object.
+    Continue,
+    /// Veto the default processing; nothing is exported for this object.
+    Skip,
+    /// Export this instead of the object's own data.
+    Replace(Replacement),
+}
+
+/// Replacement output for [`HookAction::Replace`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Replacement {
+    /// A parsed value (written as JSON by file-producing pipelines).
+    Value(UnityValue),
+    /// Decoded bytes, written as-is.
+    Bytes(Vec<u8>),
+}
+
+/// Identifies an object in a [`HookReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookedObject {
+    /// Name of the SerializedFile inside a bundle, when the pipeline runs over a bundle.
+    pub asset_name: Option<String>,
+    pub path_id: i64,
+}
+
+/// A file a hook emitted through [`HookOutput::emit`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookArtifact {
+    /// The object being processed when the artifact was emitted.
+    pub object: HookedObject,
+    /// Relative path chosen by the hook.
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Output handle given to hooks; artifacts are collected and handed to the pipeline.
+#[derive(Debug, Default)]
+pub struct HookOutput {
+    current: RefCell<Option<HookedObject>>,
+    artifacts: RefCell<Vec<HookArtifact>>,
+}
+
+impl HookOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emit an extra file for the current object. `name` is a relative path; file-producing
+    /// pipelines write it under their output directory (`..` and absolute parts are dropped).
+    pub fn emit(&self, name: impl Into<String>, bytes: Vec<u8>) {
+        let object = self.current.borrow().clone().unwrap_or(HookedObject {
+            asset_name: None,
+            path_id: 0,
+        });
+        self.artifacts.borrow_mut().push(HookArtifact {
+            object,
+            name: name.into(),
+            bytes,
+        });
+    }
+
+    /// Take the artifacts emitted so far.
+    pub fn take(&self) -> Vec<HookArtifact> {
+        std::mem::take(&mut *self.artifacts.borrow_mut())
+    }
+
+    fn set_current(&self, object: HookedObject) {
+        *self.current.borrow_mut() = Some(object);
+    }
+}
+
+/// What a hook sees for one object.
+pub struct ObjectContext<'a> {
+    handle: ObjectHandle<'a>,
+    asset_name: Option<&'a str>,
+    class_name: String,
+    script_name: OnceCell<Option<String>>,
+    parsed: OnceCell<std::result::Result<UnityObject, String>>,
+    output: &'a HookOutput,
+}
+
+impl<'a> ObjectContext<'a> {
+    pub fn new(
+        handle: ObjectHandle<'a>,
+        asset_name: Option<&'a str>,
+        output: &'a HookOutput,
+    ) -> Self {
+        let class_id = handle.class_id();
+        let class_name = unity_asset_core::get_class_name(class_id)
+            .unwrap_or_else(|| format!("Class_{}", class_id));
+        output.set_current(HookedObject {
+            asset_name: asset_name.map(str::to_string),
+            path_id: handle.path_id(),
+        });
+        Self {
+            handle,
+            asset_name,
+            class_name,
+            script_name: OnceCell::new(),
+            parsed: OnceCell::new(),
+            output,
+        }
+    }
+
+    pub fn handle(&self) -> ObjectHandle<'a> {
+        self.handle
+    }
+
+    /// The SerializedFile holding the object.
+    pub fn file(&self) -> &'a SerializedFile {
+        self.handle.file()
+    }
+
+    pub fn info(&self) -> &'a ObjectInfo {
+        self.handle.info()
+    }
+
+    /// Name of the SerializedFile inside its bundle, when running over a bundle.
+    pub fn asset_name(&self) -> Option<&'a str> {
+        self.asset_name
+    }
+
+    pub fn path_id(&self) -> i64 {
+        self.handle.path_id()
+    }
+
+    pub fn class_id(&self) -> i32 {
+        self.handle.class_id()
+    }
+
+    /// Unity class name (`Class_<id>` for unknown ids).
+    pub fn class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    /// `Namespace.ClassName` of the MonoScript a MonoBehaviour points at, when it lives in the
+    /// same file. Resolved on first call.
+    pub fn script_name(&self) -> Option<&str> {
+        self.script_name
+            .get_or_init(|| {
+                if self.class_id() != class_ids::MONO_BEHAVIOUR {
+                    return None;
+                }
+                let (class, namespace) = crate::codegen::script_name(self.file(), &self.handle)?;
+                Some(if namespace.is_empty() {
+                    class
+                } else {
+                    format!("{}.{}", namespace, class)
+                })
+            })
+            .as_deref()
+    }
+
+    /// The object's serialized bytes.
+    pub fn raw_data(&self) -> Result<&'a [u8]> {
+        self.handle.raw_data()
+    }
+
+    /// The object parsed through its TypeTree. Parsed on first call and cached.
+    pub fn object(&self) -> Result<&UnityObject> {
+        self.parsed
+            .get_or_init(|| self.handle.read().map_err(|e| e.to_string()))
+            .as_ref()
+            .map_err(|e| BinaryError::generic(e.clone()))
+    }
+
+    /// Handle for emitting extra files.
+    pub fn output(&self) -> &'a HookOutput {
+        self.output
+    }
+}
+
+impl fmt::Debug for ObjectContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObjectContext")
+            .field("asset_name", &self.asset_name)
+            .field("path_id", &self.path_id())
+            .field("class_name", &self.class_name)
+            .finish()
+    }
+}
+
+/// Objects the hooks skipped or replaced, plus emitted artifacts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HookReport {
+    /// Objects passed to the hooks.
+    pub visited: usize,
+    pub skipped: Vec<HookedObject>,
+    pub replaced: Vec<(HookedObject, Replacement)>,
+    pub artifacts: Vec<HookArtifact>,
+}
+
+impl HookReport {
+    fn merge(&mut self, other: HookReport) {
+        self.visited += other.visited;
+        self.skipped.extend(other.skipped);
+        self.replaced.extend(other.replaced);
+        self.artifacts.extend(other.artifacts);
+    }
+}
+
+/// Ordered list of [`ObjectHook`]s.
+#[derive(Default)]
+pub struct HookRegistry {
+    hooks: Vec<Box<dyn ObjectHook>>,
+}
+
+impl HookRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a hook; hooks run in registration order.
+    pub fn register<H: ObjectHook + 'static>(&mut self, hook: H) -> &mut Self {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Run the hooks on one object until one of them returns something other than
+    /// [`HookAction::Continue`].
+    pub fn dispatch(&mut self, ctx: &ObjectContext<'_>) -> HookAction {
+        for hook in &mut self.hooks {
+            match hook.on_object(ctx) {
+                HookAction::Continue => {}
+                action => return action,
+            }
+        }
+        HookAction::Continue
+    }
+
+    /// Run the hooks over every object of `file`.
+    pub fn run_file(&mut self, file: &SerializedFile, asset_name: Option<&str>) -> HookReport {
+        let output = HookOutput::new();
+        let mut report = HookReport::default();
+        for info in &file.objects {
+            let ctx = ObjectContext::new(ObjectHandle::new(file, info), asset_name, &output);
+            let object = HookedObject {
+                asset_name: asset_name.map(str::to_string),
+                path_id: info.path_id,
+            };
+            report.visited += 1;
+            match self.dispatch(&ctx) {
+                HookAction::Continue => {}
+                HookAction::Skip => report.skipped.push(object),
+                HookAction::Replace(replacement) => report.replaced.push((object, replacement)),
+            }
+        }
+        report.artifacts = output.take();
+        report
+    }
+
+    /// Run the hooks over every object of every SerializedFile in `bundle`.
+    pub fn run_bundle(&mut self, bundle: &AssetBundle) -> HookReport {
+        let names = bundle.unique_asset_names();
+        let mut report = HookReport::default();
+        for (index, asset) in bundle.assets.iter().enumerate() {
+            let name = names.get(index).map(String::as_str);
+            report.merge(self.run_file(asset, name));
+        }
+        report
+    }
+}
+
+impl fmt::Debug for HookRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HookRegistry")
+            .field("hooks", &self.hooks.len())
+            .finish()
+    }
+}
@@ -0,0 +1,503 @@
+//! Remote bundles read over HTTP(S) with range requests
+//!
+//! [`HttpSource`] is a [`DataSource`] for a URL whose server honours `Range` headers. Opening it
+//! fetches the start of the file and, for UnityFS bundles, the blocks-info range; later
+//! [`read_at`](DataSource::read_at) calls fetch only the spans not already cached. Together with
+//! the lazy bundle options this lists a bundle's nodes, or extracts a single file, without
+//! downloading the whole bundle.
+//!
+//! ```rust,no_run
+//! use unity_asset_binary::bundle::{BundleLoadOptions, BundleParser};
+//! use unity_asset_binary::http_source::HttpSource;
+//! use std::sync::Arc;
+//!
+//! let source = Arc::new(HttpSource::open("https://cdn.example.com/characters.bundle")?);
+//! let bundle = BundleParser::from_source_with_options(source.clone(), BundleLoadOptions::lazy())?;
+//! let node = bundle.nodes.iter().find(|n| n.name.ends_with(".resS")).unwrap();
+//! let payload = bundle.extract_node_data(node)?;
+//! println!("{} bytes, {} transferred", payload.len(), source.stats().bytes_transferred);
+//! # Ok::<(), unity_asset_binary::error::BinaryError>(())
+//! ```
+//!
+//! The client is blocking; don't open or read an `HttpSource` from inside an async runtime.
+
+use crate::bundle::BundleHeader;
+use crate::data_source::{DataSource, check_range};
+use crate::error::{BinaryError, Result};
+use crate::reader::{BinaryReader, ByteOrder};
+use reqwest::StatusCode;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Tuning for [`HttpSource`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct HttpSourceOptions {
+    /// Bytes of fetched ranges kept in memory; least recently used ranges are dropped first.
+    pub cache_budget: usize,
+    /// Smallest range requested; shorter reads fetch this much (read-ahead), and missing spans
+    /// closer than this are merged into one request.
+    pub min_request_size: usize,
+    /// Bytes fetched from the start of the file when opening (header and, usually, blocks info).
+    pub prefetch_size: usize,
+    /// Retries after a failed request (connection errors, 5xx and 429 responses).
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for every further attempt.
+    pub retry_backoff: Duration,
+    /// Timeout of a single request.
+    pub timeout: Duration,
+}
+
+impl Default for HttpSourceOptions {
+    fn default() -> Self {
+        Self {
+            cache_budget: 64 * 1024 * 1024,
+            min_request_size: 64 * 1024,
+            prefetch_size: 16 * 1024,
+            max_retries: 3,
+            retry_backoff: Duration::from_millis(200),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl HttpSourceOptions {
+    pub fn with_cache_budget(mut self, bytes: usize) -> Self {
+        self.cache_budget = bytes;
+        self
+    }
+
+    pub fn with_min_request_size(mut self, bytes: usize) -> Self {
+        self.min_request_size = bytes.max(1);
+        self
+    }
+
+    pub fn with_prefetch_size(mut self, bytes: usize) -> Self {
+        self.prefetch_size = bytes.max(1);
+        self
+    }
+
+    pub fn with_retries(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_backoff = backoff;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Transfer counters of an [`HttpSource`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HttpSourceStats {
+    /// Response body bytes received, including read-ahead and retried requests.
+    pub bytes_transferred: u64,
+    /// Range requests sent, including retries.
+    pub requests: u64,
+    /// Requests that failed and were retried.
+    pub retries: u64,
+    /// Bytes currently held by the range cache.
+    pub cached_bytes: usize,
+}
+
+/// A [`DataSource`] over an HTTP(S) URL, fetched with range requests and cached in memory.
+pub struct HttpSource {
+    url: String,
+    name: String,
+    len: u64,
+    client: Client,
+    options: HttpSourceOptions,
+    cache: Mutex<RangeCache>,
+    bytes_transferred: AtomicU64,
+    requests: AtomicU64,
+    retries: AtomicU64,
+}
+
+impl HttpSource {
+    /// Open `url` with default options.
+    pub fn open(url: impl Into<String>) -> Result<Self> {
+        Self::with_options(url, HttpSourceOptions::default())
+    }
+
+    /// Open `url`: learn its length from the first range response and prefetch the header
+    /// (plus the blocks-info range of UnityFS bundles).
+    ///
+    /// Servers that answer range requests with the whole body are refused.
+    pub fn with_options(url: impl Into<String>, options: HttpSourceOptions) -> Result<Self> {
+        let url = url.into();
+        let client = Client::builder()
+            .timeout(options.timeout)
+            .build()
+            .map_err(|e| BinaryError::io_error(format!("HTTP client: {}", e)))?;
+        let name = url
+            .split(['?', '#'])
+            .next()
+            .and_then(|path| path.rsplit('/').find(|part| !part.is_empty()))
+            .unwrap_or(&url)
+            .to_string();
+        let mut source = Self {
+            name,
+            len: 0,
+            client,
+            cache: Mutex::new(RangeCache::new(options.cache_budget)),
+            options,
+            url,
+            bytes_transferred: AtomicU64::new(0),
+            requests: AtomicU64::new(0),
+            retries: AtomicU64::new(0),
+        };
+
+        let (prefix, len) = source.fetch_with_len(0..source.options.prefetch_size as u64)?;
+        source.len = len;
+        source.cache_insert(0, prefix.clone());
+        if let Some(blocks_info) = blocks_info_range(&prefix, len) {
+            source.prefetch(blocks_info)?;
+        }
+        Ok(source)
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn options(&self) -> &HttpSourceOptions {
+        &self.options
+    }
+
+    /// Bytes and requests spent so far.
+    pub fn stats(&self) -> HttpSourceStats {
+        HttpSourceStats {
+            bytes_transferred: self.bytes_transferred.load(Ordering::Relaxed),
+            requests: self.requests.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            cached_bytes: self.cache.lock().unwrap().bytes,
+        }
+    }
+
+    /// Drop all cached ranges.
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Make sure `range` is cached, fetching what is missing.
+    pub fn prefetch(&self, range: Range<u64>) -> Result<()> {
+        let end = range.end.min(self.len);
+        if range.start >= end {
+            return Ok(());
+        }
+        let mut scratch = vec![0u8; (end - range.start) as usize];
+        self.read_into(range.start, &mut scratch)
+    }
+
+    fn read_into(&self, offset: u64, out: &mut [u8]) -> Result<()> {
+        let gaps = self.cache.lock().unwrap().copy_into(offset, out);
+        for request in self.plan_requests(&gaps) {
+            let bytes = self.fetch(request.clone())?;
+            for gap in gaps
+                .iter()
+                .filter(|g| g.start < request.end && g.end > request.start)
+            {
+                let start = gap.start.max(request.start);
+                let end = gap.end.min(request.end);
+                out[(start - offset) as usize..(end - offset) as usize].copy_from_slice(
+                    &bytes[(start - request.start) as usize..(end - request.start) as usize],
+                );
+            }
+            self.cache_insert(request.start, bytes);
+        }
+        Ok(())
+    }
+
+    /// Widen missing spans to the minimum request size and merge the ones that end up close.
+    fn plan_requests(&self, gaps: &[Range<u64>]) -> Vec<Range<u64>> {
+        let min = self.options.min_request_size as u64;
+        let mut requests: Vec<Range<u64>> = Vec::new();
+        for gap in gaps {
+            let end = gap.end.max(gap.start + min).min(self.len);
+            match requests.last_mut() {
+                Some(last) if gap.start <= last.end + min => last.end = last.end.max(end),
+                _ => requests.push(gap.start..end),
+            }
+        }
+        requests
+    }
+
+    fn cache_insert(&self, offset: u64, bytes: Vec<u8>) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.insert(offset, bytes);
+        cache.evict();
+    }
+
+    fn fetch(&self, range: Range<u64>) -> Result<Vec<u8>> {
+        let (bytes, _) = self.fetch_with_len(range.clone())?;
+        if bytes.len() as u64 != range.end - range.start {
+            return Err(BinaryError::not_enough_data(
+                (range.end - range.start) as usize,
+                bytes.len(),
+            ));
+        }
+        Ok(bytes)
+    }
+
+    /// GET `range` (clamped by the server to the file end); returns the body and the total
+    /// length from `Content-Range`.
+    fn fetch_with_len(&self, range: Range<u64>) -> Result<(Vec<u8>, u64)> {
+        let mut attempt = 0;
+        loop {
+            match self.try_fetch(&range) {
+                Ok(result) => return Ok(result),
+                Err(Attempt::Retry(_)) if attempt < self.options.max_retries => {
+                    self.retries.fetch_add(1, Ordering::Relaxed);
+                    std::thread::sleep(self.options.retry_backoff * 2u32.pow(attempt.min(16)));
+                    attempt += 1;
+                }
+                Err(Attempt::Retry(error)) | Err(Attempt::Fail(error)) => return Err(error),
+            }
+        }
+    }
+
+    fn try_fetch(&self, range: &Range<u64>) -> std::result::Result<(Vec<u8>, u64), Attempt> {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let response = self
+            .client
+            .get(&self.url)
+            .header(RANGE, format!("bytes={}-{}", range.start, range.end - 1))
+            .send()
+            .map_err(|e| Attempt::Retry(self.error(format!("request failed: {}", e))))?;
+
+        let status = response.status();
+        if status == StatusCode::RANGE_NOT_SATISFIABLE && range.start == 0 {
+            // Empty file: no byte range can be satisfied.
+            return Ok((Vec::new(), 0));
+        }
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(Attempt::Retry(self.error(format!("HTTP {}", status))));
+        }
+        if status == StatusCode::OK {
+            return Err(Attempt::Fail(BinaryError::unsupported(format!(
+                "{} does not support range requests",
+                self.url
+            ))));
+        }
+        if status != StatusCode::PARTIAL_CONTENT {
+            return Err(Attempt::Fail(self.error(format!("HTTP {}", status))));
+        }
+
+        let (start, total) = content_range(&response).ok_or_else(|| {
+            Attempt::Fail(self.error("missing or invalid Content-Range".to_string()))
+        })?;
+        if start != range.start {
+            return Err(Attempt::Fail(self.error(format!(
+                "asked for bytes from {}, got bytes from {}",
+                range.start, start
+            ))));
+        }
+        let bytes = response
+            .bytes()
+            .map_err(|e| Attempt::Retry(self.error(format!("reading body: {}", e))))?;
+        self.bytes_transferred
+            .fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        Ok((bytes.to_vec(), total))
+    }
+
+    fn error(&self, message: String) -> BinaryError {
+        BinaryError::io_error(format!("{}: {}", self.url, message))
+    }
+}
+
+impl DataSource for HttpSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn len(&self) -> u64 {
+        self.len
+    }
+
+    fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        check_range(&self.name, self.len, offset, len)?;
+        let mut out = vec![0u8; len];
+        self.read_into(offset, &mut out)?;
+        Ok(out)
+    }
+}
+
+impl fmt::Debug for HttpSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpSource")
+            .field("url", &self.url)
+            .field("len", &self.len)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+enum Attempt {
+    Retry(BinaryError),
+    Fail(BinaryError),
+}
+
+/// `Content-Range: bytes <start>-<end>/<total>` -> (start, total).
+fn content_range(response: &Response) -> Option<(u64, u64)> {
+    let value = response.headers().get(CONTENT_RANGE)?.to_str().ok()?;
+    let (span, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, _) = span.split_once('-')?;
+    Some((start.trim().parse().ok()?, total.trim().parse().ok()?))
+}
+
+/// Where the blocks info of a UnityFS bundle whose first bytes are `prefix` lives.
+fn blocks_info_range(prefix: &[u8], len: u64) -> Option<Range<u64>> {
+    let mut reader = BinaryReader::new(prefix, ByteOrder::Big);
+    let header = BundleHeader::from_reader(&mut reader).ok()?;
+    if !header.is_unity_fs() {
+        return None;
+    }
+    let size = u64::from(header.compressed_blocks_info_size);
+    if header.block_info_at_end() {
+        Some(len.checked_sub(size)?..len)
+    } else {
+        // Up to 16 bytes of alignment padding may precede the blocks info.
+        let start = reader.position();
+        Some(start..(start + 16 + size).min(len))
+    }
+}
+
+/// Non-overlapping cached ranges keyed by offset, evicted least recently used first.
+#[derive(Debug)]
+struct RangeCache {
+    segments: BTreeMap<u64, Segment>,
+    bytes: usize,
+    budget: usize,
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct Segment {
+    data: Vec<u8>,
+    last_used: u64,
+}
+
+impl RangeCache {
+    fn new(budget: usize) -> Self {
+        Self {
+            segments: BTreeMap::new(),
+            bytes: 0,
+            budget,
+            clock: 0,
+        }
+    }
+
+    /// Copy cached bytes at `offset` into `out`; returns the spans that are not cached.
+    fn copy_into(&mut self, offset: u64, out: &mut [u8]) -> Vec<Range<u64>> {
+        let end = offset + out.len() as u64;
+        self.clock += 1;
+        let mut gaps = Vec::new();
+        let mut position = offset;
+        let first = self
+            .segments
+            .range(..=offset)
+            .next_back()
+            .map_or(offset, |(&start, _)| start);
+        for (&start, segment) in self.segments.range_mut(first..end) {
+            let segment_end = start + segment.data.len() as u64;
+            if segment_end <= position {
+                continue;
+            }
+            if start > position {
+                gaps.push(position..start);
+                position = start;
+            }
+            let copy_end = segment_end.min(end);
+            out[(position - offset) as usize..(copy_end - offset) as usize].copy_from_slice(
+                &segment.data[(position - start) as usize..(copy_end - start) as usize],
+            );
+            segment.last_used = self.clock;
+            position = copy_end;
+        }
+        if position < end {
+            gaps.push(position..end);
+        }
+        gaps
+    }
+
+    /// Cache `data` at `offset`, merging it with overlapping and adjacent ranges.
+    fn insert(&mut self, offset: u64, data: Vec<u8>) {
+        if data.is_empty() {
+            return;
+        }
+        self.clock += 1;
+        let mut start = offset;
+        let mut end = offset + data.len() as u64;
+        let touching: Vec<u64> = self
+            .segments
+            .range(..=end)
+            .filter(|(s, segment)| **s + segment.data.len() as u64 >= offset)
+            .map(|(s, _)| *s)
+            .collect();
+        if touching.is_empty() {
+            self.bytes += data.len();
+            self.segments.insert(
+                offset,
+                Segment {
+                    data,
+                    last_used: self.clock,
+                },
+            );
+            return;
+        }
+
+        let removed: Vec<(u64, Segment)> = touching
+            .into_iter()
+            .filter_map(|s| self.segments.remove(&s).map(|segment| (s, segment)))
+            .collect();
+        for (s, segment) in &removed {
+            self.bytes -= segment.data.len();
+            start = start.min(*s);
+            end = end.max(s + segment.data.len() as u64);
+        }
+        let mut merged = vec![0u8; (end - start) as usize];
+        for (s, segment) in &removed {
+            let at = (s - start) as usize;
+            merged[at..at + segment.data.len()].copy_from_slice(&segment.data);
+        }
+        let at = (offset - start) as usize;
+        merged[at..at + data.len()].copy_from_slice(&data);
+        self.bytes += merged.len();
+        self.segments.insert(
+            start,
+            Segment {
+                data: merged,
+                last_used: self.clock,
+            },
+        );
+    }
+
+    fn evict(&mut self) {
+        while self.bytes > self.budget {
+            let Some(oldest) = self
+                .segments
+                .iter()
+                .min_by_key(|(_, segment)| segment.last_used)
+                .map(|(s, _)| *s)
+            else {
+                break;
+            };
+            if let Some(segment) = self.segments.remove(&oldest) {
+                self.bytes -= segment.data.len();
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.segments.clear();
+        self.bytes = 0;
+    }
+}
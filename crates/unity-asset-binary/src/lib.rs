@@ -39,18 +39,47 @@
 //! ```
 
 // Core modules (always available)
+pub mod addressables;
+pub mod animator;
 pub mod asset;
+pub mod asset_bundle_manifest;
+pub mod audio_mixer;
+pub mod avatar;
+pub mod build_settings;
+pub mod builtin_resources;
 pub mod bundle;
+pub mod bytes_preview;
+pub mod codegen;
 pub mod compression;
+pub mod data_source;
 pub mod data_view;
 pub mod error;
 pub mod file;
 pub mod formats;
+pub mod hooks;
+#[cfg(feature = "http")]
+pub mod http_source;
+pub mod lighting;
+pub mod material;
 pub mod metadata;
 pub mod object;
 pub mod performance;
+pub mod pptr;
+pub mod prelude;
+#[cfg(feature = "profiling")]
+pub mod profiling;
 pub mod reader;
+pub mod scene;
+pub mod shader;
+pub mod shader_audit;
 pub mod shared_bytes;
+#[cfg(feature = "fs")]
+pub mod split;
+pub mod string_policy;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+pub mod text_asset;
+pub mod tilemap;
 pub mod typetree;
 pub mod unity_objects;
 pub mod unity_version;
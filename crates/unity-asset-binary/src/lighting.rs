@@ -0,0 +1,368 @@
+//! Baked lighting data: lightmap textures and per-renderer lightmap assignments.
+//!
+//! A scene's LightmapSettings (class 157) lists the baked lightmaps in `m_Lightmaps`; each entry
+//! holds the color lightmap plus optional directional and shadow mask textures. Renderers pick
+//! one of them through `m_LightmapIndex` and place their UVs in it with
+//! `m_LightmapTilingOffset` (scale `x, y`, offset `z, w`).
+//!
+//! The editor keeps the same data in a LightingDataAsset (class 1120), where renderers are
+//! identified by scene object ids (`m_LightmappedRendererDataIDs`) instead of components.
+//!
+//! [`LightingAudit`] combines them into a [`LightingReport`]; [`lighting_report`] runs it over one
+//! serialized file.
+
+use crate::animator::{Fields, array};
+use crate::asset::SerializedFile;
+use crate::error::Result;
+use crate::unity_objects::{ObjectRef, parse_pptr};
+use serde::{Deserialize, Serialize};
+use unity_asset_core::{UnityValue, class_ids};
+
+/// `m_LightmapIndex` of a renderer that is not lightmapped.
+pub const LIGHTMAP_INDEX_NONE: u16 = 0xFFFF;
+/// `m_LightmapIndex` of a renderer that is lightmapped but has no baked lightmap yet.
+pub const LIGHTMAP_INDEX_IN_USE: u16 = 0xFFFE;
+
+/// One `m_Lightmaps` entry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightmapData {
+    /// Color lightmap (`m_Lightmap`).
+    pub lightmap: Option<ObjectRef>,
+    /// Directional lightmap (`m_DirLightmap`, `m_IndirectLightmap` before 5.6).
+    pub directional: Option<ObjectRef>,
+    pub shadow_mask: Option<ObjectRef>,
+}
+
+impl LightmapData {
+    fn from_fields(fields: &Fields) -> Self {
+        let pptr = |key: &str| fields.get(key).and_then(parse_pptr);
+        Self {
+            lightmap: pptr("m_Lightmap"),
+            directional: pptr("m_DirLightmap").or_else(|| pptr("m_IndirectLightmap")),
+            shadow_mask: pptr("m_ShadowMask"),
+        }
+    }
+}
+
+/// Where a renderer samples its baked lightmap.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LightmapAssignment {
+    pub lightmap_index: u16,
+    /// `[scale x, scale y, offset x, offset y]` applied to the renderer's lightmap UVs.
+    pub scale_offset: [f32; 4],
+}
+
+impl LightmapAssignment {
+    /// Read `m_LightmapIndex` / `m_LightmapTilingOffset` from a renderer's TypeTree data.
+    pub fn from_renderer(properties: &Fields) -> Option<Self> {
+        Some(Self {
+            lightmap_index: properties.get("m_LightmapIndex")?.as_i64()? as u16,
+            scale_offset: properties
+                .get("m_LightmapTilingOffset")
+                .and_then(UnityValue::as_object)
+                .map_or([1.0, 1.0, 0.0, 0.0], vec4),
+        })
+    }
+
+    /// Whether the renderer uses a baked lightmap.
+    pub fn is_baked(&self) -> bool {
+        self.lightmap_index < LIGHTMAP_INDEX_IN_USE
+    }
+}
+
+/// A parsed LightmapSettings (class 157).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LightmapSettings {
+    pub lightmaps: Vec<LightmapData>,
+    /// `0` non-directional, `1` combined directional.
+    pub lightmaps_mode: i32,
+    pub lighting_data_asset: Option<ObjectRef>,
+}
+
+impl LightmapSettings {
+    /// Parse LightmapSettings from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        Ok(Self {
+            lightmaps: array(properties, "m_Lightmaps")
+                .map(LightmapData::from_fields)
+                .collect(),
+            lightmaps_mode: int(properties, "m_LightmapsMode"),
+            lighting_data_asset: properties.get("m_LightingDataAsset").and_then(parse_pptr),
+        })
+    }
+}
+
+/// A `m_LightmappedRendererData` entry with its scene object id.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LightmappedRenderer {
+    /// `targetObject` of the matching `m_LightmappedRendererDataIDs` entry.
+    pub object_id: i64,
+    /// `targetPrefab`, for renderers inside prefab instances.
+    pub prefab_id: i64,
+    pub assignment: LightmapAssignment,
+}
+
+/// A parsed LightingDataAsset (class 1120).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LightingDataAsset {
+    pub name: String,
+    pub scene: Option<ObjectRef>,
+    pub lightmaps: Vec<LightmapData>,
+    pub lightmaps_mode: i32,
+    pub renderers: Vec<LightmappedRenderer>,
+}
+
+impl LightingDataAsset {
+    /// Parse LightingDataAsset from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        let renderers = array(properties, "m_LightmappedRendererData")
+            .zip(array(properties, "m_LightmappedRendererDataIDs"))
+            .filter_map(|(data, id)| {
+                Some(LightmappedRenderer {
+                    object_id: id.get("targetObject")?.as_i64()?,
+                    prefab_id: id
+                        .get("targetPrefab")
+                        .and_then(UnityValue::as_i64)
+                        .unwrap_or(0),
+                    assignment: LightmapAssignment {
+                        lightmap_index: data.get("lightmapIndex")?.as_i64()? as u16,
+                        scale_offset: data
+                            .get("lightmapST")
+                            .and_then(UnityValue::as_object)
+                            .map_or([1.0, 1.0, 0.0, 0.0], vec4),
+                    },
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            name: properties
+                .get("m_Name")
+                .and_then(UnityValue::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            scene: properties.get("m_Scene").and_then(parse_pptr),
+            lightmaps: array(properties, "m_Lightmaps")
+                .map(LightmapData::from_fields)
+                .collect(),
+            lightmaps_mode: int(properties, "m_LightmapsMode"),
+            renderers,
+        })
+    }
+}
+
+/// A color lightmap in a [`LightingReport`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LightmapTexture {
+    /// Index renderers use to refer to this lightmap.
+    pub index: usize,
+    pub texture: ObjectRef,
+    /// Texture name and size, when the texture is stored in the same file.
+    pub name: Option<String>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A lightmapped renderer component in a [`LightingReport`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RendererLightmap {
+    pub path_id: i64,
+    pub class_id: i32,
+    /// Name of the renderer's GameObject, when stored in the same file.
+    pub name: Option<String>,
+    pub assignment: LightmapAssignment,
+}
+
+/// Result of [`lighting_report`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LightingReport {
+    /// Color lightmaps, from LightmapSettings or, without one, a LightingDataAsset.
+    pub lightmaps: Vec<LightmapTexture>,
+    /// Sum of `width * height` over the lightmaps whose size is known.
+    pub total_texel_area: u64,
+    /// Lightmaps stored in another file, whose size is unknown.
+    pub unresolved_lightmaps: usize,
+    /// MeshRenderers and SkinnedMeshRenderers with a baked lightmap, by `path_id`.
+    pub renderers: Vec<RendererLightmap>,
+}
+
+impl LightingReport {
+    pub fn lightmap_count(&self) -> usize {
+        self.lightmaps.len()
+    }
+
+    /// Renderers sampling the lightmap at `index`.
+    pub fn renderers_using(&self, index: usize) -> impl Iterator<Item = &RendererLightmap> {
+        self.renderers
+            .iter()
+            .filter(move |r| r.assignment.lightmap_index as usize == index)
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// Accumulates lighting objects into a [`LightingReport`].
+///
+/// Callers look up lightmap textures when finishing, since that depends on how the containing
+/// files are loaded.
+#[derive(Debug, Default)]
+pub struct LightingAudit {
+    settings: Vec<LightmapData>,
+    data_asset: Vec<LightmapData>,
+    renderers: Vec<RendererLightmap>,
+}
+
+impl LightingAudit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_lightmap_settings(&mut self, settings: &LightmapSettings) {
+        self.settings.extend_from_slice(&settings.lightmaps);
+    }
+
+    /// Lightmaps of a LightingDataAsset are only used when no LightmapSettings lists any.
+    pub fn add_lighting_data_asset(&mut self, asset: &LightingDataAsset) {
+        self.data_asset.extend_from_slice(&asset.lightmaps);
+    }
+
+    /// Record a renderer; renderers without a baked lightmap are ignored.
+    pub fn add_renderer(&mut self, renderer: RendererLightmap) {
+        if renderer.assignment.is_baked() {
+            self.renderers.push(renderer);
+        }
+    }
+
+    /// Build the report, resolving each color lightmap to `(name, width, height)`.
+    pub fn finish(
+        self,
+        mut resolve: impl FnMut(ObjectRef) -> Option<(String, u32, u32)>,
+    ) -> LightingReport {
+        let lightmaps = if self.settings.is_empty() {
+            self.data_asset
+        } else {
+            self.settings
+        };
+        let mut report = LightingReport {
+            renderers: self.renderers,
+            ..Default::default()
+        };
+        for (index, data) in lightmaps.iter().enumerate() {
+            let Some(texture) = data.lightmap else {
+                continue;
+            };
+            let mut entry = LightmapTexture {
+                index,
+                texture,
+                name: None,
+                width: 0,
+                height: 0,
+            };
+            match resolve(texture) {
+                Some((name, width, height)) => {
+                    entry.name = Some(name);
+                    entry.width = width;
+                    entry.height = height;
+                    report.total_texel_area += width as u64 * height as u64;
+                }
+                None => report.unresolved_lightmaps += 1,
+            }
+            report.lightmaps.push(entry);
+        }
+        report
+    }
+}
+
+const REPORT_CLASSES: [i32; 4] = [
+    class_ids::LIGHTMAP_SETTINGS,
+    class_ids::LIGHTING_DATA_ASSET,
+    class_ids::MESH_RENDERER,
+    class_ids::SKINNED_MESH_RENDERER,
+];
+
+/// Collect the lightmaps and lightmapped renderers of one serialized file (typically a scene).
+///
+/// Lightmap textures and renderer GameObjects are resolved within the file; objects that fail
+/// to parse are skipped.
+pub fn lighting_report(asset: &SerializedFile) -> LightingReport {
+    let mut audit = LightingAudit::new();
+    for handle in asset.object_handles() {
+        let class_id = handle.class_id();
+        if !REPORT_CLASSES.contains(&class_id) {
+            continue;
+        }
+        let Ok(object) = handle.read() else {
+            continue;
+        };
+        match class_id {
+            class_ids::LIGHTMAP_SETTINGS => {
+                if let Ok(settings) = object.as_lightmap_settings() {
+                    audit.add_lightmap_settings(&settings);
+                }
+            }
+            class_ids::LIGHTING_DATA_ASSET => {
+                if let Ok(data_asset) = object.as_lighting_data_asset() {
+                    audit.add_lighting_data_asset(&data_asset);
+                }
+            }
+            _ => {
+                let properties = object.class.properties();
+                if let Some(assignment) = LightmapAssignment::from_renderer(properties) {
+                    audit.add_renderer(RendererLightmap {
+                        path_id: handle.path_id(),
+                        class_id,
+                        name: properties
+                            .get("m_GameObject")
+                            .and_then(parse_pptr)
+                            .and_then(|go| local_name(asset, go, class_ids::GAME_OBJECT)),
+                        assignment,
+                    });
+                }
+            }
+        }
+    }
+    audit.finish(|texture| local_texture(asset, texture))
+}
+
+fn local_fields(asset: &SerializedFile, target: ObjectRef, class_id: i32) -> Option<Fields> {
+    if target.file_id != 0 {
+        return None;
+    }
+    let object = asset
+        .find_object_handle(target.path_id)
+        .filter(|h| h.class_id() == class_id)?
+        .read()
+        .ok()?;
+    Some(object.class.properties().clone())
+}
+
+fn local_name(asset: &SerializedFile, target: ObjectRef, class_id: i32) -> Option<String> {
+    local_fields(asset, target, class_id)?
+        .get("m_Name")
+        .and_then(UnityValue::as_str)
+        .map(str::to_string)
+}
+
+fn local_texture(asset: &SerializedFile, texture: ObjectRef) -> Option<(String, u32, u32)> {
+    let fields = local_fields(asset, texture, class_ids::TEXTURE_2D)?;
+    let dimension = |key: &str| fields.get(key).and_then(UnityValue::as_i64).unwrap_or(0) as u32;
+    Some((
+        fields
+            .get("m_Name")
+            .and_then(UnityValue::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        dimension("m_Width"),
+        dimension("m_Height"),
+    ))
+}
+
+fn vec4(v: &Fields) -> [f32; 4] {
+    ["x", "y", "z", "w"].map(|key| v.get(key).and_then(UnityValue::as_f64).unwrap_or(0.0) as f32)
+}
+
+fn int(fields: &Fields, key: &str) -> i32 {
+    fields.get(key).and_then(UnityValue::as_i64).unwrap_or(0) as i32
+}
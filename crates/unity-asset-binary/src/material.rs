@@ -0,0 +1,294 @@
+//! Material (class 21) parsing.
+//!
+//! A Material names its shader and keeps the values it assigns to shader properties in
+//! `m_SavedProperties`: textures with their tiling (`m_TexEnvs`), floats, colors and, from 2021.1,
+//! integers. Each is a `map<string, T>`; old layouts key them with a `FastPropertyName`
+//! (`{name}`), binary TypeTrees yield `[key, value]` pairs and JSON dumps `{first, second}`
+//! objects, and all of these are accepted.
+//!
+//! # Raw layout
+//!
+//! Without a TypeTree the fields are read in Unity's order: `m_Name`, `m_Shader`, the keywords
+//! (a space-separated `m_ShaderKeywords` string from 5.0, `m_ValidKeywords` and
+//! `m_InvalidKeywords` arrays from 2021.3), `m_LightmapFlags`, `m_EnableInstancingVariants` /
+//! `m_DoubleSidedGI` (5.6+), `m_CustomRenderQueue`, `stringTagMap` (5.1+),
+//! `disabledShaderPasses` (5.6+) and `m_SavedProperties`. Fields after the property sheet
+//! (`m_BuildTextureStacks`, ...) are not read.
+//!
+//! [`UnityObject::as_material`](crate::object::UnityObject::as_material) reads one from an
+//! object, from its TypeTree when it has one and from the raw bytes otherwise.
+
+use crate::asset::SerializedFile;
+use crate::error::{BinaryError, Result};
+use crate::reader::{BinaryReader, ByteOrder};
+use crate::unity_objects::{ObjectRef, parse_keywords, parse_pptr};
+use crate::unity_version::UnityVersion;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use unity_asset_core::{UnityValue, class_ids};
+
+type Fields = IndexMap<String, UnityValue>;
+
+/// A texture property of a [`Material`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TexEnv {
+    /// `m_Texture`, `None` when no texture is assigned.
+    pub texture: Option<ObjectRef>,
+    /// Tiling (`m_Scale`).
+    pub scale: [f32; 2],
+    pub offset: [f32; 2],
+}
+
+impl Default for TexEnv {
+    fn default() -> Self {
+        Self {
+            texture: None,
+            scale: [1.0, 1.0],
+            offset: [0.0, 0.0],
+        }
+    }
+}
+
+/// A parsed Material.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Material {
+    pub name: String,
+    pub shader: ObjectRef,
+    /// Enabled shader keywords (`m_ShaderKeywords`, or `m_ValidKeywords` from 2021.3).
+    pub keywords: Vec<String>,
+    /// `m_InvalidKeywords` (2021.3+): keywords the shader does not declare.
+    pub invalid_keywords: Vec<String>,
+    /// `m_CustomRenderQueue`; `-1` uses the shader's queue.
+    pub custom_render_queue: i32,
+    /// `stringTagMap` (e.g. `RenderType`).
+    pub tags: IndexMap<String, String>,
+    pub disabled_shader_passes: Vec<String>,
+    pub tex_envs: IndexMap<String, TexEnv>,
+    /// `m_Ints` (2021.1+).
+    pub ints: IndexMap<String, i32>,
+    pub floats: IndexMap<String, f32>,
+    /// RGBA colors (and vectors, which Unity stores the same way).
+    pub colors: IndexMap<String, [f32; 4]>,
+}
+
+impl Material {
+    /// Parse Material from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        let Some(UnityValue::Object(saved)) = properties.get("m_SavedProperties") else {
+            return Err(BinaryError::invalid_data(
+                "Material typetree has no m_SavedProperties",
+            ));
+        };
+        let keywords = match properties.get("m_ValidKeywords") {
+            Some(valid) => parse_keywords(Some(valid)),
+            None => parse_keywords(properties.get("m_ShaderKeywords")),
+        };
+        Ok(Self {
+            name: properties
+                .get("m_Name")
+                .and_then(UnityValue::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            shader: properties
+                .get("m_Shader")
+                .and_then(parse_pptr)
+                .unwrap_or_default(),
+            keywords,
+            invalid_keywords: parse_keywords(properties.get("m_InvalidKeywords")),
+            custom_render_queue: properties
+                .get("m_CustomRenderQueue")
+                .and_then(UnityValue::as_i64)
+                .map_or(-1, |v| v as i32),
+            tags: map_entries(properties.get("stringTagMap"), |v| {
+                v.as_str().map(str::to_string)
+            }),
+            disabled_shader_passes: parse_keywords(properties.get("disabledShaderPasses")),
+            tex_envs: map_entries(saved.get("m_TexEnvs"), |v| {
+                let env = v.as_object()?;
+                Some(TexEnv {
+                    texture: env.get("m_Texture").and_then(parse_pptr),
+                    scale: vector2(env.get("m_Scale")).unwrap_or([1.0, 1.0]),
+                    offset: vector2(env.get("m_Offset")).unwrap_or_default(),
+                })
+            }),
+            ints: map_entries(saved.get("m_Ints"), |v| v.as_i64().map(|i| i as i32)),
+            floats: map_entries(saved.get("m_Floats"), |v| v.as_f64().map(|f| f as f32)),
+            colors: map_entries(saved.get("m_Colors"), color),
+        })
+    }
+
+    /// Parse Material from an object's raw bytes (no TypeTree)
+    ///
+    /// PPtrs are read as 12 bytes from Unity 5 on (serialized format 14+) and 8 bytes before.
+    pub fn from_binary_data(
+        data: &[u8],
+        byte_order: ByteOrder,
+        version: &UnityVersion,
+    ) -> Result<Self> {
+        let mut reader = BinaryReader::new(data, byte_order);
+        let mut material = Material {
+            name: reader.read_aligned_string()?,
+            shader: read_pptr(&mut reader, version)?,
+            custom_render_queue: -1,
+            ..Default::default()
+        };
+
+        if version.major == 4 && version.minor >= 1 {
+            material.keywords = read_string_array(&mut reader)?;
+        } else if version.at_least(2021, 3) {
+            material.keywords = read_string_array(&mut reader)?;
+            material.invalid_keywords = read_string_array(&mut reader)?;
+        } else if version.major >= 5 {
+            let keywords = reader.read_aligned_string()?;
+            material.keywords = keywords.split_whitespace().map(str::to_string).collect();
+        }
+        if version.major >= 5 {
+            let _lightmap_flags = reader.read_u32()?;
+        }
+        if version.at_least(5, 6) {
+            let _enable_instancing_variants = reader.read_bool()?;
+            if version.major >= 2017 {
+                let _double_sided_gi = reader.read_bool()?;
+            }
+            reader.align()?;
+        }
+        if version.at_least(4, 3) {
+            material.custom_render_queue = reader.read_i32()?;
+        }
+        if version.at_least(5, 1) {
+            for _ in 0..read_count(&mut reader, 8)? {
+                let key = reader.read_aligned_string()?;
+                material.tags.insert(key, reader.read_aligned_string()?);
+            }
+        }
+        if version.at_least(5, 6) {
+            material.disabled_shader_passes = read_string_array(&mut reader)?;
+        }
+
+        for _ in 0..read_count(&mut reader, 4)? {
+            let key = reader.read_aligned_string()?;
+            let texture = read_pptr(&mut reader, version)?;
+            let env = TexEnv {
+                texture: (!texture.is_null()).then_some(texture),
+                scale: [reader.read_f32()?, reader.read_f32()?],
+                offset: [reader.read_f32()?, reader.read_f32()?],
+            };
+            material.tex_envs.insert(key, env);
+        }
+        if version.major >= 2021 {
+            for _ in 0..read_count(&mut reader, 8)? {
+                let key = reader.read_aligned_string()?;
+                material.ints.insert(key, reader.read_i32()?);
+            }
+        }
+        for _ in 0..read_count(&mut reader, 8)? {
+            let key = reader.read_aligned_string()?;
+            material.floats.insert(key, reader.read_f32()?);
+        }
+        for _ in 0..read_count(&mut reader, 20)? {
+            let key = reader.read_aligned_string()?;
+            let rgba = [
+                reader.read_f32()?,
+                reader.read_f32()?,
+                reader.read_f32()?,
+                reader.read_f32()?,
+            ];
+            material.colors.insert(key, rgba);
+        }
+        Ok(material)
+    }
+
+    /// Assigned textures by property name (`_MainTex`, `_BumpMap`, ...)
+    pub fn textures(&self) -> impl Iterator<Item = (&str, ObjectRef)> {
+        self.tex_envs
+            .iter()
+            .filter_map(|(name, env)| Some((name.as_str(), env.texture?)))
+    }
+}
+
+/// Every Material in `asset` that parses, with its path id
+pub fn materials(asset: &SerializedFile) -> Vec<(i64, Material)> {
+    let version = UnityVersion::parse_version(&asset.unity_version).unwrap_or_default();
+    asset
+        .object_handles()
+        .filter(|h| h.class_id() == class_ids::MATERIAL)
+        .filter_map(|handle| {
+            let material = handle.read().ok()?.as_material(&version).ok()?;
+            Some((handle.path_id(), material))
+        })
+        .collect()
+}
+
+/// Entries of a `map<string, T>` whose values `value` converts; keys may be `FastPropertyName`s.
+fn map_entries<T>(
+    map: Option<&UnityValue>,
+    value: impl Fn(&UnityValue) -> Option<T>,
+) -> IndexMap<String, T> {
+    let Some(UnityValue::Array(pairs)) = map else {
+        return IndexMap::new();
+    };
+    pairs
+        .iter()
+        .filter_map(|pair| {
+            let (key, v) = match pair {
+                UnityValue::Array(kv) if kv.len() == 2 => (&kv[0], &kv[1]),
+                UnityValue::Object(obj) => (obj.get("first")?, obj.get("second")?),
+                _ => return None,
+            };
+            let key = match key {
+                UnityValue::String(key) => key.as_str(),
+                UnityValue::Object(name) => name.get("name")?.as_str()?,
+                _ => return None,
+            };
+            Some((key.to_string(), value(v)?))
+        })
+        .collect()
+}
+
+fn vector2(value: Option<&UnityValue>) -> Option<[f32; 2]> {
+    let fields = value?.as_object()?;
+    let axis = |key: &str| fields.get(key).and_then(UnityValue::as_f64);
+    Some([axis("x")? as f32, axis("y")? as f32])
+}
+
+fn color(value: &UnityValue) -> Option<[f32; 4]> {
+    let fields = value.as_object()?;
+    let channel = |key: &str| fields.get(key).and_then(UnityValue::as_f64);
+    Some([
+        channel("r")? as f32,
+        channel("g")? as f32,
+        channel("b")? as f32,
+        channel("a")? as f32,
+    ])
+}
+
+fn read_pptr(reader: &mut BinaryReader, version: &UnityVersion) -> Result<ObjectRef> {
+    let file_id = reader.read_i32()?;
+    let path_id = if version.major >= 5 {
+        reader.read_i64()?
+    } else {
+        reader.read_i32()? as i64
+    };
+    Ok(ObjectRef::new(file_id, path_id))
+}
+
+fn read_string_array(reader: &mut BinaryReader) -> Result<Vec<String>> {
+    (0..read_count(reader, 4)?)
+        .map(|_| reader.read_aligned_string())
+        .collect()
+}
+
+/// An array length, checked against the bytes left for elements of at least `element_size`.
+fn read_count(reader: &mut BinaryReader, element_size: usize) -> Result<usize> {
+    let count = reader.read_i32()?;
+    usize::try_from(count)
+        .ok()
+        .filter(|&n| n.saturating_mul(element_size) <= reader.remaining())
+        .ok_or_else(|| {
+            BinaryError::invalid_data(format!(
+                "Material array of {} elements does not fit in {} bytes",
+                count,
+                reader.remaining()
+            ))
+        })
+}
@@ -5,11 +5,14 @@
 
 use super::types::*;
 use crate::asset::SerializedFile;
+use crate::builtin_resources::BuiltinReference;
 use crate::error::Result;
 use crate::reader::BinaryReader;
-use crate::typetree::{TypeTree, TypeTreeSerializer};
+use crate::string_policy::StringPolicy;
+use crate::typetree::{TypeTree, TypeTreeParseMode, TypeTreeParseOptions, TypeTreeSerializer};
 use std::collections::{HashMap, HashSet};
 use unity_asset_core::UnityValue;
+use unity_asset_core::tag_manager::{TagManager, builtin_layer_name};
 
 /// Dependency analyzer for Unity assets
 ///
@@ -140,12 +143,16 @@ impl DependencyAnalyzer {
                 referenced_by.sort_unstable();
                 referenced_by.dedup();
                 let (file_path, guid) = resolve_external_file(asset, file_id);
+                let builtin = file_path.as_deref().and_then(|p| {
+                    BuiltinReference::from_external_path(p, path_id).map(|b| b.to_string())
+                });
                 ExternalReference {
                     file_id,
                     path_id,
                     referenced_by,
                     file_path,
                     guid,
+                    builtin,
                 }
             })
             .collect();
@@ -362,11 +369,18 @@ fn parse_object_with_typetree(
     let bytes = asset.object_bytes(info)?;
     let mut reader = BinaryReader::new(bytes, asset.header.byte_order());
     let serializer = TypeTreeSerializer::new(tree);
-    if asset.ref_types.is_empty() {
-        serializer.parse_object(&mut reader)
+    // Lenient: a single malformed field (or a non-UTF-8 name) should not drop the object from
+    // statistics/hierarchy.
+    let options = TypeTreeParseOptions {
+        mode: TypeTreeParseMode::Lenient,
+        strings: StringPolicy::Utf8Lossy,
+    };
+    let out = if asset.ref_types.is_empty() {
+        serializer.parse_object_detailed(&mut reader, options)?
     } else {
-        serializer.parse_object_with_ref_types(&mut reader, &asset.ref_types)
-    }
+        serializer.parse_object_detailed_with_ref_types(&mut reader, options, &asset.ref_types)?
+    };
+    Ok(out.properties)
 }
 
 fn scan_object_pptrs_with_typetree(
@@ -549,6 +563,8 @@ fn get_i64_ci(map: &indexmap::IndexMap<String, UnityValue>, keys: &[&str]) -> Op
 pub struct RelationshipAnalyzer {
     /// Cache for GameObject hierarchies
     hierarchy_cache: HashMap<i64, GameObjectHierarchy>,
+    /// Layer names for `GameObjectHierarchy::layer_name`
+    tag_manager: Option<TagManager>,
 }
 
 impl RelationshipAnalyzer {
@@ -556,9 +572,23 @@ impl RelationshipAnalyzer {
     pub fn new() -> Self {
         Self {
             hierarchy_cache: HashMap::new(),
+            tag_manager: None,
         }
     }
 
+    /// Name layers with the project's TagManager instead of only the builtin layer names.
+    pub fn set_tag_manager(&mut self, tag_manager: Option<TagManager>) {
+        self.tag_manager = tag_manager;
+    }
+
+    fn layer_name(&self, layer: i32) -> Option<String> {
+        let name = match &self.tag_manager {
+            Some(manager) => manager.layer_name(layer),
+            None => builtin_layer_name(layer),
+        };
+        name.map(str::to_string)
+    }
+
     /// Analyze relationships for a set of objects
     pub fn analyze_relationships(
         &mut self,
@@ -595,6 +625,8 @@ impl RelationshipAnalyzer {
                 transform_id: 0,
                 components: Vec::new(),
                 depth: 0,
+                layer: 0,
+                layer_name: None,
             };
             gameobject_hierarchy.push(hierarchy);
         }
@@ -654,7 +686,7 @@ impl RelationshipAnalyzer {
                         gameobject_props.insert(obj.path_id, values);
                     }
                 }
-                class_ids::TRANSFORM => {
+                class_ids::TRANSFORM | class_ids::RECT_TRANSFORM => {
                     if let Some(tree) = type_tree_for_object(asset, obj)
                         && !tree.is_empty()
                         && let Ok(values) = parse_object_with_typetree(asset, obj, tree)
@@ -668,6 +700,7 @@ impl RelationshipAnalyzer {
 
         // Parse GameObject -> components
         let mut go_name: HashMap<i64, String> = HashMap::new();
+        let mut go_layer: HashMap<i64, i32> = HashMap::new();
         let mut go_components: HashMap<i64, Vec<i64>> = HashMap::new();
         let mut go_transform: HashMap<i64, i64> = HashMap::new();
 
@@ -680,15 +713,21 @@ impl RelationshipAnalyzer {
                 })
                 .unwrap_or_else(|| format!("GameObject_{}", go_id));
             go_name.insert(*go_id, name);
+            if let Some(layer) = props.get("m_Layer").and_then(UnityValue::as_i64) {
+                go_layer.insert(*go_id, layer as i32);
+            }
 
             let components = extract_gameobject_components(props);
             if !components.is_empty() {
                 go_components.insert(*go_id, components.clone());
 
-                // Heuristic: the Transform component (class_id=4) is the GameObject's Transform.
+                // Heuristic: the Transform (or RectTransform) component is the GameObject's Transform.
                 for component_id in components {
                     if let Some(info) = by_path_id.get(&component_id)
-                        && info.type_id == class_ids::TRANSFORM
+                        && matches!(
+                            info.type_id,
+                            class_ids::TRANSFORM | class_ids::RECT_TRANSFORM
+                        )
                     {
                         go_transform.insert(*go_id, component_id);
                         break;
@@ -750,6 +789,7 @@ impl RelationshipAnalyzer {
             comps.sort_unstable();
             comps.dedup();
 
+            let layer = go_layer.get(go_id).copied().unwrap_or(0);
             hierarchies.insert(
                 *go_id,
                 GameObjectHierarchy {
@@ -763,6 +803,8 @@ impl RelationshipAnalyzer {
                     transform_id,
                     components: comps,
                     depth: 0,
+                    layer,
+                    layer_name: self.layer_name(layer),
                 },
             );
         }
@@ -841,6 +883,8 @@ impl RelationshipAnalyzer {
             transform_id: 0, // TODO: Find associated Transform
             components: Vec::new(),
             depth: 0,
+            layer: 0,
+            layer_name: None,
         })
     }
 
@@ -922,6 +966,19 @@ mod tests {
         assert!(analyzer.hierarchy_cache.is_empty());
     }
 
+    #[test]
+    fn test_layer_names_use_tag_manager_or_builtin_layers() {
+        let mut analyzer = RelationshipAnalyzer::new();
+        assert_eq!(analyzer.layer_name(5).as_deref(), Some("UI"));
+        assert_eq!(analyzer.layer_name(8), None);
+
+        let mut manager = TagManager::default();
+        manager.layers[8] = "Ground".to_string();
+        analyzer.set_tag_manager(Some(manager));
+        assert_eq!(analyzer.layer_name(8).as_deref(), Some("Ground"));
+        assert_eq!(analyzer.layer_name(0).as_deref(), Some("Default"));
+    }
+
     #[test]
     fn test_root_leaf_detection() {
         let analyzer = DependencyAnalyzer::new();
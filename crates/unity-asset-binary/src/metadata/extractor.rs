@@ -55,6 +55,7 @@ impl MetadataExtractor {
                 max_objects,
                 include_performance,
                 include_object_details: true,
+                tag_manager: None,
             },
             dependency_analyzer: Mutex::new(DependencyAnalyzer::new()),
             relationship_analyzer: Mutex::new(RelationshipAnalyzer::new()),
@@ -119,9 +120,6 @@ impl MetadataExtractor {
 
     /// Extract metadata from a SerializedFile
     pub fn extract_from_asset(&self, asset: &SerializedFile) -> Result<ExtractionResult> {
-        let start_time = Instant::now();
-        let mut result = ExtractionResult::new(AssetMetadata::new());
-
         // Get objects to analyze
         let objects_to_analyze: Vec<&crate::asset::ObjectInfo> =
             if let Some(max) = self.config.max_objects {
@@ -129,12 +127,26 @@ impl MetadataExtractor {
             } else {
                 asset.objects.iter().collect()
             };
+        self.extract_from_objects(asset, &objects_to_analyze)
+    }
+
+    /// Extract metadata from a subset of a SerializedFile's objects
+    ///
+    /// File info and performance metrics still describe the whole file; object statistics,
+    /// dependencies and relationships only cover `objects_to_analyze`.
+    pub fn extract_from_objects(
+        &self,
+        asset: &SerializedFile,
+        objects_to_analyze: &[&crate::asset::ObjectInfo],
+    ) -> Result<ExtractionResult> {
+        let start_time = Instant::now();
+        let mut result = ExtractionResult::new(AssetMetadata::new());
 
         // Extract basic file info
         result.metadata.file_info = self.extract_file_info(asset);
 
         // Extract object statistics
-        result.metadata.object_stats = self.extract_object_statistics(&objects_to_analyze);
+        result.metadata.object_stats = self.extract_object_statistics(objects_to_analyze);
 
         let mut dependencies: Option<DependencyInfo> = None;
 
@@ -142,11 +154,11 @@ impl MetadataExtractor {
         if self.config.include_dependencies {
             let analyzed = match self.dependency_analyzer.lock() {
                 Ok(mut analyzer) => {
-                    analyzer.analyze_dependencies_in_asset(asset, &objects_to_analyze)
+                    analyzer.analyze_dependencies_in_asset(asset, objects_to_analyze)
                 }
                 Err(e) => e
                     .into_inner()
-                    .analyze_dependencies_in_asset(asset, &objects_to_analyze),
+                    .analyze_dependencies_in_asset(asset, objects_to_analyze),
             };
 
             match analyzed {
@@ -162,14 +174,13 @@ impl MetadataExtractor {
 
         // Extract relationships if enabled
         if self.config.include_hierarchy {
-            let analyzed = match self.relationship_analyzer.lock() {
-                Ok(mut analyzer) => {
-                    analyzer.analyze_relationships_in_asset(asset, &objects_to_analyze)
-                }
-                Err(e) => e
-                    .into_inner()
-                    .analyze_relationships_in_asset(asset, &objects_to_analyze),
+            let mut analyzer = match self.relationship_analyzer.lock() {
+                Ok(analyzer) => analyzer,
+                Err(e) => e.into_inner(),
             };
+            analyzer.set_tag_manager(self.config.tag_manager.clone());
+            let analyzed = analyzer.analyze_relationships_in_asset(asset, objects_to_analyze);
+            drop(analyzer);
 
             match analyzed {
                 Ok(mut rels) => {
@@ -17,13 +17,12 @@
 //! use unity_asset_binary::asset::{SerializedFile, SerializedFileHeader};
 //!
 //! // Create extractor with custom configuration
-//! let config = ExtractionConfig {
-//!     include_dependencies: true,
-//!     include_hierarchy: true,
-//!     max_objects: Some(1000),
-//!     include_performance: true,
-//!     include_object_details: true,
-//! };
+//! let config = ExtractionConfig::default()
+//!     .with_include_dependencies(true)
+//!     .with_include_hierarchy(true)
+//!     .with_max_objects(Some(1000))
+//!     .with_include_performance(true)
+//!     .with_include_object_details(true);
 //! let extractor = MetadataExtractor::with_config(config);
 //!
 //! // Note: In real usage, you would load a SerializedFile from actual data
@@ -69,15 +68,21 @@ pub use types::{
 
 use crate::asset::SerializedFile;
 use crate::bundle::AssetBundle;
+use crate::hooks::{HookRegistry, ObjectHook};
 
 /// Main metadata processing facade
 ///
 /// This struct provides a high-level interface for metadata processing,
 /// combining extraction and analysis functionality.
+///
+/// Registered [`ObjectHook`]s run over every object before extraction. Objects a hook skips are
+/// left out of object statistics, dependencies and relationships; replacements and emitted
+/// artifacts are reported in [`ExtractionResult::hooks`].
 pub struct MetadataProcessor {
     extractor: MetadataExtractor,
     dependency_analyzer: Option<DependencyAnalyzer>,
     relationship_analyzer: Option<RelationshipAnalyzer>,
+    hooks: HookRegistry,
 }
 
 fn apply_dependency_info_to_relationships(
@@ -194,6 +199,7 @@ impl MetadataProcessor {
             extractor: MetadataExtractor::new(),
             dependency_analyzer: None,
             relationship_analyzer: None,
+            hooks: HookRegistry::new(),
         }
     }
 
@@ -213,15 +219,50 @@ impl MetadataProcessor {
             } else {
                 None
             },
+            hooks: HookRegistry::new(),
         }
     }
 
+    /// Register a per-object hook; hooks run in registration order
+    pub fn register_hook<H: ObjectHook + 'static>(&mut self, hook: H) -> &mut Self {
+        self.hooks.register(hook);
+        self
+    }
+
+    /// Get mutable access to the registered hooks
+    pub fn hooks_mut(&mut self) -> &mut HookRegistry {
+        &mut self.hooks
+    }
+
     /// Process metadata from a SerializedFile
     pub fn process_asset(
         &mut self,
         asset: &SerializedFile,
     ) -> crate::error::Result<ExtractionResult> {
-        let mut result = self.extractor.extract_from_asset(asset)?;
+        self.process_named_asset(asset, None)
+    }
+
+    fn process_named_asset(
+        &mut self,
+        asset: &SerializedFile,
+        asset_name: Option<&str>,
+    ) -> crate::error::Result<ExtractionResult> {
+        let hook_report = (!self.hooks.is_empty()).then(|| self.hooks.run_file(asset, asset_name));
+        let skipped: std::collections::HashSet<i64> = hook_report
+            .iter()
+            .flat_map(|r| r.skipped.iter().map(|o| o.path_id))
+            .collect();
+        let objects: Vec<&crate::asset::ObjectInfo> = asset
+            .objects
+            .iter()
+            .filter(|o| !skipped.contains(&o.path_id))
+            .take(self.extractor.config().max_objects.unwrap_or(usize::MAX))
+            .collect();
+
+        let mut result = self.extractor.extract_from_objects(asset, &objects)?;
+        if let Some(report) = hook_report {
+            result.hooks = report;
+        }
 
         // Enhanced dependency analysis if analyzer is available
         if let Some(ref mut analyzer) = self.dependency_analyzer
@@ -233,13 +274,6 @@ impl MetadataProcessor {
                 .nodes
                 .is_empty()
         {
-            let objects: Vec<&crate::asset::ObjectInfo> =
-                if let Some(max) = self.extractor.config().max_objects {
-                    asset.objects.iter().take(max).collect()
-                } else {
-                    asset.objects.iter().collect()
-                };
-
             match analyzer.analyze_dependencies_in_asset(asset, &objects) {
                 Ok(deps) => {
                     if self.extractor.config().include_object_details {
@@ -280,13 +314,7 @@ impl MetadataProcessor {
                 .is_empty()
             && result.metadata.relationships.asset_references.is_empty()
         {
-            let objects: Vec<&crate::asset::ObjectInfo> =
-                if let Some(max) = self.extractor.config().max_objects {
-                    asset.objects.iter().take(max).collect()
-                } else {
-                    asset.objects.iter().collect()
-                };
-
+            analyzer.set_tag_manager(self.extractor.config().tag_manager.clone());
             match analyzer.analyze_relationships_in_asset(asset, &objects) {
                 Ok(mut rels) => {
                     if self.extractor.config().include_dependencies {
@@ -313,8 +341,10 @@ impl MetadataProcessor {
     ) -> crate::error::Result<Vec<ExtractionResult>> {
         let mut results = Vec::new();
 
-        for asset in &bundle.assets {
-            let result = self.process_asset(asset)?;
+        let names = bundle.unique_asset_names();
+        for (index, asset) in bundle.assets.iter().enumerate() {
+            let name = names.get(index).map(String::as_str);
+            let result = self.process_named_asset(asset, name)?;
             results.push(result);
         }
 
@@ -379,6 +409,7 @@ pub fn create_performance_processor() -> MetadataProcessor {
         max_objects: Some(1000),
         include_performance: true,
         include_object_details: false,
+        tag_manager: None,
     };
     MetadataProcessor::with_config(config)
 }
@@ -391,6 +422,7 @@ pub fn create_comprehensive_processor() -> MetadataProcessor {
         max_objects: None,
         include_performance: true,
         include_object_details: true,
+        tag_manager: None,
     };
     MetadataProcessor::with_config(config)
 }
@@ -425,6 +457,7 @@ pub fn get_asset_statistics(asset: &SerializedFile) -> AssetStatistics {
 
 /// Quick asset statistics
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct AssetStatistics {
     pub object_count: usize,
     pub type_count: usize,
@@ -436,6 +469,7 @@ pub struct AssetStatistics {
 
 /// Metadata processing options
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ProcessingOptions {
     pub enable_caching: bool,
     pub max_cache_size: usize,
@@ -443,6 +477,32 @@ pub struct ProcessingOptions {
     pub memory_limit_mb: Option<usize>,
 }
 
+impl ProcessingOptions {
+    /// Set `enable_caching`.
+    pub fn with_enable_caching(mut self, enable_caching: bool) -> Self {
+        self.enable_caching = enable_caching;
+        self
+    }
+
+    /// Set `max_cache_size`.
+    pub fn with_max_cache_size(mut self, max_cache_size: usize) -> Self {
+        self.max_cache_size = max_cache_size;
+        self
+    }
+
+    /// Set `parallel_processing`.
+    pub fn with_parallel_processing(mut self, parallel_processing: bool) -> Self {
+        self.parallel_processing = parallel_processing;
+        self
+    }
+
+    /// Set `memory_limit_mb`.
+    pub fn with_memory_limit_mb(mut self, memory_limit_mb: Option<usize>) -> Self {
+        self.memory_limit_mb = memory_limit_mb;
+        self
+    }
+}
+
 impl Default for ProcessingOptions {
     fn default() -> Self {
         Self {
@@ -472,6 +532,7 @@ pub fn get_recommended_config(asset: &SerializedFile) -> ExtractionConfig {
             max_objects: Some(5000),
             include_performance: true,
             include_object_details: false,
+            tag_manager: None,
         }
     } else if object_count > 1000 {
         // Medium asset - balanced
@@ -481,6 +542,7 @@ pub fn get_recommended_config(asset: &SerializedFile) -> ExtractionConfig {
             max_objects: Some(2000),
             include_performance: true,
             include_object_details: true,
+            tag_manager: None,
         }
     } else {
         // Small asset - comprehensive
@@ -501,6 +563,7 @@ mod processor_tests {
                 referenced_by: vec![11],
                 file_path: Some("library/external.assets".to_string()),
                 guid: Some([7u8; 16]),
+                builtin: None,
             }],
             internal_references: vec![
                 InternalReference {
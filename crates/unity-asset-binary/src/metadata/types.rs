@@ -32,6 +32,7 @@ pub struct FileInfo {
 
 /// Object statistics within the asset
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[non_exhaustive]
 pub struct ObjectStatistics {
     pub total_objects: usize,
     pub objects_by_type: HashMap<String, usize>,
@@ -88,6 +89,9 @@ pub struct ExternalReference {
     pub file_path: Option<String>,
     /// Best-effort resolved external file GUID (from `SerializedFile.externals`)
     pub guid: Option<[u8; 16]>,
+    /// Label for references into Unity's built-in resource files (e.g. `builtin:Default-Material`)
+    #[serde(default)]
+    pub builtin: Option<String>,
 }
 
 /// Internal object reference
@@ -125,6 +129,12 @@ pub struct GameObjectHierarchy {
     pub transform_id: i64,
     pub components: Vec<i64>,
     pub depth: u32,
+    /// `m_Layer` (0 when the GameObject could not be parsed).
+    #[serde(default)]
+    pub layer: i32,
+    /// Layer name from the project's TagManager, or the builtin name of an engine layer.
+    #[serde(default)]
+    pub layer_name: Option<String>,
 }
 
 /// Component relationship information
@@ -166,6 +176,7 @@ pub struct PerformanceMetrics {
 
 /// Metadata extraction configuration
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ExtractionConfig {
     /// Whether to include dependency analysis
     pub include_dependencies: bool,
@@ -177,6 +188,46 @@ pub struct ExtractionConfig {
     pub include_performance: bool,
     /// Whether to extract detailed object summaries
     pub include_object_details: bool,
+    /// Project TagManager used to name GameObject layers in the hierarchy
+    pub tag_manager: Option<unity_asset_core::TagManager>,
+}
+
+impl ExtractionConfig {
+    /// Set `include_dependencies`.
+    pub fn with_include_dependencies(mut self, include_dependencies: bool) -> Self {
+        self.include_dependencies = include_dependencies;
+        self
+    }
+
+    /// Set `include_hierarchy`.
+    pub fn with_include_hierarchy(mut self, include_hierarchy: bool) -> Self {
+        self.include_hierarchy = include_hierarchy;
+        self
+    }
+
+    /// Set `max_objects`.
+    pub fn with_max_objects(mut self, max_objects: Option<usize>) -> Self {
+        self.max_objects = max_objects;
+        self
+    }
+
+    /// Set `include_performance`.
+    pub fn with_include_performance(mut self, include_performance: bool) -> Self {
+        self.include_performance = include_performance;
+        self
+    }
+
+    /// Set `include_object_details`.
+    pub fn with_include_object_details(mut self, include_object_details: bool) -> Self {
+        self.include_object_details = include_object_details;
+        self
+    }
+
+    /// Set `tag_manager`.
+    pub fn with_tag_manager(mut self, tag_manager: Option<unity_asset_core::TagManager>) -> Self {
+        self.tag_manager = tag_manager;
+        self
+    }
 }
 
 impl Default for ExtractionConfig {
@@ -187,6 +238,7 @@ impl Default for ExtractionConfig {
             max_objects: None,
             include_performance: true,
             include_object_details: true,
+            tag_manager: None,
         }
     }
 }
@@ -197,6 +249,8 @@ pub struct ExtractionResult {
     pub metadata: AssetMetadata,
     pub warnings: Vec<String>,
     pub errors: Vec<String>,
+    /// What registered hooks did (see [`MetadataProcessor::register_hook`](super::MetadataProcessor::register_hook))
+    pub hooks: crate::hooks::HookReport,
 }
 
 impl ExtractionResult {
@@ -205,6 +259,7 @@ impl ExtractionResult {
             metadata,
             warnings: Vec::new(),
             errors: Vec::new(),
+            hooks: crate::hooks::HookReport::default(),
         }
     }
 
@@ -227,6 +282,7 @@ impl ExtractionResult {
 
 /// Statistics about the extraction process
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ExtractionStats {
     pub objects_processed: usize,
     pub dependencies_found: usize,
@@ -1,15 +1,32 @@
 //! Unity object representation and helpers.
 
+use crate::animator::AnimatorController;
 use crate::asset::{ObjectInfo, SerializedFile, SerializedType};
+use crate::asset_bundle_manifest::AssetBundleManifestObject;
+use crate::audio_mixer::{AudioMixerController, AudioMixerGroupController};
+use crate::avatar::Avatar;
+use crate::build_settings::BuildSettings;
+use crate::bytes_preview::{BytesPreview, LenPreview};
 use crate::error::{BinaryError, Result};
+use crate::lighting::{LightingDataAsset, LightmapSettings};
+use crate::material::Material;
 use crate::reader::{BinaryReader, ByteOrder};
 use crate::shared_bytes::SharedBytes;
+use crate::string_policy::RawString;
+use crate::text_asset::TextAsset;
+use crate::tilemap::{Grid, Tilemap};
 use crate::typetree::{
-    PPtrScanResult, TypeTree, TypeTreeParseMode, TypeTreeParseOptions, TypeTreeParseOutput,
-    TypeTreeParseWarning, TypeTreeSerializationMode, TypeTreeSerializer,
+    PPtrField, PPtrScanResult, StringField, TypeTree, TypeTreeParseMode, TypeTreeParseOptions,
+    TypeTreeParseOutput, TypeTreeParseWarning, TypeTreeSerializationMode, TypeTreeSerializer,
 };
-use crate::unity_objects::{GameObject, Transform};
+use crate::unity_objects::{
+    Camera, GameObject, GraphicsSettings, Light, Shader, ShaderVariantCollection, Transform,
+};
+use crate::unity_version::UnityVersion;
+use indexmap::IndexMap;
+use std::fmt;
 use std::sync::Arc;
+use unity_asset_core::class_ids;
 use unity_asset_core::{UnityClass, UnityValue};
 
 /// A lightweight reference to a binary object within a [`SerializedFile`].
@@ -68,6 +85,39 @@ impl<'a> ObjectHandle<'a> {
         UnityObject::from_serialized_file_with_options(self.file, self.info, options)
     }
 
+    /// Parse this object's TypeTree into a [`UnityClass`].
+    ///
+    /// Unlike [`ObjectHandle::read`], this never falls back to a raw preview: a missing TypeTree
+    /// (stripped builds without a registry) or data that does not parse strictly is an error.
+    /// Classes without a known ID are named after the TypeTree's root type.
+    pub fn read_typed(&self) -> Result<UnityClass> {
+        let Some(tree) = type_tree_for_object(self.file, self.info) else {
+            return Err(BinaryError::invalid_data(format!(
+                "Object {} (class {}) has no TypeTree",
+                self.path_id(),
+                self.class_id()
+            )));
+        };
+        let tree = tree.as_ref();
+        let options = TypeTreeParseOptions::default().with_mode(TypeTreeParseMode::Strict);
+        let out = parse_object_data(
+            self.file,
+            self.info,
+            self.file.header.byte_order(),
+            tree,
+            options,
+        )
+        .map_err(|e| BinaryError::parse_error(format!("Object {}: {}", self.path_id(), e)))?;
+
+        let class_name = unity_asset_core::get_class_name(self.class_id())
+            .or_else(|| tree.nodes.first().map(|root| root.type_name.clone()))
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| class_name_from_id(self.class_id()));
+        let mut class = UnityClass::new(self.class_id(), class_name, self.path_id().to_string());
+        class.update_properties(out.properties);
+        Ok(class)
+    }
+
     /// Peek the object's name (`m_Name`/`name`) without parsing the full TypeTree.
     ///
     /// This mirrors UnityPy's `ObjectReader.peek_name()` behavior by parsing only a prefix of the
@@ -75,10 +125,20 @@ impl<'a> ObjectHandle<'a> {
     pub fn peek_name(&self) -> Result<Option<String>> {
         self.peek_name_with_options(TypeTreeParseOptions {
             mode: TypeTreeParseMode::Lenient,
+            ..Default::default()
         })
     }
 
     pub fn peek_name_with_options(&self, options: TypeTreeParseOptions) -> Result<Option<String>> {
+        Ok(self.peek_name_with_raw(options)?.map(|(name, _)| name))
+    }
+
+    /// Like [`ObjectHandle::peek_name_with_options`], also returning the name's original bytes
+    /// when `options.strings` had to decode them from something other than UTF-8.
+    pub fn peek_name_with_raw(
+        &self,
+        options: TypeTreeParseOptions,
+    ) -> Result<Option<(String, Option<Vec<u8>>)>> {
         let Some(tree) = type_tree_for_object(self.file, self.info) else {
             return Ok(None);
         };
@@ -93,11 +153,52 @@ impl<'a> ObjectHandle<'a> {
         let out = serializer.parse_object_prefix_detailed(&mut reader, options, prefix_len)?;
 
         match out.properties.get(&field) {
-            Some(UnityValue::String(s)) => Ok(Some(s.clone())),
+            Some(UnityValue::String(s)) => {
+                let raw = out
+                    .raw_strings
+                    .into_iter()
+                    .find(|r| r.path == field)
+                    .map(|r| r.raw);
+                Ok(Some((s.clone(), raw)))
+            }
             _ => Ok(None),
         }
     }
 
+    /// Peek a few root fields without parsing the full TypeTree.
+    ///
+    /// Only the root prefix up to the last requested field is parsed, so this stays cheap for
+    /// fields declared before large payloads (e.g. `m_Width` before `image data`). Returns `None`
+    /// when no TypeTree is available; requested fields missing from the tree are simply absent.
+    pub fn peek_fields(&self, fields: &[&str]) -> Result<Option<IndexMap<String, UnityValue>>> {
+        let Some(tree) = type_tree_for_object(self.file, self.info) else {
+            return Ok(None);
+        };
+        let tree = tree.as_ref();
+        let Some(prefix_len) = tree.fields_peek_prefix(fields) else {
+            return Ok(Some(IndexMap::new()));
+        };
+
+        let bytes = self.raw_data()?;
+        let mut reader = BinaryReader::new(bytes, self.file.header.byte_order());
+        let serializer = TypeTreeSerializer::new(tree);
+        let out = serializer.parse_object_prefix_detailed(
+            &mut reader,
+            TypeTreeParseOptions {
+                mode: TypeTreeParseMode::Lenient,
+                ..Default::default()
+            },
+            prefix_len,
+        )?;
+
+        Ok(Some(
+            out.properties
+                .into_iter()
+                .filter(|(k, _)| fields.contains(&k.as_str()))
+                .collect(),
+        ))
+    }
+
     /// Scan TypeTree-based object bytes and collect `PPtr` references (`fileID`, `pathID`) without
     /// allocating a full parsed `UnityValue` tree.
     pub fn scan_pptrs(&self) -> Result<Option<PPtrScanResult>> {
@@ -121,6 +222,49 @@ impl<'a> ObjectHandle<'a> {
             )?))
         }
     }
+
+    /// Collect the object's `string` fields, cut to `max_len` bytes each, without parsing the
+    /// rest of the TypeTree into values. Returns `None` when no TypeTree is available.
+    pub fn scan_strings(&self, max_len: usize) -> Result<Option<Vec<StringField>>> {
+        let Some(tree) = type_tree_for_object(self.file, self.info) else {
+            return Ok(None);
+        };
+        let tree = tree.as_ref();
+        if tree.is_empty() {
+            return Ok(None);
+        }
+
+        let bytes = self.raw_data()?;
+        let mut reader = BinaryReader::new(bytes, self.file.header.byte_order());
+        let ref_types = (!self.file.ref_types.is_empty()).then_some(self.file.ref_types.as_slice());
+        Ok(Some(
+            TypeTreeSerializer::new(tree).scan_strings_with_ref_types(
+                &mut reader,
+                max_len,
+                ref_types,
+            )?,
+        ))
+    }
+
+    /// Collect the object's `PPtr` fields with their byte ranges in [`Self::raw_data`].
+    /// Returns `None` when no TypeTree is available.
+    pub fn scan_pptr_fields(&self) -> Result<Option<Vec<PPtrField>>> {
+        let Some(tree) = type_tree_for_object(self.file, self.info) else {
+            return Ok(None);
+        };
+        let tree = tree.as_ref();
+        if tree.is_empty() {
+            return Ok(None);
+        }
+
+        let bytes = self.raw_data()?;
+        let mut reader = BinaryReader::new(bytes, self.file.header.byte_order());
+        let ref_types = (!self.file.ref_types.is_empty()).then_some(self.file.ref_types.as_slice());
+        Ok(Some(
+            TypeTreeSerializer::new(tree)
+                .scan_pptr_fields_with_ref_types(&mut reader, ref_types)?,
+        ))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -152,13 +296,15 @@ impl ObjectBytes {
 /// This is an owned wrapper which carries:
 /// - the raw `ObjectInfo` (from `asset` module)
 /// - the parsed `UnityClass` properties (best-effort)
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct UnityObject {
     pub info: ObjectInfo,
     pub class: UnityClass,
     byte_order: ByteOrder,
     raw: ObjectBytes,
     typetree_warnings: Vec<TypeTreeParseWarning>,
+    typetree_partial: bool,
+    raw_strings: Vec<RawString>,
 }
 
 impl UnityObject {
@@ -170,6 +316,8 @@ impl UnityObject {
             class,
             raw: ObjectBytes::Empty,
             typetree_warnings: Vec::new(),
+            typetree_partial: false,
+            raw_strings: Vec::new(),
         }
     }
 
@@ -214,6 +362,8 @@ impl UnityObject {
             byte_order: ByteOrder::Little,
             raw,
             typetree_warnings: Vec::new(),
+            typetree_partial: false,
+            raw_strings: Vec::new(),
         }
     }
 
@@ -230,12 +380,16 @@ impl UnityObject {
         let class_id = info.type_id;
         let type_tree = type_tree_for_object(file, info);
         let byte_order = file.header.byte_order();
-        let (start, end) = object_range(file, info)?;
-        let base = file.data_base_offset();
-        let raw = ObjectBytes::Shared {
-            data: file.data_shared(),
-            start: base + start,
-            end: base + end,
+        let raw = if !info.data.is_empty() {
+            ObjectBytes::Inline(info.data.clone())
+        } else {
+            let (start, end) = object_range(file, info)?;
+            let base = file.data_base_offset();
+            ObjectBytes::Shared {
+                data: file.data_shared(),
+                start: base + start,
+                end: base + end,
+            }
         };
 
         let mut class = UnityClass::new(
@@ -245,21 +399,28 @@ impl UnityObject {
         );
 
         let mut warnings: Vec<TypeTreeParseWarning> = Vec::new();
+        let mut partial = false;
+        let mut raw_strings = Vec::new();
 
         if let Some(tree) = type_tree {
             let tree = tree.as_ref();
+            let mode = options.mode;
             match parse_object_data(file, info, byte_order, tree, options) {
                 Ok(out) => {
                     class.update_properties(out.properties);
                     warnings = out.warnings;
+                    partial = out.partial;
+                    raw_strings = out.raw_strings;
                 }
-                Err(e) => match options.mode {
+                Err(e) => match mode {
                     TypeTreeParseMode::Strict => return Err(e),
                     TypeTreeParseMode::Lenient => {
                         warnings.push(TypeTreeParseWarning {
                             field: "<root>".to_string(),
                             error: e.to_string(),
+                            offset: 0,
                         });
+                        partial = true;
                         apply_raw_preview(&mut class, raw.as_slice());
                     }
                 },
@@ -269,15 +430,20 @@ impl UnityObject {
         }
 
         Ok(Self {
-            info: {
-                let mut cloned = info.clone();
-                cloned.data.clear();
-                cloned
-            },
+            // Bytes live in `raw`; don't copy a preloaded `info.data` buffer just to drop it.
+            info: ObjectInfo::new(
+                info.path_id,
+                info.byte_start,
+                info.byte_size,
+                info.type_id,
+                info.type_index,
+            ),
             class,
             byte_order,
             raw,
             typetree_warnings: warnings,
+            typetree_partial: partial,
+            raw_strings,
         })
     }
 
@@ -344,6 +510,195 @@ impl UnityObject {
         Transform::from_typetree(self.class.properties())
     }
 
+    pub fn as_camera(&self) -> Result<Camera> {
+        if self.class_id() != class_ids::CAMERA {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not a Camera (class_id: {})",
+                self.class_id()
+            )));
+        }
+        Camera::from_typetree(self.class.properties())
+    }
+
+    pub fn as_light(&self) -> Result<Light> {
+        if self.class_id() != class_ids::LIGHT {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not a Light (class_id: {})",
+                self.class_id()
+            )));
+        }
+        Light::from_typetree(self.class.properties())
+    }
+
+    pub fn as_shader(&self) -> Result<Shader> {
+        if self.class_id() != class_ids::SHADER {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not a Shader (class_id: {})",
+                self.class_id()
+            )));
+        }
+        Shader::from_typetree(self.class.properties())
+    }
+
+    pub fn as_shader_variant_collection(&self) -> Result<ShaderVariantCollection> {
+        if self.class_id() != class_ids::SHADER_VARIANT_COLLECTION {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not a ShaderVariantCollection (class_id: {})",
+                self.class_id()
+            )));
+        }
+        ShaderVariantCollection::from_typetree(self.class.properties())
+    }
+
+    pub fn as_avatar(&self) -> Result<Avatar> {
+        if self.class_id() != class_ids::AVATAR {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not an Avatar (class_id: {})",
+                self.class_id()
+            )));
+        }
+        Avatar::from_typetree(self.class.properties())
+    }
+
+    pub fn as_animator_controller(&self) -> Result<AnimatorController> {
+        if self.class_id() != class_ids::ANIMATOR_CONTROLLER {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not an AnimatorController (class_id: {})",
+                self.class_id()
+            )));
+        }
+        AnimatorController::from_typetree(self.class.properties())
+    }
+
+    pub fn as_build_settings(&self) -> Result<BuildSettings> {
+        if self.class_id() != class_ids::BUILD_SETTINGS {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not BuildSettings (class_id: {})",
+                self.class_id()
+            )));
+        }
+        BuildSettings::from_typetree(self.class.properties())
+    }
+
+    pub fn as_asset_bundle_manifest(&self) -> Result<AssetBundleManifestObject> {
+        if self.class_id() != class_ids::ASSET_BUNDLE_MANIFEST {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not an AssetBundleManifest (class_id: {})",
+                self.class_id()
+            )));
+        }
+        AssetBundleManifestObject::from_typetree(self.class.properties())
+    }
+
+    pub fn as_lightmap_settings(&self) -> Result<LightmapSettings> {
+        if self.class_id() != class_ids::LIGHTMAP_SETTINGS {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not LightmapSettings (class_id: {})",
+                self.class_id()
+            )));
+        }
+        LightmapSettings::from_typetree(self.class.properties())
+    }
+
+    pub fn as_lighting_data_asset(&self) -> Result<LightingDataAsset> {
+        if self.class_id() != class_ids::LIGHTING_DATA_ASSET {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not a LightingDataAsset (class_id: {})",
+                self.class_id()
+            )));
+        }
+        LightingDataAsset::from_typetree(self.class.properties())
+    }
+
+    pub fn as_tilemap(&self) -> Result<Tilemap> {
+        if self.class_id() != class_ids::TILEMAP {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not a Tilemap (class_id: {})",
+                self.class_id()
+            )));
+        }
+        Tilemap::from_typetree(self.class.properties())
+    }
+
+    pub fn as_grid(&self) -> Result<Grid> {
+        if self.class_id() != class_ids::GRID {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not a Grid (class_id: {})",
+                self.class_id()
+            )));
+        }
+        Grid::from_typetree(self.class.properties())
+    }
+
+    pub fn as_audio_mixer(&self) -> Result<AudioMixerController> {
+        if self.class_id() != class_ids::AUDIO_MIXER_CONTROLLER {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not an AudioMixerController (class_id: {})",
+                self.class_id()
+            )));
+        }
+        AudioMixerController::from_typetree(self.class.properties())
+    }
+
+    pub fn as_audio_mixer_group(&self) -> Result<AudioMixerGroupController> {
+        if self.class_id() != class_ids::AUDIO_MIXER_GROUP_CONTROLLER {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not an AudioMixerGroupController (class_id: {})",
+                self.class_id()
+            )));
+        }
+        AudioMixerGroupController::from_typetree(self.class.properties())
+    }
+
+    pub fn as_graphics_settings(&self) -> Result<GraphicsSettings> {
+        if self.class_id() != class_ids::GRAPHICS_SETTINGS {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not GraphicsSettings (class_id: {})",
+                self.class_id()
+            )));
+        }
+        GraphicsSettings::from_typetree(self.class.properties())
+    }
+
+    /// Read a TextAsset, from the TypeTree when the object has one and from the raw bytes
+    /// (laid out for `version`) otherwise. `m_Script` keeps its original bytes either way.
+    pub fn as_text_asset(&self, version: &UnityVersion) -> Result<TextAsset> {
+        if self.class_id() != class_ids::TEXT_ASSET {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not a TextAsset (class_id: {})",
+                self.class_id()
+            )));
+        }
+        if !self.has_property("m_Script") {
+            return TextAsset::from_binary_data(self.raw_data(), self.byte_order, version);
+        }
+        let mut text_asset = TextAsset::from_typetree(self.class.properties())?;
+        if let Some(UnityValue::String(script)) = self.get("m_Script")
+            && let Some(raw) = self
+                .raw_strings
+                .iter()
+                .find(|r| r.path == "m_Script" && &r.decoded == script)
+        {
+            text_asset.script = raw.raw.clone();
+        }
+        Ok(text_asset)
+    }
+
+    /// Read a Material, from the TypeTree when the object has one and from the raw bytes
+    /// (laid out for `version`) otherwise.
+    pub fn as_material(&self, version: &UnityVersion) -> Result<Material> {
+        if self.class_id() != class_ids::MATERIAL {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not a Material (class_id: {})",
+                self.class_id()
+            )));
+        }
+        if !self.has_property("m_SavedProperties") {
+            return Material::from_binary_data(self.raw_data(), self.byte_order, version);
+        }
+        Material::from_typetree(self.class.properties())
+    }
+
     pub fn is_gameobject(&self) -> bool {
         self.class_id() == 1
     }
@@ -371,6 +726,17 @@ impl UnityObject {
         &self.typetree_warnings
     }
 
+    /// Whether lenient TypeTree parsing stopped early, leaving later fields out of `class`.
+    pub fn is_partial(&self) -> bool {
+        self.typetree_partial
+    }
+
+    /// Original bytes of `string` fields that were not valid UTF-8 (see
+    /// [`TypeTreeParseOptions::strings`]); pass these to the writer to keep them byte-exact.
+    pub fn raw_strings(&self) -> &[RawString] {
+        &self.raw_strings
+    }
+
     pub fn byte_size(&self) -> u32 {
         self.info.byte_size
     }
@@ -384,6 +750,37 @@ impl UnityObject {
     }
 }
 
+/// One-line summary, e.g. `Texture2D "icon" path_id=42 size=1024`.
+impl fmt::Display for UnityObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.class_name())?;
+        if let Some(name) = self.name() {
+            write!(f, " {:?}", name)?;
+        }
+        write!(f, " path_id={} size={}", self.path_id(), self.byte_size())
+    }
+}
+
+/// Properties are shown as a count and raw bytes as a [`BytesPreview`].
+impl fmt::Debug for UnityObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnityObject")
+            .field("info", &self.info)
+            .field("class", &format_args!("{}", self.class))
+            .field(
+                "properties",
+                &LenPreview(self.class.properties().len(), "properties"),
+            )
+            .field("byte_order", &self.byte_order)
+            .field("raw", &BytesPreview(self.raw.as_slice()))
+            .field(
+                "typetree_warnings",
+                &LenPreview(self.typetree_warnings.len(), "warnings"),
+            )
+            .finish()
+    }
+}
+
 fn class_name_from_id(class_id: i32) -> String {
     unity_asset_core::get_class_name(class_id).unwrap_or_else(|| format!("Class_{}", class_id))
 }
@@ -426,6 +823,17 @@ fn type_tree_for_object<'a>(
     // Best-effort fallback: stripped files can supply a registry externally.
     // We also allow this fallback even when `enable_type_tree = true` but the internal entry is missing/empty.
     file.type_tree_registry.as_ref().and_then(|r| {
+        if let Some(typ) = from_internal(file, info)
+            && let Some(tree) = r.resolve_hashed(
+                &file.unity_version,
+                typ.class_id,
+                typ.script_id,
+                typ.old_type_hash,
+            )
+        {
+            return Some(TypeTreeSource::Shared(tree));
+        }
+
         if let Some(typ) = from_internal(file, info)
             && typ.is_script_type()
             && typ.script_id != [0u8; 16]
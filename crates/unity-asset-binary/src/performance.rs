@@ -138,6 +138,7 @@ impl Default for PerformanceMetrics {
 
 /// Performance statistics snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct PerformanceStats {
     pub bytes_processed: u64,
     pub total_parse_time: Duration,
@@ -0,0 +1,250 @@
+//! Cross-file `PPtr` resolution
+//!
+//! A `PPtr` whose `m_FileID` is not `0` points into `externals[m_FileID - 1]` of the file it
+//! appears in. Unity spells the same file several ways there: `archive:/CAB-<hash>/CAB-<hash>`
+//! for bundle members, `sharedassets0.assets` or `library/sharedassets0.assets` in players, and
+//! `Library/unity default resources` / `Resources/unity_builtin_extra` for the built-in files
+//! wherever they were shipped. [`PPtrResolver`] matches external paths against the files it
+//! knows by full path first, then by file name, ignoring ASCII case either way.
+//!
+//! ```no_run
+//! use unity_asset_binary::bundle::load_bundle;
+//! use unity_asset_binary::pptr::PPtrResolver;
+//! use unity_asset_binary::unity_objects::ObjectRef;
+//!
+//! let mut resolver = PPtrResolver::new();
+//! resolver.add_bundle(load_bundle("ui.bundle")?);
+//! resolver.add_bundle(load_bundle("textures.bundle")?);
+//!
+//! // A sprite's `m_RD.texture` pointing into the second bundle.
+//! let sprite_file = resolver.file(0).unwrap();
+//! let texture = resolver.read(sprite_file, ObjectRef::new(1, 2846391044128427536))?;
+//! println!("{:?}", texture.name());
+//! # Ok::<(), unity_asset_binary::error::BinaryError>(())
+//! ```
+
+use crate::asset::SerializedFile;
+use crate::bundle::AssetBundle;
+use crate::error::{BinaryError, Result};
+use crate::file::UnityFile;
+use crate::object::{ObjectHandle, UnityObject};
+use crate::unity_objects::ObjectRef;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// A set of loaded serialized files that `PPtr`s are resolved against
+///
+/// Files are registered under the path other files' externals use for them: bundle members
+/// under their node name (`CAB-<hash>`), standalone files under their file name.
+#[derive(Default)]
+pub struct PPtrResolver {
+    files: Vec<(String, SerializedFile)>,
+}
+
+impl PPtrResolver {
+    /// Create an empty resolver
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `file` under `name` and return its index
+    pub fn add_file(&mut self, name: impl Into<String>, file: SerializedFile) -> usize {
+        self.files.push((name.into(), file));
+        self.files.len() - 1
+    }
+
+    /// Register every serialized file of `bundle` under its node name and return their indices
+    pub fn add_bundle(&mut self, mut bundle: AssetBundle) -> Range<usize> {
+        let start = self.files.len();
+        let names = std::mem::take(&mut bundle.asset_names);
+        for (index, file) in std::mem::take(&mut bundle.assets).into_iter().enumerate() {
+            let name = names.get(index).cloned().unwrap_or_default();
+            self.files.push((name, file));
+        }
+        start..self.files.len()
+    }
+
+    /// Number of registered files
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// The registered file at `index`
+    pub fn file(&self, index: usize) -> Option<&SerializedFile> {
+        self.files.get(index).map(|(_, file)| file)
+    }
+
+    /// The name the file at `index` was registered under
+    pub fn name(&self, index: usize) -> Option<&str> {
+        self.files.get(index).map(|(name, _)| name.as_str())
+    }
+
+    /// Index of the registered file an external path refers to
+    pub fn find_file(&self, external_path: &str) -> Option<usize> {
+        let path = external_path.replace('\\', "/");
+        if path.is_empty() {
+            return None;
+        }
+        let names: Vec<String> = self
+            .files
+            .iter()
+            .map(|(name, _)| name.replace('\\', "/"))
+            .collect();
+        names
+            .iter()
+            .position(|name| *name == path)
+            .or_else(|| {
+                names
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(&path))
+            })
+            .or_else(|| {
+                let wanted = file_name(&path);
+                names
+                    .iter()
+                    .position(|name| file_name(name).eq_ignore_ascii_case(wanted))
+            })
+    }
+
+    /// Resolve `pptr`, as written in `from`, to the object it points to
+    ///
+    /// `from` does not have to be registered; `m_FileID` `0` refers to `from` itself.
+    pub fn resolve<'a>(
+        &'a self,
+        from: &'a SerializedFile,
+        pptr: ObjectRef,
+    ) -> Result<ObjectHandle<'a>> {
+        if pptr.is_null() {
+            return Err(BinaryError::invalid_data(format!(
+                "Null PPtr (file_id {})",
+                pptr.file_id
+            )));
+        }
+        let (target, target_name) = if pptr.file_id == 0 {
+            (from, "the same file")
+        } else {
+            let external = usize::try_from(pptr.file_id - 1)
+                .ok()
+                .and_then(|index| from.externals.get(index))
+                .ok_or_else(|| {
+                    BinaryError::invalid_data(format!(
+                        "PPtr file_id {} is outside the {} externals",
+                        pptr.file_id,
+                        from.externals.len()
+                    ))
+                })?;
+            let index = self
+                .find_file(&external.path)
+                .ok_or_else(|| BinaryError::external_resource_missing(external.path.clone()))?;
+            (&self.files[index].1, external.path.as_str())
+        };
+        target.find_object_handle(pptr.path_id).ok_or_else(|| {
+            BinaryError::invalid_data(format!(
+                "Object {} not found in {}",
+                pptr.path_id, target_name
+            ))
+        })
+    }
+
+    /// Resolve `pptr` like [`Self::resolve`] and read the object
+    pub fn read(&self, from: &SerializedFile, pptr: ObjectRef) -> Result<UnityObject> {
+        self.resolve(from, pptr)?.read()
+    }
+
+    /// External paths of registered files that no registered file matches, in first-seen order
+    pub fn missing_externals(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.files
+            .iter()
+            .flat_map(|(_, file)| &file.externals)
+            .filter(|external| self.find_file(&external.path).is_none())
+            .filter(|external| seen.insert(external.path.clone()))
+            .map(|external| external.path.clone())
+            .collect()
+    }
+
+    /// Load files for missing externals until every external resolves or `load` has been asked
+    /// for each one
+    ///
+    /// `load` gets an external path and returns the file it names, or `None` when it has none.
+    /// Serialized files are registered under that path and bundles by their node names; files
+    /// loaded here may add externals of their own. Returns the number of files registered.
+    pub fn load_missing<F>(&mut self, mut load: F) -> Result<usize>
+    where
+        F: FnMut(&str) -> Result<Option<UnityFile>>,
+    {
+        let before = self.files.len();
+        let mut attempted = HashSet::new();
+        loop {
+            let pending: Vec<String> = self
+                .missing_externals()
+                .into_iter()
+                .filter(|path| attempted.insert(path.clone()))
+                .collect();
+            if pending.is_empty() {
+                return Ok(self.files.len() - before);
+            }
+            for path in pending {
+                if self.find_file(&path).is_some() {
+                    continue;
+                }
+                match load(&path)? {
+                    Some(UnityFile::SerializedFile(file)) => {
+                        self.add_file(path, file);
+                    }
+                    Some(UnityFile::AssetBundle(bundle)) => {
+                        self.add_bundle(bundle);
+                    }
+                    Some(UnityFile::WebFile(_)) | None => {}
+                }
+            }
+        }
+    }
+
+    /// Load missing externals from a player data directory (e.g. `<Game>_Data`)
+    ///
+    /// Each external is looked up by file name, ignoring ASCII case, in `dir` and its
+    /// `Resources` and `Library` subdirectories, where players and the editor keep
+    /// `sharedassets*.assets` and the built-in resource files. Bundle members
+    /// (`archive:/...`) have to be registered with [`Self::add_bundle`] or supplied through
+    /// [`Self::load_missing`].
+    #[cfg(feature = "fs")]
+    pub fn load_missing_from_dir(&mut self, dir: impl AsRef<std::path::Path>) -> Result<usize> {
+        let dir = dir.as_ref();
+        self.load_missing(|path| match find_in_dir(dir, path) {
+            Some(found) => crate::file::load_unity_file(found).map(Some),
+            None => Ok(None),
+        })
+    }
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+#[cfg(feature = "fs")]
+fn find_in_dir(dir: &std::path::Path, external_path: &str) -> Option<std::path::PathBuf> {
+    if external_path.starts_with("archive:") {
+        return None;
+    }
+    let name = file_name(&external_path.replace('\\', "/")).to_string();
+    ["", "Resources", "Library"].into_iter().find_map(|sub| {
+        let mut entries: Vec<_> = std::fs::read_dir(dir.join(sub))
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.eq_ignore_ascii_case(&name))
+            })
+            .collect();
+        entries.sort();
+        entries.into_iter().next()
+    })
+}
@@ -0,0 +1,37 @@
+//! Commonly used parser types, for glob import.
+//!
+//! ```
+//! use unity_asset_binary::prelude::*;
+//!
+//! let options = BundleLoadOptions::fast().with_max_memory(Some(64 * 1024 * 1024));
+//! let parse = TypeTreeParseOptions::default().with_mode(TypeTreeParseMode::Strict);
+//! # let _ = (options, parse);
+//! ```
+//!
+//! Option and statistics structs are `#[non_exhaustive]`: start from `Default` (or a preset such
+//! as [`BundleLoadOptions::fast`]) and use the `with_*` methods, so new fields are not breaking.
+//!
+//! ```compile_fail,E0639
+//! use unity_asset_binary::prelude::*;
+//!
+//! let options = BundleLoadOptions {
+//!     validate: false,
+//!     ..Default::default()
+//! };
+//! ```
+
+pub use crate::asset::SerializedFile;
+pub use crate::bundle::{AssetBundle, BundleLoadOptions, load_bundle_from_memory};
+#[cfg(feature = "fs")]
+pub use crate::bundle::{load_bundle, load_bundle_with_options};
+pub use crate::error::{BinaryError, Result};
+#[cfg(feature = "fs")]
+pub use crate::file::load_unity_file;
+pub use crate::file::{UnityFile, load_unity_file_from_memory};
+pub use crate::hooks::{HookAction, HookRegistry, ObjectContext, ObjectHook};
+pub use crate::object::{ObjectHandle, UnityObject};
+pub use crate::pptr::PPtrResolver;
+pub use crate::scene::SceneHierarchy;
+pub use crate::string_policy::StringPolicy;
+pub use crate::typetree::{TypeTree, TypeTreeParseMode, TypeTreeParseOptions};
+pub use crate::unity_version::{InferVersion, InferredVersion, UnityVersion};
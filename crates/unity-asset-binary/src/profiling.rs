@@ -0,0 +1,132 @@
+//! Heap allocation counting for parse regression tracking (`profiling` feature).
+//!
+//! A library cannot pick the global allocator, so the benchmark/test binary installs
+//! [`CountingAllocator`] and wraps the code under test in [`measure_allocations`]:
+//!
+//! ```ignore
+//! use unity_asset_binary::profiling::{CountingAllocator, measure_allocations};
+//!
+//! #[global_allocator]
+//! static ALLOC: CountingAllocator = CountingAllocator::new();
+//!
+//! let (bundle, stats) = measure_allocations(|| load_bundle_from_memory(data));
+//! println!("{} allocations, {} bytes", stats.allocations, stats.bytes_allocated);
+//! ```
+//!
+//! Counters are per thread, so tests running in parallel do not see each other's allocations.
+//! Work moved to other threads inside the measured closure is not counted.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Counters {
+    allocations: u64,
+    bytes_allocated: u64,
+    live_bytes: i64,
+    peak_live_bytes: i64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            allocations: 0,
+            bytes_allocated: 0,
+            live_bytes: 0,
+            peak_live_bytes: 0,
+        })
+    };
+}
+
+fn record(allocated: usize, freed: usize) {
+    // `try_with` keeps allocations during thread-local teardown from panicking.
+    let _ = COUNTERS.try_with(|c| {
+        let mut v = c.get();
+        if allocated > 0 {
+            v.allocations += 1;
+            v.bytes_allocated += allocated as u64;
+        }
+        v.live_bytes += allocated as i64 - freed as i64;
+        v.peak_live_bytes = v.peak_live_bytes.max(v.live_bytes);
+        c.set(v);
+    });
+}
+
+/// A [`System`]-backed global allocator that counts allocations on the calling thread.
+#[derive(Debug, Default)]
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            // A realloc may move and copy the whole buffer, so count it as a fresh allocation.
+            record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Allocation counts observed by [`measure_allocations`].
+///
+/// All fields are zero when [`CountingAllocator`] is not the global allocator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AllocationStats {
+    /// Number of `alloc`/`realloc` calls.
+    pub allocations: u64,
+    /// Total bytes requested (reallocs count their full new size).
+    pub bytes_allocated: u64,
+    /// Highest live-heap growth above the level at the start of the measurement.
+    pub peak_live_bytes: u64,
+    /// Live-heap growth at the end (bytes still held, e.g. by the returned value).
+    pub retained_bytes: i64,
+}
+
+/// Run `f` and report the heap allocations it made on the current thread.
+pub fn measure_allocations<T>(f: impl FnOnce() -> T) -> (T, AllocationStats) {
+    let before = COUNTERS.with(|c| {
+        let mut v = c.get();
+        v.peak_live_bytes = v.live_bytes;
+        c.set(v);
+        v
+    });
+
+    let out = f();
+
+    let after = COUNTERS.with(|c| c.get());
+    let stats = AllocationStats {
+        allocations: after.allocations - before.allocations,
+        bytes_allocated: after.bytes_allocated - before.bytes_allocated,
+        peak_live_bytes: (after.peak_live_bytes - before.live_bytes).max(0) as u64,
+        retained_bytes: after.live_bytes - before.live_bytes,
+    };
+    (out, stats)
+}
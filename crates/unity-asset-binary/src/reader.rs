@@ -1,8 +1,9 @@
 //! Binary data reader for Unity files
 
 use crate::error::{BinaryError, Result};
+use crate::string_policy::{StringPolicy, decode_string};
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
-use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::io::{Cursor, Seek, SeekFrom};
 
 /// Byte order for reading binary data
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -192,14 +193,24 @@ impl<'a> BinaryReader<'a> {
         }
     }
 
-    /// Read a fixed number of bytes
+    /// Read a fixed number of bytes into an owned buffer.
+    ///
+    /// Prefer [`BinaryReader::read_bytes_ref`] when the bytes are only inspected or copied
+    /// elsewhere; this allocates on every call.
     pub fn read_bytes(&mut self, count: usize) -> Result<Vec<u8>> {
+        Ok(self.read_bytes_ref(count)?.to_vec())
+    }
+
+    /// Read a fixed number of bytes, borrowing them from the underlying data.
+    pub fn read_bytes_ref(&mut self, count: usize) -> Result<&'a [u8]> {
         if !self.has_bytes(count) {
             return Err(BinaryError::not_enough_data(count, self.remaining()));
         }
-        let mut buffer = vec![0u8; count];
-        self.cursor.read_exact(&mut buffer)?;
-        Ok(buffer)
+        let data: &'a [u8] = self.cursor.get_ref();
+        let start = self.position() as usize;
+        let end = start + count;
+        self.cursor.set_position(end as u64);
+        Ok(&data[start..end])
     }
 
     /// Skip a fixed number of bytes without allocating.
@@ -220,15 +231,22 @@ impl<'a> BinaryReader<'a> {
 
     /// Read a null-terminated string
     pub fn read_cstring(&mut self) -> Result<String> {
-        let mut bytes = Vec::new();
-        loop {
-            let byte = self.read_u8()?;
-            if byte == 0 {
-                break;
-            }
-            bytes.push(byte);
-        }
-        Ok(String::from_utf8(bytes)?)
+        Ok(self.read_cstr_ref()?.to_owned())
+    }
+
+    /// Read a null-terminated string, borrowing it from the underlying data.
+    pub fn read_cstr_ref(&mut self) -> Result<&'a str> {
+        let data: &'a [u8] = self.cursor.get_ref();
+        let start = (self.position() as usize).min(data.len());
+        let Some(len) = data[start..].iter().position(|&b| b == 0) else {
+            return Err(BinaryError::not_enough_data(
+                data.len() - start + 1,
+                data.len() - start,
+            ));
+        };
+        let bytes = self.read_bytes_ref(len)?;
+        self.skip_bytes(1)?;
+        Ok(std::str::from_utf8(bytes)?)
     }
 
     /// Read a string with a length prefix (32-bit)
@@ -240,6 +258,22 @@ impl<'a> BinaryReader<'a> {
     ///
     /// Unity typically encodes these lengths as signed 32-bit integers.
     pub fn read_string_limited(&mut self, max_len: usize) -> Result<String> {
+        Ok(self.read_str_ref_limited(max_len)?.to_owned())
+    }
+
+    /// Read a length-prefixed (32-bit) string, borrowing it from the underlying data.
+    pub fn read_str_ref(&mut self) -> Result<&'a str> {
+        self.read_str_ref_limited(Self::DEFAULT_MAX_STRING_LEN)
+    }
+
+    /// Borrowing variant of [`BinaryReader::read_string_limited`].
+    pub fn read_str_ref_limited(&mut self, max_len: usize) -> Result<&'a str> {
+        let bytes = self.read_string_bytes_ref_limited(max_len)?;
+        Ok(std::str::from_utf8(bytes)?)
+    }
+
+    /// Read a length-prefixed (32-bit) string's bytes without decoding them.
+    pub fn read_string_bytes_ref_limited(&mut self, max_len: usize) -> Result<&'a [u8]> {
         let length = self.read_i32()?;
         if length < 0 {
             return Err(BinaryError::invalid_data(format!(
@@ -262,16 +296,15 @@ impl<'a> BinaryReader<'a> {
             return Err(BinaryError::not_enough_data(length, remaining));
         }
 
-        let bytes = self.read_bytes(length)?;
-        Ok(String::from_utf8(bytes)?)
+        self.read_bytes_ref(length)
     }
 
     /// Read a string with a specific length
     pub fn read_string_fixed(&mut self, length: usize) -> Result<String> {
-        let bytes = self.read_bytes(length)?;
+        let bytes = self.read_bytes_ref(length)?;
         // Remove null terminators
         let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
-        Ok(String::from_utf8(bytes[..end].to_vec())?)
+        Ok(std::str::from_utf8(&bytes[..end])?.to_owned())
     }
 
     /// Read an aligned string (Unity format)
@@ -282,6 +315,19 @@ impl<'a> BinaryReader<'a> {
         Ok(string)
     }
 
+    /// Read an aligned string, decoding non-UTF-8 bytes according to `policy`.
+    ///
+    /// The raw bytes are returned alongside the text when the text does not round-trip to them.
+    pub fn read_aligned_string_with_policy(
+        &mut self,
+        policy: &StringPolicy,
+    ) -> Result<(String, Option<&'a [u8]>)> {
+        let bytes = self.read_string_bytes_ref_limited(Self::DEFAULT_MAX_STRING_LEN)?;
+        self.align()?;
+        let (text, lossy) = decode_string(bytes, policy)?;
+        Ok((text.into_owned(), lossy.then_some(bytes)))
+    }
+
     /// Get the current byte order
     pub fn byte_order(&self) -> ByteOrder {
         self.byte_order
@@ -338,6 +384,27 @@ mod tests {
         assert!(reader.skip_bytes(10).is_err());
     }
 
+    #[test]
+    fn test_borrowed_reads() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&5i32.to_le_bytes());
+        data.extend_from_slice(b"Hello");
+        data.extend_from_slice(b"abc\0");
+        data.extend_from_slice(&[0xAA, 0xBB]);
+        let mut reader = BinaryReader::new(&data, ByteOrder::Little);
+
+        let s = reader.read_str_ref().unwrap();
+        assert_eq!(s, "Hello");
+        assert!(std::ptr::eq(s.as_ptr(), data[4..].as_ptr()));
+        assert_eq!(reader.read_cstr_ref().unwrap(), "abc");
+        assert_eq!(reader.read_bytes_ref(2).unwrap(), &[0xAA, 0xBB]);
+        assert_eq!(reader.remaining(), 0);
+
+        assert!(reader.read_bytes_ref(1).is_err());
+        let mut unterminated = BinaryReader::new(b"abc", ByteOrder::Little);
+        assert!(unterminated.read_cstr_ref().is_err());
+    }
+
     #[test]
     fn test_endianness() {
         let data = [0x01, 0x02, 0x03, 0x04];
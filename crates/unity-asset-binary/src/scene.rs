@@ -0,0 +1,309 @@
+//! GameObject hierarchy of a scene or prefab file
+//!
+//! A GameObject lists its components in `m_Component`, one of which is its Transform (or
+//! RectTransform). Transforms link to each other through `m_Father` / `m_Children` and back to
+//! their GameObject through `m_GameObject`. [`SceneHierarchy`] follows those links to rebuild the
+//! tree of GameObjects.
+//!
+//! ```no_run
+//! use unity_asset_binary::file::{UnityFile, load_unity_file};
+//! use unity_asset_binary::scene::SceneHierarchy;
+//!
+//! let UnityFile::SerializedFile(file) = load_unity_file("level1")? else {
+//!     return Ok(());
+//! };
+//! let hierarchy = SceneHierarchy::build(&file)?;
+//! for (depth, node) in hierarchy.walk() {
+//!     println!("{}{} ({} components)", "  ".repeat(depth), node.name, node.components.len());
+//! }
+//! if let Some(hand) = hierarchy.find_by_name("Hand").next() {
+//!     println!("{:?}", hierarchy.path_of(hand.path_id));
+//! }
+//! # Ok::<(), unity_asset_binary::error::BinaryError>(())
+//! ```
+
+use crate::animator::as_bool;
+use crate::asset::SerializedFile;
+use crate::error::{BinaryError, Result};
+use crate::object::UnityObject;
+use crate::unity_objects::{GameObject, ObjectRef, Transform};
+use indexmap::IndexMap;
+use std::collections::{HashMap, HashSet};
+use unity_asset_core::class_ids;
+
+/// A component attached to a [`SceneNode`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SceneComponent {
+    pub path_id: i64,
+    /// Class ID of the component, `None` when it is not in the file
+    pub class_id: Option<i32>,
+}
+
+/// A GameObject in a [`SceneHierarchy`]
+///
+/// Parent and children are GameObject path IDs; the children keep the order of the Transform's
+/// `m_Children`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneNode {
+    /// Path ID of the GameObject
+    pub path_id: i64,
+    /// Path ID of its Transform or RectTransform
+    pub transform_path_id: Option<i64>,
+    pub name: String,
+    pub active: bool,
+    pub layer: i32,
+    pub tag: String,
+    pub components: Vec<SceneComponent>,
+    pub parent: Option<i64>,
+    pub children: Vec<i64>,
+}
+
+impl SceneNode {
+    /// Class IDs of the components, in `m_Component` order
+    pub fn component_classes(&self) -> impl Iterator<Item = i32> + '_ {
+        self.components.iter().filter_map(|c| c.class_id)
+    }
+
+    pub fn has_component(&self, class_id: i32) -> bool {
+        self.component_classes().any(|c| c == class_id)
+    }
+}
+
+/// The GameObject tree of one serialized file
+#[derive(Debug, Clone, Default)]
+pub struct SceneHierarchy {
+    nodes: Vec<SceneNode>,
+    /// GameObject and Transform path IDs to node index
+    index: HashMap<i64, usize>,
+}
+
+impl SceneHierarchy {
+    /// Read the GameObjects and Transforms of `asset` through their TypeTrees and link them
+    ///
+    /// Fails when one of those objects has no TypeTree or does not parse.
+    pub fn build(asset: &SerializedFile) -> Result<Self> {
+        let mut objects = Vec::new();
+        for handle in asset.object_handles() {
+            if is_hierarchy_class(handle.class_id()) {
+                let class = handle.read_typed()?;
+                objects.push(UnityObject::from_info_and_class(
+                    handle.info().clone(),
+                    class,
+                ));
+            }
+        }
+        Self::link(&objects, |path_id| {
+            asset.find_object(path_id).map(|info| info.type_id)
+        })
+    }
+
+    /// Link already parsed objects
+    ///
+    /// Objects other than GameObjects and Transforms only contribute the class IDs of
+    /// components.
+    pub fn from_objects(objects: &[UnityObject]) -> Result<Self> {
+        let classes: HashMap<i64, i32> = objects
+            .iter()
+            .map(|object| (object.path_id(), object.class_id()))
+            .collect();
+        let objects: Vec<UnityObject> = objects
+            .iter()
+            .filter(|object| is_hierarchy_class(object.class_id()))
+            .cloned()
+            .collect();
+        Self::link(&objects, |path_id| classes.get(&path_id).copied())
+    }
+
+    fn link(objects: &[UnityObject], class_of: impl Fn(i64) -> Option<i32>) -> Result<Self> {
+        let mut nodes = Vec::new();
+        let mut index = HashMap::new();
+        let mut transforms = IndexMap::new();
+        for object in objects {
+            let properties = object.as_unity_class().properties();
+            if object.class_id() == class_ids::GAME_OBJECT {
+                let game_object = GameObject::from_typetree(properties)?;
+                index.insert(object.path_id(), nodes.len());
+                nodes.push(SceneNode {
+                    path_id: object.path_id(),
+                    transform_path_id: None,
+                    name: game_object.name,
+                    // `m_IsActive` is a `UInt8` in some older layouts.
+                    active: properties
+                        .get("m_IsActive")
+                        .and_then(as_bool)
+                        .unwrap_or(game_object.active),
+                    layer: game_object.layer,
+                    tag: game_object.tag,
+                    components: game_object
+                        .components
+                        .iter()
+                        .filter(|c| c.file_id == 0)
+                        .map(|c| SceneComponent {
+                            path_id: c.path_id,
+                            class_id: class_of(c.path_id),
+                        })
+                        .collect(),
+                    parent: None,
+                    children: Vec::new(),
+                });
+            } else {
+                transforms.insert(object.path_id(), Transform::from_typetree(properties)?);
+            }
+        }
+
+        // Transform -> GameObject, from `m_GameObject` or else the GameObject listing it.
+        let mut owner = IndexMap::new();
+        for (i, node) in nodes.iter().enumerate() {
+            for component in &node.components {
+                if transforms.contains_key(&component.path_id) {
+                    owner.entry(component.path_id).or_insert(i);
+                }
+            }
+        }
+        for (&path_id, transform) in &transforms {
+            if let Some(i) = transform
+                .game_object
+                .filter(|go| go.file_id == 0)
+                .and_then(|go| index.get(&go.path_id).copied())
+            {
+                owner.insert(path_id, i);
+            }
+        }
+        for (&transform_path_id, &i) in &owner {
+            if nodes[i].transform_path_id.is_none() {
+                nodes[i].transform_path_id = Some(transform_path_id);
+                index.insert(transform_path_id, i);
+            }
+        }
+
+        // Children in `m_Children` order, then any whose `m_Father` claims a parent that does
+        // not list them.
+        let node_of = |pptr: &ObjectRef| {
+            (pptr.file_id == 0)
+                .then(|| owner.get(&pptr.path_id).copied())
+                .flatten()
+        };
+        let mut links: Vec<(usize, usize)> = Vec::new();
+        let transform_of =
+            |node: &SceneNode| node.transform_path_id.and_then(|t| transforms.get(&t));
+        for (i, node) in nodes.iter().enumerate() {
+            if let Some(transform) = transform_of(node) {
+                links.extend(
+                    transform
+                        .children
+                        .iter()
+                        .filter_map(node_of)
+                        .map(|c| (i, c)),
+                );
+            }
+        }
+        for (child, node) in nodes.iter().enumerate() {
+            if let Some(parent) = transform_of(node)
+                .and_then(|t| t.parent.as_ref())
+                .and_then(node_of)
+            {
+                links.push((parent, child));
+            }
+        }
+        for (parent, child) in links {
+            if parent == child
+                || nodes[child]
+                    .parent
+                    .is_some_and(|p| p != nodes[parent].path_id)
+            {
+                continue;
+            }
+            let child_id = nodes[child].path_id;
+            nodes[child].parent = Some(nodes[parent].path_id);
+            if !nodes[parent].children.contains(&child_id) {
+                nodes[parent].children.push(child_id);
+            }
+        }
+
+        let hierarchy = Self { nodes, index };
+        if let Some(node) = hierarchy
+            .nodes
+            .iter()
+            .find(|node| hierarchy.ancestors(node).any(|a| a.path_id == node.path_id))
+        {
+            return Err(BinaryError::invalid_data(format!(
+                "Transform parents of GameObject {} form a cycle",
+                node.path_id
+            )));
+        }
+        Ok(hierarchy)
+    }
+
+    /// Number of GameObjects
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// All GameObjects, in file order
+    pub fn nodes(&self) -> impl Iterator<Item = &SceneNode> {
+        self.nodes.iter()
+    }
+
+    /// The GameObject with path ID `path_id`, or the one owning the Transform with that path ID
+    pub fn get(&self, path_id: i64) -> Option<&SceneNode> {
+        self.index.get(&path_id).map(|&i| &self.nodes[i])
+    }
+
+    /// GameObjects without a parent in this file, in file order
+    pub fn roots(&self) -> impl Iterator<Item = &SceneNode> {
+        self.nodes.iter().filter(|node| node.parent.is_none())
+    }
+
+    pub fn parent(&self, node: &SceneNode) -> Option<&SceneNode> {
+        node.parent.and_then(|p| self.get(p))
+    }
+
+    pub fn children<'a>(&'a self, node: &'a SceneNode) -> impl Iterator<Item = &'a SceneNode> {
+        node.children.iter().filter_map(|&c| self.get(c))
+    }
+
+    /// Parent, grandparent, ... of `node` up to its root
+    pub fn ancestors<'a>(&'a self, node: &'a SceneNode) -> impl Iterator<Item = &'a SceneNode> {
+        let mut seen = HashSet::new();
+        std::iter::successors(self.parent(node), |n| self.parent(n))
+            .take_while(move |n| seen.insert(n.path_id))
+    }
+
+    /// GameObjects named `name`, in file order
+    pub fn find_by_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a SceneNode> {
+        self.nodes.iter().filter(move |node| node.name == name)
+    }
+
+    /// `Root/Child/GrandChild` path of a GameObject (or Transform) path ID
+    pub fn path_of(&self, path_id: i64) -> Option<String> {
+        let node = self.get(path_id)?;
+        let mut names: Vec<&str> = self.ancestors(node).map(|n| n.name.as_str()).collect();
+        names.reverse();
+        names.push(&node.name);
+        Some(names.join("/"))
+    }
+
+    /// Depth-first walk from each root, yielding `(depth, node)` with roots at depth 0
+    pub fn walk(&self) -> impl Iterator<Item = (usize, &SceneNode)> {
+        let mut stack: Vec<(usize, &SceneNode)> = self.roots().map(|n| (0, n)).collect();
+        stack.reverse();
+        std::iter::from_fn(move || {
+            let (depth, node) = stack.pop()?;
+            let start = stack.len();
+            stack.extend(self.children(node).map(|c| (depth + 1, c)));
+            stack[start..].reverse();
+            Some((depth, node))
+        })
+    }
+}
+
+fn is_hierarchy_class(class_id: i32) -> bool {
+    matches!(
+        class_id,
+        class_ids::GAME_OBJECT | class_ids::TRANSFORM | class_ids::RECT_TRANSFORM
+    )
+}
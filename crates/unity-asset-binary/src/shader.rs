@@ -0,0 +1,346 @@
+//! Shader (class 48) structure: name, properties, passes, keywords and compiled-program blobs.
+//!
+//! This is enough to answer "which shaders and variants does this bundle contain" without
+//! decompiling any program. Everything comes from the TypeTree; the serialized shader layout
+//! changes too often between versions to read without one.
+//!
+//! # Layouts
+//!
+//! - Before 5.5 a shader is its ShaderLab source (`m_Script`) plus one LZ4 `m_SubProgramBlob`
+//!   of `decompressedSize` bytes; there is no parsed form, so only the name and blob are known.
+//! - From 5.5 `m_ParsedForm` holds the parsed ShaderLab and `compressedBlob` the compiled
+//!   programs, one LZ4 stream per entry of `platforms`, located by `offsets`,
+//!   `compressedLengths` and `decompressedLengths`.
+//! - From 2019.3 those three are nested one level deeper: each platform's programs are split
+//!   into several LZ4 segments.
+//!
+//! Keywords are `m_ParsedForm.m_KeywordNames` from 2021.2; earlier versions name them per
+//! sub-program, either directly (`m_Keywords`) or through indices into the pass's
+//! `m_NameIndices`.
+
+use crate::animator::{Fields, array, deref, uint_array};
+use crate::compression::{self, CompressionType};
+use crate::error::{BinaryError, Result};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap};
+use unity_asset_core::UnityValue;
+
+/// Program slots of a `SerializedPass`.
+const PROGRAMS: &[&str] = &[
+    "progVertex",
+    "progFragment",
+    "progGeometry",
+    "progHull",
+    "progDomain",
+    "progRayTracing",
+];
+
+/// Sub-program fields holding indices into the pass's `m_NameIndices`.
+const KEYWORD_INDICES: &[&str] = &[
+    "m_KeywordIndices",
+    "m_GlobalKeywordIndices",
+    "m_LocalKeywordIndices",
+];
+
+/// One LZ4 segment of `compressedBlob`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShaderBlobSegment {
+    pub offset: u32,
+    pub compressed_length: u32,
+    pub decompressed_length: u32,
+}
+
+/// A parsed Shader (class 48).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Shader {
+    /// ShaderLab name (e.g. `Universal Render Pipeline/Lit`).
+    pub name: String,
+    /// Names of the `Properties` block entries.
+    pub prop_names: Vec<String>,
+    /// Number of passes of each sub-shader.
+    pub pass_counts: Vec<usize>,
+    /// Distinct keywords used by any variant, sorted.
+    pub keywords: Vec<String>,
+    /// `ShaderCompilerPlatform` ids the programs were compiled for; see [`platform_name`].
+    pub platforms: Vec<u32>,
+    /// Blob segments of each entry of `platforms` (one list holding the whole
+    /// `m_SubProgramBlob` before 5.5).
+    pub compressed_blob_sizes: Vec<Vec<ShaderBlobSegment>>,
+    /// Compiled sub-programs (variants) over all passes and program stages.
+    pub sub_programs: usize,
+}
+
+impl Shader {
+    /// Parse Shader from TypeTree data
+    ///
+    /// Since Unity 5.5 the shader name lives in `m_ParsedForm.m_Name` and the object's own
+    /// `m_Name` is usually empty; older layouts only have `m_Name`.
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        ShaderParser::parse(properties)
+    }
+
+    /// Number of sub-shaders
+    pub fn sub_shader_count(&self) -> usize {
+        self.pass_counts.len()
+    }
+
+    /// Total compressed size of the compiled programs
+    pub fn compressed_size(&self) -> u64 {
+        self.compressed_blob_sizes
+            .iter()
+            .flatten()
+            .map(|s| s.compressed_length as u64)
+            .sum()
+    }
+
+    /// Total decompressed size of the compiled programs
+    pub fn decompressed_size(&self) -> u64 {
+        self.compressed_blob_sizes
+            .iter()
+            .flatten()
+            .map(|s| s.decompressed_length as u64)
+            .sum()
+    }
+}
+
+/// Reads [`Shader`]s and their compiled-program blobs from TypeTree data.
+pub struct ShaderParser;
+
+impl ShaderParser {
+    /// Parse the structure of a Shader
+    pub fn parse(properties: &Fields) -> Result<Shader> {
+        let mut shader = Shader::default();
+        let form = properties.get("m_ParsedForm").and_then(deref);
+
+        let parsed_name = form
+            .and_then(|f| f.get("m_Name"))
+            .and_then(UnityValue::as_str);
+        shader.name = parsed_name
+            .filter(|n| !n.is_empty())
+            .or_else(|| properties.get("m_Name").and_then(UnityValue::as_str))
+            .unwrap_or_default()
+            .to_string();
+
+        if let Some(form) = form {
+            if let Some(prop_info) = form.get("m_PropInfo").and_then(deref) {
+                shader.prop_names = array(prop_info, "m_Props")
+                    .filter_map(|p| p.get("m_Name").and_then(UnityValue::as_str))
+                    .map(str::to_string)
+                    .collect();
+            }
+
+            let mut keywords = BTreeSet::new();
+            if let Some(UnityValue::Array(names)) = form.get("m_KeywordNames") {
+                keywords.extend(
+                    names
+                        .iter()
+                        .filter_map(UnityValue::as_str)
+                        .map(String::from),
+                );
+            }
+            for sub_shader in array(form, "m_SubShaders") {
+                let passes: Vec<&Fields> = array(sub_shader, "m_Passes").collect();
+                shader.pass_counts.push(passes.len());
+                for pass in passes {
+                    let names = name_indices(pass.get("m_NameIndices"));
+                    for program in PROGRAMS
+                        .iter()
+                        .filter_map(|p| pass.get(*p))
+                        .filter_map(deref)
+                    {
+                        for sub_program in array(program, "m_SubPrograms") {
+                            shader.sub_programs += 1;
+                            if let Some(UnityValue::Array(direct)) = sub_program.get("m_Keywords") {
+                                keywords.extend(
+                                    direct
+                                        .iter()
+                                        .filter_map(UnityValue::as_str)
+                                        .map(String::from),
+                                );
+                            }
+                            for key in KEYWORD_INDICES {
+                                keywords.extend(
+                                    uint_array(sub_program.get(*key))
+                                        .into_iter()
+                                        .filter_map(|i| names.get(&i).cloned()),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+            shader.keywords = keywords.into_iter().collect();
+        }
+
+        shader.platforms = uint_array(properties.get("platforms"));
+        if properties.contains_key("compressedBlob") {
+            let offsets = segments(properties.get("offsets"));
+            let compressed = segments(properties.get("compressedLengths"));
+            let decompressed = segments(properties.get("decompressedLengths"));
+            if offsets.len() != compressed.len() || offsets.len() != decompressed.len() {
+                return Err(BinaryError::invalid_data(format!(
+                    "Shader '{}' has {} offsets but {} / {} lengths",
+                    shader.name,
+                    offsets.len(),
+                    compressed.len(),
+                    decompressed.len()
+                )));
+            }
+            shader.compressed_blob_sizes = offsets
+                .iter()
+                .zip(&compressed)
+                .zip(&decompressed)
+                .map(|((o, c), d)| {
+                    o.iter()
+                        .zip(c)
+                        .zip(d)
+                        .map(|((&offset, &compressed_length), &decompressed_length)| {
+                            ShaderBlobSegment {
+                                offset,
+                                compressed_length,
+                                decompressed_length,
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+        } else if let Some(blob) = properties.get("m_SubProgramBlob") {
+            shader.compressed_blob_sizes = vec![vec![ShaderBlobSegment {
+                offset: 0,
+                compressed_length: bytes(Some(blob)).map_or(0, |b| b.len() as u32),
+                decompressed_length: properties
+                    .get("decompressedSize")
+                    .and_then(UnityValue::as_i64)
+                    .unwrap_or(0) as u32,
+            }]];
+        }
+
+        Ok(shader)
+    }
+
+    /// Decompress the compiled programs of `shader.platforms[index]`
+    ///
+    /// Returns one buffer per segment. Before 5.5 `index` 0 is the whole `m_SubProgramBlob`.
+    pub fn decompress_platform(
+        properties: &Fields,
+        shader: &Shader,
+        index: usize,
+    ) -> Result<Vec<Vec<u8>>> {
+        let segments = shader.compressed_blob_sizes.get(index).ok_or_else(|| {
+            BinaryError::invalid_data(format!(
+                "Shader '{}' has no platform {} (of {})",
+                shader.name,
+                index,
+                shader.compressed_blob_sizes.len()
+            ))
+        })?;
+        let blob = bytes(properties.get("compressedBlob"))
+            .or_else(|| bytes(properties.get("m_SubProgramBlob")))
+            .ok_or_else(|| {
+                BinaryError::invalid_data(format!("Shader '{}' has no program blob", shader.name))
+            })?;
+
+        segments
+            .iter()
+            .map(|segment| {
+                let start = segment.offset as usize;
+                let end = start + segment.compressed_length as usize;
+                let data = blob.get(start..end).ok_or_else(|| {
+                    BinaryError::invalid_data(format!(
+                        "Shader '{}' blob segment {}..{} is past the end ({} bytes)",
+                        shader.name,
+                        start,
+                        end,
+                        blob.len()
+                    ))
+                })?;
+                compression::decompress(
+                    data,
+                    CompressionType::Lz4,
+                    segment.decompressed_length as usize,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Name of a `ShaderCompilerPlatform` id
+pub fn platform_name(platform: u32) -> Option<&'static str> {
+    Some(match platform {
+        0 => "OpenGL",
+        1 => "D3D9",
+        2 => "Xbox360",
+        3 => "PS3",
+        4 => "D3D11",
+        5 => "GLES20",
+        6 => "NaCl",
+        7 => "Flash",
+        8 => "D3D11_9x",
+        9 => "GLES3Plus",
+        10 => "PSP2",
+        11 => "PS4",
+        12 => "XboxOne",
+        13 => "PSM",
+        14 => "Metal",
+        15 => "OpenGLCore",
+        16 => "N3DS",
+        17 => "WiiU",
+        18 => "Vulkan",
+        19 => "Switch",
+        20 => "XboxOneD3D12",
+        21 => "GameCoreXboxOne",
+        22 => "GameCoreScarlett",
+        23 => "PS5",
+        24 => "PS5NGGC",
+        _ => return None,
+    })
+}
+
+/// `offsets` / `*Lengths` as segments per platform: `vector<vector<uint>>` from 2019.3,
+/// `vector<uint>` (one segment each) before.
+fn segments(value: Option<&UnityValue>) -> Vec<Vec<u32>> {
+    let Some(UnityValue::Array(items)) = value else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .map(|item| match item {
+            UnityValue::Array(_) => uint_array(Some(item)),
+            other => other.as_i64().map(|v| vec![v as u32]).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// `vector<UInt8>` read as bytes or as an integer array.
+fn bytes(value: Option<&UnityValue>) -> Option<Cow<'_, [u8]>> {
+    match value? {
+        UnityValue::Bytes(b) => Some(Cow::Borrowed(b)),
+        UnityValue::Array(items) => Some(Cow::Owned(
+            items
+                .iter()
+                .filter_map(UnityValue::as_i64)
+                .map(|v| v as u8)
+                .collect(),
+        )),
+        _ => None,
+    }
+}
+
+/// `m_NameIndices` (`map<string, int>`) inverted to index → name.
+fn name_indices(value: Option<&UnityValue>) -> HashMap<u32, String> {
+    let Some(UnityValue::Array(pairs)) = value else {
+        return HashMap::new();
+    };
+    pairs
+        .iter()
+        .filter_map(|pair| {
+            let (name, index) = match pair {
+                UnityValue::Array(kv) if kv.len() == 2 => (&kv[0], &kv[1]),
+                UnityValue::Object(obj) => (obj.get("first")?, obj.get("second")?),
+                _ => return None,
+            };
+            Some((index.as_i64()? as u32, name.as_str()?.to_string()))
+        })
+        .collect()
+}
@@ -0,0 +1,302 @@
+//! Shader variant audit for build-size analysis.
+//!
+//! Variants that end up in a build are forced in two places: ShaderVariantCollection assets
+//! (class 200) list `(shader, pass type, keywords)` tuples, and GraphicsSettings (class 30) lists
+//! always-included shaders, whose variants are all compiled. [`ShaderAudit`] collects both and
+//! counts variants per shader and per keyword; [`AssetBundle::shader_audit`] and
+//! `Environment::shader_audit` (in `unity-asset`) run it over loaded files.
+//!
+//! # Counting
+//!
+//! - A variant is a `(pass type, keyword set)` pair. Keyword order is ignored, and a variant listed
+//!   by several collections (or twice by one) is counted once per shader.
+//! - Keyword counts are the number of distinct variants of a shader that enable the keyword,
+//!   summed over shaders for the report-wide totals.
+//! - Always-included shaders contribute no variants: their variant count is decided by the shader
+//!   source, which is not part of the serialized data.
+//!
+//! Shaders are identified by the file that contains them plus `path_id`. Names come from the
+//! Shader object when its file is loaded, or from the built-in resource table for
+//! `unity default resources` / `unity_builtin_extra`; otherwise they are left empty and counted as
+//! `unresolved`.
+
+use crate::asset::SerializedFile;
+use crate::builtin_resources::BuiltinReference;
+use crate::bundle::AssetBundle;
+use crate::unity_objects::{GraphicsSettings, ObjectRef, PassType, ShaderVariantCollection};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use unity_asset_core::class_ids;
+
+/// A shader located by containing file and `path_id`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ShaderId {
+    /// Name of the file holding the shader (for unresolved externals, the external path).
+    pub file: String,
+    pub path_id: i64,
+    /// Shader name (e.g. `Universal Render Pipeline/Lit`), when it could be resolved.
+    pub name: Option<String>,
+}
+
+/// Variant totals for one shader.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShaderAuditEntry {
+    pub shader: ShaderId,
+    /// Distinct `(pass type, keywords)` variants forced by collections.
+    pub variants: usize,
+    /// Variant count per pass type name.
+    pub pass_types: BTreeMap<String, usize>,
+    /// Variant count per keyword.
+    pub keywords: BTreeMap<String, usize>,
+    /// Names of the collections listing this shader.
+    pub collections: Vec<String>,
+    /// Listed in GraphicsSettings `m_AlwaysIncludedShaders`.
+    pub always_included: bool,
+}
+
+/// Result of a [`ShaderAudit`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShaderAuditReport {
+    /// One entry per shader, sorted by name (unresolved last), then location.
+    pub shaders: Vec<ShaderAuditEntry>,
+    /// Names of the audited ShaderVariantCollections.
+    pub collections: Vec<String>,
+    /// Sum of [`ShaderAuditEntry::variants`].
+    pub total_variants: usize,
+    /// Variant count per pass type name, over all shaders.
+    pub pass_types: BTreeMap<String, usize>,
+    /// Variant count per keyword, over all shaders.
+    pub keywords: BTreeMap<String, usize>,
+    /// Number of always-included shaders.
+    pub always_included: usize,
+    /// Shaders whose name could not be resolved.
+    pub unresolved: usize,
+}
+
+impl ShaderAuditReport {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+
+    /// The entry for the shader named `name`, if any.
+    pub fn shader(&self, name: &str) -> Option<&ShaderAuditEntry> {
+        self.shaders
+            .iter()
+            .find(|e| e.shader.name.as_deref() == Some(name))
+    }
+}
+
+#[derive(Debug, Default)]
+struct ShaderState {
+    name: Option<String>,
+    variants: BTreeSet<(PassType, Vec<String>)>,
+    collections: BTreeSet<String>,
+    always_included: bool,
+}
+
+/// Accumulates ShaderVariantCollections and GraphicsSettings into a [`ShaderAuditReport`].
+///
+/// Callers resolve each shader `PPtr` to a [`ShaderId`], since that depends on how the containing
+/// files are loaded.
+#[derive(Debug, Default)]
+pub struct ShaderAudit {
+    shaders: BTreeMap<(String, i64), ShaderState>,
+    collections: Vec<String>,
+}
+
+impl ShaderAudit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_variant_collection(
+        &mut self,
+        collection: &ShaderVariantCollection,
+        mut resolve: impl FnMut(ObjectRef) -> ShaderId,
+    ) {
+        self.collections.push(collection.name.clone());
+        for entry in &collection.shaders {
+            let state = self.state(resolve(entry.shader));
+            state.collections.insert(collection.name.clone());
+            for variant in &entry.variants {
+                let mut keywords = variant.keywords.clone();
+                keywords.sort();
+                keywords.dedup();
+                state.variants.insert((variant.pass_type, keywords));
+            }
+        }
+    }
+
+    pub fn add_graphics_settings(
+        &mut self,
+        settings: &GraphicsSettings,
+        mut resolve: impl FnMut(ObjectRef) -> ShaderId,
+    ) {
+        for shader in &settings.always_included_shaders {
+            self.state(resolve(*shader)).always_included = true;
+        }
+    }
+
+    fn state(&mut self, id: ShaderId) -> &mut ShaderState {
+        let state = self.shaders.entry((id.file, id.path_id)).or_default();
+        if state.name.is_none() {
+            state.name = id.name;
+        }
+        state
+    }
+
+    pub fn finish(self) -> ShaderAuditReport {
+        let mut report = ShaderAuditReport {
+            collections: self.collections,
+            ..Default::default()
+        };
+
+        for ((file, path_id), state) in self.shaders {
+            let mut entry = ShaderAuditEntry {
+                shader: ShaderId {
+                    file,
+                    path_id,
+                    name: state.name,
+                },
+                variants: state.variants.len(),
+                pass_types: BTreeMap::new(),
+                keywords: BTreeMap::new(),
+                collections: state.collections.into_iter().collect(),
+                always_included: state.always_included,
+            };
+            for (pass_type, keywords) in &state.variants {
+                *entry.pass_types.entry(pass_type.name()).or_default() += 1;
+                for keyword in keywords {
+                    *entry.keywords.entry(keyword.clone()).or_default() += 1;
+                }
+            }
+
+            report.total_variants += entry.variants;
+            for (pass_type, count) in &entry.pass_types {
+                *report.pass_types.entry(pass_type.clone()).or_default() += count;
+            }
+            for (keyword, count) in &entry.keywords {
+                *report.keywords.entry(keyword.clone()).or_default() += count;
+            }
+            if entry.always_included {
+                report.always_included += 1;
+            }
+            if entry.shader.name.is_none() {
+                report.unresolved += 1;
+            }
+            report.shaders.push(entry);
+        }
+
+        report.shaders.sort_by(|a, b| {
+            (
+                a.shader.name.is_none(),
+                &a.shader.name,
+                &a.shader.file,
+                a.shader.path_id,
+            )
+                .cmp(&(
+                    b.shader.name.is_none(),
+                    &b.shader.name,
+                    &b.shader.file,
+                    b.shader.path_id,
+                ))
+        });
+        report
+    }
+}
+
+impl AssetBundle {
+    /// Audit the ShaderVariantCollections and GraphicsSettings in this bundle.
+    ///
+    /// Shader references into other files of the same bundle are resolved by file name; see the
+    /// [module docs](crate::shader_audit) for how variants are counted.
+    pub fn shader_audit(&self) -> ShaderAuditReport {
+        let mut audit = ShaderAudit::new();
+        for (index, asset) in self.assets.iter().enumerate() {
+            for handle in asset.object_handles() {
+                let class_id = handle.class_id();
+                if class_id != class_ids::SHADER_VARIANT_COLLECTION
+                    && class_id != class_ids::GRAPHICS_SETTINGS
+                {
+                    continue;
+                }
+                let Ok(object) = handle.read() else {
+                    continue;
+                };
+                let resolve = |r: ObjectRef| self.resolve_shader(index, r);
+                if let Ok(collection) = object.as_shader_variant_collection() {
+                    audit.add_variant_collection(&collection, resolve);
+                } else if let Ok(settings) = object.as_graphics_settings() {
+                    audit.add_graphics_settings(&settings, resolve);
+                }
+            }
+        }
+        audit.finish()
+    }
+
+    fn resolve_shader(&self, asset_index: usize, shader: ObjectRef) -> ShaderId {
+        let asset = &self.assets[asset_index];
+        let asset_name = |i: usize| self.asset_names.get(i).cloned().unwrap_or_default();
+
+        if shader.file_id == 0 {
+            return local_shader_id(asset_name(asset_index), asset, shader.path_id);
+        }
+        let external = usize::try_from(shader.file_id - 1)
+            .ok()
+            .and_then(|i| asset.externals.get(i));
+        let Some(external) = external else {
+            return ShaderId {
+                file: format!("<file_id {}>", shader.file_id),
+                path_id: shader.path_id,
+                name: None,
+            };
+        };
+        if let Some(id) = builtin_shader_id(&external.path, shader.path_id) {
+            return id;
+        }
+
+        let target = file_name(&external.path);
+        match self
+            .asset_names
+            .iter()
+            .position(|n| file_name(n).eq_ignore_ascii_case(target))
+        {
+            Some(i) => local_shader_id(asset_name(i), &self.assets[i], shader.path_id),
+            None => ShaderId {
+                file: external.path.clone(),
+                path_id: shader.path_id,
+                name: None,
+            },
+        }
+    }
+}
+
+/// Name a shader stored in a loaded file.
+fn local_shader_id(file: String, asset: &SerializedFile, path_id: i64) -> ShaderId {
+    let name = asset
+        .find_object_handle(path_id)
+        .filter(|h| h.class_id() == class_ids::SHADER)
+        .and_then(|h| h.read().ok())
+        .and_then(|o| o.as_shader().ok())
+        .map(|s| s.name)
+        .filter(|n| !n.is_empty());
+    ShaderId {
+        file,
+        path_id,
+        name,
+    }
+}
+
+/// Name a shader in Unity's built-in resources, if `external_path` is one of those files.
+fn builtin_shader_id(external_path: &str, path_id: i64) -> Option<ShaderId> {
+    let builtin = BuiltinReference::from_external_path(external_path, path_id)?;
+    Some(ShaderId {
+        file: builtin.file.file_name().to_string(),
+        path_id,
+        name: builtin.resource.map(|r| r.name.to_string()),
+    })
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
@@ -1,6 +1,8 @@
+use crate::bytes_preview::BytesPreview;
+use std::fmt;
 use std::sync::Arc;
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub enum SharedBytes {
     Arc(Arc<[u8]>),
     #[cfg(feature = "mmap")]
@@ -36,3 +38,16 @@ impl SharedBytes {
         self.as_bytes().as_ptr() as usize
     }
 }
+
+impl fmt::Debug for SharedBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SharedBytes::Arc(_) => "Arc",
+            #[cfg(feature = "mmap")]
+            SharedBytes::Mmap(_) => "Mmap",
+        };
+        f.debug_tuple(name)
+            .field(&BytesPreview(self.as_bytes()))
+            .finish()
+    }
+}
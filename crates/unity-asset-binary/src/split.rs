@@ -0,0 +1,183 @@
+//! Split files (`<name>.split0`, `<name>.split1`, ...)
+//!
+//! Android builds split files that would exceed the APK's 2 GB entry limit into numbered chunks
+//! next to each other. Concatenated in order, the chunks are the original file. [`split_parts`]
+//! finds the chunks for either the unsplit name or any chunk, and [`SplitReader`] reads them as
+//! one stream.
+
+use crate::error::{BinaryError, Result};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Path of chunk `index` of the split file `base`.
+pub fn split_part_path(base: &Path, index: usize) -> PathBuf {
+    let mut name = base.as_os_str().to_os_string();
+    name.push(format!(".split{index}"));
+    PathBuf::from(name)
+}
+
+/// The unsplit path of a chunk path (`foo.assets.split3` -> `foo.assets`).
+pub fn split_base_path(path: &Path) -> Option<PathBuf> {
+    let ext = path.extension()?.to_str()?;
+    let index = ext.strip_prefix("split")?;
+    if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(path.with_extension(""))
+}
+
+/// The chunks of a split file, in order, for its unsplit path or any chunk path.
+///
+/// Returns `None` when `path` names an existing regular file (it is not split) or when no
+/// `.split0` chunk exists. Chunks are read up to the first missing index.
+pub fn split_parts(path: &Path) -> Option<Vec<PathBuf>> {
+    let base = match split_base_path(path) {
+        Some(base) => base,
+        None if path.is_file() => return None,
+        None => path.to_path_buf(),
+    };
+
+    let parts: Vec<PathBuf> = (0..)
+        .map(|index| split_part_path(&base, index))
+        .take_while(|part| part.is_file())
+        .collect();
+    (!parts.is_empty()).then_some(parts)
+}
+
+/// A `Read + Seek` stream over the chunks of a split file.
+#[derive(Debug)]
+pub struct SplitReader {
+    parts: Vec<PathBuf>,
+    /// Start offset of each chunk, plus the total length at the end.
+    offsets: Vec<u64>,
+    position: u64,
+    current: Option<(usize, File)>,
+}
+
+impl SplitReader {
+    /// Open the given chunks (in order).
+    pub fn new(parts: Vec<PathBuf>) -> Result<Self> {
+        let mut offsets = Vec::with_capacity(parts.len() + 1);
+        let mut total = 0u64;
+        for part in &parts {
+            offsets.push(total);
+            let len = std::fs::metadata(part)
+                .map_err(|e| BinaryError::generic(format!("Failed to stat {:?}: {}", part, e)))?
+                .len();
+            total += len;
+        }
+        offsets.push(total);
+
+        Ok(Self {
+            parts,
+            offsets,
+            position: 0,
+            current: None,
+        })
+    }
+
+    /// Open the chunks of the split file at `path` (see [`split_parts`]).
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let parts = split_parts(path.as_ref()).ok_or_else(|| {
+            BinaryError::invalid_data(format!("Not a split file: {:?}", path.as_ref()))
+        })?;
+        Self::new(parts)
+    }
+
+    pub fn parts(&self) -> &[PathBuf] {
+        &self.parts
+    }
+
+    /// Total length of the reassembled file.
+    pub fn len(&self) -> u64 {
+        self.offsets.last().copied().unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Read the whole reassembled file into one buffer.
+    pub fn read_all(mut self) -> Result<Vec<u8>> {
+        let mut data = Vec::with_capacity(self.len() as usize);
+        self.seek(SeekFrom::Start(0))
+            .and_then(|_| self.read_to_end(&mut data))
+            .map_err(|e| {
+                BinaryError::generic(format!("Failed to read split file {:?}: {}", self.parts, e))
+            })?;
+        Ok(data)
+    }
+
+    /// Index of the chunk holding `position`, or `None` at end of file.
+    fn part_at(&self, position: u64) -> Option<usize> {
+        if position >= self.len() {
+            return None;
+        }
+        let index = self.offsets.partition_point(|&start| start <= position) - 1;
+        Some(index)
+    }
+}
+
+impl Read for SplitReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        // Empty chunks are skipped by `part_at`, so one read never straddles a boundary.
+        let Some(index) = self.part_at(self.position) else {
+            return Ok(0);
+        };
+        let offset_in_part = self.position - self.offsets[index];
+        let file = match &mut self.current {
+            Some((current, file)) if *current == index => file,
+            _ => {
+                let mut file = File::open(&self.parts[index])?;
+                file.seek(SeekFrom::Start(offset_in_part))?;
+                &mut self.current.insert((index, file)).1
+            }
+        };
+
+        let remaining = self.offsets[index + 1] - self.position;
+        let len = buf.len().min(remaining as usize);
+        let read = file.read(&mut buf[..len])?;
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for SplitReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(delta) => self.len().checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.position.checked_add_signed(delta),
+        }
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "seek before start of split file",
+            )
+        })?;
+
+        if target != self.position {
+            self.position = target;
+            if let (Some(index), Some((current, file))) =
+                (self.part_at(target), self.current.as_mut())
+                && *current == index
+            {
+                file.seek(SeekFrom::Start(target - self.offsets[index]))?;
+            } else {
+                self.current = None;
+            }
+        }
+        Ok(self.position)
+    }
+}
+
+/// Read a file, reassembling it from `.splitN` chunks when `path` is split (see [`split_parts`]).
+pub fn read_file_or_split<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+    let path = path.as_ref();
+    match split_parts(path) {
+        Some(parts) => SplitReader::new(parts)?.read_all(),
+        None => std::fs::read(path)
+            .map_err(|e| BinaryError::generic(format!("Failed to read file {:?}: {}", path, e))),
+    }
+}
@@ -0,0 +1,190 @@
+//! Decoding of serialized strings that are not valid UTF-8.
+//!
+//! Unity writes `string` fields as raw bytes. Content authored with legacy tooling (common in
+//! Japanese/Chinese titles) can store Shift-JIS or GBK bytes there, which strict UTF-8 decoding
+//! rejects. [`StringPolicy`] selects how such bytes are turned into text; whenever the decoded text
+//! is not a byte-exact UTF-8 view of the input, the original bytes are kept as a [`RawString`] so
+//! writers can emit them unchanged.
+//!
+//! The policy travels with [`TypeTreeParseOptions::strings`](crate::typetree::TypeTreeParseOptions),
+//! which every object read and name peek takes, so it can be chosen per read. `BinaryReader`
+//! carries no configuration of its own; its policy-aware string read takes the policy per call.
+//! Encoding lists are owned, so they can come from runtime configuration such as a command-line
+//! flag ([`StringPolicy::from_labels`]).
+//!
+//! ```
+//! use unity_asset_binary::string_policy::{StringPolicy, decode_string};
+//!
+//! let japanese = StringPolicy::from_labels(&["shift_jis"]).unwrap();
+//!
+//! let bytes = b"\x83\x65\x83\x58\x83\x67"; // "テスト" in Shift-JIS
+//! let (text, lossy) = decode_string(bytes, &japanese).unwrap();
+//! assert_eq!(text, "テスト");
+//! assert!(lossy);
+//! ```
+
+use crate::error::{BinaryError, Result};
+use encoding_rs::Encoding;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// How `string` bytes that are not valid UTF-8 are decoded.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum StringPolicy {
+    /// Invalid UTF-8 is a parse error.
+    #[default]
+    Utf8Strict,
+    /// Invalid sequences become U+FFFD.
+    Utf8Lossy,
+    /// Try each encoding in order (without replacement); fall back to lossy UTF-8.
+    TryEncodings(Arc<[&'static Encoding]>),
+}
+
+impl StringPolicy {
+    /// [`TryEncodings`](Self::TryEncodings) over `encodings`, tried in order.
+    pub fn try_encodings(encodings: impl IntoIterator<Item = &'static Encoding>) -> Self {
+        Self::TryEncodings(encodings.into_iter().collect())
+    }
+
+    /// [`TryEncodings`](Self::TryEncodings) from WHATWG encoding labels such as `"shift_jis"`
+    /// or `"gbk"`, tried in order.
+    pub fn from_labels<S: AsRef<str>>(labels: &[S]) -> Result<Self> {
+        let encodings = labels
+            .iter()
+            .map(|label| {
+                let label = label.as_ref();
+                Encoding::for_label(label.as_bytes()).ok_or_else(|| {
+                    BinaryError::invalid_data(format!("Unknown text encoding: {}", label))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::try_encodings(encodings))
+    }
+}
+
+/// A string whose decoded text does not round-trip to its serialized bytes.
+///
+/// `path` and `occurrence` locate the field: `path` is the dotted TypeTree path from the root
+/// (array elements appear as `m_Items.data`, without an index) and `occurrence` counts the
+/// `string` fields read at that path before this one. Writers match on this key rather than
+/// on `decoded`, since different byte strings can decode to the same lossy text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawString {
+    pub decoded: String,
+    pub raw: Vec<u8>,
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub occurrence: usize,
+}
+
+/// Decode `bytes` under `policy`.
+///
+/// The flag is `true` when the text is not the bytes' own UTF-8 (a legacy encoding or replacement
+/// characters were used), i.e. when the raw bytes must be kept for a faithful write-back.
+pub fn decode_string<'a>(bytes: &'a [u8], policy: &StringPolicy) -> Result<(Cow<'a, str>, bool)> {
+    if let Ok(s) = std::str::from_utf8(bytes) {
+        return Ok((Cow::Borrowed(s), false));
+    }
+    match policy {
+        StringPolicy::Utf8Strict => Ok((Cow::Borrowed(std::str::from_utf8(bytes)?), false)),
+        StringPolicy::Utf8Lossy => Ok((String::from_utf8_lossy(bytes), true)),
+        StringPolicy::TryEncodings(encodings) => {
+            let decoded = encodings
+                .iter()
+                .find_map(|e| e.decode_without_bom_handling_and_without_replacement(bytes));
+            Ok((
+                decoded.unwrap_or_else(|| String::from_utf8_lossy(bytes)),
+                true,
+            ))
+        }
+    }
+}
+
+/// Case-insensitive substring match of `query` against a name in decoded and raw form.
+///
+/// `raw` matches when it contains the query's UTF-8 bytes, or the query encoded with one of the
+/// policy's encodings (so a query typed as text finds a name decoded under a different encoding).
+/// Raw bytes are only ASCII case-folded when they are UTF-8 or a single-byte encoding: trail bytes
+/// of Shift-JIS or GBK characters fall in the ASCII letter range and must match exactly.
+pub fn name_matches(query: &str, decoded: &str, raw: Option<&[u8]>, policy: &StringPolicy) -> bool {
+    if decoded.to_lowercase().contains(&query.to_lowercase()) {
+        return true;
+    }
+    let Some(raw) = raw else {
+        return false;
+    };
+    match policy {
+        StringPolicy::Utf8Strict | StringPolicy::Utf8Lossy => {
+            contains_bytes(raw, query.as_bytes(), true)
+        }
+        StringPolicy::TryEncodings(encodings) => {
+            let single_byte = encodings.iter().all(|e| e.is_single_byte());
+            contains_bytes(raw, query.as_bytes(), single_byte)
+                || encodings.iter().any(|e| {
+                    let (encoded, _, had_errors) = e.encode(query);
+                    !had_errors && contains_bytes(raw, &encoded, e.is_single_byte())
+                })
+        }
+    }
+}
+
+fn contains_bytes(haystack: &[u8], needle: &[u8], fold_ascii_case: bool) -> bool {
+    needle.is_empty()
+        || haystack.windows(needle.len()).any(|w| {
+            if fold_ascii_case {
+                w.eq_ignore_ascii_case(needle)
+            } else {
+                w == needle
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_utf8_is_never_flagged() {
+        for policy in [
+            StringPolicy::Utf8Strict,
+            StringPolicy::Utf8Lossy,
+            StringPolicy::try_encodings([]),
+        ] {
+            let (text, lossy) = decode_string("名前".as_bytes(), &policy).unwrap();
+            assert_eq!(text, "名前");
+            assert!(!lossy);
+        }
+    }
+
+    #[test]
+    fn exhausted_encodings_fall_back_to_lossy() {
+        let policy = StringPolicy::try_encodings([]);
+        let (text, lossy) = decode_string(b"a\xffb", &policy).unwrap();
+        assert_eq!(text, "a\u{fffd}b");
+        assert!(lossy);
+    }
+
+    #[test]
+    fn multibyte_trail_bytes_are_not_case_folded() {
+        // "ア" in Shift-JIS is 0x83 0x41; its trail byte is an ASCII 'A'.
+        let raw = b"\x83\x41";
+        let japanese = StringPolicy::try_encodings([encoding_rs::SHIFT_JIS]);
+        assert!(!name_matches("a", "ア", Some(raw), &japanese));
+        assert!(name_matches("ア", "ア", Some(raw), &japanese));
+
+        let latin = StringPolicy::try_encodings([encoding_rs::WINDOWS_1252]);
+        assert!(name_matches("caf", "?", Some(b"CAF\xe9"), &latin));
+    }
+
+    #[test]
+    fn labels_resolve_to_encodings() {
+        let policy = StringPolicy::from_labels(&["gbk", "shift_jis"]).unwrap();
+        assert_eq!(
+            policy,
+            StringPolicy::try_encodings([encoding_rs::GBK, encoding_rs::SHIFT_JIS])
+        );
+        assert!(StringPolicy::from_labels(&["klingon"]).is_err());
+    }
+}
@@ -0,0 +1,50 @@
+//! Helpers for integration tests that load JSON typetree fixtures (`test-support` feature).
+//!
+//! The fixtures are the JSON form of `read_typetree()` output; integers and floats keep their
+//! typetree kinds through the JSON round trip. Crates that need these helpers enable the
+//! feature from `[dev-dependencies]` only.
+
+use indexmap::IndexMap;
+use std::path::PathBuf;
+use unity_asset_core::UnityValue;
+
+/// Convert a JSON value to the `UnityValue` the typetree reader would produce.
+pub fn to_unity(value: &serde_json::Value) -> UnityValue {
+    match value {
+        serde_json::Value::Null => UnityValue::Null,
+        serde_json::Value::Bool(b) => UnityValue::Bool(*b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => UnityValue::Integer(i),
+            None => UnityValue::Float(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) => UnityValue::String(s.clone()),
+        serde_json::Value::Array(items) => UnityValue::Array(items.iter().map(to_unity).collect()),
+        serde_json::Value::Object(fields) => UnityValue::Object(properties(fields)),
+    }
+}
+
+/// Convert a JSON object to a typetree property map.
+pub fn properties(
+    fields: &serde_json::Map<String, serde_json::Value>,
+) -> IndexMap<String, UnityValue> {
+    fields
+        .iter()
+        .map(|(k, v)| (k.clone(), to_unity(v)))
+        .collect()
+}
+
+/// Read `tests/fixtures/<name>` of the crate under test as JSON.
+///
+/// The directory comes from `CARGO_MANIFEST_DIR` at run time, which `cargo test` sets to the
+/// package whose tests are running.
+pub fn read_fixture(name: &str) -> serde_json::Value {
+    let root = std::env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    let path = PathBuf::from(root).join("tests/fixtures").join(name);
+    let bytes = std::fs::read(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+/// Property map of the object stored under `key` in a fixture dump.
+pub fn object_properties(dump: &serde_json::Value, key: &str) -> IndexMap<String, UnityValue> {
+    properties(dump[key].as_object().unwrap())
+}
@@ -0,0 +1,116 @@
+//! TextAsset (class 49): JSON configs, Lua scripts, CSV tables and `.bytes` blobs.
+//!
+//! `m_Script` is declared as a `string` but holds whatever bytes the asset was imported from,
+//! so [`TextAsset::script`] keeps them verbatim; [`TextAsset::text`] decodes them when they are
+//! UTF-8 or BOM-marked UTF-16. The layout is `m_Name` then `m_Script` (both aligned); Unity
+//! versions before 2017.1 append the source `m_PathName`.
+//!
+//! [`UnityObject::as_text_asset`](crate::object::UnityObject::as_text_asset) reads one from an
+//! object, from its TypeTree when it has one and from the raw bytes otherwise.
+
+use crate::error::{BinaryError, Result};
+use crate::reader::{BinaryReader, ByteOrder};
+use crate::unity_version::UnityVersion;
+use encoding_rs::{UTF_8, UTF_16BE, UTF_16LE};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use unity_asset_core::UnityValue;
+
+type Fields = indexmap::IndexMap<String, UnityValue>;
+
+/// A parsed TextAsset (class 49).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextAsset {
+    pub name: String,
+    /// `m_Script`: the asset's bytes, not necessarily text.
+    pub script: Vec<u8>,
+}
+
+/// How [`TextAsset::export`] writes `script`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextAssetExport {
+    /// The bytes as stored.
+    #[default]
+    Raw,
+    /// [`TextAsset::text`] as UTF-8 without a BOM; fails for binary scripts.
+    Text,
+}
+
+impl TextAsset {
+    /// Parse TextAsset from TypeTree data
+    ///
+    /// `m_Script` that is not valid UTF-8 was decoded lossily by the TypeTree reader; use
+    /// [`UnityObject::as_text_asset`](crate::object::UnityObject::as_text_asset) to get its
+    /// original bytes back.
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        let script = match properties.get("m_Script") {
+            Some(UnityValue::String(s)) => s.as_bytes().to_vec(),
+            Some(UnityValue::Bytes(b)) => b.to_vec(),
+            _ => {
+                return Err(BinaryError::invalid_data(
+                    "TextAsset typetree has no m_Script",
+                ));
+            }
+        };
+        Ok(Self {
+            name: properties
+                .get("m_Name")
+                .and_then(UnityValue::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            script,
+        })
+    }
+
+    /// Parse TextAsset from an object's raw bytes (no TypeTree)
+    ///
+    /// From 2017.1 on the bytes must end with `m_Script`; older versions may carry `m_PathName`.
+    pub fn from_binary_data(
+        data: &[u8],
+        byte_order: ByteOrder,
+        version: &UnityVersion,
+    ) -> Result<Self> {
+        let mut reader = BinaryReader::new(data, byte_order);
+        let name = reader.read_aligned_string()?;
+        let script = reader.read_string_bytes_ref_limited(usize::MAX)?.to_vec();
+        reader.align()?;
+        if version.at_least(2017, 1) && reader.remaining() > 0 {
+            return Err(BinaryError::invalid_data(format!(
+                "TextAsset '{}' has {} bytes after m_Script",
+                name,
+                reader.remaining()
+            )));
+        }
+        Ok(Self { name, script })
+    }
+
+    /// Decode `script` as text
+    ///
+    /// A UTF-8, UTF-16LE or UTF-16BE byte order mark selects the encoding (and is dropped);
+    /// without one the bytes must be valid UTF-8.
+    pub fn text(&self) -> Result<String> {
+        let (encoding, bom_len) =
+            encoding_rs::Encoding::for_bom(&self.script).unwrap_or((UTF_8, 0));
+        let bytes = &self.script[bom_len..];
+        let text = if encoding == UTF_16LE || encoding == UTF_16BE {
+            encoding.decode_without_bom_handling_and_without_replacement(bytes)
+        } else {
+            std::str::from_utf8(bytes).ok().map(Into::into)
+        };
+        text.map(|t| t.into_owned()).ok_or_else(|| {
+            BinaryError::invalid_data(format!(
+                "TextAsset '{}' is not UTF-8 or BOM-marked UTF-16 text",
+                self.name
+            ))
+        })
+    }
+
+    /// Write `script` to `path`
+    pub fn export<P: AsRef<Path>>(&self, path: P, mode: TextAssetExport) -> Result<()> {
+        match mode {
+            TextAssetExport::Raw => std::fs::write(path, &self.script)?,
+            TextAssetExport::Text => std::fs::write(path, self.text()?)?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,388 @@
+//! Tilemap (class 1839735485) and Grid (class 156049354) parsing.
+//!
+//! A Tilemap stores its cells sparsely in `m_Tiles`, a map from cell position to a small record
+//! of indices into shared, reference-counted tables: `m_TileAssetArray` (TileBase assets),
+//! `m_TileSpriteArray`, `m_TileMatrixArray` and `m_TileColorArray`. [`Tilemap`] resolves those
+//! indices so each [`TileCell`] carries its own tile, sprite, color and matrix.
+//!
+//! The cell geometry lives on the Grid component of the Tilemap's parent GameObject; use
+//! [`tilemap_grid`] to find it within a serialized file.
+//!
+//! # Versions
+//!
+//! 2017.x stores the per-cell indices as `SInt32` with `-1` for "none" and names the flags
+//! `m_TileFlags`; 2019+ packs them as `UInt16` (`0xFFFF` for "none") followed by
+//! `dummyAlignment` and `m_AllTileFlags`. Both are accepted, as are `m_Tiles` entries read from
+//! binary (`[key, value]` pairs) or from JSON dumps (`{first, second}` objects). Indices past the
+//! end of a table resolve to `None`.
+
+use crate::animator::{Fields, array, float};
+use crate::asset::SerializedFile;
+use crate::error::{BinaryError, Result};
+use crate::unity_objects::{ObjectRef, parse_pptr};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use unity_asset_core::{UnityValue, class_ids};
+
+/// A cell position `[x, y, z]`.
+pub type CellPosition = [i32; 3];
+
+const IDENTITY: [f32; 16] = [
+    1.0, 0.0, 0.0, 0.0, //
+    0.0, 1.0, 0.0, 0.0, //
+    0.0, 0.0, 1.0, 0.0, //
+    0.0, 0.0, 0.0, 1.0,
+];
+
+/// One occupied cell.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TileCell {
+    pub position: CellPosition,
+    /// Index into [`Tilemap::tile_assets`].
+    pub tile_index: Option<usize>,
+    /// TileBase asset (usually a `Tile` MonoBehaviour).
+    pub tile: Option<ObjectRef>,
+    pub sprite: Option<ObjectRef>,
+    pub color: [f32; 4],
+    /// Row-major 4x4 transform of the tile within its cell.
+    pub matrix: [f32; 16],
+    /// `TileFlags` (lock color/transform, instantiate game object, ...).
+    pub flags: u32,
+}
+
+impl TileCell {
+    /// Whether the tile is mirrored horizontally / vertically by its matrix.
+    pub fn flips(&self) -> (bool, bool) {
+        (self.matrix[0] < 0.0, self.matrix[5] < 0.0)
+    }
+}
+
+/// A parsed Tilemap component.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Tilemap {
+    pub game_object: Option<ObjectRef>,
+    /// `m_Origin` / `m_Size`: the bounds Unity allocated, which may exceed the used cells.
+    pub origin: CellPosition,
+    pub size: CellPosition,
+    pub tile_anchor: [f32; 3],
+    pub color: [f32; 4],
+    /// `m_TileAssetArray` in index order (`None` for freed slots).
+    pub tile_assets: Vec<Option<ObjectRef>>,
+    /// Occupied cells by position, serialized as a list.
+    #[serde(with = "cell_list")]
+    pub cells: BTreeMap<CellPosition, TileCell>,
+}
+
+impl Tilemap {
+    /// Parse Tilemap from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        let Some(UnityValue::Array(tiles)) = properties.get("m_Tiles") else {
+            return Err(BinaryError::invalid_data("Tilemap has no m_Tiles"));
+        };
+
+        let tile_assets: Vec<Option<ObjectRef>> = table(properties, "m_TileAssetArray")
+            .map(|data| data.and_then(parse_pptr))
+            .collect();
+        let sprites: Vec<Option<ObjectRef>> = table(properties, "m_TileSpriteArray")
+            .map(|data| data.and_then(parse_pptr))
+            .collect();
+        let matrices: Vec<[f32; 16]> = table(properties, "m_TileMatrixArray")
+            .map(|data| {
+                data.and_then(UnityValue::as_object)
+                    .map_or(IDENTITY, matrix)
+            })
+            .collect();
+        let colors: Vec<[f32; 4]> = table(properties, "m_TileColorArray")
+            .map(|data| data.and_then(UnityValue::as_object).map_or([1.0; 4], color))
+            .collect();
+
+        let mut cells = BTreeMap::new();
+        for pair in tiles {
+            let (key, value) = match pair {
+                UnityValue::Array(kv) if kv.len() == 2 => (&kv[0], &kv[1]),
+                UnityValue::Object(obj) => match (obj.get("first"), obj.get("second")) {
+                    (Some(key), Some(value)) => (key, value),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            let (Some(key), Some(tile)) = (key.as_object(), value.as_object()) else {
+                continue;
+            };
+            let position = position(key);
+            let tile_index = index(tile, "m_TileIndex").filter(|&i| i < tile_assets.len());
+            let lookup = |key: &str, len: usize| index(tile, key).filter(|&i| i < len);
+            cells.insert(
+                position,
+                TileCell {
+                    position,
+                    tile_index,
+                    tile: tile_index.and_then(|i| tile_assets[i]),
+                    sprite: lookup("m_TileSpriteIndex", sprites.len()).and_then(|i| sprites[i]),
+                    color: lookup("m_TileColorIndex", colors.len()).map_or([1.0; 4], |i| colors[i]),
+                    matrix: lookup("m_TileMatrixIndex", matrices.len())
+                        .map_or(IDENTITY, |i| matrices[i]),
+                    flags: tile
+                        .get("m_AllTileFlags")
+                        .or_else(|| tile.get("m_TileFlags"))
+                        .and_then(UnityValue::as_i64)
+                        .unwrap_or(0) as u32,
+                },
+            );
+        }
+
+        Ok(Self {
+            game_object: properties.get("m_GameObject").and_then(parse_pptr),
+            origin: properties
+                .get("m_Origin")
+                .and_then(UnityValue::as_object)
+                .map_or([0; 3], position),
+            size: properties
+                .get("m_Size")
+                .and_then(UnityValue::as_object)
+                .map_or([0; 3], position),
+            tile_anchor: properties
+                .get("m_TileAnchor")
+                .and_then(UnityValue::as_object)
+                .map_or([0.5, 0.5, 0.0], |v| {
+                    [float(v, "x"), float(v, "y"), float(v, "z")]
+                }),
+            color: properties
+                .get("m_Color")
+                .and_then(UnityValue::as_object)
+                .map_or([1.0; 4], color),
+            tile_assets,
+            cells,
+        })
+    }
+
+    pub fn cell(&self, position: CellPosition) -> Option<&TileCell> {
+        self.cells.get(&position)
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Inclusive `(min, max)` positions of the occupied cells.
+    pub fn used_bounds(&self) -> Option<(CellPosition, CellPosition)> {
+        let mut cells = self.cells.keys();
+        let first = *cells.next()?;
+        Some(cells.fold((first, first), |(mut min, mut max), p| {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(p[axis]);
+                max[axis] = max[axis].max(p[axis]);
+            }
+            (min, max)
+        }))
+    }
+
+    /// Tile asset indices of layer `z` over the used x/y bounds, top row (highest y) first.
+    pub fn index_grid(&self, z: i32) -> Vec<Vec<Option<usize>>> {
+        let Some((min, max)) = self.used_bounds() else {
+            return Vec::new();
+        };
+        (min[1]..=max[1])
+            .rev()
+            .map(|y| {
+                (min[0]..=max[0])
+                    .map(|x| self.cell([x, y, z]).and_then(|c| c.tile_index))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// [`Tilemap::index_grid`] as CSV, with `-1` for empty cells.
+    pub fn to_csv(&self, z: i32) -> String {
+        let mut csv = String::new();
+        for row in self.index_grid(z) {
+            let line: Vec<String> = row
+                .iter()
+                .map(|i| i.map_or("-1".to_string(), |i| i.to_string()))
+                .collect();
+            let _ = writeln!(csv, "{}", line.join(","));
+        }
+        csv
+    }
+
+    /// Layer `z` as JSON: the used bounds, the tile asset table and the index grid.
+    pub fn grid_json(&self, z: i32) -> serde_json::Value {
+        let (min, max) = self.used_bounds().unwrap_or_default();
+        serde_json::json!({
+            "origin": [min[0], min[1], z],
+            "width": if self.cells.is_empty() { 0 } else { max[0] - min[0] + 1 },
+            "height": if self.cells.is_empty() { 0 } else { max[1] - min[1] + 1 },
+            "tiles": self.tile_assets,
+            "rows": self.index_grid(z),
+        })
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// `GridLayout.CellLayout`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CellLayout {
+    #[default]
+    Rectangle,
+    Hexagon,
+    Isometric,
+    IsometricZAsY,
+    Unknown(i32),
+}
+
+impl CellLayout {
+    pub fn from_i32(value: i32) -> Self {
+        match value {
+            0 => Self::Rectangle,
+            1 => Self::Hexagon,
+            2 => Self::Isometric,
+            3 => Self::IsometricZAsY,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+/// A parsed Grid component.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Grid {
+    pub cell_size: [f32; 3],
+    pub cell_gap: [f32; 3],
+    pub cell_layout: CellLayout,
+    /// `GridLayout.CellSwizzle` (`0` is XYZ).
+    pub cell_swizzle: i32,
+}
+
+impl Grid {
+    /// Parse Grid from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        let vec3 = |key: &str, default: f32| {
+            properties
+                .get(key)
+                .and_then(UnityValue::as_object)
+                .map_or([default; 3], |v| {
+                    [float(v, "x"), float(v, "y"), float(v, "z")]
+                })
+        };
+        Ok(Self {
+            cell_size: vec3("m_CellSize", 1.0),
+            cell_gap: vec3("m_CellGap", 0.0),
+            cell_layout: CellLayout::from_i32(int(properties, "m_CellLayout")),
+            cell_swizzle: int(properties, "m_CellSwizzle"),
+        })
+    }
+}
+
+/// The Grid on the parent GameObject of `tilemap`, when both are stored in `asset`.
+pub fn tilemap_grid(asset: &SerializedFile, tilemap: &Tilemap) -> Option<Grid> {
+    let game_object = local_fields(asset, tilemap.game_object?)?;
+    let transform = components(&game_object).find_map(|c| {
+        let fields = local_fields(asset, c)?;
+        fields.contains_key("m_Father").then_some(fields)
+    })?;
+    let parent = local_fields(asset, transform.get("m_Father").and_then(parse_pptr)?)?;
+    let parent_object = local_fields(asset, parent.get("m_GameObject").and_then(parse_pptr)?)?;
+    components(&parent_object).find_map(|c| {
+        let handle = asset
+            .find_object_handle(c.path_id)
+            .filter(|h| c.file_id == 0 && h.class_id() == class_ids::GRID)?;
+        Grid::from_typetree(handle.read().ok()?.class.properties()).ok()
+    })
+}
+
+/// `m_Component` entries: `{ component: PPtr }` since 5.5, `{ first: classID, second: PPtr }`
+/// before.
+fn components(game_object: &Fields) -> impl Iterator<Item = ObjectRef> + '_ {
+    array(game_object, "m_Component").filter_map(|entry| {
+        entry
+            .get("component")
+            .or_else(|| entry.get("second"))
+            .and_then(parse_pptr)
+    })
+}
+
+fn local_fields(asset: &SerializedFile, target: ObjectRef) -> Option<Fields> {
+    if target.file_id != 0 {
+        return None;
+    }
+    let object = asset.find_object_handle(target.path_id)?.read().ok()?;
+    Some(object.class.properties().clone())
+}
+
+/// `m_Data` of each entry of a reference-counted tile table, keeping slot positions.
+fn table<'a>(
+    properties: &'a Fields,
+    key: &str,
+) -> impl Iterator<Item = Option<&'a UnityValue>> + 'a {
+    let items: &[UnityValue] = match properties.get(key) {
+        Some(UnityValue::Array(items)) => items,
+        _ => &[],
+    };
+    items
+        .iter()
+        .map(|item| item.as_object().and_then(|entry| entry.get("m_Data")))
+}
+
+fn index(tile: &Fields, key: &str) -> Option<usize> {
+    let value = tile.get(key)?.as_i64()?;
+    // 2019+ writes UInt16 0xFFFF for "no entry", 2017 writes -1.
+    (value >= 0 && value != 0xFFFF).then_some(value as usize)
+}
+
+fn position(v: &Fields) -> CellPosition {
+    let axis = |short: &str, long: &str| {
+        v.get(short)
+            .or_else(|| v.get(long))
+            .and_then(UnityValue::as_i64)
+            .unwrap_or(0) as i32
+    };
+    [axis("x", "m_X"), axis("y", "m_Y"), axis("z", "m_Z")]
+}
+
+fn matrix(m: &Fields) -> [f32; 16] {
+    let mut out = IDENTITY;
+    for row in 0..4 {
+        for col in 0..4 {
+            if let Some(v) = m.get(&format!("e{row}{col}")).and_then(UnityValue::as_f64) {
+                out[row * 4 + col] = v as f32;
+            }
+        }
+    }
+    out
+}
+
+fn color(c: &Fields) -> [f32; 4] {
+    [
+        float(c, "r"),
+        float(c, "g"),
+        float(c, "b"),
+        c.get("a").and_then(UnityValue::as_f64).unwrap_or(1.0) as f32,
+    ]
+}
+
+fn int(fields: &Fields, key: &str) -> i32 {
+    fields.get(key).and_then(UnityValue::as_i64).unwrap_or(0) as i32
+}
+
+mod cell_list {
+    use super::{CellPosition, TileCell};
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::collections::BTreeMap;
+
+    pub fn serialize<S: Serializer>(
+        cells: &BTreeMap<CellPosition, TileCell>,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(cells.values())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<BTreeMap<CellPosition, TileCell>, D::Error> {
+        let cells = Vec::<TileCell>::deserialize(deserializer)?;
+        Ok(cells.into_iter().map(|c| (c.position, c)).collect())
+    }
+}
@@ -1,7 +1,9 @@
 use crate::typetree::{TypeTree, TypeTreeNode, TypeTreeRegistry, TypeTreeSerializationMode};
 use serde::{Deserialize, Deserializer};
 use std::collections::{HashMap, HashSet};
-use std::fs::{self, File};
+#[cfg(feature = "fs")]
+use std::fs;
+use std::fs::File;
 use std::io::{self, BufReader, Read};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
@@ -159,6 +161,7 @@ impl AssetRipperTypeTreeGeneratorRegistry {
         }
     }
 
+    #[cfg(feature = "fs")]
     pub fn new_from_path(
         path: impl AsRef<Path>,
     ) -> Result<Self, AssetRipperTypeTreeGeneratorRegistryError> {
@@ -175,6 +178,7 @@ impl AssetRipperTypeTreeGeneratorRegistry {
         Ok(registry)
     }
 
+    #[cfg(feature = "fs")]
     pub fn add_via_path(
         &mut self,
         path: impl AsRef<Path>,
@@ -234,6 +238,7 @@ impl AssetRipperTypeTreeGeneratorRegistry {
         Ok(true)
     }
 
+    #[cfg(feature = "fs")]
     fn index_directory(
         &mut self,
         directory: &Path,
@@ -0,0 +1,567 @@
+//! Harvested TypeTree database
+//!
+//! A build made with TypeTrees enabled carries the layout of every serialized class; later
+//! builds of the same project are often stripped. [`TypeTreeDatabase`] collects the trees of
+//! the good build keyed by the hashes Unity keeps even in stripped files (`old_type_hash`, plus
+//! `script_id` for script types), saves them in a compact versioned file, and serves them back
+//! as a [`TypeTreeRegistry`] so the stripped build parses with the harvested layouts.
+//!
+//! File layout (little endian): `UTDB`, format version (`u32`), uncompressed payload size
+//! (`u32`), then the LZ4 block-compressed payload: a string table followed by the entries, each
+//! with its key, Unity version and the tree nodes in pre-order.
+
+use crate::asset::{SerializedFile, SerializedType};
+use crate::compression::{self, CompressionType};
+use crate::error::{BinaryError, Result};
+use crate::reader::{BinaryReader, ByteOrder};
+use crate::typetree::{TpkTypeTreeRegistry, TypeTree, TypeTreeNode, TypeTreeRegistry};
+use crate::unity_version::UnityVersion;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "fs")]
+use std::path::Path;
+use std::sync::Arc;
+
+const MAGIC: &[u8; 4] = b"UTDB";
+const FORMAT_VERSION: u32 = 1;
+/// Upper bound for the decompressed payload.
+const MAX_PAYLOAD_SIZE: usize = 512 * 1024 * 1024;
+/// Deepest node nesting accepted when reading (Unity trees stay far below this).
+const MAX_NODE_DEPTH: usize = 256;
+
+/// Identity of a harvested tree: the class and the hashes of its `SerializedType`.
+///
+/// Trees imported without hashes (e.g. from a TPK) use zero hashes and are only found by
+/// class ID and Unity version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TypeTreeKey {
+    pub class_id: i32,
+    /// Script hash (`SerializedType::script_id`); zero for non-script types.
+    pub script_id: [u8; 16],
+    /// Layout hash (`SerializedType::old_type_hash`).
+    pub type_hash: [u8; 16],
+}
+
+impl TypeTreeKey {
+    /// Key of a `SerializedType`.
+    pub fn of(typ: &SerializedType) -> Self {
+        Self {
+            class_id: typ.class_id,
+            script_id: typ.script_id,
+            type_hash: typ.old_type_hash,
+        }
+    }
+
+    fn has_hash(&self) -> bool {
+        self.type_hash != [0u8; 16]
+    }
+
+    fn has_script(&self) -> bool {
+        self.script_id != [0u8; 16]
+    }
+}
+
+/// A stored tree and the Unity version it was collected from.
+#[derive(Debug, Clone)]
+pub struct TypeTreeDatabaseEntry {
+    pub unity_version: String,
+    pub tree: Arc<TypeTree>,
+}
+
+/// Two different trees collected for the same key.
+///
+/// The tree from the newer Unity version is kept (the existing one on a tie).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeTreeCollision {
+    pub key: TypeTreeKey,
+    pub kept_version: String,
+    pub dropped_version: String,
+}
+
+/// TypeTrees harvested from builds, usable as a registry for stripped builds.
+#[derive(Debug, Clone, Default)]
+pub struct TypeTreeDatabase {
+    entries: BTreeMap<TypeTreeKey, TypeTreeDatabaseEntry>,
+    collisions: Vec<TypeTreeCollision>,
+}
+
+impl TypeTreeDatabase {
+    /// Create an empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collect the trees of one serialized file.
+    pub fn collect_from(asset: &SerializedFile) -> Self {
+        let mut database = Self::new();
+        database.collect(asset);
+        database
+    }
+
+    /// Add the trees of `asset` (types and SerializeReference types); returns how many keys
+    /// were new.
+    ///
+    /// Files without TypeTrees contribute nothing.
+    pub fn collect(&mut self, asset: &SerializedFile) -> usize {
+        if !asset.enable_type_tree {
+            return 0;
+        }
+        let mut added = 0;
+        for typ in asset.types.iter().chain(asset.ref_types.iter()) {
+            if typ.type_tree.is_empty() {
+                continue;
+            }
+            let key = TypeTreeKey::of(typ);
+            let is_new = !self.entries.contains_key(&key);
+            self.insert(key, &asset.unity_version, typ.type_tree.clone());
+            if is_new {
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Store `tree` under `key`.
+    ///
+    /// An identical tree only bumps the recorded version to the newer one. A different tree
+    /// replaces the stored one only if `unity_version` is newer; either way the conflict is
+    /// recorded in [`collisions`](Self::collisions).
+    pub fn insert(&mut self, key: TypeTreeKey, unity_version: &str, tree: TypeTree) {
+        let Some(existing) = self.entries.get_mut(&key) else {
+            self.entries.insert(
+                key,
+                TypeTreeDatabaseEntry {
+                    unity_version: unity_version.to_string(),
+                    tree: Arc::new(tree),
+                },
+            );
+            return;
+        };
+
+        let newer = compare_versions(unity_version, &existing.unity_version) == Ordering::Greater;
+        if same_nodes(&existing.tree.nodes, &tree.nodes) {
+            if newer {
+                existing.unity_version = unity_version.to_string();
+            }
+            return;
+        }
+
+        let (kept_version, dropped_version) = if newer {
+            let dropped = std::mem::replace(&mut existing.unity_version, unity_version.to_string());
+            existing.tree = Arc::new(tree);
+            (unity_version.to_string(), dropped)
+        } else {
+            (existing.unity_version.clone(), unity_version.to_string())
+        };
+        self.collisions.push(TypeTreeCollision {
+            key,
+            kept_version,
+            dropped_version,
+        });
+    }
+
+    /// Import the trees a TPK registry holds for `unity_version`; returns how many were added.
+    ///
+    /// TPK trees have no hashes, so they are only used by class ID and exact Unity version.
+    pub fn import_tpk(&mut self, tpk: &TpkTypeTreeRegistry, unity_version: &str) -> usize {
+        let mut added = 0;
+        for class_id in tpk.class_ids() {
+            let Some(tree) = tpk.resolve(unity_version, class_id) else {
+                continue;
+            };
+            let key = TypeTreeKey {
+                class_id,
+                script_id: [0; 16],
+                type_hash: [0; 16],
+            };
+            if !self.entries.contains_key(&key) {
+                added += 1;
+            }
+            self.insert(key, unity_version, (*tree).clone());
+        }
+        added
+    }
+
+    pub fn get(&self, key: &TypeTreeKey) -> Option<&TypeTreeDatabaseEntry> {
+        self.entries.get(key)
+    }
+
+    /// Entries in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&TypeTreeKey, &TypeTreeDatabaseEntry)> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Conflicting trees seen while collecting or importing.
+    pub fn collisions(&self) -> &[TypeTreeCollision] {
+        &self.collisions
+    }
+
+    /// Serialize to the database file format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut strings = StringTable::default();
+        let mut body = Vec::new();
+        for (key, entry) in &self.entries {
+            body.extend_from_slice(&key.class_id.to_le_bytes());
+            body.extend_from_slice(&key.script_id);
+            body.extend_from_slice(&key.type_hash);
+            body.extend_from_slice(&strings.index(&entry.unity_version).to_le_bytes());
+            let tree = &entry.tree;
+            body.extend_from_slice(&tree.version.to_le_bytes());
+            body.extend_from_slice(&tree.platform.to_le_bytes());
+            body.push(tree.has_type_dependencies as u8);
+            body.extend_from_slice(&(tree.nodes.len() as u32).to_le_bytes());
+            for node in &tree.nodes {
+                write_node(node, &mut strings, &mut body);
+            }
+        }
+
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&(strings.strings.len() as u32).to_le_bytes());
+        for s in &strings.strings {
+            payload.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            payload.extend_from_slice(s.as_bytes());
+        }
+        payload.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        payload.extend_from_slice(&body);
+
+        let mut out = Vec::with_capacity(payload.len() / 2 + 12);
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        out.extend_from_slice(&lz4_flex::block::compress(&payload));
+        out
+    }
+
+    /// Parse the database file format.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut header = BinaryReader::new(data, ByteOrder::Little);
+        if header.read_bytes(4)? != MAGIC {
+            return Err(BinaryError::invalid_data("Not a TypeTree database"));
+        }
+        let version = header.read_u32()?;
+        if version != FORMAT_VERSION {
+            return Err(BinaryError::unsupported(format!(
+                "Unsupported TypeTree database version: {}",
+                version
+            )));
+        }
+        let size = header.read_u32()? as usize;
+        if size > MAX_PAYLOAD_SIZE {
+            return Err(BinaryError::ResourceLimitExceeded(format!(
+                "TypeTree database payload of {} bytes exceeds {} bytes",
+                size, MAX_PAYLOAD_SIZE
+            )));
+        }
+        let payload =
+            compression::decompress(header.remaining_slice(), CompressionType::Lz4, size)?;
+        let mut r = BinaryReader::new(&payload, ByteOrder::Little);
+
+        let string_count = r.read_u32()? as usize;
+        let mut strings = Vec::with_capacity(string_count.min(r.remaining() / 4));
+        for _ in 0..string_count {
+            let len = r.read_u32()? as usize;
+            let bytes = r.read_bytes(len)?;
+            strings.push(String::from_utf8(bytes).map_err(|_| {
+                BinaryError::invalid_data("Invalid UTF-8 in TypeTree database strings")
+            })?);
+        }
+
+        let entry_count = r.read_u32()? as usize;
+        let mut database = Self::new();
+        for _ in 0..entry_count {
+            let class_id = r.read_i32()?;
+            let script_id = read_hash(&mut r)?;
+            let type_hash = read_hash(&mut r)?;
+            let unity_version = lookup(&strings, r.read_u32()?)?.to_string();
+            let mut tree = TypeTree::new();
+            tree.version = r.read_u32()?;
+            tree.platform = r.read_u32()?;
+            tree.has_type_dependencies = r.read_u8()? != 0;
+            let root_count = r.read_u32()?;
+            let mut index = 0;
+            for _ in 0..root_count {
+                tree.nodes.push(read_node(&mut r, &strings, 0, &mut index)?);
+            }
+            database.entries.insert(
+                TypeTreeKey {
+                    class_id,
+                    script_id,
+                    type_hash,
+                },
+                TypeTreeDatabaseEntry {
+                    unity_version,
+                    tree: Arc::new(tree),
+                },
+            );
+        }
+        Ok(database)
+    }
+
+    /// Write the database to `path`.
+    #[cfg(feature = "fs")]
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path.as_ref(), self.to_bytes()).map_err(|e| {
+            BinaryError::generic(format!(
+                "Failed to write TypeTree database {:?}: {}",
+                path.as_ref(),
+                e
+            ))
+        })
+    }
+
+    /// Read a database written by [`save`](Self::save).
+    #[cfg(feature = "fs")]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let data = std::fs::read(path.as_ref()).map_err(|e| {
+            BinaryError::generic(format!(
+                "Failed to read TypeTree database {:?}: {}",
+                path.as_ref(),
+                e
+            ))
+        })?;
+        Self::from_bytes(&data)
+    }
+
+    /// Tree for `class_id` (and `script_id`, if non-zero) collected from exactly `unity_version`.
+    fn resolve_by_version(
+        &self,
+        unity_version: &str,
+        class_id: i32,
+        script_id: [u8; 16],
+    ) -> Option<Arc<TypeTree>> {
+        self.entries
+            .iter()
+            .find(|(key, entry)| {
+                key.class_id == class_id
+                    && key.script_id == script_id
+                    && entry.unity_version == unity_version
+            })
+            .map(|(_, entry)| entry.tree.clone())
+    }
+}
+
+impl TypeTreeRegistry for TypeTreeDatabase {
+    fn resolve(&self, unity_version: &str, class_id: i32) -> Option<Arc<TypeTree>> {
+        self.resolve_by_version(unity_version, class_id, [0; 16])
+    }
+
+    fn resolve_script(
+        &self,
+        unity_version: &str,
+        class_id: i32,
+        script_id: [u8; 16],
+    ) -> Option<Arc<TypeTree>> {
+        self.resolve_by_version(unity_version, class_id, script_id)
+    }
+
+    fn resolve_hashed(
+        &self,
+        _unity_version: &str,
+        class_id: i32,
+        script_id: [u8; 16],
+        type_hash: [u8; 16],
+    ) -> Option<Arc<TypeTree>> {
+        let key = TypeTreeKey {
+            class_id,
+            script_id,
+            type_hash,
+        };
+        if !key.has_hash() && !key.has_script() {
+            return None;
+        }
+        self.entries.get(&key).map(|entry| entry.tree.clone())
+    }
+}
+
+/// Unparseable versions sort before parseable ones, then by text.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    match (
+        UnityVersion::parse_version(a),
+        UnityVersion::parse_version(b),
+    ) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Greater,
+        (Err(_), Ok(_)) => Ordering::Less,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+/// Whether two node lists describe the same layout (string offsets and indices ignored).
+fn same_nodes(a: &[TypeTreeNode], b: &[TypeTreeNode]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| {
+            a.type_name == b.type_name
+                && a.name == b.name
+                && a.byte_size == b.byte_size
+                && a.variable_count == b.variable_count
+                && a.type_flags == b.type_flags
+                && a.version == b.version
+                && a.meta_flags == b.meta_flags
+                && a.ref_type_hash == b.ref_type_hash
+                && same_nodes(&a.children, &b.children)
+        })
+}
+
+#[derive(Default)]
+struct StringTable {
+    strings: Vec<String>,
+    indices: HashMap<String, u32>,
+}
+
+impl StringTable {
+    fn index(&mut self, s: &str) -> u32 {
+        if let Some(&index) = self.indices.get(s) {
+            return index;
+        }
+        let index = self.strings.len() as u32;
+        self.strings.push(s.to_string());
+        self.indices.insert(s.to_string(), index);
+        index
+    }
+}
+
+fn write_node(node: &TypeTreeNode, strings: &mut StringTable, out: &mut Vec<u8>) {
+    out.extend_from_slice(&strings.index(&node.type_name).to_le_bytes());
+    out.extend_from_slice(&strings.index(&node.name).to_le_bytes());
+    out.extend_from_slice(&node.byte_size.to_le_bytes());
+    out.extend_from_slice(&node.variable_count.to_le_bytes());
+    out.extend_from_slice(&node.type_flags.to_le_bytes());
+    out.extend_from_slice(&node.version.to_le_bytes());
+    out.extend_from_slice(&node.meta_flags.to_le_bytes());
+    out.extend_from_slice(&node.ref_type_hash.to_le_bytes());
+    out.extend_from_slice(&(node.children.len() as u32).to_le_bytes());
+    for child in &node.children {
+        write_node(child, strings, out);
+    }
+}
+
+fn read_node(
+    r: &mut BinaryReader<'_>,
+    strings: &[String],
+    level: usize,
+    index: &mut i32,
+) -> Result<TypeTreeNode> {
+    if level >= MAX_NODE_DEPTH {
+        return Err(BinaryError::invalid_data(format!(
+            "TypeTree database nodes nested deeper than {}",
+            MAX_NODE_DEPTH
+        )));
+    }
+    let mut node = TypeTreeNode::new();
+    node.type_name = lookup(strings, r.read_u32()?)?.to_string();
+    node.name = lookup(strings, r.read_u32()?)?.to_string();
+    node.byte_size = r.read_i32()?;
+    node.variable_count = r.read_i32()?;
+    node.type_flags = r.read_i32()?;
+    node.version = r.read_i32()?;
+    node.meta_flags = r.read_i32()?;
+    node.ref_type_hash = r.read_u64()?;
+    node.level = level as i32;
+    node.index = *index;
+    *index += 1;
+    let child_count = r.read_u32()? as usize;
+    node.children = Vec::with_capacity(child_count.min(r.remaining() / 36));
+    for _ in 0..child_count {
+        node.children.push(read_node(r, strings, level + 1, index)?);
+    }
+    Ok(node)
+}
+
+fn read_hash(r: &mut BinaryReader<'_>) -> Result<[u8; 16]> {
+    let mut hash = [0u8; 16];
+    hash.copy_from_slice(r.read_bytes_ref(16)?);
+    Ok(hash)
+}
+
+fn lookup(strings: &[String], index: u32) -> Result<&str> {
+    strings
+        .get(index as usize)
+        .map(String::as_str)
+        .ok_or_else(|| {
+            BinaryError::invalid_data(format!("TypeTree database string {} out of range", index))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(field: &str) -> TypeTree {
+        let mut root = TypeTreeNode::with_info("Texture2D".to_string(), "Base".to_string(), -1);
+        root.children.push(TypeTreeNode::with_info(
+            "int".to_string(),
+            field.to_string(),
+            4,
+        ));
+        let mut tree = TypeTree::new();
+        tree.nodes.push(root);
+        tree
+    }
+
+    fn key(hash: u8) -> TypeTreeKey {
+        TypeTreeKey {
+            class_id: 28,
+            script_id: [0; 16],
+            type_hash: [hash; 16],
+        }
+    }
+
+    #[test]
+    fn collisions_keep_the_newest_version() {
+        let mut db = TypeTreeDatabase::new();
+        db.insert(key(1), "2020.3.1f1", tree("m_Width"));
+        db.insert(key(1), "2021.3.5f1", tree("m_Width"));
+        assert!(db.collisions().is_empty());
+        assert_eq!(db.get(&key(1)).unwrap().unity_version, "2021.3.5f1");
+
+        db.insert(key(1), "2019.4.0f1", tree("m_Old"));
+        db.insert(key(1), "2022.3.0f1", tree("m_New"));
+        let entry = db.get(&key(1)).unwrap();
+        assert_eq!(entry.unity_version, "2022.3.0f1");
+        assert_eq!(entry.tree.nodes[0].children[0].name, "m_New");
+        assert_eq!(
+            db.collisions(),
+            [
+                TypeTreeCollision {
+                    key: key(1),
+                    kept_version: "2021.3.5f1".to_string(),
+                    dropped_version: "2019.4.0f1".to_string(),
+                },
+                TypeTreeCollision {
+                    key: key(1),
+                    kept_version: "2022.3.0f1".to_string(),
+                    dropped_version: "2021.3.5f1".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn bytes_round_trip_and_reject_bad_input() {
+        let mut db = TypeTreeDatabase::new();
+        db.insert(key(1), "2020.3.1f1", tree("m_Width"));
+        db.insert(key(2), "2020.3.1f1", tree("m_Height"));
+        let bytes = db.to_bytes();
+
+        let loaded = TypeTreeDatabase::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.len(), 2);
+        let entry = loaded.get(&key(2)).unwrap();
+        assert!(same_nodes(&entry.tree.nodes, &tree("m_Height").nodes));
+        assert_eq!(entry.tree.nodes[0].children[0].level, 1);
+        assert_eq!(entry.tree.nodes[0].children[0].index, 1);
+
+        assert!(TypeTreeDatabase::from_bytes(b"NOPE").is_err());
+        let mut future = bytes.clone();
+        future[4] = 9;
+        assert!(TypeTreeDatabase::from_bytes(&future).is_err());
+        assert!(TypeTreeDatabase::from_bytes(&bytes[..bytes.len() - 3]).is_err());
+    }
+}
@@ -0,0 +1,451 @@
+//! TypeTree diffs between two builds
+//!
+//! Game updates change serialized layouts without notice. [`diff`] compares two versions of one
+//! class and reports field changes keyed by field path (`stats.reward.count`, with `Array.data`
+//! segments inside arrays); [`diff_files`] does it for every type two SerializedFiles share.
+//!
+//! # Matching
+//!
+//! Siblings are matched by name first. Of the rest, a removed and an added field are reported as
+//! a rename when they sit at the same position, i.e. between the same matched neighbours and at
+//! the same index within that gap, and have the same type and byte size. Anything else is a
+//! removal plus an addition. Matched fields report type changes, size changes (only when the type
+//! is unchanged) and moves (fields outside the longest run that kept its relative order).
+
+use crate::asset::SerializedFile;
+use crate::typetree::{TypeTree, TypeTreeNode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// One field-level change; paths are relative to the root node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FieldChange {
+    Added {
+        path: String,
+        type_name: String,
+        byte_size: i32,
+    },
+    Removed {
+        path: String,
+        type_name: String,
+        byte_size: i32,
+    },
+    Renamed {
+        path: String,
+        new_path: String,
+        type_name: String,
+        byte_size: i32,
+    },
+    TypeChanged {
+        path: String,
+        old_type: String,
+        new_type: String,
+    },
+    SizeChanged {
+        path: String,
+        old_size: i32,
+        new_size: i32,
+    },
+    /// Sibling order changed; indices are positions among the parent's children.
+    Moved {
+        path: String,
+        old_index: usize,
+        new_index: usize,
+    },
+}
+
+impl FieldChange {
+    /// Path of the field in the old tree (the new path for additions).
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Added { path, .. }
+            | Self::Removed { path, .. }
+            | Self::Renamed { path, .. }
+            | Self::TypeChanged { path, .. }
+            | Self::SizeChanged { path, .. }
+            | Self::Moved { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added {
+                path,
+                type_name,
+                byte_size,
+            } => write!(f, "+ {}: {} ({})", path, type_name, byte_size),
+            Self::Removed {
+                path,
+                type_name,
+                byte_size,
+            } => write!(f, "- {}: {} ({})", path, type_name, byte_size),
+            Self::Renamed {
+                path,
+                new_path,
+                type_name,
+                byte_size,
+            } => write!(
+                f,
+                "~ {} -> {}: {} ({}) renamed",
+                path, new_path, type_name, byte_size
+            ),
+            Self::TypeChanged {
+                path,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "~ {}: type {} -> {}",
+                display_path(path),
+                old_type,
+                new_type
+            ),
+            Self::SizeChanged {
+                path,
+                old_size,
+                new_size,
+            } => write!(f, "~ {}: size {} -> {}", path, old_size, new_size),
+            Self::Moved {
+                path,
+                old_index,
+                new_index,
+            } => write!(f, "~ {}: moved {} -> {}", path, old_index, new_index),
+        }
+    }
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() { "<root>" } else { path }
+}
+
+/// Changes between two versions of a TypeTree.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeTreeDiff {
+    pub changes: Vec<FieldChange>,
+}
+
+impl TypeTreeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Unified-diff style text with `---`/`+++` headers and one line per change.
+    pub fn to_text(&self, old_label: &str, new_label: &str) -> String {
+        let mut out = format!("--- {}\n+++ {}\n", old_label, new_label);
+        for change in &self.changes {
+            out.push_str(&change.to_string());
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+impl fmt::Display for TypeTreeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_text("a", "b"))
+    }
+}
+
+/// Compare two versions of a TypeTree.
+pub fn diff(a: &TypeTree, b: &TypeTree) -> TypeTreeDiff {
+    let mut changes = Vec::new();
+    match (a.nodes.as_slice(), b.nodes.as_slice()) {
+        ([a_root], [b_root]) => {
+            if a_root.type_name != b_root.type_name {
+                changes.push(FieldChange::TypeChanged {
+                    path: String::new(),
+                    old_type: a_root.type_name.clone(),
+                    new_type: b_root.type_name.clone(),
+                });
+            }
+            diff_children(&a_root.children, &b_root.children, "", &mut changes);
+        }
+        (a_roots, b_roots) => diff_children(a_roots, b_roots, "", &mut changes),
+    }
+    TypeTreeDiff { changes }
+}
+
+fn child_path(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent, name)
+    }
+}
+
+fn diff_children(a: &[TypeTreeNode], b: &[TypeTreeNode], parent: &str, out: &mut Vec<FieldChange>) {
+    // a index -> b index
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    let mut b_used = vec![false; b.len()];
+    let mut a_matched = vec![false; a.len()];
+    for (i, node) in a.iter().enumerate() {
+        if let Some(j) = (0..b.len()).find(|&j| !b_used[j] && b[j].name == node.name) {
+            b_used[j] = true;
+            a_matched[i] = true;
+            pairs.push((i, j));
+        }
+    }
+
+    // Renames: the k-th unmatched field of a gap on both sides, with the same type and size.
+    let anchors = |matched: &[bool]| -> Vec<Option<usize>> {
+        let mut last = None;
+        matched
+            .iter()
+            .enumerate()
+            .map(|(i, &m)| {
+                let gap = last;
+                if m {
+                    last = Some(i);
+                }
+                gap
+            })
+            .collect()
+    };
+    let b_to_a: BTreeMap<usize, usize> = pairs.iter().map(|&(i, j)| (j, i)).collect();
+    let a_gaps = anchors(&a_matched);
+    let b_gaps: Vec<Option<usize>> = anchors(&b_used)
+        .into_iter()
+        .map(|gap| gap.map(|j| b_to_a[&j]))
+        .collect();
+    let gap_members = |gaps: &[Option<usize>], matched: &[bool]| {
+        let mut members: BTreeMap<Option<usize>, Vec<usize>> = BTreeMap::new();
+        for (i, gap) in gaps.iter().enumerate() {
+            if !matched[i] {
+                members.entry(*gap).or_default().push(i);
+            }
+        }
+        members
+    };
+    let a_members = gap_members(&a_gaps, &a_matched);
+    let b_members = gap_members(&b_gaps, &b_used);
+    let mut renames: Vec<(usize, usize)> = Vec::new();
+    for (gap, removed) in &a_members {
+        let Some(added) = b_members.get(gap) else {
+            continue;
+        };
+        for (&i, &j) in removed.iter().zip(added) {
+            if a[i].type_name == b[j].type_name && a[i].byte_size == b[j].byte_size {
+                renames.push((i, j));
+            }
+        }
+    }
+    for &(i, j) in &renames {
+        a_matched[i] = true;
+        b_used[j] = true;
+    }
+
+    for (i, node) in a.iter().enumerate() {
+        if !a_matched[i] {
+            out.push(FieldChange::Removed {
+                path: child_path(parent, &node.name),
+                type_name: node.type_name.clone(),
+                byte_size: node.byte_size,
+            });
+        }
+    }
+
+    let mut all_pairs: Vec<(usize, usize)> = pairs.iter().chain(&renames).copied().collect();
+    all_pairs.sort_by_key(|&(_, j)| j);
+    let kept = in_order(&all_pairs);
+    let a_for_b: BTreeMap<usize, usize> = all_pairs.iter().map(|&(i, j)| (j, i)).collect();
+
+    for (j, new) in b.iter().enumerate() {
+        let Some(&i) = a_for_b.get(&j) else {
+            out.push(FieldChange::Added {
+                path: child_path(parent, &new.name),
+                type_name: new.type_name.clone(),
+                byte_size: new.byte_size,
+            });
+            continue;
+        };
+        let old = &a[i];
+        let path = child_path(parent, &old.name);
+        if old.name != new.name {
+            out.push(FieldChange::Renamed {
+                path: path.clone(),
+                new_path: child_path(parent, &new.name),
+                type_name: new.type_name.clone(),
+                byte_size: new.byte_size,
+            });
+        }
+        if old.type_name != new.type_name {
+            out.push(FieldChange::TypeChanged {
+                path: path.clone(),
+                old_type: old.type_name.clone(),
+                new_type: new.type_name.clone(),
+            });
+        } else if old.byte_size != new.byte_size {
+            out.push(FieldChange::SizeChanged {
+                path: path.clone(),
+                old_size: old.byte_size,
+                new_size: new.byte_size,
+            });
+        }
+        if !kept.contains(&(i, j)) {
+            out.push(FieldChange::Moved {
+                path: path.clone(),
+                old_index: i,
+                new_index: j,
+            });
+        }
+        diff_children(
+            &old.children,
+            &new.children,
+            &child_path(parent, &new.name),
+            out,
+        );
+    }
+}
+
+/// Pairs (sorted by new index) in the longest run whose old indices also increase.
+fn in_order(pairs: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let n = pairs.len();
+    let mut length = vec![1usize; n];
+    let mut previous = vec![None; n];
+    for k in 0..n {
+        for m in 0..k {
+            if pairs[m].0 < pairs[k].0 && length[m] + 1 > length[k] {
+                length[k] = length[m] + 1;
+                previous[k] = Some(m);
+            }
+        }
+    }
+    let mut kept = Vec::new();
+    let mut cursor = (0..n).max_by_key(|&k| (length[k], std::cmp::Reverse(k)));
+    while let Some(k) = cursor {
+        kept.push(pairs[k]);
+        cursor = previous[k];
+    }
+    kept
+}
+
+/// A serialized type, identified by class ID and (for scripts) script hash.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct DiffedType {
+    pub class_id: i32,
+    /// Hex script hash, for script types.
+    pub script_id: Option<String>,
+    /// Root type name of the tree.
+    pub name: String,
+}
+
+impl fmt::Display for DiffedType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (class {}", self.name, self.class_id)?;
+        if let Some(script) = &self.script_id {
+            write!(f, ", script {}", script)?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// A changed type shared by both files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangedType {
+    #[serde(flatten)]
+    pub ty: DiffedType,
+    pub diff: TypeTreeDiff,
+}
+
+/// TypeTree changes between two SerializedFiles.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileTypeTreeDiff {
+    /// Types in both files whose trees differ.
+    pub changed: Vec<ChangedType>,
+    /// Types only in the old file.
+    pub removed: Vec<DiffedType>,
+    /// Types only in the new file.
+    pub added: Vec<DiffedType>,
+}
+
+impl FileTypeTreeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty() && self.added.is_empty()
+    }
+
+    /// Unified-diff style text with one `@@` hunk per changed type.
+    pub fn to_text(&self, old_label: &str, new_label: &str) -> String {
+        let mut out = format!("--- {}\n+++ {}\n", old_label, new_label);
+        for ty in &self.removed {
+            out.push_str(&format!("- type {}\n", ty));
+        }
+        for ty in &self.added {
+            out.push_str(&format!("+ type {}\n", ty));
+        }
+        for changed in &self.changed {
+            out.push_str(&format!("@@ {} @@\n", changed.ty));
+            for change in &changed.diff.changes {
+                out.push_str(&change.to_string());
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// Compare the TypeTrees of every type in `old` and `new`, matched by class ID and script hash.
+///
+/// Types without a TypeTree (stripped files) are ignored.
+pub fn diff_files(old: &SerializedFile, new: &SerializedFile) -> FileTypeTreeDiff {
+    let old_trees = trees_by_type(old);
+    let new_trees = trees_by_type(new);
+
+    let mut result = FileTypeTreeDiff::default();
+    for (key, old_tree) in &old_trees {
+        match new_trees.get(key) {
+            Some(new_tree) => {
+                let diff = diff(old_tree, new_tree);
+                if !diff.is_empty() {
+                    result.changed.push(ChangedType {
+                        ty: diffed_type(*key, new_tree),
+                        diff,
+                    });
+                }
+            }
+            None => result.removed.push(diffed_type(*key, old_tree)),
+        }
+    }
+    for (key, new_tree) in &new_trees {
+        if !old_trees.contains_key(key) {
+            result.added.push(diffed_type(*key, new_tree));
+        }
+    }
+    result
+}
+
+fn trees_by_type(file: &SerializedFile) -> BTreeMap<(i32, [u8; 16]), &TypeTree> {
+    let mut trees = BTreeMap::new();
+    for typ in &file.types {
+        if !typ.type_tree.is_empty() {
+            trees
+                .entry((typ.class_id, typ.script_id))
+                .or_insert(&typ.type_tree);
+        }
+    }
+    trees
+}
+
+fn diffed_type((class_id, script_id): (i32, [u8; 16]), tree: &TypeTree) -> DiffedType {
+    DiffedType {
+        class_id,
+        script_id: (script_id != [0; 16])
+            .then(|| script_id.iter().map(|b| format!("{:02x}", b)).collect()),
+        name: tree
+            .nodes
+            .first()
+            .map(|root| root.type_name.clone())
+            .unwrap_or_default(),
+    }
+}
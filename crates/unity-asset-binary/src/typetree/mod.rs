@@ -40,6 +40,8 @@
 pub mod assetripper_typetree;
 pub mod builder;
 mod common_strings;
+pub mod database;
+pub mod diff;
 pub mod parser;
 pub mod registry;
 pub mod serializer;
@@ -51,6 +53,10 @@ pub use assetripper_typetree::{
     AssetRipperTypeTreeGeneratorRegistry, AssetRipperTypeTreeGeneratorRegistryError,
 };
 pub use builder::{TypeTreeBuilder, TypeTreeValidator, ValidationReport};
+pub use database::{TypeTreeCollision, TypeTreeDatabase, TypeTreeDatabaseEntry, TypeTreeKey};
+pub use diff::{
+    ChangedType, DiffedType, FieldChange, FileTypeTreeDiff, TypeTreeDiff, diff, diff_files,
+};
 pub use parser::{ParsingStats, TypeTreeParser};
 pub use registry::{
     CompositeTypeTreeRegistry, InMemoryTypeTreeRegistry, JsonTypeTreeRegistry,
@@ -58,8 +64,8 @@ pub use registry::{
     TypeTreeSerializationMode,
 };
 pub use serializer::{
-    PPtrScanResult, TypeTreeParseMode, TypeTreeParseOptions, TypeTreeParseOutput,
-    TypeTreeParseWarning, TypeTreeSerializer,
+    PPtrField, PPtrScanResult, StringField, TypeTreeParseMode, TypeTreeParseOptions,
+    TypeTreeParseOutput, TypeTreeParseWarning, TypeTreeSerializer,
 };
 pub use tpk::TpkTypeTreeRegistry;
 pub use types::{TypeInfo, TypeRegistry, TypeTree, TypeTreeNode, TypeTreeStatistics};
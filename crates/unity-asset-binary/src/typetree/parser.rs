@@ -343,6 +343,7 @@ impl TypeTreeParser {
 
 /// Parsing statistics
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ParsingStats {
     pub total_nodes: usize,
     pub root_nodes: usize,
@@ -10,6 +10,7 @@ use crate::{error::BinaryError, error::Result};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::io::Read;
+#[cfg(feature = "fs")]
 use std::path::Path;
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -51,6 +52,21 @@ pub trait TypeTreeRegistry: Send + Sync + std::fmt::Debug {
     ) -> Option<Arc<TypeTree>> {
         None
     }
+
+    /// Resolve a TypeTree by the hashes a `SerializedType` keeps even when stripped
+    /// (`old_type_hash`, plus `script_id` for script types).
+    ///
+    /// Tried before the version-based lookups; registries harvested from another build of the
+    /// same project (see [`TypeTreeDatabase`](crate::typetree::TypeTreeDatabase)) match here.
+    fn resolve_hashed(
+        &self,
+        _unity_version: &str,
+        _class_id: i32,
+        _script_id: [u8; 16],
+        _type_hash: [u8; 16],
+    ) -> Option<Arc<TypeTree>> {
+        None
+    }
 }
 
 /// A generator hook for script-specific TypeTrees (UnityPy `typetree_generator` equivalent).
@@ -191,6 +207,21 @@ impl TypeTreeRegistry for CompositeTypeTreeRegistry {
         }
         None
     }
+
+    fn resolve_hashed(
+        &self,
+        unity_version: &str,
+        class_id: i32,
+        script_id: [u8; 16],
+        type_hash: [u8; 16],
+    ) -> Option<Arc<TypeTree>> {
+        for r in &self.registries {
+            if let Some(t) = r.resolve_hashed(unity_version, class_id, script_id, type_hash) {
+                return Some(t);
+            }
+        }
+        None
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -444,6 +475,7 @@ impl JsonTypeTreeRegistry {
         Ok(Self { inner })
     }
 
+    #[cfg(feature = "fs")]
     pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
         let mut f = std::fs::File::open(path.as_ref()).map_err(|e| {
             BinaryError::generic(format!(
@@ -7,7 +7,10 @@ use super::types::{TypeTree, TypeTreeNode};
 use crate::asset::SerializedType;
 use crate::error::{BinaryError, Result};
 use crate::reader::{BinaryReader, ByteOrder};
+use crate::string_policy::{RawString, StringPolicy};
 use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::ops::Range;
 use unity_asset_core::UnityValue;
 
 /// TypeTree serializer
@@ -24,6 +27,32 @@ pub struct PPtrScanResult {
     pub external: Vec<(i32, i64)>,
 }
 
+/// A `string` field found by [`TypeTreeSerializer::scan_strings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringField {
+    /// Dotted field path from the root, with array indices (`m_Items[2].name`).
+    pub path: String,
+    /// UTF-8 (lossy) value, cut to the scan's length cap.
+    pub value: String,
+    /// The stored string was longer than the cap.
+    pub truncated: bool,
+    /// Byte range of the stored string (length, bytes and alignment padding) within the object data.
+    pub range: Range<usize>,
+}
+
+/// A `PPtr` field found by [`TypeTreeSerializer::scan_pptr_fields`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PPtrField {
+    /// Dotted field path from the root, with array indices (`m_Component[0].component`).
+    pub path: String,
+    pub file_id: i32,
+    pub path_id: i64,
+    /// Byte range of the stored `m_FileID` within the object data.
+    pub file_id_range: Range<usize>,
+    /// Byte range of the stored `m_PathID` (4 or 8 bytes, per the TypeTree).
+    pub path_id_range: Range<usize>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TypeTreeParseMode {
     Strict,
@@ -31,21 +60,44 @@ pub enum TypeTreeParseMode {
     Lenient,
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
 pub struct TypeTreeParseOptions {
     pub mode: TypeTreeParseMode,
+    /// Decoding of `string` fields that are not valid UTF-8.
+    pub strings: StringPolicy,
+}
+
+impl TypeTreeParseOptions {
+    /// Set `mode`.
+    pub fn with_mode(mut self, mode: TypeTreeParseMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set `strings`.
+    pub fn with_strings(mut self, strings: StringPolicy) -> Self {
+        self.strings = strings;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct TypeTreeParseWarning {
     pub field: String,
     pub error: String,
+    /// Byte offset (within the object data) where the failing field started.
+    pub offset: u64,
 }
 
 #[derive(Debug, Default)]
 pub struct TypeTreeParseOutput {
     pub properties: IndexMap<String, UnityValue>,
     pub warnings: Vec<TypeTreeParseWarning>,
+    /// `true` when parsing stopped early and trailing root fields are missing from `properties`.
+    pub partial: bool,
+    /// Original bytes of `string` fields decoded under a non-strict [`StringPolicy`].
+    pub raw_strings: Vec<RawString>,
 }
 
 #[derive(Debug)]
@@ -53,6 +105,11 @@ struct TypeTreeParseContext<'a> {
     options: TypeTreeParseOptions,
     ref_types: Option<&'a [SerializedType]>,
     has_managed_registry: bool,
+    raw_strings: Vec<RawString>,
+    /// Dotted path of the node being parsed; only tracked when strings may be kept raw.
+    path: String,
+    /// `string` fields read so far per path, giving each [`RawString`] its occurrence.
+    string_counts: HashMap<String, usize>,
 }
 
 #[derive(Debug)]
@@ -124,6 +181,9 @@ impl<'a> TypeTreeSerializer<'a> {
                 options,
                 ref_types: None,
                 has_managed_registry: false,
+                raw_strings: Vec::new(),
+                path: String::new(),
+                string_counts: HashMap::new(),
             },
             root_children,
         )
@@ -142,6 +202,9 @@ impl<'a> TypeTreeSerializer<'a> {
                 options,
                 ref_types: Some(ref_types),
                 has_managed_registry: false,
+                raw_strings: Vec::new(),
+                path: String::new(),
+                string_counts: HashMap::new(),
             },
             root_children,
         )
@@ -163,26 +226,40 @@ impl<'a> TypeTreeSerializer<'a> {
                     self.skip_value_with_ref_types(reader, child, ctx.ref_types)?;
                     continue;
                 }
+                let start = reader.position();
                 match self.parse_value_by_type_ctx(reader, child, &mut ctx) {
                     Ok(value) => {
                         out.properties.insert(child.name.clone(), value);
                     }
                     Err(e) => {
                         if reader.remaining() == 0 {
+                            out.partial = true;
                             break;
                         }
-                        match ctx.options.mode {
-                            TypeTreeParseMode::Strict => return Err(e),
-                            TypeTreeParseMode::Lenient => out.warnings.push(TypeTreeParseWarning {
-                                field: child.name.clone(),
-                                error: e.to_string(),
-                            }),
+                        if ctx.options.mode == TypeTreeParseMode::Strict {
+                            return Err(e);
+                        }
+                        out.warnings.push(TypeTreeParseWarning {
+                            field: child.name.clone(),
+                            error: e.to_string(),
+                            offset: start,
+                        });
+
+                        // Resume after the failing field when its size is fixed; otherwise the
+                        // position of every later field is unknown, so stop here.
+                        let end = start + child.byte_size.max(0) as u64;
+                        if child.byte_size > 0 && end <= reader.len() as u64 {
+                            reader.set_position(end)?;
+                        } else {
+                            out.partial = true;
+                            break;
                         }
                     }
                 }
             }
         }
 
+        out.raw_strings = ctx.raw_strings;
         Ok(out)
     }
 
@@ -248,6 +325,269 @@ impl<'a> TypeTreeSerializer<'a> {
         Ok(out)
     }
 
+    /// Collect every `string` field of TypeTree-based object bytes.
+    ///
+    /// Subtrees without `string` nodes are skipped byte-wise rather than parsed, and string
+    /// values longer than `max_len` bytes are cut (the rest is skipped, not read).
+    pub fn scan_strings(
+        &self,
+        reader: &mut BinaryReader,
+        max_len: usize,
+    ) -> Result<Vec<StringField>> {
+        self.scan_strings_with_ref_types(reader, max_len, None)
+    }
+
+    /// [`Self::scan_strings`] with file-level `ref_types` for skipping managed reference payloads.
+    pub fn scan_strings_with_ref_types(
+        &self,
+        reader: &mut BinaryReader,
+        max_len: usize,
+        ref_types: Option<&[SerializedType]>,
+    ) -> Result<Vec<StringField>> {
+        let mut out = Vec::new();
+        let mut ctx = TypeTreeScanContext {
+            ref_types,
+            has_managed_registry: false,
+        };
+        let mut path = String::new();
+        if let Some(root) = self.tree.nodes.first() {
+            for child in &root.children {
+                path.clear();
+                path.push_str(&child.name);
+                self.scan_strings_ctx(reader, child, &mut path, max_len, &mut out, &mut ctx)?;
+            }
+        }
+        Ok(out)
+    }
+
+    /// Collect every `PPtr` field of TypeTree-based object bytes, with its path and the byte
+    /// ranges of its `m_FileID`/`m_PathID`, so references can be patched in place.
+    ///
+    /// Null references are included. `PPtr`s inside managed reference payloads are not reported.
+    pub fn scan_pptr_fields(&self, reader: &mut BinaryReader) -> Result<Vec<PPtrField>> {
+        self.scan_pptr_fields_with_ref_types(reader, None)
+    }
+
+    /// [`Self::scan_pptr_fields`] with file-level `ref_types` for skipping managed reference payloads.
+    pub fn scan_pptr_fields_with_ref_types(
+        &self,
+        reader: &mut BinaryReader,
+        ref_types: Option<&[SerializedType]>,
+    ) -> Result<Vec<PPtrField>> {
+        let mut out = Vec::new();
+        let mut ctx = TypeTreeScanContext {
+            ref_types,
+            has_managed_registry: false,
+        };
+        let mut path = String::new();
+        if let Some(root) = self.tree.nodes.first() {
+            for child in &root.children {
+                path.clear();
+                path.push_str(&child.name);
+                self.scan_pptr_fields_ctx(reader, child, &mut path, &mut out, &mut ctx)?;
+            }
+        }
+        Ok(out)
+    }
+
+    fn scan_pptr_fields_ctx(
+        &self,
+        reader: &mut BinaryReader,
+        node: &TypeTreeNode,
+        path: &mut String,
+        out: &mut Vec<PPtrField>,
+        ctx: &mut TypeTreeScanContext<'_>,
+    ) -> Result<()> {
+        if node.type_name == "ReferencedObject" || !contains_pptr(node) {
+            let mut dummy = PPtrScanResult::default();
+            return self.scan_value_ctx(reader, node, &mut dummy, ctx);
+        }
+
+        let is_array = node.children.iter().any(|c| c.type_name == "Array");
+        if is_pptr_node(node) && !is_array {
+            let mut file_id = None;
+            let mut path_id = None;
+            for child in &node.children {
+                let start = reader.position() as usize;
+                if child.name.eq_ignore_ascii_case("fileID")
+                    || child.name.eq_ignore_ascii_case("m_FileID")
+                {
+                    let v = self.scan_read_i32_like(reader, child)?;
+                    file_id = Some((v, start..start + primitive_size(child)));
+                } else if child.name.eq_ignore_ascii_case("pathID")
+                    || child.name.eq_ignore_ascii_case("m_PathID")
+                {
+                    let v = self.scan_read_i64_like(reader, child)?;
+                    path_id = Some((v, start..start + primitive_size(child)));
+                } else {
+                    let mut dummy = PPtrScanResult::default();
+                    self.scan_value_ctx(reader, child, &mut dummy, ctx)?;
+                }
+            }
+            if let (Some((file_id, file_id_range)), Some((path_id, path_id_range))) =
+                (file_id, path_id)
+            {
+                out.push(PPtrField {
+                    path: path.clone(),
+                    file_id,
+                    path_id,
+                    file_id_range,
+                    path_id_range,
+                });
+            }
+        } else if is_array {
+            let array_node = node
+                .children
+                .iter()
+                .find(|child| child.type_name == "Array")
+                .ok_or_else(|| BinaryError::invalid_data("Array node not found in array type"))?;
+            let size = reader.read_i32()?;
+            if size < 0 {
+                return Err(BinaryError::invalid_data(format!(
+                    "Negative array size: {}",
+                    size
+                )));
+            }
+            if let Some(size_node) = array_node.children.first()
+                && size_node.is_aligned()
+            {
+                reader.align_to(4)?;
+            }
+            let size = size as usize;
+            if size > Self::MAX_ARRAY_LEN {
+                return Err(BinaryError::invalid_data(format!(
+                    "Array size too large: {}",
+                    size
+                )));
+            }
+            let element = array_node
+                .children
+                .get(1)
+                .ok_or_else(|| BinaryError::invalid_data("Array element type not found"))?;
+            let base = path.len();
+            for index in 0..size {
+                path.push_str(&format!("[{}]", index));
+                self.scan_pptr_fields_ctx(reader, element, path, out, ctx)?;
+                path.truncate(base);
+            }
+            if array_node.is_aligned() {
+                reader.align_to(4)?;
+            }
+        } else {
+            let base = path.len();
+            for child in &node.children {
+                path.push('.');
+                path.push_str(&child.name);
+                self.scan_pptr_fields_ctx(reader, child, path, out, ctx)?;
+                path.truncate(base);
+            }
+        }
+
+        if node.is_aligned() {
+            reader.align_to(4)?;
+        }
+        Ok(())
+    }
+
+    fn scan_strings_ctx(
+        &self,
+        reader: &mut BinaryReader,
+        node: &TypeTreeNode,
+        path: &mut String,
+        max_len: usize,
+        out: &mut Vec<StringField>,
+        ctx: &mut TypeTreeScanContext<'_>,
+    ) -> Result<()> {
+        // Managed references are only consumed; their strings live in the ref type trees.
+        if node.type_name == "ReferencedObject" || !contains_string(node) {
+            let mut dummy = PPtrScanResult::default();
+            return self.scan_value_ctx(reader, node, &mut dummy, ctx);
+        }
+
+        if node.type_name == "string" {
+            let start = reader.position() as usize;
+            let len = reader.read_i32()?;
+            if len < 0 {
+                return Err(BinaryError::invalid_data(format!(
+                    "Negative string length: {}",
+                    len
+                )));
+            }
+            let len = len as usize;
+            if len > BinaryReader::DEFAULT_MAX_STRING_LEN {
+                return Err(BinaryError::invalid_data(format!(
+                    "String length {} exceeds limit {}",
+                    len,
+                    BinaryReader::DEFAULT_MAX_STRING_LEN
+                )));
+            }
+            let kept = len.min(max_len);
+            let value = String::from_utf8_lossy(reader.read_bytes_ref(kept)?).into_owned();
+            reader.skip_bytes(len - kept)?;
+            reader.align_to(4)?;
+            out.push(StringField {
+                path: path.clone(),
+                value,
+                truncated: kept < len,
+                range: start..reader.position() as usize,
+            });
+            return Ok(());
+        }
+
+        if node.children.iter().any(|c| c.type_name == "Array") {
+            let array_node = node
+                .children
+                .iter()
+                .find(|child| child.type_name == "Array")
+                .ok_or_else(|| BinaryError::invalid_data("Array node not found in array type"))?;
+            let size = reader.read_i32()?;
+            if size < 0 {
+                return Err(BinaryError::invalid_data(format!(
+                    "Negative array size: {}",
+                    size
+                )));
+            }
+            if let Some(size_node) = array_node.children.first()
+                && size_node.is_aligned()
+            {
+                reader.align_to(4)?;
+            }
+            let size = size as usize;
+            if size > Self::MAX_ARRAY_LEN {
+                return Err(BinaryError::invalid_data(format!(
+                    "Array size too large: {}",
+                    size
+                )));
+            }
+            let element = array_node
+                .children
+                .get(1)
+                .ok_or_else(|| BinaryError::invalid_data("Array element type not found"))?;
+            let base = path.len();
+            for index in 0..size {
+                path.push_str(&format!("[{}]", index));
+                self.scan_strings_ctx(reader, element, path, max_len, out, ctx)?;
+                path.truncate(base);
+            }
+            if array_node.is_aligned() {
+                reader.align_to(4)?;
+            }
+        } else {
+            let base = path.len();
+            for child in &node.children {
+                path.push('.');
+                path.push_str(&child.name);
+                self.scan_strings_ctx(reader, child, path, max_len, out, ctx)?;
+                path.truncate(base);
+            }
+        }
+
+        if node.is_aligned() {
+            reader.align_to(4)?;
+        }
+        Ok(())
+    }
+
     fn scan_value_ctx(
         &self,
         reader: &mut BinaryReader,
@@ -599,6 +939,26 @@ impl<'a> TypeTreeSerializer<'a> {
         reader: &mut BinaryReader,
         node: &TypeTreeNode,
         ctx: &mut TypeTreeParseContext<'a>,
+    ) -> Result<UnityValue> {
+        // Paths only key raw strings, which a strict policy never produces.
+        if ctx.options.strings == StringPolicy::Utf8Strict {
+            return self.parse_node_value(reader, node, ctx);
+        }
+        let base = ctx.path.len();
+        if base > 0 {
+            ctx.path.push('.');
+        }
+        ctx.path.push_str(&node.name);
+        let value = self.parse_node_value(reader, node, ctx);
+        ctx.path.truncate(base);
+        value
+    }
+
+    fn parse_node_value(
+        &self,
+        reader: &mut BinaryReader,
+        node: &TypeTreeNode,
+        ctx: &mut TypeTreeParseContext<'a>,
     ) -> Result<UnityValue> {
         let value = match node.type_name.as_str() {
             // Signed integers
@@ -658,7 +1018,30 @@ impl<'a> TypeTreeSerializer<'a> {
             }
 
             // String
-            "string" => UnityValue::String(reader.read_aligned_string()?),
+            "string" => {
+                let (text, raw) = reader.read_aligned_string_with_policy(&ctx.options.strings)?;
+                if ctx.options.strings != StringPolicy::Utf8Strict {
+                    let occurrence = match ctx.string_counts.get_mut(&ctx.path) {
+                        Some(count) => {
+                            *count += 1;
+                            *count - 1
+                        }
+                        None => {
+                            ctx.string_counts.insert(ctx.path.clone(), 1);
+                            0
+                        }
+                    };
+                    if let Some(raw) = raw {
+                        ctx.raw_strings.push(RawString {
+                            decoded: text.clone(),
+                            raw: raw.to_vec(),
+                            path: ctx.path.clone(),
+                            occurrence,
+                        });
+                    }
+                }
+                UnityValue::String(text)
+            }
 
             // Typeless raw bytes (UnityPy: read_byte_array)
             "TypelessData" => {
@@ -798,7 +1181,7 @@ impl<'a> TypeTreeSerializer<'a> {
                 } else {
                     // Unknown type with no children, skip bytes if size is known
                     if node.byte_size > 0 {
-                        let _data = reader.read_bytes(node.byte_size as usize)?;
+                        reader.skip_bytes(node.byte_size as usize)?;
                         UnityValue::Null
                     } else {
                         UnityValue::Null
@@ -880,12 +1263,9 @@ impl<'a> TypeTreeSerializer<'a> {
                     return Ok(UnityValue::Bytes(bytes));
                 }
                 "bool" => {
-                    let bytes = reader.read_bytes(size)?;
+                    let bytes = reader.read_bytes_ref(size)?;
                     let out = UnityValue::Array(
-                        bytes
-                            .into_iter()
-                            .map(|b| UnityValue::Bool(b != 0))
-                            .collect(),
+                        bytes.iter().map(|&b| UnityValue::Bool(b != 0)).collect(),
                     );
                     if array_node.is_aligned() {
                         reader.align_to(4)?;
@@ -896,7 +1276,7 @@ impl<'a> TypeTreeSerializer<'a> {
                     let byte_len = size
                         .checked_mul(2)
                         .ok_or_else(|| BinaryError::invalid_data("Array byte length overflow"))?;
-                    let bytes = reader.read_bytes(byte_len)?;
+                    let bytes = reader.read_bytes_ref(byte_len)?;
                     let mut out = Vec::with_capacity(size);
                     for chunk in bytes.chunks_exact(2) {
                         let raw: [u8; 2] = chunk.try_into().expect("chunks_exact size");
@@ -915,7 +1295,7 @@ impl<'a> TypeTreeSerializer<'a> {
                     let byte_len = size
                         .checked_mul(2)
                         .ok_or_else(|| BinaryError::invalid_data("Array byte length overflow"))?;
-                    let bytes = reader.read_bytes(byte_len)?;
+                    let bytes = reader.read_bytes_ref(byte_len)?;
                     let mut out = Vec::with_capacity(size);
                     for chunk in bytes.chunks_exact(2) {
                         let raw: [u8; 2] = chunk.try_into().expect("chunks_exact size");
@@ -934,7 +1314,7 @@ impl<'a> TypeTreeSerializer<'a> {
                     let byte_len = size
                         .checked_mul(4)
                         .ok_or_else(|| BinaryError::invalid_data("Array byte length overflow"))?;
-                    let bytes = reader.read_bytes(byte_len)?;
+                    let bytes = reader.read_bytes_ref(byte_len)?;
                     let mut out = Vec::with_capacity(size);
                     for chunk in bytes.chunks_exact(4) {
                         let raw: [u8; 4] = chunk.try_into().expect("chunks_exact size");
@@ -953,7 +1333,7 @@ impl<'a> TypeTreeSerializer<'a> {
                     let byte_len = size
                         .checked_mul(4)
                         .ok_or_else(|| BinaryError::invalid_data("Array byte length overflow"))?;
-                    let bytes = reader.read_bytes(byte_len)?;
+                    let bytes = reader.read_bytes_ref(byte_len)?;
                     let mut out = Vec::with_capacity(size);
                     for chunk in bytes.chunks_exact(4) {
                         let raw: [u8; 4] = chunk.try_into().expect("chunks_exact size");
@@ -972,7 +1352,7 @@ impl<'a> TypeTreeSerializer<'a> {
                     let byte_len = size
                         .checked_mul(8)
                         .ok_or_else(|| BinaryError::invalid_data("Array byte length overflow"))?;
-                    let bytes = reader.read_bytes(byte_len)?;
+                    let bytes = reader.read_bytes_ref(byte_len)?;
                     let mut out = Vec::with_capacity(size);
                     for chunk in bytes.chunks_exact(8) {
                         let raw: [u8; 8] = chunk.try_into().expect("chunks_exact size");
@@ -991,7 +1371,7 @@ impl<'a> TypeTreeSerializer<'a> {
                     let byte_len = size
                         .checked_mul(8)
                         .ok_or_else(|| BinaryError::invalid_data("Array byte length overflow"))?;
-                    let bytes = reader.read_bytes(byte_len)?;
+                    let bytes = reader.read_bytes_ref(byte_len)?;
                     let mut out = Vec::with_capacity(size);
                     for chunk in bytes.chunks_exact(8) {
                         let raw: [u8; 8] = chunk.try_into().expect("chunks_exact size");
@@ -1010,7 +1390,7 @@ impl<'a> TypeTreeSerializer<'a> {
                     let byte_len = size
                         .checked_mul(4)
                         .ok_or_else(|| BinaryError::invalid_data("Array byte length overflow"))?;
-                    let bytes = reader.read_bytes(byte_len)?;
+                    let bytes = reader.read_bytes_ref(byte_len)?;
                     let mut out = Vec::with_capacity(size);
                     for chunk in bytes.chunks_exact(4) {
                         let raw: [u8; 4] = chunk.try_into().expect("chunks_exact size");
@@ -1029,7 +1409,7 @@ impl<'a> TypeTreeSerializer<'a> {
                     let byte_len = size
                         .checked_mul(8)
                         .ok_or_else(|| BinaryError::invalid_data("Array byte length overflow"))?;
-                    let bytes = reader.read_bytes(byte_len)?;
+                    let bytes = reader.read_bytes_ref(byte_len)?;
                     let mut out = Vec::with_capacity(size);
                     for chunk in bytes.chunks_exact(8) {
                         let raw: [u8; 8] = chunk.try_into().expect("chunks_exact size");
@@ -1310,6 +1690,28 @@ fn resolve_ref_type_tree<'a>(
     })
 }
 
+fn contains_string(node: &TypeTreeNode) -> bool {
+    node.type_name == "string" || node.children.iter().any(contains_string)
+}
+
+fn is_pptr_node(node: &TypeTreeNode) -> bool {
+    (node.type_name == "PPtr" || node.type_name.starts_with("PPtr<")) && !node.children.is_empty()
+}
+
+fn contains_pptr(node: &TypeTreeNode) -> bool {
+    is_pptr_node(node) || node.children.iter().any(contains_pptr)
+}
+
+/// Stored width of a `fileID`/`pathID` primitive.
+fn primitive_size(node: &TypeTreeNode) -> usize {
+    match node.type_name.as_str() {
+        "SInt64" | "long long" | "UInt64" | "unsigned long long" | "FileSize" => 8,
+        "SInt16" | "short" | "UInt16" | "unsigned short" => 2,
+        "SInt8" | "char" | "UInt8" => 1,
+        _ => 4,
+    }
+}
+
 fn resolve_ref_type_tree_triplet<'a>(
     class: &str,
     ns: &str,
@@ -10,6 +10,7 @@ use crate::typetree::{TypeTree, TypeTreeNode, TypeTreeRegistry};
 use crate::unity_version::{UnityVersion, UnityVersionType};
 use std::collections::HashMap;
 use std::io::{Cursor, Read};
+#[cfg(feature = "fs")]
 use std::path::Path;
 use std::sync::{Arc, RwLock};
 
@@ -514,6 +515,7 @@ impl TpkTypeTreeRegistry {
         })
     }
 
+    #[cfg(feature = "fs")]
     pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
         let data = std::fs::read(path.as_ref()).map_err(|e| {
             BinaryError::generic(format!(
@@ -524,6 +526,13 @@ impl TpkTypeTreeRegistry {
         })?;
         Self::from_bytes(&data)
     }
+
+    /// Class IDs with TypeTree information, sorted.
+    pub fn class_ids(&self) -> Vec<i32> {
+        let mut ids: Vec<i32> = self.blob.class_information.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
 }
 
 impl TypeTreeRegistry for TpkTypeTreeRegistry {
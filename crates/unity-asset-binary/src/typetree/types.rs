@@ -293,6 +293,19 @@ impl TypeTree {
         None
     }
 
+    /// Return the minimal root-child prefix length covering every listed field that exists.
+    ///
+    /// Fields missing from the tree are ignored; `None` means none of them exist.
+    pub fn fields_peek_prefix(&self, fields: &[&str]) -> Option<usize> {
+        let root = self.nodes.first()?;
+        root.children
+            .iter()
+            .enumerate()
+            .filter(|(_, child)| fields.contains(&child.name.as_str()))
+            .map(|(i, _)| i + 1)
+            .max()
+    }
+
     /// Clear all nodes
     pub fn clear(&mut self) {
         self.nodes.clear();
@@ -380,6 +393,7 @@ impl Default for TypeTree {
 
 /// TypeTree statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct TypeTreeStatistics {
     pub total_nodes: usize,
     pub root_nodes: usize,
@@ -5,10 +5,13 @@
 
 use crate::error::Result;
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use unity_asset_core::UnityValue;
 
+pub use crate::shader::Shader;
+
 /// Reference to another Unity object
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ObjectRef {
     pub file_id: i32,
     pub path_id: i64,
@@ -107,30 +110,19 @@ impl GameObject {
             game_object.active = *active;
         }
 
-        // Extract components array
+        // Extract components array: `{component: PPtr}` pairs (older layouts also carry the
+        // class ID as `first`), or bare PPtrs
         if let Some(UnityValue::Array(components_array)) = properties.get("m_Component") {
             for component in components_array {
-                if let UnityValue::Object(component_obj) = component {
-                    // Each component is typically a structure with file_id and path_id
-                    let file_id = component_obj
-                        .get("fileID")
-                        .and_then(|v| match v {
-                            UnityValue::Integer(id) => Some(*id as i32),
-                            _ => None,
-                        })
-                        .unwrap_or(0);
-
-                    let path_id = component_obj
-                        .get("pathID")
-                        .and_then(|v| match v {
-                            UnityValue::Integer(id) => Some(*id),
-                            _ => None,
-                        })
-                        .unwrap_or(0);
-
-                    game_object
-                        .components
-                        .push(ObjectRef::new(file_id, path_id));
+                let pptr = match component {
+                    UnityValue::Object(pair) => pair
+                        .get("component")
+                        .or_else(|| pair.get("second"))
+                        .map_or_else(|| parse_pptr(component), parse_pptr),
+                    _ => None,
+                };
+                if let Some(pptr) = pptr {
+                    game_object.components.push(pptr);
                 }
             }
         }
@@ -153,6 +145,8 @@ pub struct Transform {
     pub scale: Vector3,
     pub parent: Option<ObjectRef>,
     pub children: Vec<ObjectRef>,
+    /// The GameObject this Transform belongs to (`m_GameObject`)
+    pub game_object: Option<ObjectRef>,
 }
 
 impl Transform {
@@ -162,6 +156,7 @@ impl Transform {
             rotation: Quaternion::identity(),
             scale: Vector3::new(1.0, 1.0, 1.0),
             parent: None,
+            game_object: None,
             children: Vec::new(),
         }
     }
@@ -187,16 +182,15 @@ impl Transform {
 
         // Extract parent
         if let Some(parent_value) = properties.get("m_Father") {
-            transform.parent = Self::parse_object_ref(parent_value);
+            transform.parent = parse_pptr(parent_value);
         }
 
         // Extract children
-        if let Some(UnityValue::Array(children_array)) = properties.get("m_Children") {
-            for child in children_array {
-                if let Some(child_ref) = Self::parse_object_ref(child) {
-                    transform.children.push(child_ref);
-                }
-            }
+        transform.children = parse_pptr_array(properties.get("m_Children"));
+
+        // Extract owning GameObject
+        if let Some(game_object_value) = properties.get("m_GameObject") {
+            transform.game_object = parse_pptr(game_object_value);
         }
 
         Ok(transform)
@@ -282,41 +276,468 @@ impl Transform {
             _ => Ok(Quaternion::identity()),
         }
     }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Unity `PassType` (`UnityEngine.Rendering.PassType`) of a shader variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PassType {
+    Normal,
+    Vertex,
+    VertexLM,
+    VertexLMRGBM,
+    ForwardBase,
+    ForwardAdd,
+    LightPrePassBase,
+    LightPrePassFinal,
+    ShadowCaster,
+    Deferred,
+    Meta,
+    MotionVectors,
+    ScriptableRenderPipeline,
+    ScriptableRenderPipelineDefaultUnlit,
+    Unknown(i32),
+}
 
-    fn parse_object_ref(value: &UnityValue) -> Option<ObjectRef> {
+impl PassType {
+    pub fn from_i32(value: i32) -> Self {
         match value {
-            UnityValue::Object(obj) => {
-                let file_id = obj
-                    .get("fileID")
-                    .and_then(|v| match v {
-                        UnityValue::Integer(id) => Some(*id as i32),
-                        _ => None,
-                    })
-                    .unwrap_or(0);
+            0 => Self::Normal,
+            1 => Self::Vertex,
+            2 => Self::VertexLM,
+            3 => Self::VertexLMRGBM,
+            4 => Self::ForwardBase,
+            5 => Self::ForwardAdd,
+            6 => Self::LightPrePassBase,
+            7 => Self::LightPrePassFinal,
+            8 => Self::ShadowCaster,
+            10 => Self::Deferred,
+            11 => Self::Meta,
+            12 => Self::MotionVectors,
+            13 => Self::ScriptableRenderPipeline,
+            14 => Self::ScriptableRenderPipelineDefaultUnlit,
+            other => Self::Unknown(other),
+        }
+    }
 
-                let path_id = obj
-                    .get("pathID")
-                    .and_then(|v| match v {
-                        UnityValue::Integer(id) => Some(*id),
-                        _ => None,
-                    })
-                    .unwrap_or(0);
+    pub fn name(&self) -> String {
+        match self {
+            Self::Unknown(value) => format!("Unknown({})", value),
+            other => format!("{:?}", other),
+        }
+    }
+}
+
+/// One variant listed in a [`ShaderVariantCollection`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShaderVariant {
+    pub pass_type: PassType,
+    pub keywords: Vec<String>,
+}
+
+/// The variants a [`ShaderVariantCollection`] lists for one shader.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShaderVariantCollectionEntry {
+    pub shader: ObjectRef,
+    pub variants: Vec<ShaderVariant>,
+}
+
+/// Unity ShaderVariantCollection (class 200)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShaderVariantCollection {
+    pub name: String,
+    pub shaders: Vec<ShaderVariantCollectionEntry>,
+}
+
+impl ShaderVariantCollection {
+    /// Parse ShaderVariantCollection from TypeTree data
+    ///
+    /// `m_Shaders` is a `map<PPtr<Shader>, ShaderInfo>`; both the binary pair form (`[key, value]`)
+    /// and the YAML form (`{first, second}`) are accepted. `keywords` may be a space-separated
+    /// string (as Unity serializes it) or a string array.
+    pub fn from_typetree(properties: &IndexMap<String, UnityValue>) -> Result<Self> {
+        let mut collection = Self::default();
+
+        if let Some(UnityValue::String(name)) = properties.get("m_Name") {
+            collection.name = name.clone();
+        }
 
-                let obj_ref = ObjectRef::new(file_id, path_id);
-                if obj_ref.is_null() {
-                    None
-                } else {
-                    Some(obj_ref)
+        if let Some(UnityValue::Array(shaders)) = properties.get("m_Shaders") {
+            for pair in shaders {
+                let (key, value) = match pair {
+                    UnityValue::Array(kv) if kv.len() == 2 => (&kv[0], &kv[1]),
+                    UnityValue::Object(obj) => match (obj.get("first"), obj.get("second")) {
+                        (Some(key), Some(value)) => (key, value),
+                        _ => continue,
+                    },
+                    _ => continue,
+                };
+                let Some(shader) = parse_pptr(key) else {
+                    continue;
+                };
+
+                let mut variants = Vec::new();
+                if let UnityValue::Object(info) = value
+                    && let Some(UnityValue::Array(items)) = info.get("variants")
+                {
+                    for item in items {
+                        let UnityValue::Object(variant) = item else {
+                            continue;
+                        };
+                        let pass_type = variant
+                            .get("passType")
+                            .and_then(UnityValue::as_i64)
+                            .unwrap_or(0);
+                        variants.push(ShaderVariant {
+                            pass_type: PassType::from_i32(pass_type as i32),
+                            keywords: parse_keywords(variant.get("keywords")),
+                        });
+                    }
                 }
+
+                collection
+                    .shaders
+                    .push(ShaderVariantCollectionEntry { shader, variants });
             }
-            _ => None,
         }
+
+        Ok(collection)
+    }
+
+    /// Total number of listed variants.
+    pub fn variant_count(&self) -> usize {
+        self.shaders.iter().map(|s| s.variants.len()).sum()
     }
 }
 
-impl Default for Transform {
+/// Shader-related parts of Unity GraphicsSettings (class 30)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphicsSettings {
+    /// `m_AlwaysIncludedShaders`: shaders compiled into every build with all their variants.
+    pub always_included_shaders: Vec<ObjectRef>,
+    /// `m_PreloadedShaders`: ShaderVariantCollections warmed up at startup.
+    pub preloaded_shaders: Vec<ObjectRef>,
+}
+
+impl GraphicsSettings {
+    /// Parse GraphicsSettings from TypeTree data
+    pub fn from_typetree(properties: &IndexMap<String, UnityValue>) -> Result<Self> {
+        Ok(Self {
+            always_included_shaders: parse_pptr_array(properties.get("m_AlwaysIncludedShaders")),
+            preloaded_shaders: parse_pptr_array(properties.get("m_PreloadedShaders")),
+        })
+    }
+}
+
+/// An RGBA color as stored (`ColorRGBA`)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+impl Default for Color {
     fn default() -> Self {
-        Self::new()
+        Self::new(1.0, 1.0, 1.0, 1.0)
+    }
+}
+
+/// Physical camera settings (2018.2+, used when `projection_matrix_mode` is 2)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PhysicalCamera {
+    /// Focal length in millimeters
+    pub focal_length: f32,
+    /// Sensor size in millimeters
+    pub sensor_size: [f32; 2],
+    pub lens_shift: [f32; 2],
+    /// `Camera.GateFitMode` (2018.3+)
+    pub gate_fit: Option<i32>,
+}
+
+/// Unity Camera component (class 20)
+///
+/// The field names have been stable since 5.x (`field of view`, `near clip plane`, ... with
+/// spaces); physical camera settings were added in 2018.2.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Camera {
+    pub game_object: Option<ObjectRef>,
+    pub enabled: bool,
+    /// `CameraClearFlags` (1 skybox, 2 solid color, 3 depth, 4 nothing)
+    pub clear_flags: u32,
+    pub background_color: Color,
+    /// Vertical field of view in degrees
+    pub field_of_view: f32,
+    pub near_clip_plane: f32,
+    pub far_clip_plane: f32,
+    pub orthographic: bool,
+    /// Half the vertical size of the view volume in orthographic mode
+    pub orthographic_size: f32,
+    pub depth: f32,
+    /// Viewport rect as `[x, y, width, height]`
+    pub viewport_rect: [f32; 4],
+    pub culling_mask: u32,
+    pub target_texture: Option<ObjectRef>,
+    /// `m_projectionMatrixMode` (2018.2+): 0 explicit, 1 implicit, 2 physical
+    pub projection_matrix_mode: Option<i32>,
+    /// Physical camera settings, when the layout has them
+    pub physical: Option<PhysicalCamera>,
+}
+
+impl Camera {
+    /// Parse Camera from TypeTree data
+    pub fn from_typetree(properties: &IndexMap<String, UnityValue>) -> Result<Self> {
+        let float = |key: &str, default: f32| get_f32(properties.get(key)).unwrap_or(default);
+        let physical = properties
+            .get("m_FocalLength")
+            .map(|focal_length| PhysicalCamera {
+                focal_length: get_f32(Some(focal_length)).unwrap_or(50.0),
+                sensor_size: parse_vector2(properties.get("m_SensorSize")).unwrap_or([36.0, 24.0]),
+                lens_shift: parse_vector2(properties.get("m_LensShift")).unwrap_or_default(),
+                gate_fit: get_i64(properties.get("m_GateFitMode")).map(|v| v as i32),
+            });
+        let viewport_rect = match properties.get("m_NormalizedViewPortRect") {
+            Some(UnityValue::Object(rect)) => {
+                ["x", "y", "width", "height"].map(|key| get_f32(rect.get(key)).unwrap_or_default())
+            }
+            _ => [0.0, 0.0, 1.0, 1.0],
+        };
+        let culling_mask = match properties.get("m_CullingMask") {
+            Some(UnityValue::Object(mask)) => get_i64(mask.get("m_Bits")),
+            other => get_i64(other),
+        };
+
+        Ok(Self {
+            game_object: properties.get("m_GameObject").and_then(parse_pptr),
+            enabled: get_flag(properties.get("m_Enabled")).unwrap_or(true),
+            clear_flags: get_i64(properties.get("m_ClearFlags")).unwrap_or(1) as u32,
+            background_color: parse_color(properties.get("m_BackGroundColor")).unwrap_or_default(),
+            field_of_view: float("field of view", 60.0),
+            near_clip_plane: float("near clip plane", 0.3),
+            far_clip_plane: float("far clip plane", 1000.0),
+            orthographic: get_flag(properties.get("orthographic")).unwrap_or(false),
+            orthographic_size: float("orthographic size", 5.0),
+            depth: float("m_Depth", 0.0),
+            viewport_rect,
+            culling_mask: culling_mask.unwrap_or(u32::MAX as i64) as u32,
+            target_texture: properties.get("m_TargetTexture").and_then(parse_pptr),
+            projection_matrix_mode: get_i64(properties.get("m_projectionMatrixMode"))
+                .map(|v| v as i32),
+            physical,
+        })
+    }
+
+    /// Whether the physical camera properties drive the projection
+    pub fn uses_physical_properties(&self) -> bool {
+        self.projection_matrix_mode == Some(2) && self.physical.is_some()
+    }
+}
+
+/// Unity `LightType`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LightType {
+    Spot,
+    Directional,
+    Point,
+    /// Rectangle area light (baked only)
+    Rectangle,
+    /// Disc area light (baked only)
+    Disc,
+    Unknown(i32),
+}
+
+impl LightType {
+    pub fn from_i32(value: i32) -> Self {
+        match value {
+            0 => Self::Spot,
+            1 => Self::Directional,
+            2 => Self::Point,
+            3 => Self::Rectangle,
+            4 => Self::Disc,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+/// Unity `LightUnit`, recorded by layouts with physical light units (2023.1+)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LightUnit {
+    Lumen,
+    Candela,
+    Lux,
+    Nits,
+    Ev100,
+    Unknown(i32),
+}
+
+impl LightUnit {
+    pub fn from_i32(value: i32) -> Self {
+        match value {
+            0 => Self::Lumen,
+            1 => Self::Candela,
+            2 => Self::Lux,
+            3 => Self::Nits,
+            4 => Self::Ev100,
+            other => Self::Unknown(other),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Self::Unknown(value) => format!("Unknown({})", value),
+            known => format!("{:?}", known),
+        }
+    }
+}
+
+/// Unity Light component (class 108)
+///
+/// `color` is stored in gamma space and `intensity` is unitless unless `light_unit` is set;
+/// neither is converted here. `inner_spot_angle` appeared in 2019.1, the color temperature
+/// fields in 5.6 and the physical light unit fields in 2023.1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Light {
+    pub game_object: Option<ObjectRef>,
+    pub enabled: bool,
+    pub light_type: LightType,
+    pub color: Color,
+    pub intensity: f32,
+    pub range: f32,
+    /// Outer cone angle of spot lights, in degrees
+    pub spot_angle: f32,
+    /// Inner cone angle of spot lights, in degrees (2019.1+)
+    pub inner_spot_angle: Option<f32>,
+    /// `LightShadows` (0 none, 1 hard, 2 soft)
+    pub shadows: i32,
+    pub shadow_strength: f32,
+    pub bounce_intensity: Option<f32>,
+    /// Size of rectangle / disc area lights
+    pub area_size: Option<[f32; 2]>,
+    /// `LightmapBakeType` (4 realtime, 2 baked, 1 mixed)
+    pub lightmapping: Option<i32>,
+    pub cookie: Option<ObjectRef>,
+    pub color_temperature: Option<f32>,
+    pub use_color_temperature: bool,
+    /// Unit of `intensity` on layouts with physical light units
+    pub light_unit: Option<LightUnit>,
+    pub lux_at_distance: Option<f32>,
+}
+
+impl Light {
+    /// Parse Light from TypeTree data
+    pub fn from_typetree(properties: &IndexMap<String, UnityValue>) -> Result<Self> {
+        let float = |key: &str| get_f32(properties.get(key));
+        let (shadows, shadow_strength) = match properties.get("m_Shadows") {
+            Some(UnityValue::Object(shadows)) => (
+                get_i64(shadows.get("m_Type")).unwrap_or(0) as i32,
+                get_f32(shadows.get("m_Strength")).unwrap_or(1.0),
+            ),
+            _ => (0, 1.0),
+        };
+
+        Ok(Self {
+            game_object: properties.get("m_GameObject").and_then(parse_pptr),
+            enabled: get_flag(properties.get("m_Enabled")).unwrap_or(true),
+            light_type: LightType::from_i32(get_i64(properties.get("m_Type")).unwrap_or(2) as i32),
+            color: parse_color(properties.get("m_Color")).unwrap_or_default(),
+            intensity: float("m_Intensity").unwrap_or(1.0),
+            range: float("m_Range").unwrap_or(10.0),
+            spot_angle: float("m_SpotAngle").unwrap_or(30.0),
+            inner_spot_angle: float("m_InnerSpotAngle"),
+            shadows,
+            shadow_strength,
+            bounce_intensity: float("m_BounceIntensity"),
+            area_size: parse_vector2(properties.get("m_AreaSize")),
+            lightmapping: get_i64(properties.get("m_Lightmapping")).map(|v| v as i32),
+            cookie: properties.get("m_Cookie").and_then(parse_pptr),
+            color_temperature: float("m_ColorTemperature"),
+            use_color_temperature: get_flag(properties.get("m_UseColorTemperature"))
+                .unwrap_or(false),
+            light_unit: get_i64(properties.get("m_LightUnit"))
+                .map(|v| LightUnit::from_i32(v as i32)),
+            lux_at_distance: float("m_LuxAtDistance"),
+        })
+    }
+}
+
+fn get_f32(value: Option<&UnityValue>) -> Option<f32> {
+    value.and_then(UnityValue::as_f64).map(|v| v as f32)
+}
+
+fn get_i64(value: Option<&UnityValue>) -> Option<i64> {
+    value.and_then(UnityValue::as_i64)
+}
+
+/// `bool` fields; older layouts store some as `UInt8`.
+fn get_flag(value: Option<&UnityValue>) -> Option<bool> {
+    match value? {
+        UnityValue::Bool(b) => Some(*b),
+        UnityValue::Integer(i) => Some(*i != 0),
+        _ => None,
+    }
+}
+
+fn parse_color(value: Option<&UnityValue>) -> Option<Color> {
+    let UnityValue::Object(fields) = value? else {
+        return None;
+    };
+    let [r, g, b, a] = ["r", "g", "b", "a"].map(|key| get_f32(fields.get(key)));
+    Some(Color::new(r?, g?, b?, a.unwrap_or(1.0)))
+}
+
+fn parse_vector2(value: Option<&UnityValue>) -> Option<[f32; 2]> {
+    let UnityValue::Object(fields) = value? else {
+        return None;
+    };
+    Some([get_f32(fields.get("x"))?, get_f32(fields.get("y"))?])
+}
+
+/// Read a `PPtr` stored as `{m_FileID, m_PathID}` (binary) or `{fileID, pathID}`; null is `None`.
+pub(crate) fn parse_pptr(value: &UnityValue) -> Option<ObjectRef> {
+    let UnityValue::Object(obj) = value else {
+        return None;
+    };
+    let file_id = obj
+        .get("m_FileID")
+        .or_else(|| obj.get("fileID"))
+        .and_then(UnityValue::as_i64)?;
+    let path_id = obj
+        .get("m_PathID")
+        .or_else(|| obj.get("pathID"))
+        .and_then(UnityValue::as_i64)?;
+    let obj_ref = ObjectRef::new(file_id as i32, path_id);
+    (!obj_ref.is_null()).then_some(obj_ref)
+}
+
+fn parse_pptr_array(value: Option<&UnityValue>) -> Vec<ObjectRef> {
+    match value {
+        Some(UnityValue::Array(items)) => items.iter().filter_map(parse_pptr).collect(),
+        _ => Vec::new(),
+    }
+}
+
+pub(crate) fn parse_keywords(value: Option<&UnityValue>) -> Vec<String> {
+    match value {
+        Some(UnityValue::String(s)) => s.split_whitespace().map(str::to_string).collect(),
+        Some(UnityValue::Array(items)) => items
+            .iter()
+            .filter_map(UnityValue::as_str)
+            .flat_map(str::split_whitespace)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
     }
 }
 
@@ -0,0 +1,380 @@
+//! Inferring the Unity version of files whose version string was stripped.
+//!
+//! Protected builds blank `unity_version` ("0.0.0" or garbage) in both the bundle header and the
+//! SerializedFiles, which sends every version-gated parser down the default branch. The layout
+//! still gives the version away; each clue bounds it from one side and [`InferVersion::infer`]
+//! intersects them:
+//!
+//! - the SerializedFile format version (each release line writes one format);
+//! - the TypeTree encoding (`PPtr` path ids are `SInt64` from 5.0);
+//! - fields present or missing in the TypeTrees of common classes (Texture2D, AudioClip, and the
+//!   GameObject component list layout);
+//! - the bundle signature and format version, when the file came from a bundle.
+
+use super::{UnityVersion, UnityVersionType, VersionCompatibility};
+use crate::asset::SerializedFile;
+use crate::bundle::BundleHeader;
+use crate::typetree::TypeTreeNode;
+use unity_asset_core::class_ids;
+
+/// A release line, `(major, minor)`.
+type Line = (u16, u16);
+
+/// Fields added in a release line and kept by every later one: presence bounds the version from
+/// below, absence (in a class that has a TypeTree) from above. Aliases cover later renames.
+const ADDED_FIELDS: &[(i32, &[&str], Line)] = &[
+    (class_ids::TEXTURE_2D, &["m_MipCount"], (5, 2)),
+    (
+        class_ids::TEXTURE_2D,
+        &["m_ForcedFallbackFormat"],
+        (2017, 3),
+    ),
+    (class_ids::TEXTURE_2D, &["m_StreamingMipmaps"], (2018, 2)),
+    (
+        class_ids::TEXTURE_2D,
+        &["m_IgnoreMasterTextureLimit", "m_IgnoreMipmapLimit"],
+        (2019, 3),
+    ),
+    (class_ids::TEXTURE_2D, &["m_MipsStripped"], (2020, 1)),
+    (
+        class_ids::TEXTURE_2D,
+        &["m_IsAlphaChannelOptional"],
+        (2020, 2),
+    ),
+    (class_ids::AUDIO_CLIP, &["m_LoadType"], (5, 0)),
+    (class_ids::AUDIO_CLIP, &["m_Ambisonic"], (2017, 1)),
+];
+
+/// Fields dropped in a release line: presence bounds the version from above.
+const REMOVED_FIELDS: &[(i32, &str, Line)] = &[(class_ids::TEXTURE_2D, "m_ReadAllowed", (5, 5))];
+
+/// Release lines considered when scoring a range, oldest first: `(major, first minor, last minor)`.
+const RELEASE_LINES: &[(u16, u16, u16)] = &[
+    (3, 0, 5),
+    (4, 0, 7),
+    (5, 0, 6),
+    (2017, 1, 4),
+    (2018, 1, 4),
+    (2019, 1, 4),
+    (2020, 1, 3),
+    (2021, 1, 3),
+    (2022, 1, 3),
+    (2023, 1, 2),
+    (6000, 0, 2),
+];
+
+/// The Unity versions consistent with a file's structure (see [`InferVersion`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct InferredVersion {
+    /// Lowest consistent version (inclusive); `None` if nothing bounds it from below
+    pub min: Option<UnityVersion>,
+    /// First version no longer consistent (exclusive); `None` if nothing bounds it from above
+    pub max: Option<UnityVersion>,
+    /// Version to hand to version-gated parsers: the declared one when intact and consistent,
+    /// otherwise the newest well-known release in the range
+    pub best: UnityVersion,
+    /// Trust in `best`, from 0 to 1: 1 for an intact declared version, otherwise one over the
+    /// number of release lines left in the range
+    pub confidence: f32,
+    /// One line per clue, in the order applied
+    pub evidence: Vec<String>,
+}
+
+impl InferredVersion {
+    /// Whether `version` lies in `min..max`
+    pub fn contains(&self, version: &UnityVersion) -> bool {
+        in_range(&self.min, &self.max, version)
+    }
+}
+
+/// Structural version inference, implemented for [`UnityVersion`]:
+///
+/// ```rust,no_run
+/// use unity_asset_binary::asset::SerializedFileParser;
+/// use unity_asset_binary::unity_version::{InferVersion, UnityVersion};
+///
+/// let mut file = SerializedFileParser::from_bytes(std::fs::read("level0")?)?;
+/// let inferred = UnityVersion::infer(&file);
+/// println!("{} ({:.0}%)", inferred.best, inferred.confidence * 100.0);
+/// file.set_unity_version(&inferred.best);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub trait InferVersion {
+    /// Narrow down the Unity version that wrote `asset` from its layout.
+    fn infer(asset: &SerializedFile) -> InferredVersion;
+
+    /// [`infer`](Self::infer), also using the header of the bundle `asset` was loaded from.
+    fn infer_in_bundle(asset: &SerializedFile, header: &BundleHeader) -> InferredVersion;
+}
+
+impl InferVersion for UnityVersion {
+    fn infer(asset: &SerializedFile) -> InferredVersion {
+        let mut range = VersionRange::default();
+        narrow_from_file(&mut range, asset);
+        range.finish(declared(&asset.unity_version))
+    }
+
+    fn infer_in_bundle(asset: &SerializedFile, header: &BundleHeader) -> InferredVersion {
+        let mut range = VersionRange::default();
+        narrow_from_file(&mut range, asset);
+        narrow_from_bundle(&mut range, header);
+        let declared = declared(&asset.unity_version).or_else(|| declared(&header.unity_revision));
+        range.finish(declared)
+    }
+}
+
+/// Whether a declared version string is blank, unparsable or implausible (e.g. "0.0.0").
+pub fn is_stripped_version(version: &str) -> bool {
+    if version.trim().is_empty() {
+        return true;
+    }
+    match UnityVersion::parse_version(version) {
+        Ok(v) => !matches!(v.major, 1..=5 | 2017..=2030 | 6000..=6999),
+        Err(_) => true,
+    }
+}
+
+impl SerializedFile {
+    /// Replace a stripped `unity_version` with the best guess of [`InferVersion::infer`], so
+    /// version-gated parsers pick the right branches.
+    ///
+    /// Returns the inference, or `None` (leaving the file alone) when the declared version is
+    /// intact.
+    pub fn apply_inferred_version(&mut self) -> Option<InferredVersion> {
+        if !is_stripped_version(&self.unity_version) {
+            return None;
+        }
+        let inferred = UnityVersion::infer(self);
+        self.set_unity_version(&inferred.best);
+        Some(inferred)
+    }
+}
+
+fn declared(version: &str) -> Option<UnityVersion> {
+    if is_stripped_version(version) {
+        return None;
+    }
+    UnityVersion::parse_version(version).ok()
+}
+
+fn in_range(
+    min: &Option<UnityVersion>,
+    max: &Option<UnityVersion>,
+    version: &UnityVersion,
+) -> bool {
+    min.as_ref().is_none_or(|min| version >= min) && max.as_ref().is_none_or(|max| version < max)
+}
+
+/// The first alpha of a release line, i.e. the lowest version of `major.minor`.
+fn line(major: u16, minor: u16) -> UnityVersion {
+    UnityVersion::new(major, minor, 0, UnityVersionType::A, 0)
+}
+
+/// `min..max` release lines that write SerializedFile format `format`.
+fn format_lines(format: u32) -> (Option<Line>, Option<Line>) {
+    match format {
+        0..=5 => (None, Some((2, 1))),
+        6 => (Some((2, 1)), Some((3, 0))),
+        7 | 8 => (Some((3, 0)), Some((3, 5))),
+        9 => (Some((3, 5)), Some((5, 0))),
+        10..=14 => (Some((5, 0)), Some((5, 1))),
+        15 => (Some((5, 0)), Some((5, 5))),
+        16 => (Some((5, 5)), Some((5, 6))),
+        17 => (Some((5, 5)), Some((2019, 1))),
+        18 | 19 => (Some((2019, 1)), Some((2019, 2))),
+        20 => (Some((2019, 2)), Some((2019, 3))),
+        21 => (Some((2019, 3)), Some((2020, 1))),
+        _ => (Some((2020, 1)), None),
+    }
+}
+
+fn narrow_from_file(range: &mut VersionRange, asset: &SerializedFile) {
+    let format = asset.header.version;
+    let (min, max) = format_lines(format);
+    range.narrow(
+        format!("SerializedFile format {}", format),
+        min.map(|(major, minor)| line(major, minor)),
+        max.map(|(major, minor)| line(major, minor)),
+    );
+
+    if let Some(path_id) = asset
+        .types
+        .iter()
+        .filter_map(|t| t.type_tree.nodes.first())
+        .find_map(|root| find_field(root, "m_PathID"))
+    {
+        match path_id.type_name.as_str() {
+            "SInt64" => range.narrow("PPtr path ids are SInt64", Some(line(5, 0)), None),
+            "int" => range.narrow("PPtr path ids are int", None, Some(line(5, 0))),
+            _ => {}
+        }
+    }
+
+    for &(class_id, names, (major, minor)) in ADDED_FIELDS {
+        let Some(root) = type_root(asset, class_id) else {
+            continue;
+        };
+        let since = line(major, minor);
+        match names.iter().find(|name| root.find_child(name).is_some()) {
+            Some(name) => range.narrow(
+                format!("{} has {}", root.type_name, name),
+                Some(since),
+                None,
+            ),
+            None => range.narrow(
+                format!("{} lacks {}", root.type_name, names[0]),
+                None,
+                Some(since),
+            ),
+        }
+    }
+
+    for &(class_id, name, (major, minor)) in REMOVED_FIELDS {
+        if let Some(root) = type_root(asset, class_id)
+            && root.find_child(name).is_some()
+        {
+            range.narrow(
+                format!("{} has {}", root.type_name, name),
+                None,
+                Some(line(major, minor)),
+            );
+        }
+    }
+
+    // 5.5 replaced the (classID, component) pairs of m_Component with bare ComponentPairs.
+    if let Some(element) = type_root(asset, class_ids::GAME_OBJECT)
+        .and_then(|root| root.find_child("m_Component"))
+        .and_then(|field| field.find_child("Array"))
+        .and_then(|array| array.find_child("data"))
+    {
+        match element.type_name.as_str() {
+            "ComponentPair" => range.narrow(
+                "GameObject components are ComponentPairs",
+                Some(line(5, 5)),
+                None,
+            ),
+            "pair" => range.narrow(
+                "GameObject components are (classID, component) pairs",
+                None,
+                Some(line(5, 5)),
+            ),
+            _ => {}
+        }
+    }
+}
+
+fn narrow_from_bundle(range: &mut VersionRange, header: &BundleHeader) {
+    if header.signature != "UnityFS" {
+        return;
+    }
+    range.narrow("UnityFS bundle", Some(line(5, 3)), None);
+    if header.version >= 7 {
+        range.narrow(
+            format!("UnityFS format {}", header.version),
+            Some(line(2019, 4)),
+            None,
+        );
+    }
+}
+
+/// The TypeTree root of the first `class_id` type that has one.
+fn type_root(asset: &SerializedFile, class_id: i32) -> Option<&TypeTreeNode> {
+    asset
+        .types
+        .iter()
+        .filter(|t| t.class_id == class_id)
+        .find_map(|t| t.type_tree.nodes.first())
+}
+
+fn find_field<'a>(node: &'a TypeTreeNode, name: &str) -> Option<&'a TypeTreeNode> {
+    if node.name == name {
+        return Some(node);
+    }
+    node.children
+        .iter()
+        .find_map(|child| find_field(child, name))
+}
+
+#[derive(Default)]
+struct VersionRange {
+    min: Option<UnityVersion>,
+    max: Option<UnityVersion>,
+    evidence: Vec<String>,
+}
+
+impl VersionRange {
+    /// Intersect with `min..max`; a clue that would empty the range is recorded but not applied.
+    fn narrow(
+        &mut self,
+        clue: impl Into<String>,
+        min: Option<UnityVersion>,
+        max: Option<UnityVersion>,
+    ) {
+        let clue = clue.into();
+        let min = match (self.min.clone(), min) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        let max = match (self.max.clone(), max) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if let (Some(lo), Some(hi)) = (&min, &max)
+            && lo >= hi
+        {
+            self.evidence
+                .push(format!("{} (ignored: contradicts earlier clues)", clue));
+            return;
+        }
+        self.min = min;
+        self.max = max;
+        self.evidence.push(clue);
+    }
+
+    fn contains(&self, version: &UnityVersion) -> bool {
+        in_range(&self.min, &self.max, version)
+    }
+
+    fn finish(mut self, declared: Option<UnityVersion>) -> InferredVersion {
+        if let Some(declared) = declared {
+            if self.contains(&declared) {
+                self.evidence.push(format!("declared version {}", declared));
+                return InferredVersion {
+                    min: self.min,
+                    max: self.max,
+                    best: declared,
+                    confidence: 1.0,
+                    evidence: self.evidence,
+                };
+            }
+            self.evidence.push(format!(
+                "declared version {} contradicts the layout",
+                declared
+            ));
+        }
+
+        let lines = RELEASE_LINES
+            .iter()
+            .flat_map(|&(major, first, last)| (first..=last).map(move |minor| line(major, minor)))
+            .filter(|l| self.contains(l))
+            .count();
+        let best = VersionCompatibility::get_known_versions()
+            .into_iter()
+            .rev()
+            .find(|v| self.contains(v))
+            .or_else(|| {
+                self.min
+                    .as_ref()
+                    .map(|min| UnityVersion::new(min.major, min.minor, 0, UnityVersionType::F, 1))
+            })
+            .unwrap_or_default();
+
+        InferredVersion {
+            min: self.min,
+            max: self.max,
+            best,
+            confidence: if lines == 0 { 0.05 } else { 1.0 / lines as f32 },
+            evidence: self.evidence,
+        }
+    }
+}
@@ -0,0 +1,10 @@
+//! Unity Version Management System
+//!
+//! The version types live in `unity-asset-core` so YAML and binary sources share them; this module
+//! re-exports them to keep existing `unity_asset_binary::unity_version::*` paths working, and adds
+//! [`InferVersion`] for files whose version string was stripped.
+
+mod inference;
+
+pub use inference::{InferVersion, InferredVersion, is_stripped_version};
+pub use unity_asset_core::unity_version::*;
@@ -0,0 +1,156 @@
+//! Addressables catalog parsing and loading against a catalog in the packed `catalog.json`
+//! format. Its two bundles map to the `banner_1` (addresses `Banner1`, label `ui`) and
+//! `atlas_test` (address `WaterTower`) samples; `Banner1` also depends on the atlas bundle.
+
+use std::path::PathBuf;
+use unity_asset_binary::addressables::{CatalogKey, ContentCatalog, ResourceLocation};
+use unity_asset_binary::bundle::BundleLoader;
+
+const UI_BUNDLE: &str = "ui_assets_all_5b7c0e6f1d2a3b4c5d6e7f8091a2b3c4.bundle";
+const ATLAS_BUNDLE: &str = "atlas_assets_all_0f1e2d3c4b5a69788796a5b4c3d2e1f0.bundle";
+
+fn catalog() -> ContentCatalog {
+    ContentCatalog::from_file(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/addressables_catalog.json"),
+    )
+    .unwrap()
+}
+
+fn sample_resolver(location: &ResourceLocation) -> Option<PathBuf> {
+    let sample = match location.file_name() {
+        UI_BUNDLE => "banner_1",
+        ATLAS_BUNDLE => "atlas_test",
+        _ => return None,
+    };
+    Some(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../tests/samples")
+            .join(sample),
+    )
+}
+
+#[test]
+fn catalog_resolves_address_to_bundle_and_dependencies() {
+    let catalog = catalog();
+    assert_eq!(catalog.locator_id, "AddressablesMainContentCatalog");
+    assert_eq!(catalog.location_count(), 4);
+
+    let locations = catalog.resolve("Banner1");
+    assert_eq!(locations.len(), 1);
+    let banner = &locations[0];
+    assert_eq!(banner.primary_key, "Banner1");
+    assert_eq!(
+        banner.internal_id,
+        "Assets/AssetBundles/Images/Banner/banner_1.png"
+    );
+    assert_eq!(
+        banner.resource_type.as_deref(),
+        Some("UnityEngine.Texture2D")
+    );
+    assert!(!banner.is_bundle());
+    assert_eq!(banner.bundle_file_name(), Some(UI_BUNDLE));
+    assert_eq!(banner.dependency_bundles(), vec![ATLAS_BUNDLE]);
+    assert_eq!(banner.dependency_hash, -1245093321);
+
+    // Prefixed internal ids are expanded; runtime placeholders are kept.
+    let bundle = banner.bundle().unwrap();
+    assert_eq!(
+        bundle.internal_id,
+        format!(
+            "{{UnityEngine.AddressableAssets.Addressables.RuntimePath}}/StandaloneWindows64/{}",
+            UI_BUNDLE
+        )
+    );
+
+    // The asset GUID and labels (ASCII and UTF-16 keys) resolve too.
+    assert_eq!(
+        catalog.resolve("9d5c3f0e2b1a4c6d8e7f6a5b4c3d2e1f"),
+        locations
+    );
+    assert_eq!(catalog.resolve("界面"), locations);
+    let labelled: Vec<String> = catalog
+        .resolve("ui")
+        .into_iter()
+        .map(|l| l.primary_key)
+        .collect();
+    assert_eq!(labelled, vec!["Banner1", "WaterTower"]);
+    assert!(catalog.resolve("Missing").is_empty());
+    assert!(catalog.keys().contains(&CatalogKey::Int(1983471102)));
+}
+
+#[test]
+fn catalog_surfaces_bundle_hash_and_crc() {
+    let catalog = catalog();
+    assert_eq!(
+        catalog.build_result_hash,
+        "e1b8a6e8d3c24f7f9a0b1c2d3e4f5a6b"
+    );
+
+    let bundles = catalog.bundle_locations();
+    assert_eq!(bundles.len(), 2);
+    let ui = bundles.iter().find(|b| b.file_name() == UI_BUNDLE).unwrap();
+    let options = ui.bundle_options.as_ref().unwrap();
+    assert_eq!(options.hash, "5b7c0e6f1d2a3b4c5d6e7f8091a2b3c4");
+    assert_eq!(options.crc, 2749367187);
+    assert_eq!(options.bundle_name, "ui_assets_all");
+    assert_eq!(options.bundle_size, 34683);
+    assert_eq!(ui.primary_key, UI_BUNDLE);
+
+    let atlas = bundles
+        .iter()
+        .find(|b| b.file_name() == ATLAS_BUNDLE)
+        .unwrap();
+    assert_eq!(atlas.bundle_options.as_ref().unwrap().crc, 0);
+}
+
+#[test]
+fn load_addressable_loads_dependencies_first_and_finds_objects() {
+    let catalog = catalog();
+    let mut loader = BundleLoader::new();
+    let loaded = loader
+        .load_addressable(&catalog, "Banner1", sample_resolver)
+        .unwrap();
+
+    let names: Vec<&str> = loaded
+        .bundles
+        .iter()
+        .map(|b| b.rsplit(['/', '\\']).next().unwrap())
+        .collect();
+    assert_eq!(names, vec!["atlas_test", "banner_1"]);
+    assert_eq!(loader.loaded_bundles().len(), 2);
+
+    // The texture and its sprite share the container path.
+    let mut path_ids: Vec<i64> = loaded.objects.iter().map(|o| o.path_id).collect();
+    path_ids.sort();
+    assert_eq!(path_ids, vec![-8325468307350463555, -3875358842991402074]);
+    for object in &loaded.objects {
+        assert_eq!(
+            object.container_path,
+            "assets/assetbundles/images/banner/banner_1.png"
+        );
+        assert_eq!(object.location.primary_key, "Banner1");
+        let bundle = loader.get_bundle(&object.bundle).unwrap();
+        assert!(
+            bundle.assets[object.asset_index]
+                .find_object(object.path_id)
+                .is_some()
+        );
+    }
+
+    let water = loader
+        .load_addressable(&catalog, "WaterTower", sample_resolver)
+        .unwrap();
+    assert_eq!(water.objects.len(), 1);
+    assert_eq!(water.objects[0].path_id, -6786743639055429899);
+
+    assert!(
+        loader
+            .load_addressable(&catalog, "Banner1", |_| None)
+            .is_err()
+    );
+    assert!(
+        loader
+            .load_addressable(&catalog, "Missing", sample_resolver)
+            .is_err()
+    );
+}
@@ -0,0 +1,77 @@
+//! Allocation regression benchmark for parsing the sample bundles (`profiling` feature).
+//!
+//! Run with `cargo test -p unity-asset-binary --features profiling --test allocation_profile_tests -- --nocapture`.
+
+use std::path::PathBuf;
+
+use unity_asset_binary::bundle::load_bundle_from_memory;
+use unity_asset_binary::profiling::{AllocationStats, CountingAllocator, measure_allocations};
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator::new();
+
+/// Per-sample `bytes_allocated` before the borrowed-read migration and the budget enforced now.
+///
+/// Baseline total was 18_501_309 bytes in 23_269 allocations; after switching temporary reads to
+/// `read_bytes_ref` it is 17_049_942 bytes in 23_218 allocations. Budgets leave ~1% headroom.
+const BUDGETS: &[(&str, u64, u64)] = &[
+    ("atlas_test", 1_183_470, 1_120_000),
+    ("banner_1", 445_854, 416_000),
+    ("char_118_yuki.ab", 3_896_037, 3_230_000),
+    ("xinzexi_2_n_tex", 12_975_948, 12_470_000),
+];
+
+fn sample_paths() -> Vec<PathBuf> {
+    let samples = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/samples");
+    let mut out: Vec<PathBuf> = std::fs::read_dir(&samples)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.is_file())
+        .collect();
+    out.sort();
+    out
+}
+
+/// Load a bundle and parse every object through its TypeTree (`None` if the bundle is unsupported).
+fn parse_all(data: Vec<u8>) -> Option<usize> {
+    let bundle = load_bundle_from_memory(data).ok()?;
+    let mut objects = 0usize;
+    for asset in &bundle.assets {
+        for handle in asset.object_handles() {
+            handle.read().unwrap();
+            objects += 1;
+        }
+    }
+    Some(objects)
+}
+
+#[test]
+fn sample_bundle_parse_allocations() {
+    let mut total = AllocationStats::default();
+    for path in sample_paths() {
+        let data = std::fs::read(&path).unwrap();
+        let (objects, stats) = measure_allocations(|| parse_all(data));
+        let Some(objects) = objects else {
+            continue;
+        };
+        let name = path.file_name().unwrap().to_string_lossy();
+        println!(
+            "{}: objects={} allocations={} bytes_allocated={} peak_live_bytes={}",
+            name, objects, stats.allocations, stats.bytes_allocated, stats.peak_live_bytes
+        );
+        assert!(stats.allocations > 0, "CountingAllocator is not installed");
+        if let Some((_, baseline, budget)) = BUDGETS.iter().find(|(n, _, _)| *n == name) {
+            assert!(
+                stats.bytes_allocated <= *budget,
+                "{name}: {} bytes allocated exceeds budget {budget} (baseline {baseline})",
+                stats.bytes_allocated
+            );
+        }
+        total.allocations += stats.allocations;
+        total.bytes_allocated += stats.bytes_allocated;
+    }
+    println!(
+        "total: allocations={} bytes_allocated={}",
+        total.allocations, total.bytes_allocated
+    );
+}
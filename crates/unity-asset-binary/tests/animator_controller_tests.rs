@@ -0,0 +1,152 @@
+//! AnimatorController state machine parsing against typetree dumps of the same controller in the
+//! 5.4 layout (`m_TypeID`, `{first, second}` TOS pairs, bare arrays) and the 2019.4 layout
+//! (`OffsetPtr` `data` wrappers, `[key, value]` TOS pairs).
+
+use indexmap::IndexMap;
+use unity_asset_binary::animator::{
+    AnimatorConditionMode, AnimatorController, AnimatorMotion, AnimatorParameterType,
+    AnimatorParameterValue, BlendTreeType,
+};
+use unity_asset_binary::test_support::{object_properties, read_fixture};
+use unity_asset_binary::unity_objects::ObjectRef;
+use unity_asset_core::UnityValue;
+
+fn load(name: &str) -> AnimatorController {
+    let dump = read_fixture(name);
+    AnimatorController::from_typetree(&object_properties(&dump, "AnimatorController")).unwrap()
+}
+
+fn assert_player_controller(controller: &AnimatorController) {
+    assert_eq!(controller.name, "PlayerController");
+    assert_eq!(controller.animation_clips.len(), 6);
+
+    let names: Vec<_> = controller
+        .parameters
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect();
+    assert_eq!(names, ["Speed", "Grounded", "Jump", "Combo"]);
+    let speed = controller.parameter("Speed").unwrap();
+    assert_eq!(speed.kind, AnimatorParameterType::Float);
+    assert_eq!(speed.default, Some(AnimatorParameterValue::Float(0.5)));
+    assert_eq!(
+        controller.parameter("Grounded").unwrap().default,
+        Some(AnimatorParameterValue::Bool(true))
+    );
+    let jump = controller.parameter("Jump").unwrap();
+    assert_eq!(jump.kind, AnimatorParameterType::Trigger);
+    assert_eq!(jump.default, Some(AnimatorParameterValue::Bool(false)));
+    assert_eq!(
+        controller.parameter("Combo").unwrap().default,
+        Some(AnimatorParameterValue::Int(2))
+    );
+
+    let layers: Vec<_> = controller.layers.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(layers, ["Base Layer", "UpperBody"]);
+
+    let base = &controller.layer("Base Layer").unwrap().state_machine;
+    let states: Vec<_> = base.states.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(states, ["Idle", "Locomotion", "Jump", "Fall"]);
+    assert_eq!(base.default_state.as_deref(), Some("Idle"));
+    assert_eq!(base.transition_count(), 5);
+    assert_eq!(base.any_state_transitions.len(), 1);
+
+    let any = &base.any_state_transitions[0];
+    assert_eq!(any.destination.as_deref(), Some("Jump"));
+    assert_eq!(any.conditions.len(), 2);
+    assert_eq!(any.conditions[0].parameter, "Jump");
+    assert_eq!(any.conditions[0].mode, AnimatorConditionMode::If);
+
+    let idle = base.state("Idle").unwrap();
+    assert_eq!(idle.full_path, "Base Layer.Idle");
+    assert_eq!(
+        idle.motion,
+        AnimatorMotion::Clip {
+            index: 0,
+            clip: Some(ObjectRef::new(0, 7400000)),
+        }
+    );
+    let to_locomotion = &idle.transitions[0];
+    assert_eq!(to_locomotion.destination.as_deref(), Some("Locomotion"));
+    assert_eq!(
+        to_locomotion.conditions[0].mode,
+        AnimatorConditionMode::Greater
+    );
+    assert!((to_locomotion.conditions[0].threshold - 0.1).abs() < 1e-6);
+
+    let jump_state = base.state("Jump").unwrap();
+    assert_eq!(jump_state.speed, 1.2);
+    assert!(jump_state.transitions[0].has_exit_time);
+    assert_eq!(jump_state.transitions[0].exit_time, 0.9);
+    assert!(jump_state.transitions[0].conditions.is_empty());
+
+    let locomotion = base.state("Locomotion").unwrap();
+    assert_eq!(locomotion.tag.as_deref(), Some("Moving"));
+    let AnimatorMotion::BlendTree(tree) = &locomotion.motion else {
+        panic!("locomotion should be a blend tree: {:?}", locomotion.motion);
+    };
+    assert_eq!(tree.blend_type, BlendTreeType::Simple1D);
+    assert_eq!(tree.parameter.as_deref(), Some("Speed"));
+    assert_eq!(tree.parameter_y, None);
+    let thresholds: Vec<_> = tree.children.iter().map(|c| c.threshold).collect();
+    assert_eq!(thresholds, [Some(0.0), Some(1.5), Some(4.0)]);
+    assert_eq!(
+        tree.children[2].motion,
+        AnimatorMotion::Clip {
+            index: 2,
+            clip: Some(ObjectRef::new(0, 7400004)),
+        }
+    );
+
+    let upper = controller.layer("UpperBody").unwrap();
+    assert_eq!(upper.blending_mode, 1);
+    assert_eq!(upper.default_weight, 0.75);
+    let upper = &upper.state_machine;
+    assert_eq!(upper.transition_count(), 3);
+    assert_eq!(upper.state("Empty").unwrap().motion, AnimatorMotion::None);
+    let wave = upper.state("Wave").unwrap();
+    assert_eq!(wave.transitions[0].destination.as_deref(), Some("Empty"));
+    // Sub-state machine destinations have no state name.
+    assert_eq!(wave.transitions[1].destination, None);
+    assert_eq!(wave.transitions[1].destination_index, 30000);
+    assert_eq!(
+        wave.transitions[1].conditions[0].mode,
+        AnimatorConditionMode::NotEqual
+    );
+}
+
+#[test]
+fn animator_controller_2019_layout() {
+    let controller = load("animator_controller_2019.json");
+    assert_player_controller(&controller);
+
+    let json = controller.to_json();
+    assert_eq!(json["parameters"][0]["kind"], "float");
+    let locomotion = &json["layers"][0]["state_machine"]["states"][1];
+    assert_eq!(locomotion["motion"]["type"], "blend_tree");
+    assert_eq!(locomotion["motion"]["children"][1]["threshold"], 1.5);
+    assert_eq!(
+        json["layers"][1]["state_machine"]["states"][0]["transitions"][0]["conditions"][0]["mode"],
+        "equals"
+    );
+}
+
+#[test]
+fn animator_controller_5x_layout_matches_2019() {
+    let legacy = load("animator_controller_5x.json");
+    assert_player_controller(&legacy);
+    assert_eq!(
+        legacy.to_json(),
+        load("animator_controller_2019.json").to_json()
+    );
+}
+
+#[test]
+fn animator_controller_requires_controller_constant() {
+    let mut properties = IndexMap::new();
+    properties.insert(
+        "m_Name".to_string(),
+        UnityValue::String("Empty".to_string()),
+    );
+    assert!(AnimatorController::from_typetree(&properties).is_err());
+}
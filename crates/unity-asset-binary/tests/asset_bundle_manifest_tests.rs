@@ -0,0 +1,140 @@
+//! AssetBundleManifest (class 290) against a typetree dump of a 2019.4 root manifest bundle:
+//! bundle names, hashes, dependency expansion with a cycle, and dependency loading through it.
+
+use indexmap::IndexMap;
+use std::path::PathBuf;
+use unity_asset_binary::asset_bundle_manifest::AssetBundleManifestObject;
+use unity_asset_binary::bundle::{BundleLoader, BundleProcessor, DependencyResolver};
+use unity_asset_binary::test_support::{object_properties, read_fixture};
+use unity_asset_core::UnityValue;
+
+fn fixture() -> AssetBundleManifestObject {
+    let dump = read_fixture("asset_bundle_manifest_2019.json");
+    AssetBundleManifestObject::from_typetree(&object_properties(&dump, "AssetBundleManifest"))
+        .unwrap()
+}
+
+fn samples() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples")
+}
+
+#[test]
+fn manifest_lists_bundles_hashes_and_dependency_indices() {
+    let manifest = fixture();
+
+    assert_eq!(
+        manifest.bundle_names,
+        vec![
+            "characters",
+            "materials",
+            "shaders",
+            "textures",
+            "ui",
+            "fonts"
+        ]
+    );
+    assert_eq!(manifest.hashes.len(), 6);
+    assert_eq!(manifest.hash_of("characters").unwrap()[0], 0x25);
+    assert_eq!(manifest.hash_of("characters").unwrap()[15], 0xca);
+    assert_eq!(manifest.hash_of("missing"), None);
+    assert_eq!(manifest.dependencies[0], vec![1, 2]);
+    assert!(manifest.bundles_with_variant.is_empty());
+
+    assert_eq!(
+        manifest.dependencies_of("characters"),
+        vec!["materials", "shaders"]
+    );
+    assert!(manifest.dependencies_of("textures").is_empty());
+    assert!(manifest.dependencies_of("missing").is_empty());
+
+    let infos = manifest.bundle_infos();
+    assert_eq!(infos[1].name, "materials");
+    assert_eq!(infos[1].dependencies, vec!["shaders", "textures"]);
+
+    let mut map = IndexMap::new();
+    map.insert(
+        "AssetBundleInfos".to_string(),
+        UnityValue::Array(Vec::new()),
+    );
+    assert!(AssetBundleManifestObject::from_typetree(&map).is_err());
+}
+
+#[test]
+fn recursive_dependencies_expand_each_bundle_once() {
+    let manifest = fixture();
+
+    assert_eq!(
+        manifest.all_dependencies("characters"),
+        vec!["shaders", "textures", "materials"]
+    );
+    // ui -> fonts -> ui is a cycle: ui is never its own dependency.
+    assert_eq!(
+        manifest.all_dependencies("ui"),
+        vec!["shaders", "textures", "materials", "characters", "fonts"]
+    );
+    assert_eq!(
+        manifest
+            .all_dependencies("fonts")
+            .last()
+            .map(String::as_str),
+        Some("ui")
+    );
+    assert!(
+        !manifest
+            .all_dependencies("fonts")
+            .contains(&"fonts".to_string())
+    );
+    assert!(manifest.all_dependencies("missing").is_empty());
+
+    assert_eq!(
+        DependencyResolver::dependencies(&manifest, "ui"),
+        vec!["characters".to_string(), "fonts".to_string()]
+    );
+}
+
+#[test]
+fn load_with_dependencies_accepts_the_binary_manifest() {
+    let mut props = IndexMap::new();
+    let pair = |i: i64, value: UnityValue| UnityValue::Array(vec![UnityValue::Integer(i), value]);
+    let info = |deps: &[i64]| {
+        let mut info = IndexMap::new();
+        info.insert(
+            "AssetBundleDependencies".to_string(),
+            UnityValue::Array(deps.iter().map(|&d| UnityValue::Integer(d)).collect()),
+        );
+        UnityValue::Object(info)
+    };
+    props.insert(
+        "AssetBundleNames".to_string(),
+        UnityValue::Array(vec![
+            pair(0, UnityValue::String("banner_1".to_string())),
+            pair(1, UnityValue::String("atlas_test".to_string())),
+        ]),
+    );
+    props.insert(
+        "AssetBundleInfos".to_string(),
+        UnityValue::Array(vec![pair(0, info(&[1])), pair(1, info(&[]))]),
+    );
+    let manifest = AssetBundleManifestObject::from_typetree(&props).unwrap();
+
+    let mut loader = BundleLoader::new();
+    let keys = loader
+        .load_with_dependencies(samples(), "banner_1", &manifest)
+        .unwrap();
+    let names: Vec<&str> = keys
+        .iter()
+        .map(|k| k.rsplit(['/', '\\']).next().unwrap())
+        .collect();
+    assert_eq!(names, vec!["atlas_test", "banner_1"]);
+}
+
+#[test]
+fn regular_bundles_are_not_manifest_bundles() {
+    let mut processor = BundleProcessor::new();
+    processor.process_file(samples().join("banner_1")).unwrap();
+    let key = samples().join("banner_1").to_string_lossy().to_string();
+
+    assert!(!processor.is_manifest_bundle(&key));
+    assert_eq!(processor.asset_bundle_manifest(&key), None);
+    assert!(!processor.is_manifest_bundle("not loaded"));
+}
@@ -0,0 +1,175 @@
+//! AudioMixer parsing against a typetree dump of a small mixer.
+//!
+//! `GameMixer` has `Master` with `Music` (Attenuation + Lowpass) and a muted `SFX` below it,
+//! two snapshots (`Snapshot`, the start snapshot, and `Quiet`) and `Music/Volume` exposed as
+//! `MusicVolume`.
+
+use indexmap::IndexMap;
+use unity_asset_binary::audio_mixer::{
+    AudioMixerController, AudioMixerEffectController, AudioMixerGroupController,
+    AudioMixerSnapshotController, AudioMixerTree, MixerGuid,
+};
+use unity_asset_binary::test_support::{object_properties, properties, read_fixture};
+use unity_asset_core::UnityValue;
+
+fn fixture() -> serde_json::Value {
+    read_fixture("audio_mixer_music_sfx.json")
+}
+
+fn parse_all<T>(
+    dump: &serde_json::Value,
+    key: &str,
+    parse: fn(&IndexMap<String, UnityValue>) -> unity_asset_binary::Result<T>,
+) -> Vec<T> {
+    dump[key]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|o| parse(&object_properties(o, "properties")).unwrap())
+        .collect()
+}
+
+fn mixer(dump: &serde_json::Value) -> AudioMixerController {
+    let fields = dump["AudioMixerController"]["properties"]
+        .as_object()
+        .unwrap();
+    AudioMixerController::from_typetree(&properties(fields)).unwrap()
+}
+
+fn tree() -> AudioMixerTree {
+    let dump = fixture();
+    AudioMixerTree::build(
+        &mixer(&dump),
+        &parse_all(
+            &dump,
+            "AudioMixerGroupController",
+            AudioMixerGroupController::from_typetree,
+        ),
+        &parse_all(
+            &dump,
+            "AudioMixerEffectController",
+            AudioMixerEffectController::from_typetree,
+        ),
+        &parse_all(
+            &dump,
+            "AudioMixerSnapshotController",
+            AudioMixerSnapshotController::from_typetree,
+        ),
+    )
+}
+
+#[test]
+fn mixer_constant_counts() {
+    let mixer = mixer(&fixture());
+
+    assert_eq!(mixer.name, "GameMixer");
+    assert_eq!(mixer.constant.groups.len(), 3);
+    assert_eq!(mixer.constant.effects.len(), 4);
+    assert_eq!(mixer.constant.snapshots.len(), 2);
+    assert_eq!(mixer.constant.group_names, ["Master", "Music", "SFX"]);
+    assert_eq!(mixer.constant.snapshot_names, ["Snapshot", "Quiet"]);
+    assert_eq!(mixer.constant.exposed_parameters, [(2882434245, 2)]);
+}
+
+#[test]
+fn tree_has_group_hierarchy_and_effects() {
+    let tree = tree();
+
+    assert_eq!(tree.group_names(), ["Master", "Music", "SFX"]);
+    assert_eq!(tree.groups.len(), 1);
+    let master = &tree.groups[0];
+    assert_eq!(master.children.len(), 2);
+
+    let music = &master.children[0];
+    let effects: Vec<&str> = music.effects.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(effects, ["Attenuation", "Lowpass"]);
+    let lowpass = &music.effects[1];
+    assert_eq!(lowpass.wet_mix, Some(0.0));
+    assert_eq!(
+        lowpass.parameters,
+        [
+            ("Cutoff freq".to_string(), 5000.0),
+            ("Resonance".to_string(), 1.0)
+        ]
+    );
+    assert_eq!(music.effects[0].wet_mix, None);
+
+    assert!(master.children[1].mute);
+}
+
+#[test]
+fn snapshots_map_values_to_parameter_labels() {
+    let tree = tree();
+
+    assert_eq!(tree.start_snapshot.as_deref(), Some("Snapshot"));
+    let quiet = tree.snapshot("Quiet").unwrap();
+    assert_eq!(quiet.values.len(), 9);
+    assert_eq!(quiet.values["Music/Volume"], -20.0);
+    assert_eq!(quiet.values["SFX/Volume"], -6.0);
+    assert_eq!(quiet.values["Music/Lowpass/Cutoff freq"], 800.0);
+    assert_eq!(
+        tree.snapshot("Snapshot").unwrap().values["Music/Volume"],
+        -3.0
+    );
+}
+
+#[test]
+fn exposed_parameters_resolve_names_from_editor_data() {
+    let tree = tree();
+
+    assert_eq!(tree.exposed_parameters.len(), 1);
+    let exposed = &tree.exposed_parameters[0];
+    assert_eq!(exposed.name.as_deref(), Some("MusicVolume"));
+    assert_eq!(exposed.parameter, "Music/Volume");
+    assert_eq!(exposed.name_hash, 2882434245);
+}
+
+#[test]
+fn constant_alone_keeps_buffer_names() {
+    // Built players ship no effect controllers or exposed parameter names.
+    let mut mixer = mixer(&fixture());
+    mixer.exposed_parameter_names.clear();
+    let tree = AudioMixerTree::build(&mixer, &[], &[], &[]);
+
+    assert_eq!(tree.group_names(), ["Master", "Music", "SFX"]);
+    let lowpass = &tree.groups[0].children[0].effects[1];
+    assert_eq!(lowpass.name, "Effect 2");
+    assert_eq!(lowpass.parameters[0].0, "Parameter 0");
+    assert_eq!(
+        tree.snapshot("Quiet").unwrap().values["Music/Volume"],
+        -20.0
+    );
+    assert_eq!(tree.exposed_parameters[0].name, None);
+}
+
+#[test]
+fn tree_json_round_trips() {
+    let tree = tree();
+    let json = tree.to_json();
+
+    assert_eq!(json["groups"][0]["name"], "Master");
+    let guid = json["groups"][0]["guid"].as_str().unwrap();
+    assert_eq!(guid.len(), 32);
+    assert_eq!(guid.parse::<MixerGuid>().unwrap(), tree.groups[0].guid);
+    assert_eq!(json["snapshots"][1]["values"]["Music/Volume"], -20.0);
+
+    let back: AudioMixerTree = serde_json::from_value(json).unwrap();
+    assert_eq!(back, tree);
+}
+
+#[test]
+fn guid_formats_like_meta_files() {
+    let guid = MixerGuid([0x01234567, 0x89abcdef, 0, 0xffffffff]);
+    assert_eq!(guid.to_string(), "76543210fedcba9800000000ffffffff");
+    assert_eq!(guid.to_string().parse::<MixerGuid>().unwrap(), guid);
+    assert!("not a guid".parse::<MixerGuid>().is_err());
+}
+
+#[test]
+fn mixer_without_constant_is_rejected() {
+    let dump = fixture();
+    let group = dump["AudioMixerGroupController"][0]["properties"]
+        .as_object()
+        .unwrap();
+    assert!(AudioMixerController::from_typetree(&properties(group)).is_err());
+}
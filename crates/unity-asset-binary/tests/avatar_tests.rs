@@ -0,0 +1,117 @@
+//! Avatar humanoid mapping against a typetree dump of a 2019.4 humanoid rig.
+//!
+//! The rig lives under `Armature/Hips`; Jaw is only mapped through `m_HumanDescription`.
+
+use indexmap::IndexMap;
+use unity_asset_binary::avatar::{Avatar, HumanBone};
+use unity_asset_binary::test_support::{object_properties, read_fixture};
+use unity_asset_core::UnityValue;
+
+fn fixture() -> IndexMap<String, UnityValue> {
+    object_properties(&read_fixture("avatar_2019.json"), "Avatar")
+}
+
+const HEAD: &str = "Armature/Hips/Spine/Chest/Neck/Head";
+
+#[test]
+fn avatar_resolves_human_bone_paths() {
+    let avatar = Avatar::from_typetree(&fixture()).unwrap();
+    assert_eq!(avatar.name, "RobotAvatar");
+    assert!(avatar.is_human());
+
+    assert_eq!(avatar.bone_path(HumanBone::Hips), Some("Armature/Hips"));
+    assert_eq!(
+        avatar.bone_path(HumanBone::Spine),
+        Some("Armature/Hips/Spine")
+    );
+    assert_eq!(avatar.bone_path(HumanBone::Head), Some(HEAD));
+    assert_eq!(
+        avatar.bone_path(HumanBone::LeftLowerLeg),
+        Some("Armature/Hips/UpperLeg.L/LowerLeg.L")
+    );
+    assert_eq!(
+        avatar.bone_path(HumanBone::LeftHand),
+        Some("Armature/Hips/Spine/Chest/Shoulder.L/UpperArm.L/LowerArm.L/Hand.L")
+    );
+    assert!(
+        avatar
+            .bone_path(HumanBone::LeftThumbProximal)
+            .unwrap()
+            .ends_with("/Hand.L/Thumb1.L")
+    );
+    // Not in the constant's mapping, filled in from the human description.
+    assert_eq!(
+        avatar.bone_path(HumanBone::Jaw),
+        Some("Armature/Hips/Spine/Chest/Neck/Head/Jaw")
+    );
+    assert_eq!(avatar.bone_path(HumanBone::UpperChest), None);
+    assert_eq!(avatar.bone_path(HumanBone::RightHand), None);
+
+    assert_eq!(avatar.human_bone_for_path(HEAD), Some(HumanBone::Head));
+    // Mesh `m_BoneNameHashes` are CRC32s of the same paths.
+    let head_hash = avatar.node(HEAD).unwrap().path_hash;
+    assert_eq!(avatar.path_for_hash(head_hash), Some(HEAD));
+    assert_eq!(avatar.human_bone_for_hash(head_hash), Some(HumanBone::Head));
+}
+
+#[test]
+fn avatar_exposes_skeleton_and_default_pose() {
+    let avatar = Avatar::from_typetree(&fixture()).unwrap();
+    assert_eq!(avatar.nodes.len(), 17);
+    assert_eq!(avatar.nodes[0].path, "");
+    assert_eq!(avatar.nodes[0].parent, None);
+
+    let head = avatar.node(HEAD).unwrap();
+    assert_eq!(head.name(), "Head");
+    let neck = &avatar.nodes[head.parent.unwrap()];
+    assert_eq!(neck.name(), "Neck");
+
+    let hips = avatar.node("Armature/Hips").unwrap().default_pose.unwrap();
+    assert_eq!(hips.translation, [0.0, 1.0, 0.0]);
+    assert_eq!(hips.rotation, [0.0, 0.0, 0.0, 1.0]);
+    let armature = avatar.node("Armature").unwrap().default_pose.unwrap();
+    assert_eq!(armature.scale, [100.0, 100.0, 100.0]);
+
+    let json = avatar.to_json();
+    assert_eq!(json["human_bones"]["Head"], HEAD);
+}
+
+#[test]
+fn avatar_pre_5_6_body_order_has_no_upper_chest() {
+    let mut properties = fixture();
+    let human = properties
+        .get_mut("m_Avatar")
+        .and_then(UnityValue::as_object_mut)
+        .and_then(|avatar| avatar.get_mut("m_Human"))
+        .and_then(UnityValue::as_object_mut)
+        .and_then(|human| human.get_mut("data"))
+        .and_then(UnityValue::as_object_mut)
+        .unwrap();
+    let Some(UnityValue::Array(body)) = human.get_mut("m_HumanBoneIndex") else {
+        panic!("fixture has no m_HumanBoneIndex");
+    };
+    body.remove(9);
+    properties.shift_remove("m_HumanDescription");
+
+    let avatar = Avatar::from_typetree(&properties).unwrap();
+    assert_eq!(
+        avatar.bone_path(HumanBone::Neck),
+        Some("Armature/Hips/Spine/Chest/Neck")
+    );
+    assert_eq!(avatar.bone_path(HumanBone::Head), Some(HEAD));
+    assert_eq!(avatar.bone_path(HumanBone::Jaw), None);
+}
+
+#[test]
+fn human_bone_names_round_trip() {
+    assert_eq!(HumanBone::ALL.len(), 55);
+    assert_eq!(HumanBone::ALL[54], HumanBone::UpperChest);
+    for bone in HumanBone::ALL {
+        assert_eq!(HumanBone::from_name(bone.name()), Some(*bone));
+    }
+    assert_eq!(
+        HumanBone::from_name("Right Little Distal"),
+        Some(HumanBone::RightLittleDistal)
+    );
+    assert_eq!(HumanBone::from_name("Tail"), None);
+}
@@ -0,0 +1,85 @@
+//! BuildSettings against typetree dumps from a 2019.4 player and a 4.7 player (`levels` list,
+//! fewer flags).
+
+use indexmap::IndexMap;
+use unity_asset_binary::build_settings::{BuildSettings, find_build_settings};
+use unity_asset_binary::test_support::{object_properties, read_fixture};
+use unity_asset_core::UnityValue;
+
+fn fixture(name: &str) -> BuildSettings {
+    let dump = read_fixture(name);
+    BuildSettings::from_typetree(&object_properties(&dump, "BuildSettings")).unwrap()
+}
+
+#[test]
+fn build_settings_lists_scenes_in_build_order() {
+    let settings = fixture("build_settings_2019.json");
+
+    assert_eq!(
+        settings.scenes,
+        vec![
+            "Assets/Scenes/Boot.unity",
+            "Assets/Scenes/MainMenu.unity",
+            "Assets/Scenes/Levels/Forest.unity",
+        ]
+    );
+    assert_eq!(settings.scene_name(2), Some("Forest"));
+    assert_eq!(settings.scene_name(3), None);
+    assert_eq!(settings.version, "2019.4.31f1");
+    assert_eq!(settings.preloaded_plugins, vec!["libfmod.so"]);
+    assert_eq!(settings.build_tags, vec!["release"]);
+    assert!(settings.enabled_vr_devices.is_empty());
+    assert_eq!(settings.flag("hasPROVersion"), Some(true));
+    assert_eq!(settings.flag("hasClusterRendering"), Some(false));
+    assert!(!settings.is_debug_build());
+}
+
+#[test]
+fn build_settings_reads_legacy_levels_list() {
+    let settings = fixture("build_settings_4x.json");
+
+    assert_eq!(
+        settings.scenes,
+        vec!["Assets/Title.unity", "Assets/Game.unity"]
+    );
+    assert_eq!(settings.version, "4.7.2f1");
+    assert!(settings.is_debug_build());
+    assert_eq!(settings.flag("hasClusterRendering"), None);
+    assert!(settings.preloaded_plugins.is_empty());
+}
+
+#[test]
+fn build_settings_keeps_enabled_editor_style_scenes() {
+    let mut fields = IndexMap::new();
+    let scene = |path: &str, enabled: bool| {
+        UnityValue::Object(
+            [
+                ("enabled".to_string(), UnityValue::Bool(enabled)),
+                ("path".to_string(), UnityValue::String(path.to_string())),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    };
+    fields.insert(
+        "scenes".to_string(),
+        UnityValue::Array(vec![
+            scene("Assets/A.unity", true),
+            scene("Assets/B.unity", false),
+            scene("Assets/C.unity", true),
+        ]),
+    );
+
+    let settings = BuildSettings::from_typetree(&fields).unwrap();
+    assert_eq!(settings.scenes, vec!["Assets/A.unity", "Assets/C.unity"]);
+    assert_eq!(settings.version, "");
+}
+
+#[test]
+fn find_build_settings_is_none_without_one() {
+    let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/samples/char_118_yuki.ab");
+    let bundle =
+        unity_asset_binary::bundle::load_bundle_from_memory(std::fs::read(path).unwrap()).unwrap();
+    assert!(find_build_settings(&bundle.assets[0]).is_none());
+}
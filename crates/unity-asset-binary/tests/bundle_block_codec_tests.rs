@@ -0,0 +1,174 @@
+//! UnityFS bundles whose blocks-info codec differs from the data block codecs.
+//!
+//! The fixtures are hand-assembled: header flags carry the blocks-info codec and each entry in
+//! the block table carries its own codec.
+
+use std::io::{BufReader, Cursor};
+
+use unity_asset_binary::bundle::{AssetBundle, BundleCompression, BundleLoadOptions, BundleParser};
+use unity_asset_binary::compression::CompressionType;
+
+const BLOCK_SIZE: usize = 4096;
+
+fn payload() -> Vec<u8> {
+    (0..BLOCK_SIZE * 3)
+        .map(|i| ((i / 7) % 251) as u8 ^ (i % 3) as u8)
+        .collect()
+}
+
+fn compress(codec: CompressionType, data: &[u8]) -> Vec<u8> {
+    match codec {
+        CompressionType::None => data.to_vec(),
+        CompressionType::Lz4 | CompressionType::Lz4Hc => lz4_flex::block::compress(data),
+        CompressionType::Lzma => {
+            // Unity layout: props(1) + dict_size(4) + raw stream, no unpacked-size field.
+            let mut input = BufReader::new(Cursor::new(data));
+            let mut out = Vec::new();
+            let options = lzma_rs::compress::Options {
+                unpacked_size: lzma_rs::compress::UnpackedSize::WriteToHeader(Some(
+                    data.len() as u64
+                )),
+            };
+            lzma_rs::lzma_compress_with_options(&mut input, &mut out, &options).unwrap();
+            out.drain(5..13);
+            out
+        }
+        other => panic!("fixture codec {} not supported", other.name()),
+    }
+}
+
+fn write_cstring(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(s.as_bytes());
+    buf.push(0);
+}
+
+/// Build a UnityFS v6 bundle with one node spanning all blocks.
+fn build_bundle(info_codec: u32, block_codecs: &[u16]) -> Vec<u8> {
+    let data = payload();
+    let chunks: Vec<&[u8]> = data.chunks(data.len() / block_codecs.len()).collect();
+    assert_eq!(chunks.len(), block_codecs.len());
+
+    let mut block_table = Vec::new();
+    let mut block_data = Vec::new();
+    for (chunk, &flags) in chunks.iter().zip(block_codecs) {
+        let codec = CompressionType::from_flags(flags as u32).unwrap();
+        let compressed = if codec == CompressionType::Lzham {
+            // Never decoded; the parser must reject it.
+            chunk.to_vec()
+        } else {
+            compress(codec, chunk)
+        };
+        block_table.extend_from_slice(&(chunk.len() as u32).to_be_bytes());
+        block_table.extend_from_slice(&(compressed.len() as u32).to_be_bytes());
+        block_table.extend_from_slice(&flags.to_be_bytes());
+        block_data.extend_from_slice(&compressed);
+    }
+
+    let mut info = vec![0u8; 16];
+    info.extend_from_slice(&(block_codecs.len() as i32).to_be_bytes());
+    info.extend_from_slice(&block_table);
+    info.extend_from_slice(&1i32.to_be_bytes());
+    info.extend_from_slice(&0i64.to_be_bytes());
+    info.extend_from_slice(&(data.len() as i64).to_be_bytes());
+    info.extend_from_slice(&4u32.to_be_bytes());
+    write_cstring(&mut info, "CAB-test.resS");
+
+    let info_codec_type = CompressionType::from_flags(info_codec).unwrap();
+    let compressed_info = if info_codec_type == CompressionType::Lzham {
+        info.clone()
+    } else {
+        compress(info_codec_type, &info)
+    };
+
+    let mut header = Vec::new();
+    write_cstring(&mut header, "UnityFS");
+    header.extend_from_slice(&6u32.to_be_bytes());
+    write_cstring(&mut header, "5.x.x");
+    write_cstring(&mut header, "2018.4.2f1");
+    let header_len = header.len() + 8 + 4 + 4 + 4;
+    let total = header_len + compressed_info.len() + block_data.len();
+    header.extend_from_slice(&(total as i64).to_be_bytes());
+    header.extend_from_slice(&(compressed_info.len() as u32).to_be_bytes());
+    header.extend_from_slice(&(info.len() as u32).to_be_bytes());
+    header.extend_from_slice(&(info_codec | 0x40).to_be_bytes());
+
+    let mut bundle = header;
+    bundle.extend_from_slice(&compressed_info);
+    bundle.extend_from_slice(&block_data);
+    assert_eq!(bundle.len(), total);
+    bundle
+}
+
+fn eager_options() -> BundleLoadOptions {
+    BundleLoadOptions::default()
+        .with_load_assets(false)
+        .with_decompress_blocks(true)
+        .with_validate(true)
+}
+
+fn assert_round_trip(info_codec: u32, block_codecs: &[u16]) -> AssetBundle {
+    let bytes = build_bundle(info_codec, block_codecs);
+    let expected = payload();
+
+    let eager = BundleParser::from_bytes_with_options(bytes.clone(), eager_options())
+        .unwrap_or_else(|e| panic!("info={info_codec} blocks={block_codecs:?}: {e}"));
+    assert_eq!(eager.data(), expected.as_slice());
+
+    let lazy = BundleParser::from_bytes_with_options(bytes, BundleLoadOptions::fast()).unwrap();
+    let node = lazy.nodes[0].clone();
+    assert_eq!(lazy.extract_node_data(&node).unwrap(), expected);
+
+    eager
+}
+
+#[test]
+fn lzma_blocks_info_with_lz4_blocks() {
+    let bundle = assert_round_trip(1, &[2, 2, 2]);
+    let stats = BundleCompression::get_compression_stats(&bundle.blocks);
+    assert_eq!(stats.block_codecs, vec![(CompressionType::Lz4, 3)]);
+}
+
+#[test]
+fn uncompressed_blocks_info_with_lz4hc_blocks() {
+    let bundle = assert_round_trip(0, &[3, 3, 3]);
+    let stats = BundleCompression::get_compression_stats(&bundle.blocks);
+    assert_eq!(stats.block_codecs, vec![(CompressionType::Lz4Hc, 3)]);
+}
+
+#[test]
+fn lz4_blocks_info_with_mixed_block_codecs() {
+    // Streamed flag (0x40) on the stored block must not affect its codec.
+    let bundle = assert_round_trip(2, &[0x40, 1, 3]);
+    let stats = BundleCompression::get_compression_stats(&bundle.blocks);
+    assert_eq!(
+        stats.block_codecs,
+        vec![
+            (CompressionType::None, 1),
+            (CompressionType::Lzma, 1),
+            (CompressionType::Lz4Hc, 1),
+        ]
+    );
+    assert_eq!(stats.unknown_codec_blocks, 0);
+}
+
+#[test]
+fn every_codec_combination_round_trips() {
+    for info_codec in [0u32, 1, 2, 3] {
+        for block_codec in [0u16, 1, 2, 3] {
+            assert_round_trip(info_codec, &[block_codec, block_codec, block_codec]);
+        }
+    }
+}
+
+#[test]
+fn lzham_is_rejected_at_both_layers() {
+    let info_err =
+        BundleParser::from_bytes_with_options(build_bundle(4, &[2, 2, 2]), eager_options())
+            .unwrap_err();
+    assert!(info_err.to_string().contains("LZHAM"), "{info_err}");
+
+    let block_err =
+        BundleParser::from_bytes_with_options(build_bundle(1, &[2, 4, 2]), eager_options())
+            .unwrap_err();
+    assert!(block_err.to_string().contains("LZHAM"), "{block_err}");
+}
@@ -0,0 +1,71 @@
+//! `AssetBundle::container` / `find_by_container_path`: container paths from `m_Container`.
+
+use unity_asset_binary::bundle::{AssetBundle, BundleParser, ContainerRef};
+use unity_asset_binary::typetree::TypeTree;
+use unity_asset_core::class_ids;
+
+fn load(name: &str) -> AssetBundle {
+    BundleParser::from_bytes(std::fs::read(format!("../../tests/samples/{}", name)).unwrap())
+        .unwrap()
+}
+
+#[test]
+fn container_paths_name_bundle_objects() {
+    let bundle = load("atlas_test");
+    let container = bundle.container();
+    assert_eq!(container.len(), 8);
+    assert_eq!(
+        container["assets/ui/textures/buildings/watertower/watertower.png"],
+        ContainerRef {
+            asset_index: 0,
+            path_id: -6786743639055429899,
+        }
+    );
+
+    for (path, target) in &container {
+        let handle = bundle.find_by_container_path(path).unwrap();
+        assert_eq!(handle.path_id(), target.path_id);
+    }
+    let atlas = bundle
+        .find_by_container_path("assets/ui/textures/atlases/buildingswatertoweratlas.spriteatlas")
+        .unwrap();
+    assert_eq!(atlas.class_id(), class_ids::SPRITE_ATLAS);
+}
+
+#[test]
+fn lookup_falls_back_to_case_insensitive_match() {
+    let bundle = load("banner_1");
+    let sprite = bundle
+        .find_by_container_path("Assets/AssetBundles/Images/Banner/banner_1.png")
+        .unwrap();
+    assert_eq!(sprite.path_id(), -8325468307350463555);
+    assert_eq!(sprite.class_id(), class_ids::SPRITE);
+    assert!(
+        bundle
+            .find_by_container_path("assets/missing.png")
+            .is_none()
+    );
+}
+
+#[test]
+fn stripped_typetree_uses_raw_layout() {
+    let mut bundle = load("xinzexi_2_n_tex");
+    let expected = bundle.container();
+    assert_eq!(expected.len(), 2);
+
+    let file = &mut bundle.assets[0];
+    let type_index = file
+        .objects
+        .iter()
+        .find(|o| o.type_id == class_ids::ASSET_BUNDLE)
+        .unwrap()
+        .type_index as usize;
+    file.types[type_index].type_tree = TypeTree::new();
+    file.enable_type_tree = false;
+
+    assert_eq!(bundle.container(), expected);
+    let mesh = bundle
+        .find_by_container_path("assets/artresource/atlas/paintings/xinzexi_2_n-mesh.asset")
+        .unwrap();
+    assert_eq!(mesh.class_id(), class_ids::MESH);
+}
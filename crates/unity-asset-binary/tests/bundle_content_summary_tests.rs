@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use unity_asset_binary::bundle::{AssetBundle, ContentSummary, load_bundle_from_memory};
+use unity_asset_core::{UnityValue, class_ids};
+
+fn sample_paths() -> Vec<PathBuf> {
+    let samples = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/samples");
+    let mut out: Vec<PathBuf> = std::fs::read_dir(&samples)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.is_file())
+        .collect();
+    out.sort();
+    out
+}
+
+/// Compute the same totals by fully parsing every object.
+fn ground_truth(bundle: &AssetBundle) -> (u64, BTreeMap<i32, usize>, f64, u64, usize) {
+    let mut pixels = 0u64;
+    let mut formats = BTreeMap::new();
+    let mut seconds = 0f64;
+    let mut vertices = 0u64;
+    let mut objects = 0usize;
+
+    for asset in &bundle.assets {
+        for handle in asset.object_handles() {
+            objects += 1;
+            let class_id = handle.class_id();
+            if ![
+                class_ids::TEXTURE_2D,
+                class_ids::AUDIO_CLIP,
+                class_ids::MESH,
+            ]
+            .contains(&class_id)
+            {
+                continue;
+            }
+            let obj = handle.read().unwrap();
+            let class = &obj.class;
+            match class_id {
+                class_ids::TEXTURE_2D => {
+                    let w = class.get("m_Width").and_then(UnityValue::as_i64).unwrap();
+                    let h = class.get("m_Height").and_then(UnityValue::as_i64).unwrap();
+                    pixels += (w * h) as u64;
+                    let format = class
+                        .get("m_TextureFormat")
+                        .and_then(UnityValue::as_i64)
+                        .unwrap();
+                    *formats.entry(format as i32).or_default() += 1;
+                }
+                class_ids::AUDIO_CLIP => {
+                    seconds += class.get("m_Length").and_then(UnityValue::as_f64).unwrap();
+                }
+                _ => {
+                    let Some(UnityValue::Object(vd)) = class.get("m_VertexData") else {
+                        panic!("mesh without m_VertexData");
+                    };
+                    vertices += vd
+                        .get("m_VertexCount")
+                        .and_then(UnityValue::as_i64)
+                        .unwrap() as u64;
+                }
+            }
+        }
+    }
+    (pixels, formats, seconds, vertices, objects)
+}
+
+#[test]
+fn content_summary_matches_full_parse_on_sample_bundles() {
+    let mut saw_texture = false;
+    let mut saw_mesh = false;
+
+    for path in sample_paths() {
+        let data = std::fs::read(&path).unwrap();
+        let Ok(bundle) = load_bundle_from_memory(data) else {
+            continue;
+        };
+
+        let summary = bundle.content_summary();
+        let (pixels, formats, seconds, vertices, objects) = ground_truth(&bundle);
+        let totals = &summary.totals;
+
+        assert_eq!(summary.files.len(), bundle.assets.len(), "{path:?}");
+        assert_eq!(totals.total_objects, objects, "{path:?}");
+        assert_eq!(totals.textures.unmeasured, 0, "{path:?}");
+        assert_eq!(totals.textures.total_pixels, pixels, "{path:?}");
+        assert_eq!(totals.textures.formats, formats, "{path:?}");
+        assert_eq!(totals.meshes.unmeasured, 0, "{path:?}");
+        assert_eq!(totals.meshes.total_vertices, vertices, "{path:?}");
+        assert!((totals.audio_clips.total_seconds - seconds).abs() < 1e-6);
+        assert_eq!(
+            totals.textures.count
+                + totals.audio_clips.count
+                + totals.meshes.count
+                + totals.mono_behaviours
+                + totals.other,
+            totals.total_objects
+        );
+
+        saw_texture |= totals.textures.count > 0;
+        saw_mesh |= totals.meshes.count > 0;
+    }
+
+    assert!(saw_texture, "expected at least one sample with textures");
+    assert!(saw_mesh, "expected at least one sample with meshes");
+}
+
+#[test]
+fn content_summary_round_trips_through_json() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/samples/char_118_yuki.ab");
+    let bundle = load_bundle_from_memory(std::fs::read(path).unwrap()).unwrap();
+    let summary = bundle.content_summary();
+
+    let json = serde_json::to_string(&summary).unwrap();
+    let back: ContentSummary = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, summary);
+}
@@ -0,0 +1,141 @@
+//! UnityFS bundles that list the same node name twice.
+//!
+//! The fixture is hand-assembled (one uncompressed block) from a SerializedFile taken out of a
+//! sample bundle, so both copies of the CAB parse as assets.
+
+use unity_asset_binary::asset::DumpFilter;
+use unity_asset_binary::bundle::{BundleLoadOptions, BundleParser, DuplicateNodeName};
+
+fn sample_cab() -> Vec<u8> {
+    let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/samples/banner_1");
+    let bundle = BundleParser::from_bytes(std::fs::read(path).unwrap()).unwrap();
+    let node_index = bundle.asset_node_index(0).unwrap();
+    bundle.extract_node_data(&bundle.nodes[node_index]).unwrap()
+}
+
+fn write_cstring(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(s.as_bytes());
+    buf.push(0);
+}
+
+/// Build a UnityFS v6 bundle with one uncompressed block holding `entries` back to back.
+fn build_bundle(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let data: Vec<u8> = entries
+        .iter()
+        .flat_map(|(_, b)| b.iter().copied())
+        .collect();
+
+    let mut info = vec![0u8; 16];
+    info.extend_from_slice(&1i32.to_be_bytes());
+    info.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    info.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    info.extend_from_slice(&0u16.to_be_bytes());
+    info.extend_from_slice(&(entries.len() as i32).to_be_bytes());
+    let mut offset = 0i64;
+    for (name, bytes) in entries {
+        info.extend_from_slice(&offset.to_be_bytes());
+        info.extend_from_slice(&(bytes.len() as i64).to_be_bytes());
+        info.extend_from_slice(&4u32.to_be_bytes());
+        write_cstring(&mut info, name);
+        offset += bytes.len() as i64;
+    }
+
+    let mut header = Vec::new();
+    write_cstring(&mut header, "UnityFS");
+    header.extend_from_slice(&6u32.to_be_bytes());
+    write_cstring(&mut header, "5.x.x");
+    write_cstring(&mut header, "2018.4.2f1");
+    let header_len = header.len() + 8 + 4 + 4 + 4;
+    let total = header_len + info.len() + data.len();
+    header.extend_from_slice(&(total as i64).to_be_bytes());
+    header.extend_from_slice(&(info.len() as u32).to_be_bytes());
+    header.extend_from_slice(&(info.len() as u32).to_be_bytes());
+    header.extend_from_slice(&0x40u32.to_be_bytes());
+
+    let mut bundle = header;
+    bundle.extend_from_slice(&info);
+    bundle.extend_from_slice(&data);
+    assert_eq!(bundle.len(), total);
+    bundle
+}
+
+fn duplicate_bundle_bytes() -> Vec<u8> {
+    let cab = sample_cab();
+    build_bundle(&[
+        ("CAB-dup", &cab),
+        ("CAB-dup.resS", b"first"),
+        ("CAB-dup", &cab),
+        ("CAB-dup.resS", b"second"),
+    ])
+}
+
+#[test]
+fn duplicate_names_keep_every_node_and_payload() {
+    let bundle = BundleParser::from_bytes(duplicate_bundle_bytes()).unwrap();
+
+    assert_eq!(bundle.nodes.len(), 4);
+    assert_eq!(bundle.assets.len(), 2);
+    assert_eq!(bundle.asset_names, vec!["CAB-dup", "CAB-dup"]);
+    assert_eq!(bundle.asset_node_index(0), Some(0));
+    assert_eq!(bundle.asset_node_index(1), Some(2));
+
+    // Name lookup still returns the first entry; the others stay reachable.
+    let first = bundle.find_file("CAB-dup.resS").unwrap();
+    assert_eq!(bundle.extract_file_data(first).unwrap(), b"first");
+    let all = bundle.find_files("CAB-dup.resS");
+    assert_eq!(all.len(), 2);
+    assert_eq!(bundle.extract_file_data(all[1]).unwrap(), b"second");
+    assert_eq!(bundle.find_nodes("CAB-dup").len(), 2);
+
+    let node = bundle.node_at(3).unwrap();
+    assert_eq!(bundle.extract_node_data(node).unwrap(), b"second");
+    assert_eq!(bundle.file_at(3).unwrap().name, "CAB-dup.resS");
+    assert_eq!(bundle.node_occurrence(1), Some(0));
+    assert_eq!(bundle.node_occurrence(3), Some(1));
+    assert_eq!(bundle.node_occurrence(4), None);
+
+    assert_eq!(
+        bundle.duplicate_node_names(),
+        vec![
+            DuplicateNodeName {
+                name: "CAB-dup".to_string(),
+                indices: vec![0, 2],
+            },
+            DuplicateNodeName {
+                name: "CAB-dup.resS".to_string(),
+                indices: vec![1, 3],
+            },
+        ]
+    );
+    assert_eq!(
+        bundle.unique_node_names(),
+        vec!["CAB-dup", "CAB-dup.resS", "CAB-dup_1", "CAB-dup_1.resS"]
+    );
+}
+
+#[test]
+fn duplicate_names_are_suffixed_when_dumping() {
+    let bundle = BundleParser::from_bytes(duplicate_bundle_bytes()).unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+
+    let report = bundle
+        .dump_raw_objects(tmp.path(), &DumpFilter::default())
+        .unwrap();
+
+    let per_file = bundle.assets[0].object_count();
+    assert!(per_file > 0);
+    assert_eq!(report.written.len(), per_file * 2);
+    assert!(tmp.path().join("CAB-dup").is_dir());
+    assert!(tmp.path().join("CAB-dup_1").is_dir());
+}
+
+#[test]
+fn unique_bundles_report_no_duplicates() {
+    let bundle = BundleParser::from_bytes_with_options(
+        build_bundle(&[("CAB-a", b"a"), ("CAB-b", b"b")]),
+        BundleLoadOptions::fast(),
+    )
+    .unwrap();
+    assert!(bundle.duplicate_node_names().is_empty());
+    assert_eq!(bundle.unique_node_names(), vec!["CAB-a", "CAB-b"]);
+}
@@ -173,11 +173,9 @@ fn legacy_directory_respects_max_compressed_size() {
         bytes.push(0);
     }
 
-    let options = BundleLoadOptions {
-        validate: false,
-        max_legacy_directory_compressed_size: Some(16),
-        ..Default::default()
-    };
+    let options = BundleLoadOptions::default()
+        .with_validate(false)
+        .with_max_legacy_directory_compressed_size(Some(16));
     let err = BundleParser::from_bytes_with_options(bytes, options).unwrap_err();
     assert!(matches!(err, BinaryError::ResourceLimitExceeded(_)));
 }
@@ -198,10 +196,7 @@ fn unityfs_blocks_info_respects_max_compressed_blocks_info_size() {
     let total_size = bytes.len() as i64;
     bytes[size_offset..size_offset + 8].copy_from_slice(&be_i64(total_size));
 
-    let options = BundleLoadOptions {
-        max_compressed_blocks_info_size: Some(16),
-        ..Default::default()
-    };
+    let options = BundleLoadOptions::default().with_max_compressed_blocks_info_size(Some(16));
     let err = BundleParser::from_bytes_with_options(bytes, options).unwrap_err();
     assert!(matches!(err, BinaryError::ResourceLimitExceeded(_)));
 }
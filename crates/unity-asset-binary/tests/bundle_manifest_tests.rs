@@ -0,0 +1,146 @@
+//! BuildPipeline `.manifest` parsing (per-bundle and build folder manifests) and dependency
+//! loading through them.
+
+use std::path::PathBuf;
+use unity_asset_binary::bundle::{
+    AssetBundleManifest, BundleLoader, BundleManifest, DependencyResolver, ManifestClassType,
+    ManifestFile,
+};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/manifests")
+        .join(name)
+}
+
+fn samples() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples")
+}
+
+#[test]
+fn bundle_manifest_reads_hashes_classes_assets_and_dependencies() {
+    let manifest = BundleManifest::from_path(fixture("characters.manifest")).unwrap();
+
+    assert_eq!(manifest.crc, 2863373584);
+    assert_eq!(
+        manifest.asset_file_hash,
+        Some([
+            0x6a, 0x4a, 0xe1, 0xe2, 0x8c, 0xf8, 0x2b, 0x1f, 0x0b, 0x7a, 0x4b, 0x7a, 0x5b, 0x2c,
+            0x1b, 0x46
+        ])
+    );
+    assert_eq!(manifest.type_tree_hash.unwrap()[0], 0x2c);
+    assert_eq!(manifest.incremental_build_hash, Some([0; 16]));
+    assert!(!manifest.hash_appended);
+
+    let class_ids: Vec<i32> = manifest.class_types.iter().map(|c| c.class_id).collect();
+    assert_eq!(class_ids, vec![1, 4, 21, 114, 137]);
+    assert_eq!(
+        manifest.class_types[3],
+        ManifestClassType {
+            class_id: 114,
+            script_guid: Some("5f7201a12d95ffc409449d95f23cf332".to_string()),
+        }
+    );
+    assert_eq!(manifest.class_types[0].script_guid, None);
+
+    assert_eq!(
+        manifest.assets,
+        vec![
+            "Assets/Characters/Hero/Hero.prefab",
+            "Assets/Characters/Hero/Hero Walk.anim",
+        ]
+    );
+    assert_eq!(
+        manifest.dependencies[0],
+        "D:/Projects/Game/AssetBundles/StandaloneWindows/materials"
+    );
+    assert_eq!(manifest.dependency_names(), vec!["materials", "shaders"]);
+
+    let shaders = BundleManifest::from_path(fixture("shaders.manifest")).unwrap();
+    assert!(shaders.dependencies.is_empty());
+    assert_eq!(shaders.incremental_build_hash, None);
+}
+
+#[test]
+fn folder_manifest_lists_bundles_and_dependency_graph() {
+    let manifest = AssetBundleManifest::from_path(fixture("StandaloneWindows.manifest")).unwrap();
+
+    assert_eq!(manifest.crc, 1394782919);
+    let names: Vec<&str> = manifest.bundles.iter().map(|b| b.name.as_str()).collect();
+    assert_eq!(names, vec!["characters", "materials", "shaders"]);
+    assert_eq!(
+        manifest.direct_dependencies("characters"),
+        ["materials", "shaders"]
+    );
+    assert!(manifest.direct_dependencies("shaders").is_empty());
+    assert_eq!(
+        manifest.all_dependencies("characters"),
+        vec!["shaders", "materials"]
+    );
+
+    assert!(matches!(
+        ManifestFile::from_path(fixture("StandaloneWindows.manifest")).unwrap(),
+        ManifestFile::Folder(_)
+    ));
+    assert!(matches!(
+        ManifestFile::from_path(fixture("characters.manifest")).unwrap(),
+        ManifestFile::Bundle(_)
+    ));
+    assert!(BundleManifest::parse("CRC: not-a-number\n").is_err());
+}
+
+#[test]
+fn load_with_dependencies_follows_folder_manifest() {
+    // A cycle (atlas_test -> char_118_yuki.ab) is loaded once.
+    let manifest = AssetBundleManifest::parse(
+        "ManifestFileVersion: 0
+CRC: 1
+AssetBundleManifest:
+  AssetBundleInfos:
+    Info_0:
+      Name: char_118_yuki.ab
+      Dependencies:
+        Dependency_0: banner_1
+    Info_1:
+      Name: banner_1
+      Dependencies:
+        Dependency_0: atlas_test
+    Info_2:
+      Name: atlas_test
+      Dependencies:
+        Dependency_0: char_118_yuki.ab
+",
+    )
+    .unwrap();
+
+    let mut loader = BundleLoader::new();
+    let keys = loader
+        .load_with_dependencies(samples(), "char_118_yuki.ab", &manifest)
+        .unwrap();
+    let names: Vec<&str> = keys
+        .iter()
+        .map(|k| k.rsplit(['/', '\\']).next().unwrap())
+        .collect();
+    assert_eq!(names, vec!["atlas_test", "banner_1", "char_118_yuki.ab"]);
+    assert_eq!(loader.loaded_bundles().len(), 3);
+
+    // Per-bundle manifests work through a closure.
+    let mut loader = BundleLoader::new();
+    let per_bundle = |name: &str| -> Vec<String> {
+        match name {
+            "banner_1" => vec!["atlas_test".to_string()],
+            _ => Vec::new(),
+        }
+    };
+    let keys = loader
+        .load_with_dependencies(samples(), "banner_1", &per_bundle)
+        .unwrap();
+    assert_eq!(keys.len(), 2);
+
+    assert!(
+        BundleLoader::new()
+            .load_with_dependencies(samples(), "missing_bundle", &manifest)
+            .is_err()
+    );
+}
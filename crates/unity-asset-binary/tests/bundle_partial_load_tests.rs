@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+use unity_asset_binary::bundle::{BundleLoader, SingleLoadOptions};
+
+const VOICE: &str =
+    "assets/torappu/dynamicassets/audio/sound_beta_2/voice/char_118_yuki/cn_002.ogg";
+const VOICE_PATH_ID: i64 = 5482297458062616526;
+const ATLAS: &str = "assets/ui/textures/atlases/buildingswatertoweratlas.spriteatlas";
+
+fn sample(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/samples")
+        .join(name)
+}
+
+#[test]
+fn load_single_reads_only_the_target_object_and_its_blocks() {
+    let loaded = BundleLoader::new()
+        .load_single(
+            sample("char_118_yuki.ab"),
+            &VOICE.to_uppercase(),
+            &SingleLoadOptions::default(),
+        )
+        .unwrap();
+
+    assert_eq!(loaded.container_path, VOICE);
+    assert_eq!(loaded.root, VOICE_PATH_ID);
+    let root = loaded.root().read().unwrap();
+    assert_eq!(root.class_name(), "AudioClip");
+    assert_eq!(root.name().as_deref(), Some("CN_002"));
+
+    // The AssetBundle object (for m_Container) and the clip; none of the other 34 clips.
+    assert_eq!(loaded.stats.objects_total, 36);
+    assert_eq!(loaded.stats.objects_read, 2);
+    for info in &loaded.file.objects {
+        let expected = info.path_id == VOICE_PATH_ID || info.path_id == 1;
+        assert_eq!(loaded.is_loaded(info.path_id), expected, "{}", info.path_id);
+    }
+
+    // The SerializedFile lives in the first block; the .resource blocks stay compressed.
+    assert_eq!(loaded.stats.blocks_total, 6);
+    assert_eq!(loaded.stats.blocks_inflated, 1);
+    assert!(loaded.bundle.assets.is_empty());
+}
+
+#[test]
+fn load_single_follows_pptrs_within_the_depth_limit() {
+    let loader = BundleLoader::new();
+    let path = sample("atlas_test");
+
+    let shallow = loader
+        .load_single(
+            &path,
+            ATLAS,
+            &SingleLoadOptions::default().with_max_depth(0),
+        )
+        .unwrap();
+    assert!(shallow.dependencies.is_empty());
+    assert_eq!(shallow.stats.objects_read, 2);
+
+    // Without the preload table the sprites (and their texture) are found through PPtrs.
+    let pptrs_only = loader
+        .load_single(
+            &path,
+            ATLAS,
+            &SingleLoadOptions::default().with_follow_preload_table(false),
+        )
+        .unwrap();
+    assert_eq!(
+        pptrs_only.root().read().unwrap().class_name(),
+        "SpriteAtlas"
+    );
+    assert_eq!(pptrs_only.dependencies.len(), 8);
+    assert!(pptrs_only.external_references.is_empty());
+    let mut classes: Vec<String> = pptrs_only
+        .dependency_handles()
+        .map(|handle| handle.read().unwrap().class_name().to_string())
+        .collect();
+    classes.dedup();
+    assert_eq!(classes.iter().filter(|c| *c == "Texture2D").count(), 1);
+    assert!(classes.iter().any(|c| c == "Sprite"));
+
+    let preload = loader
+        .load_single(&path, ATLAS, &SingleLoadOptions::default())
+        .unwrap();
+    let mut with_preload = preload.dependencies.clone();
+    let mut without = pptrs_only.dependencies.clone();
+    with_preload.sort();
+    without.sort();
+    assert_eq!(with_preload, without);
+}
+
+#[test]
+fn load_single_reports_unknown_container_paths() {
+    let err = BundleLoader::new()
+        .load_single(
+            sample("banner_1"),
+            "assets/missing.png",
+            &SingleLoadOptions::default(),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("assets/missing.png"), "{}", err);
+}
@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use unity_asset_binary::bundle::BundleLoader;
+
+fn sample(name: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/samples")
+        .join(name);
+    std::fs::read(path).unwrap()
+}
+
+#[test]
+fn refresh_reparses_only_the_overwritten_bundle() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("a.bundle");
+    let b = dir.path().join("b.bundle");
+    std::fs::write(&a, sample("banner_1")).unwrap();
+    std::fs::write(&b, sample("atlas_test")).unwrap();
+
+    let mut loader = BundleLoader::new();
+    loader.load_from_file(&a).unwrap();
+    loader.load_from_file(&b).unwrap();
+    let a_key = a.to_string_lossy().to_string();
+    let b_key = b.to_string_lossy().to_string();
+    let old_asset_names = loader.get_bundle(&a_key).unwrap().asset_names.clone();
+
+    assert_eq!(loader.refresh(), Default::default());
+
+    std::fs::write(&a, sample("char_118_yuki.ab")).unwrap();
+    let report = loader.refresh();
+    assert_eq!(report.changed, vec![a_key.clone()]);
+    assert!(report.removed.is_empty() && report.failed.is_empty());
+    assert!(!report.touched.contains(&b_key));
+    assert_eq!(
+        report.invalidated_assets,
+        old_asset_names
+            .iter()
+            .map(|n| (a_key.clone(), n.clone()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        loader.get_bundle(&a_key).unwrap().asset_names,
+        unity_asset_binary::bundle::load_bundle_from_memory(sample("char_118_yuki.ab"))
+            .unwrap()
+            .asset_names
+    );
+
+    // Nothing changed since the last refresh.
+    assert!(!loader.refresh().has_changes());
+}
+
+#[test]
+fn refresh_skips_identical_rewrites_and_drops_deleted_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("a.bundle");
+    let b = dir.path().join("b.bundle");
+    let data = sample("banner_1");
+    std::fs::write(&a, &data).unwrap();
+    std::fs::write(&b, sample("atlas_test")).unwrap();
+
+    let mut loader = BundleLoader::new();
+    loader.load_from_file(&a).unwrap();
+    loader.load_from_file(&b).unwrap();
+    let a_key = a.to_string_lossy().to_string();
+    let b_key = b.to_string_lossy().to_string();
+
+    // Rewrite identical bytes with a newer mtime: the stamp no longer matches, but the content
+    // hash does.
+    std::fs::write(&a, &data).unwrap();
+    let file = std::fs::File::options().write(true).open(&a).unwrap();
+    file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5))
+        .unwrap();
+    drop(file);
+
+    std::fs::remove_file(&b).unwrap();
+
+    let report = loader.refresh();
+    assert!(report.changed.is_empty());
+    assert_eq!(report.touched, vec![a_key.clone()]);
+    assert_eq!(report.removed, vec![b_key.clone()]);
+    assert!(loader.get_bundle(&a_key).is_some());
+    assert!(loader.get_bundle(&b_key).is_none());
+    assert_eq!(loader.loaded_bundles(), vec![a_key.as_str()]);
+}
@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+
+use unity_asset_binary::bundle::{
+    AssetBundle, BundleHeader, DirectoryNode, load_bundle_from_memory,
+};
+use unity_asset_binary::compression::CompressionBlock;
+
+/// Three LZ4 blocks plus one stored block; two nodes separated by 16 bytes of alignment padding
+/// and followed by 8 bytes of tail padding.
+fn multi_block_bundle() -> AssetBundle {
+    let mut bundle = AssetBundle::new(BundleHeader::default(), Vec::new());
+    bundle.blocks = vec![
+        CompressionBlock::new(1000, 400, 2),
+        CompressionBlock::new(1000, 500, 2),
+        CompressionBlock::new(1000, 1000, 0),
+        CompressionBlock::new(24, 30, 2),
+    ];
+    bundle.nodes = vec![
+        DirectoryNode::new("CAB-a".to_string(), 0, 1500, 4),
+        DirectoryNode::new("CAB-a.resS".to_string(), 1516, 1500, 0),
+    ];
+    bundle
+}
+
+#[test]
+fn per_block_numbers_sum_to_totals() {
+    let stats = multi_block_bundle().statistics();
+
+    assert_eq!(stats.blocks.len(), 4);
+    assert_eq!(
+        stats.blocks.iter().map(|b| b.compressed_size).sum::<u64>(),
+        stats.compressed_size
+    );
+    assert_eq!(
+        stats
+            .blocks
+            .iter()
+            .map(|b| b.uncompressed_size)
+            .sum::<u64>(),
+        stats.uncompressed_size
+    );
+    assert_eq!(stats.blocks[2].uncompressed_offset, 2000);
+    assert_eq!(stats.blocks[2].compressed_offset, 900);
+    assert_eq!(stats.blocks[0].compression, "LZ4");
+    assert!((stats.blocks[0].compression_ratio - 0.4).abs() < 1e-9);
+
+    assert_eq!(stats.stored_uncompressed_blocks, 1);
+    assert!(stats.blocks[2].stored_uncompressed);
+
+    assert_eq!(stats.padding_bytes, 16 + 8);
+    assert_eq!(
+        stats.files.iter().map(|f| f.uncompressed_size).sum::<u64>() + stats.padding_bytes,
+        stats.uncompressed_size
+    );
+    assert_eq!(stats.expanded_bytes, 6);
+    assert_eq!(stats.wasted_bytes, 30);
+
+    // CAB-a: all of block 0 (400) + half of block 1 (250).
+    assert_eq!(stats.files[0].name, "CAB-a");
+    assert_eq!(stats.files[0].estimated_compressed_size, 650);
+    // CAB-a.resS: 484/1000 of block 1 (242) + all of block 2 (1000) + 16/24 of block 3 (20).
+    assert_eq!(stats.files[1].estimated_compressed_size, 1262);
+
+    let json = stats.to_json();
+    assert_eq!(json["wasted_bytes"], 30);
+    assert_eq!(json["blocks"].as_array().map(Vec::len), Some(4));
+    assert_eq!(json["files"][1]["name"], "CAB-a.resS");
+}
+
+#[test]
+fn sample_bundle_statistics_are_consistent() {
+    let samples = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/samples");
+    let mut checked = 0;
+    for entry in std::fs::read_dir(&samples).unwrap() {
+        let path = entry.unwrap().path();
+        let data = std::fs::read(&path).unwrap();
+        let Ok(bundle) = load_bundle_from_memory(data) else {
+            continue;
+        };
+        let stats = bundle.statistics();
+        if stats.blocks.is_empty() {
+            continue;
+        }
+        checked += 1;
+
+        assert_eq!(
+            stats.blocks.iter().map(|b| b.compressed_size).sum::<u64>(),
+            stats.compressed_size,
+            "{:?}",
+            path
+        );
+        assert_eq!(
+            stats
+                .blocks
+                .iter()
+                .map(|b| b.uncompressed_size)
+                .sum::<u64>(),
+            stats.uncompressed_size,
+            "{:?}",
+            path
+        );
+        let estimated: u64 = stats
+            .files
+            .iter()
+            .map(|f| f.estimated_compressed_size)
+            .sum();
+        assert!(
+            estimated <= stats.compressed_size + stats.files.len() as u64,
+            "{:?}: {} > {}",
+            path,
+            estimated,
+            stats.compressed_size
+        );
+    }
+    assert!(checked > 0);
+}
@@ -0,0 +1,125 @@
+//! Camera (class 20) and Light (class 108) parsing against typetree dumps from 5.6 (`UInt8` flags,
+//! no inner spot angle or physical camera) and 2022.3 (physical camera, inner spot angle, color
+//! temperature in use), plus the `as_camera` / `as_light` dispatch.
+
+use indexmap::IndexMap;
+use unity_asset_binary::asset::ObjectInfo;
+use unity_asset_binary::object::UnityObject;
+use unity_asset_binary::test_support::{object_properties, read_fixture};
+use unity_asset_binary::unity_objects::{Camera, Light, LightType, LightUnit, ObjectRef};
+use unity_asset_core::{UnityClass, UnityValue, class_ids};
+
+fn load(name: &str, class: &str) -> IndexMap<String, UnityValue> {
+    object_properties(&read_fixture(name), class)
+}
+
+fn object(class_id: i32, props: IndexMap<String, UnityValue>) -> UnityObject {
+    let mut class = UnityClass::new(class_id, String::new(), "1".to_string());
+    for (key, value) in props {
+        class.set(key, value);
+    }
+    UnityObject::from_info_and_class(ObjectInfo::new(1, 0, 0, class_id, -1), class)
+}
+
+#[test]
+fn camera_5x_layout() {
+    let camera = Camera::from_typetree(&load("camera_light_5x.json", "Camera")).unwrap();
+
+    assert_eq!(camera.game_object, Some(ObjectRef::new(0, 101)));
+    assert!(camera.enabled);
+    assert_eq!(camera.clear_flags, 1);
+    assert_eq!(camera.field_of_view, 60.0);
+    assert_eq!(camera.near_clip_plane, 0.3);
+    assert_eq!(camera.far_clip_plane, 1000.0);
+    assert!(!camera.orthographic);
+    assert_eq!(camera.depth, -1.0);
+    assert_eq!(camera.viewport_rect, [0.0, 0.0, 1.0, 1.0]);
+    assert_eq!(camera.culling_mask, u32::MAX);
+    assert_eq!(camera.target_texture, None);
+    assert!((camera.background_color.b - 0.4745098).abs() < 1e-6);
+    assert_eq!(camera.projection_matrix_mode, None);
+    assert_eq!(camera.physical, None);
+    assert!(!camera.uses_physical_properties());
+}
+
+#[test]
+fn camera_2022_layout_has_physical_properties() {
+    let camera = Camera::from_typetree(&load("camera_light_2022.json", "Camera")).unwrap();
+
+    assert_eq!(camera.clear_flags, 2);
+    assert_eq!(camera.near_clip_plane, 0.1);
+    assert_eq!(camera.far_clip_plane, 500.0);
+    assert!((camera.field_of_view - 37.84929).abs() < 1e-5);
+    assert_eq!(camera.viewport_rect, [0.0, 0.5, 1.0, 0.5]);
+    assert_eq!(camera.culling_mask, 0xFFFF_FFFB);
+    assert_eq!(
+        camera.target_texture,
+        Some(ObjectRef::new(0, -6035847183402016768))
+    );
+    assert_eq!(camera.projection_matrix_mode, Some(2));
+    assert!(camera.uses_physical_properties());
+    let physical = camera.physical.unwrap();
+    assert_eq!(physical.focal_length, 35.0);
+    assert_eq!(physical.sensor_size, [36.0, 24.0]);
+    assert_eq!(physical.lens_shift, [0.0, 0.1]);
+    assert_eq!(physical.gate_fit, Some(2));
+}
+
+#[test]
+fn light_5x_layout() {
+    let light = Light::from_typetree(&load("camera_light_5x.json", "Light")).unwrap();
+
+    assert_eq!(light.game_object, Some(ObjectRef::new(0, 103)));
+    assert!(light.enabled);
+    assert_eq!(light.light_type, LightType::Spot);
+    assert_eq!(light.color.r, 1.0);
+    assert!((light.color.b - 0.8392157).abs() < 1e-6);
+    assert_eq!(light.intensity, 2.5);
+    assert_eq!(light.range, 12.0);
+    assert_eq!(light.spot_angle, 45.0);
+    assert_eq!(light.inner_spot_angle, None);
+    assert_eq!(light.shadows, 2);
+    assert_eq!(light.shadow_strength, 0.75);
+    assert_eq!(light.lightmapping, Some(4));
+    assert_eq!(light.color_temperature, Some(6570.0));
+    assert!(!light.use_color_temperature);
+    assert_eq!(light.light_unit, None);
+}
+
+#[test]
+fn light_2022_layout_and_light_units() {
+    let mut props = load("camera_light_2022.json", "Light");
+    let light = Light::from_typetree(&props).unwrap();
+
+    assert_eq!(light.light_type, LightType::Spot);
+    assert_eq!(light.color.g, 0.75);
+    assert_eq!(light.intensity, 8.0);
+    assert_eq!(light.range, 25.0);
+    assert_eq!(light.spot_angle, 60.0);
+    assert_eq!(light.inner_spot_angle, Some(42.0));
+    assert_eq!(light.shadows, 1);
+    assert_eq!(light.bounce_intensity, Some(1.0));
+    assert_eq!(light.area_size, Some([1.0, 1.0]));
+    assert_eq!(light.color_temperature, Some(5000.0));
+    assert!(light.use_color_temperature);
+    assert_eq!(light.light_unit, None);
+
+    // Layouts with physical light units record the unit; the intensity is left as stored.
+    props.insert("m_LightUnit".to_string(), UnityValue::Integer(1));
+    props.insert("m_LuxAtDistance".to_string(), UnityValue::Float(2.0));
+    let light = Light::from_typetree(&props).unwrap();
+    assert_eq!(light.light_unit, Some(LightUnit::Candela));
+    assert_eq!(light.lux_at_distance, Some(2.0));
+    assert_eq!(light.intensity, 8.0);
+}
+
+#[test]
+fn dispatch_checks_the_class() {
+    let camera = object(class_ids::CAMERA, load("camera_light_5x.json", "Camera"));
+    let light = object(class_ids::LIGHT, load("camera_light_2022.json", "Light"));
+
+    assert_eq!(camera.as_camera().unwrap().field_of_view, 60.0);
+    assert_eq!(light.as_light().unwrap().inner_spot_angle, Some(42.0));
+    assert!(camera.as_light().is_err());
+    assert!(light.as_camera().is_err());
+}
@@ -0,0 +1,292 @@
+//! C# stub generation from TypeTrees.
+//!
+//! The snapshots in `tests/fixtures/codegen` pin the output for a GameObject tree and a synthetic
+//! MonoBehaviour tree. The batch test adds MonoScript/MonoBehaviour types and objects to the
+//! `banner_1` asset, which has none of its own.
+
+use std::path::PathBuf;
+
+use unity_asset_binary::asset::{ObjectInfo, SerializedType};
+use unity_asset_binary::codegen::{csharp_from_typetree, csharp_scripts};
+use unity_asset_binary::file::{UnityFile, load_unity_file};
+use unity_asset_binary::reader::ByteOrder;
+use unity_asset_binary::typetree::{TypeTree, TypeTreeNode};
+use unity_asset_core::class_ids;
+
+const ALIGNED: i32 = 0x4000;
+
+fn node(type_name: &str, name: &str, byte_size: i32, children: Vec<TypeTreeNode>) -> TypeTreeNode {
+    let mut node = TypeTreeNode::with_info(type_name.to_string(), name.to_string(), byte_size);
+    node.children = children;
+    node
+}
+
+fn aligned(mut node: TypeTreeNode) -> TypeTreeNode {
+    node.meta_flags |= ALIGNED;
+    node
+}
+
+fn array(element: TypeTreeNode) -> TypeTreeNode {
+    aligned(node(
+        "Array",
+        "Array",
+        -1,
+        vec![node("int", "size", 4, Vec::new()), element],
+    ))
+}
+
+fn string(name: &str) -> TypeTreeNode {
+    node(
+        "string",
+        name,
+        -1,
+        vec![array(node("char", "data", 1, Vec::new()))],
+    )
+}
+
+fn vector(name: &str, element: TypeTreeNode) -> TypeTreeNode {
+    node("vector", name, -1, vec![array(element)])
+}
+
+fn pptr(target: &str, name: &str) -> TypeTreeNode {
+    node(
+        &format!("PPtr<{}>", target),
+        name,
+        12,
+        vec![
+            node("int", "m_FileID", 4, Vec::new()),
+            node("SInt64", "m_PathID", 8, Vec::new()),
+        ],
+    )
+}
+
+fn floats(type_name: &str, name: &str, fields: &[&str]) -> TypeTreeNode {
+    node(
+        type_name,
+        name,
+        4 * fields.len() as i32,
+        fields
+            .iter()
+            .map(|f| node("float", f, 4, Vec::new()))
+            .collect(),
+    )
+}
+
+fn tree(root: TypeTreeNode) -> TypeTree {
+    let mut tree = TypeTree::new();
+    tree.nodes.push(root);
+    tree
+}
+
+fn game_object_tree() -> TypeTree {
+    tree(node(
+        "GameObject",
+        "Base",
+        -1,
+        vec![
+            vector(
+                "m_Component",
+                node(
+                    "ComponentPair",
+                    "data",
+                    12,
+                    vec![pptr("Component", "component")],
+                ),
+            ),
+            node("unsigned int", "m_Layer", 4, Vec::new()),
+            aligned(string("m_Name")),
+            node("UInt16", "m_Tag", 2, Vec::new()),
+            aligned(node("bool", "m_IsActive", 1, Vec::new())),
+        ],
+    ))
+}
+
+fn item(name: &str) -> TypeTreeNode {
+    node(
+        "Item",
+        name,
+        8,
+        vec![
+            node("int", "itemId", 4, Vec::new()),
+            node("int", "count", 4, Vec::new()),
+        ],
+    )
+}
+
+fn player_stats_tree() -> TypeTree {
+    tree(node(
+        "MonoBehaviour",
+        "Base",
+        -1,
+        vec![
+            pptr("GameObject", "m_GameObject"),
+            aligned(node("UInt8", "m_Enabled", 1, Vec::new())),
+            pptr("MonoScript", "m_Script"),
+            string("m_Name"),
+            node("int", "maxHealth", 4, Vec::new()),
+            node("float", "Move Speed", 4, Vec::new()),
+            aligned(node("bool", "class", 1, Vec::new())),
+            floats("Vector3f", "spawnPoint", &["x", "y", "z"]),
+            floats("ColorRGBA", "tint", &["r", "g", "b", "a"]),
+            vector("inventory", item("data")),
+            item("equipped"),
+            pptr("$Enemy", "target"),
+            vector("tags", string("data")),
+            node(
+                "Stats",
+                "stats",
+                -1,
+                vec![
+                    node("UInt64", "experience", 8, Vec::new()),
+                    vector("bonuses", floats("Vector2f", "data", &["x", "y"])),
+                    item("reward"),
+                ],
+            ),
+            node("int", "maxHealth", 4, Vec::new()),
+        ],
+    ))
+}
+
+fn snapshot(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/codegen")
+        .join(name);
+    std::fs::read_to_string(path).unwrap()
+}
+
+#[test]
+fn game_object_tree_matches_snapshot() {
+    assert_eq!(
+        csharp_from_typetree(&game_object_tree(), "GameObject"),
+        snapshot("GameObject.cs")
+    );
+}
+
+#[test]
+fn mono_behaviour_tree_matches_snapshot() {
+    assert_eq!(
+        csharp_from_typetree(&player_stats_tree(), "PlayerStats"),
+        snapshot("PlayerStats.cs")
+    );
+}
+
+fn write_string(out: &mut Vec<u8>, s: &str, order: ByteOrder) {
+    let len = s.len() as u32;
+    out.extend_from_slice(&match order {
+        ByteOrder::Big => len.to_be_bytes(),
+        ByteOrder::Little => len.to_le_bytes(),
+    });
+    out.extend_from_slice(s.as_bytes());
+    while !out.len().is_multiple_of(4) {
+        out.push(0);
+    }
+}
+
+fn script_ref(path_id: i64, file_id: i32, order: ByteOrder) -> Vec<u8> {
+    let mut out = Vec::new();
+    match order {
+        ByteOrder::Big => {
+            out.extend_from_slice(&file_id.to_be_bytes());
+            out.extend_from_slice(&path_id.to_be_bytes());
+        }
+        ByteOrder::Little => {
+            out.extend_from_slice(&file_id.to_le_bytes());
+            out.extend_from_slice(&path_id.to_le_bytes());
+        }
+    }
+    out
+}
+
+#[test]
+fn scripts_are_generated_once_per_script_type() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/samples/banner_1");
+    let UnityFile::AssetBundle(mut bundle) = load_unity_file(&path).unwrap() else {
+        panic!("banner_1 is an AssetBundle");
+    };
+    let file = &mut bundle.assets[0];
+    let order = file.header.byte_order();
+
+    let mut mono_script = SerializedType::new(class_ids::MONO_SCRIPT);
+    mono_script.type_tree = tree(node(
+        "MonoScript",
+        "Base",
+        -1,
+        vec![string("m_ClassName"), string("m_Namespace")],
+    ));
+    let script_type = file.types.len() as i32;
+    file.types.push(mono_script);
+
+    let mono_behaviour = |script_id: u8, tree: TypeTree| {
+        let mut typ = SerializedType::new(class_ids::MONO_BEHAVIOUR);
+        typ.script_type_index = 0;
+        typ.script_id = [script_id; 16];
+        typ.type_tree = tree;
+        typ
+    };
+    let stats_type = file.types.len() as i32;
+    file.types.push(mono_behaviour(0xAB, player_stats_tree()));
+    let other_type = file.types.len() as i32;
+    file.types.push(mono_behaviour(
+        0x1C,
+        tree(node(
+            "MonoBehaviour",
+            "Base",
+            -1,
+            vec![
+                pptr("MonoScript", "m_Script"),
+                node("double", "ratio", 8, Vec::new()),
+            ],
+        )),
+    ));
+
+    let mut script_data = Vec::new();
+    write_string(&mut script_data, "PlayerStats", order);
+    write_string(&mut script_data, "Game.Player", order);
+    let mut object = |path_id: i64, type_id: i32, type_index: i32, data: Vec<u8>| {
+        let mut info = ObjectInfo::new(path_id, 0, data.len() as u32, type_id, type_index);
+        info.data = data;
+        file.objects.push(info);
+    };
+    object(9001, class_ids::MONO_SCRIPT, script_type, script_data);
+    // Two instances of the local script; the other script lives in another file.
+    for path_id in [9002, 9003] {
+        // m_GameObject and the aligned m_Enabled precede m_Script.
+        let mut data = vec![0; 16];
+        data.extend(script_ref(9001, 0, order));
+        object(path_id, class_ids::MONO_BEHAVIOUR, stats_type, data);
+    }
+    object(
+        9004,
+        class_ids::MONO_BEHAVIOUR,
+        other_type,
+        script_ref(5, 1, order),
+    );
+
+    let scripts = csharp_scripts(file);
+    assert_eq!(scripts.len(), 2);
+
+    let stats = &scripts[0];
+    assert_eq!(stats.file_name, "PlayerStats.cs");
+    assert_eq!(stats.namespace, "Game.Player");
+    assert_eq!(stats.script_id, [0xAB; 16]);
+    assert!(
+        stats
+            .source
+            .contains("namespace Game.Player\n{\n    [Serializable]\n")
+    );
+    assert!(
+        stats
+            .source
+            .contains("    public class PlayerStats : MonoBehaviour\n")
+    );
+
+    let other = &scripts[1];
+    assert_eq!(other.class_name, "Script_1c1c1c1c");
+    assert_eq!(other.file_name, "Script_1c1c1c1c.cs");
+    assert!(other.namespace.is_empty());
+    assert!(
+        other
+            .source
+            .contains("    public double ratio; // double size=8 flags=0x0\n")
+    );
+}
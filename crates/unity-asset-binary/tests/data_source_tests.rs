@@ -0,0 +1,181 @@
+//! Bundles and streamed resources read through `DataSource`s.
+//!
+//! The fixture is a hand-assembled UnityFS bundle with a serialized-file node and a `.resS`
+//! node spread over several LZ4 blocks, loaded from an in-memory VFS.
+
+use std::io::Cursor;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use unity_asset_binary::asset::SerializedFileParser;
+use unity_asset_binary::bundle::{BundleLoadOptions, BundleLoader, BundleParser};
+use unity_asset_binary::data_source::{
+    BytesSource, DataSource, FileSource, ReaderSource, VirtualFileSystem,
+};
+use unity_asset_binary::error::Result;
+
+const CAB: &str = "CAB-0123456789abcdef0123456789abcdef";
+const BLOCK_SIZE: usize = 4096;
+
+/// Counts the bytes read through it.
+#[derive(Debug)]
+struct CountingSource {
+    inner: BytesSource,
+    read: Arc<AtomicU64>,
+}
+
+impl DataSource for CountingSource {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn len(&self) -> u64 {
+        self.inner.len()
+    }
+
+    fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        self.read.fetch_add(len as u64, Ordering::SeqCst);
+        self.inner.read_at(offset, len)
+    }
+}
+
+fn resource_payload() -> Vec<u8> {
+    (0..BLOCK_SIZE * 4)
+        .map(|i| ((i * 31) % 251) as u8 ^ (i / 97) as u8)
+        .collect()
+}
+
+fn write_cstring(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(s.as_bytes());
+    buf.push(0);
+}
+
+/// UnityFS v6 bundle: a 64-byte `CAB` node followed by the `.resS` payload, LZ4 blocks.
+fn build_bundle() -> Vec<u8> {
+    let mut data = vec![0x5A; 64];
+    data.extend_from_slice(&resource_payload());
+
+    let mut block_table = Vec::new();
+    let mut block_data = Vec::new();
+    let chunks: Vec<&[u8]> = data.chunks(BLOCK_SIZE).collect();
+    for chunk in &chunks {
+        let compressed = lz4_flex::block::compress(chunk);
+        block_table.extend_from_slice(&(chunk.len() as u32).to_be_bytes());
+        block_table.extend_from_slice(&(compressed.len() as u32).to_be_bytes());
+        block_table.extend_from_slice(&2u16.to_be_bytes());
+        block_data.extend_from_slice(&compressed);
+    }
+
+    let mut info = vec![0u8; 16];
+    info.extend_from_slice(&(chunks.len() as i32).to_be_bytes());
+    info.extend_from_slice(&block_table);
+    info.extend_from_slice(&2i32.to_be_bytes());
+    info.extend_from_slice(&0i64.to_be_bytes());
+    info.extend_from_slice(&64i64.to_be_bytes());
+    info.extend_from_slice(&4u32.to_be_bytes());
+    write_cstring(&mut info, CAB);
+    info.extend_from_slice(&64i64.to_be_bytes());
+    info.extend_from_slice(&(resource_payload().len() as i64).to_be_bytes());
+    info.extend_from_slice(&4u32.to_be_bytes());
+    write_cstring(&mut info, &format!("{CAB}.resS"));
+
+    let mut header = Vec::new();
+    write_cstring(&mut header, "UnityFS");
+    header.extend_from_slice(&6u32.to_be_bytes());
+    write_cstring(&mut header, "5.x.x");
+    write_cstring(&mut header, "2018.4.2f1");
+    let total = header.len() + 20 + info.len() + block_data.len();
+    header.extend_from_slice(&(total as i64).to_be_bytes());
+    header.extend_from_slice(&(info.len() as u32).to_be_bytes());
+    header.extend_from_slice(&(info.len() as u32).to_be_bytes());
+    header.extend_from_slice(&0x40u32.to_be_bytes());
+
+    let mut bundle = header;
+    bundle.extend_from_slice(&info);
+    bundle.extend_from_slice(&block_data);
+    bundle
+}
+
+#[test]
+fn lazy_bundle_from_vfs_reads_only_the_blocks_a_stream_read_needs() {
+    let bytes = build_bundle();
+    let total = bytes.len() as u64;
+    let read = Arc::new(AtomicU64::new(0));
+    let mut vfs = VirtualFileSystem::new();
+    vfs.insert(
+        "Bundles/characters.bundle",
+        CountingSource {
+            inner: BytesSource::new("characters.bundle", bytes),
+            read: read.clone(),
+        },
+    );
+
+    let mut loader = BundleLoader::with_options(BundleLoadOptions::lazy());
+    let bundle = loader
+        .load_from_vfs(&vfs, "bundles/Characters.bundle")
+        .unwrap();
+    assert_eq!(bundle.nodes.len(), 2);
+    let after_header = read.load(Ordering::SeqCst);
+    assert!(after_header < total / 2, "{after_header} of {total}");
+
+    // A span inside the second block of the payload.
+    let stream_path = format!("archive:/{CAB}/{CAB}.resS");
+    let offset = BLOCK_SIZE as u64 + 100;
+    let chunk = bundle.read_resource(&stream_path, offset, 256).unwrap();
+    assert_eq!(
+        chunk,
+        resource_payload()[offset as usize..offset as usize + 256]
+    );
+    let after_stream = read.load(Ordering::SeqCst);
+    assert!(after_stream - after_header < total / 2);
+
+    // Eager options read the whole source and produce the same data.
+    let eager = BundleParser::from_source_with_options(
+        vfs.open("bundles/characters.bundle").unwrap(),
+        BundleLoadOptions::default().with_load_assets(false),
+    )
+    .unwrap();
+    let node = eager.find_resource_node(&stream_path).unwrap().clone();
+    assert_eq!(eager.extract_node_data(&node).unwrap(), resource_payload());
+}
+
+#[test]
+fn vfs_resolves_logical_names_and_rejects_short_reads() {
+    let mut vfs = VirtualFileSystem::new();
+    vfs.insert_bytes(&format!("{CAB}.resS"), resource_payload());
+    vfs.insert(
+        "Library/unity default resources",
+        ReaderSource::new("unity default resources", Cursor::new(vec![7u8; 32])).unwrap(),
+    );
+
+    let resource = vfs.open(&format!("archive:/{CAB}/{CAB}.resS")).unwrap();
+    assert_eq!(resource.len(), resource_payload().len() as u64);
+    assert_eq!(resource.read_at(3, 4).unwrap(), resource_payload()[3..7]);
+    assert!(resource.read_at(resource.len() - 2, 4).is_err());
+
+    assert!(vfs.contains("library/Unity Default Resources"));
+    assert_eq!(
+        vfs.read_at("unity default resources", 30, 2).unwrap(),
+        vec![7, 7]
+    );
+    assert!(vfs.open("sharedassets0.assets.resS").is_none());
+    assert_eq!(vfs.len(), 2);
+
+    // Not a serialized file: parsing through a source surfaces the parser error.
+    assert!(SerializedFileParser::from_source(resource.as_ref()).is_err());
+}
+
+#[test]
+fn file_source_reads_ranges_from_disk() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("characters.bundle");
+    std::fs::write(&path, build_bundle()).unwrap();
+
+    let source = FileSource::open(&path).unwrap();
+    assert_eq!(source.name(), "characters.bundle");
+    assert_eq!(source.read_at(0, 7).unwrap(), b"UnityFS");
+
+    let bundle = BundleParser::from_source_with_options(source, BundleLoadOptions::fast()).unwrap();
+    let node = bundle.find_node(CAB).unwrap().clone();
+    assert_eq!(bundle.extract_node_data(&node).unwrap(), vec![0x5A; 64]);
+}
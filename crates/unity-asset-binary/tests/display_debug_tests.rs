@@ -0,0 +1,102 @@
+//! Snapshot tests for `Display`/`Debug` summaries.
+//!
+//! These strings are consumed by downstream log parsing; update them deliberately.
+
+use std::path::PathBuf;
+
+use unity_asset_binary::asset::ObjectInfo;
+use unity_asset_binary::bundle::{AssetBundle, load_bundle_from_memory};
+use unity_asset_binary::bytes_preview::BytesPreview;
+use unity_asset_binary::object::UnityObject;
+
+fn sample_bundle() -> AssetBundle {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/samples/char_118_yuki.ab");
+    load_bundle_from_memory(std::fs::read(path).unwrap()).unwrap()
+}
+
+#[test]
+fn bundle_display_snapshot() {
+    let bundle = sample_bundle();
+    assert_eq!(
+        bundle.to_string(),
+        "AssetBundle UnityFS v6 unity=5.6.7f1 files=2 assets=1 compressed_size=704951"
+    );
+}
+
+#[test]
+fn serialized_file_display_snapshot() {
+    let bundle = sample_bundle();
+    assert_eq!(
+        bundle.assets[0].to_string(),
+        "SerializedFile v17 unity=5.6.7f1 platform=13 objects=36 types=2"
+    );
+}
+
+#[test]
+fn unity_object_display_snapshot() {
+    let bundle = sample_bundle();
+    let file = &bundle.assets[0];
+    let object = UnityObject::from_serialized_file(file, &file.objects[0]).unwrap();
+    assert_eq!(
+        object.to_string(),
+        "AudioClip \"CN_034\" path_id=-9143010520829473201 size=160"
+    );
+
+    let unnamed = UnityObject::from_raw(49, 7, vec![1, 2, 3]);
+    assert_eq!(unnamed.to_string(), "TextAsset path_id=7 size=3");
+}
+
+#[test]
+fn bundle_debug_truncates_payloads() {
+    let bundle = sample_bundle();
+    let debug = format!("{:?}", bundle);
+
+    // The decompressed payload is ~700 KB; Debug must stay small.
+    assert!(
+        debug.len() < 4096,
+        "Debug output too large: {}",
+        debug.len()
+    );
+    assert!(debug.contains(
+        "decompressed: Some(<717672 bytes: 00 00 0b 19 00 00 36 48 00 00 00 11 00 00 10 00 ..>)"
+    ));
+    assert!(debug.contains("blocks: [6 blocks]"));
+    assert!(debug.contains("objects: [36 objects]"));
+    assert!(debug.contains("data: <13896 bytes: 00 00 0b 19"));
+
+    let alternate = format!("{:#?}", bundle);
+    assert!(alternate.len() < 8192);
+}
+
+#[test]
+fn object_debug_snapshots() {
+    let mut info = ObjectInfo::new(7, 128, 20, 49, 0);
+    info.data = (0u8..20).collect();
+    assert_eq!(
+        format!("{:?}", info),
+        "ObjectInfo { path_id: 7, byte_start: 128, byte_size: 20, type_id: 49, type_index: 0, \
+         data: <20 bytes: 00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f ..> }"
+    );
+
+    let object = UnityObject::from_raw(49, 7, vec![0xAB; 8192]);
+    assert_eq!(
+        format!("{:?}", object),
+        "UnityObject { info: ObjectInfo { path_id: 7, byte_start: 0, byte_size: 8192, type_id: 49, \
+         type_index: -1, data: <0 bytes> }, class: TextAsset(49), properties: [3 properties], \
+         byte_order: Little, raw: <8192 bytes: ab ab ab ab ab ab ab ab ab ab ab ab ab ab ab ab ..>, \
+         typetree_warnings: [0 warnings] }"
+    );
+}
+
+#[test]
+fn bytes_preview_format() {
+    assert_eq!(format!("{}", BytesPreview(&[])), "<0 bytes>");
+    assert_eq!(
+        format!("{}", BytesPreview(&[0xff, 0x00])),
+        "<2 bytes: ff 00>"
+    );
+    assert_eq!(
+        format!("{:?}", BytesPreview(&[1u8; 16])),
+        "<16 bytes: 01 01 01 01 01 01 01 01 01 01 01 01 01 01 01 01>"
+    );
+}
@@ -0,0 +1,20 @@
+// Generated from a Unity TypeTree (GameObject).
+using System;
+using System.Collections.Generic;
+using UnityEngine;
+
+[Serializable]
+public class GameObject
+{
+    public List<ComponentPair> m_Component; // vector size=-1 flags=0x0
+    public uint m_Layer; // unsigned int size=4 flags=0x0
+    public string m_Name; // string size=-1 flags=0x4000
+    public ushort m_Tag; // UInt16 size=2 flags=0x0
+    public bool m_IsActive; // bool size=1 flags=0x4000
+
+    [Serializable]
+    public class ComponentPair
+    {
+        public Component component; // PPtr<Component> size=12 flags=0x0
+    }
+}
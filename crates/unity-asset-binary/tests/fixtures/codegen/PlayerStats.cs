@@ -0,0 +1,35 @@
+// Generated from a Unity TypeTree (MonoBehaviour).
+using System;
+using System.Collections.Generic;
+using UnityEngine;
+
+[Serializable]
+public class PlayerStats : MonoBehaviour
+{
+    public int maxHealth; // int size=4 flags=0x0
+    public float Move_Speed; // float size=4 flags=0x0 name="Move Speed"
+    public bool @class; // bool size=1 flags=0x4000 name="class"
+    public Vector3 spawnPoint; // Vector3f size=12 flags=0x0
+    public Color tint; // ColorRGBA size=16 flags=0x0
+    public List<Item> inventory; // vector size=-1 flags=0x0
+    public Item equipped; // Item size=8 flags=0x0
+    public Enemy target; // PPtr<$Enemy> size=12 flags=0x0
+    public List<string> tags; // vector size=-1 flags=0x0
+    public Stats stats; // Stats size=-1 flags=0x0
+    public int maxHealth_2; // int size=4 flags=0x0 name="maxHealth"
+
+    [Serializable]
+    public class Item
+    {
+        public int itemId; // int size=4 flags=0x0
+        public int count; // int size=4 flags=0x0
+    }
+
+    [Serializable]
+    public class Stats
+    {
+        public ulong experience; // UInt64 size=8 flags=0x0
+        public List<Vector2> bonuses; // vector size=-1 flags=0x0
+        public Item reward; // Item size=8 flags=0x0
+    }
+}
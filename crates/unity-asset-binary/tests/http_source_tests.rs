@@ -0,0 +1,202 @@
+//! `HttpSource` against a local range-serving HTTP server: lazy bundle reads, retries, caching.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use unity_asset_binary::asset::SerializedFileParser;
+use unity_asset_binary::bundle::{BundleLoadOptions, BundleParser};
+use unity_asset_binary::data_source::DataSource;
+use unity_asset_binary::http_source::{HttpSource, HttpSourceOptions};
+
+const SAMPLE: &str = "char_118_yuki.ab";
+
+/// Serves `body` with `Range` support. The first `fail_first` requests get a 503; with
+/// `ranges == false` every request gets the whole body.
+struct Server {
+    body: Vec<u8>,
+    fail_first: usize,
+    ranges: bool,
+    requests: AtomicUsize,
+}
+
+impl Server {
+    fn start(body: Vec<u8>, fail_first: usize, ranges: bool) -> (String, Arc<Server>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/bundles/{}",
+            listener.local_addr().unwrap(),
+            SAMPLE
+        );
+        let server = Arc::new(Server {
+            body,
+            fail_first,
+            ranges,
+            requests: AtomicUsize::new(0),
+        });
+        let handle = server.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let server = handle.clone();
+                std::thread::spawn(move || server.respond(stream));
+            }
+        });
+        (url, server)
+    }
+
+    fn respond(&self, mut stream: TcpStream) {
+        let mut range = None;
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                break;
+            }
+            let lower = line.to_ascii_lowercase();
+            if let Some(spec) = lower.strip_prefix("range: bytes=") {
+                let (start, end) = spec.trim().split_once('-').unwrap();
+                range = Some((
+                    start.parse::<usize>().unwrap(),
+                    end.parse::<usize>().unwrap(),
+                ));
+            }
+        }
+
+        let len = self.body.len();
+        let response = if self.requests.fetch_add(1, Ordering::SeqCst) < self.fail_first {
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                .as_bytes()
+                .to_vec()
+        } else if let (Some((start, end)), true) = (range, self.ranges) {
+            let end = end.min(len - 1);
+            let mut response = format!(
+                "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                start,
+                end,
+                len,
+                end + 1 - start
+            )
+            .into_bytes();
+            response.extend_from_slice(&self.body[start..=end]);
+            response
+        } else {
+            let mut response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                len
+            )
+            .into_bytes();
+            response.extend_from_slice(&self.body);
+            response
+        };
+        let _ = stream.write_all(&response);
+    }
+}
+
+fn sample() -> Vec<u8> {
+    std::fs::read(format!(
+        "{}/tests/samples/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        SAMPLE
+    ))
+    .unwrap()
+}
+
+fn fast_retries() -> HttpSourceOptions {
+    HttpSourceOptions::default().with_retries(3, Duration::from_millis(1))
+}
+
+#[test]
+fn lazy_bundle_over_http_extracts_one_file_without_a_full_download() {
+    let bytes = sample();
+    let total = bytes.len() as u64;
+    let (url, _server) = Server::start(bytes.clone(), 0, true);
+
+    let source = Arc::new(HttpSource::with_options(&url, fast_retries()).unwrap());
+    assert_eq!(source.len(), total);
+    assert_eq!(source.name(), SAMPLE);
+
+    let bundle =
+        BundleParser::from_source_with_options(source.clone(), BundleLoadOptions::lazy()).unwrap();
+    let local = BundleParser::from_bytes_with_options(bytes, BundleLoadOptions::lazy()).unwrap();
+    assert_eq!(bundle.node_names(), local.node_names());
+    let after_listing = source.stats();
+    assert!(
+        after_listing.bytes_transferred < total / 10,
+        "{after_listing:?}"
+    );
+
+    // The SerializedFile node lives in the first of several blocks.
+    let node = bundle
+        .nodes
+        .iter()
+        .find(|n| n.is_file() && !n.name.ends_with(".resource"))
+        .unwrap()
+        .clone();
+    let data = bundle.extract_node_data(&node).unwrap();
+    assert_eq!(data, local.extract_node_data(&node).unwrap());
+    let file = SerializedFileParser::from_bytes(data).unwrap();
+    assert!(!file.objects.is_empty());
+
+    let stats = source.stats();
+    assert!(stats.bytes_transferred < total / 4, "{stats:?} of {total}");
+    assert_eq!(stats.retries, 0);
+}
+
+#[test]
+fn failed_requests_are_retried_with_backoff() {
+    let (url, server) = Server::start(sample(), 2, true);
+    let source = HttpSource::with_options(&url, fast_retries()).unwrap();
+    assert_eq!(source.stats().retries, 2);
+    assert_eq!(source.read_at(0, 7).unwrap(), b"UnityFS");
+
+    let (url, _server) = Server::start(sample(), 5, true);
+    let err = HttpSource::with_options(
+        &url,
+        HttpSourceOptions::default().with_retries(1, Duration::from_millis(1)),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("503"), "{err}");
+    assert!(server.requests.load(Ordering::SeqCst) >= 3);
+}
+
+#[test]
+fn cached_ranges_are_served_without_new_requests() {
+    let bytes = sample();
+    let (url, _server) = Server::start(bytes.clone(), 0, true);
+    let source =
+        HttpSource::with_options(&url, fast_retries().with_min_request_size(4096)).unwrap();
+
+    let offset = 300_000;
+    assert_eq!(
+        source.read_at(offset, 1000).unwrap(),
+        bytes[offset as usize..offset as usize + 1000]
+    );
+    let before = source.stats();
+    // Inside the read-ahead of the previous read.
+    assert_eq!(
+        source.read_at(offset + 2000, 1000).unwrap(),
+        bytes[offset as usize + 2000..offset as usize + 3000]
+    );
+    assert_eq!(source.stats().requests, before.requests);
+    // Spanning cached and uncached bytes only fetches the missing tail.
+    source.read_at(offset + 4000, 200).unwrap();
+    assert_eq!(source.stats().requests, before.requests + 1);
+    assert!(source.read_at(source.len() - 2, 4).is_err());
+
+    // A zero budget keeps nothing.
+    let uncached = HttpSource::with_options(&url, fast_retries().with_cache_budget(0)).unwrap();
+    let opened = uncached.stats().requests;
+    uncached.read_at(offset, 16).unwrap();
+    uncached.read_at(offset, 16).unwrap();
+    assert_eq!(uncached.stats().cached_bytes, 0);
+    assert_eq!(uncached.stats().requests, opened + 2);
+}
+
+#[test]
+fn servers_without_range_support_are_refused() {
+    let (url, _server) = Server::start(sample(), 0, false);
+    let err = HttpSource::with_options(&url, fast_retries()).unwrap_err();
+    assert!(err.to_string().contains("range requests"), "{err}");
+}
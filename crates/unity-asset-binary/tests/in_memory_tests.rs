@@ -0,0 +1,78 @@
+//! Loading without a filesystem: every input is embedded with `include_bytes!` and read through
+//! the in-memory entry points, so these tests also build with `--no-default-features` (and for
+//! wasm32).
+
+use unity_asset_binary::addressables::ContentCatalog;
+use unity_asset_binary::bundle::BundleLoader;
+use unity_asset_binary::data_source::VirtualFileSystem;
+use unity_asset_binary::file::{UnityFile, load_unity_file_from_memory};
+
+const BANNER: &[u8] = include_bytes!("samples/banner_1");
+const ATLAS: &[u8] = include_bytes!("samples/atlas_test");
+const CATALOG: &str = include_str!("fixtures/addressables_catalog.json");
+
+const UI_BUNDLE: &str = "ui_assets_all_5b7c0e6f1d2a3b4c5d6e7f8091a2b3c4.bundle";
+const ATLAS_BUNDLE: &str = "atlas_assets_all_0f1e2d3c4b5a69788796a5b4c3d2e1f0.bundle";
+
+fn vfs() -> VirtualFileSystem {
+    let mut vfs = VirtualFileSystem::new();
+    vfs.insert_bytes("Bundles/banner_1", BANNER.to_vec());
+    vfs.insert_bytes("Bundles/atlas_test", ATLAS.to_vec());
+    vfs
+}
+
+#[test]
+fn unity_file_from_memory_reads_objects() {
+    let UnityFile::AssetBundle(bundle) = load_unity_file_from_memory(BANNER.to_vec()).unwrap()
+    else {
+        panic!("banner_1 is an AssetBundle");
+    };
+    assert!(!bundle.assets.is_empty());
+    let handles = bundle.assets[0].object_handles().collect::<Vec<_>>();
+    assert!(!handles.is_empty());
+    for handle in handles {
+        handle.read().unwrap();
+    }
+}
+
+#[test]
+fn dependencies_and_addressables_load_from_vfs() {
+    let vfs = vfs();
+
+    let mut loader = BundleLoader::new();
+    let deps = |name: &str| -> Vec<String> {
+        match name {
+            "Bundles/banner_1" => vec!["Bundles/atlas_test".to_string()],
+            _ => Vec::new(),
+        }
+    };
+    let keys = loader
+        .load_with_dependencies_from_vfs(&vfs, "Bundles/banner_1", &deps)
+        .unwrap();
+    assert_eq!(keys, vec!["Bundles/atlas_test", "Bundles/banner_1"]);
+    assert!(loader.get_bundle("Bundles/banner_1").is_some());
+    assert!(
+        BundleLoader::new()
+            .load_with_dependencies_from_vfs(&vfs, "Bundles/missing", &deps)
+            .is_err()
+    );
+
+    let catalog = ContentCatalog::from_json_str(CATALOG).unwrap();
+    let mut loader = BundleLoader::new();
+    let loaded = loader
+        .load_addressable_from_vfs(&vfs, &catalog, "Banner1", |location| {
+            match location.file_name() {
+                UI_BUNDLE => Some("banner_1".to_string()),
+                ATLAS_BUNDLE => Some("atlas_test".to_string()),
+                _ => None,
+            }
+        })
+        .unwrap();
+    assert_eq!(loaded.bundles, vec!["atlas_test", "banner_1"]);
+    assert_eq!(loaded.objects.len(), 2);
+    assert!(
+        loader
+            .load_addressable_from_vfs(&vfs, &catalog, "Banner1", |_| None)
+            .is_err()
+    );
+}
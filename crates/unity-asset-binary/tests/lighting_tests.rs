@@ -0,0 +1,142 @@
+//! Lightmap data against a typetree dump of a scene with one baked lightmap.
+//!
+//! Two MeshRenderers share lightmap 0; the SkinnedMeshRenderer is not lightmapped.
+
+use unity_asset_binary::lighting::{
+    LightingAudit, LightingDataAsset, LightmapAssignment, LightmapSettings, RendererLightmap,
+};
+use unity_asset_binary::test_support::{object_properties, read_fixture};
+use unity_asset_binary::unity_objects::ObjectRef;
+
+fn fixture() -> serde_json::Value {
+    read_fixture("lighting_one_lightmap.json")
+}
+
+fn renderers(dump: &serde_json::Value) -> Vec<RendererLightmap> {
+    dump["Renderers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| RendererLightmap {
+            path_id: r["path_id"].as_i64().unwrap(),
+            class_id: r["class_id"].as_i64().unwrap() as i32,
+            name: r["game_object"].as_str().map(str::to_string),
+            assignment: LightmapAssignment::from_renderer(&object_properties(r, "properties"))
+                .unwrap(),
+        })
+        .collect()
+}
+
+/// Resolve textures the way `lighting_report` does for objects in the same file.
+fn resolve(dump: &serde_json::Value) -> impl FnMut(ObjectRef) -> Option<(String, u32, u32)> + '_ {
+    let texture = &dump["Texture2D"];
+    move |r: ObjectRef| {
+        (r.file_id == 0 && Some(r.path_id) == texture["path_id"].as_i64()).then(|| {
+            (
+                texture["m_Name"].as_str().unwrap().to_string(),
+                texture["m_Width"].as_u64().unwrap() as u32,
+                texture["m_Height"].as_u64().unwrap() as u32,
+            )
+        })
+    }
+}
+
+#[test]
+fn lightmap_settings_lists_lightmap_textures() {
+    let dump = fixture();
+    let settings =
+        LightmapSettings::from_typetree(&object_properties(&dump, "LightmapSettings")).unwrap();
+
+    assert_eq!(settings.lightmaps_mode, 1);
+    assert_eq!(settings.lighting_data_asset, None);
+    assert_eq!(settings.lightmaps.len(), 1);
+    let lightmap = settings.lightmaps[0];
+    assert_eq!(lightmap.lightmap, Some(ObjectRef::new(0, 300)));
+    assert_eq!(lightmap.directional, Some(ObjectRef::new(0, 301)));
+    assert_eq!(lightmap.shadow_mask, None);
+}
+
+#[test]
+fn renderer_assignments_read_index_and_scale_offset() {
+    let renderers = renderers(&fixture());
+    assert_eq!(renderers[0].assignment.lightmap_index, 0);
+    assert_eq!(renderers[0].assignment.scale_offset, [0.5, 0.5, 0.0, 0.0]);
+    assert_eq!(
+        renderers[1].assignment.scale_offset,
+        [0.25, 0.25, 0.5, 0.125]
+    );
+    assert!(renderers[1].assignment.is_baked());
+    assert!(!renderers[2].assignment.is_baked());
+}
+
+#[test]
+fn lighting_data_asset_pairs_renderer_data_with_scene_ids() {
+    let dump = fixture();
+    let asset =
+        LightingDataAsset::from_typetree(&object_properties(&dump, "LightingDataAsset")).unwrap();
+
+    assert_eq!(asset.name, "LightingData");
+    assert_eq!(asset.scene, None);
+    assert_eq!(asset.lightmaps.len(), 1);
+    assert_eq!(asset.renderers.len(), 1);
+    assert_eq!(asset.renderers[0].object_id, 1734092365);
+    assert_eq!(asset.renderers[0].assignment.lightmap_index, 0);
+    assert_eq!(
+        asset.renderers[0].assignment.scale_offset,
+        [0.5, 0.5, 0.0, 0.0]
+    );
+}
+
+#[test]
+fn lighting_report_aggregates_lightmaps_and_renderers() {
+    let dump = fixture();
+    let settings =
+        LightmapSettings::from_typetree(&object_properties(&dump, "LightmapSettings")).unwrap();
+    let data_asset =
+        LightingDataAsset::from_typetree(&object_properties(&dump, "LightingDataAsset")).unwrap();
+
+    let mut audit = LightingAudit::new();
+    audit.add_lighting_data_asset(&data_asset);
+    audit.add_lightmap_settings(&settings);
+    for renderer in renderers(&dump) {
+        audit.add_renderer(renderer);
+    }
+    let report = audit.finish(resolve(&dump));
+
+    // LightmapSettings wins over the LightingDataAsset's editor-side texture reference.
+    assert_eq!(report.lightmap_count(), 1);
+    assert_eq!(report.unresolved_lightmaps, 0);
+    let lightmap = &report.lightmaps[0];
+    assert_eq!(lightmap.texture, ObjectRef::new(0, 300));
+    assert_eq!(lightmap.name.as_deref(), Some("Lightmap-0_comp_light"));
+    assert_eq!((lightmap.width, lightmap.height), (512, 256));
+    assert_eq!(report.total_texel_area, 512 * 256);
+
+    let names: Vec<_> = report
+        .renderers_using(0)
+        .map(|r| r.name.as_deref().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Floor", "Crate"]);
+    assert_eq!(report.renderers.len(), 2);
+
+    let json = report.to_json();
+    assert_eq!(json["total_texel_area"], 131072);
+    assert_eq!(json["renderers"][1]["assignment"]["scale_offset"][2], 0.5);
+}
+
+#[test]
+fn lighting_report_falls_back_to_lighting_data_asset() {
+    let dump = fixture();
+    let data_asset =
+        LightingDataAsset::from_typetree(&object_properties(&dump, "LightingDataAsset")).unwrap();
+
+    let mut audit = LightingAudit::new();
+    audit.add_lightmap_settings(&LightmapSettings::default());
+    audit.add_lighting_data_asset(&data_asset);
+    let report = audit.finish(resolve(&dump));
+
+    assert_eq!(report.lightmap_count(), 1);
+    assert_eq!(report.lightmaps[0].texture, ObjectRef::new(0, 2800000));
+    assert_eq!(report.unresolved_lightmaps, 1);
+    assert_eq!(report.total_texel_area, 0);
+}
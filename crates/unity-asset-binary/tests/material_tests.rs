@@ -0,0 +1,273 @@
+//! Material (class 21) property maps from TypeTree data and from the raw 2018 and 2022 layouts.
+
+use indexmap::IndexMap;
+use unity_asset_binary::material::{Material, TexEnv};
+use unity_asset_binary::object::UnityObject;
+use unity_asset_binary::reader::ByteOrder;
+use unity_asset_binary::unity_objects::ObjectRef;
+use unity_asset_binary::unity_version::UnityVersion;
+use unity_asset_core::UnityValue;
+
+fn object(fields: Vec<(&str, UnityValue)>) -> UnityValue {
+    UnityValue::Object(
+        fields
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
+}
+
+fn string(s: &str) -> UnityValue {
+    UnityValue::String(s.to_string())
+}
+
+fn float(v: f32) -> UnityValue {
+    UnityValue::Float(v as f64)
+}
+
+fn pptr(file_id: i64, path_id: i64) -> UnityValue {
+    object(vec![
+        ("m_FileID", UnityValue::Integer(file_id)),
+        ("m_PathID", UnityValue::Integer(path_id)),
+    ])
+}
+
+fn pair(key: UnityValue, value: UnityValue) -> UnityValue {
+    UnityValue::Array(vec![key, value])
+}
+
+/// Little-endian object bytes, aligned like Unity writes them.
+#[derive(Default)]
+struct Bytes(Vec<u8>);
+
+impl Bytes {
+    fn i32(&mut self, v: i32) -> &mut Self {
+        self.0.extend_from_slice(&v.to_le_bytes());
+        self
+    }
+
+    fn f32s(&mut self, values: &[f32]) -> &mut Self {
+        for v in values {
+            self.0.extend_from_slice(&v.to_le_bytes());
+        }
+        self
+    }
+
+    fn pptr(&mut self, file_id: i32, path_id: i64) -> &mut Self {
+        self.i32(file_id);
+        self.0.extend_from_slice(&path_id.to_le_bytes());
+        self
+    }
+
+    fn align(&mut self) -> &mut Self {
+        while !self.0.len().is_multiple_of(4) {
+            self.0.push(0);
+        }
+        self
+    }
+
+    fn str(&mut self, s: &str) -> &mut Self {
+        self.i32(s.len() as i32);
+        self.0.extend_from_slice(s.as_bytes());
+        self.align()
+    }
+
+    fn strs(&mut self, values: &[&str]) -> &mut Self {
+        self.i32(values.len() as i32);
+        for s in values {
+            self.str(s);
+        }
+        self
+    }
+}
+
+/// `Glass` as every layout below stores it.
+fn expected(version_2021: bool) -> Material {
+    let mut material = Material {
+        name: "Glass".to_string(),
+        shader: ObjectRef::new(1, 7),
+        keywords: vec!["_ALPHABLEND_ON".to_string(), "_EMISSION".to_string()],
+        custom_render_queue: 3000,
+        tags: IndexMap::from([("RenderType".to_string(), "Transparent".to_string())]),
+        disabled_shader_passes: vec!["SHADOWCASTER".to_string()],
+        tex_envs: IndexMap::from([
+            (
+                "_MainTex".to_string(),
+                TexEnv {
+                    texture: Some(ObjectRef::new(0, -42)),
+                    scale: [2.0, 2.0],
+                    offset: [0.5, 0.0],
+                },
+            ),
+            ("_BumpMap".to_string(), TexEnv::default()),
+        ]),
+        floats: IndexMap::from([
+            ("_Glossiness".to_string(), 0.75),
+            ("_Mode".to_string(), 3.0),
+        ]),
+        colors: IndexMap::from([("_Color".to_string(), [1.0, 0.5, 0.25, 0.5])]),
+        ..Default::default()
+    };
+    if version_2021 {
+        material.invalid_keywords = vec!["_OLD_KEYWORD".to_string()];
+        material.ints = IndexMap::from([("_StencilRef".to_string(), 2)]);
+    }
+    material
+}
+
+fn raw_glass(version_2021: bool) -> Vec<u8> {
+    let mut b = Bytes::default();
+    b.str("Glass").pptr(1, 7);
+    if version_2021 {
+        b.strs(&["_ALPHABLEND_ON", "_EMISSION"])
+            .strs(&["_OLD_KEYWORD"]);
+    } else {
+        b.str("_ALPHABLEND_ON _EMISSION");
+    }
+    b.i32(4); // m_LightmapFlags
+    b.0.extend_from_slice(&[1, 0]); // m_EnableInstancingVariants, m_DoubleSidedGI
+    b.align().i32(3000);
+    b.i32(1).str("RenderType").str("Transparent");
+    b.strs(&["SHADOWCASTER"]);
+
+    b.i32(2);
+    b.str("_MainTex").pptr(0, -42).f32s(&[2.0, 2.0, 0.5, 0.0]);
+    b.str("_BumpMap").pptr(0, 0).f32s(&[1.0, 1.0, 0.0, 0.0]);
+    if version_2021 {
+        b.i32(1).str("_StencilRef").i32(2);
+    }
+    b.i32(2).str("_Glossiness").f32s(&[0.75]);
+    b.str("_Mode").f32s(&[3.0]);
+    b.i32(1).str("_Color").f32s(&[1.0, 0.5, 0.25, 0.5]);
+    b.0
+}
+
+#[test]
+fn typetree_property_maps() {
+    let tex_env = |texture: UnityValue, scale: f32, offset: f32| {
+        object(vec![
+            ("m_Texture", texture),
+            (
+                "m_Scale",
+                object(vec![("x", float(scale)), ("y", float(scale))]),
+            ),
+            (
+                "m_Offset",
+                object(vec![("x", float(offset)), ("y", float(0.0))]),
+            ),
+        ])
+    };
+    // 2021.3 layout: keyword arrays, `m_Ints`; map entries as `[key, value]` pairs.
+    let properties = match object(vec![
+        ("m_Name", string("Glass")),
+        ("m_Shader", pptr(1, 7)),
+        (
+            "m_ValidKeywords",
+            UnityValue::Array(vec![string("_ALPHABLEND_ON"), string("_EMISSION")]),
+        ),
+        (
+            "m_InvalidKeywords",
+            UnityValue::Array(vec![string("_OLD_KEYWORD")]),
+        ),
+        ("m_CustomRenderQueue", UnityValue::Integer(3000)),
+        (
+            "stringTagMap",
+            UnityValue::Array(vec![pair(string("RenderType"), string("Transparent"))]),
+        ),
+        (
+            "disabledShaderPasses",
+            UnityValue::Array(vec![string("SHADOWCASTER")]),
+        ),
+        (
+            "m_SavedProperties",
+            object(vec![
+                (
+                    "m_TexEnvs",
+                    UnityValue::Array(vec![
+                        pair(string("_MainTex"), tex_env(pptr(0, -42), 2.0, 0.5)),
+                        pair(string("_BumpMap"), tex_env(pptr(0, 0), 1.0, 0.0)),
+                    ]),
+                ),
+                (
+                    "m_Ints",
+                    UnityValue::Array(vec![pair(string("_StencilRef"), UnityValue::Integer(2))]),
+                ),
+                (
+                    "m_Floats",
+                    UnityValue::Array(vec![
+                        pair(string("_Glossiness"), float(0.75)),
+                        pair(string("_Mode"), float(3.0)),
+                    ]),
+                ),
+                (
+                    "m_Colors",
+                    UnityValue::Array(vec![pair(
+                        string("_Color"),
+                        object(vec![
+                            ("r", float(1.0)),
+                            ("g", float(0.5)),
+                            ("b", float(0.25)),
+                            ("a", float(0.5)),
+                        ]),
+                    )]),
+                ),
+            ]),
+        ),
+    ]) {
+        UnityValue::Object(fields) => fields,
+        _ => unreachable!(),
+    };
+    let material = Material::from_typetree(&properties).unwrap();
+    assert_eq!(material, expected(true));
+    assert_eq!(
+        material.textures().collect::<Vec<_>>(),
+        [("_MainTex", ObjectRef::new(0, -42))]
+    );
+
+    let json = serde_json::to_value(&material).unwrap();
+    assert_eq!(json["tex_envs"]["_MainTex"]["texture"]["path_id"], -42);
+    assert_eq!(json["floats"]["_Glossiness"], 0.75);
+
+    // 5.x keys are `FastPropertyName`s inside `{first, second}` entries.
+    let legacy = match object(vec![
+        ("m_ShaderKeywords", string("_EMISSION")),
+        (
+            "m_SavedProperties",
+            object(vec![(
+                "m_Floats",
+                UnityValue::Array(vec![object(vec![
+                    ("first", object(vec![("name", string("_Cutoff"))])),
+                    ("second", float(0.5)),
+                ])]),
+            )]),
+        ),
+    ]) {
+        UnityValue::Object(fields) => fields,
+        _ => unreachable!(),
+    };
+    let material = Material::from_typetree(&legacy).unwrap();
+    assert_eq!(material.keywords, ["_EMISSION"]);
+    assert_eq!(material.floats["_Cutoff"], 0.5);
+    assert_eq!(material.custom_render_queue, -1);
+    assert!(Material::from_typetree(&IndexMap::new()).is_err());
+}
+
+#[test]
+fn raw_layouts_2018_and_2022() {
+    for (version, version_2021) in [("2018.4.11f1", false), ("2022.3.5f1", true)] {
+        let version = UnityVersion::parse_version(version).unwrap();
+        let data = raw_glass(version_2021);
+        let material = Material::from_binary_data(&data, ByteOrder::Little, &version).unwrap();
+        assert_eq!(material, expected(version_2021), "{}", version);
+
+        let object = UnityObject::from_raw(21, 5, data.clone());
+        assert_eq!(object.as_material(&version).unwrap(), material);
+
+        let truncated = &data[..data.len() - 6];
+        assert!(Material::from_binary_data(truncated, ByteOrder::Little, &version).is_err());
+    }
+
+    let not_material = UnityObject::from_raw(28, 5, Vec::new());
+    let version = UnityVersion::parse_version("2018.4.11f1").unwrap();
+    assert!(not_material.as_material(&version).is_err());
+}
@@ -0,0 +1,252 @@
+//! Object hooks: ordering, skip/replace in the raw dump and bundle `extract_all`, emitted
+//! artifacts, and skipped objects left out of metadata.
+
+use std::sync::{Arc, Mutex};
+use unity_asset_binary::asset::{DumpFilter, ObjectInfo, SerializedFile, SerializedType};
+use unity_asset_binary::bundle::BundleProcessor;
+use unity_asset_binary::hooks::{HookAction, HookRegistry, ObjectContext, Replacement};
+use unity_asset_binary::metadata::{ExtractionConfig, MetadataProcessor};
+use unity_asset_binary::typetree::{TypeTree, TypeTreeNode};
+use unity_asset_core::UnityValue;
+
+const BANNER: &str = "banner_1";
+const SPRITE: i64 = -8325468307350463555;
+const TEXT_ASSET: i64 = 4242;
+
+fn node(type_name: &str, name: &str, byte_size: i32, children: Vec<TypeTreeNode>) -> TypeTreeNode {
+    let mut node = TypeTreeNode::with_info(type_name.to_string(), name.to_string(), byte_size);
+    node.children = children;
+    node
+}
+
+fn string(name: &str) -> TypeTreeNode {
+    let array = node(
+        "Array",
+        "Array",
+        -1,
+        vec![
+            node("int", "size", 4, Vec::new()),
+            node("char", "data", 1, Vec::new()),
+        ],
+    );
+    node("string", name, -1, vec![array])
+}
+
+fn write_string(out: &mut Vec<u8>, s: &[u8]) {
+    out.extend_from_slice(&(s.len() as i32).to_le_bytes());
+    out.extend_from_slice(s);
+    while !out.len().is_multiple_of(4) {
+        out.push(0);
+    }
+}
+
+/// "Encrypted" the way a game might: every byte xor 0x5A.
+fn scramble(text: &str) -> Vec<u8> {
+    text.bytes().map(|b| b ^ 0x5A).collect()
+}
+
+/// Add a TextAsset `dialogue` whose `m_Script` holds `script` to `file`.
+fn add_text_asset(file: &mut SerializedFile, script: &[u8]) {
+    let mut ty = SerializedType::new(49);
+    let mut tree = TypeTree::new();
+    tree.nodes.push(node(
+        "TextAsset",
+        "Base",
+        -1,
+        vec![string("m_Name"), string("m_Script")],
+    ));
+    ty.type_tree = tree;
+    file.types.push(ty);
+
+    let mut data = Vec::new();
+    write_string(&mut data, b"dialogue");
+    write_string(&mut data, script);
+    let mut info = ObjectInfo::new(
+        TEXT_ASSET,
+        0,
+        data.len() as u32,
+        49,
+        file.types.len() as i32 - 1,
+    );
+    info.data = data;
+    file.objects.push(info);
+}
+
+fn processor_with_text_asset(script: &[u8]) -> BundleProcessor {
+    let mut processor = BundleProcessor::new();
+    let bytes = include_bytes!("../../../tests/samples/banner_1").to_vec();
+    processor.process_memory(BANNER.to_string(), bytes).unwrap();
+    let bundle = processor.loader_mut().get_bundle_mut(BANNER).unwrap();
+    add_text_asset(&mut bundle.assets[0], script);
+    processor
+}
+
+/// Decrypts TextAssets and writes the plain text next to the dump.
+fn decrypt_text_assets(ctx: &ObjectContext<'_>) -> HookAction {
+    if ctx.class_name() != "TextAsset" {
+        return HookAction::Continue;
+    }
+    let object = ctx.object().unwrap();
+    let Some(UnityValue::String(script)) = object.get("m_Script") else {
+        return HookAction::Continue;
+    };
+    let plain: Vec<u8> = script.bytes().map(|b| b ^ 0x5A).collect();
+    let name = object.name().unwrap();
+    ctx.output()
+        .emit(format!("text/{}.txt", name), plain.clone());
+    HookAction::Replace(Replacement::Bytes(plain))
+}
+
+#[test]
+fn extract_all_writes_hook_replacements_and_artifacts() {
+    let mut processor = processor_with_text_asset(&scramble("Hello, Doctor."));
+    processor
+        .register_hook(decrypt_text_assets)
+        .register_hook(|ctx: &ObjectContext<'_>| {
+            if ctx.path_id() == SPRITE {
+                HookAction::Skip
+            } else {
+                HookAction::Continue
+            }
+        });
+
+    let temp = tempfile::tempdir().unwrap();
+    let report = processor
+        .extract_all(BANNER, temp.path(), &DumpFilter::default())
+        .unwrap();
+
+    let asset_dir = report.written[0].file.components().next().unwrap();
+    let asset_dir = temp.path().join(asset_dir);
+    assert_eq!(
+        std::fs::read(asset_dir.join(format!("TextAsset/{}.bin", TEXT_ASSET))).unwrap(),
+        b"Hello, Doctor."
+    );
+    assert_eq!(
+        std::fs::read(asset_dir.join("text/dialogue.txt")).unwrap(),
+        b"Hello, Doctor."
+    );
+    assert!(!asset_dir.join(format!("Sprite/{}.bin", SPRITE)).exists());
+
+    assert_eq!(report.skipped_by_hook, 1);
+    assert_eq!(report.replaced_by_hook, 1);
+    assert_eq!(report.written.len(), 3);
+    assert_eq!(report.artifacts.len(), 1);
+    assert!(report.artifacts[0].ends_with("text/dialogue.txt"));
+
+    // The index describes the replacement, not the original payload.
+    let text_row = report
+        .written
+        .iter()
+        .find(|o| o.path_id == TEXT_ASSET)
+        .unwrap();
+    assert_eq!(text_row.size, 14);
+    let index = std::fs::read_to_string(asset_dir.join("index.csv")).unwrap();
+    assert!(index.contains(&format!("TextAsset,{},14,", TEXT_ASSET)));
+}
+
+#[test]
+fn hooks_run_in_order_and_stop_at_the_first_decision() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let mut hooks = HookRegistry::new();
+    let log = Arc::clone(&seen);
+    hooks.register(move |ctx: &ObjectContext<'_>| {
+        log.lock().unwrap().push(("first", ctx.path_id()));
+        if ctx.class_id() == 28 {
+            HookAction::Replace(Replacement::Value(UnityValue::String("stub".into())))
+        } else {
+            HookAction::Continue
+        }
+    });
+    let log = Arc::clone(&seen);
+    hooks.register(move |ctx: &ObjectContext<'_>| {
+        log.lock().unwrap().push(("second", ctx.path_id()));
+        HookAction::Continue
+    });
+
+    let processor = processor_with_text_asset(b"");
+    let bundle = processor.loader().get_bundle(BANNER).unwrap();
+    let report = hooks.run_bundle(bundle);
+
+    assert_eq!(report.visited, 4);
+    assert_eq!(report.replaced.len(), 1);
+    assert_eq!(
+        report.replaced[0].1,
+        Replacement::Value(UnityValue::String("stub".into()))
+    );
+    assert_eq!(
+        report.replaced[0].0.asset_name,
+        bundle.unique_asset_names().first().cloned()
+    );
+    let texture = report.replaced[0].0.path_id;
+
+    let seen = seen.lock().unwrap();
+    assert!(seen.contains(&("first", texture)));
+    assert!(!seen.contains(&("second", texture)));
+    assert!(seen.contains(&("second", SPRITE)));
+    let first = seen.iter().position(|e| *e == ("first", SPRITE)).unwrap();
+    let second = seen.iter().position(|e| *e == ("second", SPRITE)).unwrap();
+    assert!(first < second);
+}
+
+#[test]
+fn value_replacements_are_dumped_as_json() {
+    let processor = processor_with_text_asset(&scramble("line"));
+    let file = &processor.loader().get_bundle(BANNER).unwrap().assets[0];
+    let mut hooks = HookRegistry::new();
+    hooks.register(|ctx: &ObjectContext<'_>| {
+        if ctx.path_id() != TEXT_ASSET {
+            return HookAction::Skip;
+        }
+        let mut decoded = ctx.object().unwrap().as_unity_class().properties().clone();
+        decoded.insert("m_Script".into(), UnityValue::String("line".into()));
+        HookAction::Replace(Replacement::Value(UnityValue::Object(decoded)))
+    });
+
+    let temp = tempfile::tempdir().unwrap();
+    let report = file
+        .dump_raw_objects_with_hooks(temp.path(), &DumpFilter::default(), &mut hooks)
+        .unwrap();
+    assert_eq!(report.written.len(), 1);
+    assert_eq!(report.skipped_by_hook, 3);
+    let json: serde_json::Value = serde_json::from_slice(
+        &std::fs::read(temp.path().join(format!("TextAsset/{}.json", TEXT_ASSET))).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(json["m_Name"], "dialogue");
+    assert_eq!(json["m_Script"], "line");
+}
+
+#[test]
+fn metadata_leaves_out_skipped_objects() {
+    let processor = processor_with_text_asset(b"");
+    let bundle = processor.loader().get_bundle(BANNER).unwrap();
+
+    let mut metadata = MetadataProcessor::with_config(ExtractionConfig::default());
+    let baseline = metadata.process_bundle(bundle).unwrap();
+    assert_eq!(baseline[0].metadata.object_stats.total_objects, 4);
+    assert_eq!(baseline[0].hooks.visited, 0);
+
+    metadata.register_hook(|ctx: &ObjectContext<'_>| {
+        if ctx.class_name() == "TextAsset" {
+            ctx.output().emit("notes.txt", b"seen".to_vec());
+            HookAction::Skip
+        } else {
+            HookAction::Continue
+        }
+    });
+    let results = metadata.process_bundle(bundle).unwrap();
+    let result = &results[0];
+    assert_eq!(result.metadata.object_stats.total_objects, 3);
+    assert!(
+        !result
+            .metadata
+            .object_stats
+            .objects_by_type
+            .contains_key("TextAsset")
+    );
+    assert_eq!(result.hooks.visited, 4);
+    assert_eq!(result.hooks.skipped.len(), 1);
+    assert_eq!(result.hooks.skipped[0].path_id, TEXT_ASSET);
+    assert_eq!(result.hooks.artifacts[0].name, "notes.txt");
+    assert_eq!(result.hooks.artifacts[0].object.path_id, TEXT_ASSET);
+}
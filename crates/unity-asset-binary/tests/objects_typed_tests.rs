@@ -0,0 +1,70 @@
+use unity_asset_binary::asset::SerializedFile;
+use unity_asset_binary::file::{UnityFile, load_unity_file};
+use unity_asset_core::{UnityValue, class_ids};
+
+const TEXTURE: i64 = -3875358842991402074;
+
+fn banner_asset() -> SerializedFile {
+    let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/samples/banner_1");
+    match load_unity_file(&path).expect("load sample bundle") {
+        UnityFile::AssetBundle(mut b) => b.assets.remove(0),
+        other => panic!("expected AssetBundle, got {:?}", other.kind()),
+    }
+}
+
+#[test]
+fn objects_typed_matches_handle_reads() {
+    let file = banner_asset();
+    let classes: Vec<_> = file
+        .objects_typed()
+        .collect::<Result<_, _>>()
+        .expect("every object has a TypeTree");
+    assert_eq!(classes.len(), file.objects.len());
+
+    for (class, handle) in classes.iter().zip(file.object_handles()) {
+        let object = handle.read().unwrap();
+        assert_eq!(class.class_id, object.class_id());
+        assert_eq!(class.class_name, object.class_name());
+        assert_eq!(class.anchor, handle.path_id().to_string());
+        assert_eq!(class.properties(), object.as_unity_class().properties());
+    }
+
+    let texture = classes
+        .iter()
+        .find(|c| c.class_id == class_ids::TEXTURE_2D)
+        .unwrap();
+    assert_eq!(texture.class_name, "Texture2D");
+    assert_eq!(texture.name(), Some("banner_1"));
+    assert_eq!(
+        texture.get("m_Width").and_then(UnityValue::as_i64),
+        Some(492)
+    );
+}
+
+#[test]
+fn missing_type_trees_fail_per_object() {
+    let mut file = banner_asset();
+    for t in file
+        .types
+        .iter_mut()
+        .filter(|t| t.class_id == class_ids::TEXTURE_2D)
+    {
+        t.type_tree.clear();
+    }
+
+    let results: Vec<_> = file.objects_typed().collect();
+    assert_eq!(results.len(), file.objects.len());
+    for (result, info) in results.iter().zip(&file.objects) {
+        if info.type_id == class_ids::TEXTURE_2D {
+            let err = result.as_ref().unwrap_err().to_string();
+            assert!(err.contains("has no TypeTree"), "{}", err);
+            assert!(err.contains(&TEXTURE.to_string()), "{}", err);
+        } else {
+            assert!(result.is_ok(), "{:?}", result);
+        }
+    }
+
+    // The lenient reader still falls back to a raw preview for the same object.
+    let object = file.find_object_handle(TEXTURE).unwrap().read().unwrap();
+    assert!(object.has_property("_raw_data_len"));
+}
@@ -0,0 +1,141 @@
+//! `PPtrResolver`: following `PPtr`s into other serialized files through `externals`.
+
+use unity_asset_binary::bundle::{AssetBundle, BundleParser};
+use unity_asset_binary::file::UnityFile;
+use unity_asset_binary::pptr::PPtrResolver;
+use unity_asset_binary::unity_objects::ObjectRef;
+use unity_asset_core::class_ids;
+
+const ATLAS_CAB: &str = "CAB-41a198e3c6c156112514cb656a4a5a12";
+const ATLAS: i64 = -9222691446010724640;
+const BANNER_SPRITE: i64 = -8325468307350463555;
+
+fn load(name: &str) -> AssetBundle {
+    BundleParser::from_bytes(std::fs::read(format!("../../tests/samples/{}", name)).unwrap())
+        .unwrap()
+}
+
+fn archive_path(cab: &str) -> String {
+    format!("archive:/{cab}/{cab}")
+}
+
+#[test]
+fn resolves_into_other_bundles() {
+    let mut banner = load("banner_1");
+    let file = &mut banner.assets[0];
+    let atlas_file_id = file.add_external(archive_path(ATLAS_CAB));
+    let missing_file_id = file.add_external(archive_path("CAB-0000"));
+
+    let mut resolver = PPtrResolver::new();
+    assert_eq!(resolver.add_bundle(load("atlas_test")), 0..1);
+    assert_eq!(resolver.name(0), Some(ATLAS_CAB));
+
+    let atlas = resolver
+        .read(file, ObjectRef::new(atlas_file_id, ATLAS))
+        .unwrap();
+    assert_eq!(atlas.class_id(), class_ids::SPRITE_ATLAS);
+    assert_eq!(atlas.name().as_deref(), Some("BuildingsWaterTowerAtlas"));
+
+    // File id 0 stays in the referencing file, registered or not.
+    let sprite = resolver
+        .resolve(file, ObjectRef::new(0, BANNER_SPRITE))
+        .unwrap();
+    assert_eq!(sprite.class_id(), class_ids::SPRITE);
+
+    for pptr in [
+        ObjectRef::new(missing_file_id, ATLAS),
+        ObjectRef::new(atlas_file_id, 12345),
+        ObjectRef::new(99, ATLAS),
+        ObjectRef::new(-1, ATLAS),
+        ObjectRef::new(atlas_file_id, 0),
+    ] {
+        assert!(resolver.resolve(file, pptr).is_err(), "{:?}", pptr);
+    }
+}
+
+#[test]
+fn matches_player_paths_by_file_name_ignoring_case() {
+    let mut resolver = PPtrResolver::new();
+    let atlas = load("atlas_test").assets.remove(0);
+    resolver.add_file("sharedassets0.assets", atlas);
+    let extra = load("banner_1").assets.remove(0);
+    resolver.add_file("Resources/unity_builtin_extra", extra);
+
+    assert_eq!(resolver.find_file("sharedassets0.assets"), Some(0));
+    assert_eq!(resolver.find_file("library/SharedAssets0.assets"), Some(0));
+    assert_eq!(resolver.find_file("resources/unity_builtin_extra"), Some(1));
+    assert_eq!(resolver.find_file("Library/unity_builtin_extra"), Some(1));
+    assert_eq!(resolver.find_file("library/unity default resources"), None);
+    assert_eq!(resolver.find_file(""), None);
+}
+
+#[test]
+fn load_missing_follows_externals() {
+    let mut banner = load("banner_1");
+    let file_id = banner.assets[0].add_external(archive_path(ATLAS_CAB));
+    banner.assets[0].add_external("library/unity default resources");
+
+    let mut resolver = PPtrResolver::new();
+    resolver.add_bundle(banner);
+    assert_eq!(
+        resolver.missing_externals(),
+        [
+            archive_path(ATLAS_CAB),
+            "library/unity default resources".to_string()
+        ]
+    );
+
+    let mut requested = Vec::new();
+    let loaded = resolver
+        .load_missing(|path| {
+            requested.push(path.to_string());
+            Ok(path
+                .starts_with("archive:")
+                .then(|| UnityFile::AssetBundle(load("atlas_test"))))
+        })
+        .unwrap();
+    assert_eq!(loaded, 1);
+    assert_eq!(requested.len(), 2);
+    assert_eq!(
+        resolver.missing_externals(),
+        ["library/unity default resources"]
+    );
+
+    let banner_file = resolver.file(0).unwrap();
+    let atlas = resolver
+        .resolve(banner_file, ObjectRef::new(file_id, ATLAS))
+        .unwrap();
+    assert_eq!(atlas.class_id(), class_ids::SPRITE_ATLAS);
+}
+
+#[cfg(feature = "fs")]
+#[test]
+fn load_missing_from_player_data_dir() {
+    let atlas = load("atlas_test");
+    let node = atlas.asset_node_index(0).unwrap();
+    let bytes = atlas.extract_node_data(&atlas.nodes[node]).unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("sharedassets0.assets"), &bytes).unwrap();
+    std::fs::create_dir(dir.path().join("Resources")).unwrap();
+    std::fs::write(dir.path().join("Resources/unity default resources"), &bytes).unwrap();
+
+    let mut banner = load("banner_1");
+    let shared = banner.assets[0].add_external("library/SharedAssets0.assets");
+    let builtin = banner.assets[0].add_external("Library/unity default resources");
+    banner.assets[0].add_external(archive_path(ATLAS_CAB));
+
+    let mut resolver = PPtrResolver::new();
+    resolver.add_bundle(banner);
+    assert_eq!(resolver.load_missing_from_dir(dir.path()).unwrap(), 2);
+    // Bundle members are never looked up on disk.
+    assert_eq!(resolver.missing_externals(), [archive_path(ATLAS_CAB)]);
+
+    let banner_file = resolver.file(0).unwrap();
+    for file_id in [shared, builtin] {
+        let object = resolver
+            .read(banner_file, ObjectRef::new(file_id, ATLAS))
+            .unwrap();
+        assert_eq!(object.class_id(), class_ids::SPRITE_ATLAS);
+    }
+}
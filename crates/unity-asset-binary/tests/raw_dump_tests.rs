@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use unity_asset_binary::asset::{DUMP_INDEX_FILE_NAME, DumpFilter};
+use unity_asset_binary::bundle::{AssetBundle, load_bundle_from_memory};
+
+fn sample_bundle() -> AssetBundle {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/samples/char_118_yuki.ab");
+    load_bundle_from_memory(std::fs::read(path).unwrap()).unwrap()
+}
+
+fn count_bin_files(dir: &Path) -> usize {
+    let mut count = 0;
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            count += count_bin_files(&path);
+        } else if path.extension().is_some_and(|e| e == "bin") {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[test]
+fn dump_all_objects_writes_files_and_index() {
+    let bundle = sample_bundle();
+    let file = &bundle.assets[0];
+    let dir = tempfile::tempdir().unwrap();
+
+    let report = file
+        .dump_raw_objects(dir.path(), &DumpFilter::default())
+        .unwrap();
+
+    assert_eq!(report.written.len(), file.objects.len());
+    assert_eq!(count_bin_files(dir.path()), file.objects.len());
+    assert_eq!(
+        report.bytes_written,
+        file.objects.iter().map(|o| o.byte_size as u64).sum::<u64>()
+    );
+
+    let index = std::fs::read_to_string(dir.path().join(DUMP_INDEX_FILE_NAME)).unwrap();
+    let mut lines = index.lines();
+    assert_eq!(lines.next(), Some("class,path_id,size,offset,sha256"));
+
+    let by_path_id: HashMap<i64, _> = file.objects.iter().map(|o| (o.path_id, o)).collect();
+    let mut rows = 0;
+    for line in lines {
+        let cols: Vec<&str> = line.split(',').collect();
+        assert_eq!(cols.len(), 5, "{line}");
+        let path_id: i64 = cols[1].parse().unwrap();
+        let info = by_path_id[&path_id];
+        assert_eq!(cols[2].parse::<u64>().unwrap(), info.byte_size as u64);
+        assert_eq!(cols[3].parse::<u64>().unwrap(), info.byte_start);
+        assert_eq!(cols[4].len(), 64);
+
+        let payload =
+            std::fs::read(dir.path().join(cols[0]).join(format!("{path_id}.bin"))).unwrap();
+        assert_eq!(payload, file.object_bytes(info).unwrap());
+        rows += 1;
+    }
+    assert_eq!(rows, file.objects.len());
+}
+
+#[test]
+fn dump_filters_by_class_name_and_id() {
+    let bundle = sample_bundle();
+    let file = &bundle.assets[0];
+    let audio = file.objects.iter().filter(|o| o.type_id == 83).count();
+    assert!(audio > 0);
+
+    let by_name = tempfile::tempdir().unwrap();
+    let report = file
+        .dump_raw_objects(
+            by_name.path(),
+            &DumpFilter {
+                classes: vec!["audioclip".to_string()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(report.written.len(), audio);
+    assert_eq!(report.skipped_by_class, file.objects.len() - audio);
+    assert!(report.written.iter().all(|o| o.class_name == "AudioClip"));
+    assert_eq!(count_bin_files(&by_name.path().join("AudioClip")), audio);
+
+    let by_id = tempfile::tempdir().unwrap();
+    let report_by_id = file
+        .dump_raw_objects(
+            by_id.path(),
+            &DumpFilter {
+                classes: vec!["83".to_string()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(report_by_id.written, report.written);
+}
+
+#[test]
+fn dump_respects_size_caps() {
+    let bundle = sample_bundle();
+    let file = &bundle.assets[0];
+    let mut sizes: Vec<u64> = file.objects.iter().map(|o| o.byte_size as u64).collect();
+    sizes.sort_unstable();
+    let median = sizes[sizes.len() / 2];
+
+    let dir = tempfile::tempdir().unwrap();
+    let report = file
+        .dump_raw_objects(
+            dir.path(),
+            &DumpFilter {
+                max_object_size: Some(median),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let expected = sizes.iter().filter(|&&s| s <= median).count();
+    assert_eq!(report.written.len(), expected);
+    assert_eq!(report.skipped_oversize, sizes.len() - expected);
+    assert!(report.written.iter().all(|o| o.size <= median));
+    assert_eq!(count_bin_files(dir.path()), expected);
+
+    let budget = 1000;
+    let dir = tempfile::tempdir().unwrap();
+    let report = file
+        .dump_raw_objects(
+            dir.path(),
+            &DumpFilter {
+                max_total_size: Some(budget),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert!(report.bytes_written <= budget);
+    assert_eq!(
+        report.written.len() + report.skipped_total_cap,
+        file.objects.len()
+    );
+    assert!(report.skipped_total_cap > 0);
+    let index = std::fs::read_to_string(dir.path().join(DUMP_INDEX_FILE_NAME)).unwrap();
+    assert_eq!(index.lines().count(), report.written.len() + 1);
+}
+
+#[test]
+fn bundle_dump_prefixes_asset_names() {
+    let bundle = sample_bundle();
+    let dir = tempfile::tempdir().unwrap();
+    let report = bundle
+        .dump_raw_objects(dir.path(), &DumpFilter::default())
+        .unwrap();
+
+    let total: usize = bundle.assets.iter().map(|a| a.objects.len()).sum();
+    assert_eq!(report.written.len(), total);
+    for obj in &report.written {
+        assert!(dir.path().join(&obj.file).is_file(), "{:?}", obj.file);
+    }
+    let asset_dir = dir
+        .path()
+        .join(report.written[0].file.components().next().unwrap());
+    assert!(asset_dir.join(DUMP_INDEX_FILE_NAME).is_file());
+}
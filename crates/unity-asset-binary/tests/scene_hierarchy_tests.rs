@@ -0,0 +1,156 @@
+//! Scene hierarchy reconstruction from GameObject components and Transform links
+
+use unity_asset_binary::asset::ObjectInfo;
+use unity_asset_binary::bundle::BundleParser;
+use unity_asset_binary::object::UnityObject;
+use unity_asset_binary::scene::SceneHierarchy;
+use unity_asset_core::{UnityClass, UnityValue, class_ids};
+
+fn pptr(file_id: i64, path_id: i64) -> UnityValue {
+    UnityValue::Object(
+        [
+            ("m_FileID".to_string(), UnityValue::Integer(file_id)),
+            ("m_PathID".to_string(), UnityValue::Integer(path_id)),
+        ]
+        .into_iter()
+        .collect(),
+    )
+}
+
+fn unity_object(class_id: i32, path_id: i64, fields: Vec<(&str, UnityValue)>) -> UnityObject {
+    let mut class = UnityClass::new(class_id, String::new(), path_id.to_string());
+    for (key, value) in fields {
+        class.set(key.to_string(), value);
+    }
+    UnityObject::from_info_and_class(ObjectInfo::new(path_id, 0, 0, class_id, -1), class)
+}
+
+fn game_object(path_id: i64, name: &str, components: &[i64]) -> UnityObject {
+    let components = components
+        .iter()
+        .map(|&c| {
+            UnityValue::Object(
+                [("component".to_string(), pptr(0, c))]
+                    .into_iter()
+                    .collect(),
+            )
+        })
+        .collect();
+    unity_object(
+        class_ids::GAME_OBJECT,
+        path_id,
+        vec![
+            ("m_Component", UnityValue::Array(components)),
+            ("m_Layer", UnityValue::Integer(5)),
+            ("m_Name", UnityValue::String(name.to_string())),
+            ("m_IsActive", UnityValue::Integer(1)),
+        ],
+    )
+}
+
+fn transform(
+    class_id: i32,
+    path_id: i64,
+    game_object: i64,
+    father: (i64, i64),
+    children: &[i64],
+) -> UnityObject {
+    unity_object(
+        class_id,
+        path_id,
+        vec![
+            ("m_GameObject", pptr(0, game_object)),
+            (
+                "m_Children",
+                UnityValue::Array(children.iter().map(|&c| pptr(0, c)).collect()),
+            ),
+            ("m_Father", pptr(father.0, father.1)),
+        ],
+    )
+}
+
+/// `Root` holds `Child` (listed in `m_Children`) and `Side` (only through its `m_Father`);
+/// `Child` holds `Hand`. `Detached`'s parent lives in another file.
+fn scene_objects() -> Vec<UnityObject> {
+    vec![
+        game_object(1, "Root", &[2, 3]),
+        transform(class_ids::TRANSFORM, 2, 1, (0, 0), &[11]),
+        unity_object(23, 3, vec![]),
+        game_object(10, "Child", &[11, 12]),
+        transform(class_ids::RECT_TRANSFORM, 11, 10, (0, 2), &[21]),
+        unity_object(class_ids::MONO_BEHAVIOUR, 12, vec![]),
+        game_object(20, "Hand", &[21, 99]),
+        transform(class_ids::TRANSFORM, 21, 20, (0, 11), &[]),
+        game_object(30, "Side", &[31]),
+        transform(class_ids::TRANSFORM, 31, 30, (0, 2), &[]),
+        game_object(40, "Detached", &[41]),
+        transform(class_ids::TRANSFORM, 41, 40, (1, 7), &[]),
+    ]
+}
+
+#[test]
+fn hierarchy_follows_transform_links() {
+    let hierarchy = SceneHierarchy::from_objects(&scene_objects()).unwrap();
+    assert_eq!(hierarchy.len(), 5);
+
+    let roots: Vec<_> = hierarchy.roots().map(|n| n.name.as_str()).collect();
+    assert_eq!(roots, ["Root", "Detached"]);
+
+    let root = hierarchy.get(1).unwrap();
+    assert_eq!(root.transform_path_id, Some(2));
+    assert_eq!(root.children, [10, 30]);
+    assert_eq!(
+        root.component_classes().collect::<Vec<_>>(),
+        [class_ids::TRANSFORM, 23]
+    );
+    assert_eq!((root.active, root.layer), (true, 5));
+
+    let child = hierarchy.get(11).unwrap();
+    assert_eq!((child.path_id, child.parent), (10, Some(1)));
+    assert!(child.has_component(class_ids::MONO_BEHAVIOUR));
+
+    // A component missing from the file has no class.
+    let hand = hierarchy.find_by_name("Hand").next().unwrap();
+    assert_eq!(hand.components[1].class_id, None);
+    assert_eq!(
+        hierarchy.path_of(hand.path_id).as_deref(),
+        Some("Root/Child/Hand")
+    );
+    assert_eq!(hierarchy.path_of(31).as_deref(), Some("Root/Side"));
+    assert_eq!(hierarchy.path_of(99), None);
+
+    let walk: Vec<_> = hierarchy
+        .walk()
+        .map(|(depth, n)| (depth, n.name.as_str()))
+        .collect();
+    assert_eq!(
+        walk,
+        [
+            (0, "Root"),
+            (1, "Child"),
+            (2, "Hand"),
+            (1, "Side"),
+            (0, "Detached")
+        ]
+    );
+}
+
+#[test]
+fn transform_cycles_are_rejected() {
+    let objects = vec![
+        game_object(1, "A", &[2]),
+        transform(class_ids::TRANSFORM, 2, 1, (0, 4), &[]),
+        game_object(3, "B", &[4]),
+        transform(class_ids::TRANSFORM, 4, 3, (0, 2), &[]),
+    ];
+    assert!(SceneHierarchy::from_objects(&objects).is_err());
+}
+
+#[test]
+fn bundles_without_game_objects_have_empty_hierarchies() {
+    let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/samples/banner_1");
+    let bundle = BundleParser::from_bytes(std::fs::read(path).unwrap()).unwrap();
+    let hierarchy = SceneHierarchy::build(&bundle.assets[0]).unwrap();
+    assert!(hierarchy.is_empty());
+    assert_eq!(hierarchy.roots().count(), 0);
+}
@@ -0,0 +1,233 @@
+use indexmap::IndexMap;
+use unity_asset_binary::reader::{BinaryReader, ByteOrder};
+use unity_asset_binary::shader_audit::{ShaderAudit, ShaderId};
+use unity_asset_binary::typetree::{TypeTree, TypeTreeNode, TypeTreeSerializer};
+use unity_asset_binary::unity_objects::{
+    GraphicsSettings, ObjectRef, PassType, ShaderVariantCollection,
+};
+use unity_asset_core::UnityValue;
+
+fn node(type_name: &str, name: &str, children: Vec<TypeTreeNode>) -> TypeTreeNode {
+    let mut n = TypeTreeNode::with_info(type_name.to_string(), name.to_string(), -1);
+    n.children = children;
+    n
+}
+
+fn array_of(type_name: &str, name: &str, element: TypeTreeNode) -> TypeTreeNode {
+    node(
+        type_name,
+        name,
+        vec![node(
+            "Array",
+            "Array",
+            vec![node("int", "size", vec![]), element],
+        )],
+    )
+}
+
+fn pptr(name: &str) -> TypeTreeNode {
+    node(
+        "PPtr<Shader>",
+        name,
+        vec![
+            node("int", "m_FileID", vec![]),
+            node("SInt64", "m_PathID", vec![]),
+        ],
+    )
+}
+
+/// `ShaderVariantCollection { string m_Name; map<PPtr<Shader>, ShaderInfo> m_Shaders; }`
+fn variant_collection_tree() -> TypeTree {
+    let variant_info = node(
+        "VariantInfo",
+        "data",
+        vec![
+            node("string", "keywords", vec![]),
+            node("int", "passType", vec![]),
+        ],
+    );
+    let shader_info = node(
+        "ShaderInfo",
+        "second",
+        vec![array_of("vector", "variants", variant_info)],
+    );
+    let pair = node("pair", "data", vec![pptr("first"), shader_info]);
+
+    let mut tree = TypeTree::new();
+    tree.add_node(node(
+        "ShaderVariantCollection",
+        "Base",
+        vec![
+            node("string", "m_Name", vec![]),
+            array_of("map", "m_Shaders", pair),
+        ],
+    ));
+    tree
+}
+
+fn push_string(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as i32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+    while !out.len().is_multiple_of(4) {
+        out.push(0);
+    }
+}
+
+fn push_pptr(out: &mut Vec<u8>, file_id: i32, path_id: i64) {
+    out.extend_from_slice(&file_id.to_le_bytes());
+    out.extend_from_slice(&path_id.to_le_bytes());
+}
+
+/// `(shader path_id, [(keywords, passType)])`
+type Fixture<'a> = &'a [(i64, &'a [(&'a str, i32)])];
+
+fn collection_bytes(name: &str, shaders: Fixture<'_>) -> Vec<u8> {
+    let mut out = Vec::new();
+    push_string(&mut out, name);
+    out.extend_from_slice(&(shaders.len() as i32).to_le_bytes());
+    for (path_id, variants) in shaders {
+        push_pptr(&mut out, 0, *path_id);
+        out.extend_from_slice(&(variants.len() as i32).to_le_bytes());
+        for (keywords, pass_type) in *variants {
+            push_string(&mut out, keywords);
+            out.extend_from_slice(&pass_type.to_le_bytes());
+        }
+    }
+    out
+}
+
+fn parse_collection(name: &str, shaders: Fixture<'_>) -> ShaderVariantCollection {
+    let tree = variant_collection_tree();
+    let bytes = collection_bytes(name, shaders);
+    let mut reader = BinaryReader::new(&bytes, ByteOrder::Little);
+    let properties = TypeTreeSerializer::new(&tree)
+        .parse_object(&mut reader)
+        .unwrap();
+    assert_eq!(reader.position() as usize, bytes.len());
+    ShaderVariantCollection::from_typetree(&properties).unwrap()
+}
+
+fn resolve(shader: ObjectRef) -> ShaderId {
+    let name = match shader.path_id {
+        10 => Some("Custom/Lit".to_string()),
+        20 => Some("Custom/Unlit".to_string()),
+        _ => None,
+    };
+    ShaderId {
+        file: "sharedassets0.assets".to_string(),
+        path_id: shader.path_id,
+        name,
+    }
+}
+
+const WARMUP: Fixture<'static> = &[
+    (
+        10,
+        &[
+            ("_NORMALMAP", 4),
+            ("_NORMALMAP _EMISSION", 4),
+            ("", 8),
+            // Same keyword set in a different order: not a new variant.
+            ("_EMISSION _NORMALMAP", 4),
+        ],
+    ),
+    (20, &[("FOG_LINEAR", 0)]),
+];
+
+const EXTRA: Fixture<'static> = &[
+    // Repeats one Lit variant and adds one new one.
+    (10, &[("_NORMALMAP", 4), ("_NORMALMAP", 5)]),
+    (30, &[("_ALPHATEST_ON", 13)]),
+];
+
+#[test]
+fn variant_collection_parses_shader_refs_pass_types_and_keywords() {
+    let collection = parse_collection("Warmup", WARMUP);
+    assert_eq!(collection.name, "Warmup");
+    assert_eq!(collection.shaders.len(), 2);
+    assert_eq!(collection.variant_count(), 5);
+
+    let lit = &collection.shaders[0];
+    assert_eq!(lit.shader, ObjectRef::new(0, 10));
+    assert_eq!(lit.variants[1].pass_type, PassType::ForwardBase);
+    assert_eq!(lit.variants[1].keywords, vec!["_NORMALMAP", "_EMISSION"]);
+    assert_eq!(lit.variants[2].pass_type, PassType::ShadowCaster);
+    assert!(lit.variants[2].keywords.is_empty());
+    assert_eq!(
+        collection.shaders[1].variants[0].pass_type,
+        PassType::Normal
+    );
+}
+
+#[test]
+fn shader_audit_counts_variants_per_shader_and_keyword() {
+    let mut always_included = IndexMap::new();
+    always_included.insert(
+        "m_AlwaysIncludedShaders".to_string(),
+        UnityValue::Array(vec![
+            UnityValue::Object(
+                [
+                    ("m_FileID".to_string(), UnityValue::Integer(0)),
+                    ("m_PathID".to_string(), UnityValue::Integer(20)),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+            UnityValue::Object(
+                [
+                    ("m_FileID".to_string(), UnityValue::Integer(0)),
+                    ("m_PathID".to_string(), UnityValue::Integer(40)),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+        ]),
+    );
+    let settings = GraphicsSettings::from_typetree(&always_included).unwrap();
+    assert_eq!(settings.always_included_shaders.len(), 2);
+
+    let mut audit = ShaderAudit::new();
+    audit.add_variant_collection(&parse_collection("Warmup", WARMUP), resolve);
+    audit.add_variant_collection(&parse_collection("Extra", EXTRA), resolve);
+    audit.add_graphics_settings(&settings, resolve);
+    let report = audit.finish();
+
+    assert_eq!(report.collections, vec!["Warmup", "Extra"]);
+    assert_eq!(report.total_variants, 6);
+    assert_eq!(report.always_included, 2);
+    assert_eq!(report.unresolved, 2);
+
+    let names: Vec<_> = report
+        .shaders
+        .iter()
+        .map(|e| e.shader.name.as_deref())
+        .collect();
+    assert_eq!(
+        names,
+        vec![Some("Custom/Lit"), Some("Custom/Unlit"), None, None]
+    );
+
+    let lit = report.shader("Custom/Lit").unwrap();
+    assert_eq!(lit.variants, 4);
+    assert_eq!(lit.collections, vec!["Extra", "Warmup"]);
+    assert!(!lit.always_included);
+    assert_eq!(lit.keywords.get("_NORMALMAP"), Some(&3));
+    assert_eq!(lit.keywords.get("_EMISSION"), Some(&1));
+    assert_eq!(lit.pass_types.get("ForwardBase"), Some(&2));
+    assert_eq!(lit.pass_types.get("ForwardAdd"), Some(&1));
+    assert_eq!(lit.pass_types.get("ShadowCaster"), Some(&1));
+
+    let unlit = report.shader("Custom/Unlit").unwrap();
+    assert_eq!(unlit.variants, 1);
+    assert!(unlit.always_included);
+
+    assert_eq!(report.keywords.get("_NORMALMAP"), Some(&3));
+    assert_eq!(report.keywords.get("FOG_LINEAR"), Some(&1));
+    assert_eq!(report.keywords.get("_ALPHATEST_ON"), Some(&1));
+    assert_eq!(report.pass_types.get("ScriptableRenderPipeline"), Some(&1));
+
+    let json = report.to_json();
+    assert_eq!(json["total_variants"], 6);
+    assert_eq!(json["shaders"][0]["shader"]["name"], "Custom/Lit");
+    assert_eq!(json["shaders"][0]["keywords"]["_NORMALMAP"], 3);
+}
@@ -0,0 +1,254 @@
+//! Shader (class 48) structure from the 5.5–2019.2, 2019.3+ and pre-5.5 layouts.
+
+use indexmap::IndexMap;
+use unity_asset_binary::shader::{ShaderBlobSegment, ShaderParser, platform_name};
+use unity_asset_core::UnityValue;
+
+type Fields = IndexMap<String, UnityValue>;
+
+fn object(fields: Vec<(&str, UnityValue)>) -> UnityValue {
+    UnityValue::Object(
+        fields
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
+}
+
+fn fields(value: UnityValue) -> Fields {
+    match value {
+        UnityValue::Object(fields) => fields,
+        _ => unreachable!(),
+    }
+}
+
+fn string(s: &str) -> UnityValue {
+    UnityValue::String(s.to_string())
+}
+
+fn strings(items: &[&str]) -> UnityValue {
+    UnityValue::Array(items.iter().map(|s| string(s)).collect())
+}
+
+fn ints(items: &[u32]) -> UnityValue {
+    UnityValue::Array(
+        items
+            .iter()
+            .map(|&v| UnityValue::Integer(v as i64))
+            .collect(),
+    )
+}
+
+fn sub_program(keyword_indices: &[u32]) -> UnityValue {
+    object(vec![
+        ("m_BlobIndex", UnityValue::Integer(0)),
+        ("m_KeywordIndices", ints(keyword_indices)),
+    ])
+}
+
+/// A pass whose vertex and fragment programs each have one sub-program per keyword set.
+fn pass(name_indices: &[(&str, u32)], variants: &[&[u32]]) -> UnityValue {
+    let program = || {
+        object(vec![(
+            "m_SubPrograms",
+            UnityValue::Array(variants.iter().map(|k| sub_program(k)).collect()),
+        )])
+    };
+    object(vec![
+        (
+            "m_NameIndices",
+            UnityValue::Array(
+                name_indices
+                    .iter()
+                    .map(|(name, i)| {
+                        UnityValue::Array(vec![string(name), UnityValue::Integer(*i as i64)])
+                    })
+                    .collect(),
+            ),
+        ),
+        ("progVertex", program()),
+        ("progFragment", program()),
+    ])
+}
+
+fn parsed_form(sub_shaders: Vec<Vec<UnityValue>>) -> UnityValue {
+    object(vec![
+        (
+            "m_PropInfo",
+            object(vec![(
+                "m_Props",
+                UnityValue::Array(vec![
+                    object(vec![("m_Name", string("_MainTex"))]),
+                    object(vec![("m_Name", string("_Color"))]),
+                ]),
+            )]),
+        ),
+        (
+            "m_SubShaders",
+            UnityValue::Array(
+                sub_shaders
+                    .into_iter()
+                    .map(|passes| object(vec![("m_Passes", UnityValue::Array(passes))]))
+                    .collect(),
+            ),
+        ),
+        ("m_Name", string("Custom/Toon")),
+    ])
+}
+
+/// Compress each part as its own LZ4 segment, returning the blob and the segments.
+fn blob(parts: &[&[u8]]) -> (Vec<u8>, Vec<ShaderBlobSegment>) {
+    let mut blob = Vec::new();
+    let mut segments = Vec::new();
+    for part in parts {
+        let compressed = lz4_flex::block::compress(part);
+        segments.push(ShaderBlobSegment {
+            offset: blob.len() as u32,
+            compressed_length: compressed.len() as u32,
+            decompressed_length: part.len() as u32,
+        });
+        blob.extend_from_slice(&compressed);
+    }
+    (blob, segments)
+}
+
+#[test]
+fn parses_parsed_form_and_flat_offsets() {
+    let names = [
+        ("FOG_LINEAR", 0),
+        ("_ALPHATEST_ON", 1),
+        ("SHADOWS_SCREEN", 2),
+    ];
+    let (compressed, segments) = blob(&[b"d3d11 programs".repeat(20).as_slice(), b"vulkan"]);
+    let properties = fields(object(vec![
+        ("m_Name", string("")),
+        (
+            "m_ParsedForm",
+            parsed_form(vec![
+                vec![pass(&names, &[&[], &[0], &[0, 1]]), pass(&names, &[&[2]])],
+                vec![pass(&names, &[&[]])],
+            ]),
+        ),
+        ("platforms", ints(&[4, 18])),
+        (
+            "offsets",
+            ints(&segments.iter().map(|s| s.offset).collect::<Vec<_>>()),
+        ),
+        (
+            "compressedLengths",
+            ints(
+                &segments
+                    .iter()
+                    .map(|s| s.compressed_length)
+                    .collect::<Vec<_>>(),
+            ),
+        ),
+        (
+            "decompressedLengths",
+            ints(
+                &segments
+                    .iter()
+                    .map(|s| s.decompressed_length)
+                    .collect::<Vec<_>>(),
+            ),
+        ),
+        ("compressedBlob", UnityValue::Bytes(compressed)),
+    ]));
+
+    let shader = ShaderParser::parse(&properties).unwrap();
+    assert_eq!(shader.name, "Custom/Toon");
+    assert_eq!(shader.prop_names, ["_MainTex", "_Color"]);
+    assert_eq!(shader.pass_counts, [2, 1]);
+    assert_eq!(shader.sub_shader_count(), 2);
+    assert_eq!(shader.sub_programs, 2 * (3 + 1 + 1));
+    assert_eq!(
+        shader.keywords,
+        ["FOG_LINEAR", "SHADOWS_SCREEN", "_ALPHATEST_ON"]
+    );
+    assert_eq!(shader.platforms, [4, 18]);
+    let platforms: Vec<_> = shader.platforms.iter().map(|&p| platform_name(p)).collect();
+    assert_eq!(platforms, [Some("D3D11"), Some("Vulkan")]);
+    assert_eq!(
+        shader.compressed_blob_sizes,
+        [vec![segments[0]], vec![segments[1]]]
+    );
+
+    assert_eq!(
+        ShaderParser::decompress_platform(&properties, &shader, 1).unwrap(),
+        [b"vulkan".to_vec()]
+    );
+    assert!(ShaderParser::decompress_platform(&properties, &shader, 2).is_err());
+}
+
+#[test]
+fn parses_segmented_blobs_and_keyword_names() {
+    let parts: [&[u8]; 3] = [b"segment one", b"segment two", b"metal"];
+    let (compressed, segments) = blob(&parts);
+    let nested = |f: fn(&ShaderBlobSegment) -> u32| {
+        UnityValue::Array(vec![
+            ints(&[f(&segments[0]), f(&segments[1])]),
+            ints(&[f(&segments[2])]),
+        ])
+    };
+    let mut form = fields(parsed_form(vec![vec![pass(&[], &[&[]])]]));
+    form.insert(
+        "m_KeywordNames".to_string(),
+        strings(&["_NORMALMAP", "_EMISSION"]),
+    );
+    let properties = fields(object(vec![
+        ("m_Name", string("")),
+        ("m_ParsedForm", UnityValue::Object(form)),
+        ("platforms", ints(&[15, 14])),
+        ("offsets", nested(|s| s.offset)),
+        ("compressedLengths", nested(|s| s.compressed_length)),
+        ("decompressedLengths", nested(|s| s.decompressed_length)),
+        (
+            "compressedBlob",
+            UnityValue::Array(
+                compressed
+                    .iter()
+                    .map(|&b| UnityValue::Integer(b as i64))
+                    .collect(),
+            ),
+        ),
+    ]));
+
+    let shader = ShaderParser::parse(&properties).unwrap();
+    assert_eq!(shader.keywords, ["_EMISSION", "_NORMALMAP"]);
+    assert_eq!(shader.compressed_blob_sizes[0], segments[..2]);
+    assert_eq!(shader.compressed_blob_sizes[1], segments[2..]);
+    assert_eq!(
+        shader.decompressed_size(),
+        parts.iter().map(|p| p.len() as u64).sum::<u64>()
+    );
+    assert_eq!(
+        ShaderParser::decompress_platform(&properties, &shader, 0).unwrap(),
+        [parts[0].to_vec(), parts[1].to_vec()]
+    );
+
+    // Lengths that disagree with the offsets are rejected.
+    let mut broken = properties.clone();
+    broken.insert("compressedLengths".to_string(), ints(&[1]));
+    assert!(ShaderParser::parse(&broken).is_err());
+}
+
+#[test]
+fn legacy_shaders_keep_name_and_sub_program_blob() {
+    let source = b"Shader \"Legacy/Diffuse\" { SubShader { Pass { } } }".repeat(4);
+    let (compressed, segments) = blob(&[source.as_slice()]);
+    let properties = fields(object(vec![
+        ("m_Name", string("Legacy/Diffuse")),
+        ("m_Script", string("Shader \"Legacy/Diffuse\" {}")),
+        ("decompressedSize", UnityValue::Integer(source.len() as i64)),
+        ("m_SubProgramBlob", UnityValue::Bytes(compressed)),
+    ]));
+
+    let shader = ShaderParser::parse(&properties).unwrap();
+    assert_eq!(shader.name, "Legacy/Diffuse");
+    assert!(shader.platforms.is_empty() && shader.keywords.is_empty());
+    assert_eq!(shader.compressed_blob_sizes, [segments]);
+    assert_eq!(
+        ShaderParser::decompress_platform(&properties, &shader, 0).unwrap(),
+        [source]
+    );
+}
@@ -0,0 +1,106 @@
+//! Split serialized files (`<name>.split0`, `.split1`, ...) read back as the unsplit file.
+
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use unity_asset_binary::asset::{
+    SerializedFile, SerializedFileParser, parse_serialized_file_from_path,
+};
+use unity_asset_binary::file::{UnityFile, load_unity_file};
+use unity_asset_binary::split::{SplitReader, split_base_path, split_parts};
+
+/// The serialized file inside the sample bundle.
+fn sample_serialized_bytes() -> Vec<u8> {
+    let path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples/char_118_yuki.ab");
+    let bundle =
+        unity_asset_binary::bundle::load_bundle_from_memory(std::fs::read(path).unwrap()).unwrap();
+    bundle
+        .nodes
+        .iter()
+        .map(|node| bundle.extract_node_data(node).unwrap())
+        .find(|data| SerializedFileParser::from_bytes(data.clone()).is_ok())
+        .expect("sample bundle holds a serialized file")
+}
+
+/// Write `data` as `<dir>/<name>.split0..N` in `chunks` roughly equal pieces.
+fn write_split(dir: &Path, name: &str, data: &[u8], chunks: usize) -> PathBuf {
+    let size = data.len().div_ceil(chunks);
+    for (index, chunk) in data.chunks(size).enumerate() {
+        std::fs::write(dir.join(format!("{name}.split{index}")), chunk).unwrap();
+    }
+    dir.join(name)
+}
+
+fn object_summary(file: &SerializedFile) -> Vec<(i64, i32, Option<String>)> {
+    file.object_handles()
+        .map(|handle| {
+            let name = handle.read().ok().and_then(|object| object.name());
+            (handle.path_id(), handle.class_id(), name)
+        })
+        .collect()
+}
+
+#[test]
+fn split_serialized_file_parses_like_the_original() {
+    let data = sample_serialized_bytes();
+    let original = SerializedFileParser::from_bytes(data.clone()).unwrap();
+    let expected = object_summary(&original);
+    assert!(!expected.is_empty());
+
+    let tmp = tempfile::tempdir().unwrap();
+    let base = write_split(tmp.path(), "sharedassets0.assets", &data, 3);
+    assert_eq!(split_parts(&base).unwrap().len(), 3);
+
+    let from_base = parse_serialized_file_from_path(&base).unwrap();
+    assert_eq!(object_summary(&from_base), expected);
+
+    let from_chunk = parse_serialized_file_from_path(base.with_extension("assets.split1")).unwrap();
+    assert_eq!(object_summary(&from_chunk), expected);
+
+    match load_unity_file(&base).unwrap() {
+        UnityFile::SerializedFile(file) => assert_eq!(object_summary(&file), expected),
+        other => panic!("expected a serialized file, got {:?}", other.kind()),
+    }
+}
+
+#[test]
+fn split_reader_reads_and_seeks_across_chunks() {
+    let data: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+    let tmp = tempfile::tempdir().unwrap();
+    let base = write_split(tmp.path(), "data.assets", &data, 3);
+
+    let mut reader = SplitReader::open(&base).unwrap();
+    assert_eq!(reader.len(), 1000);
+
+    // 334-byte chunks: read across the first boundary.
+    reader.seek(SeekFrom::Start(330)).unwrap();
+    let mut buf = [0u8; 10];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, &data[330..340]);
+
+    reader.seek(SeekFrom::End(-5)).unwrap();
+    let mut tail = Vec::new();
+    reader.read_to_end(&mut tail).unwrap();
+    assert_eq!(tail, &data[995..]);
+
+    reader.seek(SeekFrom::Current(-700)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, &data[300..310]);
+
+    assert_eq!(SplitReader::open(&base).unwrap().read_all().unwrap(), data);
+}
+
+#[test]
+fn split_parts_ignores_regular_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let plain = tmp.path().join("level0");
+    std::fs::write(&plain, b"data").unwrap();
+    assert!(split_parts(&plain).is_none());
+    assert!(split_parts(&tmp.path().join("missing.assets")).is_none());
+
+    assert_eq!(
+        split_base_path(Path::new("a/foo.assets.split12")),
+        Some(PathBuf::from("a/foo.assets"))
+    );
+    assert_eq!(split_base_path(Path::new("foo.splitx")), None);
+}
@@ -0,0 +1,82 @@
+//! `BundleLoadOptions::streaming`: assets are parsed without decompressing the whole bundle.
+//!
+//! `char_118_yuki.ab` is a UnityFS bundle with six LZ4 blocks of up to 128KB; its serialized file
+//! lives in the first block and the `.resource` node spans all six.
+
+use unity_asset_binary::bundle::{BundleLoadOptions, BundleLoader, BundleParser};
+use unity_asset_binary::error::BinaryError;
+
+const BLOCK: usize = 131072;
+
+fn sample() -> Vec<u8> {
+    std::fs::read(
+        std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/samples/char_118_yuki.ab"),
+    )
+    .unwrap()
+}
+
+#[test]
+fn streaming_load_parses_assets_from_touched_blocks_only() {
+    let eager = BundleParser::from_bytes(sample()).unwrap();
+
+    let mut loader = BundleLoader::with_options(
+        BundleLoadOptions::streaming().with_max_unityfs_block_cache_memory(Some(2 * BLOCK)),
+    );
+    let bundle = loader
+        .load_from_memory("yuki".to_string(), sample())
+        .unwrap();
+    assert_eq!(bundle.blocks.len(), 6);
+    assert_eq!(bundle.inflated_block_count(), 1);
+    assert!(bundle.data().is_empty());
+
+    assert_eq!(bundle.asset_names, eager.asset_names);
+    let (streamed, loaded) = (&bundle.assets[0], &eager.assets[0]);
+    assert_eq!(streamed.objects.len(), loaded.objects.len());
+    for (a, b) in streamed.object_handles().zip(loaded.object_handles()) {
+        assert_eq!(a.path_id(), b.path_id());
+        let (a, b) = (a.read().unwrap(), b.read().unwrap());
+        assert_eq!(a.class_id(), b.class_id());
+        assert_eq!(a.name(), b.name());
+    }
+
+    // The resource node is read block by block through the capped cache.
+    let node = bundle
+        .nodes
+        .iter()
+        .find(|n| n.name.ends_with(".resource"))
+        .unwrap();
+    let start = node.size - 1000;
+    assert_eq!(
+        bundle.extract_node_range(node, start, 1000).unwrap(),
+        eager.extract_node_range(node, start, 1000).unwrap()
+    );
+    assert_eq!(bundle.inflated_block_count(), 2);
+    assert_eq!(
+        bundle.extract_node_data(node).unwrap(),
+        eager.extract_node_data(node).unwrap()
+    );
+}
+
+#[test]
+fn streaming_respects_the_block_cache_cap() {
+    let err = BundleParser::from_bytes_with_options(
+        sample(),
+        BundleLoadOptions::streaming().with_max_unityfs_block_cache_memory(Some(BLOCK / 2)),
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, BinaryError::ResourceLimitExceeded(ref msg) if msg.contains("max_unityfs_block_cache_memory")),
+        "{err}"
+    );
+
+    // Without `streaming` the same options decompress the whole bundle up front.
+    let bundle = BundleParser::from_bytes_with_options(
+        sample(),
+        BundleLoadOptions::streaming()
+            .with_streaming(false)
+            .with_max_unityfs_block_cache_memory(Some(BLOCK / 2)),
+    )
+    .unwrap();
+    assert_eq!(bundle.data().len() as u64, bundle.size());
+    assert_eq!(bundle.inflated_block_count(), 0);
+}
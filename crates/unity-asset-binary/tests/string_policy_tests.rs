@@ -0,0 +1,148 @@
+use encoding_rs::{GBK, SHIFT_JIS};
+use unity_asset_binary::reader::{BinaryReader, ByteOrder};
+use unity_asset_binary::string_policy::{StringPolicy, name_matches};
+use unity_asset_binary::typetree::{
+    TypeTree, TypeTreeNode, TypeTreeParseMode, TypeTreeParseOptions, TypeTreeParseOutput,
+    TypeTreeSerializer,
+};
+use unity_asset_core::UnityValue;
+
+/// "テスト" in Shift-JIS.
+const SJIS_NAME: &[u8] = b"\x83\x65\x83\x58\x83\x67";
+
+fn make_tree() -> TypeTree {
+    let mut tree = TypeTree::new();
+    let mut root = TypeTreeNode::with_info("Root".to_string(), "Root".to_string(), -1);
+    root.children.push(TypeTreeNode::with_info(
+        "string".to_string(),
+        "m_Name".to_string(),
+        -1,
+    ));
+    root.children.push(TypeTreeNode::with_info(
+        "int".to_string(),
+        "m_Value".to_string(),
+        4,
+    ));
+    tree.add_node(root);
+    tree
+}
+
+fn object_bytes() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&(SJIS_NAME.len() as i32).to_le_bytes());
+    bytes.extend_from_slice(SJIS_NAME);
+    bytes.extend_from_slice(&[0, 0]);
+    bytes.extend_from_slice(&42i32.to_le_bytes());
+    bytes
+}
+
+fn parse(mode: TypeTreeParseMode, strings: StringPolicy) -> Result<TypeTreeParseOutput, String> {
+    let tree = make_tree();
+    let bytes = object_bytes();
+    let mut reader = BinaryReader::new(&bytes, ByteOrder::Little);
+    TypeTreeSerializer::new(&tree)
+        .parse_object_detailed(
+            &mut reader,
+            TypeTreeParseOptions::default()
+                .with_mode(mode)
+                .with_strings(strings),
+        )
+        .map_err(|e| e.to_string())
+}
+
+fn name_of(out: &TypeTreeParseOutput) -> &str {
+    match out.properties.get("m_Name") {
+        Some(UnityValue::String(s)) => s,
+        other => panic!("m_Name: {:?}", other),
+    }
+}
+
+#[test]
+fn utf8_strict_rejects_shift_jis() {
+    assert!(parse(TypeTreeParseMode::Strict, StringPolicy::Utf8Strict).is_err());
+
+    let out = parse(TypeTreeParseMode::Lenient, StringPolicy::Utf8Strict).unwrap();
+    assert!(!out.properties.contains_key("m_Name"));
+    assert_eq!(out.warnings.len(), 1);
+    assert!(out.raw_strings.is_empty());
+}
+
+#[test]
+fn utf8_lossy_replaces_and_keeps_raw_bytes() {
+    let out = parse(TypeTreeParseMode::Strict, StringPolicy::Utf8Lossy).unwrap();
+    assert!(name_of(&out).contains('\u{fffd}'));
+    assert_eq!(
+        out.properties.get("m_Value"),
+        Some(&UnityValue::Integer(42))
+    );
+
+    assert_eq!(out.raw_strings.len(), 1);
+    assert_eq!(out.raw_strings[0].decoded, name_of(&out));
+    assert_eq!(out.raw_strings[0].raw, SJIS_NAME);
+}
+
+#[test]
+fn try_encodings_decodes_shift_jis() {
+    let out = parse(
+        TypeTreeParseMode::Strict,
+        StringPolicy::try_encodings([SHIFT_JIS]),
+    )
+    .unwrap();
+    assert_eq!(name_of(&out), "テスト");
+    assert_eq!(
+        out.properties.get("m_Value"),
+        Some(&UnityValue::Integer(42))
+    );
+    assert_eq!(out.raw_strings.len(), 1);
+    assert_eq!(out.raw_strings[0].raw, SJIS_NAME);
+}
+
+#[test]
+fn utf8_names_are_unaffected_by_policy() {
+    let tree = make_tree();
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&4i32.to_le_bytes());
+    bytes.extend_from_slice(b"Hero");
+    bytes.extend_from_slice(&1i32.to_le_bytes());
+
+    let mut reader = BinaryReader::new(&bytes, ByteOrder::Little);
+    let out = TypeTreeSerializer::new(&tree)
+        .parse_object_detailed(
+            &mut reader,
+            TypeTreeParseOptions::default()
+                .with_mode(TypeTreeParseMode::Strict)
+                .with_strings(StringPolicy::try_encodings([SHIFT_JIS])),
+        )
+        .unwrap();
+    assert_eq!(name_of(&out), "Hero");
+    assert!(out.raw_strings.is_empty());
+}
+
+#[test]
+fn name_search_matches_decoded_and_raw_forms() {
+    let policy = StringPolicy::try_encodings([GBK, SHIFT_JIS]);
+    let out = parse(TypeTreeParseMode::Strict, policy.clone()).unwrap();
+    let decoded = name_of(&out);
+    // The bytes are also valid GBK, so the first encoding wins...
+    assert_ne!(decoded, "テスト");
+    assert!(name_matches(decoded, decoded, Some(SJIS_NAME), &policy));
+    // ...but a query typed in Japanese still finds the object through its raw bytes.
+    assert!(name_matches("テスト", decoded, Some(SJIS_NAME), &policy));
+    assert!(name_matches("スト", decoded, Some(SJIS_NAME), &policy));
+    assert!(!name_matches("ゲーム", decoded, Some(SJIS_NAME), &policy));
+
+    // Without candidate encodings only the decoded text and raw bytes themselves are searched.
+    let lossy = String::from_utf8_lossy(SJIS_NAME);
+    assert!(!name_matches(
+        "テスト",
+        &lossy,
+        Some(SJIS_NAME),
+        &StringPolicy::Utf8Lossy
+    ));
+    assert!(name_matches(
+        "HERO",
+        "hero_\u{fffd}",
+        Some(b"hero_\x83"),
+        &StringPolicy::Utf8Lossy
+    ));
+}
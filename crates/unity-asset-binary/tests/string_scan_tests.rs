@@ -0,0 +1,135 @@
+//! `TypeTreeSerializer::scan_strings`: field paths, skipped subtrees and the value length cap.
+
+use std::ops::Range;
+use unity_asset_binary::reader::{BinaryReader, ByteOrder};
+use unity_asset_binary::typetree::{StringField, TypeTree, TypeTreeNode, TypeTreeSerializer};
+
+fn node(type_name: &str, name: &str, byte_size: i32, children: Vec<TypeTreeNode>) -> TypeTreeNode {
+    let mut node = TypeTreeNode::with_info(type_name.to_string(), name.to_string(), byte_size);
+    node.children = children;
+    node
+}
+
+fn array(element: TypeTreeNode) -> TypeTreeNode {
+    node(
+        "Array",
+        "Array",
+        -1,
+        vec![node("int", "size", 4, Vec::new()), element],
+    )
+}
+
+fn string(name: &str) -> TypeTreeNode {
+    node(
+        "string",
+        name,
+        -1,
+        vec![array(node("char", "data", 1, Vec::new()))],
+    )
+}
+
+fn vector(name: &str, element: TypeTreeNode) -> TypeTreeNode {
+    node("vector", name, -1, vec![array(element)])
+}
+
+fn text_asset_tree() -> TypeTree {
+    let mut tree = TypeTree::new();
+    tree.nodes.push(node(
+        "TextAsset",
+        "Base",
+        -1,
+        vec![
+            string("m_Name"),
+            vector(
+                "m_Entries",
+                node(
+                    "Entry",
+                    "data",
+                    -1,
+                    vec![node("int", "id", 4, Vec::new()), string("label")],
+                ),
+            ),
+            vector("m_Weights", node("float", "data", 4, Vec::new())),
+            string("m_Script"),
+        ],
+    ));
+    tree
+}
+
+fn write_string(out: &mut Vec<u8>, s: &[u8]) {
+    out.extend_from_slice(&(s.len() as i32).to_le_bytes());
+    out.extend_from_slice(s);
+    while !out.len().is_multiple_of(4) {
+        out.push(0);
+    }
+}
+
+fn text_asset_bytes(script: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_string(&mut out, b"dialogue");
+    out.extend_from_slice(&2i32.to_le_bytes());
+    for (id, label) in [(7i32, "boss_phase_2"), (8, "boss_phase_3")] {
+        out.extend_from_slice(&id.to_le_bytes());
+        write_string(&mut out, label.as_bytes());
+    }
+    out.extend_from_slice(&3i32.to_le_bytes());
+    for weight in [0.5f32, 1.0, 2.0] {
+        out.extend_from_slice(&weight.to_le_bytes());
+    }
+    write_string(&mut out, script);
+    out
+}
+
+fn field(path: &str, value: &str, truncated: bool, range: Range<usize>) -> StringField {
+    StringField {
+        path: path.to_string(),
+        value: value.to_string(),
+        truncated,
+        range,
+    }
+}
+
+#[test]
+fn strings_are_collected_with_field_paths() {
+    let tree = text_asset_tree();
+    let data = text_asset_bytes(b"line one\nboss_phase_3 starts");
+    let mut reader = BinaryReader::new(&data, ByteOrder::Little);
+    let fields = TypeTreeSerializer::new(&tree)
+        .scan_strings(&mut reader, 1024)
+        .unwrap();
+
+    assert_eq!(
+        fields,
+        vec![
+            field("m_Name", "dialogue", false, 0..12),
+            field("m_Entries[0].label", "boss_phase_2", false, 20..36),
+            field("m_Entries[1].label", "boss_phase_3", false, 40..56),
+            field("m_Script", "line one\nboss_phase_3 starts", false, 72..104),
+        ]
+    );
+    assert_eq!(reader.remaining(), 0);
+}
+
+#[test]
+fn long_values_are_cut_and_decoded_lossily() {
+    let tree = text_asset_tree();
+    let mut script = b"boss".to_vec();
+    script.push(0xFF);
+    script.extend_from_slice(&[b'x'; 64]);
+    let data = text_asset_bytes(&script);
+    let mut reader = BinaryReader::new(&data, ByteOrder::Little);
+    let fields = TypeTreeSerializer::new(&tree)
+        .scan_strings(&mut reader, 8)
+        .unwrap();
+
+    assert_eq!(fields[0], field("m_Name", "dialogue", false, 0..12));
+    assert_eq!(
+        fields[1],
+        field("m_Entries[0].label", "boss_pha", true, 20..36)
+    );
+    assert_eq!(
+        fields[3],
+        field("m_Script", "boss\u{FFFD}xxx", true, 72..148)
+    );
+    assert_eq!(reader.remaining(), 0);
+}
@@ -0,0 +1,198 @@
+//! TextAsset (class 49): TypeTree and raw-layout parsing, text decoding and export.
+//!
+//! The sample bundles ship no TextAsset, so one is added to banner_1's serialized file, once
+//! with a TypeTree and once without.
+
+use unity_asset_binary::asset::{ObjectInfo, SerializedFile, SerializedType};
+use unity_asset_binary::bundle::BundleParser;
+use unity_asset_binary::object::UnityObject;
+use unity_asset_binary::text_asset::{TextAsset, TextAssetExport};
+use unity_asset_binary::typetree::{TypeTree, TypeTreeNode};
+use unity_asset_binary::unity_version::UnityVersion;
+use unity_asset_core::class_ids;
+
+const WITH_TYPETREE: i64 = 4242;
+const WITHOUT_TYPETREE: i64 = 4243;
+const JSON: &[u8] = br#"{"stage": "1-7", "waves": [3, 5, 8]}"#;
+
+fn node(type_name: &str, name: &str, byte_size: i32, children: Vec<TypeTreeNode>) -> TypeTreeNode {
+    let mut node = TypeTreeNode::with_info(type_name.to_string(), name.to_string(), byte_size);
+    node.children = children;
+    node
+}
+
+fn string(name: &str) -> TypeTreeNode {
+    let array = node(
+        "Array",
+        "Array",
+        -1,
+        vec![
+            node("int", "size", 4, Vec::new()),
+            node("char", "data", 1, Vec::new()),
+        ],
+    );
+    let mut string = node("string", name, -1, vec![array]);
+    string.meta_flags = 0x4000;
+    string
+}
+
+fn write_string(out: &mut Vec<u8>, s: &[u8]) {
+    out.extend_from_slice(&(s.len() as i32).to_le_bytes());
+    out.extend_from_slice(s);
+    while !out.len().is_multiple_of(4) {
+        out.push(0);
+    }
+}
+
+/// banner_1's serialized file plus two TextAssets named `stage` holding `script`.
+fn banner_with_text_assets(script: &[u8]) -> SerializedFile {
+    let bundle =
+        BundleParser::from_bytes(include_bytes!("../../../tests/samples/banner_1").to_vec())
+            .unwrap();
+    let mut file = bundle.assets.into_iter().next().unwrap();
+
+    let mut data = Vec::new();
+    write_string(&mut data, b"stage");
+    write_string(&mut data, script);
+
+    for (path_id, tree) in [
+        (
+            WITH_TYPETREE,
+            vec![node(
+                "TextAsset",
+                "Base",
+                -1,
+                vec![string("m_Name"), string("m_Script")],
+            )],
+        ),
+        (WITHOUT_TYPETREE, Vec::new()),
+    ] {
+        let mut ty = SerializedType::new(class_ids::TEXT_ASSET);
+        ty.type_tree = TypeTree::new();
+        ty.type_tree.nodes = tree;
+        file.types.push(ty);
+        let type_index = file.types.len() as i32 - 1;
+        let mut info = ObjectInfo::new(
+            path_id,
+            0,
+            data.len() as u32,
+            class_ids::TEXT_ASSET,
+            type_index,
+        );
+        info.data = data.clone();
+        file.objects.push(info);
+    }
+    file
+}
+
+fn read(file: &SerializedFile, path_id: i64) -> UnityObject {
+    file.find_object_handle(path_id).unwrap().read().unwrap()
+}
+
+fn version(v: &str) -> UnityVersion {
+    UnityVersion::parse_version(v).unwrap()
+}
+
+#[test]
+fn typetree_and_raw_layouts_agree() {
+    let file = banner_with_text_assets(JSON);
+    let expected = TextAsset {
+        name: "stage".to_string(),
+        script: JSON.to_vec(),
+    };
+
+    let typed = read(&file, WITH_TYPETREE);
+    assert!(typed.has_property("m_Script"));
+    assert_eq!(
+        typed.as_text_asset(&version("2018.4.4f1")).unwrap(),
+        expected
+    );
+
+    let raw = read(&file, WITHOUT_TYPETREE);
+    assert!(!raw.has_property("m_Script"));
+    assert_eq!(raw.as_text_asset(&version("2018.4.4f1")).unwrap(), expected);
+    assert_eq!(
+        TextAsset::from_binary_data(raw.raw_data(), raw.byte_order(), &version("5.6.7f1")).unwrap(),
+        expected
+    );
+
+    // Anything else is not a TextAsset.
+    let texture = read(&file, -3875358842991402074);
+    let err = texture.as_text_asset(&version("2018.4.4f1")).unwrap_err();
+    assert!(err.to_string().contains("not a TextAsset"), "{}", err);
+}
+
+#[test]
+fn binary_scripts_keep_their_bytes() {
+    let script: Vec<u8> = (0..=255u8).rev().collect();
+    let file = banner_with_text_assets(&script);
+    for path_id in [WITH_TYPETREE, WITHOUT_TYPETREE] {
+        let text_asset = read(&file, path_id)
+            .as_text_asset(&UnityVersion::default())
+            .unwrap();
+        assert_eq!(text_asset.script, script, "{}", path_id);
+        assert!(text_asset.text().is_err());
+    }
+}
+
+#[test]
+fn path_name_is_only_allowed_before_2017_1() {
+    let mut data = Vec::new();
+    write_string(&mut data, b"stage");
+    write_string(&mut data, JSON);
+    write_string(&mut data, b"Assets/Config/stage.json");
+
+    let legacy = TextAsset::from_binary_data(
+        &data,
+        unity_asset_binary::reader::ByteOrder::Little,
+        &version("5.6.7f1"),
+    )
+    .unwrap();
+    assert_eq!(legacy.script, JSON);
+
+    let err = TextAsset::from_binary_data(
+        &data,
+        unity_asset_binary::reader::ByteOrder::Little,
+        &version("2017.1.0f3"),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("after m_Script"), "{}", err);
+}
+
+#[test]
+fn text_detects_byte_order_marks() {
+    let text = "第1章: Ready?";
+    let asset = |script: Vec<u8>| TextAsset {
+        name: "dialogue".to_string(),
+        script,
+    };
+    let utf16le: Vec<u8> = [0xFF, 0xFE]
+        .into_iter()
+        .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+        .collect();
+    let utf16be: Vec<u8> = [0xFE, 0xFF]
+        .into_iter()
+        .chain(text.encode_utf16().flat_map(u16::to_be_bytes))
+        .collect();
+    let utf8_bom: Vec<u8> = [0xEF, 0xBB, 0xBF].into_iter().chain(text.bytes()).collect();
+
+    for script in [text.as_bytes().to_vec(), utf8_bom, utf16le.clone(), utf16be] {
+        assert_eq!(asset(script).text().unwrap(), text);
+    }
+    // Unpaired surrogate.
+    assert!(asset(vec![0xFF, 0xFE, 0x00, 0xD8]).text().is_err());
+
+    let dir = tempfile::tempdir().unwrap();
+    let utf16 = asset(utf16le.clone());
+    utf16
+        .export(dir.path().join("raw.txt"), TextAssetExport::Raw)
+        .unwrap();
+    utf16
+        .export(dir.path().join("text.txt"), TextAssetExport::Text)
+        .unwrap();
+    assert_eq!(std::fs::read(dir.path().join("raw.txt")).unwrap(), utf16le);
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("text.txt")).unwrap(),
+        text
+    );
+}
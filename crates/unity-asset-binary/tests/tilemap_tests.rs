@@ -0,0 +1,100 @@
+//! Tilemap and Grid parsing against typetree dumps of a 2019+ and a 2017 Tilemap.
+//!
+//! The 2019 layer stores `m_Tiles` as `[key, value]` pairs with `UInt16` indices; the 2017
+//! layer uses `{first, second}` entries with `SInt32` indices and `-1` for "none".
+
+use indexmap::IndexMap;
+use unity_asset_binary::test_support::{object_properties, read_fixture};
+use unity_asset_binary::tilemap::{CellLayout, Grid, Tilemap};
+use unity_asset_binary::unity_objects::ObjectRef;
+use unity_asset_core::UnityValue;
+
+fn fixture(key: &str) -> IndexMap<String, UnityValue> {
+    object_properties(&read_fixture("tilemap_two_layers.json"), key)
+}
+
+#[test]
+fn tilemap_2019_resolves_cells_through_shared_tables() {
+    let tilemap = Tilemap::from_typetree(&fixture("Tilemap2019")).unwrap();
+
+    assert_eq!(tilemap.cell_count(), 5);
+    assert_eq!(tilemap.origin, [-1, -1, 0]);
+    assert_eq!(tilemap.size, [5, 4, 1]);
+    assert_eq!(tilemap.game_object, Some(ObjectRef::new(0, 10)));
+    assert_eq!(tilemap.used_bounds(), Some(([0, 0, 0], [2, 1, 0])));
+
+    let flipped = tilemap.cell([1, 0, 0]).unwrap();
+    assert_eq!(flipped.tile, Some(ObjectRef::new(0, 500)));
+    assert_eq!(flipped.sprite, Some(ObjectRef::new(0, 600)));
+    assert_eq!(flipped.flips(), (true, false));
+
+    let tinted = tilemap.cell([2, 0, 0]).unwrap();
+    assert_eq!(tinted.tile_index, Some(1));
+    assert_eq!(tinted.color, [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(tinted.flags, 1);
+
+    // 0xFFFF sprite index: the tile has no sprite.
+    assert_eq!(tilemap.cell([2, 1, 0]).unwrap().sprite, None);
+    assert!(tilemap.cell([1, 1, 0]).is_none());
+}
+
+#[test]
+fn tilemap_2017_accepts_first_second_entries() {
+    let tilemap = Tilemap::from_typetree(&fixture("Tilemap2017")).unwrap();
+
+    assert_eq!(tilemap.cell_count(), 3);
+    assert_eq!(tilemap.used_bounds(), Some(([-1, 2, 0], [1, 2, 0])));
+    assert_eq!(
+        tilemap.cell([-1, 2, 0]).unwrap().tile,
+        Some(ObjectRef::new(0, 700))
+    );
+
+    let erased = tilemap.cell([1, 2, 0]).unwrap();
+    assert_eq!(
+        (erased.tile_index, erased.tile, erased.sprite),
+        (None, None, None)
+    );
+    assert_eq!(erased.color, [1.0; 4]);
+}
+
+#[test]
+fn tilemap_exports_index_grid() {
+    let tilemap = Tilemap::from_typetree(&fixture("Tilemap2019")).unwrap();
+
+    assert_eq!(tilemap.to_csv(0), "1,-1,1\n0,0,1\n");
+    assert_eq!(tilemap.to_csv(1), "-1,-1,-1\n-1,-1,-1\n");
+
+    let json = tilemap.grid_json(0);
+    assert_eq!(json["width"], 3);
+    assert_eq!(json["height"], 2);
+    assert_eq!(json["tiles"].as_array().unwrap().len(), 2);
+    assert_eq!(json["rows"][0], serde_json::json!([1, null, 1]));
+
+    let legacy = Tilemap::from_typetree(&fixture("Tilemap2017")).unwrap();
+    assert_eq!(legacy.to_csv(0), "0,0,-1\n");
+}
+
+#[test]
+fn tilemap_json_round_trips_cells() {
+    let tilemap = Tilemap::from_typetree(&fixture("Tilemap2019")).unwrap();
+    let json = tilemap.to_json();
+
+    assert_eq!(json["cells"].as_array().unwrap().len(), 5);
+    let back: Tilemap = serde_json::from_value(json).unwrap();
+    assert_eq!(back, tilemap);
+}
+
+#[test]
+fn grid_reads_cell_geometry() {
+    let grid = Grid::from_typetree(&fixture("Grid")).unwrap();
+
+    assert_eq!(grid.cell_size, [1.0, 0.5, 1.0]);
+    assert_eq!(grid.cell_gap, [0.0; 3]);
+    assert_eq!(grid.cell_layout, CellLayout::Isometric);
+    assert_eq!(grid.cell_swizzle, 0);
+}
+
+#[test]
+fn tilemap_without_tiles_is_rejected() {
+    assert!(Tilemap::from_typetree(&fixture("Grid")).is_err());
+}
@@ -0,0 +1,266 @@
+//! TypeTree diffs: rename detection, moves, nested paths, rendering, and file-level comparison.
+
+use unity_asset_binary::file::{UnityFile, load_unity_file_from_memory};
+use unity_asset_binary::typetree::{FieldChange, TypeTree, TypeTreeNode, diff, diff_files};
+
+const BANNER: &[u8] = include_bytes!("samples/banner_1");
+
+fn node(type_name: &str, name: &str, byte_size: i32, children: Vec<TypeTreeNode>) -> TypeTreeNode {
+    let mut node = TypeTreeNode::with_info(type_name.to_string(), name.to_string(), byte_size);
+    node.children = children;
+    node
+}
+
+fn field(type_name: &str, name: &str) -> TypeTreeNode {
+    let size = match type_name {
+        "SInt64" | "double" => 8,
+        _ => 4,
+    };
+    node(type_name, name, size, Vec::new())
+}
+
+fn tree(fields: Vec<TypeTreeNode>) -> TypeTree {
+    let mut tree = TypeTree::new();
+    tree.nodes.push(node("MonoBehaviour", "Base", -1, fields));
+    tree
+}
+
+fn added(path: &str, type_name: &str, byte_size: i32) -> FieldChange {
+    FieldChange::Added {
+        path: path.to_string(),
+        type_name: type_name.to_string(),
+        byte_size,
+    }
+}
+
+fn removed(path: &str, type_name: &str, byte_size: i32) -> FieldChange {
+    FieldChange::Removed {
+        path: path.to_string(),
+        type_name: type_name.to_string(),
+        byte_size,
+    }
+}
+
+fn renamed(path: &str, new_path: &str, type_name: &str, byte_size: i32) -> FieldChange {
+    FieldChange::Renamed {
+        path: path.to_string(),
+        new_path: new_path.to_string(),
+        type_name: type_name.to_string(),
+        byte_size,
+    }
+}
+
+#[test]
+fn identical_trees_have_no_changes() {
+    let a = tree(vec![field("int", "hp"), field("float", "speed")]);
+    assert!(diff(&a, &a.clone()).is_empty());
+}
+
+#[test]
+fn same_type_at_same_position_is_a_rename() {
+    let a = tree(vec![
+        field("int", "id"),
+        field("int", "hp"),
+        field("float", "speed"),
+    ]);
+    let b = tree(vec![
+        field("int", "id"),
+        field("int", "health"),
+        field("float", "speed"),
+    ]);
+    assert_eq!(
+        diff(&a, &b).changes,
+        vec![renamed("hp", "health", "int", 4)]
+    );
+}
+
+#[test]
+fn same_type_at_another_position_is_remove_and_add() {
+    // `hp` went away and `armor` was appended; both are ints, but not in the same slot.
+    let a = tree(vec![
+        field("int", "id"),
+        field("int", "hp"),
+        field("float", "speed"),
+    ]);
+    let b = tree(vec![
+        field("int", "id"),
+        field("float", "speed"),
+        field("int", "armor"),
+    ]);
+    assert_eq!(
+        diff(&a, &b).changes,
+        vec![removed("hp", "int", 4), added("armor", "int", 4)]
+    );
+}
+
+#[test]
+fn different_type_or_size_in_the_same_slot_is_remove_and_add() {
+    let a = tree(vec![field("int", "id"), field("int", "hp")]);
+    let b = tree(vec![field("int", "id"), field("float", "health")]);
+    assert_eq!(
+        diff(&a, &b).changes,
+        vec![removed("hp", "int", 4), added("health", "float", 4)]
+    );
+
+    let b = tree(vec![
+        field("int", "id"),
+        node("int", "health", 8, Vec::new()),
+    ]);
+    assert_eq!(
+        diff(&a, &b).changes,
+        vec![removed("hp", "int", 4), added("health", "int", 8)]
+    );
+}
+
+#[test]
+fn one_removed_and_two_added_in_a_slot_pairs_the_first() {
+    let a = tree(vec![
+        field("int", "id"),
+        field("int", "hp"),
+        field("float", "speed"),
+    ]);
+    let b = tree(vec![
+        field("int", "id"),
+        field("int", "health"),
+        field("int", "shield"),
+        field("float", "speed"),
+    ]);
+    assert_eq!(
+        diff(&a, &b).changes,
+        vec![renamed("hp", "health", "int", 4), added("shield", "int", 4)]
+    );
+}
+
+#[test]
+fn insertion_before_a_rename_does_not_hide_it() {
+    let a = tree(vec![field("int", "id"), field("int", "hp")]);
+    let b = tree(vec![
+        field("float", "scale"),
+        field("int", "id"),
+        field("int", "health"),
+    ]);
+    assert_eq!(
+        diff(&a, &b).changes,
+        vec![
+            added("scale", "float", 4),
+            renamed("hp", "health", "int", 4)
+        ]
+    );
+}
+
+#[test]
+fn reordered_siblings_report_the_moved_field() {
+    let a = tree(vec![
+        field("int", "x"),
+        field("int", "y"),
+        field("int", "z"),
+    ]);
+    let b = tree(vec![
+        field("int", "z"),
+        field("int", "x"),
+        field("int", "y"),
+    ]);
+    assert_eq!(
+        diff(&a, &b).changes,
+        vec![FieldChange::Moved {
+            path: "z".to_string(),
+            old_index: 2,
+            new_index: 0,
+        }]
+    );
+}
+
+#[test]
+fn nested_changes_are_keyed_by_full_path() {
+    let stats = |count: TypeTreeNode, size: i32| {
+        node(
+            "Stats",
+            "stats",
+            size,
+            vec![node(
+                "Item",
+                "reward",
+                size,
+                vec![field("int", "itemId"), count],
+            )],
+        )
+    };
+    let a = tree(vec![stats(field("int", "count"), 8)]);
+    let b = tree(vec![stats(field("SInt64", "count"), 12)]);
+
+    let result = diff(&a, &b);
+    assert_eq!(
+        result.changes,
+        vec![
+            FieldChange::SizeChanged {
+                path: "stats".to_string(),
+                old_size: 8,
+                new_size: 12,
+            },
+            FieldChange::SizeChanged {
+                path: "stats.reward".to_string(),
+                old_size: 8,
+                new_size: 12,
+            },
+            FieldChange::TypeChanged {
+                path: "stats.reward.count".to_string(),
+                old_type: "int".to_string(),
+                new_type: "SInt64".to_string(),
+            },
+        ]
+    );
+
+    assert_eq!(
+        result.to_text("v1", "v2"),
+        "--- v1\n+++ v2\n\
+         ~ stats: size 8 -> 12\n\
+         ~ stats.reward: size 8 -> 12\n\
+         ~ stats.reward.count: type int -> SInt64\n"
+    );
+    let json = result.to_json();
+    assert_eq!(json["changes"][2]["kind"], "type_changed");
+    assert_eq!(json["changes"][2]["path"], "stats.reward.count");
+}
+
+#[test]
+fn files_compare_shared_types() {
+    let load = || {
+        let UnityFile::AssetBundle(bundle) = load_unity_file_from_memory(BANNER.to_vec()).unwrap()
+        else {
+            panic!("banner_1 is an AssetBundle");
+        };
+        bundle
+    };
+    let old = load();
+    let mut new = load();
+    let old_file = &old.assets[0];
+    let new_file = &mut new.assets[0];
+    assert!(diff_files(old_file, new_file).is_empty());
+
+    let index = new_file
+        .types
+        .iter()
+        .position(|t| !t.type_tree.is_empty())
+        .unwrap();
+    let class_id = new_file.types[index].class_id;
+    new_file.types[index].type_tree.nodes[0]
+        .children
+        .push(field("int", "m_Extra"));
+    let dropped = new_file.types.pop().unwrap();
+
+    let result = diff_files(old_file, new_file);
+    assert_eq!(result.changed.len(), 1);
+    assert_eq!(result.changed[0].ty.class_id, class_id);
+    assert_eq!(
+        result.changed[0].diff.changes,
+        vec![added("m_Extra", "int", 4)]
+    );
+    assert_eq!(result.removed.len(), 1);
+    assert_eq!(result.removed[0].class_id, dropped.class_id);
+    assert!(result.added.is_empty());
+
+    let text = result.to_text("old", "new");
+    assert!(text.contains(&format!("- type {}\n", result.removed[0])));
+    assert!(text.contains("+ m_Extra: int (4)\n"));
+    assert_eq!(result.to_json()["changed"][0]["class_id"], class_id);
+}
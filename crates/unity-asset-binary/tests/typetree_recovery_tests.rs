@@ -0,0 +1,96 @@
+use unity_asset_binary::reader::{BinaryReader, ByteOrder};
+use unity_asset_binary::typetree::{
+    TypeTree, TypeTreeNode, TypeTreeParseMode, TypeTreeParseOptions, TypeTreeSerializer,
+};
+
+/// `int m_A; string m_Name; int m_B;`, with `m_Name` declared as `name_byte_size` bytes.
+fn make_tree(name_byte_size: i32) -> TypeTree {
+    let mut tree = TypeTree::new();
+    let mut root = TypeTreeNode::with_info("Root".to_string(), "Root".to_string(), -1);
+    root.children.push(TypeTreeNode::with_info(
+        "int".to_string(),
+        "m_A".to_string(),
+        4,
+    ));
+    root.children.push(TypeTreeNode::with_info(
+        "string".to_string(),
+        "m_Name".to_string(),
+        name_byte_size,
+    ));
+    root.children.push(TypeTreeNode::with_info(
+        "int".to_string(),
+        "m_B".to_string(),
+        4,
+    ));
+    tree.add_node(root);
+    tree
+}
+
+/// Object bytes with a bogus string length in place of `m_Name`'s 4-byte payload.
+fn corrupted_bytes() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&7i32.to_le_bytes());
+    bytes.extend_from_slice(&0x7fff_0000i32.to_le_bytes());
+    bytes.extend_from_slice(b"abcd");
+    bytes.extend_from_slice(&9i32.to_le_bytes());
+    bytes
+}
+
+fn lenient() -> TypeTreeParseOptions {
+    TypeTreeParseOptions::default().with_mode(TypeTreeParseMode::Lenient)
+}
+
+#[test]
+fn lenient_parse_keeps_fields_before_corrupted_string() {
+    let tree = make_tree(-1);
+    let serializer = TypeTreeSerializer::new(&tree);
+    let bytes = corrupted_bytes();
+
+    let mut reader = BinaryReader::new(&bytes, ByteOrder::Little);
+    let out = serializer
+        .parse_object_detailed(&mut reader, lenient())
+        .unwrap();
+
+    assert!(out.partial);
+    assert_eq!(out.properties.get("m_A").and_then(|v| v.as_i64()), Some(7));
+    assert!(!out.properties.contains_key("m_Name"));
+    // Variable-size field: later offsets are unknown, so nothing after it is read.
+    assert!(!out.properties.contains_key("m_B"));
+
+    assert_eq!(out.warnings.len(), 1);
+    assert_eq!(out.warnings[0].field, "m_Name");
+    assert_eq!(out.warnings[0].offset, 4);
+}
+
+#[test]
+fn lenient_parse_skips_failing_field_with_known_byte_size() {
+    let tree = make_tree(8);
+    let serializer = TypeTreeSerializer::new(&tree);
+    let bytes = corrupted_bytes();
+
+    let mut reader = BinaryReader::new(&bytes, ByteOrder::Little);
+    let out = serializer
+        .parse_object_detailed(&mut reader, lenient())
+        .unwrap();
+
+    assert!(!out.partial);
+    assert_eq!(out.properties.get("m_A").and_then(|v| v.as_i64()), Some(7));
+    assert_eq!(out.properties.get("m_B").and_then(|v| v.as_i64()), Some(9));
+    assert_eq!(out.warnings.len(), 1);
+    assert_eq!(out.warnings[0].field, "m_Name");
+    assert_eq!(reader.position(), bytes.len() as u64);
+}
+
+#[test]
+fn strict_parse_fails_on_corrupted_string() {
+    let tree = make_tree(-1);
+    let serializer = TypeTreeSerializer::new(&tree);
+    let bytes = corrupted_bytes();
+
+    let mut reader = BinaryReader::new(&bytes, ByteOrder::Little);
+    let result = serializer.parse_object_detailed(
+        &mut reader,
+        TypeTreeParseOptions::default().with_mode(TypeTreeParseMode::Strict),
+    );
+    assert!(result.is_err());
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use unity_asset_binary::file::{UnityFile, load_unity_file};
-use unity_asset_binary::typetree::InMemoryTypeTreeRegistry;
+use unity_asset_binary::typetree::{InMemoryTypeTreeRegistry, TypeTreeDatabase, TypeTreeRegistry};
 
 #[test]
 fn registry_can_restore_typetree_parsing_when_stripped() {
@@ -42,3 +42,49 @@ fn registry_can_restore_typetree_parsing_when_stripped() {
     assert_eq!(obj.get("m_Width").and_then(|v| v.as_i64()), Some(492));
     assert_eq!(obj.get("m_Height").and_then(|v| v.as_i64()), Some(180));
 }
+
+#[test]
+fn harvested_database_parses_a_stripped_copy() {
+    let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/samples/banner_1");
+    let load = || match load_unity_file(&path).expect("load sample bundle") {
+        UnityFile::AssetBundle(b) => b,
+        other => panic!("expected AssetBundle, got {:?}", other.kind()),
+    };
+
+    let original = load();
+    let source = &original.assets[0];
+    let harvested = TypeTreeDatabase::collect_from(source);
+    assert_eq!(harvested.len(), source.types.len());
+    assert!(harvested.collisions().is_empty());
+
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("banner.ttdb");
+    harvested.save(&db_path).unwrap();
+    let database = TypeTreeDatabase::load(&db_path).unwrap();
+    assert_eq!(database.len(), harvested.len());
+
+    let mut stripped = load();
+    let file = &mut stripped.assets[0];
+    file.enable_type_tree = false;
+    for t in file.types.iter_mut() {
+        t.type_tree.clear();
+    }
+    assert!(TypeTreeDatabase::collect_from(file).is_empty());
+    let texture = file.types.iter().find(|t| t.class_id == 28).unwrap();
+    assert!(
+        database
+            .resolve_hashed("0.0.0", 28, texture.script_id, texture.old_type_hash)
+            .is_some()
+    );
+    file.set_type_tree_registry(Some(Arc::new(database)));
+
+    for handle in source.object_handles() {
+        let expected = handle.read().expect("read with embedded TypeTree");
+        let restored = file
+            .find_object_handle(handle.path_id())
+            .unwrap()
+            .read()
+            .expect("read with harvested TypeTree");
+        assert_eq!(restored.class.properties(), expected.class.properties());
+    }
+}
@@ -0,0 +1,143 @@
+//! `UnityVersion::infer` on fixtures whose version strings were stripped.
+
+use unity_asset_binary::asset::{SerializedFile, SerializedFileParser, SerializedType};
+use unity_asset_binary::bundle::{AssetBundle, BundleLoadOptions, BundleParser};
+use unity_asset_binary::typetree::TypeTreeNode;
+use unity_asset_binary::unity_version::{InferVersion, UnityVersion, is_stripped_version};
+
+fn bundle(name: &str) -> AssetBundle {
+    let path = format!("{}/tests/samples/{}", env!("CARGO_MANIFEST_DIR"), name);
+    BundleParser::from_bytes(std::fs::read(path).unwrap()).unwrap()
+}
+
+fn serialized_file(bundle: &AssetBundle) -> SerializedFile {
+    let node = bundle
+        .nodes
+        .iter()
+        .find(|n| n.is_file() && !n.name.ends_with(".resS") && !n.name.ends_with(".resource"))
+        .unwrap();
+    SerializedFileParser::from_bytes(bundle.extract_node_data(node).unwrap()).unwrap()
+}
+
+fn version(s: &str) -> UnityVersion {
+    UnityVersion::parse_version(s).unwrap()
+}
+
+#[test]
+fn stripped_fixtures_infer_ranges_containing_the_truth() {
+    // (sample, truth, release line of the best guess)
+    let cases = [
+        ("banner_1", "2018.4.4f1", (2018, 4)),
+        ("xinzexi_2_n_tex", "2017.4.37f1", (2017, 4)),
+        ("char_118_yuki.ab", "5.6.7f1", (5, 6)),
+    ];
+    for (sample, truth, (major, minor)) in cases {
+        let mut file = serialized_file(&bundle(sample));
+        assert_eq!(file.unity_version, truth);
+        assert_eq!(UnityVersion::infer(&file).best, version(truth));
+
+        for stripped in ["0.0.0", "", "\u{1}garbage"] {
+            file.unity_version = stripped.to_string();
+            assert!(is_stripped_version(stripped));
+            let inferred = UnityVersion::infer(&file);
+            assert!(
+                inferred.contains(&version(truth)),
+                "{sample}: {truth} outside {inferred:?}"
+            );
+            assert_eq!((inferred.best.major, inferred.best.minor), (major, minor));
+            assert!(inferred.confidence > 0.0 && inferred.confidence < 1.0);
+            assert!(
+                inferred
+                    .evidence
+                    .iter()
+                    .any(|e| e == "SerializedFile format 17")
+            );
+        }
+    }
+
+    // Texture2D field layouts separate the two 2017.3+ fixtures.
+    let file = serialized_file(&bundle("xinzexi_2_n_tex"));
+    let inferred = UnityVersion::infer(&file);
+    assert_eq!(inferred.min, Some(version("2017.3.0a0")));
+    assert_eq!(inferred.max, Some(version("2018.2.0a0")));
+    assert!(!inferred.contains(&version("2018.4.4f1")));
+}
+
+#[test]
+fn bundle_header_and_overrides_apply_the_inference() {
+    let bundle = bundle("banner_1");
+    let mut header = bundle.header.clone();
+    header.unity_revision = "0.0.0".to_string();
+    let mut file = serialized_file(&bundle);
+    file.unity_version = "0.0.0".to_string();
+
+    let inferred = UnityVersion::infer_in_bundle(&file, &header);
+    assert!(inferred.contains(&version("2018.4.4f1")));
+    assert!(inferred.evidence.iter().any(|e| e == "UnityFS bundle"));
+    // An intact header revision wins over the structural guess.
+    let declared = UnityVersion::infer_in_bundle(&file, &bundle.header);
+    assert_eq!(declared.best, version("2018.4.4f1"));
+    assert_eq!(declared.confidence, 1.0);
+
+    let applied = file.apply_inferred_version().unwrap();
+    assert_eq!(file.unity_version, applied.best.to_string());
+    assert!(file.apply_inferred_version().is_none());
+    for handle in file.object_handles() {
+        handle.read().unwrap();
+    }
+
+    let data = std::fs::read(format!(
+        "{}/tests/samples/banner_1",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .unwrap();
+    let overridden = BundleParser::from_bytes_with_options(
+        data,
+        BundleLoadOptions::default().with_unity_version_override(Some(applied.best.clone())),
+    )
+    .unwrap();
+    assert!(
+        overridden
+            .assets
+            .iter()
+            .all(|asset| asset.unity_version == applied.best.to_string())
+    );
+}
+
+#[test]
+fn gameobject_component_layout_bounds_the_version() {
+    let mut file = serialized_file(&bundle("banner_1"));
+    file.unity_version = "0.0.0".to_string();
+    file.header.version = 15;
+    file.types.retain(|t| t.class_id == 1);
+
+    let mut data = TypeTreeNode::with_info("pair".to_string(), "data".to_string(), -1);
+    data.children.push(TypeTreeNode::with_info(
+        "int".to_string(),
+        "first".to_string(),
+        4,
+    ));
+    let mut array = TypeTreeNode::with_info("Array".to_string(), "Array".to_string(), -1);
+    array.children.push(data);
+    let mut components =
+        TypeTreeNode::with_info("vector".to_string(), "m_Component".to_string(), -1);
+    components.children.push(array);
+    let mut root = TypeTreeNode::with_info("GameObject".to_string(), "Base".to_string(), -1);
+    root.children.push(components);
+    let mut game_object = SerializedType::new(1);
+    game_object.type_tree.add_node(root);
+    file.types.push(game_object);
+
+    let inferred = UnityVersion::infer(&file);
+    assert_eq!(inferred.min, Some(version("5.0.0a0")));
+    assert_eq!(inferred.max, Some(version("5.5.0a0")));
+    assert!(inferred.contains(&version("5.4.6f3")));
+    assert_eq!(inferred.best, version("5.0.0f4"));
+
+    let element = &mut file.types[0].type_tree.nodes[0].children[0].children[0].children[0];
+    element.type_name = "ComponentPair".to_string();
+    // Format 15 predates ComponentPairs, so the clue is recorded but ignored.
+    let inferred = UnityVersion::infer(&file);
+    assert_eq!(inferred.max, Some(version("5.5.0a0")));
+    assert!(inferred.evidence.iter().any(|e| e.contains("ignored")));
+}
@@ -115,6 +115,7 @@ fn class_id_name(class_id: i32) -> Option<&'static str> {
         9 => Some("GameManager"),
         11 => Some("AudioManager"),
         13 => Some("InputManager"),
+        78 => Some("TagManager"),
 
         // Rendering
         20 => Some("Camera"),
@@ -139,14 +140,23 @@ fn class_id_name(class_id: i32) -> Option<&'static str> {
         114 => Some("MonoBehaviour"),
         115 => Some("MonoScript"),
         128 => Some("Font"),
+        137 => Some("SkinnedMeshRenderer"),
         142 => Some("AssetBundle"),
         152 => Some("MovieTexture"),
+        157 => Some("LightmapSettings"),
         184 => Some("RenderTexture"),
         212 => Some("SpriteRenderer"),
         213 => Some("Sprite"),
+        238 => Some("NavMeshData"),
+        241 => Some("AudioMixerController"),
+        243 => Some("AudioMixerGroupController"),
+        244 => Some("AudioMixerEffectController"),
+        245 => Some("AudioMixerSnapshotController"),
+        290 => Some("AssetBundleManifest"),
         328 => Some("VideoPlayer"),
         329 => Some("VideoClip"),
         1001 => Some("PrefabInstance"),
+        1120 => Some("LightingDataAsset"),
 
         // Physics
         50 => Some("Rigidbody2D"),
@@ -154,7 +164,9 @@ fn class_id_name(class_id: i32) -> Option<&'static str> {
         56 => Some("Collider"),
 
         // Editor / additional types
+        156049354 => Some("Grid"),
         687078895 => Some("SpriteAtlas"),
+        1839735485 => Some("Tilemap"),
 
         _ => None,
     }
@@ -179,23 +191,46 @@ pub mod class_ids {
     pub const BEHAVIOUR: i32 = 8;
     pub const TRANSFORM: i32 = 4;
     pub const CAMERA: i32 = 20;
+    pub const GRAPHICS_SETTINGS: i32 = 30;
     pub const MATERIAL: i32 = 21;
     pub const MESH_RENDERER: i32 = 23;
     pub const TEXTURE_2D: i32 = 28;
+    pub const MESH_FILTER: i32 = 33;
     pub const MESH: i32 = 43;
     pub const SHADER: i32 = 48;
     pub const TEXTURE: i32 = 27;
     pub const TEXT_ASSET: i32 = 49;
     pub const ANIMATION_CLIP: i32 = 74;
+    pub const TAG_MANAGER: i32 = 78;
     pub const AUDIO_CLIP: i32 = 83;
+    pub const AVATAR: i32 = 90;
     pub const ANIMATOR_CONTROLLER: i32 = 91;
+    pub const LIGHT: i32 = 108;
+    pub const FONT: i32 = 128;
     pub const MONO_BEHAVIOUR: i32 = 114;
     pub const MONO_SCRIPT: i32 = 115;
+    pub const TEXTURE_3D: i32 = 117;
+    pub const SKINNED_MESH_RENDERER: i32 = 137;
+    pub const BUILD_SETTINGS: i32 = 141;
     pub const ASSET_BUNDLE: i32 = 142;
+    pub const LIGHTMAP_SETTINGS: i32 = 157;
+    pub const PARTICLE_SYSTEM: i32 = 198;
+    pub const PARTICLE_SYSTEM_RENDERER: i32 = 199;
+    pub const SHADER_VARIANT_COLLECTION: i32 = 200;
     pub const SPRITE_RENDERER: i32 = 212;
     pub const SPRITE: i32 = 213;
+    pub const RECT_TRANSFORM: i32 = 224;
+    pub const NAV_MESH_DATA: i32 = 238;
+    pub const AUDIO_MIXER_CONTROLLER: i32 = 241;
+    pub const AUDIO_MIXER_GROUP_CONTROLLER: i32 = 243;
+    pub const AUDIO_MIXER_EFFECT_CONTROLLER: i32 = 244;
+    pub const AUDIO_MIXER_SNAPSHOT_CONTROLLER: i32 = 245;
+    pub const ASSET_BUNDLE_MANIFEST: i32 = 290;
     pub const PREFAB_INSTANCE: i32 = 1001;
+    pub const LIGHTING_DATA_ASSET: i32 = 1120;
+    pub const GRID: i32 = 156049354;
     pub const SPRITE_ATLAS: i32 = 687078895;
+    pub const TILEMAP: i32 = 1839735485;
 }
 
 /// Common Unity class names
@@ -205,6 +240,7 @@ pub mod class_names {
     pub const COMPONENT: &str = "Component";
     pub const TRANSFORM: &str = "Transform";
     pub const CAMERA: &str = "Camera";
+    pub const LIGHT: &str = "Light";
     pub const MATERIAL: &str = "Material";
     pub const MESH_RENDERER: &str = "MeshRenderer";
     pub const TEXTURE_2D: &str = "Texture2D";
@@ -53,6 +53,22 @@ pub enum UnityAssetError {
     #[error("Parse error: {message}")]
     Parse { message: String },
 
+    /// Binary parser errors (`unity_asset_binary::BinaryError`), kept as the `source()`.
+    #[error("Binary error: {message}")]
+    Binary {
+        message: String,
+        #[source]
+        source: Box<dyn StdError + Send + Sync + 'static>,
+    },
+
+    /// YAML syntax errors from the underlying YAML parser, kept as the `source()`.
+    #[error("YAML parsing error: {message}")]
+    Yaml {
+        message: String,
+        #[source]
+        source: Box<dyn StdError + Send + Sync + 'static>,
+    },
+
     /// Wrap an underlying error while preserving its type as a `source()`.
     ///
     /// This is used by higher-level crates (e.g. environment) to add context without losing
@@ -122,6 +138,30 @@ impl UnityAssetError {
         }
     }
 
+    /// Create a binary error from the binary crate's error type.
+    ///
+    /// `unity-asset-binary` implements `From<BinaryError>` with this, so `?` converts directly.
+    pub fn binary<E>(source: E) -> Self
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        Self::Binary {
+            message: source.to_string(),
+            source: Box::new(source),
+        }
+    }
+
+    /// Create a YAML error from the YAML parser's error type.
+    pub fn yaml<E>(source: E) -> Self
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        Self::Yaml {
+            message: source.to_string(),
+            source: Box::new(source),
+        }
+    }
+
     /// Create a contextual error while preserving the underlying `source` error.
     pub fn with_source<M, E>(message: M, source: E) -> Self
     where
@@ -135,6 +175,13 @@ impl UnityAssetError {
     }
 }
 
+#[cfg(feature = "yaml")]
+impl From<serde_yaml::Error> for UnityAssetError {
+    fn from(e: serde_yaml::Error) -> Self {
+        Self::yaml(e)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,4 +199,16 @@ mod tests {
         assert!(msg.contains("m_Name"));
         assert!(msg.contains("GameObject"));
     }
+
+    #[test]
+    fn test_binary_error_keeps_source() {
+        let io = io::Error::new(io::ErrorKind::UnexpectedEof, "truncated header");
+        let err = UnityAssetError::binary(io);
+        assert!(matches!(err, UnityAssetError::Binary { .. }));
+        assert_eq!(err.to_string(), "Binary error: truncated header");
+
+        let source = err.source().expect("source");
+        let io = source.downcast_ref::<io::Error>().expect("io source");
+        assert_eq!(io.kind(), io::ErrorKind::UnexpectedEof);
+    }
 }
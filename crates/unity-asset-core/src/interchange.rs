@@ -0,0 +1,281 @@
+//! Binary interchange formats (MessagePack and CBOR) for parsed objects.
+//!
+//! Anything that implements `Serialize` can be encoded: a [`UnityValue`](crate::UnityValue), a
+//! [`UnityClass`](crate::UnityClass) or a bare property map. Compared to JSON:
+//!
+//! - `UnityValue::Bytes` is written as a native binary string (MessagePack `bin`, CBOR major
+//!   type 2) instead of an array of numbers.
+//! - Property maps keep their `IndexMap` order.
+//! - Floats stay floats: MessagePack always writes `float64`; CBOR uses the shortest encoding that
+//!   round-trips the exact value.
+//! - Structs are written as maps keyed by field name, so readers don't depend on field order.
+//!
+//! [`RecordWriter`] streams one object per record for whole-file dumps. A MessagePack stream is
+//! a plain concatenation of values; a CBOR stream is a CBOR sequence (RFC 8742).
+
+use crate::error::{Result, UnityAssetError};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::io::{Read, Write};
+
+/// A binary interchange encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterchangeFormat {
+    MessagePack,
+    Cbor,
+}
+
+impl InterchangeFormat {
+    /// Parse a format name (`msgpack`/`messagepack`, `cbor`), case-insensitively.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "msgpack" | "messagepack" => Some(Self::MessagePack),
+            "cbor" => Some(Self::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Conventional file extension, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::MessagePack => "msgpack",
+            Self::Cbor => "cbor",
+        }
+    }
+}
+
+/// Encode `value` as MessagePack.
+pub fn to_msgpack<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    rmp_serde::to_vec_named(value)
+        .map_err(|e| UnityAssetError::with_source("MessagePack encoding failed", e))
+}
+
+/// Decode a MessagePack value.
+pub fn from_msgpack<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    rmp_serde::from_slice(bytes)
+        .map_err(|e| UnityAssetError::with_source("MessagePack decoding failed", e))
+}
+
+/// Encode `value` as CBOR.
+pub fn to_cbor<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    ciborium::into_writer(value, &mut out)
+        .map_err(|e| UnityAssetError::with_source("CBOR encoding failed", e))?;
+    Ok(out)
+}
+
+/// Decode a CBOR value.
+pub fn from_cbor<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    ciborium::from_reader(bytes)
+        .map_err(|e| UnityAssetError::with_source("CBOR decoding failed", e))
+}
+
+/// Writes a stream of records, one encoded value each.
+pub struct RecordWriter<W: Write> {
+    writer: W,
+    format: InterchangeFormat,
+    records: usize,
+}
+
+impl<W: Write> RecordWriter<W> {
+    pub fn new(writer: W, format: InterchangeFormat) -> Self {
+        Self {
+            writer,
+            format,
+            records: 0,
+        }
+    }
+
+    /// Append one record.
+    pub fn write<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        match self.format {
+            InterchangeFormat::MessagePack => {
+                let mut serializer = rmp_serde::Serializer::new(&mut self.writer).with_struct_map();
+                value
+                    .serialize(&mut serializer)
+                    .map_err(|e| UnityAssetError::with_source("MessagePack encoding failed", e))?;
+            }
+            InterchangeFormat::Cbor => {
+                ciborium::into_writer(value, &mut self.writer)
+                    .map_err(|e| UnityAssetError::with_source("CBOR encoding failed", e))?;
+            }
+        }
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Number of records written so far.
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Read every record of a stream written by [`RecordWriter`].
+pub fn read_records<T: DeserializeOwned, R: Read>(
+    mut reader: R,
+    format: InterchangeFormat,
+) -> Result<Vec<T>> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let mut rest = bytes.as_slice();
+    let mut out = Vec::new();
+    while !rest.is_empty() {
+        match format {
+            InterchangeFormat::MessagePack => {
+                let mut deserializer = rmp_serde::Deserializer::new(&mut rest);
+                out.push(
+                    T::deserialize(&mut deserializer).map_err(|e| {
+                        UnityAssetError::with_source("MessagePack decoding failed", e)
+                    })?,
+                );
+            }
+            InterchangeFormat::Cbor => {
+                out.push(
+                    ciborium::from_reader(&mut rest)
+                        .map_err(|e| UnityAssetError::with_source("CBOR decoding failed", e))?,
+                );
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{UnityClass, UnityValue};
+    use indexmap::IndexMap;
+
+    fn sample() -> UnityClass {
+        let mut class = UnityClass::new(114, "MonoBehaviour".to_string(), "42".to_string());
+        class.set("m_Name".to_string(), UnityValue::String("Boss".to_string()));
+        class.set("m_Enabled".to_string(), UnityValue::Bool(true));
+        class.set(
+            "hp".to_string(),
+            UnityValue::Integer(-9_007_199_254_740_993),
+        );
+        class.set("speed".to_string(), UnityValue::Float(0.1));
+        class.set("scale".to_string(), UnityValue::Float(f32::MAX as f64));
+        class.set("m_Script".to_string(), UnityValue::Null);
+        class.set("blob".to_string(), UnityValue::Bytes(vec![0, 1, 2, 255]));
+        class.set(
+            "phases".to_string(),
+            UnityValue::Array(vec![UnityValue::Integer(1), UnityValue::Array(Vec::new())]),
+        );
+        let mut nested = IndexMap::new();
+        nested.insert("z".to_string(), UnityValue::Integer(3));
+        nested.insert("a".to_string(), UnityValue::Integer(1));
+        nested.insert("m".to_string(), UnityValue::Object(IndexMap::new()));
+        class.set("offset".to_string(), UnityValue::Object(nested));
+        class
+    }
+
+    fn assert_same(decoded: &UnityClass, expected: &UnityClass) {
+        assert_eq!(decoded.class_id, expected.class_id);
+        assert_eq!(decoded.class_name, expected.class_name);
+        assert_eq!(decoded.anchor, expected.anchor);
+        assert_eq!(decoded.properties(), expected.properties());
+        // `IndexMap` equality ignores order; check it explicitly.
+        let keys = |class: &UnityClass| class.properties().keys().cloned().collect::<Vec<_>>();
+        assert_eq!(keys(decoded), keys(expected));
+        let nested = |class: &UnityClass| match class.get("offset") {
+            Some(UnityValue::Object(map)) => map.keys().cloned().collect::<Vec<_>>(),
+            other => panic!("offset: {:?}", other),
+        };
+        assert_eq!(nested(decoded), ["z", "a", "m"]);
+    }
+
+    #[test]
+    fn msgpack_round_trip() {
+        let class = sample();
+        let bytes = to_msgpack(&class).unwrap();
+        let decoded: UnityClass = rmp_serde::from_slice(&bytes).unwrap();
+        assert_same(&decoded, &class);
+
+        // Bytes are a `bin 8` value, not an array of integers.
+        assert!(bytes.windows(6).any(|w| w == [0xc4, 4, 0, 1, 2, 255]));
+    }
+
+    #[test]
+    fn cbor_round_trip() {
+        let class = sample();
+        let bytes = to_cbor(&class).unwrap();
+        let decoded: UnityClass = ciborium::from_reader(bytes.as_slice()).unwrap();
+        assert_same(&decoded, &class);
+
+        let raw: ciborium::Value = ciborium::from_reader(bytes.as_slice()).unwrap();
+        let props = raw
+            .as_map()
+            .and_then(|fields| {
+                fields
+                    .iter()
+                    .find(|(k, _)| k.as_text() == Some("properties"))
+            })
+            .and_then(|(_, v)| v.as_map())
+            .unwrap();
+        let get = |key: &str| {
+            props
+                .iter()
+                .find(|(k, _)| k.as_text() == Some(key))
+                .unwrap()
+        };
+        assert_eq!(get("blob").1, ciborium::Value::Bytes(vec![0, 1, 2, 255]));
+        assert_eq!(get("speed").1, ciborium::Value::Float(0.1));
+    }
+
+    #[test]
+    fn utf8_bytes_stay_bytes() {
+        let value = UnityValue::Bytes(b"plain text".to_vec());
+        assert_eq!(
+            from_msgpack::<UnityValue>(&to_msgpack(&value).unwrap()).unwrap(),
+            value
+        );
+        assert_eq!(
+            from_cbor::<UnityValue>(&to_cbor(&value).unwrap()).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn floats_keep_their_exact_value() {
+        for value in [0.1, 1.0, f32::MAX as f64, f64::MIN_POSITIVE, -0.0] {
+            let value = UnityValue::Float(value);
+            let msgpack: UnityValue = from_msgpack(&to_msgpack(&value).unwrap()).unwrap();
+            let cbor: UnityValue = from_cbor(&to_cbor(&value).unwrap()).unwrap();
+            for decoded in [msgpack, cbor] {
+                match (decoded, &value) {
+                    (UnityValue::Float(a), UnityValue::Float(b)) => {
+                        assert_eq!(a.to_bits(), b.to_bits())
+                    }
+                    (other, _) => panic!("expected a float, got {:?}", other),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn record_stream_round_trip() {
+        let first = sample();
+        let mut second = UnityClass::new(1, "GameObject".to_string(), "7".to_string());
+        second.set("m_Name".to_string(), UnityValue::String("Root".to_string()));
+
+        for format in [InterchangeFormat::MessagePack, InterchangeFormat::Cbor] {
+            let mut writer = RecordWriter::new(Vec::new(), format);
+            writer.write(&first).unwrap();
+            writer.write(&second).unwrap();
+            assert_eq!(writer.records(), 2);
+            let bytes = writer.into_inner().unwrap();
+
+            let decoded: Vec<UnityClass> = read_records(bytes.as_slice(), format).unwrap();
+            assert_eq!(decoded.len(), 2);
+            assert_same(&decoded[0], &first);
+            assert_eq!(decoded[1].name(), Some("Root"));
+        }
+    }
+}
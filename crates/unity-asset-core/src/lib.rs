@@ -8,16 +8,30 @@ pub mod constants;
 pub mod document;
 pub mod dynamic_access;
 pub mod error;
+#[cfg(feature = "interchange")]
+pub mod interchange;
+pub mod prelude;
+pub mod tag_manager;
 pub mod unity_class;
+pub mod unity_ids;
 pub mod unity_value;
+pub mod unity_version;
 
 // Re-export main types
 pub use constants::*;
 pub use document::{DocumentFormat, UnityDocument};
 pub use dynamic_access::{DynamicAccess, DynamicValue};
 pub use error::{Result, UnityAssetError};
-pub use unity_class::{UnityClass, UnityClassRegistry};
+pub use tag_manager::{SortingLayer, TagManager};
+pub use unity_class::{
+    UnityClass, UnityClassRegistry, YamlCollectionStyle, YamlComments, YamlLayout,
+};
+pub use unity_ids::UnityGuid;
 pub use unity_value::UnityValue;
+pub use unity_version::{
+    Capabilities, CrunchVariant, UnityFeature, UnityVersion, UnityVersionType,
+    VersionCompatibility, VersionSettings, VertexFormatTable,
+};
 
 /// Get Unity class name from class ID
 pub fn get_class_name(class_id: i32) -> Option<String> {
@@ -0,0 +1,10 @@
+//! Commonly used core types, for glob import.
+
+pub use crate::document::{DocumentFormat, UnityDocument};
+pub use crate::dynamic_access::{DynamicAccess, DynamicValue};
+pub use crate::error::{Result, UnityAssetError};
+pub use crate::unity_class::{
+    UnityClass, UnityClassRegistry, YamlCollectionStyle, YamlComments, YamlLayout,
+};
+pub use crate::unity_value::UnityValue;
+pub use crate::unity_version::UnityVersion;
@@ -0,0 +1,281 @@
+//! Project tags, layers and sorting layers (`ProjectSettings/TagManager.asset`, class 78).
+//!
+//! GameObjects store their layer as an index (`m_Layer`) into a fixed table of 32 layers. Slots
+//! 0-7 belong to the engine (only 0, 1, 2, 4 and 5 are named); slots 8-31 are user layers named
+//! in the TagManager. Since Unity 5 the table is the `layers` string array; older projects write
+//! one `Builtin Layer N` / `User Layer N` key per slot. Players carry the same object in
+//! `globalgamemanagers`.
+//!
+//! Without a TagManager, [`builtin_layer_name`] still names the engine layers.
+
+use crate::unity_value::UnityValue;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// Number of layer slots.
+pub const LAYER_COUNT: usize = 32;
+
+/// First slot available for user layers.
+pub const FIRST_USER_LAYER: i32 = 8;
+
+/// Tags every project has; the TagManager only lists the custom ones.
+pub const BUILTIN_TAGS: [&str; 7] = [
+    "Untagged",
+    "Respawn",
+    "Finish",
+    "EditorOnly",
+    "MainCamera",
+    "Player",
+    "GameController",
+];
+
+/// Name of a fixed engine layer (`0` Default, `1` TransparentFX, `2` Ignore Raycast, `4` Water,
+/// `5` UI).
+pub fn builtin_layer_name(index: i32) -> Option<&'static str> {
+    match index {
+        0 => Some("Default"),
+        1 => Some("TransparentFX"),
+        2 => Some("Ignore Raycast"),
+        4 => Some("Water"),
+        5 => Some("UI"),
+        _ => None,
+    }
+}
+
+/// One entry of `m_SortingLayers`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SortingLayer {
+    pub name: String,
+    /// `uniqueID`, what renderers reference as `m_SortingLayerID`. `Default` is `0`.
+    pub unique_id: u32,
+    pub locked: bool,
+}
+
+/// A parsed TagManager.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagManager {
+    /// Custom tags, in the order the project lists them.
+    pub tags: Vec<String>,
+    /// Layer names by index (always [`LAYER_COUNT`] entries; unnamed slots are empty).
+    pub layers: Vec<String>,
+    /// Sorting layers in draw order (back to front).
+    pub sorting_layers: Vec<SortingLayer>,
+}
+
+impl Default for TagManager {
+    /// The TagManager of a new project: builtin layers only.
+    fn default() -> Self {
+        Self {
+            tags: Vec::new(),
+            layers: (0..LAYER_COUNT as i32)
+                .map(|i| builtin_layer_name(i).unwrap_or_default().to_string())
+                .collect(),
+            sorting_layers: vec![SortingLayer {
+                name: "Default".to_string(),
+                unique_id: 0,
+                locked: false,
+            }],
+        }
+    }
+}
+
+impl TagManager {
+    /// Parse a TagManager from its fields (YAML document or TypeTree data).
+    pub fn from_properties(properties: &IndexMap<String, UnityValue>) -> Self {
+        let tags = properties
+            .get("tags")
+            .and_then(UnityValue::as_array)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(UnityValue::as_str)
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut layers = vec![String::new(); LAYER_COUNT];
+        if let Some(names) = properties.get("layers").and_then(UnityValue::as_array) {
+            for (slot, name) in layers.iter_mut().zip(names) {
+                *slot = name.as_str().unwrap_or_default().to_string();
+            }
+        } else {
+            for (index, slot) in layers.iter_mut().enumerate() {
+                let kind = if (index as i32) < FIRST_USER_LAYER {
+                    "Builtin"
+                } else {
+                    "User"
+                };
+                if let Some(name) = properties
+                    .get(&format!("{} Layer {}", kind, index))
+                    .and_then(UnityValue::as_str)
+                {
+                    *slot = name.to_string();
+                }
+            }
+        }
+        for (index, slot) in layers.iter_mut().enumerate() {
+            if slot.is_empty()
+                && let Some(name) = builtin_layer_name(index as i32)
+            {
+                *slot = name.to_string();
+            }
+        }
+
+        let sorting_layers = properties
+            .get("m_SortingLayers")
+            .and_then(UnityValue::as_array)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(UnityValue::as_object)
+                    .map(|entry| SortingLayer {
+                        name: entry
+                            .get("name")
+                            .and_then(UnityValue::as_str)
+                            .unwrap_or_default()
+                            .to_string(),
+                        unique_id: entry
+                            .get("uniqueID")
+                            .and_then(UnityValue::as_i64)
+                            .unwrap_or(0) as u32,
+                        locked: entry.get("locked").is_some_and(|v| {
+                            v.as_bool().unwrap_or(false) || v.as_i64().unwrap_or(0) != 0
+                        }),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            tags,
+            layers,
+            sorting_layers,
+        }
+    }
+
+    /// Name of layer `index`, falling back to the builtin names for empty engine slots.
+    pub fn layer_name(&self, index: i32) -> Option<&str> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|i| self.layers.get(i))
+            .map(String::as_str)
+            .filter(|name| !name.is_empty())
+            .or_else(|| builtin_layer_name(index))
+    }
+
+    /// Index of the layer called `name`.
+    pub fn layer_index(&self, name: &str) -> Option<i32> {
+        self.layers
+            .iter()
+            .position(|layer| !layer.is_empty() && layer == name)
+            .map(|i| i as i32)
+    }
+
+    /// Named user layers (slots 8-31) as `(index, name)`.
+    pub fn user_layers(&self) -> Vec<(i32, &str)> {
+        self.layers
+            .iter()
+            .enumerate()
+            .skip(FIRST_USER_LAYER as usize)
+            .filter(|(_, name)| !name.is_empty())
+            .map(|(i, name)| (i as i32, name.as_str()))
+            .collect()
+    }
+
+    /// Builtin tags followed by the custom ones.
+    pub fn all_tags(&self) -> Vec<&str> {
+        BUILTIN_TAGS
+            .iter()
+            .copied()
+            .chain(self.tags.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// Sorting layer with the given `uniqueID` (`m_SortingLayerID`).
+    pub fn sorting_layer(&self, unique_id: u32) -> Option<&SortingLayer> {
+        self.sorting_layers
+            .iter()
+            .find(|layer| layer.unique_id == unique_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> UnityValue {
+        UnityValue::Array(
+            values
+                .iter()
+                .map(|v| UnityValue::String(v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn parses_layer_table_tags_and_sorting_layers() {
+        let mut layers = vec![
+            "Default",
+            "TransparentFX",
+            "Ignore Raycast",
+            "",
+            "Water",
+            "UI",
+            "",
+            "",
+            "Ground",
+        ];
+        layers.resize(LAYER_COUNT, "");
+        let mut sorting = IndexMap::new();
+        sorting.insert("name".to_string(), UnityValue::String("Background".into()));
+        sorting.insert("uniqueID".to_string(), UnityValue::Integer(3165519633));
+        sorting.insert("locked".to_string(), UnityValue::Integer(1));
+
+        let mut props = IndexMap::new();
+        props.insert("tags".to_string(), strings(&["Enemy"]));
+        props.insert("layers".to_string(), strings(&layers));
+        props.insert(
+            "m_SortingLayers".to_string(),
+            UnityValue::Array(vec![UnityValue::Object(sorting)]),
+        );
+
+        let manager = TagManager::from_properties(&props);
+        assert_eq!(manager.layer_name(8), Some("Ground"));
+        assert_eq!(manager.layer_name(5), Some("UI"));
+        assert_eq!(manager.layer_name(9), None);
+        assert_eq!(manager.layer_name(-1), None);
+        assert_eq!(manager.layer_index("Ground"), Some(8));
+        assert_eq!(manager.user_layers(), vec![(8, "Ground")]);
+        assert_eq!(manager.all_tags().last(), Some(&"Enemy"));
+        assert_eq!(
+            manager.sorting_layer(3165519633),
+            Some(&SortingLayer {
+                name: "Background".into(),
+                unique_id: 3165519633,
+                locked: true,
+            })
+        );
+    }
+
+    #[test]
+    fn parses_per_slot_layer_keys_and_falls_back_to_builtin_names() {
+        let mut props = IndexMap::new();
+        props.insert(
+            "Builtin Layer 0".to_string(),
+            UnityValue::String("Default".into()),
+        );
+        props.insert(
+            "User Layer 12".to_string(),
+            UnityValue::String("Enemies".into()),
+        );
+
+        let manager = TagManager::from_properties(&props);
+        assert_eq!(manager.layer_name(12), Some("Enemies"));
+        assert_eq!(manager.layer_name(4), Some("Water"));
+        assert!(manager.tags.is_empty());
+
+        assert_eq!(TagManager::default().layer_name(2), Some("Ignore Raycast"));
+        assert_eq!(builtin_layer_name(3), None);
+    }
+}
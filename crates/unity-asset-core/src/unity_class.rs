@@ -4,9 +4,10 @@
 //! runtime creation and manipulation of Unity objects.
 
 use crate::dynamic_access::{DynamicAccess, DynamicValue};
-use crate::error::Result;
+use crate::error::{Result, UnityAssetError};
 use crate::unity_value::UnityValue;
 use indexmap::IndexMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 use std::fmt;
 
@@ -15,7 +16,7 @@ use std::fmt;
 pub struct UnityClass {
     /// Class ID (numeric identifier)
     pub class_id: i32,
-    /// Class name (string identifier)
+    /// Class name (string identifier); empty for a plain mapping document (see [`Self::is_plain`])
     pub class_name: String,
     /// YAML anchor for this object
     pub anchor: String,
@@ -23,6 +24,67 @@ pub struct UnityClass {
     pub extra_anchor_data: String,
     /// Object properties
     properties: IndexMap<String, UnityValue>,
+    /// Comments read from the source YAML
+    comments: YamlComments,
+    /// Source formatting read from the YAML
+    layout: YamlLayout,
+}
+
+/// `#` comments of a YAML document, kept so a load/save cycle writes them back in place.
+///
+/// Comment lines are stored as written, without indentation (`# speed in m/s`). Key paths use
+/// the `m_Children[2].fileID` form; property renames or removals drop their comments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct YamlComments {
+    /// Full-line comments before the `--- !u!` document header.
+    pub document: Vec<String>,
+    /// Full-line comments before a key or sequence item, by key path.
+    pub leading: IndexMap<String, Vec<String>>,
+    /// Comment at the end of a key's line, by key path.
+    pub trailing: IndexMap<String, String>,
+    /// Full-line comments after the last property, when no document follows.
+    pub footer: Vec<String>,
+}
+
+impl YamlComments {
+    pub fn is_empty(&self) -> bool {
+        self.document.is_empty()
+            && self.leading.is_empty()
+            && self.trailing.is_empty()
+            && self.footer.is_empty()
+    }
+}
+
+/// How a mapping or sequence was laid out in the source YAML
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YamlCollectionStyle {
+    /// `{x: 0, y: 0}` / `[1, 2]` on the key's line
+    Flow,
+    /// One entry per line; sequence items at the key's indentation, as Unity writes them
+    Block,
+    /// Block sequence with its `- ` items indented below the key
+    IndentedBlock,
+}
+
+/// Source formatting of a YAML document that the parsed values do not carry, kept so a
+/// load/save cycle reproduces the original text.
+///
+/// Only entries that differ from what the serializer would write anyway are recorded, so files
+/// written by Unity usually have an empty layout. Key paths use the [`YamlComments`] form. A
+/// scalar's text is only reused while the value at its path still equals the value it parsed to.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct YamlLayout {
+    /// Collection styles by key path.
+    pub styles: IndexMap<String, YamlCollectionStyle>,
+    /// Scalar text as written (`0000000000000000e000000000000000`, `'a: b'`) and the value it
+    /// parsed to, by key path.
+    pub scalars: IndexMap<String, (String, UnityValue)>,
+}
+
+impl YamlLayout {
+    pub fn is_empty(&self) -> bool {
+        self.styles.is_empty() && self.scalars.is_empty()
+    }
 }
 
 impl UnityClass {
@@ -34,6 +96,8 @@ impl UnityClass {
             anchor,
             extra_anchor_data: String::new(),
             properties: IndexMap::new(),
+            comments: YamlComments::default(),
+            layout: YamlLayout::default(),
         }
     }
 
@@ -48,10 +112,88 @@ impl UnityClass {
     }
 
     /// Set a property value
+    ///
+    /// An existing key keeps its position; a new key is appended. Property order is the
+    /// serialization order.
     pub fn set<V: Into<UnityValue>>(&mut self, key: String, value: V) {
         self.properties.insert(key, value.into());
     }
 
+    /// Insert a property immediately before `key`
+    ///
+    /// If `new_key` already exists it is moved to the new position. Fails if `key` is missing.
+    pub fn insert_before<V: Into<UnityValue>>(
+        &mut self,
+        key: &str,
+        new_key: String,
+        value: V,
+    ) -> Result<()> {
+        self.insert_relative(key, new_key, value.into(), false)
+    }
+
+    /// Insert a property immediately after `key`
+    ///
+    /// If `new_key` already exists it is moved to the new position. Fails if `key` is missing.
+    pub fn insert_after<V: Into<UnityValue>>(
+        &mut self,
+        key: &str,
+        new_key: String,
+        value: V,
+    ) -> Result<()> {
+        self.insert_relative(key, new_key, value.into(), true)
+    }
+
+    fn insert_relative(
+        &mut self,
+        key: &str,
+        new_key: String,
+        value: UnityValue,
+        after: bool,
+    ) -> Result<()> {
+        if !self.properties.contains_key(key) {
+            return Err(UnityAssetError::property_not_found(
+                key.to_string(),
+                self.class_name.clone(),
+            ));
+        }
+        if new_key == key {
+            self.properties.insert(new_key, value);
+            return Ok(());
+        }
+
+        self.properties.shift_remove(&new_key);
+        let anchor = self
+            .properties
+            .get_index_of(key)
+            .expect("anchor key checked above");
+        let index = if after { anchor + 1 } else { anchor };
+        self.properties.shift_insert(index, new_key, value);
+        Ok(())
+    }
+
+    /// Move the given properties to the front, in the given order
+    ///
+    /// Properties not listed keep their relative order after them. Fails without modifying
+    /// anything if a key is missing.
+    pub fn reorder(&mut self, keys: &[&str]) -> Result<()> {
+        if let Some(missing) = keys.iter().find(|k| !self.properties.contains_key(**k)) {
+            return Err(UnityAssetError::property_not_found(
+                missing.to_string(),
+                self.class_name.clone(),
+            ));
+        }
+
+        let mut reordered = IndexMap::with_capacity(self.properties.len());
+        for key in keys {
+            if let Some((k, v)) = self.properties.shift_remove_entry(*key) {
+                reordered.insert(k, v);
+            }
+        }
+        reordered.extend(self.properties.drain(..));
+        self.properties = reordered;
+        Ok(())
+    }
+
     /// Check if a property exists
     pub fn has_property(&self, key: &str) -> bool {
         self.properties.contains_key(key)
@@ -84,12 +226,85 @@ impl UnityClass {
         self.properties.clone()
     }
 
+    /// Comments read from the source YAML
+    pub fn comments(&self) -> &YamlComments {
+        &self.comments
+    }
+
+    /// Get mutable comments
+    pub fn comments_mut(&mut self) -> &mut YamlComments {
+        &mut self.comments
+    }
+
+    /// Source formatting read from the YAML
+    pub fn layout(&self) -> &YamlLayout {
+        &self.layout
+    }
+
+    /// Get mutable source formatting
+    pub fn layout_mut(&mut self) -> &mut YamlLayout {
+        &mut self.layout
+    }
+
+    /// Whether this is an untagged top-level mapping (e.g. a `.meta` file) rather than a
+    /// `--- !u!<class_id>` object: its properties are the document's top-level keys.
+    pub fn is_plain(&self) -> bool {
+        self.class_name.is_empty()
+    }
+
     /// Get the object name (m_Name property if it exists)
     pub fn name(&self) -> Option<&str> {
         self.get("m_Name").and_then(|v| v.as_str())
     }
 }
 
+/// Serialized form of a [`UnityClass`]: identity fields plus the ordered property map.
+///
+/// YAML comments and layout are not part of it.
+#[derive(Serialize)]
+struct UnityClassRef<'a> {
+    class_id: i32,
+    class_name: &'a str,
+    anchor: &'a str,
+    extra_anchor_data: &'a str,
+    properties: &'a IndexMap<String, UnityValue>,
+}
+
+#[derive(Deserialize)]
+struct UnityClassOwned {
+    class_id: i32,
+    class_name: String,
+    #[serde(default)]
+    anchor: String,
+    #[serde(default)]
+    extra_anchor_data: String,
+    #[serde(default)]
+    properties: IndexMap<String, UnityValue>,
+}
+
+impl Serialize for UnityClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        UnityClassRef {
+            class_id: self.class_id,
+            class_name: &self.class_name,
+            anchor: &self.anchor,
+            extra_anchor_data: &self.extra_anchor_data,
+            properties: &self.properties,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for UnityClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let owned = UnityClassOwned::deserialize(deserializer)?;
+        let mut class = UnityClass::new(owned.class_id, owned.class_name, owned.anchor);
+        class.extra_anchor_data = owned.extra_anchor_data;
+        class.properties = owned.properties;
+        Ok(class)
+    }
+}
+
 impl fmt::Display for UnityClass {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}({})", self.class_name, self.class_id)
@@ -171,6 +386,122 @@ mod tests {
         assert_eq!(class.name(), Some("TestObject"));
     }
 
+    fn keys(class: &UnityClass) -> Vec<&str> {
+        class.property_names().map(String::as_str).collect()
+    }
+
+    fn nested(pairs: &[(&str, i64)]) -> UnityValue {
+        UnityValue::Object(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), UnityValue::Integer(*v)))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_set_existing_keeps_position() {
+        let mut class = UnityClass::new(4, "Transform".to_string(), "1".to_string());
+        class.set("m_GameObject".to_string(), nested(&[("fileID", 1)]));
+        class.set("m_LocalRotation".to_string(), nested(&[("x", 0), ("w", 1)]));
+        class.set("m_LocalPosition".to_string(), nested(&[("x", 0)]));
+
+        class.set("m_GameObject".to_string(), nested(&[("fileID", 2)]));
+        class.update_properties(
+            [("m_LocalRotation".to_string(), nested(&[("w", 1)]))]
+                .into_iter()
+                .collect(),
+        );
+
+        assert_eq!(
+            keys(&class),
+            ["m_GameObject", "m_LocalRotation", "m_LocalPosition"]
+        );
+        assert_eq!(class.get("m_GameObject"), Some(&nested(&[("fileID", 2)])));
+    }
+
+    #[test]
+    fn test_insert_before_and_after() {
+        let mut class = UnityClass::new(4, "Transform".to_string(), "1".to_string());
+        class.set("m_GameObject".to_string(), nested(&[("fileID", 1)]));
+        class.set("m_LocalPosition".to_string(), nested(&[("x", 0), ("y", 0)]));
+        class.set("m_Children".to_string(), UnityValue::Array(Vec::new()));
+
+        class
+            .insert_before(
+                "m_LocalPosition",
+                "m_LocalRotation".to_string(),
+                nested(&[("w", 1)]),
+            )
+            .unwrap();
+        class
+            .insert_after(
+                "m_LocalPosition",
+                "m_LocalScale".to_string(),
+                nested(&[("x", 1)]),
+            )
+            .unwrap();
+        class
+            .insert_after(
+                "m_Children",
+                "m_Father".to_string(),
+                nested(&[("fileID", 0)]),
+            )
+            .unwrap();
+        assert_eq!(
+            keys(&class),
+            [
+                "m_GameObject",
+                "m_LocalRotation",
+                "m_LocalPosition",
+                "m_LocalScale",
+                "m_Children",
+                "m_Father",
+            ]
+        );
+
+        // Existing key is moved, not duplicated; nested value is replaced wholesale.
+        class
+            .insert_before(
+                "m_LocalRotation",
+                "m_Father".to_string(),
+                nested(&[("fileID", 9)]),
+            )
+            .unwrap();
+        assert_eq!(
+            keys(&class),
+            [
+                "m_GameObject",
+                "m_Father",
+                "m_LocalRotation",
+                "m_LocalPosition",
+                "m_LocalScale",
+                "m_Children",
+            ]
+        );
+        assert_eq!(class.get("m_Father"), Some(&nested(&[("fileID", 9)])));
+
+        let err = class
+            .insert_after("m_Missing", "x".to_string(), 1i64)
+            .unwrap_err();
+        assert!(err.to_string().contains("m_Missing"));
+        assert!(!class.has_property("x"));
+    }
+
+    #[test]
+    fn test_reorder() {
+        let mut class = UnityClass::new(1, "GameObject".to_string(), "1".to_string());
+        for key in ["a", "b", "c", "d"] {
+            class.set(key.to_string(), nested(&[(key, 1)]));
+        }
+
+        class.reorder(&["c", "a"]).unwrap();
+        assert_eq!(keys(&class), ["c", "a", "b", "d"]);
+
+        assert!(class.reorder(&["d", "zz"]).is_err());
+        assert_eq!(keys(&class), ["c", "a", "b", "d"]);
+    }
+
     #[test]
     fn test_unity_class_registry() {
         let registry = UnityClassRegistry::new();
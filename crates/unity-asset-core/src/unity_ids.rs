@@ -0,0 +1,351 @@
+//! Unity's identifier math: script fileIDs, prefab instance fileIDs and asset GUIDs.
+//!
+//! - A MonoScript inside a managed DLL is referenced as `{fileID: <id>, guid: <dll guid>}`,
+//!   where `<id>` is the first four bytes (little-endian) of the MD4 hash of
+//!   `"s\0\0\0" + namespace + class name`. Scripts in `.cs` files always use fileID `11500000`.
+//! - Objects of a prefab instance get `(instance fileID ^ source fileID) & i64::MAX`.
+//! - GUIDs are 16 bytes. `.meta` files and YAML write them as 32 hex digits, each byte low
+//!   nibble first (`0x0e` is written `e0`).
+
+use crate::error::{Result, UnityAssetError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// fileID of the MonoScript of a `.cs` file.
+pub const MONO_SCRIPT_FILE_ID: i64 = 11500000;
+
+/// fileID of a MonoScript stored in a managed DLL.
+///
+/// `namespace` is empty for types in the global namespace; nested types use their own name
+/// only. The assembly does not take part in the hash: it selects the DLL, whose GUID goes next
+/// to this fileID.
+pub fn script_file_id(namespace: &str, class_name: &str) -> i64 {
+    let mut input = Vec::with_capacity(4 + namespace.len() + class_name.len());
+    input.extend_from_slice(b"s\0\0\0");
+    input.extend_from_slice(namespace.as_bytes());
+    input.extend_from_slice(class_name.as_bytes());
+    let hash = md4(&input);
+    i32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]]) as i64
+}
+
+/// fileID of the object a prefab instance creates for `source_file_id` (the object's fileID in
+/// the source prefab). `instance_file_id` is the fileID of the PrefabInstance.
+pub fn prefab_instance_file_id(source_file_id: i64, instance_file_id: i64) -> i64 {
+    (instance_file_id ^ source_file_id) & i64::MAX
+}
+
+/// A Unity asset GUID, as the 16 bytes stored in serialized files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UnityGuid(pub [u8; 16]);
+
+impl UnityGuid {
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// From the typetree form (`data[0]..data[3]`).
+    pub fn from_words(words: [u32; 4]) -> Self {
+        let mut bytes = [0u8; 16];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        Self(bytes)
+    }
+
+    /// The typetree form (`data[0]..data[3]`).
+    pub fn words(&self) -> [u32; 4] {
+        std::array::from_fn(|i| {
+            u32::from_le_bytes([
+                self.0[i * 4],
+                self.0[i * 4 + 1],
+                self.0[i * 4 + 2],
+                self.0[i * 4 + 3],
+            ])
+        })
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 16]
+    }
+
+    /// A GUID fixed by where an object was extracted from: the same `source` (e.g. a bundle
+    /// name) and `path_id` always give the same GUID, so re-extracting keeps references valid.
+    pub fn derived(source: &str, path_id: i64) -> Self {
+        let mut input = Vec::with_capacity(source.len() + 9);
+        input.extend_from_slice(source.as_bytes());
+        input.push(0);
+        input.extend_from_slice(&path_id.to_le_bytes());
+        Self(md4(&input))
+    }
+
+    /// A new random GUID in the editor's format: a version 4 UUID's 32 hex digits, as they
+    /// appear in `.meta` files (13th digit `4`, 17th digit one of `8`-`b`).
+    #[cfg(feature = "random")]
+    pub fn new_random() -> Self {
+        let mut bytes: [u8; 16] = rand::random();
+        // Digit 12 is the low nibble of byte 6, digit 16 the low nibble of byte 8.
+        bytes[6] = (bytes[6] & 0xF0) | 0x4;
+        bytes[8] = (bytes[8] & 0xF0) | 0x8 | (bytes[8] & 0x3);
+        Self(bytes)
+    }
+}
+
+impl fmt::Display for UnityGuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:x}{:x}", byte & 0xF, byte >> 4)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for UnityGuid {
+    type Err = UnityAssetError;
+
+    /// Parse the 32 hex digits of a `.meta` file (case-insensitive).
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let digits: Vec<u8> = s
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect::<Option<_>>()
+            .filter(|d: &Vec<u8>| d.len() == 32)
+            .ok_or_else(|| UnityAssetError::parse(format!("Invalid Unity GUID: {:?}", s)))?;
+        Ok(Self(std::array::from_fn(|i| {
+            digits[i * 2] | (digits[i * 2 + 1] << 4)
+        })))
+    }
+}
+
+impl Serialize for UnityGuid {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for UnityGuid {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// MD4 (RFC 1320).
+fn md4(input: &[u8]) -> [u8; 16] {
+    let mut message = input.to_vec();
+    let bit_len = (input.len() as u64).wrapping_mul(8);
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&bit_len.to_le_bytes());
+
+    let mut state: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+    for block in message.chunks_exact(64) {
+        let x: [u32; 16] = std::array::from_fn(|i| {
+            u32::from_le_bytes([
+                block[i * 4],
+                block[i * 4 + 1],
+                block[i * 4 + 2],
+                block[i * 4 + 3],
+            ])
+        });
+        let [mut a, mut b, mut c, mut d] = state;
+
+        let f = |x: u32, y: u32, z: u32| (x & y) | (!x & z);
+        let g = |x: u32, y: u32, z: u32| (x & y) | (x & z) | (y & z);
+        let h = |x: u32, y: u32, z: u32| x ^ y ^ z;
+
+        for &i in &[0, 4, 8, 12] {
+            a = a.wrapping_add(f(b, c, d)).wrapping_add(x[i]).rotate_left(3);
+            d = d
+                .wrapping_add(f(a, b, c))
+                .wrapping_add(x[i + 1])
+                .rotate_left(7);
+            c = c
+                .wrapping_add(f(d, a, b))
+                .wrapping_add(x[i + 2])
+                .rotate_left(11);
+            b = b
+                .wrapping_add(f(c, d, a))
+                .wrapping_add(x[i + 3])
+                .rotate_left(19);
+        }
+        for &i in &[0, 1, 2, 3] {
+            let k = 0x5a827999u32;
+            a = a
+                .wrapping_add(g(b, c, d))
+                .wrapping_add(x[i])
+                .wrapping_add(k)
+                .rotate_left(3);
+            d = d
+                .wrapping_add(g(a, b, c))
+                .wrapping_add(x[i + 4])
+                .wrapping_add(k)
+                .rotate_left(5);
+            c = c
+                .wrapping_add(g(d, a, b))
+                .wrapping_add(x[i + 8])
+                .wrapping_add(k)
+                .rotate_left(9);
+            b = b
+                .wrapping_add(g(c, d, a))
+                .wrapping_add(x[i + 12])
+                .wrapping_add(k)
+                .rotate_left(13);
+        }
+        for &i in &[0, 2, 1, 3] {
+            let k = 0x6ed9eba1u32;
+            a = a
+                .wrapping_add(h(b, c, d))
+                .wrapping_add(x[i])
+                .wrapping_add(k)
+                .rotate_left(3);
+            d = d
+                .wrapping_add(h(a, b, c))
+                .wrapping_add(x[i + 8])
+                .wrapping_add(k)
+                .rotate_left(9);
+            c = c
+                .wrapping_add(h(d, a, b))
+                .wrapping_add(x[i + 4])
+                .wrapping_add(k)
+                .rotate_left(11);
+            b = b
+                .wrapping_add(h(c, d, a))
+                .wrapping_add(x[i + 12])
+                .wrapping_add(k)
+                .rotate_left(15);
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+    }
+
+    let mut out = [0u8; 16];
+    for (chunk, word) in out.chunks_exact_mut(4).zip(state) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn md4_matches_rfc1320_vectors() {
+        assert_eq!(hex(&md4(b"")), "31d6cfe0d16ae931b73c59d7e0c089c0");
+        assert_eq!(hex(&md4(b"abc")), "a448017aaf21d8525fc10ae87aa6729d");
+        assert_eq!(
+            hex(&md4(
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890"
+            )),
+            "e33b4ddc9c38f2199c3e7b164fcc0536"
+        );
+    }
+
+    #[test]
+    fn script_file_ids_match_unity_ui_references() {
+        // `m_Script` fileIDs of UnityEngine.UI.dll (guid f70555f144d8491a825f0804e09c671c)
+        // components, as serialized in prefabs.
+        assert_eq!(script_file_id("UnityEngine.UI", "Image"), -765806418);
+        assert_eq!(script_file_id("UnityEngine.UI", "Text"), 708705254);
+        assert_eq!(script_file_id("UnityEngine.UI", "Button"), 1392445389);
+        assert_eq!(script_file_id("UnityEngine.UI", "CanvasScaler"), 1980459831);
+        assert_eq!(
+            script_file_id("UnityEngine.UI", "GraphicRaycaster"),
+            1301386320
+        );
+        assert_eq!(
+            script_file_id("UnityEngine.EventSystems", "EventSystem"),
+            -619905303
+        );
+        assert_eq!(
+            script_file_id("UnityEngine.EventSystems", "StandaloneInputModule"),
+            1077351063
+        );
+    }
+
+    #[test]
+    fn prefab_instance_file_id_clears_sign_bit() {
+        assert_eq!(prefab_instance_file_id(0, 0), 0);
+        assert_eq!(
+            prefab_instance_file_id(4_690_215_836_297_382_400, 1_846_028_517),
+            4_690_215_836_297_382_400 ^ 1_846_028_517
+        );
+        assert_eq!(prefab_instance_file_id(-1, 0), i64::MAX);
+        assert!(prefab_instance_file_id(i64::MIN + 5, 3) >= 0);
+    }
+
+    #[test]
+    fn derived_guid_is_stable_per_source_and_path_id() {
+        let a = UnityGuid::derived("char_118_yuki.ab", 5482297458062616526);
+        assert_eq!(
+            a,
+            UnityGuid::derived("char_118_yuki.ab", 5482297458062616526)
+        );
+        assert_ne!(
+            a,
+            UnityGuid::derived("char_118_yuki.ab", -5482297458062616526)
+        );
+        assert_ne!(a, UnityGuid::derived("banner_1", 5482297458062616526));
+        assert!(!a.is_zero());
+        assert_eq!(a.to_string().parse::<UnityGuid>().unwrap(), a);
+    }
+
+    #[test]
+    fn guid_round_trips_meta_hex() {
+        // Built-in resources: byte 8 is 0x0e / 0x0f.
+        let builtin: UnityGuid = "0000000000000000e000000000000000".parse().unwrap();
+        assert_eq!(builtin.as_bytes()[8], 0x0e);
+        assert_eq!(builtin.words(), [0, 0, 0x0e, 0]);
+
+        let text = "f70555f144d8491a825f0804e09c671c";
+        let guid: UnityGuid = text.parse().unwrap();
+        assert_eq!(guid.as_bytes()[0], 0x7f);
+        assert_eq!(guid.to_string(), text);
+        assert_eq!(UnityGuid::from_words(guid.words()), guid);
+        assert_eq!(
+            "F70555F144D8491A825F0804E09C671C"
+                .parse::<UnityGuid>()
+                .unwrap(),
+            guid
+        );
+
+        assert!("f70555f1".parse::<UnityGuid>().is_err());
+        assert!(
+            "g70555f144d8491a825f0804e09c671c"
+                .parse::<UnityGuid>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "random")]
+    fn random_guid_looks_like_editor_guids() {
+        let a = UnityGuid::new_random();
+        let b = UnityGuid::new_random();
+        assert_ne!(a, b);
+        let text = a.to_string();
+        assert_eq!(text.len(), 32);
+        assert_eq!(&text[12..13], "4");
+        assert!(matches!(&text[16..17], "8" | "9" | "a" | "b"));
+        assert_eq!(text.parse::<UnityGuid>().unwrap(), a);
+    }
+}
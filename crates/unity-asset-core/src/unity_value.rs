@@ -4,11 +4,15 @@
 //! for representing Unity asset values in a type-safe manner.
 
 use indexmap::IndexMap;
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// A Unity value that can be stored in a Unity class
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Serialized untagged. Deserialization goes by the input's own type, so binary strings from
+/// MessagePack/CBOR come back as [`UnityValue::Bytes`] even when they happen to be valid UTF-8.
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum UnityValue {
     Null,
@@ -17,11 +21,90 @@ pub enum UnityValue {
     Float(f64),
     String(String),
     Array(Vec<UnityValue>),
-    #[serde(with = "serde_bytes")]
+    #[serde(serialize_with = "serde_bytes::serialize")]
     Bytes(Vec<u8>),
     Object(IndexMap<String, UnityValue>),
 }
 
+impl<'de> Deserialize<'de> for UnityValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(UnityValueVisitor)
+    }
+}
+
+struct UnityValueVisitor;
+
+impl<'de> Visitor<'de> for UnityValueVisitor {
+    type Value = UnityValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a Unity value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<UnityValue, E> {
+        Ok(UnityValue::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<UnityValue, E> {
+        Ok(UnityValue::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<UnityValue, D::Error> {
+        UnityValue::deserialize(deserializer)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<UnityValue, E> {
+        Ok(UnityValue::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<UnityValue, E> {
+        Ok(UnityValue::Integer(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<UnityValue, E> {
+        Ok(match i64::try_from(v) {
+            Ok(v) => UnityValue::Integer(v),
+            Err(_) => UnityValue::Float(v as f64),
+        })
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<UnityValue, E> {
+        Ok(UnityValue::Float(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<UnityValue, E> {
+        Ok(UnityValue::String(v.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<UnityValue, E> {
+        Ok(UnityValue::String(v))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<UnityValue, E> {
+        Ok(UnityValue::Bytes(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<UnityValue, E> {
+        Ok(UnityValue::Bytes(v))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<UnityValue, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(UnityValue::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<UnityValue, A::Error> {
+        let mut fields = IndexMap::with_capacity(map.size_hint().unwrap_or(0).min(4096));
+        while let Some((key, value)) = map.next_entry()? {
+            fields.insert(key, value);
+        }
+        Ok(UnityValue::Object(fields))
+    }
+}
+
 impl UnityValue {
     /// Check if the value is null
     pub fn is_null(&self) -> bool {
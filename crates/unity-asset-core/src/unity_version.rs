@@ -0,0 +1,718 @@
+//! Unity Version Management System
+//!
+//! This module provides comprehensive Unity version parsing, comparison, and compatibility
+//! handling based on UnityPy's implementation.
+//!
+//! It lives in the core crate so YAML and binary sources (and the facade `Environment`) share a
+//! single version type.
+
+use crate::error::{Result, UnityAssetError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Unity version type (release channel)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
+pub enum UnityVersionType {
+    /// Alpha release
+    A = 0,
+    /// Beta release
+    B = 1,
+    /// China release
+    C = 2,
+    /// Final release
+    #[default]
+    F = 3,
+    /// Patch release
+    P = 4,
+    /// Experimental release
+    X = 5,
+    /// Unknown/Custom release
+    U = 6,
+}
+
+impl fmt::Display for UnityVersionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnityVersionType::A => write!(f, "a"),
+            UnityVersionType::B => write!(f, "b"),
+            UnityVersionType::C => write!(f, "c"),
+            UnityVersionType::F => write!(f, "f"),
+            UnityVersionType::P => write!(f, "p"),
+            UnityVersionType::X => write!(f, "x"),
+            UnityVersionType::U => write!(f, "u"),
+        }
+    }
+}
+
+impl FromStr for UnityVersionType {
+    type Err = UnityAssetError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "a" => Ok(UnityVersionType::A),
+            "b" => Ok(UnityVersionType::B),
+            "c" => Ok(UnityVersionType::C),
+            "f" => Ok(UnityVersionType::F),
+            "p" => Ok(UnityVersionType::P),
+            "x" => Ok(UnityVersionType::X),
+            _ => Ok(UnityVersionType::U),
+        }
+    }
+}
+
+/// Unity version representation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnityVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub build: u16,
+    pub version_type: UnityVersionType,
+    pub type_number: u8,
+    pub type_str: Option<String>, // For custom/unknown types
+}
+
+impl Default for UnityVersion {
+    fn default() -> Self {
+        Self {
+            major: 2020,
+            minor: 3,
+            build: 0,
+            version_type: UnityVersionType::F,
+            type_number: 1,
+            type_str: None,
+        }
+    }
+}
+
+impl UnityVersion {
+    /// Create a new Unity version
+    pub fn new(
+        major: u16,
+        minor: u16,
+        build: u16,
+        version_type: UnityVersionType,
+        type_number: u8,
+    ) -> Self {
+        Self {
+            major,
+            minor,
+            build,
+            version_type,
+            type_number,
+            type_str: None,
+        }
+    }
+
+    /// Parse Unity version from string
+    /// Supports formats like: "2020.3.12f1", "5.6.0", "2018.1.1b2"
+    pub fn parse_version(version: &str) -> Result<Self> {
+        // Mirrors UnityPy `UnityVersion.from_str` behavior:
+        // - parse `<major>.<minor>.<build><type_str><type_number>` where `<type_str>` can be more than 1 char
+        // - unknown type strings are preserved (e.g. Tuanjie `t`, UnityCN `f1c`)
+        // - ignore any revision hash suffix in parentheses (ProjectVersion.txt style)
+        let raw = version.trim();
+        if raw.is_empty() {
+            return Ok(Self::default());
+        }
+        let raw = raw.split_whitespace().next().unwrap_or(raw);
+
+        let mut parts = raw.splitn(3, '.');
+        let major = parts
+            .next()
+            .ok_or_else(|| UnityAssetError::version(format!("Invalid version format: {}", raw)))?
+            .parse::<u16>()
+            .map_err(|e| UnityAssetError::version(format!("Invalid major version: {}", e)))?;
+        let minor = parts
+            .next()
+            .ok_or_else(|| UnityAssetError::version(format!("Invalid version format: {}", raw)))?
+            .parse::<u16>()
+            .map_err(|e| UnityAssetError::version(format!("Invalid minor version: {}", e)))?;
+        let tail = parts
+            .next()
+            .ok_or_else(|| UnityAssetError::version(format!("Invalid version format: {}", raw)))?;
+
+        let (build_digits, suffix) = split_leading_digits(tail);
+        if build_digits.is_empty() {
+            return Err(UnityAssetError::version(format!(
+                "Invalid build version: {}",
+                raw
+            )));
+        }
+        let build = build_digits
+            .parse::<u16>()
+            .map_err(|e| UnityAssetError::version(format!("Invalid build version: {}", e)))?;
+
+        if suffix.is_empty() {
+            return Ok(Self::new(major, minor, build, UnityVersionType::F, 0));
+        }
+
+        let (type_str, type_number) = split_trailing_number(suffix);
+        let type_number_u8 = type_number.unwrap_or(0);
+
+        let parsed_type = UnityVersionType::from_str(type_str).unwrap_or(UnityVersionType::U);
+        let mut out = Self::new(major, minor, build, parsed_type, type_number_u8);
+
+        // Preserve unknown/custom type strings exactly, UnityPy-style.
+        if out.version_type == UnityVersionType::U {
+            out.type_str = Some(type_str.to_string());
+        }
+
+        Ok(out)
+    }
+
+    /// Parse the editor version out of a `ProjectSettings/ProjectVersion.txt` file.
+    ///
+    /// Prefers `m_EditorVersion` and falls back to `m_EditorVersionWithRevision`
+    /// (the `(revision)` suffix is ignored).
+    pub fn parse_project_version(text: &str) -> Result<Self> {
+        let mut with_revision: Option<&str> = None;
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            match key.trim() {
+                "m_EditorVersion" => return Self::parse_version(value.trim()),
+                "m_EditorVersionWithRevision" => with_revision = Some(value.trim()),
+                _ => {}
+            }
+        }
+        match with_revision {
+            Some(value) => Self::parse_version(value),
+            None => Err(UnityAssetError::version(
+                "ProjectVersion.txt does not contain m_EditorVersion",
+            )),
+        }
+    }
+
+    /// Convert to tuple for comparison
+    pub fn as_tuple(&self) -> (u16, u16, u16, u8, u8) {
+        (
+            self.major,
+            self.minor,
+            self.build,
+            self.version_type as u8,
+            self.type_number,
+        )
+    }
+
+    /// Check if this version is greater than or equal to another
+    pub fn is_gte(&self, other: &UnityVersion) -> bool {
+        self.as_tuple() >= other.as_tuple()
+    }
+
+    /// Check if this version is less than another
+    pub fn is_lt(&self, other: &UnityVersion) -> bool {
+        self.as_tuple() < other.as_tuple()
+    }
+
+    /// Check if this version supports a specific feature
+    pub fn supports_feature(&self, feature: UnityFeature) -> bool {
+        let caps = self.capabilities();
+        match feature {
+            UnityFeature::BigIds => caps.big_path_ids,
+            UnityFeature::TypeTreeEnabled => caps.typetree_enabled_by_default,
+            UnityFeature::ScriptTypeTree => caps.script_typetree,
+            UnityFeature::RefTypes => caps.ref_types,
+            UnityFeature::UnityFS => caps.unity_fs,
+            UnityFeature::LZ4Compression => caps.lz4,
+            UnityFeature::LZMACompression => caps.lzma,
+            UnityFeature::BrotliCompression => caps.brotli,
+            UnityFeature::ModernSerialization => caps.script_typetree,
+        }
+    }
+
+    /// Format capabilities of this version (see [`Capabilities`])
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::for_version(self)
+    }
+
+    /// Whether this is `major.minor` or later (build and release channel are ignored)
+    pub fn at_least(&self, major: u16, minor: u16) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+
+    /// Get the appropriate byte alignment for this version
+    pub fn get_alignment(&self) -> usize {
+        self.capabilities().alignment
+    }
+
+    /// Check if this version uses big endian by default
+    pub fn uses_big_endian(&self) -> bool {
+        // Most Unity versions use little endian, but some platforms/versions may differ
+        false
+    }
+
+    /// Get the serialized file format version for this Unity version
+    pub fn get_serialized_file_format_version(&self) -> u32 {
+        self.capabilities().serialized_file_format
+    }
+}
+
+impl fmt::Display for UnityVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.version_type == UnityVersionType::U {
+            let channel = self.type_str.as_deref().unwrap_or("u");
+            write!(
+                f,
+                "{}.{}.{}{}{}",
+                self.major, self.minor, self.build, channel, self.type_number
+            )
+        } else {
+            write!(
+                f,
+                "{}.{}.{}{}{}",
+                self.major, self.minor, self.build, self.version_type, self.type_number
+            )
+        }
+    }
+}
+
+fn split_leading_digits(s: &str) -> (&str, &str) {
+    let idx = s
+        .char_indices()
+        .find(|(_, ch)| !ch.is_ascii_digit())
+        .map(|(i, _)| i)
+        .unwrap_or_else(|| s.len());
+    s.split_at(idx)
+}
+
+fn split_trailing_number(s: &str) -> (&str, Option<u8>) {
+    let idx = s
+        .char_indices()
+        .rev()
+        .find(|(_, ch)| !ch.is_ascii_digit())
+        .map(|(i, ch)| i + ch.len_utf8())
+        .unwrap_or(0);
+    let (head, tail) = s.split_at(idx);
+    if tail.is_empty() {
+        return (head, Some(0));
+    }
+    let n = tail.parse::<u8>().ok();
+    (head, n)
+}
+
+impl PartialOrd for UnityVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for UnityVersion {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_tuple().cmp(&other.as_tuple())
+    }
+}
+
+/// Unity features that depend on version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnityFeature {
+    /// Support for 64-bit object IDs
+    BigIds,
+    /// TypeTree is enabled by default
+    TypeTreeEnabled,
+    /// Script type tree support
+    ScriptTypeTree,
+    /// Reference types support
+    RefTypes,
+    /// UnityFS format support
+    UnityFS,
+    /// LZ4 compression support
+    LZ4Compression,
+    /// LZMA compression support
+    LZMACompression,
+    /// Brotli compression support
+    BrotliCompression,
+    /// Modern serialization format
+    ModernSerialization,
+}
+
+/// Layout of the mesh vertex component format enum (`m_Channels[i].format`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum VertexFormatTable {
+    /// `VertexChannelFormat` (before 2017): Float, Float16, Color, Byte, UInt32
+    Legacy,
+    /// `VertexFormat2017` (2017.x-2018.x): the 2019 table with `Color` at index 2
+    V2017,
+    /// `VertexFormat` (2019+)
+    V2019,
+}
+
+/// Which Crunch codec wrote crunched texture data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrunchVariant {
+    /// The original crnlib format (before 2017.3)
+    Original,
+    /// Unity's Crunch fork (2017.3+), required by the ETC crunched formats
+    Unity,
+}
+
+/// What the parsers can expect from files written by one Unity version.
+///
+/// Every version-dependent layout decision goes through this table so modules agree on where a
+/// feature starts; use [`Capabilities::for_version`] (or [`UnityVersion::capabilities`]) instead
+/// of comparing `version.major` inline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// SerializedFile format version the editor typically writes
+    pub serialized_file_format: u32,
+    /// Byte alignment of serialized data
+    pub alignment: usize,
+    /// TypeTrees are written by default (4.5+)
+    pub typetree_enabled_by_default: bool,
+    /// TypeTrees use the flat node list + string buffer ("blob") encoding (5.0+)
+    pub typetree_blob: bool,
+    /// TypeTrees of `MonoBehaviour` script types are written (2018+)
+    pub script_typetree: bool,
+    /// Files carry SerializeReference ref types (2019+)
+    pub ref_types: bool,
+    /// 64-bit path ids in object references (2018.2+)
+    pub big_path_ids: bool,
+    /// UnityFS bundles (5.3+)
+    pub unity_fs: bool,
+    /// LZ4 block compression (5.3+)
+    pub lz4: bool,
+    /// LZMA compression (3.x+)
+    pub lzma: bool,
+    /// Brotli compression for WebGL builds (2020+)
+    pub brotli: bool,
+    /// Mesh vertex component format enum
+    pub vertex_format: VertexFormatTable,
+    /// UV sets in `m_VertexData.m_Channels` (2 before 5.0, 4 before 2018, then 8)
+    pub mesh_uv_sets: usize,
+    /// `m_Channels` uses the 2018 layout: position, normal, tangent, color, UV0..UV7, blend
+    /// weights, blend indices (older meshes keep skinning in `m_Skin`)
+    pub vertex_channels_2018: bool,
+    /// Blend shapes and compressed meshes (5.0+)
+    pub mesh_blend_shapes: bool,
+    /// Mesh optimization flags (2017+)
+    pub mesh_optimization: bool,
+    /// Mesh vertex data can be streamed from a `.resS` file (2017+)
+    pub streamed_mesh: bool,
+    /// Mesh usage flags (2018+)
+    pub mesh_usage_flags: bool,
+    /// Sprite borders and pixels-per-unit (5.0+)
+    pub sprite_border: bool,
+    /// SpriteAtlas assets and polygon sprites (2017+)
+    pub sprite_atlas: bool,
+    /// Sprite render meshes and physics shapes (2018+)
+    pub sprite_physics_shape: bool,
+    /// Sprite secondary textures (2019.2+)
+    pub sprite_secondary_textures: bool,
+    /// Textures may store a `GraphicsFormat` in `m_Format` instead of `m_TextureFormat` (2019+)
+    pub texture_graphics_format: bool,
+    /// ETC_RGB4Crunched / ETC2_RGBA8Crunched texture formats (2017.3+)
+    pub etc_crunched_formats: bool,
+    /// Crunch codec used for crunched textures
+    pub crunch_variant: CrunchVariant,
+    /// AudioClips reference FSB5 data through `m_Resource` rather than embedding `m_AudioData` (5.0+)
+    pub audio_fsb5: bool,
+    /// AudioClips have an `m_Ambisonic` flag (2017+)
+    pub audio_ambisonic: bool,
+    /// MP3 audio clips (4.x+)
+    pub audio_mp3: bool,
+    /// AAC audio clips (5.0+)
+    pub audio_aac: bool,
+}
+
+impl Capabilities {
+    /// Capabilities of files written by `version`
+    pub fn for_version(version: &UnityVersion) -> Self {
+        let at_least = |major: u16, minor: u16| version.at_least(major, minor);
+        let unity5 = at_least(5, 0);
+        let unity2017 = at_least(2017, 0);
+        let unity2018 = at_least(2018, 0);
+        let unity2019 = at_least(2019, 0);
+
+        let serialized_file_format = if at_least(2022, 0) {
+            22
+        } else if at_least(2020, 0) {
+            21
+        } else if unity2019 {
+            20
+        } else if unity2018 {
+            19
+        } else if unity2017 {
+            17
+        } else if unity5 {
+            15
+        } else {
+            10
+        };
+
+        Self {
+            serialized_file_format,
+            alignment: if at_least(2022, 0) { 8 } else { 4 },
+            typetree_enabled_by_default: at_least(4, 5),
+            typetree_blob: unity5,
+            script_typetree: unity2018,
+            ref_types: unity2019,
+            big_path_ids: at_least(2018, 2),
+            unity_fs: at_least(5, 3),
+            lz4: at_least(5, 3),
+            lzma: at_least(3, 0),
+            brotli: at_least(2020, 0),
+            vertex_format: if unity2019 {
+                VertexFormatTable::V2019
+            } else if unity2017 {
+                VertexFormatTable::V2017
+            } else {
+                VertexFormatTable::Legacy
+            },
+            mesh_uv_sets: if unity2018 {
+                8
+            } else if unity5 {
+                4
+            } else {
+                2
+            },
+            vertex_channels_2018: unity2018,
+            mesh_blend_shapes: unity5,
+            mesh_optimization: unity2017,
+            streamed_mesh: unity2017,
+            mesh_usage_flags: unity2018,
+            sprite_border: unity5,
+            sprite_atlas: unity2017,
+            sprite_physics_shape: unity2018,
+            sprite_secondary_textures: at_least(2019, 2),
+            texture_graphics_format: unity2019,
+            etc_crunched_formats: at_least(2017, 3),
+            crunch_variant: if at_least(2017, 3) {
+                CrunchVariant::Unity
+            } else {
+                CrunchVariant::Original
+            },
+            audio_fsb5: unity5,
+            audio_ambisonic: unity2017,
+            audio_mp3: at_least(4, 0),
+            audio_aac: unity5,
+        }
+    }
+}
+
+/// Unity version compatibility checker
+pub struct VersionCompatibility;
+
+impl VersionCompatibility {
+    /// Check if a version is supported by this parser
+    pub fn is_supported(version: &UnityVersion) -> bool {
+        // We support Unity 3.4 to 2023.x
+        version.major >= 3 && version.major <= 2023
+    }
+
+    /// Get recommended settings for a Unity version
+    pub fn get_recommended_settings(version: &UnityVersion) -> VersionSettings {
+        let caps = version.capabilities();
+        VersionSettings {
+            use_type_tree: caps.typetree_enabled_by_default,
+            alignment: caps.alignment,
+            big_endian: version.uses_big_endian(),
+            supports_big_ids: caps.big_path_ids,
+            supports_ref_types: caps.ref_types,
+            serialized_file_format: caps.serialized_file_format,
+        }
+    }
+
+    /// Get a list of known Unity versions for testing
+    pub fn get_known_versions() -> Vec<UnityVersion> {
+        vec![
+            UnityVersion::parse_version("3.4.0f5").unwrap(),
+            UnityVersion::parse_version("4.7.2f1").unwrap(),
+            UnityVersion::parse_version("5.0.0f4").unwrap(),
+            UnityVersion::parse_version("5.6.7f1").unwrap(),
+            UnityVersion::parse_version("2017.4.40f1").unwrap(),
+            UnityVersion::parse_version("2018.4.36f1").unwrap(),
+            UnityVersion::parse_version("2019.4.40f1").unwrap(),
+            UnityVersion::parse_version("2020.3.48f1").unwrap(),
+            UnityVersion::parse_version("2021.3.21f1").unwrap(),
+            UnityVersion::parse_version("2022.3.21f1").unwrap(),
+            UnityVersion::parse_version("2023.2.20f1").unwrap(),
+        ]
+    }
+}
+
+/// Version-specific settings
+#[derive(Debug, Clone)]
+pub struct VersionSettings {
+    pub use_type_tree: bool,
+    pub alignment: usize,
+    pub big_endian: bool,
+    pub supports_big_ids: bool,
+    pub supports_ref_types: bool,
+    pub serialized_file_format: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_parsing() {
+        let version = UnityVersion::parse_version("2020.3.12f1").unwrap();
+        assert_eq!(version.major, 2020);
+        assert_eq!(version.minor, 3);
+        assert_eq!(version.build, 12);
+        assert_eq!(version.version_type, UnityVersionType::F);
+        assert_eq!(version.type_number, 1);
+    }
+
+    #[test]
+    fn test_version_comparison() {
+        let v1 = UnityVersion::parse_version("2020.3.12f1").unwrap();
+        let v2 = UnityVersion::parse_version("2021.1.0f1").unwrap();
+
+        assert!(v1 < v2);
+        assert!(v2.is_gte(&v1));
+        assert!(v1.is_lt(&v2));
+    }
+
+    #[test]
+    fn test_feature_support() {
+        let old_version = UnityVersion::parse_version("5.0.0f1").unwrap();
+        let unity_fs_version = UnityVersion::parse_version("5.3.0f1").unwrap();
+        let new_version = UnityVersion::parse_version("2020.3.12f1").unwrap();
+
+        assert!(!old_version.supports_feature(UnityFeature::BigIds));
+        assert!(new_version.supports_feature(UnityFeature::BigIds));
+
+        // Unity 5.0 doesn't support UnityFS (introduced in 5.3)
+        assert!(!old_version.supports_feature(UnityFeature::UnityFS));
+        assert!(unity_fs_version.supports_feature(UnityFeature::UnityFS));
+        assert!(new_version.supports_feature(UnityFeature::UnityFS));
+    }
+
+    #[test]
+    fn test_capability_matrix() {
+        use CrunchVariant::{Original, Unity};
+        use VertexFormatTable::{Legacy, V2017, V2019};
+
+        // (version, serialized format, UnityFS, big path ids, vertex format, UV sets,
+        //  sprite atlas, secondary textures, graphics format, crunch, FSB5, ref types)
+        #[rustfmt::skip]
+        let matrix = [
+            ("3.4.0f5",     10, false, false, Legacy, 2, false, false, false, Original, false, false),
+            ("4.5.0f6",     10, false, false, Legacy, 2, false, false, false, Original, false, false),
+            ("5.0.0f4",     15, false, false, Legacy, 4, false, false, false, Original, true,  false),
+            ("5.3.4f1",     15, true,  false, Legacy, 4, false, false, false, Original, true,  false),
+            ("5.6.7f1",     15, true,  false, Legacy, 4, false, false, false, Original, true,  false),
+            ("2017.1.0f3",  17, true,  false, V2017,  4, true,  false, false, Original, true,  false),
+            ("2017.3.0f3",  17, true,  false, V2017,  4, true,  false, false, Unity,    true,  false),
+            ("2018.1.0f2",  19, true,  false, V2017,  8, true,  false, false, Unity,    true,  false),
+            ("2018.2.0f2",  19, true,  true,  V2017,  8, true,  false, false, Unity,    true,  false),
+            ("2019.1.0f2",  20, true,  true,  V2019,  8, true,  false, true,  Unity,    true,  true),
+            ("2019.2.0f1",  20, true,  true,  V2019,  8, true,  true,  true,  Unity,    true,  true),
+            ("2020.1.0f1",  21, true,  true,  V2019,  8, true,  true,  true,  Unity,    true,  true),
+            ("2022.3.21f1", 22, true,  true,  V2019,  8, true,  true,  true,  Unity,    true,  true),
+        ];
+
+        for (
+            version,
+            format,
+            unity_fs,
+            big_ids,
+            vertex_format,
+            uv_sets,
+            sprite_atlas,
+            secondary,
+            graphics_format,
+            crunch,
+            fsb5,
+            ref_types,
+        ) in matrix
+        {
+            let parsed = UnityVersion::parse_version(version).unwrap();
+            let caps = Capabilities::for_version(&parsed);
+            let actual = (
+                caps.serialized_file_format,
+                caps.unity_fs,
+                caps.big_path_ids,
+                caps.vertex_format,
+                caps.mesh_uv_sets,
+                caps.sprite_atlas,
+                caps.sprite_secondary_textures,
+                caps.texture_graphics_format,
+                caps.crunch_variant,
+                caps.audio_fsb5,
+                caps.ref_types,
+            );
+            let expected = (
+                format,
+                unity_fs,
+                big_ids,
+                vertex_format,
+                uv_sets,
+                sprite_atlas,
+                secondary,
+                graphics_format,
+                crunch,
+                fsb5,
+                ref_types,
+            );
+            assert_eq!(actual, expected, "{}", version);
+
+            // The feature query agrees with the table.
+            assert_eq!(parsed.supports_feature(UnityFeature::UnityFS), unity_fs);
+            assert_eq!(parsed.supports_feature(UnityFeature::BigIds), big_ids);
+            assert_eq!(parsed.get_serialized_file_format_version(), format);
+        }
+    }
+
+    #[test]
+    fn test_version_display() {
+        let version = UnityVersion::parse_version("2020.3.12f1").unwrap();
+        assert_eq!(version.to_string(), "2020.3.12f1");
+    }
+
+    #[test]
+    fn test_unitycn_suffix_parsing() {
+        let version = UnityVersion::parse_version("2022.3.48f1c1").unwrap();
+        assert_eq!(version.major, 2022);
+        assert_eq!(version.minor, 3);
+        assert_eq!(version.build, 48);
+        assert_eq!(version.version_type, UnityVersionType::U);
+        assert_eq!(version.type_number, 1);
+        assert_eq!(version.type_str.as_deref(), Some("f1c"));
+        assert_eq!(version.to_string(), "2022.3.48f1c1");
+    }
+
+    #[test]
+    fn test_tuanjie_channel_parsing() {
+        let version = UnityVersion::parse_version("2022.3.48t6").unwrap();
+        assert_eq!(version.major, 2022);
+        assert_eq!(version.minor, 3);
+        assert_eq!(version.build, 48);
+        assert_eq!(version.version_type, UnityVersionType::U);
+        assert_eq!(version.type_number, 6);
+        assert_eq!(version.type_str.as_deref(), Some("t"));
+        assert_eq!(version.to_string(), "2022.3.48t6");
+    }
+
+    #[test]
+    fn test_version_parsing_ignores_revision_suffix() {
+        let version = UnityVersion::parse_version("2022.3.48t6 (b281c1694403)").unwrap();
+        assert_eq!(version.to_string(), "2022.3.48t6");
+    }
+
+    #[test]
+    fn test_project_version_txt_parsing() {
+        let text =
+            "m_EditorVersion: 2021.3.5f1\nm_EditorVersionWithRevision: 2021.3.5f1 (40eb3a945986)\n";
+        let version = UnityVersion::parse_project_version(text).unwrap();
+        assert_eq!(version.to_string(), "2021.3.5f1");
+
+        let revision_only = "m_EditorVersionWithRevision: 2022.3.10f1 (ff3792e53c62)\n";
+        let version = UnityVersion::parse_project_version(revision_only).unwrap();
+        assert_eq!(version.to_string(), "2022.3.10f1");
+
+        assert!(UnityVersion::parse_project_version("foo: bar\n").is_err());
+    }
+
+    #[test]
+    fn test_compatibility_check() {
+        let supported = UnityVersion::parse_version("2020.3.12f1").unwrap();
+        let unsupported = UnityVersion::parse_version("2.0.0f1").unwrap();
+
+        assert!(VersionCompatibility::is_supported(&supported));
+        assert!(!VersionCompatibility::is_supported(&unsupported));
+    }
+}
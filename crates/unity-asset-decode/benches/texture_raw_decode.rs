@@ -0,0 +1,86 @@
+//! `TextureDecoder::decode` vs `decode_raw` on a 4096x4096 texture
+//!
+//! Both paths produce the same upload buffer: BGRA8 from a BGRA32 texture (`decode` has to
+//! swizzle the `RgbaImage` back, `decode_raw(.., PixelLayout::Bgra8)` copies the stored bytes)
+//! and RGBA8 from an RGBA32 texture. Before timing, the heap traffic of one call per path is
+//! printed via the `profiling` [`CountingAllocator`].
+//!
+//! Run with: `cargo bench -p unity-asset-decode --features texture --bench texture_raw_decode`
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use unity_asset_binary::profiling::{CountingAllocator, measure_allocations};
+use unity_asset_decode::texture::{PixelLayout, Texture2D, TextureDecoder, TextureFormat};
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator::new();
+
+const SIZE: i32 = 4096;
+
+fn texture(format: TextureFormat) -> Texture2D {
+    let mut texture = Texture2D::new("bench".to_string(), SIZE, SIZE, format);
+    texture.image_data = (0..(SIZE * SIZE * 4) as usize)
+        .map(|i| (i % 251) as u8)
+        .collect();
+    texture
+}
+
+fn via_image(decoder: &TextureDecoder, texture: &Texture2D, target: PixelLayout) -> Vec<u8> {
+    let mut data = decoder.decode(texture).unwrap().into_raw();
+    if target == PixelLayout::Bgra8 {
+        for px in data.chunks_exact_mut(4) {
+            px.swap(0, 2);
+        }
+    }
+    data
+}
+
+fn via_raw(decoder: &TextureDecoder, texture: &Texture2D, target: PixelLayout) -> Vec<u8> {
+    decoder.decode_raw(texture, target).unwrap().data
+}
+
+fn report(label: &str, f: impl FnOnce() -> Vec<u8>) {
+    let (data, stats) = measure_allocations(f);
+    println!(
+        "{label}: {} bytes out, {} allocations, {} bytes allocated, peak {} bytes live",
+        data.len(),
+        stats.allocations,
+        stats.bytes_allocated,
+        stats.peak_live_bytes
+    );
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let decoder = TextureDecoder::new();
+
+    for (format, target, name) in [
+        (TextureFormat::BGRA32, PixelLayout::Bgra8, "bgra32_to_bgra8"),
+        (TextureFormat::RGBA32, PixelLayout::Rgba8, "rgba32_to_rgba8"),
+    ] {
+        let texture = texture(format);
+        assert_eq!(
+            via_image(&decoder, &texture, target),
+            via_raw(&decoder, &texture, target)
+        );
+
+        report(&format!("{name}/decode"), || {
+            via_image(&decoder, &texture, target)
+        });
+        report(&format!("{name}/decode_raw"), || {
+            via_raw(&decoder, &texture, target)
+        });
+
+        let mut group = c.benchmark_group(format!("4096x4096/{name}"));
+        group.sample_size(10);
+        group.bench_function("decode", |b| {
+            b.iter(|| via_image(&decoder, black_box(&texture), target))
+        });
+        group.bench_function("decode_raw", |b| {
+            b.iter(|| via_raw(&decoder, black_box(&texture), target))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_decode);
+criterion_main!(benches);
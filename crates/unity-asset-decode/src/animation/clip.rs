@@ -0,0 +1,440 @@
+//! AnimationClip (class 74) curve extraction
+//!
+//! Clips built for Mecanim keep their curves in `m_MuscleClip.m_Clip`, split three ways: a
+//! streamed clip of Hermite segments, a dense clip of evenly spaced samples and a constant clip
+//! of single values. Curve indices run through the three in that order and map onto
+//! `m_ClipBindingConstant.genericBindings`, where transform position, scale and euler bindings
+//! take three curves and rotation takes four. Legacy clips instead keep editor-style curves
+//! (`m_PositionCurves`, `m_FloatCurves`, ...), which carry their paths and property names.
+//!
+//! Muscle clip bindings only store CRC32 hashes of the transform path, and of the property for
+//! non-transform bindings; [`AnimationClip::resolve_paths`] names them from an Avatar's
+//! `m_TOS` or any other hash table. Humanoid muscle curves are left as raw values.
+
+use super::curve::{AnimationCurve, Keyframe, WeightedMode, WrapMode};
+use super::streamed::read_streamed_frames;
+use crate::error::{BinaryError, Result};
+use crate::object::UnityObject;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use unity_asset_core::{UnityValue, class_ids};
+
+type Fields = IndexMap<String, UnityValue>;
+
+const XYZ: &[&str] = &["x", "y", "z"];
+const XYZW: &[&str] = &["x", "y", "z", "w"];
+
+/// One animated float: a property of the object at `path`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClipCurve {
+    /// CRC32 of the path, relative to the animated root.
+    pub path_hash: u32,
+    /// Transform path, when known (always for legacy curves).
+    pub path: Option<String>,
+    /// Class of the animated component (4 for Transform).
+    pub class_id: i32,
+    /// Property name such as `m_LocalPosition.x`; `0x<hash>` for unresolved attribute hashes.
+    pub property: String,
+    pub curve: AnimationCurve,
+}
+
+/// A parsed AnimationClip (class 74).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnimationClip {
+    pub name: String,
+    pub curves: Vec<ClipCurve>,
+    /// Duration in seconds.
+    pub length: f32,
+    pub sample_rate: f32,
+    /// Whether the curves came from the legacy editor-style lists.
+    pub legacy: bool,
+}
+
+/// A float curve binding of the muscle clip.
+#[derive(Clone, Copy)]
+struct Binding {
+    path_hash: u32,
+    attribute: u32,
+    class_id: i32,
+}
+
+impl AnimationClip {
+    /// Parse AnimationClip from UnityObject
+    pub fn from_unity_object(obj: &UnityObject) -> Result<Self> {
+        if obj.class_id() != class_ids::ANIMATION_CLIP {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not an AnimationClip (class_id: {})",
+                obj.class_id()
+            )));
+        }
+        Self::from_typetree(obj.class.properties())
+    }
+
+    /// Parse AnimationClip from TypeTree data
+    ///
+    /// Uses the muscle clip when it has any curve, the editor-style lists otherwise.
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        let mut clip = Self {
+            name: string(properties, "m_Name"),
+            sample_rate: float(properties, "m_SampleRate"),
+            ..Default::default()
+        };
+
+        let muscle = properties.get("m_MuscleClip").and_then(deref);
+        let muscle_curves = match muscle {
+            Some(muscle) => muscle_curves(muscle, properties)?,
+            None => Vec::new(),
+        };
+        match muscle {
+            Some(muscle) if !muscle_curves.is_empty() => {
+                clip.curves = muscle_curves;
+                clip.length = float(muscle, "m_StopTime") - float(muscle, "m_StartTime");
+            }
+            _ => {
+                clip.legacy = true;
+                clip.curves = editor_curves(properties);
+                clip.length = clip
+                    .curves
+                    .iter()
+                    .filter_map(|c| c.curve.time_range())
+                    .map(|(_, end)| end)
+                    .fold(0.0, f32::max);
+            }
+        }
+        Ok(clip)
+    }
+
+    /// Name paths (and property hashes) found in `names`, e.g. an Avatar's `paths`
+    pub fn resolve_paths(&mut self, names: &HashMap<u32, String>) {
+        for curve in &mut self.curves {
+            if curve.path.is_none() {
+                curve.path = names.get(&curve.path_hash).cloned();
+            }
+            if let Some(hash) = curve
+                .property
+                .strip_prefix("0x")
+                .and_then(|h| u32::from_str_radix(h, 16).ok())
+                && let Some(name) = names.get(&hash)
+            {
+                curve.property = name.clone();
+            }
+        }
+    }
+
+    /// Curves animating `path`
+    pub fn curves_for_path<'a>(&'a self, path: &'a str) -> impl Iterator<Item = &'a ClipCurve> {
+        self.curves
+            .iter()
+            .filter(move |c| c.path.as_deref() == Some(path))
+    }
+
+    /// The clip as JSON, for diffing and export (infinite slopes become `null`)
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// CRC32 of a transform path, as stored in bindings and `m_TOS`
+pub fn path_hash(path: &str) -> u32 {
+    crc32fast::hash(path.as_bytes())
+}
+
+fn muscle_curves(muscle: &Fields, properties: &Fields) -> Result<Vec<ClipCurve>> {
+    let Some(clip) = muscle.get("m_Clip").and_then(deref) else {
+        return Ok(Vec::new());
+    };
+    let streamed = clip.get("m_StreamedClip").and_then(deref);
+    let dense = clip.get("m_DenseClip").and_then(deref);
+    let constant = clip.get("m_ConstantClip").and_then(deref);
+
+    let streamed_count = streamed.map_or(0, |s| uint(s, "curveCount") as usize);
+    let dense_count = dense.map_or(0, |d| uint(d, "m_CurveCount") as usize);
+    let constant_values = constant.map_or_else(Vec::new, |c| floats(c.get("data")));
+    let total = streamed_count + dense_count + constant_values.len();
+    let mut keys: Vec<Vec<Keyframe>> = vec![Vec::new(); total];
+
+    if let Some(streamed) = streamed {
+        let words = uints(streamed.get("data"));
+        let frames = read_streamed_frames(&words)?;
+        for frame in frames.iter().take(frames.len().saturating_sub(1)).skip(2) {
+            for key in &frame.keys {
+                let Some(curve) = keys[..streamed_count].get_mut(key.index) else {
+                    return Err(BinaryError::invalid_data(format!(
+                        "Streamed key for curve {} but the clip has {} streamed curves",
+                        key.index, streamed_count
+                    )));
+                };
+                curve.push(Keyframe::with_slopes(
+                    frame.time,
+                    key.value(),
+                    key.in_slope,
+                    key.out_slope(),
+                ));
+            }
+        }
+    }
+
+    if let Some(dense) = dense.filter(|_| dense_count > 0) {
+        let samples = floats(dense.get("m_SampleArray"));
+        let frame_count = (uint(dense, "m_FrameCount") as usize).min(samples.len() / dense_count);
+        let rate = float(dense, "m_SampleRate");
+        let begin = float(dense, "m_BeginTime");
+        let step = if rate > 0.0 { 1.0 / rate } else { 0.0 };
+        for curve in 0..dense_count {
+            let value = |frame: usize| samples[frame * dense_count + curve];
+            let slope = |a: usize, b: usize| {
+                if step > 0.0 {
+                    (value(b) - value(a)) / step
+                } else {
+                    0.0
+                }
+            };
+            // Dense samples are interpolated linearly.
+            keys[streamed_count + curve] = (0..frame_count)
+                .map(|frame| {
+                    let in_slope = if frame > 0 {
+                        slope(frame - 1, frame)
+                    } else {
+                        0.0
+                    };
+                    let out_slope = if frame + 1 < frame_count {
+                        slope(frame, frame + 1)
+                    } else {
+                        0.0
+                    };
+                    Keyframe::with_slopes(
+                        begin + frame as f32 * step,
+                        value(frame),
+                        in_slope,
+                        out_slope,
+                    )
+                })
+                .collect();
+        }
+    }
+
+    let (start, stop) = (float(muscle, "m_StartTime"), float(muscle, "m_StopTime"));
+    for (i, value) in constant_values.into_iter().enumerate() {
+        keys[streamed_count + dense_count + i] = vec![
+            Keyframe::new(start, value, 0.0),
+            Keyframe::new(stop, value, 0.0),
+        ];
+    }
+
+    let bindings = bindings(properties);
+    Ok(keys
+        .into_iter()
+        .enumerate()
+        .map(|(index, keys)| {
+            let (path_hash, class_id, property) = match bindings.get(index) {
+                Some((binding, component)) => (
+                    binding.path_hash,
+                    binding.class_id,
+                    property_name(binding, *component),
+                ),
+                None => (0, 0, format!("curve_{}", index)),
+            };
+            ClipCurve {
+                path_hash,
+                // The root's path is empty, and CRC32("") is 0.
+                path: (path_hash == 0).then(String::new),
+                class_id,
+                property,
+                curve: AnimationCurve::new(keys),
+            }
+        })
+        .collect())
+}
+
+/// The binding and component of each curve index.
+fn bindings(properties: &Fields) -> Vec<(Binding, usize)> {
+    let Some(constant) = properties.get("m_ClipBindingConstant").and_then(deref) else {
+        return Vec::new();
+    };
+    let Some(UnityValue::Array(items)) = constant.get("genericBindings") else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    for binding in items.iter().filter_map(deref) {
+        if binding
+            .get("isPPtrCurve")
+            .and_then(UnityValue::as_i64)
+            .unwrap_or(0)
+            != 0
+        {
+            continue;
+        }
+        let binding = Binding {
+            path_hash: uint(binding, "path"),
+            attribute: uint(binding, "attribute"),
+            class_id: binding
+                .get("typeID")
+                .or_else(|| binding.get("classID"))
+                .and_then(UnityValue::as_i64)
+                .unwrap_or(0) as i32,
+        };
+        let components = transform_components(&binding).map_or(1, |(_, c)| c.len());
+        out.extend((0..components).map(|component| (binding, component)));
+    }
+    out
+}
+
+/// Property and components of a transform binding (`kBindTransform*`).
+fn transform_components(binding: &Binding) -> Option<(&'static str, &'static [&'static str])> {
+    if binding.class_id != class_ids::TRANSFORM {
+        return None;
+    }
+    match binding.attribute {
+        1 => Some(("m_LocalPosition", XYZ)),
+        2 => Some(("m_LocalRotation", XYZW)),
+        3 => Some(("m_LocalScale", XYZ)),
+        4 => Some(("localEulerAnglesRaw", XYZ)),
+        _ => None,
+    }
+}
+
+fn property_name(binding: &Binding, component: usize) -> String {
+    match transform_components(binding) {
+        Some((property, components)) => format!("{}.{}", property, components[component]),
+        None => format!("0x{:08x}", binding.attribute),
+    }
+}
+
+/// Curves of the legacy `m_*Curves` lists, one per component.
+fn editor_curves(properties: &Fields) -> Vec<ClipCurve> {
+    let mut out = Vec::new();
+    for (list, property, components) in [
+        ("m_RotationCurves", "m_LocalRotation", XYZW),
+        ("m_EulerCurves", "localEulerAnglesRaw", XYZ),
+        ("m_PositionCurves", "m_LocalPosition", XYZ),
+        ("m_ScaleCurves", "m_LocalScale", XYZ),
+    ] {
+        for entry in objects(properties.get(list)) {
+            let Some(curve) = entry.get("m_Curve").and_then(deref) else {
+                continue;
+            };
+            let path = string(entry, "m_Path");
+            for component in components {
+                out.push(ClipCurve {
+                    path_hash: path_hash(&path),
+                    path: Some(path.clone()),
+                    class_id: class_ids::TRANSFORM,
+                    property: format!("{}.{}", property, component),
+                    curve: component_curve(curve, Some(component)),
+                });
+            }
+        }
+    }
+    for entry in objects(properties.get("m_FloatCurves")) {
+        let Some(curve) = entry.get("curve").and_then(deref) else {
+            continue;
+        };
+        let path = string(entry, "path");
+        out.push(ClipCurve {
+            path_hash: path_hash(&path),
+            path: Some(path),
+            class_id: entry
+                .get("classID")
+                .and_then(UnityValue::as_i64)
+                .unwrap_or(0) as i32,
+            property: string(entry, "attribute"),
+            curve: component_curve(curve, None),
+        });
+    }
+    out
+}
+
+/// One component of an `AnimationCurve<Vector3f/Quaternionf>`, or a float curve as is.
+fn component_curve(curve: &Fields, component: Option<&str>) -> AnimationCurve {
+    let get = |key: &Fields, field: &str, default: f32| {
+        let value = key.get(field);
+        let value = match (value, component) {
+            (Some(UnityValue::Object(v)), Some(c)) => v.get(c),
+            (value, _) => value,
+        };
+        value
+            .and_then(UnityValue::as_f64)
+            .map_or(default, |v| v as f32)
+    };
+    let keys = objects(curve.get("m_Curve"))
+        .map(|k| {
+            Keyframe::with_slopes(
+                get(k, "time", 0.0),
+                get(k, "value", 0.0),
+                get(k, "inSlope", 0.0),
+                get(k, "outSlope", 0.0),
+            )
+            .with_weights(
+                WeightedMode::from_i32(
+                    k.get("weightedMode")
+                        .and_then(UnityValue::as_i64)
+                        .unwrap_or(0) as i32,
+                ),
+                get(k, "inWeight", 1.0 / 3.0),
+                get(k, "outWeight", 1.0 / 3.0),
+            )
+        })
+        .collect();
+    let wrap = |key: &str| {
+        WrapMode::from_serialized(curve.get(key).and_then(UnityValue::as_i64).unwrap_or(2) as i32)
+    };
+    AnimationCurve::new(keys)
+        .with_pre_wrap(wrap("m_PreInfinity"))
+        .with_post_wrap(wrap("m_PostInfinity"))
+}
+
+/// The pointee of an `OffsetPtr` (`{ data: ... }`), or the object itself.
+fn deref(value: &UnityValue) -> Option<&Fields> {
+    let fields = value.as_object()?;
+    match fields.get("data") {
+        Some(UnityValue::Object(data)) if fields.len() == 1 => Some(data),
+        _ => Some(fields),
+    }
+}
+
+fn objects(value: Option<&UnityValue>) -> impl Iterator<Item = &Fields> {
+    let items: &[UnityValue] = match value {
+        Some(UnityValue::Array(items)) => items,
+        _ => &[],
+    };
+    items.iter().filter_map(deref)
+}
+
+fn string(fields: &Fields, key: &str) -> String {
+    fields
+        .get(key)
+        .and_then(UnityValue::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn float(fields: &Fields, key: &str) -> f32 {
+    fields.get(key).and_then(UnityValue::as_f64).unwrap_or(0.0) as f32
+}
+
+fn uint(fields: &Fields, key: &str) -> u32 {
+    fields.get(key).and_then(UnityValue::as_i64).unwrap_or(0) as u32
+}
+
+fn floats(value: Option<&UnityValue>) -> Vec<f32> {
+    match value {
+        Some(UnityValue::Array(items)) => items
+            .iter()
+            .filter_map(UnityValue::as_f64)
+            .map(|v| v as f32)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn uints(value: Option<&UnityValue>) -> Vec<u32> {
+    match value {
+        Some(UnityValue::Array(items)) => items
+            .iter()
+            .filter_map(UnityValue::as_i64)
+            .map(|v| v as u32)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,355 @@
+//! AnimationCurve types and evaluation
+//!
+//! Segments between keys are cubic Hermite splines over the key tangents. When either end of a
+//! segment is weighted the segment becomes a cubic Bezier whose control points sit `weight` of
+//! the segment duration along each tangent; the default weight of 1/3 gives back the Hermite
+//! curve. An infinite tangent on either end makes the segment hold the left key's value.
+
+use crate::error::{BinaryError, Result};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use unity_asset_core::UnityValue;
+
+/// Default tangent weight; makes a weighted segment identical to the unweighted one.
+const DEFAULT_WEIGHT: f32 = 1.0 / 3.0;
+
+/// Which tangents of a key are weighted (`weightedMode`, 2018.1+).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeightedMode {
+    #[default]
+    None,
+    In,
+    Out,
+    Both,
+}
+
+impl WeightedMode {
+    pub fn from_i32(value: i32) -> Self {
+        match value {
+            1 => Self::In,
+            2 => Self::Out,
+            3 => Self::Both,
+            _ => Self::None,
+        }
+    }
+
+    pub fn has_in(self) -> bool {
+        matches!(self, Self::In | Self::Both)
+    }
+
+    pub fn has_out(self) -> bool {
+        matches!(self, Self::Out | Self::Both)
+    }
+}
+
+/// How a curve continues before its first and after its last key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WrapMode {
+    /// Hold the first/last key value.
+    #[default]
+    Clamp,
+    /// Repeat the keyed range.
+    Loop,
+    /// Repeat the keyed range, alternating forwards and backwards.
+    PingPong,
+}
+
+impl WrapMode {
+    /// From the serialized `m_PreInfinity`/`m_PostInfinity` value (`0` ping-pong, `1` loop,
+    /// `2` clamp).
+    pub fn from_serialized(value: i32) -> Self {
+        match value {
+            0 => Self::PingPong,
+            1 => Self::Loop,
+            _ => Self::Clamp,
+        }
+    }
+}
+
+/// One key of an [`AnimationCurve`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe {
+    pub time: f32,
+    pub value: f32,
+    /// Incoming tangent (`inSlope`); infinite for a stepped segment.
+    pub in_slope: f32,
+    /// Outgoing tangent (`outSlope`); infinite for a stepped segment.
+    pub out_slope: f32,
+    pub weighted_mode: WeightedMode,
+    pub in_weight: f32,
+    pub out_weight: f32,
+}
+
+impl Keyframe {
+    /// Unweighted key with both tangents set to `slope`.
+    pub fn new(time: f32, value: f32, slope: f32) -> Self {
+        Self::with_slopes(time, value, slope, slope)
+    }
+
+    pub fn with_slopes(time: f32, value: f32, in_slope: f32, out_slope: f32) -> Self {
+        Self {
+            time,
+            value,
+            in_slope,
+            out_slope,
+            weighted_mode: WeightedMode::None,
+            in_weight: DEFAULT_WEIGHT,
+            out_weight: DEFAULT_WEIGHT,
+        }
+    }
+
+    pub fn with_weights(mut self, mode: WeightedMode, in_weight: f32, out_weight: f32) -> Self {
+        self.weighted_mode = mode;
+        self.in_weight = in_weight;
+        self.out_weight = out_weight;
+        self
+    }
+}
+
+/// A keyframed float curve.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AnimationCurve {
+    /// Keys sorted by time.
+    pub keys: Vec<Keyframe>,
+    pub pre_wrap: WrapMode,
+    pub post_wrap: WrapMode,
+}
+
+impl AnimationCurve {
+    /// Create a curve, sorting the keys by time.
+    pub fn new(mut keys: Vec<Keyframe>) -> Self {
+        keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Self {
+            keys,
+            pre_wrap: WrapMode::Clamp,
+            post_wrap: WrapMode::Clamp,
+        }
+    }
+
+    /// Straight line between two keys (`AnimationCurve.Linear`).
+    pub fn linear(time_start: f32, value_start: f32, time_end: f32, value_end: f32) -> Self {
+        let slope = if time_end == time_start {
+            0.0
+        } else {
+            (value_end - value_start) / (time_end - time_start)
+        };
+        Self::new(vec![
+            Keyframe::new(time_start, value_start, slope),
+            Keyframe::new(time_end, value_end, slope),
+        ])
+    }
+
+    /// Smooth step between two keys with flat tangents (`AnimationCurve.EaseInOut`).
+    pub fn ease_in_out(time_start: f32, value_start: f32, time_end: f32, value_end: f32) -> Self {
+        Self::new(vec![
+            Keyframe::new(time_start, value_start, 0.0),
+            Keyframe::new(time_end, value_end, 0.0),
+        ])
+    }
+
+    pub fn with_pre_wrap(mut self, mode: WrapMode) -> Self {
+        self.pre_wrap = mode;
+        self
+    }
+
+    pub fn with_post_wrap(mut self, mode: WrapMode) -> Self {
+        self.post_wrap = mode;
+        self
+    }
+
+    /// Parse from an `AnimationCurve` typetree object (`m_Curve`, `m_PreInfinity`,
+    /// `m_PostInfinity`).
+    pub fn from_typetree(properties: &IndexMap<String, UnityValue>) -> Result<Self> {
+        let Some(UnityValue::Array(keys)) = properties.get("m_Curve") else {
+            return Err(BinaryError::invalid_data("AnimationCurve has no m_Curve"));
+        };
+        let float = |k: &IndexMap<String, UnityValue>, key: &str, default: f32| {
+            k.get(key)
+                .and_then(UnityValue::as_f64)
+                .map_or(default, |v| v as f32)
+        };
+        let int = |k: &IndexMap<String, UnityValue>, key: &str, default: i32| {
+            k.get(key)
+                .and_then(UnityValue::as_i64)
+                .map_or(default, |v| v as i32)
+        };
+        let keys = keys
+            .iter()
+            .filter_map(UnityValue::as_object)
+            .map(|k| Keyframe {
+                time: float(k, "time", 0.0),
+                value: float(k, "value", 0.0),
+                in_slope: float(k, "inSlope", 0.0),
+                out_slope: float(k, "outSlope", 0.0),
+                weighted_mode: WeightedMode::from_i32(int(k, "weightedMode", 0)),
+                in_weight: float(k, "inWeight", DEFAULT_WEIGHT),
+                out_weight: float(k, "outWeight", DEFAULT_WEIGHT),
+            })
+            .collect();
+        Ok(Self::new(keys)
+            .with_pre_wrap(WrapMode::from_serialized(int(
+                properties,
+                "m_PreInfinity",
+                2,
+            )))
+            .with_post_wrap(WrapMode::from_serialized(int(
+                properties,
+                "m_PostInfinity",
+                2,
+            ))))
+    }
+
+    /// Time of the first and last key, or `None` for an empty curve.
+    pub fn time_range(&self) -> Option<(f32, f32)> {
+        Some((self.keys.first()?.time, self.keys.last()?.time))
+    }
+
+    /// Sample the curve at `time`. An empty curve evaluates to `0`.
+    pub fn evaluate(&self, time: f32) -> f32 {
+        let (first, last) = match self.keys.as_slice() {
+            [] => return 0.0,
+            [key] => return key.value,
+            [first, .., last] => (first, last),
+        };
+
+        let time = if time < first.time {
+            match self.pre_wrap {
+                WrapMode::Clamp => return first.value,
+                mode => wrap(mode, time, first.time, last.time),
+            }
+        } else if time > last.time {
+            match self.post_wrap {
+                WrapMode::Clamp => return last.value,
+                mode => wrap(mode, time, first.time, last.time),
+            }
+        } else {
+            time
+        };
+
+        let right = self
+            .keys
+            .partition_point(|k| k.time <= time)
+            .clamp(1, self.keys.len() - 1);
+        interpolate(&self.keys[right - 1], &self.keys[right], time)
+    }
+
+    /// `steps` evenly spaced samples from `start` to `end` inclusive, for plotting.
+    pub fn sample_range(&self, start: f32, end: f32, steps: usize) -> Vec<f32> {
+        match steps {
+            0 => Vec::new(),
+            1 => vec![self.evaluate(start)],
+            _ => {
+                let step = (end - start) / (steps - 1) as f32;
+                (0..steps)
+                    .map(|i| self.evaluate(start + step * i as f32))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Map a time outside `[first, last]` back into it.
+fn wrap(mode: WrapMode, time: f32, first: f32, last: f32) -> f32 {
+    let length = last - first;
+    if length <= 0.0 {
+        return first;
+    }
+    let offset = time - first;
+    match mode {
+        WrapMode::Clamp => time.clamp(first, last),
+        WrapMode::Loop => first + offset.rem_euclid(length),
+        WrapMode::PingPong => first + length - (offset.rem_euclid(2.0 * length) - length).abs(),
+    }
+}
+
+fn interpolate(lhs: &Keyframe, rhs: &Keyframe, time: f32) -> f32 {
+    let dt = rhs.time - lhs.time;
+    if time >= rhs.time {
+        return rhs.value;
+    }
+    if dt <= 0.0 || !lhs.out_slope.is_finite() || !rhs.in_slope.is_finite() {
+        return lhs.value;
+    }
+    let s = (time - lhs.time) / dt;
+
+    let out_weighted = lhs.weighted_mode.has_out();
+    let in_weighted = rhs.weighted_mode.has_in();
+    if !out_weighted && !in_weighted {
+        let m0 = lhs.out_slope * dt;
+        let m1 = rhs.in_slope * dt;
+        let (s2, s3) = (s * s, s * s * s);
+        return (2.0 * s3 - 3.0 * s2 + 1.0) * lhs.value
+            + (s3 - 2.0 * s2 + s) * m0
+            + (-2.0 * s3 + 3.0 * s2) * rhs.value
+            + (s3 - s2) * m1;
+    }
+
+    let w0 = if out_weighted {
+        lhs.out_weight.clamp(0.0, 1.0)
+    } else {
+        DEFAULT_WEIGHT
+    };
+    let w1 = if in_weighted {
+        rhs.in_weight.clamp(0.0, 1.0)
+    } else {
+        DEFAULT_WEIGHT
+    };
+    let u = bezier_parameter(s, w0, 1.0 - w1);
+    bezier(
+        [
+            lhs.value,
+            lhs.value + w0 * dt * lhs.out_slope,
+            rhs.value - w1 * dt * rhs.in_slope,
+            rhs.value,
+        ],
+        u,
+    )
+}
+
+fn bezier(p: [f32; 4], u: f32) -> f32 {
+    let v = 1.0 - u;
+    v * v * v * p[0] + 3.0 * v * v * u * p[1] + 3.0 * v * u * u * p[2] + u * u * u * p[3]
+}
+
+/// Solve `x(u) = s` for the Bezier parameter, where `x` runs from 0 to 1 through control points
+/// `x1` and `x2` in `[0, 1]` (so `x` is monotonic). Newton steps, falling back to bisection.
+fn bezier_parameter(s: f32, x1: f32, x2: f32) -> f32 {
+    let (mut lo, mut hi) = (0.0f32, 1.0f32);
+    let mut u = s;
+    for _ in 0..32 {
+        let x = bezier([0.0, x1, x2, 1.0], u) - s;
+        if x.abs() < 1e-7 {
+            break;
+        }
+        if x < 0.0 {
+            lo = u;
+        } else {
+            hi = u;
+        }
+        let v = 1.0 - u;
+        let dx = 3.0 * v * v * x1 + 6.0 * v * u * (x2 - x1) + 3.0 * u * u * (1.0 - x2);
+        let next = u - x / dx;
+        u = if dx.abs() > 1e-6 && next > lo && next < hi {
+            next
+        } else {
+            0.5 * (lo + hi)
+        };
+    }
+    u
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_maps_into_range() {
+        assert_eq!(wrap(WrapMode::Loop, 2.5, 1.0, 2.0), 1.5);
+        assert_eq!(wrap(WrapMode::Loop, 0.25, 1.0, 2.0), 1.25);
+        assert_eq!(wrap(WrapMode::PingPong, 2.25, 1.0, 2.0), 1.75);
+        assert_eq!(wrap(WrapMode::PingPong, 3.25, 1.0, 2.0), 1.25);
+        assert_eq!(wrap(WrapMode::PingPong, 0.5, 1.0, 2.0), 1.5);
+    }
+}
@@ -0,0 +1,31 @@
+//! Unity animation data module
+//!
+//! Keyframed [`AnimationCurve`]s with Unity-compatible evaluation: cubic Hermite segments,
+//! weighted (Bezier) tangents, infinite tangents as steps, and pre/post wrap modes; and
+//! [`AnimationClip`]s (class 74) read into one such curve per animated property.
+//!
+//! # Architecture
+//!
+//! - `curve` - Keyframe and curve types, parsing from TypeTree data and evaluation
+//! - `streamed` - Decoding of the compressed StreamedClip keyframe stream
+//! - `clip` - AnimationClip curves from the muscle clip or the legacy curve lists
+//!
+//! # Examples
+//!
+//! ```rust
+//! use unity_asset_decode::animation::{AnimationCurve, WrapMode};
+//!
+//! let curve = AnimationCurve::ease_in_out(0.0, 0.0, 1.0, 1.0).with_post_wrap(WrapMode::PingPong);
+//! assert_eq!(curve.evaluate(0.5), 0.5);
+//! assert_eq!(curve.evaluate(1.5), 0.5);
+//! let plot = curve.sample_range(0.0, 2.0, 9);
+//! assert_eq!(plot.len(), 9);
+//! ```
+
+pub mod clip;
+pub mod curve;
+pub mod streamed;
+
+pub use clip::{AnimationClip, ClipCurve, path_hash};
+pub use curve::{AnimationCurve, Keyframe, WeightedMode, WrapMode};
+pub use streamed::{StreamedFrame, StreamedKey, read_streamed_frames};
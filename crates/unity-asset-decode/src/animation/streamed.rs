@@ -0,0 +1,114 @@
+//! StreamedClip keyframe decoding
+//!
+//! `m_StreamedClip.data` is a `vector<uint>` holding a byte stream of frames, each a time and
+//! the keys that start a new segment at that time. A key is a curve index plus the four cubic
+//! coefficients of its segment; the value and out-slope are the constant and linear terms, and
+//! the in-slope is recovered from the previous segment of the same curve.
+
+use crate::error::{BinaryError, Result};
+use crate::reader::{BinaryReader, ByteOrder};
+
+/// One key of a streamed frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StreamedKey {
+    /// Curve index within the clip.
+    pub index: usize,
+    /// Cubic coefficients of the segment starting here, highest degree first.
+    pub coeff: [f32; 4],
+    /// Tangent arriving at this key, from the previous segment of the curve.
+    pub in_slope: f32,
+}
+
+impl StreamedKey {
+    pub fn value(&self) -> f32 {
+        self.coeff[3]
+    }
+
+    pub fn out_slope(&self) -> f32 {
+        self.coeff[2]
+    }
+
+    /// In-slope of the key `dx` later that ends this segment with value `next_value`.
+    ///
+    /// A segment with no cubic, quadratic or linear term is a step: the slope is infinite.
+    fn next_in_slope(&self, dx: f32, next_value: f32) -> f32 {
+        if self.coeff[0] == 0.0 && self.coeff[1] == 0.0 && self.coeff[2] == 0.0 {
+            return f32::INFINITY;
+        }
+        let dx = dx.max(0.0001);
+        let dy = next_value - self.value();
+        let d1 = self.out_slope() * dx;
+        let d2 = dy + dy + dy - d1 - d1 - self.coeff[1] * dx * dx;
+        d2 / dx
+    }
+}
+
+/// One frame of a streamed clip.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamedFrame {
+    pub time: f32,
+    pub keys: Vec<StreamedKey>,
+}
+
+/// Decode the frames of `m_StreamedClip.data`, filling in each key's in-slope
+///
+/// The words were read in the file's byte order, so the stream is rebuilt little-endian.
+pub fn read_streamed_frames(words: &[u32]) -> Result<Vec<StreamedFrame>> {
+    let bytes: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+    let mut reader = BinaryReader::new(&bytes, ByteOrder::Little);
+    let mut frames = Vec::new();
+    while reader.remaining() > 0 {
+        let time = reader.read_f32()?;
+        let count = reader.read_i32()?;
+        let count = usize::try_from(count)
+            .ok()
+            .filter(|&n| n.saturating_mul(20) <= reader.remaining())
+            .ok_or_else(|| {
+                BinaryError::invalid_data(format!(
+                    "Streamed frame at {} has {} keys but {} bytes remain",
+                    time,
+                    count,
+                    reader.remaining()
+                ))
+            })?;
+        let keys = (0..count)
+            .map(|_| {
+                let index = reader.read_i32()?;
+                let mut coeff = [0.0; 4];
+                for c in &mut coeff {
+                    *c = reader.read_f32()?;
+                }
+                Ok(StreamedKey {
+                    index: usize::try_from(index).map_err(|_| {
+                        BinaryError::invalid_data(format!(
+                            "Negative streamed curve index {}",
+                            index
+                        ))
+                    })?,
+                    coeff,
+                    in_slope: 0.0,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        frames.push(StreamedFrame { time, keys });
+    }
+
+    // Only the keys between the two leading frames and the trailing one are real keyframes,
+    // but in-slopes may come from any earlier frame.
+    for frame_index in 2..frames.len().saturating_sub(1) {
+        let (before, rest) = frames.split_at_mut(frame_index);
+        let frame = &mut rest[0];
+        for key in &mut frame.keys {
+            let previous = before.iter().rev().find_map(|prev| {
+                prev.keys
+                    .iter()
+                    .find(|k| k.index == key.index)
+                    .map(|k| (prev.time, *k))
+            });
+            if let Some((time, previous)) = previous {
+                key.in_slope = previous.next_in_slope(frame.time - time, key.value());
+            }
+        }
+    }
+    Ok(frames)
+}
@@ -5,6 +5,7 @@
 
 use super::formats::AudioCompressionFormat;
 use super::types::{AudioClip, AudioClipMeta, StreamingInfo};
+use crate::bundle::AssetBundle;
 use crate::error::{BinaryError, Result};
 use crate::object::UnityObject;
 use crate::unity_version::UnityVersion;
@@ -173,7 +174,7 @@ impl AudioClipConverter {
             .unwrap_or_else(|_| "UnknownAudio".to_string());
 
         // Read metadata based on Unity version
-        if self.version.major < 5 {
+        if !self.version.capabilities().audio_fsb5 {
             // Legacy format (Unity < 5.0)
             let format = reader.read_i32().unwrap_or(0);
             let type_ = reader.read_i32().unwrap_or(0);
@@ -236,7 +237,7 @@ impl AudioClipConverter {
                 compression_format = AudioCompressionFormat::from(compression_format_val);
                 compression_format_read = true;
 
-                if self.version.major >= 2017 {
+                if self.version.capabilities().audio_ambisonic {
                     clip.ambisonic = reader.read_bool().ok();
                     let _ = reader.align();
                 }
@@ -309,11 +310,12 @@ impl AudioClipConverter {
         ];
 
         // Add formats based on Unity version
-        if self.version.major >= 4 {
+        let caps = self.version.capabilities();
+        if caps.audio_mp3 {
             formats.push(AudioCompressionFormat::MP3);
         }
 
-        if self.version.major >= 5 {
+        if caps.audio_aac {
             formats.push(AudioCompressionFormat::AAC);
         }
 
@@ -390,6 +392,27 @@ impl AudioClipConverter {
             Err(BinaryError::invalid_data("No audio data available"))
         }
     }
+
+    /// Get audio data, reading streamed bytes from the `.resource` node of `bundle`
+    ///
+    /// Like [`get_audio_data`](Self::get_audio_data), but `stream_info.path`
+    /// (`archive:/CAB-<hash>/CAB-<hash>.resource`) is resolved with
+    /// [`AssetBundle::find_resource_node`] instead of the filesystem. A resource that is not in
+    /// `bundle` fails with [`BinaryError::ExternalResourceMissing`] naming the path.
+    pub fn get_audio_data_from_bundle(
+        &self,
+        clip: &AudioClip,
+        bundle: &AssetBundle,
+    ) -> Result<Vec<u8>> {
+        if !clip.data.is_empty() {
+            Ok(clip.data.clone())
+        } else if clip.is_streamed() {
+            let stream = &clip.stream_info;
+            bundle.read_resource(&stream.path, stream.offset, stream.size as u64)
+        } else {
+            Err(BinaryError::invalid_data("No audio data available"))
+        }
+    }
 }
 
 // Legacy compatibility - alias for the old processor name
@@ -3,293 +3,470 @@
 //! This module provides audio decoding capabilities using Symphonia
 //! for various audio formats supported by Unity.
 
-use super::formats::AudioCompressionFormat;
-use super::types::{AudioClip, DecodedAudio};
+use super::formats::{AudioCompressionFormat, DetectedCodec};
+use super::fsb5::{self, Fsb5Bank, VorbisHeaderTable};
+use super::types::{AudioClip, AudioClipMeta, DecodedAudio};
 use crate::error::{BinaryError, Result};
+use std::sync::Arc;
 
 /// Main audio decoder
 ///
 /// This struct provides methods for decoding various audio formats
 /// using the Symphonia audio library.
-pub struct AudioDecoder;
+///
+/// The declared compression format is only a probe hint: the payload's magic bytes
+/// (see [`AudioCompressionFormat::detect`]) are consulted when decoding as declared
+/// fails, or first when [`AudioDecoder::with_trust_magic`] is set.
+///
+/// FSB5 banks decode the clip's `subsound_index`; Vorbis banks need their setup header in
+/// [`AudioDecoder::with_vorbis_headers`].
+#[derive(Debug, Clone)]
+pub struct AudioDecoder {
+    trust_magic: bool,
+    vorbis_headers: Arc<VorbisHeaderTable>,
+}
 
 impl AudioDecoder {
     /// Create a new audio decoder
     pub fn new() -> Self {
-        Self
+        Self {
+            trust_magic: false,
+            vorbis_headers: Arc::default(),
+        }
+    }
+
+    /// Decode by the detected container before trying the declared format
+    pub fn with_trust_magic(mut self, trust_magic: bool) -> Self {
+        self.trust_magic = trust_magic;
+        self
+    }
+
+    /// Whether the detected container takes precedence over the declared format
+    pub fn trust_magic(&self) -> bool {
+        self.trust_magic
+    }
+
+    /// Vorbis setup headers for FSB5 Vorbis banks
+    pub fn with_vorbis_headers(mut self, headers: Arc<VorbisHeaderTable>) -> Self {
+        self.vorbis_headers = headers;
+        self
+    }
+
+    /// Setup headers available to FSB5 Vorbis banks
+    pub fn vorbis_headers(&self) -> &VorbisHeaderTable {
+        &self.vorbis_headers
     }
 
     /// Decode audio using Symphonia (supports many formats)
+    ///
+    /// The detected container is recorded in [`DecodedAudio::codec`]; a declared format
+    /// that does not match it is reported in [`DecodedAudio::warnings`].
     pub fn decode(&self, clip: &AudioClip) -> Result<DecodedAudio> {
-        use std::io::Cursor;
-        use symphonia::core::audio::{AudioBufferRef, Signal};
-        use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
-        use symphonia::core::errors::Error as SymphoniaError;
-        use symphonia::core::formats::FormatOptions;
-        use symphonia::core::io::MediaSourceStream;
-        use symphonia::core::meta::MetadataOptions;
-        use symphonia::core::probe::Hint;
-
-        if clip.data.is_empty() {
-            return Err(BinaryError::invalid_data("No audio data to decode"));
-        }
+        let declared = clip.compression_format();
+        let detected = AudioCompressionFormat::detect(&clip.data);
+        let decode_detected = |codec| self.decode_detected(clip, codec);
 
-        // Create a media source from the audio data
-        let cursor = Cursor::new(clip.data.clone());
-        let media_source = MediaSourceStream::new(Box::new(cursor), Default::default());
-
-        // Create a probe hint based on the compression format
-        let mut hint = Hint::new();
-        match clip.compression_format() {
-            AudioCompressionFormat::Vorbis => hint.with_extension("ogg"),
-            AudioCompressionFormat::MP3 => hint.with_extension("mp3"),
-            AudioCompressionFormat::AAC => hint.with_extension("aac"),
-            AudioCompressionFormat::PCM => hint.with_extension("wav"),
-            _ => &mut hint,
+        let mut audio = match detected {
+            Some(codec) if self.trust_magic => decode_detected(codec)?,
+            _ => match open_track(clip).and_then(decode_track) {
+                Ok(audio) => audio,
+                Err(err) => match detected {
+                    Some(codec) => decode_detected(codec).map_err(|_| err)?,
+                    None => return Err(err),
+                },
+            },
         };
 
-        // Get the metadata and format readers
-        let meta_opts: MetadataOptions = Default::default();
-        let fmt_opts: FormatOptions = Default::default();
-
-        // Probe the media source
-        let probed = symphonia::default::get_probe()
-            .format(&hint, media_source, &fmt_opts, &meta_opts)
-            .map_err(|e| BinaryError::generic(format!("Failed to probe audio format: {}", e)))?;
-
-        // Get the instantiated format reader
-        let mut format = probed.format;
-
-        // Find the first audio track with a known (decodeable) codec
-        let track = format
-            .tracks()
-            .iter()
-            .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
-            .ok_or_else(|| BinaryError::generic("No supported audio tracks found"))?;
-
-        // Use the default options for the decoder
-        let dec_opts: DecoderOptions = Default::default();
-
-        // Create a decoder for the track
-        let mut decoder = symphonia::default::get_codecs()
-            .make(&track.codec_params, &dec_opts)
-            .map_err(|e| BinaryError::generic(format!("Failed to create decoder: {}", e)))?;
-
-        // Store the track identifier, it will be used to filter packets
-        let track_id = track.id;
-
-        let mut samples = Vec::new();
-        let mut sample_rate = 44100u32;
-        let mut channels = 2u32;
-
-        // The decode loop
-        loop {
-            // Get the next packet from the media format
-            let packet = match format.next_packet() {
-                Ok(packet) => packet,
-                Err(SymphoniaError::ResetRequired) => {
-                    // The track list has been changed. Re-examine it and create a new set of decoders,
-                    // then restart the decode loop. This is an advanced feature and it is not
-                    // unreasonable to consider this "the end of the stream". As of v0.5.0, the only
-                    // usage of this is for chained OGG physical streams.
-                    break;
-                }
-                Err(SymphoniaError::IoError(_)) => {
-                    // The packet reader has reached the end of the stream
-                    break;
-                }
-                Err(err) => {
-                    // A unrecoverable error occurred, halt decoding
-                    return Err(BinaryError::generic(format!("Decode error: {}", err)));
-                }
-            };
+        audio.codec = detected;
+        if let Some(codec) = detected
+            && declared != AudioCompressionFormat::Unknown
+            && !codec.matches(declared)
+        {
+            audio.warnings.push(format!(
+                "AudioClip '{}' declares {:?} but its payload is {}",
+                clip.name, declared, codec
+            ));
+        }
+        Ok(audio)
+    }
 
-            // Consume any new metadata that has been read since the last packet
-            while !format.metadata().is_latest() {
-                // Pop the latest metadata and consume it
-                format.metadata().pop();
+    /// Check if a format can be decoded
+    pub fn can_decode(&self, format: AudioCompressionFormat) -> bool {
+        matches!(
+            format,
+            AudioCompressionFormat::PCM
+                | AudioCompressionFormat::Vorbis
+                | AudioCompressionFormat::MP3
+                | AudioCompressionFormat::AAC
+                | AudioCompressionFormat::ADPCM
+        )
+    }
+
+    /// Get list of supported formats
+    pub fn supported_formats(&self) -> Vec<AudioCompressionFormat> {
+        vec![
+            AudioCompressionFormat::PCM,
+            AudioCompressionFormat::Vorbis,
+            AudioCompressionFormat::MP3,
+            AudioCompressionFormat::AAC,
+            AudioCompressionFormat::ADPCM,
+        ]
+    }
+
+    /// Decode `clip.data` as the container its magic bytes identified.
+    fn decode_detected(&self, clip: &AudioClip, codec: DetectedCodec) -> Result<DecodedAudio> {
+        match codec {
+            DetectedCodec::Fsb5 { .. } => {
+                let subsound = match clip.meta {
+                    AudioClipMeta::Modern { subsound_index, .. } => subsound_index.max(0) as usize,
+                    AudioClipMeta::Legacy { .. } => 0,
+                };
+                decode_fsb5(&clip.data, subsound, &self.vorbis_headers)
             }
+            _ => open_data(&clip.data, Some(codec.extension())).and_then(decode_track),
+        }
+    }
+}
 
-            // If the packet does not belong to the selected track, skip over it
-            if packet.track_id() != track_id {
-                continue;
+/// Decode every packet of an opened track into interleaved f32 samples.
+fn decode_track(opened: OpenedTrack) -> Result<DecodedAudio> {
+    use symphonia::core::audio::{AudioBufferRef, Signal};
+    use symphonia::core::errors::Error as SymphoniaError;
+
+    let OpenedTrack {
+        mut format,
+        mut decoder,
+        track_id,
+        ..
+    } = opened;
+
+    let mut samples = Vec::new();
+    let mut sample_rate = 44100u32;
+    let mut channels = 2u32;
+
+    // The decode loop
+    loop {
+        // Get the next packet from the media format
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::ResetRequired) => {
+                // The track list has been changed. Re-examine it and create a new set of decoders,
+                // then restart the decode loop. This is an advanced feature and it is not
+                // unreasonable to consider this "the end of the stream". As of v0.5.0, the only
+                // usage of this is for chained OGG physical streams.
+                break;
+            }
+            Err(SymphoniaError::IoError(_)) => {
+                // The packet reader has reached the end of the stream
+                break;
             }
+            Err(err) => {
+                // A unrecoverable error occurred, halt decoding
+                return Err(BinaryError::generic(format!("Decode error: {}", err)));
+            }
+        };
+
+        // Consume any new metadata that has been read since the last packet
+        while !format.metadata().is_latest() {
+            // Pop the latest metadata and consume it
+            format.metadata().pop();
+        }
+
+        // If the packet does not belong to the selected track, skip over it
+        if packet.track_id() != track_id {
+            continue;
+        }
 
-            // Decode the packet into an audio buffer
-            match decoder.decode(&packet) {
-                Ok(decoded) => {
-                    // Get audio buffer information
-                    let spec = *decoded.spec();
-                    sample_rate = spec.rate;
-                    channels = spec.channels.count() as u32;
-
-                    // Convert the audio buffer to f32 samples
-                    match decoded {
-                        AudioBufferRef::F32(buf) => {
-                            samples.extend_from_slice(buf.chan(0));
-                            if channels > 1 {
-                                for ch in 1..channels as usize {
-                                    if ch < buf.spec().channels.count() {
-                                        let channel_samples = buf.chan(ch);
-                                        // Interleave channels
-                                        for (i, &sample) in channel_samples.iter().enumerate() {
-                                            if i * channels as usize + ch < samples.len() {
-                                                samples.insert(i * channels as usize + ch, sample);
-                                            } else {
-                                                samples.push(sample);
-                                            }
+        // Decode the packet into an audio buffer
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                // Get audio buffer information
+                let spec = *decoded.spec();
+                sample_rate = spec.rate;
+                channels = spec.channels.count() as u32;
+
+                // Convert the audio buffer to f32 samples
+                match decoded {
+                    AudioBufferRef::F32(buf) => {
+                        samples.extend_from_slice(buf.chan(0));
+                        if channels > 1 {
+                            for ch in 1..channels as usize {
+                                if ch < buf.spec().channels.count() {
+                                    let channel_samples = buf.chan(ch);
+                                    // Interleave channels
+                                    for (i, &sample) in channel_samples.iter().enumerate() {
+                                        if i * channels as usize + ch < samples.len() {
+                                            samples.insert(i * channels as usize + ch, sample);
+                                        } else {
+                                            samples.push(sample);
                                         }
                                     }
                                 }
                             }
                         }
-                        AudioBufferRef::U8(buf) => {
-                            for ch in 0..channels as usize {
-                                if ch < buf.spec().channels.count() {
-                                    let channel_samples = buf.chan(ch);
-                                    for &sample in channel_samples {
-                                        let normalized = (sample as f32 - 128.0) / 128.0;
-                                        samples.push(normalized);
-                                    }
+                    }
+                    AudioBufferRef::U8(buf) => {
+                        for ch in 0..channels as usize {
+                            if ch < buf.spec().channels.count() {
+                                let channel_samples = buf.chan(ch);
+                                for &sample in channel_samples {
+                                    let normalized = (sample as f32 - 128.0) / 128.0;
+                                    samples.push(normalized);
                                 }
                             }
                         }
-                        AudioBufferRef::U16(buf) => {
-                            for ch in 0..channels as usize {
-                                if ch < buf.spec().channels.count() {
-                                    let channel_samples = buf.chan(ch);
-                                    for &sample in channel_samples {
-                                        let normalized = (sample as f32 - 32768.0) / 32768.0;
-                                        samples.push(normalized);
-                                    }
+                    }
+                    AudioBufferRef::U16(buf) => {
+                        for ch in 0..channels as usize {
+                            if ch < buf.spec().channels.count() {
+                                let channel_samples = buf.chan(ch);
+                                for &sample in channel_samples {
+                                    let normalized = (sample as f32 - 32768.0) / 32768.0;
+                                    samples.push(normalized);
                                 }
                             }
                         }
-                        AudioBufferRef::U32(buf) => {
-                            for ch in 0..channels as usize {
-                                if ch < buf.spec().channels.count() {
-                                    let channel_samples = buf.chan(ch);
-                                    for &sample in channel_samples {
-                                        let normalized =
-                                            (sample as f32 - 2147483648.0) / 2147483648.0;
-                                        samples.push(normalized);
-                                    }
+                    }
+                    AudioBufferRef::U32(buf) => {
+                        for ch in 0..channels as usize {
+                            if ch < buf.spec().channels.count() {
+                                let channel_samples = buf.chan(ch);
+                                for &sample in channel_samples {
+                                    let normalized = (sample as f32 - 2147483648.0) / 2147483648.0;
+                                    samples.push(normalized);
                                 }
                             }
                         }
-                        AudioBufferRef::S8(buf) => {
-                            for ch in 0..channels as usize {
-                                if ch < buf.spec().channels.count() {
-                                    let channel_samples = buf.chan(ch);
-                                    for &sample in channel_samples {
-                                        let normalized = sample as f32 / 128.0;
-                                        samples.push(normalized);
-                                    }
+                    }
+                    AudioBufferRef::S8(buf) => {
+                        for ch in 0..channels as usize {
+                            if ch < buf.spec().channels.count() {
+                                let channel_samples = buf.chan(ch);
+                                for &sample in channel_samples {
+                                    let normalized = sample as f32 / 128.0;
+                                    samples.push(normalized);
                                 }
                             }
                         }
-                        AudioBufferRef::S16(buf) => {
-                            for ch in 0..channels as usize {
-                                if ch < buf.spec().channels.count() {
-                                    let channel_samples = buf.chan(ch);
-                                    for &sample in channel_samples {
-                                        let normalized = sample as f32 / 32768.0;
-                                        samples.push(normalized);
-                                    }
+                    }
+                    AudioBufferRef::S16(buf) => {
+                        for ch in 0..channels as usize {
+                            if ch < buf.spec().channels.count() {
+                                let channel_samples = buf.chan(ch);
+                                for &sample in channel_samples {
+                                    let normalized = sample as f32 / 32768.0;
+                                    samples.push(normalized);
                                 }
                             }
                         }
-                        AudioBufferRef::S32(buf) => {
-                            for ch in 0..channels as usize {
-                                if ch < buf.spec().channels.count() {
-                                    let channel_samples = buf.chan(ch);
-                                    for &sample in channel_samples {
-                                        let normalized = sample as f32 / 2147483648.0;
-                                        samples.push(normalized);
-                                    }
+                    }
+                    AudioBufferRef::S32(buf) => {
+                        for ch in 0..channels as usize {
+                            if ch < buf.spec().channels.count() {
+                                let channel_samples = buf.chan(ch);
+                                for &sample in channel_samples {
+                                    let normalized = sample as f32 / 2147483648.0;
+                                    samples.push(normalized);
                                 }
                             }
                         }
-                        AudioBufferRef::F64(buf) => {
-                            for ch in 0..channels as usize {
-                                if ch < buf.spec().channels.count() {
-                                    let channel_samples = buf.chan(ch);
-                                    for &sample in channel_samples {
-                                        samples.push(sample as f32);
-                                    }
+                    }
+                    AudioBufferRef::F64(buf) => {
+                        for ch in 0..channels as usize {
+                            if ch < buf.spec().channels.count() {
+                                let channel_samples = buf.chan(ch);
+                                for &sample in channel_samples {
+                                    samples.push(sample as f32);
                                 }
                             }
                         }
-                        AudioBufferRef::U24(buf) => {
-                            for ch in 0..channels as usize {
-                                if ch < buf.spec().channels.count() {
-                                    let channel_samples = buf.chan(ch);
-                                    for &sample in channel_samples {
-                                        let value = sample.inner() as i32;
-                                        let normalized = (value as f32 - 8388608.0) / 8388608.0;
-                                        samples.push(normalized);
-                                    }
+                    }
+                    AudioBufferRef::U24(buf) => {
+                        for ch in 0..channels as usize {
+                            if ch < buf.spec().channels.count() {
+                                let channel_samples = buf.chan(ch);
+                                for &sample in channel_samples {
+                                    let value = sample.inner() as i32;
+                                    let normalized = (value as f32 - 8388608.0) / 8388608.0;
+                                    samples.push(normalized);
                                 }
                             }
                         }
-                        AudioBufferRef::S24(buf) => {
-                            for ch in 0..channels as usize {
-                                if ch < buf.spec().channels.count() {
-                                    let channel_samples = buf.chan(ch);
-                                    for &sample in channel_samples {
-                                        let value = sample.inner();
-                                        let normalized = value as f32 / 8388608.0;
-                                        samples.push(normalized);
-                                    }
+                    }
+                    AudioBufferRef::S24(buf) => {
+                        for ch in 0..channels as usize {
+                            if ch < buf.spec().channels.count() {
+                                let channel_samples = buf.chan(ch);
+                                for &sample in channel_samples {
+                                    let value = sample.inner();
+                                    let normalized = value as f32 / 8388608.0;
+                                    samples.push(normalized);
                                 }
                             }
                         }
                     }
                 }
-                Err(SymphoniaError::IoError(_)) => {
-                    // The packet reader has reached the end of the stream
-                    break;
-                }
-                Err(SymphoniaError::DecodeError(_)) => {
-                    // Decode error, try to continue
-                    continue;
-                }
-                Err(err) => {
-                    // A unrecoverable error occurred, halt decoding
-                    return Err(BinaryError::generic(format!("Decode error: {}", err)));
-                }
+            }
+            Err(SymphoniaError::IoError(_)) => {
+                // The packet reader has reached the end of the stream
+                break;
+            }
+            Err(SymphoniaError::DecodeError(_)) => {
+                // Decode error, try to continue
+                continue;
+            }
+            Err(err) => {
+                // A unrecoverable error occurred, halt decoding
+                return Err(BinaryError::generic(format!("Decode error: {}", err)));
             }
         }
+    }
 
-        if samples.is_empty() {
-            return Err(BinaryError::generic("No audio samples decoded"));
-        }
+    if samples.is_empty() {
+        return Err(BinaryError::generic("No audio samples decoded"));
+    }
 
-        Ok(DecodedAudio::new(samples, sample_rate, channels))
+    Ok(DecodedAudio::new(samples, sample_rate, channels))
+}
+
+/// Decode subsound `subsound` of an FMOD FSB5 bank.
+///
+/// PCM samples are converted directly, MPEG samples go through Symphonia and Vorbis samples
+/// are rebuilt into an Ogg stream first (see [`Fsb5Bank::rebuild`]); the other FMOD codecs
+/// (ADPCM, console formats) are reported as unsupported.
+fn decode_fsb5(data: &[u8], subsound: usize, headers: &VorbisHeaderTable) -> Result<DecodedAudio> {
+    let bank = Fsb5Bank::parse(data)?;
+    let sample = bank.samples.get(subsound).ok_or_else(|| {
+        BinaryError::invalid_data(format!(
+            "FSB5 bank has {} samples, not {}",
+            bank.samples.len(),
+            subsound + 1
+        ))
+    })?;
+    if sample.channels == 0 {
+        return Err(BinaryError::invalid_data("FSB5 sample has no channels"));
     }
 
-    /// Check if a format can be decoded
-    pub fn can_decode(&self, format: AudioCompressionFormat) -> bool {
-        matches!(
-            format,
-            AudioCompressionFormat::PCM
-                | AudioCompressionFormat::Vorbis
-                | AudioCompressionFormat::MP3
-                | AudioCompressionFormat::AAC
-                | AudioCompressionFormat::ADPCM
-        )
+    let payload = sample.data.as_slice();
+    let samples: Vec<f32> = match bank.mode {
+        fsb5::MODE_PCM8 => payload.iter().map(|&b| b as i8 as f32 / 128.0).collect(),
+        fsb5::MODE_PCM16 => payload
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+            .collect(),
+        fsb5::MODE_PCM24 => payload
+            .chunks_exact(3)
+            .map(|b| (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / 8388608.0)
+            .collect(),
+        fsb5::MODE_PCM32 => payload
+            .chunks_exact(4)
+            .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2147483648.0)
+            .collect(),
+        fsb5::MODE_PCM_FLOAT => payload
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect(),
+        fsb5::MODE_MPEG => return open_data(payload, Some("mp3")).and_then(decode_track),
+        fsb5::MODE_VORBIS => {
+            let ogg = bank.rebuild(subsound, headers)?;
+            return open_data(&ogg, Some("ogg")).and_then(decode_track);
+        }
+        mode => {
+            return Err(BinaryError::unsupported(format!(
+                "FSB5 codec {} ({:?}) is not supported",
+                mode,
+                bank.compression_format()
+            )));
+        }
+    };
+    if samples.is_empty() {
+        return Err(BinaryError::generic("No audio samples decoded"));
     }
 
-    /// Get list of supported formats
-    pub fn supported_formats(&self) -> Vec<AudioCompressionFormat> {
-        vec![
-            AudioCompressionFormat::PCM,
-            AudioCompressionFormat::Vorbis,
-            AudioCompressionFormat::MP3,
-            AudioCompressionFormat::AAC,
-            AudioCompressionFormat::ADPCM,
-        ]
+    Ok(DecodedAudio::new(
+        samples,
+        sample.frequency,
+        sample.channels,
+    ))
+}
+
+/// A probed clip with a decoder for its first decodable track.
+pub(super) struct OpenedTrack {
+    pub format: Box<dyn symphonia::core::formats::FormatReader>,
+    pub decoder: Box<dyn symphonia::core::codecs::Decoder>,
+    pub track_id: u32,
+    /// Total frames, when the container declares it.
+    pub n_frames: Option<u64>,
+}
+
+/// Probe `clip.data` and create a decoder for its first track with a known codec.
+pub(super) fn open_track(clip: &AudioClip) -> Result<OpenedTrack> {
+    // Create a probe hint based on the compression format
+    let extension = match clip.compression_format() {
+        AudioCompressionFormat::Vorbis => Some("ogg"),
+        AudioCompressionFormat::MP3 => Some("mp3"),
+        AudioCompressionFormat::AAC => Some("aac"),
+        AudioCompressionFormat::PCM => Some("wav"),
+        _ => None,
+    };
+    open_data(&clip.data, extension)
+}
+
+/// Probe `data`, hinted by a file extension, and open its first decodable track.
+fn open_data(data: &[u8], extension: Option<&str>) -> Result<OpenedTrack> {
+    use std::io::Cursor;
+    use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    if data.is_empty() {
+        return Err(BinaryError::invalid_data("No audio data to decode"));
+    }
+
+    // Create a media source from the audio data
+    let cursor = Cursor::new(data.to_vec());
+    let media_source = MediaSourceStream::new(Box::new(cursor), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(extension) = extension {
+        hint.with_extension(extension);
     }
+
+    // Get the metadata and format readers
+    let meta_opts: MetadataOptions = Default::default();
+    let fmt_opts: FormatOptions = Default::default();
+
+    // Probe the media source
+    let probed = symphonia::default::get_probe()
+        .format(&hint, media_source, &fmt_opts, &meta_opts)
+        .map_err(|e| BinaryError::generic(format!("Failed to probe audio format: {}", e)))?;
+
+    // Get the instantiated format reader
+    let format = probed.format;
+
+    // Find the first audio track with a known (decodeable) codec
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| BinaryError::generic("No supported audio tracks found"))?;
+
+    // Use the default options for the decoder
+    let dec_opts: DecoderOptions = Default::default();
+
+    // Create a decoder for the track
+    let decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &dec_opts)
+        .map_err(|e| BinaryError::generic(format!("Failed to create decoder: {}", e)))?;
+
+    Ok(OpenedTrack {
+        track_id: track.id,
+        n_frames: track.codec_params.n_frames,
+        format,
+        decoder,
+    })
 }
 
 impl Default for AudioDecoder {
@@ -2,9 +2,9 @@
 //!
 //! This module provides functionality for exporting audio to various formats.
 
-use super::types::DecodedAudio;
+use super::types::{AudioSample, DecodedAudio};
 use crate::error::{BinaryError, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Audio exporter utility
 ///
@@ -151,6 +151,24 @@ impl AudioExporter {
         }
     }
 
+    /// Write each sample to `dir` under [`AudioSample::file_name`], creating `dir` if needed
+    pub fn export_samples<P: AsRef<Path>>(samples: &[AudioSample], dir: P) -> Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir).map_err(|e| {
+            BinaryError::generic(format!("Failed to create {}: {}", dir.display(), e))
+        })?;
+        samples
+            .iter()
+            .map(|sample| {
+                let path = dir.join(sample.file_name());
+                std::fs::write(&path, &sample.data).map_err(|e| {
+                    BinaryError::generic(format!("Failed to write {}: {}", path.display(), e))
+                })?;
+                Ok(path)
+            })
+            .collect()
+    }
+
     /// Get supported export formats
     pub fn supported_formats() -> Vec<&'static str> {
         vec!["wav", "pcm", "raw"]
@@ -203,29 +221,13 @@ impl AudioExporter {
 
 /// Export options for advanced export scenarios
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ExportOptions {
     pub format: AudioFormat,
     pub bit_depth: u8,
     pub sample_rate: Option<u32>, // For resampling
 }
 
-/// Supported audio export formats
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum AudioFormat {
-    Wav,
-    RawPcm,
-}
-
-impl Default for ExportOptions {
-    fn default() -> Self {
-        Self {
-            format: AudioFormat::Wav,
-            bit_depth: 16,
-            sample_rate: None,
-        }
-    }
-}
-
 impl ExportOptions {
     /// Create WAV export options
     pub fn wav() -> Self {
@@ -261,6 +263,40 @@ impl ExportOptions {
             AudioFormat::RawPcm => {
                 AudioExporter::export_raw_pcm(audio_to_export, path, self.bit_depth)
             }
+            AudioFormat::Ogg => Err(BinaryError::unsupported(
+                "Ogg output copies the encoded clip; use AudioDecoder::decode_to_writer",
+            )),
+        }
+    }
+
+    /// Set `format`.
+    pub fn with_format(mut self, format: AudioFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set `bit_depth`.
+    pub fn with_bit_depth(mut self, bit_depth: u8) -> Self {
+        self.bit_depth = bit_depth;
+        self
+    }
+}
+
+/// Supported audio export formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Wav,
+    RawPcm,
+    /// Ogg Vorbis passthrough (streaming export only).
+    Ogg,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            format: AudioFormat::Wav,
+            bit_depth: 16,
+            sample_rate: None,
         }
     }
 }
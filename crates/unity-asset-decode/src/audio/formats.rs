@@ -239,7 +239,7 @@ impl AudioCompressionFormat {
     }
 
     /// Get recommended file extension
-    pub fn extension(&self) -> &str {
+    pub fn extension(&self) -> &'static str {
         match self {
             AudioCompressionFormat::PCM | AudioCompressionFormat::ADPCM => "wav",
             AudioCompressionFormat::Vorbis => "ogg",
@@ -264,3 +264,126 @@ impl AudioCompressionFormat {
         }
     }
 }
+
+/// Container or codec identified from the leading bytes of an audio payload.
+///
+/// Returned by [`AudioCompressionFormat::detect`]; used when a clip's declared
+/// compression format does not describe what its data actually holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DetectedCodec {
+    /// `OggS` page stream (Vorbis, Opus or FLAC inside)
+    Ogg,
+    /// `RIFF` / `WAVE` file
+    Wav,
+    /// MPEG audio, with or without a leading ID3v2 tag
+    Mp3,
+    /// Native FLAC stream (`fLaC`)
+    Flac,
+    /// FMOD `FSB5` sound bank; `mode` is the bank's codec field
+    Fsb5 { mode: u32 },
+}
+
+impl DetectedCodec {
+    /// Human-readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            DetectedCodec::Ogg => "Ogg",
+            DetectedCodec::Wav => "WAV",
+            DetectedCodec::Mp3 => "MP3",
+            DetectedCodec::Flac => "FLAC",
+            DetectedCodec::Fsb5 { .. } => "FSB5",
+        }
+    }
+
+    /// File extension of the container, used as the probe hint
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DetectedCodec::Ogg => "ogg",
+            DetectedCodec::Wav => "wav",
+            DetectedCodec::Mp3 => "mp3",
+            DetectedCodec::Flac => "flac",
+            DetectedCodec::Fsb5 { .. } => "fsb",
+        }
+    }
+
+    /// The Unity compression format this payload corresponds to.
+    ///
+    /// FSB5 banks map through their codec field; FLAC has no Unity equivalent.
+    pub fn compression_format(&self) -> AudioCompressionFormat {
+        match self {
+            DetectedCodec::Ogg => AudioCompressionFormat::Vorbis,
+            DetectedCodec::Wav => AudioCompressionFormat::PCM,
+            DetectedCodec::Mp3 => AudioCompressionFormat::MP3,
+            DetectedCodec::Flac => AudioCompressionFormat::Unknown,
+            DetectedCodec::Fsb5 { mode } => match mode {
+                1..=5 => AudioCompressionFormat::PCM,
+                6 => AudioCompressionFormat::GCADPCM,
+                7 | 16 => AudioCompressionFormat::ADPCM,
+                8 => AudioCompressionFormat::VAG,
+                9 => AudioCompressionFormat::HEVAG,
+                10 => AudioCompressionFormat::XMA,
+                11 => AudioCompressionFormat::MP3,
+                13 => AudioCompressionFormat::ATRAC9,
+                15 => AudioCompressionFormat::Vorbis,
+                _ => AudioCompressionFormat::Unknown,
+            },
+        }
+    }
+
+    /// Whether a clip declaring `declared` is expected to carry this payload
+    pub fn matches(&self, declared: AudioCompressionFormat) -> bool {
+        let actual = self.compression_format();
+        actual == declared
+            || (*self == DetectedCodec::Wav && declared == AudioCompressionFormat::ADPCM)
+    }
+}
+
+impl std::fmt::Display for DetectedCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DetectedCodec::Fsb5 { mode } => write!(f, "FSB5 (mode {})", mode),
+            _ => f.write_str(self.name()),
+        }
+    }
+}
+
+impl AudioCompressionFormat {
+    /// Identify the container of an audio payload from its magic bytes.
+    ///
+    /// Recognizes `OggS`, `RIFF`/`WAVE`, `FSB5`, `fLaC`, an ID3v2 tag and a bare MPEG
+    /// layer I-III frame sync. Returns `None` for anything else, including ADTS AAC.
+    pub fn detect(data: &[u8]) -> Option<DetectedCodec> {
+        if data.starts_with(b"OggS") {
+            return Some(DetectedCodec::Ogg);
+        }
+        if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WAVE") {
+            return Some(DetectedCodec::Wav);
+        }
+        if data.starts_with(b"fLaC") {
+            return Some(DetectedCodec::Flac);
+        }
+        if data.starts_with(b"FSB5") {
+            let mode = data.get(24..28)?;
+            return Some(DetectedCodec::Fsb5 {
+                mode: u32::from_le_bytes([mode[0], mode[1], mode[2], mode[3]]),
+            });
+        }
+        if data.starts_with(b"ID3") || is_mpeg_frame_sync(data) {
+            return Some(DetectedCodec::Mp3);
+        }
+        None
+    }
+}
+
+/// An MPEG audio frame header: 11-bit sync, a defined version and layer, and valid
+/// bitrate and sample-rate indices.
+fn is_mpeg_frame_sync(data: &[u8]) -> bool {
+    let [0xFF, b1, b2, ..] = *data else {
+        return false;
+    };
+    let version = (b1 >> 3) & 0x3;
+    let layer = (b1 >> 1) & 0x3;
+    let bitrate = b2 >> 4;
+    let sample_rate = (b2 >> 2) & 0x3;
+    b1 & 0xE0 == 0xE0 && version != 1 && layer != 0 && bitrate != 0xF && sample_rate != 3
+}
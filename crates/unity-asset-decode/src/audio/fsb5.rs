@@ -0,0 +1,753 @@
+//! FMOD FSB5 sound banks
+//!
+//! Most AudioClips built by Unity 5+ hold an FSB5 bank: a 60-byte header (64 for version 0),
+//! one sample header per sub-sound, an optional name table and the sample data. A sample
+//! header packs the frequency index, channel count, data offset and length into a `u64`,
+//! followed by extra chunks (channel/frequency overrides, loop points, the Vorbis setup CRC).
+//!
+//! [`Fsb5Bank::rebuild`] turns a sub-sound into a standalone file: PCM becomes WAV, MPEG
+//! frames are copied as they are, and Vorbis packets are wrapped into Ogg pages. FMOD strips
+//! the Vorbis identification, comment and setup headers; the first two are rebuilt from the
+//! sample header, but the setup header (the codebooks) only exists as a CRC-32 in the bank.
+//! It has to be supplied through a [`VorbisHeaderTable`], e.g. one loaded from the header dumps
+//! shipped with FSB extraction tools.
+
+use super::formats::{AudioCompressionFormat, DetectedCodec};
+use super::types::{AudioClip, AudioSample};
+use crate::error::{BinaryError, Result};
+use std::collections::HashMap;
+
+const FREQUENCIES: [u32; 11] = [
+    4000, 8000, 11000, 11025, 16000, 22050, 24000, 32000, 44100, 48000, 96000,
+];
+/// Channel counts of the 2-bit channel code in the sample header.
+const CHANNELS: [u32; 4] = [1, 2, 6, 8];
+
+const CHUNK_CHANNELS: u32 = 1;
+const CHUNK_FREQUENCY: u32 = 2;
+const CHUNK_LOOP: u32 = 3;
+const CHUNK_VORBIS_DATA: u32 = 11;
+
+pub(super) const MODE_PCM8: u32 = 1;
+pub(super) const MODE_PCM16: u32 = 2;
+pub(super) const MODE_PCM24: u32 = 3;
+pub(super) const MODE_PCM32: u32 = 4;
+pub(super) const MODE_PCM_FLOAT: u32 = 5;
+pub(super) const MODE_MPEG: u32 = 11;
+pub(super) const MODE_VORBIS: u32 = 15;
+
+/// FMOD always encodes Vorbis with 256/2048-sample blocks.
+const VORBIS_BLOCKSIZE_SHORT: u32 = 256;
+const VORBIS_BLOCKSIZE_LONG: u32 = 2048;
+const VORBIS_VENDOR: &[u8] = b"unity-asset";
+/// Ogg pages are closed once their body reaches this size.
+const OGG_PAGE_TARGET: usize = 4096;
+
+/// A parsed FSB5 bank
+#[derive(Debug, Clone)]
+pub struct Fsb5Bank {
+    /// Header version (0 or 1)
+    pub version: u32,
+    /// Codec of every sample in the bank (FMOD `SOUND_FORMAT`)
+    pub mode: u32,
+    pub samples: Vec<Fsb5Sample>,
+}
+
+/// One sub-sound of an [`Fsb5Bank`]
+#[derive(Debug, Clone, Default)]
+pub struct Fsb5Sample {
+    /// Entry of the name table, when the bank has one
+    pub name: Option<String>,
+    pub frequency: u32,
+    pub channels: u32,
+    /// Length in frames
+    pub num_samples: u32,
+    /// Loop start and end frame, as stored
+    pub loop_points: Option<(u32, u32)>,
+    /// CRC-32 identifying the Vorbis setup header this sample was encoded with
+    pub vorbis_crc32: Option<u32>,
+    /// Encoded sample data
+    pub data: Vec<u8>,
+}
+
+impl Fsb5Bank {
+    /// Parse the header, sample headers, name table and sample data of an FSB5 bank
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if !data.starts_with(b"FSB5") {
+            return Err(BinaryError::invalid_data("Not an FSB5 bank"));
+        }
+        let u32_at = |offset: usize| -> Result<u32> {
+            data.get(offset..offset + 4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .ok_or_else(|| BinaryError::invalid_data("Truncated FSB5 header"))
+        };
+
+        let version = u32_at(4)?;
+        let num_samples = u32_at(8)? as usize;
+        let sample_headers_size = u32_at(12)? as usize;
+        let name_table_size = u32_at(16)? as usize;
+        let data_size = u32_at(20)? as usize;
+        let mode = u32_at(24)?;
+        let header_size = if version == 0 { 64 } else { 60 };
+        let name_table = header_size + sample_headers_size;
+        let base = name_table + name_table_size;
+        if num_samples == 0 {
+            return Err(BinaryError::invalid_data("FSB5 bank has no samples"));
+        }
+        if num_samples > sample_headers_size / 8 {
+            return Err(BinaryError::invalid_data(format!(
+                "FSB5 bank declares {} samples in {} bytes of sample headers",
+                num_samples, sample_headers_size
+            )));
+        }
+
+        // Sample header: bit 0 extra chunks, 1-4 frequency index, 5-6 channel code,
+        // 7-33 data offset / 32, 34-63 sample count.
+        let mut offset = header_size;
+        let mut samples = Vec::with_capacity(num_samples);
+        let mut starts = Vec::with_capacity(num_samples);
+        for _ in 0..num_samples {
+            let bits = u64::from(u32_at(offset)?) | (u64::from(u32_at(offset + 4)?) << 32);
+            offset += 8;
+            let mut sample = Fsb5Sample {
+                frequency: FREQUENCIES
+                    .get(((bits >> 1) & 0xF) as usize)
+                    .copied()
+                    .unwrap_or(44100),
+                channels: CHANNELS[((bits >> 5) & 0x3) as usize],
+                num_samples: (bits >> 34) as u32,
+                ..Default::default()
+            };
+            starts.push(((bits >> 7) & 0x7FF_FFFF) as usize * 32);
+
+            let mut more_chunks = bits & 1 != 0;
+            while more_chunks {
+                let chunk = u32_at(offset)?;
+                more_chunks = chunk & 1 != 0;
+                let size = ((chunk >> 1) & 0xFF_FFFF) as usize;
+                let body = offset + 4;
+                match chunk >> 25 {
+                    CHUNK_CHANNELS => {
+                        sample.channels = u32::from(*data.get(body).ok_or_else(|| {
+                            BinaryError::invalid_data("Truncated FSB5 channel chunk")
+                        })?)
+                    }
+                    CHUNK_FREQUENCY => sample.frequency = u32_at(body)?,
+                    CHUNK_LOOP => sample.loop_points = Some((u32_at(body)?, u32_at(body + 4)?)),
+                    CHUNK_VORBIS_DATA => sample.vorbis_crc32 = Some(u32_at(body)?),
+                    _ => {}
+                }
+                offset = body + size;
+            }
+            samples.push(sample);
+        }
+
+        if name_table_size > 0 {
+            for (i, sample) in samples.iter_mut().enumerate() {
+                let start = name_table + u32_at(name_table + i * 4)? as usize;
+                let bytes = data.get(start..base.min(data.len())).unwrap_or_default();
+                let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+                sample.name = Some(String::from_utf8_lossy(&bytes[..end]).into_owned());
+            }
+        }
+
+        // Each sample runs up to the next one's data offset.
+        for (i, sample) in samples.iter_mut().enumerate() {
+            let end = starts.get(i + 1).copied().unwrap_or(data_size);
+            sample.data = data
+                .get(base + starts[i]..(base + end).min(data.len()))
+                .ok_or_else(|| {
+                    BinaryError::invalid_data(format!("FSB5 sample {} data out of range", i))
+                })?
+                .to_vec();
+        }
+
+        Ok(Self {
+            version,
+            mode,
+            samples,
+        })
+    }
+
+    /// Unity compression format of the bank's codec
+    pub fn compression_format(&self) -> AudioCompressionFormat {
+        DetectedCodec::Fsb5 { mode: self.mode }.compression_format()
+    }
+
+    /// File extension of [`Self::rebuild`]'s output, if the codec can be rebuilt
+    pub fn extension(&self) -> Option<&'static str> {
+        match self.mode {
+            MODE_PCM8..=MODE_PCM_FLOAT => Some("wav"),
+            MODE_MPEG => Some("mp3"),
+            MODE_VORBIS => Some("ogg"),
+            _ => None,
+        }
+    }
+
+    /// Rebuild sample `index` into a playable file (see [`Self::extension`])
+    ///
+    /// Vorbis samples need their setup header in `headers`; without it this fails with an
+    /// `Unsupported` error naming the CRC.
+    pub fn rebuild(&self, index: usize, headers: &VorbisHeaderTable) -> Result<Vec<u8>> {
+        let sample = self.samples.get(index).ok_or_else(|| {
+            BinaryError::invalid_data(format!(
+                "FSB5 bank has {} samples, not {}",
+                self.samples.len(),
+                index + 1
+            ))
+        })?;
+        if sample.channels == 0 {
+            return Err(BinaryError::invalid_data("FSB5 sample has no channels"));
+        }
+        match self.mode {
+            MODE_PCM8..=MODE_PCM_FLOAT => Ok(pcm_to_wav(sample, self.mode)),
+            MODE_MPEG => Ok(sample.data.clone()),
+            MODE_VORBIS => rebuild_vorbis(sample, index as u32, headers),
+            mode => Err(BinaryError::unsupported(format!(
+                "FSB5 codec {} ({:?}) is not supported",
+                mode,
+                self.compression_format()
+            ))),
+        }
+    }
+}
+
+impl AudioClip {
+    /// The clip's audio as playable files, one per FSB5 sub-sound
+    ///
+    /// Other payloads are returned as a single sample holding `data` as it is. A bank with one
+    /// sub-sound is named after the clip, otherwise sub-sound `i` is `name-i`. Vorbis banks
+    /// fail without setup headers; see [`Self::extract_samples_with`].
+    pub fn extract_samples(&self) -> Result<Vec<AudioSample>> {
+        self.extract_samples_with(&VorbisHeaderTable::default())
+    }
+
+    /// [`Self::extract_samples`] with the setup headers of FSB5 Vorbis banks
+    pub fn extract_samples_with(&self, headers: &VorbisHeaderTable) -> Result<Vec<AudioSample>> {
+        if self.data.is_empty() {
+            return Err(BinaryError::invalid_data(format!(
+                "AudioClip {:?} has no audio data",
+                self.name
+            )));
+        }
+        if !self.data.starts_with(b"FSB5") {
+            let properties = self.properties();
+            let extension = AudioCompressionFormat::detect(&self.data)
+                .map_or_else(|| self.compression_format().extension(), |c| c.extension());
+            return Ok(vec![AudioSample {
+                name: self.name.clone(),
+                extension,
+                frequency: properties.sample_rate.max(0) as u32,
+                channels: properties.channels.max(0) as u32,
+                loop_points: None,
+                data: self.data.clone(),
+            }]);
+        }
+
+        let bank = Fsb5Bank::parse(&self.data)?;
+        let extension = bank.extension().ok_or_else(|| {
+            BinaryError::unsupported(format!(
+                "FSB5 codec {} ({:?}) is not supported",
+                bank.mode,
+                bank.compression_format()
+            ))
+        })?;
+        let single = bank.samples.len() == 1;
+        bank.samples
+            .iter()
+            .enumerate()
+            .map(|(i, sample)| {
+                Ok(AudioSample {
+                    name: if single {
+                        self.name.clone()
+                    } else {
+                        format!("{}-{}", self.name, i)
+                    },
+                    extension,
+                    frequency: sample.frequency,
+                    channels: sample.channels,
+                    loop_points: sample.loop_points,
+                    data: bank.rebuild(i, headers)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Vorbis setup headers by the CRC-32 FSB5 banks store in place of them
+#[derive(Debug, Clone, Default)]
+pub struct VorbisHeaderTable {
+    headers: HashMap<u32, Vec<u8>>,
+}
+
+impl VorbisHeaderTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the setup header FMOD identifies by `crc32`
+    ///
+    /// `setup` may be the whole packet or its body without the `\x05vorbis` prefix.
+    pub fn insert(&mut self, crc32: u32, setup: impl Into<Vec<u8>>) {
+        let mut setup = setup.into();
+        if !setup.starts_with(b"\x05vorbis") {
+            setup.splice(0..0, *b"\x05vorbis");
+        }
+        self.headers.insert(crc32, setup);
+    }
+
+    /// Setup header packet for `crc32`, `\x05vorbis` prefix included
+    pub fn get(&self, crc32: u32) -> Option<&[u8]> {
+        self.headers.get(&crc32).map(Vec::as_slice)
+    }
+
+    pub fn len(&self) -> usize {
+        self.headers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+}
+
+impl FromIterator<(u32, Vec<u8>)> for VorbisHeaderTable {
+    fn from_iter<I: IntoIterator<Item = (u32, Vec<u8>)>>(iter: I) -> Self {
+        let mut table = Self::new();
+        for (crc32, setup) in iter {
+            table.insert(crc32, setup);
+        }
+        table
+    }
+}
+
+/// WAV file around FSB5 PCM data; 8-bit samples are converted from signed to unsigned.
+fn pcm_to_wav(sample: &Fsb5Sample, mode: u32) -> Vec<u8> {
+    let (bits, format_tag): (u16, u16) = match mode {
+        MODE_PCM8 => (8, 1),
+        MODE_PCM16 => (16, 1),
+        MODE_PCM24 => (24, 1),
+        MODE_PCM32 => (32, 1),
+        _ => (32, 3),
+    };
+    let block_align = sample.channels as usize * usize::from(bits / 8);
+    let data = &sample.data[..sample.data.len() / block_align * block_align];
+
+    let mut out = Vec::with_capacity(44 + data.len());
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+    out.extend_from_slice(b"WAVEfmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&format_tag.to_le_bytes());
+    out.extend_from_slice(&(sample.channels as u16).to_le_bytes());
+    out.extend_from_slice(&sample.frequency.to_le_bytes());
+    out.extend_from_slice(&(sample.frequency * block_align as u32).to_le_bytes());
+    out.extend_from_slice(&(block_align as u16).to_le_bytes());
+    out.extend_from_slice(&bits.to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    if mode == MODE_PCM8 {
+        out.extend(data.iter().map(|b| b ^ 0x80));
+    } else {
+        out.extend_from_slice(data);
+    }
+    out
+}
+
+/// Ogg Vorbis stream from FMOD's `u16` length-prefixed Vorbis packets
+fn rebuild_vorbis(
+    sample: &Fsb5Sample,
+    serial: u32,
+    headers: &VorbisHeaderTable,
+) -> Result<Vec<u8>> {
+    let crc32 = sample
+        .vorbis_crc32
+        .ok_or_else(|| BinaryError::invalid_data("FSB5 Vorbis sample has no setup CRC chunk"))?;
+    let setup = headers.get(crc32).ok_or_else(|| {
+        BinaryError::unsupported(format!(
+            "FSB5 Vorbis setup header {:08x} is not in the header table",
+            crc32
+        ))
+    })?;
+    let modes = vorbis_mode_blockflags(setup, sample.channels).ok_or_else(|| {
+        BinaryError::invalid_data(format!("Malformed Vorbis setup header {:08x}", crc32))
+    })?;
+
+    let mut writer = OggWriter::new(serial);
+    writer.packet(&vorbis_identification(sample), 0);
+    writer.flush(false);
+    writer.packet(&vorbis_comment(), 0);
+    writer.packet(setup, 0);
+    writer.flush(false);
+
+    let packets = vorbis_packets(&sample.data);
+    let mode_bits = ilog(modes.len() as u32 - 1);
+    let mut granule = 0u64;
+    let mut previous_blocksize = None;
+    for (i, packet) in packets.iter().enumerate() {
+        let blocksize = vorbis_packet_blocksize(packet, &modes, mode_bits).ok_or_else(|| {
+            BinaryError::invalid_data(format!("Vorbis packet {} is not an audio packet", i))
+        })?;
+        if let Some(previous) = previous_blocksize {
+            let end = granule + u64::from(previous + blocksize) / 4;
+            // The last packet is trimmed to the sample count.
+            granule = if i + 1 == packets.len() && sample.num_samples > 0 {
+                end.min(u64::from(sample.num_samples)).max(granule)
+            } else {
+                end
+            };
+        }
+        previous_blocksize = Some(blocksize);
+        writer.packet(packet, granule as i64);
+    }
+    Ok(writer.finish())
+}
+
+/// FMOD's packet stream: `u16` length, packet, ... up to a zero length or the end
+fn vorbis_packets(data: &[u8]) -> Vec<&[u8]> {
+    let mut packets = Vec::new();
+    let mut offset = 0;
+    while let Some(size) = data.get(offset..offset + 2) {
+        let size = u16::from_le_bytes([size[0], size[1]]) as usize;
+        let Some(packet) = data.get(offset + 2..offset + 2 + size).filter(|_| size > 0) else {
+            break;
+        };
+        packets.push(packet);
+        offset += 2 + size;
+    }
+    packets
+}
+
+fn vorbis_identification(sample: &Fsb5Sample) -> Vec<u8> {
+    let mut packet = b"\x01vorbis".to_vec();
+    packet.extend_from_slice(&0u32.to_le_bytes());
+    packet.push(sample.channels as u8);
+    packet.extend_from_slice(&sample.frequency.to_le_bytes());
+    // Maximum, nominal and minimum bitrate unset
+    packet.extend_from_slice(&[0; 12]);
+    packet.push(
+        ((VORBIS_BLOCKSIZE_LONG.trailing_zeros() << 4) | VORBIS_BLOCKSIZE_SHORT.trailing_zeros())
+            as u8,
+    );
+    packet.push(1);
+    packet
+}
+
+fn vorbis_comment() -> Vec<u8> {
+    let mut packet = b"\x03vorbis".to_vec();
+    packet.extend_from_slice(&(VORBIS_VENDOR.len() as u32).to_le_bytes());
+    packet.extend_from_slice(VORBIS_VENDOR);
+    packet.extend_from_slice(&0u32.to_le_bytes());
+    packet.push(1);
+    packet
+}
+
+/// Block size of an audio packet: type bit 0, then the mode number selecting a block flag
+fn vorbis_packet_blocksize(packet: &[u8], modes: &[bool], mode_bits: u32) -> Option<u32> {
+    let mut reader = BitReader::new(packet);
+    if reader.read(1)? != 0 {
+        return None;
+    }
+    let long = *modes.get(reader.read(mode_bits)? as usize)?;
+    Some(if long {
+        VORBIS_BLOCKSIZE_LONG
+    } else {
+        VORBIS_BLOCKSIZE_SHORT
+    })
+}
+
+/// Number of bits needed to store `value`
+fn ilog(value: u32) -> u32 {
+    32 - value.leading_zeros()
+}
+
+/// Block flag of each mode of a Vorbis setup header
+///
+/// The modes come last, so everything before them (codebooks, floors, residues, mappings) is
+/// walked per the Vorbis I specification.
+fn vorbis_mode_blockflags(setup: &[u8], channels: u32) -> Option<Vec<bool>> {
+    let mut r = BitReader::new(setup.strip_prefix(b"\x05vorbis")?);
+
+    for _ in 0..r.read(8)? + 1 {
+        if r.read(24)? != 0x56_4342 {
+            return None;
+        }
+        let dimensions = r.read(16)?;
+        let entries = r.read(24)?;
+        if r.read(1)? == 1 {
+            // Ordered: runs of entries per increasing length
+            r.read(5)?;
+            let mut entry = 0;
+            while entry < entries {
+                entry += r.read(ilog(entries - entry))?;
+            }
+            if entry > entries {
+                return None;
+            }
+        } else {
+            let sparse = r.read(1)? == 1;
+            for _ in 0..entries {
+                if !sparse || r.read(1)? == 1 {
+                    r.read(5)?;
+                }
+            }
+        }
+        match r.read(4)? {
+            0 => {}
+            lookup @ (1 | 2) => {
+                r.skip(64)?;
+                let value_bits = r.read(4)? + 1;
+                r.read(1)?;
+                let values = if lookup == 1 {
+                    lookup1_values(entries, dimensions)
+                } else {
+                    u64::from(entries) * u64::from(dimensions)
+                };
+                r.skip(values * u64::from(value_bits))?;
+            }
+            _ => return None,
+        }
+    }
+
+    // Time domain transforms: placeholders, all zero
+    for _ in 0..r.read(6)? + 1 {
+        if r.read(16)? != 0 {
+            return None;
+        }
+    }
+
+    for _ in 0..r.read(6)? + 1 {
+        match r.read(16)? {
+            0 => {
+                r.skip(8 + 16 + 16 + 6 + 8)?;
+                let books = r.read(4)? + 1;
+                r.skip(u64::from(books) * 8)?;
+            }
+            1 => {
+                let partitions = r.read(5)?;
+                let classes: Vec<u32> =
+                    (0..partitions).map(|_| r.read(4)).collect::<Option<_>>()?;
+                let class_count = classes.iter().max().map_or(0, |&c| c + 1);
+                let mut dimensions = Vec::with_capacity(class_count as usize);
+                for _ in 0..class_count {
+                    dimensions.push(r.read(3)? + 1);
+                    let subclasses = r.read(2)?;
+                    if subclasses > 0 {
+                        r.read(8)?;
+                    }
+                    r.skip((1u64 << subclasses) * 8)?;
+                }
+                r.read(2)?;
+                let range_bits = r.read(4)?;
+                for class in classes {
+                    r.skip(u64::from(dimensions[class as usize] * range_bits))?;
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    for _ in 0..r.read(6)? + 1 {
+        if r.read(16)? > 2 {
+            return None;
+        }
+        r.skip(24 * 3)?;
+        let classifications = r.read(6)? + 1;
+        r.read(8)?;
+        let mut books = 0;
+        for _ in 0..classifications {
+            let mut cascade = r.read(3)?;
+            if r.read(1)? == 1 {
+                cascade |= r.read(5)? << 3;
+            }
+            books += cascade.count_ones();
+        }
+        r.skip(u64::from(books) * 8)?;
+    }
+
+    let channel_bits = ilog(channels.saturating_sub(1));
+    for _ in 0..r.read(6)? + 1 {
+        if r.read(16)? != 0 {
+            return None;
+        }
+        let submaps = if r.read(1)? == 1 { r.read(4)? + 1 } else { 1 };
+        if r.read(1)? == 1 {
+            let steps = r.read(8)? + 1;
+            r.skip(u64::from(steps * channel_bits * 2))?;
+        }
+        if r.read(2)? != 0 {
+            return None;
+        }
+        if submaps > 1 {
+            r.skip(u64::from(channels) * 4)?;
+        }
+        r.skip(u64::from(submaps) * 24)?;
+    }
+
+    let modes = (0..r.read(6)? + 1)
+        .map(|_| {
+            let blockflag = r.read(1)? == 1;
+            r.skip(16 + 16 + 8)?;
+            Some(blockflag)
+        })
+        .collect::<Option<Vec<_>>>()?;
+    (r.read(1)? == 1).then_some(modes)
+}
+
+/// Largest `r` with `r^dimensions <= entries`
+fn lookup1_values(entries: u32, dimensions: u32) -> u64 {
+    if dimensions == 0 {
+        return 0;
+    }
+    let fits = |r: u64| {
+        r.checked_pow(dimensions)
+            .is_some_and(|p| p <= u64::from(entries))
+    };
+    // Start from the float estimate and correct its rounding.
+    let mut r = f64::from(entries).powf(1.0 / f64::from(dimensions)) as u64;
+    while fits(r + 1) {
+        r += 1;
+    }
+    while r > 0 && !fits(r) {
+        r -= 1;
+    }
+    r
+}
+
+/// Vorbis bit packing: least significant bit of each byte first
+struct BitReader<'a> {
+    data: &'a [u8],
+    bit: u64,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, bit: 0 }
+    }
+
+    fn read(&mut self, bits: u32) -> Option<u32> {
+        let mut value = 0u32;
+        for i in 0..bits {
+            let byte = *self.data.get((self.bit / 8) as usize)?;
+            value |= u32::from((byte >> (self.bit % 8)) & 1) << i;
+            self.bit += 1;
+        }
+        Some(value)
+    }
+
+    fn skip(&mut self, bits: u64) -> Option<()> {
+        self.bit += bits;
+        (self.bit <= self.data.len() as u64 * 8).then_some(())
+    }
+}
+
+const OGG_CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u32) << 24;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04C1_1DB7
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn ogg_crc(data: &[u8]) -> u32 {
+    data.iter().fold(0, |crc, &byte| {
+        (crc << 8) ^ OGG_CRC_TABLE[((crc >> 24) as u8 ^ byte) as usize]
+    })
+}
+
+/// Lays packets out into the pages of a single logical Ogg stream
+struct OggWriter {
+    serial: u32,
+    sequence: u32,
+    out: Vec<u8>,
+    lacing: Vec<u8>,
+    body: Vec<u8>,
+    /// Granule position of the last packet completed on the pending page, -1 if none
+    granule: i64,
+    /// The pending page starts with the rest of a packet
+    continued: bool,
+}
+
+impl OggWriter {
+    fn new(serial: u32) -> Self {
+        Self {
+            serial,
+            sequence: 0,
+            out: Vec::new(),
+            lacing: Vec::new(),
+            body: Vec::new(),
+            granule: -1,
+            continued: false,
+        }
+    }
+
+    fn packet(&mut self, packet: &[u8], granule: i64) {
+        if self.body.len() >= OGG_PAGE_TARGET {
+            self.flush(false);
+        }
+        // 255-byte segments, closed by one shorter than 255 (possibly empty)
+        let segments = packet.len() / 255 + 1;
+        for i in 0..segments {
+            let segment = &packet[i * 255..packet.len().min(i * 255 + 255)];
+            self.lacing.push(segment.len() as u8);
+            self.body.extend_from_slice(segment);
+            let last = i + 1 == segments;
+            if last {
+                self.granule = granule;
+            }
+            if self.lacing.len() == 255 {
+                self.flush(false);
+                self.continued = !last;
+            }
+        }
+    }
+
+    fn flush(&mut self, end: bool) {
+        if self.lacing.is_empty() && !end {
+            return;
+        }
+        let mut flags = 0u8;
+        if self.continued {
+            flags |= 0x01;
+        }
+        if self.sequence == 0 {
+            flags |= 0x02;
+        }
+        if end {
+            flags |= 0x04;
+        }
+        let start = self.out.len();
+        self.out.extend_from_slice(b"OggS\0");
+        self.out.push(flags);
+        self.out.extend_from_slice(&self.granule.to_le_bytes());
+        self.out.extend_from_slice(&self.serial.to_le_bytes());
+        self.out.extend_from_slice(&self.sequence.to_le_bytes());
+        self.out.extend_from_slice(&[0; 4]);
+        self.out.push(self.lacing.len() as u8);
+        self.out.append(&mut self.lacing);
+        self.out.append(&mut self.body);
+        let crc = ogg_crc(&self.out[start..]);
+        self.out[start + 22..start + 26].copy_from_slice(&crc.to_le_bytes());
+
+        self.sequence += 1;
+        self.granule = -1;
+        self.continued = false;
+    }
+
+    /// Close the stream with an end-of-stream page
+    fn finish(mut self) -> Vec<u8> {
+        self.flush(true);
+        self.out
+    }
+}
@@ -11,6 +11,8 @@
 //! - `converter` - Main conversion logic from Unity objects
 //! - `decoder` - Audio decoding using Symphonia
 //! - `export` - Audio export functionality
+//! - `fsb5` - FMOD FSB5 banks split into playable per-sub-sound files
+//! - `stream` - Streaming decode into an `io::Write` sink for long clips
 //!
 //! # Examples
 //!
@@ -36,18 +38,26 @@ pub mod converter;
 pub mod decoder;
 pub mod export;
 pub mod formats;
+pub mod fsb5;
+pub mod stream;
 pub mod types;
 
 // Re-export main types for easy access
 pub use converter::{AudioClipConverter, AudioClipProcessor}; // Processor is legacy alias
 pub use decoder::AudioDecoder;
 pub use export::{AudioExporter, AudioFormat, ExportOptions};
-pub use formats::{AudioCompressionFormat, AudioFormatInfo, FMODSoundType};
+pub use formats::{AudioCompressionFormat, AudioFormatInfo, DetectedCodec, FMODSoundType};
+pub use fsb5::{Fsb5Bank, Fsb5Sample, VorbisHeaderTable};
+pub use stream::{AudioStreamOptions, AudioStreamStats, WAV_HEADER_LEN};
 pub use types::{
-    AudioAnalysis, AudioClip, AudioClipMeta, AudioInfo, AudioProperties, DecodedAudio,
+    AudioAnalysis, AudioClip, AudioClipMeta, AudioInfo, AudioProperties, AudioSample, DecodedAudio,
     StreamingInfo,
 };
 
+/// Audio data size (bytes) from which [`AudioProcessor::process_and_export`] streams the decode
+/// instead of holding the whole PCM stream in memory.
+pub const STREAMING_EXPORT_THRESHOLD: usize = 4 * 1024 * 1024;
+
 /// Main audio processing facade
 ///
 /// This struct provides a high-level interface for audio processing,
@@ -66,6 +76,12 @@ impl AudioProcessor {
         }
     }
 
+    /// Use `decoder` (e.g. one with Vorbis setup headers) for decoding and sample extraction
+    pub fn with_decoder(mut self, decoder: AudioDecoder) -> Self {
+        self.decoder = decoder;
+        self
+    }
+
     /// Process Unity object to AudioClip
     pub fn convert_object(
         &self,
@@ -85,16 +101,83 @@ impl AudioProcessor {
     }
 
     /// Full pipeline: convert object -> decode -> export
+    ///
+    /// A streamed clip reads its bytes from the `.resource` node of `bundle` when one is given
+    /// (see [`AudioClipConverter::get_audio_data_from_bundle`]). Clips with at least
+    /// [`STREAMING_EXPORT_THRESHOLD`] bytes of audio data are streamed to the file with
+    /// [`Self::stream_export`] instead of being decoded in memory first.
     pub fn process_and_export<P: AsRef<std::path::Path>>(
         &self,
         obj: &crate::object::UnityObject,
+        bundle: Option<&crate::bundle::AssetBundle>,
         output_path: P,
     ) -> crate::error::Result<()> {
-        let audio_clip = self.convert_object(obj)?;
+        let mut audio_clip = self.convert_object(obj)?;
+        if let Some(bundle) = bundle
+            && audio_clip.data.is_empty()
+            && audio_clip.is_streamed()
+        {
+            audio_clip.data = self
+                .converter
+                .get_audio_data_from_bundle(&audio_clip, bundle)?;
+        }
+        if audio_clip.data.len() >= STREAMING_EXPORT_THRESHOLD {
+            return self.stream_export(&audio_clip, output_path).map(|_| ());
+        }
         let decoded_audio = self.decode_audio(&audio_clip)?;
         AudioExporter::export_auto(&decoded_audio, output_path)
     }
 
+    /// Decode `clip` straight into a file (raw PCM for `.pcm`/`.raw`, WAV otherwise), patching
+    /// the WAV header afterwards if its sizes were not known up front.
+    pub fn stream_export<P: AsRef<std::path::Path>>(
+        &self,
+        clip: &AudioClip,
+        output_path: P,
+    ) -> crate::error::Result<AudioStreamStats> {
+        use std::io::Write;
+
+        let path = output_path.as_ref();
+        let format = match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("pcm") | Some("raw") => AudioFormat::RawPcm,
+            _ => AudioFormat::Wav,
+        };
+        let file = std::fs::File::create(path).map_err(|e| {
+            crate::error::BinaryError::generic(format!("Failed to create audio file: {}", e))
+        })?;
+        let mut writer = std::io::BufWriter::new(file);
+        let stats = self.decoder.decode_to_writer(
+            clip,
+            &mut writer,
+            format,
+            &AudioStreamOptions::default(),
+        )?;
+        if !stats.header_complete {
+            stats.patch_wav_header(&mut writer)?;
+        }
+        writer
+            .flush()
+            .map_err(|e| crate::error::BinaryError::generic(format!("Flush error: {}", e)))?;
+        Ok(stats)
+    }
+
+    /// Write the clip's audio to `dir` as playable files, one per FSB5 sub-sound
+    ///
+    /// See [`AudioClip::extract_samples`]; FSB5 Vorbis banks use the decoder's setup headers.
+    pub fn export_samples<P: AsRef<std::path::Path>>(
+        &self,
+        clip: &AudioClip,
+        dir: P,
+    ) -> crate::error::Result<Vec<std::path::PathBuf>> {
+        let samples = clip.extract_samples_with(self.decoder.vorbis_headers())?;
+        AudioExporter::export_samples(&samples, dir)
+    }
+
     /// Check if a format can be processed
     pub fn can_process(&self, format: AudioCompressionFormat) -> bool {
         self.converter.can_process(format) && self.decoder.can_decode(format)
@@ -178,6 +261,14 @@ pub fn export_audio<P: AsRef<std::path::Path>>(
     AudioExporter::export_auto(audio, path)
 }
 
+/// Quick function to write an AudioClip's sub-sounds to `dir` as `.ogg`/`.wav`/`.mp3` files
+pub fn export_audio_samples<P: AsRef<std::path::Path>>(
+    clip: &AudioClip,
+    dir: P,
+) -> crate::error::Result<Vec<std::path::PathBuf>> {
+    AudioExporter::export_samples(&clip.extract_samples()?, dir)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
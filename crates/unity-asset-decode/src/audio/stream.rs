@@ -0,0 +1,377 @@
+//! Streaming audio export
+//!
+//! [`AudioDecoder::decode_to_writer`] decodes a clip packet by packet and writes each packet to
+//! the sink as soon as it is decoded, so memory stays at a few decode frames instead of the
+//! whole PCM stream held by [`DecodedAudio`](super::DecodedAudio).
+//!
+//! WAV output always has an 80-byte header: `RIFF`, a 28-byte `JUNK` chunk reserved for an RF64
+//! `ds64` chunk, `fmt ` and the `data` chunk header. When the length is known up front (most
+//! containers declare it) the header is final; otherwise the sizes are left at `0xFFFFFFFF` and
+//! [`AudioStreamStats::patch_wav_header`] fixes them on a seekable sink. Outputs over 4 GiB are
+//! written as RF64.
+
+use super::decoder::{AudioDecoder, OpenedTrack, open_track};
+use super::export::AudioFormat;
+use super::formats::AudioCompressionFormat;
+use super::types::AudioClip;
+use crate::error::{BinaryError, Result};
+use std::io::{Seek, SeekFrom, Write};
+use symphonia::core::audio::{SampleBuffer, SignalSpec};
+use symphonia::core::conv::ConvertibleSample;
+use symphonia::core::errors::Error as SymphoniaError;
+
+/// Size of the WAV header written by [`AudioDecoder::decode_to_writer`].
+pub const WAV_HEADER_LEN: u64 = 80;
+
+/// Largest Ogg passthrough write.
+const OGG_CHUNK: usize = 64 * 1024;
+
+/// Options for [`AudioDecoder::decode_to_writer`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct AudioStreamOptions {
+    /// Sample width of WAV / raw PCM output: 16 or 32.
+    pub bit_depth: u8,
+}
+
+impl AudioStreamOptions {
+    /// Set `bit_depth`.
+    pub fn with_bit_depth(mut self, bit_depth: u8) -> Self {
+        self.bit_depth = bit_depth;
+        self
+    }
+}
+
+impl Default for AudioStreamOptions {
+    fn default() -> Self {
+        Self { bit_depth: 16 }
+    }
+}
+
+/// What [`AudioDecoder::decode_to_writer`] wrote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioStreamStats {
+    pub format: AudioFormat,
+    pub sample_rate: u32,
+    pub channels: u32,
+    /// Bits per sample of PCM output (`0` for Ogg passthrough).
+    pub bits_per_sample: u16,
+    /// Interleaved samples written (frames × channels).
+    pub samples_written: u64,
+    /// Audio payload bytes written, excluding the WAV header.
+    pub data_bytes: u64,
+    /// Duration in seconds.
+    pub duration: f64,
+    /// Whether the WAV header written up front already has the final sizes. When `false`,
+    /// call [`Self::patch_wav_header`] on a seekable sink.
+    pub header_complete: bool,
+    /// Largest buffer held at once while converting decoded packets.
+    pub peak_buffer_bytes: usize,
+}
+
+impl AudioStreamStats {
+    /// Frames written (samples per channel).
+    pub fn frames(&self) -> u64 {
+        self.samples_written / u64::from(self.channels.max(1))
+    }
+
+    /// Total bytes written, header included.
+    pub fn total_bytes(&self) -> u64 {
+        match self.format {
+            AudioFormat::Wav => WAV_HEADER_LEN + self.data_bytes,
+            _ => self.data_bytes,
+        }
+    }
+
+    /// Rewrite the WAV header with the final sizes (switching to RF64 past 4 GiB).
+    ///
+    /// The WAV must have been written from the start of `sink`; the position is restored to the
+    /// end afterwards.
+    pub fn patch_wav_header<W: Write + Seek>(&self, sink: &mut W) -> Result<()> {
+        if self.format != AudioFormat::Wav {
+            return Err(BinaryError::invalid_data("Only WAV output has a header"));
+        }
+        let header = wav_header(
+            self.sample_rate,
+            self.channels,
+            self.bits_per_sample,
+            Some(self.data_bytes),
+        );
+        sink.seek(SeekFrom::Start(0)).map_err(write_error)?;
+        sink.write_all(&header).map_err(write_error)?;
+        sink.seek(SeekFrom::End(0)).map_err(write_error)?;
+        Ok(())
+    }
+}
+
+impl AudioDecoder {
+    /// Decode `clip` into `sink` as `format` without holding the whole PCM stream in memory.
+    ///
+    /// WAV and raw PCM are decoded packet by packet; [`AudioFormat::Ogg`] copies an Ogg Vorbis
+    /// clip through unchanged (reading rate, channels and length from its pages).
+    pub fn decode_to_writer(
+        &self,
+        clip: &AudioClip,
+        sink: &mut dyn Write,
+        format: AudioFormat,
+        options: &AudioStreamOptions,
+    ) -> Result<AudioStreamStats> {
+        match (format, options.bit_depth) {
+            (AudioFormat::Ogg, _) => ogg_passthrough(clip, sink),
+            (_, 16) => stream_pcm::<i16>(clip, sink, format),
+            (_, 32) => stream_pcm::<i32>(clip, sink, format),
+            (_, bits) => Err(BinaryError::invalid_data(format!(
+                "Unsupported bit depth for streaming export: {}",
+                bits
+            ))),
+        }
+    }
+}
+
+/// A PCM sample type written little-endian.
+trait PcmSample: ConvertibleSample {
+    const BITS: u16;
+    fn put_le(self, out: &mut Vec<u8>);
+}
+
+impl PcmSample for i16 {
+    const BITS: u16 = 16;
+    fn put_le(self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+}
+
+impl PcmSample for i32 {
+    const BITS: u16 = 32;
+    fn put_le(self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+}
+
+fn stream_pcm<S: PcmSample>(
+    clip: &AudioClip,
+    sink: &mut dyn Write,
+    format: AudioFormat,
+) -> Result<AudioStreamStats> {
+    let OpenedTrack {
+        format: mut reader,
+        mut decoder,
+        track_id,
+        n_frames,
+    } = open_track(clip)?;
+
+    let mut stats = AudioStreamStats {
+        format,
+        sample_rate: 0,
+        channels: 0,
+        bits_per_sample: S::BITS,
+        samples_written: 0,
+        data_bytes: 0,
+        duration: 0.0,
+        header_complete: format != AudioFormat::Wav,
+        peak_buffer_bytes: 0,
+    };
+    let mut samples: Option<(SignalSpec, SampleBuffer<S>)> = None;
+    let mut bytes = Vec::new();
+    let mut header_frames = None;
+
+    loop {
+        let packet = match reader.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::ResetRequired) | Err(SymphoniaError::IoError(_)) => break,
+            Err(err) => return Err(BinaryError::generic(format!("Decode error: {}", err))),
+        };
+        while !reader.metadata().is_latest() {
+            reader.metadata().pop();
+        }
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(SymphoniaError::IoError(_)) => break,
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(err) => return Err(BinaryError::generic(format!("Decode error: {}", err))),
+        };
+        let spec = *decoded.spec();
+        if stats.channels == 0 {
+            stats.sample_rate = spec.rate;
+            stats.channels = spec.channels.count() as u32;
+            if format == AudioFormat::Wav {
+                let data_bytes = n_frames
+                    .map(|frames| frames * u64::from(stats.channels) * u64::from(S::BITS / 8));
+                header_frames = n_frames;
+                sink.write_all(&wav_header(
+                    stats.sample_rate,
+                    stats.channels,
+                    S::BITS,
+                    data_bytes,
+                ))
+                .map_err(write_error)?;
+            }
+        } else if spec.rate != stats.sample_rate || spec.channels.count() as u32 != stats.channels {
+            return Err(BinaryError::unsupported(
+                "Sample rate or channel layout changed mid-stream",
+            ));
+        }
+
+        let needed = decoded.capacity() * spec.channels.count();
+        let buffer = match &mut samples {
+            Some((buffer_spec, buffer)) if *buffer_spec == spec && buffer.capacity() >= needed => {
+                buffer
+            }
+            slot => {
+                let buffer = SampleBuffer::<S>::new(decoded.capacity() as u64, spec);
+                &mut slot.insert((spec, buffer)).1
+            }
+        };
+        buffer.copy_interleaved_ref(decoded);
+
+        bytes.clear();
+        for &sample in buffer.samples() {
+            sample.put_le(&mut bytes);
+        }
+        sink.write_all(&bytes).map_err(write_error)?;
+
+        stats.samples_written += buffer.samples().len() as u64;
+        stats.data_bytes += bytes.len() as u64;
+        stats.peak_buffer_bytes = stats
+            .peak_buffer_bytes
+            .max(buffer.capacity() * std::mem::size_of::<S>() + bytes.capacity());
+    }
+
+    if stats.channels == 0 {
+        return Err(BinaryError::generic("No audio samples decoded"));
+    }
+    if format == AudioFormat::Wav {
+        stats.header_complete = header_frames == Some(stats.frames());
+    }
+    stats.duration = stats.frames() as f64 / f64::from(stats.sample_rate);
+    Ok(stats)
+}
+
+/// Copy an Ogg stream page by page, reading the Vorbis identification header and the last
+/// granule position for the stats.
+fn ogg_passthrough(clip: &AudioClip, sink: &mut dyn Write) -> Result<AudioStreamStats> {
+    let data = clip.data.as_slice();
+    if clip.compression_format() != AudioCompressionFormat::Vorbis || !data.starts_with(b"OggS") {
+        return Err(BinaryError::unsupported(
+            "Ogg passthrough needs a clip holding an Ogg Vorbis stream",
+        ));
+    }
+
+    let mut stats = AudioStreamStats {
+        format: AudioFormat::Ogg,
+        sample_rate: 0,
+        channels: 0,
+        bits_per_sample: 0,
+        samples_written: 0,
+        data_bytes: 0,
+        duration: 0.0,
+        header_complete: true,
+        peak_buffer_bytes: 0,
+    };
+    let mut frames = 0u64;
+    let mut offset = 0;
+    while offset < data.len() {
+        let page = ogg_page(&data[offset..]).ok_or_else(|| {
+            BinaryError::invalid_data(format!("Malformed Ogg page at offset {}", offset))
+        })?;
+        if stats.channels == 0 {
+            let packet = &data[offset + page.header_len..offset + page.len];
+            if packet.len() >= 16 && packet.starts_with(b"\x01vorbis") {
+                stats.channels = u32::from(packet[11]);
+                stats.sample_rate =
+                    u32::from_le_bytes([packet[12], packet[13], packet[14], packet[15]]);
+            }
+        }
+        if page.granule >= 0 {
+            frames = frames.max(page.granule as u64);
+        }
+        for chunk in data[offset..offset + page.len].chunks(OGG_CHUNK) {
+            sink.write_all(chunk).map_err(write_error)?;
+        }
+        offset += page.len;
+    }
+
+    if stats.channels == 0 || stats.sample_rate == 0 {
+        return Err(BinaryError::invalid_data(
+            "Ogg stream has no Vorbis identification header",
+        ));
+    }
+    stats.samples_written = frames * u64::from(stats.channels);
+    stats.data_bytes = data.len() as u64;
+    stats.duration = frames as f64 / f64::from(stats.sample_rate);
+    Ok(stats)
+}
+
+struct OggPage {
+    granule: i64,
+    /// Page header plus segment table.
+    header_len: usize,
+    /// Whole page, body included.
+    len: usize,
+}
+
+fn ogg_page(data: &[u8]) -> Option<OggPage> {
+    if data.len() < 27 || !data.starts_with(b"OggS") {
+        return None;
+    }
+    let granule = i64::from_le_bytes(data[6..14].try_into().ok()?);
+    let segments = data[26] as usize;
+    let header_len = 27 + segments;
+    let body: usize = data.get(27..header_len)?.iter().map(|&s| s as usize).sum();
+    let len = header_len + body;
+    (len <= data.len()).then_some(OggPage {
+        granule,
+        header_len,
+        len,
+    })
+}
+
+/// `RIFF`/`RF64` header with a 28-byte `JUNK`/`ds64` chunk; `data_bytes: None` leaves the sizes
+/// unset (`0xFFFFFFFF`).
+fn wav_header(sample_rate: u32, channels: u32, bits: u16, data_bytes: Option<u64>) -> Vec<u8> {
+    let block_align = channels * u32::from(bits / 8);
+    let riff_bytes = data_bytes.map(|d| WAV_HEADER_LEN - 8 + d);
+    let rf64 = riff_bytes.is_some_and(|r| r > u64::from(u32::MAX));
+    let small = |size: Option<u64>| match size {
+        Some(size) if !rf64 => size as u32,
+        _ => u32::MAX,
+    };
+
+    let mut out = Vec::with_capacity(WAV_HEADER_LEN as usize);
+    out.extend_from_slice(if rf64 { b"RF64" } else { b"RIFF" });
+    out.extend_from_slice(&small(riff_bytes).to_le_bytes());
+    out.extend_from_slice(b"WAVE");
+
+    out.extend_from_slice(if rf64 { b"ds64" } else { b"JUNK" });
+    out.extend_from_slice(&28u32.to_le_bytes());
+    if rf64 {
+        let data_bytes = data_bytes.unwrap_or_default();
+        out.extend_from_slice(&riff_bytes.unwrap_or_default().to_le_bytes());
+        out.extend_from_slice(&data_bytes.to_le_bytes());
+        out.extend_from_slice(&(data_bytes / u64::from(block_align.max(1))).to_le_bytes());
+        out.extend_from_slice(&0u32.to_le_bytes()); // table length
+    } else {
+        out.extend_from_slice(&[0; 28]);
+    }
+
+    out.extend_from_slice(b"fmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    out.extend_from_slice(&(channels as u16).to_le_bytes());
+    out.extend_from_slice(&sample_rate.to_le_bytes());
+    out.extend_from_slice(&(sample_rate * block_align).to_le_bytes());
+    out.extend_from_slice(&(block_align as u16).to_le_bytes());
+    out.extend_from_slice(&bits.to_le_bytes());
+
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&small(data_bytes).to_le_bytes());
+    out
+}
+
+fn write_error(e: std::io::Error) -> BinaryError {
+    BinaryError::generic(format!("Write error: {}", e))
+}
@@ -4,6 +4,8 @@
 
 use super::formats::AudioCompressionFormat;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use unity_asset_binary::bytes_preview::BytesPreview;
 
 /// Streaming info for external audio data
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -61,7 +63,7 @@ impl Default for AudioClipMeta {
 ///
 /// This structure contains all the data needed to represent a Unity AudioClip object.
 /// It includes both metadata and the actual audio data.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Clone, Serialize, Deserialize, Default)]
 pub struct AudioClip {
     pub name: String,
     pub meta: AudioClipMeta,
@@ -189,6 +191,77 @@ pub struct AudioProperties {
     pub length: f32,
 }
 
+/// One-line summary, e.g. `AudioClip "hit" format=Vorbis channels=2 frequency=44100 length=1.250s data=20480`.
+///
+/// Streamed clips append `streamed=<size>`.
+impl fmt::Display for AudioClip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let properties = self.properties();
+        write!(
+            f,
+            "AudioClip {:?} format={:?} channels={} frequency={} length={:.3}s data={}",
+            self.name,
+            self.compression_format(),
+            properties.channels,
+            properties.sample_rate,
+            properties.length,
+            self.data.len()
+        )?;
+        if self.is_streamed() {
+            write!(f, " streamed={}", self.stream_info.size)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for AudioClip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AudioClip")
+            .field("name", &self.name)
+            .field("meta", &self.meta)
+            .field("source", &self.source)
+            .field("offset", &self.offset)
+            .field("size", &self.size)
+            .field("stream_info", &self.stream_info)
+            .field("data", &BytesPreview(&self.data))
+            .field("ambisonic", &self.ambisonic)
+            .finish()
+    }
+}
+
+/// One playable file carried by an AudioClip, see [`AudioClip::extract_samples`]
+#[derive(Clone)]
+pub struct AudioSample {
+    pub name: String,
+    /// Extension matching `data` (`ogg`, `wav`, `mp3`, ...)
+    pub extension: &'static str,
+    pub frequency: u32,
+    pub channels: u32,
+    /// Loop start and end frame, when the container stores them
+    pub loop_points: Option<(u32, u32)>,
+    pub data: Vec<u8>,
+}
+
+impl AudioSample {
+    /// `name.extension`, with path separators in the name replaced by `_`
+    pub fn file_name(&self) -> String {
+        format!("{}.{}", self.name.replace(['/', '\\'], "_"), self.extension)
+    }
+}
+
+impl fmt::Debug for AudioSample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AudioSample")
+            .field("name", &self.name)
+            .field("extension", &self.extension)
+            .field("frequency", &self.frequency)
+            .field("channels", &self.channels)
+            .field("loop_points", &self.loop_points)
+            .field("data", &BytesPreview(&self.data))
+            .finish()
+    }
+}
+
 /// Audio information structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioInfo {
@@ -208,6 +281,10 @@ pub struct DecodedAudio {
     pub sample_rate: u32,
     pub channels: u32,
     pub duration: f32,
+    /// Container identified from the payload's magic bytes, if any
+    pub codec: Option<super::formats::DetectedCodec>,
+    /// Non-fatal findings, such as a declared format that does not match the payload
+    pub warnings: Vec<String>,
 }
 
 impl DecodedAudio {
@@ -219,6 +296,8 @@ impl DecodedAudio {
             sample_rate,
             channels,
             duration,
+            codec: None,
+            warnings: Vec::new(),
         }
     }
 
@@ -0,0 +1,32 @@
+//! Embedded font file export
+
+use super::types::{Font, FontFormat};
+use crate::error::{BinaryError, Result};
+use std::path::{Path, PathBuf};
+
+/// Font file exporter
+pub struct FontExporter;
+
+impl FontExporter {
+    /// Write `font.font_data` to `path` with the extension of its format
+    ///
+    /// Any extension on `path` is replaced. Data without a recognised sfnt header is written as
+    /// `.ttf`, which is what Unity imports most. Returns the path written.
+    pub fn export<P: AsRef<Path>>(font: &Font, path: P) -> Result<PathBuf> {
+        if !font.has_font_data() {
+            return Err(BinaryError::invalid_data(format!(
+                "Font '{}' has no embedded font data",
+                font.name
+            )));
+        }
+        let format = font.format().unwrap_or(FontFormat::TrueType);
+        let path = path.as_ref().with_extension(format.extension());
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, &font.font_data)?;
+        Ok(path)
+    }
+}
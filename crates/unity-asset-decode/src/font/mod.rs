@@ -0,0 +1,35 @@
+//! Unity Font processing module
+//!
+//! Font objects (class 128) carry the glyph layout Unity generated for the legacy text system
+//! and, in `m_FontData`, the TTF/OTF file the font was imported from. The file is exported as
+//! is, with its extension picked from the sfnt magic bytes.
+//!
+//! # Architecture
+//!
+//! - `types` - Font metadata and the embedded file format
+//! - `parser` - Parsing from TypeTree data or the raw object layout
+//! - `export` - Writing the embedded font file
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use unity_asset_decode::font::{FontExporter, fonts};
+//!
+//! # fn example(asset: &unity_asset_decode::asset::SerializedFile) -> unity_asset_decode::Result<()> {
+//! for (_, font) in fonts(asset) {
+//!     println!("{}: {} pt, line spacing {}", font.name, font.font_size, font.line_spacing);
+//!     if font.has_font_data() {
+//!         FontExporter::export(&font, format!("fonts/{}", font.name))?;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod export;
+pub mod parser;
+pub mod types;
+
+pub use export::FontExporter;
+pub use parser::fonts;
+pub use types::{Font, FontFormat};
@@ -0,0 +1,188 @@
+//! Font parsing from TypeTree data or the raw object layout
+//!
+//! Without a TypeTree the layout is read as UnityPy does: 5.5 moved the material and texture
+//! references to the front and made the character rects fixed-size, 4.x dropped the grid
+//! fields of 3.x. Fields after `m_FontData` (font names, fallbacks) are only read when present.
+
+use super::types::Font;
+use crate::asset::SerializedFile;
+use crate::error::{BinaryError, Result};
+use crate::object::UnityObject;
+use crate::reader::{BinaryReader, ByteOrder};
+use crate::unity_version::UnityVersion;
+use indexmap::IndexMap;
+use unity_asset_core::{UnityValue, class_ids};
+
+type Fields = IndexMap<String, UnityValue>;
+
+impl Font {
+    /// Parse Font from UnityObject, from its TypeTree when it has one
+    pub fn from_unity_object(obj: &UnityObject, version: &UnityVersion) -> Result<Self> {
+        if obj.class_id() != class_ids::FONT {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not a Font (class_id: {})",
+                obj.class_id()
+            )));
+        }
+        if obj.has_property("m_FontData") {
+            Self::from_typetree(obj.class.properties())
+        } else {
+            Self::from_binary_data(obj.raw_data(), obj.byte_order(), version)
+        }
+    }
+
+    /// Parse Font from TypeTree data
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        let font_data = match properties.get("m_FontData") {
+            Some(UnityValue::Bytes(b)) => b.clone(),
+            Some(UnityValue::String(s)) => s.as_bytes().to_vec(),
+            Some(UnityValue::Array(items)) => items
+                .iter()
+                .filter_map(UnityValue::as_i64)
+                .map(|v| v as u8)
+                .collect(),
+            _ => return Err(BinaryError::invalid_data("Font typetree has no m_FontData")),
+        };
+        let float = |key: &str| {
+            properties
+                .get(key)
+                .and_then(UnityValue::as_f64)
+                .unwrap_or_default() as f32
+        };
+        let int = |key: &str| {
+            properties
+                .get(key)
+                .and_then(UnityValue::as_i64)
+                .unwrap_or_default() as i32
+        };
+        Ok(Self {
+            name: properties
+                .get("m_Name")
+                .and_then(UnityValue::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            font_size: float("m_FontSize"),
+            line_spacing: float("m_LineSpacing"),
+            character_spacing: int("m_CharacterSpacing"),
+            character_padding: int("m_CharacterPadding"),
+            ascii_start_offset: int("m_AsciiStartOffset"),
+            character_count: match properties.get("m_CharacterRects") {
+                Some(UnityValue::Array(items)) => items.len(),
+                _ => 0,
+            },
+            font_names: match properties.get("m_FontNames") {
+                Some(UnityValue::Array(items)) => items
+                    .iter()
+                    .filter_map(UnityValue::as_str)
+                    .map(str::to_string)
+                    .collect(),
+                _ => Vec::new(),
+            },
+            font_data,
+        })
+    }
+
+    /// Parse Font from an object's raw bytes (no TypeTree)
+    ///
+    /// PPtrs are read as 12 bytes from Unity 5 on (serialized format 14+) and 8 bytes before.
+    pub fn from_binary_data(
+        data: &[u8],
+        byte_order: ByteOrder,
+        version: &UnityVersion,
+    ) -> Result<Self> {
+        let mut reader = BinaryReader::new(data, byte_order);
+        let pptr_size = if version.major >= 5 { 12 } else { 8 };
+        let mut font = Font {
+            name: reader.read_aligned_string()?,
+            ..Default::default()
+        };
+
+        if version.at_least(5, 5) {
+            font.line_spacing = reader.read_f32()?;
+            reader.skip_bytes(pptr_size)?; // m_DefaultMaterial
+            font.font_size = reader.read_f32()?;
+            reader.skip_bytes(pptr_size)?; // m_Texture
+            font.ascii_start_offset = reader.read_i32()?;
+            let _tracking = reader.read_f32()?;
+            font.character_spacing = reader.read_i32()?;
+            font.character_padding = reader.read_i32()?;
+            let _convert_case = reader.read_i32()?;
+            // CharacterInfo: index, uv and vert rects, advance, flipped (aligned).
+            font.character_count = read_count(&mut reader, 44)?;
+            reader.skip_bytes(font.character_count * 44)?;
+            let kerning = read_count(&mut reader, 8)?;
+            reader.skip_bytes(kerning * 8)?;
+            let _pixel_scale = reader.read_f32()?;
+        } else {
+            font.ascii_start_offset = reader.read_i32()?;
+            if version.major <= 3 {
+                let _font_count_x = reader.read_i32()?;
+                let _font_count_y = reader.read_i32()?;
+            }
+            let _kerning = reader.read_f32()?;
+            font.line_spacing = reader.read_f32()?;
+            if version.major <= 3 {
+                let per_character_kerning = read_count(&mut reader, 8)?;
+                reader.skip_bytes(per_character_kerning * 8)?;
+            } else {
+                font.character_spacing = reader.read_i32()?;
+                font.character_padding = reader.read_i32()?;
+            }
+            let _convert_case = reader.read_i32()?;
+            reader.skip_bytes(pptr_size)?; // m_DefaultMaterial
+            let rect_size = if version.major >= 4 { 44 } else { 40 };
+            font.character_count = read_count(&mut reader, rect_size)?;
+            reader.skip_bytes(font.character_count * rect_size)?;
+            reader.skip_bytes(pptr_size)?; // m_Texture
+            let kerning = read_count(&mut reader, 8)?;
+            reader.skip_bytes(kerning * 8)?;
+            if version.major <= 3 {
+                let _grid_font = reader.read_bool()?;
+                reader.align()?;
+            } else {
+                let _pixel_scale = reader.read_f32()?;
+            }
+        }
+
+        let size = read_count(&mut reader, 1)?;
+        font.font_data = reader.read_bytes(size)?;
+        reader.align()?;
+
+        if reader.remaining() >= 4 {
+            let count = read_count(&mut reader, 4)?;
+            font.font_names = (0..count)
+                .map(|_| reader.read_aligned_string())
+                .collect::<Result<_>>()?;
+        }
+        Ok(font)
+    }
+}
+
+/// Every Font in `asset` that parses, with its path id
+pub fn fonts(asset: &SerializedFile) -> Vec<(i64, Font)> {
+    let version = UnityVersion::parse_version(&asset.unity_version).unwrap_or_default();
+    asset
+        .object_handles()
+        .filter(|h| h.class_id() == class_ids::FONT)
+        .filter_map(|handle| {
+            let object = handle.read().ok()?;
+            let font = Font::from_unity_object(&object, &version).ok()?;
+            Some((handle.path_id(), font))
+        })
+        .collect()
+}
+
+/// An array length, checked against the bytes left for elements of `element_size`.
+fn read_count(reader: &mut BinaryReader, element_size: usize) -> Result<usize> {
+    let count = reader.read_i32()?;
+    usize::try_from(count)
+        .ok()
+        .filter(|&n| n.saturating_mul(element_size) <= reader.remaining())
+        .ok_or_else(|| {
+            BinaryError::invalid_data(format!(
+                "Font array of {} elements does not fit in {} bytes",
+                count,
+                reader.remaining()
+            ))
+        })
+}
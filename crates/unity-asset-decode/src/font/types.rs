@@ -0,0 +1,69 @@
+//! Font data structures
+
+use serde::{Deserialize, Serialize};
+
+/// Container format of an embedded font file, from its first four bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FontFormat {
+    /// `00 01 00 00` (or Apple's `true`): TrueType outlines.
+    TrueType,
+    /// `OTTO`: OpenType with CFF outlines.
+    OpenType,
+    /// `ttcf`: several fonts sharing tables.
+    TrueTypeCollection,
+}
+
+impl FontFormat {
+    /// Identify the format of a font file
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        match data.get(..4)? {
+            [0x00, 0x01, 0x00, 0x00] | b"true" => Some(Self::TrueType),
+            b"OTTO" => Some(Self::OpenType),
+            b"ttcf" => Some(Self::TrueTypeCollection),
+            _ => None,
+        }
+    }
+
+    /// File extension, without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::TrueType => "ttf",
+            Self::OpenType => "otf",
+            Self::TrueTypeCollection => "ttc",
+        }
+    }
+}
+
+/// A parsed Font (class 128)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Font {
+    pub name: String,
+    /// Point size the glyph texture was generated at.
+    pub font_size: f32,
+    pub line_spacing: f32,
+    /// Extra advance between characters, in pixels.
+    pub character_spacing: i32,
+    /// Padding around each glyph in the texture, in pixels.
+    pub character_padding: i32,
+    /// Character code of the first glyph of a custom (bitmap) font.
+    pub ascii_start_offset: i32,
+    /// Number of entries in `m_CharacterRects`.
+    pub character_count: usize,
+    /// Family names to look up on the system before falling back to `font_data`.
+    pub font_names: Vec<String>,
+    /// The imported TTF/OTF file; empty for bitmap fonts and fonts using system fonts only.
+    #[serde(skip)]
+    pub font_data: Vec<u8>,
+}
+
+impl Font {
+    /// Whether a font file is embedded
+    pub fn has_font_data(&self) -> bool {
+        !self.font_data.is_empty()
+    }
+
+    /// Format of the embedded font file, if recognised
+    pub fn format(&self) -> Option<FontFormat> {
+        FontFormat::detect(&self.font_data)
+    }
+}
@@ -1,14 +1,15 @@
 //! Unity asset decode/export helpers.
 //!
 //! This crate intentionally depends on `unity-asset-binary` and provides optional, heavier
-//! processing layers (Texture/Audio/Sprite/Mesh) behind feature flags.
+//! processing layers (Texture/Audio/Sprite/TextMeshPro/Mesh/ParticleSystem/Animation/NavMesh/
+//! Font, `.meta` generation) behind feature flags.
 
 pub use unity_asset_binary::{BinaryError, Result};
 
 // Re-export core parsing modules so moved processors can keep their `crate::...` paths.
 pub use unity_asset_binary::{
-    asset, bundle, compression, error, file, formats, object, reader, typetree, unity_objects,
-    unity_version, webfile,
+    asset, bundle, compression, data_source, error, file, formats, object, reader, tilemap,
+    typetree, unity_objects, unity_version, webfile,
 };
 
 #[cfg(feature = "texture")]
@@ -20,5 +21,25 @@ pub mod audio;
 #[cfg(feature = "sprite")]
 pub mod sprite;
 
+#[cfg(feature = "sprite")]
+pub mod tmp;
+
 #[cfg(feature = "mesh")]
 pub mod mesh;
+
+#[cfg(feature = "particles")]
+pub mod particles;
+
+#[cfg(feature = "animation")]
+pub mod animation;
+
+#[cfg(feature = "navmesh")]
+pub mod navmesh;
+
+#[cfg(feature = "font")]
+pub mod font;
+
+#[cfg(feature = "meta")]
+pub mod meta;
+
+pub mod prelude;
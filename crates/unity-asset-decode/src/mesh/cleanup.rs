@@ -0,0 +1,636 @@
+//! Mesh post-processing
+//!
+//! Welding, normal and tangent recomputation on the raw `m_VertexData` / `m_IndexBuffer`.
+//! Vertices are moved as whole records, so attributes that are not recomputed (colors, extra UV
+//! sets, skin weights) keep their original formats and values. Index buffers are rewritten per
+//! submesh with `baseVertex` folded in, and blend shape deltas follow their vertices.
+
+use super::types::{BlendShapeVertex, ChannelInfo, Mesh};
+use super::vertex::{ComponentFormat, tangent_channel_index, uv_channel_index};
+use crate::error::{BinaryError, Result};
+use crate::unity_version::UnityVersion;
+use std::collections::HashMap;
+
+/// Post-processing run by the exporters before a mesh is written
+///
+/// Steps run in order: weld, recompute normals, recompute tangents. The default does nothing.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct MeshCleanup {
+    /// Weld vertices closer than this distance (see [`Mesh::weld_vertices`])
+    pub weld_epsilon: Option<f32>,
+    /// Only weld vertices whose first UV set also matches
+    pub weld_compare_uvs: bool,
+    /// Recompute normals with this smoothing angle in degrees (see [`Mesh::recompute_normals`])
+    pub smoothing_angle: Option<f32>,
+    /// Recompute tangents from UV0 (see [`Mesh::recompute_tangents`])
+    pub recompute_tangents: bool,
+}
+
+impl MeshCleanup {
+    /// Weld vertices within `epsilon`, keeping UV seams when `compare_uvs` is set.
+    pub fn with_weld(mut self, epsilon: f32, compare_uvs: bool) -> Self {
+        self.weld_epsilon = Some(epsilon);
+        self.weld_compare_uvs = compare_uvs;
+        self
+    }
+
+    /// Recompute normals, smoothing across edges sharper than `angle` degrees.
+    pub fn with_recompute_normals(mut self, angle: f32) -> Self {
+        self.smoothing_angle = Some(angle);
+        self
+    }
+
+    /// Set `recompute_tangents`.
+    pub fn with_recompute_tangents(mut self, recompute_tangents: bool) -> Self {
+        self.recompute_tangents = recompute_tangents;
+        self
+    }
+
+    /// Whether any step is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.weld_epsilon.is_some() || self.smoothing_angle.is_some() || self.recompute_tangents
+    }
+
+    /// Run the enabled steps on `mesh`
+    pub fn apply(&self, mesh: &mut Mesh, version: &UnityVersion) -> Result<()> {
+        if let Some(epsilon) = self.weld_epsilon {
+            mesh.weld_vertices(version, epsilon, self.weld_compare_uvs)?;
+        }
+        if let Some(angle) = self.smoothing_angle {
+            mesh.recompute_normals(version, angle)?;
+        }
+        if self.recompute_tangents {
+            mesh.recompute_tangents(version)?;
+        }
+        Ok(())
+    }
+}
+
+impl Mesh {
+    /// Merge vertices whose positions lie within `position_epsilon` on every axis
+    ///
+    /// With `compare_uvs`, the first UV set must match within the same epsilon too, so UV
+    /// seams stay split. Every other attribute is taken from the first vertex of each group;
+    /// normals and tangents usually need recomputing afterwards. Triangles are kept even when
+    /// welding collapses them. Returns the number of vertices removed.
+    pub fn weld_vertices(
+        &mut self,
+        version: &UnityVersion,
+        position_epsilon: f32,
+        compare_uvs: bool,
+    ) -> Result<usize> {
+        let positions = self.positions(version)?;
+        let uvs = if compare_uvs && self.has_uv0(version) {
+            Some(self.read_uvs(version, 0)?)
+        } else {
+            None
+        };
+        let epsilon = position_epsilon.max(0.0);
+        let cell_of = |p: &[f32; 3]| -> [i64; 3] {
+            if epsilon > 0.0 {
+                p.map(|v| (v / epsilon).floor() as i64)
+            } else {
+                p.map(|v| v.to_bits() as i64)
+            }
+        };
+        let close = |a: usize, b: usize| {
+            let near = |x: f32, y: f32| (x - y).abs() <= epsilon;
+            (0..3).all(|axis| near(positions[a][axis], positions[b][axis]))
+                && uvs
+                    .as_ref()
+                    .is_none_or(|uvs| near(uvs[a][0], uvs[b][0]) && near(uvs[a][1], uvs[b][1]))
+        };
+
+        // Representatives per grid cell; neighbouring cells are searched when epsilon > 0.
+        let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+        let mut remap = vec![0u32; positions.len()];
+        let mut sources: Vec<u32> = Vec::new();
+        for (vertex, position) in positions.iter().enumerate() {
+            let cell = cell_of(position);
+            let span: &[i64] = if epsilon > 0.0 { &[-1, 0, 1] } else { &[0] };
+            let mut found = None;
+            'search: for dx in span {
+                for dy in span {
+                    for dz in span {
+                        let key = [cell[0] + dx, cell[1] + dy, cell[2] + dz];
+                        if let Some(candidates) = grid.get(&key)
+                            && let Some(&rep) = candidates.iter().find(|&&rep| close(rep, vertex))
+                        {
+                            found = Some(rep);
+                            break 'search;
+                        }
+                    }
+                }
+            }
+            match found {
+                Some(rep) => remap[vertex] = remap[rep],
+                None => {
+                    remap[vertex] = sources.len() as u32;
+                    sources.push(vertex as u32);
+                    grid.entry(cell).or_default().push(vertex);
+                }
+            }
+        }
+
+        let removed = positions.len() - sources.len();
+        if removed == 0 {
+            return Ok(0);
+        }
+        let indices = self
+            .submesh_indices()?
+            .into_iter()
+            .map(|list| {
+                list.into_iter()
+                    .map(|i| {
+                        remap.get(i as usize).copied().ok_or_else(|| {
+                            BinaryError::invalid_data(format!(
+                                "Mesh {:?} index {} is out of range ({} vertices)",
+                                self.name,
+                                i,
+                                positions.len()
+                            ))
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<Result<_>>()?;
+        let targets = remap.iter().map(|&new| vec![new]).collect();
+        self.rebuild_vertices(version, &sources, targets)?;
+        self.set_submesh_indices(indices);
+        Ok(removed)
+    }
+
+    /// Replace normals with area-weighted face normals, smoothed across faces that meet at an
+    /// angle of at most `smoothing_angle_deg` degrees
+    ///
+    /// Faces that share a position count as neighbours even when they use different vertices
+    /// (split UV seams stay smooth). A vertex whose corners end up with different normals is
+    /// split, so `0` gives faceted shading and `180` smooths everything. Lines and points keep
+    /// their normals; vertices used by no face get whatever normal they had (or zero).
+    pub fn recompute_normals(
+        &mut self,
+        version: &UnityVersion,
+        smoothing_angle_deg: f32,
+    ) -> Result<()> {
+        let positions = self.positions(version)?;
+        let mut indices = self.submesh_indices()?;
+        let faces = self.faces(&indices, &positions);
+
+        let key = |v: u32| positions[v as usize].map(f32::to_bits);
+        let mut faces_at: HashMap<[u32; 3], Vec<usize>> = HashMap::new();
+        for (f, face) in faces.iter().enumerate() {
+            for &(submesh, corner) in &face.corners {
+                let list = faces_at.entry(key(indices[submesh][corner])).or_default();
+                if list.last() != Some(&f) {
+                    list.push(f);
+                }
+            }
+        }
+
+        let threshold = smoothing_angle_deg.clamp(0.0, 180.0).to_radians().cos() - 1e-5;
+        let mut normals: Vec<[f32; 3]> = if has_channel(self, 1, 3) {
+            self.read_channel(version, 1)?
+                .into_iter()
+                .map(|n| [n[0], n[1], n[2]])
+                .collect()
+        } else {
+            vec![[0.0; 3]; positions.len()]
+        };
+        // Vertices written so far, with the copies made for diverging corners
+        let mut assigned: Vec<Option<[f32; 3]>> = vec![None; positions.len()];
+        let mut copies: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut sources: Vec<u32> = (0..positions.len() as u32).collect();
+
+        for face in &faces {
+            let unit = normalize(face.normal);
+            for &(submesh, corner) in &face.corners {
+                let vertex = indices[submesh][corner];
+                let mut sum = [0.0f32; 3];
+                for &g in &faces_at[&key(vertex)] {
+                    if dot(unit, normalize(faces[g].normal)) >= threshold {
+                        sum = add(sum, faces[g].normal);
+                    }
+                }
+                let normal = if length(sum) > 0.0 {
+                    normalize(sum)
+                } else {
+                    unit
+                };
+
+                let same = |n: &[f32; 3]| (0..3).all(|a| (n[a] - normal[a]).abs() <= 1e-5);
+                let target = match assigned[vertex as usize] {
+                    None => vertex,
+                    Some(existing) if same(&existing) => vertex,
+                    Some(_) => {
+                        let list = copies.entry(vertex).or_default();
+                        match list.iter().find(|&&c| same(&normals[c as usize])) {
+                            Some(&copy) => copy,
+                            None => {
+                                let copy = sources.len() as u32;
+                                sources.push(vertex);
+                                normals.push(normal);
+                                list.push(copy);
+                                copy
+                            }
+                        }
+                    }
+                };
+                if target == vertex {
+                    assigned[vertex as usize] = Some(normal);
+                    normals[vertex as usize] = normal;
+                }
+                indices[submesh][corner] = target;
+            }
+        }
+
+        if sources.len() > positions.len() {
+            let mut targets = vec![Vec::new(); positions.len()];
+            for (new, &source) in sources.iter().enumerate() {
+                targets[source as usize].push(new as u32);
+            }
+            self.rebuild_vertices(version, &sources, targets)?;
+            self.set_submesh_indices(indices);
+        }
+        let values: Vec<Vec<f32>> = normals.iter().map(|n| n.to_vec()).collect();
+        self.write_channel(version, 1, &values)
+    }
+
+    /// Replace tangents with per-vertex tangents from the first UV set
+    ///
+    /// A simpler scheme than MikkTSpace: each triangle's UV-space derivative is accumulated on
+    /// its vertices, then orthogonalized against the vertex normal (Gram-Schmidt). `w` is the
+    /// bitangent sign Unity expects (`binormal = cross(normal, tangent.xyz) * w`). Requires
+    /// normals and UV0; vertices without usable UVs get a tangent perpendicular to the normal.
+    pub fn recompute_tangents(&mut self, version: &UnityVersion) -> Result<()> {
+        let positions = self.positions(version)?;
+        if !has_channel(self, 1, 3) {
+            return Err(BinaryError::invalid_data(format!(
+                "Mesh {:?} has no normals; recompute them before tangents",
+                self.name
+            )));
+        }
+        if !self.has_uv0(version) {
+            return Err(BinaryError::invalid_data(format!(
+                "Mesh {:?} has no UV0 to derive tangents from",
+                self.name
+            )));
+        }
+        let normals: Vec<[f32; 3]> = self
+            .read_channel(version, 1)?
+            .into_iter()
+            .map(|n| [n[0], n[1], n[2]])
+            .collect();
+        let uvs = self.read_uvs(version, 0)?;
+
+        let mut tan = vec![[0.0f32; 3]; positions.len()];
+        let mut bitan = vec![[0.0f32; 3]; positions.len()];
+        for triangle in self.submesh_triangles()?.into_iter().flatten() {
+            let [a, b, c] = triangle.map(|i| i as usize);
+            if a.max(b).max(c) >= positions.len() {
+                continue;
+            }
+            let e1 = sub(positions[b], positions[a]);
+            let e2 = sub(positions[c], positions[a]);
+            let (s1, t1) = (uvs[b][0] - uvs[a][0], uvs[b][1] - uvs[a][1]);
+            let (s2, t2) = (uvs[c][0] - uvs[a][0], uvs[c][1] - uvs[a][1]);
+            let det = s1 * t2 - s2 * t1;
+            if det.abs() <= f32::EPSILON {
+                continue;
+            }
+            let r = 1.0 / det;
+            let sdir = scale(sub(scale(e1, t2), scale(e2, t1)), r);
+            let tdir = scale(sub(scale(e2, s1), scale(e1, s2)), r);
+            for v in [a, b, c] {
+                tan[v] = add(tan[v], sdir);
+                bitan[v] = add(bitan[v], tdir);
+            }
+        }
+
+        let values: Vec<Vec<f32>> = normals
+            .iter()
+            .zip(tan.iter().zip(&bitan))
+            .map(|(&n, (&t, &b))| {
+                let mut tangent = sub(t, scale(n, dot(n, t)));
+                if length(tangent) <= 1e-12 {
+                    tangent = any_perpendicular(n);
+                }
+                let tangent = normalize(tangent);
+                let w = if dot(cross(n, tangent), b) < 0.0 {
+                    -1.0
+                } else {
+                    1.0
+                };
+                vec![tangent[0], tangent[1], tangent[2], w]
+            })
+            .collect();
+        self.write_channel(version, tangent_channel_index(version), &values)
+    }
+
+    fn positions(&self, version: &UnityVersion) -> Result<Vec<[f32; 3]>> {
+        Ok(self
+            .read_channel(version, 0)?
+            .into_iter()
+            .map(|p| {
+                [
+                    p[0],
+                    p.get(1).copied().unwrap_or(0.0),
+                    p.get(2).copied().unwrap_or(0.0),
+                ]
+            })
+            .collect())
+    }
+
+    fn has_uv0(&self, version: &UnityVersion) -> bool {
+        uv_channel_index(version, 0).is_some_and(|i| has_channel(self, i, 2))
+    }
+
+    /// Raw index list of each submesh, `baseVertex` applied
+    fn submesh_indices(&self) -> Result<Vec<Vec<u32>>> {
+        let index_size = if self.index_format == 1 { 4 } else { 2 };
+        self.sub_meshes
+            .iter()
+            .enumerate()
+            .map(|(i, sub_mesh)| {
+                let start = sub_mesh.first_byte as usize;
+                let end = start + sub_mesh.index_count as usize * index_size;
+                let bytes = self.index_buffer.get(start..end).ok_or_else(|| {
+                    BinaryError::invalid_data(format!(
+                        "Index buffer of mesh {:?} ends before submesh {}",
+                        self.name, i
+                    ))
+                })?;
+                Ok(bytes
+                    .chunks_exact(index_size)
+                    .map(|c| match c {
+                        [a, b] => u16::from_le_bytes([*a, *b]) as u32,
+                        _ => u32::from_le_bytes([c[0], c[1], c[2], c[3]]),
+                    } + sub_mesh.base_vertex)
+                    .collect())
+            })
+            .collect()
+    }
+
+    /// Write the index lists back, one contiguous range per submesh with `baseVertex` 0
+    ///
+    /// Switches to 32-bit indices when an index no longer fits in 16 bits.
+    fn set_submesh_indices(&mut self, indices: Vec<Vec<u32>>) {
+        if indices.iter().flatten().any(|&i| i > u16::MAX as u32) {
+            self.index_format = 1;
+        }
+        let wide = self.index_format == 1;
+        let mut buffer = Vec::new();
+        for (sub_mesh, list) in self.sub_meshes.iter_mut().zip(&indices) {
+            sub_mesh.first_byte = buffer.len() as u32;
+            sub_mesh.index_count = list.len() as u32;
+            sub_mesh.base_vertex = 0;
+            let (min, max) = list
+                .iter()
+                .fold((u32::MAX, 0), |(lo, hi), &i| (lo.min(i), hi.max(i)));
+            sub_mesh.first_vertex = if list.is_empty() { 0 } else { min };
+            sub_mesh.vertex_count = if list.is_empty() { 0 } else { max - min + 1 };
+            for &index in list {
+                if wide {
+                    buffer.extend_from_slice(&index.to_le_bytes());
+                } else {
+                    buffer.extend_from_slice(&(index as u16).to_le_bytes());
+                }
+            }
+        }
+        self.index_buffer = buffer;
+    }
+
+    /// Polygons of the triangle and quad submeshes with their area-weighted normals
+    fn faces(&self, indices: &[Vec<u32>], positions: &[[f32; 3]]) -> Vec<Face> {
+        let mut faces = Vec::new();
+        for (submesh, (sub_mesh, list)) in self.sub_meshes.iter().zip(indices).enumerate() {
+            let size = match sub_mesh.topology {
+                0 => 3,
+                2 => 4,
+                _ => continue,
+            };
+            for (polygon, chunk) in list.chunks_exact(size).enumerate() {
+                if chunk.iter().any(|&i| i as usize >= positions.len()) {
+                    continue;
+                }
+                let p = |k: usize| positions[chunk[k] as usize];
+                let mut normal = cross(sub(p(1), p(0)), sub(p(2), p(0)));
+                if size == 4 {
+                    normal = add(normal, cross(sub(p(2), p(0)), sub(p(3), p(0))));
+                }
+                faces.push(Face {
+                    normal: scale(normal, 0.5),
+                    corners: (0..size).map(|k| (submesh, polygon * size + k)).collect(),
+                });
+            }
+        }
+        faces
+    }
+
+    /// Rebuild `m_VertexData` from the vertices listed in `sources` (repeats allowed)
+    ///
+    /// Each stream is copied record by record. `targets[old]` lists the new vertices that take
+    /// over the blend shape deltas of old vertex `old`; within a shape the first delta landing
+    /// on a vertex wins.
+    fn rebuild_vertices(
+        &mut self,
+        version: &UnityVersion,
+        sources: &[u32],
+        targets: Vec<Vec<u32>>,
+    ) -> Result<()> {
+        let layout = self.stream_layout(version);
+        let old = &self.vertex_data.data_size;
+        let mut data = Vec::new();
+        for &(start, stride) in &layout {
+            data.resize(data.len().next_multiple_of(16), 0);
+            for &source in sources {
+                let offset = start + source as usize * stride;
+                let record = old.get(offset..offset + stride).ok_or_else(|| {
+                    BinaryError::invalid_data(format!(
+                        "Vertex data of mesh {:?} ends before vertex {}",
+                        self.name, source
+                    ))
+                })?;
+                data.extend_from_slice(record);
+            }
+        }
+        self.vertex_data.data_size = data;
+        self.vertex_data.vertex_count = sources.len() as u32;
+
+        if let Some(blend) = &mut self.blend_shape_data {
+            let mut vertices: Vec<BlendShapeVertex> = Vec::with_capacity(blend.vertices.len());
+            for shape in &mut blend.shapes {
+                let first = shape.first_vertex as usize;
+                let end = (first + shape.vertex_count as usize).min(blend.vertices.len());
+                let mut deltas: Vec<BlendShapeVertex> = Vec::new();
+                for delta in blend.vertices.get(first..end).unwrap_or_default() {
+                    for &target in targets.get(delta.index as usize).into_iter().flatten() {
+                        if deltas.iter().all(|d| d.index != target) {
+                            deltas.push(BlendShapeVertex {
+                                index: target,
+                                ..delta.clone()
+                            });
+                        }
+                    }
+                }
+                deltas.sort_by_key(|d| d.index);
+                shape.first_vertex = vertices.len() as u32;
+                shape.vertex_count = deltas.len() as u32;
+                vertices.extend(deltas);
+            }
+            blend.vertices = vertices;
+        }
+        Ok(())
+    }
+
+    /// Store one value per vertex in channel `index`
+    ///
+    /// An existing channel with the same dimension is written in place in its own format.
+    /// Otherwise the vertex data is re-laid out as a single stream of `Float` channels (the
+    /// values [`Mesh::read_channel`] returns are unchanged) with the new channel added.
+    fn write_channel(
+        &mut self,
+        version: &UnityVersion,
+        index: usize,
+        values: &[Vec<f32>],
+    ) -> Result<()> {
+        let dimension = values.first().map_or(0, Vec::len);
+        let vertex_count = self.vertex_data.vertex_count as usize;
+        if values.len() != vertex_count {
+            return Err(BinaryError::invalid_data(format!(
+                "Mesh {:?}: {} values for {} vertices",
+                self.name,
+                values.len(),
+                vertex_count
+            )));
+        }
+
+        if let Some(channel) = self.vertex_data.channels.get(index).cloned()
+            && (channel.dimension & 0xf) as usize == dimension
+            && let Some(format) = ComponentFormat::from_raw(channel.format, version)
+        {
+            let (start, stride) = self.stream_layout(version)[channel.stream as usize];
+            let size = format.size();
+            let data = &mut self.vertex_data.data_size;
+            for (vertex, value) in values.iter().enumerate() {
+                let base = start + vertex * stride + channel.offset as usize;
+                let bytes = data.get_mut(base..base + dimension * size).ok_or_else(|| {
+                    BinaryError::invalid_data(format!(
+                        "Vertex data of mesh {:?} ends before vertex {} of channel {}",
+                        self.name, vertex, index
+                    ))
+                })?;
+                for (component, out) in value.iter().zip(bytes.chunks_exact_mut(size)) {
+                    format.write(*component, out);
+                }
+            }
+            return Ok(());
+        }
+
+        let mut columns: Vec<Option<Vec<Vec<f32>>>> = Vec::new();
+        for (i, channel) in self.vertex_data.channels.iter().enumerate() {
+            columns.push(if i != index && channel.dimension & 0xf > 0 {
+                Some(self.read_channel(version, i)?)
+            } else {
+                None
+            });
+        }
+        if columns.len() <= index {
+            columns.resize(index + 1, None);
+        }
+        columns[index] = Some(values.to_vec());
+        self.set_float_channels(&columns)
+    }
+
+    /// Replace `m_VertexData` with a single stream holding `columns[i]` as `Float` channel `i`
+    ///
+    /// Every present column has one value per vertex (`vertex_count`); `None` leaves the
+    /// channel empty.
+    pub(super) fn set_float_channels(&mut self, columns: &[Option<Vec<Vec<f32>>>]) -> Result<()> {
+        let vertex_count = self.vertex_data.vertex_count as usize;
+        let mut channels = Vec::with_capacity(columns.len());
+        let mut stride = 0usize;
+        for column in columns {
+            let dimension = column.as_ref().and_then(|c| c.first()).map_or(0, Vec::len);
+            channels.push(ChannelInfo {
+                stream: 0,
+                offset: if dimension > 0 { stride as u8 } else { 0 },
+                format: 0,
+                dimension: dimension as u8,
+            });
+            stride += dimension * 4;
+        }
+        if stride > u8::MAX as usize {
+            return Err(BinaryError::unsupported(format!(
+                "Mesh {:?}: a {}-byte float vertex does not fit the channel table",
+                self.name, stride
+            )));
+        }
+        let mut data = Vec::with_capacity(vertex_count * stride);
+        for vertex in 0..vertex_count {
+            for column in columns.iter().flatten() {
+                for value in &column[vertex] {
+                    data.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+        }
+        self.vertex_data.channels = channels;
+        self.vertex_data.data_size = data;
+        Ok(())
+    }
+}
+
+/// A triangle or quad: its area-weighted normal and `(submesh, position in index list)` corners
+struct Face {
+    normal: [f32; 3],
+    corners: Vec<(usize, usize)>,
+}
+
+fn has_channel(mesh: &Mesh, index: usize, min_dimension: u8) -> bool {
+    mesh.vertex_data
+        .channels
+        .get(index)
+        .is_some_and(|c| c.dimension & 0xf >= min_dimension)
+}
+
+fn add(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: [f32; 3], s: f32) -> [f32; 3] {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn length(a: [f32; 3]) -> f32 {
+    dot(a, a).sqrt()
+}
+
+fn normalize(a: [f32; 3]) -> [f32; 3] {
+    let len = length(a);
+    if len > 0.0 { scale(a, 1.0 / len) } else { a }
+}
+
+fn any_perpendicular(n: [f32; 3]) -> [f32; 3] {
+    let axis = if n[0].abs() < 0.9 {
+        [1.0, 0.0, 0.0]
+    } else {
+        [0.0, 1.0, 0.0]
+    };
+    sub(axis, scale(n, dot(n, axis)))
+}
@@ -0,0 +1,308 @@
+//! Mesh compression
+//!
+//! Meshes imported with "Mesh Compression" keep no `m_VertexData`; their attributes live in
+//! `m_CompressedMesh` as bit-packed vectors. A [`PackedFloatVector`] quantizes each value to
+//! `bit_size` bits over `start..start + range`, a [`PackedIntVector`] stores plain `bit_size`-bit
+//! integers, both packed least significant bit first.
+//!
+//! [`Mesh::decompress`] unpacks them into a single stream of `Float` channels in the version's
+//! channel order, so [`Mesh::read_channel`] and the exporters read them like any other mesh.
+//! Normals and tangents are stored as X and Y only; Z is rebuilt from the unit length with its
+//! sign taken from `m_NormalSigns` / `m_TangentSigns`. Bone weights (2018+ channel layouts only)
+//! are 5-bit fractions of 31, with the last weight of a vertex implied when three do not add up
+//! to one.
+
+use super::types::{CompressedMesh, Mesh, PackedFloatVector, PackedIntVector};
+use super::vertex::{color_channel_index, tangent_channel_index, uv_channel_index};
+use crate::error::{BinaryError, Result};
+use crate::unity_version::UnityVersion;
+
+/// `m_UVInfo` holds 4 bits per UV set: the dimension minus one, and whether the set exists.
+const UV_INFO_BITS: u32 = 4;
+const UV_DIMENSION_MASK: u32 = 3;
+const UV_CHANNEL_EXISTS: u32 = 4;
+const MAX_UV_SETS: usize = 8;
+
+/// Skin weights are quantized to 1/31.
+const WEIGHT_SCALE: u32 = 31;
+
+impl PackedFloatVector {
+    /// Decode all `num_items` values
+    ///
+    /// Stops early when `data` is too short for the declared item count.
+    pub fn unpack(&self) -> Vec<f32> {
+        let bit_size = self.bit_size.min(32);
+        if bit_size == 0 {
+            return vec![self.start; self.num_items as usize];
+        }
+        // Same arithmetic as Unity, so values round-trip bit for bit.
+        let scale = 1.0 / self.range;
+        let max = ((1u64 << bit_size) - 1) as f32;
+        unpack_bits(&self.data, bit_size, self.num_items as usize)
+            .map(|x| x as f32 / (scale * max) + self.start)
+            .collect()
+    }
+}
+
+impl PackedIntVector {
+    /// Decode all `num_items` values
+    ///
+    /// Stops early when `data` is too short for the declared item count.
+    pub fn unpack(&self) -> Vec<u32> {
+        let bit_size = self.bit_size.min(32);
+        if bit_size == 0 {
+            return vec![0; self.num_items as usize];
+        }
+        unpack_bits(&self.data, bit_size, self.num_items as usize).collect()
+    }
+}
+
+/// `count` integers of `bit_size` bits (1..=32) from `data`, least significant bit first
+fn unpack_bits(data: &[u8], bit_size: u8, count: usize) -> impl Iterator<Item = u32> + '_ {
+    let bit_size = bit_size as usize;
+    let available = data.len() * 8 / bit_size;
+    (0..count.min(available)).map(move |item| {
+        let mut bit = item * bit_size;
+        let mut value = 0u64;
+        let mut read = 0;
+        while read < bit_size {
+            let shift = bit % 8;
+            let taken = (bit_size - read).min(8 - shift);
+            value |= ((data[bit / 8] >> shift) as u64) << read;
+            bit += taken;
+            read += taken;
+        }
+        (value & ((1u64 << bit_size) - 1)) as u32
+    })
+}
+
+impl CompressedMesh {
+    /// Whether the mesh was saved compressed (an uncompressed mesh serializes empty vectors)
+    pub fn is_empty(&self) -> bool {
+        self.vertices.num_items == 0 && self.triangles.num_items == 0
+    }
+}
+
+impl Mesh {
+    /// Unpack `m_CompressedMesh` into `m_VertexData` and `m_IndexBuffer`
+    ///
+    /// Vertex data is only rebuilt when the mesh has none, and the index buffer only when it is
+    /// empty; indices are written in the mesh's `m_IndexFormat`, so submesh byte ranges keep
+    /// their meaning. Returns `false` when there is nothing to unpack.
+    pub fn decompress(&mut self, version: &UnityVersion) -> Result<bool> {
+        let Some(compressed) = self.compressed_mesh.take().filter(|c| !c.is_empty()) else {
+            return Ok(false);
+        };
+        let result = self.unpack(&compressed, version);
+        self.compressed_mesh = Some(compressed);
+        result.map(|()| true)
+    }
+
+    fn unpack(&mut self, compressed: &CompressedMesh, version: &UnityVersion) -> Result<()> {
+        if self.vertex_data.data_size.is_empty() && compressed.vertices.num_items > 0 {
+            self.unpack_vertices(compressed, version)?;
+        }
+        if self.index_buffer.is_empty() && compressed.triangles.num_items > 0 {
+            let indices = compressed.triangles.unpack();
+            let mut buffer = Vec::with_capacity(indices.len() * 4);
+            for index in indices {
+                if self.index_format == 1 {
+                    buffer.extend_from_slice(&index.to_le_bytes());
+                } else {
+                    let index = u16::try_from(index).map_err(|_| {
+                        BinaryError::invalid_data(format!(
+                            "Mesh {:?}: index {} does not fit its 16-bit index format",
+                            self.name, index
+                        ))
+                    })?;
+                    buffer.extend_from_slice(&index.to_le_bytes());
+                }
+            }
+            self.index_buffer = buffer;
+        }
+        Ok(())
+    }
+
+    fn unpack_vertices(
+        &mut self,
+        compressed: &CompressedMesh,
+        version: &UnityVersion,
+    ) -> Result<()> {
+        let vertex_count = compressed.vertices.num_items as usize / 3;
+        let caps = version.capabilities();
+        let channel_count = if caps.vertex_channels_2018 {
+            14
+        } else {
+            tangent_channel_index(version) + 1
+        };
+        let mut columns: Vec<Option<Vec<Vec<f32>>>> = vec![None; channel_count];
+        let name = self.name.clone();
+        let mut set = |index: usize, values: Vec<f32>, dimension: usize, what: &str| {
+            if values.len() < vertex_count * dimension {
+                return Err(BinaryError::invalid_data(format!(
+                    "Compressed mesh {:?} has {} {} values for {} vertices",
+                    name,
+                    values.len(),
+                    what,
+                    vertex_count
+                )));
+            }
+            columns[index] = Some(
+                values
+                    .chunks_exact(dimension)
+                    .take(vertex_count)
+                    .map(<[f32]>::to_vec)
+                    .collect(),
+            );
+            Ok(())
+        };
+
+        set(0, compressed.vertices.unpack(), 3, "position")?;
+
+        if compressed.uv.num_items > 0 {
+            let uvs = compressed.uv.unpack();
+            if compressed.uv_info != 0 {
+                let mut offset = 0;
+                for uv in 0..MAX_UV_SETS {
+                    let bits = (compressed.uv_info >> (uv as u32 * UV_INFO_BITS))
+                        & ((1 << UV_INFO_BITS) - 1);
+                    if bits & UV_CHANNEL_EXISTS == 0 {
+                        continue;
+                    }
+                    let dimension = 1 + (bits & UV_DIMENSION_MASK) as usize;
+                    let end = (offset + dimension * vertex_count).min(uvs.len());
+                    if let Some(index) = uv_channel_index(version, uv) {
+                        set(index, uvs[offset..end].to_vec(), dimension, "UV")?;
+                    }
+                    offset = end;
+                }
+            } else {
+                // Before `m_UVInfo`: UV0, then UV1 when there are enough values.
+                for uv in 0..2 {
+                    let start = uv * vertex_count * 2;
+                    if let Some(index) = uv_channel_index(version, uv)
+                        && uvs.len() >= start + vertex_count * 2
+                    {
+                        set(index, uvs[start..].to_vec(), 2, "UV")?;
+                    }
+                }
+            }
+        }
+
+        if compressed.normals.num_items > 0 {
+            let signs = compressed.normal_signs.unpack();
+            let normals = unit_vectors(&compressed.normals.unpack(), |i| signs.get(i) != Some(&0));
+            set(1, normals.into_iter().flatten().collect(), 3, "normal")?;
+        }
+
+        if compressed.tangents.num_items > 0 {
+            // Two signs per tangent: Z, then the bitangent direction (W).
+            let signs = compressed.tangent_signs.unpack();
+            let tangents = unit_vectors(&compressed.tangents.unpack(), |i| {
+                signs.get(i * 2) != Some(&0)
+            });
+            let tangents: Vec<f32> = tangents
+                .into_iter()
+                .enumerate()
+                .flat_map(|(i, [x, y, z])| {
+                    let w = if signs.get(i * 2 + 1).is_some_and(|&s| s > 0) {
+                        1.0
+                    } else {
+                        -1.0
+                    };
+                    [x, y, z, w]
+                })
+                .collect();
+            set(tangent_channel_index(version), tangents, 4, "tangent")?;
+        }
+
+        let colors = match (&compressed.float_colors, &compressed.colors) {
+            (Some(float_colors), _) if float_colors.num_items > 0 => Some(float_colors.unpack()),
+            // Pre-5.0 colors pack RGBA into one 32-bit item.
+            (_, Some(colors)) if colors.num_items > 0 => {
+                let bytes = PackedIntVector {
+                    num_items: colors.num_items * 4,
+                    data: colors.data.clone(),
+                    bit_size: colors.bit_size / 4,
+                };
+                Some(
+                    bytes
+                        .unpack()
+                        .into_iter()
+                        .map(|c| c as f32 / 255.0)
+                        .collect(),
+                )
+            }
+            _ => None,
+        };
+        if let Some(colors) = colors {
+            set(color_channel_index(version), colors, 4, "color")?;
+        }
+
+        if caps.vertex_channels_2018 && compressed.weights.num_items > 0 {
+            let (weights, bones) = unpack_skin(
+                &compressed.weights.unpack(),
+                &compressed.bone_indices.unpack(),
+                vertex_count,
+            );
+            set(12, weights, 4, "bone weight")?;
+            set(13, bones, 4, "bone index")?;
+        }
+
+        self.vertex_data.vertex_count = vertex_count as u32;
+        self.set_float_channels(&columns)
+    }
+}
+
+/// Unit vectors from packed X, Y pairs; `positive(i)` gives the sign of vector `i`'s Z
+fn unit_vectors(xy: &[f32], positive: impl Fn(usize) -> bool) -> Vec<[f32; 3]> {
+    xy.chunks_exact(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            let (mut x, mut y) = (pair[0], pair[1]);
+            let z_squared = 1.0 - x * x - y * y;
+            let mut z = if z_squared >= 0.0 {
+                z_squared.sqrt()
+            } else {
+                let length = (x * x + y * y).sqrt();
+                if length > 0.0 {
+                    x /= length;
+                    y /= length;
+                }
+                0.0
+            };
+            if !positive(i) {
+                z = -z;
+            }
+            [x, y, z]
+        })
+        .collect()
+}
+
+/// Per-vertex weights and bone indices (4 each) from Unity's variable-length skin stream
+///
+/// A vertex's weights end once they add up to 31; after three that do not, the fourth is the
+/// remainder and only its bone index is stored.
+fn unpack_skin(weights: &[u32], bones: &[u32], vertex_count: usize) -> (Vec<f32>, Vec<f32>) {
+    let mut out_weights = vec![0.0; vertex_count * 4];
+    let mut out_bones = vec![0.0; vertex_count * 4];
+    let mut bone_iter = bones.iter().map(|&b| b as f32);
+    let (mut vertex, mut slot, mut sum) = (0, 0, 0);
+    for &weight in weights {
+        if vertex >= vertex_count {
+            break;
+        }
+        out_weights[vertex * 4 + slot] = weight as f32 / WEIGHT_SCALE as f32;
+        out_bones[vertex * 4 + slot] = bone_iter.next().unwrap_or_default();
+        slot += 1;
+        sum += weight;
+        if sum >= WEIGHT_SCALE {
+            (vertex, slot, sum) = (vertex + 1, 0, 0);
+        } else if slot == 3 {
+            out_weights[vertex * 4 + 3] = (WEIGHT_SCALE - sum) as f32 / WEIGHT_SCALE as f32;
+            out_bones[vertex * 4 + 3] = bone_iter.next().unwrap_or_default();
+            (vertex, slot, sum) = (vertex + 1, 0, 0);
+        }
+    }
+    (out_weights, out_bones)
+}
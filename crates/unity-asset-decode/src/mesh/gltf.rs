@@ -0,0 +1,1096 @@
+//! glTF scene export
+//!
+//! Writes a Transform subtree as binary glTF (GLB): one node per Transform with its local TRS,
+//! meshes from MeshFilter/MeshRenderer and SkinnedMeshRenderer (with a skin when the mesh has
+//! bone weights and bind poses), optional materials from `_Color` / `_MainTex`, and cameras and
+//! `KHR_lights_punctual` lights for enabled Camera and Light components. A single mesh can also
+//! be written on its own through [`MeshProcessor::export_to_gltf`](super::MeshProcessor::export_to_gltf).
+//!
+//! Each submesh becomes a primitive sharing the mesh's positions, normals, tangents, UV sets
+//! 0-3, vertex colors and (2018+) bone weights; blend shape channels become morph targets.
+//!
+//! Unity is left-handed and glTF right-handed, so X is mirrored, rotations and bind poses are
+//! conjugated accordingly, and triangle winding is reversed. V is flipped for glTF's top-left
+//! UV origin. Cameras and lights sit on a child node turned 180° about Y, since glTF's look down
+//! -Z where Unity's look down +Z.
+
+use super::cleanup::MeshCleanup;
+use super::parser::MeshParser;
+use super::types::Mesh;
+use super::vertex::{color_channel_index, tangent_channel_index, uv_channel_index};
+use crate::asset::SerializedFile;
+use crate::error::{BinaryError, Result};
+use crate::object::UnityObject;
+use crate::unity_objects::{Camera, Light, LightType};
+use crate::unity_version::UnityVersion;
+use indexmap::IndexMap;
+use serde_json::{Map, Value, json};
+use std::collections::{HashMap, HashSet};
+use unity_asset_core::{UnityValue, class_ids};
+
+type Fields = IndexMap<String, UnityValue>;
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+
+const FLOAT: u32 = 5126;
+const UNSIGNED_SHORT: u32 = 5123;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+const LIGHTS_PUNCTUAL: &str = "KHR_lights_punctual";
+
+/// `TEXCOORD_n` sets written per mesh
+const MAX_UV_SETS: usize = 4;
+
+/// glTF export options
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct GltfOptions {
+    /// Emit materials (base color from `_Color`, base color texture from `_MainTex`)
+    ///
+    /// Textures are only embedded when the `texture` feature is enabled.
+    pub materials: bool,
+    /// Emit skins for SkinnedMeshRenderers whose bones are inside the exported subtree
+    pub skins: bool,
+    /// Post-processing run on each mesh before encoding; a mesh it fails on is written as is
+    pub cleanup: MeshCleanup,
+    /// Emit glTF cameras for Camera components
+    pub cameras: bool,
+    /// Emit `KHR_lights_punctual` lights for directional, point and spot Light components
+    ///
+    /// Colors are converted to linear; intensities are written as stored, with the Unity light
+    /// unit (when the layout has one) recorded in the light's `extras`.
+    pub lights: bool,
+    /// Emit blend shape channels as morph targets, each from the channel's last (full weight)
+    /// frame, with the channel names in the mesh's `extras.targetNames`
+    pub blend_shapes: bool,
+}
+
+impl GltfOptions {
+    /// Set `materials`.
+    pub fn with_materials(mut self, materials: bool) -> Self {
+        self.materials = materials;
+        self
+    }
+
+    /// Set `skins`.
+    pub fn with_skins(mut self, skins: bool) -> Self {
+        self.skins = skins;
+        self
+    }
+
+    /// Set `cleanup`.
+    pub fn with_cleanup(mut self, cleanup: MeshCleanup) -> Self {
+        self.cleanup = cleanup;
+        self
+    }
+
+    /// Set `cameras`.
+    pub fn with_cameras(mut self, cameras: bool) -> Self {
+        self.cameras = cameras;
+        self
+    }
+
+    /// Set `lights`.
+    pub fn with_lights(mut self, lights: bool) -> Self {
+        self.lights = lights;
+        self
+    }
+
+    /// Set `blend_shapes`.
+    pub fn with_blend_shapes(mut self, blend_shapes: bool) -> Self {
+        self.blend_shapes = blend_shapes;
+        self
+    }
+}
+
+impl Default for GltfOptions {
+    fn default() -> Self {
+        Self {
+            materials: true,
+            skins: true,
+            cleanup: MeshCleanup::default(),
+            cameras: true,
+            lights: true,
+            blend_shapes: true,
+        }
+    }
+}
+
+/// Export the hierarchy below `root_path_id` (a GameObject or its Transform) as a GLB file
+///
+/// Transforms whose GameObject, mesh or material cannot be resolved (other files, stripped or
+/// streamed data) are still emitted as named empty nodes.
+pub fn export_hierarchy_to_gltf(
+    asset: &SerializedFile,
+    root_path_id: i64,
+    options: &GltfOptions,
+) -> Result<Vec<u8>> {
+    let handle = asset
+        .find_object_handle(root_path_id)
+        .ok_or_else(|| BinaryError::invalid_data(format!("Object {} not found", root_path_id)))?;
+    let root_transform = match handle.class_id() {
+        class_ids::TRANSFORM | class_ids::RECT_TRANSFORM => root_path_id,
+        class_ids::GAME_OBJECT => {
+            let game_object = handle.read()?;
+            components(game_object.class.properties())
+                .into_iter()
+                .find(|id| {
+                    asset.find_object(*id).is_some_and(|info| {
+                        matches!(
+                            info.type_id,
+                            class_ids::TRANSFORM | class_ids::RECT_TRANSFORM
+                        )
+                    })
+                })
+                .ok_or_else(|| {
+                    BinaryError::invalid_data(format!(
+                        "GameObject {} has no Transform",
+                        root_path_id
+                    ))
+                })?
+        }
+        other => {
+            return Err(BinaryError::invalid_data(format!(
+                "Object {} is class {}, not a GameObject or Transform",
+                root_path_id, other
+            )));
+        }
+    };
+
+    let version = UnityVersion::parse_version(&asset.unity_version).unwrap_or_default();
+    let mut builder = GltfBuilder::new(Some(asset), version, options);
+    let root = builder
+        .add_transform(root_transform, &mut HashSet::new())
+        .unwrap_or_default();
+    builder.resolve_skins();
+    builder.finish(root)
+}
+
+/// Export `mesh` as a GLB file with a single node
+///
+/// With `options.skins` and bind poses, the node gets a skin whose joints are one child node
+/// per bind pose, placed at the bone's bind-time transform.
+pub(super) fn export_mesh(
+    mesh: &Mesh,
+    version: UnityVersion,
+    options: &GltfOptions,
+) -> Result<Vec<u8>> {
+    let mut builder = GltfBuilder::new(None, version, options);
+    let index = builder.encode_mesh(mesh, &[])?;
+    builder
+        .nodes
+        .push(json!({ "name": mesh.name, "mesh": index }));
+    let skinned = builder.meshes[index]["primitives"][0]["attributes"]
+        .get("JOINTS_0")
+        .is_some();
+    let bones: Option<Vec<[f32; 16]>> = mesh.bind_pose.iter().map(invert_affine).collect();
+    if skinned
+        && !mesh.bind_pose.is_empty()
+        && let Some(bones) = bones
+    {
+        let mut joints = Vec::with_capacity(bones.len());
+        for (i, bone) in bones.iter().enumerate() {
+            let name = match mesh.bone_name_hashes.get(i) {
+                Some(hash) => format!("Bone_{:08x}", hash),
+                None => format!("Bone_{}", i),
+            };
+            builder
+                .nodes
+                .push(json!({ "name": name, "matrix": mirror_matrix(bone) }));
+            joints.push(builder.nodes.len() - 1);
+        }
+        builder.nodes[0]["children"] = json!(joints);
+        builder.push_skin(0, joints, &mesh.bind_pose);
+    }
+    builder.finish(0)
+}
+
+/// A SkinnedMeshRenderer waiting for the whole subtree to be known
+/// glTF mesh index and the source mesh's bind poses
+type MeshEntry = (usize, Vec<[f32; 16]>);
+
+struct PendingSkin {
+    node: usize,
+    bones: Vec<i64>,
+    bind_poses: Vec<[f32; 16]>,
+}
+
+struct GltfBuilder<'a> {
+    /// Source of referenced objects; `None` when exporting a lone mesh
+    asset: Option<&'a SerializedFile>,
+    parser: MeshParser,
+    options: &'a GltfOptions,
+    nodes: Vec<Value>,
+    node_by_transform: HashMap<i64, usize>,
+    meshes: Vec<Value>,
+    mesh_cache: HashMap<(i64, Vec<i64>), Option<MeshEntry>>,
+    materials: Vec<Value>,
+    material_cache: HashMap<i64, Option<usize>>,
+    textures: Vec<Value>,
+    images: Vec<Value>,
+    skins: Vec<Value>,
+    pending_skins: Vec<PendingSkin>,
+    cameras: Vec<Value>,
+    lights: Vec<Value>,
+    accessors: Vec<Value>,
+    buffer_views: Vec<Value>,
+    bin: Vec<u8>,
+}
+
+impl<'a> GltfBuilder<'a> {
+    fn new(
+        asset: Option<&'a SerializedFile>,
+        version: UnityVersion,
+        options: &'a GltfOptions,
+    ) -> Self {
+        Self {
+            asset,
+            parser: MeshParser::new(version),
+            options,
+            nodes: Vec::new(),
+            node_by_transform: HashMap::new(),
+            meshes: Vec::new(),
+            mesh_cache: HashMap::new(),
+            materials: Vec::new(),
+            material_cache: HashMap::new(),
+            textures: Vec::new(),
+            images: Vec::new(),
+            skins: Vec::new(),
+            pending_skins: Vec::new(),
+            cameras: Vec::new(),
+            lights: Vec::new(),
+            accessors: Vec::new(),
+            buffer_views: Vec::new(),
+            bin: Vec::new(),
+        }
+    }
+
+    fn read(&self, path_id: i64, class_id: i32) -> Option<UnityObject> {
+        self.asset?
+            .find_object_handle(path_id)
+            .filter(|h| h.class_id() == class_id)?
+            .read()
+            .ok()
+    }
+
+    /// Add a node for the Transform `path_id` and its children; `None` if already added
+    fn add_transform(&mut self, path_id: i64, visited: &mut HashSet<i64>) -> Option<usize> {
+        if !visited.insert(path_id) {
+            return None;
+        }
+        let index = self.nodes.len();
+        self.nodes
+            .push(json!({ "name": format!("Transform_{}", path_id) }));
+        self.node_by_transform.insert(path_id, index);
+
+        let Some(transform) = self
+            .read(path_id, class_ids::TRANSFORM)
+            .or_else(|| self.read(path_id, class_ids::RECT_TRANSFORM))
+        else {
+            return Some(index);
+        };
+        let props = transform.class.properties();
+        let mut node = Map::new();
+        if let Ok(trs) = transform.as_transform() {
+            let p = trs.position;
+            let r = trs.rotation;
+            let s = trs.scale;
+            node.insert("translation".into(), json!([-p.x, p.y, p.z]));
+            node.insert("rotation".into(), json!([r.x, -r.y, -r.z, r.w]));
+            node.insert("scale".into(), json!([s.x, s.y, s.z]));
+        }
+
+        let game_object = props
+            .get("m_GameObject")
+            .and_then(local_pptr)
+            .and_then(|id| self.read(id, class_ids::GAME_OBJECT));
+        let mut name = format!("Transform_{}", path_id);
+        let mut child_nodes = Vec::new();
+        if let Some(game_object) = &game_object {
+            let go = game_object.class.properties();
+            if let Some(go_name) = go.get("m_Name").and_then(UnityValue::as_str) {
+                name = go_name.to_string();
+            }
+            let components = components(go);
+            self.attach_renderers(&mut node, index, &components);
+            child_nodes = self.add_cameras_and_lights(&name, &components);
+        }
+        node.insert("name".into(), Value::String(name));
+        self.nodes[index] = Value::Object(node);
+
+        let children: Vec<i64> = props
+            .get("m_Children")
+            .and_then(UnityValue::as_array)
+            .map(|children| children.iter().filter_map(local_pptr).collect())
+            .unwrap_or_default();
+        child_nodes.extend(
+            children
+                .into_iter()
+                .filter_map(|child| self.add_transform(child, visited)),
+        );
+        if !child_nodes.is_empty() {
+            self.nodes[index]["children"] = json!(child_nodes);
+        }
+        Some(index)
+    }
+
+    /// Attach the mesh of the GameObject's MeshFilter/MeshRenderer or SkinnedMeshRenderer
+    fn attach_renderers(
+        &mut self,
+        node: &mut Map<String, Value>,
+        index: usize,
+        components: &[i64],
+    ) {
+        let mut mesh_id = None;
+        let mut materials = Vec::new();
+        let mut bones = None;
+        for &component in components {
+            let Some(info) = self.asset.and_then(|asset| asset.find_object(component)) else {
+                continue;
+            };
+            let class_id = info.type_id;
+            if !matches!(
+                class_id,
+                class_ids::MESH_FILTER
+                    | class_ids::MESH_RENDERER
+                    | class_ids::SKINNED_MESH_RENDERER
+            ) {
+                continue;
+            }
+            let Some(object) = self.read(component, class_id) else {
+                continue;
+            };
+            let props = object.class.properties();
+            if class_id != class_ids::MESH_RENDERER {
+                mesh_id = mesh_id.or_else(|| props.get("m_Mesh").and_then(local_pptr));
+            }
+            if class_id != class_ids::MESH_FILTER {
+                materials = pptr_array(props.get("m_Materials"));
+            }
+            if class_id == class_ids::SKINNED_MESH_RENDERER {
+                bones = Some(pptr_array(props.get("m_Bones")));
+            }
+        }
+
+        let Some(mesh_id) = mesh_id else {
+            return;
+        };
+        let Some((mesh, bind_poses)) = self.mesh(mesh_id, materials) else {
+            return;
+        };
+        node.insert("mesh".into(), json!(mesh));
+        if let Some(bones) = bones
+            && self.options.skins
+            && !bones.is_empty()
+            && bones.len() == bind_poses.len()
+        {
+            self.pending_skins.push(PendingSkin {
+                node: index,
+                bones,
+                bind_poses,
+            });
+        }
+    }
+
+    /// Child nodes carrying the GameObject's enabled Camera and Light components
+    fn add_cameras_and_lights(&mut self, name: &str, components: &[i64]) -> Vec<usize> {
+        let mut added = Vec::new();
+        for &component in components {
+            let Some(info) = self.asset.and_then(|asset| asset.find_object(component)) else {
+                continue;
+            };
+            let extension = match info.type_id {
+                class_ids::CAMERA if self.options.cameras => self
+                    .read(component, class_ids::CAMERA)
+                    .and_then(|object| object.as_camera().ok())
+                    .filter(|camera| camera.enabled)
+                    .map(|camera| {
+                        self.cameras.push(gltf_camera(&camera));
+                        ("camera", json!(self.cameras.len() - 1))
+                    }),
+                class_ids::LIGHT if self.options.lights => self
+                    .read(component, class_ids::LIGHT)
+                    .and_then(|object| object.as_light().ok())
+                    .filter(|light| light.enabled)
+                    .and_then(|light| gltf_light(&light))
+                    .map(|light| {
+                        self.lights.push(light);
+                        let light = json!({ LIGHTS_PUNCTUAL: { "light": self.lights.len() - 1 } });
+                        ("extensions", light)
+                    }),
+                _ => None,
+            };
+            let Some((key, value)) = extension else {
+                continue;
+            };
+            let kind = if key == "camera" { "Camera" } else { "Light" };
+            let mut node = json!({
+                "name": format!("{}_{}", name, kind),
+                "rotation": [0.0, 1.0, 0.0, 0.0],
+            });
+            node[key] = value;
+            self.nodes.push(node);
+            added.push(self.nodes.len() - 1);
+        }
+        added
+    }
+
+    /// glTF mesh for Unity mesh `path_id` rendered with `materials` (one per submesh)
+    fn mesh(&mut self, path_id: i64, materials: Vec<i64>) -> Option<MeshEntry> {
+        let key = (path_id, materials);
+        if let Some(cached) = self.mesh_cache.get(&key) {
+            return cached.clone();
+        }
+        let result = self
+            .read(path_id, class_ids::MESH)
+            .and_then(|object| self.parser.parse_from_unity_object(&object).ok())
+            .and_then(|parsed| {
+                let mut source = parsed.mesh;
+                if self.options.cleanup.is_enabled() {
+                    let mut cleaned = source.clone();
+                    if self
+                        .options
+                        .cleanup
+                        .apply(&mut cleaned, self.parser.version())
+                        .is_ok()
+                    {
+                        source = cleaned;
+                    }
+                }
+                let mesh = self.encode_mesh(&source, &key.1).ok()?;
+                Some((mesh, source.bind_pose))
+            });
+        self.mesh_cache.insert(key, result.clone());
+        result
+    }
+
+    fn encode_mesh(&mut self, mesh: &Mesh, materials: &[i64]) -> Result<usize> {
+        let version = self.parser.version().clone();
+        let positions: Vec<[f32; 3]> = mesh
+            .read_channel(&version, 0)?
+            .into_iter()
+            .map(|p| [-p[0], p[1], p[2]])
+            .collect();
+        let vertex_count = positions.len();
+        let triangles = mesh.submesh_triangles()?;
+        if let Some(&bad) = triangles
+            .iter()
+            .flatten()
+            .flatten()
+            .find(|&&i| i as usize >= vertex_count)
+        {
+            return Err(BinaryError::invalid_data(format!(
+                "Mesh {:?} index {} is out of range ({} vertices)",
+                mesh.name, bad, vertex_count
+            )));
+        }
+
+        let mut attributes = Map::new();
+        let (min, max) = bounds(&positions);
+        let view = self.push_view(&floats(positions.iter().flatten()), Some(ARRAY_BUFFER));
+        attributes.insert(
+            "POSITION".into(),
+            json!(self.push_accessor(view, FLOAT, vertex_count, "VEC3", Some((min, max)))),
+        );
+        if has_channel(mesh, 1, 3) {
+            let normals: Vec<f32> = mesh
+                .read_channel(&version, 1)?
+                .into_iter()
+                .flat_map(|n| [-n[0], n[1], n[2]])
+                .collect();
+            let view = self.push_view(&floats(normals.iter()), Some(ARRAY_BUFFER));
+            attributes.insert(
+                "NORMAL".into(),
+                json!(self.push_accessor(view, FLOAT, vertex_count, "VEC3", None)),
+            );
+            let tangent = tangent_channel_index(&version);
+            if has_channel(mesh, tangent, 4) {
+                // Mirroring X flips handedness, so the bitangent sign flips too.
+                let tangents: Vec<f32> = mesh
+                    .read_channel(&version, tangent)?
+                    .into_iter()
+                    .flat_map(|t| [-t[0], t[1], t[2], -t[3]])
+                    .collect();
+                let view = self.push_view(&floats(tangents.iter()), Some(ARRAY_BUFFER));
+                attributes.insert(
+                    "TANGENT".into(),
+                    json!(self.push_accessor(view, FLOAT, vertex_count, "VEC4", None)),
+                );
+            }
+        }
+        // glTF UV sets must be numbered without gaps, so stop at the first missing one.
+        for uv in 0..MAX_UV_SETS {
+            if !uv_channel_index(&version, uv).is_some_and(|i| has_channel(mesh, i, 2)) {
+                break;
+            }
+            let uvs: Vec<f32> = mesh
+                .read_uvs(&version, uv)?
+                .into_iter()
+                .flat_map(|[u, v]| [u, 1.0 - v])
+                .collect();
+            let view = self.push_view(&floats(uvs.iter()), Some(ARRAY_BUFFER));
+            attributes.insert(
+                format!("TEXCOORD_{}", uv),
+                json!(self.push_accessor(view, FLOAT, vertex_count, "VEC2", None)),
+            );
+        }
+        let color = color_channel_index(&version);
+        if has_channel(mesh, color, 3) {
+            let colors: Vec<f32> = mesh
+                .read_channel(&version, color)?
+                .into_iter()
+                .flat_map(|c| [c[0], c[1], c[2], c.get(3).copied().unwrap_or(1.0)])
+                .collect();
+            let view = self.push_view(&floats(colors.iter()), Some(ARRAY_BUFFER));
+            attributes.insert(
+                "COLOR_0".into(),
+                json!(self.push_accessor(view, FLOAT, vertex_count, "VEC4", None)),
+            );
+        }
+        if self.options.skins
+            && let Some((weights, joints)) = skin_channels(&version)
+            && has_channel(mesh, weights, 1)
+            && has_channel(mesh, joints, 1)
+        {
+            let weights = mesh.read_channel(&version, weights)?;
+            let joints = mesh.read_channel(&version, joints)?;
+            let mut weight_bytes = Vec::with_capacity(vertex_count * 16);
+            let mut joint_bytes = Vec::with_capacity(vertex_count * 8);
+            for (w, j) in weights.iter().zip(&joints) {
+                for k in 0..4 {
+                    let weight = w.get(k).copied().unwrap_or(0.0);
+                    let joint = if k < w.len() { j.get(k).copied() } else { None };
+                    weight_bytes.extend_from_slice(&weight.to_le_bytes());
+                    joint_bytes.extend_from_slice(&(joint.unwrap_or(0.0) as u16).to_le_bytes());
+                }
+            }
+            let view = self.push_view(&weight_bytes, Some(ARRAY_BUFFER));
+            attributes.insert(
+                "WEIGHTS_0".into(),
+                json!(self.push_accessor(view, FLOAT, vertex_count, "VEC4", None)),
+            );
+            let view = self.push_view(&joint_bytes, Some(ARRAY_BUFFER));
+            attributes.insert(
+                "JOINTS_0".into(),
+                json!(self.push_accessor(view, UNSIGNED_SHORT, vertex_count, "VEC4", None)),
+            );
+        }
+
+        let (targets, target_names) = self.morph_targets(mesh, vertex_count, &attributes)?;
+
+        let mut primitives = Vec::new();
+        for (submesh, tris) in triangles.iter().enumerate() {
+            if tris.is_empty() {
+                continue;
+            }
+            let indices: Vec<u8> = tris
+                .iter()
+                .flat_map(|[a, b, c]| [*a, *c, *b])
+                .flat_map(u32::to_le_bytes)
+                .collect();
+            let view = self.push_view(&indices, Some(ELEMENT_ARRAY_BUFFER));
+            let accessor = self.push_accessor(view, UNSIGNED_INT, tris.len() * 3, "SCALAR", None);
+            let mut primitive = json!({ "attributes": attributes.clone(), "indices": accessor });
+            if !targets.is_empty() {
+                primitive["targets"] = json!(targets);
+            }
+            if self.options.materials
+                && let Some(&material) = materials.get(submesh)
+                && let Some(material) = self.material(material)
+            {
+                primitive["material"] = json!(material);
+            }
+            primitives.push(primitive);
+        }
+        if primitives.is_empty() {
+            return Err(BinaryError::invalid_data(format!(
+                "Mesh {:?} has no triangles",
+                mesh.name
+            )));
+        }
+
+        let mut gltf_mesh = json!({ "name": mesh.name, "primitives": primitives });
+        if !targets.is_empty() {
+            gltf_mesh["weights"] = json!(vec![0.0; targets.len()]);
+            gltf_mesh["extras"] = json!({ "targetNames": target_names });
+        }
+        self.meshes.push(gltf_mesh);
+        Ok(self.meshes.len() - 1)
+    }
+
+    /// Morph targets (and their names) for the mesh's blend shape channels
+    ///
+    /// Normal and tangent deltas are only written when the shape has them and the mesh has the
+    /// matching attribute. Channels without frames are skipped.
+    fn morph_targets(
+        &mut self,
+        mesh: &Mesh,
+        vertex_count: usize,
+        attributes: &Map<String, Value>,
+    ) -> Result<(Vec<Value>, Vec<String>)> {
+        let mut targets = Vec::new();
+        let mut names = Vec::new();
+        let Some(blend) = mesh
+            .blend_shape_data
+            .as_ref()
+            .filter(|_| self.options.blend_shapes)
+        else {
+            return Ok((targets, names));
+        };
+        for channel in &blend.channels {
+            let Some(shape) = usize::try_from(channel.frame_index + channel.frame_count - 1)
+                .ok()
+                .filter(|_| channel.frame_count > 0)
+                .and_then(|frame| blend.shapes.get(frame))
+            else {
+                continue;
+            };
+            let first = shape.first_vertex as usize;
+            let deltas = blend
+                .vertices
+                .get(first..first + shape.vertex_count as usize)
+                .ok_or_else(|| {
+                    BinaryError::invalid_data(format!(
+                        "Blend shape {:?} of mesh {:?} has vertices {}..{} ({} stored)",
+                        channel.name,
+                        mesh.name,
+                        first,
+                        first + shape.vertex_count as usize,
+                        blend.vertices.len()
+                    ))
+                })?;
+            let mut positions = vec![[0.0; 3]; vertex_count];
+            let mut normals = vec![[0.0; 3]; vertex_count];
+            let mut tangents = vec![[0.0; 3]; vertex_count];
+            for delta in deltas {
+                let i = delta.index as usize;
+                if i >= vertex_count {
+                    return Err(BinaryError::invalid_data(format!(
+                        "Blend shape {:?} of mesh {:?} moves vertex {} ({} vertices)",
+                        channel.name, mesh.name, i, vertex_count
+                    )));
+                }
+                let mirror = |[x, y, z]: [f32; 3]| [-x, y, z];
+                positions[i] = mirror(delta.vertex);
+                normals[i] = mirror(delta.normal);
+                tangents[i] = mirror(delta.tangent);
+            }
+
+            let mut target = Map::new();
+            let view = self.push_view(&floats(positions.iter().flatten()), Some(ARRAY_BUFFER));
+            let accessor =
+                self.push_accessor(view, FLOAT, vertex_count, "VEC3", Some(bounds(&positions)));
+            target.insert("POSITION".into(), json!(accessor));
+            for (key, present, values) in [
+                ("NORMAL", shape.has_normals, &normals),
+                ("TANGENT", shape.has_tangents, &tangents),
+            ] {
+                if present && attributes.contains_key(key) {
+                    let view = self.push_view(&floats(values.iter().flatten()), Some(ARRAY_BUFFER));
+                    let accessor = self.push_accessor(view, FLOAT, vertex_count, "VEC3", None);
+                    target.insert(key.into(), json!(accessor));
+                }
+            }
+            targets.push(Value::Object(target));
+            names.push(channel.name.clone());
+        }
+        Ok((targets, names))
+    }
+
+    /// glTF material for the Material `path_id`
+    fn material(&mut self, path_id: i64) -> Option<usize> {
+        if let Some(cached) = self.material_cache.get(&path_id) {
+            return *cached;
+        }
+        let result = self.read(path_id, class_ids::MATERIAL).map(|material| {
+            let props = material.class.properties();
+            let mut pbr = json!({ "metallicFactor": 0.0 });
+            if let Some(color) = saved_property(props, "m_Colors", "_Color").and_then(color) {
+                pbr["baseColorFactor"] = json!(color);
+            }
+            if let Some(texture) = saved_property(props, "m_TexEnvs", "_MainTex")
+                .and_then(|env| env.as_object()?.get("m_Texture"))
+                .and_then(local_pptr)
+                .and_then(|texture| self.texture(texture))
+            {
+                pbr["baseColorTexture"] = json!({ "index": texture });
+            }
+            let name = props
+                .get("m_Name")
+                .and_then(UnityValue::as_str)
+                .unwrap_or_default();
+            self.materials
+                .push(json!({ "name": name, "pbrMetallicRoughness": pbr }));
+            self.materials.len() - 1
+        });
+        self.material_cache.insert(path_id, result);
+        result
+    }
+
+    /// Decode the Texture2D `path_id` into an embedded PNG texture
+    #[cfg(feature = "texture")]
+    fn texture(&mut self, path_id: i64) -> Option<usize> {
+        use crate::texture::Texture2DConverter;
+
+        let object = self.read(path_id, class_ids::TEXTURE_2D)?;
+        let converter = Texture2DConverter::new(self.parser.version().clone());
+        let texture = converter.from_unity_object(&object).ok()?;
+        let image = converter.decode_to_image(&texture).ok()?;
+        let mut png = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut png, image::ImageFormat::Png).ok()?;
+
+        let view = self.push_view(&png.into_inner(), None);
+        self.images.push(json!({
+            "name": texture.name,
+            "bufferView": view,
+            "mimeType": "image/png",
+        }));
+        self.textures
+            .push(json!({ "source": self.images.len() - 1 }));
+        Some(self.textures.len() - 1)
+    }
+
+    #[cfg(not(feature = "texture"))]
+    fn texture(&mut self, _path_id: i64) -> Option<usize> {
+        None
+    }
+
+    /// Turn pending SkinnedMeshRenderers into skins once every node is known
+    fn resolve_skins(&mut self) {
+        for pending in std::mem::take(&mut self.pending_skins) {
+            let joints: Option<Vec<usize>> = pending
+                .bones
+                .iter()
+                .map(|bone| self.node_by_transform.get(bone).copied())
+                .collect();
+            let Some(joints) = joints else {
+                continue;
+            };
+            self.push_skin(pending.node, joints, &pending.bind_poses);
+        }
+    }
+
+    /// Skin of `joints` (one per bind pose) on node `node`
+    fn push_skin(&mut self, node: usize, joints: Vec<usize>, bind_poses: &[[f32; 16]]) {
+        let matrices: Vec<u8> = bind_poses
+            .iter()
+            .flat_map(mirror_matrix)
+            .flat_map(f32::to_le_bytes)
+            .collect();
+        let view = self.push_view(&matrices, None);
+        let accessor = self.push_accessor(view, FLOAT, joints.len(), "MAT4", None);
+        self.skins
+            .push(json!({ "joints": joints, "inverseBindMatrices": accessor }));
+        self.nodes[node]["skin"] = json!(self.skins.len() - 1);
+    }
+
+    fn push_view(&mut self, bytes: &[u8], target: Option<u32>) -> usize {
+        while !self.bin.len().is_multiple_of(4) {
+            self.bin.push(0);
+        }
+        let mut view = json!({
+            "buffer": 0,
+            "byteOffset": self.bin.len(),
+            "byteLength": bytes.len(),
+        });
+        if let Some(target) = target {
+            view["target"] = json!(target);
+        }
+        self.bin.extend_from_slice(bytes);
+        self.buffer_views.push(view);
+        self.buffer_views.len() - 1
+    }
+
+    fn push_accessor(
+        &mut self,
+        view: usize,
+        component_type: u32,
+        count: usize,
+        kind: &str,
+        bounds: Option<([f32; 3], [f32; 3])>,
+    ) -> usize {
+        let mut accessor = json!({
+            "bufferView": view,
+            "componentType": component_type,
+            "count": count,
+            "type": kind,
+        });
+        if let Some((min, max)) = bounds {
+            accessor["min"] = json!(min);
+            accessor["max"] = json!(max);
+        }
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    fn finish(mut self, root: usize) -> Result<Vec<u8>> {
+        while !self.bin.len().is_multiple_of(4) {
+            self.bin.push(0);
+        }
+        let mut document = json!({
+            "asset": { "version": "2.0", "generator": "unity-asset" },
+            "scene": 0,
+            "scenes": [{ "nodes": [root] }],
+            "nodes": self.nodes,
+        });
+        let sections = [
+            ("meshes", self.meshes),
+            ("materials", self.materials),
+            ("textures", self.textures),
+            ("images", self.images),
+            ("skins", self.skins),
+            ("cameras", self.cameras),
+            ("accessors", self.accessors),
+            ("bufferViews", self.buffer_views),
+        ];
+        for (key, values) in sections {
+            if !values.is_empty() {
+                document[key] = Value::Array(values);
+            }
+        }
+        if !self.lights.is_empty() {
+            document["extensions"] = json!({ LIGHTS_PUNCTUAL: { "lights": self.lights } });
+            document["extensionsUsed"] = json!([LIGHTS_PUNCTUAL]);
+        }
+        if !self.bin.is_empty() {
+            document["buffers"] = json!([{ "byteLength": self.bin.len() }]);
+        }
+
+        let mut json = serde_json::to_vec(&document)
+            .map_err(|e| BinaryError::generic(format!("Failed to serialize glTF JSON: {}", e)))?;
+        while json.len() % 4 != 0 {
+            json.push(b' ');
+        }
+        let bin_chunk = if self.bin.is_empty() {
+            0
+        } else {
+            8 + self.bin.len()
+        };
+        let total = 12 + 8 + json.len() + bin_chunk;
+        let mut glb = Vec::with_capacity(total);
+        glb.extend_from_slice(&GLB_MAGIC.to_le_bytes());
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&(total as u32).to_le_bytes());
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(&CHUNK_JSON.to_le_bytes());
+        glb.extend_from_slice(&json);
+        if !self.bin.is_empty() {
+            glb.extend_from_slice(&(self.bin.len() as u32).to_le_bytes());
+            glb.extend_from_slice(&CHUNK_BIN.to_le_bytes());
+            glb.extend_from_slice(&self.bin);
+        }
+        Ok(glb)
+    }
+}
+
+fn gltf_camera(camera: &Camera) -> Value {
+    let znear = camera.near_clip_plane.max(f32::EPSILON);
+    let zfar = camera.far_clip_plane.max(znear * 2.0);
+    if camera.orthographic {
+        // The aspect ratio depends on the screen; assume square.
+        let size = camera.orthographic_size.abs().max(f32::EPSILON);
+        json!({
+            "type": "orthographic",
+            "orthographic": { "xmag": size, "ymag": size, "znear": znear, "zfar": zfar },
+        })
+    } else {
+        json!({
+            "type": "perspective",
+            "perspective": {
+                "yfov": camera.field_of_view.to_radians(),
+                "znear": znear,
+                "zfar": zfar,
+            },
+        })
+    }
+}
+
+/// `KHR_lights_punctual` light; `None` for area lights, which have no glTF equivalent
+fn gltf_light(light: &Light) -> Option<Value> {
+    let linear = |c: f32| {
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let color = light.color;
+    let mut value = json!({
+        "color": [linear(color.r), linear(color.g), linear(color.b)],
+        "intensity": light.intensity,
+    });
+    match light.light_type {
+        LightType::Directional => value["type"] = json!("directional"),
+        LightType::Point => {
+            value["type"] = json!("point");
+            value["range"] = json!(light.range);
+        }
+        LightType::Spot => {
+            let outer = (light.spot_angle / 2.0).to_radians();
+            let inner = light.inner_spot_angle.unwrap_or_default() / 2.0;
+            value["type"] = json!("spot");
+            value["range"] = json!(light.range);
+            value["spot"] = json!({
+                "innerConeAngle": inner.to_radians().min(outer),
+                "outerConeAngle": outer,
+            });
+        }
+        LightType::Rectangle | LightType::Disc | LightType::Unknown(_) => return None,
+    }
+    if let Some(unit) = light.light_unit {
+        value["extras"] = json!({ "unityLightUnit": unit.name() });
+    }
+    Some(value)
+}
+
+/// `m_Channels` indices of BlendWeight / BlendIndices (2018+; older meshes use `m_Skin`)
+fn skin_channels(version: &UnityVersion) -> Option<(usize, usize)> {
+    version
+        .capabilities()
+        .vertex_channels_2018
+        .then_some((12, 13))
+}
+
+fn has_channel(mesh: &Mesh, index: usize, min_dimension: u8) -> bool {
+    mesh.vertex_data
+        .channels
+        .get(index)
+        .is_some_and(|c| c.dimension & 0xf >= min_dimension)
+}
+
+fn floats<'f>(values: impl Iterator<Item = &'f f32>) -> Vec<u8> {
+    values.flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn bounds(points: &[[f32; 3]]) -> ([f32; 3], [f32; 3]) {
+    let mut min = [f32::MAX; 3];
+    let mut max = [f32::MIN; 3];
+    for point in points {
+        for axis in 0..3 {
+            min[axis] = min[axis].min(point[axis]);
+            max[axis] = max[axis].max(point[axis]);
+        }
+    }
+    (min, max)
+}
+
+/// Row-major Unity bind pose to a column-major glTF matrix, mirrored on X
+fn mirror_matrix(m: &[f32; 16]) -> [f32; 16] {
+    let sign = |i: usize| if i == 0 { -1.0 } else { 1.0 };
+    let mut out = [0.0; 16];
+    for row in 0..4 {
+        for column in 0..4 {
+            out[column * 4 + row] = m[row * 4 + column] * sign(row) * sign(column);
+        }
+    }
+    out
+}
+
+/// Inverse of a row-major affine matrix (last row `0 0 0 1`), `None` when it is singular
+fn invert_affine(m: &[f32; 16]) -> Option<[f32; 16]> {
+    let a = |row: usize, column: usize| m[row * 4 + column];
+    let cofactor = |row: usize, column: usize| {
+        let (r0, r1) = ((row + 1) % 3, (row + 2) % 3);
+        let (c0, c1) = ((column + 1) % 3, (column + 2) % 3);
+        a(r0, c0) * a(r1, c1) - a(r0, c1) * a(r1, c0)
+    };
+    let det = (0..3)
+        .map(|column| a(0, column) * cofactor(0, column))
+        .sum::<f32>();
+    if det.abs() <= f32::EPSILON {
+        return None;
+    }
+    let mut out = [0.0; 16];
+    for row in 0..3 {
+        for column in 0..3 {
+            // Inverse is the transposed cofactor matrix over the determinant.
+            out[row * 4 + column] = cofactor(column, row) / det;
+        }
+    }
+    for row in 0..3 {
+        out[row * 4 + 3] = -(0..3).map(|k| out[row * 4 + k] * a(k, 3)).sum::<f32>();
+    }
+    out[15] = 1.0;
+    Some(out)
+}
+
+/// Path ID of a PPtr into the same file
+fn local_pptr(value: &UnityValue) -> Option<i64> {
+    let fields = value.as_object()?;
+    let file_id = fields
+        .get("m_FileID")
+        .or_else(|| fields.get("fileID"))?
+        .as_i64()?;
+    let path_id = fields
+        .get("m_PathID")
+        .or_else(|| fields.get("pathID"))?
+        .as_i64()?;
+    (file_id == 0 && path_id != 0).then_some(path_id)
+}
+
+fn pptr_array(value: Option<&UnityValue>) -> Vec<i64> {
+    value
+        .and_then(UnityValue::as_array)
+        .map(|items| items.iter().filter_map(local_pptr).collect())
+        .unwrap_or_default()
+}
+
+/// Component path IDs of a GameObject (`m_Component` entries are `{component: PPtr}`)
+fn components(game_object: &Fields) -> Vec<i64> {
+    game_object
+        .get("m_Component")
+        .and_then(UnityValue::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let pair = item.as_object()?;
+                    pair.get("component")
+                        .map_or_else(|| local_pptr(item), local_pptr)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Value of `name` in a `m_SavedProperties` map (`m_TexEnvs`, `m_Colors`, ...)
+pub(super) fn saved_property<'v>(
+    material: &'v Fields,
+    list: &str,
+    name: &str,
+) -> Option<&'v UnityValue> {
+    let entries = material
+        .get("m_SavedProperties")?
+        .as_object()?
+        .get(list)?
+        .as_array()?;
+    entries.iter().find_map(|entry| {
+        // Map entries arrive as `[first, second]` pairs or `{first, second}` objects.
+        let (first, second) = match entry {
+            UnityValue::Array(pair) if pair.len() == 2 => (&pair[0], &pair[1]),
+            UnityValue::Object(pair) => (pair.get("first")?, pair.get("second")?),
+            _ => return None,
+        };
+        let key = match first {
+            UnityValue::String(key) => key.as_str(),
+            UnityValue::Object(key) => key.get("name")?.as_str()?,
+            _ => return None,
+        };
+        (key == name).then_some(second)
+    })
+}
+
+pub(super) fn color(value: &UnityValue) -> Option<[f32; 4]> {
+    let fields = value.as_object()?;
+    let channel = |key: &str| {
+        fields
+            .get(key)
+            .and_then(UnityValue::as_f64)
+            .map(|v| v as f32)
+    };
+    Some([channel("r")?, channel("g")?, channel("b")?, channel("a")?])
+}
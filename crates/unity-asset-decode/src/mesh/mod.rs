@@ -9,6 +9,12 @@
 //! - `types` - Core data structures (Mesh, VertexData, SubMesh, etc.)
 //! - `parser` - Mesh parsing from Unity objects
 //! - `processor` - High-level mesh processing and export
+//! - `vertex` - Reading vertex channels and submesh triangles
+//! - `cleanup` - Vertex welding and normal/tangent recomputation
+//! - `compressed` - Unpacking `m_CompressedMesh` into vertex data
+//! - `uv_layout` - UV layout images (feature `mesh-export`)
+//! - `gltf` - Meshes and GameObject hierarchies as binary glTF (feature `mesh-export`)
+//! - `obj` - OBJ + MTL export with material colors and textures (feature `mesh-export`)
 //!
 //! # Examples
 //!
@@ -18,13 +24,12 @@
 //!
 //! // Create processor with custom configuration
 //! let version = UnityVersion::parse_version("2020.3.12f1")?;
-//! let config = MeshConfig {
-//!     extract_vertices: true,
-//!     extract_indices: true,
-//!     process_blend_shapes: true,
-//!     decompress_meshes: true,
-//!     max_vertex_count: Some(100000),
-//! };
+//! let config = MeshConfig::default()
+//!     .with_extract_vertices(true)
+//!     .with_extract_indices(true)
+//!     .with_process_blend_shapes(true)
+//!     .with_decompress_meshes(true)
+//!     .with_max_vertex_count(Some(100000));
 //! let processor = MeshProcessor::with_config(version, config);
 //!
 //! // Note: In real usage, you would create a UnityObject from parsed data
@@ -33,11 +38,27 @@
 //! # Ok::<(), unity_asset_decode::error::BinaryError>(())
 //! ```
 
+pub mod cleanup;
+pub mod compressed;
+#[cfg(feature = "mesh-export")]
+pub mod gltf;
+#[cfg(feature = "mesh-export")]
+pub mod obj;
 pub mod parser;
 pub mod processor;
 pub mod types;
+#[cfg(feature = "mesh-export")]
+pub mod uv_layout;
+pub mod vertex;
 
 // Re-export main types for easy access
+#[cfg(feature = "mesh-export")]
+use crate::unity_version::VertexFormatTable;
+
+pub use cleanup::MeshCleanup;
+pub use gltf::{GltfOptions, export_hierarchy_to_gltf};
+#[cfg(feature = "mesh-export")]
+pub use obj::{ObjAssetResolver, ObjExportReport, ObjMaterials, export_to_obj_with_materials};
 pub use parser::MeshParser;
 pub use processor::{MeshProcessor, MeshStats};
 pub use types::{
@@ -63,6 +84,9 @@ pub use types::{
     SubMesh,
     VertexData,
 };
+#[cfg(feature = "mesh-export")]
+pub use uv_layout::{UvLayoutOptions, UvOverflow};
+pub use vertex::uv_channel_index;
 
 /// Main mesh processing facade
 ///
@@ -100,6 +124,12 @@ impl MeshManager {
         self.processor.export_to_obj(mesh)
     }
 
+    /// Export mesh to binary glTF (GLB)
+    #[cfg(feature = "mesh-export")]
+    pub fn export_to_gltf(&self, mesh: &Mesh) -> crate::error::Result<Vec<u8>> {
+        self.processor.export_to_gltf(mesh)
+    }
+
     /// Get mesh statistics
     pub fn get_statistics(&self, meshes: &[&Mesh]) -> MeshStats {
         self.processor.get_mesh_stats(meshes)
@@ -181,6 +211,7 @@ pub fn create_performance_manager(version: crate::unity_version::UnityVersion) -
         process_blend_shapes: false,
         decompress_meshes: false,
         max_vertex_count: Some(10000),
+        cleanup: MeshCleanup::default(),
     };
     MeshManager::with_config(version, config)
 }
@@ -193,6 +224,7 @@ pub fn create_full_manager(version: crate::unity_version::UnityVersion) -> MeshM
         process_blend_shapes: true,
         decompress_meshes: true,
         max_vertex_count: None,
+        cleanup: MeshCleanup::default(),
     };
     MeshManager::with_config(version, config)
 }
@@ -257,19 +289,22 @@ pub fn is_mesh_feature_supported(
     version: &crate::unity_version::UnityVersion,
     feature: &str,
 ) -> bool {
+    let caps = version.capabilities();
     match feature {
         "basic_mesh" | "sub_meshes" | "vertex_data" => true,
-        "blend_shapes" | "compressed_mesh" => version.major >= 5,
-        "mesh_optimization" | "streaming_info" => version.major >= 2017,
-        "mesh_usage_flags" => version.major >= 2018,
-        "mesh_topology" | "vertex_attributes" => version.major >= 2019,
+        "blend_shapes" | "compressed_mesh" => caps.mesh_blend_shapes,
+        "mesh_optimization" => caps.mesh_optimization,
+        "streaming_info" => caps.streamed_mesh,
+        "mesh_usage_flags" => caps.mesh_usage_flags,
+        "mesh_topology" | "vertex_attributes" => caps.vertex_format == VertexFormatTable::V2019,
         _ => false,
     }
 }
 
 /// Get recommended mesh configuration for Unity version
 pub fn get_recommended_config(version: &crate::unity_version::UnityVersion) -> MeshConfig {
-    if version.major >= 2019 {
+    let caps = version.capabilities();
+    if caps.vertex_format == VertexFormatTable::V2019 {
         // Modern Unity - full features
         MeshConfig {
             extract_vertices: true,
@@ -277,8 +312,9 @@ pub fn get_recommended_config(version: &crate::unity_version::UnityVersion) -> M
             process_blend_shapes: true,
             decompress_meshes: true,
             max_vertex_count: None,
+            cleanup: MeshCleanup::default(),
         }
-    } else if version.major >= 2017 {
+    } else if caps.streamed_mesh {
         // Unity 2017+ - streaming support
         MeshConfig {
             extract_vertices: true,
@@ -286,8 +322,9 @@ pub fn get_recommended_config(version: &crate::unity_version::UnityVersion) -> M
             process_blend_shapes: true,
             decompress_meshes: true,
             max_vertex_count: Some(100000),
+            cleanup: MeshCleanup::default(),
         }
-    } else if version.major >= 5 {
+    } else if caps.mesh_blend_shapes {
         // Unity 5+ - basic features
         MeshConfig {
             extract_vertices: true,
@@ -295,6 +332,7 @@ pub fn get_recommended_config(version: &crate::unity_version::UnityVersion) -> M
             process_blend_shapes: false,
             decompress_meshes: false,
             max_vertex_count: Some(50000),
+            cleanup: MeshCleanup::default(),
         }
     } else {
         // Legacy Unity - minimal features
@@ -304,12 +342,14 @@ pub fn get_recommended_config(version: &crate::unity_version::UnityVersion) -> M
             process_blend_shapes: false,
             decompress_meshes: false,
             max_vertex_count: Some(10000),
+            cleanup: MeshCleanup::default(),
         }
     }
 }
 
 /// Mesh processing options
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ProcessingOptions {
     pub parallel_processing: bool,
     pub cache_results: bool,
@@ -317,6 +357,32 @@ pub struct ProcessingOptions {
     pub generate_lods: bool,
 }
 
+impl ProcessingOptions {
+    /// Set `parallel_processing`.
+    pub fn with_parallel_processing(mut self, parallel_processing: bool) -> Self {
+        self.parallel_processing = parallel_processing;
+        self
+    }
+
+    /// Set `cache_results`.
+    pub fn with_cache_results(mut self, cache_results: bool) -> Self {
+        self.cache_results = cache_results;
+        self
+    }
+
+    /// Set `validate_meshes`.
+    pub fn with_validate_meshes(mut self, validate_meshes: bool) -> Self {
+        self.validate_meshes = validate_meshes;
+        self
+    }
+
+    /// Set `generate_lods`.
+    pub fn with_generate_lods(mut self, generate_lods: bool) -> Self {
+        self.generate_lods = generate_lods;
+        self
+    }
+}
+
 impl Default for ProcessingOptions {
     fn default() -> Self {
         Self {
@@ -0,0 +1,349 @@
+//! OBJ export with materials
+//!
+//! Writes `<mesh>.obj` with one `usemtl` per submesh, a sibling `<mesh>.mtl` with a diffuse
+//! color (`Kd`, `d`) from each Material's `_Color` and a `map_Kd` for its `_MainTex`, and the
+//! referenced textures as PNGs next to them. All paths in the files are relative to `out_dir`.
+//!
+//! Materials come from a renderer's `m_Materials` or an explicit list, in submesh order. A
+//! submesh without a resolvable Material uses a shared `default` entry, and a texture that cannot
+//! be resolved or decoded is left out of its material; both are reported as warnings rather than
+//! failing the export. Textures are only decoded when the `texture` feature is enabled.
+
+use super::gltf::{color, saved_property};
+use super::processor::MeshProcessor;
+use super::types::Mesh;
+use crate::asset::SerializedFile;
+use crate::error::{BinaryError, Result};
+use crate::object::UnityObject;
+use crate::unity_version::UnityVersion;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use unity_asset_core::UnityValue;
+
+const DEFAULT_MATERIAL: &str = "default";
+
+/// Where an OBJ export's materials come from
+#[derive(Debug, Clone, Copy)]
+pub enum ObjMaterials<'a> {
+    /// A MeshRenderer or SkinnedMeshRenderer; its `m_Materials` are resolved through the resolver
+    Renderer(&'a UnityObject),
+    /// Material objects, one per submesh
+    Materials(&'a [UnityObject]),
+}
+
+/// Looks up the Materials and Textures an OBJ export references
+///
+/// Implemented by [`SerializedFile`] (PPtrs into the same file) and by closures.
+pub trait ObjAssetResolver {
+    /// The object the PPtr `(file_id, path_id)` points at
+    fn resolve(&self, file_id: i32, path_id: i64) -> Option<UnityObject>;
+}
+
+impl ObjAssetResolver for SerializedFile {
+    fn resolve(&self, file_id: i32, path_id: i64) -> Option<UnityObject> {
+        if file_id != 0 {
+            return None;
+        }
+        self.find_object_handle(path_id)?.read().ok()
+    }
+}
+
+impl<F> ObjAssetResolver for F
+where
+    F: Fn(i32, i64) -> Option<UnityObject>,
+{
+    fn resolve(&self, file_id: i32, path_id: i64) -> Option<UnityObject> {
+        self(file_id, path_id)
+    }
+}
+
+/// Files written by [`export_to_obj_with_materials`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ObjExportReport {
+    pub obj_path: PathBuf,
+    pub mtl_path: PathBuf,
+    /// `usemtl` name of each submesh
+    pub submesh_materials: Vec<String>,
+    /// PNGs written for `map_Kd`
+    pub textures: Vec<PathBuf>,
+    /// Materials and textures that fell back to defaults
+    pub warnings: Vec<String>,
+}
+
+/// Export `mesh` to `out_dir` as OBJ + MTL, with its materials' textures as PNGs
+///
+/// Uses the default [`MeshConfig`](super::MeshConfig); see
+/// [`MeshProcessor::export_to_obj_with_materials`] to choose the cleanup pass.
+pub fn export_to_obj_with_materials(
+    mesh: &Mesh,
+    version: &UnityVersion,
+    materials: ObjMaterials<'_>,
+    texture_resolver: &dyn ObjAssetResolver,
+    out_dir: &Path,
+) -> Result<ObjExportReport> {
+    let processor = MeshProcessor::new(version.clone());
+    export_with_processor(&processor, mesh, materials, texture_resolver, out_dir)
+}
+
+pub(super) fn export_with_processor(
+    processor: &MeshProcessor,
+    mesh: &Mesh,
+    materials: ObjMaterials<'_>,
+    resolver: &dyn ObjAssetResolver,
+    out_dir: &Path,
+) -> Result<ObjExportReport> {
+    std::fs::create_dir_all(out_dir).map_err(|e| {
+        BinaryError::generic(format!("Failed to create {}: {}", out_dir.display(), e))
+    })?;
+    let base = file_stem(&mesh.name, "mesh");
+    let mut report = ObjExportReport {
+        obj_path: out_dir.join(format!("{}.obj", base)),
+        mtl_path: out_dir.join(format!("{}.mtl", base)),
+        ..Default::default()
+    };
+
+    let mut writer = MtlWriter {
+        resolver,
+        version: processor.version(),
+        out_dir,
+        entries: Vec::new(),
+        by_key: HashMap::new(),
+        textures: HashMap::new(),
+        report: &mut report,
+    };
+    let sources = writer.material_sources(materials);
+    for index in 0..mesh.sub_meshes.len() {
+        let name = match sources.get(index) {
+            Some(Some((key, material))) => writer.material(*key, material),
+            Some(None) => writer.default_material(),
+            None => {
+                writer
+                    .report
+                    .warnings
+                    .push(format!("Submesh {} has no material", index));
+                writer.default_material()
+            }
+        };
+        writer.report.submesh_materials.push(name);
+    }
+    let mtl = writer.finish();
+
+    let mtllib = format!("{}.mtl", base);
+    let obj = processor.obj_text(mesh, Some((&mtllib, &report.submesh_materials)))?;
+    write_file(&report.obj_path, obj.as_bytes())?;
+    write_file(&report.mtl_path, mtl.as_bytes())?;
+    Ok(report)
+}
+
+/// One `newmtl` entry
+struct MtlEntry {
+    name: String,
+    diffuse: [f32; 4],
+    texture: Option<String>,
+}
+
+struct MtlWriter<'a> {
+    resolver: &'a dyn ObjAssetResolver,
+    version: &'a UnityVersion,
+    out_dir: &'a Path,
+    entries: Vec<MtlEntry>,
+    /// Entry name per material PPtr (`None` for the default entry)
+    by_key: HashMap<Option<(i32, i64)>, String>,
+    /// PNG file name per texture PPtr, `None` when it failed
+    textures: HashMap<(i32, i64), Option<String>>,
+    report: &'a mut ObjExportReport,
+}
+
+impl MtlWriter<'_> {
+    /// Material per submesh with its PPtr; `None` where it could not be resolved
+    fn material_sources(
+        &mut self,
+        materials: ObjMaterials<'_>,
+    ) -> Vec<Option<((i32, i64), UnityObject)>> {
+        match materials {
+            ObjMaterials::Materials(list) => list
+                .iter()
+                .map(|material| Some(((0, material.path_id()), material.clone())))
+                .collect(),
+            ObjMaterials::Renderer(renderer) => renderer
+                .get("m_Materials")
+                .and_then(UnityValue::as_array)
+                .map(|items| items.iter().map(pptr).collect::<Vec<_>>())
+                .unwrap_or_default()
+                .into_iter()
+                .enumerate()
+                .map(|(slot, pptr)| {
+                    let resolved = pptr.and_then(|(file_id, path_id)| {
+                        let object = self.resolver.resolve(file_id, path_id)?;
+                        Some(((file_id, path_id), object))
+                    });
+                    if resolved.is_none() {
+                        self.report.warnings.push(format!(
+                            "Material {} of {:?} could not be resolved",
+                            slot,
+                            renderer.name().unwrap_or_default()
+                        ));
+                    }
+                    resolved
+                })
+                .collect(),
+        }
+    }
+
+    fn default_material(&mut self) -> String {
+        if !self.by_key.contains_key(&None) {
+            self.entries.push(MtlEntry {
+                name: DEFAULT_MATERIAL.to_string(),
+                diffuse: [1.0; 4],
+                texture: None,
+            });
+            self.by_key.insert(None, DEFAULT_MATERIAL.to_string());
+        }
+        DEFAULT_MATERIAL.to_string()
+    }
+
+    fn material(&mut self, key: (i32, i64), material: &UnityObject) -> String {
+        if let Some(name) = self.by_key.get(&Some(key)) {
+            return name.clone();
+        }
+        let props = material.class.properties();
+        let raw_name = props
+            .get("m_Name")
+            .and_then(UnityValue::as_str)
+            .unwrap_or_default();
+        let name = self.unique_name(file_stem(raw_name, "material"));
+        let diffuse = saved_property(props, "m_Colors", "_Color")
+            .and_then(color)
+            .unwrap_or([1.0; 4]);
+        let texture = saved_property(props, "m_TexEnvs", "_MainTex")
+            .and_then(|env| env.as_object()?.get("m_Texture"))
+            .and_then(pptr)
+            .and_then(|texture| self.texture(texture, raw_name));
+
+        self.entries.push(MtlEntry {
+            name: name.clone(),
+            diffuse,
+            texture,
+        });
+        self.by_key.insert(Some(key), name.clone());
+        name
+    }
+
+    fn unique_name(&self, stem: String) -> String {
+        let taken = |name: &str| self.entries.iter().any(|e| e.name == name);
+        if !taken(&stem) && stem != DEFAULT_MATERIAL {
+            return stem;
+        }
+        (1..)
+            .map(|n| format!("{}_{}", stem, n))
+            .find(|name| !taken(name))
+            .unwrap_or(stem)
+    }
+
+    /// Write the texture as a PNG; its file name, or `None` with a warning
+    fn texture(&mut self, key: (i32, i64), material: &str) -> Option<String> {
+        if let Some(cached) = self.textures.get(&key) {
+            return cached.clone();
+        }
+        let result = match self.resolver.resolve(key.0, key.1) {
+            None => Err(format!(
+                "Texture ({}, {}) of material {:?} could not be resolved",
+                key.0, key.1, material
+            )),
+            Some(object) => self.write_texture(&object).map_err(|e| {
+                format!(
+                    "Texture {:?} of material {:?} was not exported: {}",
+                    object.name().unwrap_or_default(),
+                    material,
+                    e
+                )
+            }),
+        };
+        let file = result.map_err(|w| self.report.warnings.push(w)).ok();
+        self.textures.insert(key, file.clone());
+        file
+    }
+
+    #[cfg(feature = "texture")]
+    fn write_texture(&mut self, object: &UnityObject) -> Result<String> {
+        use crate::texture::{Texture2DConverter, TextureExporter};
+
+        let converter = Texture2DConverter::new(self.version.clone());
+        let texture = converter.from_unity_object(object)?;
+        let image = converter.decode_to_image(&texture)?;
+
+        let stem = file_stem(&texture.name, "texture");
+        let taken = |name: &str| self.report.textures.iter().any(|p| p.ends_with(name));
+        let file = std::iter::once(format!("{}.png", stem))
+            .chain((1..).map(|n| format!("{}_{}.png", stem, n)))
+            .find(|name| !taken(name))
+            .unwrap_or_default();
+        let path = self.out_dir.join(&file);
+        TextureExporter::export_png(&image, &path)?;
+        self.report.textures.push(path);
+        Ok(file)
+    }
+
+    #[cfg(not(feature = "texture"))]
+    fn write_texture(&mut self, _object: &UnityObject) -> Result<String> {
+        let _ = (self.version, self.out_dir);
+        Err(BinaryError::unsupported(
+            "texture decoding requires the `texture` feature",
+        ))
+    }
+
+    fn finish(self) -> String {
+        let mut mtl = String::from("# Exported from Unity Asset Parser\n");
+        for entry in &self.entries {
+            let [r, g, b, a] = entry.diffuse;
+            mtl.push_str(&format!("\nnewmtl {}\n", entry.name));
+            mtl.push_str(&format!("Kd {} {} {}\n", r, g, b));
+            if a < 1.0 {
+                mtl.push_str(&format!("d {}\n", a));
+            }
+            if let Some(texture) = &entry.texture {
+                mtl.push_str(&format!("map_Kd {}\n", texture));
+            }
+        }
+        mtl
+    }
+}
+
+/// `(file_id, path_id)` of a non-null PPtr
+fn pptr(value: &UnityValue) -> Option<(i32, i64)> {
+    let fields = value.as_object()?;
+    let file_id = fields
+        .get("m_FileID")
+        .or_else(|| fields.get("fileID"))?
+        .as_i64()?;
+    let path_id = fields
+        .get("m_PathID")
+        .or_else(|| fields.get("pathID"))?
+        .as_i64()?;
+    (path_id != 0).then_some((file_id as i32, path_id))
+}
+
+/// `name` reduced to characters safe in file names and MTL statements
+fn file_stem(name: &str, fallback: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.trim_matches(['_', '.']).is_empty() {
+        fallback.to_string()
+    } else {
+        stem
+    }
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<()> {
+    std::fs::write(path, bytes)
+        .map_err(|e| BinaryError::generic(format!("Failed to write {}: {}", path.display(), e)))
+}
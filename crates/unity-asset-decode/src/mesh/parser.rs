@@ -3,11 +3,15 @@
 //! This module provides the main parsing logic for Unity Mesh objects.
 
 use super::types::*;
-use crate::error::Result;
+use crate::bundle::{AssetBundle, normalize_stream_path};
+use crate::error::{BinaryError, Result};
 use crate::object::UnityObject;
 use crate::reader::BinaryReader;
 use crate::unity_version::UnityVersion;
 use indexmap::IndexMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
 use unity_asset_core::UnityValue;
 
 /// Mesh parser
@@ -16,12 +20,23 @@ use unity_asset_core::UnityValue;
 /// various data sources including TypeTree and binary data.
 pub struct MeshParser {
     version: UnityVersion,
+    decompress: bool,
 }
 
 impl MeshParser {
     /// Create a new mesh parser
     pub fn new(version: UnityVersion) -> Self {
-        Self { version }
+        Self {
+            version,
+            decompress: true,
+        }
+    }
+
+    /// Set whether `m_CompressedMesh` is unpacked into vertex data (see [`Mesh::decompress`]);
+    /// on by default.
+    pub fn with_decompress(mut self, decompress: bool) -> Self {
+        self.decompress = decompress;
+        self
     }
 
     /// Parse Mesh from UnityObject
@@ -57,6 +72,11 @@ impl MeshParser {
             self.extract_index_buffer(&mut mesh, index_buffer_value)?;
         }
 
+        // Extract index format (2017.3+; 16-bit before)
+        if let Some(UnityValue::Integer(index_format)) = properties.get("m_IndexFormat") {
+            mesh.index_format = *index_format as i32;
+        }
+
         // Extract readable flag
         if let Some(UnityValue::Bool(is_readable)) = properties.get("m_IsReadable") {
             mesh.is_readable = *is_readable;
@@ -87,6 +107,17 @@ impl MeshParser {
             self.extract_bind_poses(&mut mesh, bind_poses_value)?;
         }
 
+        // Extract compressed mesh (empty unless saved with mesh compression)
+        if let Some(UnityValue::Object(compressed_obj)) = properties.get("m_CompressedMesh") {
+            let compressed = Self::extract_compressed_mesh(compressed_obj);
+            if !compressed.is_empty() {
+                mesh.compressed_mesh = Some(compressed);
+                if self.decompress {
+                    mesh.decompress(&self.version)?;
+                }
+            }
+        }
+
         Ok(mesh)
     }
 
@@ -141,6 +172,17 @@ impl MeshParser {
                     {
                         sub_mesh.triangle_count = *triangle_count as u32;
                     }
+                    if let Some(UnityValue::Integer(base_vertex)) = sub_mesh_obj.get("baseVertex") {
+                        sub_mesh.base_vertex = *base_vertex as u32;
+                    }
+                    if let Some(UnityValue::Integer(first_vertex)) = sub_mesh_obj.get("firstVertex")
+                    {
+                        sub_mesh.first_vertex = *first_vertex as u32;
+                    }
+                    if let Some(UnityValue::Integer(vertex_count)) = sub_mesh_obj.get("vertexCount")
+                    {
+                        sub_mesh.vertex_count = *vertex_count as u32;
+                    }
 
                     mesh.sub_meshes.push(sub_mesh);
                 }
@@ -305,9 +347,9 @@ impl MeshParser {
                 if let UnityValue::Object(matrix_obj) = bind_pose_value {
                     let mut matrix = [0.0f32; 16];
 
-                    // Extract matrix elements (simplified)
+                    // Row-major: `e<row><column>`
                     for (i, matrix_element) in matrix.iter_mut().enumerate() {
-                        let key = format!("e{:02}", i);
+                        let key = format!("e{}{}", i / 4, i % 4);
                         if let Some(UnityValue::Float(val)) = matrix_obj.get(&key) {
                             *matrix_element = *val as f32;
                         }
@@ -320,6 +362,151 @@ impl MeshParser {
         Ok(())
     }
 
+    /// Extract `m_CompressedMesh`
+    fn extract_compressed_mesh(obj: &IndexMap<String, UnityValue>) -> CompressedMesh {
+        let packed = |key: &str| obj.get(key).and_then(UnityValue::as_object);
+        let floats = |key: &str| packed(key).map(Self::extract_packed_floats);
+        let ints = |key: &str| packed(key).map(Self::extract_packed_ints);
+        CompressedMesh {
+            vertices: floats("m_Vertices").unwrap_or_default(),
+            uv: floats("m_UV").unwrap_or_default(),
+            normals: floats("m_Normals").unwrap_or_default(),
+            tangents: floats("m_Tangents").unwrap_or_default(),
+            weights: ints("m_Weights").unwrap_or_default(),
+            normal_signs: ints("m_NormalSigns").unwrap_or_default(),
+            tangent_signs: ints("m_TangentSigns").unwrap_or_default(),
+            float_colors: floats("m_FloatColors"),
+            bone_indices: ints("m_BoneIndices").unwrap_or_default(),
+            triangles: ints("m_Triangles").unwrap_or_default(),
+            colors: ints("m_Colors"),
+            uv_info: obj
+                .get("m_UVInfo")
+                .and_then(UnityValue::as_i64)
+                .unwrap_or_default() as u32,
+        }
+    }
+
+    fn extract_packed_floats(obj: &IndexMap<String, UnityValue>) -> PackedFloatVector {
+        let float = |key: &str| {
+            obj.get(key)
+                .and_then(UnityValue::as_f64)
+                .unwrap_or_default()
+        };
+        let ints = Self::extract_packed_ints(obj);
+        PackedFloatVector {
+            num_items: ints.num_items,
+            range: float("m_Range") as f32,
+            start: float("m_Start") as f32,
+            data: ints.data,
+            bit_size: ints.bit_size,
+        }
+    }
+
+    fn extract_packed_ints(obj: &IndexMap<String, UnityValue>) -> PackedIntVector {
+        let int = |key: &str| {
+            obj.get(key)
+                .and_then(UnityValue::as_i64)
+                .unwrap_or_default()
+        };
+        let data = match obj.get("m_Data") {
+            Some(UnityValue::Bytes(bytes)) => bytes.clone(),
+            Some(UnityValue::Array(items)) => items
+                .iter()
+                .filter_map(UnityValue::as_i64)
+                .map(|b| b as u8)
+                .collect(),
+            _ => Vec::new(),
+        };
+        PackedIntVector {
+            num_items: int("m_NumItems") as u32,
+            data,
+            bit_size: int("m_BitSize") as u8,
+        }
+    }
+
+    /// Load the vertex data a mesh keeps in a `.resS` node of `bundle` (`m_StreamData`)
+    pub fn load_streaming_data(&self, mesh: &Mesh, bundle: &AssetBundle) -> Result<Vec<u8>> {
+        let stream = Self::stream_info(mesh)?;
+        bundle.read_resource(&stream.path, stream.offset, stream.size as u64)
+    }
+
+    /// Load the vertex data of a mesh from a `.resS` file next to the `.assets` file at `asset_path`
+    ///
+    /// Tries the stream path relative to the asset's directory, then its bare file name.
+    pub fn load_streaming_data_from_file(&self, mesh: &Mesh, asset_path: &Path) -> Result<Vec<u8>> {
+        let stream = Self::stream_info(mesh)?;
+        let dir = asset_path.parent().unwrap_or_else(|| Path::new("."));
+        let normalized = normalize_stream_path(&stream.path);
+        let mut candidates = vec![dir.join(&normalized)];
+        if let Some(file_name) = Path::new(&normalized).file_name() {
+            candidates.push(dir.join(file_name));
+        }
+
+        for path in &candidates {
+            let Ok(mut file) = File::open(path) else {
+                continue;
+            };
+            let mut buffer = vec![0u8; stream.size as usize];
+            file.seek(SeekFrom::Start(stream.offset))?;
+            file.read_exact(&mut buffer).map_err(|e| {
+                BinaryError::invalid_data(format!(
+                    "Stream range {}+{} of {} is out of bounds: {}",
+                    stream.offset,
+                    stream.size,
+                    path.display(),
+                    e
+                ))
+            })?;
+            return Ok(buffer);
+        }
+
+        Err(BinaryError::generic(format!(
+            "Could not find streaming file {} next to {}",
+            stream.path,
+            asset_path.display()
+        )))
+    }
+
+    /// Fill in streamed vertex data, preferring `bundle` and falling back to files next to
+    /// `asset_path`
+    ///
+    /// Returns `false` when the mesh is not streamed or already has its vertex data.
+    pub fn resolve_streaming_data(
+        &self,
+        mesh: &mut Mesh,
+        bundle: Option<&AssetBundle>,
+        asset_path: Option<&Path>,
+    ) -> Result<bool> {
+        if !mesh.has_streaming_data() || !mesh.vertex_data.data_size.is_empty() {
+            return Ok(false);
+        }
+
+        let data = match (bundle, asset_path) {
+            (Some(bundle), None) => self.load_streaming_data(mesh, bundle)?,
+            (None, Some(path)) => self.load_streaming_data_from_file(mesh, path)?,
+            (Some(bundle), Some(path)) => self
+                .load_streaming_data(mesh, bundle)
+                .or_else(|_| self.load_streaming_data_from_file(mesh, path))?,
+            (None, None) => {
+                return Err(BinaryError::invalid_data(format!(
+                    "Mesh {:?} is streamed but no bundle or asset path was given",
+                    mesh.name
+                )));
+            }
+        };
+        mesh.vertex_data.data_size = data;
+        Ok(true)
+    }
+
+    fn stream_info(mesh: &Mesh) -> Result<&StreamingInfo> {
+        mesh.stream_data
+            .as_ref()
+            .filter(|s| !s.path.is_empty())
+            .ok_or_else(|| {
+                BinaryError::invalid_data(format!("Mesh {:?} has no streaming path", mesh.name))
+            })
+    }
+
     /// Get the Unity version
     pub fn version(&self) -> &UnityVersion {
         &self.version
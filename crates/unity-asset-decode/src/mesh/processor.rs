@@ -3,11 +3,21 @@
 //! This module provides high-level mesh processing functionality including
 //! mesh export and optimization.
 
+#[cfg(feature = "mesh-export")]
+use super::gltf::GltfOptions;
+#[cfg(feature = "mesh-export")]
+use super::obj::{ObjAssetResolver, ObjExportReport, ObjMaterials};
 use super::parser::MeshParser;
 use super::types::*;
+#[cfg(feature = "mesh-export")]
+use super::uv_layout::UvLayoutOptions;
+use super::vertex::uv_channel_index;
+use crate::bundle::AssetBundle;
 use crate::error::Result;
 use crate::object::UnityObject;
 use crate::unity_version::UnityVersion;
+use std::borrow::Cow;
+use std::path::Path;
 
 /// Mesh processor
 ///
@@ -30,7 +40,7 @@ impl MeshProcessor {
     /// Create a Mesh processor with custom configuration
     pub fn with_config(version: UnityVersion, config: MeshConfig) -> Self {
         Self {
-            parser: MeshParser::new(version),
+            parser: MeshParser::new(version).with_decompress(config.decompress_meshes),
             config,
         }
     }
@@ -58,6 +68,27 @@ impl MeshProcessor {
         Ok(result)
     }
 
+    /// Parse Mesh from Unity object and load vertex data streamed into a `.resS` resource
+    ///
+    /// The resource is looked up in `bundle` first, then next to `asset_path` (for bare
+    /// `.assets` + `.resS` pairs). A failed lookup is recorded as a warning; the mesh is still
+    /// returned without vertex data.
+    pub fn parse_mesh_with_resources(
+        &self,
+        object: &UnityObject,
+        bundle: Option<&AssetBundle>,
+        asset_path: Option<&Path>,
+    ) -> Result<MeshResult> {
+        let mut result = self.parse_mesh(object)?;
+        if let Err(e) = self
+            .parser
+            .resolve_streaming_data(&mut result.mesh, bundle, asset_path)
+        {
+            result.add_warning(format!("Failed to load streamed vertex data: {}", e));
+        }
+        Ok(result)
+    }
+
     /// Validate mesh data
     pub fn validate_mesh(&self, mesh: &Mesh) -> Result<()> {
         // Check basic validity
@@ -96,65 +127,141 @@ impl MeshProcessor {
     }
 
     /// Export mesh to OBJ format
+    ///
+    /// Runs [`MeshConfig::cleanup`] on a copy first. X is mirrored and the triangle winding
+    /// reversed for OBJ's right-handed convention, and each submesh becomes a `g` group.
+    /// Fails when the vertex data is not available (streamed without its resource, or
+    /// compressed).
     pub fn export_to_obj(&self, mesh: &Mesh) -> Result<String> {
-        let mut obj_data = String::new();
+        self.obj_text(mesh, None)
+    }
+
+    /// Export mesh to OBJ with a sibling `.mtl` and its textures as PNGs in `out_dir`
+    ///
+    /// See [`export_to_obj_with_materials`](super::obj::export_to_obj_with_materials).
+    #[cfg(feature = "mesh-export")]
+    pub fn export_to_obj_with_materials(
+        &self,
+        mesh: &Mesh,
+        materials: ObjMaterials<'_>,
+        texture_resolver: &dyn ObjAssetResolver,
+        out_dir: &Path,
+    ) -> Result<ObjExportReport> {
+        super::obj::export_with_processor(self, mesh, materials, texture_resolver, out_dir)
+    }
 
-        // Header
+    /// Export mesh to binary glTF (GLB)
+    ///
+    /// Runs [`MeshConfig::cleanup`] on a copy first. Each submesh becomes a primitive with
+    /// positions, normals, tangents, UV sets 0-3, vertex colors and, when the mesh has bind
+    /// poses, bone weights and a skin with one joint node per bind pose. Blend shape channels
+    /// become morph targets when [`MeshConfig::process_blend_shapes`] is set. Materials are not
+    /// written; see [`export_hierarchy_to_gltf`](super::export_hierarchy_to_gltf) for those.
+    #[cfg(feature = "mesh-export")]
+    pub fn export_to_gltf(&self, mesh: &Mesh) -> Result<Vec<u8>> {
+        let options = GltfOptions::default()
+            .with_materials(false)
+            .with_skins(!mesh.bind_pose.is_empty())
+            .with_blend_shapes(self.config.process_blend_shapes);
+        super::gltf::export_mesh(&*self.cleaned(mesh)?, self.version().clone(), &options)
+    }
+
+    /// `mesh` after [`MeshConfig::cleanup`], borrowed when cleanup is disabled
+    fn cleaned<'m>(&self, mesh: &'m Mesh) -> Result<Cow<'m, Mesh>> {
+        if !self.config.cleanup.is_enabled() {
+            return Ok(Cow::Borrowed(mesh));
+        }
+        let mut cleaned = mesh.clone();
+        self.config.cleanup.apply(&mut cleaned, self.version())?;
+        Ok(Cow::Owned(cleaned))
+    }
+
+    /// OBJ text for `mesh`; with `materials`, a `mtllib` line and one `usemtl` per submesh
+    pub(super) fn obj_text(
+        &self,
+        mesh: &Mesh,
+        materials: Option<(&str, &[String])>,
+    ) -> Result<String> {
+        let mesh = &*self.cleaned(mesh)?;
+        let positions = self.extract_vertex_positions(mesh)?;
+        let normals = self.extract_vertex_normals(mesh)?;
+        let uvs = self.extract_uv_coordinates(mesh)?;
+        let triangles = mesh.submesh_triangles()?;
+
+        let mut obj_data = String::new();
         obj_data.push_str("# Exported from Unity Asset Parser\n");
         obj_data.push_str(&format!("# Mesh: {}\n", mesh.name));
-        obj_data.push_str(&format!("# Vertices: {}\n", mesh.vertex_count()));
+        obj_data.push_str(&format!("# Vertices: {}\n", positions.len()));
         obj_data.push_str(&format!("# SubMeshes: {}\n", mesh.sub_meshes.len()));
-        obj_data.push('\n');
-
-        // Export vertices (placeholder - would need actual vertex data parsing)
-        obj_data.push_str("# Vertices\n");
-        for _i in 0..mesh.vertex_count() {
-            obj_data.push_str("v 0.0 0.0 0.0\n");
+        if let Some((mtllib, _)) = materials {
+            obj_data.push_str(&format!("mtllib {}\n", mtllib));
         }
+        obj_data.push_str(&format!("o {}\n", mesh.name));
 
-        // Export normals (placeholder)
-        obj_data.push_str("\n# Normals\n");
-        for _i in 0..mesh.vertex_count() {
-            obj_data.push_str("vn 0.0 1.0 0.0\n");
+        for [x, y, z] in &positions {
+            obj_data.push_str(&format!("v {} {} {}\n", -x, y, z));
         }
-
-        // Export UV coordinates (placeholder)
-        obj_data.push_str("\n# UV Coordinates\n");
-        for _i in 0..mesh.vertex_count() {
-            obj_data.push_str("vt 0.0 0.0\n");
+        for [u, v] in &uvs {
+            obj_data.push_str(&format!("vt {} {}\n", u, v));
+        }
+        for [x, y, z] in &normals {
+            obj_data.push_str(&format!("vn {} {} {}\n", -x, y, z));
         }
 
-        // Export faces (placeholder)
-        obj_data.push_str("\n# Faces\n");
-        if !mesh.sub_meshes.is_empty() {
-            for (i, sub_mesh) in mesh.sub_meshes.iter().enumerate() {
-                obj_data.push_str(&format!(
-                    "# SubMesh {}: {} triangles\n",
-                    i, sub_mesh.triangle_count
-                ));
-
-                // Would need to parse actual index data here
-                for j in 0..sub_mesh.triangle_count {
-                    let base = j * 3 + 1; // OBJ indices are 1-based
-                    obj_data.push_str(&format!(
-                        "f {}/{}/{} {}/{}/{} {}/{}/{}\n",
-                        base,
-                        base,
-                        base,
-                        base + 1,
-                        base + 1,
-                        base + 1,
-                        base + 2,
-                        base + 2,
-                        base + 2
-                    ));
+        let corner = |i: u32| {
+            let i = i + 1; // OBJ indices are 1-based
+            match (uvs.is_empty(), normals.is_empty()) {
+                (false, false) => format!("{i}/{i}/{i}"),
+                (false, true) => format!("{i}/{i}"),
+                (true, false) => format!("{i}//{i}"),
+                (true, true) => i.to_string(),
+            }
+        };
+        for (i, tris) in triangles.iter().enumerate() {
+            obj_data.push_str(&format!("g submesh_{}\n", i));
+            if let Some(name) = materials.and_then(|(_, names)| names.get(i)) {
+                obj_data.push_str(&format!("usemtl {}\n", name));
+            }
+            for &[a, b, c] in tris {
+                if [a, b, c].iter().any(|&v| v as usize >= positions.len()) {
+                    return Err(crate::error::BinaryError::invalid_data(format!(
+                        "Mesh {:?} index out of range ({} vertices)",
+                        mesh.name,
+                        positions.len()
+                    )));
                 }
+                obj_data.push_str(&format!("f {} {} {}\n", corner(a), corner(c), corner(b)));
             }
         }
 
         Ok(obj_data)
     }
 
+    /// Render the triangles of UV set `uv_channel` into a `size` x `size` wireframe image
+    ///
+    /// See [`MeshProcessor::render_uv_layout_with`] for fills and UVs outside 0..1.
+    #[cfg(feature = "mesh-export")]
+    pub fn render_uv_layout(
+        &self,
+        mesh: &Mesh,
+        uv_channel: usize,
+        size: u32,
+    ) -> Result<image::RgbaImage> {
+        self.render_uv_layout_with(mesh, uv_channel, size, &UvLayoutOptions::default())
+    }
+
+    /// Render a UV layout with explicit [`UvLayoutOptions`]
+    #[cfg(feature = "mesh-export")]
+    pub fn render_uv_layout_with(
+        &self,
+        mesh: &Mesh,
+        uv_channel: usize,
+        size: u32,
+        options: &UvLayoutOptions,
+    ) -> Result<image::RgbaImage> {
+        super::uv_layout::render_uv_layout(mesh, self.version(), uv_channel, size, options)
+    }
+
     /// Get mesh statistics
     pub fn get_mesh_stats(&self, meshes: &[&Mesh]) -> MeshStats {
         let mut stats = MeshStats {
@@ -201,28 +308,21 @@ impl MeshProcessor {
     /// Get supported mesh features for this Unity version
     pub fn get_supported_features(&self) -> Vec<&'static str> {
         let version = self.parser.version();
-        let mut features = vec!["basic_mesh", "sub_meshes", "vertex_data"];
-
-        if version.major >= 5 {
-            features.push("blend_shapes");
-            features.push("compressed_mesh");
-        }
-
-        if version.major >= 2017 {
-            features.push("mesh_optimization");
-            features.push("streaming_info");
-        }
-
-        if version.major >= 2018 {
-            features.push("mesh_usage_flags");
-        }
-
-        if version.major >= 2019 {
-            features.push("mesh_topology");
-            features.push("vertex_attributes");
-        }
-
-        features
+        [
+            "basic_mesh",
+            "sub_meshes",
+            "vertex_data",
+            "blend_shapes",
+            "compressed_mesh",
+            "mesh_optimization",
+            "streaming_info",
+            "mesh_usage_flags",
+            "mesh_topology",
+            "vertex_attributes",
+        ]
+        .into_iter()
+        .filter(|feature| super::is_mesh_feature_supported(version, feature))
+        .collect()
     }
 
     /// Check if a feature is supported
@@ -237,6 +337,8 @@ impl MeshProcessor {
 
     /// Set the configuration
     pub fn set_config(&mut self, config: MeshConfig) {
+        self.parser =
+            MeshParser::new(self.version().clone()).with_decompress(config.decompress_meshes);
         self.config = config;
     }
 
@@ -250,35 +352,60 @@ impl MeshProcessor {
         self.parser.set_version(version);
     }
 
-    /// Extract vertex positions (if available)
-    pub fn extract_vertex_positions(&self, _mesh: &Mesh) -> Result<Vec<[f32; 3]>> {
-        // This would require parsing the actual vertex data
-        // For now, return empty vector
-        Ok(Vec::new())
+    /// Extract vertex positions
+    pub fn extract_vertex_positions(&self, mesh: &Mesh) -> Result<Vec<[f32; 3]>> {
+        Ok(mesh
+            .read_channel(self.version(), 0)?
+            .into_iter()
+            .map(|p| {
+                [
+                    p[0],
+                    p.get(1).copied().unwrap_or(0.0),
+                    p.get(2).copied().unwrap_or(0.0),
+                ]
+            })
+            .collect())
     }
 
-    /// Extract vertex normals (if available)
-    pub fn extract_vertex_normals(&self, _mesh: &Mesh) -> Result<Vec<[f32; 3]>> {
-        // This would require parsing the actual vertex data
-        // For now, return empty vector
-        Ok(Vec::new())
+    /// Extract vertex normals (empty when the mesh has none)
+    pub fn extract_vertex_normals(&self, mesh: &Mesh) -> Result<Vec<[f32; 3]>> {
+        if !has_channel(mesh, 1, 3) {
+            return Ok(Vec::new());
+        }
+        Ok(mesh
+            .read_channel(self.version(), 1)?
+            .into_iter()
+            .map(|n| [n[0], n[1], n[2]])
+            .collect())
     }
 
-    /// Extract UV coordinates (if available)
-    pub fn extract_uv_coordinates(&self, _mesh: &Mesh) -> Result<Vec<[f32; 2]>> {
-        // This would require parsing the actual vertex data
-        // For now, return empty vector
-        Ok(Vec::new())
+    /// Extract the first UV set (empty when the mesh has none)
+    pub fn extract_uv_coordinates(&self, mesh: &Mesh) -> Result<Vec<[f32; 2]>> {
+        let present = uv_channel_index(self.version(), 0).is_some_and(|i| has_channel(mesh, i, 2));
+        if !present {
+            return Ok(Vec::new());
+        }
+        mesh.read_uvs(self.version(), 0)
     }
 
-    /// Extract triangle indices
-    pub fn extract_triangle_indices(&self, _mesh: &Mesh) -> Result<Vec<u32>> {
-        // This would require parsing the index buffer
-        // For now, return empty vector
-        Ok(Vec::new())
+    /// Extract triangle indices of all submeshes
+    pub fn extract_triangle_indices(&self, mesh: &Mesh) -> Result<Vec<u32>> {
+        Ok(mesh
+            .submesh_triangles()?
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect())
     }
 }
 
+fn has_channel(mesh: &Mesh, index: usize, min_dimension: u8) -> bool {
+    mesh.vertex_data
+        .channels
+        .get(index)
+        .is_some_and(|c| c.dimension & 0xf >= min_dimension)
+}
+
 impl Default for MeshProcessor {
     fn default() -> Self {
         Self::new(UnityVersion::default())
@@ -287,6 +414,7 @@ impl Default for MeshProcessor {
 
 /// Mesh processing statistics
 #[derive(Debug, Clone, Default)]
+#[non_exhaustive]
 pub struct MeshStats {
     pub total_meshes: usize,
     pub total_vertices: u32,
@@ -2,12 +2,16 @@
 //!
 //! This module defines all the data structures used for Unity Mesh processing.
 
+use super::cleanup::MeshCleanup;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use unity_asset_binary::avatar::{Avatar, HumanBone};
+use unity_asset_binary::bytes_preview::{BytesPreview, LenPreview};
 
 /// Vertex data structure
 ///
 /// Contains information about vertex layout and data for a mesh.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct VertexData {
     pub vertex_count: u32,
     pub channels: Vec<ChannelInfo>,
@@ -129,7 +133,7 @@ pub struct CompressedMesh {
 /// Packed float vector for compressed data
 ///
 /// Compressed floating-point data with quantization information.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct PackedFloatVector {
     pub num_items: u32,
     pub range: f32,
@@ -141,7 +145,7 @@ pub struct PackedFloatVector {
 /// Packed int vector for compressed data
 ///
 /// Compressed integer data with bit packing.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct PackedIntVector {
     pub num_items: u32,
     pub data: Vec<u8>,
@@ -151,7 +155,7 @@ pub struct PackedIntVector {
 /// Mesh object representation
 ///
 /// Main mesh structure containing all mesh data and metadata.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Mesh {
     pub name: String,
     pub sub_meshes: Vec<SubMesh>,
@@ -202,8 +206,107 @@ impl Default for Mesh {
     }
 }
 
+impl fmt::Debug for VertexData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VertexData")
+            .field("vertex_count", &self.vertex_count)
+            .field("channels", &self.channels)
+            .field("data_size", &BytesPreview(&self.data_size))
+            .finish()
+    }
+}
+
+impl fmt::Debug for PackedFloatVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PackedFloatVector")
+            .field("num_items", &self.num_items)
+            .field("range", &self.range)
+            .field("start", &self.start)
+            .field("data", &BytesPreview(&self.data))
+            .field("bit_size", &self.bit_size)
+            .finish()
+    }
+}
+
+impl fmt::Debug for PackedIntVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PackedIntVector")
+            .field("num_items", &self.num_items)
+            .field("data", &BytesPreview(&self.data))
+            .field("bit_size", &self.bit_size)
+            .finish()
+    }
+}
+
+/// One-line summary, e.g. `Mesh "Cube" vertices=24 submeshes=1 index_buffer=72 vertex_data=1152`.
+///
+/// Streamed meshes append `streamed=<size>`.
+impl fmt::Display for Mesh {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Mesh {:?} vertices={} submeshes={} index_buffer={} vertex_data={}",
+            self.name,
+            self.vertex_data.vertex_count,
+            self.sub_meshes.len(),
+            self.index_buffer.len(),
+            self.vertex_data.data_size.len()
+        )?;
+        if let Some(stream) = &self.stream_data
+            && stream.size > 0
+        {
+            write!(f, " streamed={}", stream.size)?;
+        }
+        Ok(())
+    }
+}
+
+/// Per-vertex tables are shown as counts and byte buffers as [`BytesPreview`]s.
+impl fmt::Debug for Mesh {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mesh")
+            .field("name", &self.name)
+            .field("sub_meshes", &self.sub_meshes)
+            .field(
+                "blend_shape_data",
+                &self
+                    .blend_shape_data
+                    .as_ref()
+                    .map(|data| LenPreview(data.shapes.len(), "shapes")),
+            )
+            .field("bind_pose", &LenPreview(self.bind_pose.len(), "matrices"))
+            .field(
+                "bone_name_hashes",
+                &LenPreview(self.bone_name_hashes.len(), "hashes"),
+            )
+            .field("root_bone_name_hash", &self.root_bone_name_hash)
+            .field("mesh_compression", &self.mesh_compression)
+            .field("is_readable", &self.is_readable)
+            .field("keep_vertices", &self.keep_vertices)
+            .field("keep_indices", &self.keep_indices)
+            .field("index_format", &self.index_format)
+            .field("index_buffer", &BytesPreview(&self.index_buffer))
+            .field("vertex_data", &self.vertex_data)
+            .field("compressed_mesh", &self.compressed_mesh)
+            .field("local_aabb", &self.local_aabb)
+            .field("mesh_usage_flags", &self.mesh_usage_flags)
+            .field(
+                "baked_convex_collision_mesh",
+                &BytesPreview(&self.baked_convex_collision_mesh),
+            )
+            .field(
+                "baked_triangle_collision_mesh",
+                &BytesPreview(&self.baked_triangle_collision_mesh),
+            )
+            .field("mesh_metrics", &self.mesh_metrics)
+            .field("stream_data", &self.stream_data)
+            .finish()
+    }
+}
+
 /// Mesh processing configuration
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct MeshConfig {
     /// Whether to extract vertex data
     pub extract_vertices: bool,
@@ -215,6 +318,46 @@ pub struct MeshConfig {
     pub decompress_meshes: bool,
     /// Maximum vertex count to process
     pub max_vertex_count: Option<u32>,
+    /// Post-processing run before OBJ export
+    pub cleanup: MeshCleanup,
+}
+
+impl MeshConfig {
+    /// Set `extract_vertices`.
+    pub fn with_extract_vertices(mut self, extract_vertices: bool) -> Self {
+        self.extract_vertices = extract_vertices;
+        self
+    }
+
+    /// Set `extract_indices`.
+    pub fn with_extract_indices(mut self, extract_indices: bool) -> Self {
+        self.extract_indices = extract_indices;
+        self
+    }
+
+    /// Set `process_blend_shapes`.
+    pub fn with_process_blend_shapes(mut self, process_blend_shapes: bool) -> Self {
+        self.process_blend_shapes = process_blend_shapes;
+        self
+    }
+
+    /// Set `decompress_meshes`.
+    pub fn with_decompress_meshes(mut self, decompress_meshes: bool) -> Self {
+        self.decompress_meshes = decompress_meshes;
+        self
+    }
+
+    /// Set `max_vertex_count`.
+    pub fn with_max_vertex_count(mut self, max_vertex_count: Option<u32>) -> Self {
+        self.max_vertex_count = max_vertex_count;
+        self
+    }
+
+    /// Set `cleanup`.
+    pub fn with_cleanup(mut self, cleanup: MeshCleanup) -> Self {
+        self.cleanup = cleanup;
+        self
+    }
 }
 
 impl Default for MeshConfig {
@@ -225,6 +368,7 @@ impl Default for MeshConfig {
             process_blend_shapes: true,
             decompress_meshes: true,
             max_vertex_count: None,
+            cleanup: MeshCleanup::default(),
         }
     }
 }
@@ -308,6 +452,37 @@ impl Mesh {
         &self.local_aabb
     }
 
+    /// Transform path of each bone (`m_BoneNameHashes`), resolved through an Avatar's `m_TOS`
+    pub fn bone_paths<'a>(&self, avatar: &'a Avatar) -> Vec<Option<&'a str>> {
+        self.bone_name_hashes
+            .iter()
+            .map(|&hash| avatar.path_for_hash(hash))
+            .collect()
+    }
+
+    /// Standard human bone of each bone, for humanoid avatars
+    pub fn human_bones(&self, avatar: &Avatar) -> Vec<Option<HumanBone>> {
+        self.bone_name_hashes
+            .iter()
+            .map(|&hash| avatar.human_bone_for_hash(hash))
+            .collect()
+    }
+
+    /// Joint names for skinning export: the human bone name when mapped, else the transform
+    /// name, else the raw hash
+    pub fn joint_names(&self, avatar: &Avatar) -> Vec<String> {
+        self.bone_name_hashes
+            .iter()
+            .map(|&hash| match avatar.human_bone_for_hash(hash) {
+                Some(bone) => bone.name().to_string(),
+                None => avatar
+                    .path_for_hash(hash)
+                    .map(|path| path.rsplit('/').next().unwrap_or(path).to_string())
+                    .unwrap_or_else(|| hash.to_string()),
+            })
+            .collect()
+    }
+
     /// Get mesh information summary
     pub fn get_info(&self) -> MeshInfo {
         MeshInfo {
@@ -0,0 +1,251 @@
+//! UV layout rendering
+//!
+//! Rasterizes the triangles of one UV set into an image for atlas debugging: triangle edges as
+//! one-pixel lines, optionally over translucent fills colored per submesh. V points up, so the
+//! image matches how the layout looks over the texture.
+
+use super::types::Mesh;
+use crate::error::{BinaryError, Result};
+use crate::unity_version::UnityVersion;
+use image::{Rgba, RgbaImage};
+
+/// Largest number of 0..1 tiles per axis [`UvOverflow::Expand`] will allocate.
+pub const MAX_EXPAND_TILES: u32 = 16;
+
+/// Fill colors cycled through by submesh index.
+const SUBMESH_COLORS: [[u8; 3]; 8] = [
+    [230, 80, 80],
+    [80, 170, 230],
+    [110, 200, 90],
+    [240, 190, 60],
+    [170, 110, 220],
+    [60, 200, 190],
+    [240, 130, 40],
+    [200, 200, 200],
+];
+const FILL_ALPHA: u8 = 96;
+
+/// What to do with UVs outside 0..1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UvOverflow {
+    /// Shift each triangle into the 0..1 tile and wrap pixels around the edges (tiling UVs)
+    #[default]
+    Wrap,
+    /// Grow the canvas by whole `size` x `size` tiles until every UV fits
+    Expand,
+}
+
+/// UV layout rendering options
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct UvLayoutOptions {
+    pub overflow: UvOverflow,
+    /// Fill triangles with a translucent per-submesh color under the wireframe
+    pub fill: bool,
+    pub line_color: [u8; 4],
+}
+
+impl UvLayoutOptions {
+    /// Set `overflow`.
+    pub fn with_overflow(mut self, overflow: UvOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Set `fill`.
+    pub fn with_fill(mut self, fill: bool) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    /// Set `line_color`.
+    pub fn with_line_color(mut self, line_color: [u8; 4]) -> Self {
+        self.line_color = line_color;
+        self
+    }
+}
+
+impl Default for UvLayoutOptions {
+    fn default() -> Self {
+        Self {
+            overflow: UvOverflow::default(),
+            fill: false,
+            line_color: [255, 255, 255, 255],
+        }
+    }
+}
+
+/// Render UV set `uv_channel` of `mesh` with `size` pixels per 0..1 tile
+///
+/// Errors when the UV set is missing, an index points past the vertex data, or a triangle
+/// repeats a vertex.
+pub fn render_uv_layout(
+    mesh: &Mesh,
+    version: &UnityVersion,
+    uv_channel: usize,
+    size: u32,
+    options: &UvLayoutOptions,
+) -> Result<RgbaImage> {
+    if size < 2 {
+        return Err(BinaryError::invalid_data(
+            "UV layout size must be at least 2",
+        ));
+    }
+    let uvs = mesh.read_uvs(version, uv_channel)?;
+    let submeshes = mesh.submesh_triangles()?;
+
+    let mut triangles = Vec::new();
+    for (submesh, tris) in submeshes.iter().enumerate() {
+        for (t, tri) in tris.iter().enumerate() {
+            if let Some(&bad) = tri.iter().find(|&&i| i as usize >= uvs.len()) {
+                return Err(BinaryError::invalid_data(format!(
+                    "Submesh {} triangle {} uses vertex {} but mesh {:?} has {} vertices",
+                    submesh,
+                    t,
+                    bad,
+                    mesh.name,
+                    uvs.len()
+                )));
+            }
+            if tri[0] == tri[1] || tri[1] == tri[2] || tri[0] == tri[2] {
+                return Err(BinaryError::invalid_data(format!(
+                    "Submesh {} triangle {} is degenerate (vertices {:?})",
+                    submesh, t, tri
+                )));
+            }
+            triangles.push((submesh, tri.map(|i| uvs[i as usize])));
+        }
+    }
+
+    // Tile range covered by the canvas, in UV units.
+    let (origin, tiles) = match options.overflow {
+        UvOverflow::Wrap => ([0.0, 0.0], [1, 1]),
+        UvOverflow::Expand => {
+            let mut min = [0.0f32, 0.0];
+            let mut max = [1.0f32, 1.0];
+            for uv in triangles.iter().flat_map(|(_, tri)| tri) {
+                for axis in 0..2 {
+                    min[axis] = min[axis].min(uv[axis].floor());
+                    max[axis] = max[axis].max(uv[axis].ceil());
+                }
+            }
+            let tiles = [(max[0] - min[0]) as u32, (max[1] - min[1]) as u32];
+            if tiles[0] > MAX_EXPAND_TILES || tiles[1] > MAX_EXPAND_TILES {
+                return Err(BinaryError::invalid_data(format!(
+                    "UVs span {}x{} tiles (limit {}); use wrapping instead",
+                    tiles[0], tiles[1], MAX_EXPAND_TILES
+                )));
+            }
+            (min, tiles)
+        }
+    };
+    let mut canvas = Canvas {
+        image: RgbaImage::new(size * tiles[0], size * tiles[1]),
+        wrap: options.overflow == UvOverflow::Wrap,
+    };
+    let (width, height) = canvas.image.dimensions();
+    let to_pixel = |uv: [f32; 2]| {
+        [
+            (uv[0] - origin[0]) * (width - 1) as f32 / tiles[0] as f32,
+            (origin[1] + tiles[1] as f32 - uv[1]) * (height - 1) as f32 / tiles[1] as f32,
+        ]
+    };
+    let placed: Vec<(usize, [[f32; 2]; 3])> = triangles
+        .iter()
+        .map(|(submesh, tri)| {
+            let tri = match options.overflow {
+                // Move the triangle so its centroid lands in the 0..1 tile.
+                UvOverflow::Wrap => {
+                    let shift = [0, 1]
+                        .map(|axis| (tri.iter().map(|uv| uv[axis]).sum::<f32>() / 3.0).floor());
+                    tri.map(|uv| [uv[0] - shift[0], uv[1] - shift[1]])
+                }
+                UvOverflow::Expand => *tri,
+            };
+            (*submesh, tri.map(to_pixel))
+        })
+        .collect();
+
+    if options.fill {
+        for (submesh, tri) in &placed {
+            let [r, g, b] = SUBMESH_COLORS[submesh % SUBMESH_COLORS.len()];
+            canvas.fill_triangle(tri, Rgba([r, g, b, FILL_ALPHA]));
+        }
+    }
+    let line = Rgba(options.line_color);
+    for (_, tri) in &placed {
+        for edge in 0..3 {
+            canvas.line(tri[edge], tri[(edge + 1) % 3], line);
+        }
+    }
+    Ok(canvas.image)
+}
+
+struct Canvas {
+    image: RgbaImage,
+    wrap: bool,
+}
+
+impl Canvas {
+    fn plot(&mut self, x: i64, y: i64, color: Rgba<u8>) {
+        let (width, height) = (self.image.width() as i64, self.image.height() as i64);
+        let (x, y) = if self.wrap {
+            (x.rem_euclid(width), y.rem_euclid(height))
+        } else {
+            (x.clamp(0, width - 1), y.clamp(0, height - 1))
+        };
+        self.image.put_pixel(x as u32, y as u32, color);
+    }
+
+    /// Bresenham line between two pixel positions
+    fn line(&mut self, from: [f32; 2], to: [f32; 2], color: Rgba<u8>) {
+        let (mut x, mut y) = (from[0].round() as i64, from[1].round() as i64);
+        let (x1, y1) = (to[0].round() as i64, to[1].round() as i64);
+        let (dx, dy) = ((x1 - x).abs(), -(y1 - y).abs());
+        let (sx, sy) = ((x1 - x).signum(), (y1 - y).signum());
+        let mut error = dx + dy;
+        loop {
+            self.plot(x, y, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += sx;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Fill the pixels whose centers lie inside the triangle (edges inclusive)
+    fn fill_triangle(&mut self, tri: &[[f32; 2]; 3], color: Rgba<u8>) {
+        let edge = |a: [f32; 2], b: [f32; 2], p: [f32; 2]| {
+            (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+        };
+        let area = edge(tri[0], tri[1], tri[2]);
+        if area == 0.0 {
+            return;
+        }
+        let min_x = tri.iter().map(|p| p[0]).fold(f32::MAX, f32::min).floor() as i64;
+        let max_x = tri.iter().map(|p| p[0]).fold(f32::MIN, f32::max).ceil() as i64;
+        let min_y = tri.iter().map(|p| p[1]).fold(f32::MAX, f32::min).floor() as i64;
+        let max_y = tri.iter().map(|p| p[1]).fold(f32::MIN, f32::max).ceil() as i64;
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let p = [x as f32, y as f32];
+                let w = [
+                    edge(tri[1], tri[2], p),
+                    edge(tri[2], tri[0], p),
+                    edge(tri[0], tri[1], p),
+                ];
+                if w.iter().all(|w| w * area.signum() >= 0.0) {
+                    self.plot(x, y, color);
+                }
+            }
+        }
+    }
+}
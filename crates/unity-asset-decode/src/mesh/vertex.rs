@@ -0,0 +1,306 @@
+//! Vertex and index buffer access
+//!
+//! Reads attributes out of `m_VertexData` using the channel table: channels in the same stream
+//! are interleaved, streams follow each other aligned to 16 bytes. Attribute formats are
+//! normalized to `f32` (UNorm/SNorm scaled to 0..1 / -1..1).
+
+use super::types::Mesh;
+use crate::error::{BinaryError, Result};
+use crate::unity_version::{UnityVersion, VertexFormatTable};
+
+/// Component formats, independent of the version-specific `VertexFormat` numbering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ComponentFormat {
+    Float,
+    Half,
+    UNorm8,
+    SNorm8,
+    UNorm16,
+    SNorm16,
+    UInt8,
+    SInt8,
+    UInt16,
+    SInt16,
+    UInt32,
+    SInt32,
+}
+
+impl ComponentFormat {
+    pub(super) fn from_raw(format: u8, version: &UnityVersion) -> Option<Self> {
+        use ComponentFormat::*;
+        // The enum gained `Color` in 5.x/2017 and dropped it again in 2019.
+        let table: &[Self] = match version.capabilities().vertex_format {
+            VertexFormatTable::V2019 => &[
+                Float, Half, UNorm8, SNorm8, UNorm16, SNorm16, UInt8, SInt8, UInt16, SInt16,
+                UInt32, SInt32,
+            ],
+            VertexFormatTable::V2017 => &[
+                Float, Half, UNorm8, UNorm8, SNorm8, UNorm16, SNorm16, UInt8, SInt8, UInt16,
+                SInt16, UInt32, SInt32,
+            ],
+            VertexFormatTable::Legacy => &[Float, Half, UNorm8, UInt8, UInt32],
+        };
+        table.get(format as usize).copied()
+    }
+
+    pub(super) fn size(self) -> usize {
+        use ComponentFormat::*;
+        match self {
+            Float | UInt32 | SInt32 => 4,
+            Half | UNorm16 | SNorm16 | UInt16 | SInt16 => 2,
+            UNorm8 | SNorm8 | UInt8 | SInt8 => 1,
+        }
+    }
+
+    fn read(self, bytes: &[u8]) -> f32 {
+        use ComponentFormat::*;
+        let u16_at = || u16::from_le_bytes([bytes[0], bytes[1]]);
+        let u32_at = || u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        match self {
+            Float => f32::from_bits(u32_at()),
+            Half => half_to_f32(u16_at()),
+            UNorm8 => bytes[0] as f32 / 255.0,
+            SNorm8 => (bytes[0] as i8 as f32 / 127.0).max(-1.0),
+            UNorm16 => u16_at() as f32 / 65535.0,
+            SNorm16 => (u16_at() as i16 as f32 / 32767.0).max(-1.0),
+            UInt8 => bytes[0] as f32,
+            SInt8 => bytes[0] as i8 as f32,
+            UInt16 => u16_at() as f32,
+            SInt16 => u16_at() as i16 as f32,
+            UInt32 => u32_at() as f32,
+            SInt32 => u32_at() as i32 as f32,
+        }
+    }
+
+    /// Inverse of [`ComponentFormat::read`]; `out` holds [`ComponentFormat::size`] bytes
+    pub(super) fn write(self, value: f32, out: &mut [u8]) {
+        use ComponentFormat::*;
+        let unorm = |max: f32| (value.clamp(0.0, 1.0) * max).round();
+        let snorm = |max: f32| (value.clamp(-1.0, 1.0) * max).round();
+        match self {
+            Float => out.copy_from_slice(&value.to_le_bytes()),
+            Half => out.copy_from_slice(&f32_to_half(value).to_le_bytes()),
+            UNorm8 => out[0] = unorm(255.0) as u8,
+            SNorm8 => out[0] = snorm(127.0) as i8 as u8,
+            UNorm16 => out.copy_from_slice(&(unorm(65535.0) as u16).to_le_bytes()),
+            SNorm16 => out.copy_from_slice(&(snorm(32767.0) as i16).to_le_bytes()),
+            UInt8 => out[0] = value.round() as u8,
+            SInt8 => out[0] = value.round() as i8 as u8,
+            UInt16 => out.copy_from_slice(&(value.round() as u16).to_le_bytes()),
+            SInt16 => out.copy_from_slice(&(value.round() as i16).to_le_bytes()),
+            UInt32 => out.copy_from_slice(&(value.round() as u32).to_le_bytes()),
+            SInt32 => out.copy_from_slice(&(value.round() as i32).to_le_bytes()),
+        }
+    }
+}
+
+/// Index into `m_Channels` of UV set `uv` (0-based), if the version has that many UV sets
+pub fn uv_channel_index(version: &UnityVersion, uv: usize) -> Option<usize> {
+    let caps = version.capabilities();
+    if uv >= caps.mesh_uv_sets {
+        return None;
+    }
+    if caps.vertex_channels_2018 {
+        // Position, normal, tangent, color, UV0..UV7, weights, indices
+        Some(4 + uv)
+    } else {
+        // Position, normal, color, UV0..UV3 (UV0, UV1 before 5.0), tangent
+        Some(3 + uv)
+    }
+}
+
+/// Index into `m_Channels` of the vertex color channel
+pub(super) fn color_channel_index(version: &UnityVersion) -> usize {
+    if version.capabilities().vertex_channels_2018 {
+        // Position, normal, tangent, color, ...
+        3
+    } else {
+        // Position, normal, color, ...
+        2
+    }
+}
+
+/// Index into `m_Channels` of the tangent channel
+pub(super) fn tangent_channel_index(version: &UnityVersion) -> usize {
+    let caps = version.capabilities();
+    if caps.vertex_channels_2018 {
+        // Position, normal, tangent, ...
+        2
+    } else {
+        // Position, normal, color, UV sets, tangent
+        3 + caps.mesh_uv_sets
+    }
+}
+
+impl Mesh {
+    /// Read channel `index` of `m_VertexData`, one `Vec` of components per vertex
+    ///
+    /// Errors when the channel is absent or empty, or the vertex data is too short for it
+    /// (e.g. because it is streamed into a `.resS` file that has not been loaded).
+    pub fn read_channel(&self, version: &UnityVersion, index: usize) -> Result<Vec<Vec<f32>>> {
+        let vertex_data = &self.vertex_data;
+        let channel = vertex_data
+            .channels
+            .get(index)
+            .filter(|c| c.dimension & 0xf > 0)
+            .ok_or_else(|| {
+                BinaryError::invalid_data(format!(
+                    "Mesh {:?} has no vertex channel {}",
+                    self.name, index
+                ))
+            })?;
+        if vertex_data.data_size.is_empty() {
+            return Err(BinaryError::invalid_data(format!(
+                "Mesh {:?} has no vertex data (streamed or compressed)",
+                self.name
+            )));
+        }
+        let format = ComponentFormat::from_raw(channel.format, version)
+            .ok_or_else(|| BinaryError::unsupported(format!("Vertex format {}", channel.format)))?;
+
+        let vertex_count = vertex_data.vertex_count as usize;
+        let (stream_start, stride) = self.stream_layout(version)[channel.stream as usize];
+        let dimension = (channel.dimension & 0xf) as usize;
+        let size = format.size();
+
+        (0..vertex_count)
+            .map(|v| {
+                let base = stream_start + v * stride + channel.offset as usize;
+                let bytes = vertex_data
+                    .data_size
+                    .get(base..base + dimension * size)
+                    .ok_or_else(|| {
+                        BinaryError::invalid_data(format!(
+                            "Vertex data of mesh {:?} ends before vertex {} of channel {}",
+                            self.name, v, index
+                        ))
+                    })?;
+                Ok(bytes.chunks_exact(size).map(|c| format.read(c)).collect())
+            })
+            .collect()
+    }
+
+    /// `(start, stride)` in `m_VertexData` of each stream up to the last one a channel uses
+    pub(super) fn stream_layout(&self, version: &UnityVersion) -> Vec<(usize, usize)> {
+        let channels = &self.vertex_data.channels;
+        let stride_of = |stream: u8| -> usize {
+            channels
+                .iter()
+                .filter(|c| c.stream == stream && c.dimension & 0xf > 0)
+                .map(|c| {
+                    let size = ComponentFormat::from_raw(c.format, version).map_or(4, |f| f.size());
+                    (c.dimension & 0xf) as usize * size
+                })
+                .sum()
+        };
+        let streams = channels.iter().map(|c| c.stream).max().map_or(0, |s| s + 1);
+        let vertex_count = self.vertex_data.vertex_count as usize;
+        let mut layout = Vec::with_capacity(streams as usize);
+        let mut start = 0usize;
+        for stream in 0..streams {
+            let stride = stride_of(stream);
+            layout.push((start, stride));
+            start = (start + vertex_count * stride).next_multiple_of(16);
+        }
+        layout
+    }
+
+    /// UV set `uv` (0-based) as `[u, v]` pairs
+    pub fn read_uvs(&self, version: &UnityVersion, uv: usize) -> Result<Vec<[f32; 2]>> {
+        let index = uv_channel_index(version, uv).ok_or_else(|| {
+            BinaryError::invalid_data(format!("Unity {} has no UV channel {}", version, uv))
+        })?;
+        let present = self
+            .vertex_data
+            .channels
+            .get(index)
+            .is_some_and(|c| c.dimension & 0xf >= 2);
+        if !present {
+            return Err(BinaryError::invalid_data(format!(
+                "Mesh {:?} has no UV channel {}",
+                self.name, uv
+            )));
+        }
+        Ok(self
+            .read_channel(version, index)?
+            .into_iter()
+            .map(|c| [c[0], c[1]])
+            .collect())
+    }
+
+    /// Triangles of each submesh as vertex indices (`baseVertex` applied)
+    ///
+    /// Quads are split in two; line and point submeshes yield no triangles.
+    pub fn submesh_triangles(&self) -> Result<Vec<Vec<[u32; 3]>>> {
+        let index_size = if self.index_format == 1 { 4 } else { 2 };
+        self.sub_meshes
+            .iter()
+            .enumerate()
+            .map(|(i, sub_mesh)| {
+                let start = sub_mesh.first_byte as usize;
+                let end = start + sub_mesh.index_count as usize * index_size;
+                let bytes = self.index_buffer.get(start..end).ok_or_else(|| {
+                    BinaryError::invalid_data(format!(
+                        "Index buffer of mesh {:?} ends before submesh {}",
+                        self.name, i
+                    ))
+                })?;
+                let indices: Vec<u32> = bytes
+                    .chunks_exact(index_size)
+                    .map(|c| match c {
+                        [a, b] => u16::from_le_bytes([*a, *b]) as u32,
+                        _ => u32::from_le_bytes([c[0], c[1], c[2], c[3]]),
+                    } + sub_mesh.base_vertex)
+                    .collect();
+                Ok(match sub_mesh.topology {
+                    0 => indices
+                        .chunks_exact(3)
+                        .map(|t| [t[0], t[1], t[2]])
+                        .collect(),
+                    2 => indices
+                        .chunks_exact(4)
+                        .flat_map(|q| [[q[0], q[1], q[2]], [q[0], q[2], q[3]]])
+                        .collect(),
+                    _ => Vec::new(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn f32_to_half(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    if value.is_nan() {
+        return sign | 0x7e00;
+    }
+    let magnitude = value.abs();
+    if magnitude >= 65520.0 {
+        return sign | 0x7c00;
+    }
+    if magnitude < 6.103_515_6e-5 {
+        // Subnormal: multiples of 2^-24
+        return sign | (magnitude * 16_777_216.0).round() as u16;
+    }
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127 + 15;
+    let mantissa = bits & 0x7f_ffff;
+    let mut half = ((exponent as u32) << 10) | (mantissa >> 13);
+    // Round to nearest, ties to even; a carry into the exponent is still correct.
+    let rest = mantissa & 0x1fff;
+    if rest > 0x1000 || (rest == 0x1000 && half & 1 == 1) {
+        half += 1;
+    }
+    sign | half as u16
+}
+
+fn half_to_f32(h: u16) -> f32 {
+    let sign = if h & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((h >> 10) & 0x1f) as i32;
+    let mantissa = (h & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        31 if mantissa == 0.0 => sign * f32::INFINITY,
+        31 => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
@@ -0,0 +1,425 @@
+//! `.meta` files for extracted assets.
+//!
+//! Unity keys every reference to an asset by the GUID in its `.meta` file, so an extracted file
+//! dropped into a project without one gets a fresh GUID and nothing that pointed at the original
+//! resolves. [`MetaGenerator`] builds the `.meta` next to an export: the GUID is supplied by the
+//! caller (or [derived](UnityGuid::derived) from the source bundle and PathID), and the importer
+//! settings are taken from the source object so the file re-imports close to how it was built.
+//!
+//! Only the settings that can be recovered from the built object are written; the editor fills
+//! in defaults for everything else on import.
+//!
+//! ```rust,no_run
+//! use unity_asset_core::UnityGuid;
+//! use unity_asset_decode::meta::MetaGenerator;
+//!
+//! let guid = UnityGuid::derived("textassets.ab", 42);
+//! let meta = MetaGenerator::for_text(guid);
+//! std::fs::write("notes.txt.meta", meta.dump_yaml()?)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use indexmap::IndexMap;
+use unity_asset_core::{UnityClass, UnityDocument, UnityGuid, UnityValue};
+use unity_asset_yaml::YamlDocument;
+
+#[cfg(feature = "audio")]
+use crate::audio::{AudioClip, AudioClipMeta, AudioCompressionFormat};
+#[cfg(feature = "mesh")]
+use crate::mesh::Mesh;
+#[cfg(feature = "sprite")]
+use crate::sprite::Sprite;
+#[cfg(feature = "texture")]
+use crate::texture::Texture2D;
+
+/// `fileFormatVersion` of every `.meta` file written since Unity 5.
+pub const META_FILE_FORMAT_VERSION: i64 = 2;
+
+/// Builds `.meta` documents for extracted assets.
+///
+/// Each document is a single plain mapping (see [`UnityClass::is_plain`]); write it with
+/// [`YamlDocument::dump_yaml`] or [`YamlDocument::save_to`] as `<exported file>.meta`.
+pub struct MetaGenerator;
+
+impl MetaGenerator {
+    /// `TextureImporter` settings for a texture exported as an image (e.g. PNG).
+    ///
+    /// Mipmaps, sRGB, readability, filter/wrap settings and the compression family of the
+    /// source format are kept; `nPOTScale` is off so the exported size is imported as-is.
+    #[cfg(feature = "texture")]
+    pub fn for_texture(texture: &Texture2D, guid: UnityGuid) -> YamlDocument {
+        meta_document(
+            guid,
+            "TextureImporter",
+            texture_importer(texture, &TextureImport::default()),
+        )
+    }
+
+    /// `TextureImporter` settings for a sprite exported as its own image: a single sprite with
+    /// the source's pivot, pixels-per-unit, border, extrusion and mesh type.
+    #[cfg(feature = "sprite")]
+    pub fn for_sprite(sprite: &Sprite, texture: &Texture2D, guid: UnityGuid) -> YamlDocument {
+        let pivot_centered = sprite.pivot_x == 0.5 && sprite.pivot_y == 0.5;
+        let import = TextureImport {
+            texture_type: TEXTURE_TYPE_SPRITE,
+            sprite_mode: if sprite.is_polygon {
+                SPRITE_MODE_POLYGON
+            } else {
+                SPRITE_MODE_SINGLE
+            },
+            sprite_extrude: sprite.extrude as i64,
+            sprite_mesh_type: sprite.settings.mesh_type as i64,
+            // 0 = center, 9 = custom (the pivot below).
+            alignment: if pivot_centered { 0 } else { 9 },
+            pivot: [sprite.pivot_x, sprite.pivot_y],
+            pixels_to_units: sprite.pixels_to_units,
+            border: [
+                sprite.border_x,
+                sprite.border_y,
+                sprite.border_z,
+                sprite.border_w,
+            ],
+        };
+        meta_document(guid, "TextureImporter", texture_importer(texture, &import))
+    }
+
+    /// `AudioImporter` settings: load type, compression format, sample rate, preload/background
+    /// loading and ambisonic flag of the source clip.
+    ///
+    /// Platform-only compression formats (VAG, XMA, AAC, ATRAC9, ...) are imported as Vorbis,
+    /// the editor's default.
+    #[cfg(feature = "audio")]
+    pub fn for_audio(clip: &AudioClip, guid: UnityGuid) -> YamlDocument {
+        let (load_type, frequency, preload, background, legacy_3d) = match &clip.meta {
+            AudioClipMeta::Modern {
+                load_type,
+                frequency,
+                preload_audio_data,
+                load_in_background,
+                legacy_3d,
+                ..
+            } => (
+                *load_type as i64,
+                *frequency as i64,
+                *preload_audio_data,
+                *load_in_background,
+                *legacy_3d,
+            ),
+            AudioClipMeta::Legacy { is_3d, .. } => (0, 44100, true, false, *is_3d),
+        };
+        let compression_format = match clip.compression_format() {
+            AudioCompressionFormat::PCM => 0,
+            AudioCompressionFormat::ADPCM => 2,
+            AudioCompressionFormat::MP3 => 3,
+            _ => 1,
+        };
+
+        let settings = object([
+            ("externalObjects", object([])),
+            ("serializedVersion", int(6)),
+            (
+                "defaultSettings",
+                object([
+                    ("loadType", int(load_type)),
+                    ("sampleRateSetting", int(0)),
+                    ("sampleRateOverride", int(frequency)),
+                    ("compressionFormat", int(compression_format)),
+                    ("quality", int(1)),
+                    ("conversionMode", int(0)),
+                ]),
+            ),
+            ("platformSettingOverrides", object([])),
+            ("forceToMono", int(0)),
+            ("normalize", int(1)),
+            ("preloadAudioData", flag(preload)),
+            ("loadInBackground", flag(background)),
+            ("ambisonic", flag(clip.ambisonic.unwrap_or(false))),
+            ("3D", flag(legacy_3d)),
+        ]);
+        meta_document(guid, "AudioImporter", settings)
+    }
+
+    /// `ModelImporter` settings for a mesh exported as a model file (e.g. OBJ): compression,
+    /// readability, blend shapes and index format of the source mesh, with materials, animation
+    /// and rigging import turned off.
+    #[cfg(feature = "mesh")]
+    pub fn for_mesh(mesh: &Mesh, guid: UnityGuid) -> YamlDocument {
+        let has_blend_shapes = mesh
+            .blend_shape_data
+            .as_ref()
+            .is_some_and(|b| !b.shapes.is_empty());
+        // Mesh: 0 = UInt16, 1 = UInt32. Importer: 0 = auto, 1 = 16 bit, 2 = 32 bit.
+        let index_format = if mesh.index_format == 1 { 2 } else { 1 };
+
+        let settings = object([
+            ("internalIDToNameTable", UnityValue::Array(Vec::new())),
+            ("externalObjects", object([])),
+            ("serializedVersion", int(19301)),
+            (
+                "materials",
+                object([
+                    ("materialImportMode", int(0)),
+                    ("materialName", int(0)),
+                    ("materialSearch", int(1)),
+                    ("materialLocation", int(1)),
+                ]),
+            ),
+            (
+                "meshes",
+                object([
+                    ("lODScreenPercentages", UnityValue::Array(Vec::new())),
+                    ("globalScale", int(1)),
+                    ("meshCompression", int(mesh.mesh_compression as i64)),
+                    ("addColliders", int(0)),
+                    ("importVisibility", int(0)),
+                    ("importBlendShapes", flag(has_blend_shapes)),
+                    ("importCameras", int(0)),
+                    ("importLights", int(0)),
+                    ("swapUVChannels", int(0)),
+                    ("generateSecondaryUV", int(0)),
+                    ("useFileUnits", int(1)),
+                    ("keepQuads", int(0)),
+                    ("weldVertices", int(1)),
+                    ("preserveHierarchy", int(0)),
+                    ("meshOptimizationFlags", int(-1)),
+                    ("indexFormat", int(index_format)),
+                    ("isReadable", flag(mesh.is_readable)),
+                    ("useFileScale", int(1)),
+                ]),
+            ),
+            (
+                "tangentSpace",
+                object([
+                    ("normalSmoothAngle", int(60)),
+                    ("normalImportMode", int(0)),
+                    ("tangentImportMode", int(3)),
+                    ("normalCalculationMode", int(4)),
+                ]),
+            ),
+            ("importAnimation", int(0)),
+            ("animationType", int(0)),
+        ]);
+        meta_document(guid, "ModelImporter", settings)
+    }
+
+    /// `TextScriptImporter` settings for a TextAsset exported as `.txt`/`.bytes`; the importer
+    /// has no settings of its own.
+    pub fn for_text(guid: UnityGuid) -> YamlDocument {
+        meta_document(
+            guid,
+            "TextScriptImporter",
+            object([("externalObjects", object([]))]),
+        )
+    }
+}
+
+/// `fileFormatVersion`, `guid`, the importer block and the trailing fields every importer has.
+fn meta_document(guid: UnityGuid, importer: &str, settings: UnityValue) -> YamlDocument {
+    let mut settings = match settings {
+        UnityValue::Object(map) => map,
+        _ => IndexMap::new(),
+    };
+    for key in ["userData", "assetBundleName", "assetBundleVariant"] {
+        settings.insert(key.to_string(), UnityValue::String(String::new()));
+    }
+
+    let mut meta = UnityClass::new(0, String::new(), "doc_0".to_string());
+    meta.set(
+        "fileFormatVersion".to_string(),
+        int(META_FILE_FORMAT_VERSION),
+    );
+    meta.set("guid".to_string(), UnityValue::String(guid.to_string()));
+    meta.set(importer.to_string(), UnityValue::Object(settings));
+
+    let mut doc = YamlDocument::new();
+    doc.add_entry(meta);
+    doc
+}
+
+#[cfg(feature = "texture")]
+const TEXTURE_TYPE_DEFAULT: i64 = 0;
+#[cfg(feature = "sprite")]
+const TEXTURE_TYPE_SPRITE: i64 = 8;
+#[cfg(feature = "sprite")]
+const SPRITE_MODE_SINGLE: i64 = 1;
+#[cfg(feature = "sprite")]
+const SPRITE_MODE_POLYGON: i64 = 3;
+
+/// The sprite half of `TextureImporter`; the defaults describe a plain texture.
+#[cfg(feature = "texture")]
+struct TextureImport {
+    texture_type: i64,
+    sprite_mode: i64,
+    sprite_extrude: i64,
+    sprite_mesh_type: i64,
+    alignment: i64,
+    pivot: [f32; 2],
+    pixels_to_units: f32,
+    border: [f32; 4],
+}
+
+#[cfg(feature = "texture")]
+impl Default for TextureImport {
+    fn default() -> Self {
+        Self {
+            texture_type: TEXTURE_TYPE_DEFAULT,
+            sprite_mode: 0,
+            sprite_extrude: 1,
+            sprite_mesh_type: 1,
+            alignment: 0,
+            pivot: [0.5, 0.5],
+            pixels_to_units: 100.0,
+            border: [0.0; 4],
+        }
+    }
+}
+
+#[cfg(feature = "texture")]
+fn texture_importer(texture: &Texture2D, import: &TextureImport) -> UnityValue {
+    let info = texture.format.info();
+    let max_size = max_texture_size(texture.width, texture.height);
+    let compression = if info.compressed { 1 } else { 0 };
+    let crunched = flag(texture.format.is_crunch_compressed());
+    let gl = &texture.texture_settings;
+
+    object([
+        ("internalIDToNameTable", UnityValue::Array(Vec::new())),
+        ("externalObjects", object([])),
+        ("serializedVersion", int(11)),
+        (
+            "mipmaps",
+            object([
+                ("mipMapMode", int(0)),
+                (
+                    "enableMipMap",
+                    flag(texture.mip_map || texture.mip_count > 1),
+                ),
+                // m_ColorSpace: 1 = sRGB (gamma), 0 = linear.
+                ("sRGBTexture", flag(texture.color_space == 1)),
+                ("linearTexture", int(0)),
+                ("fadeOut", int(0)),
+                ("borderMipMap", int(0)),
+                ("mipMapsPreserveCoverage", int(0)),
+                ("alphaTestReferenceValue", float(0.5)),
+                ("mipMapFadeDistanceStart", int(1)),
+                ("mipMapFadeDistanceEnd", int(3)),
+            ]),
+        ),
+        (
+            "bumpmap",
+            object([
+                ("convertToNormalMap", int(0)),
+                ("externalNormalMap", int(0)),
+                ("heightScale", float(0.25)),
+                ("normalMapFilter", int(0)),
+            ]),
+        ),
+        ("isReadable", flag(texture.is_readable)),
+        ("streamingMipmaps", int(0)),
+        ("streamingMipmapsPriority", int(0)),
+        ("grayScaleToAlpha", int(0)),
+        ("generateCubemap", int(6)),
+        ("cubemapConvolution", int(0)),
+        ("seamlessCubemap", int(0)),
+        ("textureFormat", int(1)),
+        ("maxTextureSize", int(max_size)),
+        (
+            "textureSettings",
+            object([
+                ("serializedVersion", int(2)),
+                ("filterMode", int(gl.filter_mode as i64)),
+                ("aniso", int(gl.aniso as i64)),
+                ("mipBias", float(gl.mip_bias)),
+                ("wrapU", int(gl.wrap_u as i64)),
+                ("wrapV", int(gl.wrap_v as i64)),
+                ("wrapW", int(gl.wrap_w as i64)),
+            ]),
+        ),
+        ("nPOTScale", int(0)),
+        ("lightmap", int(0)),
+        ("compressionQuality", int(50)),
+        ("spriteMode", int(import.sprite_mode)),
+        ("spriteExtrude", int(import.sprite_extrude)),
+        ("spriteMeshType", int(import.sprite_mesh_type)),
+        ("alignment", int(import.alignment)),
+        (
+            "spritePivot",
+            object([("x", float(import.pivot[0])), ("y", float(import.pivot[1]))]),
+        ),
+        ("spritePixelsToUnits", float(import.pixels_to_units)),
+        (
+            "spriteBorder",
+            object([
+                ("x", float(import.border[0])),
+                ("y", float(import.border[1])),
+                ("z", float(import.border[2])),
+                ("w", float(import.border[3])),
+            ]),
+        ),
+        ("spriteGenerateFallbackPhysicsShape", int(1)),
+        ("alphaUsage", flag(info.has_alpha)),
+        ("alphaIsTransparency", flag(info.has_alpha)),
+        ("spriteTessellationDetail", int(-1)),
+        ("textureType", int(import.texture_type)),
+        ("textureShape", int(1)),
+        ("singleChannelComponent", int(0)),
+        ("maxTextureSizeSet", int(0)),
+        ("compressedTextureSizeSet", int(0)),
+        ("textureFormatSet", int(0)),
+        ("applyGammaDecoding", int(0)),
+        (
+            "platformSettings",
+            UnityValue::Array(vec![object([
+                ("serializedVersion", int(3)),
+                (
+                    "buildTarget",
+                    UnityValue::String("DefaultTexturePlatform".to_string()),
+                ),
+                ("maxTextureSize", int(max_size)),
+                ("resizeAlgorithm", int(0)),
+                ("textureFormat", int(-1)),
+                ("textureCompression", int(compression)),
+                ("compressionQuality", int(50)),
+                ("crunchedCompression", crunched),
+                ("allowsAlphaSplitting", int(0)),
+                ("overridden", int(0)),
+                ("androidETC2FallbackOverride", int(0)),
+            ])]),
+        ),
+        ("spritePackingTag", UnityValue::String(String::new())),
+        ("pSDRemoveMatte", int(0)),
+        ("pSDShowRemoveMatteOption", int(0)),
+    ])
+}
+
+/// Smallest importer size option (32..=16384) that does not downscale the texture.
+#[cfg(feature = "texture")]
+fn max_texture_size(width: i32, height: i32) -> i64 {
+    let largest = width.max(height).max(1) as u32;
+    largest.next_power_of_two().clamp(32, 16384) as i64
+}
+
+fn object<const N: usize>(entries: [(&str, UnityValue); N]) -> UnityValue {
+    UnityValue::Object(
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    )
+}
+
+fn int(value: i64) -> UnityValue {
+    UnityValue::Integer(value)
+}
+
+/// Importer booleans are written as `0`/`1`.
+#[cfg(any(feature = "texture", feature = "audio", feature = "mesh"))]
+fn flag(value: bool) -> UnityValue {
+    UnityValue::Integer(value as i64)
+}
+
+/// Via the shortest decimal form, so `0.1f32` is written `0.1` rather than `0.10000000149…`.
+#[cfg(feature = "texture")]
+fn float(value: f32) -> UnityValue {
+    UnityValue::Float(value.to_string().parse().unwrap_or(value as f64))
+}
@@ -0,0 +1,105 @@
+//! Walkable surface export
+//!
+//! Writes the triangulated ground polygons of every tile as a single Wavefront OBJ mesh, with one
+//! group and material per area id so areas can be told apart in any viewer.
+
+use super::types::NavMeshData;
+use crate::error::Result;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// NavMesh OBJ exporter
+pub struct NavMeshExporter;
+
+impl NavMeshExporter {
+    /// Display color for an area id. The built-in areas get Unity's editor colors (Walkable
+    /// blue, Not Walkable red, Jump yellow); user areas are spread around the hue circle.
+    pub fn area_color(area: u8) -> [f32; 3] {
+        match area {
+            0 => [0.0, 0.75, 1.0],
+            1 => [1.0, 0.2, 0.2],
+            2 => [1.0, 0.85, 0.0],
+            _ => {
+                let hue = (area as f32 * 0.618_034).fract() * 6.0;
+                let x = 1.0 - (hue % 2.0 - 1.0).abs();
+                match hue as u32 {
+                    0 => [1.0, x, 0.0],
+                    1 => [x, 1.0, 0.0],
+                    2 => [0.0, 1.0, x],
+                    3 => [0.0, x, 1.0],
+                    4 => [x, 0.0, 1.0],
+                    _ => [1.0, 0.0, x],
+                }
+            }
+        }
+    }
+
+    /// OBJ text for the walkable surface, grouped by area (`g area_<id>` / `usemtl area_<id>`).
+    ///
+    /// `mtllib` names the material library to reference, if any.
+    pub fn to_obj(navmesh: &NavMeshData, mtllib: Option<&str>) -> String {
+        let mesh = navmesh.walkable_mesh();
+        let mut obj = String::new();
+        let _ = writeln!(obj, "# NavMesh: {}", navmesh.name);
+        let _ = writeln!(
+            obj,
+            "# Tiles: {}, Polygons: {}",
+            navmesh.tiles.len(),
+            navmesh.polygon_count()
+        );
+        if let Some(mtllib) = mtllib {
+            let _ = writeln!(obj, "mtllib {mtllib}");
+        }
+        let _ = writeln!(obj, "o {}", object_name(&navmesh.name));
+        for [x, y, z] in &mesh.vertices {
+            let _ = writeln!(obj, "v {x} {y} {z}");
+        }
+        for area in navmesh.area_ids() {
+            let _ = writeln!(obj, "g area_{area}");
+            let _ = writeln!(obj, "usemtl area_{area}");
+            for (triangle, _) in mesh
+                .triangles
+                .iter()
+                .zip(&mesh.areas)
+                .filter(|(_, a)| **a == area)
+            {
+                let _ = writeln!(
+                    obj,
+                    "f {} {} {}",
+                    triangle[0] + 1,
+                    triangle[1] + 1,
+                    triangle[2] + 1
+                );
+            }
+        }
+        obj
+    }
+
+    /// MTL text with one diffuse material per area id.
+    pub fn to_mtl(navmesh: &NavMeshData) -> String {
+        let mut mtl = String::new();
+        for area in navmesh.area_ids() {
+            let [r, g, b] = Self::area_color(area);
+            let _ = writeln!(mtl, "newmtl area_{area}\nKd {r} {g} {b}\n");
+        }
+        mtl
+    }
+
+    /// Write `path` (OBJ) and a `.mtl` next to it
+    pub fn export_obj<P: AsRef<Path>>(navmesh: &NavMeshData, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let mtl_path = path.with_extension("mtl");
+        let mtllib = mtl_path.file_name().and_then(|n| n.to_str());
+        std::fs::write(&mtl_path, Self::to_mtl(navmesh))?;
+        std::fs::write(path, Self::to_obj(navmesh, mtllib))?;
+        Ok(())
+    }
+}
+
+fn object_name(name: &str) -> String {
+    if name.is_empty() {
+        "NavMesh".to_string()
+    } else {
+        name.replace(char::is_whitespace, "_")
+    }
+}
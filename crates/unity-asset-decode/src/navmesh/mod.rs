@@ -0,0 +1,38 @@
+//! Unity NavMeshData processing module
+//!
+//! Parses baked navigation meshes (class 238): the agent settings, off-mesh links, height
+//! meshes and the tile payloads in `m_NavMeshTiles`, which are decoded by a dedicated reader
+//! because the typetree only describes them as bytes. The walkable surface can be exported as a
+//! single OBJ mesh grouped by area id.
+//!
+//! # Architecture
+//!
+//! - `types` - NavMeshData, tiles, polygons and the triangulated walkable mesh
+//! - `tile` - Reader for the binary tile payload
+//! - `parser` - Parsing from TypeTree data
+//! - `export` - OBJ/MTL export of the walkable surface
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use unity_asset_decode::navmesh::{NavMeshData, NavMeshExporter};
+//!
+//! # fn example(object: &unity_asset_decode::object::UnityObject) -> unity_asset_decode::Result<()> {
+//! let navmesh = NavMeshData::from_unity_object(object)?;
+//! println!("{} polygons in areas {:?}", navmesh.polygon_count(), navmesh.area_ids());
+//! NavMeshExporter::export_obj(&navmesh, "navmesh.obj")?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod export;
+pub mod parser;
+pub mod tile;
+pub mod types;
+
+pub use export::NavMeshExporter;
+pub use tile::TILE_MAGIC;
+pub use types::{
+    NavMeshAgentSettings, NavMeshData, NavMeshHeightMesh, NavMeshPolygon, NavMeshTile, OffMeshLink,
+    WalkableMesh,
+};
@@ -0,0 +1,167 @@
+//! NavMeshData parsing from TypeTree data
+
+use super::types::{
+    NavMeshAgentSettings, NavMeshData, NavMeshHeightMesh, NavMeshTile, OffMeshLink,
+};
+use crate::error::{BinaryError, Result};
+use crate::object::UnityObject;
+use indexmap::IndexMap;
+use unity_asset_core::{UnityValue, class_ids};
+
+type Fields = IndexMap<String, UnityValue>;
+
+impl NavMeshData {
+    /// Parse NavMeshData from UnityObject
+    pub fn from_unity_object(obj: &UnityObject) -> Result<Self> {
+        if obj.class_id() != class_ids::NAV_MESH_DATA {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not a NavMeshData (class_id: {})",
+                obj.class_id()
+            )));
+        }
+        Self::from_typetree(obj.class.properties())
+    }
+
+    /// Parse NavMeshData from TypeTree data, decoding every tile payload
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        let Some(UnityValue::Array(tiles)) = properties.get("m_NavMeshTiles") else {
+            return Err(BinaryError::invalid_data(
+                "NavMeshData has no m_NavMeshTiles",
+            ));
+        };
+        let tiles = tiles
+            .iter()
+            .filter_map(UnityValue::as_object)
+            .filter_map(|tile| tile.get("m_MeshData").and_then(bytes))
+            // Tiles without geometry serialize as empty blobs.
+            .filter(|data| !data.is_empty())
+            .map(|data| NavMeshTile::from_bytes(&data))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            name: properties
+                .get("m_Name")
+                .and_then(UnityValue::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            agent: agent_settings(properties),
+            tiles,
+            height_meshes: objects(properties.get("m_HeightMeshes"))
+                .map(|mesh| NavMeshHeightMesh {
+                    vertices: objects(mesh.get("m_Vertices")).map(vec3).collect(),
+                    indices: match mesh.get("m_Indices") {
+                        Some(UnityValue::Array(items)) => items
+                            .iter()
+                            .filter_map(UnityValue::as_i64)
+                            .map(|i| i as u32)
+                            .collect(),
+                        _ => Vec::new(),
+                    },
+                })
+                .collect(),
+            off_mesh_links: objects(properties.get("m_OffMeshLinks"))
+                .map(|link| OffMeshLink {
+                    start: link
+                        .get("m_Start")
+                        .and_then(UnityValue::as_object)
+                        .map_or([0.0; 3], vec3),
+                    end: link
+                        .get("m_End")
+                        .and_then(UnityValue::as_object)
+                        .map_or([0.0; 3], vec3),
+                    radius: float(link, "m_Radius").unwrap_or(0.0),
+                    link_type: int(link, "m_LinkType").unwrap_or(0) as u16,
+                    area: int(link, "m_Area").unwrap_or(0) as u8,
+                    // `m_LinkDirection`: 0 one-way, 1 bidirectional.
+                    bidirectional: int(link, "m_LinkDirection").unwrap_or(0) != 0,
+                })
+                .collect(),
+            position: properties
+                .get("m_Position")
+                .and_then(UnityValue::as_object)
+                .map_or([0.0; 3], vec3),
+            rotation: properties
+                .get("m_Rotation")
+                .and_then(UnityValue::as_object)
+                .map_or([0.0, 0.0, 0.0, 1.0], |q| {
+                    [
+                        float(q, "x").unwrap_or(0.0),
+                        float(q, "y").unwrap_or(0.0),
+                        float(q, "z").unwrap_or(0.0),
+                        float(q, "w").unwrap_or(1.0),
+                    ]
+                }),
+        })
+    }
+}
+
+fn agent_settings(properties: &Fields) -> NavMeshAgentSettings {
+    if let Some(settings) = properties
+        .get("m_NavMeshBuildSettings")
+        .and_then(UnityValue::as_object)
+    {
+        return NavMeshAgentSettings {
+            agent_type_id: int(settings, "agentTypeID").unwrap_or(0),
+            radius: float(settings, "agentRadius").unwrap_or(0.0),
+            height: float(settings, "agentHeight").unwrap_or(0.0),
+            climb: float(settings, "agentClimb").unwrap_or(0.0),
+            slope: float(settings, "agentSlope").unwrap_or(0.0),
+            ledge_drop_height: float(settings, "ledgeDropHeight").unwrap_or(0.0),
+            max_jump_across_distance: float(settings, "maxJumpAcrossDistance").unwrap_or(0.0),
+            cell_size: float(settings, "cellSize").unwrap_or(0.0),
+            tile_size: int(settings, "tileSize").unwrap_or(0),
+        };
+    }
+    // Before 5.6 only the baked walkable dimensions are stored.
+    let Some(params) = properties
+        .get("m_NavMeshParams")
+        .and_then(UnityValue::as_object)
+    else {
+        return NavMeshAgentSettings::default();
+    };
+    NavMeshAgentSettings {
+        radius: float(params, "walkableRadius").unwrap_or(0.0),
+        height: float(params, "walkableHeight").unwrap_or(0.0),
+        climb: float(params, "walkableClimb").unwrap_or(0.0),
+        cell_size: float(params, "cellSize").unwrap_or(0.0),
+        tile_size: float(params, "tileSize").unwrap_or(0.0) as i32,
+        ..Default::default()
+    }
+}
+
+/// `vector<UInt8>` arrives as bytes or, from slower paths, as an integer array.
+fn bytes(value: &UnityValue) -> Option<Vec<u8>> {
+    match value {
+        UnityValue::Bytes(data) => Some(data.clone()),
+        UnityValue::Array(items) => items.iter().map(|v| v.as_i64().map(|b| b as u8)).collect(),
+        _ => None,
+    }
+}
+
+fn objects(value: Option<&UnityValue>) -> impl Iterator<Item = &Fields> {
+    let items: &[UnityValue] = match value {
+        Some(UnityValue::Array(items)) => items,
+        _ => &[],
+    };
+    items.iter().filter_map(UnityValue::as_object)
+}
+
+fn vec3(v: &Fields) -> [f32; 3] {
+    [
+        float(v, "x").unwrap_or(0.0),
+        float(v, "y").unwrap_or(0.0),
+        float(v, "z").unwrap_or(0.0),
+    ]
+}
+
+fn float(fields: &Fields, key: &str) -> Option<f32> {
+    fields.get(key)?.as_f64().map(|v| v as f32)
+}
+
+fn int(fields: &Fields, key: &str) -> Option<i32> {
+    match fields.get(key)? {
+        UnityValue::Integer(i) => Some(*i as i32),
+        UnityValue::Bool(b) => Some(*b as i32),
+        _ => None,
+    }
+}
@@ -0,0 +1,105 @@
+//! NavMesh tile payload reader
+//!
+//! `m_NavMeshTiles[i].m_MeshData` is an opaque byte blob in the typetree. It holds a Detour
+//! tile: a `DNAV` header, then vertices and fixed-size polygons, followed by links, detail
+//! meshes, a BV tree and off-mesh connections that are not needed for the walkable surface.
+//! All values are little-endian and every section is 4-byte aligned.
+
+use super::types::{NavMeshPolygon, NavMeshTile};
+use crate::error::{BinaryError, Result};
+use crate::reader::{BinaryReader, ByteOrder};
+
+/// `'D' << 24 | 'N' << 16 | 'A' << 8 | 'V'`.
+pub const TILE_MAGIC: i32 = 0x444E_4156;
+
+/// Vertex slots per polygon; unused slots follow the used ones.
+const VERTS_PER_POLYGON: usize = 6;
+/// `areaAndtype` high bits: `1` marks an off-mesh connection polygon.
+const POLY_TYPE_OFF_MESH_CONNECTION: u8 = 1;
+
+impl NavMeshTile {
+    /// Decode a tile from its `m_MeshData` bytes.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut reader = BinaryReader::new(data, ByteOrder::Little);
+        let magic = reader.read_i32()?;
+        if magic != TILE_MAGIC {
+            return Err(BinaryError::invalid_data(format!(
+                "NavMesh tile has bad magic 0x{magic:08X}"
+            )));
+        }
+        let version = reader.read_i32()?;
+        let x = reader.read_i32()?;
+        let y = reader.read_i32()?;
+        let layer = reader.read_i32()?;
+        let _user_id = reader.read_u32()?;
+        let poly_count = count(reader.read_i32()?, "polygon")?;
+        let vert_count = count(reader.read_i32()?, "vertex")?;
+        // maxLinkCount, detailMeshCount, detailVertCount, detailTriCount, bvNodeCount,
+        // offMeshConCount, offMeshBase
+        reader.skip_bytes(7 * 4)?;
+        let walkable_height = reader.read_f32()?;
+        let walkable_radius = reader.read_f32()?;
+        let walkable_climb = reader.read_f32()?;
+        let bounds_min = read_vec3(&mut reader)?;
+        let bounds_max = read_vec3(&mut reader)?;
+        let _bv_quant_factor = reader.read_f32()?;
+
+        let needed = vert_count * 12 + poly_count * 32;
+        if reader.remaining() < needed {
+            return Err(BinaryError::not_enough_data(needed, reader.remaining()));
+        }
+
+        let vertices = (0..vert_count)
+            .map(|_| read_vec3(&mut reader))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut polygons = Vec::with_capacity(poly_count);
+        for _ in 0..poly_count {
+            let _first_link = reader.read_u32()?;
+            let mut slots = [0u16; VERTS_PER_POLYGON];
+            for slot in &mut slots {
+                *slot = reader.read_u16()?;
+            }
+            reader.skip_bytes(VERTS_PER_POLYGON * 2)?; // neighbour edges
+            let flags = reader.read_u16()?;
+            let used = (reader.read_u8()? as usize).min(VERTS_PER_POLYGON);
+            let area_and_type = reader.read_u8()?;
+
+            let polygon_vertices = slots[..used].to_vec();
+            if let Some(bad) = polygon_vertices.iter().find(|&&v| v as usize >= vert_count) {
+                return Err(BinaryError::invalid_data(format!(
+                    "NavMesh polygon references vertex {bad} of {vert_count}"
+                )));
+            }
+            polygons.push(NavMeshPolygon {
+                vertices: polygon_vertices,
+                area: area_and_type & 0x3f,
+                flags,
+                is_off_mesh_connection: area_and_type >> 6 == POLY_TYPE_OFF_MESH_CONNECTION,
+            });
+        }
+
+        Ok(Self {
+            x,
+            y,
+            layer,
+            version,
+            vertices,
+            polygons,
+            bounds_min,
+            bounds_max,
+            walkable_height,
+            walkable_radius,
+            walkable_climb,
+        })
+    }
+}
+
+fn count(value: i32, what: &str) -> Result<usize> {
+    usize::try_from(value)
+        .map_err(|_| BinaryError::invalid_data(format!("NavMesh tile has {value} {what} entries")))
+}
+
+fn read_vec3(reader: &mut BinaryReader) -> Result<[f32; 3]> {
+    Ok([reader.read_f32()?, reader.read_f32()?, reader.read_f32()?])
+}
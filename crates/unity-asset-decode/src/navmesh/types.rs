@@ -0,0 +1,154 @@
+//! NavMeshData data structures
+//!
+//! Typed views of a baked NavMeshData (class 238): the decoded tiles, the agent the navmesh was
+//! baked for, height meshes and auto-generated off-mesh links.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Agent settings the navmesh was baked with (`m_NavMeshBuildSettings`, or `m_NavMeshParams`
+/// before 5.6).
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct NavMeshAgentSettings {
+    pub agent_type_id: i32,
+    pub radius: f32,
+    pub height: f32,
+    /// Maximum step height.
+    pub climb: f32,
+    /// Maximum walkable slope in degrees.
+    pub slope: f32,
+    pub ledge_drop_height: f32,
+    pub max_jump_across_distance: f32,
+    pub cell_size: f32,
+    pub tile_size: i32,
+}
+
+/// One polygon of a tile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NavMeshPolygon {
+    /// Indices into the tile's vertices, 3 to 6 of them (2 for off-mesh connections).
+    pub vertices: Vec<u16>,
+    pub area: u8,
+    pub flags: u16,
+    /// Off-mesh connection polygons join two points rather than covering ground.
+    pub is_off_mesh_connection: bool,
+}
+
+/// A decoded `m_NavMeshTiles` entry.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct NavMeshTile {
+    pub x: i32,
+    pub y: i32,
+    pub layer: i32,
+    /// Tile data format version from the header.
+    pub version: i32,
+    pub vertices: Vec<[f32; 3]>,
+    pub polygons: Vec<NavMeshPolygon>,
+    pub bounds_min: [f32; 3],
+    pub bounds_max: [f32; 3],
+    pub walkable_height: f32,
+    pub walkable_radius: f32,
+    pub walkable_climb: f32,
+}
+
+impl NavMeshTile {
+    /// Polygons that cover walkable ground.
+    pub fn ground_polygons(&self) -> impl Iterator<Item = &NavMeshPolygon> {
+        self.polygons.iter().filter(|p| !p.is_off_mesh_connection)
+    }
+}
+
+/// A `m_HeightMeshes` entry: detail geometry used for accurate agent placement.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct NavMeshHeightMesh {
+    pub vertices: Vec<[f32; 3]>,
+    pub indices: Vec<u32>,
+}
+
+/// A generated off-mesh link (`m_OffMeshLinks`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OffMeshLink {
+    pub start: [f32; 3],
+    pub end: [f32; 3],
+    pub radius: f32,
+    /// `0` drop down, `1` jump across.
+    pub link_type: u16,
+    pub area: u8,
+    pub bidirectional: bool,
+}
+
+/// A parsed NavMeshData (class 238).
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct NavMeshData {
+    pub name: String,
+    pub agent: NavMeshAgentSettings,
+    pub tiles: Vec<NavMeshTile>,
+    pub height_meshes: Vec<NavMeshHeightMesh>,
+    pub off_mesh_links: Vec<OffMeshLink>,
+    /// Placement of the tiles in the scene (`m_Position`, `m_Rotation`).
+    pub position: [f32; 3],
+    pub rotation: [f32; 4],
+}
+
+impl NavMeshData {
+    pub fn polygon_count(&self) -> usize {
+        self.tiles.iter().map(|t| t.ground_polygons().count()).sum()
+    }
+
+    /// Area ids used by ground polygons, ascending.
+    pub fn area_ids(&self) -> BTreeSet<u8> {
+        self.tiles
+            .iter()
+            .flat_map(|t| t.ground_polygons().map(|p| p.area))
+            .collect()
+    }
+
+    /// All ground polygons of all tiles as one triangle mesh in scene space.
+    pub fn walkable_mesh(&self) -> WalkableMesh {
+        let mut mesh = WalkableMesh::default();
+        for tile in &self.tiles {
+            let base = mesh.vertices.len() as u32;
+            mesh.vertices
+                .extend(tile.vertices.iter().map(|v| self.to_scene(*v)));
+            for polygon in tile.ground_polygons() {
+                // Detour polygons are convex, so a fan covers them.
+                for pair in polygon.vertices.windows(2).skip(1) {
+                    mesh.triangles.push([
+                        base + polygon.vertices[0] as u32,
+                        base + pair[0] as u32,
+                        base + pair[1] as u32,
+                    ]);
+                    mesh.areas.push(polygon.area);
+                }
+            }
+        }
+        mesh
+    }
+
+    fn to_scene(&self, v: [f32; 3]) -> [f32; 3] {
+        let [qx, qy, qz, qw] = self.rotation;
+        // v' = v + 2w(q x v) + 2 q x (q x v)
+        let t = [
+            2.0 * (qy * v[2] - qz * v[1]),
+            2.0 * (qz * v[0] - qx * v[2]),
+            2.0 * (qx * v[1] - qy * v[0]),
+        ];
+        [
+            v[0] + qw * t[0] + (qy * t[2] - qz * t[1]) + self.position[0],
+            v[1] + qw * t[1] + (qz * t[0] - qx * t[2]) + self.position[1],
+            v[2] + qw * t[2] + (qx * t[1] - qy * t[0]) + self.position[2],
+        ]
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// Triangulated walkable surface with an area id per triangle.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct WalkableMesh {
+    pub vertices: Vec<[f32; 3]>,
+    pub triangles: Vec<[u32; 3]>,
+    pub areas: Vec<u8>,
+}
@@ -0,0 +1,37 @@
+//! Unity ParticleSystem parsing module
+//!
+//! Typed parsing of ParticleSystem (class 198) components: the main module, emission (rates
+//! and bursts) and shape modules, plus the companion ParticleSystemRenderer (class 199).
+//! Property values use [`MinMaxCurve`] and [`MinMaxGradient`], which keep Unity's
+//! constant/curve/random-between-two modes and the embedded curve keys.
+//!
+//! # Architecture
+//!
+//! - `types` - Module structs and the curve/gradient value types
+//! - `parser` - Version-aware parsing from TypeTree data
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use unity_asset_decode::particles::ParticleSystemParser;
+//! use unity_asset_decode::unity_version::UnityVersion;
+//!
+//! let version = UnityVersion::parse_version("2021.3.5f1")?;
+//! let parser = ParticleSystemParser::new(version);
+//!
+//! // In real usage, pass the typetree properties of a class 198 object:
+//! // let system = parser.parse_from_unity_object(&object)?;
+//! // println!("{}", system.info(None).to_json());
+//! # let _ = parser;
+//! # Ok::<(), unity_asset_decode::error::BinaryError>(())
+//! ```
+
+pub mod parser;
+pub mod types;
+
+pub use parser::ParticleSystemParser;
+pub use types::{
+    AnimationCurve, Burst, EmissionModule, Gradient, GradientAlphaKey, GradientColorKey, Keyframe,
+    MainModule, MinMaxCurve, MinMaxGradient, ParticleSystem, ParticleSystemInfo,
+    ParticleSystemRenderer, ShapeModule,
+};
@@ -0,0 +1,449 @@
+//! ParticleSystem parsing implementation
+//!
+//! The serialized layout changed a lot between Unity 5.x and 2021: emission went from
+//! `m_Type`/`rate` plus four fixed burst slots to `rateOverTime`/`rateOverDistance` and a burst
+//! array (5.5), bursts gained `countCurve` and `probability` (2018.3), and the shape module
+//! replaced `boxX`/`boxY`/`boxZ` and float `radius`/`arc` with a transform and
+//! `MultiModeParameter`s (2017.1), and MinMaxCurves gained `minScalar` (5.6). The parser goes by
+//! the Unity version where a field's meaning changed and by field presence elsewhere, so
+//! typetrees from patch releases that straddle a change still parse.
+
+use super::types::*;
+use crate::error::{BinaryError, Result};
+use crate::object::UnityObject;
+use crate::unity_objects::ObjectRef;
+use crate::unity_version::UnityVersion;
+use indexmap::IndexMap;
+use unity_asset_core::UnityValue;
+
+type Fields = IndexMap<String, UnityValue>;
+
+/// ParticleSystem parser
+///
+/// Parses ParticleSystem (class 198) and ParticleSystemRenderer (class 199) objects from
+/// TypeTree data.
+pub struct ParticleSystemParser {
+    version: UnityVersion,
+}
+
+impl ParticleSystemParser {
+    /// Create a new particle system parser
+    pub fn new(version: UnityVersion) -> Self {
+        Self { version }
+    }
+
+    fn at_least(&self, major: u16, minor: u16) -> bool {
+        self.version.at_least(major, minor)
+    }
+
+    /// Parse ParticleSystem from UnityObject
+    pub fn parse_from_unity_object(&self, obj: &UnityObject) -> Result<ParticleSystem> {
+        self.parse_from_typetree(obj.class.properties())
+    }
+
+    /// Parse ParticleSystemRenderer from UnityObject
+    pub fn parse_renderer_from_unity_object(
+        &self,
+        obj: &UnityObject,
+    ) -> Result<ParticleSystemRenderer> {
+        self.parse_renderer_from_typetree(obj.class.properties())
+    }
+
+    /// Parse ParticleSystem from TypeTree properties
+    pub fn parse_from_typetree(&self, properties: &Fields) -> Result<ParticleSystem> {
+        let initial = object(properties, "InitialModule").ok_or_else(|| {
+            BinaryError::invalid_data("ParticleSystem has no InitialModule".to_string())
+        })?;
+
+        let mut system = ParticleSystem {
+            game_object: properties.get("m_GameObject").and_then(pptr),
+            main: self.parse_main(properties, initial),
+            ..Default::default()
+        };
+        if let Some(emission) = object(properties, "EmissionModule") {
+            system.emission = self.parse_emission(emission);
+        }
+        if let Some(shape) = object(properties, "ShapeModule") {
+            system.shape = self.parse_shape(shape);
+        }
+        Ok(system)
+    }
+
+    /// Parse ParticleSystemRenderer from TypeTree properties
+    pub fn parse_renderer_from_typetree(
+        &self,
+        properties: &Fields,
+    ) -> Result<ParticleSystemRenderer> {
+        let mut renderer = ParticleSystemRenderer {
+            game_object: properties.get("m_GameObject").and_then(pptr),
+            enabled: boolean(properties, "m_Enabled").unwrap_or(true),
+            render_mode: int(properties, "m_RenderMode").unwrap_or(0),
+            mesh: properties.get("m_Mesh").and_then(pptr),
+            sorting_order: int(properties, "m_SortingOrder").unwrap_or(0),
+            ..Default::default()
+        };
+        if let Some(UnityValue::Array(materials)) = properties.get("m_Materials") {
+            renderer.materials = materials.iter().filter_map(pptr).collect();
+        }
+        Ok(renderer)
+    }
+
+    fn parse_main(&self, properties: &Fields, initial: &Fields) -> MainModule {
+        let mut main = MainModule::default();
+
+        main.duration = float(properties, "lengthInSec").unwrap_or(main.duration);
+        main.looping = boolean(properties, "looping").unwrap_or(main.looping);
+        main.prewarm = boolean(properties, "prewarm").unwrap_or(false);
+        main.play_on_awake = boolean(properties, "playOnAwake").unwrap_or(main.play_on_awake);
+        main.simulation_speed = float(properties, "simulationSpeed")
+            .or_else(|| float(properties, "speed"))
+            .unwrap_or(1.0);
+        main.simulation_space = int(properties, "moveWithTransform").unwrap_or(0);
+        main.scaling_mode = int(properties, "scalingMode").unwrap_or(0);
+        // Constants before 5.5, MinMaxCurves after.
+        main.start_delay = self.curve_or_constant(properties.get("startDelay"));
+
+        main.start_lifetime = self.curve_or(initial, "startLifetime", main.start_lifetime);
+        main.start_speed = self.curve_or(initial, "startSpeed", main.start_speed);
+        main.start_color = initial
+            .get("startColor")
+            .map(|v| self.min_max_gradient(v))
+            .unwrap_or_default();
+        main.start_size = self.curve_or(initial, "startSize", main.start_size);
+        main.size_3d = boolean(initial, "size3D").unwrap_or(false);
+        if main.size_3d {
+            main.start_size_y = initial
+                .get("startSizeY")
+                .map(|v| self.curve_or_constant(Some(v)));
+            main.start_size_z = initial
+                .get("startSizeZ")
+                .map(|v| self.curve_or_constant(Some(v)));
+        }
+        main.start_rotation = self.curve_or(initial, "startRotation", MinMaxCurve::default());
+        main.gravity_modifier = self.curve_or_constant(initial.get("gravityModifier"));
+        main.max_particles = int(initial, "maxNumParticles")
+            .or_else(|| int(properties, "maxNumParticles"))
+            .unwrap_or(main.max_particles);
+        main
+    }
+
+    fn parse_emission(&self, emission: &Fields) -> EmissionModule {
+        let mut module = EmissionModule {
+            enabled: boolean(emission, "enabled").unwrap_or(true),
+            ..Default::default()
+        };
+
+        let legacy = !self.at_least(5, 5) || !emission.contains_key("rateOverTime");
+        if legacy && let Some(rate) = emission.get("rate") {
+            // `m_Type`: 0 per second, 1 per unit distance.
+            let rate = self.curve_or_constant(Some(rate));
+            if int(emission, "m_Type") == Some(1) {
+                module.rate_over_distance = rate;
+            } else {
+                module.rate_over_time = rate;
+            }
+        } else {
+            module.rate_over_time = self.curve_or(emission, "rateOverTime", MinMaxCurve::default());
+            module.rate_over_distance =
+                self.curve_or(emission, "rateOverDistance", MinMaxCurve::default());
+        }
+
+        let burst_count = int(emission, "m_BurstCount").map(|n| n.max(0) as usize);
+        match emission.get("m_Bursts") {
+            Some(UnityValue::Array(bursts)) => {
+                let limit = burst_count.unwrap_or(bursts.len());
+                module.bursts = bursts
+                    .iter()
+                    .take(limit)
+                    .filter_map(UnityValue::as_object)
+                    .map(|b| self.parse_burst(b))
+                    .collect();
+            }
+            _ => {
+                // 5.x: four fixed slots `time0..3`, `cnt0..3` (and `cntmax0..3` from 5.3).
+                for i in 0..burst_count.unwrap_or(0).min(4) {
+                    let time = float(emission, &format!("time{i}")).unwrap_or(0.0);
+                    let min = int(emission, &format!("cnt{i}")).unwrap_or(0) as f32;
+                    let max = int(emission, &format!("cntmax{i}")).map_or(min, |n| n as f32);
+                    module.bursts.push(Burst {
+                        time,
+                        count: two_constants(min, max),
+                        cycle_count: 1,
+                        repeat_interval: 0.01,
+                        probability: 1.0,
+                    });
+                }
+            }
+        }
+        module
+    }
+
+    fn parse_burst(&self, burst: &Fields) -> Burst {
+        let count = match burst.get("countCurve") {
+            Some(curve) => self.min_max_curve(curve),
+            None => {
+                let min = int(burst, "minCount").unwrap_or(0) as f32;
+                let max = int(burst, "maxCount").map_or(min, |n| n as f32);
+                two_constants(min, max)
+            }
+        };
+        Burst {
+            time: float(burst, "time").unwrap_or(0.0),
+            count,
+            cycle_count: int(burst, "cycleCount").unwrap_or(1),
+            repeat_interval: float(burst, "repeatInterval").unwrap_or(0.01),
+            probability: float(burst, "probability").unwrap_or(1.0),
+        }
+    }
+
+    fn parse_shape(&self, shape: &Fields) -> ShapeModule {
+        let defaults = ShapeModule::default();
+        let mut module = ShapeModule {
+            enabled: boolean(shape, "enabled").unwrap_or(defaults.enabled),
+            shape_type: int(shape, "type").unwrap_or(defaults.shape_type),
+            angle: float(shape, "angle").unwrap_or(defaults.angle),
+            length: float(shape, "length").unwrap_or(defaults.length),
+            radius: multi_mode(shape, "radius").unwrap_or(defaults.radius),
+            radius_thickness: float(shape, "radiusThickness").unwrap_or(1.0),
+            arc: multi_mode(shape, "arc").unwrap_or(defaults.arc),
+            mesh: shape.get("m_Mesh").and_then(pptr),
+            mesh_renderer: shape.get("m_MeshRenderer").and_then(pptr),
+            skinned_mesh_renderer: shape.get("m_SkinnedMeshRenderer").and_then(pptr),
+            align_to_direction: boolean(shape, "alignToDirection").unwrap_or(false),
+            random_direction_amount: float(shape, "randomDirectionAmount")
+                .or_else(|| boolean(shape, "randomDirection").map(|b| if b { 1.0 } else { 0.0 }))
+                .unwrap_or(0.0),
+            spherical_direction_amount: float(shape, "sphericalDirectionAmount").unwrap_or(0.0),
+            ..defaults
+        };
+
+        if shape.contains_key("m_Scale") {
+            module.position = vector3(shape.get("m_Position")).unwrap_or([0.0; 3]);
+            module.rotation = vector3(shape.get("m_Rotation")).unwrap_or([0.0; 3]);
+            module.scale = vector3(shape.get("m_Scale")).unwrap_or([1.0; 3]);
+        } else {
+            module.scale = [
+                float(shape, "boxX").unwrap_or(1.0),
+                float(shape, "boxY").unwrap_or(1.0),
+                float(shape, "boxZ").unwrap_or(1.0),
+            ];
+        }
+        module
+    }
+
+    fn curve_or(&self, fields: &Fields, key: &str, default: MinMaxCurve) -> MinMaxCurve {
+        fields
+            .get(key)
+            .map(|v| self.min_max_curve(v))
+            .unwrap_or(default)
+    }
+
+    /// A MinMaxCurve, or a plain float from the layouts that stored one.
+    fn curve_or_constant(&self, value: Option<&UnityValue>) -> MinMaxCurve {
+        match value {
+            Some(v @ UnityValue::Object(_)) => self.min_max_curve(v),
+            Some(v) => MinMaxCurve::constant(v.as_f64().unwrap_or(0.0) as f32),
+            None => MinMaxCurve::default(),
+        }
+    }
+
+    fn min_max_curve(&self, value: &UnityValue) -> MinMaxCurve {
+        let Some(fields) = value.as_object() else {
+            return MinMaxCurve::constant(value.as_f64().unwrap_or(0.0) as f32);
+        };
+        let scalar = float(fields, "scalar").unwrap_or(0.0);
+        let max = fields
+            .get("maxCurve")
+            .map(animation_curve)
+            .unwrap_or_default();
+        let min = fields
+            .get("minCurve")
+            .map(animation_curve)
+            .unwrap_or_default();
+        // `minScalar` arrived in 5.6; before it, the lower constant is folded into `minCurve`
+        // relative to `scalar`.
+        let min_scalar = float(fields, "minScalar").filter(|_| self.at_least(5, 6));
+
+        match int(fields, "minMaxState") {
+            Some(MinMaxCurve::STATE_CURVE) => MinMaxCurve::Curve { scalar, curve: max },
+            Some(MinMaxCurve::STATE_TWO_CURVES) => MinMaxCurve::TwoCurves {
+                scalar,
+                min_scalar: min_scalar.unwrap_or(scalar),
+                min,
+                max,
+            },
+            Some(MinMaxCurve::STATE_TWO_CONSTANTS) => {
+                let lower = min_scalar
+                    .unwrap_or_else(|| min.keys.first().map_or(0.0, |k| k.value) * scalar);
+                two_constants(lower, scalar)
+            }
+            _ => MinMaxCurve::constant(scalar),
+        }
+    }
+
+    fn min_max_gradient(&self, value: &UnityValue) -> MinMaxGradient {
+        let Some(fields) = value.as_object() else {
+            return MinMaxGradient::default();
+        };
+        let color = |key| fields.get(key).and_then(color).unwrap_or([1.0; 4]);
+        let gradient = |key| fields.get(key).map(gradient).unwrap_or_default();
+
+        match int(fields, "minMaxState") {
+            Some(MinMaxGradient::STATE_GRADIENT) => MinMaxGradient::Gradient {
+                gradient: gradient("maxGradient"),
+            },
+            Some(MinMaxGradient::STATE_TWO_COLORS) => MinMaxGradient::TwoColors {
+                min: color("minColor"),
+                max: color("maxColor"),
+            },
+            Some(MinMaxGradient::STATE_TWO_GRADIENTS) => MinMaxGradient::TwoGradients {
+                min: gradient("minGradient"),
+                max: gradient("maxGradient"),
+            },
+            Some(MinMaxGradient::STATE_RANDOM_COLOR) => MinMaxGradient::RandomColor {
+                gradient: gradient("maxGradient"),
+            },
+            _ => MinMaxGradient::Color {
+                color: color("maxColor"),
+            },
+        }
+    }
+}
+
+fn two_constants(min: f32, max: f32) -> MinMaxCurve {
+    if min == max {
+        MinMaxCurve::constant(max)
+    } else {
+        MinMaxCurve::TwoConstants { min, max }
+    }
+}
+
+fn animation_curve(value: &UnityValue) -> AnimationCurve {
+    let Some(fields) = value.as_object() else {
+        return AnimationCurve::default();
+    };
+    let keys = match fields.get("m_Curve") {
+        Some(UnityValue::Array(keys)) => keys
+            .iter()
+            .filter_map(UnityValue::as_object)
+            .map(|k| Keyframe {
+                time: float(k, "time").unwrap_or(0.0),
+                value: float(k, "value").unwrap_or(0.0),
+                in_slope: float(k, "inSlope").unwrap_or(0.0),
+                out_slope: float(k, "outSlope").unwrap_or(0.0),
+                weighted_mode: int(k, "weightedMode").unwrap_or(0),
+                in_weight: float(k, "inWeight").unwrap_or(1.0 / 3.0),
+                out_weight: float(k, "outWeight").unwrap_or(1.0 / 3.0),
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    AnimationCurve {
+        keys,
+        pre_infinity: int(fields, "m_PreInfinity").unwrap_or(2),
+        post_infinity: int(fields, "m_PostInfinity").unwrap_or(2),
+    }
+}
+
+fn gradient(value: &UnityValue) -> Gradient {
+    let Some(fields) = value.as_object() else {
+        return Gradient::default();
+    };
+    let color_count = int(fields, "m_NumColorKeys").unwrap_or(0).clamp(0, 8);
+    let alpha_count = int(fields, "m_NumAlphaKeys").unwrap_or(0).clamp(0, 8);
+    let key_time = |prefix: &str, i: i32| {
+        int(fields, &format!("{prefix}{i}")).unwrap_or(0) as f32 / u16::MAX as f32
+    };
+    let key_color = |i: i32| {
+        fields
+            .get(&format!("key{i}"))
+            .and_then(color)
+            .unwrap_or([1.0; 4])
+    };
+
+    Gradient {
+        color_keys: (0..color_count)
+            .map(|i| {
+                let [r, g, b, _] = key_color(i);
+                GradientColorKey {
+                    time: key_time("ctime", i),
+                    color: [r, g, b],
+                }
+            })
+            .collect(),
+        alpha_keys: (0..alpha_count)
+            .map(|i| GradientAlphaKey {
+                time: key_time("atime", i),
+                alpha: key_color(i)[3],
+            })
+            .collect(),
+        mode: int(fields, "m_Mode").unwrap_or(0),
+    }
+}
+
+/// `ColorRGBAf { r, g, b, a }`, or the packed `ColorRGBA32 { rgba }` used before 5.6.
+fn color(value: &UnityValue) -> Option<[f32; 4]> {
+    let fields = value.as_object()?;
+    if let Some(rgba) = fields.get("rgba").and_then(UnityValue::as_i64) {
+        let rgba = rgba as u32;
+        return Some(std::array::from_fn(|i| {
+            ((rgba >> (8 * i)) & 0xff) as f32 / 255.0
+        }));
+    }
+    Some([
+        float(fields, "r")?,
+        float(fields, "g")?,
+        float(fields, "b")?,
+        float(fields, "a").unwrap_or(1.0),
+    ])
+}
+
+fn vector3(value: Option<&UnityValue>) -> Option<[f32; 3]> {
+    let fields = value?.as_object()?;
+    Some([
+        float(fields, "x")?,
+        float(fields, "y")?,
+        float(fields, "z")?,
+    ])
+}
+
+/// A float, or the `value` of a `MultiModeParameter`.
+fn multi_mode(fields: &Fields, key: &str) -> Option<f32> {
+    match fields.get(key)? {
+        UnityValue::Object(param) => float(param, "value"),
+        other => other.as_f64().map(|v| v as f32),
+    }
+}
+
+fn object<'a>(fields: &'a Fields, key: &str) -> Option<&'a Fields> {
+    fields.get(key).and_then(UnityValue::as_object)
+}
+
+fn float(fields: &Fields, key: &str) -> Option<f32> {
+    fields.get(key)?.as_f64().map(|v| v as f32)
+}
+
+fn int(fields: &Fields, key: &str) -> Option<i32> {
+    match fields.get(key)? {
+        UnityValue::Integer(i) => Some(*i as i32),
+        UnityValue::Bool(b) => Some(*b as i32),
+        _ => None,
+    }
+}
+
+/// Bools are serialized as `bool` or as `UInt8` depending on the version.
+fn boolean(fields: &Fields, key: &str) -> Option<bool> {
+    match fields.get(key)? {
+        UnityValue::Bool(b) => Some(*b),
+        UnityValue::Integer(i) => Some(*i != 0),
+        _ => None,
+    }
+}
+
+fn pptr(value: &UnityValue) -> Option<ObjectRef> {
+    let fields = value.as_object()?;
+    let file_id = fields.get("m_FileID").and_then(UnityValue::as_i64)?;
+    let path_id = fields.get("m_PathID").and_then(UnityValue::as_i64)?;
+    let obj_ref = ObjectRef::new(file_id as i32, path_id);
+    (!obj_ref.is_null()).then_some(obj_ref)
+}
@@ -0,0 +1,443 @@
+//! ParticleSystem data structures
+//!
+//! Typed views of the ParticleSystem (class 198) modules and the value types they are built
+//! from: [`AnimationCurve`], [`MinMaxCurve`], [`Gradient`] and [`MinMaxGradient`].
+
+use crate::unity_objects::ObjectRef;
+use serde::{Deserialize, Serialize};
+
+/// One key of an [`AnimationCurve`].
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Keyframe {
+    pub time: f32,
+    pub value: f32,
+    pub in_slope: f32,
+    pub out_slope: f32,
+    /// `0` none, `1` in, `2` out, `3` both (2018.1+; `0` before).
+    pub weighted_mode: i32,
+    pub in_weight: f32,
+    pub out_weight: f32,
+}
+
+/// A keyframed curve, as embedded in [`MinMaxCurve`].
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AnimationCurve {
+    pub keys: Vec<Keyframe>,
+    /// Wrap mode before the first key (`m_PreInfinity`).
+    pub pre_infinity: i32,
+    /// Wrap mode after the last key (`m_PostInfinity`).
+    pub post_infinity: i32,
+}
+
+impl AnimationCurve {
+    /// Smallest and largest key value, or `None` for an empty curve.
+    ///
+    /// Overshoot between keys from the tangents is not included.
+    pub fn value_range(&self) -> Option<(f32, f32)> {
+        let mut values = self.keys.iter().map(|k| k.value);
+        let first = values.next()?;
+        Some(values.fold((first, first), |(lo, hi), v| (lo.min(v), hi.max(v))))
+    }
+}
+
+/// A particle property that is a constant, a curve, or a random value between two of either.
+///
+/// Curves are normalized: the effective value is the curve value times `scalar` (or
+/// `min_scalar` for the lower curve).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum MinMaxCurve {
+    Constant {
+        value: f32,
+    },
+    Curve {
+        scalar: f32,
+        curve: AnimationCurve,
+    },
+    TwoCurves {
+        scalar: f32,
+        min_scalar: f32,
+        min: AnimationCurve,
+        max: AnimationCurve,
+    },
+    TwoConstants {
+        min: f32,
+        max: f32,
+    },
+}
+
+impl Default for MinMaxCurve {
+    fn default() -> Self {
+        Self::Constant { value: 0.0 }
+    }
+}
+
+impl MinMaxCurve {
+    pub const STATE_CONSTANT: i32 = 0;
+    pub const STATE_CURVE: i32 = 1;
+    pub const STATE_TWO_CURVES: i32 = 2;
+    pub const STATE_TWO_CONSTANTS: i32 = 3;
+
+    pub fn constant(value: f32) -> Self {
+        Self::Constant { value }
+    }
+
+    /// Smallest and largest value the property can take (curve keys only, see
+    /// [`AnimationCurve::value_range`]).
+    pub fn range(&self) -> (f32, f32) {
+        let scaled = |curve: &AnimationCurve, scalar: f32| {
+            let (lo, hi) = curve.value_range().unwrap_or((0.0, 0.0));
+            let (a, b) = (lo * scalar, hi * scalar);
+            (a.min(b), a.max(b))
+        };
+        match self {
+            Self::Constant { value } => (*value, *value),
+            Self::Curve { scalar, curve } => scaled(curve, *scalar),
+            Self::TwoCurves {
+                scalar,
+                min_scalar,
+                min,
+                max,
+            } => {
+                let (a_lo, a_hi) = scaled(min, *min_scalar);
+                let (b_lo, b_hi) = scaled(max, *scalar);
+                (a_lo.min(b_lo), a_hi.max(b_hi))
+            }
+            Self::TwoConstants { min, max } => (min.min(*max), min.max(*max)),
+        }
+    }
+
+    /// Largest value the property can take.
+    pub fn max_value(&self) -> f32 {
+        self.range().1
+    }
+}
+
+/// A color key of a [`Gradient`].
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct GradientColorKey {
+    pub time: f32,
+    pub color: [f32; 3],
+}
+
+/// An alpha key of a [`Gradient`].
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct GradientAlphaKey {
+    pub time: f32,
+    pub alpha: f32,
+}
+
+/// Color gradient with up to eight color and eight alpha keys.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Gradient {
+    pub color_keys: Vec<GradientColorKey>,
+    pub alpha_keys: Vec<GradientAlphaKey>,
+    /// `0` blend, `1` fixed (2018.3+; `0` before).
+    pub mode: i32,
+}
+
+/// A particle color that is constant, a gradient, or a random pick between two of either.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum MinMaxGradient {
+    Color {
+        color: [f32; 4],
+    },
+    Gradient {
+        gradient: Gradient,
+    },
+    TwoColors {
+        min: [f32; 4],
+        max: [f32; 4],
+    },
+    TwoGradients {
+        min: Gradient,
+        max: Gradient,
+    },
+    /// A random color sampled from the gradient.
+    RandomColor {
+        gradient: Gradient,
+    },
+}
+
+impl Default for MinMaxGradient {
+    fn default() -> Self {
+        Self::Color {
+            color: [1.0, 1.0, 1.0, 1.0],
+        }
+    }
+}
+
+impl MinMaxGradient {
+    pub const STATE_COLOR: i32 = 0;
+    pub const STATE_GRADIENT: i32 = 1;
+    pub const STATE_TWO_COLORS: i32 = 2;
+    pub const STATE_TWO_GRADIENTS: i32 = 3;
+    pub const STATE_RANDOM_COLOR: i32 = 4;
+}
+
+/// Top-level playback settings plus the `InitialModule` start values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MainModule {
+    /// Duration in seconds (`lengthInSec`).
+    pub duration: f32,
+    pub looping: bool,
+    pub prewarm: bool,
+    pub play_on_awake: bool,
+    /// A constant before 5.5.
+    pub start_delay: MinMaxCurve,
+    /// `simulationSpeed` (`speed` before 5.5).
+    pub simulation_speed: f32,
+    /// `0` local, `1` world, `2` custom; before 5.5 a bool (world = `1`).
+    pub simulation_space: i32,
+    /// `0` hierarchy, `1` local, `2` shape.
+    pub scaling_mode: i32,
+    pub start_lifetime: MinMaxCurve,
+    pub start_speed: MinMaxCurve,
+    pub start_color: MinMaxGradient,
+    pub start_size: MinMaxCurve,
+    /// Per-axis sizes, when `size_3d` is set (5.6+).
+    pub size_3d: bool,
+    pub start_size_y: Option<MinMaxCurve>,
+    pub start_size_z: Option<MinMaxCurve>,
+    pub start_rotation: MinMaxCurve,
+    /// A constant before 5.5.
+    pub gravity_modifier: MinMaxCurve,
+    pub max_particles: i32,
+}
+
+impl Default for MainModule {
+    fn default() -> Self {
+        Self {
+            duration: 5.0,
+            looping: true,
+            prewarm: false,
+            play_on_awake: true,
+            start_delay: MinMaxCurve::default(),
+            simulation_speed: 1.0,
+            simulation_space: 0,
+            scaling_mode: 0,
+            start_lifetime: MinMaxCurve::constant(5.0),
+            start_speed: MinMaxCurve::constant(5.0),
+            start_color: MinMaxGradient::default(),
+            start_size: MinMaxCurve::constant(1.0),
+            size_3d: false,
+            start_size_y: None,
+            start_size_z: None,
+            start_rotation: MinMaxCurve::default(),
+            gravity_modifier: MinMaxCurve::default(),
+            max_particles: 1000,
+        }
+    }
+}
+
+/// One emission burst.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Burst {
+    pub time: f32,
+    /// Particles per cycle; `minCount`/`maxCount` before 2018.3 become
+    /// [`MinMaxCurve::TwoConstants`].
+    pub count: MinMaxCurve,
+    /// `0` repeats forever.
+    pub cycle_count: i32,
+    pub repeat_interval: f32,
+    /// Chance of the burst firing (2018.3+; `1.0` before).
+    pub probability: f32,
+}
+
+/// Emission rates and bursts.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct EmissionModule {
+    pub enabled: bool,
+    /// Particles per second.
+    pub rate_over_time: MinMaxCurve,
+    /// Particles per unit moved.
+    pub rate_over_distance: MinMaxCurve,
+    pub bursts: Vec<Burst>,
+}
+
+impl EmissionModule {
+    /// Most particles the bursts can emit in a single cycle each.
+    pub fn max_burst_particles(&self) -> f32 {
+        self.bursts.iter().map(|b| b.count.max_value()).sum()
+    }
+}
+
+/// Where particles are spawned.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShapeModule {
+    pub enabled: bool,
+    /// Raw `type` value; see [`ShapeModule::shape_name`].
+    pub shape_type: i32,
+    pub angle: f32,
+    pub length: f32,
+    /// `radius` (a `MultiModeParameter` since 2017.1, whose `value` is used here).
+    pub radius: f32,
+    pub radius_thickness: f32,
+    /// `arc` in degrees (a `MultiModeParameter` since 2017.1).
+    pub arc: f32,
+    pub position: [f32; 3],
+    pub rotation: [f32; 3],
+    /// `m_Scale`; before 2017.1 the box size (`boxX`/`boxY`/`boxZ`).
+    pub scale: [f32; 3],
+    pub mesh: Option<ObjectRef>,
+    pub mesh_renderer: Option<ObjectRef>,
+    pub skinned_mesh_renderer: Option<ObjectRef>,
+    pub align_to_direction: bool,
+    pub random_direction_amount: f32,
+    pub spherical_direction_amount: f32,
+}
+
+impl Default for ShapeModule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            shape_type: 4,
+            angle: 25.0,
+            length: 5.0,
+            radius: 1.0,
+            radius_thickness: 1.0,
+            arc: 360.0,
+            position: [0.0; 3],
+            rotation: [0.0; 3],
+            scale: [1.0; 3],
+            mesh: None,
+            mesh_renderer: None,
+            skinned_mesh_renderer: None,
+            align_to_direction: false,
+            random_direction_amount: 0.0,
+            spherical_direction_amount: 0.0,
+        }
+    }
+}
+
+impl ShapeModule {
+    /// Name of the shape type, following `ParticleSystemShapeType`.
+    pub fn shape_name(&self) -> &'static str {
+        match self.shape_type {
+            0 | 1 => "Sphere",
+            2 | 3 => "Hemisphere",
+            4 | 7 | 8 => "Cone",
+            5 | 15 | 16 => "Box",
+            6 => "Mesh",
+            9 => "ConeVolume",
+            10 | 11 => "Circle",
+            12 => "SingleSidedEdge",
+            13 => "MeshRenderer",
+            14 => "SkinnedMeshRenderer",
+            17 => "Donut",
+            18 => "Rectangle",
+            19 => "Sprite",
+            20 => "SpriteRenderer",
+            _ => "Unknown",
+        }
+    }
+}
+
+/// A ParticleSystem component (class 198).
+///
+/// Only the main, emission and shape modules are typed; the renderer is a separate
+/// [`ParticleSystemRenderer`] component on the same GameObject.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ParticleSystem {
+    pub game_object: Option<ObjectRef>,
+    pub main: MainModule,
+    pub emission: EmissionModule,
+    pub shape: ShapeModule,
+}
+
+impl ParticleSystem {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+
+    /// Summary of this system, with `renderer` when its renderer component was parsed too.
+    pub fn info(&self, renderer: Option<&ParticleSystemRenderer>) -> ParticleSystemInfo {
+        let (lifetime_min, lifetime_max) = self.main.start_lifetime.range();
+        let rate_max = if self.emission.enabled {
+            self.emission.rate_over_time.max_value()
+        } else {
+            0.0
+        };
+        let burst_max = if self.emission.enabled {
+            self.emission.max_burst_particles()
+        } else {
+            0.0
+        };
+        let estimated = (rate_max * lifetime_max + burst_max).ceil().max(0.0) as u32;
+
+        ParticleSystemInfo {
+            duration: self.main.duration,
+            looping: self.main.looping,
+            max_particles: self.main.max_particles,
+            start_lifetime: [lifetime_min, lifetime_max],
+            start_speed: self.main.start_speed.range().into(),
+            start_size: self.main.start_size.range().into(),
+            emission_rate: self.emission.rate_over_time.range().into(),
+            burst_count: self.emission.bursts.len(),
+            shape: self.shape.shape_name().to_string(),
+            estimated_max_alive: estimated.min(self.main.max_particles.max(0) as u32),
+            render_mode: renderer.map(|r| r.render_mode_name().to_string()),
+            materials: renderer.map(|r| r.materials.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+/// A ParticleSystemRenderer component (class 199).
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ParticleSystemRenderer {
+    pub game_object: Option<ObjectRef>,
+    pub enabled: bool,
+    /// Raw `m_RenderMode`; see [`ParticleSystemRenderer::render_mode_name`].
+    pub render_mode: i32,
+    pub materials: Vec<ObjectRef>,
+    /// Mesh used when rendering particles as meshes.
+    pub mesh: Option<ObjectRef>,
+    pub sorting_order: i32,
+}
+
+impl ParticleSystemRenderer {
+    /// Name of the render mode, following `ParticleSystemRenderMode`.
+    pub fn render_mode_name(&self) -> &'static str {
+        match self.render_mode {
+            0 => "Billboard",
+            1 => "Stretch",
+            2 => "HorizontalBillboard",
+            3 => "VerticalBillboard",
+            4 => "Mesh",
+            5 => "None",
+            _ => "Unknown",
+        }
+    }
+
+    /// Whether this renderer sits on the same GameObject as `system`.
+    pub fn belongs_to(&self, system: &ParticleSystem) -> bool {
+        self.game_object.is_some() && self.game_object == system.game_object
+    }
+}
+
+/// Flat summary of a [`ParticleSystem`] for reports and export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParticleSystemInfo {
+    pub duration: f32,
+    pub looping: bool,
+    pub max_particles: i32,
+    /// `[min, max]` ranges of the start values.
+    pub start_lifetime: [f32; 2],
+    pub start_speed: [f32; 2],
+    pub start_size: [f32; 2],
+    pub emission_rate: [f32; 2],
+    pub burst_count: usize,
+    pub shape: String,
+    /// Upper bound on live particles from emission rate, lifetime and bursts, capped at
+    /// `max_particles`.
+    pub estimated_max_alive: u32,
+    pub render_mode: Option<String>,
+    pub materials: Vec<ObjectRef>,
+}
+
+impl ParticleSystemInfo {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
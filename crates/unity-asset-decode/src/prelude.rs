@@ -0,0 +1,32 @@
+//! Commonly used decode types for the enabled features, for glob import.
+
+pub use crate::{BinaryError, Result};
+
+#[cfg(feature = "texture")]
+pub use crate::texture::{
+    HdrImage, Texture2D, Texture2DConverter, Texture3D, TextureExporter, TextureFormat,
+};
+
+#[cfg(feature = "audio")]
+pub use crate::audio::{AudioClip, AudioClipConverter, AudioExporter, AudioFormat};
+
+#[cfg(feature = "sprite")]
+pub use crate::sprite::{Sprite, SpriteConfig, SpriteProcessor, TilemapRenderer};
+
+#[cfg(feature = "sprite")]
+pub use crate::tmp::{TmpFontAsset, TmpFontExporter};
+
+#[cfg(feature = "mesh")]
+pub use crate::mesh::{Mesh, MeshConfig, MeshProcessor};
+
+#[cfg(feature = "particles")]
+pub use crate::particles::{ParticleSystem, ParticleSystemInfo, ParticleSystemParser};
+
+#[cfg(feature = "animation")]
+pub use crate::animation::{AnimationClip, AnimationCurve, Keyframe, WrapMode};
+
+#[cfg(feature = "navmesh")]
+pub use crate::navmesh::{NavMeshData, NavMeshExporter};
+
+#[cfg(feature = "font")]
+pub use crate::font::{Font, FontExporter};
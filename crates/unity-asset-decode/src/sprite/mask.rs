@@ -0,0 +1,145 @@
+//! Geometry helpers for tight-packed sprites: packing rotation, triangle masks and outlines.
+//!
+//! Points here are in image pixels with a top-left origin (the `image` crate convention);
+//! callers flip Unity's bottom-left coordinates before passing them in.
+
+use super::types::SpriteSettings;
+use image::{RgbaImage, imageops};
+use std::collections::HashMap;
+
+/// Undo the atlas packing rotation of a cropped sprite region.
+pub(crate) fn apply_packing_rotation(image: RgbaImage, rotation: i32) -> RgbaImage {
+    match rotation {
+        SpriteSettings::ROTATION_FLIP_HORIZONTAL => imageops::flip_horizontal(&image),
+        SpriteSettings::ROTATION_FLIP_VERTICAL => imageops::flip_vertical(&image),
+        SpriteSettings::ROTATION_180 => imageops::rotate180(&image),
+        SpriteSettings::ROTATION_90 => imageops::rotate90(&image),
+        _ => image,
+    }
+}
+
+/// Map a point of a `width` x `height` crop the same way [`apply_packing_rotation`] maps its
+/// pixels.
+pub(crate) fn rotate_point(p: [f32; 2], width: f32, height: f32, rotation: i32) -> [f32; 2] {
+    let [x, y] = p;
+    match rotation {
+        SpriteSettings::ROTATION_FLIP_HORIZONTAL => [width - x, y],
+        SpriteSettings::ROTATION_FLIP_VERTICAL => [x, height - y],
+        SpriteSettings::ROTATION_180 => [width - x, height - y],
+        // Clockwise: the crop's left edge becomes the top edge.
+        SpriteSettings::ROTATION_90 => [height - y, x],
+        _ => p,
+    }
+}
+
+/// Rasterize triangles into a `width` x `height` coverage mask (row-major, top-left origin).
+///
+/// A pixel is covered when its center lies inside (or on an edge of) any triangle; winding
+/// order does not matter.
+pub(crate) fn rasterize(triangles: &[[[f32; 2]; 3]], width: u32, height: u32) -> Vec<bool> {
+    const EPS: f32 = 1e-4;
+    let mut mask = vec![false; width as usize * height as usize];
+
+    for &[a, b, c] in triangles {
+        let area = edge(a, b, c);
+        if area.abs() < EPS {
+            continue;
+        }
+        let min_x = a[0].min(b[0]).min(c[0]).floor().max(0.0) as u32;
+        let min_y = a[1].min(b[1]).min(c[1]).floor().max(0.0) as u32;
+        let max_x = (a[0].max(b[0]).max(c[0]).ceil() as u32).min(width);
+        let max_y = (a[1].max(b[1]).max(c[1]).ceil() as u32).min(height);
+
+        for py in min_y..max_y {
+            for px in min_x..max_x {
+                let p = [px as f32 + 0.5, py as f32 + 0.5];
+                let w0 = edge(b, c, p) / area;
+                let w1 = edge(c, a, p) / area;
+                let w2 = edge(a, b, p) / area;
+                if w0 >= -EPS && w1 >= -EPS && w2 >= -EPS {
+                    mask[py as usize * width as usize + px as usize] = true;
+                }
+            }
+        }
+    }
+
+    mask
+}
+
+fn edge(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
+
+/// Clear every pixel not covered by `mask`.
+pub(crate) fn apply_mask(image: &mut RgbaImage, mask: &[bool]) {
+    for (pixel, &covered) in image.pixels_mut().zip(mask) {
+        if !covered {
+            pixel.0 = [0, 0, 0, 0];
+        }
+    }
+}
+
+/// Boundary loops of a triangle mesh, as vertex index paths (edges used by one triangle only).
+pub(crate) fn boundary_loops(triangles: &[[u32; 3]]) -> Vec<Vec<u32>> {
+    let mut counts: HashMap<(u32, u32), usize> = HashMap::new();
+    for t in triangles {
+        for (a, b) in [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])] {
+            *counts.entry((a.min(b), a.max(b))).or_default() += 1;
+        }
+    }
+
+    // Directed boundary edges in triangle order, so loops keep the mesh winding.
+    let mut next: Vec<(u32, u32)> = Vec::new();
+    for t in triangles {
+        for (a, b) in [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])] {
+            if counts[&(a.min(b), a.max(b))] == 1 {
+                next.push((a, b));
+            }
+        }
+    }
+
+    let mut used = vec![false; next.len()];
+    let mut loops = Vec::new();
+    for start in 0..next.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+        let (first, mut current) = next[start];
+        let mut path = vec![first];
+        while current != first {
+            path.push(current);
+            let Some(i) = (0..next.len()).find(|&i| !used[i] && next[i].0 == current) else {
+                break;
+            };
+            used[i] = true;
+            current = next[i].1;
+        }
+        loops.push(path);
+    }
+    loops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boundary_of_a_quad_is_one_loop_of_four() {
+        let loops = boundary_loops(&[[0, 1, 2], [0, 2, 3]]);
+        assert_eq!(loops, vec![vec![0, 1, 2, 3]]);
+    }
+
+    #[test]
+    fn rotate_point_matches_image_rotation() {
+        // 3x2 image, pixel (0,0) goes to the top-right of the 2x3 rotated image.
+        let mut image = RgbaImage::new(3, 2);
+        image.put_pixel(0, 0, image::Rgba([255, 0, 0, 255]));
+        let rotated = apply_packing_rotation(image, SpriteSettings::ROTATION_90);
+        assert_eq!(rotated.dimensions(), (2, 3));
+        assert_eq!(rotated.get_pixel(1, 0).0, [255, 0, 0, 255]);
+
+        let center = rotate_point([0.5, 0.5], 3.0, 2.0, SpriteSettings::ROTATION_90);
+        assert_eq!(center, [1.5, 0.5]);
+    }
+}
@@ -9,6 +9,7 @@
 //! - `types` - Core data structures (Sprite, SpriteRenderData, etc.)
 //! - `parser` - Sprite parsing from Unity objects
 //! - `processor` - High-level sprite processing and image extraction
+//! - `tilemap` - Tilemap layer rendering from resolved sprites
 //!
 //! # Examples
 //!
@@ -18,12 +19,11 @@
 //!
 //! // Create processor with custom configuration
 //! let version = UnityVersion::parse_version("2020.3.12f1")?;
-//! let config = SpriteConfig {
-//!     extract_images: true,
-//!     process_atlas: true,
-//!     max_sprite_size: Some((2048, 2048)),
-//!     apply_transformations: true,
-//! };
+//! let config = SpriteConfig::default()
+//!     .with_extract_images(true)
+//!     .with_process_atlas(true)
+//!     .with_max_sprite_size(Some((2048, 2048)))
+//!     .with_apply_transformations(true);
 //! let processor = SpriteProcessor::with_config(version, config);
 //!
 //! // Note: In real usage, you would create a UnityObject from parsed data
@@ -32,17 +32,21 @@
 //! # Ok::<(), unity_asset_decode::error::BinaryError>(())
 //! ```
 
+mod mask;
 pub mod parser;
 pub mod processor;
+pub mod tilemap;
 pub mod types;
 
 // Re-export main types for easy access
 pub use parser::SpriteParser;
 pub use processor::{SpriteProcessor, SpriteStats};
+pub use tilemap::TilemapRenderer;
 pub use types::{
     // Core sprite types
     Sprite,
     SpriteAtlas,
+    SpriteAtlasData,
     SpriteBorder,
     // Configuration and results
     SpriteConfig,
@@ -51,6 +55,7 @@ pub use types::{
     SpritePivot,
     SpriteRect,
     SpriteRenderData,
+    SpriteRenderDataKey,
     SpriteResult,
     SpriteSettings,
 };
@@ -96,6 +101,15 @@ impl SpriteManager {
             .process_sprite_with_texture(sprite_object, texture)
     }
 
+    /// Process sprite with its main and secondary textures resolved in `asset`
+    pub fn process_sprite_with_asset(
+        &self,
+        sprite_object: &crate::object::UnityObject,
+        asset: &crate::asset::SerializedFile,
+    ) -> crate::error::Result<SpriteResult> {
+        self.processor.extract_with_asset(sprite_object, asset)
+    }
+
     /// Process multiple sprites as an atlas
     pub fn process_sprite_atlas(
         &self,
@@ -104,6 +118,25 @@ impl SpriteManager {
         self.processor.process_sprite_atlas(sprites)
     }
 
+    /// Parse a SpriteAtlas (class 687078895) object
+    pub fn parse_sprite_atlas(
+        &self,
+        object: &crate::object::UnityObject,
+    ) -> crate::error::Result<SpriteAtlas> {
+        self.processor.parse_sprite_atlas(object)
+    }
+
+    /// Extract a packed sprite's image using its atlas render data
+    pub fn extract_sprite_from_atlas(
+        &self,
+        sprite: &Sprite,
+        atlas: &SpriteAtlas,
+        atlas_texture: &crate::texture::Texture2D,
+    ) -> crate::error::Result<Vec<u8>> {
+        self.processor
+            .extract_sprite_from_atlas(sprite, atlas, atlas_texture)
+    }
+
     /// Get sprite statistics
     pub fn get_statistics(&self, sprites: &[&Sprite]) -> SpriteStats {
         self.processor.get_sprite_stats(sprites)
@@ -164,6 +197,8 @@ pub fn create_performance_manager(version: crate::unity_version::UnityVersion) -
         process_atlas: false,
         max_sprite_size: Some((1024, 1024)),
         apply_transformations: false,
+        mask_tight_sprites: false,
+        extract_secondary_textures: false,
     };
     SpriteManager::with_config(version, config)
 }
@@ -175,6 +210,8 @@ pub fn create_full_manager(version: crate::unity_version::UnityVersion) -> Sprit
         process_atlas: true,
         max_sprite_size: None,
         apply_transformations: true,
+        mask_tight_sprites: true,
+        extract_secondary_textures: true,
     };
     SpriteManager::with_config(version, config)
 }
@@ -230,40 +267,49 @@ pub fn is_sprite_feature_supported(
     version: &crate::unity_version::UnityVersion,
     feature: &str,
 ) -> bool {
+    let caps = version.capabilities();
     match feature {
         "basic_sprite" | "rect" | "pivot" => true,
-        "border" | "pixels_to_units" => version.major >= 5,
-        "polygon_sprites" | "sprite_atlas" => version.major >= 2017,
-        "sprite_mesh" | "sprite_physics" => version.major >= 2018,
+        "border" | "pixels_to_units" => caps.sprite_border,
+        "polygon_sprites" | "sprite_atlas" => caps.sprite_atlas,
+        "sprite_mesh" | "sprite_physics" => caps.sprite_physics_shape,
+        "secondary_textures" => caps.sprite_secondary_textures,
         _ => false,
     }
 }
 
 /// Get recommended sprite configuration for Unity version
 pub fn get_recommended_config(version: &crate::unity_version::UnityVersion) -> SpriteConfig {
-    if version.major >= 2018 {
+    let caps = version.capabilities();
+    if caps.sprite_physics_shape {
         // Modern Unity - full features
         SpriteConfig {
             extract_images: true,
             process_atlas: true,
             max_sprite_size: None,
             apply_transformations: true,
+            mask_tight_sprites: true,
+            extract_secondary_textures: true,
         }
-    } else if version.major >= 2017 {
+    } else if caps.sprite_atlas {
         // Unity 2017 - atlas support
         SpriteConfig {
             extract_images: true,
             process_atlas: true,
             max_sprite_size: Some((2048, 2048)),
             apply_transformations: true,
+            mask_tight_sprites: true,
+            extract_secondary_textures: false,
         }
-    } else if version.major >= 5 {
+    } else if caps.sprite_border {
         // Unity 5+ - basic features
         SpriteConfig {
             extract_images: true,
             process_atlas: false,
             max_sprite_size: Some((1024, 1024)),
             apply_transformations: false,
+            mask_tight_sprites: true,
+            extract_secondary_textures: false,
         }
     } else {
         // Legacy Unity - minimal features
@@ -272,12 +318,15 @@ pub fn get_recommended_config(version: &crate::unity_version::UnityVersion) -> S
             process_atlas: false,
             max_sprite_size: Some((512, 512)),
             apply_transformations: false,
+            mask_tight_sprites: true,
+            extract_secondary_textures: false,
         }
     }
 }
 
 /// Sprite processing options
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ProcessingOptions {
     pub parallel_processing: bool,
     pub cache_results: bool,
@@ -285,6 +334,32 @@ pub struct ProcessingOptions {
     pub generate_thumbnails: bool,
 }
 
+impl ProcessingOptions {
+    /// Set `parallel_processing`.
+    pub fn with_parallel_processing(mut self, parallel_processing: bool) -> Self {
+        self.parallel_processing = parallel_processing;
+        self
+    }
+
+    /// Set `cache_results`.
+    pub fn with_cache_results(mut self, cache_results: bool) -> Self {
+        self.cache_results = cache_results;
+        self
+    }
+
+    /// Set `validate_sprites`.
+    pub fn with_validate_sprites(mut self, validate_sprites: bool) -> Self {
+        self.validate_sprites = validate_sprites;
+        self
+    }
+
+    /// Set `generate_thumbnails`.
+    pub fn with_generate_thumbnails(mut self, generate_thumbnails: bool) -> Self {
+        self.generate_thumbnails = generate_thumbnails;
+        self
+    }
+}
+
 impl Default for ProcessingOptions {
     fn default() -> Self {
         Self {
@@ -6,7 +6,8 @@ use super::types::*;
 use crate::error::Result;
 use crate::object::UnityObject;
 use crate::reader::BinaryReader;
-use crate::unity_version::UnityVersion;
+use crate::unity_objects::ObjectRef;
+use crate::unity_version::{UnityVersion, VertexFormatTable};
 use indexmap::IndexMap;
 use unity_asset_core::UnityValue;
 
@@ -82,6 +83,11 @@ impl SpriteParser {
             self.extract_render_data(&mut sprite, render_data_value)?;
         }
 
+        // Extract physics shape
+        if let Some(physics_shape_value) = properties.get("m_PhysicsShape") {
+            self.extract_physics_shape(&mut sprite, physics_shape_value)?;
+        }
+
         // Extract atlas tags
         if let Some(atlas_tags_value) = properties.get("m_AtlasTags") {
             self.extract_atlas_tags(&mut sprite, atlas_tags_value)?;
@@ -92,9 +98,63 @@ impl SpriteParser {
             self.extract_sprite_atlas(&mut sprite, sprite_atlas_value)?;
         }
 
+        sprite.render_data_key = properties.get("m_RenderDataKey").and_then(render_data_key);
+
         Ok(sprite)
     }
 
+    /// Parse a SpriteAtlas (class 687078895) from TypeTree properties
+    ///
+    /// `m_RenderDataMap` entries that are not a key/value pair are skipped.
+    pub fn parse_atlas_from_typetree(
+        &self,
+        properties: &IndexMap<String, UnityValue>,
+    ) -> Result<SpriteAtlas> {
+        let string = |key: &str| {
+            properties
+                .get(key)
+                .and_then(UnityValue::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let mut atlas = SpriteAtlas {
+            name: string("m_Name"),
+            tag: string("m_Tag"),
+            is_variant: matches!(properties.get("m_IsVariant"), Some(UnityValue::Bool(true))),
+            ..Default::default()
+        };
+
+        if let Some(UnityValue::Array(sprites)) = properties.get("m_PackedSprites") {
+            // Null entries are kept so the list stays aligned with the names.
+            atlas.packed_sprite_refs = sprites
+                .iter()
+                .map(|sprite| pptr(sprite).unwrap_or(ObjectRef::new(0, 0)))
+                .collect();
+        }
+        if let Some(UnityValue::Array(names)) = properties.get("m_PackedSpriteNamesToIndex") {
+            atlas.packed_sprites = names
+                .iter()
+                .filter_map(|n| n.as_str().map(str::to_string))
+                .collect();
+        }
+        if let Some(UnityValue::Array(entries)) = properties.get("m_RenderDataMap") {
+            atlas.render_data_map = entries
+                .iter()
+                .filter_map(|entry| {
+                    let (key, data) = pair(entry)?;
+                    Some((render_data_key(key)?, atlas_data(data.as_object()?)))
+                })
+                .collect();
+        }
+        atlas.texture_path_id = atlas
+            .render_data_map
+            .first()
+            .and_then(|(_, data)| data.texture)
+            .map_or(0, |texture| texture.path_id);
+
+        Ok(atlas)
+    }
+
     /// Parse Sprite from raw binary data (fallback method)
     #[allow(clippy::field_reassign_with_default)]
     pub fn parse_from_binary_data(&self, data: &[u8]) -> Result<Sprite> {
@@ -237,10 +297,118 @@ impl SpriteParser {
                 }
             }
 
+            if let Some([x, y]) = rd_obj.get("textureRectOffset").and_then(vector2) {
+                sprite.render_data.texture_rect_offset_x = x;
+                sprite.render_data.texture_rect_offset_y = y;
+            }
+            if let Some([x, y]) = rd_obj.get("atlasRectOffset").and_then(vector2) {
+                sprite.render_data.atlas_rect_offset_x = x;
+                sprite.render_data.atlas_rect_offset_y = y;
+            }
+            if let Some(UnityValue::Integer(raw)) = rd_obj.get("settingsRaw") {
+                sprite.settings = SpriteSettings::from_raw(*raw as u32);
+            }
+
             // Extract other render data fields
             if let Some(UnityValue::Float(downscale)) = rd_obj.get("downscaleMultiplier") {
                 sprite.render_data.downscale_multiplier = *downscale as f32;
             }
+
+            self.extract_render_mesh(&mut sprite.render_data, rd_obj);
+
+            if let Some(UnityValue::Array(secondary)) = rd_obj.get("secondaryTextures") {
+                sprite.secondary_textures = secondary
+                    .iter()
+                    .filter_map(|entry| {
+                        let UnityValue::Object(entry) = entry else {
+                            return None;
+                        };
+                        let name = entry.get("name")?.as_str()?.to_string();
+                        let texture = entry.get("texture").and_then(pptr)?;
+                        Some((name, texture))
+                    })
+                    .collect();
+            }
+        }
+        Ok(())
+    }
+
+    /// Extract the render mesh: `m_VertexData` + `m_IndexBuffer` (5.6+), or the older
+    /// `vertices` + `indices` arrays.
+    fn extract_render_mesh(
+        &self,
+        render_data: &mut SpriteRenderData,
+        rd_obj: &IndexMap<String, UnityValue>,
+    ) {
+        if let Some(UnityValue::Object(vertex_data)) = rd_obj.get("m_VertexData") {
+            let uv_channel = if self.version.capabilities().vertex_channels_2018 {
+                4
+            } else {
+                3
+            };
+            let layout = VertexLayout::from_typetree(vertex_data, &self.version);
+            render_data.vertices = layout
+                .read_channel(0)
+                .into_iter()
+                .map(|v| [v[0], v[1], v.get(2).copied().unwrap_or(0.0)])
+                .collect();
+            render_data.uvs = layout
+                .read_channel(uv_channel)
+                .into_iter()
+                .filter(|v| v.len() >= 2)
+                .map(|v| [v[0], v[1]])
+                .collect();
+            if let Some(index_bytes) = rd_obj.get("m_IndexBuffer").and_then(byte_array) {
+                render_data.indices = index_bytes
+                    .chunks_exact(2)
+                    .map(|b| u16::from_le_bytes([b[0], b[1]]) as u32)
+                    .collect();
+            }
+        } else if let Some(UnityValue::Array(vertices)) = rd_obj.get("vertices") {
+            for vertex in vertices {
+                let UnityValue::Object(vertex) = vertex else {
+                    continue;
+                };
+                let Some(UnityValue::Object(pos)) = vertex.get("pos") else {
+                    continue;
+                };
+                let [x, y] = vector2(&UnityValue::Object(pos.clone())).unwrap_or_default();
+                let z = match pos.get("z") {
+                    Some(UnityValue::Float(z)) => *z as f32,
+                    _ => 0.0,
+                };
+                render_data.vertices.push([x, y, z]);
+                if let Some(uv) = vertex.get("uv").and_then(vector2) {
+                    render_data.uvs.push(uv);
+                }
+            }
+            if let Some(UnityValue::Array(indices)) = rd_obj.get("indices") {
+                render_data.indices = indices
+                    .iter()
+                    .filter_map(|i| match i {
+                        UnityValue::Integer(i) => Some(*i as u32),
+                        _ => None,
+                    })
+                    .collect();
+            }
+        }
+
+        if render_data.uvs.len() != render_data.vertices.len() {
+            render_data.uvs.clear();
+        }
+    }
+
+    /// Extract `m_PhysicsShape` (a list of Vector2f paths)
+    fn extract_physics_shape(&self, sprite: &mut Sprite, value: &UnityValue) -> Result<()> {
+        if let UnityValue::Array(paths) = value {
+            sprite.physics_shape = paths
+                .iter()
+                .filter_map(|path| match path {
+                    UnityValue::Array(points) => Some(points.iter().filter_map(vector2).collect()),
+                    _ => None,
+                })
+                .filter(|path: &Vec<[f32; 2]>| !path.is_empty())
+                .collect();
         }
         Ok(())
     }
@@ -305,6 +473,234 @@ impl SpriteParser {
     }
 }
 
+fn vector2(value: &UnityValue) -> Option<[f32; 2]> {
+    let UnityValue::Object(obj) = value else {
+        return None;
+    };
+    let component = |key: &str| match obj.get(key)? {
+        UnityValue::Float(v) => Some(*v as f32),
+        UnityValue::Integer(v) => Some(*v as f32),
+        _ => None,
+    };
+    Some([component("x")?, component("y")?])
+}
+
+fn pptr(value: &UnityValue) -> Option<ObjectRef> {
+    let UnityValue::Object(obj) = value else {
+        return None;
+    };
+    let file_id = obj.get("m_FileID")?.as_i64()?;
+    let path_id = obj.get("m_PathID")?.as_i64()?;
+    let pptr = ObjectRef::new(file_id as i32, path_id);
+    (!pptr.is_null()).then_some(pptr)
+}
+
+/// A `pair<K, V>` element, serialized as `[first, second]` or `{first, second}`.
+fn pair(value: &UnityValue) -> Option<(&UnityValue, &UnityValue)> {
+    match value {
+        UnityValue::Array(kv) if kv.len() == 2 => Some((&kv[0], &kv[1])),
+        UnityValue::Object(obj) => Some((obj.get("first")?, obj.get("second")?)),
+        _ => None,
+    }
+}
+
+/// `pair<GUID, SInt64>`, with the GUID as `data[0]..data[3]` or a `data` array.
+fn render_data_key(value: &UnityValue) -> Option<SpriteRenderDataKey> {
+    let (guid, file_id) = pair(value)?;
+    let guid = guid.as_object()?;
+    let word = |i: usize| match guid.get("data") {
+        Some(UnityValue::Array(words)) => words.get(i)?.as_i64(),
+        _ => guid.get(&format!("data[{i}]"))?.as_i64(),
+    };
+    Some(SpriteRenderDataKey {
+        guid: [word(0)?, word(1)?, word(2)?, word(3)?].map(|w| w as u32),
+        file_id: file_id.as_i64()?,
+    })
+}
+
+fn rect(value: &UnityValue) -> Option<SpriteRect> {
+    let UnityValue::Object(obj) = value else {
+        return None;
+    };
+    let [x, y] = vector2(value)?;
+    let component = |key: &str| obj.get(key)?.as_f64().map(|v| v as f32);
+    Some(SpriteRect {
+        x,
+        y,
+        width: component("width")?,
+        height: component("height")?,
+    })
+}
+
+/// `SpriteAtlasData`, the value side of `m_RenderDataMap`
+fn atlas_data(data: &IndexMap<String, UnityValue>) -> SpriteAtlasData {
+    SpriteAtlasData {
+        texture: data.get("texture").and_then(pptr),
+        alpha_texture: data.get("alphaTexture").and_then(pptr),
+        texture_rect: data.get("textureRect").and_then(rect).unwrap_or_default(),
+        texture_rect_offset: data
+            .get("textureRectOffset")
+            .and_then(vector2)
+            .unwrap_or_default(),
+        atlas_rect_offset: data
+            .get("atlasRectOffset")
+            .and_then(vector2)
+            .unwrap_or_default(),
+        downscale_multiplier: data
+            .get("downscaleMultiplier")
+            .and_then(UnityValue::as_f64)
+            .unwrap_or(1.0) as f32,
+        settings: data
+            .get("settingsRaw")
+            .and_then(UnityValue::as_i64)
+            .map(|raw| SpriteSettings::from_raw(raw as u32))
+            .unwrap_or_default(),
+        secondary_textures: match data.get("secondaryTextures") {
+            Some(UnityValue::Array(entries)) => entries
+                .iter()
+                .filter_map(|entry| {
+                    let entry = entry.as_object()?;
+                    let name = entry.get("name")?.as_str()?.to_string();
+                    Some((name, entry.get("texture").and_then(pptr)?))
+                })
+                .collect(),
+            _ => Vec::new(),
+        },
+    }
+}
+
+fn byte_array(value: &UnityValue) -> Option<Vec<u8>> {
+    match value {
+        UnityValue::Bytes(b) => Some(b.clone()),
+        UnityValue::Array(items) => Some(
+            items
+                .iter()
+                .filter_map(|item| match item {
+                    UnityValue::Integer(b) => Some(*b as u8),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// `m_VertexData` channel layout and bytes, enough to read float channels.
+struct VertexLayout {
+    vertex_count: usize,
+    /// `(stream, offset, format, dimension)` per channel.
+    channels: Vec<(u8, u8, u8, u8)>,
+    data: Vec<u8>,
+    format_sizes: &'static [usize],
+}
+
+impl VertexLayout {
+    fn from_typetree(vertex_data: &IndexMap<String, UnityValue>, version: &UnityVersion) -> Self {
+        let vertex_count = match vertex_data.get("m_VertexCount") {
+            Some(UnityValue::Integer(n)) => (*n).max(0) as usize,
+            _ => 0,
+        };
+        let field = |channel: &IndexMap<String, UnityValue>, key: &str| match channel.get(key) {
+            Some(UnityValue::Integer(v)) => *v as u8,
+            _ => 0,
+        };
+        let channels = match vertex_data.get("m_Channels") {
+            Some(UnityValue::Array(channels)) => channels
+                .iter()
+                .map(|channel| match channel {
+                    UnityValue::Object(c) => (
+                        field(c, "stream"),
+                        field(c, "offset"),
+                        field(c, "format"),
+                        field(c, "dimension") & 0xf,
+                    ),
+                    _ => (0, 0, 0, 0),
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let data = vertex_data
+            .get("m_DataSize")
+            .and_then(byte_array)
+            .unwrap_or_default();
+
+        // Component sizes per format value; the enum changed in 2017 and again in 2019.
+        let format_sizes: &'static [usize] = match version.capabilities().vertex_format {
+            VertexFormatTable::V2019 => &[4, 2, 1, 1, 2, 2, 1, 1, 2, 2, 4, 4],
+            VertexFormatTable::V2017 => &[4, 2, 1, 1, 1, 2, 2, 1, 1, 2, 2, 4, 4],
+            VertexFormatTable::Legacy => &[4, 2, 1, 1, 4],
+        };
+
+        Self {
+            vertex_count,
+            channels,
+            data,
+            format_sizes,
+        }
+    }
+
+    fn component_size(&self, format: u8) -> usize {
+        self.format_sizes.get(format as usize).copied().unwrap_or(4)
+    }
+
+    /// Read one channel as floats; empty when absent or not a float format.
+    fn read_channel(&self, index: usize) -> Vec<Vec<f32>> {
+        let Some(&(stream, offset, format, dimension)) = self.channels.get(index) else {
+            return Vec::new();
+        };
+        // Float and Float16 are 0 and 1 in every version of the format enum.
+        if dimension == 0 || format > 1 {
+            return Vec::new();
+        }
+
+        let stride_of = |s: u8| -> usize {
+            self.channels
+                .iter()
+                .filter(|c| c.0 == s && c.3 > 0)
+                .map(|c| c.3 as usize * self.component_size(c.2))
+                .sum()
+        };
+        let mut stream_start = 0usize;
+        for s in 0..stream {
+            stream_start += self.vertex_count * stride_of(s);
+            stream_start = stream_start.div_ceil(16) * 16;
+        }
+        let stride = stride_of(stream);
+        let size = self.component_size(format);
+
+        let mut out = Vec::with_capacity(self.vertex_count);
+        for v in 0..self.vertex_count {
+            let base = stream_start + v * stride + offset as usize;
+            let mut value = Vec::with_capacity(dimension as usize);
+            for d in 0..dimension as usize {
+                let at = base + d * size;
+                let Some(bytes) = self.data.get(at..at + size) else {
+                    return Vec::new();
+                };
+                value.push(if format == 0 {
+                    f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    half_to_f32(u16::from_le_bytes([bytes[0], bytes[1]]))
+                });
+            }
+            out.push(value);
+        }
+        out
+    }
+}
+
+fn half_to_f32(h: u16) -> f32 {
+    let sign = if h & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((h >> 10) & 0x1f) as i32;
+    let mantissa = (h & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        31 if mantissa == 0.0 => sign * f32::INFINITY,
+        31 => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
 impl Default for SpriteParser {
     fn default() -> Self {
         Self::new(UnityVersion::default())
@@ -3,13 +3,16 @@
 //! This module provides high-level sprite processing functionality including
 //! image extraction and sprite atlas handling.
 
+use super::mask;
 use super::parser::SpriteParser;
 use super::types::*;
+use crate::asset::SerializedFile;
 use crate::error::{BinaryError, Result};
 use crate::object::UnityObject;
 use crate::texture::Texture2D;
 use crate::unity_version::UnityVersion;
 use image::{RgbaImage, imageops};
+use unity_asset_core::class_ids;
 
 /// Sprite processor
 ///
@@ -65,13 +68,114 @@ impl SpriteProcessor {
     }
 
     /// Extract sprite image from texture
+    ///
+    /// Crops [`Sprite::texture_rect`], undoes the atlas packing rotation, and for tight-packed
+    /// sprites clears the pixels outside the render mesh (see
+    /// [`SpriteConfig::mask_tight_sprites`]) so neighbouring atlas entries do not bleed in.
     pub fn extract_sprite_image(&self, sprite: &Sprite, texture: &Texture2D) -> Result<Vec<u8>> {
         // Get texture image data using converter
         let converter = crate::texture::Texture2DConverter::new(self.parser.version().clone());
         let texture_image = converter.decode_to_image(texture)?;
+        let sprite_image = self.crop_sprite(sprite, &texture_image)?;
+        encode_png(&sprite_image)
+    }
+
+    /// Parse a SpriteAtlas (class 687078895) object
+    pub fn parse_sprite_atlas(&self, object: &UnityObject) -> Result<SpriteAtlas> {
+        if object.class_id() != class_ids::SPRITE_ATLAS {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not a SpriteAtlas (class_id: {})",
+                object.class_id()
+            )));
+        }
+        self.parser
+            .parse_atlas_from_typetree(object.class.properties())
+    }
+
+    /// Extract a packed sprite's image from its atlas texture
+    ///
+    /// A packed sprite's own `m_RD` describes it before packing; the texture rect, offsets and
+    /// packing settings in the atlas come from `atlas`'s render data entry under the sprite's
+    /// `m_RenderDataKey`. `atlas_texture` is that entry's [`SpriteAtlasData::texture`].
+    pub fn extract_sprite_from_atlas(
+        &self,
+        sprite: &Sprite,
+        atlas: &SpriteAtlas,
+        atlas_texture: &Texture2D,
+    ) -> Result<Vec<u8>> {
+        let data = atlas.sprite_data(sprite).ok_or_else(|| {
+            BinaryError::invalid_data(format!(
+                "Sprite {} has no render data in atlas {}",
+                sprite.name, atlas.name
+            ))
+        })?;
+        self.extract_sprite_image(&with_atlas_data(sprite, data), atlas_texture)
+    }
 
+    /// Process a sprite whose textures live in `asset`
+    ///
+    /// Resolves `m_RD.texture` in `asset` and extracts the main image like
+    /// [`Self::process_sprite_with_texture`]. With [`SpriteConfig::extract_secondary_textures`],
+    /// every secondary texture is cropped with the same rect, packing rotation and mask into
+    /// [`SpriteResult::secondary_images`]; ones that cannot be resolved or decoded are reported
+    /// as warnings.
+    pub fn extract_with_asset(
+        &self,
+        sprite_object: &UnityObject,
+        asset: &SerializedFile,
+    ) -> Result<SpriteResult> {
+        let mut result = self.parse_sprite(sprite_object)?;
+        if !self.config.extract_images {
+            return Ok(result);
+        }
+
+        let texture_image =
+            self.load_texture_image(asset, result.sprite.render_data.texture_path_id);
+        match texture_image.and_then(|image| self.crop_sprite(&result.sprite, &image)) {
+            Ok(image) => result.image_data = Some(encode_png(&image)?),
+            Err(e) => result.add_warning(format!("Failed to extract sprite image: {}", e)),
+        }
+
+        if self.config.extract_secondary_textures {
+            for (name, texture) in result.sprite.secondary_textures.clone() {
+                let image = if texture.file_id != 0 {
+                    Err(BinaryError::unsupported(format!(
+                        "texture is in external file {}",
+                        texture.file_id
+                    )))
+                } else {
+                    self.load_texture_image(asset, texture.path_id)
+                        .and_then(|image| self.crop_sprite(&result.sprite, &image))
+                };
+                match image {
+                    Ok(image) => result.secondary_images.push((name, encode_png(&image)?)),
+                    Err(e) => result.add_warning(format!(
+                        "Failed to extract secondary texture {}: {}",
+                        name, e
+                    )),
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Decode the Texture2D with `path_id` in `asset`
+    fn load_texture_image(&self, asset: &SerializedFile, path_id: i64) -> Result<RgbaImage> {
+        let object = asset
+            .find_object_handle(path_id)
+            .filter(|h| h.class_id() == class_ids::TEXTURE_2D)
+            .ok_or_else(|| BinaryError::invalid_data(format!("Texture {} not found", path_id)))?
+            .read()?;
+        let converter = crate::texture::Texture2DConverter::new(self.parser.version().clone());
+        let texture = converter.from_unity_object(&object)?;
+        converter.decode_to_image(&texture)
+    }
+
+    /// Crop the sprite out of a decoded texture, undoing packing rotation and tight packing
+    fn crop_sprite(&self, sprite: &Sprite, texture_image: &RgbaImage) -> Result<RgbaImage> {
         // Calculate sprite bounds
-        let sprite_rect = sprite.get_rect();
+        let sprite_rect = sprite.texture_rect();
         let texture_width = texture_image.width();
         let texture_height = texture_image.height();
 
@@ -105,34 +209,79 @@ impl SpriteProcessor {
         // So we need to flip the Y coordinate
         let flipped_y = texture_height - y - height;
 
-        let sprite_image =
-            imageops::crop_imm(&texture_image, x, flipped_y, width, height).to_image();
+        let mut sprite_image =
+            imageops::crop_imm(texture_image, x, flipped_y, width, height).to_image();
 
-        // Apply transformations if enabled
-        let final_image = if self.config.apply_transformations {
-            self.apply_sprite_transformations(sprite_image, sprite)?
+        let rotation = if sprite.settings.packed {
+            sprite.settings.packing_rotation
         } else {
-            sprite_image
+            0
         };
+        sprite_image = mask::apply_packing_rotation(sprite_image, rotation);
+
+        if self.config.mask_tight_sprites && sprite.settings.is_tight() {
+            let triangles = self.mesh_triangles(sprite, &sprite_rect, texture_image.dimensions());
+            if !triangles.is_empty() {
+                let coverage =
+                    mask::rasterize(&triangles, sprite_image.width(), sprite_image.height());
+                mask::apply_mask(&mut sprite_image, &coverage);
+            }
+        }
 
-        // Convert to PNG bytes
-        let mut png_data = Vec::new();
-        {
-            use image::ImageEncoder;
-            use image::codecs::png::PngEncoder;
-
-            let encoder = PngEncoder::new(&mut png_data);
-            encoder
-                .write_image(
-                    final_image.as_raw(),
-                    final_image.width(),
-                    final_image.height(),
-                    image::ExtendedColorType::Rgba8,
-                )
-                .map_err(|e| BinaryError::generic(format!("Failed to encode PNG: {}", e)))?;
+        // Apply transformations if enabled
+        if self.config.apply_transformations {
+            self.apply_sprite_transformations(sprite_image, sprite)
+        } else {
+            Ok(sprite_image)
         }
+    }
+
+    /// Render mesh triangles in the pixels of the extracted (un-rotated) sprite image.
+    ///
+    /// UVs are mapped into the cropped atlas region and then through the packing rotation, the
+    /// same way the crop itself is. Meshes without UVs fall back to vertex positions, which
+    /// are already in the sprite's own frame.
+    fn mesh_triangles(
+        &self,
+        sprite: &Sprite,
+        rect: &SpriteRect,
+        (texture_width, texture_height): (u32, u32),
+    ) -> Vec<[[f32; 2]; 3]> {
+        let rd = &sprite.render_data;
+        let crop_width = rect.width.floor();
+        let crop_height = rect.height.floor();
+        let rotation = if sprite.settings.packed {
+            sprite.settings.packing_rotation
+        } else {
+            0
+        };
+        let out_height = if rotation == SpriteSettings::ROTATION_90 {
+            crop_width
+        } else {
+            crop_height
+        };
 
-        Ok(png_data)
+        let point = |i: u32| -> [f32; 2] {
+            let i = i as usize;
+            if rd.uvs.len() == rd.vertices.len() {
+                let [u, v] = rd.uvs[i];
+                let x = u * texture_width as f32 - rect.x;
+                let y = v * texture_height as f32 - rect.y;
+                mask::rotate_point([x, crop_height - y], crop_width, crop_height, rotation)
+            } else {
+                let [x, y, _] = rd.vertices[i];
+                let x = x * sprite.pixels_to_units + sprite.rect_width * sprite.pivot_x
+                    - rd.texture_rect_offset_x;
+                let y = y * sprite.pixels_to_units + sprite.rect_height * sprite.pivot_y
+                    - rd.texture_rect_offset_y;
+                [x, out_height - y]
+            }
+        };
+
+        rd.triangles()
+            .into_iter()
+            .map(|[a, b, c]| [point(a), point(b), point(c)])
+            .collect()
     }
 
     /// Apply sprite transformations (pivot, offset, etc.)
@@ -193,24 +342,21 @@ impl SpriteProcessor {
     /// Get supported sprite features for this Unity version
     pub fn get_supported_features(&self) -> Vec<&'static str> {
         let version = self.parser.version();
-        let mut features = vec!["basic_sprite", "rect", "pivot"];
-
-        if version.major >= 5 {
-            features.push("border");
-            features.push("pixels_to_units");
-        }
-
-        if version.major >= 2017 {
-            features.push("polygon_sprites");
-            features.push("sprite_atlas");
-        }
-
-        if version.major >= 2018 {
-            features.push("sprite_mesh");
-            features.push("sprite_physics");
-        }
-
-        features
+        [
+            "basic_sprite",
+            "rect",
+            "pivot",
+            "border",
+            "pixels_to_units",
+            "polygon_sprites",
+            "sprite_atlas",
+            "sprite_mesh",
+            "sprite_physics",
+            "secondary_textures",
+        ]
+        .into_iter()
+        .filter(|feature| super::is_sprite_feature_supported(version, feature))
+        .collect()
     }
 
     /// Check if a feature is supported
@@ -313,6 +459,41 @@ impl SpriteProcessor {
     }
 }
 
+/// `sprite` with its texture, rect, offsets and settings taken from an atlas entry
+fn with_atlas_data(sprite: &Sprite, data: &SpriteAtlasData) -> Sprite {
+    let mut packed = sprite.clone();
+    let rd = &mut packed.render_data;
+    rd.texture_path_id = data.texture.map_or(0, |texture| texture.path_id);
+    rd.texture_rect_x = data.texture_rect.x;
+    rd.texture_rect_y = data.texture_rect.y;
+    rd.texture_rect_width = data.texture_rect.width;
+    rd.texture_rect_height = data.texture_rect.height;
+    [rd.texture_rect_offset_x, rd.texture_rect_offset_y] = data.texture_rect_offset;
+    [rd.atlas_rect_offset_x, rd.atlas_rect_offset_y] = data.atlas_rect_offset;
+    rd.downscale_multiplier = data.downscale_multiplier;
+    packed.settings = data.settings.clone();
+    if !data.secondary_textures.is_empty() {
+        packed.secondary_textures = data.secondary_textures.clone();
+    }
+    packed
+}
+
+fn encode_png(image: &RgbaImage) -> Result<Vec<u8>> {
+    use image::ImageEncoder;
+    use image::codecs::png::PngEncoder;
+
+    let mut png_data = Vec::new();
+    PngEncoder::new(&mut png_data)
+        .write_image(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            image::ExtendedColorType::Rgba8,
+        )
+        .map_err(|e| BinaryError::generic(format!("Failed to encode PNG: {}", e)))?;
+    Ok(png_data)
+}
+
 impl Default for SpriteProcessor {
     fn default() -> Self {
         Self::new(UnityVersion::default())
@@ -321,6 +502,7 @@ impl Default for SpriteProcessor {
 
 /// Sprite processing statistics
 #[derive(Debug, Clone, Default)]
+#[non_exhaustive]
 pub struct SpriteStats {
     pub total_sprites: usize,
     pub total_area: f32,
@@ -0,0 +1,98 @@
+//! Tilemap layer rendering
+//!
+//! Composites the sprites of one Tilemap layer into a single image, one cell per
+//! `cell_width x cell_height` pixel block. Sprite lookup is left to the caller, since sprites
+//! usually live in other objects (or other files) than the Tilemap itself.
+
+use crate::tilemap::{Grid, TileCell, Tilemap};
+use crate::unity_objects::ObjectRef;
+use image::{Rgba, RgbaImage};
+
+/// Renders a Tilemap layer to an image
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TilemapRenderer {
+    pub cell_width: u32,
+    pub cell_height: u32,
+}
+
+impl TilemapRenderer {
+    pub fn new(cell_width: u32, cell_height: u32) -> Self {
+        Self {
+            cell_width: cell_width.max(1),
+            cell_height: cell_height.max(1),
+        }
+    }
+
+    /// Cell size in pixels from the Grid's cell size and the sprites' pixels per unit.
+    pub fn for_grid(grid: &Grid, pixels_per_unit: f32) -> Self {
+        Self::new(
+            (grid.cell_size[0] * pixels_per_unit).round() as u32,
+            (grid.cell_size[1] * pixels_per_unit).round() as u32,
+        )
+    }
+
+    /// Render layer `z` over the Tilemap's used bounds, top row (highest y) first.
+    ///
+    /// `resolve` maps a sprite reference to its image (top row first, as exported). Each
+    /// sprite is centered on its cell's tile anchor, mirrored by the sign of the cell matrix
+    /// and tinted by the cell and Tilemap colors. Cells without a resolvable sprite stay
+    /// transparent.
+    pub fn render<F>(&self, tilemap: &Tilemap, z: i32, mut resolve: F) -> RgbaImage
+    where
+        F: FnMut(ObjectRef) -> Option<RgbaImage>,
+    {
+        let Some((min, max)) = tilemap.used_bounds() else {
+            return RgbaImage::new(0, 0);
+        };
+        let columns = (max[0] - min[0] + 1) as u32;
+        let rows = (max[1] - min[1] + 1) as u32;
+        let mut canvas = RgbaImage::new(columns * self.cell_width, rows * self.cell_height);
+
+        for cell in tilemap.cells.values().filter(|c| c.position[2] == z) {
+            let Some(sprite) = cell.sprite.and_then(&mut resolve) else {
+                continue;
+            };
+            let tint = std::array::from_fn(|i| cell.color[i] * tilemap.color[i]);
+            // Anchor point of the cell in canvas pixels, y pointing down.
+            let anchor_x = (cell.position[0] - min[0]) as f32 + tilemap.tile_anchor[0];
+            let anchor_y = (max[1] - cell.position[1]) as f32 + 1.0 - tilemap.tile_anchor[1];
+            let left = (anchor_x * self.cell_width as f32 - sprite.width() as f32 / 2.0).round();
+            let top = (anchor_y * self.cell_height as f32 - sprite.height() as f32 / 2.0).round();
+            blit(&mut canvas, &sprite, left as i64, top as i64, cell, tint);
+        }
+        canvas
+    }
+}
+
+fn blit(
+    canvas: &mut RgbaImage,
+    sprite: &RgbaImage,
+    left: i64,
+    top: i64,
+    cell: &TileCell,
+    tint: [f32; 4],
+) {
+    let (flip_x, flip_y) = cell.flips();
+    for (sx, sy, pixel) in sprite.enumerate_pixels() {
+        let sx = if flip_x { sprite.width() - 1 - sx } else { sx };
+        let sy = if flip_y { sprite.height() - 1 - sy } else { sy };
+        let (x, y) = (left + sx as i64, top + sy as i64);
+        if x < 0 || y < 0 || x >= canvas.width() as i64 || y >= canvas.height() as i64 {
+            continue;
+        }
+        let source: [f32; 4] = std::array::from_fn(|i| pixel[i] as f32 / 255.0 * tint[i]);
+        let target = canvas.get_pixel_mut(x as u32, y as u32);
+        *target = over(source, *target);
+    }
+}
+
+/// Straight-alpha "source over destination".
+fn over(source: [f32; 4], destination: Rgba<u8>) -> Rgba<u8> {
+    let dst: [f32; 4] = std::array::from_fn(|i| destination[i] as f32 / 255.0);
+    let alpha = source[3] + dst[3] * (1.0 - source[3]);
+    if alpha <= 0.0 {
+        return Rgba([0; 4]);
+    }
+    let color = |i: usize| (source[i] * source[3] + dst[i] * dst[3] * (1.0 - source[3])) / alpha;
+    Rgba([color(0), color(1), color(2), alpha].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8))
+}
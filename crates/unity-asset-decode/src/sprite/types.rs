@@ -2,6 +2,7 @@
 //!
 //! This module defines all the data structures used for Unity Sprite processing.
 
+use crate::unity_objects::ObjectRef;
 use serde::{Deserialize, Serialize};
 
 /// Sprite render data
@@ -20,6 +21,23 @@ pub struct SpriteRenderData {
     pub atlas_rect_offset_x: f32,
     pub atlas_rect_offset_y: f32,
     pub downscale_multiplier: f32,
+    /// Render mesh vertex positions in local units, relative to the pivot.
+    pub vertices: Vec<[f32; 3]>,
+    /// Texture coordinates (0..1 over the whole texture), one per vertex when stored.
+    pub uvs: Vec<[f32; 2]>,
+    /// Triangle list indices into `vertices`.
+    pub indices: Vec<u32>,
+}
+
+impl SpriteRenderData {
+    /// Mesh triangles as vertex index triples (incomplete and out-of-range triangles are skipped).
+    pub fn triangles(&self) -> Vec<[u32; 3]> {
+        self.indices
+            .chunks_exact(3)
+            .map(|t| [t[0], t[1], t[2]])
+            .filter(|t| t.iter().all(|&i| (i as usize) < self.vertices.len()))
+            .collect()
+    }
 }
 
 /// Sprite settings
@@ -28,11 +46,37 @@ pub struct SpriteRenderData {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SpriteSettings {
     pub packed: bool,
+    /// `0` = rectangle, `1` = tight.
     pub packing_mode: i32,
+    /// `0` = none, `1` = flip horizontal, `2` = flip vertical, `3` = rotate 180, `4` = rotate 90.
     pub packing_rotation: i32,
     pub mesh_type: i32,
 }
 
+impl SpriteSettings {
+    pub const PACKING_MODE_TIGHT: i32 = 1;
+    pub const ROTATION_FLIP_HORIZONTAL: i32 = 1;
+    pub const ROTATION_FLIP_VERTICAL: i32 = 2;
+    pub const ROTATION_180: i32 = 3;
+    pub const ROTATION_90: i32 = 4;
+
+    /// Decode `m_RD.settingsRaw` (bit 0 packed, bit 1 packing mode, bits 2-5 rotation, bit 6
+    /// mesh type).
+    pub fn from_raw(raw: u32) -> Self {
+        Self {
+            packed: raw & 1 != 0,
+            packing_mode: ((raw >> 1) & 1) as i32,
+            packing_rotation: ((raw >> 2) & 0xf) as i32,
+            mesh_type: ((raw >> 6) & 1) as i32,
+        }
+    }
+
+    /// Tight packing: the atlas region is shared with other sprites outside the mesh.
+    pub fn is_tight(&self) -> bool {
+        self.packing_mode == Self::PACKING_MODE_TIGHT
+    }
+}
+
 /// Sprite rectangle information
 ///
 /// Defines the rectangular area of a sprite within its texture.
@@ -162,10 +206,17 @@ pub struct Sprite {
     pub is_polygon: bool,
     pub render_data: SpriteRenderData,
     pub settings: SpriteSettings,
+    /// `m_PhysicsShape` outlines in local units, relative to the pivot.
+    pub physics_shape: Vec<Vec<[f32; 2]>>,
+    /// `m_RD.secondaryTextures` (2019.2+): `(name, texture)` pairs such as `_NormalMap` and
+    /// `_MaskTex`, sharing the main texture's layout.
+    pub secondary_textures: Vec<(String, ObjectRef)>,
 
     // Atlas reference
     pub atlas_tags: Vec<String>,
     pub sprite_atlas_path_id: Option<i64>,
+    /// `m_RenderDataKey` (2017.1+): this sprite's entry in a [`SpriteAtlas`] render data map.
+    pub render_data_key: Option<SpriteRenderDataKey>,
 }
 
 impl Default for Sprite {
@@ -189,14 +240,18 @@ impl Default for Sprite {
             is_polygon: false,
             render_data: SpriteRenderData::default(),
             settings: SpriteSettings::default(),
+            physics_shape: Vec::new(),
+            secondary_textures: Vec::new(),
             atlas_tags: Vec::new(),
             sprite_atlas_path_id: None,
+            render_data_key: None,
         }
     }
 }
 
 /// Sprite processing configuration
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct SpriteConfig {
     /// Whether to extract sprite images
     pub extract_images: bool,
@@ -206,6 +261,48 @@ pub struct SpriteConfig {
     pub max_sprite_size: Option<(u32, u32)>,
     /// Whether to apply sprite transformations
     pub apply_transformations: bool,
+    /// Whether to clear pixels outside the render mesh of tight-packed sprites
+    pub mask_tight_sprites: bool,
+    /// Whether to also extract the sprite's secondary textures (normal maps, masks)
+    pub extract_secondary_textures: bool,
+}
+
+impl SpriteConfig {
+    /// Set `extract_images`.
+    pub fn with_extract_images(mut self, extract_images: bool) -> Self {
+        self.extract_images = extract_images;
+        self
+    }
+
+    /// Set `process_atlas`.
+    pub fn with_process_atlas(mut self, process_atlas: bool) -> Self {
+        self.process_atlas = process_atlas;
+        self
+    }
+
+    /// Set `max_sprite_size`.
+    pub fn with_max_sprite_size(mut self, max_sprite_size: Option<(u32, u32)>) -> Self {
+        self.max_sprite_size = max_sprite_size;
+        self
+    }
+
+    /// Set `apply_transformations`.
+    pub fn with_apply_transformations(mut self, apply_transformations: bool) -> Self {
+        self.apply_transformations = apply_transformations;
+        self
+    }
+
+    /// Set `mask_tight_sprites`.
+    pub fn with_mask_tight_sprites(mut self, mask_tight_sprites: bool) -> Self {
+        self.mask_tight_sprites = mask_tight_sprites;
+        self
+    }
+
+    /// Set `extract_secondary_textures`.
+    pub fn with_extract_secondary_textures(mut self, extract_secondary_textures: bool) -> Self {
+        self.extract_secondary_textures = extract_secondary_textures;
+        self
+    }
 }
 
 impl Default for SpriteConfig {
@@ -215,6 +312,8 @@ impl Default for SpriteConfig {
             process_atlas: true,
             max_sprite_size: None,
             apply_transformations: true,
+            mask_tight_sprites: true,
+            extract_secondary_textures: true,
         }
     }
 }
@@ -224,6 +323,8 @@ impl Default for SpriteConfig {
 pub struct SpriteResult {
     pub sprite: Sprite,
     pub image_data: Option<Vec<u8>>,
+    /// PNG images of the secondary textures, cropped like `image_data`, keyed by name.
+    pub secondary_images: Vec<(String, Vec<u8>)>,
     pub warnings: Vec<String>,
     pub errors: Vec<String>,
 }
@@ -233,6 +334,7 @@ impl SpriteResult {
         Self {
             sprite,
             image_data: None,
+            secondary_images: Vec::new(),
             warnings: Vec::new(),
             errors: Vec::new(),
         }
@@ -262,15 +364,100 @@ impl SpriteResult {
     pub fn has_image(&self) -> bool {
         self.image_data.is_some()
     }
+
+    /// Write the extracted images into `dir` as `<name>.png`, plus `<name><secondary>.png`
+    /// per secondary image (e.g. `Hero_NormalMap.png`). Returns the written paths.
+    pub fn save_images<P: AsRef<std::path::Path>>(
+        &self,
+        dir: P,
+    ) -> crate::error::Result<Vec<std::path::PathBuf>> {
+        let dir = dir.as_ref();
+        let stem = if self.sprite.name.is_empty() {
+            "Sprite"
+        } else {
+            self.sprite.name.as_str()
+        };
+        let mut written = Vec::new();
+        if let Some(image_data) = &self.image_data {
+            let path = dir.join(format!("{stem}.png"));
+            std::fs::write(&path, image_data)?;
+            written.push(path);
+        }
+        for (name, image_data) in &self.secondary_images {
+            let suffix = if name.starts_with('_') {
+                name.clone()
+            } else {
+                format!("_{name}")
+            };
+            let path = dir.join(format!("{stem}{suffix}.png"));
+            std::fs::write(&path, image_data)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
 }
 
 /// Sprite atlas information
+///
+/// Either built from a set of sprites ([`SpriteProcessor::process_sprite_atlas`]) or parsed
+/// from a SpriteAtlas object (class 687078895, [`SpriteProcessor::parse_sprite_atlas`]), in
+/// which case `render_data_map` says where each packed sprite's pixels are.
+///
+/// [`SpriteProcessor::process_sprite_atlas`]: super::SpriteProcessor::process_sprite_atlas
+/// [`SpriteProcessor::parse_sprite_atlas`]: super::SpriteProcessor::parse_sprite_atlas
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SpriteAtlas {
     pub name: String,
+    /// Texture of the first render data entry (atlases may span several pages).
     pub texture_path_id: i64,
     pub sprites: Vec<SpriteInfo>,
+    /// `m_PackedSpriteNamesToIndex`, in the order of `packed_sprite_refs`.
     pub packed_sprites: Vec<String>,
+    /// `m_PackedSprites`.
+    pub packed_sprite_refs: Vec<ObjectRef>,
+    /// `m_RenderDataMap`, keyed by each sprite's [`Sprite::render_data_key`].
+    pub render_data_map: Vec<(SpriteRenderDataKey, SpriteAtlasData)>,
+    pub tag: String,
+    pub is_variant: bool,
+}
+
+impl SpriteAtlas {
+    /// Render data stored under `key`
+    pub fn render_data(&self, key: &SpriteRenderDataKey) -> Option<&SpriteAtlasData> {
+        self.render_data_map
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, data)| data)
+    }
+
+    /// Render data for `sprite`, looked up by its `m_RenderDataKey`
+    pub fn sprite_data(&self, sprite: &Sprite) -> Option<&SpriteAtlasData> {
+        self.render_data(sprite.render_data_key.as_ref()?)
+    }
+}
+
+/// Key of a sprite's atlas render data: the sprite asset's GUID and its local file ID.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SpriteRenderDataKey {
+    /// GUID words as stored (`data[0]..data[3]`).
+    pub guid: [u32; 4],
+    pub file_id: i64,
+}
+
+/// One `m_RenderDataMap` entry: where a packed sprite's pixels are in the atlas.
+///
+/// Takes the place of the sprite's own `m_RD` texture, rect, offsets and settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpriteAtlasData {
+    pub texture: Option<ObjectRef>,
+    pub alpha_texture: Option<ObjectRef>,
+    pub texture_rect: SpriteRect,
+    pub texture_rect_offset: [f32; 2],
+    pub atlas_rect_offset: [f32; 2],
+    pub downscale_multiplier: f32,
+    pub settings: SpriteSettings,
+    /// `secondaryTextures` (2020.2+).
+    pub secondary_textures: Vec<(String, ObjectRef)>,
 }
 
 /// Helper functions for sprite types
@@ -316,6 +503,11 @@ impl Sprite {
         self.border_x > 0.0 || self.border_y > 0.0 || self.border_z > 0.0 || self.border_w > 0.0
     }
 
+    /// Secondary textures as `(name, texture)` pairs; empty before 2019.2.
+    pub fn secondary_textures(&self) -> &[(String, ObjectRef)] {
+        &self.secondary_textures
+    }
+
     /// Check if sprite is from an atlas
     pub fn is_atlas_sprite(&self) -> bool {
         self.sprite_atlas_path_id.is_some()
@@ -334,6 +526,58 @@ impl Sprite {
             1.0
         }
     }
+
+    /// Region of the texture holding the sprite pixels: `m_RD.textureRect` when set, otherwise
+    /// `m_Rect`.
+    pub fn texture_rect(&self) -> SpriteRect {
+        let rd = &self.render_data;
+        if rd.texture_rect_width > 0.0 && rd.texture_rect_height > 0.0 {
+            SpriteRect {
+                x: rd.texture_rect_x,
+                y: rd.texture_rect_y,
+                width: rd.texture_rect_width,
+                height: rd.texture_rect_height,
+            }
+        } else {
+            self.get_rect()
+        }
+    }
+
+    /// Convert a local-unit point (relative to the pivot) to sprite pixels, origin at the
+    /// bottom-left of `m_Rect`.
+    pub fn local_to_pixels(&self, point: [f32; 2]) -> [f32; 2] {
+        [
+            point[0] * self.pixels_to_units + self.rect_width * self.pivot_x,
+            point[1] * self.pixels_to_units + self.rect_height * self.pivot_y,
+        ]
+    }
+
+    /// Sprite outline polygons in sprite pixels (see [`Self::local_to_pixels`]).
+    ///
+    /// Uses `m_PhysicsShape` when the sprite has one, otherwise the boundary loops of the
+    /// render mesh. Empty for sprites without either.
+    pub fn outline(&self) -> Vec<Vec<[f32; 2]>> {
+        if !self.physics_shape.is_empty() {
+            return self
+                .physics_shape
+                .iter()
+                .map(|path| path.iter().map(|&p| self.local_to_pixels(p)).collect())
+                .collect();
+        }
+
+        let vertices = &self.render_data.vertices;
+        super::mask::boundary_loops(&self.render_data.triangles())
+            .into_iter()
+            .map(|path| {
+                path.into_iter()
+                    .map(|i| {
+                        let v = vertices[i as usize];
+                        self.local_to_pixels([v[0], v[1]])
+                    })
+                    .collect()
+            })
+            .collect()
+    }
 }
 
 impl SpriteRect {
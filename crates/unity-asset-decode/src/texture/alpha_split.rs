@@ -0,0 +1,131 @@
+//! ETC1 alpha-split textures
+//!
+//! ETC1 has no alpha channel, so older mobile builds store an RGBA texture as an RGB texture
+//! plus a companion alpha texture: named after the main one (`<name> alpha`, `<name>_alpha`),
+//! bound as `_AlphaTex` on the material, or referenced as a sprite's `m_RD.alphaTexture`.
+//! Neither half decodes correctly alone; [`merge_alpha`] recombines them.
+
+use super::formats::TextureFormat;
+use crate::asset::SerializedFile;
+use crate::error::{BinaryError, Result};
+use image::RgbaImage;
+use indexmap::IndexMap;
+use unity_asset_core::{UnityValue, class_ids};
+
+type Fields = IndexMap<String, UnityValue>;
+
+/// Name suffixes (case-insensitive) that mark a texture as another texture's alpha half.
+pub const ALPHA_NAME_SUFFIXES: &[&str] = &[" alpha", "_alpha", "-alpha", "alpha", "_a"];
+
+/// Copy the alpha half into `rgb`'s alpha channel.
+///
+/// The alpha comes from the companion's alpha channel when it is an `Alpha8` texture and from
+/// its red channel otherwise (ETC1 or RGB companions store the mask as grey).
+pub fn merge_alpha(
+    mut rgb: RgbaImage,
+    alpha: &RgbaImage,
+    alpha_format: TextureFormat,
+) -> Result<RgbaImage> {
+    if rgb.dimensions() != alpha.dimensions() {
+        return Err(BinaryError::invalid_data(format!(
+            "Alpha texture is {}x{} but the RGB texture is {}x{}",
+            alpha.width(),
+            alpha.height(),
+            rgb.width(),
+            rgb.height()
+        )));
+    }
+    let channel = if alpha_format == TextureFormat::Alpha8 {
+        3
+    } else {
+        0
+    };
+    for (pixel, mask) in rgb.pixels_mut().zip(alpha.pixels()) {
+        pixel.0[3] = mask.0[channel];
+    }
+    Ok(rgb)
+}
+
+/// Find the alpha companion of the Texture2D `texture_path_id` in `asset` (best-effort).
+///
+/// Explicit references win: a material binding the texture as `_MainTex` with an `_AlphaTex`,
+/// or a sprite whose `m_RD.alphaTexture` is set. Otherwise a Texture2D named after the texture
+/// with one of [`ALPHA_NAME_SUFFIXES`] is used. Only companions in the same file are returned.
+pub fn find_alpha_companion(asset: &SerializedFile, texture_path_id: i64) -> Option<i64> {
+    let texture = asset
+        .find_object_handle(texture_path_id)
+        .filter(|h| h.class_id() == class_ids::TEXTURE_2D)?;
+
+    for handle in asset.object_handles() {
+        let companion = match handle.class_id() {
+            class_ids::MATERIAL => handle.read().ok().and_then(|material| {
+                let textures = material_textures(material.class.properties());
+                let main = textures.iter().find(|(name, _)| name == "_MainTex")?.1;
+                let alpha = textures.iter().find(|(name, _)| name == "_AlphaTex")?.1;
+                (main == (0, texture_path_id)).then_some(alpha)
+            }),
+            class_ids::SPRITE => handle.read().ok().and_then(|sprite| {
+                let rd = sprite.class.properties().get("m_RD")?.as_object()?;
+                let main = rd.get("texture").and_then(pptr)?;
+                let alpha = rd.get("alphaTexture").and_then(pptr)?;
+                (main == (0, texture_path_id)).then_some(alpha)
+            }),
+            _ => None,
+        };
+        if let Some((0, path_id)) = companion
+            && path_id != texture_path_id
+        {
+            return Some(path_id);
+        }
+    }
+
+    let name = texture.peek_name().ok().flatten()?.to_lowercase();
+    asset
+        .object_handles()
+        .filter(|h| h.class_id() == class_ids::TEXTURE_2D && h.path_id() != texture_path_id)
+        .find(|h| {
+            h.peek_name().ok().flatten().is_some_and(|candidate| {
+                let candidate = candidate.to_lowercase();
+                ALPHA_NAME_SUFFIXES
+                    .iter()
+                    .any(|suffix| candidate.strip_suffix(suffix) == Some(name.as_str()))
+            })
+        })
+        .map(|h| h.path_id())
+}
+
+/// Material texture bindings (`m_SavedProperties.m_TexEnvs`) as `(property, (file_id, path_id))`.
+pub(crate) fn material_textures(properties: &Fields) -> Vec<(String, (i32, i64))> {
+    let Some(UnityValue::Array(envs)) = properties
+        .get("m_SavedProperties")
+        .and_then(UnityValue::as_object)
+        .and_then(|saved| saved.get("m_TexEnvs"))
+    else {
+        return Vec::new();
+    };
+    envs.iter()
+        .filter_map(|entry| {
+            // `map<FastPropertyName, UnityTexEnv>` entries arrive as `[first, second]` pairs or
+            // as `{first, second}` objects, with the key as a string or `{name}`.
+            let (first, second) = match entry {
+                UnityValue::Array(pair) if pair.len() == 2 => (&pair[0], &pair[1]),
+                UnityValue::Object(pair) => (pair.get("first")?, pair.get("second")?),
+                _ => return None,
+            };
+            let name = match first {
+                UnityValue::String(name) => name.clone(),
+                UnityValue::Object(key) => key.get("name")?.as_str()?.to_string(),
+                _ => return None,
+            };
+            let texture = second.as_object()?.get("m_Texture").and_then(pptr)?;
+            Some((name, texture))
+        })
+        .collect()
+}
+
+fn pptr(value: &UnityValue) -> Option<(i32, i64)> {
+    let fields = value.as_object()?;
+    let file_id = fields.get("m_FileID")?.as_i64()?;
+    let path_id = fields.get("m_PathID")?.as_i64()?;
+    (path_id != 0).then_some((file_id as i32, path_id))
+}
@@ -0,0 +1,314 @@
+//! Channel operations on decoded textures
+//!
+//! Unity packs unrelated data into the channels of one texture: metallic in R and smoothness in
+//! A, DXT5nm normal maps with X in alpha and Y in green, BC5 normal maps with only X and Y. A
+//! straight PNG export of those looks wrong; the functions here pull channels apart, reorder
+//! them, or rebuild a standard tangent-space normal map.
+//!
+//! [`TransformRule`]s attach one [`ChannelTransform`] to the textures matching a name pattern or
+//! bound to a material slot, and [`ExportOptions`](super::ExportOptions) applies them on export.
+
+use crate::asset::SerializedFile;
+use crate::error::{BinaryError, Result};
+use image::{GrayImage, Luma, Rgba, RgbaImage};
+use std::fmt;
+use std::str::FromStr;
+use unity_asset_core::class_ids;
+
+/// One channel of an RGBA image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Channel {
+    R,
+    G,
+    B,
+    A,
+}
+
+impl Channel {
+    /// Index of the channel in an RGBA pixel
+    pub fn index(self) -> usize {
+        match self {
+            Channel::R => 0,
+            Channel::G => 1,
+            Channel::B => 2,
+            Channel::A => 3,
+        }
+    }
+
+    /// Parse `r`, `g`, `b` or `a` (case-insensitive)
+    pub fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_lowercase() {
+            'r' => Some(Channel::R),
+            'g' => Some(Channel::G),
+            'b' => Some(Channel::B),
+            'a' => Some(Channel::A),
+            _ => None,
+        }
+    }
+}
+
+/// Copy one channel into a grayscale image
+pub fn extract_channel(image: &RgbaImage, channel: Channel) -> GrayImage {
+    let index = channel.index();
+    GrayImage::from_fn(image.width(), image.height(), |x, y| {
+        Luma([image.get_pixel(x, y).0[index]])
+    })
+}
+
+/// Reorder channels: output channel `i` takes the source channel named by `pattern[i]`
+///
+/// `pattern` has four characters from `rgba01`; `0` and `1` fill the channel with 0 or 255.
+/// `"agbr"` swaps red and alpha, `"rgb1"` drops alpha.
+pub fn swizzle(image: &RgbaImage, pattern: &str) -> Result<RgbaImage> {
+    let sources = parse_swizzle(pattern)?;
+    let mut out = image.clone();
+    for pixel in out.pixels_mut() {
+        let source = pixel.0;
+        for (value, from) in pixel.0.iter_mut().zip(sources) {
+            *value = match from {
+                SwizzleSource::Channel(channel) => source[channel.index()],
+                SwizzleSource::Zero => 0,
+                SwizzleSource::One => 255,
+            };
+        }
+    }
+    Ok(out)
+}
+
+/// Rebuild a DXT5nm normal map (X in alpha, Y in green) as a standard RGB normal map
+///
+/// Matches Unity's `UnpackNormalDXT5nm`: `xy = (a, g) * 2 - 1`, `z = sqrt(1 - saturate(x² + y²))`.
+/// The output stores `(x, y, z) * 0.5 + 0.5` in RGB with opaque alpha, so X and Y keep their
+/// exact byte values.
+pub fn reconstruct_normal_dxt5nm(image: &RgbaImage) -> RgbaImage {
+    reconstruct_normal(image, Channel::A, Channel::G)
+}
+
+/// Rebuild a BC5 normal map (X in red, Y in green, no Z) as a standard RGB normal map
+///
+/// Same math as [`reconstruct_normal_dxt5nm`] with X read from red.
+pub fn reconstruct_normal_bc5(image: &RgbaImage) -> RgbaImage {
+    reconstruct_normal(image, Channel::R, Channel::G)
+}
+
+fn reconstruct_normal(image: &RgbaImage, x: Channel, y: Channel) -> RgbaImage {
+    let unpack = |v: u8| v as f32 / 255.0 * 2.0 - 1.0;
+    let pack = |v: f32| ((v + 1.0) * 0.5 * 255.0).round().clamp(0.0, 255.0) as u8;
+
+    let mut out = RgbaImage::new(image.width(), image.height());
+    for (dst, src) in out.pixels_mut().zip(image.pixels()) {
+        let nx = unpack(src.0[x.index()]);
+        let ny = unpack(src.0[y.index()]);
+        let nz = (1.0 - (nx * nx + ny * ny).clamp(0.0, 1.0)).sqrt();
+        *dst = Rgba([pack(nx), pack(ny), pack(nz), 255]);
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy)]
+enum SwizzleSource {
+    Channel(Channel),
+    Zero,
+    One,
+}
+
+fn parse_swizzle(pattern: &str) -> Result<[SwizzleSource; 4]> {
+    let invalid = || {
+        BinaryError::invalid_data(format!(
+            "Invalid swizzle '{}': expected four characters from 'rgba01'",
+            pattern
+        ))
+    };
+    let mut sources = [SwizzleSource::Zero; 4];
+    let mut chars = pattern.chars();
+    for source in &mut sources {
+        *source = match chars.next().ok_or_else(invalid)? {
+            '0' => SwizzleSource::Zero,
+            '1' => SwizzleSource::One,
+            c => SwizzleSource::Channel(Channel::from_char(c).ok_or_else(invalid)?),
+        };
+    }
+    if chars.next().is_some() {
+        return Err(invalid());
+    }
+    Ok(sources)
+}
+
+/// A named channel operation, applied to a whole decoded image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChannelTransform {
+    /// One channel as an opaque grey image (`r`, `g`, `b`, `a`)
+    Extract(Channel),
+    /// A [`swizzle`] pattern (`swizzle:agbr`, or just `agbr`)
+    Swizzle(String),
+    /// [`reconstruct_normal_dxt5nm`] (`dxt5nm`)
+    NormalDxt5nm,
+    /// [`reconstruct_normal_bc5`] (`bc5`)
+    NormalBc5,
+}
+
+impl ChannelTransform {
+    /// Apply the transform; the result is always RGBA so it can go straight to an exporter
+    pub fn apply(&self, image: &RgbaImage) -> Result<RgbaImage> {
+        match self {
+            ChannelTransform::Extract(channel) => {
+                let gray = extract_channel(image, *channel);
+                Ok(RgbaImage::from_fn(gray.width(), gray.height(), |x, y| {
+                    let v = gray.get_pixel(x, y).0[0];
+                    Rgba([v, v, v, 255])
+                }))
+            }
+            ChannelTransform::Swizzle(pattern) => swizzle(image, pattern),
+            ChannelTransform::NormalDxt5nm => Ok(reconstruct_normal_dxt5nm(image)),
+            ChannelTransform::NormalBc5 => Ok(reconstruct_normal_bc5(image)),
+        }
+    }
+}
+
+impl FromStr for ChannelTransform {
+    type Err = BinaryError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let lower = s.trim().to_ascii_lowercase();
+        match lower.as_str() {
+            "dxt5nm" => return Ok(ChannelTransform::NormalDxt5nm),
+            "bc5" => return Ok(ChannelTransform::NormalBc5),
+            _ => {}
+        }
+        let mut chars = lower.chars();
+        if let (Some(c), None) = (chars.next(), chars.next())
+            && let Some(channel) = Channel::from_char(c)
+        {
+            return Ok(ChannelTransform::Extract(channel));
+        }
+        let pattern = lower.strip_prefix("swizzle:").unwrap_or(&lower);
+        parse_swizzle(pattern)?;
+        Ok(ChannelTransform::Swizzle(pattern.to_string()))
+    }
+}
+
+impl fmt::Display for ChannelTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChannelTransform::Extract(channel) => {
+                write!(f, "{}", ["r", "g", "b", "a"][channel.index()])
+            }
+            ChannelTransform::Swizzle(pattern) => write!(f, "swizzle:{}", pattern),
+            ChannelTransform::NormalDxt5nm => write!(f, "dxt5nm"),
+            ChannelTransform::NormalBc5 => write!(f, "bc5"),
+        }
+    }
+}
+
+/// Which textures a [`TransformRule`] applies to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransformTarget {
+    /// Texture names matching a case-insensitive glob (`*`, `?`)
+    Name(String),
+    /// Textures bound to this material property, e.g. `_BumpMap` (see [`texture_material_slots`])
+    Slot(String),
+}
+
+/// A [`ChannelTransform`] for the textures matching `target`
+///
+/// Parses from `<glob>=<transform>` or `slot:<property>=<transform>`, e.g. `*_n=dxt5nm` or
+/// `slot:_MetallicGlossMap=a`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransformRule {
+    pub target: TransformTarget,
+    pub transform: ChannelTransform,
+}
+
+impl TransformRule {
+    /// Rule for texture names matching `pattern`
+    pub fn by_name(pattern: impl Into<String>, transform: ChannelTransform) -> Self {
+        Self {
+            target: TransformTarget::Name(pattern.into()),
+            transform,
+        }
+    }
+
+    /// Rule for textures bound to the material property `slot`
+    pub fn by_slot(slot: impl Into<String>, transform: ChannelTransform) -> Self {
+        Self {
+            target: TransformTarget::Slot(slot.into()),
+            transform,
+        }
+    }
+
+    /// Whether the rule applies to a texture called `name` bound to the material `slots`
+    pub fn matches(&self, name: &str, slots: &[String]) -> bool {
+        match &self.target {
+            TransformTarget::Name(pattern) => {
+                glob_match(&pattern.to_lowercase(), &name.to_lowercase())
+            }
+            TransformTarget::Slot(slot) => slots.iter().any(|s| s == slot),
+        }
+    }
+}
+
+impl FromStr for TransformRule {
+    type Err = BinaryError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (target, transform) = s.rsplit_once('=').ok_or_else(|| {
+            BinaryError::invalid_data(format!(
+                "Invalid transform rule '{}': expected '<pattern>=<transform>'",
+                s
+            ))
+        })?;
+        let transform = transform.parse()?;
+        Ok(match target.strip_prefix("slot:") {
+            Some(slot) => Self::by_slot(slot, transform),
+            None => Self::by_name(target, transform),
+        })
+    }
+}
+
+/// Material properties (`m_SavedProperties.m_TexEnvs` keys) that bind the Texture2D
+/// `texture_path_id`, across the materials of `asset` (best-effort)
+pub fn texture_material_slots(asset: &SerializedFile, texture_path_id: i64) -> Vec<String> {
+    let mut slots: Vec<String> = Vec::new();
+    for handle in asset.object_handles() {
+        if handle.class_id() != class_ids::MATERIAL {
+            continue;
+        }
+        let Ok(material) = handle.read() else {
+            continue;
+        };
+        for (name, texture) in super::alpha_split::material_textures(material.class.properties()) {
+            if texture == (0, texture_path_id) && !slots.contains(&name) {
+                slots.push(name);
+            }
+        }
+    }
+    slots
+}
+
+/// `*` / `?` glob match over bytes.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let (pattern, text) = (pattern.as_bytes(), text.as_bytes());
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((sp, st)) => {
+                    p = sp + 1;
+                    t = st + 1;
+                    star = Some((sp, st + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
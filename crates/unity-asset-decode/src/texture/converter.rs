@@ -5,12 +5,14 @@
 
 use super::decoders::TextureDecoder;
 use super::formats::TextureFormat;
-use super::types::Texture2D;
+use super::types::{GLTextureSettings, StreamingInfo, Texture2D, Texture3D};
+use crate::bundle::AssetBundle;
 use crate::error::{BinaryError, Result};
 use crate::object::UnityObject;
 use crate::unity_version::UnityVersion;
 use image::RgbaImage;
-use unity_asset_core::UnityValue;
+use indexmap::IndexMap;
+use unity_asset_core::{UnityClass, UnityValue};
 
 /// Main texture converter
 ///
@@ -46,19 +48,6 @@ impl Texture2DConverter {
     }
 
     fn try_parse_typetree(&self, obj: &UnityObject) -> Result<Texture2D> {
-        fn as_i32(v: &UnityValue) -> Option<i32> {
-            v.as_i64().and_then(|n| i32::try_from(n).ok())
-        }
-        fn as_u32(v: &UnityValue) -> Option<u32> {
-            v.as_i64().and_then(|n| u32::try_from(n).ok())
-        }
-        fn as_u64(v: &UnityValue) -> Option<u64> {
-            v.as_i64().and_then(|n| u64::try_from(n).ok())
-        }
-        fn as_f32(v: &UnityValue) -> Option<f32> {
-            v.as_f64().map(|n| n as f32)
-        }
-
         let props = obj.class.properties();
 
         let name = props
@@ -110,55 +99,15 @@ impl Texture2DConverter {
             ..Default::default()
         };
 
-        if let Some(UnityValue::Object(settings)) = props.get("m_TextureSettings") {
-            texture.texture_settings.filter_mode =
-                settings.get("m_FilterMode").and_then(as_i32).unwrap_or(0);
-            texture.texture_settings.aniso = settings.get("m_Aniso").and_then(as_i32).unwrap_or(0);
-            texture.texture_settings.mip_bias =
-                settings.get("m_MipBias").and_then(as_f32).unwrap_or(0.0);
-            texture.texture_settings.wrap_u = settings.get("m_WrapU").and_then(as_i32).unwrap_or(0);
-            texture.texture_settings.wrap_v = settings.get("m_WrapV").and_then(as_i32).unwrap_or(0);
-            texture.texture_settings.wrap_w = settings.get("m_WrapW").and_then(as_i32).unwrap_or(0);
+        if let Some(settings) = parse_texture_settings(props) {
+            texture.texture_settings = settings;
         }
-
-        // Embedded bytes (`image_data` in UnityPy; some TypeTrees may use "image data").
-        let image_data_value = props
-            .get("image_data")
-            .or_else(|| props.get("image data"))
-            .or_else(|| props.get("m_ImageData"));
-        if let Some(v) = image_data_value {
-            match v {
-                UnityValue::Bytes(b) => {
-                    texture.data_size = b.len() as i32;
-                    texture.image_data = b.clone();
-                }
-                UnityValue::Array(items) => {
-                    let mut bytes = Vec::with_capacity(items.len());
-                    for item in items {
-                        let Some(n) = item.as_i64() else {
-                            break;
-                        };
-                        let Ok(b) = u8::try_from(n) else {
-                            break;
-                        };
-                        bytes.push(b);
-                    }
-                    texture.data_size = bytes.len() as i32;
-                    texture.image_data = bytes;
-                }
-                _ => {}
-            }
+        if let Some(image_data) = parse_image_data(props) {
+            texture.data_size = image_data.len() as i32;
+            texture.image_data = image_data;
         }
-
-        // Streamed texture data: `m_StreamData: { path, offset, size }`
-        if let Some(UnityValue::Object(stream_obj)) = props.get("m_StreamData") {
-            texture.stream_info.path = stream_obj
-                .get("path")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string();
-            texture.stream_info.offset = stream_obj.get("offset").and_then(as_u64).unwrap_or(0);
-            texture.stream_info.size = stream_obj.get("size").and_then(as_u32).unwrap_or(0);
+        if let Some(stream_info) = parse_stream_info(props) {
+            texture.stream_info = stream_info;
         }
 
         if texture.width <= 0 || texture.height <= 0 {
@@ -279,6 +228,23 @@ impl Texture2DConverter {
         }
     }
 
+    /// Fill in `image_data` of a streamed texture from the `.resS` node of `bundle`
+    ///
+    /// `stream_info.path` (`archive:/CAB-<hash>/CAB-<hash>.resS`) is resolved with
+    /// [`AssetBundle::find_resource_node`]. Does nothing when the texture is not streamed or
+    /// already has its bytes. A resource that is not in `bundle` fails with
+    /// [`BinaryError::ExternalResourceMissing`] naming the path; load the bundle holding it and
+    /// call this again with that one.
+    pub fn resolve_stream_data(&self, texture: &mut Texture2D, bundle: &AssetBundle) -> Result<()> {
+        if !texture.is_streamed() || texture.has_image_data() {
+            return Ok(());
+        }
+        let stream = &texture.stream_info;
+        texture.image_data =
+            bundle.read_resource(&stream.path, stream.offset, stream.size as u64)?;
+        Ok(())
+    }
+
     /// Decode texture to RGBA image
     ///
     /// This method uses the texture decoder to convert texture data to RGBA format
@@ -286,7 +252,209 @@ impl Texture2DConverter {
         // Use the texture decoder to decode the image
         self.decoder.decode(texture)
     }
+
+    /// Store `texture` back into the TypeTree properties of a Texture2D object
+    ///
+    /// Updates the dimensions, format, mip and size fields and the embedded image bytes, and
+    /// resets `m_StreamData` from `texture.stream_info`. Only fields the object's layout already
+    /// has are written, so the result serializes with the object's original TypeTree.
+    pub fn write_to_class(&self, texture: &Texture2D, class: &mut UnityClass) -> Result<()> {
+        let props = class.properties_mut();
+        let image_key = ["image_data", "image data", "m_ImageData"]
+            .into_iter()
+            .find(|key| props.contains_key(*key))
+            .ok_or_else(|| {
+                BinaryError::invalid_data("Texture2D typetree has no image data field")
+            })?;
+
+        let mut set = |key: &str, value: UnityValue| {
+            if let Some(slot) = props.get_mut(key) {
+                *slot = value;
+            }
+        };
+        set("m_Width", UnityValue::Integer(texture.width as i64));
+        set("m_Height", UnityValue::Integer(texture.height as i64));
+        set(
+            "m_CompleteImageSize",
+            UnityValue::Integer(texture.complete_image_size as i64),
+        );
+        set(
+            "m_TextureFormat",
+            UnityValue::Integer(texture.format as i32 as i64),
+        );
+        set("m_MipMap", UnityValue::Bool(texture.mip_map));
+        set("m_MipCount", UnityValue::Integer(texture.mip_count as i64));
+        set(
+            "m_ImageCount",
+            UnityValue::Integer(texture.image_count as i64),
+        );
+        set(image_key, UnityValue::Bytes(texture.image_data.clone()));
+
+        if let Some(UnityValue::Object(stream)) = props.get_mut("m_StreamData") {
+            let info = &texture.stream_info;
+            for (key, value) in [
+                ("path", UnityValue::String(info.path.clone())),
+                ("offset", UnityValue::Integer(info.offset as i64)),
+                ("size", UnityValue::Integer(info.size as i64)),
+            ] {
+                if let Some(slot) = stream.get_mut(key) {
+                    *slot = value;
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 // Legacy compatibility - alias for the old processor name
 pub type Texture2DProcessor = Texture2DConverter;
+
+/// Texture3D converter
+///
+/// Converts Texture3D (class 117) objects. Before 2019.1 `m_Format` is a `TextureFormat`;
+/// from 2019.1 it is a `GraphicsFormat` and is mapped with
+/// [`TextureFormat::from_graphics_format`].
+pub struct Texture3DConverter {
+    version: UnityVersion,
+}
+
+impl Texture3DConverter {
+    /// Create a new Texture3D converter
+    pub fn new(version: UnityVersion) -> Self {
+        Self { version }
+    }
+
+    /// Convert Unity object to Texture3D
+    pub fn from_unity_object(&self, obj: &UnityObject) -> Result<Texture3D> {
+        self.from_typetree(obj.class.properties())
+    }
+
+    /// Convert TypeTree properties to Texture3D
+    pub fn from_typetree(&self, props: &IndexMap<String, UnityValue>) -> Result<Texture3D> {
+        let (format, graphics_format) = match props.get("m_TextureFormat").and_then(as_i32) {
+            Some(format) => (TextureFormat::from(format), None),
+            None => match props.get("m_Format").and_then(as_i32) {
+                Some(format) if self.version.capabilities().texture_graphics_format => {
+                    (TextureFormat::from_graphics_format(format), Some(format))
+                }
+                Some(format) => (TextureFormat::from(format), None),
+                None => (TextureFormat::Unknown, None),
+            },
+        };
+
+        let mut texture = Texture3D {
+            name: props
+                .get("m_Name")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            width: props.get("m_Width").and_then(as_i32).unwrap_or(0),
+            height: props.get("m_Height").and_then(as_i32).unwrap_or(0),
+            depth: props.get("m_Depth").and_then(as_i32).unwrap_or(0),
+            format,
+            graphics_format,
+            mip_count: props.get("m_MipCount").and_then(as_i32).unwrap_or(1),
+            is_readable: props
+                .get("m_IsReadable")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            color_space: props.get("m_ColorSpace").and_then(as_i32).unwrap_or(0),
+            ..Default::default()
+        };
+        if let Some(settings) = parse_texture_settings(props) {
+            texture.texture_settings = settings;
+        }
+        if let Some(image_data) = parse_image_data(props) {
+            texture.data_size = image_data.len() as i32;
+            texture.image_data = image_data;
+        }
+        if let Some(stream_info) = parse_stream_info(props) {
+            texture.stream_info = stream_info;
+        }
+
+        if !texture.has_valid_dimensions() {
+            return Err(BinaryError::invalid_data(
+                "Texture3D typetree missing dimensions",
+            ));
+        }
+        if texture.image_data.is_empty() && !texture.is_streamed() {
+            return Err(BinaryError::invalid_data(
+                "Texture3D typetree did not contain image bytes or stream data",
+            ));
+        }
+
+        Ok(texture)
+    }
+}
+
+fn as_i32(v: &UnityValue) -> Option<i32> {
+    v.as_i64().and_then(|n| i32::try_from(n).ok())
+}
+
+fn as_u32(v: &UnityValue) -> Option<u32> {
+    v.as_i64().and_then(|n| u32::try_from(n).ok())
+}
+
+fn as_u64(v: &UnityValue) -> Option<u64> {
+    v.as_i64().and_then(|n| u64::try_from(n).ok())
+}
+
+fn as_f32(v: &UnityValue) -> Option<f32> {
+    v.as_f64().map(|n| n as f32)
+}
+
+/// `m_TextureSettings`
+fn parse_texture_settings(props: &IndexMap<String, UnityValue>) -> Option<GLTextureSettings> {
+    let UnityValue::Object(settings) = props.get("m_TextureSettings")? else {
+        return None;
+    };
+    Some(GLTextureSettings {
+        filter_mode: settings.get("m_FilterMode").and_then(as_i32).unwrap_or(0),
+        aniso: settings.get("m_Aniso").and_then(as_i32).unwrap_or(0),
+        mip_bias: settings.get("m_MipBias").and_then(as_f32).unwrap_or(0.0),
+        wrap_u: settings.get("m_WrapU").and_then(as_i32).unwrap_or(0),
+        wrap_v: settings.get("m_WrapV").and_then(as_i32).unwrap_or(0),
+        wrap_w: settings.get("m_WrapW").and_then(as_i32).unwrap_or(0),
+    })
+}
+
+/// Embedded bytes (`image_data` in UnityPy; some TypeTrees may use "image data").
+fn parse_image_data(props: &IndexMap<String, UnityValue>) -> Option<Vec<u8>> {
+    let value = props
+        .get("image_data")
+        .or_else(|| props.get("image data"))
+        .or_else(|| props.get("m_ImageData"))?;
+    match value {
+        UnityValue::Bytes(b) => Some(b.clone()),
+        UnityValue::Array(items) => {
+            let mut bytes = Vec::with_capacity(items.len());
+            for item in items {
+                let Some(n) = item.as_i64() else {
+                    break;
+                };
+                let Ok(b) = u8::try_from(n) else {
+                    break;
+                };
+                bytes.push(b);
+            }
+            Some(bytes)
+        }
+        _ => None,
+    }
+}
+
+/// Streamed texture data: `m_StreamData: { path, offset, size }`
+fn parse_stream_info(props: &IndexMap<String, UnityValue>) -> Option<StreamingInfo> {
+    let UnityValue::Object(stream_obj) = props.get("m_StreamData")? else {
+        return None;
+    };
+    Some(StreamingInfo {
+        path: stream_obj
+            .get("path")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        offset: stream_obj.get("offset").and_then(as_u64).unwrap_or(0),
+        size: stream_obj.get("size").and_then(as_u32).unwrap_or(0),
+    })
+}
@@ -5,7 +5,7 @@
 use super::{Decoder, create_rgba_image, validate_dimensions};
 use crate::error::{BinaryError, Result};
 use crate::texture::formats::TextureFormat;
-use crate::texture::types::Texture2D;
+use crate::texture::types::{DecodedPixels, PixelLayout, Texture2D};
 use image::RgbaImage;
 
 /// Decoder for basic uncompressed texture formats
@@ -18,7 +18,7 @@ impl BasicDecoder {
     }
 
     /// Decode RGBA32 format (R8G8B8A8)
-    fn decode_rgba32(&self, data: &[u8], width: u32, height: u32) -> Result<RgbaImage> {
+    fn decode_rgba32(&self, data: &[u8], width: u32, height: u32) -> Result<Vec<u8>> {
         validate_dimensions(width, height)?;
 
         let expected_size = (width * height * 4) as usize;
@@ -31,11 +31,11 @@ impl BasicDecoder {
         }
 
         // RGBA32 is already in the correct format
-        create_rgba_image(data[..expected_size].to_vec(), width, height)
+        Ok(data[..expected_size].to_vec())
     }
 
     /// Decode RGB24 format (R8G8B8)
-    fn decode_rgb24(&self, data: &[u8], width: u32, height: u32) -> Result<RgbaImage> {
+    fn decode_rgb24(&self, data: &[u8], width: u32, height: u32) -> Result<Vec<u8>> {
         validate_dimensions(width, height)?;
 
         let expected_size = (width * height * 3) as usize;
@@ -56,11 +56,11 @@ impl BasicDecoder {
             rgba_data.push(255); // A (fully opaque)
         }
 
-        create_rgba_image(rgba_data, width, height)
+        Ok(rgba_data)
     }
 
     /// Decode ARGB32 format (A8R8G8B8)
-    fn decode_argb32(&self, data: &[u8], width: u32, height: u32) -> Result<RgbaImage> {
+    fn decode_argb32(&self, data: &[u8], width: u32, height: u32) -> Result<Vec<u8>> {
         validate_dimensions(width, height)?;
 
         let expected_size = (width * height * 4) as usize;
@@ -81,11 +81,19 @@ impl BasicDecoder {
             rgba_data.push(chunk[0]); // A (from position 0)
         }
 
-        create_rgba_image(rgba_data, width, height)
+        Ok(rgba_data)
     }
 
     /// Decode BGRA32 format (B8G8R8A8)
-    fn decode_bgra32(&self, data: &[u8], width: u32, height: u32) -> Result<RgbaImage> {
+    ///
+    /// With `to_rgba = false` the bytes are returned as stored (BGRA), skipping the swizzle.
+    fn decode_bgra32(
+        &self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        to_rgba: bool,
+    ) -> Result<Vec<u8>> {
         validate_dimensions(width, height)?;
 
         let expected_size = (width * height * 4) as usize;
@@ -97,20 +105,19 @@ impl BasicDecoder {
             )));
         }
 
-        // Convert BGRA32 to RGBA32 by swapping R and B channels
-        let mut rgba_data = Vec::with_capacity(expected_size);
-        for chunk in data[..expected_size].chunks_exact(4) {
-            rgba_data.push(chunk[2]); // R (from B position)
-            rgba_data.push(chunk[1]); // G (unchanged)
-            rgba_data.push(chunk[0]); // B (from R position)
-            rgba_data.push(chunk[3]); // A (unchanged)
+        let mut out = data[..expected_size].to_vec();
+        if to_rgba {
+            // Convert BGRA32 to RGBA32 by swapping R and B channels
+            for px in out.chunks_exact_mut(4) {
+                px.swap(0, 2);
+            }
         }
 
-        create_rgba_image(rgba_data, width, height)
+        Ok(out)
     }
 
     /// Decode Alpha8 format (single channel alpha)
-    fn decode_alpha8(&self, data: &[u8], width: u32, height: u32) -> Result<RgbaImage> {
+    fn decode_alpha8(&self, data: &[u8], width: u32, height: u32) -> Result<Vec<u8>> {
         validate_dimensions(width, height)?;
 
         let expected_size = (width * height) as usize;
@@ -131,11 +138,11 @@ impl BasicDecoder {
             rgba_data.push(alpha); // A (from source)
         }
 
-        create_rgba_image(rgba_data, width, height)
+        Ok(rgba_data)
     }
 
     /// Decode RGBA4444 format (4 bits per channel)
-    fn decode_rgba4444(&self, data: &[u8], width: u32, height: u32) -> Result<RgbaImage> {
+    fn decode_rgba4444(&self, data: &[u8], width: u32, height: u32) -> Result<Vec<u8>> {
         validate_dimensions(width, height)?;
 
         let expected_size = (width * height * 2) as usize; // 2 bytes per pixel
@@ -165,11 +172,11 @@ impl BasicDecoder {
             rgba_data.push(a << 4 | a);
         }
 
-        create_rgba_image(rgba_data, width, height)
+        Ok(rgba_data)
     }
 
     /// Decode ARGB4444 format (4 bits per channel, ARGB order)
-    fn decode_argb4444(&self, data: &[u8], width: u32, height: u32) -> Result<RgbaImage> {
+    fn decode_argb4444(&self, data: &[u8], width: u32, height: u32) -> Result<Vec<u8>> {
         validate_dimensions(width, height)?;
 
         let expected_size = (width * height * 2) as usize; // 2 bytes per pixel
@@ -199,11 +206,11 @@ impl BasicDecoder {
             rgba_data.push(a << 4 | a); // A
         }
 
-        create_rgba_image(rgba_data, width, height)
+        Ok(rgba_data)
     }
 
     /// Decode RGB565 format (5-6-5 bits per channel)
-    fn decode_rgb565(&self, data: &[u8], width: u32, height: u32) -> Result<RgbaImage> {
+    fn decode_rgb565(&self, data: &[u8], width: u32, height: u32) -> Result<Vec<u8>> {
         validate_dimensions(width, height)?;
 
         let expected_size = (width * height * 2) as usize; // 2 bytes per pixel
@@ -232,12 +239,34 @@ impl BasicDecoder {
             rgba_data.push(255); // Alpha (fully opaque)
         }
 
-        create_rgba_image(rgba_data, width, height)
+        Ok(rgba_data)
     }
 }
 
-impl Decoder for BasicDecoder {
-    fn decode(&self, texture: &Texture2D) -> Result<RgbaImage> {
+impl BasicDecoder {
+    /// Decode into the requested pixel layout without going through `image`.
+    ///
+    /// BGRA32 sources requested as [`PixelLayout::Bgra8`] are copied as-is.
+    pub fn decode_raw(&self, texture: &Texture2D, target: PixelLayout) -> Result<DecodedPixels> {
+        let width = texture.width as u32;
+        let height = texture.height as u32;
+
+        if texture.format == TextureFormat::BGRA32 && target == PixelLayout::Bgra8 {
+            let data = self.decode_bgra32(&texture.image_data, width, height, false)?;
+            return Ok(DecodedPixels {
+                data,
+                width,
+                height,
+                stride: width as usize * 4,
+                layout: PixelLayout::Bgra8,
+            });
+        }
+
+        let rgba = self.decode_rgba8(texture)?;
+        Ok(DecodedPixels::from_rgba8(rgba, width, height, target))
+    }
+
+    fn decode_rgba8(&self, texture: &Texture2D) -> Result<Vec<u8>> {
         let width = texture.width as u32;
         let height = texture.height as u32;
         let data = &texture.image_data;
@@ -246,7 +275,7 @@ impl Decoder for BasicDecoder {
             TextureFormat::RGBA32 => self.decode_rgba32(data, width, height),
             TextureFormat::RGB24 => self.decode_rgb24(data, width, height),
             TextureFormat::ARGB32 => self.decode_argb32(data, width, height),
-            TextureFormat::BGRA32 => self.decode_bgra32(data, width, height),
+            TextureFormat::BGRA32 => self.decode_bgra32(data, width, height, true),
             TextureFormat::Alpha8 => self.decode_alpha8(data, width, height),
             TextureFormat::RGBA4444 => self.decode_rgba4444(data, width, height),
             TextureFormat::ARGB4444 => self.decode_argb4444(data, width, height),
@@ -257,6 +286,13 @@ impl Decoder for BasicDecoder {
             ))),
         }
     }
+}
+
+impl Decoder for BasicDecoder {
+    fn decode(&self, texture: &Texture2D) -> Result<RgbaImage> {
+        let rgba = self.decode_rgba8(texture)?;
+        create_rgba_image(rgba, texture.width as u32, texture.height as u32)
+    }
 
     fn can_decode(&self, format: TextureFormat) -> bool {
         format.is_basic_format()
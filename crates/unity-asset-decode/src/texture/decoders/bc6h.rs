@@ -0,0 +1,306 @@
+//! BC6H block decoder with floating point output
+//!
+//! `texture2ddecoder` only produces 8-bit color, which clips BC6H's HDR range at 1.0. This
+//! decoder follows the same bit layouts (D3D11 BC6H spec) but returns the unquantized
+//! half-float channels as `f32`.
+
+use crate::texture::hdr::half_to_f32;
+
+/// Endpoint channel ids used in [`Mode::layout`].
+const R: u8 = 0;
+const G: u8 = 1;
+const B: u8 = 2;
+
+/// `(channel, endpoint, bit count, shift)`: read `bit count` bits into `endpoint` of `channel`
+/// starting at bit `shift`.
+type Field = (u8, u8, u8, u8);
+
+struct Mode {
+    transformed: bool,
+    two_regions: bool,
+    endpoint_bits: u32,
+    delta_bits: [u32; 3],
+    layout: &'static [Field],
+}
+
+/// Valid modes ordered by mode code: 0, 1 (2-bit codes) then 2, 3, 6, 7, 10, 11, 14, 15, 18,
+/// 22, 26, 30 (5-bit codes).
+#[rustfmt::skip]
+static MODES: [Mode; 14] = [
+    // mode 0
+    Mode {
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 10,
+        delta_bits: [5, 5, 5],
+        layout: &[(G, 2, 1, 4), (B, 2, 1, 4), (B, 3, 1, 4), (R, 0, 10, 0), (G, 0, 10, 0), (B, 0, 10, 0), (R, 1, 5, 0), (G, 3, 1, 4), (G, 2, 4, 0), (G, 1, 5, 0), (B, 3, 1, 0), (G, 3, 4, 0), (B, 1, 5, 0), (B, 3, 1, 1), (B, 2, 4, 0), (R, 2, 5, 0), (B, 3, 1, 2), (R, 3, 5, 0), (B, 3, 1, 3)],
+    },
+    // mode 1
+    Mode {
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 7,
+        delta_bits: [6, 6, 6],
+        layout: &[(G, 2, 1, 5), (G, 3, 1, 4), (G, 3, 1, 5), (R, 0, 7, 0), (B, 3, 1, 0), (B, 3, 1, 1), (B, 2, 1, 4), (G, 0, 7, 0), (B, 2, 1, 5), (B, 3, 1, 2), (G, 2, 1, 4), (B, 0, 7, 0), (B, 3, 1, 3), (B, 3, 1, 5), (B, 3, 1, 4), (R, 1, 6, 0), (G, 2, 4, 0), (G, 1, 6, 0), (G, 3, 4, 0), (B, 1, 6, 0), (B, 2, 4, 0), (R, 2, 6, 0), (R, 3, 6, 0)],
+    },
+    // mode 2
+    Mode {
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 11,
+        delta_bits: [5, 4, 4],
+        layout: &[(R, 0, 10, 0), (G, 0, 10, 0), (B, 0, 10, 0), (R, 1, 5, 0), (R, 0, 1, 10), (G, 2, 4, 0), (G, 1, 4, 0), (G, 0, 1, 10), (B, 3, 1, 0), (G, 3, 4, 0), (B, 1, 4, 0), (B, 0, 1, 10), (B, 3, 1, 1), (B, 2, 4, 0), (R, 2, 5, 0), (B, 3, 1, 2), (R, 3, 5, 0), (B, 3, 1, 3)],
+    },
+    // mode 3
+    Mode {
+        transformed: false,
+        two_regions: false,
+        endpoint_bits: 10,
+        delta_bits: [10, 10, 10],
+        layout: &[(R, 0, 10, 0), (G, 0, 10, 0), (B, 0, 10, 0), (R, 1, 10, 0), (G, 1, 10, 0), (B, 1, 10, 0)],
+    },
+    // mode 6
+    Mode {
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 11,
+        delta_bits: [4, 5, 4],
+        layout: &[(R, 0, 10, 0), (G, 0, 10, 0), (B, 0, 10, 0), (R, 1, 4, 0), (R, 0, 1, 10), (G, 3, 1, 4), (G, 2, 4, 0), (G, 1, 5, 0), (G, 0, 1, 10), (G, 3, 4, 0), (B, 1, 4, 0), (B, 0, 1, 10), (B, 3, 1, 1), (B, 2, 4, 0), (R, 2, 4, 0), (B, 3, 1, 0), (B, 3, 1, 2), (R, 3, 4, 0), (G, 2, 1, 4), (B, 3, 1, 3)],
+    },
+    // mode 7
+    Mode {
+        transformed: true,
+        two_regions: false,
+        endpoint_bits: 11,
+        delta_bits: [9, 9, 9],
+        layout: &[(R, 0, 10, 0), (G, 0, 10, 0), (B, 0, 10, 0), (R, 1, 9, 0), (R, 0, 1, 10), (G, 1, 9, 0), (G, 0, 1, 10), (B, 1, 9, 0), (B, 0, 1, 10)],
+    },
+    // mode 10
+    Mode {
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 11,
+        delta_bits: [4, 4, 5],
+        layout: &[(R, 0, 10, 0), (G, 0, 10, 0), (B, 0, 10, 0), (R, 1, 4, 0), (R, 0, 1, 10), (B, 2, 1, 4), (G, 2, 4, 0), (G, 1, 4, 0), (G, 0, 1, 10), (B, 3, 1, 0), (G, 3, 4, 0), (B, 1, 5, 0), (B, 0, 1, 10), (B, 2, 4, 0), (R, 2, 4, 0), (B, 3, 1, 1), (B, 3, 1, 2), (R, 3, 4, 0), (B, 3, 1, 4), (B, 3, 1, 3)],
+    },
+    // mode 11
+    Mode {
+        transformed: true,
+        two_regions: false,
+        endpoint_bits: 12,
+        delta_bits: [8, 8, 8],
+        layout: &[(R, 0, 10, 0), (G, 0, 10, 0), (B, 0, 10, 0), (R, 1, 8, 0), (R, 0, 1, 11), (R, 0, 1, 10), (G, 1, 8, 0), (G, 0, 1, 11), (G, 0, 1, 10), (B, 1, 8, 0), (B, 0, 1, 11), (B, 0, 1, 10)],
+    },
+    // mode 14
+    Mode {
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 9,
+        delta_bits: [5, 5, 5],
+        layout: &[(R, 0, 9, 0), (B, 2, 1, 4), (G, 0, 9, 0), (G, 2, 1, 4), (B, 0, 9, 0), (B, 3, 1, 4), (R, 1, 5, 0), (G, 3, 1, 4), (G, 2, 4, 0), (G, 1, 5, 0), (B, 3, 1, 0), (G, 3, 4, 0), (B, 1, 5, 0), (B, 3, 1, 1), (B, 2, 4, 0), (R, 2, 5, 0), (B, 3, 1, 2), (R, 3, 5, 0), (B, 3, 1, 3)],
+    },
+    // mode 15
+    Mode {
+        transformed: true,
+        two_regions: false,
+        endpoint_bits: 16,
+        delta_bits: [4, 4, 4],
+        layout: &[(R, 0, 10, 0), (G, 0, 10, 0), (B, 0, 10, 0), (R, 1, 4, 0), (R, 0, 1, 15), (R, 0, 1, 14), (R, 0, 1, 13), (R, 0, 1, 12), (R, 0, 1, 11), (R, 0, 1, 10), (G, 1, 4, 0), (G, 0, 1, 15), (G, 0, 1, 14), (G, 0, 1, 13), (G, 0, 1, 12), (G, 0, 1, 11), (G, 0, 1, 10), (B, 1, 4, 0), (B, 0, 1, 15), (B, 0, 1, 14), (B, 0, 1, 13), (B, 0, 1, 12), (B, 0, 1, 11), (B, 0, 1, 10)],
+    },
+    // mode 18
+    Mode {
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 8,
+        delta_bits: [6, 5, 5],
+        layout: &[(R, 0, 8, 0), (G, 3, 1, 4), (B, 2, 1, 4), (G, 0, 8, 0), (B, 3, 1, 2), (G, 2, 1, 4), (B, 0, 8, 0), (B, 3, 1, 3), (B, 3, 1, 4), (R, 1, 6, 0), (G, 2, 4, 0), (G, 1, 5, 0), (B, 3, 1, 0), (G, 3, 4, 0), (B, 1, 5, 0), (B, 3, 1, 1), (B, 2, 4, 0), (R, 2, 6, 0), (R, 3, 6, 0)],
+    },
+    // mode 22
+    Mode {
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 8,
+        delta_bits: [5, 6, 5],
+        layout: &[(R, 0, 8, 0), (B, 3, 1, 0), (B, 2, 1, 4), (G, 0, 8, 0), (G, 2, 1, 5), (G, 2, 1, 4), (B, 0, 8, 0), (G, 3, 1, 5), (B, 3, 1, 4), (R, 1, 5, 0), (G, 3, 1, 4), (G, 2, 4, 0), (G, 1, 6, 0), (G, 3, 4, 0), (B, 1, 5, 0), (B, 3, 1, 1), (B, 2, 4, 0), (R, 2, 5, 0), (B, 3, 1, 2), (R, 3, 5, 0), (B, 3, 1, 3)],
+    },
+    // mode 26
+    Mode {
+        transformed: true,
+        two_regions: true,
+        endpoint_bits: 8,
+        delta_bits: [5, 5, 6],
+        layout: &[(R, 0, 8, 0), (B, 3, 1, 1), (B, 2, 1, 4), (G, 0, 8, 0), (B, 2, 1, 5), (G, 2, 1, 4), (B, 0, 8, 0), (B, 3, 1, 5), (B, 3, 1, 4), (R, 1, 5, 0), (G, 3, 1, 4), (G, 2, 4, 0), (G, 1, 5, 0), (B, 3, 1, 0), (G, 3, 4, 0), (B, 1, 6, 0), (B, 2, 4, 0), (R, 2, 5, 0), (B, 3, 1, 2), (R, 3, 5, 0), (B, 3, 1, 3)],
+    },
+    // mode 30
+    Mode {
+        transformed: false,
+        two_regions: true,
+        endpoint_bits: 6,
+        delta_bits: [6, 6, 6],
+        layout: &[(R, 0, 6, 0), (G, 3, 1, 4), (B, 3, 1, 0), (B, 3, 1, 1), (B, 2, 1, 4), (G, 0, 6, 0), (G, 2, 1, 5), (B, 2, 1, 5), (B, 3, 1, 2), (G, 2, 1, 4), (B, 0, 6, 0), (G, 3, 1, 5), (B, 3, 1, 3), (B, 3, 1, 5), (B, 3, 1, 4), (R, 1, 6, 0), (G, 2, 4, 0), (G, 1, 6, 0), (G, 3, 4, 0), (B, 1, 6, 0), (B, 2, 4, 0), (R, 2, 6, 0), (R, 3, 6, 0)],
+    },
+];
+
+/// Two-region partition masks (bit `i` set: pixel `i` belongs to the second region).
+const PARTITIONS: [u16; 32] = [
+    0xcccc, 0x8888, 0xeeee, 0xecc8, 0xc880, 0xfeec, 0xfec8, 0xec80, 0xc800, 0xffec, 0xfe80, 0xe800,
+    0xffe8, 0xff00, 0xfff0, 0xf000, 0xf710, 0x008e, 0x7100, 0x08ce, 0x008c, 0x7310, 0x3100, 0x8cce,
+    0x088c, 0x3110, 0x6666, 0x366c, 0x17e8, 0x0ff0, 0x718e, 0x399c,
+];
+
+/// Anchor pixel of the second region per partition.
+const ANCHORS: [usize; 32] = [
+    15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 2, 8, 2, 2, 8, 8, 15, 2, 8,
+    2, 2, 8, 8, 2, 2,
+];
+
+const WEIGHTS_3: [u32; 8] = [0, 9, 18, 27, 37, 46, 55, 64];
+const WEIGHTS_4: [u32; 16] = [0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64];
+
+struct Bits<'a> {
+    data: &'a [u8; 16],
+    position: usize,
+}
+
+impl Bits<'_> {
+    fn read(&mut self, count: u8) -> u32 {
+        let mut value = 0;
+        for i in 0..count as usize {
+            let bit = self.position + i;
+            value |= ((self.data[bit / 8] >> (bit % 8)) as u32 & 1) << i;
+        }
+        self.position += count as usize;
+        value
+    }
+}
+
+fn mode(code: u32) -> Option<&'static Mode> {
+    let index = match code {
+        0 => 0,
+        1 => 1,
+        2 => 2,
+        3 => 3,
+        6 => 4,
+        7 => 5,
+        10 => 6,
+        11 => 7,
+        14 => 8,
+        15 => 9,
+        18 => 10,
+        22 => 11,
+        26 => 12,
+        30 => 13,
+        _ => return None,
+    };
+    Some(&MODES[index])
+}
+
+fn sign_extend(value: i32, bits: u32) -> i32 {
+    let shift = 32 - bits;
+    (value << shift) >> shift
+}
+
+fn unquantize(value: i32, bits: u32, signed: bool) -> i32 {
+    if signed {
+        if bits >= 16 {
+            return value;
+        }
+        let (negative, magnitude) = (value < 0, value.abs());
+        let unq = if magnitude == 0 {
+            0
+        } else if magnitude >= (1 << (bits - 1)) - 1 {
+            0x7fff
+        } else {
+            ((magnitude << 15) + 0x4000) >> (bits - 1)
+        };
+        if negative { -unq } else { unq }
+    } else {
+        if bits >= 15 || value == 0 {
+            return value;
+        }
+        if value == (1 << bits) - 1 {
+            return 0xffff;
+        }
+        ((value << 16) + 0x8000) >> bits
+    }
+}
+
+/// Scale an interpolated value into half-float bits.
+fn finish_unquantize(value: i32, signed: bool) -> u16 {
+    if signed {
+        let magnitude = (value.abs() * 31) >> 5;
+        if value < 0 {
+            magnitude as u16 | 0x8000
+        } else {
+            magnitude as u16
+        }
+    } else {
+        ((value * 31) >> 6) as u16
+    }
+}
+
+/// Decode one 16-byte block into 4x4 RGB texels, row-major. Reserved modes decode to black.
+pub(crate) fn decode_block(block: &[u8; 16], signed: bool) -> [[f32; 3]; 16] {
+    let mut bits = Bits {
+        data: block,
+        position: 0,
+    };
+    let mut code = bits.read(2);
+    if code & 2 != 0 {
+        code |= bits.read(3) << 2;
+    }
+    let Some(mode) = mode(code) else {
+        return [[0.0; 3]; 16];
+    };
+
+    // endpoints[channel][endpoint]: w, x (region 0), y, z (region 1)
+    let mut endpoints = [[0i32; 4]; 3];
+    for &(channel, endpoint, count, shift) in mode.layout {
+        endpoints[channel as usize][endpoint as usize] |= (bits.read(count) << shift) as i32;
+    }
+
+    let regions = if mode.two_regions { 2 } else { 1 };
+    for (values, &delta_bits) in endpoints.iter_mut().zip(&mode.delta_bits) {
+        if signed {
+            values[0] = sign_extend(values[0], mode.endpoint_bits);
+        }
+        let base = values[0];
+        for value in &mut values[1..regions * 2] {
+            if signed || mode.transformed {
+                *value = sign_extend(*value, delta_bits);
+            }
+            if mode.transformed {
+                *value = (*value + base) & ((1 << mode.endpoint_bits) - 1);
+                if signed {
+                    *value = sign_extend(*value, mode.endpoint_bits);
+                }
+            }
+        }
+        for value in &mut values[..regions * 2] {
+            *value = unquantize(*value, mode.endpoint_bits, signed);
+        }
+    }
+
+    let partition = if mode.two_regions {
+        bits.read(5) as usize
+    } else {
+        0
+    };
+    let mut texels = [[0.0; 3]; 16];
+    for (i, texel) in texels.iter_mut().enumerate() {
+        let (region, weight) = if mode.two_regions {
+            let region = (PARTITIONS[partition] >> i) as usize & 1;
+            // Anchor indices drop their implied-zero top bit.
+            let anchor = i == 0 || (region == 1 && i == ANCHORS[partition]);
+            (region, WEIGHTS_3[bits.read(3 - anchor as u8) as usize])
+        } else {
+            (0, WEIGHTS_4[bits.read(4 - (i == 0) as u8) as usize])
+        };
+        for (out, values) in texel.iter_mut().zip(&endpoints) {
+            let (e0, e1) = (values[region * 2], values[region * 2 + 1]);
+            let interpolated = (e0 * (64 - weight as i32) + e1 * weight as i32 + 32) >> 6;
+            *out = half_to_f32(finish_unquantize(interpolated, signed));
+        }
+    }
+    texels
+}
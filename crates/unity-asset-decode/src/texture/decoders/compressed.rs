@@ -1,21 +1,48 @@
 //! Compressed texture format decoders
 //!
 //! This module handles compressed texture formats like DXT1, DXT5, BC7, etc.
-//! Requires the texture-advanced feature for texture2ddecoder integration.
+//! Requires the texture-advanced feature for texture2ddecoder integration, except for BC6H,
+//! which goes through the crate's own float decoder (see [`decode_hdr`]) and is tonemapped.
 
 use super::{Decoder, create_rgba_image, validate_dimensions};
 use crate::error::{BinaryError, Result};
 use crate::texture::formats::TextureFormat;
+use crate::texture::hdr::decode_hdr;
 use crate::texture::types::Texture2D;
 use image::RgbaImage;
 
 /// Decoder for compressed texture formats
-pub struct CompressedDecoder;
+///
+/// BC5 normal maps get their Z channel rebuilt into blue unless
+/// [`CompressedDecoder::with_bc5_normal_reconstruction`] turns it off, and BC6H is tonemapped
+/// with [`CompressedDecoder::with_bc6h_exposure`] (see [`HdrImage::tonemap`]).
+///
+/// [`HdrImage::tonemap`]: crate::texture::HdrImage::tonemap
+#[derive(Debug, Clone)]
+pub struct CompressedDecoder {
+    reconstruct_bc5_normals: bool,
+    bc6h_exposure: f32,
+}
 
 impl CompressedDecoder {
     /// Create a new compressed decoder
     pub fn new() -> Self {
-        Self
+        Self {
+            reconstruct_bc5_normals: true,
+            bc6h_exposure: 1.0,
+        }
+    }
+
+    /// Rebuild Z of BC5 normal maps into blue (otherwise blue is 0)
+    pub fn with_bc5_normal_reconstruction(mut self, reconstruct: bool) -> Self {
+        self.reconstruct_bc5_normals = reconstruct;
+        self
+    }
+
+    /// Scale applied to BC6H colors before tonemapping
+    pub fn with_bc6h_exposure(mut self, exposure: f32) -> Self {
+        self.bc6h_exposure = exposure;
+        self
     }
 
     /// Decode DXT1 format
@@ -111,11 +138,11 @@ impl CompressedDecoder {
 
         match texture2ddecoder::decode_bc4(data, width as usize, height as usize, &mut output) {
             Ok(_) => {
-                // Convert u32 to u8 RGBA (BC4 is single channel, so replicate to RGB)
+                // texture2ddecoder writes BGRA words with the BC4 channel in red; replicate it to RGB
                 let rgba_data: Vec<u8> = output
                     .iter()
                     .flat_map(|&pixel| {
-                        let value = (pixel & 0xFF) as u8;
+                        let value = ((pixel >> 16) & 0xFF) as u8;
                         [value, value, value, 255] // Replicate to RGB, full alpha
                     })
                     .collect();
@@ -126,7 +153,7 @@ impl CompressedDecoder {
         }
     }
 
-    /// Decode BC5 format (two channel)
+    /// Decode BC5 format (two channel), reconstructing the normal's Z into blue when enabled
     #[cfg(feature = "texture-advanced")]
     fn decode_bc5(&self, data: &[u8], width: u32, height: u32) -> Result<RgbaImage> {
         validate_dimensions(width, height)?;
@@ -135,20 +162,25 @@ impl CompressedDecoder {
 
         match texture2ddecoder::decode_bc5(data, width as usize, height as usize, &mut output) {
             Ok(_) => {
-                // Convert u32 to u8 RGBA (BC5 has RG channels)
+                // Convert BGRA u32 to u8 RGBA (BC5 has RG channels)
                 let rgba_data: Vec<u8> = output
                     .iter()
                     .flat_map(|&pixel| {
                         [
-                            (pixel & 0xFF) as u8,        // R
-                            ((pixel >> 8) & 0xFF) as u8, // G
-                            0,                           // B (not present in BC5)
-                            255,                         // A (full alpha)
+                            ((pixel >> 16) & 0xFF) as u8, // R
+                            ((pixel >> 8) & 0xFF) as u8,  // G
+                            0,                            // B (not present in BC5)
+                            255,                          // A (full alpha)
                         ]
                     })
                     .collect();
 
-                create_rgba_image(rgba_data, width, height)
+                let image = create_rgba_image(rgba_data, width, height)?;
+                Ok(if self.reconstruct_bc5_normals {
+                    crate::texture::reconstruct_normal_bc5(&image)
+                } else {
+                    image
+                })
             }
             Err(e) => Err(BinaryError::generic(format!("BC5 decoding failed: {}", e))),
         }
@@ -181,6 +213,7 @@ impl Decoder for CompressedDecoder {
             TextureFormat::BC4 => self.decode_bc4(data, width, height),
             #[cfg(feature = "texture-advanced")]
             TextureFormat::BC5 => self.decode_bc5(data, width, height),
+            TextureFormat::BC6H => Ok(decode_hdr(texture)?.tonemap(self.bc6h_exposure)),
 
             #[cfg(not(feature = "texture-advanced"))]
             format if format.is_compressed_format() => self.decode_unsupported(format),
@@ -201,13 +234,14 @@ impl Decoder for CompressedDecoder {
                     | TextureFormat::DXT5
                     | TextureFormat::BC4
                     | TextureFormat::BC5
+                    | TextureFormat::BC6H
                     | TextureFormat::BC7
             )
         }
 
         #[cfg(not(feature = "texture-advanced"))]
         {
-            false
+            format == TextureFormat::BC6H
         }
     }
 
@@ -219,13 +253,14 @@ impl Decoder for CompressedDecoder {
                 TextureFormat::DXT5,
                 TextureFormat::BC4,
                 TextureFormat::BC5,
+                TextureFormat::BC6H,
                 TextureFormat::BC7,
             ]
         }
 
         #[cfg(not(feature = "texture-advanced"))]
         {
-            vec![]
+            vec![TextureFormat::BC6H]
         }
     }
 }
@@ -27,22 +27,7 @@ impl MobileDecoder {
 
         match texture2ddecoder::decode_etc2_rgb(data, width as usize, height as usize, &mut output)
         {
-            Ok(_) => {
-                // Convert u32 RGBA to u8 RGBA
-                let rgba_data: Vec<u8> = output
-                    .iter()
-                    .flat_map(|&pixel| {
-                        [
-                            (pixel & 0xFF) as u8,         // R
-                            ((pixel >> 8) & 0xFF) as u8,  // G
-                            ((pixel >> 16) & 0xFF) as u8, // B
-                            255,                          // A (ETC2 RGB has no alpha)
-                        ]
-                    })
-                    .collect();
-
-                create_rgba_image(rgba_data, width, height)
-            }
+            Ok(_) => rgba_image(&output, width, height, false),
             Err(e) => Err(BinaryError::generic(format!(
                 "ETC2 RGB decoding failed: {}",
                 e
@@ -63,22 +48,7 @@ impl MobileDecoder {
             height as usize,
             &mut output,
         ) {
-            Ok(_) => {
-                // Convert u32 RGBA to u8 RGBA
-                let rgba_data: Vec<u8> = output
-                    .iter()
-                    .flat_map(|&pixel| {
-                        [
-                            (pixel & 0xFF) as u8,         // R
-                            ((pixel >> 8) & 0xFF) as u8,  // G
-                            ((pixel >> 16) & 0xFF) as u8, // B
-                            ((pixel >> 24) & 0xFF) as u8, // A
-                        ]
-                    })
-                    .collect();
-
-                create_rgba_image(rgba_data, width, height)
-            }
+            Ok(_) => rgba_image(&output, width, height, true),
             Err(e) => Err(BinaryError::generic(format!(
                 "ETC2 RGBA8 decoding failed: {}",
                 e
@@ -101,22 +71,7 @@ impl MobileDecoder {
             4,
             &mut output,
         ) {
-            Ok(_) => {
-                // Convert u32 RGBA to u8 RGBA
-                let rgba_data: Vec<u8> = output
-                    .iter()
-                    .flat_map(|&pixel| {
-                        [
-                            (pixel & 0xFF) as u8,         // R
-                            ((pixel >> 8) & 0xFF) as u8,  // G
-                            ((pixel >> 16) & 0xFF) as u8, // B
-                            ((pixel >> 24) & 0xFF) as u8, // A
-                        ]
-                    })
-                    .collect();
-
-                create_rgba_image(rgba_data, width, height)
-            }
+            Ok(_) => rgba_image(&output, width, height, true),
             Err(e) => Err(BinaryError::generic(format!(
                 "ASTC 4x4 decoding failed: {}",
                 e
@@ -139,22 +94,7 @@ impl MobileDecoder {
             6,
             &mut output,
         ) {
-            Ok(_) => {
-                // Convert u32 RGBA to u8 RGBA
-                let rgba_data: Vec<u8> = output
-                    .iter()
-                    .flat_map(|&pixel| {
-                        [
-                            (pixel & 0xFF) as u8,         // R
-                            ((pixel >> 8) & 0xFF) as u8,  // G
-                            ((pixel >> 16) & 0xFF) as u8, // B
-                            ((pixel >> 24) & 0xFF) as u8, // A
-                        ]
-                    })
-                    .collect();
-
-                create_rgba_image(rgba_data, width, height)
-            }
+            Ok(_) => rgba_image(&output, width, height, true),
             Err(e) => Err(BinaryError::generic(format!(
                 "ASTC 6x6 decoding failed: {}",
                 e
@@ -177,22 +117,7 @@ impl MobileDecoder {
             8,
             &mut output,
         ) {
-            Ok(_) => {
-                // Convert u32 RGBA to u8 RGBA
-                let rgba_data: Vec<u8> = output
-                    .iter()
-                    .flat_map(|&pixel| {
-                        [
-                            (pixel & 0xFF) as u8,         // R
-                            ((pixel >> 8) & 0xFF) as u8,  // G
-                            ((pixel >> 16) & 0xFF) as u8, // B
-                            ((pixel >> 24) & 0xFF) as u8, // A
-                        ]
-                    })
-                    .collect();
-
-                create_rgba_image(rgba_data, width, height)
-            }
+            Ok(_) => rgba_image(&output, width, height, true),
             Err(e) => Err(BinaryError::generic(format!(
                 "ASTC 8x8 decoding failed: {}",
                 e
@@ -200,6 +125,56 @@ impl MobileDecoder {
         }
     }
 
+    /// Decode ETC1 (`ETC_RGB4`) format
+    #[cfg(feature = "texture-advanced")]
+    fn decode_etc1(&self, data: &[u8], width: u32, height: u32) -> Result<RgbaImage> {
+        validate_dimensions(width, height)?;
+
+        let mut output = vec![0u32; (width * height) as usize];
+
+        match texture2ddecoder::decode_etc1(data, width as usize, height as usize, &mut output) {
+            Ok(_) => rgba_image(&output, width, height, false),
+            Err(e) => Err(BinaryError::generic(format!("ETC1 decoding failed: {}", e))),
+        }
+    }
+
+    /// Decode PVRTC format, 2 or 4 bits per pixel
+    ///
+    /// PVRTC blocks are stored in Morton order over a power-of-two grid, so both sides of the
+    /// texture must be powers of two. The RGB variants ignore the decoded alpha.
+    #[cfg(feature = "texture-advanced")]
+    fn decode_pvrtc(
+        &self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        two_bpp: bool,
+        has_alpha: bool,
+    ) -> Result<RgbaImage> {
+        validate_dimensions(width, height)?;
+        if !width.is_power_of_two() || !height.is_power_of_two() {
+            return Err(BinaryError::invalid_data(format!(
+                "PVRTC textures must have power-of-two dimensions, got {}x{}",
+                width, height
+            )));
+        }
+
+        let mut output = vec![0u32; (width * height) as usize];
+
+        let decoded = if two_bpp {
+            texture2ddecoder::decode_pvrtc_2bpp(data, width as usize, height as usize, &mut output)
+        } else {
+            texture2ddecoder::decode_pvrtc_4bpp(data, width as usize, height as usize, &mut output)
+        };
+        match decoded {
+            Ok(_) => rgba_image(&output, width, height, has_alpha),
+            Err(e) => Err(BinaryError::generic(format!(
+                "PVRTC decoding failed: {}",
+                e
+            ))),
+        }
+    }
+
     /// Fallback for when texture-advanced feature is not enabled
     #[cfg(not(feature = "texture-advanced"))]
     fn decode_unsupported(&self, format: TextureFormat) -> Result<RgbaImage> {
@@ -210,6 +185,23 @@ impl MobileDecoder {
     }
 }
 
+/// Build an image from texture2ddecoder output
+///
+/// texture2ddecoder packs each pixel as little-endian BGRA. Formats without an alpha channel
+/// are forced opaque.
+#[cfg(feature = "texture-advanced")]
+fn rgba_image(pixels: &[u32], width: u32, height: u32, has_alpha: bool) -> Result<RgbaImage> {
+    let rgba_data: Vec<u8> = pixels
+        .iter()
+        .flat_map(|&pixel| {
+            let [b, g, r, a] = pixel.to_le_bytes();
+            [r, g, b, if has_alpha { a } else { 255 }]
+        })
+        .collect();
+
+    create_rgba_image(rgba_data, width, height)
+}
+
 impl Decoder for MobileDecoder {
     fn decode(&self, texture: &Texture2D) -> Result<RgbaImage> {
         let width = texture.width as u32;
@@ -217,6 +209,16 @@ impl Decoder for MobileDecoder {
         let data = &texture.image_data;
 
         match texture.format {
+            #[cfg(feature = "texture-advanced")]
+            TextureFormat::ETC_RGB4 => self.decode_etc1(data, width, height),
+            #[cfg(feature = "texture-advanced")]
+            TextureFormat::PVRTC_RGB2 => self.decode_pvrtc(data, width, height, true, false),
+            #[cfg(feature = "texture-advanced")]
+            TextureFormat::PVRTC_RGBA2 => self.decode_pvrtc(data, width, height, true, true),
+            #[cfg(feature = "texture-advanced")]
+            TextureFormat::PVRTC_RGB4 => self.decode_pvrtc(data, width, height, false, false),
+            #[cfg(feature = "texture-advanced")]
+            TextureFormat::PVRTC_RGBA4 => self.decode_pvrtc(data, width, height, false, true),
             #[cfg(feature = "texture-advanced")]
             TextureFormat::ETC2_RGB => self.decode_etc2_rgb(data, width, height),
             #[cfg(feature = "texture-advanced")]
@@ -243,7 +245,12 @@ impl Decoder for MobileDecoder {
         {
             matches!(
                 format,
-                TextureFormat::ETC2_RGB
+                TextureFormat::PVRTC_RGB2
+                    | TextureFormat::PVRTC_RGBA2
+                    | TextureFormat::PVRTC_RGB4
+                    | TextureFormat::PVRTC_RGBA4
+                    | TextureFormat::ETC_RGB4
+                    | TextureFormat::ETC2_RGB
                     | TextureFormat::ETC2_RGBA8
                     | TextureFormat::ASTC_RGBA_4x4
                     | TextureFormat::ASTC_RGBA_6x6
@@ -261,6 +268,11 @@ impl Decoder for MobileDecoder {
         #[cfg(feature = "texture-advanced")]
         {
             vec![
+                TextureFormat::PVRTC_RGB2,
+                TextureFormat::PVRTC_RGBA2,
+                TextureFormat::PVRTC_RGB4,
+                TextureFormat::PVRTC_RGBA4,
+                TextureFormat::ETC_RGB4,
                 TextureFormat::ETC2_RGB,
                 TextureFormat::ETC2_RGBA8,
                 TextureFormat::ASTC_RGBA_4x4,
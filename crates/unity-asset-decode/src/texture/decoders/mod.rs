@@ -4,6 +4,7 @@
 //! organized by format category for better maintainability.
 
 mod basic;
+pub(crate) mod bc6h;
 mod compressed;
 mod crunch;
 mod mobile;
@@ -14,7 +15,7 @@ pub use crunch::CrunchDecoder;
 pub use mobile::MobileDecoder;
 
 use super::formats::TextureFormat;
-use super::types::Texture2D;
+use super::types::{DecodedPixels, PixelLayout, Texture2D};
 use crate::error::{BinaryError, Result};
 use image::RgbaImage;
 
@@ -40,34 +41,62 @@ impl TextureDecoder {
         }
     }
 
+    /// Rebuild Z of BC5 normal maps into blue (see
+    /// [`CompressedDecoder::with_bc5_normal_reconstruction`])
+    pub fn with_bc5_normal_reconstruction(mut self, reconstruct: bool) -> Self {
+        self.compressed = self.compressed.with_bc5_normal_reconstruction(reconstruct);
+        self
+    }
+
+    /// Exposure of the BC6H tonemap (see [`CompressedDecoder::with_bc6h_exposure`])
+    pub fn with_bc6h_exposure(mut self, exposure: f32) -> Self {
+        self.compressed = self.compressed.with_bc6h_exposure(exposure);
+        self
+    }
+
     /// Decode texture to RGBA image
     ///
-    /// This method dispatches to the appropriate specialized decoder
-    /// based on the texture format.
+    /// Thin wrapper over [`TextureDecoder::decode_raw`] with [`PixelLayout::Rgba8`].
     pub fn decode(&self, texture: &Texture2D) -> Result<RgbaImage> {
+        let pixels = self.decode_raw(texture, PixelLayout::Rgba8)?;
+        create_rgba_image(pixels.data, pixels.width, pixels.height)
+    }
+
+    /// Decode texture into a raw pixel buffer in the requested layout
+    ///
+    /// This is the GPU-upload path: basic formats are converted straight into the target
+    /// layout without building an `image::RgbaImage`, and BGRA32 textures requested as
+    /// [`PixelLayout::Bgra8`] are copied without a channel swizzle. Block-compressed formats
+    /// are decoded to RGBA first and converted in place.
+    pub fn decode_raw(&self, texture: &Texture2D, target: PixelLayout) -> Result<DecodedPixels> {
         // Validate texture first
         texture
             .validate()
             .map_err(|e| BinaryError::invalid_data(&e))?;
 
         // Handle Crunch compression first (it can wrap other formats)
-        if texture.format.is_crunch_compressed() {
-            return self.crunch.decode(texture);
-        }
-
-        // Dispatch to appropriate decoder based on format category
-        if texture.format.is_basic_format() {
-            self.basic.decode(texture)
+        let image = if texture.format.is_crunch_compressed() {
+            self.crunch.decode(texture)?
+        } else if texture.format.is_basic_format() {
+            return self.basic.decode_raw(texture, target);
         } else if texture.format.is_compressed_format() {
-            self.compressed.decode(texture)
+            self.compressed.decode(texture)?
         } else if texture.format.is_mobile_format() {
-            self.mobile.decode(texture)
+            self.mobile.decode(texture)?
         } else {
-            Err(BinaryError::unsupported(format!(
+            return Err(BinaryError::unsupported(format!(
                 "Unsupported texture format: {:?}",
                 texture.format
-            )))
-        }
+            )));
+        };
+
+        let (width, height) = image.dimensions();
+        Ok(DecodedPixels::from_rgba8(
+            image.into_raw(),
+            width,
+            height,
+            target,
+        ))
     }
 
     /// Check if a format can be decoded
@@ -97,8 +126,23 @@ impl TextureDecoder {
             TextureFormat::DXT5,
             #[cfg(feature = "texture-advanced")]
             TextureFormat::BC7,
+            #[cfg(feature = "texture-advanced")]
+            TextureFormat::BC4,
+            #[cfg(feature = "texture-advanced")]
+            TextureFormat::BC5,
+            TextureFormat::BC6H,
             // Mobile formats (when texture-advanced feature is enabled)
             #[cfg(feature = "texture-advanced")]
+            TextureFormat::PVRTC_RGB2,
+            #[cfg(feature = "texture-advanced")]
+            TextureFormat::PVRTC_RGBA2,
+            #[cfg(feature = "texture-advanced")]
+            TextureFormat::PVRTC_RGB4,
+            #[cfg(feature = "texture-advanced")]
+            TextureFormat::PVRTC_RGBA4,
+            #[cfg(feature = "texture-advanced")]
+            TextureFormat::ETC_RGB4,
+            #[cfg(feature = "texture-advanced")]
             TextureFormat::ETC2_RGB,
             #[cfg(feature = "texture-advanced")]
             TextureFormat::ETC2_RGBA8,
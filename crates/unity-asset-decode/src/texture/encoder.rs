@@ -0,0 +1,211 @@
+//! Texture re-encoding
+//!
+//! The inverse of [`BasicDecoder`](super::decoders::BasicDecoder): RGBA images are packed back
+//! into the uncompressed Unity formats, which is what [`Texture2D::set_image`] uses to replace a
+//! texture's pixels. Block-compressed formats cannot be encoded; textures in those formats are
+//! re-encoded into a caller-chosen uncompressed format instead.
+//!
+//! Images are in the orientation [`TextureDecoder`](super::TextureDecoder) returns them in
+//! (Unity's bottom-up row order), so a decode / transform / `set_image` round trip keeps the
+//! texture's orientation.
+
+use super::decoders::TextureDecoder;
+use super::formats::TextureFormat;
+use super::types::{StreamingInfo, Texture2D};
+use crate::error::{BinaryError, Result};
+use image::RgbaImage;
+use image::imageops::{self, FilterType};
+
+/// Encoder for the uncompressed texture formats
+pub struct TextureEncoder;
+
+impl TextureEncoder {
+    /// Create a new texture encoder
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check if a format can be encoded
+    pub fn can_encode(&self, format: TextureFormat) -> bool {
+        self.supported_formats().contains(&format)
+    }
+
+    /// Get list of supported formats
+    pub fn supported_formats(&self) -> Vec<TextureFormat> {
+        vec![
+            TextureFormat::Alpha8,
+            TextureFormat::RGB24,
+            TextureFormat::RGBA32,
+            TextureFormat::ARGB32,
+            TextureFormat::BGRA32,
+            TextureFormat::RGBA4444,
+            TextureFormat::ARGB4444,
+            TextureFormat::RGB565,
+        ]
+    }
+
+    /// Pack `image` into `format`
+    pub fn encode(&self, image: &RgbaImage, format: TextureFormat) -> Result<Vec<u8>> {
+        let pixels = image.pixels().map(|p| p.0);
+        let data = match format {
+            TextureFormat::Alpha8 => pixels.map(|[_, _, _, a]| a).collect(),
+            TextureFormat::RGB24 => pixels.flat_map(|[r, g, b, _]| [r, g, b]).collect(),
+            TextureFormat::RGBA32 => image.as_raw().clone(),
+            TextureFormat::ARGB32 => pixels.flat_map(|[r, g, b, a]| [a, r, g, b]).collect(),
+            TextureFormat::BGRA32 => pixels.flat_map(|[r, g, b, a]| [b, g, r, a]).collect(),
+            TextureFormat::RGBA4444 => pixels
+                .flat_map(|[r, g, b, a]| pack4444([r, g, b, a]).to_le_bytes())
+                .collect(),
+            TextureFormat::ARGB4444 => pixels
+                .flat_map(|[r, g, b, a]| pack4444([a, r, g, b]).to_le_bytes())
+                .collect(),
+            TextureFormat::RGB565 => pixels
+                .flat_map(|[r, g, b, _]| {
+                    let pixel = (scale(r, 31) << 11) | (scale(g, 63) << 5) | scale(b, 31);
+                    pixel.to_le_bytes()
+                })
+                .collect(),
+            other => {
+                return Err(BinaryError::unsupported(format!(
+                    "Cannot encode texture format {:?}",
+                    other
+                )));
+            }
+        };
+        Ok(data)
+    }
+}
+
+impl Default for TextureEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `v` (0..=255) scaled to `0..=max`, rounded
+fn scale(v: u8, max: u16) -> u16 {
+    (v as u16 * max + 127) / 255
+}
+
+/// Four 8-bit channels as 4-bit nibbles, first channel in the high nibble
+fn pack4444(channels: [u8; 4]) -> u16 {
+    channels
+        .iter()
+        .fold(0, |pixel, &c| (pixel << 4) | scale(c, 15))
+}
+
+/// How [`Texture2D::set_image`] stores the new pixels
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ReencodeOptions {
+    /// Target format (`None`: keep the texture's format, which must be encodable)
+    pub format: Option<TextureFormat>,
+    /// Write a full mip chain (`None`: only when the texture had more than one mip)
+    pub mipmaps: Option<bool>,
+}
+
+impl ReencodeOptions {
+    /// Set `format`.
+    pub fn with_format(mut self, format: TextureFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Set `mipmaps`.
+    pub fn with_mipmaps(mut self, mipmaps: bool) -> Self {
+        self.mipmaps = Some(mipmaps);
+        self
+    }
+}
+
+impl Texture2D {
+    /// Replace the texture's pixels with `image`
+    ///
+    /// Re-encodes into the current format or `options.format`, regenerates mips down to 1x1
+    /// when requested, and updates the size fields. The data becomes embedded: any
+    /// `stream_info` is cleared. Use
+    /// [`Texture2DConverter::write_to_class`](super::Texture2DConverter::write_to_class) to
+    /// store the result back into the object's TypeTree.
+    pub fn set_image(&mut self, image: &RgbaImage, options: ReencodeOptions) -> Result<()> {
+        let format = options.format.unwrap_or(self.format);
+        let encoder = TextureEncoder::new();
+        if !encoder.can_encode(format) {
+            return Err(BinaryError::unsupported(format!(
+                "Cannot re-encode into {:?}; choose an uncompressed format with \
+                 ReencodeOptions::with_format",
+                format
+            )));
+        }
+        let (width, height) = image.dimensions();
+        if width == 0 || height == 0 {
+            return Err(BinaryError::invalid_data("Image has no pixels"));
+        }
+        let mipmaps = options
+            .mipmaps
+            .unwrap_or(self.mip_map || self.mip_count > 1);
+
+        let mut data = encoder.encode(image, format)?;
+        let mut mip_count = 1;
+        let mut level = image.clone();
+        while mipmaps && (level.width() > 1 || level.height() > 1) {
+            let (w, h) = ((level.width() / 2).max(1), (level.height() / 2).max(1));
+            level = imageops::resize(&level, w, h, FilterType::Triangle);
+            data.extend(encoder.encode(&level, format)?);
+            mip_count += 1;
+        }
+
+        self.width = width as i32;
+        self.height = height as i32;
+        self.format = format;
+        self.mip_map = mipmaps;
+        self.mip_count = mip_count;
+        self.image_count = 1;
+        self.complete_image_size = data.len() as i32;
+        self.data_size = data.len() as i32;
+        self.image_data = data;
+        self.stream_info = StreamingInfo::default();
+        Ok(())
+    }
+
+    /// Decode, apply `f` to the image and [`set_image`](Self::set_image) the result
+    pub fn transform_image(
+        &mut self,
+        options: ReencodeOptions,
+        f: impl FnOnce(&RgbaImage) -> RgbaImage,
+    ) -> Result<()> {
+        let image = TextureDecoder::new().decode(self)?;
+        self.set_image(&f(&image), options)
+    }
+
+    /// Rotate the decoded image 90° clockwise, keeping the format
+    pub fn rotate90(&mut self) -> Result<()> {
+        self.transform_image(ReencodeOptions::default(), imageops::rotate90)
+    }
+
+    /// Rotate 180°, keeping the format
+    pub fn rotate180(&mut self) -> Result<()> {
+        self.transform_image(ReencodeOptions::default(), imageops::rotate180)
+    }
+
+    /// Rotate the decoded image 270° clockwise, keeping the format
+    pub fn rotate270(&mut self) -> Result<()> {
+        self.transform_image(ReencodeOptions::default(), imageops::rotate270)
+    }
+
+    /// Mirror left to right, keeping the format
+    pub fn flip_horizontal(&mut self) -> Result<()> {
+        self.transform_image(ReencodeOptions::default(), imageops::flip_horizontal)
+    }
+
+    /// Mirror top to bottom, keeping the format
+    pub fn flip_vertical(&mut self) -> Result<()> {
+        self.transform_image(ReencodeOptions::default(), imageops::flip_vertical)
+    }
+
+    /// Resample to `width` x `height`, keeping the format
+    pub fn resize(&mut self, width: u32, height: u32, filter: FilterType) -> Result<()> {
+        self.transform_image(ReencodeOptions::default(), |image| {
+            imageops::resize(image, width, height, filter)
+        })
+    }
+}
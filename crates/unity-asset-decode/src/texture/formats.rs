@@ -256,6 +256,70 @@ impl TextureFormat {
                 has_alpha: true,
                 supported: true,
             },
+            TextureFormat::BC4 => TextureFormatInfo {
+                name: "BC4".to_string(),
+                bits_per_pixel: 4,
+                block_size: (4, 4),
+                compressed: true,
+                has_alpha: false,
+                supported: true,
+            },
+            TextureFormat::BC5 => TextureFormatInfo {
+                name: "BC5".to_string(),
+                bits_per_pixel: 8,
+                block_size: (4, 4),
+                compressed: true,
+                has_alpha: false,
+                supported: true,
+            },
+            TextureFormat::BC6H => TextureFormatInfo {
+                name: "BC6H".to_string(),
+                bits_per_pixel: 8,
+                block_size: (4, 4),
+                compressed: true,
+                has_alpha: false,
+                supported: true,
+            },
+            TextureFormat::PVRTC_RGB2 => TextureFormatInfo {
+                name: "PVRTC_RGB2".to_string(),
+                bits_per_pixel: 2,
+                block_size: (8, 4),
+                compressed: true,
+                has_alpha: false,
+                supported: true,
+            },
+            TextureFormat::PVRTC_RGBA2 => TextureFormatInfo {
+                name: "PVRTC_RGBA2".to_string(),
+                bits_per_pixel: 2,
+                block_size: (8, 4),
+                compressed: true,
+                has_alpha: true,
+                supported: true,
+            },
+            TextureFormat::PVRTC_RGB4 => TextureFormatInfo {
+                name: "PVRTC_RGB4".to_string(),
+                bits_per_pixel: 4,
+                block_size: (4, 4),
+                compressed: true,
+                has_alpha: false,
+                supported: true,
+            },
+            TextureFormat::PVRTC_RGBA4 => TextureFormatInfo {
+                name: "PVRTC_RGBA4".to_string(),
+                bits_per_pixel: 4,
+                block_size: (4, 4),
+                compressed: true,
+                has_alpha: true,
+                supported: true,
+            },
+            TextureFormat::ETC_RGB4 => TextureFormatInfo {
+                name: "ETC_RGB4".to_string(),
+                bits_per_pixel: 4,
+                block_size: (4, 4),
+                compressed: true,
+                has_alpha: false,
+                supported: true,
+            },
             TextureFormat::ETC2_RGB => TextureFormatInfo {
                 name: "ETC2_RGB".to_string(),
                 bits_per_pixel: 4,
@@ -280,10 +344,45 @@ impl TextureFormat {
                 has_alpha: true,
                 supported: true,
             },
+            // 16-byte blocks; bits per pixel rounded down (3.56 and 2).
+            TextureFormat::ASTC_RGBA_6x6 => TextureFormatInfo {
+                name: "ASTC_RGBA_6x6".to_string(),
+                bits_per_pixel: 3,
+                block_size: (6, 6),
+                compressed: true,
+                has_alpha: true,
+                supported: true,
+            },
+            TextureFormat::ASTC_RGBA_8x8 => TextureFormatInfo {
+                name: "ASTC_RGBA_8x8".to_string(),
+                bits_per_pixel: 2,
+                block_size: (8, 8),
+                compressed: true,
+                has_alpha: true,
+                supported: true,
+            },
             _ => TextureFormatInfo::default(),
         }
     }
 
+    /// Map a `GraphicsFormat` value (used by `m_Format` of Texture3D/Texture2DArray since 2019.1)
+    /// to the matching texture format.
+    ///
+    /// Only the common 8-bit and DXT formats are mapped; anything else is `Unknown`.
+    pub fn from_graphics_format(value: i32) -> TextureFormat {
+        match value {
+            // R8G8B8_SRGB / R8G8B8_UNorm
+            3 | 7 => TextureFormat::RGB24,
+            // R8G8B8A8_SRGB / R8G8B8A8_UNorm
+            4 | 8 => TextureFormat::RGBA32,
+            // RGBA_DXT1_SRGB / RGBA_DXT1_UNorm
+            96 | 97 => TextureFormat::DXT1,
+            // RGBA_DXT5_SRGB / RGBA_DXT5_UNorm
+            100 | 101 => TextureFormat::DXT5,
+            _ => TextureFormat::Unknown,
+        }
+    }
+
     /// Check if format is supported for decoding
     pub fn is_supported(&self) -> bool {
         self.info().supported
@@ -293,16 +392,34 @@ impl TextureFormat {
     pub fn calculate_data_size(&self, width: u32, height: u32) -> u32 {
         let info = self.info();
         if info.compressed {
+            // PVRTC stores at least 2x2 blocks, however small the texture is
+            let (width, height) = match self {
+                TextureFormat::PVRTC_RGB2 | TextureFormat::PVRTC_RGBA2 => {
+                    (width.max(16), height.max(8))
+                }
+                TextureFormat::PVRTC_RGB4 | TextureFormat::PVRTC_RGBA4 => {
+                    (width.max(8), height.max(8))
+                }
+                _ => (width, height),
+            };
             let blocks_x = width.div_ceil(info.block_size.0);
             let blocks_y = height.div_ceil(info.block_size.1);
             // For compressed formats, calculate bytes per block
             let bytes_per_block = match self {
                 TextureFormat::DXT1 => 8,
                 TextureFormat::DXT5 => 16,
-                TextureFormat::BC7 => 16,
+                TextureFormat::BC4 => 8,
+                TextureFormat::BC5 | TextureFormat::BC6H | TextureFormat::BC7 => 16,
+                TextureFormat::PVRTC_RGB2
+                | TextureFormat::PVRTC_RGBA2
+                | TextureFormat::PVRTC_RGB4
+                | TextureFormat::PVRTC_RGBA4
+                | TextureFormat::ETC_RGB4 => 8,
                 TextureFormat::ETC2_RGB => 8,
                 TextureFormat::ETC2_RGBA8 => 16,
-                TextureFormat::ASTC_RGBA_4x4 => 16,
+                TextureFormat::ASTC_RGBA_4x4
+                | TextureFormat::ASTC_RGBA_6x6
+                | TextureFormat::ASTC_RGBA_8x8 => 16,
                 _ => info.bits_per_pixel / 8,
             };
             blocks_x * blocks_y * bytes_per_block
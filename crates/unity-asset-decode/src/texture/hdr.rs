@@ -0,0 +1,263 @@
+//! HDR texture decoding
+//!
+//! [`TextureDecoder`](super::TextureDecoder) produces 8-bit images, which clips HDR lightmaps
+//! and reflection probes at 1.0. [`decode_hdr`] keeps the full range instead: half and float
+//! formats, RGB9e5 and BC6H are decoded to `f32` as stored, and LDR textures flagged as
+//! lightmaps (`m_LightmapFormat`) are expanded from their dLDR or RGBM encoding.
+
+use super::decoders::{TextureDecoder, bc6h};
+use super::formats::TextureFormat;
+use super::types::Texture2D;
+use crate::error::{BinaryError, Result};
+use image::RgbaImage;
+
+/// `m_LightmapFormat` (texture usage mode) values that change how LDR data is decoded.
+const USAGE_LIGHTMAP_DOUBLE_LDR: i32 = 1;
+const USAGE_LIGHTMAP_RGBM: i32 = 2;
+const USAGE_RGBM_ENCODED: i32 = 5;
+const USAGE_DOUBLE_LDR: i32 = 7;
+const USAGE_BAKED_LIGHTMAP_DOUBLE_LDR: i32 = 8;
+const USAGE_BAKED_LIGHTMAP_RGBM: i32 = 9;
+const USAGE_REALTIME_LIGHTMAP_RGBM: i32 = 11;
+
+/// RGBM range used by Unity's lightmap encoding.
+const RGBM_RANGE: f32 = 5.0;
+
+/// Floating point RGBA image
+#[derive(Debug, Clone, PartialEq)]
+pub struct HdrImage {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major pixels in Unity's row order (bottom row first), like the 8-bit decoders.
+    pub pixels: Vec<[f32; 4]>,
+}
+
+impl HdrImage {
+    /// Create a black, opaque image
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0.0, 0.0, 0.0, 1.0]; (width * height) as usize],
+        }
+    }
+
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<[f32; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.pixels.get((y * self.width + x) as usize).copied()
+    }
+
+    /// Largest color channel value, useful to tell real HDR content from LDR data.
+    pub fn max_value(&self) -> f32 {
+        self.pixels
+            .iter()
+            .flat_map(|p| &p[..3])
+            .fold(0.0, |max, &v| if v > max { v } else { max })
+    }
+
+    /// Expand an 8-bit image to floats in `0.0..=1.0`
+    pub fn from_rgba8(image: &RgbaImage) -> Self {
+        Self {
+            width: image.width(),
+            height: image.height(),
+            pixels: image
+                .pixels()
+                .map(|p| p.0.map(|c| c as f32 / 255.0))
+                .collect(),
+        }
+    }
+
+    /// Tonemap to 8 bits: color is scaled by `exposure` and compressed with Reinhard's
+    /// `c / (1 + c)`; alpha is clamped
+    pub fn tonemap(&self, exposure: f32) -> RgbaImage {
+        let map = |c: f32| {
+            let c = (c * exposure).max(0.0);
+            c / (1.0 + c)
+        };
+        let data = self
+            .pixels
+            .iter()
+            .flat_map(|&[r, g, b, a]| {
+                [map(r), map(g), map(b), a.clamp(0.0, 1.0)].map(|c| (c * 255.0).round() as u8)
+            })
+            .collect();
+        RgbaImage::from_raw(self.width, self.height, data).unwrap_or_default()
+    }
+
+    /// Clamp to `0.0..=1.0` and quantize to 8 bits
+    pub fn to_rgba8(&self) -> RgbaImage {
+        let data = self
+            .pixels
+            .iter()
+            .flat_map(|p| p.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8))
+            .collect();
+        RgbaImage::from_raw(self.width, self.height, data).unwrap_or_default()
+    }
+}
+
+/// Check if [`decode_hdr`] keeps values above 1.0 for this format
+pub fn is_hdr_format(format: TextureFormat) -> bool {
+    matches!(
+        format,
+        TextureFormat::RHalf
+            | TextureFormat::RGHalf
+            | TextureFormat::RGBAHalf
+            | TextureFormat::RFloat
+            | TextureFormat::RGFloat
+            | TextureFormat::RGBAFloat
+            | TextureFormat::RGB9e5Float
+            | TextureFormat::BC6H
+    )
+}
+
+/// Decode the first mip of a texture to floating point RGBA
+///
+/// LDR formats go through [`TextureDecoder`]; lightmap-encoded ones are then expanded (dLDR
+/// doubles the color, RGBM multiplies it by `alpha * 5`). Decoded values stay in the texture's
+/// own color space.
+pub fn decode_hdr(texture: &Texture2D) -> Result<HdrImage> {
+    if !is_hdr_format(texture.format) {
+        return decode_encoded_ldr(texture);
+    }
+    if !texture.has_valid_dimensions() {
+        return Err(BinaryError::invalid_data("Invalid texture dimensions"));
+    }
+    if !texture.has_image_data() {
+        return Err(BinaryError::invalid_data("No image data available"));
+    }
+    let (width, height) = texture.dimensions();
+    let data = &texture.image_data;
+
+    let channels = |count: usize, size: usize| -> Result<HdrImage> {
+        let stride = count * size;
+        let needed = (width * height) as usize * stride;
+        if data.len() < needed {
+            return Err(BinaryError::not_enough_data(needed, data.len()));
+        }
+        let mut image = HdrImage::new(width, height);
+        for (pixel, bytes) in image.pixels.iter_mut().zip(data.chunks_exact(stride)) {
+            for (c, value) in bytes.chunks_exact(size).enumerate() {
+                pixel[c] = if size == 2 {
+                    half_to_f32(u16::from_le_bytes([value[0], value[1]]))
+                } else {
+                    f32::from_le_bytes([value[0], value[1], value[2], value[3]])
+                };
+            }
+        }
+        Ok(image)
+    };
+
+    match texture.format {
+        TextureFormat::RHalf => channels(1, 2),
+        TextureFormat::RGHalf => channels(2, 2),
+        TextureFormat::RGBAHalf => channels(4, 2),
+        TextureFormat::RFloat => channels(1, 4),
+        TextureFormat::RGFloat => channels(2, 4),
+        TextureFormat::RGBAFloat => channels(4, 4),
+        TextureFormat::RGB9e5Float => {
+            let needed = (width * height) as usize * 4;
+            if data.len() < needed {
+                return Err(BinaryError::not_enough_data(needed, data.len()));
+            }
+            let mut image = HdrImage::new(width, height);
+            for (pixel, bytes) in image.pixels.iter_mut().zip(data.chunks_exact(4)) {
+                let [r, g, b] =
+                    rgb9e5_to_f32(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]));
+                *pixel = [r, g, b, 1.0];
+            }
+            Ok(image)
+        }
+        TextureFormat::BC6H => decode_bc6h(data, width, height),
+        _ => unreachable!("checked by is_hdr_format"),
+    }
+}
+
+/// LDR formats, expanding lightmap encodings
+fn decode_encoded_ldr(texture: &Texture2D) -> Result<HdrImage> {
+    let mut image = HdrImage::from_rgba8(&TextureDecoder::new().decode(texture)?);
+    match texture.light_map_format {
+        USAGE_LIGHTMAP_DOUBLE_LDR | USAGE_DOUBLE_LDR | USAGE_BAKED_LIGHTMAP_DOUBLE_LDR => {
+            for pixel in &mut image.pixels {
+                pixel[..3].iter_mut().for_each(|c| *c *= 2.0);
+            }
+        }
+        USAGE_LIGHTMAP_RGBM
+        | USAGE_RGBM_ENCODED
+        | USAGE_BAKED_LIGHTMAP_RGBM
+        | USAGE_REALTIME_LIGHTMAP_RGBM => {
+            for pixel in &mut image.pixels {
+                let scale = pixel[3] * RGBM_RANGE;
+                *pixel = [pixel[0] * scale, pixel[1] * scale, pixel[2] * scale, 1.0];
+            }
+        }
+        _ => {}
+    }
+    Ok(image)
+}
+
+/// Decode unsigned BC6H blocks (Unity's `BC6H` is always the unsigned variant)
+fn decode_bc6h(data: &[u8], width: u32, height: u32) -> Result<HdrImage> {
+    let blocks_x = width.div_ceil(4) as usize;
+    let blocks_y = height.div_ceil(4) as usize;
+    let needed = blocks_x * blocks_y * 16;
+    if data.len() < needed {
+        return Err(BinaryError::not_enough_data(needed, data.len()));
+    }
+
+    let mut image = HdrImage::new(width, height);
+    for (b, block) in data[..needed].chunks_exact(16).enumerate() {
+        let block: &[u8; 16] = block.try_into().expect("chunks_exact(16)");
+        let texels = bc6h::decode_block(block, false);
+        let (bx, by) = ((b % blocks_x) * 4, (b / blocks_x) * 4);
+        for (i, [r, g, b]) in texels.into_iter().enumerate() {
+            let (x, y) = (bx + i % 4, by + i / 4);
+            if x < width as usize && y < height as usize {
+                image.pixels[y * width as usize + x] = [r, g, b, 1.0];
+            }
+        }
+    }
+    Ok(image)
+}
+
+fn rgb9e5_to_f32(packed: u32) -> [f32; 3] {
+    let scale = 2f32.powi((packed >> 27) as i32 - 15 - 9);
+    [0, 9, 18].map(|shift| ((packed >> shift) & 0x1ff) as f32 * scale)
+}
+
+pub(crate) fn half_to_f32(h: u16) -> f32 {
+    let sign = if h & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((h >> 10) & 0x1f) as i32;
+    let mantissa = (h & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        31 if mantissa == 0.0 => sign * f32::INFINITY,
+        31 => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+/// Round to the nearest half float; values beyond the half range become infinity.
+pub(crate) fn f32_to_half(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    if value.is_nan() {
+        return sign | 0x7e00;
+    }
+    let magnitude = value.abs();
+    if magnitude >= 65520.0 {
+        return sign | 0x7c00;
+    }
+    if magnitude < 2f32.powi(-14) {
+        // Subnormal: multiples of 2^-24.
+        return sign | (magnitude * 2f32.powi(24)).round_ties_even() as u16;
+    }
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127 + 15;
+    let mantissa = bits & 0x7f_ffff;
+    // Round the 23-bit mantissa to 10 bits, ties to even; a carry bumps the exponent.
+    let rounded = ((exponent as u32) << 10) + (mantissa >> 13);
+    let remainder = mantissa & 0x1fff;
+    let round_up = remainder > 0x1000 || (remainder == 0x1000 && rounded & 1 == 1);
+    sign | (rounded + round_up as u32) as u16
+}
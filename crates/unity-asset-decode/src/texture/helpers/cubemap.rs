@@ -0,0 +1,272 @@
+//! Cubemap layout export
+//!
+//! Stitches the six faces of a cubemap into a horizontal cross or resamples them into an
+//! equirectangular (latitude/longitude) panorama for skybox previews.
+//!
+//! Faces are given in Unity's order `+X, -X, +Y, -Y, +Z, -Z` and must be square and equally
+//! sized. Unity stores texture rows bottom-up, so faces decoded straight from Unity data are
+//! upside down relative to the usual cube map face orientation; [`CubemapExporter`] flips them
+//! unless [`CubemapExporter::with_flip_y`] is turned off for faces that are already upright.
+
+use super::export::ExportOptions;
+use crate::error::{BinaryError, Result};
+use image::{Rgba, RgbaImage, imageops};
+use std::f32::consts::PI;
+use std::path::Path;
+
+/// Face indices in Unity's cubemap order.
+pub const FACE_POSITIVE_X: usize = 0;
+pub const FACE_NEGATIVE_X: usize = 1;
+pub const FACE_POSITIVE_Y: usize = 2;
+pub const FACE_NEGATIVE_Y: usize = 3;
+pub const FACE_POSITIVE_Z: usize = 4;
+pub const FACE_NEGATIVE_Z: usize = 5;
+
+/// Cubemap exporter
+///
+/// Writes cubemap faces as a horizontal cross (4x3 faces) or an equirectangular panorama using
+/// the image format from its [`ExportOptions`].
+#[derive(Debug, Clone)]
+pub struct CubemapExporter {
+    options: ExportOptions,
+    flip_y: bool,
+}
+
+impl Default for CubemapExporter {
+    fn default() -> Self {
+        Self::new(ExportOptions::default())
+    }
+}
+
+impl CubemapExporter {
+    /// Create a new cubemap exporter
+    pub fn new(options: ExportOptions) -> Self {
+        Self {
+            options,
+            flip_y: true,
+        }
+    }
+
+    /// Set whether faces are flipped vertically before use (default `true`, for faces
+    /// decoded from Unity's bottom-up data).
+    pub fn with_flip_y(mut self, flip_y: bool) -> Self {
+        self.flip_y = flip_y;
+        self
+    }
+
+    /// Build the horizontal cross layout
+    ///
+    /// ```text
+    ///       +Y
+    ///   -X  +Z  +X  -Z
+    ///       -Y
+    /// ```
+    pub fn cross(&self, faces: &[RgbaImage; 6]) -> Result<RgbaImage> {
+        let size = face_size(faces)?;
+        let mut cross = RgbaImage::new(size * 4, size * 3);
+        let placement = [
+            (FACE_POSITIVE_Y, 1, 0),
+            (FACE_NEGATIVE_X, 0, 1),
+            (FACE_POSITIVE_Z, 1, 1),
+            (FACE_POSITIVE_X, 2, 1),
+            (FACE_NEGATIVE_Z, 3, 1),
+            (FACE_NEGATIVE_Y, 1, 2),
+        ];
+        for (face, column, row) in placement {
+            let (x, y) = ((column * size) as i64, (row * size) as i64);
+            if self.flip_y {
+                imageops::replace(&mut cross, &imageops::flip_vertical(&faces[face]), x, y);
+            } else {
+                imageops::replace(&mut cross, &faces[face], x, y);
+            }
+        }
+        Ok(cross)
+    }
+
+    /// Export the horizontal cross layout to `path`
+    pub fn export_cross<P: AsRef<Path>>(&self, faces: &[RgbaImage; 6], path: P) -> Result<()> {
+        self.options.export(&self.cross(faces)?, path)
+    }
+
+    /// Resample the faces into a `width` x `height` equirectangular panorama
+    ///
+    /// The center of the image looks down +Z, +X is to the right and +Y at the top. Samples are
+    /// bilinear, taking neighbouring texels from the adjacent face at face edges so seams do not
+    /// show. A `width` of `0` uses [`ExportOptions::equirect_width`] (default: four face widths);
+    /// a `height` of `0` uses half the width.
+    pub fn equirectangular(
+        &self,
+        faces: &[RgbaImage; 6],
+        width: u32,
+        height: u32,
+    ) -> Result<RgbaImage> {
+        let size = face_size(faces)?;
+        let width = match width {
+            0 => self.options.equirect_width.unwrap_or(size * 4),
+            w => w,
+        };
+        let height = match height {
+            0 => (width / 2).max(1),
+            h => h,
+        };
+        if width == 0 {
+            return Err(BinaryError::invalid_data("Equirectangular width is zero"));
+        }
+
+        let sampler = FaceSampler {
+            faces,
+            size,
+            flip_y: self.flip_y,
+        };
+        let mut out = RgbaImage::new(width, height);
+        for (x, y, pixel) in out.enumerate_pixels_mut() {
+            let longitude = ((x as f32 + 0.5) / width as f32 - 0.5) * 2.0 * PI;
+            let latitude = (0.5 - (y as f32 + 0.5) / height as f32) * PI;
+            let direction = [
+                latitude.cos() * longitude.sin(),
+                latitude.sin(),
+                latitude.cos() * longitude.cos(),
+            ];
+            *pixel = sampler.sample(direction);
+        }
+        Ok(out)
+    }
+
+    /// Export an equirectangular panorama to `path`; see [`CubemapExporter::equirectangular`]
+    pub fn export_equirectangular<P: AsRef<Path>>(
+        &self,
+        faces: &[RgbaImage; 6],
+        width: u32,
+        height: u32,
+        path: P,
+    ) -> Result<()> {
+        self.options
+            .export(&self.equirectangular(faces, width, height)?, path)
+    }
+}
+
+fn face_size(faces: &[RgbaImage; 6]) -> Result<u32> {
+    let (width, height) = faces[0].dimensions();
+    if width == 0 || width != height {
+        return Err(BinaryError::invalid_data(format!(
+            "Cubemap faces must be square, got {}x{}",
+            width, height
+        )));
+    }
+    if faces.iter().any(|f| f.dimensions() != (width, height)) {
+        return Err(BinaryError::invalid_data("Cubemap faces differ in size"));
+    }
+    Ok(width)
+}
+
+/// Major axis and the `(s, t)` axes of each face, following the Direct3D/OpenGL cube map
+/// convention (`t` grows downwards in the face image).
+const FACE_AXES: [([f32; 3], [f32; 3], [f32; 3]); 6] = [
+    ([1.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, -1.0, 0.0]),
+    ([-1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, -1.0, 0.0]),
+    ([0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]),
+    ([0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0]),
+    ([0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, -1.0, 0.0]),
+    ([0.0, 0.0, -1.0], [-1.0, 0.0, 0.0], [0.0, -1.0, 0.0]),
+];
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Face hit by `direction`, with face coordinates in `0..1`.
+fn project(direction: [f32; 3]) -> (usize, f32, f32) {
+    let [x, y, z] = direction;
+    let (ax, ay, az) = (x.abs(), y.abs(), z.abs());
+    let face = if ax >= ay && ax >= az {
+        if x >= 0.0 {
+            FACE_POSITIVE_X
+        } else {
+            FACE_NEGATIVE_X
+        }
+    } else if ay >= az {
+        if y >= 0.0 {
+            FACE_POSITIVE_Y
+        } else {
+            FACE_NEGATIVE_Y
+        }
+    } else if z >= 0.0 {
+        FACE_POSITIVE_Z
+    } else {
+        FACE_NEGATIVE_Z
+    };
+    let (major, s_axis, t_axis) = FACE_AXES[face];
+    let ma = dot(direction, major);
+    let s = (dot(direction, s_axis) / ma + 1.0) * 0.5;
+    let t = (dot(direction, t_axis) / ma + 1.0) * 0.5;
+    (face, s, t)
+}
+
+struct FaceSampler<'a> {
+    faces: &'a [RgbaImage; 6],
+    size: u32,
+    flip_y: bool,
+}
+
+impl FaceSampler<'_> {
+    fn sample(&self, direction: [f32; 3]) -> Rgba<u8> {
+        let (face, s, t) = project(direction);
+        let size = self.size as f32;
+        let (fx, fy) = (s * size - 0.5, t * size - 0.5);
+        let (x0, y0) = (fx.floor(), fy.floor());
+        let (wx, wy) = (fx - x0, fy - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        let mut acc = [0.0f32; 4];
+        for (dx, dy, weight) in [
+            (0, 0, (1.0 - wx) * (1.0 - wy)),
+            (1, 0, wx * (1.0 - wy)),
+            (0, 1, (1.0 - wx) * wy),
+            (1, 1, wx * wy),
+        ] {
+            if weight == 0.0 {
+                continue;
+            }
+            let texel = self.texel(face, x0 + dx, y0 + dy);
+            for (a, c) in acc.iter_mut().zip(texel.0) {
+                *a += c as f32 * weight;
+            }
+        }
+        Rgba(acc.map(|c| c.round().clamp(0.0, 255.0) as u8))
+    }
+
+    /// Texel `(i, j)` of `face`; coordinates just outside the face wrap onto its neighbour.
+    fn texel(&self, face: usize, i: i64, j: i64) -> Rgba<u8> {
+        let size = self.size as i64;
+        let (face, i, j) = if (0..size).contains(&i) && (0..size).contains(&j) {
+            (face, i, j)
+        } else {
+            let sc = ((i as f32 + 0.5) / self.size as f32) * 2.0 - 1.0;
+            let tc = ((j as f32 + 0.5) / self.size as f32) * 2.0 - 1.0;
+            let (major, s_axis, t_axis) = FACE_AXES[face];
+            let direction: [f32; 3] =
+                std::array::from_fn(|k| major[k] + sc * s_axis[k] + tc * t_axis[k]);
+            let (face, s, t) = project(direction);
+            let to_texel = |v: f32| ((v * self.size as f32) as i64).clamp(0, size - 1);
+            (face, to_texel(s), to_texel(t))
+        };
+        let row = if self.flip_y { size - 1 - j } else { j };
+        *self.faces[face].get_pixel(i as u32, row as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projection_hits_face_centers() {
+        assert_eq!(project([1.0, 0.0, 0.0]), (FACE_POSITIVE_X, 0.5, 0.5));
+        assert_eq!(project([0.0, -1.0, 0.0]), (FACE_NEGATIVE_Y, 0.5, 0.5));
+        assert_eq!(project([0.0, 0.0, -1.0]), (FACE_NEGATIVE_Z, 0.5, 0.5));
+        // Up and to the right on +Z lands in the face's top-right quadrant.
+        let (face, s, t) = project([0.5, 0.5, 1.0]);
+        assert_eq!(face, FACE_POSITIVE_Z);
+        assert!(s > 0.5 && t < 0.5);
+    }
+}
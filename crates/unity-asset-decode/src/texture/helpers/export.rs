@@ -3,8 +3,13 @@
 //! This module provides functionality for exporting textures to various image formats.
 
 use crate::error::{BinaryError, Result};
+use crate::texture::channels::{ChannelTransform, TransformRule};
+use crate::texture::decoders::TextureDecoder;
+use crate::texture::hdr::HdrImage;
+use crate::texture::helpers::ktx2::{Ktx2Options, encode_ktx2};
+use crate::texture::types::{Texture2D, Texture3D};
 use image::{ImageFormat, RgbaImage};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Texture exporter utility
 ///
@@ -52,6 +57,27 @@ impl TextureExporter {
             .map_err(|e| BinaryError::generic(format!("Failed to save TIFF: {}", e)))
     }
 
+    /// Export an HDR image as half-float OpenEXR
+    ///
+    /// Values above 1.0 are kept, so HDR lightmaps and probes survive the round trip.
+    pub fn export_exr<P: AsRef<Path>>(image: &HdrImage, path: P) -> Result<()> {
+        std::fs::write(path, super::exr::encode_exr(image))
+            .map_err(|e| BinaryError::generic(format!("Failed to save EXR: {}", e)))
+    }
+
+    /// Export a Texture2D as KTX2, keeping its mip levels
+    ///
+    /// Unlike the image exports this takes the texture itself, so GPU-ready block-compressed
+    /// data can be stored without a decode round trip; see [`encode_ktx2`].
+    pub fn export_ktx2<P: AsRef<Path>>(
+        texture: &Texture2D,
+        path: P,
+        options: &Ktx2Options,
+    ) -> Result<()> {
+        std::fs::write(path, encode_ktx2(texture, options)?)
+            .map_err(|e| BinaryError::generic(format!("Failed to save KTX2: {}", e)))
+    }
+
     /// Export texture with automatic format detection based on file extension
     pub fn export_auto<P: AsRef<Path>>(image: &RgbaImage, path: P) -> Result<()> {
         let path_ref = path.as_ref();
@@ -127,24 +153,94 @@ impl TextureExporter {
         Self::validate_for_export(image)?;
         Self::export_auto(image, path)
     }
+
+    /// Place equally sized slices side by side, left to right
+    pub fn slice_strip(slices: &[RgbaImage]) -> Result<RgbaImage> {
+        let Some(first) = slices.first() else {
+            return Err(BinaryError::invalid_data("No slices to combine"));
+        };
+        let (width, height) = first.dimensions();
+        if slices.iter().any(|s| s.dimensions() != (width, height)) {
+            return Err(BinaryError::invalid_data("Slices differ in size"));
+        }
+
+        let mut strip = RgbaImage::new(width * slices.len() as u32, height);
+        for (z, slice) in slices.iter().enumerate() {
+            image::imageops::replace(&mut strip, slice, (z as u32 * width) as i64, 0);
+        }
+        Ok(strip)
+    }
+
+    /// Export decoded volume slices according to `options.slice_layout`
+    ///
+    /// [`SliceLayout::Separate`] writes `<stem>_<z>.<ext>` next to `path`;
+    /// [`SliceLayout::Strip`] writes one image at `path`. Returns the written paths.
+    pub fn export_slices<P: AsRef<Path>>(
+        slices: &[RgbaImage],
+        path: P,
+        options: &ExportOptions,
+    ) -> Result<Vec<PathBuf>> {
+        let path = path.as_ref();
+        match options.slice_layout {
+            SliceLayout::Strip => {
+                let strip = Self::slice_strip(slices)?;
+                Self::validate_for_export(&strip)?;
+                options.export(&strip, path)?;
+                Ok(vec![path.to_path_buf()])
+            }
+            SliceLayout::Separate => {
+                let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("slice");
+                let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("png");
+                let mut written = Vec::with_capacity(slices.len());
+                for (z, slice) in slices.iter().enumerate() {
+                    let slice_path = path.with_file_name(format!("{}_{}.{}", stem, z, extension));
+                    options.export(slice, &slice_path)?;
+                    written.push(slice_path);
+                }
+                Ok(written)
+            }
+        }
+    }
+
+    /// Decode every slice of a Texture3D and export it with [`TextureExporter::export_slices`]
+    pub fn export_texture3d<P: AsRef<Path>>(
+        texture: &Texture3D,
+        path: P,
+        options: &ExportOptions,
+    ) -> Result<Vec<PathBuf>> {
+        let slices = texture.decode_all_slices()?;
+        Self::export_slices(&slices, path, options)
+    }
+}
+
+/// How volume (Texture3D) slices are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SliceLayout {
+    /// One image per slice
+    #[default]
+    Separate,
+    /// All slices side by side in one horizontal strip (the usual LUT layout)
+    Strip,
 }
 
 /// Export options for advanced export scenarios
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ExportOptions {
     pub format: ImageFormat,
-    pub quality: Option<u8>,     // For JPEG
-    pub compression: Option<u8>, // For PNG
-}
-
-impl Default for ExportOptions {
-    fn default() -> Self {
-        Self {
-            format: ImageFormat::Png,
-            quality: Some(90),
-            compression: Some(6),
-        }
-    }
+    pub quality: Option<u8>,       // For JPEG
+    pub compression: Option<u8>,   // For PNG
+    pub slice_layout: SliceLayout, // For Texture3D
+    /// Equirectangular panorama width for cubemaps when no explicit size is given
+    /// (`None`: four face widths).
+    pub equirect_width: Option<u32>,
+    /// Per-texture channel transforms; the first rule matching a texture is applied by
+    /// [`ExportOptions::apply_channel_transforms`].
+    pub channel_transforms: Vec<TransformRule>,
+    /// Rebuild Z of BC5 normal maps into blue when decoding through [`ExportOptions::decoder`]
+    pub reconstruct_bc5_normals: bool,
+    /// Exposure of the BC6H tonemap in [`ExportOptions::decoder`]
+    pub bc6h_exposure: f32,
 }
 
 impl ExportOptions {
@@ -154,6 +250,11 @@ impl ExportOptions {
             format: ImageFormat::Png,
             quality: None,
             compression: Some(6),
+            slice_layout: SliceLayout::default(),
+            equirect_width: None,
+            channel_transforms: Vec::new(),
+            reconstruct_bc5_normals: true,
+            bc6h_exposure: 1.0,
         }
     }
 
@@ -163,6 +264,11 @@ impl ExportOptions {
             format: ImageFormat::Jpeg,
             quality: Some(quality.clamp(1, 100)),
             compression: None,
+            slice_layout: SliceLayout::default(),
+            equirect_width: None,
+            channel_transforms: Vec::new(),
+            reconstruct_bc5_normals: true,
+            bc6h_exposure: 1.0,
         }
     }
 
@@ -172,6 +278,11 @@ impl ExportOptions {
             format: ImageFormat::Bmp,
             quality: None,
             compression: None,
+            slice_layout: SliceLayout::default(),
+            equirect_width: None,
+            channel_transforms: Vec::new(),
+            reconstruct_bc5_normals: true,
+            bc6h_exposure: 1.0,
         }
     }
 
@@ -185,4 +296,85 @@ impl ExportOptions {
             _ => TextureExporter::export_with_format(image, path, self.format),
         }
     }
+
+    /// Set `format`.
+    pub fn with_format(mut self, format: ImageFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set `equirect_width`.
+    pub fn with_equirect_width(mut self, equirect_width: Option<u32>) -> Self {
+        self.equirect_width = equirect_width;
+        self
+    }
+
+    /// Set `slice_layout`.
+    pub fn with_slice_layout(mut self, slice_layout: SliceLayout) -> Self {
+        self.slice_layout = slice_layout;
+        self
+    }
+
+    /// Set `reconstruct_bc5_normals`.
+    pub fn with_bc5_normal_reconstruction(mut self, reconstruct: bool) -> Self {
+        self.reconstruct_bc5_normals = reconstruct;
+        self
+    }
+
+    /// Set `bc6h_exposure`.
+    pub fn with_bc6h_exposure(mut self, exposure: f32) -> Self {
+        self.bc6h_exposure = exposure;
+        self
+    }
+
+    /// A [`TextureDecoder`] configured with the BC5 and BC6H settings
+    pub fn decoder(&self) -> TextureDecoder {
+        TextureDecoder::new()
+            .with_bc5_normal_reconstruction(self.reconstruct_bc5_normals)
+            .with_bc6h_exposure(self.bc6h_exposure)
+    }
+
+    /// Append a rule to `channel_transforms`.
+    pub fn with_channel_transform(mut self, rule: TransformRule) -> Self {
+        self.channel_transforms.push(rule);
+        self
+    }
+
+    /// The transform of the first rule matching a texture called `name` bound to the material
+    /// `slots` (see [`texture_material_slots`](crate::texture::texture_material_slots))
+    pub fn channel_transform_for(&self, name: &str, slots: &[String]) -> Option<&ChannelTransform> {
+        self.channel_transforms
+            .iter()
+            .find(|rule| rule.matches(name, slots))
+            .map(|rule| &rule.transform)
+    }
+
+    /// Apply [`ExportOptions::channel_transform_for`] to a decoded texture; images without a
+    /// matching rule are returned unchanged
+    pub fn apply_channel_transforms(
+        &self,
+        image: RgbaImage,
+        name: &str,
+        slots: &[String],
+    ) -> Result<RgbaImage> {
+        match self.channel_transform_for(name, slots) {
+            Some(transform) => transform.apply(&image),
+            None => Ok(image),
+        }
+    }
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            format: ImageFormat::Png,
+            quality: Some(90),
+            compression: Some(6),
+            slice_layout: SliceLayout::default(),
+            equirect_width: None,
+            channel_transforms: Vec::new(),
+            reconstruct_bc5_normals: true,
+            bc6h_exposure: 1.0,
+        }
+    }
 }
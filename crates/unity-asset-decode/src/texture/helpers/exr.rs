@@ -0,0 +1,76 @@
+//! Minimal OpenEXR writer
+//!
+//! Writes a single-part, uncompressed scanline file with half-float `R`, `G`, `B`, `A`
+//! channels, which every EXR reader accepts and which keeps HDR values above 1.0.
+
+use crate::texture::hdr::{HdrImage, f32_to_half};
+
+const MAGIC: u32 = 20000630;
+/// Format version 2, single-part scanline file.
+const VERSION: u32 = 2;
+const PIXEL_TYPE_HALF: i32 = 1;
+
+/// Channels in the alphabetical order EXR stores them, with their index in a pixel.
+const CHANNELS: [(&str, usize); 4] = [("A", 3), ("B", 2), ("G", 1), ("R", 0)];
+
+/// Encode an image as an EXR file
+///
+/// Rows are written in the image's own order; HDR images decoded from Unity data are bottom
+/// row first, matching the 8-bit export paths.
+pub fn encode_exr(image: &HdrImage) -> Vec<u8> {
+    let (width, height) = (image.width as usize, image.height as usize);
+    let mut out = Vec::new();
+    out.extend_from_slice(&MAGIC.to_le_bytes());
+    out.extend_from_slice(&VERSION.to_le_bytes());
+
+    let mut channels = Vec::new();
+    for (name, _) in CHANNELS {
+        channels.extend_from_slice(name.as_bytes());
+        channels.push(0);
+        channels.extend_from_slice(&PIXEL_TYPE_HALF.to_le_bytes());
+        channels.extend_from_slice(&[0; 4]); // pLinear + reserved
+        channels.extend_from_slice(&1i32.to_le_bytes()); // xSampling
+        channels.extend_from_slice(&1i32.to_le_bytes()); // ySampling
+    }
+    channels.push(0);
+    let window: Vec<u8> = [0, 0, width as i32 - 1, height as i32 - 1]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+
+    attribute(&mut out, "channels", "chlist", &channels);
+    attribute(&mut out, "compression", "compression", &[0]);
+    attribute(&mut out, "dataWindow", "box2i", &window);
+    attribute(&mut out, "displayWindow", "box2i", &window);
+    attribute(&mut out, "lineOrder", "lineOrder", &[0]);
+    attribute(&mut out, "pixelAspectRatio", "float", &1f32.to_le_bytes());
+    attribute(&mut out, "screenWindowCenter", "v2f", &[0; 8]);
+    attribute(&mut out, "screenWindowWidth", "float", &1f32.to_le_bytes());
+    out.push(0);
+
+    let line_size = width * CHANNELS.len() * 2;
+    let table_end = out.len() + height * 8;
+    for y in 0..height {
+        let offset = table_end + y * (8 + line_size);
+        out.extend_from_slice(&(offset as u64).to_le_bytes());
+    }
+    for (y, row) in image.pixels.chunks_exact(width.max(1)).enumerate() {
+        out.extend_from_slice(&(y as i32).to_le_bytes());
+        out.extend_from_slice(&(line_size as i32).to_le_bytes());
+        for (_, index) in CHANNELS {
+            for pixel in row {
+                out.extend_from_slice(&f32_to_half(pixel[index]).to_le_bytes());
+            }
+        }
+    }
+    out
+}
+
+fn attribute(out: &mut Vec<u8>, name: &str, kind: &str, value: &[u8]) {
+    out.extend_from_slice(name.as_bytes());
+    out.push(0);
+    out.extend_from_slice(kind.as_bytes());
+    out.push(0);
+    out.extend_from_slice(&(value.len() as i32).to_le_bytes());
+    out.extend_from_slice(value);
+}
@@ -0,0 +1,357 @@
+//! Minimal KTX2 writer
+//!
+//! Block-compressed formats a GPU can sample directly (BC1/3/4/5/7, ETC1/ETC2, ASTC) are copied
+//! into the container untouched with the matching `VkFormat`; everything else is decoded to
+//! `R8G8B8A8`. Every mip level present in the source is kept in the level index.
+//!
+//! No supercompression is applied (`supercompressionScheme = 0`); Basis Universal UASTC/ETC1S
+//! output is tracked in `docs/ROADMAP.md`.
+//!
+//! Unity stores rows bottom-up and compressed blocks cannot be flipped losslessly, so the data
+//! is written as-is and tagged `KTXorientation = "ru"`.
+
+use crate::error::{BinaryError, Result};
+use crate::texture::decoders::TextureDecoder;
+use crate::texture::formats::TextureFormat;
+use crate::texture::types::Texture2D;
+
+/// File identifier `«KTX 20»\r\n\x1A\n`.
+pub const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+/// Identifier plus the nine `u32` header fields and the DFD/KVD/SGD index.
+const HEADER_SIZE: usize = 80;
+const LEVEL_INDEX_ENTRY_SIZE: usize = 24;
+
+const VK_FORMAT_R8G8B8A8_UNORM: u32 = 37;
+
+// Data format descriptor color models and channel ids (Khronos Data Format Specification).
+const MODEL_RGBSDA: u8 = 1;
+const MODEL_BC1A: u8 = 128;
+const MODEL_BC3: u8 = 130;
+const MODEL_BC4: u8 = 131;
+const MODEL_BC5: u8 = 132;
+const MODEL_BC7: u8 = 134;
+const MODEL_ETC2: u8 = 161;
+const MODEL_ASTC: u8 = 162;
+const CHANNEL_ALPHA: u8 = 15;
+const CHANNEL_ETC2_COLOR: u8 = 2;
+const SAMPLE_LINEAR: u8 = 0x10;
+
+/// How [`encode_ktx2`] stores the pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Ktx2Encoding {
+    /// Copy block-compressed data with a matching `VkFormat`, decode anything else to RGBA8
+    #[default]
+    Auto,
+    /// Always decode to `R8G8B8A8`
+    Rgba8,
+}
+
+/// KTX2 export options
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct Ktx2Options {
+    pub encoding: Ktx2Encoding,
+    /// Pick the sRGB `VkFormat` variant (`None`: sRGB when the texture's `m_ColorSpace` is 1).
+    pub srgb: Option<bool>,
+}
+
+impl Ktx2Options {
+    /// Set `encoding`.
+    pub fn with_encoding(mut self, encoding: Ktx2Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Set `srgb`.
+    pub fn with_srgb(mut self, srgb: Option<bool>) -> Self {
+        self.srgb = srgb;
+        self
+    }
+}
+
+/// Block layout and descriptor of a format stored without re-encoding
+struct BlockFormat {
+    /// `(UNORM, SRGB)` Vulkan formats; the same value twice for formats without sRGB.
+    vk_format: (u32, u32),
+    block: (u32, u32),
+    bytes_per_block: u32,
+    model: u8,
+    /// `(bit offset, bit length, channel)`
+    samples: &'static [(u16, u8, u8)],
+}
+
+impl BlockFormat {
+    fn level_size(&self, width: u32, height: u32) -> usize {
+        (width.div_ceil(self.block.0) * height.div_ceil(self.block.1) * self.bytes_per_block)
+            as usize
+    }
+}
+
+const RGBA8: BlockFormat = BlockFormat {
+    vk_format: (VK_FORMAT_R8G8B8A8_UNORM, 43),
+    block: (1, 1),
+    bytes_per_block: 4,
+    model: MODEL_RGBSDA,
+    samples: &[(0, 8, 0), (8, 8, 1), (16, 8, 2), (24, 8, CHANNEL_ALPHA)],
+};
+
+fn astc(vk_unorm: u32, block: (u32, u32)) -> BlockFormat {
+    BlockFormat {
+        vk_format: (vk_unorm, vk_unorm + 1),
+        block,
+        bytes_per_block: 16,
+        model: MODEL_ASTC,
+        samples: &[(0, 128, 0)],
+    }
+}
+
+fn bc(
+    vk_format: (u32, u32),
+    bytes_per_block: u32,
+    model: u8,
+    samples: &'static [(u16, u8, u8)],
+) -> BlockFormat {
+    BlockFormat {
+        vk_format,
+        block: (4, 4),
+        bytes_per_block,
+        model,
+        samples,
+    }
+}
+
+/// The container layout for `format` when its blocks can be copied as-is
+fn passthrough_format(format: TextureFormat) -> Option<BlockFormat> {
+    Some(match format {
+        TextureFormat::DXT1 => bc((131, 132), 8, MODEL_BC1A, &[(0, 64, 0)]),
+        TextureFormat::DXT5 => bc(
+            (137, 138),
+            16,
+            MODEL_BC3,
+            &[(0, 64, CHANNEL_ALPHA), (64, 64, 0)],
+        ),
+        TextureFormat::BC4 => bc((139, 139), 8, MODEL_BC4, &[(0, 64, 0)]),
+        TextureFormat::BC5 => bc((141, 141), 16, MODEL_BC5, &[(0, 64, 0), (64, 64, 1)]),
+        TextureFormat::BC7 => bc((145, 146), 16, MODEL_BC7, &[(0, 128, 0)]),
+        // ETC1 data is valid ETC2 RGB.
+        TextureFormat::ETC_RGB4 | TextureFormat::ETC2_RGB => {
+            bc((147, 148), 8, MODEL_ETC2, &[(0, 64, CHANNEL_ETC2_COLOR)])
+        }
+        TextureFormat::ETC2_RGBA8 => bc(
+            (151, 152),
+            16,
+            MODEL_ETC2,
+            &[(0, 64, CHANNEL_ALPHA), (64, 64, CHANNEL_ETC2_COLOR)],
+        ),
+        TextureFormat::ASTC_RGB_4x4 | TextureFormat::ASTC_RGBA_4x4 => astc(157, (4, 4)),
+        TextureFormat::ASTC_RGB_5x5 | TextureFormat::ASTC_RGBA_5x5 => astc(161, (5, 5)),
+        TextureFormat::ASTC_RGB_6x6 | TextureFormat::ASTC_RGBA_6x6 => astc(165, (6, 6)),
+        TextureFormat::ASTC_RGB_8x8 | TextureFormat::ASTC_RGBA_8x8 => astc(171, (8, 8)),
+        TextureFormat::ASTC_RGB_10x10 | TextureFormat::ASTC_RGBA_10x10 => astc(179, (10, 10)),
+        TextureFormat::ASTC_RGB_12x12 | TextureFormat::ASTC_RGBA_12x12 => astc(183, (12, 12)),
+        _ => return None,
+    })
+}
+
+/// Encode `texture` (with its image data loaded) as a KTX2 file
+pub fn encode_ktx2(texture: &Texture2D, options: &Ktx2Options) -> Result<Vec<u8>> {
+    if !texture.has_valid_dimensions() {
+        return Err(BinaryError::invalid_data("Invalid texture dimensions"));
+    }
+    if texture.image_data.is_empty() {
+        return Err(BinaryError::invalid_data(
+            "Texture has no image data (load streamed data first)",
+        ));
+    }
+    let srgb = options.srgb.unwrap_or(texture.color_space == 1);
+
+    let passthrough = match options.encoding {
+        Ktx2Encoding::Auto => passthrough_format(texture.format),
+        Ktx2Encoding::Rgba8 => None,
+    };
+    let (format, levels) = match passthrough {
+        Some(format) => {
+            let levels = source_levels(texture, |w, h| format.level_size(w, h))
+                .into_iter()
+                .map(|data| data.to_vec())
+                .collect();
+            (format, levels)
+        }
+        None => (RGBA8, decode_levels(texture)?),
+    };
+    if levels.is_empty() {
+        return Err(BinaryError::invalid_data(format!(
+            "Image data ({} bytes) is smaller than the top mip level",
+            texture.image_data.len()
+        )));
+    }
+
+    // BC4/BC5 have no sRGB variant.
+    let srgb = srgb && format.vk_format.0 != format.vk_format.1;
+    let vk_format = if srgb {
+        format.vk_format.1
+    } else {
+        format.vk_format.0
+    };
+    let dfd = data_format_descriptor(&format, srgb);
+    let kvd = key_values(&[
+        ("KTXorientation", "ru"),
+        (
+            "KTXwriter",
+            concat!("unity-asset-decode ", env!("CARGO_PKG_VERSION")),
+        ),
+    ]);
+
+    let dfd_offset = HEADER_SIZE + levels.len() * LEVEL_INDEX_ENTRY_SIZE;
+    let kvd_offset = dfd_offset + dfd.len();
+    let mut out = Vec::new();
+    out.extend_from_slice(&KTX2_IDENTIFIER);
+    for value in [
+        vk_format,
+        1, // typeSize
+        texture.width as u32,
+        texture.height as u32,
+        0, // pixelDepth
+        0, // layerCount
+        1, // faceCount
+        levels.len() as u32,
+        0, // supercompressionScheme
+        dfd_offset as u32,
+        dfd.len() as u32,
+        kvd_offset as u32,
+        kvd.len() as u32,
+    ] {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    out.extend_from_slice(&[0; 16]); // sgdByteOffset, sgdByteLength
+
+    // Level data is stored smallest first, each level aligned to lcm(block size, 4).
+    let alignment = lcm(format.bytes_per_block as usize, 4);
+    let mut offset = kvd_offset + kvd.len();
+    let mut placed = vec![0; levels.len()];
+    for (level, data) in levels.iter().enumerate().rev() {
+        offset = offset.next_multiple_of(alignment);
+        placed[level] = offset;
+        offset += data.len();
+    }
+    for (data, offset) in levels.iter().zip(&placed) {
+        for value in [*offset, data.len(), data.len()] {
+            out.extend_from_slice(&(value as u64).to_le_bytes());
+        }
+    }
+    out.extend_from_slice(&dfd);
+    out.extend_from_slice(&kvd);
+    for (level, data) in levels.iter().enumerate().rev() {
+        out.resize(placed[level], 0);
+        out.extend_from_slice(data);
+    }
+    Ok(out)
+}
+
+/// Number of mip levels the texture declares
+fn declared_levels(texture: &Texture2D) -> u32 {
+    if texture.mip_map || texture.mip_count > 1 {
+        texture.mip_count.max(1) as u32
+    } else {
+        1
+    }
+}
+
+/// Slice the source data into mip levels, dropping levels the data is too short for
+fn source_levels(texture: &Texture2D, level_size: impl Fn(u32, u32) -> usize) -> Vec<&[u8]> {
+    let (width, height) = texture.dimensions();
+    let mut levels = Vec::new();
+    let mut offset = 0;
+    for level in 0..declared_levels(texture) {
+        let size = level_size((width >> level).max(1), (height >> level).max(1));
+        let Some(data) = texture.image_data.get(offset..offset + size) else {
+            break;
+        };
+        levels.push(data);
+        offset += size;
+    }
+    levels
+}
+
+/// Decode every mip level to RGBA8
+fn decode_levels(texture: &Texture2D) -> Result<Vec<Vec<u8>>> {
+    let decoder = TextureDecoder::new();
+    let (width, height) = texture.dimensions();
+    let level_size = |w, h| texture.format.calculate_data_size(w, h) as usize;
+    if level_size(width, height) == 0 {
+        // Size unknown for this format: only the top level can be located.
+        return Ok(vec![decoder.decode(texture)?.into_raw()]);
+    }
+    source_levels(texture, level_size)
+        .into_iter()
+        .enumerate()
+        .map(|(level, data)| {
+            let level_texture = Texture2D {
+                width: (width >> level).max(1) as i32,
+                height: (height >> level).max(1) as i32,
+                format: texture.format,
+                image_data: data.to_vec(),
+                ..Default::default()
+            };
+            Ok(decoder.decode(&level_texture)?.into_raw())
+        })
+        .collect()
+}
+
+/// A data format descriptor holding one basic descriptor block
+fn data_format_descriptor(format: &BlockFormat, srgb: bool) -> Vec<u8> {
+    let block_size = 24 + 16 * format.samples.len();
+    let mut dfd = Vec::with_capacity(4 + block_size);
+    dfd.extend_from_slice(&((4 + block_size) as u32).to_le_bytes());
+    dfd.extend_from_slice(&0u32.to_le_bytes()); // vendorId = Khronos, descriptorType = basic
+    dfd.extend_from_slice(&2u16.to_le_bytes()); // versionNumber
+    dfd.extend_from_slice(&(block_size as u16).to_le_bytes());
+    let transfer = if srgb { 2 } else { 1 };
+    dfd.extend_from_slice(&[format.model, 1, transfer, 0]); // BT.709 primaries, straight alpha
+    dfd.extend_from_slice(&[(format.block.0 - 1) as u8, (format.block.1 - 1) as u8, 0, 0]);
+    dfd.extend_from_slice(&[format.bytes_per_block as u8, 0, 0, 0, 0, 0, 0, 0]);
+    for &(bit_offset, bit_length, channel) in format.samples {
+        // Alpha stays linear in sRGB formats.
+        let qualifiers = if srgb && channel == CHANNEL_ALPHA {
+            SAMPLE_LINEAR
+        } else {
+            0
+        };
+        let upper = if format.model == MODEL_RGBSDA {
+            (1u32 << bit_length) - 1
+        } else {
+            u32::MAX
+        };
+        dfd.extend_from_slice(&bit_offset.to_le_bytes());
+        dfd.extend_from_slice(&[bit_length - 1, channel | qualifiers]);
+        dfd.extend_from_slice(&[0; 4]); // samplePosition
+        dfd.extend_from_slice(&0u32.to_le_bytes()); // sampleLower
+        dfd.extend_from_slice(&upper.to_le_bytes());
+    }
+    dfd
+}
+
+/// Key/value data; keys must already be sorted
+fn key_values(entries: &[(&str, &str)]) -> Vec<u8> {
+    let mut kvd = Vec::new();
+    for (key, value) in entries {
+        let length = key.len() + 1 + value.len() + 1;
+        kvd.extend_from_slice(&(length as u32).to_le_bytes());
+        kvd.extend_from_slice(key.as_bytes());
+        kvd.push(0);
+        kvd.extend_from_slice(value.as_bytes());
+        kvd.push(0);
+        kvd.resize(kvd.len().next_multiple_of(4), 0);
+    }
+    kvd
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    a / x * b
+}
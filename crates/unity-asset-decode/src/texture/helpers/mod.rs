@@ -2,8 +2,14 @@
 //!
 //! This module provides utility functions and helpers for texture processing.
 
+pub mod cubemap;
 pub mod export;
+pub mod exr;
+pub mod ktx2;
 pub mod swizzler;
 
+pub use cubemap::CubemapExporter;
 pub use export::TextureExporter;
+pub use exr::encode_exr;
+pub use ktx2::{Ktx2Encoding, Ktx2Options, encode_ktx2};
 pub use swizzler::TextureSwizzler;
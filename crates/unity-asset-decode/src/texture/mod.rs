@@ -7,10 +7,15 @@
 //!
 //! The module is organized into several sub-modules:
 //! - `formats` - Texture format definitions and metadata
-//! - `types` - Core data structures (Texture2D, etc.)
+//! - `types` - Core data structures (Texture2D, Texture3D, etc.)
 //! - `converter` - Main conversion logic from Unity objects
 //! - `decoders` - Specialized decoders for different format categories
+//! - `encoder` - Re-encoding images into uncompressed formats (`Texture2D::set_image`)
 //! - `helpers` - Utility functions for export and data manipulation
+//! - `hdr` - Floating point decoding for HDR formats and encoded lightmaps
+//! - `alpha_split` - Recombining ETC1 RGB textures with their separate alpha textures
+//! - `channels` - Channel extraction, swizzles and normal map reconstruction
+//! - `similarity` - Perceptual hashes and near-duplicate grouping
 //!
 //! # Examples
 //!
@@ -32,24 +37,42 @@
 //! // TextureExporter::export_png(&image, "output.png")?;
 //! ```
 
+pub mod alpha_split;
+pub mod channels;
 pub mod converter;
 pub mod decoders;
+pub mod encoder;
 pub mod formats;
+pub mod hdr;
 pub mod helpers;
+pub mod similarity;
 pub mod types;
 
 // Re-export main types for easy access
-pub use converter::{Texture2DConverter, Texture2DProcessor}; // Processor is legacy alias
+pub use alpha_split::{find_alpha_companion, merge_alpha};
+pub use channels::{
+    Channel, ChannelTransform, TransformRule, TransformTarget, extract_channel,
+    reconstruct_normal_bc5, reconstruct_normal_dxt5nm, swizzle, texture_material_slots,
+};
+pub use converter::{Texture2DConverter, Texture2DProcessor, Texture3DConverter}; // Processor is legacy alias
 pub use decoders::{Decoder, TextureDecoder};
+pub use encoder::{ReencodeOptions, TextureEncoder};
 pub use formats::{TextureFormat, TextureFormatInfo};
-pub use helpers::{TextureExporter, TextureSwizzler};
-pub use types::{GLTextureSettings, StreamingInfo, Texture2D};
+pub use hdr::{HdrImage, decode_hdr, is_hdr_format};
+pub use helpers::{CubemapExporter, TextureExporter, TextureSwizzler};
+pub use similarity::{
+    PHash, SimilarGroup, TextureFingerprint, find_similar_textures, perceptual_hash,
+};
+pub use types::{
+    DecodedPixels, GLTextureSettings, PixelLayout, StreamingInfo, Texture2D, Texture3D,
+};
 
 // Re-export decoder types for advanced usage
 pub use decoders::{BasicDecoder, CompressedDecoder, CrunchDecoder, MobileDecoder};
 
 // Re-export export options
-pub use helpers::export::ExportOptions;
+pub use helpers::export::{ExportOptions, SliceLayout};
+pub use helpers::ktx2::{Ktx2Encoding, Ktx2Options, encode_ktx2};
 
 /// Main texture processing facade
 ///
@@ -82,7 +105,23 @@ impl TextureProcessor {
         self.decoder.decode(texture)
     }
 
+    /// Decode an alpha-split pair: `rgb` supplies the color and `alpha` the alpha channel
+    ///
+    /// See [`alpha_split::merge_alpha`]; both textures must have the same dimensions.
+    pub fn decode_with_alpha_split(
+        &self,
+        rgb: &Texture2D,
+        alpha: &Texture2D,
+    ) -> crate::error::Result<image::RgbaImage> {
+        let color = self.decode_texture(rgb)?;
+        let mask = self.decode_texture(alpha)?;
+        merge_alpha(color, &mask, alpha.format)
+    }
+
     /// Full pipeline: convert object -> decode -> export
+    ///
+    /// Streamed textures need the bundle holding their `.resS` data; see
+    /// [`TextureProcessor::process_and_export_from_bundle`].
     pub fn process_and_export<P: AsRef<std::path::Path>>(
         &self,
         obj: &crate::object::UnityObject,
@@ -93,6 +132,22 @@ impl TextureProcessor {
         TextureExporter::export_auto(&image, output_path)
     }
 
+    /// [`TextureProcessor::process_and_export`] for an object read from `bundle`, loading
+    /// streamed image data from the bundle's `.resS` node first
+    ///
+    /// See [`Texture2DConverter::resolve_stream_data`].
+    pub fn process_and_export_from_bundle<P: AsRef<std::path::Path>>(
+        &self,
+        obj: &crate::object::UnityObject,
+        bundle: &crate::bundle::AssetBundle,
+        output_path: P,
+    ) -> crate::error::Result<()> {
+        let mut texture = self.convert_object(obj)?;
+        self.converter.resolve_stream_data(&mut texture, bundle)?;
+        let image = self.decode_texture(&texture)?;
+        TextureExporter::export_auto(&image, output_path)
+    }
+
     /// Check if a format can be processed
     pub fn can_process(&self, format: TextureFormat) -> bool {
         self.decoder.can_decode(format)
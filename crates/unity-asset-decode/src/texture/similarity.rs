@@ -0,0 +1,221 @@
+//! Near-duplicate detection for decoded textures
+//!
+//! Exact content hashes miss the same texture shipped at another resolution or with a slight
+//! recolor. [`perceptual_hash`] reduces an image to 64 bits that survive both, and
+//! [`find_similar_textures`] groups textures whose hashes are within a Hamming distance.
+//!
+//! # Algorithm (frozen)
+//!
+//! The hash is a difference hash (dHash). Its output is part of the stable interface: hashes
+//! stored in reports stay comparable across releases.
+//!
+//! 1. Luma per pixel: `299 * R + 587 * G + 114 * B` (alpha is ignored).
+//! 2. Downscale to 9x8 with an area-weighted box filter: each output cell sums the luma of the
+//!    source area it covers, partially covered pixels weighted by their overlap. Overlaps are
+//!    measured exactly in integers by scaling the image by 9 horizontally and 8 vertically, so
+//!    every cell covers the same area and the sums compare like means. Images smaller than 9x8
+//!    are stretched the same way.
+//! 3. For each row `y` in `0..8` and column `x` in `0..8`, set bit `63 - (y * 8 + x)` when cell
+//!    `(x, y)` is darker than cell `(x + 1, y)`.
+//!
+//! Uniform images hash to 0, so flat textures of any color group together.
+
+use image::RgbaImage;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+const HASH_WIDTH: usize = 9;
+const HASH_HEIGHT: usize = 8;
+
+/// 64-bit perceptual hash of an image (see the module docs for the algorithm)
+///
+/// Displays and serializes as 16 lowercase hex digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PHash(pub u64);
+
+impl PHash {
+    /// Hamming distance: the number of differing bits (0..=64)
+    pub fn distance(&self, other: &PHash) -> u32 {
+        (self.0 ^ other.0).count_ones()
+    }
+}
+
+impl fmt::Display for PHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl FromStr for PHash {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        u64::from_str_radix(s, 16).map(PHash)
+    }
+}
+
+impl Serialize for PHash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PHash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Hash `image` (see the module docs for the algorithm)
+///
+/// An empty image hashes to 0.
+pub fn perceptual_hash(image: &RgbaImage) -> PHash {
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    if width == 0 || height == 0 {
+        return PHash(0);
+    }
+
+    let columns = box_weights(width, HASH_WIDTH);
+    let rows = box_weights(height, HASH_HEIGHT);
+    let mut cells = [[0u64; HASH_WIDTH]; HASH_HEIGHT];
+    for (cy, row_weights) in rows.iter().enumerate() {
+        for (cx, column_weights) in columns.iter().enumerate() {
+            let mut sum = 0u64;
+            for &(y, wy) in row_weights {
+                for &(x, wx) in column_weights {
+                    let [r, g, b, _] = image.get_pixel(x as u32, y as u32).0;
+                    let luma = 299 * r as u64 + 587 * g as u64 + 114 * b as u64;
+                    sum += luma * wx * wy;
+                }
+            }
+            cells[cy][cx] = sum;
+        }
+    }
+
+    let mut hash = 0u64;
+    for (y, row) in cells.iter().enumerate() {
+        for x in 0..HASH_WIDTH - 1 {
+            if row[x] < row[x + 1] {
+                hash |= 1 << (63 - (y * 8 + x));
+            }
+        }
+    }
+    PHash(hash)
+}
+
+/// For each of `cells` output cells, the source pixels it covers with their overlap, in units of
+/// `1 / cells` pixel.
+fn box_weights(source: usize, cells: usize) -> Vec<Vec<(usize, u64)>> {
+    // Cell `c` spans `[c * source, (c + 1) * source)` and pixel `p` spans
+    // `[p * cells, (p + 1) * cells)` on the common scale.
+    (0..cells)
+        .map(|cell| {
+            let (start, end) = (cell * source, (cell + 1) * source);
+            (start / cells..end.div_ceil(cells).min(source))
+                .map(|pixel| {
+                    let overlap = end.min((pixel + 1) * cells) - start.max(pixel * cells);
+                    (pixel, overlap as u64)
+                })
+                .filter(|&(_, overlap)| overlap > 0)
+                .collect()
+        })
+        .collect()
+}
+
+/// A hashed texture with where it came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextureFingerprint {
+    /// Source locator (bundle entry, object key, output path, ...)
+    pub locator: String,
+    pub width: u32,
+    pub height: u32,
+    pub hash: PHash,
+}
+
+impl TextureFingerprint {
+    /// Hash `image`.
+    pub fn new(locator: impl Into<String>, image: &RgbaImage) -> Self {
+        Self {
+            locator: locator.into(),
+            width: image.width(),
+            height: image.height(),
+            hash: perceptual_hash(image),
+        }
+    }
+
+    /// `width * height`
+    pub fn pixels(&self) -> u64 {
+        self.width as u64 * self.height as u64
+    }
+}
+
+/// Textures that are near-identical to each other
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimilarGroup {
+    /// At least two members, largest first (ties by locator)
+    pub members: Vec<TextureFingerprint>,
+}
+
+impl SimilarGroup {
+    /// The member with the most pixels
+    pub fn largest(&self) -> &TextureFingerprint {
+        &self.members[0]
+    }
+}
+
+/// Group textures whose hashes are within `max_distance` bits
+///
+/// Grouping is transitive (single linkage): `a` and `c` share a group when both are close to
+/// `b`, even if they are further apart from each other. Textures without a close match are not
+/// reported. Groups are ordered by their largest member's pixel count, largest first.
+pub fn find_similar_textures<I>(textures: I, max_distance: u32) -> Vec<SimilarGroup>
+where
+    I: IntoIterator<Item = TextureFingerprint>,
+{
+    let textures: Vec<TextureFingerprint> = textures.into_iter().collect();
+    let mut parent: Vec<usize> = (0..textures.len()).collect();
+
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for i in 0..textures.len() {
+        for j in i + 1..textures.len() {
+            if textures[i].hash.distance(&textures[j].hash) <= max_distance {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                if a != b {
+                    parent[b] = a;
+                }
+            }
+        }
+    }
+
+    let mut groups: std::collections::BTreeMap<usize, Vec<TextureFingerprint>> =
+        std::collections::BTreeMap::new();
+    for (i, texture) in textures.into_iter().enumerate() {
+        let group = root(&mut parent, i);
+        groups.entry(group).or_default().push(texture);
+    }
+
+    let by_size = |a: &TextureFingerprint, b: &TextureFingerprint| {
+        b.pixels()
+            .cmp(&a.pixels())
+            .then_with(|| a.locator.cmp(&b.locator))
+    };
+    let mut out: Vec<SimilarGroup> = groups
+        .into_values()
+        .filter(|members| members.len() > 1)
+        .map(|mut members| {
+            members.sort_by(by_size);
+            SimilarGroup { members }
+        })
+        .collect();
+    out.sort_by(|a, b| by_size(a.largest(), b.largest()));
+    out
+}
@@ -2,8 +2,13 @@
 //!
 //! This module defines the core data structures used for texture processing.
 
+use super::decoders::TextureDecoder;
 use super::formats::TextureFormat;
+use crate::error::BinaryError;
+use image::RgbaImage;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use unity_asset_binary::bytes_preview::BytesPreview;
 
 /// Streaming info for external texture data
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -28,7 +33,7 @@ pub struct GLTextureSettings {
 ///
 /// This structure contains all the data needed to represent a Unity Texture2D object.
 /// It includes both metadata and the actual image data.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Texture2D {
     pub name: String,
     pub width: i32,
@@ -136,3 +141,285 @@ impl Texture2D {
         Ok(())
     }
 }
+
+/// One-line summary, e.g. `Texture2D "icon" 256x256 format=RGBA32 mips=9 data=349524`.
+///
+/// Streamed textures append `streamed=<size>`.
+impl fmt::Display for Texture2D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Texture2D {:?} {}x{} format={:?} mips={} data={}",
+            self.name,
+            self.width,
+            self.height,
+            self.format,
+            self.mip_count,
+            self.image_data.len()
+        )?;
+        if self.is_streamed() {
+            write!(f, " streamed={}", self.stream_info.size)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Texture2D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Texture2D")
+            .field("name", &self.name)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("complete_image_size", &self.complete_image_size)
+            .field("format", &self.format)
+            .field("mip_map", &self.mip_map)
+            .field("mip_count", &self.mip_count)
+            .field("is_readable", &self.is_readable)
+            .field("image_count", &self.image_count)
+            .field("texture_dimension", &self.texture_dimension)
+            .field("light_map_format", &self.light_map_format)
+            .field("color_space", &self.color_space)
+            .field("data_size", &self.data_size)
+            .field("stream_info", &self.stream_info)
+            .field("texture_settings", &self.texture_settings)
+            .field("image_data", &BytesPreview(&self.image_data))
+            .field("forced_fallback_format", &self.forced_fallback_format)
+            .field("downscale_fallback", &self.downscale_fallback)
+            .field("is_alpha_channel_optional", &self.is_alpha_channel_optional)
+            .field("mips_stripped", &self.mips_stripped)
+            .finish()
+    }
+}
+
+/// Texture3D object representation (class 117)
+///
+/// Volume textures such as color grading LUTs and noise volumes. The top mip level stores
+/// `depth` slices of `width` x `height` back to back, so each slice can be decoded like a
+/// Texture2D of the same format.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Texture3D {
+    pub name: String,
+    pub width: i32,
+    pub height: i32,
+    pub depth: i32,
+    pub format: TextureFormat,
+    /// Raw `m_Format` when it is a `GraphicsFormat` (2019.1+).
+    pub graphics_format: Option<i32>,
+    pub mip_count: i32,
+    pub is_readable: bool,
+    pub color_space: i32,
+    pub data_size: i32,
+    pub stream_info: StreamingInfo,
+    pub texture_settings: GLTextureSettings,
+    pub image_data: Vec<u8>,
+}
+
+impl Default for Texture3D {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            width: 0,
+            height: 0,
+            depth: 0,
+            format: TextureFormat::Unknown,
+            graphics_format: None,
+            mip_count: 1,
+            is_readable: false,
+            color_space: 0,
+            data_size: 0,
+            stream_info: StreamingInfo::default(),
+            texture_settings: GLTextureSettings::default(),
+            image_data: Vec::new(),
+        }
+    }
+}
+
+impl Texture3D {
+    /// Check if texture has valid dimensions
+    pub fn has_valid_dimensions(&self) -> bool {
+        self.width > 0 && self.height > 0 && self.depth > 0
+    }
+
+    /// Check if texture uses external streaming
+    pub fn is_streamed(&self) -> bool {
+        !self.stream_info.path.is_empty() && self.stream_info.size > 0
+    }
+
+    /// Size in bytes of one top-level slice
+    pub fn slice_size(&self) -> usize {
+        self.format
+            .calculate_data_size(self.width as u32, self.height as u32) as usize
+    }
+
+    /// One slice as a Texture2D sharing this texture's format
+    ///
+    /// Slice offsets are computed from the format's bits per pixel, so only basic
+    /// (uncompressed) formats are supported; block-compressed formats return an error.
+    pub fn slice(&self, z: usize) -> crate::error::Result<Texture2D> {
+        if !self.has_valid_dimensions() {
+            return Err(BinaryError::invalid_data("Invalid Texture3D dimensions"));
+        }
+        if !self.format.is_basic_format() {
+            return Err(BinaryError::unsupported(format!(
+                "Texture3D slices can only be decoded for uncompressed formats, not {:?}",
+                self.format
+            )));
+        }
+        if z >= self.depth as usize {
+            return Err(BinaryError::invalid_data(format!(
+                "Texture3D slice {} out of range (depth {})",
+                z, self.depth
+            )));
+        }
+
+        let size = self.slice_size();
+        let start = z * size;
+        let data = self.image_data.get(start..start + size).ok_or_else(|| {
+            BinaryError::invalid_data(format!(
+                "Texture3D data too short for slice {}: need {} bytes, have {}",
+                z,
+                start + size,
+                self.image_data.len()
+            ))
+        })?;
+
+        Ok(Texture2D {
+            name: format!("{}_{}", self.name, z),
+            width: self.width,
+            height: self.height,
+            format: self.format,
+            color_space: self.color_space,
+            data_size: size as i32,
+            texture_settings: self.texture_settings.clone(),
+            image_data: data.to_vec(),
+            ..Default::default()
+        })
+    }
+
+    /// Decode slice `z` to an RGBA image
+    pub fn decode_slice(&self, z: usize) -> crate::error::Result<RgbaImage> {
+        TextureDecoder::new().decode(&self.slice(z)?)
+    }
+
+    /// Decode every slice, front to back
+    pub fn decode_all_slices(&self) -> crate::error::Result<Vec<RgbaImage>> {
+        let decoder = TextureDecoder::new();
+        (0..self.depth.max(0) as usize)
+            .map(|z| decoder.decode(&self.slice(z)?))
+            .collect()
+    }
+}
+
+/// One-line summary, e.g. `Texture3D "lut" 32x32x32 format=RGBA32 data=131072`.
+impl fmt::Display for Texture3D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Texture3D {:?} {}x{}x{} format={:?} data={}",
+            self.name,
+            self.width,
+            self.height,
+            self.depth,
+            self.format,
+            self.image_data.len()
+        )?;
+        if self.is_streamed() {
+            write!(f, " streamed={}", self.stream_info.size)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Texture3D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Texture3D")
+            .field("name", &self.name)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("depth", &self.depth)
+            .field("format", &self.format)
+            .field("graphics_format", &self.graphics_format)
+            .field("mip_count", &self.mip_count)
+            .field("is_readable", &self.is_readable)
+            .field("color_space", &self.color_space)
+            .field("data_size", &self.data_size)
+            .field("stream_info", &self.stream_info)
+            .field("texture_settings", &self.texture_settings)
+            .field("image_data", &BytesPreview(&self.image_data))
+            .finish()
+    }
+}
+
+/// Pixel layout requested from [`TextureDecoder::decode_raw`](super::TextureDecoder::decode_raw)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PixelLayout {
+    /// 8-bit R, G, B, A
+    Rgba8,
+    /// 8-bit B, G, R, A (matches `Bgra8Unorm` swapchain/texture formats)
+    Bgra8,
+    /// 32-bit float R, G, B, A in native byte order, normalized to `0.0..=1.0`
+    Rgba32F,
+}
+
+impl PixelLayout {
+    /// Bytes per pixel for this layout
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelLayout::Rgba8 | PixelLayout::Bgra8 => 4,
+            PixelLayout::Rgba32F => 16,
+        }
+    }
+}
+
+/// Decoded pixel buffer ready for upload (no `image` crate involved)
+#[derive(Clone, PartialEq)]
+pub struct DecodedPixels {
+    pub data: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    /// Bytes per row (always tightly packed: `width * layout.bytes_per_pixel()`)
+    pub stride: usize,
+    pub layout: PixelLayout,
+}
+
+impl DecodedPixels {
+    /// Wrap a tightly packed RGBA8 buffer, converting it to `target` in place where possible.
+    pub(crate) fn from_rgba8(data: Vec<u8>, width: u32, height: u32, target: PixelLayout) -> Self {
+        let data = match target {
+            PixelLayout::Rgba8 => data,
+            PixelLayout::Bgra8 => {
+                let mut data = data;
+                for px in data.chunks_exact_mut(4) {
+                    px.swap(0, 2);
+                }
+                data
+            }
+            PixelLayout::Rgba32F => {
+                let mut out = Vec::with_capacity(data.len() * 4);
+                for &c in &data {
+                    out.extend_from_slice(&(c as f32 / 255.0).to_ne_bytes());
+                }
+                out
+            }
+        };
+        Self {
+            data,
+            width,
+            height,
+            stride: width as usize * target.bytes_per_pixel(),
+            layout: target,
+        }
+    }
+}
+
+impl fmt::Debug for DecodedPixels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodedPixels")
+            .field("data", &BytesPreview(&self.data))
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("stride", &self.stride)
+            .field("layout", &self.layout)
+            .finish()
+    }
+}
@@ -0,0 +1,82 @@
+//! Glyph manifest and atlas export
+//!
+//! Writes a font asset as `<name>.json` (face info, glyph table, character table and kerning)
+//! next to `<name>.png`, the decoded SDF atlas, which is enough to re-render text outside Unity.
+
+use super::types::TmpFontAsset;
+use crate::asset::SerializedFile;
+use crate::error::{BinaryError, Result};
+use crate::texture::{Texture2DConverter, TextureDecoder, TextureExporter};
+use crate::unity_version::UnityVersion;
+use image::RgbaImage;
+use std::path::{Path, PathBuf};
+use unity_asset_core::class_ids;
+
+/// TMP font asset exporter
+pub struct TmpFontExporter;
+
+impl TmpFontExporter {
+    /// Decode the atlas texture `index` of `font`, which must live in `asset`.
+    pub fn load_atlas(
+        font: &TmpFontAsset,
+        asset: &SerializedFile,
+        index: usize,
+    ) -> Result<RgbaImage> {
+        let texture = font.atlas_textures.get(index).ok_or_else(|| {
+            BinaryError::invalid_data(format!("Font asset has no atlas texture {index}"))
+        })?;
+        if texture.file_id != 0 {
+            return Err(BinaryError::invalid_data(format!(
+                "Atlas texture is in external file {}",
+                texture.file_id
+            )));
+        }
+        let object = asset
+            .find_object_handle(texture.path_id)
+            .filter(|h| h.class_id() == class_ids::TEXTURE_2D)
+            .ok_or_else(|| {
+                BinaryError::invalid_data(format!("Atlas texture {} not found", texture.path_id))
+            })?
+            .read()?;
+        let version = UnityVersion::parse_version(&asset.unity_version).unwrap_or_default();
+        let texture = Texture2DConverter::new(version).from_unity_object(&object)?;
+        TextureDecoder::new().decode(&texture)
+    }
+
+    /// Write the JSON glyph manifest to `path`
+    pub fn export_manifest<P: AsRef<Path>>(font: &TmpFontAsset, path: P) -> Result<()> {
+        let json = serde_json::to_string_pretty(&font.to_manifest_json())
+            .map_err(|e| BinaryError::generic(format!("Failed to serialize manifest: {e}")))?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Write `<name>.json` and one PNG per atlas texture into `dir`, returning the written paths.
+    ///
+    /// The first atlas is `<name>.png`, further ones `<name>_<index>.png`.
+    pub fn export<P: AsRef<Path>>(
+        font: &TmpFontAsset,
+        asset: &SerializedFile,
+        dir: P,
+    ) -> Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        let stem = if font.name.is_empty() {
+            "TMP_FontAsset"
+        } else {
+            font.name.as_str()
+        };
+        let manifest = dir.join(format!("{stem}.json"));
+        Self::export_manifest(font, &manifest)?;
+        let mut written = vec![manifest];
+        for index in 0..font.atlas_textures.len() {
+            let atlas = Self::load_atlas(font, asset, index)?;
+            let path = match index {
+                0 => dir.join(format!("{stem}.png")),
+                _ => dir.join(format!("{stem}_{index}.png")),
+            };
+            TextureExporter::export_png(&atlas, &path)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
@@ -0,0 +1,37 @@
+//! TextMeshPro font asset module
+//!
+//! TMP_FontAsset objects are MonoBehaviours whose layout has been stable for years, so they are
+//! parsed directly from the TypeTree: face info, the glyph and character tables, kerning pairs
+//! and the SDF atlas texture references. Both the legacy (`m_glyphInfoList`) and current
+//! (`m_GlyphTable` / `m_CharacterTable`) layouts are supported.
+//!
+//! # Architecture
+//!
+//! - `types` - Font asset, glyph, character and kerning types
+//! - `parser` - Parsing from TypeTree data and script-name recognition
+//! - `export` - JSON glyph manifest and atlas PNG export
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use unity_asset_decode::tmp::{TmpFontExporter, tmp_font_assets};
+//!
+//! # fn example(asset: &unity_asset_decode::asset::SerializedFile) -> unity_asset_decode::Result<()> {
+//! for (_, font) in tmp_font_assets(asset) {
+//!     println!("{}: {} glyphs", font.name, font.glyphs.len());
+//!     TmpFontExporter::export(&font, asset, "fonts")?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod export;
+pub mod parser;
+pub mod types;
+
+pub use export::TmpFontExporter;
+pub use parser::{TMP_FONT_ASSET_SCRIPT, font_layout, script_class_name, tmp_font_assets};
+pub use types::{
+    GlyphAdjustment, GlyphMetrics, GlyphRect, TmpCharacter, TmpFaceInfo, TmpFontAsset,
+    TmpFontLayout, TmpGlyph, TmpKerningPair,
+};
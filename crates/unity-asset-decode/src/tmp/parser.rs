@@ -0,0 +1,334 @@
+//! TMP_FontAsset parsing from TypeTree data
+
+use super::types::{
+    GlyphAdjustment, GlyphMetrics, GlyphRect, TmpCharacter, TmpFaceInfo, TmpFontAsset,
+    TmpFontLayout, TmpGlyph, TmpKerningPair,
+};
+use crate::asset::SerializedFile;
+use crate::error::{BinaryError, Result};
+use crate::object::UnityObject;
+use crate::unity_objects::ObjectRef;
+use indexmap::IndexMap;
+use unity_asset_core::{UnityValue, class_ids};
+
+type Fields = IndexMap<String, UnityValue>;
+
+/// Script class name of TextMeshPro font assets.
+pub const TMP_FONT_ASSET_SCRIPT: &str = "TMP_FontAsset";
+
+impl TmpFontAsset {
+    /// Parse a TMP_FontAsset from a MonoBehaviour object
+    pub fn from_unity_object(obj: &UnityObject) -> Result<Self> {
+        if obj.class_id() != class_ids::MONO_BEHAVIOUR {
+            return Err(BinaryError::invalid_data(format!(
+                "Object is not a MonoBehaviour (class_id: {})",
+                obj.class_id()
+            )));
+        }
+        Self::from_typetree(obj.class.properties())
+    }
+
+    /// Parse a TMP_FontAsset from TypeTree data, in either layout
+    pub fn from_typetree(properties: &Fields) -> Result<Self> {
+        match font_layout(properties) {
+            Some(TmpFontLayout::Current) => Ok(current(properties)),
+            Some(TmpFontLayout::Legacy) => Ok(legacy(properties)),
+            None => Err(BinaryError::invalid_data(
+                "MonoBehaviour has no TMP glyph table",
+            )),
+        }
+    }
+}
+
+/// Which TMP_FontAsset layout `properties` has, if it looks like a font asset at all.
+///
+/// Current assets keep the legacy fields around, so an asset counts as legacy when only the
+/// legacy glyph list is populated (a font that was never upgraded in the editor).
+pub fn font_layout(properties: &Fields) -> Option<TmpFontLayout> {
+    let current = properties.contains_key("m_GlyphTable") && properties.contains_key("m_FaceInfo");
+    let legacy = properties.contains_key("m_glyphInfoList")
+        && properties
+            .get("m_fontInfo")
+            .and_then(UnityValue::as_object)
+            .is_some();
+    let populated = |key: &str| objects(properties.get(key)).next().is_some();
+    let never_upgraded = populated("m_glyphInfoList") && !populated("m_GlyphTable");
+    if current && !(legacy && never_upgraded) {
+        Some(TmpFontLayout::Current)
+    } else if legacy {
+        Some(TmpFontLayout::Legacy)
+    } else {
+        None
+    }
+}
+
+/// Class name of a MonoBehaviour's script, when the MonoScript lives in `asset`.
+pub fn script_class_name(asset: &SerializedFile, properties: &Fields) -> Option<String> {
+    let script = properties.get("m_Script").and_then(pptr)?;
+    if script.file_id != 0 {
+        return None;
+    }
+    let object = asset
+        .find_object_handle(script.path_id)
+        .filter(|h| h.class_id() == class_ids::MONO_SCRIPT)?
+        .read()
+        .ok()?;
+    object
+        .class
+        .properties()
+        .get("m_ClassName")
+        .and_then(UnityValue::as_str)
+        .map(str::to_string)
+}
+
+/// Every TMP_FontAsset in `asset`, with its path id.
+///
+/// MonoBehaviours are recognized by script name. When the script lives in another file (the
+/// usual case in bundles) the object is accepted if its fields have a font asset layout.
+pub fn tmp_font_assets(asset: &SerializedFile) -> Vec<(i64, TmpFontAsset)> {
+    asset
+        .object_handles()
+        .filter(|h| h.class_id() == class_ids::MONO_BEHAVIOUR)
+        .filter_map(|handle| {
+            let object = handle.read().ok()?;
+            let properties = object.class.properties();
+            if script_class_name(asset, properties)
+                .is_some_and(|name| name != TMP_FONT_ASSET_SCRIPT)
+            {
+                return None;
+            }
+            let font = TmpFontAsset::from_typetree(properties).ok()?;
+            Some((handle.path_id(), font))
+        })
+        .collect()
+}
+
+fn current(properties: &Fields) -> TmpFontAsset {
+    let face = object(properties, "m_FaceInfo");
+    let glyphs: Vec<TmpGlyph> = objects(properties.get("m_GlyphTable"))
+        .map(|glyph| {
+            let metrics = object(glyph, "m_Metrics");
+            let rect = object(glyph, "m_GlyphRect");
+            TmpGlyph {
+                index: uint(glyph, "m_Index"),
+                rect: GlyphRect {
+                    x: uint(rect, "m_X"),
+                    y: uint(rect, "m_Y"),
+                    width: uint(rect, "m_Width"),
+                    height: uint(rect, "m_Height"),
+                },
+                metrics: GlyphMetrics {
+                    width: float(metrics, "m_Width"),
+                    height: float(metrics, "m_Height"),
+                    bearing_x: float(metrics, "m_HorizontalBearingX"),
+                    bearing_y: float(metrics, "m_HorizontalBearingY"),
+                    advance: float(metrics, "m_HorizontalAdvance"),
+                },
+                scale: float_or(glyph, "m_Scale", 1.0),
+                atlas_index: uint(glyph, "m_AtlasIndex"),
+            }
+        })
+        .collect();
+    let characters = objects(properties.get("m_CharacterTable"))
+        .map(|character| TmpCharacter {
+            unicode: uint(character, "m_Unicode"),
+            glyph_index: uint(character, "m_GlyphIndex"),
+            scale: float_or(character, "m_Scale", 1.0),
+        })
+        .collect();
+    let kerning =
+        objects(object(properties, "m_FontFeatureTable").get("m_GlyphPairAdjustmentRecords"))
+            .map(|record| {
+                let first = object(record, "m_FirstAdjustmentRecord");
+                let second = object(record, "m_SecondAdjustmentRecord");
+                TmpKerningPair {
+                    first_glyph: uint(first, "m_GlyphIndex"),
+                    second_glyph: uint(second, "m_GlyphIndex"),
+                    first_adjustment: value_record(object(first, "m_GlyphValueRecord")),
+                    second_adjustment: value_record(object(second, "m_GlyphValueRecord")),
+                }
+            })
+            .collect();
+
+    TmpFontAsset {
+        name: string(properties, "m_Name"),
+        layout: TmpFontLayout::Current,
+        face: TmpFaceInfo {
+            family_name: string(face, "m_FamilyName"),
+            style_name: string(face, "m_StyleName"),
+            point_size: float(face, "m_PointSize"),
+            scale: float_or(face, "m_Scale", 1.0),
+            line_height: float(face, "m_LineHeight"),
+            ascent_line: float(face, "m_AscentLine"),
+            cap_line: float(face, "m_CapLine"),
+            baseline: float(face, "m_Baseline"),
+            descent_line: float(face, "m_DescentLine"),
+            underline_offset: float(face, "m_UnderlineOffset"),
+            tab_width: float(face, "m_TabWidth"),
+            padding: float(properties, "m_AtlasPadding"),
+            atlas_width: uint(properties, "m_AtlasWidth"),
+            atlas_height: uint(properties, "m_AtlasHeight"),
+        },
+        glyphs,
+        characters,
+        kerning,
+        atlas_textures: match properties.get("m_AtlasTextures") {
+            Some(UnityValue::Array(items)) => items.iter().filter_map(pptr).collect(),
+            _ => Vec::new(),
+        },
+    }
+}
+
+fn legacy(properties: &Fields) -> TmpFontAsset {
+    let info = object(properties, "m_fontInfo");
+    let atlas_height = float(info, "AtlasHeight");
+    let glyphs: Vec<TmpGlyph> = objects(properties.get("m_glyphInfoList"))
+        .map(|glyph| {
+            let (x, y) = (float(glyph, "x"), float(glyph, "y"));
+            let (width, height) = (float(glyph, "width"), float(glyph, "height"));
+            TmpGlyph {
+                index: uint(glyph, "id"),
+                rect: GlyphRect {
+                    x: x.round() as u32,
+                    // Legacy rects are measured from the top of the atlas.
+                    y: (atlas_height - y - height).round().max(0.0) as u32,
+                    width: width.round() as u32,
+                    height: height.round() as u32,
+                },
+                metrics: GlyphMetrics {
+                    width,
+                    height,
+                    bearing_x: float(glyph, "xOffset"),
+                    bearing_y: float(glyph, "yOffset"),
+                    advance: float(glyph, "xAdvance"),
+                },
+                scale: float_or(glyph, "scale", 1.0),
+                atlas_index: 0,
+            }
+        })
+        .collect();
+    // Legacy glyphs are keyed by character code.
+    let characters = glyphs
+        .iter()
+        .map(|glyph| TmpCharacter {
+            unicode: glyph.index,
+            glyph_index: glyph.index,
+            scale: 1.0,
+        })
+        .collect();
+    let kerning = objects(object(properties, "m_kerningInfo").get("kerningPairs"))
+        .map(|pair| {
+            if pair.contains_key("m_FirstGlyph") {
+                TmpKerningPair {
+                    first_glyph: uint(pair, "m_FirstGlyph"),
+                    second_glyph: uint(pair, "m_SecondGlyph"),
+                    first_adjustment: legacy_adjustment(object(pair, "m_FirstGlyphAdjustments")),
+                    second_adjustment: legacy_adjustment(object(pair, "m_SecondGlyphAdjustments")),
+                }
+            } else {
+                // Before TMP 1.3 a pair only carried an advance offset for the first glyph.
+                TmpKerningPair {
+                    first_glyph: uint(pair, "AscII_Left"),
+                    second_glyph: uint(pair, "AscII_Right"),
+                    first_adjustment: GlyphAdjustment {
+                        x_advance: float(pair, "XadvanceOffset"),
+                        ..Default::default()
+                    },
+                    second_adjustment: GlyphAdjustment::default(),
+                }
+            }
+        })
+        .collect();
+
+    TmpFontAsset {
+        name: string(properties, "m_Name"),
+        layout: TmpFontLayout::Legacy,
+        face: TmpFaceInfo {
+            family_name: string(info, "Name"),
+            style_name: String::new(),
+            point_size: float(info, "PointSize"),
+            scale: float_or(info, "Scale", 1.0),
+            line_height: float(info, "LineHeight"),
+            ascent_line: float(info, "Ascender"),
+            cap_line: float(info, "CapHeight"),
+            baseline: float(info, "Baseline"),
+            descent_line: float(info, "Descender"),
+            underline_offset: float(info, "Underline"),
+            tab_width: float(info, "TabWidth"),
+            padding: float(info, "Padding"),
+            atlas_width: float(info, "AtlasWidth") as u32,
+            atlas_height: atlas_height as u32,
+        },
+        glyphs,
+        characters,
+        kerning,
+        atlas_textures: properties.get("atlas").and_then(pptr).into_iter().collect(),
+    }
+}
+
+fn value_record(record: &Fields) -> GlyphAdjustment {
+    GlyphAdjustment {
+        x_placement: float(record, "m_XPlacement"),
+        y_placement: float(record, "m_YPlacement"),
+        x_advance: float(record, "m_XAdvance"),
+        y_advance: float(record, "m_YAdvance"),
+    }
+}
+
+fn legacy_adjustment(record: &Fields) -> GlyphAdjustment {
+    GlyphAdjustment {
+        x_placement: float(record, "xPlacement"),
+        y_placement: float(record, "yPlacement"),
+        x_advance: float(record, "xAdvance"),
+        y_advance: float(record, "yAdvance"),
+    }
+}
+
+fn pptr(value: &UnityValue) -> Option<ObjectRef> {
+    let fields = value.as_object()?;
+    let file_id = fields.get("m_FileID")?.as_i64()?;
+    let path_id = fields.get("m_PathID")?.as_i64()?;
+    let pptr = ObjectRef::new(file_id as i32, path_id);
+    (!pptr.is_null()).then_some(pptr)
+}
+
+static EMPTY: std::sync::LazyLock<Fields> = std::sync::LazyLock::new(Fields::new);
+
+/// A nested struct, or an empty one when missing so lookups fall back to defaults.
+fn object<'a>(fields: &'a Fields, key: &str) -> &'a Fields {
+    fields
+        .get(key)
+        .and_then(UnityValue::as_object)
+        .unwrap_or(&EMPTY)
+}
+
+fn objects(value: Option<&UnityValue>) -> impl Iterator<Item = &Fields> {
+    let items: &[UnityValue] = match value {
+        Some(UnityValue::Array(items)) => items,
+        _ => &[],
+    };
+    items.iter().filter_map(UnityValue::as_object)
+}
+
+fn string(fields: &Fields, key: &str) -> String {
+    fields
+        .get(key)
+        .and_then(UnityValue::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn float(fields: &Fields, key: &str) -> f32 {
+    float_or(fields, key, 0.0)
+}
+
+fn float_or(fields: &Fields, key: &str, default: f32) -> f32 {
+    fields
+        .get(key)
+        .and_then(UnityValue::as_f64)
+        .map_or(default, |v| v as f32)
+}
+
+fn uint(fields: &Fields, key: &str) -> u32 {
+    fields.get(key).and_then(UnityValue::as_i64).unwrap_or(0) as u32
+}
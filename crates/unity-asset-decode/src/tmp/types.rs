@@ -0,0 +1,129 @@
+//! TextMeshPro font asset data structures
+//!
+//! Both serialized layouts are normalized into the same types: glyph rects use the current
+//! layout's convention (pixels, origin at the bottom-left of the atlas), and legacy glyphs are
+//! indexed by their character code.
+
+use crate::unity_objects::ObjectRef;
+use serde::{Deserialize, Serialize};
+
+/// Which TMP_FontAsset serialization the asset was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TmpFontLayout {
+    /// Before TMP 1.4: `m_fontInfo`, `m_glyphInfoList`, `m_kerningInfo` and a single `atlas`.
+    Legacy,
+    /// TMP 1.4+: `m_FaceInfo`, `m_GlyphTable`, `m_CharacterTable` and `m_FontFeatureTable`.
+    Current,
+}
+
+/// Font face metrics (`m_FaceInfo`, or `m_fontInfo` in the legacy layout).
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TmpFaceInfo {
+    pub family_name: String,
+    pub style_name: String,
+    /// Sampling point size the atlas was generated at.
+    pub point_size: f32,
+    pub scale: f32,
+    pub line_height: f32,
+    pub ascent_line: f32,
+    pub cap_line: f32,
+    pub baseline: f32,
+    pub descent_line: f32,
+    pub underline_offset: f32,
+    pub tab_width: f32,
+    /// Spread of the distance field around each glyph, in atlas pixels.
+    pub padding: f32,
+    pub atlas_width: u32,
+    pub atlas_height: u32,
+}
+
+/// A glyph's rectangle in the atlas, in pixels from the bottom-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct GlyphRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Glyph layout metrics, in points at the face's point size.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct GlyphMetrics {
+    pub width: f32,
+    pub height: f32,
+    pub bearing_x: f32,
+    pub bearing_y: f32,
+    pub advance: f32,
+}
+
+/// One `m_GlyphTable` (or `m_glyphInfoList`) entry.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TmpGlyph {
+    pub index: u32,
+    pub rect: GlyphRect,
+    pub metrics: GlyphMetrics,
+    pub scale: f32,
+    /// Index into the font's atlas textures.
+    pub atlas_index: u32,
+}
+
+/// One `m_CharacterTable` entry: a character code mapped to a glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct TmpCharacter {
+    pub unicode: u32,
+    pub glyph_index: u32,
+    pub scale: f32,
+}
+
+/// Placement and advance adjustment applied to one glyph of a pair.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct GlyphAdjustment {
+    pub x_placement: f32,
+    pub y_placement: f32,
+    pub x_advance: f32,
+    pub y_advance: f32,
+}
+
+/// A kerning / pair adjustment record between two glyphs.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct TmpKerningPair {
+    pub first_glyph: u32,
+    pub second_glyph: u32,
+    pub first_adjustment: GlyphAdjustment,
+    pub second_adjustment: GlyphAdjustment,
+}
+
+/// A parsed TMP_FontAsset MonoBehaviour
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TmpFontAsset {
+    pub name: String,
+    pub layout: TmpFontLayout,
+    pub face: TmpFaceInfo,
+    pub glyphs: Vec<TmpGlyph>,
+    pub characters: Vec<TmpCharacter>,
+    pub kerning: Vec<TmpKerningPair>,
+    /// SDF atlas textures (`m_AtlasTextures`, or the legacy `atlas`).
+    pub atlas_textures: Vec<ObjectRef>,
+}
+
+impl TmpFontAsset {
+    /// The first atlas texture, which holds every glyph unless multi-atlas is enabled.
+    pub fn atlas_texture(&self) -> Option<ObjectRef> {
+        self.atlas_textures.first().copied()
+    }
+
+    pub fn glyph(&self, index: u32) -> Option<&TmpGlyph> {
+        self.glyphs.iter().find(|g| g.index == index)
+    }
+
+    /// Glyph for a character, through the character table.
+    pub fn glyph_for_char(&self, c: char) -> Option<&TmpGlyph> {
+        let character = self.characters.iter().find(|ch| ch.unicode == c as u32)?;
+        self.glyph(character.glyph_index)
+    }
+
+    /// JSON glyph manifest: face info, atlas size, glyphs, characters and kerning pairs.
+    pub fn to_manifest_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
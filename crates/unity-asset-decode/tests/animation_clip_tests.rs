@@ -0,0 +1,318 @@
+//! AnimationClip (class 74) curves from the streamed, dense and constant muscle clips and from
+//! the legacy curve lists.
+//!
+//! The streamed data is built from Hermite segments, so the decoded tangents are known exactly.
+
+#![cfg(feature = "animation")]
+
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use unity_asset_core::UnityValue;
+use unity_asset_decode::animation::{AnimationClip, path_hash, read_streamed_frames};
+
+fn object(fields: Vec<(&str, UnityValue)>) -> UnityValue {
+    UnityValue::Object(
+        fields
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
+}
+
+fn fields(value: UnityValue) -> IndexMap<String, UnityValue> {
+    match value {
+        UnityValue::Object(fields) => fields,
+        _ => unreachable!(),
+    }
+}
+
+fn int(v: i64) -> UnityValue {
+    UnityValue::Integer(v)
+}
+
+fn float(v: f32) -> UnityValue {
+    UnityValue::Float(v as f64)
+}
+
+fn floats(values: &[f32]) -> UnityValue {
+    UnityValue::Array(values.iter().map(|&v| float(v)).collect())
+}
+
+/// Coefficients of the Hermite segment from `(v0, m0)` to `(v1, m1)` over `dx`.
+fn hermite(v0: f32, m0: f32, v1: f32, m1: f32, dx: f32) -> [f32; 4] {
+    let dy = v1 - v0;
+    [
+        (m0 * dx + m1 * dx - 2.0 * dy) / (dx * dx * dx),
+        (3.0 * dy - 2.0 * m0 * dx - m1 * dx) / (dx * dx),
+        m0,
+        v0,
+    ]
+}
+
+/// `(time, [(curve, coefficients)])`
+type Frame = (f32, Vec<(i32, [f32; 4])>);
+
+/// `m_StreamedClip.data` for `frames`.
+fn streamed_words(frames: &[Frame]) -> Vec<u32> {
+    let mut bytes = Vec::new();
+    for (time, keys) in frames {
+        bytes.extend_from_slice(&time.to_le_bytes());
+        bytes.extend_from_slice(&(keys.len() as i32).to_le_bytes());
+        for (index, coeff) in keys {
+            bytes.extend_from_slice(&index.to_le_bytes());
+            for c in coeff {
+                bytes.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+    }
+    bytes
+        .chunks_exact(4)
+        .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+        .collect()
+}
+
+/// Curve 0 eases 0 -> 1 over a second then steps; curve 1 holds 5 for two seconds.
+fn streamed_frames() -> Vec<Frame> {
+    vec![
+        (f32::NEG_INFINITY, vec![]),
+        (f32::NEG_INFINITY, vec![]),
+        (
+            0.0,
+            vec![
+                (0, hermite(0.0, 0.0, 1.0, 2.0, 1.0)),
+                (1, [0.0, 0.0, 0.0, 5.0]),
+            ],
+        ),
+        (1.0, vec![(0, [0.0, 0.0, 0.0, 1.0])]),
+        (
+            2.0,
+            vec![(0, [0.0, 0.0, 0.0, 1.0]), (1, [0.0, 0.0, 0.0, 5.0])],
+        ),
+        (f32::INFINITY, vec![]),
+    ]
+}
+
+fn binding(path: u32, attribute: u32, type_id: i64, pptr: bool) -> UnityValue {
+    object(vec![
+        ("path", int(path as i64)),
+        ("attribute", int(attribute as i64)),
+        ("typeID", int(type_id)),
+        ("customType", int(0)),
+        ("isPPtrCurve", int(pptr as i64)),
+    ])
+}
+
+fn muscle_clip_properties() -> IndexMap<String, UnityValue> {
+    let words = streamed_words(&streamed_frames());
+    let clip = object(vec![
+        (
+            "m_StreamedClip",
+            object(vec![
+                (
+                    "data",
+                    UnityValue::Array(words.iter().map(|&w| int(w as i64)).collect()),
+                ),
+                ("curveCount", int(2)),
+            ]),
+        ),
+        (
+            "m_DenseClip",
+            object(vec![
+                ("m_FrameCount", int(3)),
+                ("m_CurveCount", int(1)),
+                ("m_SampleRate", float(10.0)),
+                ("m_BeginTime", float(0.0)),
+                ("m_SampleArray", floats(&[0.0, 0.5, 2.0])),
+            ]),
+        ),
+        ("m_ConstantClip", object(vec![("data", floats(&[0.25]))])),
+    ]);
+    fields(object(vec![
+        ("m_Name", UnityValue::String("Wave".to_string())),
+        ("m_SampleRate", float(30.0)),
+        (
+            "m_MuscleClip",
+            object(vec![
+                ("m_StartTime", float(0.0)),
+                ("m_StopTime", float(2.0)),
+                ("m_Clip", object(vec![("data", clip)])),
+            ]),
+        ),
+        (
+            "m_ClipBindingConstant",
+            object(vec![(
+                "genericBindings",
+                UnityValue::Array(vec![
+                    binding(path_hash("Root/Arm"), 1, 4, false),
+                    binding(path_hash("Root/Arm/Hand"), 0xdead_beef, 23, false),
+                    binding(path_hash("Root"), 0x1234, 212, true),
+                ]),
+            )]),
+        ),
+    ]))
+}
+
+#[test]
+fn streamed_in_slopes_come_from_previous_segment() {
+    let frames = read_streamed_frames(&streamed_words(&streamed_frames())).unwrap();
+    assert_eq!(frames.len(), 6);
+    let at_one = frames[3].keys[0];
+    assert_eq!((at_one.value(), at_one.in_slope), (1.0, 2.0));
+    // A segment with no slope terms is a step.
+    assert_eq!(frames[4].keys[0].in_slope, f32::INFINITY);
+
+    let mut truncated = streamed_words(&streamed_frames());
+    truncated.truncate(12);
+    assert!(read_streamed_frames(&truncated).is_err());
+}
+
+#[test]
+fn muscle_clip_curves_follow_bindings() {
+    let mut clip = AnimationClip::from_typetree(&muscle_clip_properties()).unwrap();
+    assert_eq!(clip.name, "Wave");
+    assert_eq!((clip.length, clip.sample_rate), (2.0, 30.0));
+    assert!(!clip.legacy);
+
+    let properties: Vec<_> = clip.curves.iter().map(|c| c.property.as_str()).collect();
+    assert_eq!(
+        properties,
+        [
+            "m_LocalPosition.x",
+            "m_LocalPosition.y",
+            "m_LocalPosition.z",
+            "0xdeadbeef"
+        ]
+    );
+    assert!(clip.curves.iter().all(|c| c.path.is_none()));
+
+    // Streamed: keys at 0, 1 and 2 with the encoded tangents.
+    let x = &clip.curves[0].curve;
+    let keys: Vec<_> = x
+        .keys
+        .iter()
+        .map(|k| (k.time, k.value, k.in_slope, k.out_slope))
+        .collect();
+    assert_eq!(
+        keys,
+        [
+            (0.0, 0.0, 0.0, 0.0),
+            (1.0, 1.0, 2.0, 0.0),
+            (2.0, 1.0, f32::INFINITY, 0.0)
+        ]
+    );
+    assert!((x.evaluate(0.5) - 0.25).abs() < 1e-5);
+    assert_eq!(clip.curves[1].curve.evaluate(1.3), 5.0);
+
+    // Dense: linear between samples 0.1s apart.
+    let z = &clip.curves[2].curve;
+    assert_eq!(z.keys.len(), 3);
+    assert!((z.keys[2].time - 0.2).abs() < 1e-6);
+    assert!((z.evaluate(0.15) - 1.25).abs() < 1e-4);
+
+    // Constant: held from start to stop.
+    let constant = &clip.curves[3].curve;
+    assert_eq!(constant.time_range(), Some((0.0, 2.0)));
+    assert_eq!(constant.evaluate(1.0), 0.25);
+
+    let names = HashMap::from([
+        (path_hash("Root/Arm"), "Root/Arm".to_string()),
+        (path_hash("Root/Arm/Hand"), "Root/Arm/Hand".to_string()),
+        (0xdead_beef, "material._Glow".to_string()),
+    ]);
+    clip.resolve_paths(&names);
+    assert_eq!(clip.curves_for_path("Root/Arm").count(), 3);
+    assert_eq!(clip.curves[3].path.as_deref(), Some("Root/Arm/Hand"));
+    assert_eq!(clip.curves[3].property, "material._Glow");
+    assert_eq!(clip.curves[3].class_id, 23);
+
+    let json = clip.to_json();
+    assert_eq!(json["curves"][0]["property"], "m_LocalPosition.x");
+    assert!(json["curves"][0]["curve"]["keys"][2]["in_slope"].is_null());
+}
+
+#[test]
+fn legacy_curves_are_split_per_component() {
+    let vector_key = |time: f32, x: f32, y: f32, z: f32| {
+        let v = |x: f32, y: f32, z: f32| {
+            object(vec![("x", float(x)), ("y", float(y)), ("z", float(z))])
+        };
+        object(vec![
+            ("time", float(time)),
+            ("value", v(x, y, z)),
+            ("inSlope", v(0.0, 0.0, 0.0)),
+            ("outSlope", v(0.0, 0.0, 0.0)),
+        ])
+    };
+    let curve = |keys: Vec<UnityValue>| {
+        object(vec![
+            ("m_Curve", UnityValue::Array(keys)),
+            ("m_PreInfinity", int(2)),
+            ("m_PostInfinity", int(1)),
+        ])
+    };
+    let properties = fields(object(vec![
+        ("m_Name", UnityValue::String("Door".to_string())),
+        ("m_SampleRate", float(60.0)),
+        (
+            "m_PositionCurves",
+            UnityValue::Array(vec![object(vec![
+                (
+                    "m_Curve",
+                    curve(vec![
+                        vector_key(0.0, 0.0, 1.0, 0.0),
+                        vector_key(1.5, 2.0, 1.0, 0.0),
+                    ]),
+                ),
+                ("m_Path", UnityValue::String("Frame/Door".to_string())),
+            ])]),
+        ),
+        (
+            "m_FloatCurves",
+            UnityValue::Array(vec![object(vec![
+                (
+                    "curve",
+                    curve(vec![object(vec![
+                        ("time", float(0.5)),
+                        ("value", float(0.8)),
+                        ("inSlope", float(0.0)),
+                        ("outSlope", float(0.0)),
+                    ])]),
+                ),
+                ("attribute", UnityValue::String("m_Intensity".to_string())),
+                ("path", UnityValue::String("Frame/Lamp".to_string())),
+                ("classID", int(108)),
+            ])]),
+        ),
+        (
+            "m_MuscleClip",
+            object(vec![
+                ("m_StartTime", float(0.0)),
+                ("m_StopTime", float(0.0)),
+                ("m_Clip", object(vec![("data", object(vec![]))])),
+            ]),
+        ),
+    ]));
+
+    let clip = AnimationClip::from_typetree(&properties).unwrap();
+    assert!(clip.legacy);
+    assert_eq!(clip.length, 1.5);
+    assert_eq!(clip.curves.len(), 4);
+
+    let door: Vec<_> = clip.curves_for_path("Frame/Door").collect();
+    assert_eq!(door.len(), 3);
+    assert_eq!(door[0].property, "m_LocalPosition.x");
+    assert_eq!(door[0].path_hash, path_hash("Frame/Door"));
+    assert_eq!(door[0].curve.keys[1].value, 2.0);
+    assert_eq!(door[1].curve.keys[0].value, 1.0);
+    assert_eq!(
+        door[0].curve.post_wrap,
+        unity_asset_decode::animation::WrapMode::Loop
+    );
+
+    let lamp = &clip.curves[3];
+    assert_eq!(
+        (lamp.path.as_deref(), lamp.property.as_str(), lamp.class_id),
+        (Some("Frame/Lamp"), "m_Intensity", 108)
+    );
+    assert_eq!(lamp.curve.evaluate(2.0), 0.8);
+}
@@ -0,0 +1,170 @@
+//! AnimationCurve evaluation against reference values.
+//!
+//! EaseInOut/Linear values are those `AnimationCurve.Evaluate` returns for the built-in presets;
+//! the weighted cases come from solving the Bezier form of each segment by bisection in f64.
+
+#![cfg(feature = "animation")]
+
+use indexmap::IndexMap;
+use unity_asset_core::UnityValue;
+use unity_asset_decode::animation::{AnimationCurve, Keyframe, WeightedMode, WrapMode};
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < 1e-5,
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn hermite_presets_match_unity() {
+    let ease = AnimationCurve::ease_in_out(0.0, 0.0, 1.0, 1.0);
+    assert_close(ease.evaluate(0.25), 0.15625);
+    assert_close(ease.evaluate(0.5), 0.5);
+    assert_close(ease.evaluate(0.75), 0.84375);
+
+    let ease = AnimationCurve::ease_in_out(0.0, 0.0, 2.0, 2.0);
+    assert_close(ease.evaluate(0.5), 0.3125);
+
+    let linear = AnimationCurve::linear(0.0, 0.0, 2.0, 4.0);
+    assert_close(linear.evaluate(0.5), 1.0);
+    assert_close(linear.evaluate(1.5), 3.0);
+
+    // Keys are sorted on construction; exact key times return key values.
+    let curve = AnimationCurve::new(vec![
+        Keyframe::new(1.0, 5.0, 0.0),
+        Keyframe::new(0.0, 2.0, 0.0),
+        Keyframe::new(2.0, -1.0, 0.0),
+    ]);
+    assert_eq!(curve.time_range(), Some((0.0, 2.0)));
+    assert_close(curve.evaluate(1.0), 5.0);
+    assert_close(curve.evaluate(2.0), -1.0);
+    assert_close(curve.evaluate(1.5), 2.0);
+
+    assert_eq!(AnimationCurve::default().evaluate(3.0), 0.0);
+    assert_eq!(
+        AnimationCurve::new(vec![Keyframe::new(1.0, 7.0, 0.0)]).evaluate(-4.0),
+        7.0
+    );
+}
+
+#[test]
+fn infinite_tangents_step() {
+    let curve = AnimationCurve::new(vec![
+        Keyframe::with_slopes(0.0, 1.0, 0.0, f32::INFINITY),
+        Keyframe::with_slopes(1.0, 3.0, 0.0, 0.0),
+        Keyframe::with_slopes(2.0, 0.0, f32::INFINITY, 0.0),
+    ]);
+    assert_eq!(curve.evaluate(0.0), 1.0);
+    assert_eq!(curve.evaluate(0.99), 1.0);
+    assert_eq!(curve.evaluate(1.0), 3.0);
+    // An infinite in-tangent on the right key also holds the left value.
+    assert_eq!(curve.evaluate(1.5), 3.0);
+    assert_eq!(curve.evaluate(2.0), 0.0);
+}
+
+#[test]
+fn weighted_tangents() {
+    // Default weights reproduce the Hermite segment.
+    let weighted = AnimationCurve::new(vec![
+        Keyframe::new(0.0, 0.0, 0.0).with_weights(WeightedMode::Both, 1.0 / 3.0, 1.0 / 3.0),
+        Keyframe::new(2.0, 2.0, 0.0).with_weights(WeightedMode::Both, 1.0 / 3.0, 1.0 / 3.0),
+    ]);
+    assert_close(weighted.evaluate(0.5), 0.3125);
+
+    // Full weights on flat tangents flatten both ends.
+    let heavy = AnimationCurve::new(vec![
+        Keyframe::new(0.0, 0.0, 0.0).with_weights(WeightedMode::Out, 0.0, 1.0),
+        Keyframe::new(1.0, 1.0, 0.0).with_weights(WeightedMode::In, 1.0, 0.0),
+    ]);
+    assert_close(heavy.evaluate(0.1), 0.003_761_675);
+    assert_close(heavy.evaluate(0.25), 0.029_724_606);
+    assert_close(heavy.evaluate(0.5), 0.5);
+
+    // Only the left key's out tangent is weighted; the right end keeps 1/3.
+    let mixed = AnimationCurve::new(vec![
+        Keyframe::with_slopes(0.0, 0.0, 0.0, 2.0).with_weights(WeightedMode::Out, 0.0, 0.8),
+        Keyframe::new(1.0, 1.0, 0.0).with_weights(WeightedMode::None, 0.1, 0.0),
+    ]);
+    assert_close(
+        mixed.evaluate(0.3),
+        AnimationCurve::new(vec![
+            Keyframe::with_slopes(0.0, 0.0, 0.0, 2.0).with_weights(WeightedMode::Out, 0.0, 0.8),
+            Keyframe::new(1.0, 1.0, 0.0).with_weights(WeightedMode::In, 1.0 / 3.0, 0.0),
+        ])
+        .evaluate(0.3),
+    );
+    let both = AnimationCurve::new(vec![
+        Keyframe::with_slopes(0.0, 0.0, 0.0, 2.0).with_weights(WeightedMode::Out, 0.0, 0.8),
+        Keyframe::new(1.0, 1.0, 0.0).with_weights(WeightedMode::In, 0.1, 0.0),
+    ]);
+    assert_close(both.evaluate(0.3), 0.555_763_6);
+    assert_close(both.evaluate(0.7), 1.066_716_5);
+}
+
+#[test]
+fn wrap_modes() {
+    let ramp = AnimationCurve::linear(0.0, 0.0, 1.0, 1.0);
+    assert_eq!(ramp.evaluate(5.0), 1.0);
+    assert_eq!(ramp.evaluate(-5.0), 0.0);
+
+    let looped = ramp
+        .clone()
+        .with_pre_wrap(WrapMode::Loop)
+        .with_post_wrap(WrapMode::Loop);
+    assert_close(looped.evaluate(1.25), 0.25);
+    assert_close(looped.evaluate(2.5), 0.5);
+    assert_close(looped.evaluate(-0.25), 0.75);
+
+    let ping_pong = ramp
+        .clone()
+        .with_pre_wrap(WrapMode::PingPong)
+        .with_post_wrap(WrapMode::PingPong);
+    assert_close(ping_pong.evaluate(1.25), 0.75);
+    assert_close(ping_pong.evaluate(2.25), 0.25);
+    assert_close(ping_pong.evaluate(-0.25), 0.25);
+
+    let samples = looped.sample_range(0.0, 2.0, 9);
+    // The last key time is inclusive; a full period later wraps to the start again.
+    let expected = [0.0, 0.25, 0.5, 0.75, 1.0, 0.25, 0.5, 0.75, 0.0];
+    for (actual, expected) in samples.iter().zip(expected) {
+        assert_close(*actual, expected);
+    }
+    assert!(ramp.sample_range(0.0, 1.0, 0).is_empty());
+    assert_eq!(ramp.sample_range(0.5, 1.0, 1), vec![0.5]);
+}
+
+#[test]
+fn curve_from_typetree() {
+    let key = |time: f64, value: f64, slope: f64| {
+        UnityValue::Object(IndexMap::from([
+            ("time".to_string(), UnityValue::Float(time)),
+            ("value".to_string(), UnityValue::Float(value)),
+            ("inSlope".to_string(), UnityValue::Float(slope)),
+            ("outSlope".to_string(), UnityValue::Float(slope)),
+            ("tangentMode".to_string(), UnityValue::Integer(0)),
+            ("weightedMode".to_string(), UnityValue::Integer(2)),
+            ("inWeight".to_string(), UnityValue::Float(1.0 / 3.0)),
+            ("outWeight".to_string(), UnityValue::Float(1.0 / 3.0)),
+        ]))
+    };
+    let properties = IndexMap::from([
+        (
+            "m_Curve".to_string(),
+            UnityValue::Array(vec![key(0.0, 0.0, 2.0), key(0.5, 1.0, 2.0)]),
+        ),
+        ("m_PreInfinity".to_string(), UnityValue::Integer(2)),
+        ("m_PostInfinity".to_string(), UnityValue::Integer(0)),
+        ("m_RotationOrder".to_string(), UnityValue::Integer(4)),
+    ]);
+
+    let curve = AnimationCurve::from_typetree(&properties).unwrap();
+    assert_eq!(curve.keys.len(), 2);
+    assert_eq!(curve.keys[0].weighted_mode, WeightedMode::Out);
+    assert_eq!(curve.pre_wrap, WrapMode::Clamp);
+    assert_eq!(curve.post_wrap, WrapMode::PingPong);
+    assert_close(curve.evaluate(0.25), 0.5);
+    assert_close(curve.evaluate(0.75), 0.5);
+
+    assert!(AnimationCurve::from_typetree(&IndexMap::new()).is_err());
+}
@@ -0,0 +1,114 @@
+//! Streamed AudioClip data resolved from the containing bundle
+//!
+//! Every clip in char_118_yuki.ab keeps its FSB5 bytes in the bundle's `.resource` node.
+
+#![cfg(feature = "audio")]
+
+use unity_asset_core::UnityValue;
+use unity_asset_decode::audio::{
+    AudioClipConverter, AudioCompressionFormat, AudioProcessor, Fsb5Bank,
+};
+use unity_asset_decode::bundle::{AssetBundle, BundleParser};
+use unity_asset_decode::error::BinaryError;
+use unity_asset_decode::object::UnityObject;
+use unity_asset_decode::unity_version::UnityVersion;
+
+const CLIP: i64 = 5482297458062616526;
+
+fn sample() -> (AssetBundle, UnityObject) {
+    let bundle = BundleParser::from_bytes(
+        include_bytes!("../../../tests/samples/char_118_yuki.ab").to_vec(),
+    )
+    .unwrap();
+    let object = bundle.assets[0]
+        .find_object_handle(CLIP)
+        .unwrap()
+        .read()
+        .unwrap();
+    (bundle, object)
+}
+
+#[test]
+fn get_audio_data_from_bundle_reads_the_resource_range() {
+    let (bundle, object) = sample();
+    let converter = AudioClipConverter::new(UnityVersion::default());
+    let clip = converter.from_unity_object(&object).unwrap();
+    assert!(clip.is_streamed());
+    assert!(clip.data.is_empty());
+
+    let data = converter
+        .get_audio_data_from_bundle(&clip, &bundle)
+        .unwrap();
+    assert_eq!(data.len(), clip.stream_info.size as usize);
+    assert!(data.starts_with(b"FSB5"));
+    let bank = Fsb5Bank::parse(&data).unwrap();
+    assert_eq!(bank.compression_format(), AudioCompressionFormat::Vorbis);
+    assert_eq!(bank.samples.len(), 1);
+    assert_eq!(bank.samples[0].frequency, 44100);
+    assert!(bank.samples[0].vorbis_crc32.is_some());
+    let node = bundle.find_resource_node(&clip.stream_info.path).unwrap();
+    assert!(node.name.ends_with(".resource"));
+    assert_eq!(
+        data,
+        bundle
+            .extract_node_range(node, clip.stream_info.offset, clip.stream_info.size as u64)
+            .unwrap()
+    );
+
+    // Embedded bytes win over the stream.
+    let mut embedded = clip.clone();
+    embedded.data = vec![1, 2, 3];
+    assert_eq!(
+        converter
+            .get_audio_data_from_bundle(&embedded, &bundle)
+            .unwrap(),
+        [1, 2, 3]
+    );
+}
+
+#[test]
+fn resource_in_another_bundle_is_a_typed_error() {
+    let (bundle, object) = sample();
+    let converter = AudioClipConverter::new(UnityVersion::default());
+    let mut clip = converter.from_unity_object(&object).unwrap();
+    let path = "archive:/CAB-0123456789abcdef/CAB-0123456789abcdef.resource";
+    clip.stream_info.path = path.to_string();
+
+    match converter.get_audio_data_from_bundle(&clip, &bundle) {
+        Err(BinaryError::ExternalResourceMissing { path: missing }) => assert_eq!(missing, path),
+        other => panic!("expected ExternalResourceMissing, got {:?}", other),
+    }
+}
+
+#[test]
+fn process_and_export_reads_streamed_clip_from_bundle() {
+    let (bundle, object) = sample();
+    let processor = AudioProcessor::new(UnityVersion::default());
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("CN_002.wav");
+
+    // Without the bundle there is nothing to decode.
+    match processor.process_and_export(&object, None, &out) {
+        Err(BinaryError::InvalidData(msg)) => assert!(msg.contains("No audio data"), "{}", msg),
+        other => panic!("expected InvalidData, got {:?}", other),
+    }
+
+    // With it the FSB5 payload reaches the decoder, which lacks the bank's Vorbis setup header.
+    let err = processor
+        .process_and_export(&object, Some(&bundle), &out)
+        .unwrap_err();
+    assert!(!matches!(err, BinaryError::InvalidData(_)), "{:?}", err);
+
+    // A streamed clip whose resource lives elsewhere reports the missing path.
+    let mut elsewhere = object.clone();
+    let path = "archive:/CAB-0123456789abcdef/CAB-0123456789abcdef.resource";
+    let Some(UnityValue::Object(mut resource)) = object.get("m_Resource").cloned() else {
+        panic!("AudioClip has no m_Resource");
+    };
+    resource.insert("m_Source".to_string(), UnityValue::String(path.to_string()));
+    elsewhere.set("m_Resource".to_string(), UnityValue::Object(resource));
+    match processor.process_and_export(&elsewhere, Some(&bundle), &out) {
+        Err(BinaryError::ExternalResourceMissing { path: missing }) => assert_eq!(missing, path),
+        other => panic!("expected ExternalResourceMissing, got {:?}", other),
+    }
+}
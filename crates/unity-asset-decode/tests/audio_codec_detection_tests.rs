@@ -0,0 +1,280 @@
+//! `AudioCompressionFormat::detect` and `AudioDecoder` falling back to the payload's magic
+//! bytes when an AudioClip's declared compression format is wrong.
+
+#![cfg(feature = "audio")]
+
+use std::io::Cursor;
+use unity_asset_decode::audio::{AudioClip, AudioCompressionFormat, AudioDecoder, DetectedCodec};
+
+const RATE: u32 = 44100;
+const FRAMES: usize = 64;
+
+fn ramp() -> Vec<i16> {
+    (0..FRAMES as i16).map(|i| i * 300 - 9600).collect()
+}
+
+fn clip(declared: AudioCompressionFormat, data: Vec<u8>) -> AudioClip {
+    let mut clip = AudioClip::new("mislabeled".to_string(), declared);
+    clip.data = data;
+    clip
+}
+
+fn wav() -> Vec<u8> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut data = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut data, spec).unwrap();
+    for sample in ramp() {
+        writer.write_sample(sample).unwrap();
+    }
+    writer.finalize().unwrap();
+    data.into_inner()
+}
+
+/// A one-subsound FSB5 (v1) bank in `mode` holding `payload`, mono at 44.1 kHz.
+fn fsb5(mode: u32, payload: &[u8]) -> Vec<u8> {
+    let mut out = b"FSB5".to_vec();
+    for field in [1, 1, 8, 0, payload.len() as u32, mode] {
+        out.extend_from_slice(&field.to_le_bytes());
+    }
+    out.resize(60, 0);
+    // No extra chunks, frequency index 8 (44100), one channel, data offset 0, FRAMES samples.
+    let sample_header = (8u64 << 1) | ((FRAMES as u64) << 34);
+    out.extend_from_slice(&sample_header.to_le_bytes());
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Silent MPEG-1 layer III frames (128 kbps, 44.1 kHz, mono), optionally behind an ID3v2 tag.
+fn mp3(id3: bool) -> Vec<u8> {
+    let mut out = Vec::new();
+    if id3 {
+        // ID3v2.3 tag holding 16 bytes of padding.
+        out.extend_from_slice(b"ID3\x03\x00\x00\x00\x00\x00\x10");
+        out.extend_from_slice(&[0; 16]);
+    }
+    for _ in 0..8 {
+        let mut frame = vec![0u8; 417];
+        frame[..4].copy_from_slice(&[0xFF, 0xFB, 0x90, 0xC0]);
+        out.extend_from_slice(&frame);
+    }
+    out
+}
+
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |mut crc, &byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |mut crc, &byte| {
+        crc ^= u16::from(byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+fn ogg_crc(data: &[u8]) -> u32 {
+    data.iter().fold(0u32, |mut crc, &byte| {
+        crc ^= u32::from(byte) << 24;
+        for _ in 0..8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04C1_1DB7
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// A last-block STREAMINFO metadata block: 16-bit mono, FRAMES samples in one block.
+fn flac_stream_info() -> Vec<u8> {
+    let mut out = vec![0x80, 0, 0, 34];
+    out.extend_from_slice(&(FRAMES as u16).to_be_bytes());
+    out.extend_from_slice(&(FRAMES as u16).to_be_bytes());
+    out.extend_from_slice(&[0; 6]);
+    let packed = (u64::from(RATE) << 44) | (15 << 36) | FRAMES as u64;
+    out.extend_from_slice(&packed.to_be_bytes());
+    out.extend_from_slice(&[0; 16]);
+    out
+}
+
+/// One FLAC frame with a verbatim subframe of the ramp.
+fn flac_frame() -> Vec<u8> {
+    // Fixed blocking, 8-bit block size follows, 44.1 kHz, mono, 16-bit, frame 0.
+    let mut frame = vec![0xFF, 0xF8, 0x69, 0x08, 0x00, FRAMES as u8 - 1];
+    frame.push(crc8(&frame));
+    frame.push(0x02);
+    for sample in ramp() {
+        frame.extend_from_slice(&sample.to_be_bytes());
+    }
+    let crc = crc16(&frame);
+    frame.extend_from_slice(&crc.to_be_bytes());
+    frame
+}
+
+fn flac() -> Vec<u8> {
+    let mut out = b"fLaC".to_vec();
+    out.extend(flac_stream_info());
+    out.extend(flac_frame());
+    out
+}
+
+fn ogg_page(flags: u8, granule: u64, sequence: u32, packet: &[u8]) -> Vec<u8> {
+    assert!(packet.len() < 255);
+    let mut page = b"OggS".to_vec();
+    page.push(0);
+    page.push(flags);
+    page.extend_from_slice(&granule.to_le_bytes());
+    page.extend_from_slice(&0x1234u32.to_le_bytes());
+    page.extend_from_slice(&sequence.to_le_bytes());
+    page.extend_from_slice(&[0; 4]);
+    page.push(1);
+    page.push(packet.len() as u8);
+    page.extend_from_slice(packet);
+    let crc = ogg_crc(&page);
+    page[22..26].copy_from_slice(&crc.to_le_bytes());
+    page
+}
+
+/// Ogg FLAC: the mapping header packet, then the frame on the final page.
+fn ogg_flac() -> Vec<u8> {
+    let mut header = vec![0x7F];
+    header.extend_from_slice(b"FLAC");
+    header.extend_from_slice(&[1, 0, 0, 0]);
+    header.extend_from_slice(b"fLaC");
+    header.extend(flac_stream_info());
+    let mut out = ogg_page(0x02, 0, 0, &header);
+    out.extend(ogg_page(0x04, FRAMES as u64, 1, &flac_frame()));
+    out
+}
+
+#[test]
+fn detect_recognizes_each_container_magic() {
+    let detect = AudioCompressionFormat::detect;
+    assert_eq!(detect(&ogg_flac()), Some(DetectedCodec::Ogg));
+    assert_eq!(detect(&wav()), Some(DetectedCodec::Wav));
+    assert_eq!(detect(&flac()), Some(DetectedCodec::Flac));
+    assert_eq!(detect(&mp3(false)), Some(DetectedCodec::Mp3));
+    assert_eq!(detect(&mp3(true)), Some(DetectedCodec::Mp3));
+    assert_eq!(
+        detect(&fsb5(15, &[0; 16])),
+        Some(DetectedCodec::Fsb5 { mode: 15 })
+    );
+
+    // ADTS AAC shares the 0xFFF sync but has layer 0; RIFF without WAVE is not audio.
+    assert_eq!(detect(&[0xFF, 0xF1, 0x50, 0x80]), None);
+    assert_eq!(detect(b"RIFF\0\0\0\0AVI "), None);
+    assert_eq!(detect(b"FSB5\x01"), None);
+    assert_eq!(detect(&[]), None);
+
+    let vorbis_bank = DetectedCodec::Fsb5 { mode: 15 };
+    assert_eq!(
+        vorbis_bank.compression_format(),
+        AudioCompressionFormat::Vorbis
+    );
+    assert!(vorbis_bank.matches(AudioCompressionFormat::Vorbis));
+    assert!(!DetectedCodec::Ogg.matches(AudioCompressionFormat::PCM));
+}
+
+#[test]
+fn mislabeled_wav_decodes_with_a_mismatch_warning() {
+    let audio = AudioDecoder::new()
+        .decode(&clip(AudioCompressionFormat::Vorbis, wav()))
+        .unwrap();
+
+    assert_eq!(audio.codec, Some(DetectedCodec::Wav));
+    assert_eq!((audio.sample_rate, audio.channels), (RATE, 1));
+    assert_eq!(audio.samples.len(), FRAMES);
+    assert!((audio.samples[0] - (-9600.0 / 32768.0)).abs() < 1e-4);
+    assert_eq!(audio.warnings.len(), 1);
+    assert!(audio.warnings[0].contains("Vorbis"), "{:?}", audio.warnings);
+    assert!(audio.warnings[0].contains("WAV"), "{:?}", audio.warnings);
+
+    // A correctly labeled clip decodes without diagnostics.
+    let audio = AudioDecoder::new()
+        .decode(&clip(AudioCompressionFormat::PCM, wav()))
+        .unwrap();
+    assert_eq!(audio.codec, Some(DetectedCodec::Wav));
+    assert!(audio.warnings.is_empty());
+}
+
+#[test]
+fn mislabeled_fsb5_falls_back_to_the_bank_decoder() {
+    let payload: Vec<u8> = ramp().iter().flat_map(|s| s.to_le_bytes()).collect();
+    let bank = fsb5(2, &payload);
+
+    let audio = AudioDecoder::new()
+        .decode(&clip(AudioCompressionFormat::Vorbis, bank))
+        .unwrap();
+    assert_eq!(audio.codec, Some(DetectedCodec::Fsb5 { mode: 2 }));
+    assert_eq!((audio.sample_rate, audio.channels), (RATE, 1));
+    let expected: Vec<f32> = ramp().iter().map(|&s| s as f32 / 32768.0).collect();
+    assert_eq!(audio.samples, expected);
+    assert!(audio.warnings[0].contains("FSB5 (mode 2)"));
+
+    // Codecs without a decoder still fail, with the declared format's error.
+    let vorbis_bank = fsb5(15, &[0; 64]);
+    assert!(
+        AudioDecoder::new()
+            .decode(&clip(AudioCompressionFormat::Vorbis, vorbis_bank))
+            .is_err()
+    );
+}
+
+#[test]
+fn mislabeled_mp3_decodes_when_trusting_magic() {
+    let decoder = AudioDecoder::new().with_trust_magic(true);
+    assert!(decoder.trust_magic());
+
+    for id3 in [false, true] {
+        let audio = decoder
+            .decode(&clip(AudioCompressionFormat::PCM, mp3(id3)))
+            .unwrap();
+        assert_eq!(audio.codec, Some(DetectedCodec::Mp3));
+        assert_eq!(audio.sample_rate, RATE);
+        assert!(!audio.samples.is_empty());
+        assert!(audio.samples.iter().all(|s| s.abs() < 1e-6));
+        assert_eq!(audio.warnings.len(), 1);
+    }
+}
+
+#[test]
+fn mislabeled_flac_streams_decode() {
+    let native = AudioDecoder::new()
+        .decode(&clip(AudioCompressionFormat::MP3, flac()))
+        .unwrap();
+    assert_eq!(native.codec, Some(DetectedCodec::Flac));
+    assert_eq!((native.sample_rate, native.channels), (RATE, 1));
+    assert_eq!(native.samples.len(), FRAMES);
+    assert!(native.samples.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(native.warnings.len(), 1);
+
+    let ogg = AudioDecoder::new()
+        .with_trust_magic(true)
+        .decode(&clip(AudioCompressionFormat::PCM, ogg_flac()))
+        .unwrap();
+    assert_eq!(ogg.codec, Some(DetectedCodec::Ogg));
+    assert_eq!(ogg.samples, native.samples);
+    assert!(ogg.warnings[0].contains("PCM"));
+}
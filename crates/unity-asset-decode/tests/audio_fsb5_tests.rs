@@ -0,0 +1,324 @@
+//! FSB5 banks split into per-sub-sound files: PCM rebuilt as WAV and Vorbis packets wrapped
+//! into Ogg pages around a setup header looked up by CRC.
+//!
+//! The Vorbis fixture uses a minimal setup header (one two-entry codebook, a floor 1 with no
+//! partitions, a short and a long mode) and packets that mark every channel unused, so they
+//! decode to silence.
+
+#![cfg(feature = "audio")]
+
+use std::sync::Arc;
+use unity_asset_decode::audio::{
+    AudioClip, AudioClipMeta, AudioCompressionFormat, AudioDecoder, AudioProcessor, Fsb5Bank,
+    VorbisHeaderTable,
+};
+use unity_asset_decode::error::BinaryError;
+use unity_asset_decode::unity_version::UnityVersion;
+
+const SETUP_CRC: u32 = 0x1234_ABCD;
+
+struct Sample {
+    frequency_index: u64,
+    channel_code: u64,
+    frames: u64,
+    chunks: Vec<(u32, Vec<u8>)>,
+    data: Vec<u8>,
+}
+
+/// A version 1 bank in `mode`; `names` adds a name table.
+fn bank(mode: u32, samples: &[Sample], names: Option<&[&str]>) -> Vec<u8> {
+    let mut headers = Vec::new();
+    let mut data = Vec::new();
+    for sample in samples {
+        let offset = data.len() as u64 / 32;
+        let bits = u64::from(!sample.chunks.is_empty())
+            | (sample.frequency_index << 1)
+            | (sample.channel_code << 5)
+            | (offset << 7)
+            | (sample.frames << 34);
+        headers.extend_from_slice(&bits.to_le_bytes());
+        for (i, (kind, body)) in sample.chunks.iter().enumerate() {
+            let more = u32::from(i + 1 < sample.chunks.len());
+            let chunk = more | ((body.len() as u32) << 1) | (kind << 25);
+            headers.extend_from_slice(&chunk.to_le_bytes());
+            headers.extend_from_slice(body);
+        }
+        data.extend_from_slice(&sample.data);
+        data.resize(data.len().next_multiple_of(32), 0);
+    }
+
+    let mut name_table = Vec::new();
+    if let Some(names) = names {
+        let mut strings = Vec::new();
+        for name in names {
+            let offset = (names.len() * 4 + strings.len()) as u32;
+            name_table.extend_from_slice(&offset.to_le_bytes());
+            strings.extend_from_slice(name.as_bytes());
+            strings.push(0);
+        }
+        name_table.extend_from_slice(&strings);
+    }
+
+    let mut out = b"FSB5".to_vec();
+    for field in [
+        1,
+        samples.len() as u32,
+        headers.len() as u32,
+        name_table.len() as u32,
+        data.len() as u32,
+        mode,
+    ] {
+        out.extend_from_slice(&field.to_le_bytes());
+    }
+    out.resize(60, 0);
+    out.extend_from_slice(&headers);
+    out.extend_from_slice(&name_table);
+    out.extend_from_slice(&data);
+    out
+}
+
+fn clip(data: Vec<u8>, subsound_index: i32) -> AudioClip {
+    let mut clip = AudioClip::new("voice".to_string(), AudioCompressionFormat::Vorbis);
+    if let AudioClipMeta::Modern {
+        subsound_index: index,
+        ..
+    } = &mut clip.meta
+    {
+        *index = subsound_index;
+    }
+    clip.data = data;
+    clip
+}
+
+/// Vorbis bit packing, least significant bit first
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    bit: usize,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, bits: u32) -> &mut Self {
+        for i in 0..bits {
+            if self.bit.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            self.bytes[self.bit / 8] |= (((value >> i) & 1) as u8) << (self.bit % 8);
+            self.bit += 1;
+        }
+        self
+    }
+}
+
+/// Setup header body (without `\x05vorbis`)
+fn setup_header() -> Vec<u8> {
+    let mut w = BitWriter::default();
+    // One codebook: sync, 1 dimension, 2 entries of length 1, no lookup
+    w.write(0, 8).write(0x56_4342, 24).write(1, 16).write(2, 24);
+    w.write(0, 1)
+        .write(0, 1)
+        .write(0, 5)
+        .write(0, 5)
+        .write(0, 4);
+    // One time domain placeholder
+    w.write(0, 6).write(0, 16);
+    // One floor 1: no partitions, multiplier 2, 8 range bits
+    w.write(0, 6)
+        .write(1, 16)
+        .write(0, 5)
+        .write(1, 2)
+        .write(8, 4);
+    // One residue 2 over nothing, classified by codebook 0
+    w.write(0, 6)
+        .write(2, 16)
+        .write(0, 24)
+        .write(0, 24)
+        .write(31, 24);
+    w.write(0, 6).write(0, 8).write(0, 3).write(0, 1);
+    // One mapping: a single submap, no coupling
+    w.write(0, 6)
+        .write(0, 16)
+        .write(0, 1)
+        .write(0, 1)
+        .write(0, 2);
+    w.write(0, 8).write(0, 8).write(0, 8);
+    // Two modes: short and long blocks
+    w.write(1, 6);
+    for blockflag in [0, 1] {
+        w.write(blockflag, 1).write(0, 16).write(0, 16).write(0, 8);
+    }
+    w.write(1, 1);
+    w.bytes
+}
+
+/// Audio packets: `long` selects mode 1 (with both window flags set), every channel unused
+fn vorbis_data(packets: &[(bool, usize)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for &(long, size) in packets {
+        let mut packet = vec![0u8; size];
+        packet[0] = if long { 0b1110 } else { 0 };
+        out.extend_from_slice(&(size as u16).to_le_bytes());
+        out.extend_from_slice(&packet);
+    }
+    out
+}
+
+fn vorbis_bank(frames: u64, packets: &[(bool, usize)]) -> Vec<u8> {
+    bank(
+        15,
+        &[Sample {
+            frequency_index: 8,
+            channel_code: 0,
+            frames,
+            chunks: vec![(11, [SETUP_CRC.to_le_bytes(), [0; 4]].concat())],
+            data: vorbis_data(packets),
+        }],
+        None,
+    )
+}
+
+struct Page {
+    flags: u8,
+    granule: i64,
+    sequence: u32,
+    segments: usize,
+}
+
+fn ogg_pages(mut data: &[u8]) -> Vec<Page> {
+    let mut pages = Vec::new();
+    while !data.is_empty() {
+        assert!(data.starts_with(b"OggS"));
+        let segments = data[26] as usize;
+        let body: usize = data[27..27 + segments].iter().map(|&s| s as usize).sum();
+        pages.push(Page {
+            flags: data[5],
+            granule: i64::from_le_bytes(data[6..14].try_into().unwrap()),
+            sequence: u32::from_le_bytes(data[18..22].try_into().unwrap()),
+            segments,
+        });
+        data = &data[27 + segments + body..];
+    }
+    pages
+}
+
+#[test]
+fn pcm_bank_parses_every_sample_and_rebuilds_wav() {
+    let first: Vec<i16> = (0..32).map(|i| i * 100 - 1600).collect();
+    let second: Vec<i16> = (0..48).map(|i| 2000 - i * 50).collect();
+    let pcm = |s: &[i16]| s.iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<u8>>();
+    let data = bank(
+        2,
+        &[
+            Sample {
+                frequency_index: 8,
+                channel_code: 0,
+                frames: 32,
+                chunks: vec![(3, [4u32.to_le_bytes(), 28u32.to_le_bytes()].concat())],
+                data: pcm(&first),
+            },
+            Sample {
+                frequency_index: 8,
+                channel_code: 1,
+                frames: 24,
+                chunks: vec![(2, 22050u32.to_le_bytes().to_vec())],
+                data: pcm(&second),
+            },
+        ],
+        Some(&["intro", "loop"]),
+    );
+
+    let parsed = Fsb5Bank::parse(&data).unwrap();
+    assert_eq!((parsed.version, parsed.mode), (1, 2));
+    assert_eq!(parsed.compression_format(), AudioCompressionFormat::PCM);
+    let [a, b] = &parsed.samples[..] else {
+        panic!("expected two samples");
+    };
+    assert_eq!(a.name.as_deref(), Some("intro"));
+    assert_eq!((a.frequency, a.channels, a.num_samples), (44100, 1, 32));
+    assert_eq!(a.loop_points, Some((4, 28)));
+    assert_eq!(a.data, pcm(&first));
+    assert_eq!(b.name.as_deref(), Some("loop"));
+    assert_eq!((b.frequency, b.channels, b.loop_points), (22050, 2, None));
+    assert_eq!(&b.data[..96], pcm(&second));
+
+    let samples = clip(data.clone(), 0).extract_samples().unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].file_name(), "voice-0.wav");
+    assert_eq!(samples[1].file_name(), "voice-1.wav");
+    assert_eq!(samples[0].loop_points, Some((4, 28)));
+    let mut reader = hound::WavReader::new(std::io::Cursor::new(&samples[1].data)).unwrap();
+    assert_eq!(reader.spec().channels, 2);
+    assert_eq!(reader.spec().sample_rate, 22050);
+    let decoded: Vec<i16> = reader.samples::<i16>().map(Result::unwrap).collect();
+    assert_eq!(decoded[..48], second);
+
+    // The decoder follows the clip's subsound index.
+    let audio = AudioDecoder::new()
+        .with_trust_magic(true)
+        .decode(&clip(data.clone(), 1))
+        .unwrap();
+    assert_eq!((audio.sample_rate, audio.channels), (22050, 2));
+    assert!((audio.samples[0] - 2000.0 / 32768.0).abs() < 1e-6);
+
+    let dir = tempfile::tempdir().unwrap();
+    let written = AudioProcessor::new(UnityVersion::default())
+        .export_samples(&clip(data, 0), dir.path())
+        .unwrap();
+    assert_eq!(written.len(), 2);
+    assert_eq!(std::fs::read(&written[0]).unwrap(), samples[0].data);
+}
+
+#[test]
+fn vorbis_bank_rebuilds_into_ogg_pages() {
+    // 2 short, 2 long, 1 short, and one packet long enough to continue over a page break.
+    let packets = [
+        (false, 4),
+        (false, 4),
+        (true, 4),
+        (true, 300),
+        (false, 4),
+        (false, 65000),
+    ];
+    let data = vorbis_bank(2400, &packets);
+    let parsed = Fsb5Bank::parse(&data).unwrap();
+    assert_eq!(parsed.samples[0].vorbis_crc32, Some(SETUP_CRC));
+
+    // Without the setup header there is nothing to decode with.
+    match clip(data.clone(), 0).extract_samples() {
+        Err(BinaryError::Unsupported(msg)) => assert!(msg.contains("1234abcd"), "{}", msg),
+        other => panic!("expected Unsupported, got {:?}", other),
+    }
+
+    let headers: VorbisHeaderTable = [(SETUP_CRC, setup_header())].into_iter().collect();
+    let samples = clip(data.clone(), 0)
+        .extract_samples_with(&headers)
+        .unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].file_name(), "voice.ogg");
+
+    let ogg = &samples[0].data;
+    assert!(ogg[28..].starts_with(b"\x01vorbis"));
+    let pages = ogg_pages(ogg);
+    assert_eq!(pages[0].flags, 0x02);
+    assert!(
+        pages
+            .iter()
+            .enumerate()
+            .all(|(i, p)| p.sequence == i as u32)
+    );
+    assert_eq!(pages.last().unwrap().flags & 0x04, 0x04);
+    // Granules: 0, +128, +576, +1024, +576, then 128 more trimmed to the 2400 frames. The
+    // 65000-byte packet fills the first audio page up to 255 segments and ends on the next.
+    let full = pages.iter().position(|p| p.segments == 255).unwrap();
+    assert_eq!(pages[full].granule, 2304);
+    assert_eq!(pages[full + 1].flags, 0x01 | 0x04);
+    assert_eq!(pages[full + 1].granule, 2400);
+
+    let audio = AudioDecoder::new()
+        .with_vorbis_headers(Arc::new(headers))
+        .decode(&clip(data, 0))
+        .unwrap();
+    assert_eq!((audio.sample_rate, audio.channels), (44100, 1));
+    assert!(!audio.samples.is_empty());
+    assert!(audio.samples.iter().all(|s| *s == 0.0));
+}
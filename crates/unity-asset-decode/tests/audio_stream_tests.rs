@@ -0,0 +1,252 @@
+//! `AudioDecoder::decode_to_writer`: bounded buffering, WAV/RF64 headers and Ogg passthrough.
+
+#![cfg(feature = "audio")]
+
+use std::io::{Cursor, Write};
+use unity_asset_decode::audio::{
+    AudioClip, AudioCompressionFormat, AudioDecoder, AudioFormat, AudioProcessor,
+    AudioStreamOptions, AudioStreamStats, WAV_HEADER_LEN,
+};
+use unity_asset_decode::unity_version::UnityVersion;
+
+const RATE: u32 = 8000;
+
+fn sample(frame: u32, channel: u32) -> i16 {
+    (((frame * 7 + channel * 3) % 2000) as i16 - 1000) * 16
+}
+
+/// A PCM clip holding a 16-bit WAV of `seconds` seconds.
+fn wav_clip(seconds: u32, channels: u16) -> AudioClip {
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate: RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut data = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut data, spec).unwrap();
+    for frame in 0..seconds * RATE {
+        for channel in 0..u32::from(channels) {
+            writer.write_sample(sample(frame, channel)).unwrap();
+        }
+    }
+    writer.finalize().unwrap();
+
+    let mut clip = AudioClip::new("long".to_string(), AudioCompressionFormat::PCM);
+    clip.data = data.into_inner();
+    clip
+}
+
+/// Counts bytes, keeps the first 80 (the WAV header) and tracks the largest single write.
+#[derive(Default)]
+struct CountingSink {
+    written: u64,
+    largest_write: usize,
+    head: Vec<u8>,
+}
+
+impl Write for CountingSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.written += buf.len() as u64;
+        self.largest_write = self.largest_write.max(buf.len());
+        let keep = (WAV_HEADER_LEN as usize).saturating_sub(self.head.len());
+        self.head.extend_from_slice(&buf[..keep.min(buf.len())]);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+fn u64_at(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())
+}
+
+#[test]
+fn long_clip_streams_with_bounded_buffers() {
+    let seconds = 240;
+    let clip = wav_clip(seconds, 2);
+    let frames = u64::from(seconds * RATE);
+    let mut sink = CountingSink::default();
+
+    let stats = AudioDecoder::new()
+        .decode_to_writer(
+            &clip,
+            &mut sink,
+            AudioFormat::Wav,
+            &AudioStreamOptions::default(),
+        )
+        .unwrap();
+
+    assert_eq!(stats.frames(), frames);
+    assert_eq!(stats.samples_written, frames * 2);
+    assert_eq!(stats.data_bytes, frames * 4);
+    assert!((stats.duration - f64::from(seconds)).abs() < 1e-9);
+    assert!(stats.header_complete);
+    assert_eq!(sink.written, stats.total_bytes());
+
+    // The decoded PCM is ~7.7 MB; nothing close to that is ever buffered.
+    const BOUND: usize = 64 * 1024;
+    assert!(
+        stats.peak_buffer_bytes <= BOUND,
+        "{}",
+        stats.peak_buffer_bytes
+    );
+    assert!(sink.largest_write <= BOUND, "{}", sink.largest_write);
+
+    let head = &sink.head;
+    assert_eq!(&head[0..4], b"RIFF");
+    assert_eq!(u64::from(u32_at(head, 4)), sink.written - 8);
+    assert_eq!(&head[8..12], b"WAVE");
+    assert_eq!(&head[12..16], b"JUNK");
+    assert_eq!(&head[48..52], b"fmt ");
+    assert_eq!(u16::from_le_bytes([head[58], head[59]]), 2);
+    assert_eq!(u32_at(head, 60), RATE);
+    assert_eq!(u16::from_le_bytes([head[70], head[71]]), 16);
+    assert_eq!(&head[72..76], b"data");
+    assert_eq!(u64::from(u32_at(head, 76)), stats.data_bytes);
+}
+
+#[test]
+fn streamed_wav_reads_back_sample_exact() {
+    let clip = wav_clip(2, 1);
+    let mut out = Vec::new();
+    let stats = AudioDecoder::new()
+        .decode_to_writer(
+            &clip,
+            &mut out,
+            AudioFormat::Wav,
+            &AudioStreamOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(out.len() as u64, stats.total_bytes());
+
+    let mut reader = hound::WavReader::new(Cursor::new(out)).unwrap();
+    assert_eq!(reader.spec().channels, 1);
+    assert_eq!(reader.spec().sample_rate, RATE);
+    let samples: Vec<i16> = reader.samples::<i16>().map(|s| s.unwrap()).collect();
+    assert_eq!(samples.len() as u64, stats.frames());
+    assert!(
+        samples
+            .iter()
+            .enumerate()
+            .all(|(i, &s)| s == sample(i as u32, 0))
+    );
+
+    let mut raw = Vec::new();
+    let raw_stats = AudioDecoder::new()
+        .decode_to_writer(
+            &clip,
+            &mut raw,
+            AudioFormat::RawPcm,
+            &AudioStreamOptions::default().with_bit_depth(32),
+        )
+        .unwrap();
+    assert_eq!(raw.len() as u64, raw_stats.data_bytes);
+    assert_eq!(raw_stats.data_bytes, stats.frames() * 4);
+}
+
+#[test]
+fn patched_header_switches_to_rf64_past_4_gib() {
+    let stats = AudioStreamStats {
+        format: AudioFormat::Wav,
+        sample_rate: 48000,
+        channels: 2,
+        bits_per_sample: 16,
+        samples_written: 1_500_000_000 * 2,
+        data_bytes: 6_000_000_000,
+        duration: 31250.0,
+        header_complete: false,
+        peak_buffer_bytes: 0,
+    };
+    let mut sink = Cursor::new(vec![0xAA; WAV_HEADER_LEN as usize + 16]);
+    stats.patch_wav_header(&mut sink).unwrap();
+    assert_eq!(sink.position(), WAV_HEADER_LEN + 16);
+
+    let head = sink.into_inner();
+    assert_eq!(&head[0..4], b"RF64");
+    assert_eq!(u32_at(&head, 4), u32::MAX);
+    assert_eq!(&head[12..16], b"ds64");
+    assert_eq!(u32_at(&head, 16), 28);
+    assert_eq!(u64_at(&head, 20), 6_000_000_000 + WAV_HEADER_LEN - 8);
+    assert_eq!(u64_at(&head, 28), 6_000_000_000);
+    assert_eq!(u64_at(&head, 36), 1_500_000_000);
+    assert_eq!(&head[72..76], b"data");
+    assert_eq!(u32_at(&head, 76), u32::MAX);
+    assert_eq!(head[80], 0xAA);
+}
+
+fn ogg_page(granule: i64, flags: u8, body: &[u8]) -> Vec<u8> {
+    let mut page = b"OggS".to_vec();
+    page.extend_from_slice(&[0, flags]);
+    page.extend_from_slice(&granule.to_le_bytes());
+    page.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]); // serial, sequence, crc
+    page.push(1);
+    page.push(body.len() as u8);
+    page.extend_from_slice(body);
+    page
+}
+
+#[test]
+fn ogg_is_copied_through_with_stats_from_its_pages() {
+    let mut id_header = b"\x01vorbis".to_vec();
+    id_header.extend_from_slice(&0u32.to_le_bytes());
+    id_header.push(2);
+    id_header.extend_from_slice(&44100u32.to_le_bytes());
+    id_header.extend_from_slice(&[0; 14]);
+    let mut data = ogg_page(0, 2, &id_header);
+    data.extend(ogg_page(44100, 0, &[7; 200]));
+    data.extend(ogg_page(88200, 4, &[9; 120]));
+
+    let mut clip = AudioClip::new("theme".to_string(), AudioCompressionFormat::Vorbis);
+    clip.data = data.clone();
+    let mut out = Vec::new();
+    let stats = AudioDecoder::new()
+        .decode_to_writer(
+            &clip,
+            &mut out,
+            AudioFormat::Ogg,
+            &AudioStreamOptions::default(),
+        )
+        .unwrap();
+
+    assert_eq!(out, data);
+    assert_eq!((stats.sample_rate, stats.channels), (44100, 2));
+    assert_eq!(stats.frames(), 88200);
+    assert!((stats.duration - 2.0).abs() < 1e-9);
+
+    let pcm = wav_clip(1, 1);
+    assert!(
+        AudioDecoder::new()
+            .decode_to_writer(
+                &pcm,
+                &mut Vec::new(),
+                AudioFormat::Ogg,
+                &AudioStreamOptions::default()
+            )
+            .is_err()
+    );
+}
+
+#[test]
+fn processor_streams_clips_to_files() {
+    let clip = wav_clip(3, 2);
+    let temp = tempfile::tempdir().unwrap();
+    let processor = AudioProcessor::new(UnityVersion::default());
+
+    let wav = temp.path().join("long.wav");
+    let stats = processor.stream_export(&clip, &wav).unwrap();
+    assert_eq!(std::fs::metadata(&wav).unwrap().len(), stats.total_bytes());
+    let reader = hound::WavReader::open(&wav).unwrap();
+    assert_eq!(u64::from(reader.duration()), stats.frames());
+
+    let raw = temp.path().join("long.raw");
+    let stats = processor.stream_export(&clip, &raw).unwrap();
+    assert_eq!(stats.format, AudioFormat::RawPcm);
+    assert_eq!(std::fs::metadata(&raw).unwrap().len(), stats.data_bytes);
+}
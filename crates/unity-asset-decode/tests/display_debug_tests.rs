@@ -0,0 +1,109 @@
+//! Snapshot tests for `Display`/`Debug` summaries of decoded object types.
+//!
+//! These strings are consumed by downstream log parsing; update them deliberately.
+
+#![allow(unused_imports)]
+
+#[cfg(feature = "audio")]
+#[test]
+fn audio_clip_display_and_debug() {
+    use unity_asset_decode::audio::{AudioClip, AudioCompressionFormat};
+
+    let mut clip = AudioClip::new("hit".to_string(), AudioCompressionFormat::Vorbis);
+    if let unity_asset_decode::audio::AudioClipMeta::Modern { length, .. } = &mut clip.meta {
+        *length = 1.25;
+    }
+    clip.data = vec![0x4f; 1 << 20];
+
+    assert_eq!(
+        clip.to_string(),
+        "AudioClip \"hit\" format=Vorbis channels=2 frequency=44100 length=1.250s data=1048576"
+    );
+
+    clip.stream_info.path = "archive:/CAB-1/CAB-1.resource".to_string();
+    clip.stream_info.size = 4096;
+    assert!(clip.to_string().ends_with(" data=1048576 streamed=4096"));
+
+    let debug = format!("{:?}", clip);
+    assert!(
+        debug.len() < 1024,
+        "Debug output too large: {}",
+        debug.len()
+    );
+    assert!(
+        debug.contains("data: <1048576 bytes: 4f 4f 4f 4f 4f 4f 4f 4f 4f 4f 4f 4f 4f 4f 4f 4f ..>")
+    );
+}
+
+#[cfg(feature = "mesh")]
+#[test]
+fn mesh_display_and_debug() {
+    use unity_asset_decode::mesh::{Mesh, PackedIntVector, SubMesh};
+
+    let mut mesh = Mesh {
+        name: "Cube".to_string(),
+        sub_meshes: vec![SubMesh::default()],
+        index_buffer: vec![0; 72],
+        ..Default::default()
+    };
+    mesh.vertex_data.vertex_count = 24;
+    mesh.vertex_data.data_size = vec![0x3f; 1152];
+
+    assert_eq!(
+        mesh.to_string(),
+        "Mesh \"Cube\" vertices=24 submeshes=1 index_buffer=72 vertex_data=1152"
+    );
+
+    let packed = PackedIntVector {
+        num_items: 3,
+        data: vec![1, 2, 3],
+        bit_size: 8,
+    };
+    assert_eq!(
+        format!("{:?}", packed),
+        "PackedIntVector { num_items: 3, data: <3 bytes: 01 02 03>, bit_size: 8 }"
+    );
+
+    let debug = format!("{:?}", mesh);
+    assert!(
+        debug.len() < 2048,
+        "Debug output too large: {}",
+        debug.len()
+    );
+    assert!(debug.contains(
+        "vertex_data: VertexData { vertex_count: 24, channels: [], \
+         data_size: <1152 bytes: 3f 3f 3f 3f 3f 3f 3f 3f 3f 3f 3f 3f 3f 3f 3f 3f ..> }"
+    ));
+    assert!(debug.contains("index_buffer: <72 bytes: 00"));
+    assert!(debug.contains("bind_pose: [0 matrices]"));
+}
+
+#[cfg(feature = "texture")]
+#[test]
+fn texture_display_and_debug() {
+    use unity_asset_decode::texture::{PixelLayout, Texture2D, TextureDecoder, TextureFormat};
+
+    let mut texture = Texture2D::new("icon".to_string(), 256, 256, TextureFormat::RGBA32);
+    texture.mip_count = 9;
+    texture.image_data = vec![0xff; 256 * 256 * 4];
+
+    assert_eq!(
+        texture.to_string(),
+        "Texture2D \"icon\" 256x256 format=RGBA32 mips=9 data=262144"
+    );
+
+    let debug = format!("{:?}", texture);
+    assert!(
+        debug.len() < 2048,
+        "Debug output too large: {}",
+        debug.len()
+    );
+    assert!(debug.contains(
+        "image_data: <262144 bytes: ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ..>"
+    ));
+
+    let pixels = TextureDecoder::new()
+        .decode_raw(&texture, PixelLayout::Bgra8)
+        .unwrap();
+    assert!(format!("{:?}", pixels).starts_with("DecodedPixels { data: <262144 bytes: ff"));
+}
@@ -0,0 +1,162 @@
+//! Font (class 128) parsing and embedded TTF/OTF export.
+//!
+//! The sample bundles ship no Font, so one is added to banner_1's serialized file in the 5.5+
+//! raw layout (no TypeTree).
+
+#![cfg(feature = "font")]
+
+use indexmap::IndexMap;
+use unity_asset_binary::asset::{ObjectInfo, SerializedFile, SerializedType};
+use unity_asset_binary::bundle::BundleParser;
+use unity_asset_binary::reader::ByteOrder;
+use unity_asset_binary::unity_version::UnityVersion;
+use unity_asset_core::{UnityValue, class_ids};
+use unity_asset_decode::font::{Font, FontExporter, FontFormat, fonts};
+
+const FONT: i64 = 5150;
+const TTF: &[u8] = &[0x00, 0x01, 0x00, 0x00, 0x00, 0x0C, 0x00, 0x80, 0x00, 0x03];
+
+fn load(name: &str) -> SerializedFile {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/samples")
+        .join(name);
+    let bundle = BundleParser::from_bytes(std::fs::read(path).unwrap()).unwrap();
+    bundle.assets.into_iter().next().unwrap()
+}
+
+fn write_string(out: &mut Vec<u8>, s: &[u8]) {
+    out.extend_from_slice(&(s.len() as i32).to_le_bytes());
+    out.extend_from_slice(s);
+    while !out.len().is_multiple_of(4) {
+        out.push(0);
+    }
+}
+
+/// A 5.5+ Font: 16 pt, line spacing 18.5, two character rects, one kerning pair.
+fn font_bytes(font_data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_string(&mut out, b"Roboto-Regular");
+    out.extend_from_slice(&18.5f32.to_le_bytes()); // m_LineSpacing
+    out.extend_from_slice(&[0; 12]); // m_DefaultMaterial
+    out.extend_from_slice(&16.0f32.to_le_bytes()); // m_FontSize
+    out.extend_from_slice(&[0; 12]); // m_Texture
+    out.extend_from_slice(&32i32.to_le_bytes()); // m_AsciiStartOffset
+    out.extend_from_slice(&0f32.to_le_bytes()); // m_Tracking
+    out.extend_from_slice(&1i32.to_le_bytes()); // m_CharacterSpacing
+    out.extend_from_slice(&2i32.to_le_bytes()); // m_CharacterPadding
+    out.extend_from_slice(&0i32.to_le_bytes()); // m_ConvertCase
+    out.extend_from_slice(&2i32.to_le_bytes());
+    out.extend_from_slice(&[0; 88]); // m_CharacterRects
+    out.extend_from_slice(&1i32.to_le_bytes());
+    out.extend_from_slice(&[0; 8]); // m_KerningValues
+    out.extend_from_slice(&1f32.to_le_bytes()); // m_PixelScale
+    write_string(&mut out, font_data);
+    out.extend_from_slice(&1i32.to_le_bytes());
+    write_string(&mut out, b"Roboto");
+    out
+}
+
+#[test]
+fn finds_font_objects_in_bundles() {
+    for name in [
+        "banner_1",
+        "char_118_yuki.ab",
+        "atlas_test",
+        "xinzexi_2_n_tex",
+    ] {
+        let file = load(name);
+        assert!(
+            file.objects.iter().all(|o| o.type_id != class_ids::FONT),
+            "{}",
+            name
+        );
+        assert!(fonts(&file).is_empty(), "{}", name);
+    }
+
+    let mut file = load("banner_1");
+    file.types.push(SerializedType::new(class_ids::FONT));
+    let data = font_bytes(TTF);
+    let mut info = ObjectInfo::new(
+        FONT,
+        0,
+        data.len() as u32,
+        class_ids::FONT,
+        file.types.len() as i32 - 1,
+    );
+    info.data = data;
+    file.objects.push(info);
+
+    let found = fonts(&file);
+    assert_eq!(found.len(), 1);
+    let (path_id, font) = &found[0];
+    assert_eq!(*path_id, FONT);
+    assert_eq!(font.name, "Roboto-Regular");
+    assert_eq!(font.font_size, 16.0);
+    assert_eq!(font.line_spacing, 18.5);
+    assert_eq!((font.character_spacing, font.character_padding), (1, 2));
+    assert_eq!(font.ascii_start_offset, 32);
+    assert_eq!(font.character_count, 2);
+    assert_eq!(font.font_names, ["Roboto"]);
+    assert_eq!(font.font_data, TTF);
+    assert_eq!(font.format(), Some(FontFormat::TrueType));
+}
+
+#[test]
+fn truncated_raw_layout_is_an_error() {
+    let data = font_bytes(TTF);
+    let version = UnityVersion::parse_version("2018.4.4f1").unwrap();
+    assert!(Font::from_binary_data(&data[..data.len() - 40], ByteOrder::Little, &version).is_err());
+}
+
+#[test]
+fn typetree_fields_are_read() {
+    let properties: IndexMap<String, UnityValue> = [
+        ("m_Name", UnityValue::String("NotoSans".to_string())),
+        ("m_LineSpacing", UnityValue::Float(12.0)),
+        ("m_FontSize", UnityValue::Float(10.0)),
+        ("m_CharacterSpacing", UnityValue::Integer(0)),
+        ("m_CharacterPadding", UnityValue::Integer(1)),
+        ("m_CharacterRects", UnityValue::Array(Vec::new())),
+        ("m_FontData", UnityValue::Bytes(b"OTTO\x00\x09".to_vec())),
+        (
+            "m_FontNames",
+            UnityValue::Array(vec![UnityValue::String("Noto Sans".to_string())]),
+        ),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v))
+    .collect();
+
+    let font = Font::from_typetree(&properties).unwrap();
+    assert_eq!(font.name, "NotoSans");
+    assert_eq!((font.font_size, font.line_spacing), (10.0, 12.0));
+    assert_eq!(font.character_padding, 1);
+    assert_eq!(font.font_names, ["Noto Sans"]);
+    assert_eq!(font.format(), Some(FontFormat::OpenType));
+}
+
+#[test]
+fn export_picks_extension_from_magic() {
+    let dir = tempfile::tempdir().unwrap();
+    let font = |font_data: &[u8]| Font {
+        name: "font".to_string(),
+        font_data: font_data.to_vec(),
+        ..Default::default()
+    };
+
+    for (data, extension) in [
+        (TTF, "ttf"),
+        (b"true\x00\x01".as_slice(), "ttf"),
+        (b"OTTO\x00\x09".as_slice(), "otf"),
+        (b"ttcf\x00\x02".as_slice(), "ttc"),
+        // Unrecognised headers are still written, as TrueType.
+        (b"wOFF\x00\x01".as_slice(), "ttf"),
+    ] {
+        let path =
+            FontExporter::export(&font(data), dir.path().join(extension).join("font.bin")).unwrap();
+        assert_eq!(path.extension().unwrap(), extension);
+        assert_eq!(std::fs::read(&path).unwrap(), data);
+    }
+
+    assert!(FontExporter::export(&font(&[]), dir.path().join("empty")).is_err());
+}
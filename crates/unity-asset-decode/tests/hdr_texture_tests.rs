@@ -0,0 +1,187 @@
+#![cfg(feature = "texture")]
+
+use unity_asset_decode::texture::{
+    HdrImage, Texture2D, TextureExporter, TextureFormat, decode_hdr, is_hdr_format,
+};
+
+fn texture(format: TextureFormat, width: i32, height: i32, image_data: Vec<u8>) -> Texture2D {
+    Texture2D {
+        name: "Lightmap-0_comp_light".to_string(),
+        width,
+        height,
+        format,
+        data_size: image_data.len() as i32,
+        image_data,
+        ..Default::default()
+    }
+}
+
+/// IEEE half bits for values that are exact in half precision.
+fn half(value: f32) -> [u8; 2] {
+    let bits = value.to_bits();
+    let sign = (bits >> 16) & 0x8000;
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127 + 15;
+    let half = if value == 0.0 {
+        sign
+    } else {
+        sign | ((exponent as u32) << 10) | ((bits >> 13) & 0x3ff)
+    };
+    (half as u16).to_le_bytes()
+}
+
+const LIGHTMAP: [[f32; 4]; 4] = [
+    [4.0, 2.5, 1.0, 1.0],
+    [0.5, 0.25, 0.125, 1.0],
+    [16.0, 8.0, 0.0, 1.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+fn rgba_half_lightmap() -> Texture2D {
+    let data = LIGHTMAP.iter().flatten().flat_map(|&v| half(v)).collect();
+    texture(TextureFormat::RGBAHalf, 2, 2, data)
+}
+
+#[test]
+fn rgba_half_decodes_values_above_one() {
+    assert!(is_hdr_format(TextureFormat::RGBAHalf));
+    assert!(!is_hdr_format(TextureFormat::RGBA32));
+
+    let image = decode_hdr(&rgba_half_lightmap()).unwrap();
+    assert_eq!((image.width, image.height), (2, 2));
+    assert_eq!(image.pixels, LIGHTMAP.to_vec());
+    assert_eq!(image.max_value(), 16.0);
+    assert_eq!(image.to_rgba8().get_pixel(0, 0).0, [255, 255, 255, 255]);
+}
+
+#[test]
+fn float_and_shared_exponent_formats_decode() {
+    let data: Vec<u8> = [3.5f32, 0.25]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    let image = decode_hdr(&texture(TextureFormat::RGFloat, 1, 1, data)).unwrap();
+    assert_eq!(image.pixels[0], [3.5, 0.25, 0.0, 1.0]);
+
+    // RGB9e5: mantissas 256, 128, 0 with exponent 16 -> 2^(16 - 24) * m
+    let packed: u32 = 256 | (128 << 9) | (16 << 27);
+    let image = decode_hdr(&texture(
+        TextureFormat::RGB9e5Float,
+        1,
+        1,
+        packed.to_le_bytes().to_vec(),
+    ))
+    .unwrap();
+    assert_eq!(image.pixels[0], [1.0, 0.5, 0.0, 1.0]);
+}
+
+#[test]
+fn rgbm_lightmap_is_expanded() {
+    let mut rgbm = texture(TextureFormat::RGBA32, 1, 1, vec![255, 128, 0, 102]);
+    rgbm.light_map_format = 9; // BakedLightmapRGBM
+    let image = decode_hdr(&rgbm).unwrap();
+    // alpha 102/255 * 5 = 2.0
+    assert!((image.pixels[0][0] - 2.0).abs() < 1e-5);
+    assert!((image.pixels[0][1] - 128.0 / 255.0 * 2.0).abs() < 1e-5);
+    assert_eq!(image.pixels[0][3], 1.0);
+
+    let mut dldr = texture(TextureFormat::RGBA32, 1, 1, vec![255, 0, 0, 255]);
+    dldr.light_map_format = 8; // BakedLightmapDoubleLDR
+    assert_eq!(decode_hdr(&dldr).unwrap().pixels[0], [2.0, 0.0, 0.0, 1.0]);
+}
+
+struct BitWriter {
+    block: [u8; 16],
+    position: usize,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, count: usize) {
+        for i in 0..count {
+            if value >> i & 1 == 1 {
+                let bit = self.position + i;
+                self.block[bit / 8] |= 1 << (bit % 8);
+            }
+        }
+        self.position += count;
+    }
+}
+
+/// BC6H mode 3 (one region, 10-bit endpoints stored as-is).
+fn bc6h_mode3_block(e0: [u32; 3], e1: [u32; 3], indices: [u32; 16]) -> [u8; 16] {
+    let mut bits = BitWriter {
+        block: [0; 16],
+        position: 0,
+    };
+    bits.write(0b00011, 5);
+    for endpoint in [e0, e1] {
+        for channel in endpoint {
+            bits.write(channel, 10);
+        }
+    }
+    for (i, index) in indices.into_iter().enumerate() {
+        bits.write(index, if i == 0 { 3 } else { 4 });
+    }
+    assert_eq!(bits.position, 128);
+    bits.block
+}
+
+#[test]
+fn bc6h_keeps_hdr_range() {
+    let mut indices = [0; 16];
+    indices[1] = 15;
+    indices[2] = 7;
+    let block = bc6h_mode3_block([562, 281, 0], [0, 0, 0], indices);
+    let image = decode_hdr(&texture(TextureFormat::BC6H, 4, 4, block.to_vec())).unwrap();
+
+    // Endpoint 562 unquantizes to 36000, i.e. half 0x441D.
+    let expected = 4.0 * (1.0 + 29.0 / 1024.0);
+    assert_eq!(image.pixels[0][0], expected);
+    // Interpolation happens on half bit patterns: 281 is 0x2216, not half of 0x441D.
+    assert_eq!(image.pixels[0][1], (1.0 + 534.0 / 1024.0) / 128.0);
+    assert_eq!(image.pixels[0][2], 0.0);
+    assert_eq!(image.pixels[1], [0.0, 0.0, 0.0, 1.0]);
+    // Index 7 has weight 30/64 towards the zero endpoint: half 0x242F.
+    assert_eq!(image.pixels[2][0], (1.0 + 47.0 / 1024.0) / 64.0);
+    assert!(image.pixels[3..].iter().all(|p| p[0] == expected));
+}
+
+#[test]
+fn bc6h_crops_partial_blocks() {
+    let block = bc6h_mode3_block([562, 0, 0], [0, 0, 0], [0; 16]);
+    let image = decode_hdr(&texture(TextureFormat::BC6H, 2, 3, block.to_vec())).unwrap();
+    assert_eq!(image.pixels.len(), 6);
+    assert!(image.pixels.iter().all(|p| p[0] > 4.0));
+}
+
+#[test]
+fn exr_export_preserves_values_above_one() {
+    let image = decode_hdr(&rgba_half_lightmap()).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Lightmap-0_comp_light.exr");
+    TextureExporter::export_exr(&image, &path).unwrap();
+
+    let read = image::open(&path).unwrap().into_rgba32f();
+    assert_eq!(read.dimensions(), (2, 2));
+    for (i, expected) in LIGHTMAP.iter().enumerate() {
+        let pixel = read.get_pixel(i as u32 % 2, i as u32 / 2).0;
+        assert_eq!(&pixel, expected, "pixel {i}");
+    }
+}
+
+#[test]
+fn exr_export_rounds_to_half_precision() {
+    let image = HdrImage {
+        width: 1,
+        height: 1,
+        pixels: vec![[1.0 / 3.0, 70000.0, -2.5, 0.0]],
+    };
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("rounded.exr");
+    TextureExporter::export_exr(&image, &path).unwrap();
+
+    let pixel = image::open(&path).unwrap().into_rgba32f().get_pixel(0, 0).0;
+    assert!((pixel[0] - 1.0 / 3.0).abs() < 1e-3);
+    assert!(pixel[1].is_infinite());
+    assert_eq!(pixel[2], -2.5);
+    assert_eq!(pixel[3], 0.0);
+}
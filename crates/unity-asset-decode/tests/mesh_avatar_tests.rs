@@ -0,0 +1,85 @@
+//! Labelling skinned mesh joints through an Avatar's path table and human mapping.
+
+#![cfg(feature = "mesh")]
+
+use indexmap::IndexMap;
+use unity_asset_binary::avatar::{Avatar, HumanBone};
+use unity_asset_core::UnityValue;
+use unity_asset_decode::mesh::Mesh;
+
+const PATHS: [&str; 3] = ["Armature/Hips", "Armature/Hips/Spine", "Armature/Hips/Tail"];
+
+fn object(fields: Vec<(&str, UnityValue)>) -> UnityValue {
+    UnityValue::Object(
+        fields
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
+}
+
+/// Stand-in for the CRC32 Unity stores; only consistency between mesh and avatar matters.
+fn hash(path: &str) -> u32 {
+    PATHS.iter().position(|p| *p == path).unwrap() as u32 + 1000
+}
+
+/// Avatar whose human skeleton maps Hips and Spine; Tail is a generic bone.
+fn avatar() -> Avatar {
+    let ids = |paths: &[&str]| {
+        UnityValue::Array(
+            paths
+                .iter()
+                .map(|p| UnityValue::Integer(hash(p) as i64))
+                .collect(),
+        )
+    };
+    let mut body = vec![UnityValue::Integer(-1); 25];
+    body[0] = UnityValue::Integer(0);
+    body[7] = UnityValue::Integer(1);
+
+    let mut properties = IndexMap::new();
+    properties.insert(
+        "m_TOS".to_string(),
+        UnityValue::Array(
+            PATHS
+                .iter()
+                .map(|p| {
+                    UnityValue::Array(vec![
+                        UnityValue::Integer(hash(p) as i64),
+                        UnityValue::String(p.to_string()),
+                    ])
+                })
+                .collect(),
+        ),
+    );
+    properties.insert(
+        "m_Avatar".to_string(),
+        object(vec![(
+            "m_Human",
+            object(vec![
+                ("m_Skeleton", object(vec![("m_ID", ids(&PATHS[..2]))])),
+                ("m_HumanBoneIndex", UnityValue::Array(body)),
+            ]),
+        )]),
+    );
+    Avatar::from_typetree(&properties).unwrap()
+}
+
+#[test]
+fn mesh_joints_are_labelled_with_human_bones() {
+    let avatar = avatar();
+    let mesh = Mesh {
+        bone_name_hashes: vec![hash(PATHS[1]), hash(PATHS[2]), hash(PATHS[0]), 42],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        mesh.bone_paths(&avatar),
+        vec![Some(PATHS[1]), Some(PATHS[2]), Some(PATHS[0]), None]
+    );
+    assert_eq!(
+        mesh.human_bones(&avatar),
+        vec![Some(HumanBone::Spine), None, Some(HumanBone::Hips), None]
+    );
+    assert_eq!(mesh.joint_names(&avatar), ["Spine", "Tail", "Hips", "42"]);
+}
@@ -0,0 +1,267 @@
+//! Welding, normal and tangent recomputation on a synthetic unit cube.
+//!
+//! The cube has four vertices per face (24 in total), UVs running along each face's tangent and
+//! bitangent axes, and its faces split over two submeshes.
+
+#![cfg(feature = "mesh-export")]
+
+use unity_asset_decode::mesh::{
+    BlendShape, BlendShapeData, BlendShapeVertex, ChannelInfo, Mesh, MeshCleanup, MeshConfig,
+    MeshProcessor, SubMesh,
+};
+use unity_asset_decode::unity_version::UnityVersion;
+
+/// (normal, tangent, bitangent) per face, with `tangent x bitangent = normal`.
+const FACES: [[[f32; 3]; 3]; 6] = [
+    [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+    [[-1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]],
+    [[0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [1.0, 0.0, 0.0]],
+    [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]],
+    [[0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+    [[0.0, 0.0, -1.0], [0.0, 1.0, 0.0], [1.0, 0.0, 0.0]],
+];
+
+fn version() -> UnityVersion {
+    UnityVersion::parse_version("2020.3.33f1").unwrap()
+}
+
+/// Split cube; normals are stored (and zeroed) only when `normals` is set.
+fn cube(normals: bool) -> Mesh {
+    let mut mesh = Mesh {
+        name: "Cube".to_string(),
+        ..Default::default()
+    };
+    let channel = |offset, dimension| ChannelInfo {
+        stream: 0,
+        offset,
+        format: 0,
+        dimension,
+    };
+    let uv_offset = if normals { 24 } else { 12 };
+    mesh.vertex_data.channels = (0..14).map(|_| channel(0, 0)).collect();
+    mesh.vertex_data.channels[0] = channel(0, 3);
+    if normals {
+        mesh.vertex_data.channels[1] = channel(12, 3);
+    }
+    mesh.vertex_data.channels[4] = channel(uv_offset, 2);
+    mesh.vertex_data.vertex_count = 24;
+
+    let mut indices: Vec<u16> = Vec::new();
+    for (face, [n, t, b]) in FACES.iter().enumerate() {
+        for [s, r] in [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]] {
+            let position: Vec<f32> = (0..3).map(|a| 0.5 * (n[a] + s * t[a] + r * b[a])).collect();
+            let mut values = position;
+            if normals {
+                values.extend([0.0; 3]);
+            }
+            values.extend([(s + 1.0) / 2.0, (r + 1.0) / 2.0]);
+            for value in values {
+                mesh.vertex_data
+                    .data_size
+                    .extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        let base = face as u16 * 4;
+        indices.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+    for index in indices {
+        mesh.index_buffer.extend_from_slice(&index.to_le_bytes());
+    }
+    mesh.sub_meshes = vec![
+        SubMesh {
+            first_byte: 0,
+            index_count: 18,
+            triangle_count: 6,
+            vertex_count: 12,
+            ..Default::default()
+        },
+        SubMesh {
+            first_byte: 36,
+            index_count: 18,
+            triangle_count: 6,
+            first_vertex: 12,
+            vertex_count: 12,
+            ..Default::default()
+        },
+    ];
+    mesh
+}
+
+fn positions(mesh: &Mesh) -> Vec<[f32; 3]> {
+    mesh.read_channel(&version(), 0)
+        .unwrap()
+        .into_iter()
+        .map(|p| [p[0], p[1], p[2]])
+        .collect()
+}
+
+fn normals(mesh: &Mesh) -> Vec<[f32; 3]> {
+    mesh.read_channel(&version(), 1)
+        .unwrap()
+        .into_iter()
+        .map(|n| [n[0], n[1], n[2]])
+        .collect()
+}
+
+fn close(a: &[f32], b: &[f32]) -> bool {
+    a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-5)
+}
+
+#[test]
+fn weld_merges_split_cube_corners() {
+    let v = version();
+    let mut mesh = cube(true);
+    let original = positions(&mesh);
+    // Shape 0 moves vertex 0 (face +X) and vertex 9 (face +Y); shape 1 moves vertex 0 and vertex
+    // 13, the same corner on face -Y.
+    let delta = |index, x| BlendShapeVertex {
+        vertex: [x, 0.0, 0.0],
+        index,
+        ..Default::default()
+    };
+    mesh.blend_shape_data = Some(BlendShapeData {
+        vertices: vec![delta(0, 1.0), delta(9, 2.0), delta(0, 3.0), delta(13, 4.0)],
+        shapes: vec![
+            BlendShape {
+                first_vertex: 0,
+                vertex_count: 2,
+                ..Default::default()
+            },
+            BlendShape {
+                first_vertex: 2,
+                vertex_count: 2,
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    });
+
+    // With UVs compared, only corners where two faces happen to share a UV weld.
+    let mut seams = mesh.clone();
+    let removed = seams.weld_vertices(&v, 1e-4, true).unwrap();
+    assert!(removed > 0 && removed < 16, "{removed}");
+    let uvs = seams.read_uvs(&v, 0).unwrap();
+    let corners = positions(&seams);
+    for a in 0..corners.len() {
+        for b in a + 1..corners.len() {
+            assert!(!(close(&corners[a], &corners[b]) && close(&uvs[a], &uvs[b])));
+        }
+    }
+
+    assert_eq!(mesh.weld_vertices(&v, 1e-4, false).unwrap(), 16);
+    assert_eq!(mesh.vertex_count(), 8);
+    let welded = positions(&mesh);
+    assert_eq!(welded.len(), 8);
+    for corner in &original {
+        assert_eq!(welded.iter().filter(|p| close(*p, corner)).count(), 1);
+    }
+
+    // Submeshes keep their triangles and point at the same corners.
+    let triangles = mesh.submesh_triangles().unwrap();
+    assert_eq!(triangles.iter().map(Vec::len).collect::<Vec<_>>(), [6, 6]);
+    for (submesh, tris) in triangles.iter().enumerate() {
+        let sub_mesh = &mesh.sub_meshes[submesh];
+        for (i, tri) in tris.iter().enumerate() {
+            for (k, &index) in tri.iter().enumerate() {
+                let old = [0, 1, 2, 0, 2, 3][(i % 2) * 3 + k] + (submesh * 3 + i / 2) * 4;
+                assert!(close(&welded[index as usize], &original[old]));
+                assert!(index >= sub_mesh.first_vertex);
+                assert!(index < sub_mesh.first_vertex + sub_mesh.vertex_count);
+            }
+        }
+    }
+
+    let blend = mesh.blend_shape_data.as_ref().unwrap();
+    assert_eq!(blend.shapes[0].vertex_count, 2);
+    assert!(blend.vertices[0].index < blend.vertices[1].index);
+    // Welded deltas of one shape keep the first.
+    assert_eq!(
+        (blend.shapes[1].first_vertex, blend.shapes[1].vertex_count),
+        (2, 1)
+    );
+    assert_eq!(blend.vertices.len(), 3);
+    for (old, x) in [(0, 1.0), (9, 2.0), (0, 3.0)] {
+        let delta = blend.vertices.iter().find(|d| d.vertex[0] == x).unwrap();
+        assert!(close(&welded[delta.index as usize], &original[old]));
+    }
+}
+
+#[test]
+fn normals_split_welded_cube_at_sharp_edges() {
+    let v = version();
+    let mut mesh = cube(true);
+    mesh.weld_vertices(&v, 1e-4, false).unwrap();
+
+    // 180 degrees smooths every corner: 8 vertices pointing away from the centre.
+    let mut smooth = mesh.clone();
+    smooth.recompute_normals(&v, 180.0).unwrap();
+    assert_eq!(smooth.vertex_count(), 8);
+    for (p, n) in positions(&smooth).iter().zip(normals(&smooth)) {
+        assert!(close(&[n.iter().map(|c| c * c).sum::<f32>()], &[1.0]));
+        for axis in 0..3 {
+            assert_eq!(p[axis].signum(), n[axis].signum(), "{p:?} {n:?}");
+        }
+    }
+
+    // 60 degrees keeps cube edges hard: each corner splits into one vertex per face again.
+    mesh.recompute_normals(&v, 60.0).unwrap();
+    assert_eq!(mesh.vertex_count(), 24);
+    let normals = normals(&mesh);
+    let triangles = mesh.submesh_triangles().unwrap();
+    for (t, tri) in triangles.iter().flatten().enumerate() {
+        let face = FACES[t / 2][0];
+        for &index in tri {
+            assert!(close(&normals[index as usize], &face), "{t}: {tri:?}");
+        }
+    }
+}
+
+#[test]
+fn missing_normals_and_tangents_are_added() {
+    let v = version();
+    let mut mesh = cube(false);
+    assert!(mesh.recompute_tangents(&v).is_err());
+
+    mesh.recompute_normals(&v, 30.0).unwrap();
+    assert_eq!(mesh.vertex_count(), 24);
+    let normals = normals(&mesh);
+    for (vertex, normal) in normals.iter().enumerate() {
+        assert!(close(normal, &FACES[vertex / 4][0]));
+    }
+    // Re-laid out as float channels; positions and UVs read back unchanged.
+    assert_eq!(positions(&mesh), positions(&cube(false)));
+    assert_eq!(
+        mesh.read_uvs(&v, 0).unwrap(),
+        cube(false).read_uvs(&v, 0).unwrap()
+    );
+
+    mesh.recompute_tangents(&v).unwrap();
+    let tangents = mesh.read_channel(&v, 2).unwrap();
+    for (vertex, tangent) in tangents.iter().enumerate() {
+        let [_, t, _] = FACES[vertex / 4];
+        assert!(close(&tangent[..3], &t), "{vertex}: {tangent:?}");
+        assert_eq!(tangent[3], 1.0);
+    }
+}
+
+#[test]
+fn obj_export_runs_cleanup() {
+    let config = MeshConfig::default().with_cleanup(
+        MeshCleanup::default()
+            .with_weld(1e-4, false)
+            .with_recompute_normals(180.0),
+    );
+    let processor = MeshProcessor::with_config(version(), config);
+    let obj = processor.export_to_obj(&cube(true)).unwrap();
+    let count = |prefix: &str| obj.lines().filter(|l| l.starts_with(prefix)).count();
+    assert_eq!(count("v "), 8);
+    assert_eq!(count("vn "), 8);
+    assert_eq!(count("vt "), 8);
+    assert_eq!(count("f "), 12);
+    assert_eq!(count("g "), 2);
+
+    let plain = MeshProcessor::new(version())
+        .export_to_obj(&cube(true))
+        .unwrap();
+    assert_eq!(plain.lines().filter(|l| l.starts_with("v ")).count(), 24);
+}
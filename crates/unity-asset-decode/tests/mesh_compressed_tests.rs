@@ -0,0 +1,303 @@
+//! Meshes saved with mesh compression: bit-packed `m_CompressedMesh` vectors unpacked into
+//! vertex data and an index buffer.
+//!
+//! The fixture is a quad built the way Unity writes it, with its values quantized by
+//! [`pack_floats`] and [`pack_ints`].
+
+#![cfg(feature = "mesh")]
+
+use indexmap::IndexMap;
+use unity_asset_core::UnityValue;
+use unity_asset_decode::mesh::{self, MeshParser, PackedFloatVector, PackedIntVector};
+use unity_asset_decode::unity_version::UnityVersion;
+
+fn object(fields: Vec<(&str, UnityValue)>) -> UnityValue {
+    UnityValue::Object(
+        fields
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
+}
+
+fn int(v: i64) -> UnityValue {
+    UnityValue::Integer(v)
+}
+
+fn bits(values: &[u32], bit_size: u32) -> Vec<u8> {
+    let mut data = vec![0u8; (values.len() * bit_size as usize).div_ceil(8)];
+    for (i, &value) in values.iter().enumerate() {
+        for bit in 0..bit_size {
+            let position = i * bit_size as usize + bit as usize;
+            data[position / 8] |= (((value >> bit) & 1) as u8) << (position % 8);
+        }
+    }
+    data
+}
+
+/// `m_Data` as a TypeTree byte array
+fn bytes(data: Vec<u8>) -> UnityValue {
+    UnityValue::Array(data.into_iter().map(|b| int(b as i64)).collect())
+}
+
+fn pack_ints(values: &[u32], bit_size: u32) -> UnityValue {
+    object(vec![
+        ("m_NumItems", int(values.len() as i64)),
+        ("m_Data", bytes(bits(values, bit_size))),
+        ("m_BitSize", int(bit_size as i64)),
+    ])
+}
+
+/// Quantize `values` to 16 bits over their own range.
+fn pack_floats(values: &[f32]) -> UnityValue {
+    let start = values.iter().copied().fold(f32::MAX, f32::min);
+    let end = values.iter().copied().fold(f32::MIN, f32::max);
+    let range = (end - start).max(f32::EPSILON);
+    let quantized: Vec<u32> = values
+        .iter()
+        .map(|v| ((v - start) / range * 65535.0).round() as u32)
+        .collect();
+    object(vec![
+        ("m_NumItems", int(values.len() as i64)),
+        ("m_Range", UnityValue::Float(range as f64)),
+        ("m_Start", UnityValue::Float(start as f64)),
+        ("m_Data", bytes(bits(&quantized, 16))),
+        ("m_BitSize", int(16)),
+    ])
+}
+
+fn empty_floats() -> UnityValue {
+    object(vec![
+        ("m_NumItems", int(0)),
+        ("m_Range", UnityValue::Float(0.0)),
+        ("m_Start", UnityValue::Float(0.0)),
+        ("m_Data", bytes(Vec::new())),
+        ("m_BitSize", int(0)),
+    ])
+}
+
+fn mesh_properties(compressed: UnityValue) -> IndexMap<String, UnityValue> {
+    let UnityValue::Object(properties) = object(vec![
+        ("m_Name", UnityValue::String("Quad".to_string())),
+        (
+            "m_SubMeshes",
+            UnityValue::Array(vec![object(vec![
+                ("firstByte", int(0)),
+                ("indexCount", int(6)),
+                ("topology", int(0)),
+                ("triangleCount", int(2)),
+                ("vertexCount", int(4)),
+            ])]),
+        ),
+        ("m_MeshCompression", int(3)),
+        ("m_IndexBuffer", UnityValue::Array(Vec::new())),
+        (
+            "m_VertexData",
+            object(vec![
+                ("m_VertexCount", int(0)),
+                ("m_Channels", UnityValue::Array(Vec::new())),
+                ("m_DataSize", UnityValue::Array(Vec::new())),
+            ]),
+        ),
+        ("m_CompressedMesh", compressed),
+    ]) else {
+        unreachable!()
+    };
+    properties
+}
+
+const POSITIONS: [f32; 12] = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+const TRIANGLES: [u32; 6] = [0, 1, 2, 0, 2, 3];
+
+fn assert_close(actual: &[Vec<f32>], expected: &[&[f32]]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert_eq!(a.len(), e.len(), "{:?} vs {:?}", a, e);
+        for (x, y) in a.iter().zip(*e) {
+            assert!((x - y).abs() < 1e-3, "{:?} vs {:?}", a, e);
+        }
+    }
+}
+
+#[test]
+fn packed_vectors_unpack_lsb_first() {
+    let ints = PackedIntVector {
+        num_items: 3,
+        data: vec![0xE1, 0x1F],
+        bit_size: 5,
+    };
+    assert_eq!(ints.unpack(), [1, 31, 7]);
+    // A fourth item would need bits past the end of the data.
+    let truncated = PackedIntVector {
+        num_items: 4,
+        ..ints
+    };
+    assert_eq!(truncated.unpack(), [1, 31, 7]);
+
+    let floats = PackedFloatVector {
+        num_items: 3,
+        range: 3.0,
+        start: -1.0,
+        data: bits(&[0, 3, 1], 2),
+        bit_size: 2,
+    };
+    assert_eq!(floats.unpack(), [-1.0, 2.0, 0.0]);
+    let constant = PackedFloatVector {
+        num_items: 2,
+        start: 0.5,
+        ..Default::default()
+    };
+    assert_eq!(constant.unpack(), [0.5, 0.5]);
+}
+
+#[test]
+fn compressed_mesh_2019_fills_vertex_data() {
+    let version = UnityVersion::parse_version("2019.4.40f1").unwrap();
+    let uv0: Vec<f32> = POSITIONS.chunks(3).flat_map(|p| [p[0], p[1]]).collect();
+    let uv1: Vec<f32> = uv0.iter().map(|v| v * 0.5 + 0.25).collect();
+    let uvs: Vec<f32> = uv0.iter().chain(&uv1).copied().collect();
+    let compressed = object(vec![
+        ("m_Vertices", pack_floats(&POSITIONS)),
+        ("m_UV", pack_floats(&uvs)),
+        // Vertex 0 leans to +X with +Z; the others face -Z.
+        (
+            "m_Normals",
+            pack_floats(&[0.6, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]),
+        ),
+        ("m_NormalSigns", pack_ints(&[1, 0, 0, 0], 1)),
+        (
+            "m_Tangents",
+            pack_floats(&[1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0]),
+        ),
+        ("m_TangentSigns", pack_ints(&[1, 1, 1, 0, 1, 1, 1, 1], 1)),
+        (
+            "m_FloatColors",
+            pack_floats(&[1.0, 0.0, 0.0, 1.0].repeat(4)),
+        ),
+        // 31 | 15 + 16 | 10 + 10 + 5 (+ implied 6) | 31
+        ("m_Weights", pack_ints(&[31, 15, 16, 10, 10, 5, 31], 5)),
+        ("m_BoneIndices", pack_ints(&[1, 0, 1, 0, 1, 2, 3, 2], 3)),
+        ("m_Triangles", pack_ints(&TRIANGLES, 2)),
+        ("m_UVInfo", int(0x55)),
+    ]);
+    let mesh = MeshParser::new(version.clone())
+        .parse_from_typetree(&mesh_properties(compressed))
+        .unwrap();
+
+    assert!(mesh.is_compressed());
+    assert_eq!(mesh::get_vertex_count(&mesh), 4);
+    assert_close(
+        &mesh.read_channel(&version, 0).unwrap(),
+        &[
+            &[0.0, 0.0, 0.0],
+            &[1.0, 0.0, 0.0],
+            &[1.0, 1.0, 0.0],
+            &[0.0, 1.0, 0.0],
+        ],
+    );
+    assert_close(
+        &mesh.read_channel(&version, 1).unwrap(),
+        &[
+            &[0.6, 0.0, 0.8],
+            &[0.0, 0.0, -1.0],
+            &[0.0, 0.0, -1.0],
+            &[0.0, 0.0, -1.0],
+        ],
+    );
+    assert_close(
+        &mesh.read_channel(&version, 2).unwrap(),
+        &[
+            &[1.0, 0.0, 0.0, 1.0],
+            &[1.0, 0.0, 0.0, -1.0],
+            &[1.0, 0.0, 0.0, 1.0],
+            &[0.0, 1.0, 0.0, 1.0],
+        ],
+    );
+    assert_close(
+        &mesh.read_channel(&version, 3).unwrap()[..1],
+        &[&[1.0, 0.0, 0.0, 1.0]],
+    );
+    let uv = mesh.read_uvs(&version, 1).unwrap();
+    assert!((uv[2][0] - 0.75).abs() < 1e-3 && (uv[2][1] - 0.75).abs() < 1e-3);
+    assert_close(
+        &mesh.read_channel(&version, 12).unwrap(),
+        &[
+            &[1.0, 0.0, 0.0, 0.0],
+            &[15.0 / 31.0, 16.0 / 31.0, 0.0, 0.0],
+            &[10.0 / 31.0, 10.0 / 31.0, 5.0 / 31.0, 6.0 / 31.0],
+            &[1.0, 0.0, 0.0, 0.0],
+        ],
+    );
+    assert_close(
+        &mesh.read_channel(&version, 13).unwrap(),
+        &[
+            &[1.0, 0.0, 0.0, 0.0],
+            &[0.0, 1.0, 0.0, 0.0],
+            &[0.0, 1.0, 2.0, 3.0],
+            &[2.0, 0.0, 0.0, 0.0],
+        ],
+    );
+    assert_eq!(
+        mesh.submesh_triangles().unwrap(),
+        [vec![[0, 1, 2], [0, 2, 3]]]
+    );
+
+    // Left packed when decompression is off.
+    let packed = MeshParser::new(version)
+        .with_decompress(false)
+        .parse_from_typetree(&mesh_properties(object(vec![
+            ("m_Vertices", pack_floats(&POSITIONS)),
+            ("m_Triangles", pack_ints(&TRIANGLES, 2)),
+        ])))
+        .unwrap();
+    assert!(packed.is_compressed());
+    assert_eq!(mesh::get_vertex_count(&packed), 0);
+    assert!(packed.index_buffer.is_empty());
+}
+
+#[test]
+fn compressed_mesh_4x_layout() {
+    // Position, normal, color, UV0, UV1, tangent; colors are packed 32-bit RGBA.
+    let version = UnityVersion::parse_version("4.7.2f1").unwrap();
+    let uvs: Vec<f32> = (0..16).map(|i| i as f32 / 16.0).collect();
+    let rgba = |r: u32, a: u32| r | (a << 24);
+    let compressed = object(vec![
+        ("m_Vertices", pack_floats(&POSITIONS)),
+        ("m_UV", pack_floats(&uvs)),
+        (
+            "m_Colors",
+            pack_ints(&[rgba(255, 255), 0, 0, rgba(51, 0)], 32),
+        ),
+        ("m_Triangles", pack_ints(&TRIANGLES, 3)),
+        ("m_UVInfo", int(0)),
+    ]);
+    let mesh = MeshParser::new(version.clone())
+        .parse_from_typetree(&mesh_properties(compressed))
+        .unwrap();
+    assert_eq!(mesh.vertex_count(), 4);
+    assert_close(
+        &mesh.read_channel(&version, 2).unwrap(),
+        &[
+            &[1.0, 0.0, 0.0, 1.0],
+            &[0.0; 4],
+            &[0.0; 4],
+            &[0.2, 0.0, 0.0, 0.0],
+        ],
+    );
+    let uv1 = mesh.read_uvs(&version, 1).unwrap();
+    assert!((uv1[0][0] - 0.5).abs() < 1e-3);
+    assert_eq!(mesh.submesh_triangles().unwrap()[0].len(), 2);
+
+    // Too few normals for the vertex count.
+    let broken = object(vec![
+        ("m_Vertices", pack_floats(&POSITIONS)),
+        ("m_Normals", pack_floats(&[0.0, 0.0])),
+        ("m_NormalSigns", pack_ints(&[1], 1)),
+        ("m_UV", empty_floats()),
+    ]);
+    assert!(
+        MeshParser::new(version)
+            .parse_from_typetree(&mesh_properties(broken))
+            .is_err()
+    );
+}
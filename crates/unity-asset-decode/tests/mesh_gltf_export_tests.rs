@@ -0,0 +1,296 @@
+//! Standalone mesh export to GLB on a synthetic skinned quad with colors, two UV sets, two
+//! submeshes and a blend shape.
+
+#![cfg(feature = "mesh-export")]
+
+use serde_json::{Value, json};
+use unity_asset_decode::mesh::{
+    BlendShape, BlendShapeChannel, BlendShapeData, BlendShapeVertex, ChannelInfo, Mesh, MeshConfig,
+    MeshProcessor, SubMesh,
+};
+use unity_asset_decode::unity_version::UnityVersion;
+
+const POSITIONS: [[f32; 3]; 4] = [
+    [0.0, 0.0, 0.0],
+    [1.0, 0.0, 0.0],
+    [1.0, 1.0, 0.0],
+    [0.0, 1.0, 0.0],
+];
+
+fn processor(config: MeshConfig) -> MeshProcessor {
+    MeshProcessor::with_config(UnityVersion::parse_version("2020.3.33f1").unwrap(), config)
+}
+
+/// Quad split over two submeshes; vertex `i` is red `i * 64`, bound to bone `i % 2`.
+///
+/// Per vertex: position, normal, tangent, color (UNorm8), UV0, UV1, weights, bone indices
+/// (UInt32), 92 bytes in all.
+fn quad() -> Mesh {
+    let mut mesh = Mesh {
+        name: "Quad".to_string(),
+        ..Default::default()
+    };
+    let channel = |offset, format, dimension| ChannelInfo {
+        stream: 0,
+        offset,
+        format,
+        dimension,
+    };
+    let channels = &mut mesh.vertex_data.channels;
+    *channels = (0..14).map(|_| channel(0, 0, 0)).collect();
+    channels[0] = channel(0, 0, 3);
+    channels[1] = channel(12, 0, 3);
+    channels[2] = channel(24, 0, 4);
+    channels[3] = channel(40, 2, 4);
+    channels[4] = channel(44, 0, 2);
+    channels[5] = channel(52, 0, 2);
+    channels[12] = channel(60, 0, 4);
+    channels[13] = channel(76, 10, 4);
+    mesh.vertex_data.vertex_count = 4;
+
+    let data = &mut mesh.vertex_data.data_size;
+    for (i, position) in POSITIONS.iter().enumerate() {
+        let uv = [position[0], position[1]];
+        let floats = position
+            .iter()
+            .chain(&[0.0, 0.0, -1.0])
+            .chain(&[1.0, 0.0, 0.0, 1.0]);
+        data.extend(floats.flat_map(|v| v.to_le_bytes()));
+        data.extend([i as u8 * 64, 0, 0, 255]);
+        let uvs = [uv[0], uv[1], uv[0] / 2.0, uv[1] / 2.0];
+        data.extend(uvs.iter().flat_map(|v| v.to_le_bytes()));
+        data.extend([1.0f32, 0.0, 0.0, 0.0].iter().flat_map(|v| v.to_le_bytes()));
+        data.extend([i as u32 % 2, 0, 0, 0].iter().flat_map(|v| v.to_le_bytes()));
+    }
+
+    for index in [0u16, 1, 2, 0, 2, 3] {
+        mesh.index_buffer.extend_from_slice(&index.to_le_bytes());
+    }
+    mesh.sub_meshes = (0..2)
+        .map(|i| SubMesh {
+            first_byte: i * 6,
+            index_count: 3,
+            triangle_count: 1,
+            vertex_count: 4,
+            ..Default::default()
+        })
+        .collect();
+
+    mesh.bind_pose = vec![
+        [
+            1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        ],
+        [
+            1.0, 0.0, 0.0, -1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        ],
+    ];
+
+    // "Smile" has a half frame moving vertex 0 and a full frame moving vertices 1 and 3.
+    let delta = |index, y| BlendShapeVertex {
+        vertex: [0.5, y, 0.0],
+        normal: [0.0, 0.25, 0.0],
+        tangent: [0.0; 3],
+        index,
+    };
+    let shape = |first_vertex, vertex_count| BlendShape {
+        first_vertex,
+        vertex_count,
+        has_normals: true,
+        has_tangents: false,
+    };
+    mesh.blend_shape_data = Some(BlendShapeData {
+        vertices: vec![delta(0, 0.1), delta(1, 0.2), delta(3, 0.3)],
+        shapes: vec![shape(0, 1), shape(1, 2)],
+        channels: vec![BlendShapeChannel {
+            name: "Smile".to_string(),
+            name_hash: 0,
+            frame_index: 0,
+            frame_count: 2,
+        }],
+        full_weights: vec![50.0, 100.0],
+    });
+    mesh
+}
+
+fn parse_glb(glb: &[u8]) -> (Value, Vec<u8>) {
+    let u32_at = |offset: usize| u32::from_le_bytes(glb[offset..offset + 4].try_into().unwrap());
+    assert_eq!(&glb[..4], b"glTF");
+    assert_eq!(u32_at(8) as usize, glb.len());
+    let json_len = u32_at(12) as usize;
+    let document = serde_json::from_slice(&glb[20..20 + json_len]).unwrap();
+    let bin_start = 20 + json_len;
+    assert_eq!(&glb[bin_start + 4..bin_start + 8], b"BIN\0");
+    let bin_len = u32_at(bin_start) as usize;
+    (
+        document,
+        glb[bin_start + 8..bin_start + 8 + bin_len].to_vec(),
+    )
+}
+
+/// Floats of accessor `index`
+fn read_floats(doc: &Value, bin: &[u8], index: &Value) -> Vec<f32> {
+    let accessor = &doc["accessors"][index.as_u64().unwrap() as usize];
+    let view = &doc["bufferViews"][accessor["bufferView"].as_u64().unwrap() as usize];
+    let start = view["byteOffset"].as_u64().unwrap() as usize;
+    let length = view["byteLength"].as_u64().unwrap() as usize;
+    bin[start..start + length]
+        .chunks_exact(4)
+        .map(|c| f32::from_le_bytes(c.try_into().unwrap()))
+        .collect()
+}
+
+#[test]
+fn accessors_and_buffer_views_cover_every_attribute() {
+    let glb = processor(MeshConfig::default())
+        .export_to_gltf(&quad())
+        .unwrap();
+    let (doc, bin) = parse_glb(&glb);
+    assert_eq!(doc["buffers"][0]["byteLength"], bin.len());
+
+    // Every accessor fills its view exactly, and every view lies inside the buffer.
+    for accessor in doc["accessors"].as_array().unwrap() {
+        let view = &doc["bufferViews"][accessor["bufferView"].as_u64().unwrap() as usize];
+        let components = match accessor["type"].as_str().unwrap() {
+            "SCALAR" => 1,
+            "VEC2" => 2,
+            "VEC3" => 3,
+            "VEC4" => 4,
+            "MAT4" => 16,
+            other => panic!("unexpected accessor type {}", other),
+        };
+        let size = match accessor["componentType"].as_u64().unwrap() {
+            5123 => 2,
+            _ => 4,
+        };
+        let count = accessor["count"].as_u64().unwrap();
+        assert_eq!(
+            view["byteLength"].as_u64().unwrap(),
+            count * components * size
+        );
+        let end = view["byteOffset"].as_u64().unwrap() + view["byteLength"].as_u64().unwrap();
+        assert!(end as usize <= bin.len());
+    }
+
+    let mesh = &doc["meshes"][0];
+    let primitives = mesh["primitives"].as_array().unwrap();
+    assert_eq!(primitives.len(), 2);
+    let attributes = &primitives[0]["attributes"];
+    let mut names: Vec<&str> = attributes
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        [
+            "COLOR_0",
+            "JOINTS_0",
+            "NORMAL",
+            "POSITION",
+            "TANGENT",
+            "TEXCOORD_0",
+            "TEXCOORD_1",
+            "WEIGHTS_0"
+        ]
+    );
+    for name in names {
+        let accessor = &doc["accessors"][attributes[name].as_u64().unwrap() as usize];
+        assert_eq!(accessor["count"], 4, "{}", name);
+    }
+    for primitive in primitives {
+        assert_eq!(primitive["attributes"], *attributes);
+        let indices = &doc["accessors"][primitive["indices"].as_u64().unwrap() as usize];
+        assert_eq!(indices["count"], 3);
+        assert!(primitive.get("material").is_none());
+    }
+    // 8 attributes, 2 morph target attributes, 2 index lists, 1 inverse bind matrix list.
+    assert_eq!(doc["accessors"].as_array().unwrap().len(), 13);
+
+    let colors = read_floats(&doc, &bin, &attributes["COLOR_0"]);
+    assert_eq!(colors[4..8], [64.0 / 255.0, 0.0, 0.0, 1.0]);
+    let uv1 = read_floats(&doc, &bin, &attributes["TEXCOORD_1"]);
+    assert_eq!(uv1[2..4], [0.5, 1.0]);
+    let positions = read_floats(&doc, &bin, &attributes["POSITION"]);
+    assert_eq!(positions[3..6], [-1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn blend_shapes_become_morph_targets() {
+    let (doc, bin) = parse_glb(
+        &processor(MeshConfig::default())
+            .export_to_gltf(&quad())
+            .unwrap(),
+    );
+    let mesh = &doc["meshes"][0];
+    assert_eq!(mesh["weights"], json!([0.0]));
+    assert_eq!(mesh["extras"]["targetNames"], json!(["Smile"]));
+
+    let targets = &mesh["primitives"][0]["targets"];
+    assert_eq!(targets.as_array().unwrap().len(), 1);
+    assert_eq!(mesh["primitives"][1]["targets"], *targets);
+    let target = &targets[0];
+    assert!(target.get("TANGENT").is_none());
+
+    // The full-weight frame, with X mirrored and untouched vertices left at zero.
+    let position = &doc["accessors"][target["POSITION"].as_u64().unwrap() as usize];
+    assert_eq!(position["min"], json!([-0.5, 0.0, 0.0]));
+    let deltas = read_floats(&doc, &bin, &target["POSITION"]);
+    assert_eq!(
+        deltas,
+        [0.0, 0.0, 0.0, -0.5, 0.2, 0.0, 0.0, 0.0, 0.0, -0.5, 0.3, 0.0]
+    );
+    let normals = read_floats(&doc, &bin, &target["NORMAL"]);
+    assert_eq!(normals[3..6], [0.0, 0.25, 0.0]);
+
+    let without = processor(MeshConfig::default().with_process_blend_shapes(false))
+        .export_to_gltf(&quad())
+        .unwrap();
+    let (doc, _) = parse_glb(&without);
+    assert!(doc["meshes"][0]["primitives"][0].get("targets").is_none());
+    assert!(doc["meshes"][0].get("weights").is_none());
+
+    let mut broken = quad();
+    broken.blend_shape_data.as_mut().unwrap().vertices[2].index = 9;
+    assert!(
+        processor(MeshConfig::default())
+            .export_to_gltf(&broken)
+            .is_err()
+    );
+}
+
+#[test]
+fn skin_joints_follow_bind_poses() {
+    let (doc, bin) = parse_glb(
+        &processor(MeshConfig::default())
+            .export_to_gltf(&quad())
+            .unwrap(),
+    );
+    assert_eq!(doc["scenes"][0]["nodes"], json!([0]));
+    let nodes = doc["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(nodes[0]["mesh"], 0);
+    assert_eq!(nodes[0]["skin"], 0);
+    assert_eq!(nodes[0]["children"], json!([1, 2]));
+    assert_eq!(doc["skins"][0]["joints"], json!([1, 2]));
+
+    // Bone 1's bind pose moves by -1 on X, so the bone sits at +1 on Unity's X, -1 on glTF's.
+    assert_eq!(nodes[2]["matrix"][12], -1.0);
+    let inverse_bind = read_floats(&doc, &bin, &doc["skins"][0]["inverseBindMatrices"]);
+    assert_eq!(inverse_bind.len(), 32);
+    assert_eq!(inverse_bind[16 + 12], 1.0);
+
+    // Without bind poses the weights are left out.
+    let mut unskinned = quad();
+    unskinned.bind_pose.clear();
+    let (doc, _) = parse_glb(
+        &processor(MeshConfig::default())
+            .export_to_gltf(&unskinned)
+            .unwrap(),
+    );
+    assert!(doc.get("skins").is_none());
+    let attributes = &doc["meshes"][0]["primitives"][0]["attributes"];
+    assert!(attributes.get("JOINTS_0").is_none());
+    assert!(attributes.get("WEIGHTS_0").is_none());
+}
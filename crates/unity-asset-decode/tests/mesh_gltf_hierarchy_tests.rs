@@ -0,0 +1,695 @@
+//! glTF export of a GameObject hierarchy.
+//!
+//! The fixture is a hand-assembled SerializedFile (format 17, TypeTrees embedded) holding a
+//! `Root` GameObject with one `Child` that renders a quad through MeshFilter + MeshRenderer, and a
+//! second scene with Camera and Light components.
+
+#![cfg(feature = "mesh-export")]
+
+use serde_json::Value;
+use unity_asset_decode::asset::{SerializedFile, SerializedFileParser};
+use unity_asset_decode::mesh::{GltfOptions, export_hierarchy_to_gltf};
+
+const ALIGN: i32 = 0x4000;
+
+const ROOT_GO: i64 = 1;
+const ROOT_TRANSFORM: i64 = 2;
+const CHILD_GO: i64 = 3;
+const CHILD_TRANSFORM: i64 = 4;
+const MESH_FILTER: i64 = 5;
+const MESH_RENDERER: i64 = 6;
+const MESH: i64 = 7;
+const MATERIAL: i64 = 8;
+const CAMERA: i64 = 9;
+const SPOT: i64 = 10;
+const AREA: i64 = 11;
+const HIDDEN_CAMERA: i64 = 12;
+const ORTHO_CAMERA: i64 = 13;
+
+/// TypeTree node: level, type, name, byte size, meta flags
+type Node = (u8, &'static str, &'static str, i32, i32);
+
+fn pptr(level: u8, name: &'static str) -> Vec<Node> {
+    vec![
+        (level, "PPtr<Object>", name, 12, 0),
+        (level + 1, "int", "m_FileID", 4, 0),
+        (level + 1, "SInt64", "m_PathID", 8, 0),
+    ]
+}
+
+fn floats(
+    level: u8,
+    type_name: &'static str,
+    name: &'static str,
+    fields: &[&'static str],
+) -> Vec<Node> {
+    let mut nodes = vec![(level, type_name, name, 4 * fields.len() as i32, 0)];
+    nodes.extend(fields.iter().map(|f| (level + 1, "float", *f, 4, 0)));
+    nodes
+}
+
+fn string(level: u8, name: &'static str) -> Vec<Node> {
+    vec![
+        (level, "string", name, -1, ALIGN),
+        (level + 1, "Array", "Array", -1, ALIGN),
+        (level + 2, "int", "size", 4, 0),
+        (level + 2, "char", "data", 1, 0),
+    ]
+}
+
+fn vector(level: u8, name: &'static str, element: Vec<Node>) -> Vec<Node> {
+    let mut nodes = vec![
+        (level, "vector", name, -1, ALIGN),
+        (level + 1, "Array", "Array", -1, ALIGN),
+        (level + 2, "int", "size", 4, 0),
+    ];
+    nodes.extend(element);
+    nodes
+}
+
+fn type_tree(class: &'static str, fields: Vec<Vec<Node>>) -> Vec<Node> {
+    let mut nodes = vec![(0, class, "Base", -1, 0)];
+    nodes.extend(fields.into_iter().flatten());
+    nodes
+}
+
+fn game_object_tree() -> Vec<Node> {
+    let mut pair = vec![(3, "ComponentPair", "data", 12, 0)];
+    pair.extend(pptr(4, "component"));
+    type_tree(
+        "GameObject",
+        vec![vector(1, "m_Component", pair), string(1, "m_Name")],
+    )
+}
+
+fn transform_tree() -> Vec<Node> {
+    type_tree(
+        "Transform",
+        vec![
+            pptr(1, "m_GameObject"),
+            floats(1, "Quaternionf", "m_LocalRotation", &["x", "y", "z", "w"]),
+            floats(1, "Vector3f", "m_LocalPosition", &["x", "y", "z"]),
+            floats(1, "Vector3f", "m_LocalScale", &["x", "y", "z"]),
+            vector(1, "m_Children", pptr(3, "data")),
+            pptr(1, "m_Father"),
+        ],
+    )
+}
+
+fn mesh_filter_tree() -> Vec<Node> {
+    type_tree(
+        "MeshFilter",
+        vec![pptr(1, "m_GameObject"), pptr(1, "m_Mesh")],
+    )
+}
+
+fn mesh_renderer_tree() -> Vec<Node> {
+    type_tree(
+        "MeshRenderer",
+        vec![
+            pptr(1, "m_GameObject"),
+            vector(1, "m_Materials", pptr(3, "data")),
+        ],
+    )
+}
+
+fn mesh_tree() -> Vec<Node> {
+    let submesh = vec![
+        (3, "SubMesh", "data", 24, 0),
+        (4, "unsigned int", "firstByte", 4, 0),
+        (4, "unsigned int", "indexCount", 4, 0),
+        (4, "int", "topology", 4, 0),
+        (4, "unsigned int", "baseVertex", 4, 0),
+        (4, "unsigned int", "firstVertex", 4, 0),
+        (4, "unsigned int", "vertexCount", 4, 0),
+    ];
+    let channel = vec![
+        (4, "ChannelInfo", "data", 4, 0),
+        (5, "UInt8", "stream", 1, 0),
+        (5, "UInt8", "offset", 1, 0),
+        (5, "UInt8", "format", 1, 0),
+        (5, "UInt8", "dimension", 1, 0),
+    ];
+    let mut vertex_data = vec![
+        (1, "VertexData", "m_VertexData", -1, 0),
+        (2, "unsigned int", "m_VertexCount", 4, 0),
+    ];
+    vertex_data.extend(vector(2, "m_Channels", channel));
+    vertex_data.push((2, "TypelessData", "m_DataSize", -1, ALIGN));
+    type_tree(
+        "Mesh",
+        vec![
+            string(1, "m_Name"),
+            vector(1, "m_SubMeshes", submesh),
+            vec![(1, "int", "m_IndexFormat", 4, 0)],
+            vector(1, "m_IndexBuffer", vec![(3, "UInt8", "data", 1, 0)]),
+            vertex_data,
+        ],
+    )
+}
+
+fn material_tree() -> Vec<Node> {
+    let mut color = vec![(4, "pair", "data", -1, 0)];
+    color.extend(string(5, "first"));
+    color.extend(floats(5, "ColorRGBA", "second", &["r", "g", "b", "a"]));
+    let mut saved = vec![(1, "UnityPropertySheet", "m_SavedProperties", -1, 0)];
+    saved.extend(vector(2, "m_Colors", color));
+    type_tree("Material", vec![string(1, "m_Name"), saved])
+}
+
+fn camera_tree() -> Vec<Node> {
+    type_tree(
+        "Camera",
+        vec![
+            pptr(1, "m_GameObject"),
+            vec![(1, "UInt8", "m_Enabled", 1, ALIGN)],
+            vec![(1, "unsigned int", "m_ClearFlags", 4, 0)],
+            vec![(1, "float", "near clip plane", 4, 0)],
+            vec![(1, "float", "far clip plane", 4, 0)],
+            vec![(1, "float", "field of view", 4, 0)],
+            vec![(1, "bool", "orthographic", 1, ALIGN)],
+            vec![(1, "float", "orthographic size", 4, 0)],
+        ],
+    )
+}
+
+fn light_tree() -> Vec<Node> {
+    type_tree(
+        "Light",
+        vec![
+            pptr(1, "m_GameObject"),
+            vec![(1, "UInt8", "m_Enabled", 1, ALIGN)],
+            vec![(1, "int", "m_Type", 4, 0)],
+            floats(1, "ColorRGBA", "m_Color", &["r", "g", "b", "a"]),
+            vec![(1, "float", "m_Intensity", 4, 0)],
+            vec![(1, "float", "m_Range", 4, 0)],
+            vec![(1, "float", "m_SpotAngle", 4, 0)],
+            vec![(1, "float", "m_InnerSpotAngle", 4, 0)],
+            vec![(1, "int", "m_LightUnit", 4, 0)],
+        ],
+    )
+}
+
+/// Little-endian object data writer
+#[derive(Default)]
+struct Data(Vec<u8>);
+
+impl Data {
+    fn i32(&mut self, v: i32) -> &mut Self {
+        self.0.extend_from_slice(&v.to_le_bytes());
+        self
+    }
+
+    fn u32(&mut self, v: u32) -> &mut Self {
+        self.0.extend_from_slice(&v.to_le_bytes());
+        self
+    }
+
+    fn f32s(&mut self, values: &[f32]) -> &mut Self {
+        for v in values {
+            self.0.extend_from_slice(&v.to_le_bytes());
+        }
+        self
+    }
+
+    fn pptr(&mut self, path_id: i64) -> &mut Self {
+        self.i32(0);
+        self.0.extend_from_slice(&path_id.to_le_bytes());
+        self
+    }
+
+    fn align(&mut self) -> &mut Self {
+        while !self.0.len().is_multiple_of(4) {
+            self.0.push(0);
+        }
+        self
+    }
+
+    fn string(&mut self, s: &str) -> &mut Self {
+        self.i32(s.len() as i32);
+        self.0.extend_from_slice(s.as_bytes());
+        self.align()
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.i32(bytes.len() as i32);
+        self.0.extend_from_slice(bytes);
+        self.align()
+    }
+
+    fn flag(&mut self, v: bool) -> &mut Self {
+        self.0.push(u8::from(v));
+        self.align()
+    }
+
+    fn pptrs(&mut self, path_ids: &[i64]) -> &mut Self {
+        self.i32(path_ids.len() as i32);
+        for &id in path_ids {
+            self.pptr(id);
+        }
+        self
+    }
+}
+
+fn game_object(name: &str, components: &[i64]) -> Vec<u8> {
+    let mut d = Data::default();
+    d.pptrs(components).string(name);
+    d.0
+}
+
+fn transform(
+    game_object: i64,
+    position: [f32; 3],
+    rotation: [f32; 4],
+    children: &[i64],
+    father: i64,
+) -> Vec<u8> {
+    let mut d = Data::default();
+    d.pptr(game_object)
+        .f32s(&rotation)
+        .f32s(&position)
+        .f32s(&[1.0, 1.0, 1.0])
+        .pptrs(children)
+        .pptr(father);
+    d.0
+}
+
+fn quad_mesh() -> Vec<u8> {
+    let mut vertices = Vec::new();
+    for [u, v] in [[0.0f32, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]] {
+        for value in [u - 0.5, v - 0.5, 0.0, u, v] {
+            vertices.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    let indices: Vec<u8> = [0u16, 1, 2, 0, 2, 3]
+        .iter()
+        .flat_map(|i| i.to_le_bytes())
+        .collect();
+
+    let mut d = Data::default();
+    d.string("Quad");
+    d.i32(1).u32(0).u32(6).i32(0).u32(0).u32(0).u32(4);
+    d.i32(0).bytes(&indices);
+    // 2019 layout: 14 channels, position in 0 and UV0 in 4.
+    d.u32(4).i32(14);
+    for channel in 0..14 {
+        let info: [u8; 4] = match channel {
+            0 => [0, 0, 0, 3],
+            4 => [0, 12, 0, 2],
+            _ => [0; 4],
+        };
+        d.0.extend_from_slice(&info);
+    }
+    d.bytes(&vertices);
+    d.0
+}
+
+fn material() -> Vec<u8> {
+    let mut d = Data::default();
+    d.string("QuadMat")
+        .i32(1)
+        .string("_Color")
+        .f32s(&[1.0, 0.5, 0.25, 1.0]);
+    d.0
+}
+
+/// Assemble a format-17 SerializedFile from `(path_id, type_index, data)` objects
+fn serialized_file(types: &[(i32, Vec<Node>)], objects: &[(i64, i32, Vec<u8>)]) -> Vec<u8> {
+    let mut meta = Vec::new();
+    meta.extend_from_slice(b"2019.4.0f1\0");
+    meta.extend_from_slice(&5i32.to_le_bytes());
+    meta.push(1);
+    meta.extend_from_slice(&(types.len() as i32).to_le_bytes());
+    for (class_id, nodes) in types {
+        meta.extend_from_slice(&class_id.to_le_bytes());
+        meta.push(0);
+        meta.extend_from_slice(&(-1i16).to_le_bytes());
+        meta.extend_from_slice(&[0; 16]);
+
+        let mut strings = Vec::new();
+        let mut offset_of = |s: &str| {
+            let offset = strings.len() as u32;
+            strings.extend_from_slice(s.as_bytes());
+            strings.push(0);
+            offset
+        };
+        let mut blob = Vec::new();
+        for (index, (level, type_name, name, byte_size, flags)) in nodes.iter().enumerate() {
+            blob.extend_from_slice(&1u16.to_le_bytes());
+            blob.push(*level);
+            blob.push(u8::from(*type_name == "Array"));
+            blob.extend_from_slice(&offset_of(type_name).to_le_bytes());
+            blob.extend_from_slice(&offset_of(name).to_le_bytes());
+            blob.extend_from_slice(&byte_size.to_le_bytes());
+            blob.extend_from_slice(&(index as i32).to_le_bytes());
+            blob.extend_from_slice(&flags.to_le_bytes());
+        }
+        meta.extend_from_slice(&(nodes.len() as i32).to_le_bytes());
+        meta.extend_from_slice(&(strings.len() as i32).to_le_bytes());
+        meta.extend_from_slice(&blob);
+        meta.extend_from_slice(&strings);
+    }
+
+    let mut data = Vec::new();
+    let mut table = Vec::new();
+    for (path_id, type_index, bytes) in objects {
+        while !data.len().is_multiple_of(8) {
+            data.push(0);
+        }
+        table.push((*path_id, data.len() as u32, bytes.len() as u32, *type_index));
+        data.extend_from_slice(bytes);
+    }
+    meta.extend_from_slice(&(objects.len() as i32).to_le_bytes());
+    for (path_id, start, size, type_index) in table {
+        while !(20 + meta.len()).is_multiple_of(4) {
+            meta.push(0);
+        }
+        meta.extend_from_slice(&path_id.to_le_bytes());
+        meta.extend_from_slice(&start.to_le_bytes());
+        meta.extend_from_slice(&size.to_le_bytes());
+        meta.extend_from_slice(&type_index.to_le_bytes());
+    }
+    meta.extend_from_slice(&0i32.to_le_bytes()); // script types
+    meta.extend_from_slice(&0i32.to_le_bytes()); // externals
+    meta.push(0); // user information
+
+    let data_offset = (20 + meta.len()).next_multiple_of(16);
+    let file_size = data_offset + data.len();
+    let mut file = Vec::with_capacity(file_size);
+    file.extend_from_slice(&(meta.len() as u32).to_be_bytes());
+    file.extend_from_slice(&(file_size as u32).to_be_bytes());
+    file.extend_from_slice(&17u32.to_be_bytes());
+    file.extend_from_slice(&(data_offset as u32).to_be_bytes());
+    file.extend_from_slice(&[0; 4]);
+    file.extend_from_slice(&meta);
+    file.resize(data_offset, 0);
+    file.extend_from_slice(&data);
+    file
+}
+
+/// Root (at 1, 2, 3, turned 90° about Y) with one Child rendering `mesh`
+fn fixture(mesh: i64) -> SerializedFile {
+    let types = [
+        (1, game_object_tree()),
+        (4, transform_tree()),
+        (33, mesh_filter_tree()),
+        (23, mesh_renderer_tree()),
+        (43, mesh_tree()),
+        (21, material_tree()),
+    ];
+    let half = std::f32::consts::FRAC_1_SQRT_2;
+    let objects = [
+        (ROOT_GO, 0, game_object("Root", &[ROOT_TRANSFORM])),
+        (
+            ROOT_TRANSFORM,
+            1,
+            transform(
+                ROOT_GO,
+                [1.0, 2.0, 3.0],
+                [0.0, half, 0.0, half],
+                &[CHILD_TRANSFORM],
+                0,
+            ),
+        ),
+        (
+            CHILD_GO,
+            0,
+            game_object("Child", &[CHILD_TRANSFORM, MESH_FILTER, MESH_RENDERER]),
+        ),
+        (
+            CHILD_TRANSFORM,
+            1,
+            transform(
+                CHILD_GO,
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+                &[],
+                ROOT_TRANSFORM,
+            ),
+        ),
+        (MESH_FILTER, 2, {
+            let mut d = Data::default();
+            d.pptr(CHILD_GO).pptr(mesh);
+            d.0
+        }),
+        (MESH_RENDERER, 3, {
+            let mut d = Data::default();
+            d.pptr(CHILD_GO).pptrs(&[MATERIAL]);
+            d.0
+        }),
+        (MESH, 4, quad_mesh()),
+        (MATERIAL, 5, material()),
+    ];
+    SerializedFileParser::from_bytes(serialized_file(&types, &objects)).unwrap()
+}
+
+fn camera(game_object: i64, enabled: bool, fov: f32, orthographic: bool) -> Vec<u8> {
+    let mut d = Data::default();
+    d.pptr(game_object).flag(enabled).u32(1);
+    d.f32s(&[0.5, 200.0, fov]).flag(orthographic).f32s(&[4.0]);
+    d.0
+}
+
+fn light(game_object: i64, kind: i32, spot_angles: [f32; 2]) -> Vec<u8> {
+    let mut d = Data::default();
+    d.pptr(game_object).flag(true).i32(kind);
+    d.f32s(&[1.0, 0.5, 0.0, 1.0, 3.0, 20.0]).f32s(&spot_angles);
+    // Candela
+    d.i32(1);
+    d.0
+}
+
+/// Root (at 1, 2, 3) with an enabled perspective Camera and a disabled one; its Child has a spot
+/// Light, an area Light and an orthographic Camera
+fn scene_fixture() -> SerializedFile {
+    let types = [
+        (1, game_object_tree()),
+        (4, transform_tree()),
+        (20, camera_tree()),
+        (108, light_tree()),
+    ];
+    let objects = [
+        (
+            ROOT_GO,
+            0,
+            game_object("Root", &[ROOT_TRANSFORM, CAMERA, HIDDEN_CAMERA]),
+        ),
+        (
+            ROOT_TRANSFORM,
+            1,
+            transform(
+                ROOT_GO,
+                [1.0, 2.0, 3.0],
+                [0.0, 0.0, 0.0, 1.0],
+                &[CHILD_TRANSFORM],
+                0,
+            ),
+        ),
+        (
+            CHILD_GO,
+            0,
+            game_object("Child", &[CHILD_TRANSFORM, SPOT, AREA, ORTHO_CAMERA]),
+        ),
+        (
+            CHILD_TRANSFORM,
+            1,
+            transform(
+                CHILD_GO,
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+                &[],
+                ROOT_TRANSFORM,
+            ),
+        ),
+        (CAMERA, 2, camera(ROOT_GO, true, 60.0, false)),
+        (HIDDEN_CAMERA, 2, camera(ROOT_GO, false, 90.0, false)),
+        (ORTHO_CAMERA, 2, camera(CHILD_GO, true, 60.0, true)),
+        (SPOT, 3, light(CHILD_GO, 0, [60.0, 30.0])),
+        (AREA, 3, light(CHILD_GO, 3, [30.0, 0.0])),
+    ];
+    SerializedFileParser::from_bytes(serialized_file(&types, &objects)).unwrap()
+}
+
+/// Split a GLB into its JSON document and (possibly absent) BIN chunk
+fn parse_glb(glb: &[u8]) -> (Value, Vec<u8>) {
+    let u32_at = |offset: usize| u32::from_le_bytes(glb[offset..offset + 4].try_into().unwrap());
+    assert_eq!(&glb[..4], b"glTF");
+    assert_eq!(u32_at(4), 2);
+    assert_eq!(u32_at(8) as usize, glb.len());
+    let json_len = u32_at(12) as usize;
+    assert_eq!(&glb[16..20], b"JSON");
+    let document = serde_json::from_slice(&glb[20..20 + json_len]).unwrap();
+    let bin_start = 20 + json_len;
+    if bin_start == glb.len() {
+        return (document, Vec::new());
+    }
+    assert_eq!(&glb[bin_start + 4..bin_start + 8], b"BIN\0");
+    let bin_len = u32_at(bin_start) as usize;
+    (
+        document,
+        glb[bin_start + 8..bin_start + 8 + bin_len].to_vec(),
+    )
+}
+
+#[test]
+fn nodes_parenting_and_mesh_attachment() {
+    let asset = fixture(MESH);
+    let glb = export_hierarchy_to_gltf(&asset, ROOT_GO, &GltfOptions::default()).unwrap();
+    let (doc, bin) = parse_glb(&glb);
+
+    let nodes = doc["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(doc["scenes"][0]["nodes"], serde_json::json!([0]));
+    assert_eq!(nodes[0]["name"], "Root");
+    assert_eq!(nodes[0]["children"], serde_json::json!([1]));
+    assert!(nodes[0].get("mesh").is_none());
+    assert_eq!(nodes[1]["name"], "Child");
+    assert_eq!(nodes[1]["mesh"], 0);
+
+    // X is mirrored: translation negates x, rotation negates y and z.
+    assert_eq!(nodes[0]["translation"], serde_json::json!([-1.0, 2.0, 3.0]));
+    let rotation: Vec<f64> = nodes[0]["rotation"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_f64().unwrap())
+        .collect();
+    assert!((rotation[1] + std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+
+    let primitive = &doc["meshes"][0]["primitives"][0];
+    assert_eq!(doc["meshes"][0]["name"], "Quad");
+    assert_eq!(primitive["material"], 0);
+    assert_eq!(doc["materials"][0]["name"], "QuadMat");
+    assert_eq!(
+        doc["materials"][0]["pbrMetallicRoughness"]["baseColorFactor"],
+        serde_json::json!([1.0, 0.5, 0.25, 1.0])
+    );
+    let position =
+        &doc["accessors"][primitive["attributes"]["POSITION"].as_u64().unwrap() as usize];
+    assert_eq!(position["count"], 4);
+    assert_eq!(position["min"], serde_json::json!([-0.5, -0.5, 0.0]));
+    assert!(primitive["attributes"].get("TEXCOORD_0").is_some());
+
+    // Winding is reversed: Unity's (0, 1, 2) becomes (0, 2, 1).
+    let indices = &doc["accessors"][primitive["indices"].as_u64().unwrap() as usize];
+    assert_eq!(indices["count"], 6);
+    let view = &doc["bufferViews"][indices["bufferView"].as_u64().unwrap() as usize];
+    let start = view["byteOffset"].as_u64().unwrap() as usize;
+    let first: Vec<u32> = bin[start..start + 12]
+        .chunks_exact(4)
+        .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+        .collect();
+    assert_eq!(first, vec![0, 2, 1]);
+    assert_eq!(doc["buffers"][0]["byteLength"], bin.len());
+}
+
+#[test]
+fn transform_root_and_option_toggles() {
+    let asset = fixture(MESH);
+    let from_go = export_hierarchy_to_gltf(&asset, ROOT_GO, &GltfOptions::default()).unwrap();
+    let from_transform =
+        export_hierarchy_to_gltf(&asset, ROOT_TRANSFORM, &GltfOptions::default()).unwrap();
+    assert_eq!(from_go, from_transform);
+
+    let options = GltfOptions::default().with_materials(false);
+    let (doc, _) = parse_glb(&export_hierarchy_to_gltf(&asset, CHILD_GO, &options).unwrap());
+    assert_eq!(doc["nodes"].as_array().unwrap().len(), 1);
+    assert!(doc.get("materials").is_none());
+    assert!(doc["meshes"][0]["primitives"][0].get("material").is_none());
+
+    assert!(export_hierarchy_to_gltf(&asset, MESH, &options).is_err());
+    assert!(export_hierarchy_to_gltf(&asset, 99, &options).is_err());
+}
+
+#[test]
+fn unresolved_mesh_leaves_an_empty_named_node() {
+    let asset = fixture(99);
+    let glb = export_hierarchy_to_gltf(&asset, ROOT_GO, &GltfOptions::default()).unwrap();
+    let (doc, _) = parse_glb(&glb);
+
+    let nodes = doc["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(nodes[1]["name"], "Child");
+    assert!(nodes[1].get("mesh").is_none());
+    assert!(doc.get("meshes").is_none());
+}
+
+#[test]
+fn cameras_and_lights_hang_off_their_nodes() {
+    let asset = scene_fixture();
+    let glb = export_hierarchy_to_gltf(&asset, ROOT_GO, &GltfOptions::default()).unwrap();
+    let (doc, _) = parse_glb(&glb);
+
+    // Root, its camera node, Child, and Child's spot light and orthographic camera nodes.
+    let nodes = doc["nodes"].as_array().unwrap();
+    let names: Vec<_> = nodes.iter().map(|n| n["name"].as_str().unwrap()).collect();
+    assert_eq!(
+        names,
+        [
+            "Root",
+            "Root_Camera",
+            "Child",
+            "Child_Light",
+            "Child_Camera"
+        ]
+    );
+    assert_eq!(nodes[0]["children"], serde_json::json!([1, 2]));
+    assert_eq!(nodes[2]["children"], serde_json::json!([3, 4]));
+    assert_eq!(
+        nodes[1]["rotation"],
+        serde_json::json!([0.0, 1.0, 0.0, 0.0])
+    );
+    assert_eq!(nodes[1]["camera"], 0);
+    assert_eq!(nodes[4]["camera"], 1);
+
+    let cameras = doc["cameras"].as_array().unwrap();
+    assert_eq!(cameras.len(), 2);
+    assert_eq!(cameras[0]["type"], "perspective");
+    let yfov = cameras[0]["perspective"]["yfov"].as_f64().unwrap();
+    assert!((yfov - std::f64::consts::FRAC_PI_3).abs() < 1e-6);
+    assert_eq!(cameras[0]["perspective"]["znear"], 0.5);
+    assert_eq!(cameras[0]["perspective"]["zfar"], 200.0);
+    assert_eq!(cameras[1]["type"], "orthographic");
+    assert_eq!(cameras[1]["orthographic"]["ymag"], 4.0);
+
+    // The area light has no glTF equivalent and is skipped.
+    assert_eq!(
+        doc["extensionsUsed"],
+        serde_json::json!(["KHR_lights_punctual"])
+    );
+    let lights = doc["extensions"]["KHR_lights_punctual"]["lights"]
+        .as_array()
+        .unwrap();
+    assert_eq!(lights.len(), 1);
+    assert_eq!(
+        nodes[3]["extensions"]["KHR_lights_punctual"]["light"],
+        serde_json::json!(0)
+    );
+    let spot = &lights[0];
+    assert_eq!(spot["type"], "spot");
+    assert_eq!(spot["intensity"], 3.0);
+    assert_eq!(spot["range"], 20.0);
+    assert_eq!(spot["extras"]["unityLightUnit"], "Candela");
+    // Gamma 0.5 is about 0.214 linear.
+    let color: Vec<f64> = spot["color"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_f64().unwrap())
+        .collect();
+    assert_eq!(color[0], 1.0);
+    assert!((color[1] - 0.214).abs() < 1e-3);
+    assert_eq!(color[2], 0.0);
+    let outer = spot["spot"]["outerConeAngle"].as_f64().unwrap();
+    let inner = spot["spot"]["innerConeAngle"].as_f64().unwrap();
+    assert!((outer - 30f64.to_radians()).abs() < 1e-6);
+    assert!((inner - 15f64.to_radians()).abs() < 1e-6);
+
+    let options = GltfOptions::default()
+        .with_cameras(false)
+        .with_lights(false);
+    let (doc, _) = parse_glb(&export_hierarchy_to_gltf(&asset, ROOT_GO, &options).unwrap());
+    assert_eq!(doc["nodes"].as_array().unwrap().len(), 2);
+    assert!(doc.get("cameras").is_none());
+    assert!(doc.get("extensions").is_none());
+}
@@ -0,0 +1,250 @@
+//! OBJ export with an `.mtl`: two quads in two submeshes rendered with two textured Materials,
+//! and the default entries used when a Material or Texture cannot be resolved.
+
+#![cfg(all(feature = "mesh-export", feature = "texture"))]
+
+use indexmap::IndexMap;
+use unity_asset_core::{UnityClass, UnityValue};
+use unity_asset_decode::asset::{ObjectInfo, class_ids};
+use unity_asset_decode::mesh::{
+    ChannelInfo, Mesh, MeshProcessor, ObjMaterials, SubMesh, export_to_obj_with_materials,
+};
+use unity_asset_decode::object::UnityObject;
+use unity_asset_decode::unity_version::UnityVersion;
+
+const RED_MAT: i64 = 10;
+const BLUE_MAT: i64 = 11;
+const RED_TEX: i64 = 20;
+const BLUE_TEX: i64 = 21;
+
+fn version() -> UnityVersion {
+    UnityVersion::parse_version("2020.3.33f1").unwrap()
+}
+
+fn object(class_id: i32, path_id: i64, props: Vec<(&str, UnityValue)>) -> UnityObject {
+    let mut class = UnityClass::new(class_id, String::new(), path_id.to_string());
+    for (key, value) in props {
+        class.set(key.to_string(), value);
+    }
+    UnityObject::from_info_and_class(ObjectInfo::new(path_id, 0, 0, class_id, -1), class)
+}
+
+fn fields(pairs: Vec<(&str, UnityValue)>) -> UnityValue {
+    UnityValue::Object(
+        pairs
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect::<IndexMap<_, _>>(),
+    )
+}
+
+fn pptr(path_id: i64) -> UnityValue {
+    fields(vec![
+        ("m_FileID", UnityValue::Integer(0)),
+        ("m_PathID", UnityValue::Integer(path_id)),
+    ])
+}
+
+fn pair(key: &str, value: UnityValue) -> UnityValue {
+    UnityValue::Array(vec![UnityValue::String(key.to_string()), value])
+}
+
+fn material(path_id: i64, name: &str, rgba: [f64; 4], texture: i64) -> UnityObject {
+    let color = fields(
+        ["r", "g", "b", "a"]
+            .into_iter()
+            .zip(rgba)
+            .map(|(k, v)| (k, UnityValue::Float(v)))
+            .collect(),
+    );
+    let env = fields(vec![("m_Texture", pptr(texture))]);
+    let saved = fields(vec![
+        ("m_TexEnvs", UnityValue::Array(vec![pair("_MainTex", env)])),
+        ("m_Colors", UnityValue::Array(vec![pair("_Color", color)])),
+    ]);
+    object(
+        class_ids::MATERIAL,
+        path_id,
+        vec![
+            ("m_Name", UnityValue::String(name.to_string())),
+            ("m_SavedProperties", saved),
+        ],
+    )
+}
+
+/// A 2x2 RGBA32 texture of one color
+fn texture(path_id: i64, name: &str, rgba: [u8; 4]) -> UnityObject {
+    object(
+        class_ids::TEXTURE_2D,
+        path_id,
+        vec![
+            ("m_Name", UnityValue::String(name.to_string())),
+            ("m_Width", UnityValue::Integer(2)),
+            ("m_Height", UnityValue::Integer(2)),
+            ("m_TextureFormat", UnityValue::Integer(4)),
+            ("image data", UnityValue::Bytes(rgba.repeat(4))),
+        ],
+    )
+}
+
+fn renderer(materials: &[i64]) -> UnityObject {
+    object(
+        class_ids::MESH_RENDERER,
+        1,
+        vec![(
+            "m_Materials",
+            UnityValue::Array(materials.iter().map(|&id| pptr(id)).collect()),
+        )],
+    )
+}
+
+fn resolve(_file_id: i32, path_id: i64) -> Option<UnityObject> {
+    match path_id {
+        RED_MAT => Some(material(
+            RED_MAT,
+            "Red Paint",
+            [1.0, 0.0, 0.0, 1.0],
+            RED_TEX,
+        )),
+        BLUE_MAT => Some(material(BLUE_MAT, "Blue", [0.0, 0.0, 1.0, 0.5], BLUE_TEX)),
+        RED_TEX => Some(texture(RED_TEX, "red", [255, 0, 0, 255])),
+        BLUE_TEX => Some(texture(BLUE_TEX, "blue", [0, 0, 255, 255])),
+        _ => None,
+    }
+}
+
+/// Two side-by-side quads, one per submesh
+fn two_quads() -> Mesh {
+    let mut mesh = Mesh {
+        name: "Panels".to_string(),
+        ..Default::default()
+    };
+    let channel = |offset, dimension| ChannelInfo {
+        stream: 0,
+        offset,
+        format: 0,
+        dimension,
+    };
+    mesh.vertex_data.channels = (0..14).map(|_| channel(0, 0)).collect();
+    mesh.vertex_data.channels[0] = channel(0, 3);
+    mesh.vertex_data.channels[4] = channel(12, 2);
+    mesh.vertex_data.vertex_count = 8;
+
+    for quad in 0..2u16 {
+        for [u, v] in [[0.0f32, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]] {
+            for value in [u + quad as f32, v, 0.0, u, v] {
+                mesh.vertex_data
+                    .data_size
+                    .extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        let base = quad * 4;
+        for index in [base, base + 1, base + 2, base, base + 2, base + 3] {
+            mesh.index_buffer.extend_from_slice(&index.to_le_bytes());
+        }
+    }
+    mesh.sub_meshes = (0..2)
+        .map(|quad| SubMesh {
+            first_byte: quad * 12,
+            index_count: 6,
+            triangle_count: 2,
+            first_vertex: quad * 4,
+            vertex_count: 4,
+            ..Default::default()
+        })
+        .collect();
+    mesh
+}
+
+#[test]
+fn two_submeshes_get_two_textured_materials() {
+    let dir = tempfile::tempdir().unwrap();
+    let report = export_to_obj_with_materials(
+        &two_quads(),
+        &version(),
+        ObjMaterials::Renderer(&renderer(&[RED_MAT, BLUE_MAT])),
+        &resolve,
+        dir.path(),
+    )
+    .unwrap();
+
+    assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+    assert_eq!(report.obj_path, dir.path().join("Panels.obj"));
+    assert_eq!(report.submesh_materials, vec!["Red_Paint", "Blue"]);
+
+    let obj = std::fs::read_to_string(&report.obj_path).unwrap();
+    assert!(obj.contains("mtllib Panels.mtl\n"));
+    let red = obj.find("usemtl Red_Paint").unwrap();
+    let blue = obj.find("usemtl Blue").unwrap();
+    assert!(obj.find("g submesh_0").unwrap() < red && red < obj.find("g submesh_1").unwrap());
+    assert!(obj.find("g submesh_1").unwrap() < blue);
+    assert_eq!(obj.matches("\nf ").count(), 4);
+
+    let mtl = std::fs::read_to_string(dir.path().join("Panels.mtl")).unwrap();
+    assert_eq!(mtl.matches("newmtl ").count(), 2);
+    assert!(mtl.contains("newmtl Red_Paint\nKd 1 0 0\nmap_Kd red.png\n"));
+    assert!(mtl.contains("newmtl Blue\nKd 0 0 1\nd 0.5\nmap_Kd blue.png\n"));
+
+    assert_eq!(
+        report.textures,
+        vec![dir.path().join("red.png"), dir.path().join("blue.png")]
+    );
+    let red = image::open(dir.path().join("red.png")).unwrap().to_rgba8();
+    assert_eq!(red.dimensions(), (2, 2));
+    assert_eq!(red.get_pixel(0, 0).0, [255, 0, 0, 255]);
+    let blue = image::open(dir.path().join("blue.png")).unwrap().to_rgba8();
+    assert_eq!(blue.get_pixel(1, 1).0, [0, 0, 255, 255]);
+
+    // The processor method writes the same files.
+    let again = tempfile::tempdir().unwrap();
+    let via_processor = MeshProcessor::new(version())
+        .export_to_obj_with_materials(
+            &two_quads(),
+            ObjMaterials::Renderer(&renderer(&[RED_MAT, BLUE_MAT])),
+            &resolve,
+            again.path(),
+        )
+        .unwrap();
+    assert_eq!(via_processor.submesh_materials, report.submesh_materials);
+    assert_eq!(
+        std::fs::read_to_string(again.path().join("Panels.mtl")).unwrap(),
+        mtl
+    );
+}
+
+#[test]
+fn missing_materials_and_textures_fall_back_with_warnings() {
+    let dir = tempfile::tempdir().unwrap();
+
+    // Material 99 does not resolve.
+    let report = export_to_obj_with_materials(
+        &two_quads(),
+        &version(),
+        ObjMaterials::Renderer(&renderer(&[RED_MAT, 99])),
+        &resolve,
+        dir.path(),
+    )
+    .unwrap();
+    assert_eq!(report.submesh_materials, vec!["Red_Paint", "default"]);
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("Material 1"));
+    let mtl = std::fs::read_to_string(&report.mtl_path).unwrap();
+    assert!(mtl.contains("newmtl default\nKd 1 1 1\n"));
+
+    // One material for two submeshes, and its texture is missing.
+    let untextured = material(BLUE_MAT, "Blue", [0.0, 0.0, 1.0, 1.0], 98);
+    let report = export_to_obj_with_materials(
+        &two_quads(),
+        &version(),
+        ObjMaterials::Materials(std::slice::from_ref(&untextured)),
+        &resolve,
+        dir.path(),
+    )
+    .unwrap();
+    assert_eq!(report.submesh_materials, vec!["Blue", "default"]);
+    assert_eq!(report.warnings.len(), 2, "{:?}", report.warnings);
+    assert!(report.textures.is_empty());
+    let mtl = std::fs::read_to_string(&report.mtl_path).unwrap();
+    assert!(mtl.contains("newmtl Blue\nKd 0 0 1\n\n"));
+    assert!(!mtl.contains("map_Kd"));
+}
@@ -0,0 +1,188 @@
+//! Meshes whose vertex buffer is streamed into a `.resS` resource.
+//!
+//! The quad's vertex bytes are written into a sibling `.resS` file (or a bundle node) behind
+//! some padding, and the mesh only keeps `m_StreamData` pointing at them.
+
+#![cfg(feature = "mesh")]
+
+use unity_asset_decode::bundle::BundleParser;
+use unity_asset_decode::mesh::{
+    ChannelInfo, Mesh, MeshParser, MeshProcessor, StreamingInfo, SubMesh,
+};
+use unity_asset_decode::unity_version::UnityVersion;
+
+const PADDING: usize = 16;
+
+fn version() -> UnityVersion {
+    UnityVersion::parse_version("2020.3.33f1").unwrap()
+}
+
+/// Interleaved position + UV0 bytes of a unit quad
+fn quad_vertex_bytes() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for [u, v] in [[0.0f32, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]] {
+        for value in [u - 0.5, v - 0.5, 0.0, u, v] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+/// Quad mesh without vertex bytes, streamed from `path` after [`PADDING`] bytes
+fn streamed_quad(path: &str) -> Mesh {
+    let mut mesh = Mesh {
+        name: "Quad".to_string(),
+        ..Default::default()
+    };
+    let channel = |offset, dimension| ChannelInfo {
+        stream: 0,
+        offset,
+        format: 0,
+        dimension,
+    };
+    mesh.vertex_data.channels = (0..14).map(|_| channel(0, 0)).collect();
+    mesh.vertex_data.channels[0] = channel(0, 3);
+    mesh.vertex_data.channels[4] = channel(12, 2);
+    mesh.vertex_data.vertex_count = 4;
+    for index in [0u16, 1, 2, 0, 2, 3] {
+        mesh.index_buffer.extend_from_slice(&index.to_le_bytes());
+    }
+    mesh.sub_meshes = vec![SubMesh {
+        index_count: 6,
+        ..Default::default()
+    }];
+    mesh.stream_data = Some(StreamingInfo {
+        offset: PADDING as u64,
+        size: quad_vertex_bytes().len() as u32,
+        path: path.to_string(),
+    });
+    mesh
+}
+
+fn resource_bytes() -> Vec<u8> {
+    let mut bytes = vec![0xEE; PADDING];
+    bytes.extend_from_slice(&quad_vertex_bytes());
+    bytes.extend_from_slice(&[0xEE; 8]);
+    bytes
+}
+
+fn write_cstring(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(s.as_bytes());
+    buf.push(0);
+}
+
+/// UnityFS v6 bundle with a single uncompressed block holding one `.resS` node
+fn resource_bundle(name: &str, data: &[u8]) -> Vec<u8> {
+    let mut info = vec![0u8; 16];
+    info.extend_from_slice(&1i32.to_be_bytes());
+    info.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    info.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    info.extend_from_slice(&0u16.to_be_bytes());
+    info.extend_from_slice(&1i32.to_be_bytes());
+    info.extend_from_slice(&0i64.to_be_bytes());
+    info.extend_from_slice(&(data.len() as i64).to_be_bytes());
+    info.extend_from_slice(&4u32.to_be_bytes());
+    write_cstring(&mut info, name);
+
+    let mut header = Vec::new();
+    write_cstring(&mut header, "UnityFS");
+    header.extend_from_slice(&6u32.to_be_bytes());
+    write_cstring(&mut header, "5.x.x");
+    write_cstring(&mut header, "2018.4.2f1");
+    let total = header.len() + 20 + info.len() + data.len();
+    header.extend_from_slice(&(total as i64).to_be_bytes());
+    header.extend_from_slice(&(info.len() as u32).to_be_bytes());
+    header.extend_from_slice(&(info.len() as u32).to_be_bytes());
+    header.extend_from_slice(&0x40u32.to_be_bytes());
+
+    let mut bundle = header;
+    bundle.extend_from_slice(&info);
+    bundle.extend_from_slice(data);
+    bundle
+}
+
+fn assert_quad_uvs(mesh: &Mesh) {
+    let uvs = mesh.read_uvs(&version(), 0).unwrap();
+    assert_eq!(uvs, vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+}
+
+#[test]
+fn sibling_ress_file_fills_vertex_data() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("level0.resS"), resource_bytes()).unwrap();
+    let asset_path = dir.path().join("level0");
+    let parser = MeshParser::new(version());
+
+    let mut mesh = streamed_quad("level0.resS");
+    assert!(mesh.read_uvs(&version(), 0).is_err());
+    assert!(
+        parser
+            .resolve_streaming_data(&mut mesh, None, Some(&asset_path))
+            .unwrap()
+    );
+    assert_eq!(mesh.vertex_data.data_size, quad_vertex_bytes());
+    assert_quad_uvs(&mesh);
+
+    // Already resolved: nothing to do.
+    assert!(
+        !parser
+            .resolve_streaming_data(&mut mesh, None, Some(&asset_path))
+            .unwrap()
+    );
+
+    let processor = MeshProcessor::new(version());
+    let positions = processor.extract_vertex_positions(&mesh).unwrap();
+    assert_eq!(positions[2], [0.5, 0.5, 0.0]);
+    assert_eq!(
+        processor.extract_triangle_indices(&mesh).unwrap(),
+        vec![0, 1, 2, 0, 2, 3]
+    );
+}
+
+#[test]
+fn bundle_resource_node_fills_vertex_data() {
+    let cab = "CAB-0123456789abcdef0123456789abcdef";
+    let bundle =
+        BundleParser::from_bytes(resource_bundle(&format!("{cab}.resS"), &resource_bytes()))
+            .unwrap();
+    let parser = MeshParser::new(version());
+
+    let mesh = streamed_quad(&format!("archive:/{cab}/{cab}.resS"));
+    let bytes = parser.load_streaming_data(&mesh, &bundle).unwrap();
+    assert_eq!(bytes, quad_vertex_bytes());
+
+    let mut mesh = mesh;
+    assert!(
+        parser
+            .resolve_streaming_data(&mut mesh, Some(&bundle), None)
+            .unwrap()
+    );
+    assert_quad_uvs(&mesh);
+}
+
+#[test]
+fn missing_or_short_resources_are_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let asset_path = dir.path().join("level0");
+    let parser = MeshParser::new(version());
+
+    let mut mesh = streamed_quad("level0.resS");
+    let err = parser
+        .resolve_streaming_data(&mut mesh, None, Some(&asset_path))
+        .unwrap_err();
+    assert!(err.to_string().contains("level0.resS"), "{err}");
+    assert!(mesh.vertex_data.data_size.is_empty());
+
+    std::fs::write(dir.path().join("level0.resS"), &resource_bytes()[..40]).unwrap();
+    assert!(
+        parser
+            .load_streaming_data_from_file(&mesh, &asset_path)
+            .is_err()
+    );
+
+    assert!(
+        parser
+            .resolve_streaming_data(&mut mesh, None, None)
+            .is_err()
+    );
+}
@@ -0,0 +1,126 @@
+//! UV layout rendering of a synthetic quad.
+//!
+//! The quad covers the whole 0..1 UV square with two triangles split along the
+//! bottom-left/top-right diagonal, one per submesh.
+
+#![cfg(feature = "mesh-export")]
+
+use unity_asset_decode::mesh::{
+    ChannelInfo, Mesh, MeshProcessor, SubMesh, UvLayoutOptions, UvOverflow,
+};
+use unity_asset_decode::unity_version::UnityVersion;
+
+const WHITE: [u8; 4] = [255, 255, 255, 255];
+const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+fn processor() -> MeshProcessor {
+    MeshProcessor::new(UnityVersion::parse_version("2020.3.33f1").unwrap())
+}
+
+/// Position + UV0 quad (2020 channel layout) with its UVs offset by `shift`.
+fn quad(shift: [f32; 2]) -> Mesh {
+    let mut mesh = Mesh {
+        name: "Quad".to_string(),
+        ..Default::default()
+    };
+    let channel = |offset, dimension| ChannelInfo {
+        stream: 0,
+        offset,
+        format: 0,
+        dimension,
+    };
+    mesh.vertex_data.channels = (0..14).map(|_| channel(0, 0)).collect();
+    mesh.vertex_data.channels[0] = channel(0, 3);
+    mesh.vertex_data.channels[4] = channel(12, 2);
+    mesh.vertex_data.vertex_count = 4;
+    for [u, v] in [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]] {
+        for value in [u - 0.5, v - 0.5, 0.0, u + shift[0], v + shift[1]] {
+            mesh.vertex_data
+                .data_size
+                .extend_from_slice(&f32::to_le_bytes(value));
+        }
+    }
+    for index in [0u16, 1, 2, 0, 2, 3] {
+        mesh.index_buffer.extend_from_slice(&index.to_le_bytes());
+    }
+    mesh.sub_meshes = vec![
+        SubMesh {
+            first_byte: 0,
+            index_count: 3,
+            ..Default::default()
+        },
+        SubMesh {
+            first_byte: 6,
+            index_count: 3,
+            ..Default::default()
+        },
+    ];
+    mesh
+}
+
+#[test]
+fn wireframe_pixels() {
+    let image = processor()
+        .render_uv_layout(&quad([0.0, 0.0]), 0, 16)
+        .unwrap();
+
+    assert_eq!(image.dimensions(), (16, 16));
+    // Corners, the bottom edge (v = 0 is the last row) and the diagonal.
+    for (x, y) in [(0, 0), (15, 0), (0, 15), (15, 15), (7, 15), (8, 7)] {
+        assert_eq!(image.get_pixel(x, y).0, WHITE, "({x}, {y})");
+    }
+    assert_eq!(image.get_pixel(4, 4).0, CLEAR);
+    assert_eq!(image.get_pixel(11, 11).0, CLEAR);
+}
+
+#[test]
+fn filled_triangles_use_submesh_colors() {
+    let options = UvLayoutOptions::default().with_fill(true);
+    let image = processor()
+        .render_uv_layout_with(&quad([0.0, 0.0]), 0, 16, &options)
+        .unwrap();
+
+    assert_eq!(image.get_pixel(11, 11).0, [230, 80, 80, 96]);
+    assert_eq!(image.get_pixel(4, 4).0, [80, 170, 230, 96]);
+    assert_eq!(image.get_pixel(8, 7).0, WHITE);
+}
+
+#[test]
+fn tiled_uvs_wrap_or_expand() {
+    let processor = processor();
+    let base = processor
+        .render_uv_layout(&quad([0.0, 0.0]), 0, 16)
+        .unwrap();
+    let wrapped = processor
+        .render_uv_layout(&quad([2.0, -1.0]), 0, 16)
+        .unwrap();
+    assert_eq!(wrapped, base);
+
+    let options = UvLayoutOptions::default().with_overflow(UvOverflow::Expand);
+    let expanded = processor
+        .render_uv_layout_with(&quad([1.0, 0.0]), 0, 16, &options)
+        .unwrap();
+    assert_eq!(expanded.dimensions(), (32, 16));
+    assert_eq!(expanded.get_pixel(31, 15).0, WHITE);
+    assert_eq!(expanded.get_pixel(4, 4).0, CLEAR);
+}
+
+#[test]
+fn missing_uv_channel_is_an_error() {
+    let err = processor()
+        .render_uv_layout(&quad([0.0, 0.0]), 1, 16)
+        .unwrap_err();
+    assert!(err.to_string().contains("UV channel 1"), "{err}");
+}
+
+#[test]
+fn degenerate_triangles_are_an_error() {
+    let mut mesh = quad([0.0, 0.0]);
+    mesh.index_buffer[2..4].copy_from_slice(&0u16.to_le_bytes());
+    let err = processor().render_uv_layout(&mesh, 0, 16).unwrap_err();
+    assert!(err.to_string().contains("degenerate"), "{err}");
+
+    let mut mesh = quad([0.0, 0.0]);
+    mesh.index_buffer[0..2].copy_from_slice(&9u16.to_le_bytes());
+    assert!(processor().render_uv_layout(&mesh, 0, 16).is_err());
+}
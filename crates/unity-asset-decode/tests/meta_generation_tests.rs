@@ -0,0 +1,270 @@
+//! `.meta` generation for extracted assets.
+//!
+//! The fixtures under `tests/fixtures/meta/` are the expected `.meta` files. Their importer
+//! blocks follow the editor's own layout for the 2019.4-2022.3 importer versions; keys the
+//! editor would add on import are left to its defaults.
+
+#![cfg(feature = "meta")]
+
+use std::path::PathBuf;
+use unity_asset_core::{UnityDocument, UnityGuid, UnityValue};
+use unity_asset_decode::meta::MetaGenerator;
+use unity_asset_yaml::YamlDocument;
+
+const GUID: &str = "a1b2c3d4e5f60718293a4b5c6d7e8f90";
+
+fn guid() -> UnityGuid {
+    GUID.parse().unwrap()
+}
+
+fn assert_golden(doc: &YamlDocument, fixture: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/meta")
+        .join(fixture);
+    let actual = doc.dump_yaml().unwrap();
+    let expected = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(actual, expected, "{} differs", path.display());
+
+    // The generated file reads back as a plain mapping with the same GUID.
+    let reparsed = YamlDocument::from_yaml_str(&actual).unwrap();
+    let meta = &reparsed.entries()[0];
+    assert!(meta.is_plain());
+    assert_eq!(meta.get("fileFormatVersion"), Some(&UnityValue::Integer(2)));
+    assert_eq!(meta.get("guid").and_then(|v| v.as_str()), Some(GUID));
+}
+
+#[test]
+fn text_asset_meta_matches_golden() {
+    assert_golden(&MetaGenerator::for_text(guid()), "text.txt.meta");
+}
+
+#[cfg(feature = "texture")]
+mod texture {
+    use super::*;
+    use unity_asset_decode::texture::{GLTextureSettings, Texture2D, TextureFormat};
+
+    pub(super) fn ui_texture() -> Texture2D {
+        let mut texture = Texture2D::new("ui_atlas".to_string(), 300, 200, TextureFormat::DXT5);
+        texture.mip_map = true;
+        texture.mip_count = 9;
+        texture.color_space = 1;
+        texture.is_readable = true;
+        texture.texture_settings = GLTextureSettings {
+            filter_mode: 2,
+            aniso: 4,
+            mip_bias: 0.0,
+            wrap_u: 1,
+            wrap_v: 0,
+            wrap_w: 0,
+        };
+        texture
+    }
+
+    #[test]
+    fn texture_meta_matches_golden() {
+        assert_golden(
+            &MetaGenerator::for_texture(&ui_texture(), guid()),
+            "texture.png.meta",
+        );
+    }
+
+    #[test]
+    fn texture_meta_follows_source_settings() {
+        let mut texture = Texture2D::new("lut".to_string(), 16, 4096, TextureFormat::RGB24);
+        texture.color_space = 0;
+        texture.texture_settings.wrap_u = 1;
+        let doc = MetaGenerator::for_texture(&texture, guid());
+        let importer = doc.entries()[0]
+            .get("TextureImporter")
+            .and_then(|v| v.as_object())
+            .unwrap();
+        let field = |path: &[&str]| {
+            let mut value = importer.get(path[0]).unwrap();
+            for key in &path[1..] {
+                value = value.as_object().unwrap().get(*key).unwrap();
+            }
+            value.as_i64().unwrap()
+        };
+
+        assert_eq!(field(&["mipmaps", "enableMipMap"]), 0);
+        assert_eq!(field(&["mipmaps", "sRGBTexture"]), 0);
+        assert_eq!(field(&["textureSettings", "wrapU"]), 1);
+        assert_eq!(field(&["maxTextureSize"]), 4096);
+        assert_eq!(field(&["alphaUsage"]), 0);
+        let platform = importer
+            .get("platformSettings")
+            .unwrap()
+            .as_array()
+            .unwrap()[0]
+            .as_object()
+            .unwrap();
+        assert_eq!(
+            platform.get("textureCompression").unwrap().as_i64(),
+            Some(0)
+        );
+        assert_eq!(
+            platform.get("crunchedCompression").unwrap().as_i64(),
+            Some(0)
+        );
+
+        texture.format = TextureFormat::DXT5Crunched;
+        let doc = MetaGenerator::for_texture(&texture, guid());
+        let importer = doc.entries()[0]
+            .get("TextureImporter")
+            .and_then(|v| v.as_object())
+            .unwrap();
+        let platform = importer
+            .get("platformSettings")
+            .unwrap()
+            .as_array()
+            .unwrap()[0]
+            .as_object()
+            .unwrap();
+        assert_eq!(
+            platform.get("crunchedCompression").unwrap().as_i64(),
+            Some(1)
+        );
+    }
+}
+
+#[cfg(feature = "sprite")]
+#[test]
+fn sprite_meta_matches_golden() {
+    use unity_asset_decode::sprite::Sprite;
+
+    let sprite = Sprite {
+        name: "icon_coin".to_string(),
+        pixels_to_units: 32.0,
+        pivot_x: 0.5,
+        pivot_y: 0.0,
+        border_x: 4.0,
+        border_y: 2.0,
+        border_z: 4.0,
+        border_w: 2.0,
+        ..Default::default()
+    };
+    assert_golden(
+        &MetaGenerator::for_sprite(&sprite, &texture::ui_texture(), guid()),
+        "sprite.png.meta",
+    );
+}
+
+#[cfg(feature = "audio")]
+#[test]
+fn audio_meta_matches_golden() {
+    use unity_asset_decode::audio::{AudioClip, AudioClipMeta, AudioCompressionFormat};
+
+    let clip = AudioClip {
+        name: "bgm_title".to_string(),
+        meta: AudioClipMeta::Modern {
+            load_type: 2,
+            channels: 2,
+            frequency: 48000,
+            bits_per_sample: 16,
+            length: 92.5,
+            is_tracker_format: false,
+            subsound_index: 0,
+            preload_audio_data: false,
+            load_in_background: true,
+            legacy_3d: true,
+            compression_format: AudioCompressionFormat::Vorbis,
+        },
+        ambisonic: Some(false),
+        ..Default::default()
+    };
+    assert_golden(&MetaGenerator::for_audio(&clip, guid()), "audio.ogg.meta");
+}
+
+#[cfg(feature = "mesh")]
+#[test]
+fn mesh_meta_matches_golden() {
+    use unity_asset_decode::mesh::Mesh;
+
+    let mesh = Mesh {
+        name: "rock_01".to_string(),
+        index_format: 1,
+        is_readable: false,
+        mesh_compression: 2,
+        ..Default::default()
+    };
+    assert_golden(&MetaGenerator::for_mesh(&mesh, guid()), "mesh.obj.meta");
+}
+
+#[test]
+fn derived_guid_is_written_as_meta_hex() {
+    let guid = UnityGuid::derived("char_118_yuki.ab", 5482297458062616526);
+    let doc = MetaGenerator::for_text(guid);
+    let text = doc.dump_yaml().unwrap();
+    assert!(text.contains(&format!("\nguid: {guid}\n")));
+    assert_eq!(
+        text.lines().nth(1).unwrap()["guid: ".len()..]
+            .parse::<UnityGuid>()
+            .unwrap(),
+        guid
+    );
+}
+
+#[cfg(feature = "sprite")]
+#[test]
+fn sprite_meta_from_bundle_keeps_source_sprite_settings() {
+    use unity_asset_decode::asset::SerializedFileParser;
+    use unity_asset_decode::bundle::BundleParser;
+    use unity_asset_decode::object::UnityObject;
+    use unity_asset_decode::sprite::SpriteProcessor;
+    use unity_asset_decode::texture::TextureProcessor;
+    use unity_asset_decode::unity_version::UnityVersion;
+
+    const SPRITE: i64 = -8325468307350463555;
+    const TEXTURE: i64 = -3875358842991402074;
+
+    let bytes = std::fs::read(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples/banner_1"),
+    )
+    .unwrap();
+    let bundle = BundleParser::from_bytes(bytes).unwrap();
+    let node = bundle
+        .nodes
+        .iter()
+        .find(|n| n.is_file() && !n.name.ends_with(".resS") && !n.name.ends_with(".resource"))
+        .unwrap();
+    let file = SerializedFileParser::from_bytes(bundle.extract_node_data(node).unwrap()).unwrap();
+    let version = UnityVersion::parse_version(&file.unity_version).unwrap_or_default();
+    let read = |path_id| {
+        UnityObject::from_serialized_file(&file, file.find_object(path_id).unwrap()).unwrap()
+    };
+
+    let texture = TextureProcessor::new(version.clone())
+        .convert_object(&read(TEXTURE))
+        .unwrap();
+    let sprite = SpriteProcessor::new(version)
+        .parse_sprite(&read(SPRITE))
+        .unwrap()
+        .sprite;
+
+    let guid = UnityGuid::derived("banner_1", SPRITE);
+    let doc = MetaGenerator::for_sprite(&sprite, &texture, guid);
+    let meta = &doc.entries()[0];
+    assert_eq!(
+        meta.get("guid").and_then(|v| v.as_str()),
+        Some(guid.to_string().as_str())
+    );
+    let importer = meta
+        .get("TextureImporter")
+        .and_then(|v| v.as_object())
+        .unwrap();
+    assert_eq!(importer.get("textureType").unwrap().as_i64(), Some(8));
+    assert_eq!(
+        importer.get("spritePixelsToUnits").unwrap().as_f64(),
+        Some(sprite.pixels_to_units as f64)
+    );
+    let max_size = importer.get("maxTextureSize").unwrap().as_i64().unwrap();
+    assert!(max_size >= texture.width.max(texture.height) as i64);
+    assert_eq!(
+        importer
+            .get("mipmaps")
+            .and_then(|v| v.as_object())
+            .and_then(|m| m.get("sRGBTexture"))
+            .and_then(|v| v.as_i64()),
+        Some((texture.color_space == 1) as i64)
+    );
+}
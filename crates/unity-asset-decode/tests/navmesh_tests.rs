@@ -0,0 +1,194 @@
+//! NavMeshData parsing and walkable surface export.
+//!
+//! `navmesh_plane.json` is the typetree dump of a 10x10 plane baked with the default humanoid
+//! agent: one tile holding the inset ground quad plus a drop-down off-mesh connection.
+
+#![cfg(feature = "navmesh")]
+
+use indexmap::IndexMap;
+use unity_asset_binary::test_support::{object_properties, read_fixture};
+use unity_asset_core::UnityValue;
+use unity_asset_decode::navmesh::{NavMeshData, NavMeshExporter, NavMeshTile, TILE_MAGIC};
+
+fn plane() -> IndexMap<String, UnityValue> {
+    object_properties(&read_fixture("navmesh_plane.json"), "NavMeshData")
+}
+
+/// A tile payload with the given vertices and `(vertices, area)` ground polygons.
+fn tile_bytes(vertices: &[[f32; 3]], polygons: &[(&[u16], u8)]) -> Vec<u8> {
+    let mut data = Vec::new();
+    let ints = [
+        TILE_MAGIC,
+        7,
+        1,
+        0,
+        0,
+        0,
+        polygons.len() as i32,
+        vertices.len() as i32,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+    ];
+    for v in ints {
+        data.extend_from_slice(&v.to_le_bytes());
+    }
+    for v in [2.0f32, 0.5, 0.4, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0] {
+        data.extend_from_slice(&v.to_le_bytes());
+    }
+    for v in vertices.iter().flatten() {
+        data.extend_from_slice(&v.to_le_bytes());
+    }
+    for (indices, area) in polygons {
+        data.extend_from_slice(&u32::MAX.to_le_bytes());
+        for slot in 0..12 {
+            let v = indices.get(slot).copied().filter(|_| slot < 6).unwrap_or(0);
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.push(indices.len() as u8);
+        data.push(*area);
+    }
+    data
+}
+
+#[test]
+fn navmesh_plane_parses_tile_agent_and_links() {
+    let navmesh = NavMeshData::from_typetree(&plane()).unwrap();
+
+    assert_eq!(navmesh.agent.radius, 0.5);
+    assert_eq!(navmesh.agent.height, 2.0);
+    assert_eq!(navmesh.agent.climb, 0.4);
+    assert_eq!(navmesh.agent.slope, 45.0);
+    assert_eq!(navmesh.agent.tile_size, 256);
+
+    // The empty second tile is skipped.
+    assert_eq!(navmesh.tiles.len(), 1);
+    let tile = &navmesh.tiles[0];
+    assert_eq!(tile.version, 7);
+    assert_eq!(tile.vertices.len(), 6);
+    assert_eq!(tile.polygons.len(), 2);
+    assert_eq!(tile.polygons[0].vertices, [0, 3, 2, 1]);
+    assert_eq!(tile.polygons[0].area, 0);
+    assert!(tile.polygons[1].is_off_mesh_connection);
+    assert_eq!(tile.polygons[1].area, 2);
+    assert_eq!(tile.bounds_min, [-4.5, -2.0, -4.5]);
+    assert_eq!(tile.walkable_radius, 0.5);
+
+    assert_eq!(navmesh.polygon_count(), 1);
+    assert_eq!(navmesh.area_ids().into_iter().collect::<Vec<_>>(), [0]);
+
+    assert_eq!(navmesh.off_mesh_links.len(), 1);
+    let link = navmesh.off_mesh_links[0];
+    assert_eq!(link.start, [4.0, 0.0, 4.0]);
+    assert_eq!(link.end, [4.0, -2.0, 7.0]);
+    assert_eq!(link.area, 2);
+    assert!(!link.bidirectional);
+
+    assert_eq!(
+        navmesh.to_json()["tiles"][0]["polygons"][0]["vertices"][1],
+        3
+    );
+}
+
+#[test]
+fn navmesh_plane_exports_a_quad() {
+    let navmesh = NavMeshData::from_typetree(&plane()).unwrap();
+    let mesh = navmesh.walkable_mesh();
+    assert_eq!(mesh.triangles, [[0, 3, 2], [0, 2, 1]]);
+    assert_eq!(mesh.areas, [0, 0]);
+    for corner in [
+        [-4.5, 0.0, -4.5],
+        [4.5, 0.0, -4.5],
+        [4.5, 0.0, 4.5],
+        [-4.5, 0.0, 4.5],
+    ] {
+        assert!(mesh.vertices.contains(&corner), "{corner:?}");
+    }
+
+    let obj = NavMeshExporter::to_obj(&navmesh, None);
+    assert!(obj.contains("o NavMesh\n"));
+    assert!(obj.contains("v -4.5 0 -4.5\n"));
+    assert!(obj.contains("g area_0\nusemtl area_0\nf 1 4 3\nf 1 3 2\n"));
+    assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), 2);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("plane.obj");
+    NavMeshExporter::export_obj(&navmesh, &path).unwrap();
+    let written = std::fs::read_to_string(&path).unwrap();
+    assert!(written.contains("mtllib plane.mtl\n"));
+    let mtl = std::fs::read_to_string(dir.path().join("plane.mtl")).unwrap();
+    assert!(mtl.starts_with("newmtl area_0\nKd 0 0.75 1\n"));
+}
+
+#[test]
+fn navmesh_export_groups_areas_and_applies_placement() {
+    let mut properties = plane();
+    let vertices = [
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [1.0, 0.0, 1.0],
+        [0.0, 0.0, 1.0],
+        [2.0, 0.0, 0.0],
+    ];
+    let payload = tile_bytes(&vertices, &[(&[0, 3, 2, 1], 0), (&[1, 2, 4], 3)]);
+    properties.insert(
+        "m_NavMeshTiles".to_string(),
+        UnityValue::Array(vec![UnityValue::Object(IndexMap::from([(
+            "m_MeshData".to_string(),
+            UnityValue::Bytes(payload),
+        )]))]),
+    );
+    // 90 degrees about +Y, then lifted by 10.
+    let half = std::f64::consts::FRAC_1_SQRT_2;
+    properties.insert(
+        "m_Rotation".to_string(),
+        UnityValue::Object(IndexMap::from([
+            ("x".to_string(), UnityValue::Float(0.0)),
+            ("y".to_string(), UnityValue::Float(half)),
+            ("z".to_string(), UnityValue::Float(0.0)),
+            ("w".to_string(), UnityValue::Float(half)),
+        ])),
+    );
+    properties.insert(
+        "m_Position".to_string(),
+        UnityValue::Object(IndexMap::from([
+            ("x".to_string(), UnityValue::Float(0.0)),
+            ("y".to_string(), UnityValue::Float(10.0)),
+            ("z".to_string(), UnityValue::Float(0.0)),
+        ])),
+    );
+
+    let navmesh = NavMeshData::from_typetree(&properties).unwrap();
+    assert_eq!(navmesh.area_ids().into_iter().collect::<Vec<_>>(), [0, 3]);
+    let mesh = navmesh.walkable_mesh();
+    assert_eq!(mesh.areas, [0, 0, 3]);
+    let [x, y, z] = mesh.vertices[4];
+    assert!(x.abs() < 1e-5 && (y - 10.0).abs() < 1e-5 && (z + 2.0).abs() < 1e-5);
+
+    let obj = NavMeshExporter::to_obj(&navmesh, None);
+    let area_3 = obj.split("g area_3\n").nth(1).unwrap();
+    assert_eq!(area_3, "usemtl area_3\nf 2 3 5\n");
+    assert!(NavMeshExporter::to_mtl(&navmesh).contains("newmtl area_3\n"));
+}
+
+#[test]
+fn navmesh_tile_rejects_bad_payloads() {
+    let payload = tile_bytes(&[[0.0; 3]; 3], &[(&[0, 1, 2], 0)]);
+    assert!(NavMeshTile::from_bytes(&payload).is_ok());
+
+    let mut bad_magic = payload.clone();
+    bad_magic[0] ^= 0xFF;
+    assert!(NavMeshTile::from_bytes(&bad_magic).is_err());
+    assert!(NavMeshTile::from_bytes(&payload[..payload.len() - 4]).is_err());
+
+    let out_of_range = tile_bytes(&[[0.0; 3]; 3], &[(&[0, 1, 5], 0)]);
+    let err = NavMeshTile::from_bytes(&out_of_range)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("vertex 5"), "{err}");
+}
@@ -0,0 +1,272 @@
+//! ParticleSystem typed parsing against typetree dumps captured from 2019.4 and 2021.3 projects.
+//!
+//! The fixtures are the JSON form of `read_typetree()` output, trimmed to the modules the parser
+//! types; integers and floats keep their typetree kinds through the JSON round trip.
+
+#![cfg(feature = "particles")]
+
+use indexmap::IndexMap;
+use unity_asset_binary::test_support::{object_properties, read_fixture};
+use unity_asset_core::UnityValue;
+use unity_asset_decode::particles::{
+    MinMaxCurve, MinMaxGradient, ParticleSystem, ParticleSystemParser, ParticleSystemRenderer,
+};
+use unity_asset_decode::unity_objects::ObjectRef;
+use unity_asset_decode::unity_version::UnityVersion;
+
+fn load(name: &str) -> (ParticleSystem, ParticleSystemRenderer) {
+    let dump = read_fixture(name);
+    let version = UnityVersion::parse_version(dump["unity_version"].as_str().unwrap()).unwrap();
+    let parser = ParticleSystemParser::new(version);
+
+    let system = parser
+        .parse_from_typetree(&object_properties(&dump, "ParticleSystem"))
+        .unwrap();
+    let renderer = parser
+        .parse_renderer_from_typetree(&object_properties(&dump, "ParticleSystemRenderer"))
+        .unwrap();
+    (system, renderer)
+}
+
+#[test]
+fn particle_system_2019_parses_main_emission_and_shape() {
+    let (system, renderer) = load("particle_system_2019.json");
+
+    let main = &system.main;
+    assert_eq!(main.duration, 3.0);
+    assert!(main.looping);
+    assert_eq!(main.max_particles, 200);
+    assert_eq!(main.scaling_mode, 1);
+    assert_eq!(
+        main.start_lifetime,
+        MinMaxCurve::TwoConstants { min: 1.5, max: 2.5 }
+    );
+    assert_eq!(main.start_size, MinMaxCurve::constant(0.5));
+    assert_eq!(main.gravity_modifier.range(), (0.2, 0.2));
+    assert!(main.start_size_y.is_none());
+
+    let MinMaxCurve::Curve { scalar, curve } = &main.start_speed else {
+        panic!("start speed should be a curve: {:?}", main.start_speed);
+    };
+    assert_eq!(*scalar, 4.0);
+    assert_eq!(curve.keys.len(), 2);
+    assert_eq!(curve.keys[0].value, 0.25);
+    assert_eq!(curve.keys[0].out_slope, 0.75);
+    assert_eq!(main.start_speed.range(), (1.0, 4.0));
+
+    let MinMaxGradient::Gradient { gradient } = &main.start_color else {
+        panic!("start color should be a gradient: {:?}", main.start_color);
+    };
+    assert_eq!(gradient.color_keys.len(), 2);
+    assert_eq!(gradient.color_keys[0].color, [1.0, 0.5, 0.0]);
+    assert_eq!(gradient.color_keys[1].time, 1.0);
+    assert_eq!(gradient.alpha_keys.len(), 3);
+    assert_eq!(gradient.alpha_keys[1].alpha, 0.5);
+    assert!((gradient.alpha_keys[1].time - 0.5).abs() < 1e-4);
+
+    let emission = &system.emission;
+    assert!(emission.enabled);
+    assert_eq!(emission.rate_over_time, MinMaxCurve::constant(40.0));
+    assert_eq!(emission.bursts.len(), 2);
+    assert_eq!(emission.bursts[0].count, MinMaxCurve::constant(30.0));
+    assert_eq!(
+        emission.bursts[1].count,
+        MinMaxCurve::TwoConstants {
+            min: 5.0,
+            max: 10.0
+        }
+    );
+    assert_eq!(emission.bursts[1].cycle_count, 3);
+    assert_eq!(emission.bursts[1].probability, 0.5);
+    assert_eq!(emission.max_burst_particles(), 40.0);
+
+    let shape = &system.shape;
+    assert_eq!(shape.shape_name(), "Cone");
+    assert_eq!(shape.angle, 15.0);
+    assert_eq!(shape.radius, 0.8);
+    assert_eq!(shape.arc, 360.0);
+    assert_eq!(shape.rotation, [-90.0, 0.0, 0.0]);
+    assert!(shape.mesh.is_none());
+
+    assert!(renderer.belongs_to(&system));
+    assert_eq!(renderer.render_mode_name(), "Stretch");
+    assert_eq!(renderer.sorting_order, 5);
+    assert_eq!(
+        renderer.materials,
+        vec![ObjectRef::new(0, -4203221871227843091)]
+    );
+
+    let info = system.info(Some(&renderer));
+    assert_eq!(info.start_lifetime, [1.5, 2.5]);
+    assert_eq!(info.shape, "Cone");
+    assert_eq!(info.burst_count, 2);
+    // 40/s * 2.5s + 40 from bursts, under the 200 cap.
+    assert_eq!(info.estimated_max_alive, 140);
+
+    let json = info.to_json();
+    assert_eq!(json["render_mode"], "Stretch");
+    assert_eq!(json["max_particles"], 200);
+}
+
+#[test]
+fn particle_system_2021_parses_size_3d_two_curves_and_box_shape() {
+    let (system, renderer) = load("particle_system_2021.json");
+
+    let main = &system.main;
+    assert_eq!(main.duration, 1.5);
+    assert!(!main.looping);
+    assert_eq!(main.simulation_space, 1);
+    assert_eq!(main.max_particles, 50);
+    assert_eq!(
+        main.start_delay,
+        MinMaxCurve::TwoConstants { min: 0.1, max: 0.5 }
+    );
+    assert_eq!(
+        main.start_speed,
+        MinMaxCurve::TwoConstants { min: 2.0, max: 8.0 }
+    );
+    assert_eq!(
+        main.start_color,
+        MinMaxGradient::TwoColors {
+            min: [0.0, 0.0, 1.0, 1.0],
+            max: [1.0, 0.0, 0.0, 0.5],
+        }
+    );
+
+    assert!(main.size_3d);
+    assert_eq!(main.start_size_y, Some(MinMaxCurve::constant(3.0)));
+    let MinMaxCurve::TwoCurves {
+        scalar,
+        min_scalar,
+        min,
+        max,
+    } = &main.start_size
+    else {
+        panic!("start size should be two curves: {:?}", main.start_size);
+    };
+    assert_eq!((*scalar, *min_scalar), (2.0, 1.0));
+    assert_eq!(min.keys[1].value, 0.5);
+    assert_eq!(max.keys[0].value, 0.5);
+    assert_eq!(main.start_size.range(), (0.25, 2.0));
+    assert_eq!(main.gravity_modifier.range(), (-1.0, 0.0));
+
+    let emission = &system.emission;
+    assert_eq!(emission.rate_over_time, MinMaxCurve::constant(0.0));
+    assert_eq!(
+        emission.rate_over_distance,
+        MinMaxCurve::TwoConstants {
+            min: 4.0,
+            max: 12.0
+        }
+    );
+    assert!(emission.bursts.is_empty());
+
+    let shape = &system.shape;
+    assert_eq!(shape.shape_name(), "Box");
+    assert_eq!(shape.position, [0.0, 1.0, 0.0]);
+    assert_eq!(shape.scale, [2.0, 1.0, 3.0]);
+    assert_eq!(shape.arc, 180.0);
+    assert!(shape.align_to_direction);
+    assert_eq!(shape.random_direction_amount, 0.25);
+
+    assert_eq!(renderer.render_mode_name(), "Mesh");
+    assert_eq!(renderer.mesh, Some(ObjectRef::new(0, 4300000)));
+    assert_eq!(renderer.materials.len(), 2);
+
+    let json = system.to_json();
+    assert_eq!(json["main"]["start_speed"]["mode"], "two_constants");
+    assert_eq!(json["main"]["start_size"]["mode"], "two_curves");
+    assert_eq!(json["shape"]["scale"][2], 3.0);
+}
+
+#[test]
+fn legacy_5x_emission_and_shape_layout() {
+    let object = |fields: Vec<(&str, UnityValue)>| {
+        UnityValue::Object(
+            fields
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        )
+    };
+    // 5.3: no `minScalar`; the lower constant is `minCurve` key 0 relative to `scalar`.
+    let lifetime = object(vec![
+        ("scalar", UnityValue::Float(4.0)),
+        ("minMaxState", UnityValue::Integer(3)),
+        (
+            "minCurve",
+            object(vec![(
+                "m_Curve",
+                UnityValue::Array(vec![object(vec![("value", UnityValue::Float(0.5))])]),
+            )]),
+        ),
+    ]);
+    let constant = |v: f64| {
+        object(vec![
+            ("scalar", UnityValue::Float(v)),
+            ("minMaxState", UnityValue::Integer(0)),
+        ])
+    };
+
+    let mut properties = IndexMap::new();
+    properties.insert("lengthInSec".to_string(), UnityValue::Float(2.0));
+    properties.insert("startDelay".to_string(), UnityValue::Float(0.25));
+    properties.insert("speed".to_string(), UnityValue::Float(0.5));
+    properties.insert(
+        "InitialModule".to_string(),
+        object(vec![
+            ("startLifetime", lifetime),
+            ("gravityModifier", UnityValue::Float(1.0)),
+            ("maxNumParticles", UnityValue::Integer(64)),
+        ]),
+    );
+    properties.insert(
+        "EmissionModule".to_string(),
+        object(vec![
+            ("enabled", UnityValue::Bool(true)),
+            ("m_Type", UnityValue::Integer(0)),
+            ("rate", constant(10.0)),
+            ("m_BurstCount", UnityValue::Integer(1)),
+            ("time0", UnityValue::Float(0.5)),
+            ("cnt0", UnityValue::Integer(20)),
+            ("cntmax0", UnityValue::Integer(25)),
+        ]),
+    );
+    properties.insert(
+        "ShapeModule".to_string(),
+        object(vec![
+            ("type", UnityValue::Integer(5)),
+            ("radius", UnityValue::Float(2.0)),
+            ("boxX", UnityValue::Float(3.0)),
+            ("boxY", UnityValue::Float(4.0)),
+            ("boxZ", UnityValue::Float(5.0)),
+            ("randomDirection", UnityValue::Bool(true)),
+        ]),
+    );
+
+    let parser = ParticleSystemParser::new(UnityVersion::parse_version("5.3.4f1").unwrap());
+    let system = parser.parse_from_typetree(&properties).unwrap();
+
+    assert_eq!(system.main.start_delay, MinMaxCurve::constant(0.25));
+    assert_eq!(system.main.simulation_speed, 0.5);
+    assert_eq!(
+        system.main.start_lifetime,
+        MinMaxCurve::TwoConstants { min: 2.0, max: 4.0 }
+    );
+    assert_eq!(system.main.gravity_modifier, MinMaxCurve::constant(1.0));
+    assert_eq!(system.emission.rate_over_time, MinMaxCurve::constant(10.0));
+    assert_eq!(system.emission.bursts.len(), 1);
+    assert_eq!(
+        system.emission.bursts[0].count,
+        MinMaxCurve::TwoConstants {
+            min: 20.0,
+            max: 25.0
+        }
+    );
+    assert_eq!(system.shape.radius, 2.0);
+    assert_eq!(system.shape.scale, [3.0, 4.0, 5.0]);
+    assert_eq!(system.shape.random_direction_amount, 1.0);
+
+    properties.shift_remove("InitialModule");
+    assert!(parser.parse_from_typetree(&properties).is_err());
+}
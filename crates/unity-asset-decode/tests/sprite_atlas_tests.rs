@@ -0,0 +1,213 @@
+//! SpriteAtlas (class 687078895) parsing and extracting packed sprites through their
+//! `m_RenderDataKey`.
+
+#![cfg(feature = "sprite")]
+
+use indexmap::IndexMap;
+use unity_asset_core::{UnityValue, class_ids};
+use unity_asset_decode::bundle::BundleParser;
+use unity_asset_decode::sprite::{Sprite, SpriteParser, SpriteProcessor, SpriteRenderDataKey};
+use unity_asset_decode::texture::{Texture2D, TextureFormat};
+use unity_asset_decode::unity_version::UnityVersion;
+
+const ATLAS: i64 = -9222691446010724640;
+
+fn object(fields: Vec<(&str, UnityValue)>) -> UnityValue {
+    UnityValue::Object(
+        fields
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
+}
+
+fn pptr(path_id: i64) -> UnityValue {
+    object(vec![
+        ("m_FileID", UnityValue::Integer(0)),
+        ("m_PathID", UnityValue::Integer(path_id)),
+    ])
+}
+
+fn rect(x: f64, y: f64, width: f64, height: f64) -> UnityValue {
+    object(vec![
+        ("x", UnityValue::Float(x)),
+        ("y", UnityValue::Float(y)),
+        ("width", UnityValue::Float(width)),
+        ("height", UnityValue::Float(height)),
+    ])
+}
+
+fn key_value(key: SpriteRenderDataKey) -> UnityValue {
+    object(vec![
+        (
+            "first",
+            object(vec![(
+                "data",
+                UnityValue::Array(
+                    key.guid
+                        .iter()
+                        .map(|&w| UnityValue::Integer(w as i64))
+                        .collect(),
+                ),
+            )]),
+        ),
+        ("second", UnityValue::Integer(key.file_id)),
+    ])
+}
+
+#[test]
+fn sample_atlas_maps_every_packed_sprite() {
+    let bundle = BundleParser::from_bytes(
+        std::fs::read(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples/atlas_test"),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let file = &bundle.assets[0];
+    let version = UnityVersion::parse_version(&file.unity_version).unwrap();
+    let processor = SpriteProcessor::new(version);
+
+    let atlas_object = file.find_object_handle(ATLAS).unwrap().read().unwrap();
+    let atlas = processor.parse_sprite_atlas(&atlas_object).unwrap();
+    assert_eq!(atlas.name, "BuildingsWaterTowerAtlas");
+    assert_eq!(atlas.tag, "BuildingsWaterTowerAtlas");
+    assert!(!atlas.is_variant);
+    assert_eq!(atlas.packed_sprites.len(), 7);
+    assert_eq!(atlas.packed_sprite_refs.len(), 7);
+    assert_eq!(atlas.render_data_map.len(), 7);
+    assert_eq!(atlas.texture_path_id, 602278551932518654);
+
+    let mut found = 0;
+    for handle in file.object_handles() {
+        if handle.class_id() != class_ids::SPRITE {
+            continue;
+        }
+        let sprite = processor
+            .parse_sprite(&handle.read().unwrap())
+            .unwrap()
+            .sprite;
+        let index = atlas
+            .packed_sprite_refs
+            .iter()
+            .position(|r| r.path_id == handle.path_id())
+            .unwrap();
+        assert_eq!(atlas.packed_sprites[index], sprite.name);
+
+        let data = atlas.sprite_data(&sprite).unwrap();
+        assert_eq!(data.texture.unwrap().path_id, atlas.texture_path_id);
+        assert!(data.settings.packed && data.settings.is_tight());
+        // The sprite's own rect is relative to its source image, not the atlas page.
+        assert_eq!(
+            data.texture_rect.width,
+            sprite.render_data.texture_rect_width
+        );
+        assert_ne!(data.texture_rect.x, sprite.render_data.texture_rect_x);
+        found += 1;
+    }
+    assert_eq!(found, 7);
+
+    let texture = file.find_object_handle(atlas.texture_path_id).unwrap();
+    assert!(
+        processor
+            .parse_sprite_atlas(&texture.read().unwrap())
+            .is_err()
+    );
+}
+
+/// 8x8 RGBA32 with red, green, blue and yellow quadrants (top-left, top-right, bottom-left,
+/// bottom-right).
+fn quadrant_texture() -> Texture2D {
+    let mut texture = Texture2D {
+        width: 8,
+        height: 8,
+        format: TextureFormat::RGBA32,
+        ..Default::default()
+    };
+    for y in 0..8 {
+        for x in 0..8 {
+            texture.image_data.extend_from_slice(match (x < 4, y < 4) {
+                (true, true) => &[255, 0, 0, 255],
+                (false, true) => &[0, 255, 0, 255],
+                (true, false) => &[0, 0, 255, 255],
+                (false, false) => &[255, 255, 0, 255],
+            });
+        }
+    }
+    texture
+}
+
+#[test]
+fn packed_sprite_is_cropped_from_its_atlas_entry() {
+    let key = SpriteRenderDataKey {
+        guid: [0x1234, 0x5678, 0x9abc, 0xdef0],
+        file_id: 21300000,
+    };
+    let properties: IndexMap<String, UnityValue> = [
+        ("m_Name", UnityValue::String("UI".to_string())),
+        ("m_PackedSprites", UnityValue::Array(vec![pptr(77)])),
+        (
+            "m_PackedSpriteNamesToIndex",
+            UnityValue::Array(vec![UnityValue::String("Coin".to_string())]),
+        ),
+        (
+            "m_RenderDataMap",
+            UnityValue::Array(vec![object(vec![
+                ("first", key_value(key)),
+                (
+                    "second",
+                    object(vec![
+                        ("texture", pptr(5)),
+                        ("alphaTexture", pptr(0)),
+                        ("textureRect", rect(4.0, 0.0, 4.0, 4.0)),
+                        ("settingsRaw", UnityValue::Integer(1)),
+                        ("downscaleMultiplier", UnityValue::Float(1.0)),
+                    ]),
+                ),
+            ])]),
+        ),
+        ("m_Tag", UnityValue::String("UI".to_string())),
+        ("m_IsVariant", UnityValue::Bool(false)),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v))
+    .collect();
+    let atlas = SpriteParser::default()
+        .parse_atlas_from_typetree(&properties)
+        .unwrap();
+    assert_eq!(atlas.texture_path_id, 5);
+    assert_eq!(atlas.render_data(&key).unwrap().alpha_texture, None);
+
+    // The sprite's own m_RD still points at the red quadrant.
+    let mut sprite = Sprite {
+        name: "Coin".to_string(),
+        render_data_key: Some(key),
+        ..Default::default()
+    };
+    sprite.render_data.texture_rect_x = 0.0;
+    sprite.render_data.texture_rect_y = 4.0;
+    sprite.render_data.texture_rect_width = 4.0;
+    sprite.render_data.texture_rect_height = 4.0;
+
+    let texture = quadrant_texture();
+    let processor = SpriteProcessor::new(UnityVersion::default());
+    let pixels = |png: Vec<u8>| image::load_from_memory(&png).unwrap().to_rgba8();
+
+    let packed = pixels(
+        processor
+            .extract_sprite_from_atlas(&sprite, &atlas, &texture)
+            .unwrap(),
+    );
+    assert_eq!(packed.dimensions(), (4, 4));
+    assert!(packed.pixels().all(|p| p.0 == [255, 255, 0, 255]));
+
+    let own = pixels(processor.extract_sprite_image(&sprite, &texture).unwrap());
+    assert!(own.pixels().all(|p| p.0 == [255, 0, 0, 255]));
+
+    sprite.render_data_key = Some(SpriteRenderDataKey { file_id: 1, ..key });
+    assert!(
+        processor
+            .extract_sprite_from_atlas(&sprite, &atlas, &texture)
+            .is_err()
+    );
+}
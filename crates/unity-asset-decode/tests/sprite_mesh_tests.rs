@@ -0,0 +1,235 @@
+//! Tight-packed sprites: render mesh parsing, outlines and masked extraction.
+
+#![cfg(feature = "sprite")]
+
+use indexmap::IndexMap;
+use unity_asset_core::UnityValue;
+use unity_asset_decode::sprite::{Sprite, SpriteParser, SpriteProcessor, SpriteSettings};
+use unity_asset_decode::texture::{Texture2D, TextureFormat};
+use unity_asset_decode::unity_version::UnityVersion;
+
+const RED: [u8; 4] = [255, 0, 0, 255];
+const GREEN: [u8; 4] = [0, 255, 0, 255];
+const BLUE: [u8; 4] = [0, 0, 255, 255];
+const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+fn object(fields: Vec<(&str, UnityValue)>) -> UnityValue {
+    UnityValue::Object(
+        fields
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
+}
+
+fn vec2(x: f64, y: f64) -> UnityValue {
+    object(vec![
+        ("x", UnityValue::Float(x)),
+        ("y", UnityValue::Float(y)),
+    ])
+}
+
+fn channel(offset: i64, dimension: i64) -> UnityValue {
+    object(vec![
+        ("stream", UnityValue::Integer(0)),
+        ("offset", UnityValue::Integer(offset)),
+        ("format", UnityValue::Integer(0)),
+        ("dimension", UnityValue::Integer(dimension)),
+    ])
+}
+
+/// 48x32 atlas holding two tight-packed triangles with overlapping rects.
+///
+/// In Unity coordinates (bottom-left origin), pixels whose center has `x + y <= 32` belong to
+/// sprite A and those with `x + y >= 48` to sprite B; the band between them is filler.
+fn atlas() -> Texture2D {
+    let mut texture = Texture2D {
+        width: 48,
+        height: 32,
+        format: TextureFormat::RGBA32,
+        ..Default::default()
+    };
+    for row in 0..32 {
+        let y = 31 - row;
+        for x in 0..48 {
+            let sum = x + y + 1;
+            let color = if sum <= 32 {
+                RED
+            } else if sum >= 48 {
+                GREEN
+            } else {
+                BLUE
+            };
+            texture.image_data.extend_from_slice(&color);
+        }
+    }
+    texture
+}
+
+fn tight_sprite(x: f32) -> Sprite {
+    let mut sprite = Sprite {
+        rect_width: 32.0,
+        rect_height: 32.0,
+        ..Default::default()
+    };
+    sprite.render_data.texture_rect_x = x;
+    sprite.render_data.texture_rect_width = 32.0;
+    sprite.render_data.texture_rect_height = 32.0;
+    sprite.settings = SpriteSettings::from_raw(0b11);
+    sprite
+}
+
+/// Sprite A: lower-left triangle of atlas x 0..32, mesh given by positions only.
+fn sprite_a() -> Sprite {
+    let mut sprite = tight_sprite(0.0);
+    sprite.render_data.vertices = vec![[-0.16, -0.16, 0.0], [0.16, -0.16, 0.0], [-0.16, 0.16, 0.0]];
+    sprite.render_data.indices = vec![0, 1, 2];
+    sprite
+}
+
+/// Sprite B: upper-right triangle of atlas x 16..48, packed flipped horizontally, mesh with UVs.
+fn sprite_b() -> Sprite {
+    let mut sprite = tight_sprite(16.0);
+    sprite.settings.packing_rotation = SpriteSettings::ROTATION_FLIP_HORIZONTAL;
+    sprite.render_data.vertices = vec![[0.16, 0.16, 0.0], [-0.16, 0.16, 0.0], [0.16, -0.16, 0.0]];
+    sprite.render_data.uvs = vec![[1.0, 1.0], [16.0 / 48.0, 1.0], [1.0, 0.0]];
+    sprite.render_data.indices = vec![0, 1, 2];
+    sprite
+}
+
+fn extract(sprite: &Sprite) -> image::RgbaImage {
+    let png = SpriteProcessor::new(UnityVersion::default())
+        .extract_sprite_image(sprite, &atlas())
+        .unwrap();
+    image::load_from_memory(&png).unwrap().to_rgba8()
+}
+
+fn colors(image: &image::RgbaImage) -> Vec<[u8; 4]> {
+    let mut colors: Vec<[u8; 4]> = image.pixels().map(|p| p.0).collect();
+    colors.sort();
+    colors.dedup();
+    colors
+}
+
+#[test]
+fn adjacent_tight_sprites_extract_without_bleed() {
+    let a = extract(&sprite_a());
+    assert_eq!(a.dimensions(), (32, 32));
+    assert_eq!(a.get_pixel(0, 31).0, RED);
+    assert_eq!(a.get_pixel(31, 0).0, CLEAR);
+    assert_eq!(a.get_pixel(20, 5).0, CLEAR);
+    assert_eq!(colors(&a), vec![CLEAR, RED]);
+
+    // Flipped back: B's triangle is in the upper-left of the output.
+    let b = extract(&sprite_b());
+    assert_eq!(b.get_pixel(2, 2).0, GREEN);
+    assert_eq!(b.get_pixel(31, 31).0, CLEAR);
+    assert_eq!(b.get_pixel(28, 28).0, CLEAR);
+    assert_eq!(colors(&b), vec![CLEAR, GREEN]);
+}
+
+#[test]
+fn rect_packed_and_unmasked_sprites_keep_the_whole_rect() {
+    let mut sprite = sprite_a();
+    sprite.settings = SpriteSettings::default();
+    assert_eq!(colors(&extract(&sprite)), vec![BLUE, GREEN, RED]);
+}
+
+#[test]
+fn render_mesh_and_physics_shape_parse_from_typetree() {
+    // Three vertices: position (3 floats) + uv0 (2 floats), 20-byte stride.
+    let mut vertex_bytes = Vec::new();
+    for (pos, uv) in [
+        ([-0.16f32, -0.16, 0.0], [0.0f32, 0.0]),
+        ([0.16, -0.16, 0.0], [0.5, 0.0]),
+        ([-0.16, 0.16, 0.0], [0.0, 1.0]),
+    ] {
+        for v in pos.into_iter().chain(uv) {
+            vertex_bytes.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+    let empty = || channel(0, 0);
+    let render_data = object(vec![
+        ("textureRect", {
+            object(vec![
+                ("x", UnityValue::Float(8.0)),
+                ("y", UnityValue::Float(0.0)),
+                ("width", UnityValue::Float(32.0)),
+                ("height", UnityValue::Float(32.0)),
+            ])
+        }),
+        ("textureRectOffset", vec2(1.0, 2.0)),
+        ("settingsRaw", UnityValue::Integer(1 | 2 | (4 << 2))),
+        ("m_IndexBuffer", UnityValue::Bytes(vec![0, 0, 1, 0, 2, 0])),
+        (
+            "m_VertexData",
+            object(vec![
+                ("m_VertexCount", UnityValue::Integer(3)),
+                (
+                    "m_Channels",
+                    UnityValue::Array(vec![
+                        channel(0, 3),
+                        empty(),
+                        empty(),
+                        empty(),
+                        channel(12, 2),
+                    ]),
+                ),
+                ("m_DataSize", UnityValue::Bytes(vertex_bytes)),
+            ]),
+        ),
+    ]);
+
+    let mut properties = IndexMap::new();
+    properties.insert("m_Name".to_string(), UnityValue::String("Tri".to_string()));
+    properties.insert(
+        "m_Rect".to_string(),
+        object(vec![
+            ("x", UnityValue::Float(0.0)),
+            ("y", UnityValue::Float(0.0)),
+            ("width", UnityValue::Float(32.0)),
+            ("height", UnityValue::Float(32.0)),
+        ]),
+    );
+    properties.insert("m_RD".to_string(), render_data);
+
+    let parser = SpriteParser::new(UnityVersion::parse_version("2020.3.12f1").unwrap());
+    let mut sprite = parser.parse_from_typetree(&properties).unwrap();
+
+    assert!(sprite.settings.packed);
+    assert!(sprite.settings.is_tight());
+    assert_eq!(
+        sprite.settings.packing_rotation,
+        SpriteSettings::ROTATION_90
+    );
+    assert_eq!(sprite.texture_rect().x, 8.0);
+    assert_eq!(sprite.render_data.texture_rect_offset_y, 2.0);
+    assert_eq!(sprite.render_data.vertices[1], [0.16, -0.16, 0.0]);
+    assert_eq!(sprite.render_data.uvs[2], [0.0, 1.0]);
+    assert_eq!(sprite.render_data.indices, vec![0, 1, 2]);
+
+    // Without a physics shape the outline is the mesh boundary.
+    let outline = sprite.outline();
+    assert_eq!(outline.len(), 1);
+    assert_eq!(outline[0].len(), 3);
+    for (got, want) in outline[0]
+        .iter()
+        .zip([[0.0, 0.0], [32.0, 0.0], [0.0, 32.0]])
+    {
+        assert!((got[0] - want[0]).abs() < 1e-4 && (got[1] - want[1]).abs() < 1e-4);
+    }
+
+    properties.insert(
+        "m_PhysicsShape".to_string(),
+        UnityValue::Array(vec![UnityValue::Array(vec![
+            vec2(-0.16, -0.16),
+            vec2(0.0, -0.16),
+            vec2(-0.16, 0.0),
+        ])]),
+    );
+    sprite = parser.parse_from_typetree(&properties).unwrap();
+    assert_eq!(sprite.physics_shape.len(), 1);
+    let outline = sprite.outline();
+    assert_eq!(outline[0].len(), 3);
+    assert!((outline[0][1][0] - 16.0).abs() < 1e-4);
+}
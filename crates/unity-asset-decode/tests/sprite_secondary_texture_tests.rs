@@ -0,0 +1,129 @@
+//! Sprite secondary textures (2D lighting normal maps and masks).
+//!
+//! `sprite_normal_map_2020.json` is the typetree dump of an 8x8 sprite from a 2020.3 project
+//! with a `_NormalMap` in the same file and a `_MaskTex` in another one.
+
+#![cfg(feature = "sprite")]
+
+use indexmap::IndexMap;
+use unity_asset_binary::test_support::{object_properties, read_fixture};
+use unity_asset_core::UnityValue;
+use unity_asset_decode::sprite::{Sprite, SpriteParser, SpriteProcessor, SpriteResult};
+use unity_asset_decode::texture::{Texture2D, TextureFormat};
+use unity_asset_decode::unity_objects::ObjectRef;
+use unity_asset_decode::unity_version::UnityVersion;
+
+fn version() -> UnityVersion {
+    UnityVersion::parse_version("2020.3.33f1").unwrap()
+}
+
+fn fixture() -> IndexMap<String, UnityValue> {
+    object_properties(
+        &read_fixture("sprite_normal_map_2020.json")["Sprite"],
+        "properties",
+    )
+}
+
+fn sprite() -> Sprite {
+    SpriteParser::new(version())
+        .parse_from_typetree(&fixture())
+        .unwrap()
+}
+
+/// 32x16 texture filled with `background`, with the sprite's 8x8 rect at (16, 8) set to `fill`.
+fn texture(background: [u8; 4], fill: [u8; 4]) -> Texture2D {
+    let mut texture = Texture2D {
+        width: 32,
+        height: 16,
+        format: TextureFormat::RGBA32,
+        ..Default::default()
+    };
+    for row in 0..16 {
+        let y = 15 - row;
+        for x in 0..32 {
+            let inside = (16..24).contains(&x) && (8..16).contains(&y);
+            let color = if inside { fill } else { background };
+            texture.image_data.extend_from_slice(&color);
+        }
+    }
+    texture
+}
+
+#[test]
+fn secondary_textures_are_parsed() {
+    let sprite = sprite();
+
+    assert_eq!(
+        sprite.secondary_textures(),
+        [
+            ("_NormalMap".to_string(), ObjectRef::new(0, 3)),
+            ("_MaskTex".to_string(), ObjectRef::new(2, 41)),
+        ]
+    );
+    assert_eq!(sprite.render_data.texture_path_id, 2);
+}
+
+#[test]
+fn sprites_without_secondary_textures_still_parse() {
+    let mut properties = fixture();
+    let Some(UnityValue::Object(rd)) = properties.get_mut("m_RD") else {
+        panic!("fixture has no m_RD");
+    };
+    rd.shift_remove("secondaryTextures");
+
+    let sprite = SpriteParser::new(UnityVersion::parse_version("2019.1.14f1").unwrap())
+        .parse_from_typetree(&properties)
+        .unwrap();
+    assert!(sprite.secondary_textures().is_empty());
+    assert_eq!(sprite.rect_width, 8.0);
+}
+
+#[test]
+fn normal_map_is_cropped_like_the_main_image() {
+    let sprite = sprite();
+    let processor = SpriteProcessor::new(version());
+    let normal_flat = [128, 128, 255, 255];
+
+    let main = processor
+        .extract_sprite_image(&sprite, &texture([0, 0, 0, 0], [255, 0, 0, 255]))
+        .unwrap();
+    let normal = processor
+        .extract_sprite_image(&sprite, &texture([0, 0, 0, 255], normal_flat))
+        .unwrap();
+
+    let main = image::load_from_memory(&main).unwrap().to_rgba8();
+    let normal = image::load_from_memory(&normal).unwrap().to_rgba8();
+    assert_eq!(normal.dimensions(), main.dimensions());
+    assert_eq!(normal.dimensions(), (8, 8));
+    assert!(normal.pixels().all(|p| p.0 == normal_flat));
+}
+
+#[test]
+fn secondary_images_are_saved_with_name_suffix() {
+    let mut result = SpriteResult::new(sprite());
+    result.image_data = Some(vec![1]);
+    result.secondary_images = vec![
+        ("_NormalMap".to_string(), vec![2]),
+        ("Emission".to_string(), vec![3]),
+    ];
+
+    let dir = tempfile::tempdir().unwrap();
+    let written = result.save_images(dir.path()).unwrap();
+    let names: Vec<_> = written
+        .iter()
+        .map(|p| p.file_name().unwrap().to_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        ["Hero.png", "Hero_NormalMap.png", "Hero_Emission.png"]
+    );
+    assert_eq!(std::fs::read(&written[1]).unwrap(), [2]);
+}
+
+#[test]
+fn secondary_textures_are_a_2019_2_feature() {
+    let processor = SpriteProcessor::new(version());
+    assert!(processor.is_feature_supported("secondary_textures"));
+    let processor = SpriteProcessor::new(UnityVersion::parse_version("2019.1.14f1").unwrap());
+    assert!(!processor.is_feature_supported("secondary_textures"));
+}
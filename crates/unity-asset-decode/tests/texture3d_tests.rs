@@ -0,0 +1,124 @@
+//! Texture3D parsing and per-slice decoding
+//!
+//! Uses a synthetic 4x4x4 RGBA32 volume where every slice is filled with its own color.
+
+#![cfg(feature = "texture")]
+
+use indexmap::IndexMap;
+use unity_asset_core::UnityValue;
+use unity_asset_decode::texture::{
+    ExportOptions, SliceLayout, Texture3D, Texture3DConverter, TextureExporter, TextureFormat,
+};
+use unity_asset_decode::unity_version::UnityVersion;
+
+const SLICE_COLORS: [[u8; 4]; 4] = [
+    [255, 0, 0, 255],
+    [0, 255, 0, 255],
+    [0, 0, 255, 255],
+    [255, 255, 255, 128],
+];
+
+fn volume_bytes() -> Vec<u8> {
+    SLICE_COLORS
+        .iter()
+        .flat_map(|color| color.repeat(16))
+        .collect()
+}
+
+fn properties(format_key: &str, format: i64) -> IndexMap<String, UnityValue> {
+    let mut props = IndexMap::new();
+    props.insert("m_Name".to_string(), UnityValue::String("Lut".to_string()));
+    props.insert(format_key.to_string(), UnityValue::Integer(format));
+    props.insert("m_Width".to_string(), UnityValue::Integer(4));
+    props.insert("m_Height".to_string(), UnityValue::Integer(4));
+    props.insert("m_Depth".to_string(), UnityValue::Integer(4));
+    props.insert("m_MipCount".to_string(), UnityValue::Integer(1));
+    props.insert("m_DataSize".to_string(), UnityValue::Integer(256));
+    props.insert("image data".to_string(), UnityValue::Bytes(volume_bytes()));
+    props
+}
+
+fn lut() -> Texture3D {
+    // 2019.1+ stores a GraphicsFormat: 8 = R8G8B8A8_UNorm.
+    Texture3DConverter::new(UnityVersion::parse_version("2020.3.12f1").unwrap())
+        .from_typetree(&properties("m_Format", 8))
+        .unwrap()
+}
+
+#[test]
+fn texture3d_parses_dimensions_and_format() {
+    let texture = lut();
+    assert_eq!(texture.name, "Lut");
+    assert_eq!((texture.width, texture.height, texture.depth), (4, 4, 4));
+    assert_eq!(texture.format, TextureFormat::RGBA32);
+    assert_eq!(texture.graphics_format, Some(8));
+    assert_eq!(texture.slice_size(), 64);
+    assert_eq!(
+        texture.to_string(),
+        "Texture3D \"Lut\" 4x4x4 format=RGBA32 data=256"
+    );
+
+    // Older versions store a TextureFormat in `m_Format`.
+    let legacy = Texture3DConverter::new(UnityVersion::parse_version("2018.4.2f1").unwrap())
+        .from_typetree(&properties("m_Format", 4))
+        .unwrap();
+    assert_eq!(legacy.format, TextureFormat::RGBA32);
+    assert_eq!(legacy.graphics_format, None);
+}
+
+#[test]
+fn texture3d_decodes_each_slice() {
+    let texture = lut();
+    for (z, color) in SLICE_COLORS.iter().enumerate() {
+        let slice = texture.decode_slice(z).unwrap();
+        assert_eq!(slice.dimensions(), (4, 4));
+        assert!(slice.pixels().all(|p| p.0 == *color), "slice {z}");
+    }
+
+    let slices = texture.decode_all_slices().unwrap();
+    assert_eq!(slices.len(), 4);
+    assert_eq!(slices[3].get_pixel(2, 2).0, SLICE_COLORS[3]);
+
+    assert!(texture.decode_slice(4).is_err());
+}
+
+#[test]
+fn texture3d_rejects_compressed_and_short_data() {
+    let mut texture = lut();
+    texture.format = TextureFormat::DXT5;
+    let err = texture.decode_slice(0).unwrap_err().to_string();
+    assert!(err.contains("DXT5"), "{err}");
+
+    let mut texture = lut();
+    texture.image_data.truncate(200);
+    assert!(texture.decode_slice(2).is_ok());
+    assert!(texture.decode_slice(3).is_err());
+}
+
+#[test]
+fn texture3d_exports_separate_slices_or_a_strip() {
+    let texture = lut();
+    let dir = tempfile::tempdir().unwrap();
+
+    let written = TextureExporter::export_texture3d(
+        &texture,
+        dir.path().join("lut.png"),
+        &ExportOptions::png(),
+    )
+    .unwrap();
+    assert_eq!(written.len(), 4);
+    assert_eq!(written[2], dir.path().join("lut_2.png"));
+    let slice = image::open(&written[2]).unwrap().to_rgba8();
+    assert_eq!(slice.get_pixel(0, 0).0, SLICE_COLORS[2]);
+
+    let options = ExportOptions::png().with_slice_layout(SliceLayout::Strip);
+    let written =
+        TextureExporter::export_texture3d(&texture, dir.path().join("strip.png"), &options)
+            .unwrap();
+    assert_eq!(written, vec![dir.path().join("strip.png")]);
+    let strip = image::open(&written[0]).unwrap().to_rgba8();
+    assert_eq!(strip.dimensions(), (16, 4));
+    for (z, color) in SLICE_COLORS.iter().enumerate() {
+        assert_eq!(strip.get_pixel(z as u32 * 4 + 1, 3).0, *color);
+    }
+}
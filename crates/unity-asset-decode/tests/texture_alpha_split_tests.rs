@@ -0,0 +1,88 @@
+//! ETC1 alpha-split recombination.
+//!
+//! The pair is a 4x4 RGB texture with one color per row and a 4x4 grey mask with one alpha
+//! value per column, the layout the ETC1 "split alpha channel" import option produces.
+
+#![cfg(feature = "texture")]
+
+use unity_asset_decode::texture::{Texture2D, TextureFormat, TextureProcessor, merge_alpha};
+
+const ROWS: [[u8; 3]; 4] = [[255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 255]];
+const COLUMNS: [u8; 4] = [0, 85, 170, 255];
+
+fn rgb_texture() -> Texture2D {
+    let mut texture = Texture2D {
+        name: "Atlas".to_string(),
+        width: 4,
+        height: 4,
+        format: TextureFormat::RGBA32,
+        ..Default::default()
+    };
+    for row in ROWS {
+        for _ in 0..4 {
+            texture
+                .image_data
+                .extend_from_slice(&[row[0], row[1], row[2], 255]);
+        }
+    }
+    texture
+}
+
+fn alpha_texture(format: TextureFormat) -> Texture2D {
+    let mut texture = Texture2D {
+        name: "Atlas_alpha".to_string(),
+        width: 4,
+        height: 4,
+        format,
+        ..Default::default()
+    };
+    for _ in 0..4 {
+        for value in COLUMNS {
+            match format {
+                TextureFormat::Alpha8 => texture.image_data.push(value),
+                _ => texture
+                    .image_data
+                    .extend_from_slice(&[value, value, value, 255]),
+            }
+        }
+    }
+    texture
+}
+
+fn assert_merged(image: &image::RgbaImage) {
+    assert_eq!(image.dimensions(), (4, 4));
+    for y in 0..4 {
+        let [r, g, b, _] = image.get_pixel(0, y).0;
+        assert!(ROWS.contains(&[r, g, b]), "unexpected color at row {y}");
+        for (x, alpha) in COLUMNS.into_iter().enumerate() {
+            assert_eq!(
+                image.get_pixel(x as u32, y).0,
+                [r, g, b, alpha],
+                "pixel ({x}, {y})"
+            );
+        }
+    }
+}
+
+#[test]
+fn grey_mask_alpha_comes_from_red_channel() {
+    let merged = TextureProcessor::default()
+        .decode_with_alpha_split(&rgb_texture(), &alpha_texture(TextureFormat::RGBA32))
+        .unwrap();
+    assert_merged(&merged);
+}
+
+#[test]
+fn alpha8_mask_alpha_comes_from_alpha_channel() {
+    let merged = TextureProcessor::default()
+        .decode_with_alpha_split(&rgb_texture(), &alpha_texture(TextureFormat::Alpha8))
+        .unwrap();
+    assert_merged(&merged);
+}
+
+#[test]
+fn mismatched_dimensions_are_rejected() {
+    let rgb = image::RgbaImage::new(4, 4);
+    let alpha = image::RgbaImage::new(2, 2);
+    assert!(merge_alpha(rgb, &alpha, TextureFormat::RGBA32).is_err());
+}
@@ -0,0 +1,140 @@
+//! BC4, BC5 and BC6H through `TextureDecoder`, from hand-built 4x4 blocks.
+
+#![cfg(feature = "texture-advanced")]
+
+use unity_asset_decode::texture::{ExportOptions, Texture2D, TextureDecoder, TextureFormat};
+
+fn texture(format: TextureFormat, image_data: Vec<u8>) -> Texture2D {
+    Texture2D {
+        name: "block".to_string(),
+        width: 4,
+        height: 4,
+        format,
+        data_size: image_data.len() as i32,
+        image_data,
+        ..Default::default()
+    }
+}
+
+struct BitWriter {
+    block: Vec<u8>,
+    position: usize,
+}
+
+impl BitWriter {
+    fn new(bytes: usize) -> Self {
+        Self {
+            block: vec![0; bytes],
+            position: 0,
+        }
+    }
+
+    fn write(&mut self, value: u32, count: usize) {
+        for i in 0..count {
+            if value >> i & 1 == 1 {
+                let bit = self.position + i;
+                self.block[bit / 8] |= 1 << (bit % 8);
+            }
+        }
+        self.position += count;
+    }
+}
+
+/// A BC4 block: two endpoints and a 3-bit index per texel.
+fn bc4_block(e0: u8, e1: u8, indices: [u32; 16]) -> Vec<u8> {
+    let mut bits = BitWriter::new(8);
+    bits.write(e0.into(), 8);
+    bits.write(e1.into(), 8);
+    for index in indices {
+        bits.write(index, 3);
+    }
+    bits.block
+}
+
+/// BC6H mode 3 (one region, 10-bit endpoints stored as-is).
+fn bc6h_mode3_block(e0: [u32; 3], e1: [u32; 3], indices: [u32; 16]) -> Vec<u8> {
+    let mut bits = BitWriter::new(16);
+    bits.write(0b00011, 5);
+    for endpoint in [e0, e1] {
+        for channel in endpoint {
+            bits.write(channel, 10);
+        }
+    }
+    for (i, index) in indices.into_iter().enumerate() {
+        bits.write(index, if i == 0 { 3 } else { 4 });
+    }
+    bits.block
+}
+
+fn first_texel_differs(value: u32) -> [u32; 16] {
+    let mut indices = [0; 16];
+    indices[0] = value;
+    indices
+}
+
+#[test]
+fn bc4_decodes_to_grayscale() {
+    let block = bc4_block(200, 40, first_texel_differs(1));
+    assert_eq!(TextureFormat::BC4.calculate_data_size(4, 4), 8);
+    let image = TextureDecoder::new()
+        .decode(&texture(TextureFormat::BC4, block))
+        .unwrap();
+    assert_eq!(image.get_pixel(0, 0).0, [40, 40, 40, 255]);
+    assert!(image.pixels().skip(1).all(|p| p.0 == [200, 200, 200, 255]));
+}
+
+#[test]
+fn bc5_rebuilds_the_normal_z_unless_disabled() {
+    // X: 128 (flat) in the first texel, 255 (fully tilted) elsewhere; Y: 128 everywhere.
+    let data = [
+        bc4_block(255, 128, first_texel_differs(1)),
+        bc4_block(128, 128, [0; 16]),
+    ]
+    .concat();
+    assert_eq!(
+        TextureFormat::BC5.calculate_data_size(4, 4),
+        data.len() as u32
+    );
+    let bc5 = texture(TextureFormat::BC5, data);
+
+    let image = TextureDecoder::new().decode(&bc5).unwrap();
+    assert_eq!(image.get_pixel(0, 0).0, [128, 128, 255, 255]);
+    assert_eq!(image.get_pixel(1, 0).0, [255, 128, 128, 255]);
+
+    let raw = ExportOptions::png()
+        .with_bc5_normal_reconstruction(false)
+        .decoder()
+        .decode(&bc5)
+        .unwrap();
+    assert_eq!(raw.get_pixel(0, 0).0, [128, 128, 0, 255]);
+    assert_eq!(raw.get_pixel(1, 0).0, [255, 128, 0, 255]);
+}
+
+#[test]
+fn bc6h_is_tonemapped_with_the_configured_exposure() {
+    // Endpoint 495 unquantizes to half 1.0; index 15 picks the black endpoint.
+    let block = bc6h_mode3_block([495, 495, 495], [0, 0, 0], first_texel_differs(7));
+    let mut indices = [0; 16];
+    indices[1] = 15;
+    let block = [block, bc6h_mode3_block([495, 0, 495], [0, 0, 0], indices)].concat();
+    let mut bc6h = texture(TextureFormat::BC6H, block);
+    bc6h.width = 8;
+
+    let decoder = TextureDecoder::new();
+    assert!(decoder.can_decode(TextureFormat::BC6H));
+    let image = decoder.decode(&bc6h).unwrap();
+    // 1.0 / (1 + 1.0)
+    assert_eq!(image.get_pixel(1, 0).0, [128, 128, 128, 255]);
+    assert_eq!(image.get_pixel(4, 0).0, [128, 0, 128, 255]);
+    assert_eq!(image.get_pixel(5, 0).0, [0, 0, 0, 255]);
+
+    // 3.0 / (1 + 3.0)
+    let bright = ExportOptions::png()
+        .with_bc6h_exposure(3.0)
+        .decoder()
+        .decode(&bc6h)
+        .unwrap();
+    assert_eq!(bright.get_pixel(1, 0).0, [191, 191, 191, 255]);
+    // Index 7 of 15 sits just under halfway to black, so it stays darker.
+    assert!(bright.get_pixel(0, 0).0[0] < 191);
+}
@@ -0,0 +1,98 @@
+//! Streamed Texture2D data resolved from the containing bundle
+//!
+//! xinzexi_2_n_tex's 2048x976 ETC2 texture keeps its pixels in the bundle's `.resS` node.
+
+#![cfg(feature = "texture-advanced")]
+
+use unity_asset_decode::bundle::{AssetBundle, BundleParser};
+use unity_asset_decode::error::BinaryError;
+use unity_asset_decode::object::UnityObject;
+use unity_asset_decode::texture::{Texture2DConverter, TextureProcessor};
+use unity_asset_decode::unity_version::UnityVersion;
+
+const TEXTURE: i64 = 7993808429769519520;
+
+fn sample() -> (AssetBundle, UnityObject) {
+    let bundle =
+        BundleParser::from_bytes(include_bytes!("../../../tests/samples/xinzexi_2_n_tex").to_vec())
+            .unwrap();
+    let object = bundle.assets[0]
+        .find_object_handle(TEXTURE)
+        .unwrap()
+        .read()
+        .unwrap();
+    (bundle, object)
+}
+
+#[test]
+fn resolve_stream_data_reads_the_ress_range() {
+    let (bundle, object) = sample();
+    let converter = Texture2DConverter::new(UnityVersion::default());
+    let mut texture = converter.from_unity_object(&object).unwrap();
+    assert!(texture.is_streamed());
+    assert!(texture.image_data.is_empty());
+
+    converter
+        .resolve_stream_data(&mut texture, &bundle)
+        .unwrap();
+    assert_eq!(texture.image_data.len(), texture.stream_info.size as usize);
+    let node = bundle
+        .find_resource_node(&texture.stream_info.path)
+        .unwrap();
+    assert!(node.name.ends_with(".resS"));
+    assert_eq!(
+        texture.image_data,
+        bundle
+            .extract_node_range(
+                node,
+                texture.stream_info.offset,
+                texture.stream_info.size as u64
+            )
+            .unwrap()
+    );
+
+    let image = converter.decode_to_image(&texture).unwrap();
+    assert_eq!(image.dimensions(), (2048, 976));
+
+    // Already resolved: nothing is read again.
+    texture.stream_info.path = "archive:/CAB-0/CAB-0.resS".to_string();
+    converter
+        .resolve_stream_data(&mut texture, &bundle)
+        .unwrap();
+}
+
+#[test]
+fn resource_in_another_bundle_is_a_typed_error() {
+    let (bundle, object) = sample();
+    let converter = Texture2DConverter::new(UnityVersion::default());
+    let mut texture = converter.from_unity_object(&object).unwrap();
+    let path = "archive:/CAB-0123456789abcdef/CAB-0123456789abcdef.resS";
+    texture.stream_info.path = path.to_string();
+
+    match converter.resolve_stream_data(&mut texture, &bundle) {
+        Err(BinaryError::ExternalResourceMissing { path: missing }) => assert_eq!(missing, path),
+        other => panic!("expected ExternalResourceMissing, got {:?}", other),
+    }
+    assert!(texture.image_data.is_empty());
+}
+
+#[test]
+fn process_and_export_from_bundle_writes_streamed_texture() {
+    let (bundle, object) = sample();
+    let processor = TextureProcessor::default();
+    let dir = tempfile::tempdir().unwrap();
+
+    // Without the bundle there are no pixels to decode.
+    assert!(
+        processor
+            .process_and_export(&object, dir.path().join("missing.png"))
+            .is_err()
+    );
+
+    let out = dir.path().join("xinzexi_2_n_tex.png");
+    processor
+        .process_and_export_from_bundle(&object, &bundle, &out)
+        .unwrap();
+    let image = image::open(&out).unwrap();
+    assert_eq!((image.width(), image.height()), (2048, 976));
+}
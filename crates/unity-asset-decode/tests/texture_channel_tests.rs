@@ -0,0 +1,155 @@
+//! Channel extraction, swizzles and normal map reconstruction on known pixels.
+
+#![cfg(feature = "texture")]
+
+use image::{Rgba, RgbaImage};
+use unity_asset_decode::texture::{
+    Channel, ChannelTransform, ExportOptions, TransformRule, extract_channel,
+    reconstruct_normal_bc5, reconstruct_normal_dxt5nm, swizzle,
+};
+
+/// A one-row image with one pixel per entry.
+fn row(pixels: &[[u8; 4]]) -> RgbaImage {
+    RgbaImage::from_fn(pixels.len() as u32, 1, |x, _| Rgba(pixels[x as usize]))
+}
+
+fn pixels(image: &RgbaImage) -> Vec<[u8; 4]> {
+    image.pixels().map(|p| p.0).collect()
+}
+
+/// Metallic in R, smoothness in A.
+const PACKED: [[u8; 4]; 3] = [[10, 20, 30, 40], [200, 0, 0, 255], [0, 255, 128, 1]];
+
+#[test]
+fn extract_channel_copies_one_channel() {
+    let image = row(&PACKED);
+    let alpha = extract_channel(&image, Channel::A);
+    assert_eq!(alpha.dimensions(), (3, 1));
+    assert_eq!(alpha.as_raw(), &vec![40, 255, 1]);
+    assert_eq!(
+        extract_channel(&image, Channel::R).as_raw(),
+        &vec![10, 200, 0]
+    );
+
+    // As a transform the channel becomes opaque grey.
+    let grey = "g"
+        .parse::<ChannelTransform>()
+        .unwrap()
+        .apply(&image)
+        .unwrap();
+    assert_eq!(
+        pixels(&grey),
+        [[20, 20, 20, 255], [0, 0, 0, 255], [255, 255, 255, 255]]
+    );
+}
+
+#[test]
+fn swizzle_reorders_and_fills_channels() {
+    let image = row(&PACKED);
+    assert_eq!(
+        pixels(&swizzle(&image, "agbr").unwrap()),
+        [[40, 20, 30, 10], [255, 0, 0, 200], [1, 255, 128, 0]]
+    );
+    assert_eq!(
+        pixels(&swizzle(&image, "RRR1").unwrap()),
+        [[10, 10, 10, 255], [200, 200, 200, 255], [0, 0, 0, 255]]
+    );
+    assert_eq!(
+        pixels(&swizzle(&image, "b0g1").unwrap()),
+        [[30, 0, 20, 255], [0, 0, 0, 255], [128, 0, 255, 255]]
+    );
+    for bad in ["rgb", "rgbaa", "rgbx", ""] {
+        assert!(swizzle(&image, bad).is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn dxt5nm_rebuilds_z_from_alpha_and_green() {
+    // (r, g, b, a) with X in alpha and Y in green; R and B are ignored.
+    let image = row(&[
+        [255, 128, 0, 128],  // flat: (0, 0, 1)
+        [255, 128, 0, 255],  // +X: Z clamps to 0
+        [0, 128, 77, 218],   // tilted along X
+        [255, 200, 255, 64], // tilted along -X and +Y
+        [9, 0, 9, 0],        // (-1, -1): saturated, Z = 0
+        [1, 90, 2, 180],
+    ]);
+    assert_eq!(
+        pixels(&reconstruct_normal_dxt5nm(&image)),
+        [
+            [128, 128, 255, 255],
+            [255, 128, 128, 255],
+            [218, 128, 217, 255],
+            [64, 200, 211, 255],
+            [0, 0, 128, 255],
+            [180, 90, 237, 255],
+        ]
+    );
+}
+
+#[test]
+fn bc5_rebuilds_z_from_red_and_green() {
+    let image = row(&[[128, 128, 0, 0], [218, 128, 0, 0], [64, 200, 13, 77]]);
+    assert_eq!(
+        pixels(&reconstruct_normal_bc5(&image)),
+        [
+            [128, 128, 255, 255],
+            [218, 128, 217, 255],
+            [64, 200, 211, 255]
+        ]
+    );
+
+    // X and Y bytes survive the unpack/repack unchanged for every value.
+    let ramp = RgbaImage::from_fn(256, 1, |x, _| Rgba([x as u8, 255 - x as u8, 0, 0]));
+    for (x, p) in reconstruct_normal_bc5(&ramp).pixels().enumerate() {
+        assert_eq!((p.0[0], p.0[1]), (x as u8, 255 - x as u8));
+    }
+}
+
+#[test]
+fn export_options_apply_the_first_matching_rule() {
+    let options = ExportOptions::png()
+        .with_channel_transform("slot:_BumpMap=dxt5nm".parse().unwrap())
+        .with_channel_transform("*_MS=swizzle:aaa1".parse().unwrap())
+        .with_channel_transform(TransformRule::by_name(
+            "hero_?",
+            ChannelTransform::NormalBc5,
+        ));
+
+    let rule: TransformRule = "slot:_MetallicGlossMap=a".parse().unwrap();
+    assert_eq!(
+        rule,
+        TransformRule::by_slot("_MetallicGlossMap", ChannelTransform::Extract(Channel::A))
+    );
+    assert_eq!(rule.transform.to_string(), "a");
+    assert!("no-equals".parse::<TransformRule>().is_err());
+    assert!("*=rgbq".parse::<TransformRule>().is_err());
+
+    let bump = vec!["_BumpMap".to_string()];
+    assert_eq!(
+        options.channel_transform_for("Rock_MS", &bump),
+        Some(&ChannelTransform::NormalDxt5nm)
+    );
+    assert_eq!(
+        options.channel_transform_for("rock_ms", &[]),
+        Some(&ChannelTransform::Swizzle("aaa1".to_string()))
+    );
+    assert_eq!(
+        options.channel_transform_for("Hero_N", &[]),
+        Some(&ChannelTransform::NormalBc5)
+    );
+    assert_eq!(options.channel_transform_for("Hero_NM", &[]), None);
+
+    let image = row(&PACKED);
+    let out = options
+        .apply_channel_transforms(image.clone(), "Rock_MS", &[])
+        .unwrap();
+    assert_eq!(
+        pixels(&out),
+        [[40, 40, 40, 255], [255, 255, 255, 255], [1, 1, 1, 255]]
+    );
+    let untouched = options
+        .apply_channel_transforms(image.clone(), "Albedo", &[])
+        .unwrap();
+    assert_eq!(untouched, image);
+}
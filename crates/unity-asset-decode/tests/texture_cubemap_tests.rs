@@ -0,0 +1,115 @@
+//! Cubemap cross and equirectangular export
+//!
+//! Six solid-color 8x8 faces in Unity order (+X, -X, +Y, -Y, +Z, -Z).
+
+#![cfg(feature = "texture")]
+
+use image::{Rgba, RgbaImage};
+use unity_asset_decode::texture::{CubemapExporter, ExportOptions};
+
+const POS_X: [u8; 4] = [255, 0, 0, 255];
+const NEG_X: [u8; 4] = [0, 255, 0, 255];
+const POS_Y: [u8; 4] = [0, 0, 255, 255];
+const NEG_Y: [u8; 4] = [255, 255, 0, 255];
+const POS_Z: [u8; 4] = [255, 255, 255, 255];
+const NEG_Z: [u8; 4] = [0, 0, 0, 255];
+
+fn faces() -> [RgbaImage; 6] {
+    [POS_X, NEG_X, POS_Y, NEG_Y, POS_Z, NEG_Z].map(|c| RgbaImage::from_pixel(8, 8, Rgba(c)))
+}
+
+#[test]
+fn equirectangular_puts_faces_at_their_directions() {
+    let panorama = CubemapExporter::default()
+        .equirectangular(&faces(), 64, 32)
+        .unwrap();
+    assert_eq!(panorama.dimensions(), (64, 32));
+
+    // Poles.
+    assert_eq!(panorama.get_pixel(5, 0).0, POS_Y);
+    assert_eq!(panorama.get_pixel(50, 31).0, NEG_Y);
+    // Equator: center looks down +Z, +X a quarter turn right, -X left, -Z at the wrap seam.
+    assert_eq!(panorama.get_pixel(32, 16).0, POS_Z);
+    assert_eq!(panorama.get_pixel(48, 16).0, POS_X);
+    assert_eq!(panorama.get_pixel(16, 16).0, NEG_X);
+    assert_eq!(panorama.get_pixel(0, 16).0, NEG_Z);
+    assert_eq!(panorama.get_pixel(63, 16).0, NEG_Z);
+}
+
+#[test]
+fn equirectangular_blends_across_face_seams() {
+    let panorama = CubemapExporter::default()
+        .equirectangular(&faces(), 64, 32)
+        .unwrap();
+
+    // Just past the +Z/+X seam: mostly +X red with some +Z white mixed in.
+    let seam = panorama.get_pixel(40, 16).0;
+    assert_eq!(seam[0], 255);
+    assert!(seam[1] > 0 && seam[1] < 128, "{seam:?}");
+    assert_eq!(seam[1], seam[2]);
+
+    // Just below the top: the +Y/+Z edge blends blue with white.
+    let edge = panorama.get_pixel(32, 7).0;
+    assert_eq!(edge[2], 255);
+    assert!(edge[0] > 0 && edge[0] < 255, "{edge:?}");
+}
+
+#[test]
+fn equirectangular_size_falls_back_to_options() {
+    let exporter = CubemapExporter::default();
+    assert_eq!(
+        exporter
+            .equirectangular(&faces(), 0, 0)
+            .unwrap()
+            .dimensions(),
+        (32, 16)
+    );
+
+    let exporter = CubemapExporter::new(ExportOptions::png().with_equirect_width(Some(20)));
+    assert_eq!(
+        exporter
+            .equirectangular(&faces(), 0, 0)
+            .unwrap()
+            .dimensions(),
+        (20, 10)
+    );
+}
+
+#[test]
+fn cross_layout_places_and_flips_faces() {
+    let mut faces = faces();
+    // Unity rows are bottom-up: row 0 of the +Z data is the bottom edge of the face.
+    for x in 0..8 {
+        faces[4].put_pixel(x, 0, Rgba([255, 0, 255, 255]));
+    }
+
+    let cross = CubemapExporter::default().cross(&faces).unwrap();
+    assert_eq!(cross.dimensions(), (32, 24));
+    assert_eq!(cross.get_pixel(12, 4).0, POS_Y);
+    assert_eq!(cross.get_pixel(4, 12).0, NEG_X);
+    assert_eq!(cross.get_pixel(20, 12).0, POS_X);
+    assert_eq!(cross.get_pixel(28, 12).0, NEG_Z);
+    assert_eq!(cross.get_pixel(12, 20).0, NEG_Y);
+    assert_eq!(cross.get_pixel(0, 0).0, [0, 0, 0, 0]);
+    assert_eq!(cross.get_pixel(12, 15).0, [255, 0, 255, 255]);
+    assert_eq!(cross.get_pixel(12, 8).0, POS_Z);
+
+    let unflipped = CubemapExporter::default()
+        .with_flip_y(false)
+        .cross(&faces)
+        .unwrap();
+    assert_eq!(unflipped.get_pixel(12, 8).0, [255, 0, 255, 255]);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sky_cross.png");
+    CubemapExporter::default()
+        .export_cross(&faces, &path)
+        .unwrap();
+    assert_eq!(
+        image::open(&path).unwrap().to_rgba8().dimensions(),
+        (32, 24)
+    );
+
+    faces[3] = RgbaImage::new(4, 4);
+    assert!(CubemapExporter::default().cross(&faces).is_err());
+}
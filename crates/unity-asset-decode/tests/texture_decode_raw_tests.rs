@@ -0,0 +1,116 @@
+//! Raw texture decode tests
+//!
+//! Verifies channel order of `TextureDecoder::decode_raw` for every `PixelLayout` using a
+//! known 2x2 pattern (red, green, blue, semi-transparent white).
+
+#![cfg(feature = "texture")]
+
+use unity_asset_decode::texture::{
+    DecodedPixels, PixelLayout, Texture2D, TextureDecoder, TextureFormat,
+};
+
+/// Expected pixels in RGBA order, row-major.
+const PATTERN_RGBA: [[u8; 4]; 4] = [
+    [255, 0, 0, 255],
+    [0, 255, 0, 255],
+    [0, 0, 255, 255],
+    [255, 255, 255, 128],
+];
+
+fn texture(format: TextureFormat, reorder: impl Fn([u8; 4]) -> [u8; 4]) -> Texture2D {
+    let mut texture = Texture2D::new("pattern".to_string(), 2, 2, format);
+    texture.image_data = PATTERN_RGBA.iter().flat_map(|&px| reorder(px)).collect();
+    texture
+}
+
+fn rgba32() -> Texture2D {
+    texture(TextureFormat::RGBA32, |px| px)
+}
+
+fn bgra32() -> Texture2D {
+    texture(TextureFormat::BGRA32, |[r, g, b, a]| [b, g, r, a])
+}
+
+fn argb32() -> Texture2D {
+    texture(TextureFormat::ARGB32, |[r, g, b, a]| [a, r, g, b])
+}
+
+fn pixels_u8(decoded: &DecodedPixels) -> Vec<[u8; 4]> {
+    decoded
+        .data
+        .chunks_exact(4)
+        .map(|px| [px[0], px[1], px[2], px[3]])
+        .collect()
+}
+
+fn assert_shape(decoded: &DecodedPixels, layout: PixelLayout) {
+    assert_eq!(decoded.layout, layout);
+    assert_eq!((decoded.width, decoded.height), (2, 2));
+    assert_eq!(decoded.stride, 2 * layout.bytes_per_pixel());
+    assert_eq!(decoded.data.len(), decoded.stride * 2);
+}
+
+#[test]
+fn rgba8_layout_is_rgba_for_all_sources() {
+    let decoder = TextureDecoder::new();
+    for texture in [rgba32(), bgra32(), argb32()] {
+        let decoded = decoder.decode_raw(&texture, PixelLayout::Rgba8).unwrap();
+        assert_shape(&decoded, PixelLayout::Rgba8);
+        assert_eq!(pixels_u8(&decoded), PATTERN_RGBA, "{:?}", texture.format);
+    }
+}
+
+#[test]
+fn bgra8_layout_is_bgra_for_all_sources() {
+    let decoder = TextureDecoder::new();
+    let expected: Vec<[u8; 4]> = PATTERN_RGBA
+        .iter()
+        .map(|&[r, g, b, a]| [b, g, r, a])
+        .collect();
+    for texture in [rgba32(), bgra32(), argb32()] {
+        let decoded = decoder.decode_raw(&texture, PixelLayout::Bgra8).unwrap();
+        assert_shape(&decoded, PixelLayout::Bgra8);
+        assert_eq!(pixels_u8(&decoded), expected, "{:?}", texture.format);
+    }
+}
+
+#[test]
+fn bgra32_to_bgra8_is_a_plain_copy() {
+    let texture = bgra32();
+    let decoded = TextureDecoder::new()
+        .decode_raw(&texture, PixelLayout::Bgra8)
+        .unwrap();
+    assert_eq!(decoded.data, texture.image_data);
+}
+
+#[test]
+fn rgba32f_layout_is_normalized_rgba() {
+    let decoder = TextureDecoder::new();
+    for texture in [rgba32(), bgra32()] {
+        let decoded = decoder.decode_raw(&texture, PixelLayout::Rgba32F).unwrap();
+        assert_shape(&decoded, PixelLayout::Rgba32F);
+
+        let floats: Vec<f32> = decoded
+            .data
+            .chunks_exact(4)
+            .map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        let expected: Vec<f32> = PATTERN_RGBA
+            .iter()
+            .flatten()
+            .map(|&c| c as f32 / 255.0)
+            .collect();
+        assert_eq!(floats, expected, "{:?}", texture.format);
+    }
+}
+
+#[test]
+fn decode_matches_decode_raw_rgba8() {
+    let decoder = TextureDecoder::new();
+    for texture in [rgba32(), bgra32(), argb32()] {
+        let image = decoder.decode(&texture).unwrap();
+        let raw = decoder.decode_raw(&texture, PixelLayout::Rgba8).unwrap();
+        assert_eq!(image.dimensions(), (raw.width, raw.height));
+        assert_eq!(image.into_raw(), raw.data);
+    }
+}
@@ -0,0 +1,66 @@
+//! `TextureEncoder` round trips through the basic decoder for every encodable format.
+
+#![cfg(feature = "texture")]
+
+use image::{Rgba, RgbaImage};
+use unity_asset_decode::texture::{Texture2D, TextureDecoder, TextureEncoder, TextureFormat};
+
+fn gradient() -> RgbaImage {
+    RgbaImage::from_fn(5, 3, |x, y| {
+        Rgba([
+            x as u8 * 60,
+            y as u8 * 120,
+            255 - x as u8 * 50,
+            40 + y as u8 * 100,
+        ])
+    })
+}
+
+fn round_trip(image: &RgbaImage, format: TextureFormat) -> RgbaImage {
+    let mut texture = Texture2D::new("t".to_string(), 5, 3, format);
+    texture.image_data = TextureEncoder::new().encode(image, format).unwrap();
+    assert_eq!(
+        texture.image_data.len() as u32,
+        texture.expected_data_size(),
+        "{:?}",
+        format
+    );
+    TextureDecoder::new().decode(&texture).unwrap()
+}
+
+#[test]
+fn encodable_formats_decode_back() {
+    let image = gradient();
+    let encoder = TextureEncoder::new();
+    for format in encoder.supported_formats() {
+        let decoded = round_trip(&image, format);
+        // Channel precision per format, as (tolerance, has color, has alpha).
+        let (tolerance, color, alpha) = match format {
+            TextureFormat::Alpha8 => (0, false, true),
+            TextureFormat::RGB24 => (0, true, false),
+            TextureFormat::RGBA4444 | TextureFormat::ARGB4444 => (8, true, true),
+            TextureFormat::RGB565 => (4, true, false),
+            _ => (0, true, true),
+        };
+        for (a, b) in decoded.pixels().zip(image.pixels()) {
+            for channel in 0..4 {
+                let expected = match channel {
+                    3 if !alpha => 255,
+                    0..=2 if !color => 255,
+                    _ => b.0[channel],
+                };
+                assert!(
+                    a.0[channel].abs_diff(expected) <= tolerance,
+                    "{:?} channel {}: {} vs {}",
+                    format,
+                    channel,
+                    a.0[channel],
+                    expected
+                );
+            }
+        }
+    }
+
+    assert!(!encoder.can_encode(TextureFormat::DXT1));
+    assert!(encoder.encode(&image, TextureFormat::DXT5).is_err());
+}
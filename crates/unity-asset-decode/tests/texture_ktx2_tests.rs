@@ -0,0 +1,154 @@
+//! KTX2 export tests
+//!
+//! Writes synthetic textures and parses the container back: header fields, the level index and
+//! the level bytes.
+
+#![cfg(feature = "texture")]
+
+use unity_asset_decode::texture::{
+    Ktx2Encoding, Ktx2Options, Texture2D, TextureExporter, TextureFormat, encode_ktx2,
+};
+
+struct Ktx2 {
+    bytes: Vec<u8>,
+}
+
+impl Ktx2 {
+    fn u32_at(&self, offset: usize) -> u32 {
+        u32::from_le_bytes(self.bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn u64_at(&self, offset: usize) -> u64 {
+        u64::from_le_bytes(self.bytes[offset..offset + 8].try_into().unwrap())
+    }
+
+    fn vk_format(&self) -> u32 {
+        self.u32_at(12)
+    }
+
+    fn dimensions(&self) -> (u32, u32) {
+        (self.u32_at(20), self.u32_at(24))
+    }
+
+    fn level_count(&self) -> u32 {
+        self.u32_at(40)
+    }
+
+    fn level(&self, level: usize) -> &[u8] {
+        let entry = 80 + level * 24;
+        let offset = self.u64_at(entry) as usize;
+        let length = self.u64_at(entry + 8) as usize;
+        &self.bytes[offset..offset + length]
+    }
+
+    fn key_value_data(&self) -> &[u8] {
+        let offset = self.u32_at(56) as usize;
+        &self.bytes[offset..offset + self.u32_at(60) as usize]
+    }
+}
+
+fn encode(texture: &Texture2D, options: &Ktx2Options) -> Ktx2 {
+    Ktx2 {
+        bytes: encode_ktx2(texture, options).unwrap(),
+    }
+}
+
+/// 8x8 DXT1 texture with a full mip chain (8x8, 4x4, 2x2, 1x1) of distinct bytes
+fn dxt1_with_mips() -> Texture2D {
+    let mut texture = Texture2D::new("Bricks".to_string(), 8, 8, TextureFormat::DXT1);
+    texture.mip_map = true;
+    texture.mip_count = 4;
+    texture.image_data = (0..32 + 8 + 8 + 8).map(|i| i as u8).collect();
+    texture
+}
+
+#[test]
+fn passthrough_header_and_levels() {
+    let texture = dxt1_with_mips();
+    let ktx = encode(&texture, &Ktx2Options::default());
+
+    assert_eq!(
+        &ktx.bytes[..12],
+        &[
+            0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A
+        ]
+    );
+    assert_eq!(ktx.vk_format(), 131); // VK_FORMAT_BC1_RGB_UNORM_BLOCK
+    assert_eq!(ktx.dimensions(), (8, 8));
+    assert_eq!(ktx.level_count(), 4);
+    assert_eq!(ktx.level(0), &texture.image_data[..32]);
+    assert_eq!(ktx.level(1), &texture.image_data[32..40]);
+    assert_eq!(ktx.level(3), &texture.image_data[48..]);
+    // Smaller levels come first in the file, each aligned to the 8-byte block.
+    let entry_offset = |level: usize| ktx.u64_at(80 + level * 24);
+    assert!(entry_offset(3) < entry_offset(0));
+    assert!((0..4).all(|level| entry_offset(level) % 8 == 0));
+}
+
+#[test]
+fn srgb_follows_color_space_unless_overridden() {
+    let mut texture = dxt1_with_mips();
+    texture.color_space = 1;
+    assert_eq!(encode(&texture, &Ktx2Options::default()).vk_format(), 132);
+
+    let options = Ktx2Options::default().with_srgb(Some(false));
+    assert_eq!(encode(&texture, &options).vk_format(), 131);
+
+    texture.format = TextureFormat::ASTC_RGBA_4x4;
+    texture.image_data = vec![7; 16 * 4 + 16 + 16 + 16];
+    let ktx = encode(&texture, &Ktx2Options::default());
+    assert_eq!(ktx.vk_format(), 158); // VK_FORMAT_ASTC_4x4_SRGB_BLOCK
+    assert_eq!(ktx.level_count(), 4);
+}
+
+#[test]
+fn uncompressed_textures_are_decoded_per_level() {
+    let mut texture = Texture2D::new("Ramp".to_string(), 4, 4, TextureFormat::RGBA32);
+    texture.mip_map = true;
+    texture.mip_count = 2;
+    texture.image_data = (0..(16 + 4) * 4).map(|i| i as u8).collect();
+    let ktx = encode(&texture, &Ktx2Options::default());
+
+    assert_eq!(ktx.vk_format(), 37); // VK_FORMAT_R8G8B8A8_UNORM
+    assert_eq!(ktx.level_count(), 2);
+    assert_eq!(ktx.level(0), &texture.image_data[..64]);
+    assert_eq!(ktx.level(1), &texture.image_data[64..]);
+}
+
+#[test]
+fn rgba8_encoding_skips_passthrough() {
+    let mut texture = Texture2D::new("Icon".to_string(), 2, 2, TextureFormat::RGB24);
+    texture.image_data = vec![255, 0, 0, 0, 255, 0, 0, 0, 255, 9, 9, 9];
+    let options = Ktx2Options::default().with_encoding(Ktx2Encoding::Rgba8);
+    let ktx = encode(&texture, &options);
+
+    assert_eq!(ktx.vk_format(), 37);
+    assert_eq!(ktx.level_count(), 1);
+    assert_eq!(&ktx.level(0)[..8], &[255, 0, 0, 255, 0, 255, 0, 255]);
+}
+
+#[test]
+fn truncated_mip_chains_keep_complete_levels() {
+    let mut texture = dxt1_with_mips();
+    texture.image_data.truncate(44);
+    assert_eq!(encode(&texture, &Ktx2Options::default()).level_count(), 2);
+
+    texture.image_data.truncate(16);
+    assert!(encode_ktx2(&texture, &Ktx2Options::default()).is_err());
+    texture.image_data.clear();
+    assert!(encode_ktx2(&texture, &Ktx2Options::default()).is_err());
+}
+
+#[test]
+fn orientation_is_recorded_and_file_is_written() {
+    let texture = dxt1_with_mips();
+    let ktx = encode(&texture, &Ktx2Options::default());
+    let kvd = ktx.key_value_data();
+    let needle = b"KTXorientation\0ru\0";
+    assert!(kvd.windows(needle.len()).any(|w| w == needle));
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bricks.ktx2");
+    TextureExporter::export_ktx2(&texture, &path, &Ktx2Options::default()).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), ktx.bytes);
+}
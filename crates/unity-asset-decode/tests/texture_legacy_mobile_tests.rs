@@ -0,0 +1,111 @@
+//! ETC1 (`ETC_RGB4`) and PVRTC through `TextureDecoder`, from hand-built blocks.
+
+#![cfg(feature = "texture-advanced")]
+
+use unity_asset_decode::error::BinaryError;
+use unity_asset_decode::texture::{Texture2D, TextureDecoder, TextureFormat};
+
+fn texture(format: TextureFormat, width: i32, height: i32, image_data: Vec<u8>) -> Texture2D {
+    Texture2D {
+        name: "block".to_string(),
+        width,
+        height,
+        format,
+        data_size: image_data.len() as i32,
+        image_data,
+        ..Default::default()
+    }
+}
+
+/// A PVRTC block with all-zero modulation, so every texel takes color A.
+fn pvrtc_block(color_a: u16, color_b: u16) -> Vec<u8> {
+    let mut block = vec![0; 4];
+    block.extend_from_slice(&color_a.to_le_bytes());
+    block.extend_from_slice(&color_b.to_le_bytes());
+    block
+}
+
+#[test]
+fn etc1_decodes_both_subblocks() {
+    // Individual mode, side-by-side subblocks: red 0xA/0x5, green 0x0/0x0, blue 0xF/0x0.
+    // Codeword 0 with pixel index 0 adds 2 to every channel.
+    let block = vec![0xA5, 0x00, 0xF0, 0x00, 0, 0, 0, 0];
+    assert_eq!(TextureFormat::ETC_RGB4.calculate_data_size(4, 4), 8);
+
+    let image = TextureDecoder::new()
+        .decode(&texture(TextureFormat::ETC_RGB4, 4, 4, block))
+        .unwrap();
+    for y in 0..4 {
+        assert_eq!(image.get_pixel(0, y).0, [172, 2, 255, 255]);
+        assert_eq!(image.get_pixel(1, y).0, [172, 2, 255, 255]);
+        assert_eq!(image.get_pixel(2, y).0, [87, 2, 2, 255]);
+        assert_eq!(image.get_pixel(3, y).0, [87, 2, 2, 255]);
+    }
+}
+
+#[test]
+fn pvrtc_takes_alpha_only_for_rgba_variants() {
+    // Opaque red, and fully transparent black. PVRTC stores at least 2x2 blocks.
+    let red = pvrtc_block(0x8000 | 0x1F << 10, 0x8000 | 0x1F).repeat(4);
+    let clear = pvrtc_block(0, 0).repeat(4);
+    assert_eq!(TextureFormat::PVRTC_RGB4.calculate_data_size(4, 4), 32);
+    let decoder = TextureDecoder::new();
+
+    let image = decoder
+        .decode(&texture(TextureFormat::PVRTC_RGB4, 4, 4, red.clone()))
+        .unwrap();
+    assert!(image.pixels().all(|p| p.0 == [255, 0, 0, 255]));
+
+    let rgba = decoder
+        .decode(&texture(TextureFormat::PVRTC_RGBA4, 4, 4, clear.clone()))
+        .unwrap();
+    assert!(rgba.pixels().all(|p| p.0[3] == 0));
+    let rgb = decoder
+        .decode(&texture(TextureFormat::PVRTC_RGB4, 4, 4, clear))
+        .unwrap();
+    assert!(rgb.pixels().all(|p| p.0[3] == 255));
+
+    // 2bpp blocks cover 8x4 texels, with a 16x8 minimum.
+    assert_eq!(TextureFormat::PVRTC_RGB2.calculate_data_size(8, 8), 32);
+    assert_eq!(TextureFormat::PVRTC_RGB2.calculate_data_size(32, 32), 256);
+    let two_bpp = decoder
+        .decode(&texture(TextureFormat::PVRTC_RGB2, 8, 8, red))
+        .unwrap();
+    assert_eq!(two_bpp.dimensions(), (8, 8));
+    assert!(two_bpp.pixels().all(|p| p.0 == [255, 0, 0, 255]));
+}
+
+#[test]
+fn pvrtc_rejects_non_power_of_two_sizes() {
+    let red = pvrtc_block(0x8000 | 0x1F << 10, 0x8000 | 0x1F);
+    let wide = texture(TextureFormat::PVRTC_RGBA4, 12, 4, red.repeat(6));
+    match TextureDecoder::new().decode(&wide) {
+        Err(BinaryError::InvalidData(msg)) => {
+            assert!(
+                msg.contains("power-of-two") && msg.contains("12x4"),
+                "{}",
+                msg
+            )
+        }
+        other => panic!(
+            "expected InvalidData, got {:?}",
+            other.map(|i| i.dimensions())
+        ),
+    }
+}
+
+#[test]
+fn legacy_mobile_formats_are_advertised() {
+    let decoder = TextureDecoder::new();
+    let supported = decoder.supported_formats();
+    for format in [
+        TextureFormat::PVRTC_RGB2,
+        TextureFormat::PVRTC_RGBA2,
+        TextureFormat::PVRTC_RGB4,
+        TextureFormat::PVRTC_RGBA4,
+        TextureFormat::ETC_RGB4,
+    ] {
+        assert!(supported.contains(&format), "{:?}", format);
+        assert!(decoder.can_decode(format));
+    }
+}
@@ -0,0 +1,118 @@
+//! Perceptual hashes of synthetic textures: copies, brightened and downscaled variants, and
+//! unrelated images.
+
+#![cfg(feature = "texture")]
+
+use image::{Rgba, RgbaImage, imageops};
+use unity_asset_decode::texture::{
+    PHash, TextureFingerprint, find_similar_textures, perceptual_hash,
+};
+
+/// Smooth two-frequency pattern; `seed` changes the phase and orientation.
+fn pattern(width: u32, height: u32, seed: f32) -> RgbaImage {
+    RgbaImage::from_fn(width, height, |x, y| {
+        let (u, v) = (x as f32 / width as f32, y as f32 / height as f32);
+        let a = (u * (6.0 + seed) + seed).sin() * (v * (4.0 + 2.0 * seed) - seed).cos();
+        let b = ((u - v) * (3.0 + seed)).sin();
+        let value = |k: f32| (128.0 + 60.0 * a + 40.0 * b * k).clamp(0.0, 255.0) as u8;
+        Rgba([value(1.0), value(0.5), value(-1.0), 255])
+    })
+}
+
+fn brighten(image: &RgbaImage, amount: u8) -> RgbaImage {
+    let mut out = image.clone();
+    for pixel in out.pixels_mut() {
+        for channel in &mut pixel.0[..3] {
+            *channel = channel.saturating_add(amount);
+        }
+    }
+    out
+}
+
+#[test]
+fn identical_images_hash_equal() {
+    let image = pattern(256, 256, 0.0);
+    let hash = perceptual_hash(&image);
+    assert_eq!(hash, perceptual_hash(&image.clone()));
+    assert_eq!(hash.distance(&hash), 0);
+    assert_ne!(hash, PHash(0));
+
+    // Frozen algorithm: the hex form round-trips and uniform images hash to zero.
+    assert_eq!(hash.to_string().len(), 16);
+    assert_eq!(hash.to_string().parse::<PHash>().unwrap(), hash);
+    assert_eq!(
+        perceptual_hash(&RgbaImage::from_pixel(32, 32, Rgba([200, 10, 10, 255]))),
+        PHash(0)
+    );
+    // A left-to-right ramp is brighter in every right-hand cell: all 64 bits set.
+    let ramp = RgbaImage::from_fn(90, 8, |x, _| Rgba([x as u8, x as u8, x as u8, 255]));
+    assert_eq!(perceptual_hash(&ramp), PHash(u64::MAX));
+    assert_eq!(perceptual_hash(&RgbaImage::new(0, 0)), PHash(0));
+}
+
+#[test]
+fn brightened_and_resized_copies_stay_close() {
+    let image = pattern(256, 256, 0.0);
+    let hash = perceptual_hash(&image);
+
+    let brightened = perceptual_hash(&brighten(&image, 12));
+    assert!(
+        hash.distance(&brightened) <= 4,
+        "{}",
+        hash.distance(&brightened)
+    );
+
+    let half = imageops::resize(&image, 128, 128, imageops::FilterType::Triangle);
+    let resized = perceptual_hash(&half);
+    assert!(hash.distance(&resized) <= 4, "{}", hash.distance(&resized));
+}
+
+#[test]
+fn unrelated_images_are_far_apart() {
+    let hashes: Vec<PHash> = [0.0, 1.3, 2.7]
+        .iter()
+        .map(|&seed| perceptual_hash(&pattern(128, 128, seed)))
+        .collect();
+    for i in 0..hashes.len() {
+        for j in i + 1..hashes.len() {
+            assert!(
+                hashes[i].distance(&hashes[j]) > 12,
+                "{i} {j}: {}",
+                hashes[i].distance(&hashes[j])
+            );
+        }
+    }
+}
+
+#[test]
+fn similar_textures_are_grouped_largest_first() {
+    let base = pattern(256, 256, 0.0);
+    let other = pattern(256, 256, 1.3);
+    let textures = vec![
+        TextureFingerprint::new("bundle_a/Hero.png", &base),
+        TextureFingerprint::new("bundle_b/Rock.png", &other),
+        TextureFingerprint::new(
+            "bundle_b/Hero_low.png",
+            &imageops::resize(&base, 64, 64, imageops::FilterType::Triangle),
+        ),
+        TextureFingerprint::new("bundle_c/Hero_bright.png", &brighten(&base, 10)),
+        TextureFingerprint::new("bundle_c/Unique.png", &pattern(64, 64, 2.7)),
+    ];
+
+    let groups = find_similar_textures(textures, 6);
+    assert_eq!(groups.len(), 1, "{groups:?}");
+    let group = &groups[0];
+    let locators: Vec<&str> = group.members.iter().map(|m| m.locator.as_str()).collect();
+    assert_eq!(
+        locators,
+        [
+            "bundle_a/Hero.png",
+            "bundle_c/Hero_bright.png",
+            "bundle_b/Hero_low.png"
+        ]
+    );
+    assert_eq!((group.largest().width, group.largest().height), (256, 256));
+    assert_eq!((group.members[2].width, group.members[2].height), (64, 64));
+
+    assert!(find_similar_textures(Vec::new(), 6).is_empty());
+}
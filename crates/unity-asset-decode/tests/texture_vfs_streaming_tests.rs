@@ -0,0 +1,119 @@
+//! Streamed Texture2D pixels read from a bundle that only exists in a virtual file system.
+
+#![cfg(feature = "texture")]
+
+use unity_asset_core::{UnityClass, UnityValue};
+use unity_asset_decode::bundle::{BundleLoadOptions, BundleLoader};
+use unity_asset_decode::data_source::VirtualFileSystem;
+use unity_asset_decode::{
+    asset::ObjectInfo, asset::class_ids, object::UnityObject, texture::Texture2DConverter,
+    unity_version::UnityVersion,
+};
+
+const CAB: &str = "CAB-fedcba9876543210fedcba9876543210";
+const PADDING: usize = 32;
+
+/// 2x2 RGBA32: red, green, blue, white
+fn pixels() -> Vec<u8> {
+    vec![
+        255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 255, 255, 255, 255,
+    ]
+}
+
+fn write_cstring(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(s.as_bytes());
+    buf.push(0);
+}
+
+/// UnityFS v6 bundle with a single uncompressed block holding one `.resS` node
+fn resource_bundle(name: &str, data: &[u8]) -> Vec<u8> {
+    let mut info = vec![0u8; 16];
+    info.extend_from_slice(&1i32.to_be_bytes());
+    info.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    info.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    info.extend_from_slice(&0u16.to_be_bytes());
+    info.extend_from_slice(&1i32.to_be_bytes());
+    info.extend_from_slice(&0i64.to_be_bytes());
+    info.extend_from_slice(&(data.len() as i64).to_be_bytes());
+    info.extend_from_slice(&4u32.to_be_bytes());
+    write_cstring(&mut info, name);
+
+    let mut header = Vec::new();
+    write_cstring(&mut header, "UnityFS");
+    header.extend_from_slice(&6u32.to_be_bytes());
+    write_cstring(&mut header, "5.x.x");
+    write_cstring(&mut header, "2018.4.2f1");
+    let total = header.len() + 20 + info.len() + data.len();
+    header.extend_from_slice(&(total as i64).to_be_bytes());
+    header.extend_from_slice(&(info.len() as u32).to_be_bytes());
+    header.extend_from_slice(&(info.len() as u32).to_be_bytes());
+    header.extend_from_slice(&0x40u32.to_be_bytes());
+
+    let mut bundle = header;
+    bundle.extend_from_slice(&info);
+    bundle.extend_from_slice(data);
+    bundle
+}
+
+fn streamed_texture() -> UnityObject {
+    let mut class = UnityClass::new(
+        class_ids::TEXTURE_2D,
+        "Texture2D".to_string(),
+        "1".to_string(),
+    );
+    class.set("m_Name".to_string(), UnityValue::String("Tex".to_string()));
+    class.set("m_Width".to_string(), UnityValue::Integer(2));
+    class.set("m_Height".to_string(), UnityValue::Integer(2));
+    class.set("m_TextureFormat".to_string(), UnityValue::Integer(4));
+    class.set("m_IsReadable".to_string(), UnityValue::Bool(true));
+
+    let mut stream = indexmap::IndexMap::new();
+    stream.insert(
+        "path".to_string(),
+        UnityValue::String(format!("archive:/{CAB}/{CAB}.resS")),
+    );
+    stream.insert("offset".to_string(), UnityValue::Integer(PADDING as i64));
+    stream.insert("size".to_string(), UnityValue::Integer(16));
+    class.set("m_StreamData".to_string(), UnityValue::Object(stream));
+
+    UnityObject::from_info_and_class(ObjectInfo::new(1, 0, 0, class_ids::TEXTURE_2D, -1), class)
+}
+
+#[test]
+fn streamed_texture_decodes_from_in_memory_bundle() {
+    let mut resource = vec![0xCC; PADDING];
+    resource.extend_from_slice(&pixels());
+
+    let mut vfs = VirtualFileSystem::new();
+    vfs.insert_bytes(
+        "AssetBundles/textures.bundle",
+        resource_bundle(&format!("{CAB}.resS"), &resource),
+    );
+
+    let mut loader = BundleLoader::with_options(BundleLoadOptions::lazy());
+    let bundle = loader
+        .load_from_vfs(&vfs, "AssetBundles/textures.bundle")
+        .unwrap();
+
+    let converter = Texture2DConverter::new(UnityVersion::default());
+    let mut texture = converter.from_unity_object(&streamed_texture()).unwrap();
+    assert!(texture.is_streamed());
+    texture.image_data = bundle
+        .read_resource(
+            &texture.stream_info.path,
+            texture.stream_info.offset,
+            texture.stream_info.size as u64,
+        )
+        .unwrap();
+
+    let image = converter.decode_to_image(&texture).unwrap();
+    assert_eq!((image.width(), image.height()), (2, 2));
+    let mut colors: Vec<[u8; 4]> = image.pixels().map(|p| p.0).collect();
+    colors.sort_unstable();
+    let mut expected: Vec<[u8; 4]> = pixels()
+        .chunks(4)
+        .map(|c| [c[0], c[1], c[2], c[3]])
+        .collect();
+    expected.sort_unstable();
+    assert_eq!(colors, expected);
+}
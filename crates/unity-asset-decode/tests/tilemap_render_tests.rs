@@ -0,0 +1,97 @@
+//! Tilemap layer rendering with in-memory sprites.
+
+#![cfg(feature = "sprite")]
+
+use image::{Rgba, RgbaImage};
+use unity_asset_decode::sprite::TilemapRenderer;
+use unity_asset_decode::tilemap::{CellLayout, Grid, TileCell, Tilemap};
+use unity_asset_decode::unity_objects::ObjectRef;
+
+const IDENTITY: [f32; 16] = [
+    1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+];
+
+fn cell(x: i32, y: i32, sprite: i64) -> TileCell {
+    TileCell {
+        position: [x, y, 0],
+        tile_index: Some(0),
+        tile: Some(ObjectRef::new(0, 500)),
+        sprite: Some(ObjectRef::new(0, sprite)),
+        color: [1.0; 4],
+        matrix: IDENTITY,
+        flags: 0,
+    }
+}
+
+fn tilemap(cells: Vec<TileCell>) -> Tilemap {
+    Tilemap {
+        tile_anchor: [0.5, 0.5, 0.0],
+        color: [1.0; 4],
+        tile_assets: vec![Some(ObjectRef::new(0, 500))],
+        cells: cells.into_iter().map(|c| (c.position, c)).collect(),
+        ..Default::default()
+    }
+}
+
+/// 2x2 sprite: red left column, green right column.
+fn split_sprite() -> RgbaImage {
+    RgbaImage::from_fn(2, 2, |x, _| {
+        if x == 0 {
+            Rgba([255, 0, 0, 255])
+        } else {
+            Rgba([0, 255, 0, 255])
+        }
+    })
+}
+
+fn resolve(r: ObjectRef) -> Option<RgbaImage> {
+    match r.path_id {
+        600 => Some(split_sprite()),
+        601 => Some(RgbaImage::from_pixel(2, 2, Rgba([255, 255, 255, 255]))),
+        _ => None,
+    }
+}
+
+#[test]
+fn renders_cells_top_row_first() {
+    // (0,1) above (0,0); (1,0) has no sprite and stays transparent.
+    let map = tilemap(vec![cell(0, 0, 601), cell(0, 1, 600), cell(1, 0, 999)]);
+
+    let image = TilemapRenderer::new(2, 2).render(&map, 0, resolve);
+
+    assert_eq!(image.dimensions(), (4, 4));
+    assert_eq!(image.get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+    assert_eq!(image.get_pixel(1, 0), &Rgba([0, 255, 0, 255]));
+    assert_eq!(image.get_pixel(0, 3), &Rgba([255, 255, 255, 255]));
+    assert_eq!(image.get_pixel(3, 3), &Rgba([0, 0, 0, 0]));
+}
+
+#[test]
+fn applies_matrix_flip_and_color_tint() {
+    let mut flipped = cell(0, 0, 600);
+    flipped.matrix[0] = -1.0;
+    let mut tinted = cell(1, 0, 601);
+    tinted.color = [0.0, 0.0, 1.0, 1.0];
+    let map = tilemap(vec![flipped, tinted]);
+
+    let image = TilemapRenderer::new(2, 2).render(&map, 0, resolve);
+
+    assert_eq!(image.dimensions(), (4, 2));
+    assert_eq!(image.get_pixel(0, 0), &Rgba([0, 255, 0, 255]));
+    assert_eq!(image.get_pixel(1, 0), &Rgba([255, 0, 0, 255]));
+    assert_eq!(image.get_pixel(2, 1), &Rgba([0, 0, 255, 255]));
+}
+
+#[test]
+fn cell_size_follows_grid() {
+    let grid = Grid {
+        cell_size: [1.0, 0.5, 1.0],
+        cell_layout: CellLayout::Rectangle,
+        ..Default::default()
+    };
+    let renderer = TilemapRenderer::for_grid(&grid, 16.0);
+    assert_eq!((renderer.cell_width, renderer.cell_height), (16, 8));
+
+    let empty = tilemap(Vec::new());
+    assert_eq!(renderer.render(&empty, 0, resolve).dimensions(), (0, 0));
+}
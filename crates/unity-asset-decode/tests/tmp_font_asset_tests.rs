@@ -0,0 +1,106 @@
+//! TextMeshPro font asset parsing and manifest export.
+//!
+//! `tmp_font_asset_2020.json` is the typetree dump of a LiberationSans SDF asset (TMP 3.0,
+//! Unity 2020.3) trimmed to the glyphs `A`..`E` with one `A`/`D` kerning pair;
+//! `tmp_font_asset_legacy.json` is an Arial SDF asset in the pre-1.4 layout with `A` and `B`.
+
+#![cfg(feature = "sprite")]
+
+use indexmap::IndexMap;
+use unity_asset_binary::test_support::{object_properties, read_fixture};
+use unity_asset_core::UnityValue;
+use unity_asset_decode::tmp::{
+    GlyphRect, TmpFontAsset, TmpFontExporter, TmpFontLayout, font_layout,
+};
+use unity_asset_decode::unity_objects::ObjectRef;
+
+fn fixture(name: &str) -> IndexMap<String, UnityValue> {
+    object_properties(&read_fixture(name)["TMP_FontAsset"], "properties")
+}
+
+#[test]
+fn current_layout_glyph_and_character_tables() {
+    let properties = fixture("tmp_font_asset_2020.json");
+    assert_eq!(font_layout(&properties), Some(TmpFontLayout::Current));
+    let font = TmpFontAsset::from_typetree(&properties).unwrap();
+
+    assert_eq!(font.name, "LiberationSans SDF");
+    assert_eq!(font.glyphs.len(), 5);
+    assert_eq!(font.characters.len(), 5);
+    let c = font.glyph_for_char('C').unwrap();
+    assert_eq!(c.index, 38);
+    assert_eq!(
+        c.rect,
+        GlyphRect {
+            x: 110,
+            y: 460,
+            width: 32,
+            height: 42
+        }
+    );
+    assert_eq!(c.metrics.advance, 36.0);
+    assert!(font.glyph_for_char('Z').is_none());
+}
+
+#[test]
+fn current_layout_face_atlas_and_kerning() {
+    let font = TmpFontAsset::from_typetree(&fixture("tmp_font_asset_2020.json")).unwrap();
+
+    assert_eq!(font.face.family_name, "Liberation Sans");
+    assert_eq!(font.face.point_size, 86.0);
+    assert_eq!(font.face.line_height, 98.867_19);
+    assert_eq!(font.face.padding, 9.0);
+    assert_eq!((font.face.atlas_width, font.face.atlas_height), (512, 512));
+    assert_eq!(font.atlas_texture(), Some(ObjectRef::new(0, 44)));
+
+    assert_eq!(font.kerning.len(), 1);
+    let pair = font.kerning[0];
+    assert_eq!((pair.first_glyph, pair.second_glyph), (36, 39));
+    assert_eq!(pair.first_adjustment.x_advance, -4.5);
+}
+
+#[test]
+fn legacy_layout_is_normalized() {
+    let properties = fixture("tmp_font_asset_legacy.json");
+    assert_eq!(font_layout(&properties), Some(TmpFontLayout::Legacy));
+    let font = TmpFontAsset::from_typetree(&properties).unwrap();
+
+    assert_eq!(font.face.family_name, "Arial");
+    assert_eq!(font.face.point_size, 52.0);
+    assert_eq!(font.atlas_texture(), Some(ObjectRef::new(0, 8)));
+    assert_eq!(font.glyphs.len(), 2);
+    // Legacy rects are flipped to be measured from the bottom of the 256px atlas.
+    assert_eq!(
+        font.glyph_for_char('B').unwrap().rect,
+        GlyphRect {
+            x: 60,
+            y: 204,
+            width: 30,
+            height: 42
+        }
+    );
+    assert_eq!(font.kerning[0].first_glyph, 'A' as u32);
+    assert_eq!(font.kerning[0].first_adjustment.x_advance, -2.0);
+}
+
+#[test]
+fn other_monobehaviours_are_rejected() {
+    let mut properties = fixture("tmp_font_asset_2020.json");
+    properties.shift_remove("m_GlyphTable");
+    assert_eq!(font_layout(&properties), None);
+    assert!(TmpFontAsset::from_typetree(&properties).is_err());
+}
+
+#[test]
+fn manifest_json_round_trips() {
+    let font = TmpFontAsset::from_typetree(&fixture("tmp_font_asset_2020.json")).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("font.json");
+    TmpFontExporter::export_manifest(&font, &path).unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert_eq!(json["glyphs"].as_array().unwrap().len(), 5);
+    assert_eq!(json["characters"][0]["unicode"], 'A' as u32);
+    let back: TmpFontAsset = serde_json::from_value(json).unwrap();
+    assert_eq!(back, font);
+}
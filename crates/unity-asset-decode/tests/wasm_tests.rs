@@ -0,0 +1,45 @@
+//! Bundle parsing and texture decoding from embedded bytes, run natively and on wasm32.
+//!
+//! CI runs this file in Node with `wasm-pack test --node` (see the `wasm` job), so it only uses
+//! in-memory entry points and builds with `--no-default-features`.
+
+#![cfg(feature = "texture-advanced")]
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+use unity_asset_core::class_ids;
+use unity_asset_decode::bundle::BundleParser;
+use unity_asset_decode::texture::{Texture2DConverter, TextureFormat};
+use unity_asset_decode::unity_version::UnityVersion;
+
+const BANNER: &[u8] = include_bytes!("../../../tests/samples/banner_1");
+
+#[test]
+fn embedded_bundle_texture_decodes() {
+    let bundle = BundleParser::from_bytes(BANNER.to_vec()).unwrap();
+    let object = bundle.assets[0]
+        .object_handles()
+        .find(|handle| handle.class_id() == class_ids::TEXTURE_2D)
+        .expect("banner_1 holds a Texture2D")
+        .read()
+        .unwrap();
+
+    let converter = Texture2DConverter::new(UnityVersion::default());
+    let mut texture = converter.from_unity_object(&object).unwrap();
+    assert_eq!(texture.name, "banner_1");
+    assert_eq!(texture.format, TextureFormat::ASTC_RGBA_6x6);
+
+    // The pixels live in the bundle's `.resS` node.
+    converter
+        .resolve_stream_data(&mut texture, &bundle)
+        .unwrap();
+    assert_eq!(
+        texture.image_data.len() as u32,
+        texture.expected_data_size()
+    );
+
+    let image = converter.decode_to_image(&texture).unwrap();
+    assert_eq!(image.dimensions(), (492, 180));
+    assert!(image.pixels().any(|p| p.0[3] > 0));
+}
@@ -48,12 +48,11 @@ fn main() -> anyhow::Result<()> {
 
     let index = SearchIndex::open_or_create_with_options(
         &paths,
-        SearchIndexOptions {
-            index_bundle_container_entries,
-            max_bundle_container_entries_per_bundle,
-            respect_ignore_files,
-            respect_project_gitignore,
-        },
+        SearchIndexOptions::default()
+            .with_index_bundle_container_entries(index_bundle_container_entries)
+            .with_max_bundle_container_entries_per_bundle(max_bundle_container_entries_per_bundle)
+            .with_respect_ignore_files(respect_ignore_files)
+            .with_respect_project_gitignore(respect_project_gitignore),
     )?;
 
     let start = Instant::now();
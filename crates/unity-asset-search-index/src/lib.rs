@@ -54,7 +54,7 @@ pub struct Location {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub guid: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub file_id: Option<u64>,
+    pub file_id: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub class_id: Option<u32>,
 }
@@ -82,7 +82,7 @@ pub struct ReferenceHit {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReferenceContext {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub doc_file_id: Option<u64>,
+    pub doc_file_id: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub doc_class_id: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -100,7 +100,7 @@ pub struct ReferenceContext {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReferenceObject {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub doc_file_id: Option<u64>,
+    pub doc_file_id: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub doc_class_id: Option<u32>,
     pub stable_id: String,
@@ -117,7 +117,7 @@ pub struct ReferenceObject {
 pub struct ReferencesResponse {
     pub guid: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub file_id: Option<u64>,
+    pub file_id: Option<i64>,
     pub took_ms: u128,
     pub total_hits: usize,
     pub hits: Vec<ReferenceHit>,
@@ -201,6 +201,7 @@ pub struct IndexPaths {
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct SearchIndexOptions {
     pub index_bundle_container_entries: bool,
     pub max_bundle_container_entries_per_bundle: usize,
@@ -208,6 +209,38 @@ pub struct SearchIndexOptions {
     pub respect_project_gitignore: bool,
 }
 
+impl SearchIndexOptions {
+    /// Set `index_bundle_container_entries`.
+    pub fn with_index_bundle_container_entries(
+        mut self,
+        index_bundle_container_entries: bool,
+    ) -> Self {
+        self.index_bundle_container_entries = index_bundle_container_entries;
+        self
+    }
+
+    /// Set `max_bundle_container_entries_per_bundle`.
+    pub fn with_max_bundle_container_entries_per_bundle(
+        mut self,
+        max_bundle_container_entries_per_bundle: usize,
+    ) -> Self {
+        self.max_bundle_container_entries_per_bundle = max_bundle_container_entries_per_bundle;
+        self
+    }
+
+    /// Set `respect_ignore_files`.
+    pub fn with_respect_ignore_files(mut self, respect_ignore_files: bool) -> Self {
+        self.respect_ignore_files = respect_ignore_files;
+        self
+    }
+
+    /// Set `respect_project_gitignore`.
+    pub fn with_respect_project_gitignore(mut self, respect_project_gitignore: bool) -> Self {
+        self.respect_project_gitignore = respect_project_gitignore;
+        self
+    }
+}
+
 impl Default for SearchIndexOptions {
     fn default() -> Self {
         Self {
@@ -1529,7 +1562,7 @@ impl SearchIndex {
     pub fn references(
         &self,
         guid: &str,
-        file_id: Option<u64>,
+        file_id: Option<i64>,
         limit: usize,
     ) -> Result<ReferencesResponse> {
         let start = Instant::now();
@@ -1608,7 +1641,7 @@ impl SearchIndex {
         &self,
         project_root: &Path,
         guid: &str,
-        file_id: Option<u64>,
+        file_id: Option<i64>,
         limit: usize,
     ) -> Result<ReferencesResponse> {
         let mut resp = self.references(guid, file_id, limit)?;
@@ -1737,7 +1770,7 @@ fn stable_id_base(guid: Option<&str>, path: &str) -> String {
     format!("path:{path}")
 }
 
-fn stable_id_for(guid: Option<&str>, path: &str, file_id: Option<u64>) -> String {
+fn stable_id_for(guid: Option<&str>, path: &str, file_id: Option<i64>) -> String {
     let mut out = stable_id_base(guid, path);
     if let Some(file_id) = file_id {
         out.push('#');
@@ -3053,19 +3086,19 @@ fn extract_content_for_file(
 #[derive(Debug, Clone, Copy)]
 struct DocHeader {
     class_id: u32,
-    file_id: u64,
+    file_id: i64,
 }
 
 #[derive(Debug, Clone)]
 struct YamlDocInfo {
     name: Option<String>,
-    game_object_id: Option<u64>,
+    game_object_id: Option<i64>,
 }
 
 fn extract_reference_contexts_from_yaml(
     text: &str,
     guid: &str,
-    file_id: Option<u64>,
+    file_id: Option<i64>,
 ) -> Vec<ReferenceContext> {
     let guid = guid.trim().to_lowercase();
     if guid.is_empty() {
@@ -3175,7 +3208,7 @@ fn extract_reference_contexts_from_yaml(
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 struct ContextKey {
-    doc_file_id: Option<u64>,
+    doc_file_id: Option<i64>,
     doc_class_id: Option<u32>,
     object_name: Option<String>,
     hierarchy_path: Option<String>,
@@ -3274,7 +3307,7 @@ fn join_hints(hints: std::collections::BTreeSet<String>) -> Option<String> {
 fn extract_reference_contexts_from_binary(
     abs_path: &Path,
     guid: &str,
-    file_id: Option<u64>,
+    file_id: Option<i64>,
 ) -> Vec<ReferenceContext> {
     let guid = normalize_guid_string(guid);
     if guid.is_empty() {
@@ -3333,7 +3366,7 @@ fn extract_reference_contexts_from_serialized_file(
     file: &unity_asset_binary::asset::SerializedFile,
     self_guid: Option<&str>,
     target_guid: &str,
-    target_file_id: Option<u64>,
+    target_file_id: Option<i64>,
 ) -> Vec<ReferenceContext> {
     const MAX_OBJECTS: usize = 50_000;
     const MAX_CONTEXTS: usize = 20;
@@ -3365,24 +3398,18 @@ fn extract_reference_contexts_from_serialized_file(
             && self_guid == target_guid
         {
             for id in &pptrs.internal {
-                let Ok(path_id_u64) = u64::try_from(*id) else {
-                    continue;
-                };
-                if target_file_id.is_some_and(|want| want != path_id_u64) {
+                if target_file_id.is_some_and(|want| want != *id) {
                     continue;
                 }
                 matched = true;
-                field_hint = Some(format!("binary internal pathID={path_id_u64}"));
+                field_hint = Some(format!("binary internal pathID={id}"));
                 break;
             }
         }
 
         if !matched {
             for (file_id, path_id) in &pptrs.external {
-                let Ok(path_id_u64) = u64::try_from(*path_id) else {
-                    continue;
-                };
-                if target_file_id.is_some_and(|want| want != path_id_u64) {
+                if target_file_id.is_some_and(|want| want != *path_id) {
                     continue;
                 }
                 if *file_id <= 0 {
@@ -3396,9 +3423,7 @@ fn extract_reference_contexts_from_serialized_file(
                     continue;
                 }
                 matched = true;
-                field_hint = Some(format!(
-                    "binary external fileID={file_id} pathID={path_id_u64}"
-                ));
+                field_hint = Some(format!("binary external fileID={file_id} pathID={path_id}"));
                 break;
             }
         }
@@ -3407,7 +3432,7 @@ fn extract_reference_contexts_from_serialized_file(
             continue;
         }
 
-        let doc_file_id = u64::try_from(handle.path_id()).ok();
+        let doc_file_id = Some(handle.path_id());
         let doc_class_id = u32::try_from(handle.class_id()).ok();
         let object_name = handle.peek_name().ok().flatten();
 
@@ -3446,15 +3471,15 @@ fn guess_field_hint(line: &str) -> Option<String> {
 
 #[derive(Debug, Default)]
 struct YamlAnalysis {
-    docs: std::collections::BTreeMap<u64, YamlDocInfo>,
-    go_names: std::collections::BTreeMap<u64, String>,
-    transforms: std::collections::BTreeMap<u64, TransformLink>,
-    transform_by_game_object: std::collections::BTreeMap<u64, u64>,
-    hierarchy_path_by_transform: std::collections::BTreeMap<u64, String>,
+    docs: std::collections::BTreeMap<i64, YamlDocInfo>,
+    go_names: std::collections::BTreeMap<i64, String>,
+    transforms: std::collections::BTreeMap<i64, TransformLink>,
+    transform_by_game_object: std::collections::BTreeMap<i64, i64>,
+    hierarchy_path_by_transform: std::collections::BTreeMap<i64, String>,
 }
 
 impl YamlAnalysis {
-    fn context_for_doc(&self, doc_file_id: u64) -> Option<(Option<String>, Option<String>)> {
+    fn context_for_doc(&self, doc_file_id: i64) -> Option<(Option<String>, Option<String>)> {
         let doc = self.docs.get(&doc_file_id)?;
         let object_name = doc.name.clone().or_else(|| {
             doc.game_object_id
@@ -3549,7 +3574,7 @@ fn analyze_unity_yaml_docs(text: &str) -> YamlAnalysis {
         }
     }
 
-    let mut cache = std::collections::BTreeMap::<u64, Option<String>>::new();
+    let mut cache = std::collections::BTreeMap::<i64, Option<String>>::new();
     for (transform_id, link) in &analysis.transforms {
         let Some(go_id) = link.game_object_id else {
             continue;
@@ -3710,17 +3735,17 @@ fn extract_unity_yaml_content(
 
 #[derive(Debug, Clone, Copy)]
 struct TransformLink {
-    game_object_id: Option<u64>,
-    father_transform_id: Option<u64>,
+    game_object_id: Option<i64>,
+    father_transform_id: Option<i64>,
 }
 
 fn extract_unity_yaml_hierarchy_paths(text: &str) -> Vec<String> {
-    let mut go_names: std::collections::BTreeMap<u64, String> = std::collections::BTreeMap::new();
-    let mut transforms: std::collections::BTreeMap<u64, TransformLink> =
+    let mut go_names: std::collections::BTreeMap<i64, String> = std::collections::BTreeMap::new();
+    let mut transforms: std::collections::BTreeMap<i64, TransformLink> =
         std::collections::BTreeMap::new();
 
     let mut current_class: Option<u32> = None;
-    let mut current_id: Option<u64> = None;
+    let mut current_id: Option<i64> = None;
 
     for line in text.lines() {
         let line = line.trim_end();
@@ -3765,7 +3790,7 @@ fn extract_unity_yaml_hierarchy_paths(text: &str) -> Vec<String> {
     }
 
     let mut out = std::collections::BTreeSet::<String>::new();
-    let mut cached_paths: std::collections::BTreeMap<u64, Option<String>> =
+    let mut cached_paths: std::collections::BTreeMap<i64, Option<String>> =
         std::collections::BTreeMap::new();
 
     for (transform_id, link) in &transforms {
@@ -3794,11 +3819,11 @@ fn extract_unity_yaml_hierarchy_paths(text: &str) -> Vec<String> {
 }
 
 fn resolve_transform_path(
-    transform_id: u64,
+    transform_id: i64,
     leaf_name: &str,
-    go_names: &std::collections::BTreeMap<u64, String>,
-    transforms: &std::collections::BTreeMap<u64, TransformLink>,
-    cache: &mut std::collections::BTreeMap<u64, Option<String>>,
+    go_names: &std::collections::BTreeMap<i64, String>,
+    transforms: &std::collections::BTreeMap<i64, TransformLink>,
+    cache: &mut std::collections::BTreeMap<i64, Option<String>>,
 ) -> Option<String> {
     if let Some(cached) = cache.get(&transform_id) {
         return cached.clone();
@@ -3838,7 +3863,7 @@ fn resolve_transform_path(
     out
 }
 
-fn parse_unity_yaml_doc_header(line: &str) -> Option<(u32, u64)> {
+fn parse_unity_yaml_doc_header(line: &str) -> Option<(u32, i64)> {
     // Example: --- !u!1 &123456
     let line = line.trim();
     if !line.starts_with("---") {
@@ -3852,7 +3877,7 @@ fn parse_unity_yaml_doc_header(line: &str) -> Option<(u32, u64)> {
 
     let amp_pos = line.rfind('&')?;
     let id_str = line[amp_pos + 1..].trim();
-    let file_id: u64 = id_str.parse().ok()?;
+    let file_id: i64 = id_str.parse().ok()?;
     Some((class_id, file_id))
 }
 
@@ -3867,16 +3892,17 @@ fn parse_unity_yaml_scalar(line: &str, key: &str) -> Option<String> {
     Some(value.trim_matches('"').to_string())
 }
 
-fn parse_file_id(line: &str) -> Option<u64> {
+fn parse_file_id(line: &str) -> Option<i64> {
     // {fileID: 123} or fileID: 123
     let idx = line.find("fileID:")?;
     let after = &line[idx + "fileID:".len()..];
-    let digits = after
-        .trim_start()
+    let after = after.trim_start();
+    let sign_len = usize::from(after.starts_with('-'));
+    let digits_len = after[sign_len..]
         .chars()
         .take_while(|c| c.is_ascii_digit())
-        .collect::<String>();
-    digits.parse().ok()
+        .count();
+    after[..sign_len + digits_len].parse().ok()
 }
 
 fn extract_csharp_terms(text: &str) -> String {
@@ -4287,6 +4313,47 @@ Transform:
         assert!(paths.iter().any(|p| p == "Root/Child"));
     }
 
+    #[test]
+    fn unity_yaml_negative_file_ids_keep_their_sign() {
+        assert_eq!(
+            parse_unity_yaml_doc_header("--- !u!1 &-9223372036854775808"),
+            Some((1, i64::MIN))
+        );
+        assert_eq!(
+            parse_file_id("  m_Father: {fileID: -8679921383154817045}"),
+            Some(-8679921383154817045)
+        );
+        assert_eq!(parse_file_id("  m_Father: {fileID: 0}"), Some(0));
+
+        let text = r#"%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!1 &-10
+GameObject:
+  m_Name: Root
+--- !u!4 &-20
+Transform:
+  m_GameObject: {fileID: -10}
+  m_Father: {fileID: 0}
+--- !u!1 &9223372036854775807
+GameObject:
+  m_Name: Child
+--- !u!4 &-9223372036854775808
+Transform:
+  m_GameObject: {fileID: 9223372036854775807}
+  m_Father: {fileID: -20}
+"#;
+
+        let paths = extract_unity_yaml_hierarchy_paths(text);
+        assert!(paths.iter().any(|p| p == "Root/Child"));
+        assert_eq!(
+            stable_id_for(None, "Assets/a.prefab", Some(i64::MIN)),
+            format!(
+                "{}#-9223372036854775808",
+                stable_id_base(None, "Assets/a.prefab")
+            )
+        );
+    }
+
     #[test]
     fn group_reference_contexts_merges_hints_for_same_object() {
         let a = ReferenceContext {
@@ -99,6 +99,36 @@ pub(crate) fn chunk_based_compress(
     Ok((compressed_data, block_info))
 }
 
+/// [`chunk_based_compress`] with compression restarting at each of `boundaries` (ascending data
+/// offsets), so no block spans one.
+pub(crate) fn chunk_based_compress_segments(
+    data: &[u8],
+    boundaries: &[u64],
+    block_info_flag: u32,
+) -> Result<(Vec<u8>, Vec<UnityFsBlockInfo>)> {
+    if boundaries.is_empty() {
+        return chunk_based_compress(data, block_info_flag);
+    }
+
+    let mut compressed_data = Vec::new();
+    let mut block_info = Vec::new();
+    let mut start = 0usize;
+    for end in boundaries
+        .iter()
+        .map(|&b| b as usize)
+        .chain(std::iter::once(data.len()))
+    {
+        if end <= start || end > data.len() {
+            continue;
+        }
+        let (compressed, blocks) = chunk_based_compress(&data[start..end], block_info_flag)?;
+        compressed_data.extend_from_slice(&compressed);
+        block_info.extend(blocks);
+        start = end;
+    }
+    Ok((compressed_data, block_info))
+}
+
 fn make_block_info(uncompressed: usize, compressed: usize, flags: u32) -> Result<UnityFsBlockInfo> {
     let uncompressed_size = u32::try_from(uncompressed).map_err(|_| {
         UnityAssetError::format(format!(
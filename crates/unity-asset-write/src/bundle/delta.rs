@@ -0,0 +1,552 @@
+//! Object-level delta patches between two versions of a UnityFS bundle.
+//!
+//! [`bundle_diff`] compares the decompressed directory entries of two bundles. Each entry of
+//! the new bundle becomes a list of [`PatchOp`]s: ranges copied from an entry of the old bundle,
+//! or literal bytes. Inside serialized files, objects are matched by path ID and content hash, so
+//! an unchanged object is a copy even if it moved; the metadata region, padding and resource
+//! files are compared in fixed-size blocks at the same offsets. Header fields and compression
+//! flags are carried explicitly.
+//!
+//! [`bundle_apply`] rebuilds the entries from the old bundle's bytes and repacks them with the
+//! UnityFS writer. The result re-parses to the same entries and objects as the new bundle, but
+//! block boundaries and compressed bytes may differ.
+//!
+//! A serialized patch ([`BundlePatch::to_bytes`]) is:
+//!
+//! ```text
+//! "UABPATCH"  magic
+//! u32         format version (1)
+//! u32         uncompressed body size
+//! [u8; 32]    SHA-256 of the uncompressed body
+//! ...         LZMA-compressed body (Unity layout, no size field)
+//! ```
+//!
+//! The body holds SHA-256 digests of the old and new entry contents, so applying a patch to the
+//! wrong base bundle, or a corrupted patch, fails instead of producing a broken bundle.
+
+use std::collections::HashMap;
+
+use sha2::{Digest, Sha256};
+use unity_asset_binary::asset::SerializedFile;
+use unity_asset_binary::bundle::{AssetBundle, BundleParser};
+use unity_asset_binary::compression::{CompressionType, decompress};
+use unity_asset_binary::reader::{BinaryReader, ByteOrder};
+use unity_asset_core::{Result, UnityAssetError};
+
+use crate::bundle::layout::BundleEntry;
+use crate::bundle::writer::write_unityfs;
+use crate::{BinaryWriter, Endian, PackerOptions, UnityPyPacker, compress_lzma_unity};
+
+const PATCH_MAGIC: &[u8; 8] = b"UABPATCH";
+const PATCH_FORMAT_VERSION: u32 = 1;
+
+/// Block size used when comparing bytes that are not object payloads.
+const BLOCK_SIZE: usize = 1024;
+
+const OP_COPY: u8 = 0;
+const OP_LITERAL: u8 = 1;
+
+/// How to produce a run of bytes of a new directory entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchOp {
+    /// `len` bytes at `offset` of the old bundle's entry `node` (an index into its `nodes`).
+    Copy { node: u32, offset: u64, len: u64 },
+    /// Bytes carried by the patch.
+    Literal(Vec<u8>),
+}
+
+/// One directory entry of the new bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchNode {
+    pub name: String,
+    pub flags: u32,
+    pub size: u64,
+    /// SHA-256 of the entry's bytes.
+    pub hash: [u8; 32],
+    pub ops: Vec<PatchOp>,
+}
+
+/// Header fields of the new bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchHeader {
+    pub version: u32,
+    pub unity_version: String,
+    pub unity_revision: String,
+    /// Bundle header flags (`data_flag`).
+    pub data_flag: u32,
+    /// Compression flags of the data blocks.
+    pub block_info_flag: u32,
+}
+
+/// The difference between two versions of a bundle (see the [module docs](self)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundlePatch {
+    /// Content hash of the bundle the patch applies to.
+    pub old_hash: [u8; 32],
+    /// Content hash of the bundle the patch produces.
+    pub new_hash: [u8; 32],
+    pub header: PatchHeader,
+    pub nodes: Vec<PatchNode>,
+}
+
+impl BundlePatch {
+    /// Total size of the literal bytes carried by the patch.
+    pub fn literal_size(&self) -> u64 {
+        self.ops()
+            .map(|op| match op {
+                PatchOp::Literal(bytes) => bytes.len() as u64,
+                PatchOp::Copy { .. } => 0,
+            })
+            .sum()
+    }
+
+    /// Total size of the bytes copied from the old bundle.
+    pub fn copied_size(&self) -> u64 {
+        self.ops()
+            .map(|op| match op {
+                PatchOp::Copy { len, .. } => *len,
+                PatchOp::Literal(_) => 0,
+            })
+            .sum()
+    }
+
+    fn ops(&self) -> impl Iterator<Item = &PatchOp> {
+        self.nodes.iter().flat_map(|node| node.ops.iter())
+    }
+
+    /// Serialize the patch.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let body = self.encode_body();
+        let body_size = u32::try_from(body.len()).map_err(|_| {
+            UnityAssetError::format(format!("Bundle patch body too large: {}", body.len()))
+        })?;
+
+        let mut writer = BinaryWriter::new(Endian::Little);
+        writer.write(PATCH_MAGIC);
+        writer.write_u32(PATCH_FORMAT_VERSION);
+        writer.write_u32(body_size);
+        writer.write(&sha256(&body));
+        writer.write(&compress_lzma_unity(&body)?);
+        Ok(writer.into_bytes())
+    }
+
+    /// Parse a serialized patch, checking its magic, version and body hash.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut reader = BinaryReader::new(data, ByteOrder::Little);
+        let magic = reader.read_bytes(PATCH_MAGIC.len())?;
+        if magic != PATCH_MAGIC {
+            return Err(UnityAssetError::format("Not a bundle patch (bad magic)"));
+        }
+        let version = reader.read_u32()?;
+        if version != PATCH_FORMAT_VERSION {
+            return Err(UnityAssetError::format(format!(
+                "Unsupported bundle patch format version: {}",
+                version
+            )));
+        }
+        let body_size = reader.read_u32()? as usize;
+        let body_hash = read_hash(&mut reader)?;
+        let body = decompress(reader.read_remaining(), CompressionType::Lzma, body_size)
+            .map_err(|e| UnityAssetError::with_source("Failed to decompress bundle patch", e))?;
+        if body.len() != body_size || sha256(&body) != body_hash {
+            return Err(UnityAssetError::format(
+                "Bundle patch is corrupted (body hash mismatch)",
+            ));
+        }
+        Self::decode_body(&body)
+    }
+
+    fn encode_body(&self) -> Vec<u8> {
+        let mut writer = BinaryWriter::new(Endian::Little);
+        writer.write(&self.old_hash);
+        writer.write(&self.new_hash);
+        writer.write_u32(self.header.version);
+        writer.write_string_to_null(&self.header.unity_version);
+        writer.write_string_to_null(&self.header.unity_revision);
+        writer.write_u32(self.header.data_flag);
+        writer.write_u32(self.header.block_info_flag);
+
+        writer.write_u32(self.nodes.len() as u32);
+        for node in &self.nodes {
+            writer.write_string_to_null(&node.name);
+            writer.write_u32(node.flags);
+            writer.write_u64(node.size);
+            writer.write(&node.hash);
+            writer.write_u32(node.ops.len() as u32);
+            for op in &node.ops {
+                match op {
+                    PatchOp::Copy { node, offset, len } => {
+                        writer.write_u8(OP_COPY);
+                        writer.write_u32(*node);
+                        writer.write_u64(*offset);
+                        writer.write_u64(*len);
+                    }
+                    PatchOp::Literal(bytes) => {
+                        writer.write_u8(OP_LITERAL);
+                        writer.write_u64(bytes.len() as u64);
+                        writer.write(bytes);
+                    }
+                }
+            }
+        }
+        writer.into_bytes()
+    }
+
+    fn decode_body(body: &[u8]) -> Result<Self> {
+        let mut reader = BinaryReader::new(body, ByteOrder::Little);
+        let old_hash = read_hash(&mut reader)?;
+        let new_hash = read_hash(&mut reader)?;
+        let header = PatchHeader {
+            version: reader.read_u32()?,
+            unity_version: reader.read_cstring()?,
+            unity_revision: reader.read_cstring()?,
+            data_flag: reader.read_u32()?,
+            block_info_flag: reader.read_u32()?,
+        };
+
+        let node_count = reader.read_u32()?;
+        let mut nodes = Vec::new();
+        for _ in 0..node_count {
+            let name = reader.read_cstring()?;
+            let flags = reader.read_u32()?;
+            let size = reader.read_u64()?;
+            let hash = read_hash(&mut reader)?;
+            let op_count = reader.read_u32()?;
+            let mut ops = Vec::new();
+            for _ in 0..op_count {
+                ops.push(match reader.read_u8()? {
+                    OP_COPY => PatchOp::Copy {
+                        node: reader.read_u32()?,
+                        offset: reader.read_u64()?,
+                        len: reader.read_u64()?,
+                    },
+                    OP_LITERAL => {
+                        let len = reader.read_u64()? as usize;
+                        PatchOp::Literal(reader.read_bytes(len)?)
+                    }
+                    other => {
+                        return Err(UnityAssetError::format(format!(
+                            "Unknown bundle patch op: {}",
+                            other
+                        )));
+                    }
+                });
+            }
+            nodes.push(PatchNode {
+                name,
+                flags,
+                size,
+                hash,
+                ops,
+            });
+        }
+
+        Ok(Self {
+            old_hash,
+            new_hash,
+            header,
+            nodes,
+        })
+    }
+}
+
+/// Compute the patch turning `old` into `new`. Both must be UnityFS bundles.
+pub fn bundle_diff(old: &AssetBundle, new: &AssetBundle) -> Result<BundlePatch> {
+    for bundle in [old, new] {
+        require_unityfs(bundle)?;
+    }
+
+    let old_entries = file_entries(old)?;
+    let new_entries = file_entries(new)?;
+    let old_files = serialized_files_by_node(old);
+    let new_files = serialized_files_by_node(new);
+
+    let mut nodes = Vec::new();
+    for (new_index, new_data) in &new_entries {
+        let node = &new.nodes[*new_index];
+        let base = old_entries
+            .iter()
+            .find(|(i, _)| old.nodes[*i].name == node.name)
+            .map(|(i, data)| NodeBase {
+                index: *i as u32,
+                data,
+                file: old_files.get(i).copied(),
+            });
+
+        let ops = match (&base, new_files.get(new_index)) {
+            (Some(base), Some(file)) => diff_serialized(base, new_data, file),
+            (Some(base), None) => diff_blocks(base, new_data, 0..new_data.len()),
+            (None, _) => vec![PatchOp::Literal(new_data.clone())],
+        };
+        nodes.push(PatchNode {
+            name: node.name.clone(),
+            flags: node.flags,
+            size: new_data.len() as u64,
+            hash: sha256(new_data),
+            ops: coalesce(ops),
+        });
+    }
+
+    Ok(BundlePatch {
+        old_hash: content_hash(old, &old_entries),
+        new_hash: content_hash(new, &new_entries),
+        header: PatchHeader {
+            version: new.header.version,
+            unity_version: new.header.unity_version.clone(),
+            unity_revision: new.header.unity_revision.clone(),
+            data_flag: new.header.flags,
+            block_info_flag: new.blocks.first().map(|b| b.flags as u32).unwrap_or(64),
+        },
+        nodes,
+    })
+}
+
+/// Apply `patch` to the bytes of the bundle it was made from, returning the new bundle.
+pub fn bundle_apply(old_bundle_bytes: &[u8], patch: &BundlePatch) -> Result<Vec<u8>> {
+    let mut old = BundleParser::from_bytes(old_bundle_bytes.to_vec())
+        .map_err(|e| UnityAssetError::with_source("Failed to parse patch base bundle", e))?;
+    require_unityfs(&old)?;
+
+    let old_entries = file_entries(&old)?;
+    if content_hash(&old, &old_entries) != patch.old_hash {
+        return Err(UnityAssetError::format(
+            "Bundle patch does not apply to this bundle (base hash mismatch)",
+        ));
+    }
+    let old_data: HashMap<usize, &[u8]> = old_entries
+        .iter()
+        .map(|(i, data)| (*i, data.as_slice()))
+        .collect();
+
+    let mut entries = Vec::with_capacity(patch.nodes.len());
+    for node in &patch.nodes {
+        let mut bytes = Vec::with_capacity(node.size as usize);
+        for op in &node.ops {
+            match op {
+                PatchOp::Literal(literal) => bytes.extend_from_slice(literal),
+                PatchOp::Copy {
+                    node: index,
+                    offset,
+                    len,
+                } => {
+                    let range = old_data
+                        .get(&(*index as usize))
+                        .and_then(|data| data.get(*offset as usize..(*offset + *len) as usize))
+                        .ok_or_else(|| {
+                            UnityAssetError::format(format!(
+                                "Bundle patch copies outside old entry {} ({}+{})",
+                                index, offset, len
+                            ))
+                        })?;
+                    bytes.extend_from_slice(range);
+                }
+            }
+        }
+        if bytes.len() as u64 != node.size || sha256(&bytes) != node.hash {
+            return Err(UnityAssetError::format(format!(
+                "Bundle patch produced wrong contents for {}",
+                node.name
+            )));
+        }
+        entries.push(BundleEntry {
+            name: node.name.clone(),
+            flags: node.flags,
+            bytes,
+        });
+    }
+
+    let new_hash = entries_hash(
+        entries
+            .iter()
+            .map(|e| (e.name.as_str(), e.flags, e.bytes.as_slice())),
+    );
+    if new_hash != patch.new_hash {
+        return Err(UnityAssetError::format(
+            "Bundle patch is corrupted (result hash mismatch)",
+        ));
+    }
+
+    // The old bundle only serves as the header template for the writer.
+    old.header.version = patch.header.version;
+    old.header.unity_version = patch.header.unity_version.clone();
+    old.header.unity_revision = patch.header.unity_revision.clone();
+    old.header.flags = patch.header.data_flag;
+    let options = PackerOptions::default().with_packer(UnityPyPacker::UnityFsFlags {
+        block_info_flag: patch.header.block_info_flag,
+        data_flag: patch.header.data_flag,
+    });
+    Ok(write_unityfs(&old, &entries, &[], options)?.0)
+}
+
+/// An entry of the old bundle that a new entry is diffed against.
+struct NodeBase<'a> {
+    index: u32,
+    data: &'a [u8],
+    file: Option<&'a SerializedFile>,
+}
+
+impl NodeBase<'_> {
+    fn copy(&self, offset: usize, len: usize) -> PatchOp {
+        PatchOp::Copy {
+            node: self.index,
+            offset: offset as u64,
+            len: len as u64,
+        }
+    }
+}
+
+fn require_unityfs(bundle: &AssetBundle) -> Result<()> {
+    if bundle.header.signature != "UnityFS" {
+        return Err(UnityAssetError::format(format!(
+            "Bundle patches are only implemented for UnityFS (got {})",
+            bundle.header.signature
+        )));
+    }
+    Ok(())
+}
+
+/// Data-carrying directory entries as `(node index, bytes)`.
+fn file_entries(bundle: &AssetBundle) -> Result<Vec<(usize, Vec<u8>)>> {
+    bundle
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.flags & 0x3 == 0)
+        .map(|(i, node)| {
+            let data = bundle.extract_node_data(node).map_err(|e| {
+                UnityAssetError::with_source(
+                    format!("Failed to extract bundle node data: {}", node.name),
+                    e,
+                )
+            })?;
+            Ok((i, data))
+        })
+        .collect()
+}
+
+fn serialized_files_by_node(bundle: &AssetBundle) -> HashMap<usize, &SerializedFile> {
+    bundle
+        .assets
+        .iter()
+        .enumerate()
+        .filter_map(|(i, asset)| Some((bundle.asset_node_index(i)?, asset)))
+        .collect()
+}
+
+/// Metadata and gaps as blocks, objects as copies of unchanged old objects or literals.
+fn diff_serialized(base: &NodeBase<'_>, data: &[u8], file: &SerializedFile) -> Vec<PatchOp> {
+    let old_objects: HashMap<i64, (usize, usize, [u8; 32])> = base
+        .file
+        .map(|old| {
+            old.objects
+                .iter()
+                .filter_map(|o| {
+                    let range = object_range(base.data, o.byte_start, o.byte_size)?;
+                    Some((
+                        o.path_id,
+                        (range.start, range.len(), sha256(&base.data[range])),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut objects: Vec<_> = file
+        .objects
+        .iter()
+        .filter_map(|o| Some((o.path_id, object_range(data, o.byte_start, o.byte_size)?)))
+        .collect();
+    objects.sort_by_key(|(_, range)| range.start);
+
+    let mut ops = Vec::new();
+    let mut pos = 0;
+    for (path_id, range) in objects {
+        if range.start < pos {
+            continue;
+        }
+        ops.extend(diff_blocks(base, data, pos..range.start));
+        let payload = &data[range.clone()];
+        match old_objects.get(&path_id) {
+            Some(&(offset, len, hash)) if len == payload.len() && hash == sha256(payload) => {
+                ops.push(base.copy(offset, len))
+            }
+            _ => ops.push(PatchOp::Literal(payload.to_vec())),
+        }
+        pos = range.end;
+    }
+    ops.extend(diff_blocks(base, data, pos..data.len()));
+    ops
+}
+
+fn object_range(data: &[u8], byte_start: u64, byte_size: u32) -> Option<std::ops::Range<usize>> {
+    let start = usize::try_from(byte_start).ok()?;
+    let end = start.checked_add(byte_size as usize)?;
+    (end <= data.len()).then_some(start..end)
+}
+
+/// `range` of `data` in blocks: copies where the old entry has the same bytes at the same offset.
+fn diff_blocks(base: &NodeBase<'_>, data: &[u8], range: std::ops::Range<usize>) -> Vec<PatchOp> {
+    let mut ops = Vec::new();
+    let mut start = range.start;
+    while start < range.end {
+        let end = (start + BLOCK_SIZE).min(range.end);
+        if base.data.get(start..end) == Some(&data[start..end]) {
+            ops.push(base.copy(start, end - start));
+        } else {
+            ops.push(PatchOp::Literal(data[start..end].to_vec()));
+        }
+        start = end;
+    }
+    ops
+}
+
+/// Merge adjacent literals and contiguous copies from the same entry.
+fn coalesce(ops: Vec<PatchOp>) -> Vec<PatchOp> {
+    let mut out: Vec<PatchOp> = Vec::with_capacity(ops.len());
+    for op in ops {
+        match (out.last_mut(), op) {
+            (Some(PatchOp::Literal(last)), PatchOp::Literal(bytes)) => last.extend(bytes),
+            (
+                Some(PatchOp::Copy {
+                    node: last_node,
+                    offset: last_offset,
+                    len: last_len,
+                }),
+                PatchOp::Copy { node, offset, len },
+            ) if *last_node == node && *last_offset + *last_len == offset => *last_len += len,
+            (_, op) => out.push(op),
+        }
+    }
+    out
+}
+
+fn content_hash(bundle: &AssetBundle, entries: &[(usize, Vec<u8>)]) -> [u8; 32] {
+    entries_hash(entries.iter().map(|(i, data)| {
+        let node = &bundle.nodes[*i];
+        (node.name.as_str(), node.flags, data.as_slice())
+    }))
+}
+
+/// SHA-256 over every entry's name, flags, size and bytes, in directory order.
+fn entries_hash<'a>(entries: impl Iterator<Item = (&'a str, u32, &'a [u8])>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for (name, flags, data) in entries {
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update(flags.to_le_bytes());
+        hasher.update((data.len() as u64).to_le_bytes());
+        hasher.update(data);
+    }
+    hasher.finalize().into()
+}
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+fn read_hash(reader: &mut BinaryReader<'_>) -> Result<[u8; 32]> {
+    let bytes = reader.read_bytes(32)?;
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&bytes);
+    Ok(hash)
+}
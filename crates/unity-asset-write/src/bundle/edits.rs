@@ -1,11 +1,15 @@
 use std::collections::HashMap;
 
 /// A minimal edit set for repacking a bundle:
-/// replace specific directory entries (by name) with new raw bytes.
+/// replace specific directory entries with new raw bytes.
+///
+/// Entries are keyed by name and occurrence, since a bundle may list the same name more than
+/// once: occurrence 0 is the first node with that name, 1 the second, and so on (see
+/// `AssetBundle::node_occurrence`).
 #[derive(Debug, Clone, Default)]
 pub struct BundleEdits {
-    by_name: HashMap<String, Vec<u8>>,
-    flags: HashMap<String, u32>,
+    by_name: HashMap<(String, usize), Vec<u8>>,
+    flags: HashMap<(String, usize), u32>,
 }
 
 impl BundleEdits {
@@ -17,25 +21,51 @@ impl BundleEdits {
         self.by_name.is_empty()
     }
 
+    /// Replace the first entry named `name`.
     pub fn replace_file_bytes(&mut self, name: impl Into<String>, bytes: Vec<u8>) {
-        self.by_name.insert(name.into(), bytes);
+        self.replace_file_bytes_at(name, 0, bytes);
+    }
+
+    /// Replace the `occurrence`-th entry named `name`.
+    pub fn replace_file_bytes_at(
+        &mut self,
+        name: impl Into<String>,
+        occurrence: usize,
+        bytes: Vec<u8>,
+    ) {
+        self.by_name.insert((name.into(), occurrence), bytes);
     }
 
     pub fn add_file_bytes(&mut self, name: impl Into<String>, bytes: Vec<u8>, flags: u32) {
-        let name = name.into();
-        self.by_name.insert(name.clone(), bytes);
-        self.flags.insert(name, flags);
+        let key = (name.into(), 0);
+        self.by_name.insert(key.clone(), bytes);
+        self.flags.insert(key, flags);
     }
 
-    pub(crate) fn get(&self, name: &str) -> Option<&[u8]> {
-        self.by_name.get(name).map(|v| v.as_slice())
+    pub(crate) fn get(&self, name: &str, occurrence: usize) -> Option<&[u8]> {
+        self.by_name
+            .get(&(name.to_string(), occurrence))
+            .map(|v| v.as_slice())
     }
 
-    pub(crate) fn flags(&self, name: &str) -> Option<u32> {
-        self.flags.get(name).copied()
+    pub(crate) fn flags(&self, name: &str, occurrence: usize) -> Option<u32> {
+        self.flags.get(&(name.to_string(), occurrence)).copied()
     }
 
-    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
-        self.by_name.iter().map(|(k, v)| (k.as_str(), v.as_slice()))
+    /// Entries not matched by any existing node, ordered by name then occurrence.
+    pub(crate) fn unmatched<'a>(
+        &'a self,
+        matched: &HashMap<&str, usize>,
+    ) -> Vec<(&'a str, usize, &'a [u8])> {
+        let mut extra: Vec<_> = self
+            .by_name
+            .iter()
+            .filter(|((name, occurrence), _)| {
+                matched.get(name.as_str()).is_none_or(|n| occurrence >= n)
+            })
+            .map(|((name, occurrence), v)| (name.as_str(), *occurrence, v.as_slice()))
+            .collect();
+        extra.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+        extra
     }
 }
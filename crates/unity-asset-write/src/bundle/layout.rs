@@ -0,0 +1,492 @@
+//! Bundle save layout: directory order, `.resS` packing and block boundaries.
+//!
+//! Unity's own layout interleaves unrelated data, which costs compression when repacking. A
+//! non-[`Layout::Preserve`] layout:
+//! - reorders directory entries (serialized files, then `.resS`, then `.resource`, then the rest
+//!   for [`Layout::GroupByType`]; caller-chosen for [`Layout::Custom`]);
+//! - repacks each resource file so ranges streamed by objects of the same class sit together,
+//!   rewriting the `StreamingInfo` / `StreamedResource` offsets that point into it;
+//! - starts new compression blocks at serialized-file boundaries, so patching a small CAB only
+//!   changes its own blocks.
+//!
+//! Stream offsets are relative to their resource file, so moving a whole entry needs no rewrite;
+//! only ranges moved inside a resource file do.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use unity_asset_binary::asset::{SerializedFile, SerializedFileParser};
+use unity_asset_binary::bundle::normalize_stream_path;
+use unity_asset_binary::reader::ByteOrder;
+use unity_asset_binary::typetree::TypeTreeNode;
+use unity_asset_core::{Result, UnityAssetError};
+
+use crate::PackerOptions;
+
+/// Resource ranges are placed on this alignment when repacked (Unity aligns `.resS` data to 16).
+const RESOURCE_ALIGNMENT: u64 = 16;
+
+/// How directory entries and resource data are arranged when saving a bundle.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Layout {
+    /// Keep the original order and block layout (UnityPy `save` parity).
+    #[default]
+    Preserve,
+    /// Serialized files first, then `.resS`, `.resource` and other entries; resource files are
+    /// repacked by streaming class.
+    GroupByType,
+    /// These entry names first, in this order; unlisted entries follow in their original order.
+    /// Resource files are repacked as for [`Layout::GroupByType`].
+    Custom(Vec<String>),
+}
+
+/// Options for [`BundleWriter::save_with_options`](crate::bundle::BundleWriter::save_with_options).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BundleSaveOptions {
+    pub packer: PackerOptions,
+    pub layout: Layout,
+}
+
+impl BundleSaveOptions {
+    /// Set `packer`.
+    pub fn with_packer(mut self, packer: PackerOptions) -> Self {
+        self.packer = packer;
+        self
+    }
+
+    /// Set `layout`.
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+}
+
+/// Directory order and compressed size of one layout.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayoutSummary {
+    /// Directory entry names, in data order.
+    pub order: Vec<String>,
+    pub block_count: usize,
+    /// Size of the compressed block data (excluding header and blocks info).
+    pub compressed_size: u64,
+}
+
+/// What a layout pass changed, with the original layout compressed the same way for comparison.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayoutReport {
+    pub before: LayoutSummary,
+    pub after: LayoutSummary,
+    /// Resource ranges that moved inside their resource file.
+    pub moved_ranges: usize,
+    /// Stream offset fields rewritten in serialized files.
+    pub rewritten_offsets: usize,
+    /// Resource files left as they were, with the reason.
+    pub unpacked_resources: Vec<(String, String)>,
+}
+
+impl LayoutReport {
+    pub fn order_changed(&self) -> bool {
+        self.before.order != self.after.order
+    }
+
+    /// Compressed bytes saved by the new layout (negative when it grew).
+    pub fn saved_bytes(&self) -> i64 {
+        self.before.compressed_size as i64 - self.after.compressed_size as i64
+    }
+}
+
+/// A saved bundle and the layout report.
+#[derive(Debug, Clone)]
+pub struct SavedBundle {
+    pub bytes: Vec<u8>,
+    pub report: LayoutReport,
+}
+
+/// A directory entry about to be written.
+#[derive(Debug, Clone)]
+pub(crate) struct BundleEntry {
+    pub name: String,
+    pub flags: u32,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum EntryKind {
+    Serialized,
+    StreamedResource,
+    Resource,
+    Other,
+}
+
+/// A stream offset field: which resource range an object points at, and where the offset is
+/// stored in the serialized file.
+#[derive(Debug, Clone)]
+struct StreamRef {
+    class_id: i32,
+    resource: String,
+    offset: u64,
+    size: u64,
+    /// Byte range of the offset field in the serialized file's bytes.
+    field: Range<usize>,
+    byte_order: ByteOrder,
+}
+
+/// Apply `layout` to `entries`; returns the new entries and the data offsets where compression
+/// blocks should restart.
+pub(crate) fn apply_layout(
+    mut entries: Vec<BundleEntry>,
+    layout: &Layout,
+    report: &mut LayoutReport,
+) -> Result<(Vec<BundleEntry>, Vec<u64>)> {
+    if *layout == Layout::Preserve {
+        return Ok((entries, Vec::new()));
+    }
+
+    let parsed: Vec<Option<SerializedFile>> = entries
+        .iter()
+        .map(|entry| match entry_kind_by_name(&entry.name) {
+            Some(_) => None,
+            None => SerializedFileParser::from_bytes(entry.bytes.clone()).ok(),
+        })
+        .collect();
+    let kinds: Vec<EntryKind> = entries
+        .iter()
+        .zip(&parsed)
+        .map(|(entry, file)| {
+            entry_kind_by_name(&entry.name).unwrap_or(if file.is_some() {
+                EntryKind::Serialized
+            } else {
+                EntryKind::Other
+            })
+        })
+        .collect();
+
+    repack_resources(&mut entries, &parsed, &kinds, report)?;
+
+    let mut order: Vec<usize> = (0..entries.len()).collect();
+    match layout {
+        Layout::Preserve => {}
+        Layout::GroupByType => order.sort_by_key(|&i| kinds[i]),
+        Layout::Custom(names) => order = custom_order(&entries, names)?,
+    }
+
+    let mut boundaries = Vec::new();
+    let mut position = 0u64;
+    let mut slots: Vec<Option<BundleEntry>> = entries.into_iter().map(Some).collect();
+    let mut ordered = Vec::with_capacity(slots.len());
+    for index in order {
+        let entry = slots[index]
+            .take()
+            .expect("layout order visits each entry once");
+        let end = position + entry.bytes.len() as u64;
+        if kinds[index] == EntryKind::Serialized {
+            boundaries.extend([position, end]);
+        }
+        position = end;
+        ordered.push(entry);
+    }
+    boundaries.retain(|&b| b > 0 && b < position);
+    boundaries.dedup();
+    Ok((ordered, boundaries))
+}
+
+fn entry_kind_by_name(name: &str) -> Option<EntryKind> {
+    let lower = name.to_ascii_lowercase();
+    if lower.ends_with(".ress") {
+        Some(EntryKind::StreamedResource)
+    } else if lower.ends_with(".resource") {
+        Some(EntryKind::Resource)
+    } else {
+        None
+    }
+}
+
+fn custom_order(entries: &[BundleEntry], names: &[String]) -> Result<Vec<usize>> {
+    let mut placed = vec![false; entries.len()];
+    let mut order = Vec::with_capacity(entries.len());
+    for name in names {
+        let index = (0..entries.len())
+            .find(|&i| !placed[i] && entries[i].name == *name)
+            .ok_or_else(|| {
+                UnityAssetError::format(format!(
+                    "Custom bundle layout names an entry that is not in the bundle: {}",
+                    name
+                ))
+            })?;
+        placed[index] = true;
+        order.push(index);
+    }
+    order.extend((0..entries.len()).filter(|&i| !placed[i]));
+    Ok(order)
+}
+
+/// Regroup each resource file's streamed ranges by class (then original offset) and rewrite the
+/// offsets pointing at them.
+fn repack_resources(
+    entries: &mut [BundleEntry],
+    parsed: &[Option<SerializedFile>],
+    kinds: &[EntryKind],
+    report: &mut LayoutReport,
+) -> Result<()> {
+    let mut refs: Vec<(usize, StreamRef)> = Vec::new();
+    for (index, file) in parsed.iter().enumerate() {
+        let Some(file) = file else {
+            continue;
+        };
+        match stream_refs(file) {
+            Some(found) => refs.extend(found.into_iter().map(|r| (index, r))),
+            None => {
+                // Offsets into resources can't all be found; moving their data would break them.
+                for (entry, kind) in entries.iter().zip(kinds) {
+                    if matches!(kind, EntryKind::StreamedResource | EntryKind::Resource) {
+                        report.unpacked_resources.push((
+                            entry.name.clone(),
+                            format!("{} has objects without a TypeTree", entries[index].name),
+                        ));
+                    }
+                }
+                return Ok(());
+            }
+        }
+    }
+
+    for resource_index in 0..entries.len() {
+        if !matches!(
+            kinds[resource_index],
+            EntryKind::StreamedResource | EntryKind::Resource
+        ) {
+            continue;
+        }
+        let name = entries[resource_index].name.clone();
+        let targets: Vec<&(usize, StreamRef)> = refs
+            .iter()
+            .filter(|(_, r)| r.resource.eq_ignore_ascii_case(&name))
+            .collect();
+        let plan = match plan_resource(&entries[resource_index].bytes, &targets) {
+            Ok(plan) => plan,
+            Err(reason) => {
+                report.unpacked_resources.push((name, reason));
+                continue;
+            }
+        };
+
+        for (file_index, r) in &targets {
+            let new_offset = plan.moved[&(r.offset, r.size)];
+            if new_offset == r.offset {
+                continue;
+            }
+            let stored = encode_offset(new_offset, r.field.len(), r.byte_order);
+            entries[*file_index].bytes[r.field.clone()].copy_from_slice(&stored);
+            report.rewritten_offsets += 1;
+        }
+        report.moved_ranges += plan
+            .moved
+            .iter()
+            .filter(|((old, _), new)| old != *new)
+            .count();
+        entries[resource_index].bytes = plan.bytes;
+    }
+    Ok(())
+}
+
+struct ResourcePlan {
+    bytes: Vec<u8>,
+    /// `(old offset, size)` -> new offset.
+    moved: HashMap<(u64, u64), u64>,
+}
+
+/// New contents of a resource file: referenced ranges grouped by class, then the bytes no object
+/// in the bundle points at (kept, in their original order, for readers outside the bundle).
+fn plan_resource(
+    data: &[u8],
+    refs: &[&(usize, StreamRef)],
+) -> std::result::Result<ResourcePlan, String> {
+    let len = data.len() as u64;
+    let mut ranges: Vec<(i32, u64, u64)> = Vec::new();
+    for (_, r) in refs {
+        if r.size == 0 {
+            continue;
+        }
+        if r.offset.checked_add(r.size).is_none_or(|end| end > len) {
+            return Err(format!(
+                "range {}+{} is outside the file ({} bytes)",
+                r.offset, r.size, len
+            ));
+        }
+        match ranges
+            .iter_mut()
+            .find(|(_, offset, size)| (*offset, *size) == (r.offset, r.size))
+        {
+            Some(existing) => existing.0 = existing.0.min(r.class_id),
+            None => ranges.push((r.class_id, r.offset, r.size)),
+        }
+    }
+
+    let mut by_offset = ranges.clone();
+    by_offset.sort_by_key(|&(_, offset, _)| offset);
+    if by_offset.windows(2).any(|w| w[0].1 + w[0].2 > w[1].1) {
+        return Err("streamed ranges overlap".to_string());
+    }
+
+    ranges.sort_by_key(|&(class_id, offset, _)| (class_id, offset));
+    let mut bytes = Vec::with_capacity(data.len());
+    let mut moved = HashMap::new();
+    for &(_, offset, size) in &ranges {
+        bytes.resize(bytes.len().next_multiple_of(RESOURCE_ALIGNMENT as usize), 0);
+        moved.insert((offset, size), bytes.len() as u64);
+        bytes.extend_from_slice(&data[offset as usize..(offset + size) as usize]);
+    }
+
+    let mut position = 0u64;
+    for &(_, offset, size) in by_offset.iter().chain([(0, len, 0)].iter()) {
+        if offset > position {
+            bytes.extend_from_slice(&data[position as usize..offset as usize]);
+        }
+        position = position.max(offset + size);
+    }
+
+    for refs in refs {
+        let r = &refs.1;
+        if r.size > 0 && r.field.len() < 8 && moved[&(r.offset, r.size)] > u64::from(u32::MAX) {
+            return Err("a moved range no longer fits a 32-bit offset".to_string());
+        }
+    }
+    moved.extend(
+        refs.iter()
+            .filter(|(_, r)| r.size == 0)
+            .map(|(_, r)| ((r.offset, 0), r.offset)),
+    );
+    Ok(ResourcePlan { bytes, moved })
+}
+
+fn encode_offset(offset: u64, width: usize, byte_order: ByteOrder) -> Vec<u8> {
+    match (width, byte_order) {
+        (8, ByteOrder::Little) => offset.to_le_bytes().to_vec(),
+        (8, ByteOrder::Big) => offset.to_be_bytes().to_vec(),
+        (_, ByteOrder::Little) => (offset as u32).to_le_bytes().to_vec(),
+        (_, ByteOrder::Big) => (offset as u32).to_be_bytes().to_vec(),
+    }
+}
+
+/// Stream offset fields of every object in `file`, or `None` when an object has no TypeTree.
+fn stream_refs(file: &SerializedFile) -> Option<Vec<StreamRef>> {
+    let byte_order = file.header.byte_order();
+    let mut refs = Vec::new();
+    for info in &file.objects {
+        let serialized_type = match usize::try_from(info.type_index) {
+            Ok(index) => file.types.get(index),
+            Err(_) => file.types.iter().find(|t| t.class_id == info.type_id),
+        };
+        let tree = serialized_type
+            .map(|t| &t.type_tree)
+            .filter(|t| file.enable_type_tree && !t.is_empty())?;
+        let root = tree.nodes.first()?;
+        let handle = file.find_object_handle(info.path_id)?;
+        let strings = handle.scan_strings(usize::MAX).ok()??;
+        let data = handle.raw_data().ok()?;
+        let base = usize::try_from(info.byte_start).ok()?;
+
+        for string in strings {
+            let Some((parent, leaf)) = string.path.rsplit_once('.') else {
+                continue;
+            };
+            let Some(node) = find_node(root, parent) else {
+                continue;
+            };
+            let Some((offset_field, size_field)) = stream_fields(node, leaf, &string.range) else {
+                continue;
+            };
+            if offset_field.end > data.len() || size_field.end > data.len() {
+                continue;
+            }
+            refs.push(StreamRef {
+                class_id: info.type_id,
+                resource: resource_name(&string.value),
+                offset: read_uint(&data[offset_field.clone()], byte_order),
+                size: read_uint(&data[size_field], byte_order),
+                field: base + offset_field.start..base + offset_field.end,
+                byte_order,
+            });
+        }
+    }
+    Some(refs)
+}
+
+/// For a `StreamingInfo { offset, size, path }` or `StreamedResource { m_Source, m_Offset,
+/// m_Size }` node whose string child `leaf` is stored at `string`, the byte ranges of the offset
+/// and size fields.
+fn stream_fields(
+    node: &TypeTreeNode,
+    leaf: &str,
+    string: &Range<usize>,
+) -> Option<(Range<usize>, Range<usize>)> {
+    let (offset_name, size_name) = match (node.type_name.as_str(), leaf) {
+        ("StreamingInfo", "path") => ("offset", "size"),
+        ("StreamedResource", "m_Source") => ("m_Offset", "m_Size"),
+        _ => return None,
+    };
+    let string_index = node.children.iter().position(|c| c.name == leaf)?;
+    let mut ranges: HashMap<&str, Range<usize>> = HashMap::new();
+
+    let mut end = string.start;
+    for child in node.children[..string_index].iter().rev() {
+        let size = usize::try_from(child.byte_size).ok()?;
+        ranges.insert(child.name.as_str(), end.checked_sub(size)?..end);
+        end -= size;
+    }
+    let mut start = string.end;
+    for child in &node.children[string_index + 1..] {
+        let size = usize::try_from(child.byte_size).ok()?;
+        ranges.insert(child.name.as_str(), start..start + size);
+        start += size;
+    }
+
+    let offset = ranges.remove(offset_name)?;
+    let size = ranges.remove(size_name)?;
+    if !matches!(offset.len(), 4 | 8) || !matches!(size.len(), 4 | 8) {
+        return None;
+    }
+    Some((offset, size))
+}
+
+/// The node at a scanned field path such as `m_StreamData` or `m_Textures[2].m_StreamData`.
+fn find_node<'a>(root: &'a TypeTreeNode, path: &str) -> Option<&'a TypeTreeNode> {
+    let mut node = root;
+    for segment in path.split('.') {
+        let (name, indexed) = match segment.find('[') {
+            Some(bracket) => (&segment[..bracket], true),
+            None => (segment, false),
+        };
+        node = node.children.iter().find(|c| c.name == name)?;
+        if indexed {
+            node = node
+                .children
+                .iter()
+                .find(|c| c.name == "Array")?
+                .children
+                .iter()
+                .find(|c| c.name == "data")?;
+        }
+    }
+    Some(node)
+}
+
+fn resource_name(stream_path: &str) -> String {
+    let path = normalize_stream_path(stream_path);
+    path.rsplit('/').next().unwrap_or(&path).to_string()
+}
+
+fn read_uint(bytes: &[u8], byte_order: ByteOrder) -> u64 {
+    let mut buf = [0u8; 8];
+    match byte_order {
+        ByteOrder::Little => {
+            buf[..bytes.len()].copy_from_slice(bytes);
+            u64::from_le_bytes(buf)
+        }
+        ByteOrder::Big => {
+            buf[8 - bytes.len()..].copy_from_slice(bytes);
+            u64::from_be_bytes(buf)
+        }
+    }
+}
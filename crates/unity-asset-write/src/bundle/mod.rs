@@ -1,8 +1,12 @@
 //! UnityFS bundle rebuild/save support (UnityPy parity).
 
 mod chunk;
+mod delta;
 mod edits;
+pub mod layout;
 mod writer;
 
+pub use delta::{BundlePatch, PatchHeader, PatchNode, PatchOp, bundle_apply, bundle_diff};
 pub use edits::BundleEdits;
-pub use writer::BundleWriter;
+pub use layout::{BundleSaveOptions, Layout, LayoutReport, LayoutSummary, SavedBundle};
+pub use writer::{AssetBundleExt, BundleWriter};
@@ -1,11 +1,15 @@
 use unity_asset_binary::bundle::{AssetBundle, BundleHeader};
 use unity_asset_binary::unity_version::UnityVersion;
 
-use std::collections::HashSet;
+use std::collections::HashMap;
 use unity_asset_core::{Result, UnityAssetError};
 
 use crate::bundle::BundleEdits;
-use crate::bundle::chunk::chunk_based_compress;
+use crate::bundle::chunk::{UnityFsBlockInfo, chunk_based_compress, chunk_based_compress_segments};
+use crate::bundle::layout::{
+    BundleEntry, BundleSaveOptions, Layout, LayoutReport, LayoutSummary, SavedBundle, apply_layout,
+};
+use crate::serialized_file::SerializedFileExt;
 use crate::{
     BinaryWriter, Endian, PackerOptions, UnityPyPacker, compress_lz4, compress_lzma_unity,
     compress_lzma_unity_with_size,
@@ -32,159 +36,56 @@ impl BundleWriter {
         }
     }
 
-    /// UnityFS (`BundleFile.save_fs`) implementation.
-    pub fn save_unityfs(
+    /// Save a bundle with a [`Layout`] pass, reporting the original and new layouts.
+    ///
+    /// [`Layout::Preserve`] writes the same bytes as [`BundleWriter::save`]. Other layouts are
+    /// only implemented for UnityFS.
+    pub fn save_with_options(
         bundle: &AssetBundle,
         edits: &BundleEdits,
-        options: PackerOptions,
-    ) -> Result<Vec<u8>> {
-        let header = &bundle.header;
-
-        let (data_flag, block_info_flag) = resolve_unityfs_flags(header, bundle, options)?;
-
-        if (data_flag & 0x40) == 0 {
-            return Err(UnityAssetError::format(
-                "UnityFS writer requires DirectoryInfo (data_flag must include 0x40)",
-            ));
-        }
-
-        // Build the concatenated file data stream and the directory table (name, flags, length).
-        let mut data_writer = BinaryWriter::new(Endian::Big);
-        let mut files: Vec<(String, u32, u64)> = Vec::new();
-        let mut existing_names: HashSet<&str> = HashSet::new();
-
-        for node in &bundle.nodes {
-            if !node.is_file() {
-                continue;
+        options: &BundleSaveOptions,
+    ) -> Result<SavedBundle> {
+        if bundle.header.signature != "UnityFS" {
+            if options.layout != Layout::Preserve {
+                return Err(UnityAssetError::format(format!(
+                    "Bundle layout optimization is only implemented for UnityFS (got {})",
+                    bundle.header.signature
+                )));
             }
-
-            let bytes = if let Some(replaced) = edits.get(&node.name) {
-                replaced.to_vec()
-            } else {
-                bundle.extract_node_data(node).map_err(|e| {
-                    UnityAssetError::with_source(
-                        format!("Failed to extract bundle node data: {}", node.name),
-                        e,
-                    )
-                })?
-            };
-
-            existing_names.insert(node.name.as_str());
-
-            let len_u64 = bytes.len() as u64;
-            data_writer.write(&bytes);
-            let flags = edits.flags(&node.name).unwrap_or(node.flags);
-            files.push((node.name.clone(), flags, len_u64));
+            let bytes = Self::save(bundle, edits, options.packer)?;
+            return Ok(SavedBundle {
+                bytes,
+                report: LayoutReport::default(),
+            });
         }
 
-        // Append new files that were not present in the original bundle.
-        let mut extra: Vec<(&str, &[u8])> = edits
-            .iter()
-            .filter(|(name, _)| !existing_names.contains(*name))
-            .collect();
-        extra.sort_by(|(a, _), (b, _)| a.cmp(b));
-        for (name, bytes) in extra {
-            let len_u64 = bytes.len() as u64;
-            data_writer.write(bytes);
-            let flags = edits.flags(name).unwrap_or(0);
-            files.push((name.to_string(), flags, len_u64));
+        let entries = collect_unityfs_entries(bundle, edits)?;
+        let (_, block_info_flag) = resolve_unityfs_flags(&bundle.header, bundle, options.packer)?;
+        let mut report = LayoutReport::default();
+        if options.layout != Layout::Preserve {
+            let data = concat_entries(&entries);
+            let (compressed, blocks) = chunk_based_compress(&data, block_info_flag)?;
+            report.before = layout_summary(&entries, &compressed, &blocks);
         }
 
-        let file_data = data_writer.into_bytes();
-
-        // Compress the file data into UnityFS blocks (UnityPy chunk_based_compress).
-        let (file_data, block_info) = chunk_based_compress(&file_data, block_info_flag)?;
-
-        // Build block info (uncompressed) = hash(16) + block table + directory table.
-        let mut block_writer = BinaryWriter::new(Endian::Big);
-        block_writer.write(&[0u8; 16]);
-
-        let block_count_i32 = i32::try_from(block_info.len()).map_err(|_| {
-            UnityAssetError::format(format!(
-                "UnityFS block count too large for i32: {}",
-                block_info.len()
-            ))
-        })?;
-        block_writer.write_i32(block_count_i32);
-        for b in &block_info {
-            block_writer.write_u32(b.uncompressed_size);
-            block_writer.write_u32(b.compressed_size);
-            block_writer.write_u16(b.flags);
-        }
-
-        let file_count_i32 = i32::try_from(files.len()).map_err(|_| {
-            UnityAssetError::format(format!(
-                "UnityFS file count too large for i32: {}",
-                files.len()
-            ))
-        })?;
-        block_writer.write_i32(file_count_i32);
-
-        let mut offset: i64 = 0;
-        for (name, flags, len) in &files {
-            block_writer.write_i64(offset);
-            block_writer.write_i64(*len as i64);
-            offset = offset
-                .checked_add(*len as i64)
-                .ok_or_else(|| UnityAssetError::format("UnityFS directory offset overflow"))?;
-            block_writer.write_u32(*flags);
-            block_writer.write_string_to_null(name);
-        }
-
-        let uncompressed_block_data = block_writer.into_bytes();
-
-        let block_data = compress_unityfs_blob(&uncompressed_block_data, data_flag & 0x3F)?;
-
-        // Write bundle header + payload (UnityPy ordering and alignment).
-        let uses_block_alignment = unityfs_uses_block_alignment(header);
-
-        let mut writer = BinaryWriter::new(Endian::Big);
-        writer.write_string_to_null(&header.signature);
-        writer.write_u32(header.version);
-        writer.write_string_to_null(&header.unity_version);
-        writer.write_string_to_null(&header.unity_revision);
-
-        let writer_header_pos = writer.position();
-        writer.write_i64(0); // bundle_size placeholder
-        writer.write_u32(u32::try_from(block_data.len()).map_err(|_| {
-            UnityAssetError::format(format!(
-                "UnityFS compressed blocks info too large for u32: {}",
-                block_data.len()
-            ))
-        })?);
-        writer.write_u32(u32::try_from(uncompressed_block_data.len()).map_err(|_| {
-            UnityAssetError::format(format!(
-                "UnityFS uncompressed blocks info too large for u32: {}",
-                uncompressed_block_data.len()
-            ))
-        })?);
-        writer.write_u32(data_flag);
-
-        if uses_block_alignment {
-            writer.align_stream(16);
-        }
-
-        if (data_flag & 0x80) != 0 {
-            // BlocksInfoAtEnd
-            if (data_flag & 0x200) != 0 {
-                writer.align_stream(16);
-            }
-            writer.write(&file_data);
-            writer.write(&block_data);
-        } else {
-            writer.write(&block_data);
-            if (data_flag & 0x200) != 0 {
-                writer.align_stream(16);
-            }
-            writer.write(&file_data);
+        let (entries, boundaries) = apply_layout(entries, &options.layout, &mut report)?;
+        let (bytes, compressed, blocks) =
+            write_unityfs(bundle, &entries, &boundaries, options.packer)?;
+        report.after = layout_summary(&entries, &compressed, &blocks);
+        if options.layout == Layout::Preserve {
+            report.before = report.after.clone();
         }
+        Ok(SavedBundle { bytes, report })
+    }
 
-        let writer_end_pos = writer.position();
-        writer.set_position(writer_header_pos);
-        writer.write_i64(writer_end_pos as i64);
-        writer.set_position(writer_end_pos);
-
-        Ok(writer.into_bytes())
+    /// UnityFS (`BundleFile.save_fs`) implementation.
+    pub fn save_unityfs(
+        bundle: &AssetBundle,
+        edits: &BundleEdits,
+        options: PackerOptions,
+    ) -> Result<Vec<u8>> {
+        let entries = collect_unityfs_entries(bundle, edits)?;
+        Ok(write_unityfs(bundle, &entries, &[], options)?.0)
     }
 
     /// UnityWeb / UnityRaw (`BundleFile.save_web_raw`) implementation.
@@ -200,14 +101,18 @@ impl BundleWriter {
         }
 
         let mut files: Vec<(String, Vec<u8>)> = Vec::new();
-        let mut existing_names: HashSet<&str> = HashSet::new();
+        let mut occurrences: HashMap<&str, usize> = HashMap::new();
 
         for node in &bundle.nodes {
+            let count = occurrences.entry(node.name.as_str()).or_default();
+            let occurrence = *count;
+            *count += 1;
+
             if !node.is_file() {
                 continue;
             }
 
-            let bytes = if let Some(replaced) = edits.get(&node.name) {
+            let bytes = if let Some(replaced) = edits.get(&node.name, occurrence) {
                 replaced.to_vec()
             } else {
                 bundle.extract_node_data(node).map_err(|e| {
@@ -217,18 +122,11 @@ impl BundleWriter {
                     )
                 })?
             };
-
-            existing_names.insert(node.name.as_str());
             files.push((node.name.clone(), bytes));
         }
 
         // Append new files that were not present in the original bundle.
-        let mut extra: Vec<(&str, &[u8])> = edits
-            .iter()
-            .filter(|(name, _)| !existing_names.contains(*name))
-            .collect();
-        extra.sort_by(|(a, _), (b, _)| a.cmp(b));
-        for (name, bytes) in extra {
+        for (name, _, bytes) in edits.unmatched(&occurrences) {
             files.push((name.to_string(), bytes.to_vec()));
         }
 
@@ -360,6 +258,225 @@ impl BundleWriter {
     }
 }
 
+/// `AssetBundle::to_bytes`, for bundles read with `unity-asset-binary`.
+///
+/// Rebuilds the container (directory info, blocks, header sizes and flags) with
+/// [`BundleWriter::save`], compressing with `options.packer`. SerializedFiles in `assets` that
+/// hold inline `ObjectInfo::data` are re-serialized with [`SerializedFileExt::to_bytes`], so
+/// objects patched in place are written back; every other node keeps its original bytes.
+pub trait AssetBundleExt {
+    fn to_bytes(&self, options: PackerOptions) -> Result<Vec<u8>>;
+}
+
+impl AssetBundleExt for AssetBundle {
+    fn to_bytes(&self, options: PackerOptions) -> Result<Vec<u8>> {
+        let mut edits = BundleEdits::new();
+        for (i, asset) in self.assets.iter().enumerate() {
+            if asset.objects.iter().all(|info| info.data.is_empty()) {
+                continue;
+            }
+            let Some(index) = self.asset_node_index(i) else {
+                continue;
+            };
+            let occurrence = self.node_occurrence(index).unwrap_or(0);
+            edits.replace_file_bytes_at(
+                self.nodes[index].name.clone(),
+                occurrence,
+                asset.to_bytes()?,
+            );
+        }
+        BundleWriter::save(self, &edits, options)
+    }
+}
+
+/// Directory entries to write: original nodes (or their replacements), then added files.
+fn collect_unityfs_entries(bundle: &AssetBundle, edits: &BundleEdits) -> Result<Vec<BundleEntry>> {
+    let mut entries = Vec::new();
+    let mut occurrences: HashMap<&str, usize> = HashMap::new();
+
+    for node in &bundle.nodes {
+        let count = occurrences.entry(node.name.as_str()).or_default();
+        let occurrence = *count;
+        *count += 1;
+
+        // Directory (0x1) and deleted (0x2) entries carry no data. Plain files such as `.resS`
+        // nodes may have no flags at all, so `is_file` (the SerializedFile bit) is not enough.
+        if node.flags & 0x3 != 0 {
+            continue;
+        }
+
+        let bytes = if let Some(replaced) = edits.get(&node.name, occurrence) {
+            replaced.to_vec()
+        } else {
+            bundle.extract_node_data(node).map_err(|e| {
+                UnityAssetError::with_source(
+                    format!("Failed to extract bundle node data: {}", node.name),
+                    e,
+                )
+            })?
+        };
+
+        let flags = edits.flags(&node.name, occurrence).unwrap_or(node.flags);
+        entries.push(BundleEntry {
+            name: node.name.clone(),
+            flags,
+            bytes,
+        });
+    }
+
+    // Append new files that were not present in the original bundle.
+    for (name, occurrence, bytes) in edits.unmatched(&occurrences) {
+        let flags = edits.flags(name, occurrence).unwrap_or(0);
+        entries.push(BundleEntry {
+            name: name.to_string(),
+            flags,
+            bytes: bytes.to_vec(),
+        });
+    }
+
+    Ok(entries)
+}
+
+fn concat_entries(entries: &[BundleEntry]) -> Vec<u8> {
+    let mut data_writer = BinaryWriter::new(Endian::Big);
+    for entry in entries {
+        data_writer.write(&entry.bytes);
+    }
+    data_writer.into_bytes()
+}
+
+fn layout_summary(
+    entries: &[BundleEntry],
+    compressed: &[u8],
+    blocks: &[UnityFsBlockInfo],
+) -> LayoutSummary {
+    LayoutSummary {
+        order: entries.iter().map(|e| e.name.clone()).collect(),
+        block_count: blocks.len(),
+        compressed_size: compressed.len() as u64,
+    }
+}
+
+/// Write a UnityFS bundle holding `entries`, restarting compression blocks at `boundaries`.
+/// Returns the bundle bytes plus the compressed block data and block table.
+pub(super) fn write_unityfs(
+    bundle: &AssetBundle,
+    entries: &[BundleEntry],
+    boundaries: &[u64],
+    options: PackerOptions,
+) -> Result<(Vec<u8>, Vec<u8>, Vec<UnityFsBlockInfo>)> {
+    let header = &bundle.header;
+
+    let (data_flag, block_info_flag) = resolve_unityfs_flags(header, bundle, options)?;
+
+    if (data_flag & 0x40) == 0 {
+        return Err(UnityAssetError::format(
+            "UnityFS writer requires DirectoryInfo (data_flag must include 0x40)",
+        ));
+    }
+
+    let file_data = concat_entries(entries);
+    let files: Vec<(&str, u32, u64)> = entries
+        .iter()
+        .map(|e| (e.name.as_str(), e.flags, e.bytes.len() as u64))
+        .collect();
+
+    // Compress the file data into UnityFS blocks (UnityPy chunk_based_compress).
+    let (file_data, block_info) =
+        chunk_based_compress_segments(&file_data, boundaries, block_info_flag)?;
+
+    // Build block info (uncompressed) = hash(16) + block table + directory table.
+    let mut block_writer = BinaryWriter::new(Endian::Big);
+    block_writer.write(&[0u8; 16]);
+
+    let block_count_i32 = i32::try_from(block_info.len()).map_err(|_| {
+        UnityAssetError::format(format!(
+            "UnityFS block count too large for i32: {}",
+            block_info.len()
+        ))
+    })?;
+    block_writer.write_i32(block_count_i32);
+    for b in &block_info {
+        block_writer.write_u32(b.uncompressed_size);
+        block_writer.write_u32(b.compressed_size);
+        block_writer.write_u16(b.flags);
+    }
+
+    let file_count_i32 = i32::try_from(files.len()).map_err(|_| {
+        UnityAssetError::format(format!(
+            "UnityFS file count too large for i32: {}",
+            files.len()
+        ))
+    })?;
+    block_writer.write_i32(file_count_i32);
+
+    let mut offset: i64 = 0;
+    for (name, flags, len) in &files {
+        block_writer.write_i64(offset);
+        block_writer.write_i64(*len as i64);
+        offset = offset
+            .checked_add(*len as i64)
+            .ok_or_else(|| UnityAssetError::format("UnityFS directory offset overflow"))?;
+        block_writer.write_u32(*flags);
+        block_writer.write_string_to_null(name);
+    }
+
+    let uncompressed_block_data = block_writer.into_bytes();
+
+    let block_data = compress_unityfs_blob(&uncompressed_block_data, data_flag & 0x3F)?;
+
+    // Write bundle header + payload (UnityPy ordering and alignment).
+    let uses_block_alignment = unityfs_uses_block_alignment(header);
+
+    let mut writer = BinaryWriter::new(Endian::Big);
+    writer.write_string_to_null(&header.signature);
+    writer.write_u32(header.version);
+    writer.write_string_to_null(&header.unity_version);
+    writer.write_string_to_null(&header.unity_revision);
+
+    let writer_header_pos = writer.position();
+    writer.write_i64(0); // bundle_size placeholder
+    writer.write_u32(u32::try_from(block_data.len()).map_err(|_| {
+        UnityAssetError::format(format!(
+            "UnityFS compressed blocks info too large for u32: {}",
+            block_data.len()
+        ))
+    })?);
+    writer.write_u32(u32::try_from(uncompressed_block_data.len()).map_err(|_| {
+        UnityAssetError::format(format!(
+            "UnityFS uncompressed blocks info too large for u32: {}",
+            uncompressed_block_data.len()
+        ))
+    })?);
+    writer.write_u32(data_flag);
+
+    if uses_block_alignment {
+        writer.align_stream(16);
+    }
+
+    if (data_flag & 0x80) != 0 {
+        // BlocksInfoAtEnd
+        if (data_flag & 0x200) != 0 {
+            writer.align_stream(16);
+        }
+        writer.write(&file_data);
+        writer.write(&block_data);
+    } else {
+        writer.write(&block_data);
+        if (data_flag & 0x200) != 0 {
+            writer.align_stream(16);
+        }
+        writer.write(&file_data);
+    }
+
+    let writer_end_pos = writer.position();
+    writer.set_position(writer_header_pos);
+    writer.write_i64(writer_end_pos as i64);
+    writer.set_position(writer_end_pos);
+
+    Ok((writer.into_bytes(), file_data, block_info))
+}
+
 fn resolve_unityfs_flags(
     header: &BundleHeader,
     bundle: &AssetBundle,
@@ -539,9 +656,7 @@ mod tests {
         let saved = BundleWriter::save(
             &bundle,
             &BundleEdits::default(),
-            PackerOptions {
-                packer: UnityPyPacker::Original,
-            },
+            PackerOptions::default().with_packer(UnityPyPacker::Original),
         )
         .unwrap();
 
@@ -566,9 +681,7 @@ mod tests {
         let saved = BundleWriter::save(
             &bundle,
             &BundleEdits::default(),
-            PackerOptions {
-                packer: UnityPyPacker::Original,
-            },
+            PackerOptions::default().with_packer(UnityPyPacker::Original),
         )
         .unwrap();
 
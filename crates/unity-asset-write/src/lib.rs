@@ -14,6 +14,7 @@ pub mod bundle;
 mod compression;
 pub mod object;
 mod packer;
+pub mod prelude;
 pub mod resources;
 pub mod serialized_file;
 pub mod typetree;
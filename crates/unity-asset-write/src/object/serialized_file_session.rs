@@ -1,6 +1,7 @@
 use indexmap::IndexMap;
 use unity_asset_binary::asset::{ObjectInfo, SerializedFile, SerializedType};
 use unity_asset_binary::reader::ByteOrder;
+use unity_asset_binary::string_policy::RawString;
 use unity_asset_binary::typetree::TypeTree;
 use unity_asset_core::{UnityAssetError, UnityClass, UnityValue};
 
@@ -70,7 +71,12 @@ impl<'a> SerializedFileEditSession<'a> {
 
         f(&mut obj.class)?;
 
-        let bytes = encode_object_typetree(self.file, handle.info(), obj.class.properties())?;
+        let bytes = encode_object_typetree(
+            self.file,
+            handle.info(),
+            obj.class.properties(),
+            obj.raw_strings(),
+        )?;
         self.edits.set_object_bytes(path_id, bytes);
         self.mark_changed();
         Ok(())
@@ -88,7 +94,7 @@ impl<'a> SerializedFileEditSession<'a> {
                 path_id
             ))
         })?;
-        let bytes = encode_object_typetree(self.file, info, properties)?;
+        let bytes = encode_object_typetree(self.file, info, properties, &[])?;
         self.edits.set_object_bytes(path_id, bytes);
         self.mark_changed();
         Ok(())
@@ -119,6 +125,7 @@ fn encode_object_typetree(
     file: &SerializedFile,
     info: &ObjectInfo,
     properties: &IndexMap<String, UnityValue>,
+    raw_strings: &[RawString],
 ) -> Result<Vec<u8>> {
     let Some(tree) = type_tree_for_object(file, info) else {
         return Err(UnityAssetError::format(format!(
@@ -149,14 +156,16 @@ fn encode_object_typetree(
         )
     })?;
 
-    writer.write_object_with_original_bytes(
-        &mut w,
-        properties,
-        original,
-        TypeTreeWriteOptions {
-            allow_missing_fields: false,
-        },
-    )?;
+    writer
+        .raw_strings(raw_strings)
+        .write_object_with_original_bytes(
+            &mut w,
+            properties,
+            original,
+            TypeTreeWriteOptions {
+                allow_missing_fields: false,
+            },
+        )?;
     Ok(w.into_bytes())
 }
 
@@ -49,10 +49,24 @@ impl fmt::Display for UnityPyPacker {
 
 /// Options for saving/repacking outputs.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct PackerOptions {
     pub packer: UnityPyPacker,
 }
 
+impl PackerOptions {
+    /// Recompress with the codecs the bundle was loaded with (UnityPy `"original"`).
+    pub fn match_original() -> Self {
+        Self::default().with_packer(UnityPyPacker::Original)
+    }
+
+    /// Set `packer`.
+    pub fn with_packer(mut self, packer: UnityPyPacker) -> Self {
+        self.packer = packer;
+        self
+    }
+}
+
 impl Default for PackerOptions {
     fn default() -> Self {
         Self {
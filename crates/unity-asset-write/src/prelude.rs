@@ -0,0 +1,8 @@
+//! Commonly used write types, for glob import.
+
+pub use crate::bundle::{AssetBundleExt, BundleEdits, BundleSaveOptions, BundleWriter, Layout};
+pub use crate::serialized_file::{
+    SerializedFileEdits, SerializedFileExt, SerializedFileSaveOptions, SerializedFileWriter,
+};
+pub use crate::typetree::{TypeTreeWriteOptions, TypeTreeWriter};
+pub use crate::{PackerOptions, Result, UnityAssetError, UnityPyPacker};
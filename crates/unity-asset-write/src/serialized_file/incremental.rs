@@ -0,0 +1,453 @@
+//! Incremental SerializedFile saving.
+//!
+//! Instead of rebuilding the metadata and data streams, an incremental save copies the original
+//! file and only rewrites the payloads of dirty objects plus their object table entries:
+//! - a payload that fits its original slot is written in place and the rest of the slot zeroed
+//! - a payload that grew is appended to the end of the data region (8-aligned) and its entry
+//!   points there; the header's `file_size` follows
+//!
+//! When neither works (relocation disabled, legacy v<9 layout with trailing metadata, offsets that
+//! no longer fit the entry fields, added externals, added/removed objects) the writer falls back
+//! to a full [`SerializedFileWriter::save_with_options`] rebuild.
+//!
+//! Only object payloads are considered: edits to types, externals or other metadata on the parsed
+//! `SerializedFile` are not detected and require a full save.
+
+use crate::Result;
+use crate::serialized_file::edit::SerializedFileEdits;
+use crate::serialized_file::writer::{SerializedFileSaveOptions, SerializedFileWriter};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+use unity_asset_binary::asset::{SerializedFile, SerializedType};
+use unity_asset_binary::reader::{BinaryReader, ByteOrder};
+use unity_asset_core::UnityAssetError;
+
+/// Data stream alignment between objects (matches the full writer).
+const OBJECT_ALIGNMENT: usize = 8;
+
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct IncrementalSaveOptions {
+    /// Append objects that outgrew their slot to the end of the data region.
+    ///
+    /// When false, any grown object makes the whole save fall back to a full rewrite.
+    pub allow_relocation: bool,
+    /// Options for the full rewrite fallback.
+    pub full_save: SerializedFileSaveOptions,
+}
+
+impl IncrementalSaveOptions {
+    /// Set `allow_relocation`.
+    pub fn with_allow_relocation(mut self, allow_relocation: bool) -> Self {
+        self.allow_relocation = allow_relocation;
+        self
+    }
+
+    /// Set `full_save`.
+    pub fn with_full_save(mut self, full_save: SerializedFileSaveOptions) -> Self {
+        self.full_save = full_save;
+        self
+    }
+}
+
+impl Default for IncrementalSaveOptions {
+    fn default() -> Self {
+        Self {
+            allow_relocation: true,
+            full_save: SerializedFileSaveOptions::default(),
+        }
+    }
+}
+
+/// How a dirty object was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectPatchStrategy {
+    /// Written over its original slot (equal or smaller size).
+    InPlace,
+    /// Appended to the end of the data region.
+    Relocated,
+    /// The file was rebuilt by the full writer.
+    FullRewrite,
+}
+
+/// One dirty object in an [`IncrementalSaveReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchedObject {
+    pub path_id: i64,
+    pub strategy: ObjectPatchStrategy,
+    pub old_size: u32,
+    pub new_size: u32,
+    /// Absolute offset of the payload in the saved file (`None` after a full rewrite).
+    pub byte_start: Option<u64>,
+}
+
+/// Outcome of an incremental save.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IncrementalSaveReport {
+    /// Dirty objects in object table order; unchanged objects are not listed.
+    pub objects: Vec<PatchedObject>,
+    /// Why the writer fell back to a full rewrite, if it did.
+    pub full_rewrite_reason: Option<String>,
+    /// Size of the saved file.
+    pub file_size: u64,
+}
+
+impl IncrementalSaveReport {
+    pub fn is_full_rewrite(&self) -> bool {
+        self.full_rewrite_reason.is_some()
+    }
+
+    /// Number of objects written with `strategy`.
+    pub fn count(&self, strategy: ObjectPatchStrategy) -> usize {
+        self.objects
+            .iter()
+            .filter(|o| o.strategy == strategy)
+            .count()
+    }
+}
+
+impl SerializedFileWriter {
+    /// Save by patching a copy of the original bytes (see the module docs).
+    pub fn save_incremental(
+        file: &SerializedFile,
+        edits: &SerializedFileEdits,
+        options: IncrementalSaveOptions,
+    ) -> Result<(Vec<u8>, IncrementalSaveReport)> {
+        match plan(file, edits, options)? {
+            Plan::Patch(patch) => {
+                let mut out = file.data().to_vec();
+                out.resize(patch.new_len, 0);
+                for (offset, bytes) in &patch.writes {
+                    out[*offset..*offset + bytes.len()].copy_from_slice(bytes);
+                }
+                Ok((out, patch.report))
+            }
+            Plan::Full(report) => {
+                let out = Self::save_with_options(file, edits, options.full_save)?;
+                let report = full_report(report, out.len());
+                Ok((out, report))
+            }
+        }
+    }
+
+    /// Patch the file at `path` in place: only the dirty byte ranges are written (opt-in).
+    ///
+    /// `path` must hold the bytes `file` was parsed from; its length is checked. A full rewrite
+    /// fallback replaces the whole file.
+    pub fn save_incremental_in_place(
+        path: impl AsRef<Path>,
+        file: &SerializedFile,
+        edits: &SerializedFileEdits,
+        options: IncrementalSaveOptions,
+    ) -> Result<IncrementalSaveReport> {
+        let path = path.as_ref();
+        let io_err = |e: std::io::Error| {
+            UnityAssetError::with_source(format!("Failed to patch {}", path.display()), e)
+        };
+
+        match plan(file, edits, options)? {
+            Plan::Patch(patch) => {
+                let mut out = std::fs::OpenOptions::new()
+                    .read(true)
+                    .write(true)
+                    .open(path)
+                    .map_err(io_err)?;
+                let on_disk = out.metadata().map_err(io_err)?.len();
+                if on_disk != file.data().len() as u64 {
+                    return Err(UnityAssetError::format(format!(
+                        "{} is {} bytes but the parsed SerializedFile is {} bytes",
+                        path.display(),
+                        on_disk,
+                        file.data().len()
+                    )));
+                }
+                out.set_len(patch.new_len as u64).map_err(io_err)?;
+                for (offset, bytes) in &patch.writes {
+                    out.seek(SeekFrom::Start(*offset as u64)).map_err(io_err)?;
+                    out.write_all(bytes).map_err(io_err)?;
+                }
+                out.flush().map_err(io_err)?;
+                Ok(patch.report)
+            }
+            Plan::Full(report) => {
+                let out = Self::save_with_options(file, edits, options.full_save)?;
+                std::fs::write(path, &out).map_err(io_err)?;
+                Ok(full_report(report, out.len()))
+            }
+        }
+    }
+}
+
+enum Plan {
+    Patch(Patch),
+    Full(IncrementalSaveReport),
+}
+
+struct Patch {
+    /// `(absolute offset, bytes)`, applied in order
+    writes: Vec<(usize, Vec<u8>)>,
+    new_len: usize,
+    report: IncrementalSaveReport,
+}
+
+fn full_report(mut report: IncrementalSaveReport, len: usize) -> IncrementalSaveReport {
+    for object in &mut report.objects {
+        object.strategy = ObjectPatchStrategy::FullRewrite;
+        object.byte_start = None;
+    }
+    report.file_size = len as u64;
+    report
+}
+
+/// Location of one object table entry's offset/size fields in the original bytes.
+struct EntryFields {
+    path_id: i64,
+    byte_start_at: usize,
+    byte_size_at: usize,
+}
+
+fn plan(
+    file: &SerializedFile,
+    edits: &SerializedFileEdits,
+    options: IncrementalSaveOptions,
+) -> Result<Plan> {
+    let original = file.data();
+    let version = file.header.version;
+    let data_offset = file.header.data_offset as usize;
+    let endian = file.header.byte_order();
+
+    // Dirty objects with their new payloads.
+    let mut dirty = Vec::new();
+    for (index, info) in file.objects.iter().enumerate() {
+        let new_bytes = match edits.get(info.path_id) {
+            Some(bytes) => bytes,
+            None if !info.data.is_empty() => info.data.as_slice(),
+            None => continue,
+        };
+        let old = file.object_bytes(info).map_err(|e| {
+            UnityAssetError::with_source(
+                format!(
+                    "Failed to read original object bytes: path_id={}",
+                    info.path_id
+                ),
+                e,
+            )
+        })?;
+        if old != new_bytes {
+            dirty.push((index, new_bytes));
+        }
+    }
+
+    let mut report = IncrementalSaveReport {
+        objects: dirty
+            .iter()
+            .map(|&(index, bytes)| PatchedObject {
+                path_id: file.objects[index].path_id,
+                strategy: ObjectPatchStrategy::InPlace,
+                old_size: file.objects[index].byte_size,
+                new_size: bytes.len() as u32,
+                byte_start: Some(file.objects[index].byte_start),
+            })
+            .collect(),
+        full_rewrite_reason: None,
+        file_size: original.len() as u64,
+    };
+    let fall_back = |mut report: IncrementalSaveReport, reason: String| {
+        report.full_rewrite_reason = Some(reason);
+        Ok(Plan::Full(report))
+    };
+
+    if edits
+        .additional_externals
+        .iter()
+        .any(|ext| !file.externals.iter().any(|e| e.path == ext.path))
+    {
+        return fall_back(report, "external references were added".to_string());
+    }
+    let entries = match object_table_fields(file) {
+        Ok(entries) => entries,
+        Err(e) => return fall_back(report, format!("object table not readable: {}", e)),
+    };
+    if entries.len() != file.objects.len()
+        || entries
+            .iter()
+            .zip(&file.objects)
+            .any(|(entry, info)| entry.path_id != info.path_id)
+    {
+        return fall_back(report, "objects were added or removed".to_string());
+    }
+
+    let put_u32 = |value: u32| match endian {
+        ByteOrder::Little => value.to_le_bytes().to_vec(),
+        ByteOrder::Big => value.to_be_bytes().to_vec(),
+    };
+    let put_i64 = |value: i64| match endian {
+        ByteOrder::Little => value.to_le_bytes().to_vec(),
+        ByteOrder::Big => value.to_be_bytes().to_vec(),
+    };
+
+    let mut writes: Vec<(usize, Vec<u8>)> = Vec::new();
+    let mut end = original.len();
+    for (dirty_index, &(index, bytes)) in dirty.iter().enumerate() {
+        let info = &file.objects[index];
+        let entry = &entries[index];
+        let new_size: u32 = match bytes.len().try_into() {
+            Ok(size) => size,
+            Err(_) => {
+                let reason = format!("object {} is too large: {}", info.path_id, bytes.len());
+                return fall_back(report, reason);
+            }
+        };
+
+        let start = if new_size <= info.byte_size {
+            let start = info.byte_start as usize;
+            let mut slot = bytes.to_vec();
+            slot.resize(info.byte_size as usize, 0);
+            writes.push((start, slot));
+            start
+        } else {
+            if !options.allow_relocation {
+                let reason = format!("object {} grew and relocation is disabled", info.path_id);
+                return fall_back(report, reason);
+            }
+            if version < 9 {
+                let reason = format!(
+                    "object {} grew and legacy (v{}) files keep metadata after the data",
+                    info.path_id, version
+                );
+                return fall_back(report, reason);
+            }
+            if file.header.file_size != original.len() as u64 {
+                let reason = format!(
+                    "object {} grew and the header file_size {} does not match the data ({} bytes)",
+                    info.path_id,
+                    file.header.file_size,
+                    original.len()
+                );
+                return fall_back(report, reason);
+            }
+
+            let relative = (end - data_offset).next_multiple_of(OBJECT_ALIGNMENT);
+            let start = data_offset + relative;
+            if version < 22 && u32::try_from(start + bytes.len()).is_err() {
+                let reason = format!("object {} does not fit a v{} file", info.path_id, version);
+                return fall_back(report, reason);
+            }
+            let mut tail = vec![0u8; start - end];
+            tail.extend_from_slice(bytes);
+            writes.push((end, tail));
+            end = start + bytes.len();
+
+            let field = if version >= 22 {
+                put_i64(relative as i64)
+            } else {
+                put_u32(relative as u32)
+            };
+            writes.push((entry.byte_start_at, field));
+            report.objects[dirty_index].strategy = ObjectPatchStrategy::Relocated;
+            start
+        };
+        writes.push((entry.byte_size_at, put_u32(new_size)));
+        report.objects[dirty_index].byte_start = Some(start as u64);
+    }
+
+    if end != original.len() {
+        // Header fields are always big-endian.
+        if version >= 22 {
+            writes.push((24, (end as i64).to_be_bytes().to_vec()));
+        } else {
+            writes.push((4, (end as u32).to_be_bytes().to_vec()));
+        }
+    }
+    report.file_size = end as u64;
+
+    Ok(Plan::Patch(Patch {
+        writes,
+        new_len: end,
+        report,
+    }))
+}
+
+/// Walk the original metadata up to the object table and record where each entry's
+/// `byte_start`/`byte_size` fields live.
+fn object_table_fields(
+    file: &SerializedFile,
+) -> unity_asset_binary::error::Result<Vec<EntryFields>> {
+    let header = &file.header;
+    let version = header.version;
+    let mut reader = BinaryReader::new(file.data(), header.byte_order());
+
+    let metadata_start = if version >= 22 {
+        48
+    } else if version >= 9 {
+        20
+    } else {
+        // Legacy: metadata at the end, after a 1-byte endian flag.
+        header.file_size - header.metadata_size as u64 + 1
+    };
+    reader.set_position(metadata_start)?;
+
+    if version >= 7 {
+        reader.read_cstring()?;
+    }
+    if version >= 8 {
+        reader.read_i32()?;
+    }
+    let enable_type_tree = if version >= 13 {
+        reader.read_bool()?
+    } else {
+        file.enable_type_tree
+    };
+    let type_count = reader.read_i32()?.max(0);
+    for _ in 0..type_count {
+        SerializedType::from_reader(&mut reader, version, enable_type_tree, false)?;
+    }
+    let big_id_enabled = if (7..14).contains(&version) {
+        reader.read_i32()? != 0
+    } else {
+        false
+    };
+
+    let object_count = reader.read_i32()?.max(0) as usize;
+    let mut entries = Vec::with_capacity(object_count);
+    for _ in 0..object_count {
+        let path_id = if big_id_enabled {
+            reader.read_i64()?
+        } else if version < 14 {
+            reader.read_i32()? as i64
+        } else {
+            reader.align()?;
+            reader.read_i64()?
+        };
+
+        let byte_start_at = reader.position() as usize;
+        if version >= 22 {
+            reader.read_i64()?;
+        } else {
+            reader.read_u32()?;
+        }
+        let byte_size_at = reader.position() as usize;
+        reader.read_u32()?;
+        reader.read_i32()?;
+
+        if version < 16 {
+            reader.read_u16()?;
+        }
+        if version < 11 {
+            reader.read_u16()?;
+        }
+        if (11..17).contains(&version) {
+            reader.read_i16()?;
+        }
+        if version == 15 || version == 16 {
+            reader.read_u8()?;
+        }
+
+        entries.push(EntryFields {
+            path_id,
+            byte_start_at,
+            byte_size_at,
+        });
+    }
+    Ok(entries)
+}
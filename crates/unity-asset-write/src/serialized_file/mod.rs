@@ -4,11 +4,18 @@
 //! - metadata stream (types, object table, scripts, externals, ref types, user info)
 //! - data stream (object payloads)
 //! - header + offsets + alignment
+//!
+//! Small edits can instead be patched into a copy of the original bytes (or the file itself),
+//! see `SerializedFileWriter::save_incremental`.
 
 mod edit;
+mod incremental;
 mod types_write;
 mod typetree_dump;
 mod writer;
 
 pub use edit::SerializedFileEdits;
-pub use writer::{SerializedFileSaveOptions, SerializedFileWriter};
+pub use incremental::{
+    IncrementalSaveOptions, IncrementalSaveReport, ObjectPatchStrategy, PatchedObject,
+};
+pub use writer::{SerializedFileExt, SerializedFileSaveOptions, SerializedFileWriter};
@@ -8,6 +8,7 @@ use unity_asset_binary::asset::{ObjectInfo, SerializedFile};
 use unity_asset_core::UnityAssetError;
 
 #[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub struct SerializedFileSaveOptions {
     /// Best-effort: allow saving even if not all object bytes were preloaded.
     ///
@@ -15,6 +16,14 @@ pub struct SerializedFileSaveOptions {
     pub allow_lazy_object_reads: bool,
 }
 
+impl SerializedFileSaveOptions {
+    /// Set `allow_lazy_object_reads`.
+    pub fn with_allow_lazy_object_reads(mut self, allow_lazy_object_reads: bool) -> Self {
+        self.allow_lazy_object_reads = allow_lazy_object_reads;
+        self
+    }
+}
+
 impl Default for SerializedFileSaveOptions {
     fn default() -> Self {
         Self {
@@ -81,9 +90,9 @@ impl SerializedFileWriter {
         meta.write_i32(obj_count_i32);
 
         for info in &file.objects {
-            write_object_entry(file, info, edits, &mut meta, &mut data, options)?;
-            // UnityPy aligns object data stream to 8 after each object.
+            // Objects start 8-aligned; like Unity, the last one is not padded.
             data.align_stream(8);
+            write_object_entry(file, info, edits, &mut meta, &mut data, options)?;
         }
 
         // Script types (v>=11)
@@ -162,7 +171,10 @@ impl SerializedFileWriter {
         } else {
             let mut out = header_size + metadata_size;
             out += (16 - (out % 16)) % 16;
-            out
+            // Unity starts small files' data at 4096; keep the original offset while the
+            // metadata still fits in front of it.
+            let original: usize = file.header.data_offset.try_into().unwrap_or(0);
+            out.max(original)
         };
 
         let file_size: usize = if version < 9 {
@@ -239,7 +251,7 @@ impl SerializedFileWriter {
             out.write(&file.header.reserved);
 
             out.write(meta.bytes());
-            out.align_stream(16);
+            out.write(&vec![0u8; data_offset - out.position()]);
             out.write(data.bytes());
         } else {
             // UnityPy writes an "old" header with zeros, followed by the extended fields.
@@ -255,7 +267,7 @@ impl SerializedFileWriter {
             out.write_i64(file.header.unknown);
 
             out.write(meta.bytes());
-            out.align_stream(16);
+            out.write(&vec![0u8; data_offset - out.position()]);
             out.write(data.bytes());
         }
 
@@ -263,6 +275,23 @@ impl SerializedFileWriter {
     }
 }
 
+/// `SerializedFile::to_bytes`, for files read with `unity-asset-binary`.
+///
+/// Re-serializes the header, metadata (types, object table, script types, externals, ref types)
+/// and object data in the file's own byte order and format version. Objects with inline
+/// `ObjectInfo::data` (e.g. from `serialize_object_with_typetree`) are written with those bytes,
+/// the rest with their original ones, so an unmodified file comes back byte-identical. Use
+/// [`SerializedFileWriter::save`] to apply [`SerializedFileEdits`] without touching the file.
+pub trait SerializedFileExt {
+    fn to_bytes(&self) -> Result<Vec<u8>>;
+}
+
+impl SerializedFileExt for SerializedFile {
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        SerializedFileWriter::save(self, &SerializedFileEdits::new())
+    }
+}
+
 fn write_object_entry(
     file: &SerializedFile,
     info: &ObjectInfo,
@@ -1,4 +1,6 @@
+use std::collections::HashMap;
 use unity_asset_binary::asset::SerializedType;
+use unity_asset_binary::string_policy::RawString;
 
 /// Mutable write context, mirroring UnityPy's `TypeTreeConfig` behavior.
 #[derive(Debug, Clone, Default)]
@@ -8,4 +10,38 @@ pub struct TypeTreeWriteContext<'a> {
     pub has_managed_registry: bool,
     /// Optional managed reference type list (Unity `ref_types`) for resolving `ReferencedObjectData`.
     pub ref_types: Option<&'a [SerializedType]>,
+    /// Original bytes for strings that were decoded from non-UTF-8 data; matching values are
+    /// written back byte-exact.
+    pub raw_strings: &'a [RawString],
+    /// Dotted path of the node being written; only tracked when `raw_strings` is non-empty.
+    pub path: String,
+    /// `string` fields written so far per path, matched against [`RawString::occurrence`].
+    pub string_counts: HashMap<String, usize>,
+}
+
+impl<'a> TypeTreeWriteContext<'a> {
+    /// Append `name` to the current path, returning the length to truncate back to.
+    pub(crate) fn enter(&mut self, name: &str) -> usize {
+        let base = self.path.len();
+        if base > 0 {
+            self.path.push('.');
+        }
+        self.path.push_str(name);
+        base
+    }
+
+    /// Count a `string` field at the current path and return the raw bytes recorded for it, if
+    /// its text is unchanged since it was read.
+    pub(crate) fn raw_string(&mut self, decoded: &str) -> Option<&'a RawString> {
+        if self.raw_strings.is_empty() {
+            return None;
+        }
+        let count = self.string_counts.entry(self.path.clone()).or_insert(0);
+        let occurrence = *count;
+        *count += 1;
+        let path = self.path.as_str();
+        self.raw_strings
+            .iter()
+            .find(|r| r.path == path && r.occurrence == occurrence && r.decoded == decoded)
+    }
 }
@@ -2,12 +2,13 @@ use crate::binary_writer::{BinaryWriter, Endian};
 use crate::typetree::context::TypeTreeWriteContext;
 use crate::typetree::primitives::write_primitive;
 use crate::typetree::referenced_object::write_referenced_object;
-use crate::typetree::writer::{TypeTreeWriteOptions, write_value};
+use crate::typetree::writer::{TypeTreeWriteOptions, write_node};
 use crate::{Result, UnityAssetError};
 
 use indexmap::IndexMap;
 use unity_asset_binary::asset::SerializedType;
 use unity_asset_binary::reader::{BinaryReader, ByteOrder};
+use unity_asset_binary::string_policy::RawString;
 use unity_asset_binary::typetree::{TypeTree, TypeTreeNode, TypeTreeSerializer};
 use unity_asset_core::UnityValue;
 
@@ -15,6 +16,7 @@ pub(crate) fn write_object_with_original_bytes(
     writer: &mut BinaryWriter,
     tree: &TypeTree,
     ref_types: Option<&[SerializedType]>,
+    raw_strings: &[RawString],
     properties: &IndexMap<String, UnityValue>,
     original_bytes: &[u8],
     options: TypeTreeWriteOptions,
@@ -33,6 +35,7 @@ pub(crate) fn write_object_with_original_bytes(
     let mut original = BinaryReader::new(original_bytes, byte_order);
     let mut ctx = TypeTreeWriteContext {
         ref_types,
+        raw_strings,
         ..Default::default()
     };
 
@@ -96,6 +99,46 @@ fn write_value_with_template(
     original_bytes: &[u8],
     ctx: &mut TypeTreeWriteContext<'_>,
     options: TypeTreeWriteOptions,
+) -> Result<()> {
+    if ctx.raw_strings.is_empty() {
+        return write_node_with_template(
+            writer,
+            serializer,
+            ref_types,
+            byte_order,
+            node,
+            value,
+            original_bytes,
+            ctx,
+            options,
+        );
+    }
+    let base = ctx.enter(&node.name);
+    let result = write_node_with_template(
+        writer,
+        serializer,
+        ref_types,
+        byte_order,
+        node,
+        value,
+        original_bytes,
+        ctx,
+        options,
+    );
+    ctx.path.truncate(base);
+    result
+}
+
+fn write_node_with_template(
+    writer: &mut BinaryWriter,
+    serializer: &TypeTreeSerializer<'_>,
+    ref_types: Option<&[SerializedType]>,
+    byte_order: ByteOrder,
+    node: &TypeTreeNode,
+    value: &UnityValue,
+    original_bytes: &[u8],
+    ctx: &mut TypeTreeWriteContext<'_>,
+    options: TypeTreeWriteOptions,
 ) -> Result<()> {
     let mut align = node.is_aligned();
     if node
@@ -106,6 +149,10 @@ fn write_value_with_template(
         align = true;
     }
 
+    if node.type_name == "string" {
+        return write_node(writer, node, value, ctx, options);
+    }
+
     if write_primitive(writer, node.type_name.as_str(), value)? {
         if align {
             writer.align_stream(4);
@@ -206,7 +253,7 @@ fn write_value_with_template(
     // (`m_FileID/m_PathID` vs `fileID/pathID`, `Null` -> zero pointer).
     let is_pptr = node.type_name == "PPtr" || node.type_name.starts_with("PPtr<");
     if is_pptr {
-        return write_value(writer, node, value, ctx, options);
+        return write_node(writer, node, value, ctx, options);
     }
 
     if node.type_name == "ReferencedObject" {
@@ -237,7 +284,7 @@ fn write_value_with_template(
                 node.type_name, node.name
             )));
         }
-        write_value(writer, node, value, ctx, options)?;
+        write_node(writer, node, value, ctx, options)?;
         return Ok(());
     }
 
@@ -321,7 +368,7 @@ fn write_array_with_template(
 
     if matches!(elem_node.type_name.as_str(), "UInt8" | "SInt8" | "char") {
         // Delegate to the normal writer logic; byte-like arrays have no nested fields to preserve.
-        write_value(writer, node, value, ctx, options)?;
+        write_node(writer, node, value, ctx, options)?;
         return Ok(());
     }
 
@@ -5,18 +5,29 @@ use crate::typetree::primitives::write_primitive;
 use crate::typetree::referenced_object::write_referenced_object;
 use indexmap::IndexMap;
 use unity_asset_binary::asset::SerializedType;
+use unity_asset_binary::string_policy::RawString;
 use unity_asset_binary::typetree::{TypeTree, TypeTreeNode};
 use unity_asset_core::{UnityAssetError, UnityValue};
 
 #[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
 pub struct TypeTreeWriteOptions {
     pub allow_missing_fields: bool,
 }
 
+impl TypeTreeWriteOptions {
+    /// Set `allow_missing_fields`.
+    pub fn with_allow_missing_fields(mut self, allow_missing_fields: bool) -> Self {
+        self.allow_missing_fields = allow_missing_fields;
+        self
+    }
+}
+
 /// A TypeTree-driven writer, targeting UnityPy's `TypeTreeHelper.write_value` behavior.
 pub struct TypeTreeWriter<'a> {
     tree: &'a TypeTree,
     ref_types: Option<&'a [SerializedType]>,
+    raw_strings: &'a [RawString],
 }
 
 impl<'a> TypeTreeWriter<'a> {
@@ -24,6 +35,7 @@ impl<'a> TypeTreeWriter<'a> {
         Self {
             tree,
             ref_types: None,
+            raw_strings: &[],
         }
     }
 
@@ -31,9 +43,17 @@ impl<'a> TypeTreeWriter<'a> {
         Self {
             tree,
             ref_types: Some(ref_types),
+            raw_strings: &[],
         }
     }
 
+    /// Write strings that are unchanged since they were read using their original bytes
+    /// (see `UnityObject::raw_strings`), matched by field path and occurrence.
+    pub fn raw_strings(mut self, raw_strings: &'a [RawString]) -> Self {
+        self.raw_strings = raw_strings;
+        self
+    }
+
     pub fn tree(&self) -> &'a TypeTree {
         self.tree
     }
@@ -51,6 +71,7 @@ impl<'a> TypeTreeWriter<'a> {
 
         let mut ctx = TypeTreeWriteContext {
             ref_types: self.ref_types,
+            raw_strings: self.raw_strings,
             ..Default::default()
         };
         for child in &root.children {
@@ -88,6 +109,7 @@ impl<'a> TypeTreeWriter<'a> {
             writer,
             self.tree,
             self.ref_types,
+            self.raw_strings,
             properties,
             original_bytes,
             options,
@@ -101,6 +123,24 @@ pub(crate) fn write_value(
     value: &UnityValue,
     ctx: &mut TypeTreeWriteContext<'_>,
     options: TypeTreeWriteOptions,
+) -> Result<()> {
+    // Paths only key raw strings; skip the bookkeeping when there are none.
+    if ctx.raw_strings.is_empty() {
+        return write_node(writer, node, value, ctx, options);
+    }
+    let base = ctx.enter(&node.name);
+    let result = write_node(writer, node, value, ctx, options);
+    ctx.path.truncate(base);
+    result
+}
+
+/// [`write_value`] without entering `node` into the field path, for callers that already have.
+pub(crate) fn write_node(
+    writer: &mut BinaryWriter,
+    node: &TypeTreeNode,
+    value: &UnityValue,
+    ctx: &mut TypeTreeWriteContext<'_>,
+    options: TypeTreeWriteOptions,
 ) -> Result<()> {
     // UnityPy alignment: node meta flag, plus array child meta flag.
     let mut align = node.is_aligned();
@@ -112,6 +152,15 @@ pub(crate) fn write_value(
         align = true;
     }
 
+    if node.type_name == "string"
+        && let UnityValue::String(s) = value
+        && let Some(raw) = ctx.raw_string(s)
+    {
+        writer.write_byte_array(&raw.raw)?;
+        writer.align_stream(4);
+        return Ok(());
+    }
+
     if write_primitive(writer, node.type_name.as_str(), value)? {
         if align {
             writer.align_stream(4);
@@ -468,6 +517,116 @@ mod tests {
         );
     }
 
+    #[test]
+    fn roundtrip_non_utf8_string_writes_original_bytes() {
+        use unity_asset_binary::string_policy::StringPolicy;
+        use unity_asset_binary::typetree::{TypeTreeParseMode, TypeTreeParseOptions};
+
+        let mut root = node("TestObject", "Base");
+        root.children.push(node("string", "m_Name"));
+        root.children.push(node("int", "m_Int"));
+        let mut tree = TypeTree::new();
+        tree.add_node(root);
+
+        // "テスト" in Shift-JIS, padded to 4 bytes, then m_Int = 5.
+        let mut original = Vec::new();
+        original.extend_from_slice(&6i32.to_le_bytes());
+        original.extend_from_slice(b"\x83\x65\x83\x58\x83\x67\0\0");
+        original.extend_from_slice(&5i32.to_le_bytes());
+
+        let mut reader = BinaryReader::new(&original, ByteOrder::Little);
+        let parsed = TypeTreeSerializer::new(&tree)
+            .parse_object_detailed(
+                &mut reader,
+                TypeTreeParseOptions::default()
+                    .with_mode(TypeTreeParseMode::Strict)
+                    .with_strings(StringPolicy::Utf8Lossy),
+            )
+            .unwrap();
+        assert_eq!(parsed.raw_strings.len(), 1);
+
+        let mut out = BinaryWriter::new(Endian::Little);
+        TypeTreeWriter::new(&tree)
+            .raw_strings(&parsed.raw_strings)
+            .write_object(
+                &mut out,
+                &parsed.properties,
+                TypeTreeWriteOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(out.bytes(), original.as_slice());
+    }
+
+    #[test]
+    fn roundtrip_distinct_lossy_strings_keep_their_own_bytes() {
+        use unity_asset_binary::string_policy::StringPolicy;
+        use unity_asset_binary::typetree::{TypeTreeParseMode, TypeTreeParseOptions};
+
+        let mut array = node("Array", "Array");
+        array.children.push(node("int", "size"));
+        array.children.push(node("string", "data"));
+        let mut tags = node("vector", "m_Tags");
+        tags.children.push(array);
+        let mut root = node("TestObject", "Base");
+        root.children.push(node("string", "m_Name"));
+        root.children.push(tags);
+        let mut tree = TypeTree::new();
+        tree.add_node(root);
+
+        // Every string is a single invalid UTF-8 byte (padded to 4) and decodes to "\u{fffd}".
+        let string = |byte: u8| [1, 0, 0, 0, byte, 0, 0, 0];
+        let mut original = Vec::new();
+        original.extend_from_slice(&string(0x80));
+        original.extend_from_slice(&2i32.to_le_bytes());
+        original.extend_from_slice(&string(0xff));
+        original.extend_from_slice(&string(0xfe));
+
+        let mut reader = BinaryReader::new(&original, ByteOrder::Little);
+        let parsed = TypeTreeSerializer::new(&tree)
+            .parse_object_detailed(
+                &mut reader,
+                TypeTreeParseOptions::default()
+                    .with_mode(TypeTreeParseMode::Strict)
+                    .with_strings(StringPolicy::Utf8Lossy),
+            )
+            .unwrap();
+        let keys: Vec<_> = parsed
+            .raw_strings
+            .iter()
+            .map(|r| (r.path.as_str(), r.occurrence, r.decoded.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                ("m_Name", 0, "\u{fffd}"),
+                ("m_Tags.data", 0, "\u{fffd}"),
+                ("m_Tags.data", 1, "\u{fffd}"),
+            ]
+        );
+
+        let writer_impl = TypeTreeWriter::new(&tree).raw_strings(&parsed.raw_strings);
+        let mut out = BinaryWriter::new(Endian::Little);
+        writer_impl
+            .write_object(
+                &mut out,
+                &parsed.properties,
+                TypeTreeWriteOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(out.bytes(), original.as_slice());
+
+        let mut out = BinaryWriter::new(Endian::Little);
+        writer_impl
+            .write_object_with_original_bytes(
+                &mut out,
+                &parsed.properties,
+                &original,
+                TypeTreeWriteOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(out.bytes(), original.as_slice());
+    }
+
     #[test]
     fn roundtrip_array_of_u8_as_unityvalue_array() {
         // Layout similar to `vector<UInt8>` / `Array` node conventions.
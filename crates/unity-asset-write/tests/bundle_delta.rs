@@ -0,0 +1,183 @@
+//! `bundle_diff` / `bundle_apply`: a patch between banner_1 and a version with one modified
+//! texture carries only the changed bytes, applies back to the new version, and rejects the
+//! wrong base bundle or a corrupted patch.
+
+use unity_asset_binary::asset::{SerializedFile, SerializedFileParser};
+use unity_asset_binary::bundle::{AssetBundle, BundleParser};
+use unity_asset_core::UnityValue;
+use unity_asset_write::bundle::{
+    BundleEdits, BundlePatch, BundleWriter, PatchOp, bundle_apply, bundle_diff,
+};
+use unity_asset_write::object::SerializedFileEditSession;
+use unity_asset_write::serialized_file::SerializedFileWriter;
+use unity_asset_write::{PackerOptions, UnityPyPacker};
+
+const CAB: &str = "CAB-fa4c27fa39f48e1346f48009626ba08d";
+const RESS: &str = "CAB-fa4c27fa39f48e1346f48009626ba08d.resS";
+const TEXTURE: i64 = -3875358842991402074;
+const SPRITE: i64 = -8325468307350463555;
+
+fn old_bytes() -> Vec<u8> {
+    include_bytes!("../../../tests/samples/banner_1").to_vec()
+}
+
+fn parse(bytes: &[u8]) -> AssetBundle {
+    BundleParser::from_bytes(bytes.to_vec()).unwrap()
+}
+
+fn node_data(bundle: &AssetBundle, name: &str) -> Vec<u8> {
+    let node = bundle.nodes.iter().find(|n| n.name == name).unwrap();
+    bundle.extract_node_data(node).unwrap()
+}
+
+fn cab(bundle: &AssetBundle) -> SerializedFile {
+    SerializedFileParser::from_bytes(node_data(bundle, CAB)).unwrap()
+}
+
+/// banner_1 with the texture renamed and the first 2 KiB of its pixel data inverted.
+fn new_bytes() -> Vec<u8> {
+    let old = parse(&old_bytes());
+    let file = cab(&old);
+    let mut session = SerializedFileEditSession::new(&file);
+    session
+        .edit_object(TEXTURE, |class| {
+            *class.get_mut("m_Name").unwrap() = UnityValue::String("banner_v2".to_string());
+            Ok(())
+        })
+        .unwrap();
+    let cab_bytes = SerializedFileWriter::save(&file, &session.into_edits()).unwrap();
+
+    let mut pixels = node_data(&old, RESS);
+    for byte in &mut pixels[..2048] {
+        *byte = !*byte;
+    }
+
+    let mut edits = BundleEdits::new();
+    edits.replace_file_bytes(CAB, cab_bytes);
+    edits.replace_file_bytes(RESS, pixels);
+    let options = PackerOptions::default().with_packer(UnityPyPacker::Original);
+    BundleWriter::save(&old, &edits, options).unwrap()
+}
+
+#[test]
+fn patch_carries_only_the_modified_texture() {
+    let old_bytes = old_bytes();
+    let new_bytes = new_bytes();
+    let (old, new) = (parse(&old_bytes), parse(&new_bytes));
+
+    let patch = bundle_diff(&old, &new).unwrap();
+    let serialized = patch.to_bytes().unwrap();
+    assert!(
+        serialized.len() * 4 < new_bytes.len(),
+        "patch {} bytes, new bundle {} bytes",
+        serialized.len(),
+        new_bytes.len()
+    );
+    assert!(patch.copied_size() > patch.literal_size() * 4);
+    assert_eq!(patch.header.unity_version, new.header.unity_version);
+
+    // The sprite is unchanged and copied from the old CAB; the renamed texture is a literal.
+    let old_file = cab(&old);
+    let sprite = old_file.find_object(SPRITE).unwrap();
+    let cab_ops = &patch.nodes.iter().find(|n| n.name == CAB).unwrap().ops;
+    assert!(cab_ops.iter().any(|op| matches!(
+        op,
+        PatchOp::Copy { offset, len, .. }
+            if *offset <= sprite.byte_start
+                && sprite.byte_start + sprite.byte_size as u64 <= offset + len
+    )));
+    let texture = cab(&new)
+        .find_object(TEXTURE)
+        .map(|o| cab(&new).object_bytes(o).unwrap().to_vec())
+        .unwrap();
+    assert!(cab_ops.iter().any(|op| matches!(
+        op,
+        PatchOp::Literal(bytes) if bytes.windows(texture.len()).any(|w| w == texture)
+    )));
+
+    // Diffing a bundle against itself copies everything.
+    let identity = bundle_diff(&old, &old).unwrap();
+    assert_eq!(identity.literal_size(), 0);
+    assert_eq!(identity.old_hash, identity.new_hash);
+}
+
+#[test]
+fn applied_patch_reparses_as_the_new_bundle() {
+    let old_bytes = old_bytes();
+    let new_bytes = new_bytes();
+    let new = parse(&new_bytes);
+    let patch = bundle_diff(&parse(&old_bytes), &new).unwrap();
+    let patch = BundlePatch::from_bytes(&patch.to_bytes().unwrap()).unwrap();
+
+    let applied = parse(&bundle_apply(&old_bytes, &patch).unwrap());
+    assert_eq!(applied.header.unity_revision, new.header.unity_revision);
+    assert_eq!(applied.header.flags, new.header.flags);
+    assert_eq!(applied.node_names(), new.node_names());
+    for (a, b) in applied.nodes.iter().zip(&new.nodes) {
+        assert_eq!(a.flags, b.flags);
+        assert_eq!(
+            applied.extract_node_data(a).unwrap(),
+            new.extract_node_data(b).unwrap()
+        );
+    }
+
+    let (applied_file, new_file) = (cab(&applied), cab(&new));
+    assert_eq!(applied_file.objects.len(), new_file.objects.len());
+    for (a, b) in applied_file.objects.iter().zip(&new_file.objects) {
+        assert_eq!(a.path_id, b.path_id);
+        assert_eq!(
+            applied_file.object_bytes(a).unwrap(),
+            new_file.object_bytes(b).unwrap()
+        );
+    }
+    assert_eq!(
+        applied_file
+            .find_object_handle(TEXTURE)
+            .unwrap()
+            .peek_name()
+            .unwrap()
+            .as_deref(),
+        Some("banner_v2")
+    );
+}
+
+#[test]
+fn wrong_base_and_corrupted_patches_are_rejected() {
+    let old_bytes = old_bytes();
+    let new_bytes = new_bytes();
+    let patch = bundle_diff(&parse(&old_bytes), &parse(&new_bytes)).unwrap();
+
+    // The new bundle and an unrelated bundle are not the patch's base.
+    assert!(bundle_apply(&new_bytes, &patch).is_err());
+    let atlas = include_bytes!("../../../tests/samples/atlas_test");
+    assert!(bundle_apply(atlas, &patch).is_err());
+
+    let serialized = patch.to_bytes().unwrap();
+    let mut corrupted = serialized.clone();
+    let middle = corrupted.len() / 2;
+    corrupted[middle] ^= 0xFF;
+    assert!(BundlePatch::from_bytes(&corrupted).is_err());
+    assert!(BundlePatch::from_bytes(&serialized[..serialized.len() / 2]).is_err());
+
+    let mut bad_magic = serialized.clone();
+    bad_magic[0] = b'X';
+    assert!(BundlePatch::from_bytes(&bad_magic).is_err());
+
+    let mut future = serialized;
+    future[8] = 99;
+    assert!(BundlePatch::from_bytes(&future).is_err());
+
+    // A tampered literal is caught by the per-entry hash when applying.
+    let mut tampered = patch.clone();
+    let literal = tampered
+        .nodes
+        .iter_mut()
+        .flat_map(|n| n.ops.iter_mut())
+        .find_map(|op| match op {
+            PatchOp::Literal(bytes) => Some(bytes),
+            PatchOp::Copy { .. } => None,
+        })
+        .unwrap();
+    literal[0] ^= 0xFF;
+    assert!(bundle_apply(&old_bytes, &tampered).is_err());
+}
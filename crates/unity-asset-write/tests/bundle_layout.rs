@@ -0,0 +1,147 @@
+//! `BundleWriter::save_with_options`: layout reordering, `.resS` repacking with StreamingInfo
+//! rewrites, CAB block isolation, and the before/after report.
+
+use unity_asset_binary::asset::{SerializedFile, SerializedFileParser};
+use unity_asset_binary::bundle::{AssetBundle, BundleParser};
+use unity_asset_binary::reader::ByteOrder;
+use unity_asset_decode::texture::{Decoder, MobileDecoder, Texture2DConverter};
+use unity_asset_decode::unity_version::UnityVersion;
+use unity_asset_write::bundle::{BundleEdits, BundleSaveOptions, BundleWriter, Layout};
+use unity_asset_write::serialized_file::{SerializedFileEdits, SerializedFileWriter};
+use unity_asset_write::{PackerOptions, UnityPyPacker};
+
+const CAB: &str = "CAB-fa4c27fa39f48e1346f48009626ba08d";
+const COPY: &str = "CAB-fa4c27fa39f48e1346f48009626ba08d-copy";
+const TEXTURE: i64 = -3875358842991402074;
+const PADDING: usize = 4096;
+
+fn banner() -> AssetBundle {
+    BundleParser::from_bytes(include_bytes!("../../../tests/samples/banner_1").to_vec()).unwrap()
+}
+
+fn cab(bundle: &AssetBundle, name: &str) -> SerializedFile {
+    let node = bundle.nodes.iter().find(|n| n.name == name).unwrap();
+    SerializedFileParser::from_bytes(bundle.extract_node_data(node).unwrap()).unwrap()
+}
+
+fn node_data(bundle: &AssetBundle, name: &str) -> Vec<u8> {
+    let node = bundle.nodes.iter().find(|n| n.name == name).unwrap();
+    bundle.extract_node_data(node).unwrap()
+}
+
+/// banner_1 with `PADDING` unreferenced bytes in front of the texture's `.resS` data, and a second
+/// serialized file (a copy of the CAB) appended after the `.resS`.
+fn padded_bundle() -> AssetBundle {
+    let original = banner();
+    let mut file = cab(&original, CAB);
+
+    // StreamingInfo { unsigned int offset; unsigned int size; string path } in this Unity version.
+    let handle = file.find_object_handle(TEXTURE).unwrap();
+    let path = handle
+        .scan_strings(usize::MAX)
+        .unwrap()
+        .unwrap()
+        .into_iter()
+        .find(|s| s.path == "m_StreamData.path")
+        .unwrap();
+    let mut data = handle.raw_data().unwrap().to_vec();
+    let offset = match file.header.byte_order() {
+        ByteOrder::Little => (PADDING as u32).to_le_bytes(),
+        ByteOrder::Big => (PADDING as u32).to_be_bytes(),
+    };
+    data[path.range.start - 8..path.range.start - 4].copy_from_slice(&offset);
+    let info = file
+        .objects
+        .iter_mut()
+        .find(|o| o.path_id == TEXTURE)
+        .unwrap();
+    info.data = data;
+    let patched = SerializedFileWriter::save(&file, &SerializedFileEdits::new()).unwrap();
+
+    let mut resource = vec![0xEE; PADDING];
+    resource.extend_from_slice(&node_data(&original, &format!("{CAB}.resS")));
+
+    let mut edits = BundleEdits::new();
+    edits.replace_file_bytes(CAB, patched.clone());
+    edits.replace_file_bytes(format!("{CAB}.resS"), resource);
+    edits.add_file_bytes(COPY, patched, 4);
+    let bytes = BundleWriter::save(&original, &edits, PackerOptions::default()).unwrap();
+    BundleParser::from_bytes(bytes).unwrap()
+}
+
+/// The texture in `file` (ASTC 6x6), with its streamed pixels read from `bundle`, decoded to RGBA.
+fn decoded_texture(bundle: &AssetBundle, file: &SerializedFile) -> (u64, Vec<u8>) {
+    let object = file.find_object_handle(TEXTURE).unwrap().read().unwrap();
+    let converter = Texture2DConverter::new(UnityVersion::default());
+    let mut texture = converter.from_unity_object(&object).unwrap();
+    assert!(texture.is_streamed());
+    converter.resolve_stream_data(&mut texture, bundle).unwrap();
+    let image = MobileDecoder::new().decode(&texture).unwrap();
+    (texture.stream_info.offset, image.into_raw())
+}
+
+#[test]
+fn group_by_type_repacks_resources_and_reloads() {
+    let padded = padded_bundle();
+    let (offset, expected) = decoded_texture(&padded, &cab(&padded, CAB));
+    assert_eq!(offset, PADDING as u64);
+
+    let options = BundleSaveOptions::default()
+        .with_packer(PackerOptions::default().with_packer(UnityPyPacker::Lz4))
+        .with_layout(Layout::GroupByType);
+    let saved = BundleWriter::save_with_options(&padded, &BundleEdits::new(), &options).unwrap();
+    let report = &saved.report;
+
+    let resource = format!("{CAB}.resS");
+    assert_eq!(report.before.order, [CAB, resource.as_str(), COPY]);
+    assert_eq!(report.after.order, [CAB, COPY, resource.as_str()]);
+    assert!(report.order_changed());
+    assert_eq!(report.moved_ranges, 1);
+    assert_eq!(report.rewritten_offsets, 2);
+    assert!(report.unpacked_resources.is_empty());
+    // Each CAB gets its own blocks.
+    assert_eq!(report.before.block_count, 1);
+    assert_eq!(report.after.block_count, 3);
+    assert!(report.before.compressed_size > 0 && report.after.compressed_size > 0);
+
+    let reloaded = BundleParser::from_bytes(saved.bytes).unwrap();
+    assert_eq!(reloaded.node_names(), [CAB, COPY, resource.as_str()]);
+    assert_eq!(
+        node_data(&reloaded, &resource).len(),
+        node_data(&padded, &resource).len()
+    );
+    for name in [CAB, COPY] {
+        let (offset, pixels) = decoded_texture(&reloaded, &cab(&reloaded, name));
+        assert_eq!(offset, 0);
+        assert_eq!(pixels, expected);
+    }
+}
+
+#[test]
+fn preserve_matches_plain_save_and_custom_orders_entries() {
+    let bundle = banner();
+    let resource = format!("{CAB}.resS");
+
+    let plain = BundleWriter::save(&bundle, &BundleEdits::new(), PackerOptions::default()).unwrap();
+    let preserved = BundleWriter::save_with_options(
+        &bundle,
+        &BundleEdits::new(),
+        &BundleSaveOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(preserved.bytes, plain);
+    assert!(!preserved.report.order_changed());
+    assert_eq!(preserved.report.before, preserved.report.after);
+
+    let custom = BundleSaveOptions::default().with_layout(Layout::Custom(vec![resource.clone()]));
+    let saved = BundleWriter::save_with_options(&bundle, &BundleEdits::new(), &custom).unwrap();
+    assert_eq!(saved.report.after.order, [resource.as_str(), CAB]);
+    // The texture already starts the .resS, so nothing inside it moves.
+    assert_eq!(saved.report.moved_ranges, 0);
+    let reloaded = BundleParser::from_bytes(saved.bytes).unwrap();
+    assert_eq!(reloaded.node_names(), [resource.as_str(), CAB]);
+    assert_eq!(node_data(&reloaded, CAB), node_data(&bundle, CAB));
+
+    let unknown = BundleSaveOptions::default().with_layout(Layout::Custom(vec!["nope".into()]));
+    assert!(BundleWriter::save_with_options(&bundle, &BundleEdits::new(), &unknown).is_err());
+}
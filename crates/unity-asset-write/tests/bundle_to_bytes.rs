@@ -0,0 +1,104 @@
+//! `AssetBundleExt::to_bytes`: repack a UnityFS bundle with a chosen codec, including objects
+//! patched in place through `serialize_object_with_typetree`.
+
+use unity_asset_binary::bundle::{AssetBundle, BundleParser};
+use unity_asset_binary::compression::CompressionType;
+use unity_asset_binary::typetree::serialize_object_with_typetree;
+use unity_asset_core::UnityValue;
+use unity_asset_write::bundle::AssetBundleExt;
+use unity_asset_write::{PackerOptions, UnityPyPacker};
+
+const TEXTURE: i64 = -3875358842991402074;
+
+fn load(name: &str) -> AssetBundle {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/samples")
+        .join(name);
+    BundleParser::from_bytes(std::fs::read(path).unwrap()).unwrap()
+}
+
+fn node_data(bundle: &AssetBundle) -> Vec<(String, Vec<u8>)> {
+    bundle
+        .nodes
+        .iter()
+        .map(|n| (n.name.clone(), bundle.extract_node_data(n).unwrap()))
+        .collect()
+}
+
+#[test]
+fn repacks_with_each_codec() {
+    let bundle = load("char_118_yuki.ab");
+    let original_codec = bundle.blocks[0].compression_type().unwrap();
+    assert_ne!(original_codec, CompressionType::None);
+
+    for (options, codec) in [
+        (
+            PackerOptions::default().with_packer(UnityPyPacker::None),
+            CompressionType::None,
+        ),
+        (
+            PackerOptions::default().with_packer(UnityPyPacker::Lz4),
+            CompressionType::Lz4,
+        ),
+        (
+            PackerOptions::default().with_packer(UnityPyPacker::Lzma),
+            CompressionType::Lzma,
+        ),
+        (PackerOptions::match_original(), original_codec),
+    ] {
+        let repacked = BundleParser::from_bytes(bundle.to_bytes(options).unwrap()).unwrap();
+        // Blocks that do not shrink are stored raw, as Unity does.
+        let codecs: Vec<_> = repacked
+            .blocks
+            .iter()
+            .map(|b| b.compression_type().unwrap())
+            .collect();
+        assert!(codecs.contains(&codec), "{:?}: {:?}", options, codecs);
+        assert!(
+            codecs
+                .iter()
+                .all(|&c| c == codec || c == CompressionType::None),
+            "{:?}: {:?}",
+            options,
+            codecs
+        );
+        assert_eq!(node_data(&repacked), node_data(&bundle), "{:?}", options);
+        assert_eq!(repacked.asset_names, bundle.asset_names);
+    }
+}
+
+#[test]
+fn patched_object_is_repacked() {
+    let mut bundle = load("banner_1");
+    let file = &bundle.assets[0];
+    let object = file.find_object_handle(TEXTURE).unwrap().read().unwrap();
+    let mut properties = object.as_unity_class().properties().clone();
+    properties.insert(
+        "m_Name".to_string(),
+        UnityValue::String("banner_patched".to_string()),
+    );
+    let index = file
+        .objects
+        .iter()
+        .position(|o| o.path_id == TEXTURE)
+        .unwrap();
+    let tree = &file.types[file.objects[index].type_index as usize].type_tree;
+    let bytes = serialize_object_with_typetree(tree, &properties).unwrap();
+    bundle.assets[0].objects[index].data = bytes;
+
+    let repacked =
+        BundleParser::from_bytes(bundle.to_bytes(PackerOptions::match_original()).unwrap())
+            .unwrap();
+    let texture = repacked.assets[0]
+        .find_object_handle(TEXTURE)
+        .unwrap()
+        .read()
+        .unwrap();
+    assert_eq!(texture.name(), Some("banner_patched".to_string()));
+    assert_eq!(texture.get("m_Width"), object.get("m_Width"));
+
+    // The `.resS` node is carried over untouched.
+    let (original, repacked) = (node_data(&bundle), node_data(&repacked));
+    assert_eq!(original[1], repacked[1]);
+    assert_ne!(original[0], repacked[0]);
+}
@@ -51,9 +51,7 @@ fn roundtrip_bundle_noop(bundle: &AssetBundle) -> anyhow::Result<()> {
     let saved = BundleWriter::save(
         bundle,
         &BundleEdits::default(),
-        PackerOptions {
-            packer: UnityPyPacker::Original,
-        },
+        PackerOptions::default().with_packer(UnityPyPacker::Original),
     )?;
 
     let reparsed = unity_asset_binary::bundle::BundleParser::from_bytes(saved)?;
@@ -237,14 +237,17 @@ fn external_corpus_bundle_roundtrip_and_optional_unitypy_validation() -> anyhow:
             .map(|n| n.name.clone())
             .collect();
 
-        let saved =
-            match BundleWriter::save(&bundle, &BundleEdits::default(), PackerOptions { packer }) {
-                Ok(b) => b,
-                Err(e) => {
-                    failures.push((path.to_path_buf(), format!("save failed: {e}")));
-                    return Ok(true);
-                }
-            };
+        let saved = match BundleWriter::save(
+            &bundle,
+            &BundleEdits::default(),
+            PackerOptions::default().with_packer(packer),
+        ) {
+            Ok(b) => b,
+            Err(e) => {
+                failures.push((path.to_path_buf(), format!("save failed: {e}")));
+                return Ok(true);
+            }
+        };
 
         let reparsed =
             match BundleParser::from_bytes_with_options(saved.clone(), BundleLoadOptions::lazy()) {
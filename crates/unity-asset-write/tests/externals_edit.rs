@@ -0,0 +1,149 @@
+//! Externals table edits: PPtrs keep resolving to the same external after swap/remove + save.
+
+use unity_asset_binary::asset::{DanglingReferences, SerializedFile, SerializedFileParser};
+use unity_asset_binary::reader::ByteOrder;
+use unity_asset_write::serialized_file::{SerializedFileEdits, SerializedFileWriter};
+
+const SPRITE: i64 = -8325468307350463555;
+const ASSET_BUNDLE: i64 = 1;
+const ATLAS: &str = "archive:/CAB-atlas/CAB-atlas";
+const RESOURCES: &str = "library/unity default resources";
+
+fn banner_file() -> SerializedFile {
+    let bytes = include_bytes!("../../../tests/samples/banner_1").to_vec();
+    let bundle = unity_asset_binary::bundle::BundleParser::from_bytes(bytes).unwrap();
+    let node = bundle
+        .nodes
+        .iter()
+        .find(|n| n.is_file() && !n.name.ends_with(".resS") && !n.name.ends_with(".resource"))
+        .unwrap();
+    SerializedFileParser::from_bytes(bundle.extract_node_data(node).unwrap()).unwrap()
+}
+
+/// Point the `PPtr` at `field` of object `path_id` to `(file_id, target)` by patching its bytes.
+fn point(file: &mut SerializedFile, path_id: i64, field: &str, file_id: i32, target: i64) {
+    let handle = file.find_object_handle(path_id).unwrap();
+    let fields = handle.scan_pptr_fields().unwrap().unwrap();
+    let pptr = fields.iter().find(|f| f.path == field).unwrap();
+    assert_eq!(file.header.byte_order(), ByteOrder::Little);
+    let mut bytes = handle.raw_data().unwrap().to_vec();
+    bytes[pptr.file_id_range.clone()].copy_from_slice(&file_id.to_le_bytes());
+    bytes[pptr.path_id_range.clone()].copy_from_slice(&target.to_le_bytes());
+    let info = file
+        .objects
+        .iter_mut()
+        .find(|o| o.path_id == path_id)
+        .unwrap();
+    info.data = bytes;
+}
+
+/// `(external path, path_id)` the `PPtr` at `field` of object `path_id` refers to.
+fn resolve(file: &SerializedFile, path_id: i64, field: &str) -> (String, i64) {
+    let fields = file
+        .find_object_handle(path_id)
+        .unwrap()
+        .scan_pptr_fields()
+        .unwrap()
+        .unwrap();
+    let pptr = fields.iter().find(|f| f.path == field).unwrap();
+    let external = match pptr.file_id {
+        0 => String::new(),
+        id => file.externals[id as usize - 1].path.clone(),
+    };
+    (external, pptr.path_id)
+}
+
+fn resave(file: &SerializedFile) -> SerializedFile {
+    let saved = SerializedFileWriter::save(file, &SerializedFileEdits::new()).unwrap();
+    SerializedFileParser::from_bytes(saved).unwrap()
+}
+
+/// banner_1 with two externals: the Sprite texture points into the first, the first preload
+/// entry into the second.
+fn two_external_fixture() -> SerializedFile {
+    let mut file = banner_file();
+    assert!(file.externals.is_empty());
+    assert_eq!(file.add_external(ATLAS), 1);
+    assert_eq!(file.add_external(RESOURCES), 2);
+    assert_eq!(file.add_external(ATLAS), 1);
+    point(&mut file, SPRITE, "m_RD.texture", 1, 111);
+    point(&mut file, ASSET_BUNDLE, "m_PreloadTable[0]", 2, 222);
+    resave(&file)
+}
+
+#[test]
+fn swapping_externals_keeps_references_on_their_targets() {
+    let mut file = two_external_fixture();
+    assert_eq!(
+        resolve(&file, SPRITE, "m_RD.texture"),
+        (ATLAS.to_string(), 111)
+    );
+
+    let report = file.remap_external(0, 1).unwrap();
+    assert_eq!(report.field_count(), 2);
+    let touched: Vec<(i64, &str, i32, i32)> = report
+        .objects
+        .iter()
+        .flat_map(|o| {
+            o.fields
+                .iter()
+                .map(move |f| (o.path_id, f.path.as_str(), f.old_file_id, f.new_file_id))
+        })
+        .collect();
+    assert!(touched.contains(&(SPRITE, "m_RD.texture", 1, 2)));
+    assert!(touched.contains(&(ASSET_BUNDLE, "m_PreloadTable[0]", 2, 1)));
+    assert!(!report.has_nulled());
+
+    let file = resave(&file);
+    assert_eq!(file.externals[0].path, RESOURCES);
+    assert_eq!(file.externals[1].path, ATLAS);
+    assert_eq!(
+        resolve(&file, SPRITE, "m_RD.texture"),
+        (ATLAS.to_string(), 111)
+    );
+    assert_eq!(
+        resolve(&file, ASSET_BUNDLE, "m_PreloadTable[0]"),
+        (RESOURCES.to_string(), 222)
+    );
+    // Unrelated internal references are untouched and the objects still parse.
+    assert_eq!(resolve(&file, ASSET_BUNDLE, "m_PreloadTable[1]").0, "");
+    assert!(file.find_object_handle(SPRITE).unwrap().read().is_ok());
+}
+
+#[test]
+fn removing_a_referenced_external_is_refused_or_nulls_references() {
+    let mut file = two_external_fixture();
+    let before = file.find_object(SPRITE).unwrap().data.clone();
+
+    let err = file
+        .remove_external(0, DanglingReferences::Refuse)
+        .unwrap_err();
+    assert!(err.to_string().contains("m_RD.texture"), "{}", err);
+    assert_eq!(file.externals.len(), 2);
+    assert_eq!(file.find_object(SPRITE).unwrap().data, before);
+
+    let report = file
+        .remove_external(0, DanglingReferences::SetNull)
+        .unwrap();
+    assert!(report.has_nulled());
+    let file = resave(&file);
+    assert_eq!(file.externals.len(), 1);
+    assert_eq!(resolve(&file, SPRITE, "m_RD.texture"), (String::new(), 0));
+    // The second external slid into slot 1.
+    assert_eq!(
+        resolve(&file, ASSET_BUNDLE, "m_PreloadTable[0]"),
+        (RESOURCES.to_string(), 222)
+    );
+}
+
+#[test]
+fn removing_an_unused_external_touches_nothing() {
+    let mut file = two_external_fixture();
+    assert_eq!(file.add_external("archive:/CAB-unused/CAB-unused"), 3);
+
+    let report = file.remove_external(2, DanglingReferences::Refuse).unwrap();
+    assert_eq!(report.field_count(), 0);
+    assert!(report.script_types.is_empty());
+    assert_eq!(file.externals.len(), 2);
+    assert!(file.remove_external(5, DanglingReferences::Refuse).is_err());
+}
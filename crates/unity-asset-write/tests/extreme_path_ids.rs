@@ -0,0 +1,110 @@
+//! PathIDs at the ends of the `i64` range survive save, lookup, references, dumps and JSON.
+
+use unity_asset_binary::asset::{DumpFilter, SerializedFile, SerializedFileParser};
+use unity_asset_binary::object::{ObjectHandle, UnityObject};
+use unity_asset_binary::reader::ByteOrder;
+use unity_asset_write::serialized_file::{SerializedFileEdits, SerializedFileWriter};
+
+const SPRITE: i64 = -8325468307350463555;
+const TEXTURE: i64 = -3875358842991402074;
+const CONTAINER_PATH: &str = "assets/assetbundles/images/banner/banner_1.png";
+
+fn banner_file() -> SerializedFile {
+    let bytes = include_bytes!("../../../tests/samples/banner_1").to_vec();
+    let bundle = unity_asset_binary::bundle::BundleParser::from_bytes(bytes).unwrap();
+    let node = bundle
+        .nodes
+        .iter()
+        .find(|n| n.is_file() && !n.name.ends_with(".resS") && !n.name.ends_with(".resource"))
+        .unwrap();
+    SerializedFileParser::from_bytes(bundle.extract_node_data(node).unwrap()).unwrap()
+}
+
+/// Renumber objects per `mapping`, rewriting every local `PPtr` that points at them, then
+/// save and reparse.
+fn renumbered(mapping: &[(i64, i64)]) -> SerializedFile {
+    let mut file = banner_file();
+    assert_eq!(file.header.byte_order(), ByteOrder::Little);
+
+    let mut patched = Vec::with_capacity(file.objects.len());
+    for info in &file.objects {
+        let handle = ObjectHandle::new(&file, info);
+        let mut bytes = handle.raw_data().unwrap().to_vec();
+        for field in handle.scan_pptr_fields().unwrap().unwrap() {
+            if field.file_id != 0 {
+                continue;
+            }
+            if let Some(&(_, new)) = mapping.iter().find(|(old, _)| *old == field.path_id) {
+                bytes[field.path_id_range.clone()].copy_from_slice(&new.to_le_bytes());
+            }
+        }
+        patched.push(bytes);
+    }
+
+    for (info, bytes) in file.objects.iter_mut().zip(patched) {
+        info.data = bytes;
+        if let Some(&(_, new)) = mapping.iter().find(|(old, _)| *old == info.path_id) {
+            info.path_id = new;
+        }
+    }
+
+    let saved = SerializedFileWriter::save(&file, &SerializedFileEdits::new()).unwrap();
+    SerializedFileParser::from_bytes(saved).unwrap()
+}
+
+#[test]
+fn extreme_path_ids_roundtrip_through_lookup_and_references() {
+    let file = renumbered(&[(TEXTURE, i64::MIN), (SPRITE, i64::MAX)]);
+
+    assert_eq!(file.find_object(i64::MIN).unwrap().type_id, 28);
+    assert_eq!(file.find_object(i64::MAX).unwrap().type_id, 213);
+    assert!(file.find_object(TEXTURE).is_none());
+    assert!(file.find_object(SPRITE).is_none());
+
+    let texture_ref = file
+        .find_object_handle(i64::MAX)
+        .unwrap()
+        .scan_pptr_fields()
+        .unwrap()
+        .unwrap()
+        .into_iter()
+        .find(|f| f.path == "m_RD.texture")
+        .unwrap();
+    assert_eq!((texture_ref.file_id, texture_ref.path_id), (0, i64::MIN));
+
+    let bundle_info = file.find_object(1).unwrap();
+    let container = file.assetbundle_container_raw(bundle_info).unwrap();
+    let targets: Vec<i64> = container
+        .iter()
+        .filter(|(path, _, _)| path == CONTAINER_PATH)
+        .map(|(_, _, path_id)| *path_id)
+        .collect();
+    assert_eq!(targets, vec![i64::MIN, i64::MAX]);
+}
+
+#[test]
+fn extreme_path_ids_keep_their_sign_in_dumps_and_json() {
+    let file = renumbered(&[(TEXTURE, i64::MIN), (SPRITE, i64::MAX)]);
+
+    let temp = tempfile::tempdir().unwrap();
+    let report = file
+        .dump_raw_objects(temp.path(), &DumpFilter::default())
+        .unwrap();
+    let names: Vec<String> = report
+        .written
+        .iter()
+        .map(|o| o.file.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert!(names.contains(&"-9223372036854775808.bin".to_string()));
+    assert!(names.contains(&"9223372036854775807.bin".to_string()));
+    let index = std::fs::read_to_string(temp.path().join("index.csv")).unwrap();
+    assert!(index.contains(",-9223372036854775808,"));
+
+    let sprite =
+        UnityObject::from_serialized_file(&file, file.find_object(i64::MAX).unwrap()).unwrap();
+    assert_eq!(sprite.path_id(), i64::MAX);
+    let rd = serde_json::to_value(sprite.get("m_RD").unwrap()).unwrap();
+    assert_eq!(rd["texture"]["m_PathID"], serde_json::Value::from(i64::MIN));
+    let text = serde_json::to_string(&rd).unwrap();
+    assert!(text.contains("-9223372036854775808"));
+}
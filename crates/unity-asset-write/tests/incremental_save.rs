@@ -0,0 +1,201 @@
+//! `SerializedFileWriter::save_incremental`: small edits patch the original bytes, grown objects
+//! are relocated to the end of the data region, and both results re-parse.
+
+use unity_asset_binary::asset::{SerializedFile, SerializedFileParser};
+use unity_asset_core::UnityValue;
+use unity_asset_write::object::SerializedFileEditSession;
+use unity_asset_write::serialized_file::{
+    IncrementalSaveOptions, ObjectPatchStrategy, SerializedFileEdits, SerializedFileWriter,
+};
+
+const TEXTURE: i64 = -3875358842991402074;
+const SPRITE: i64 = -8325468307350463555;
+
+fn banner_bytes() -> Vec<u8> {
+    let bytes = include_bytes!("../../../tests/samples/banner_1").to_vec();
+    let bundle = unity_asset_binary::bundle::BundleParser::from_bytes(bytes).unwrap();
+    let node = bundle
+        .nodes
+        .iter()
+        .find(|n| n.is_file() && !n.name.ends_with(".resS") && !n.name.ends_with(".resource"))
+        .unwrap();
+    bundle.extract_node_data(node).unwrap()
+}
+
+fn rename(file: &SerializedFile, path_id: i64, name: &str) -> SerializedFileEdits {
+    let mut session = SerializedFileEditSession::new(file);
+    session
+        .edit_object(path_id, |class| {
+            *class.get_mut("m_Name").unwrap() = UnityValue::String(name.to_string());
+            Ok(())
+        })
+        .unwrap();
+    session.into_edits()
+}
+
+fn peek_name(file: &SerializedFile, path_id: i64) -> String {
+    file.find_object_handle(path_id)
+        .unwrap()
+        .peek_name()
+        .unwrap()
+        .unwrap()
+}
+
+/// Payloads of every object except `skip`, in table order.
+fn other_payloads(file: &SerializedFile, skip: i64) -> Vec<Vec<u8>> {
+    file.objects
+        .iter()
+        .filter(|o| o.path_id != skip)
+        .map(|o| file.object_bytes(o).unwrap().to_vec())
+        .collect()
+}
+
+#[test]
+fn smaller_edit_patches_only_the_object_and_its_entry() {
+    let original = banner_bytes();
+    let file = SerializedFileParser::from_bytes(original.clone()).unwrap();
+    let info = file.find_object(TEXTURE).unwrap().clone();
+    let edits = rename(&file, TEXTURE, "b");
+    let new_size = edits.get(TEXTURE).unwrap().len();
+    assert!(new_size < info.byte_size as usize);
+
+    let (saved, report) =
+        SerializedFileWriter::save_incremental(&file, &edits, IncrementalSaveOptions::default())
+            .unwrap();
+    assert!(!report.is_full_rewrite());
+    assert_eq!(report.objects.len(), 1);
+    assert_eq!(report.objects[0].strategy, ObjectPatchStrategy::InPlace);
+    assert_eq!(report.objects[0].byte_start, Some(info.byte_start));
+    assert_eq!(saved.len(), original.len());
+
+    // Outside the object's slot only the 4-byte size field in the metadata changed.
+    let slot = info.byte_start as usize..info.byte_start as usize + info.byte_size as usize;
+    let changed: Vec<usize> = (0..saved.len())
+        .filter(|&i| saved[i] != original[i] && !slot.contains(&i))
+        .collect();
+    assert!(!changed.is_empty() && changed.len() <= 4, "{changed:?}");
+    assert!(
+        changed
+            .iter()
+            .all(|&i| i < file.header.data_offset as usize)
+    );
+    assert!(
+        saved[slot.start + new_size..slot.end]
+            .iter()
+            .all(|&b| b == 0)
+    );
+
+    let reparsed = SerializedFileParser::from_bytes(saved).unwrap();
+    assert_eq!(peek_name(&reparsed, TEXTURE), "b");
+    assert_eq!(peek_name(&reparsed, SPRITE), "banner_1");
+    assert_eq!(
+        reparsed.find_object(TEXTURE).unwrap().byte_size as usize,
+        new_size
+    );
+    assert_eq!(
+        other_payloads(&reparsed, TEXTURE),
+        other_payloads(&file, TEXTURE)
+    );
+}
+
+#[test]
+fn larger_edit_relocates_the_object() {
+    let original = banner_bytes();
+    let file = SerializedFileParser::from_bytes(original.clone()).unwrap();
+    let info = file.find_object(TEXTURE).unwrap().clone();
+    let edits = rename(&file, TEXTURE, "Banner_Renamed_Much_Longer_Than_Before");
+    let new_size = edits.get(TEXTURE).unwrap().len();
+    assert!(new_size > info.byte_size as usize);
+
+    let (saved, report) =
+        SerializedFileWriter::save_incremental(&file, &edits, IncrementalSaveOptions::default())
+            .unwrap();
+    assert_eq!(report.count(ObjectPatchStrategy::Relocated), 1);
+    let start = report.objects[0].byte_start.unwrap();
+    assert!(start >= original.len() as u64);
+    assert_eq!((start - file.header.data_offset) % 8, 0);
+    assert_eq!(report.file_size, saved.len() as u64);
+    // The data before the appended payload is untouched apart from metadata fields.
+    let data_offset = file.header.data_offset as usize;
+    assert_eq!(saved[data_offset..original.len()], original[data_offset..]);
+
+    let reparsed = SerializedFileParser::from_bytes(saved.clone()).unwrap();
+    assert_eq!(reparsed.header.file_size, saved.len() as u64);
+    assert_eq!(reparsed.find_object(TEXTURE).unwrap().byte_start, start);
+    assert_eq!(
+        peek_name(&reparsed, TEXTURE),
+        "Banner_Renamed_Much_Longer_Than_Before"
+    );
+    assert_eq!(peek_name(&reparsed, SPRITE), "banner_1");
+    assert_eq!(
+        other_payloads(&reparsed, TEXTURE),
+        other_payloads(&file, TEXTURE)
+    );
+}
+
+#[test]
+fn disabling_relocation_falls_back_to_a_full_rewrite() {
+    let file = SerializedFileParser::from_bytes(banner_bytes()).unwrap();
+    let edits = rename(&file, TEXTURE, "Banner_Renamed_Much_Longer_Than_Before");
+
+    let (saved, report) = SerializedFileWriter::save_incremental(
+        &file,
+        &edits,
+        IncrementalSaveOptions::default().with_allow_relocation(false),
+    )
+    .unwrap();
+    assert!(report.is_full_rewrite(), "{report:?}");
+    assert_eq!(report.count(ObjectPatchStrategy::FullRewrite), 1);
+    assert_eq!(saved, SerializedFileWriter::save(&file, &edits).unwrap());
+
+    // Nothing dirty: the original bytes come back unchanged.
+    let (unchanged, report) = SerializedFileWriter::save_incremental(
+        &file,
+        &SerializedFileEdits::new(),
+        IncrementalSaveOptions::default(),
+    )
+    .unwrap();
+    assert!(report.objects.is_empty());
+    assert_eq!(unchanged, file.data());
+}
+
+#[test]
+fn in_place_patch_matches_the_copy() {
+    let original = banner_bytes();
+    let file = SerializedFileParser::from_bytes(original.clone()).unwrap();
+    let mut edits = rename(&file, TEXTURE, "b");
+    let sprite = rename(&file, SPRITE, "Sprite_Renamed_Much_Longer_Than_Before");
+    edits.set_object_bytes(SPRITE, sprite.get(SPRITE).unwrap().to_vec());
+
+    let (copy, copy_report) =
+        SerializedFileWriter::save_incremental(&file, &edits, IncrementalSaveOptions::default())
+            .unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("banner_1.assets");
+    std::fs::write(&path, &original).unwrap();
+    let report = SerializedFileWriter::save_incremental_in_place(
+        &path,
+        &file,
+        &edits,
+        IncrementalSaveOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(report, copy_report);
+    assert_eq!(report.count(ObjectPatchStrategy::InPlace), 1);
+    assert_eq!(report.count(ObjectPatchStrategy::Relocated), 1);
+    assert_eq!(std::fs::read(&path).unwrap(), copy);
+
+    // A file that is not the parsed one is refused.
+    std::fs::write(&path, &original[..original.len() - 1]).unwrap();
+    assert!(
+        SerializedFileWriter::save_incremental_in_place(
+            &path,
+            &file,
+            &edits,
+            IncrementalSaveOptions::default()
+        )
+        .is_err()
+    );
+}
@@ -0,0 +1,120 @@
+//! `SerializedFile::rename_object`: `m_Name` and matching container paths survive save + reload.
+
+use unity_asset_binary::asset::{ObjectInfo, SerializedFile, SerializedFileParser, SerializedType};
+use unity_asset_binary::typetree::TypeTree;
+use unity_asset_decode::texture::Texture2DConverter;
+use unity_asset_decode::unity_version::UnityVersion;
+use unity_asset_write::serialized_file::{SerializedFileEdits, SerializedFileWriter};
+
+const TEXTURE: i64 = -3875358842991402074;
+const SPRITE: i64 = -8325468307350463555;
+const ASSET_BUNDLE: i64 = 1;
+
+fn banner_file() -> SerializedFile {
+    let bytes = include_bytes!("../../../tests/samples/banner_1").to_vec();
+    let bundle = unity_asset_binary::bundle::BundleParser::from_bytes(bytes).unwrap();
+    let node = bundle
+        .nodes
+        .iter()
+        .find(|n| n.is_file() && !n.name.ends_with(".resS") && !n.name.ends_with(".resource"))
+        .unwrap();
+    SerializedFileParser::from_bytes(bundle.extract_node_data(node).unwrap()).unwrap()
+}
+
+fn resave(file: &SerializedFile) -> SerializedFile {
+    let saved = SerializedFileWriter::save(file, &SerializedFileEdits::new()).unwrap();
+    SerializedFileParser::from_bytes(saved).unwrap()
+}
+
+fn peek_name(file: &SerializedFile, path_id: i64) -> String {
+    file.find_object_handle(path_id)
+        .unwrap()
+        .peek_name()
+        .unwrap()
+        .unwrap()
+}
+
+fn typed_texture(file: &SerializedFile) -> unity_asset_decode::texture::Texture2D {
+    let object = file.find_object_handle(TEXTURE).unwrap().read().unwrap();
+    Texture2DConverter::new(UnityVersion::default())
+        .from_unity_object(&object)
+        .unwrap()
+}
+
+/// `(container path, path_id)` pairs of the AssetBundle object.
+fn container(file: &SerializedFile) -> Vec<(String, i64)> {
+    let bundle = file.find_object_handle(ASSET_BUNDLE).unwrap();
+    let strings = bundle.scan_strings(usize::MAX).unwrap().unwrap();
+    let pptrs = bundle.scan_pptr_fields().unwrap().unwrap();
+    strings
+        .iter()
+        .filter_map(|s| {
+            let entry = s.path.strip_suffix(".first")?;
+            let asset = format!("{}.second.asset", entry);
+            let pptr = pptrs.iter().find(|p| p.path == asset)?;
+            Some((s.value.clone(), pptr.path_id))
+        })
+        .collect()
+}
+
+#[test]
+fn renamed_texture_round_trips() {
+    let mut file = banner_file();
+    let before = typed_texture(&file);
+    assert_eq!(before.name, "banner_1");
+
+    file.rename_object(TEXTURE, "Banner_Renamed_Longer")
+        .unwrap();
+    assert_eq!(peek_name(&file, TEXTURE), "Banner_Renamed_Longer");
+
+    let reloaded = resave(&file);
+    assert_eq!(peek_name(&reloaded, TEXTURE), "Banner_Renamed_Longer");
+    let after = typed_texture(&reloaded);
+    assert_eq!(after.name, "Banner_Renamed_Longer");
+    assert_eq!(
+        (after.width, after.height, after.format, after.data_size),
+        (before.width, before.height, before.format, before.data_size)
+    );
+
+    // Only the texture's container entry follows; the sprite keeps its path and name.
+    let entries = container(&reloaded);
+    assert!(entries.contains(&(
+        "assets/assetbundles/images/banner/banner_renamed_longer.png".to_string(),
+        TEXTURE
+    )));
+    assert!(entries.contains(&(
+        "assets/assetbundles/images/banner/banner_1.png".to_string(),
+        SPRITE
+    )));
+    assert_eq!(peek_name(&reloaded, SPRITE), "banner_1");
+}
+
+#[test]
+fn objects_without_a_type_tree_rename_their_leading_string() {
+    let mut file = banner_file();
+    let type_index = file.find_object(TEXTURE).unwrap().type_index as usize;
+    let tree = std::mem::replace(&mut file.types[type_index].type_tree, TypeTree::new());
+
+    file.rename_object(TEXTURE, "tex").unwrap();
+
+    file.types[type_index].type_tree = tree;
+    assert_eq!(peek_name(&file, TEXTURE), "tex");
+    assert_eq!(typed_texture(&resave(&file)).name, "tex");
+}
+
+#[test]
+fn classes_without_m_name_are_refused() {
+    let mut file = banner_file();
+    file.types.push(SerializedType::new(4));
+    let mut transform = ObjectInfo::new(77, 0, 8, 4, file.types.len() as i32 - 1);
+    transform.data = vec![4, 0, 0, 0, b'r', b'o', b'o', b't'];
+    file.objects.push(transform);
+
+    let err = file.rename_object(77, "renamed").unwrap_err();
+    assert!(err.to_string().contains("no m_Name"), "{}", err);
+    assert_eq!(
+        file.find_object(77).unwrap().data,
+        [4, 0, 0, 0, b'r', b'o', b'o', b't']
+    );
+    assert!(file.rename_object(12345, "missing").is_err());
+}
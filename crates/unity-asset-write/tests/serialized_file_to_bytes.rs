@@ -0,0 +1,267 @@
+//! `SerializedFileExt::to_bytes`: unmodified files re-serialize byte-identically, and objects
+//! patched through `serialize_object_with_typetree` are written back in place.
+
+use unity_asset_binary::asset::{SerializedFile, SerializedFileParser};
+use unity_asset_binary::bundle::BundleParser;
+use unity_asset_binary::typetree::serialize_object_with_typetree;
+use unity_asset_core::UnityValue;
+use unity_asset_write::serialized_file::SerializedFileExt;
+
+const TEXTURE: i64 = -3875358842991402074;
+
+/// The serialized file of each sample bundle, as stored in the bundle.
+fn cab(name: &str) -> Vec<u8> {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/samples")
+        .join(name);
+    let bundle = BundleParser::from_bytes(std::fs::read(path).unwrap()).unwrap();
+    let node = bundle
+        .nodes
+        .iter()
+        .find(|n| n.name == bundle.asset_names[0])
+        .unwrap();
+    bundle.extract_node_data(node).unwrap()
+}
+
+#[test]
+fn unmodified_files_round_trip_byte_identical() {
+    // banner_1 has no padding after its last object; char_118_yuki.ab starts its data at 4096.
+    for name in [
+        "banner_1",
+        "char_118_yuki.ab",
+        "atlas_test",
+        "xinzexi_2_n_tex",
+    ] {
+        let original = cab(name);
+        let file = SerializedFileParser::from_bytes(original.clone()).unwrap();
+        assert_eq!(file.header.version, 17, "{}", name);
+        assert_eq!(file.to_bytes().unwrap(), original, "{}", name);
+    }
+}
+
+// Node offsets into Unity's common string table.
+const COMMON: u32 = 0x8000_0000;
+const ARRAY: u32 = COMMON | 49;
+const BASE: u32 = COMMON | 55;
+const CHAR: u32 = COMMON | 81;
+const DATA: u32 = COMMON | 106;
+const INT: u32 = COMMON | 222;
+const M_NAME: u32 = COMMON | 427;
+const M_SCRIPT: u32 = COMMON | 490;
+const SIZE: u32 = COMMON | 795;
+const STRING: u32 = COMMON | 840;
+const TEXT_ASSET: u32 = COMMON | 847;
+
+fn push_cstring(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(s.as_bytes());
+    out.push(0);
+}
+
+fn push_aligned_string(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as i32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+    out.resize(out.len().next_multiple_of(4), 0);
+}
+
+/// Blob typetree: `(level, type_flags, type, name, byte_size, meta_flags)` per node.
+fn push_type_tree(
+    out: &mut Vec<u8>,
+    version: u32,
+    nodes: &[(u8, u8, u32, u32, i32, i32)],
+    strings: &[u8],
+) {
+    out.extend_from_slice(&(nodes.len() as i32).to_le_bytes());
+    out.extend_from_slice(&(strings.len() as i32).to_le_bytes());
+    for (index, &(level, flags, type_str, name_str, byte_size, meta_flags)) in
+        nodes.iter().enumerate()
+    {
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.push(level);
+        out.push(flags);
+        out.extend_from_slice(&type_str.to_le_bytes());
+        out.extend_from_slice(&name_str.to_le_bytes());
+        out.extend_from_slice(&byte_size.to_le_bytes());
+        out.extend_from_slice(&(index as i32).to_le_bytes());
+        out.extend_from_slice(&meta_flags.to_le_bytes());
+        if version >= 19 {
+            // m_RefTypeHash
+            out.extend_from_slice(&0u64.to_le_bytes());
+        }
+    }
+    out.extend_from_slice(strings);
+}
+
+/// A little-endian TextAsset file laid out by hand for format 19-21 (Unity 2019.1-2019.4),
+/// independent of the writer under test.
+///
+/// From 19 every typetree node carries a ref type hash, 20 adds the `SerializeReference` type
+/// table and 21 adds type dependencies and the ref types' class names.
+fn hand_built_serialized_file(version: u32, unity_version: &str) -> Vec<u8> {
+    let string_field = |name| {
+        [
+            (1, 0, STRING, name, -1, 0x8000),
+            (2, 1, ARRAY, ARRAY, -1, 0x4001),
+            (3, 0, INT, SIZE, 4, 0x0001),
+            (3, 0, CHAR, DATA, 1, 0x0001),
+        ]
+    };
+    let mut text_asset = vec![(0, 0, TEXT_ASSET, BASE, -1, 0)];
+    text_asset.extend(string_field(M_NAME));
+    text_asset.extend(string_field(M_SCRIPT));
+
+    let mut meta = Vec::new();
+    push_cstring(&mut meta, unity_version);
+    meta.extend_from_slice(&13i32.to_le_bytes()); // target platform: Android
+    meta.push(1); // enableTypeTree
+
+    meta.extend_from_slice(&1i32.to_le_bytes());
+    meta.extend_from_slice(&49i32.to_le_bytes()); // TextAsset
+    meta.push(0); // m_IsStrippedType
+    meta.extend_from_slice(&(-1i16).to_le_bytes()); // m_ScriptTypeIndex
+    meta.extend_from_slice(&[0x5a; 16]); // m_OldTypeHash
+    push_type_tree(&mut meta, version, &text_asset, &[]);
+    if version >= 21 {
+        meta.extend_from_slice(&0i32.to_le_bytes()); // m_TypeDependencies
+    }
+
+    let mut data = Vec::new();
+    push_aligned_string(&mut data, "notes");
+    push_aligned_string(&mut data, &format!("format {version}"));
+
+    meta.extend_from_slice(&1i32.to_le_bytes());
+    meta.resize((20 + meta.len()).next_multiple_of(4) - 20, 0);
+    meta.extend_from_slice(&(-7i64).to_le_bytes()); // path id
+    meta.extend_from_slice(&0u32.to_le_bytes()); // byte start
+    meta.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    meta.extend_from_slice(&0i32.to_le_bytes()); // type index
+
+    // m_ScriptTypes: the MonoScript behind the ref type below.
+    meta.extend_from_slice(&1i32.to_le_bytes());
+    meta.extend_from_slice(&1i32.to_le_bytes());
+    meta.resize((20 + meta.len()).next_multiple_of(4) - 20, 0);
+    meta.extend_from_slice(&11500000i64.to_le_bytes());
+
+    meta.extend_from_slice(&1i32.to_le_bytes());
+    push_cstring(&mut meta, "");
+    meta.extend_from_slice(&[0x11; 16]);
+    meta.extend_from_slice(&0i32.to_le_bytes());
+    push_cstring(&mut meta, "archive:/CAB-scripts/CAB-scripts");
+
+    if version >= 20 {
+        meta.extend_from_slice(&1i32.to_le_bytes());
+        meta.extend_from_slice(&0i32.to_le_bytes());
+        meta.push(0);
+        meta.extend_from_slice(&0i16.to_le_bytes());
+        meta.extend_from_slice(&[0x22; 16]); // m_ScriptID
+        meta.extend_from_slice(&[0x33; 16]); // m_OldTypeHash
+        let strings = b"Payload\0value\0";
+        push_type_tree(
+            &mut meta,
+            version,
+            &[(0, 0, 0, BASE, 4, 0), (1, 0, INT, 8, 4, 0)],
+            strings,
+        );
+        if version >= 21 {
+            push_cstring(&mut meta, "Payload");
+            push_cstring(&mut meta, "Game");
+            push_cstring(&mut meta, "Assembly-CSharp");
+        }
+    }
+
+    push_cstring(&mut meta, "");
+
+    // Small files keep their data at 4096 like the Unity-built samples.
+    let data_offset = 4096u32;
+    let mut out = Vec::new();
+    out.extend_from_slice(&(meta.len() as u32).to_be_bytes());
+    out.extend_from_slice(&(data_offset + data.len() as u32).to_be_bytes());
+    out.extend_from_slice(&version.to_be_bytes());
+    out.extend_from_slice(&data_offset.to_be_bytes());
+    out.extend_from_slice(&[0, 0, 0, 0]); // little endian + reserved
+    out.extend_from_slice(&meta);
+    out.resize(data_offset as usize, 0);
+    out.extend_from_slice(&data);
+    out
+}
+
+#[test]
+fn hand_built_format_19_to_21_files_round_trip_byte_identical() {
+    for (version, unity_version) in [
+        (19, "2019.1.14f1"),
+        (20, "2019.2.21f1"),
+        (21, "2019.4.40f1"),
+    ] {
+        let original = hand_built_serialized_file(version, unity_version);
+        let file = SerializedFileParser::from_bytes(original.clone()).unwrap();
+        assert_eq!(file.header.version, version);
+        assert_eq!(file.ref_types.len(), usize::from(version >= 20));
+        if version >= 21 {
+            assert_eq!(file.ref_types[0].class_name, "Payload");
+        }
+
+        let object = file.find_object_handle(-7).unwrap().read().unwrap();
+        assert_eq!(object.name(), Some("notes".to_string()));
+        assert_eq!(
+            object.get("m_Script"),
+            Some(&UnityValue::String(format!("format {version}")))
+        );
+
+        assert_eq!(file.to_bytes().unwrap(), original, "v{}", version);
+    }
+}
+
+#[test]
+fn every_format_version_from_17_round_trips() {
+    for version in 17..=22 {
+        let mut file = SerializedFileParser::from_bytes(cab("banner_1")).unwrap();
+        file.header.version = version;
+        let saved = file.to_bytes().unwrap();
+
+        let reparsed = SerializedFileParser::from_bytes(saved.clone()).unwrap();
+        assert_eq!(reparsed.header.version, version);
+        assert_eq!(reparsed.header.byte_order(), file.header.byte_order());
+        assert_eq!(reparsed.to_bytes().unwrap(), saved, "v{}", version);
+        for (a, b) in file.object_handles().zip(reparsed.object_handles()) {
+            assert_eq!(a.path_id(), b.path_id());
+            assert_eq!(a.raw_data().unwrap(), b.raw_data().unwrap(), "v{}", version);
+        }
+    }
+}
+
+#[test]
+fn patched_object_is_written_back() {
+    let mut file: SerializedFile = SerializedFileParser::from_bytes(cab("banner_1")).unwrap();
+    let object = file.find_object_handle(TEXTURE).unwrap().read().unwrap();
+    let mut properties = object.as_unity_class().properties().clone();
+    properties.insert(
+        "m_Name".to_string(),
+        UnityValue::String("banner_patched".to_string()),
+    );
+
+    let index = file
+        .objects
+        .iter()
+        .position(|o| o.path_id == TEXTURE)
+        .unwrap();
+    let type_index = file.objects[index].type_index as usize;
+    let bytes =
+        serialize_object_with_typetree(&file.types[type_index].type_tree, &properties).unwrap();
+    file.objects[index].data = bytes;
+
+    let reparsed = SerializedFileParser::from_bytes(file.to_bytes().unwrap()).unwrap();
+    assert_eq!(reparsed.objects.len(), file.objects.len());
+    for handle in reparsed.object_handles() {
+        let object = handle.read().unwrap();
+        let original = file
+            .find_object_handle(handle.path_id())
+            .unwrap()
+            .read()
+            .unwrap();
+        if handle.path_id() == TEXTURE {
+            assert_eq!(object.name(), Some("banner_patched".to_string()));
+            assert_eq!(object.get("m_Width"), original.get("m_Width"));
+        } else {
+            assert_eq!(object.name(), original.name());
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! `Texture2D::set_image` + `Texture2DConverter::write_to_class`: banner_1's streamed ASTC texture
+//! is re-encoded as a small mipmapped RGBA32 texture, rotated, saved and re-parsed.
+
+use image::RgbaImage;
+use image::imageops::{self, FilterType};
+use unity_asset_binary::asset::{SerializedFile, SerializedFileParser};
+use unity_asset_binary::bundle::{AssetBundle, BundleParser};
+use unity_asset_core::UnityAssetError;
+use unity_asset_decode::texture::{
+    Decoder, MobileDecoder, ReencodeOptions, Texture2D, Texture2DConverter, TextureDecoder,
+    TextureFormat,
+};
+use unity_asset_decode::unity_version::UnityVersion;
+use unity_asset_write::object::SerializedFileEditSession;
+use unity_asset_write::serialized_file::SerializedFileWriter;
+
+const CAB: &str = "CAB-fa4c27fa39f48e1346f48009626ba08d";
+const RESS: &str = "CAB-fa4c27fa39f48e1346f48009626ba08d.resS";
+const TEXTURE: i64 = -3875358842991402074;
+
+fn node_data(bundle: &AssetBundle, name: &str) -> Vec<u8> {
+    let node = bundle.nodes.iter().find(|n| n.name == name).unwrap();
+    bundle.extract_node_data(node).unwrap()
+}
+
+fn converter() -> Texture2DConverter {
+    Texture2DConverter::new(UnityVersion::default())
+}
+
+fn read_texture(file: &SerializedFile) -> Texture2D {
+    let object = file.find_object_handle(TEXTURE).unwrap().read().unwrap();
+    converter().from_unity_object(&object).unwrap()
+}
+
+/// Write `texture` into the file's Texture2D object, save and re-parse
+fn save(file: &SerializedFile, texture: &Texture2D) -> SerializedFile {
+    let mut session = SerializedFileEditSession::new(file);
+    session
+        .edit_object(TEXTURE, |class| {
+            converter()
+                .write_to_class(texture, class)
+                .map_err(|e| UnityAssetError::with_source("write texture", e))
+        })
+        .unwrap();
+    let bytes = SerializedFileWriter::save(file, &session.into_edits()).unwrap();
+    SerializedFileParser::from_bytes(bytes).unwrap()
+}
+
+/// banner_1's texture shrunk to 16x8 and stored as embedded RGBA32 with a full mip chain
+fn rgba32_fixture() -> (SerializedFile, RgbaImage) {
+    let bundle =
+        BundleParser::from_bytes(include_bytes!("../../../tests/samples/banner_1").to_vec())
+            .unwrap();
+    let file = SerializedFileParser::from_bytes(node_data(&bundle, CAB)).unwrap();
+    let mut texture = read_texture(&file);
+    assert_eq!(texture.format, TextureFormat::ASTC_RGBA_6x6);
+    let start = texture.stream_info.offset as usize;
+    texture.image_data =
+        node_data(&bundle, RESS)[start..start + texture.stream_info.size as usize].to_vec();
+    let banner = MobileDecoder::new().decode(&texture).unwrap();
+
+    // Compressed textures keep their format unless told otherwise, which is not encodable.
+    let small = imageops::resize(&banner, 16, 8, FilterType::Triangle);
+    assert!(
+        texture
+            .clone()
+            .set_image(&small, ReencodeOptions::default())
+            .is_err()
+    );
+    texture
+        .set_image(
+            &small,
+            ReencodeOptions::default()
+                .with_format(TextureFormat::RGBA32)
+                .with_mipmaps(true),
+        )
+        .unwrap();
+    assert!(!texture.is_streamed());
+
+    let saved = save(&file, &texture);
+    let fixture = read_texture(&saved);
+    assert_eq!(fixture.format, TextureFormat::RGBA32);
+    assert_eq!((fixture.width, fixture.height), (16, 8));
+    // 16x8, 8x4, 4x2, 2x1, 1x1
+    assert_eq!(fixture.mip_count, 5);
+    assert_eq!(fixture.complete_image_size, (128 + 32 + 8 + 2 + 1) * 4);
+    assert_eq!(
+        fixture.image_data.len(),
+        fixture.complete_image_size as usize
+    );
+    assert!(fixture.stream_info.path.is_empty());
+    (saved, small)
+}
+
+#[test]
+fn rotated_texture_saves_and_reparses() {
+    let (file, original) = rgba32_fixture();
+    let mut texture = read_texture(&file);
+    texture.rotate90().unwrap();
+
+    let reloaded = read_texture(&save(&file, &texture));
+    assert_eq!(reloaded.format, TextureFormat::RGBA32);
+    assert_eq!((reloaded.width, reloaded.height), (8, 16));
+    assert_eq!(reloaded.mip_count, 5);
+    assert_eq!(reloaded.complete_image_size, (128 + 32 + 8 + 2 + 1) * 4);
+
+    // Clockwise: source (x, y) lands at (height - 1 - y, x).
+    let rotated = TextureDecoder::new().decode(&reloaded).unwrap();
+    for (x, y, pixel) in original.enumerate_pixels() {
+        assert_eq!(rotated.get_pixel(7 - y, x), pixel, "pixel ({}, {})", x, y);
+    }
+    assert_ne!(rotated.get_pixel(0, 0), original.get_pixel(0, 0));
+
+    // Mirroring twice and rotating the rest of the way gets back to the original pixels.
+    let mut texture = reloaded;
+    texture.flip_horizontal().unwrap();
+    texture.flip_horizontal().unwrap();
+    texture.rotate270().unwrap();
+    assert_eq!(
+        TextureDecoder::new().decode(&texture).unwrap().into_raw(),
+        original.into_raw()
+    );
+}
+
+#[test]
+fn reencoding_into_another_format_and_size() {
+    let (file, original) = rgba32_fixture();
+    let mut texture = read_texture(&file);
+    texture.resize(4, 2, FilterType::Nearest).unwrap();
+    assert_eq!(
+        (texture.width, texture.height, texture.mip_count),
+        (4, 2, 3)
+    );
+
+    let image = TextureDecoder::new().decode(&texture).unwrap();
+    texture
+        .set_image(
+            &image,
+            ReencodeOptions::default()
+                .with_format(TextureFormat::RGB24)
+                .with_mipmaps(false),
+        )
+        .unwrap();
+    let reloaded = read_texture(&save(&file, &texture));
+    assert_eq!(reloaded.format, TextureFormat::RGB24);
+    assert_eq!((reloaded.width, reloaded.height), (4, 2));
+    assert_eq!(reloaded.mip_count, 1);
+    assert_eq!(reloaded.image_data.len(), 4 * 2 * 3);
+
+    let decoded = TextureDecoder::new().decode(&reloaded).unwrap();
+    let expected = imageops::resize(&original, 4, 2, FilterType::Nearest);
+    for (a, b) in decoded.pixels().zip(expected.pixels()) {
+        assert_eq!(a.0[..3], b.0[..3]);
+        assert_eq!(a.0[3], 255);
+    }
+}
@@ -274,9 +274,7 @@ fn unitypy_can_load_saved_unityfs_bundle() -> anyhow::Result<()> {
     let saved = BundleWriter::save(
         &bundle,
         &BundleEdits::default(),
-        PackerOptions {
-            packer: UnityPyPacker::Original,
-        },
+        PackerOptions::default().with_packer(UnityPyPacker::Original),
     )?;
 
     let tmp = tempfile::NamedTempFile::new()?;
@@ -638,9 +636,7 @@ fn unitypy_observes_rust_typetree_edit_in_repacked_bundle() -> anyhow::Result<()
         &mut w,
         obj.class.properties(),
         original_bytes,
-        TypeTreeWriteOptions {
-            allow_missing_fields: false,
-        },
+        TypeTreeWriteOptions::default().with_allow_missing_fields(false),
     )?;
     let patched_bytes = w.into_bytes();
 
@@ -653,9 +649,7 @@ fn unitypy_observes_rust_typetree_edit_in_repacked_bundle() -> anyhow::Result<()
     let saved_bundle = BundleWriter::save(
         &bundle,
         &bundle_edits,
-        PackerOptions {
-            packer: UnityPyPacker::Original,
-        },
+        PackerOptions::default().with_packer(UnityPyPacker::Original),
     )?;
 
     let tmp = tempfile::NamedTempFile::new()?;
@@ -0,0 +1,216 @@
+//! Comment capture for Unity YAML text
+//!
+//! serde_yaml drops comments, so the loader runs this line scan next to it and stores the
+//! result on each `UnityClass` (see [`YamlComments`]). Full-line comments attach to the next
+//! key or sequence item; comments before a `---` header (or the class name line) belong to that
+//! document. Key paths follow the block structure by indentation, which is all Unity writes.
+
+use unity_asset_core::YamlComments;
+
+/// An open mapping key or sequence item.
+struct Frame {
+    /// Column of the key (for items: one past the `-`).
+    column: usize,
+    path: String,
+    /// Index of the next `- ` item under this key.
+    next_item: usize,
+}
+
+/// Collect comments per document, in document order.
+///
+/// Text before the first `---` belongs to the first document.
+pub(crate) fn scan_comments(text: &str) -> Vec<YamlComments> {
+    let mut documents = vec![YamlComments::default()];
+    let mut started = false;
+    let mut pending: Vec<String> = Vec::new();
+    let mut frames: Vec<Frame> = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim_end_matches('\r');
+        let content = line.trim_start();
+        if content.is_empty() {
+            continue;
+        }
+        if content.starts_with('#') {
+            pending.push(content.trim_end().to_string());
+            continue;
+        }
+        if line.starts_with('%') {
+            continue;
+        }
+        if line.starts_with("---") {
+            if started {
+                documents.push(YamlComments::default());
+            }
+            started = true;
+            frames.clear();
+            let doc = documents.last_mut().expect("at least one document");
+            doc.document.append(&mut pending);
+            continue;
+        }
+        started = true;
+
+        let doc = documents.last_mut().expect("at least one document");
+        let (body, trailing) = split_trailing_comment(content);
+        let indent = line.len() - content.len();
+
+        // The class name line (`MonoBehaviour:`) opens the document body.
+        if indent == 0 && body != "-" && !body.starts_with("- ") {
+            doc.document.append(&mut pending);
+            if let Some(comment) = trailing {
+                doc.trailing.insert(String::new(), comment.to_string());
+            }
+            frames.clear();
+            continue;
+        }
+
+        let mut column = indent;
+        let mut rest = body;
+        let mut line_path: Option<String> = None;
+        if rest == "-" || rest.starts_with("- ") {
+            while frames.last().is_some_and(|f| f.column > column) {
+                frames.pop();
+            }
+            let Some(owner) = frames.last_mut() else {
+                continue;
+            };
+            let item = format!("{}[{}]", owner.path, owner.next_item);
+            owner.next_item += 1;
+            frames.push(Frame {
+                column: column + 1,
+                path: item.clone(),
+                next_item: 0,
+            });
+            line_path = Some(item);
+
+            let after_dash = rest[1..].trim_start();
+            column += rest.len() - after_dash.len();
+            rest = after_dash;
+        }
+
+        let mut trailing_path = line_path.clone();
+        if let Some(key) = mapping_key(rest) {
+            while frames.last().is_some_and(|f| f.column >= column) {
+                frames.pop();
+            }
+            let key_path = match frames.last() {
+                Some(parent) => format!("{}.{}", parent.path, key),
+                None => key.to_string(),
+            };
+            frames.push(Frame {
+                column,
+                path: key_path.clone(),
+                next_item: 0,
+            });
+            line_path.get_or_insert_with(|| key_path.clone());
+            trailing_path = Some(key_path);
+        }
+
+        // Continuation lines of multi-line scalars keep their comments for the next key.
+        let Some(path) = line_path else {
+            continue;
+        };
+        if !pending.is_empty() {
+            doc.leading.entry(path).or_default().append(&mut pending);
+        }
+        if let (Some(comment), Some(path)) = (trailing, trailing_path) {
+            doc.trailing.insert(path, comment.to_string());
+        }
+    }
+
+    documents
+        .last_mut()
+        .expect("at least one document")
+        .footer
+        .append(&mut pending);
+    documents
+}
+
+/// Split `value # comment` into the value and the comment.
+///
+/// A `#` only starts a comment after whitespace and outside quoted scalars.
+pub(crate) fn split_trailing_comment(content: &str) -> (&str, Option<&str>) {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut previous = ' ';
+    for (index, c) in content.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && previous.is_whitespace() && index > 0 => {
+                return (
+                    content[..index].trim_end(),
+                    Some(content[index..].trim_end()),
+                );
+            }
+            None => {}
+        }
+        previous = c;
+    }
+    (content, None)
+}
+
+/// The key of a `key: value` / `key:` line, if it is one.
+pub(crate) fn mapping_key(text: &str) -> Option<&str> {
+    if text.starts_with(['{', '[', '"', '\'', '&', '*', '!', '|', '>']) {
+        return None;
+    }
+    let end = text
+        .find(": ")
+        .or_else(|| text.strip_suffix(':').map(str::len))?;
+    let key = text[..end].trim_end();
+    (!key.is_empty()).then_some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_anchors_comments_to_key_paths() {
+        let text = "%YAML 1.1
+# first document
+--- !u!1 &1
+GameObject: # class
+  # the name
+  m_Name: Player # trailing
+  m_Component:
+  # transform first
+  - component: {fileID: 4} # the transform
+  - component: {fileID: 5}
+  m_Color: {r: 1, g: 1, b: 1, a: 1}
+  m_Text: 'not # a comment'
+  m_Nested:
+    inner:
+      # deep
+      value: 3
+# before second
+--- !u!4 &4
+Transform:
+  m_Father: {fileID: 0}
+# at the end
+";
+        let docs = scan_comments(text);
+        assert_eq!(docs.len(), 2);
+
+        let first = &docs[0];
+        assert_eq!(first.document, vec!["# first document"]);
+        assert_eq!(first.trailing[""], "# class");
+        assert_eq!(first.leading["m_Name"], vec!["# the name"]);
+        assert_eq!(first.trailing["m_Name"], "# trailing");
+        assert_eq!(first.leading["m_Component[0]"], vec!["# transform first"]);
+        assert_eq!(
+            first.trailing["m_Component[0].component"],
+            "# the transform"
+        );
+        assert_eq!(first.leading["m_Nested.inner.value"], vec!["# deep"]);
+        assert!(!first.trailing.contains_key("m_Text"));
+
+        let second = &docs[1];
+        assert_eq!(second.document, vec!["# before second"]);
+        assert_eq!(second.footer, vec!["# at the end"]);
+    }
+}
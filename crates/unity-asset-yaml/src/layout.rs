@@ -0,0 +1,456 @@
+//! Source layout capture for Unity YAML text
+//!
+//! serde_yaml keeps neither how scalars were spelled (`0000000000000000e000000000000000` becomes
+//! `0.0`, `'a: b'` loses its quotes) nor which collections were written in flow style. Like
+//! [`scan_comments`](crate::comments::scan_comments), the loader runs this line scan next to it;
+//! [`apply_layout`] then keeps only what the serializer would not reproduce on its own (see
+//! [`YamlLayout`]), which for files written by Unity is usually nothing.
+//!
+//! Paths include the top-level key (the class name line), since a document is only known to be a
+//! plain mapping after parsing.
+
+use crate::comments::{mapping_key, split_trailing_comment};
+use crate::unity_yaml_serializer::{default_style, plain_scalar};
+use indexmap::IndexMap;
+use unity_asset_core::{UnityClass, UnityValue, YamlCollectionStyle, YamlLayout};
+
+/// Scalar texts and collection styles of one document, by key path.
+#[derive(Debug, Default)]
+pub(crate) struct ScannedLayout {
+    scalars: IndexMap<String, String>,
+    styles: IndexMap<String, YamlCollectionStyle>,
+}
+
+/// An open mapping key or sequence item.
+struct Frame {
+    /// Column of the key (for items: one past the `-`).
+    column: usize,
+    path: String,
+    /// Index of the next `- ` item under this key.
+    next_item: usize,
+}
+
+/// A scalar or flow collection that may continue on the following lines.
+struct OpenScalar {
+    path: String,
+    text: String,
+    /// A flow collection (`{r: 1, g: 1,` wrapped before `b: 1, a: 1}`)
+    flow: bool,
+    /// Continuation lines of plain scalars are indented past this column.
+    column: usize,
+    /// Closing quote still to be found.
+    quote: Option<char>,
+    /// Blank lines seen since the last continuation line.
+    blank_lines: usize,
+}
+
+/// Collect the layout of each document, in document order.
+///
+/// Documents are split the same way as [`scan_comments`](crate::comments::scan_comments).
+pub(crate) fn scan_layout(text: &str) -> Vec<ScannedLayout> {
+    let mut documents = vec![ScannedLayout::default()];
+    let mut started = false;
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut open: Option<OpenScalar> = None;
+
+    for line in text.lines() {
+        let line = line.trim_end_matches('\r');
+        let content = line.trim_start();
+        let indent = line.len() - content.len();
+
+        if let Some(scalar) = open.as_mut() {
+            let doc = documents.last_mut().expect("at least one document");
+            if scalar.flow {
+                // Line breaks inside flow collections fold into spaces.
+                scalar.text.push(' ');
+                scalar.text.push_str(content.trim_end());
+                if flow_closed(&scalar.text) {
+                    finish(doc, open.take());
+                }
+                continue;
+            }
+            if let Some(quote) = scalar.quote {
+                scalar.text.push('\n');
+                scalar.text.push_str(line);
+                if closing_quote(content, quote).is_some() {
+                    scalar.quote = None;
+                    finish(doc, open.take());
+                }
+                continue;
+            }
+            if content.is_empty() {
+                scalar.blank_lines += 1;
+                continue;
+            }
+            if indent > scalar.column
+                && !content.starts_with('#')
+                && !is_item(content)
+                && mapping_key(content).is_none()
+            {
+                for _ in 0..=scalar.blank_lines {
+                    scalar.text.push('\n');
+                }
+                scalar.blank_lines = 0;
+                scalar.text.push_str(line);
+                continue;
+            }
+            finish(doc, open.take());
+        }
+
+        if content.is_empty() || content.starts_with('#') || line.starts_with('%') {
+            continue;
+        }
+        if line.starts_with("---") {
+            if started {
+                documents.push(ScannedLayout::default());
+            }
+            started = true;
+            frames.clear();
+            continue;
+        }
+        started = true;
+
+        let doc = documents.last_mut().expect("at least one document");
+        let (mut rest, _) = split_trailing_comment(content);
+        let mut column = indent;
+        let mut value_path: Option<String> = None;
+
+        if is_item(rest) {
+            while frames.last().is_some_and(|f| f.column > column) {
+                frames.pop();
+            }
+            let Some(owner) = frames.last_mut() else {
+                continue;
+            };
+            if owner.next_item == 0 {
+                let style = if column > owner.column {
+                    YamlCollectionStyle::IndentedBlock
+                } else {
+                    YamlCollectionStyle::Block
+                };
+                doc.styles.insert(owner.path.clone(), style);
+            }
+            let item = format!("{}[{}]", owner.path, owner.next_item);
+            owner.next_item += 1;
+            frames.push(Frame {
+                column: column + 1,
+                path: item.clone(),
+                next_item: 0,
+            });
+
+            let after_dash = rest[1..].trim_start();
+            let dash_column = column;
+            column += rest.len() - after_dash.len();
+            rest = after_dash;
+            if mapping_key(rest).is_none() {
+                open = record_value(doc, item, rest, dash_column);
+                continue;
+            }
+        }
+
+        if let Some(key) = mapping_key(rest) {
+            while frames.last().is_some_and(|f| f.column >= column) {
+                frames.pop();
+            }
+            let key_path = match frames.last() {
+                Some(parent) => {
+                    // A key nested under a key or item makes the parent a block mapping.
+                    doc.styles
+                        .entry(parent.path.clone())
+                        .or_insert(YamlCollectionStyle::Block);
+                    format!("{}.{}", parent.path, key)
+                }
+                None => key.to_string(),
+            };
+            frames.push(Frame {
+                column,
+                path: key_path.clone(),
+                next_item: 0,
+            });
+            let value = rest[key.len()..].trim_start();
+            value_path = Some(key_path);
+            rest = value.strip_prefix(':').unwrap_or_default().trim_start();
+        }
+
+        if let Some(path) = value_path {
+            open = record_value(doc, path, rest, column);
+        }
+    }
+
+    if let Some(doc) = documents.last_mut() {
+        finish(doc, open);
+    }
+    documents
+}
+
+fn is_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Record the value text that follows a key or `- `; returns a scalar that may continue.
+fn record_value(
+    doc: &mut ScannedLayout,
+    path: String,
+    text: &str,
+    column: usize,
+) -> Option<OpenScalar> {
+    match text.chars().next() {
+        Some('{' | '[') => {
+            doc.styles.insert(path.clone(), YamlCollectionStyle::Flow);
+            let open = OpenScalar {
+                path,
+                text: text.to_string(),
+                flow: true,
+                column,
+                quote: None,
+                blank_lines: 0,
+            };
+            if flow_closed(text) {
+                finish(doc, Some(open));
+                return None;
+            }
+            Some(open)
+        }
+        // Block scalars, anchors, aliases and tags are left to the default rendering.
+        Some('|' | '>' | '&' | '*' | '!') => None,
+        Some(quote @ ('\'' | '"')) => {
+            let open = OpenScalar {
+                path,
+                text: text.to_string(),
+                flow: false,
+                column,
+                quote: Some(quote),
+                blank_lines: 0,
+            };
+            if closing_quote(&text[1..], quote).is_some() {
+                finish(doc, Some(open));
+                return None;
+            }
+            Some(open)
+        }
+        _ => Some(OpenScalar {
+            path,
+            text: text.to_string(),
+            flow: false,
+            column,
+            quote: None,
+            blank_lines: 0,
+        }),
+    }
+}
+
+fn finish(doc: &mut ScannedLayout, scalar: Option<OpenScalar>) {
+    match scalar {
+        Some(flow) if flow.flow => scan_flow(doc, &flow.path, &flow.text),
+        Some(scalar) => {
+            doc.scalars.insert(scalar.path, scalar.text);
+        }
+        None => {}
+    }
+}
+
+/// Whether every bracket opened in `text` is closed (outside quoted scalars).
+fn flow_closed(text: &str) -> bool {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    for c in text.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '{' | '[' => depth += 1,
+                '}' | ']' => depth = depth.saturating_sub(1),
+                _ => {}
+            },
+        }
+    }
+    depth == 0 && quote.is_none()
+}
+
+/// Byte offset of the quote closing a scalar, searching from the start of `text`.
+fn closing_quote(text: &str, quote: char) -> Option<usize> {
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' if quote == '"' => {
+                chars.next();
+            }
+            '\'' if quote == '\'' && chars.peek().is_some_and(|(_, next)| *next == '\'') => {
+                chars.next();
+            }
+            c if c == quote => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Record the entries of a one-line flow collection (`{fileID: 0, guid: …}`, `[1, 2]`).
+fn scan_flow(doc: &mut ScannedLayout, path: &str, text: &str) {
+    let Some(inner) = text
+        .strip_prefix(['{', '['])
+        .and_then(|t| t.strip_suffix(['}', ']']))
+    else {
+        return;
+    };
+    let mapping = text.starts_with('{');
+    for (index, entry) in split_flow_entries(inner).into_iter().enumerate() {
+        let (entry_path, value) = if mapping {
+            let Some((key, value)) = entry.split_once(':') else {
+                continue;
+            };
+            (format!("{}.{}", path, key.trim()), value.trim())
+        } else {
+            (format!("{}[{}]", path, index), entry)
+        };
+        if value.starts_with(['{', '[']) {
+            doc.styles
+                .insert(entry_path.clone(), YamlCollectionStyle::Flow);
+            scan_flow(doc, &entry_path, value);
+        } else {
+            doc.scalars.insert(entry_path, value.to_string());
+        }
+    }
+}
+
+/// Split flow collection contents on top-level commas.
+fn split_flow_entries(inner: &str) -> Vec<&str> {
+    let mut entries = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (index, c) in inner.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '{' | '[' => depth += 1,
+                '}' | ']' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    entries.push(inner[start..index].trim());
+                    start = index + 1;
+                }
+                _ => {}
+            },
+        }
+    }
+    let last = inner[start..].trim();
+    if !last.is_empty() || !entries.is_empty() {
+        entries.push(last);
+    }
+    entries
+}
+
+/// Store on `class` the parts of `scanned` the serializer would not reproduce.
+///
+/// Empty scalars (`m_Name: `) that serde_yaml read as null become empty strings, which is what
+/// Unity writes them for.
+pub(crate) fn apply_layout(class: &mut UnityClass, scanned: &ScannedLayout) {
+    let prefix = if class.is_plain() {
+        String::new()
+    } else {
+        format!("{}.", class.class_name)
+    };
+    let mut layout = YamlLayout::default();
+    for (key, value) in class.properties_mut() {
+        settle(value, key.clone(), &prefix, scanned, &mut layout);
+    }
+    *class.layout_mut() = layout;
+}
+
+fn settle(
+    value: &mut UnityValue,
+    path: String,
+    prefix: &str,
+    scanned: &ScannedLayout,
+    layout: &mut YamlLayout,
+) {
+    let source_path = format!("{}{}", prefix, path);
+    if let Some(style) = scanned.styles.get(&source_path)
+        && default_style(value).is_some_and(|default| default != *style)
+    {
+        layout.styles.insert(path.clone(), *style);
+    }
+    match value {
+        UnityValue::Object(map) => {
+            for (key, value) in map {
+                settle(value, format!("{}.{}", path, key), prefix, scanned, layout);
+            }
+        }
+        UnityValue::Array(items) => {
+            for (index, value) in items.iter_mut().enumerate() {
+                settle(
+                    value,
+                    format!("{}[{}]", path, index),
+                    prefix,
+                    scanned,
+                    layout,
+                );
+            }
+        }
+        UnityValue::Bytes(_) => {}
+        _ => {
+            let Some(text) = scanned.scalars.get(&source_path) else {
+                return;
+            };
+            if text.is_empty() && value.is_null() {
+                *value = UnityValue::String(String::new());
+            } else if plain_scalar(value).as_deref() != Some(text.as_str()) {
+                layout.scalars.insert(path, (text.clone(), value.clone()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_records_scalar_text_and_styles() {
+        let text = "%YAML 1.1
+--- !u!1 &1
+GameObject:
+  m_Name: 'Hello: world'
+  m_Guid: 0000000000000000e000000000000000
+  m_Component:
+  - component: {fileID: 4}
+  m_Tags:
+    - a
+  m_Text: 'first
+
+    second'
+  m_Empty:
+  m_Next: 1
+";
+        let docs = scan_layout(text);
+        assert_eq!(docs.len(), 1);
+        let doc = &docs[0];
+        assert_eq!(doc.scalars["GameObject.m_Name"], "'Hello: world'");
+        assert_eq!(
+            doc.scalars["GameObject.m_Guid"],
+            "0000000000000000e000000000000000"
+        );
+        assert_eq!(
+            doc.scalars["GameObject.m_Component[0].component.fileID"],
+            "4"
+        );
+        assert_eq!(doc.scalars["GameObject.m_Text"], "'first\n\n    second'");
+        assert_eq!(doc.scalars["GameObject.m_Empty"], "");
+        assert_eq!(doc.scalars["GameObject.m_Next"], "1");
+        assert_eq!(
+            doc.styles["GameObject.m_Component"],
+            YamlCollectionStyle::Block
+        );
+        assert_eq!(
+            doc.styles["GameObject.m_Component[0].component"],
+            YamlCollectionStyle::Flow
+        );
+        assert_eq!(
+            doc.styles["GameObject.m_Tags"],
+            YamlCollectionStyle::IndentedBlock
+        );
+    }
+}
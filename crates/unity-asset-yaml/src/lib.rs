@@ -25,14 +25,18 @@
 // Re-export core types
 pub use unity_asset_core::{
     DocumentFormat, Result, UnityAssetError, UnityClass, UnityClassRegistry, UnityValue,
-    constants::*,
+    UnityVersion, constants::*,
 };
 
 // Core modules
+mod comments;
 pub mod constants;
+mod layout;
 pub mod python_like_api;
 pub mod serde_unity_loader;
+pub mod settings;
 pub mod unity_yaml_serializer;
+pub mod upgraders;
 pub mod yaml_document;
 
 // Re-export main types
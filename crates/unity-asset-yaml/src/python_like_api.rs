@@ -38,11 +38,18 @@ impl PythonLikeUnityDocument {
     /// let doc = PythonLikeUnityDocument::load_yaml("ProjectSettings.asset", false)?;
     /// # Ok::<(), unity_asset_core::UnityAssetError>(())
     /// ```
+    #[cfg(feature = "fs")]
     pub fn load_yaml<P: AsRef<Path>>(file_path: P, try_preserve_types: bool) -> Result<Self> {
         let inner = YamlDocument::load_yaml(file_path, try_preserve_types)?;
         Ok(Self { inner })
     }
 
+    /// Parse Unity YAML text (the in-memory counterpart of [`load_yaml`](Self::load_yaml))
+    pub fn from_yaml_str(text: &str) -> Result<Self> {
+        let inner = YamlDocument::from_yaml_str(text)?;
+        Ok(Self { inner })
+    }
+
     /// Get the first entry (similar to Python's doc.entry)
     pub fn entry(&self) -> Option<PythonLikeUnityClass<'_>> {
         self.inner.entries().first().map(PythonLikeUnityClass::new)
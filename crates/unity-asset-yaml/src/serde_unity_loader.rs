@@ -51,7 +51,7 @@ impl SerdeUnityLoader {
         let mut content = String::new();
         reader
             .read_to_string(&mut content)
-            .map_err(|e| UnityAssetError::parse(format!("Failed to read input: {}", e)))?;
+            .map_err(|e| UnityAssetError::with_source("Failed to read input", e))?;
 
         // Preprocess Unity YAML to handle Unity-specific features
         let processed_content = self.preprocess_unity_yaml(&content)?;
@@ -59,15 +59,32 @@ impl SerdeUnityLoader {
         // Parse YAML using serde_yaml
         let documents: Vec<Value> = serde_yaml::Deserializer::from_str(&processed_content)
             .map(Value::deserialize)
-            .collect::<std::result::Result<Vec<_>, _>>()
-            .map_err(|e| UnityAssetError::parse(format!("YAML parsing error: {}", e)))?;
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        // Comments and source layout are matched to documents by position
+        let mut comments = crate::comments::scan_comments(&content);
+        if comments.len() != documents.len() {
+            comments.clear();
+        }
+        let mut layouts = crate::layout::scan_layout(&content);
+        if layouts.len() != documents.len() {
+            layouts.clear();
+        }
 
         // Convert each document to UnityClass
         let mut unity_classes = Vec::new();
         let mut warnings: Vec<SerdeUnityWarning> = Vec::new();
         for (doc_index, document) in documents.iter().enumerate() {
             match self.convert_document_to_unity_class(document, doc_index) {
-                Ok(unity_class) => unity_classes.push(unity_class),
+                Ok(mut unity_class) => {
+                    if let Some(doc_comments) = comments.get_mut(doc_index) {
+                        *unity_class.comments_mut() = std::mem::take(doc_comments);
+                    }
+                    if let Some(layout) = layouts.get(doc_index) {
+                        crate::layout::apply_layout(&mut unity_class, layout);
+                    }
+                    unity_classes.push(unity_class)
+                }
                 Err(e) => {
                     // Best-effort: keep parsing other documents (no stderr logging from library code).
                     warnings.push(SerdeUnityWarning {
@@ -99,7 +116,7 @@ impl SerdeUnityLoader {
         reader
             .read_to_string(&mut content)
             .await
-            .map_err(|e| UnityAssetError::parse(format!("Failed to read input: {}", e)))?;
+            .map_err(|e| UnityAssetError::with_source("Failed to read input", e))?;
 
         // Use the existing string processing logic
         self.load_from_str(&content)
@@ -114,7 +131,7 @@ impl SerdeUnityLoader {
         reader
             .read_to_string(&mut content)
             .await
-            .map_err(|e| UnityAssetError::parse(format!("Failed to read input: {}", e)))?;
+            .map_err(|e| UnityAssetError::with_source("Failed to read input", e))?;
 
         self.load_from_reader_detailed(std::io::Cursor::new(content.into_bytes()))
     }
@@ -123,7 +140,7 @@ impl SerdeUnityLoader {
     fn preprocess_unity_yaml(&self, content: &str) -> Result<String> {
         let mut processed = String::new();
         let mut in_document = false;
-        let mut current_class_info: Option<(i32, String)> = None;
+        let mut current_class_info: Option<(i32, String, String)> = None;
 
         for line in content.lines() {
             let trimmed = line.trim();
@@ -157,12 +174,15 @@ impl SerdeUnityLoader {
                 && !trimmed.starts_with(' ')
                 && trimmed.ends_with(':')
             {
-                if let Some((class_id, anchor)) = &current_class_info {
+                if let Some((class_id, anchor, extra)) = &current_class_info {
                     // Add Unity metadata as special properties
                     let class_name = trimmed.trim_end_matches(':');
                     processed.push_str(&format!("{}:\n", class_name));
                     processed.push_str(&format!("  __unity_class_id__: {}\n", class_id));
                     processed.push_str(&format!("  __unity_anchor__: \"{}\"\n", anchor));
+                    if !extra.is_empty() {
+                        processed.push_str(&format!("  __unity_extra__: \"{}\"\n", extra));
+                    }
                     current_class_info = None;
                 } else {
                     processed.push_str(line);
@@ -179,25 +199,30 @@ impl SerdeUnityLoader {
         Ok(processed)
     }
 
-    /// Parse Unity document header like "--- !u!129 &1"
-    fn parse_unity_document_header(&self, line: &str) -> Option<(i32, String)> {
+    /// Parse Unity document header like "--- !u!129 &1" (or "--- !u!114 &5 stripped")
+    ///
+    /// Returns the class ID, the anchor and any extra anchor data after it.
+    fn parse_unity_document_header(&self, line: &str) -> Option<(i32, String, String)> {
         let parts: Vec<&str> = line.split_whitespace().collect();
 
         let mut class_id = 0;
         let mut anchor = "0".to_string();
+        let mut extra: Vec<&str> = Vec::new();
 
-        for part in parts {
+        for part in parts.into_iter().skip(1) {
             if let Some(stripped) = part.strip_prefix("!u!") {
                 if let Ok(id) = stripped.parse::<i32>() {
                     class_id = id;
                 }
             } else if let Some(stripped) = part.strip_prefix('&') {
                 anchor = stripped.to_string();
+            } else {
+                extra.push(part);
             }
         }
 
         if class_id > 0 {
-            Some((class_id, anchor))
+            Some((class_id, anchor, extra.join(" ")))
         } else {
             None
         }
@@ -210,6 +235,20 @@ impl SerdeUnityLoader {
         doc_index: usize,
     ) -> Result<UnityClass> {
         match document {
+            Value::Mapping(mapping) if mapping.len() > 1 => {
+                // Untagged document with several top-level keys (e.g. a `.meta` file): a plain
+                // mapping rather than one class.
+                let mut unity_class =
+                    UnityClass::new(0, String::new(), format!("doc_{}", doc_index));
+                for (key, value) in mapping {
+                    let key = match key {
+                        Value::String(s) => s.clone(),
+                        other => serde_yaml::to_string(other)?.trim_end().to_string(),
+                    };
+                    unity_class.set(key, Self::convert_value_to_unity_value(value)?);
+                }
+                Ok(unity_class)
+            }
             Value::Mapping(mapping) => {
                 // Look for Unity class structure
                 if let Some((class_key, class_value)) = mapping.iter().next() {
@@ -219,10 +258,11 @@ impl SerdeUnityLoader {
                     };
 
                     // Extract Unity metadata from the class properties
-                    let (class_id, anchor, properties) =
+                    let (class_id, anchor, extra_anchor_data, properties) =
                         if let Value::Mapping(class_props) = class_value {
                             let mut class_id = 0;
                             let mut anchor = format!("doc_{}", doc_index);
+                            let mut extra_anchor_data = String::new();
                             let mut filtered_props = IndexMap::new();
 
                             for (key, value) in class_props {
@@ -240,6 +280,11 @@ impl SerdeUnityLoader {
                                                 anchor = a.clone();
                                             }
                                         }
+                                        "__unity_extra__" => {
+                                            if let Value::String(extra) = value {
+                                                extra_anchor_data = extra.clone();
+                                            }
+                                        }
                                         _ => {
                                             // Regular property
                                             let unity_value =
@@ -250,10 +295,15 @@ impl SerdeUnityLoader {
                                 }
                             }
 
-                            (class_id, anchor, UnityValue::Object(filtered_props))
+                            (
+                                class_id,
+                                anchor,
+                                extra_anchor_data,
+                                UnityValue::Object(filtered_props),
+                            )
                         } else {
                             let properties = Self::convert_value_to_unity_value(class_value)?;
-                            (0, format!("doc_{}", doc_index), properties)
+                            (0, format!("doc_{}", doc_index), String::new(), properties)
                         };
 
                     // Always use the actual class name from YAML - it's more reliable than ID mapping
@@ -261,6 +311,7 @@ impl SerdeUnityLoader {
                     let final_class_name = class_name;
 
                     let mut unity_class = UnityClass::new(class_id, final_class_name, anchor);
+                    unity_class.extra_anchor_data = extra_anchor_data;
 
                     // Add properties
                     if let UnityValue::Object(props) = properties {
@@ -0,0 +1,33 @@
+//! Project settings assets under `ProjectSettings/`.
+//!
+//! Settings files are single-document YAML assets; the typed views themselves live in
+//! `unity_asset_core` so binary players (`globalgamemanagers`) can share them.
+
+use crate::yaml_document::YamlDocument;
+#[cfg(feature = "fs")]
+use std::path::Path;
+#[cfg(feature = "fs")]
+use unity_asset_core::{Result, UnityAssetError};
+use unity_asset_core::{UnityDocument, class_ids};
+
+pub use unity_asset_core::tag_manager::{
+    BUILTIN_TAGS, FIRST_USER_LAYER, LAYER_COUNT, SortingLayer, TagManager, builtin_layer_name,
+};
+
+/// The TagManager object of a loaded document, if it has one.
+pub fn find_tag_manager(document: &YamlDocument) -> Option<TagManager> {
+    document
+        .entries()
+        .iter()
+        .find(|entry| entry.class_id == class_ids::TAG_MANAGER || entry.class_name == "TagManager")
+        .map(|entry| TagManager::from_properties(entry.properties()))
+}
+
+/// Load `ProjectSettings/TagManager.asset`.
+#[cfg(feature = "fs")]
+pub fn load_tag_manager<P: AsRef<Path>>(path: P) -> Result<TagManager> {
+    let path = path.as_ref();
+    let document = YamlDocument::load_yaml(path, false)?;
+    find_tag_manager(&document)
+        .ok_or_else(|| UnityAssetError::format(format!("No TagManager object in {:?}", path)))
+}
@@ -8,8 +8,11 @@
 //! - Proper formatting and line endings
 
 use crate::constants::{LineEnding, UNITY_TAG_URI, UNITY_YAML_VERSION};
+use indexmap::IndexMap;
 use std::fmt::Write;
-use unity_asset_core::{Result, UnityAssetError, UnityClass, UnityValue};
+use unity_asset_core::{
+    Result, UnityAssetError, UnityClass, UnityValue, YamlCollectionStyle, YamlComments, YamlLayout,
+};
 
 /// Unity YAML serializer
 pub struct UnityYamlSerializer {
@@ -21,6 +24,16 @@ pub struct UnityYamlSerializer {
     indent_level: usize,
     /// Whether this is the first document
     first_document: bool,
+    /// Comments of the class being written
+    comments: YamlComments,
+    /// Layout of the class being written
+    layout: YamlLayout,
+    /// Key path of the value being written (`m_Children[2].fileID`)
+    path: String,
+    /// Column reached on the line being written (tracked for wrapping flow collections)
+    column: usize,
+    /// Indentation of wrapped flow collection lines
+    flow_indent: usize,
 }
 
 impl UnityYamlSerializer {
@@ -31,6 +44,11 @@ impl UnityYamlSerializer {
             indent_size: 2,
             indent_level: 0,
             first_document: true,
+            comments: YamlComments::default(),
+            layout: YamlLayout::default(),
+            path: String::new(),
+            column: 0,
+            flow_indent: 0,
         }
     }
 
@@ -55,8 +73,8 @@ impl UnityYamlSerializer {
     ) -> Result<()> {
         self.first_document = true;
 
-        // Write YAML header for first document
-        if !classes.is_empty() {
+        // Write YAML header for first document (plain mappings such as `.meta` files have none)
+        if classes.iter().any(|class| !class.is_plain()) {
             self.write_yaml_header(writer)?;
         }
 
@@ -71,6 +89,17 @@ impl UnityYamlSerializer {
         Ok(())
     }
 
+    /// Serialize a single Unity class as one `--- !u!` document (no `%YAML`/`%TAG` header).
+    ///
+    /// Used when splicing freshly serialized documents into existing YAML text.
+    pub fn serialize_class_to_writer<W: Write>(
+        &mut self,
+        writer: &mut W,
+        class: &UnityClass,
+    ) -> Result<()> {
+        self.serialize_unity_class(writer, class)
+    }
+
     /// Write YAML header (version and tags)
     fn write_yaml_header<W: Write>(&self, writer: &mut W) -> Result<()> {
         // Write YAML version
@@ -101,6 +130,18 @@ impl UnityYamlSerializer {
         writer: &mut W,
         class: &UnityClass,
     ) -> Result<()> {
+        self.comments = class.comments().clone();
+        self.layout = class.layout().clone();
+        self.path.clear();
+        for line in &self.comments.document {
+            write!(writer, "{}{}", line, self.line_ending.as_str())
+                .map_err(|e| UnityAssetError::format(format!("Failed to write comment: {}", e)))?;
+        }
+
+        if class.is_plain() {
+            return self.serialize_plain_mapping(writer, class);
+        }
+
         // Write document separator with Unity tag and anchor
         write!(writer, "--- !u!{} &{}", class.class_id, class.anchor).map_err(|e| {
             UnityAssetError::format(format!("Failed to write document header: {}", e))
@@ -117,283 +158,318 @@ impl UnityYamlSerializer {
             .map_err(|e| UnityAssetError::format(format!("Failed to write line ending: {}", e)))?;
 
         // Write class name and properties
-        write!(writer, "{}:{}", class.class_name, self.line_ending.as_str())
+        write!(writer, "{}:", class.class_name)
             .map_err(|e| UnityAssetError::format(format!("Failed to write class name: {}", e)))?;
+        if let Some(comment) = self.comments.trailing.get("") {
+            write!(writer, " {}", comment)
+                .map_err(|e| UnityAssetError::format(format!("Failed to write comment: {}", e)))?;
+        }
+        write!(writer, "{}", self.line_ending.as_str())
+            .map_err(|e| UnityAssetError::format(format!("Failed to write line ending: {}", e)))?;
 
         // Serialize properties
         self.indent_level = 1;
         for (key, value) in class.properties() {
-            self.serialize_property(writer, key, value)?;
+            self.serialize_property(writer, key, value, false)?;
         }
 
+        self.indent_level = 0;
+        for line in &self.comments.footer {
+            write!(writer, "{}{}", line, self.line_ending.as_str())
+                .map_err(|e| UnityAssetError::format(format!("Failed to write comment: {}", e)))?;
+        }
+        self.comments = YamlComments::default();
+        self.layout = YamlLayout::default();
+
+        Ok(())
+    }
+
+    /// Serialize an untagged top-level mapping: properties at column 0, no `--- !u!` header.
+    fn serialize_plain_mapping<W: Write>(
+        &mut self,
+        writer: &mut W,
+        class: &UnityClass,
+    ) -> Result<()> {
+        if !self.first_document {
+            write!(writer, "---{}", self.line_ending.as_str()).map_err(|e| {
+                UnityAssetError::format(format!("Failed to write document separator: {}", e))
+            })?;
+        }
+
+        self.indent_level = 0;
+        for (key, value) in class.properties() {
+            self.serialize_property(writer, key, value, false)?;
+        }
+
+        for line in &self.comments.footer {
+            write!(writer, "{}{}", line, self.line_ending.as_str())
+                .map_err(|e| UnityAssetError::format(format!("Failed to write comment: {}", e)))?;
+        }
+        self.comments = YamlComments::default();
+        self.layout = YamlLayout::default();
+
         Ok(())
     }
 
     /// Serialize a property key-value pair
+    ///
+    /// `after_dash` writes the key right after a sequence item's `- ` (no indentation).
     fn serialize_property<W: Write>(
         &mut self,
         writer: &mut W,
         key: &str,
         value: &UnityValue,
+        after_dash: bool,
     ) -> Result<()> {
-        // Write indentation
-        self.write_indent(writer)?;
+        let path = if self.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", self.path, key)
+        };
+        let parent = std::mem::replace(&mut self.path, path);
+        if !after_dash {
+            self.write_leading_comments(writer)?;
+            self.write_indent(writer)?;
+        }
 
         // Write property key
-        write!(writer, "{}: ", key)
+        write!(writer, "{}:", key)
             .map_err(|e| UnityAssetError::format(format!("Failed to write property key: {}", e)))?;
+        self.column = self.indent_level * self.indent_size + key.chars().count() + 1;
 
         // Write property value
-        self.serialize_value(writer, value, false)?;
+        self.serialize_commented_value(writer, value)?;
 
+        self.path = parent;
         Ok(())
     }
 
-    /// Serialize a Unity value
-    fn serialize_value<W: Write>(
+    /// Write the full-line comments recorded before the current path.
+    fn write_leading_comments<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let Some(lines) = self.comments.leading.get(&self.path) else {
+            return Ok(());
+        };
+        for line in lines {
+            self.write_indent(writer)?;
+            write!(writer, "{}{}", line, self.line_ending.as_str())
+                .map_err(|e| UnityAssetError::format(format!("Failed to write comment: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    /// Serialize a value, appending the current path's trailing comment to its first line.
+    fn serialize_commented_value<W: Write>(
         &mut self,
         writer: &mut W,
         value: &UnityValue,
-        inline: bool,
     ) -> Result<()> {
-        match value {
-            UnityValue::Null => {
-                write!(writer, "{{fileID: 0}}{}", self.line_ending.as_str()).map_err(|e| {
-                    UnityAssetError::format(format!("Failed to write null value: {}", e))
-                })?;
-            }
-            UnityValue::Bool(b) => {
-                write!(
-                    writer,
-                    "{}{}",
-                    if *b { "1" } else { "0" },
-                    self.line_ending.as_str()
-                )
-                .map_err(|e| {
-                    UnityAssetError::format(format!("Failed to write bool value: {}", e))
-                })?;
-            }
-            UnityValue::Integer(i) => {
-                write!(writer, "{}{}", i, self.line_ending.as_str()).map_err(|e| {
-                    UnityAssetError::format(format!("Failed to write integer value: {}", e))
-                })?;
-            }
-            UnityValue::Float(f) => {
-                write!(writer, "{}{}", f, self.line_ending.as_str()).map_err(|e| {
-                    UnityAssetError::format(format!("Failed to write float value: {}", e))
-                })?;
-            }
-            UnityValue::String(s) => {
-                // Handle string quoting based on content
-                if self.needs_quoting(s) {
-                    write!(
-                        writer,
-                        "\"{}\"{}",
-                        self.escape_string(s),
-                        self.line_ending.as_str()
-                    )
-                } else {
-                    write!(writer, "{}{}", s, self.line_ending.as_str())
-                }
-                .map_err(|e| {
-                    UnityAssetError::format(format!("Failed to write string value: {}", e))
-                })?;
+        let Some(comment) = self.comments.trailing.get(&self.path).cloned() else {
+            return self.serialize_value(writer, value);
+        };
+
+        let mut buffer = String::new();
+        self.serialize_value(&mut buffer, value)?;
+        let split = buffer.find(['\r', '\n']).unwrap_or(buffer.len());
+        let (first, rest) = buffer.split_at(split);
+        if first.trim().is_empty() {
+            write!(writer, " {}{}", comment, rest)
+        } else {
+            write!(writer, "{} {}{}", first, comment, rest)
+        }
+        .map_err(|e| UnityAssetError::format(format!("Failed to write comment: {}", e)))
+    }
+
+    /// Serialize a value following its `key:` or `-`
+    ///
+    /// Scalars and flow collections go on the same line after a space; block collections start
+    /// on the next line, with sequence items at the key's own indentation as Unity writes them.
+    fn serialize_value<W: Write>(&mut self, writer: &mut W, value: &UnityValue) -> Result<()> {
+        match self.collection_style(value) {
+            None | Some(YamlCollectionStyle::Flow) => {
+                write_text(writer, " ", "value")?;
+                self.column += 1;
+                self.flow_indent = self.indent_level * self.indent_size;
+                self.serialize_flow(writer, value)?;
+                write_text(writer, self.line_ending.as_str(), "line ending")
             }
-            UnityValue::Array(arr) => {
-                if arr.is_empty() {
-                    write!(writer, "[]{}", self.line_ending.as_str()).map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write empty array: {}", e))
-                    })?;
-                } else if inline || self.is_simple_array(arr) {
-                    // Write inline array
-                    write!(writer, "[").map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write array start: {}", e))
-                    })?;
-                    for (i, item) in arr.iter().enumerate() {
-                        if i > 0 {
-                            write!(writer, ", ").map_err(|e| {
-                                UnityAssetError::format(format!(
-                                    "Failed to write array separator: {}",
-                                    e
-                                ))
-                            })?;
+            Some(style) => {
+                write_text(writer, self.line_ending.as_str(), "line ending")?;
+                let indented = style == YamlCollectionStyle::IndentedBlock;
+                match value {
+                    UnityValue::Object(obj) => {
+                        self.indent_level += 1;
+                        for (key, value) in obj {
+                            self.serialize_property(writer, key, value, false)?;
                         }
-                        self.serialize_value_inline(writer, item)?;
+                        self.indent_level -= 1;
+                        Ok(())
                     }
-                    write!(writer, "]{}", self.line_ending.as_str()).map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write inline array end: {}", e))
-                    })?;
-                } else {
-                    // Write block array
-                    write!(writer, "{}", self.line_ending.as_str()).map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write array start: {}", e))
-                    })?;
-                    self.indent_level += 1;
-                    for item in arr {
-                        self.write_indent(writer)?;
-                        write!(writer, "- ").map_err(|e| {
-                            UnityAssetError::format(format!(
-                                "Failed to write array item prefix: {}",
-                                e
-                            ))
-                        })?;
-                        match item {
-                            UnityValue::Array(inner)
-                                if !inner.is_empty() && !self.is_simple_array(inner) =>
-                            {
-                                self.serialize_value(writer, item, false)?
-                            }
-                            UnityValue::Object(inner)
-                                if !inner.is_empty() && !self.is_simple_object(inner) =>
-                            {
-                                self.serialize_value(writer, item, false)?
-                            }
-                            _ => self.serialize_value(writer, item, true)?,
-                        }
+                    UnityValue::Array(items) => self.serialize_items(writer, items, indented),
+                    UnityValue::Bytes(bytes) => {
+                        let items: Vec<UnityValue> = bytes
+                            .iter()
+                            .map(|b| UnityValue::Integer(*b as i64))
+                            .collect();
+                        self.serialize_items(writer, &items, indented)
                     }
-                    self.indent_level -= 1;
+                    _ => unreachable!("scalars have no collection style"),
                 }
             }
-            UnityValue::Bytes(b) => {
-                if b.is_empty() {
-                    write!(writer, "[]{}", self.line_ending.as_str()).map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write empty bytes: {}", e))
-                    })?;
-                } else if inline || b.len() <= 64 {
-                    write!(writer, "[").map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write bytes start: {}", e))
-                    })?;
-                    for (i, item) in b.iter().enumerate() {
-                        if i > 0 {
-                            write!(writer, ", ").map_err(|e| {
-                                UnityAssetError::format(format!(
-                                    "Failed to write bytes separator: {}",
-                                    e
-                                ))
-                            })?;
-                        }
-                        write!(writer, "{}", item).map_err(|e| {
-                            UnityAssetError::format(format!("Failed to write byte value: {}", e))
-                        })?;
-                    }
-                    write!(writer, "]{}", self.line_ending.as_str()).map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write bytes end: {}", e))
-                    })?;
-                } else {
-                    write!(writer, "{}", self.line_ending.as_str()).map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write bytes start: {}", e))
-                    })?;
+        }
+    }
+
+    /// Write block sequence items (`- value`), one per line
+    fn serialize_items<W: Write>(
+        &mut self,
+        writer: &mut W,
+        items: &[UnityValue],
+        indented: bool,
+    ) -> Result<()> {
+        if indented {
+            self.indent_level += 1;
+        }
+        for (index, item) in items.iter().enumerate() {
+            let item_path = format!("{}[{}]", self.path, index);
+            let parent = std::mem::replace(&mut self.path, item_path);
+            self.write_leading_comments(writer)?;
+            self.write_indent(writer)?;
+            write_text(writer, "-", "array item prefix")?;
+            self.column = self.indent_level * self.indent_size + 1;
+
+            let block = matches!(
+                self.collection_style(item),
+                Some(YamlCollectionStyle::Block | YamlCollectionStyle::IndentedBlock)
+            );
+            match item {
+                // The first key shares the `- ` line; the rest line up under it.
+                UnityValue::Object(obj) if block => {
+                    write_text(writer, " ", "array item prefix")?;
                     self.indent_level += 1;
-                    for item in b {
-                        self.write_indent(writer)?;
-                        write!(writer, "- {}", item).map_err(|e| {
-                            UnityAssetError::format(format!(
-                                "Failed to write bytes item prefix: {}",
-                                e
-                            ))
-                        })?;
-                        write!(writer, "{}", self.line_ending.as_str()).map_err(|e| {
-                            UnityAssetError::format(format!(
-                                "Failed to write bytes line ending: {}",
-                                e
-                            ))
-                        })?;
+                    for (position, (key, value)) in obj.iter().enumerate() {
+                        self.serialize_property(writer, key, value, position == 0)?;
                     }
                     self.indent_level -= 1;
                 }
-            }
-            UnityValue::Object(obj) => {
-                if obj.is_empty() {
-                    write!(writer, "{{}}{}", self.line_ending.as_str()).map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write empty object: {}", e))
-                    })?;
-                } else if inline || self.is_simple_object(obj) {
-                    // Write inline object
-                    write!(writer, "{{").map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write object start: {}", e))
-                    })?;
-                    for (i, (key, value)) in obj.iter().enumerate() {
-                        if i > 0 {
-                            write!(writer, ", ").map_err(|e| {
-                                UnityAssetError::format(format!(
-                                    "Failed to write object separator: {}",
-                                    e
-                                ))
-                            })?;
-                        }
-                        write!(writer, "{}: ", key).map_err(|e| {
-                            UnityAssetError::format(format!("Failed to write object key: {}", e))
-                        })?;
-                        self.serialize_value_inline(writer, value)?;
-                    }
-                    write!(writer, "}}{}", self.line_ending.as_str()).map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write inline object end: {}", e))
-                    })?;
-                } else {
-                    // Write block object
-                    write!(writer, "{}", self.line_ending.as_str()).map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write object start: {}", e))
-                    })?;
+                UnityValue::Array(_) | UnityValue::Bytes(_) if block => {
                     self.indent_level += 1;
-                    for (key, value) in obj {
-                        self.serialize_property(writer, key, value)?;
-                    }
+                    self.serialize_commented_value(writer, item)?;
                     self.indent_level -= 1;
                 }
+                _ => self.serialize_commented_value(writer, item)?,
             }
+            self.path = parent;
+        }
+        if indented {
+            self.indent_level -= 1;
         }
         Ok(())
     }
 
-    /// Serialize a value inline (for arrays and objects)
-    fn serialize_value_inline<W: Write>(&self, writer: &mut W, value: &UnityValue) -> Result<()> {
+    /// Write a scalar or a flow collection (`{x: 0, y: 0}`, `[1, 2]`) on the current line
+    ///
+    /// Like Unity, a line that has grown past 80 columns is broken after a `,`.
+    fn serialize_flow<W: Write>(&mut self, writer: &mut W, value: &UnityValue) -> Result<()> {
         match value {
-            UnityValue::Null => {
-                write!(writer, "{{fileID: 0}}").map_err(|e| {
-                    UnityAssetError::format(format!("Failed to write null value: {}", e))
-                })?;
-            }
-            UnityValue::Bool(b) => {
-                write!(writer, "{}", if *b { "1" } else { "0" }).map_err(|e| {
-                    UnityAssetError::format(format!("Failed to write bool value: {}", e))
-                })?;
-            }
-            UnityValue::Integer(i) => {
-                write!(writer, "{}", i).map_err(|e| {
-                    UnityAssetError::format(format!("Failed to write integer value: {}", e))
-                })?;
-            }
-            UnityValue::Float(f) => {
-                write!(writer, "{}", f).map_err(|e| {
-                    UnityAssetError::format(format!("Failed to write float value: {}", e))
-                })?;
+            UnityValue::Object(obj) => {
+                self.write_flow_text(writer, "{")?;
+                self.flow_indent += self.indent_size;
+                for (index, (key, value)) in obj.iter().enumerate() {
+                    if index > 0 {
+                        self.write_flow_separator(writer)?;
+                    }
+                    self.write_flow_text(writer, &format!("{}: ", key))?;
+                    let entry_path = format!("{}.{}", self.path, key);
+                    let parent = std::mem::replace(&mut self.path, entry_path);
+                    self.serialize_flow(writer, value)?;
+                    self.path = parent;
+                }
+                self.flow_indent -= self.indent_size;
+                self.write_flow_text(writer, "}")
             }
-            UnityValue::String(s) => {
-                if self.needs_quoting(s) {
-                    write!(writer, "\"{}\"", self.escape_string(s)).map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write quoted string: {}", e))
-                    })?;
-                } else {
-                    write!(writer, "{}", s).map_err(|e| {
-                        UnityAssetError::format(format!("Failed to write string: {}", e))
-                    })?;
+            UnityValue::Array(items) => {
+                self.write_flow_text(writer, "[")?;
+                self.flow_indent += self.indent_size;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        self.write_flow_separator(writer)?;
+                    }
+                    let item_path = format!("{}[{}]", self.path, index);
+                    let parent = std::mem::replace(&mut self.path, item_path);
+                    self.serialize_flow(writer, item)?;
+                    self.path = parent;
                 }
+                self.flow_indent -= self.indent_size;
+                self.write_flow_text(writer, "]")
             }
-            UnityValue::Bytes(b) => {
-                write!(writer, "<bytes len={}>", b.len()).map_err(|e| {
-                    UnityAssetError::format(format!("Failed to write bytes: {}", e))
-                })?;
+            UnityValue::Bytes(bytes) => {
+                let items: Vec<UnityValue> = bytes
+                    .iter()
+                    .map(|b| UnityValue::Integer(*b as i64))
+                    .collect();
+                self.serialize_flow(writer, &UnityValue::Array(items))
             }
-            UnityValue::Array(_) | UnityValue::Object(_) => {
-                // For complex nested structures, we might need more sophisticated handling
-                write!(writer, "{{...}}").map_err(|e| {
-                    UnityAssetError::format(format!("Failed to write complex value: {}", e))
-                })?;
+            scalar => {
+                let text = match self.layout.scalars.get(&self.path) {
+                    Some((text, parsed)) if parsed == scalar => {
+                        text.replace('\n', self.line_ending.as_str())
+                    }
+                    _ => plain_scalar(scalar).unwrap_or_default(),
+                };
+                self.write_flow_text(writer, &text)
             }
         }
+    }
+
+    /// Write `,` and then a space, or a line break once the line is past 80 columns
+    fn write_flow_separator<W: Write>(&mut self, writer: &mut W) -> Result<()> {
+        self.write_flow_text(writer, ",")?;
+        if self.column > FLOW_LINE_WIDTH {
+            write_text(writer, self.line_ending.as_str(), "line ending")?;
+            let indent = " ".repeat(self.flow_indent);
+            self.column = 0;
+            self.write_flow_text(writer, &indent)
+        } else {
+            self.write_flow_text(writer, " ")
+        }
+    }
+
+    /// Write text on the current line, keeping track of the column
+    fn write_flow_text<W: Write>(&mut self, writer: &mut W, text: &str) -> Result<()> {
+        write_text(writer, text, "value")?;
+        match text.rfind('\n') {
+            Some(newline) => self.column = text[newline + 1..].chars().count(),
+            None => self.column += text.chars().count(),
+        }
         Ok(())
     }
 
+    /// Layout of a collection at the current path; `None` for scalars
+    ///
+    /// The source file's style wins over the default, and comments inside a collection force
+    /// block style since flow collections cannot hold them.
+    fn collection_style(&self, value: &UnityValue) -> Option<YamlCollectionStyle> {
+        let default = default_style(value)?;
+        let style = self
+            .layout
+            .styles
+            .get(&self.path)
+            .copied()
+            .unwrap_or(default);
+        let empty = match value {
+            UnityValue::Object(obj) => obj.is_empty(),
+            UnityValue::Array(items) => items.is_empty(),
+            UnityValue::Bytes(bytes) => bytes.is_empty(),
+            _ => false,
+        };
+        Some(if empty {
+            YamlCollectionStyle::Flow
+        } else if style == YamlCollectionStyle::Flow && self.has_nested_comments() {
+            YamlCollectionStyle::Block
+        } else {
+            style
+        })
+    }
+
     /// Write indentation
     fn write_indent<W: Write>(&self, writer: &mut W) -> Result<()> {
         for _ in 0..(self.indent_level * self.indent_size) {
@@ -404,50 +480,113 @@ impl UnityYamlSerializer {
         Ok(())
     }
 
-    /// Check if a string needs quoting
-    fn needs_quoting(&self, s: &str) -> bool {
-        s.is_empty()
-            || s.contains('\n')
-            || s.contains('\r')
-            || s.contains('"')
-            || s.contains('\'')
-            || s.contains(':')
-            || s.contains('[')
-            || s.contains(']')
-            || s.contains('{')
-            || s.contains('}')
-            || s.starts_with(' ')
-            || s.ends_with(' ')
+    /// Whether comments were recorded below the current path (they need block layout).
+    fn has_nested_comments(&self) -> bool {
+        let nested = |key: &String| {
+            key.strip_prefix(self.path.as_str())
+                .is_some_and(|rest| rest.starts_with(['.', '[']))
+        };
+        self.comments.leading.keys().any(nested) || self.comments.trailing.keys().any(nested)
     }
+}
 
-    /// Escape a string for YAML
-    fn escape_string(&self, s: &str) -> String {
-        s.replace('\\', "\\\\")
-            .replace('"', "\\\"")
-            .replace('\n', "\\n")
-            .replace('\r', "\\r")
-            .replace('\t', "\\t")
-    }
+/// Column past which Unity breaks flow collections onto the next line
+const FLOW_LINE_WIDTH: usize = 80;
 
-    /// Check if an array should be written inline
-    fn is_simple_array(&self, arr: &[UnityValue]) -> bool {
-        arr.len() <= 3
-            && arr.iter().all(|v| match v {
-                UnityValue::Integer(_) | UnityValue::Float(_) | UnityValue::Bool(_) => true,
-                UnityValue::String(s) => s.len() < 20,
-                _ => false,
-            })
-    }
+/// Mappings Unity writes in flow style: vectors, quaternions, colors and object references.
+const FLOW_MAPPING_KEYS: &[&[&str]] = &[
+    &["x", "y"],
+    &["x", "y", "z"],
+    &["x", "y", "z", "w"],
+    &["r", "g", "b", "a"],
+    &["fileID"],
+    &["fileID", "guid", "type"],
+];
 
-    /// Check if an object should be written inline
-    fn is_simple_object(&self, obj: &indexmap::IndexMap<String, UnityValue>) -> bool {
-        obj.len() <= 3
-            && obj.values().all(|v| match v {
-                UnityValue::Integer(_) | UnityValue::Float(_) | UnityValue::Bool(_) => true,
-                UnityValue::String(s) => s.len() < 20,
-                _ => false,
-            })
-    }
+/// How Unity lays out a collection when nothing else is known; `None` for scalars.
+///
+/// Sequences are block style, and so are mappings except the small value types listed in
+/// [`FLOW_MAPPING_KEYS`]; empty collections are `[]` / `{}`.
+pub(crate) fn default_style(value: &UnityValue) -> Option<YamlCollectionStyle> {
+    let style = match value {
+        UnityValue::Array(items) if items.is_empty() => YamlCollectionStyle::Flow,
+        UnityValue::Array(_) => YamlCollectionStyle::Block,
+        UnityValue::Bytes(bytes) if bytes.len() <= 64 => YamlCollectionStyle::Flow,
+        UnityValue::Bytes(_) => YamlCollectionStyle::Block,
+        UnityValue::Object(obj) if obj.is_empty() || is_flow_mapping(obj) => {
+            YamlCollectionStyle::Flow
+        }
+        UnityValue::Object(_) => YamlCollectionStyle::Block,
+        _ => return None,
+    };
+    Some(style)
+}
+
+fn is_flow_mapping(obj: &IndexMap<String, UnityValue>) -> bool {
+    let scalars = obj.values().all(|v| {
+        !matches!(
+            v,
+            UnityValue::Array(_) | UnityValue::Object(_) | UnityValue::Bytes(_)
+        )
+    });
+    scalars
+        && FLOW_MAPPING_KEYS
+            .iter()
+            .any(|keys| keys.len() == obj.len() && keys.iter().all(|key| obj.contains_key(*key)))
+}
+
+/// A scalar as the serializer writes it when the source text is unknown; `None` for collections.
+///
+/// Booleans are `0`/`1`, null is a null object reference (`{fileID: 0}`) and empty strings are
+/// written as nothing at all (`m_Name: `), like Unity does.
+pub(crate) fn plain_scalar(value: &UnityValue) -> Option<String> {
+    let text = match value {
+        UnityValue::Null => "{fileID: 0}".to_string(),
+        UnityValue::Bool(b) => if *b { "1" } else { "0" }.to_string(),
+        UnityValue::Integer(i) => i.to_string(),
+        UnityValue::Float(f) => f.to_string(),
+        // Unity single-quotes strings unless they need escapes.
+        UnityValue::String(s) if needs_quoting(s) && s.chars().any(char::is_control) => {
+            format!("\"{}\"", escape_string(s))
+        }
+        UnityValue::String(s) if needs_quoting(s) => format!("'{}'", s.replace('\'', "''")),
+        UnityValue::String(s) => s.clone(),
+        UnityValue::Array(_) | UnityValue::Bytes(_) | UnityValue::Object(_) => return None,
+    };
+    Some(text)
+}
+
+/// Check if a string needs quoting
+fn needs_quoting(s: &str) -> bool {
+    s.contains('\n')
+        || s.contains('\r')
+        || s.contains('"')
+        || s.contains('\'')
+        || s.contains(':')
+        || s.contains('[')
+        || s.contains(']')
+        || s.contains('{')
+        || s.contains('}')
+        || s.contains(" #")
+        || s.starts_with(['#', '&', '*', '!', '|', '>', '%', '@', '`', ','])
+        || s.starts_with("- ")
+        || s.starts_with(' ')
+        || s.ends_with(' ')
+}
+
+/// Escape a string for YAML
+fn escape_string(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t")
+}
+
+fn write_text<W: Write>(writer: &mut W, text: &str, what: &str) -> Result<()> {
+    writer
+        .write_str(text)
+        .map_err(|e| UnityAssetError::format(format!("Failed to write {}: {}", what, e)))
 }
 
 impl Default for UnityYamlSerializer {
@@ -0,0 +1,348 @@
+//! `serializedVersion`-aware layout upgrades for Unity YAML objects
+//!
+//! Unity bumps a class's `serializedVersion` whenever its YAML layout changes (fields added,
+//! renamed or restructured). Consumers reading scenes written by older editors have to cope with
+//! both shapes; an upgrader rewrites one version's layout into the next so downstream code only
+//! needs to understand the latest one.
+//!
+//! Each upgrader is registered for a `(class_name, from_version)` pair and moves an object from
+//! `from_version` to `from_version + 1`. [`upgrade_class`] chains them until no upgrader matches
+//! and bumps `serializedVersion` after every step, so applying it twice is a no-op.
+//!
+//! Built-in upgraders cover a few well-known transitions for `Transform`, `MeshRenderer` and
+//! `PlayerSettings`; they are not an exhaustive history of Unity's layouts.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use unity_asset_core::{UnityClass, UnityValue};
+//! use unity_asset_yaml::upgraders;
+//!
+//! fn add_flag(class: &mut UnityClass) {
+//!     class.set("m_MyFlag".to_string(), UnityValue::Integer(0));
+//! }
+//!
+//! upgraders::register("MyComponent", 1, add_flag);
+//!
+//! let mut class = UnityClass::new(114, "MyComponent".to_string(), "1".to_string());
+//! assert_eq!(upgraders::upgrade_class(&mut class), 1);
+//! assert_eq!(upgraders::serialized_version(&class), 2);
+//! ```
+
+use std::sync::{LazyLock, RwLock};
+use unity_asset_core::{UnityClass, UnityValue};
+
+/// Property key holding an object's layout version.
+pub const SERIALIZED_VERSION_KEY: &str = "serializedVersion";
+
+/// Function rewriting an object from one `serializedVersion` layout to the next.
+///
+/// The function must not touch `serializedVersion` itself; the framework bumps it.
+pub type UpgradeFn = fn(&mut UnityClass);
+
+/// A registered upgrade step.
+#[derive(Debug, Clone)]
+pub struct Upgrader {
+    /// Class name the step applies to (e.g. `Transform`).
+    pub class_name: String,
+    /// `serializedVersion` the step upgrades from; the result is `from_version + 1`.
+    pub from_version: i64,
+    /// The rewrite itself.
+    pub upgrade: UpgradeFn,
+}
+
+static REGISTRY: LazyLock<RwLock<Vec<Upgrader>>> =
+    LazyLock::new(|| RwLock::new(builtin_upgraders()));
+
+/// Register (or replace) the upgrader for `class_name` at `from_version`.
+pub fn register(class_name: &str, from_version: i64, upgrade: UpgradeFn) {
+    let mut registry = match REGISTRY.write() {
+        Ok(v) => v,
+        Err(e) => e.into_inner(),
+    };
+    registry.retain(|u| !(u.class_name == class_name && u.from_version == from_version));
+    registry.push(Upgrader {
+        class_name: class_name.to_string(),
+        from_version,
+        upgrade,
+    });
+}
+
+/// Whether any upgrader is registered for `class_name`.
+pub fn has_upgraders(class_name: &str) -> bool {
+    let registry = match REGISTRY.read() {
+        Ok(v) => v,
+        Err(e) => e.into_inner(),
+    };
+    registry.iter().any(|u| u.class_name == class_name)
+}
+
+/// Snapshot of all registered upgraders, ordered by class name and `from_version`.
+pub fn registered() -> Vec<Upgrader> {
+    let registry = match REGISTRY.read() {
+        Ok(v) => v,
+        Err(e) => e.into_inner(),
+    };
+    let mut out = registry.clone();
+    out.sort_by(|a, b| {
+        a.class_name
+            .cmp(&b.class_name)
+            .then(a.from_version.cmp(&b.from_version))
+    });
+    out
+}
+
+/// Read an object's `serializedVersion`.
+///
+/// Unity omits the key for classes that never changed their layout, which is equivalent to 1.
+pub fn serialized_version(class: &UnityClass) -> i64 {
+    class
+        .get(SERIALIZED_VERSION_KEY)
+        .and_then(|v| match v {
+            UnityValue::Integer(i) => Some(*i),
+            UnityValue::String(s) => s.trim().parse().ok(),
+            _ => None,
+        })
+        .unwrap_or(1)
+}
+
+/// Whether an upgrade step is registered for `class` at its current `serializedVersion`.
+pub fn needs_upgrade(class: &UnityClass) -> bool {
+    let registry = match REGISTRY.read() {
+        Ok(v) => v,
+        Err(e) => e.into_inner(),
+    };
+    let version = serialized_version(class);
+    registry
+        .iter()
+        .any(|u| u.class_name == class.class_name && u.from_version == version)
+}
+
+/// Apply every matching upgrade step to `class`, returning how many steps ran.
+pub fn upgrade_class(class: &mut UnityClass) -> usize {
+    let registry = match REGISTRY.read() {
+        Ok(v) => v.clone(),
+        Err(e) => e.into_inner().clone(),
+    };
+
+    let mut steps = 0usize;
+    loop {
+        let version = serialized_version(class);
+        let Some(step) = registry
+            .iter()
+            .find(|u| u.class_name == class.class_name && u.from_version == version)
+        else {
+            break;
+        };
+        (step.upgrade)(class);
+        set_serialized_version(class, version + 1);
+        steps += 1;
+    }
+    steps
+}
+
+/// Set `serializedVersion`, keeping its position or placing it where Unity writes it.
+fn set_serialized_version(class: &mut UnityClass, version: i64) {
+    let props = class.properties_mut();
+    if let Some(value) = props.get_mut(SERIALIZED_VERSION_KEY) {
+        *value = UnityValue::Integer(version);
+        return;
+    }
+    let index = props
+        .get_index_of("m_GameObject")
+        .or_else(|| props.get_index_of("m_PrefabAsset"))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    props.shift_insert(
+        index,
+        SERIALIZED_VERSION_KEY.to_string(),
+        UnityValue::Integer(version),
+    );
+}
+
+/// Insert `key` right after `after` (or at the end) unless it already exists.
+fn insert_after(class: &mut UnityClass, after: &str, key: &str, value: UnityValue) {
+    let props = class.properties_mut();
+    if props.contains_key(key) {
+        return;
+    }
+    match props.get_index_of(after) {
+        Some(i) => {
+            props.shift_insert(i + 1, key.to_string(), value);
+        }
+        None => {
+            props.insert(key.to_string(), value);
+        }
+    }
+}
+
+fn builtin_upgraders() -> Vec<Upgrader> {
+    vec![
+        Upgrader {
+            class_name: "Transform".to_string(),
+            from_version: 1,
+            upgrade: upgrade_transform_v1,
+        },
+        Upgrader {
+            class_name: "MeshRenderer".to_string(),
+            from_version: 1,
+            upgrade: upgrade_mesh_renderer_v1,
+        },
+        Upgrader {
+            class_name: "PlayerSettings".to_string(),
+            from_version: 10,
+            upgrade: upgrade_player_settings_v10,
+        },
+    ]
+}
+
+/// Transform 1 -> 2: `m_ConstrainProportionsScale` was added, and editors that predate
+/// `m_LocalEulerAnglesHint` get one derived from `m_LocalRotation`.
+fn upgrade_transform_v1(class: &mut UnityClass) {
+    if !class.has_property("m_LocalEulerAnglesHint") {
+        let hint = class
+            .get("m_LocalRotation")
+            .and_then(quaternion_to_euler_degrees)
+            .unwrap_or([0.0; 3]);
+        class.set(
+            "m_LocalEulerAnglesHint".to_string(),
+            vector3_value(hint[0], hint[1], hint[2]),
+        );
+    }
+    insert_after(
+        class,
+        "m_LocalScale",
+        "m_ConstrainProportionsScale",
+        UnityValue::Integer(0),
+    );
+}
+
+/// MeshRenderer 1 -> 2: the `m_UseLightProbes` toggle became the `m_LightProbeUsage` enum,
+/// and `m_RenderingLayerMask` was introduced (defaulting to layer 1).
+fn upgrade_mesh_renderer_v1(class: &mut UnityClass) {
+    if let Some(old) = class.properties_mut().shift_remove("m_UseLightProbes") {
+        let usage = match old {
+            UnityValue::Bool(b) => i64::from(b),
+            UnityValue::Integer(i) => i64::from(i != 0),
+            _ => 1,
+        };
+        insert_after(
+            class,
+            "m_ReceiveShadows",
+            "m_LightProbeUsage",
+            UnityValue::Integer(usage),
+        );
+    }
+    insert_after(
+        class,
+        "m_LightProbeUsage",
+        "m_RenderingLayerMask",
+        UnityValue::Integer(1),
+    );
+}
+
+/// PlayerSettings 10 -> 11: per-platform bundle identifiers moved from the flat
+/// `iPhoneBundleIdentifier` key into the `applicationIdentifier` map.
+fn upgrade_player_settings_v10(class: &mut UnityClass) {
+    let Some(bundle_id) = class
+        .properties_mut()
+        .shift_remove("iPhoneBundleIdentifier")
+    else {
+        return;
+    };
+
+    let mut identifiers = match class.properties_mut().shift_remove("applicationIdentifier") {
+        Some(UnityValue::Object(map)) => map,
+        _ => indexmap::IndexMap::new(),
+    };
+    for platform in ["Android", "Standalone", "iPhone"] {
+        identifiers
+            .entry(platform.to_string())
+            .or_insert_with(|| bundle_id.clone());
+    }
+    class.set(
+        "applicationIdentifier".to_string(),
+        UnityValue::Object(identifiers),
+    );
+}
+
+fn vector3_value(x: f64, y: f64, z: f64) -> UnityValue {
+    let mut map = indexmap::IndexMap::new();
+    map.insert("x".to_string(), UnityValue::Float(x));
+    map.insert("y".to_string(), UnityValue::Float(y));
+    map.insert("z".to_string(), UnityValue::Float(z));
+    UnityValue::Object(map)
+}
+
+/// Convert a `{x, y, z, w}` quaternion to Unity's ZXY Euler angles in degrees.
+fn quaternion_to_euler_degrees(value: &UnityValue) -> Option<[f64; 3]> {
+    let UnityValue::Object(q) = value else {
+        return None;
+    };
+    let get = |k: &str| q.get(k).and_then(UnityValue::as_f64);
+    let (x, y, z, w) = (get("x")?, get("y")?, get("z")?, get("w")?);
+
+    let sin_x = (2.0 * (w * x - y * z)).clamp(-1.0, 1.0);
+    let ex = sin_x.asin();
+    let ey = (2.0 * (w * y + x * z)).atan2(1.0 - 2.0 * (x * x + y * y));
+    let ez = (2.0 * (w * z + x * y)).atan2(1.0 - 2.0 * (x * x + z * z));
+
+    let round = |rad: f64| {
+        let deg = rad.to_degrees();
+        let rounded = (deg * 1000.0).round() / 1000.0;
+        if rounded == 0.0 { 0.0 } else { rounded }
+    };
+    Some([round(ex), round(ey), round(ez)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_serialized_version_defaults_to_one() {
+        let class = UnityClass::new(4, "Transform".to_string(), "1".to_string());
+        assert_eq!(serialized_version(&class), 1);
+    }
+
+    #[test]
+    fn test_quaternion_to_euler() {
+        let mut q = indexmap::IndexMap::new();
+        q.insert("x".to_string(), UnityValue::Float(0.0));
+        q.insert(
+            "y".to_string(),
+            UnityValue::Float(std::f64::consts::FRAC_1_SQRT_2),
+        );
+        q.insert("z".to_string(), UnityValue::Float(0.0));
+        q.insert(
+            "w".to_string(),
+            UnityValue::Float(std::f64::consts::FRAC_1_SQRT_2),
+        );
+        let euler = quaternion_to_euler_degrees(&UnityValue::Object(q)).unwrap();
+        assert_eq!(euler, [0.0, 90.0, 0.0]);
+    }
+
+    #[test]
+    fn test_register_replaces_existing_step() {
+        fn first(class: &mut UnityClass) {
+            class.set("a".to_string(), UnityValue::Integer(1));
+        }
+        fn second(class: &mut UnityClass) {
+            class.set("b".to_string(), UnityValue::Integer(2));
+        }
+
+        register("UpgraderReplaceTest", 3, first);
+        register("UpgraderReplaceTest", 3, second);
+        let steps: Vec<_> = registered()
+            .into_iter()
+            .filter(|u| u.class_name == "UpgraderReplaceTest")
+            .collect();
+        assert_eq!(steps.len(), 1);
+
+        let mut class = UnityClass::new(0, "UpgraderReplaceTest".to_string(), "1".to_string());
+        class.set(SERIALIZED_VERSION_KEY.to_string(), UnityValue::Integer(3));
+        assert_eq!(upgrade_class(&mut class), 1);
+        assert!(class.has_property("b"));
+        assert!(!class.has_property("a"));
+    }
+}
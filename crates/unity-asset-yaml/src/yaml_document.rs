@@ -3,12 +3,19 @@
 //! This module provides the concrete implementation of UnityDocument
 //! for YAML format files.
 
+use crate::serde_unity_loader::SerdeUnityLoader;
 use crate::unity_yaml_serializer::UnityYamlSerializer;
+use indexmap::IndexMap;
+use std::collections::HashMap;
+#[cfg(feature = "fs")]
 use std::fs;
+use std::ops::Range;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
 use unity_asset_core::{
-    DocumentFormat, LineEnding, Result, UnityAssetError, UnityClass, UnityDocument,
-    document::DocumentMetadata,
+    DocumentFormat, LineEnding, Result, UnityAssetError, UnityClass, UnityDocument, UnityValue,
+    UnityVersion, document::DocumentMetadata,
 };
 
 #[cfg(feature = "async")]
@@ -20,11 +27,194 @@ use unity_asset_core::document::AsyncUnityDocument;
 #[derive(Debug, Clone)]
 pub struct YamlDocument {
     /// The Unity objects in this document
+    ///
+    /// In lazy mode, entries that have not been materialized only carry their header
+    /// (class id, class name, anchor, extra anchor data) and no properties.
     data: Vec<UnityClass>,
+    /// Raw source text and per-entry parse state (lazy mode only)
+    lazy: Option<LazyState>,
+    /// Source text of entries that failed to parse when the document left lazy mode
+    unparsed: Option<UnparsedBodies>,
     /// Document metadata
     metadata: DocumentMetadata,
     /// Line ending style used in the original file
     newline: LineEnding,
+    /// Editor version of the project this document belongs to (e.g. from `ProjectVersion.txt`)
+    unity_version: Option<UnityVersion>,
+}
+
+/// Source text and per-entry state backing a lazily-loaded document.
+#[derive(Debug, Clone)]
+struct LazyState {
+    /// The full original file contents.
+    source: Arc<str>,
+    /// The `%YAML` / `%TAG` directives; comments after them belong to the first document.
+    prelude: Range<usize>,
+    /// One slot per entry; `None` once the entry has been materialized into `data`.
+    slots: Vec<Option<LazySlot>>,
+    /// Number of document bodies parsed so far (shared by clones).
+    parse_count: Arc<AtomicUsize>,
+}
+
+/// A document body that has not been materialized for mutation yet.
+#[derive(Debug, Clone)]
+struct LazySlot {
+    /// Byte range of the document (separator line included) inside `LazyState::source`.
+    span: Range<usize>,
+    /// Parsed body, filled on first read access.
+    parsed: OnceLock<UnityClass>,
+}
+
+impl LazyState {
+    fn slot(&self, index: usize) -> Option<&LazySlot> {
+        self.slots.get(index).and_then(Option::as_ref)
+    }
+
+    /// Return the parsed class for `index`, parsing it on first access.
+    ///
+    /// Returns `Ok(None)` when the entry has already been materialized.
+    fn resolve(&self, index: usize, header: &UnityClass) -> Result<Option<&UnityClass>> {
+        let Some(slot) = self.slot(index) else {
+            return Ok(None);
+        };
+        if let Some(class) = slot.parsed.get() {
+            return Ok(Some(class));
+        }
+        let class = parse_lazy_document(&self.source, &self.parse_count, &slot.span, header)?;
+        Ok(Some(slot.parsed.get_or_init(|| class)))
+    }
+}
+
+/// Original text of documents whose body could not be parsed.
+///
+/// Kept after leaving lazy mode so those documents are written back unchanged. Callers may
+/// reorder entries by then, so bodies are keyed by class id and anchor instead of index.
+#[derive(Debug, Clone)]
+struct UnparsedBodies {
+    source: Arc<str>,
+    prelude: Range<usize>,
+    spans: HashMap<(i32, String), Range<usize>>,
+}
+
+impl UnparsedBodies {
+    /// Source text for `class`, while it is still the header-only placeholder.
+    fn text_for(&self, class: &UnityClass) -> Option<&str> {
+        if !class.properties().is_empty() {
+            return None;
+        }
+        let span = self.spans.get(&(class.class_id, class.anchor.clone()))?;
+        Some(&self.source[span.clone()])
+    }
+}
+
+/// Whether a raw Unity YAML document has `key` among its top-level properties.
+///
+/// Unity writes the properties of a document two spaces under its class name line.
+fn has_top_level_key(text: &str, key: &str) -> bool {
+    text.lines().any(|line| {
+        line.strip_prefix("  ")
+            .filter(|rest| !rest.starts_with([' ', '-']))
+            .and_then(|rest| rest.strip_prefix(key))
+            .is_some_and(|rest| rest.starts_with(':'))
+    })
+}
+
+/// Parse one `--- !u!` document out of the lazy source text.
+fn parse_lazy_document(
+    source: &str,
+    parse_count: &AtomicUsize,
+    span: &Range<usize>,
+    header: &UnityClass,
+) -> Result<UnityClass> {
+    parse_count.fetch_add(1, Ordering::Relaxed);
+    let text = &source[span.clone()];
+    let (mut classes, warnings) =
+        SerdeUnityLoader::new().load_from_reader_detailed(text.as_bytes())?;
+    if classes.is_empty() {
+        let detail = warnings
+            .first()
+            .map(|w| w.error.clone())
+            .unwrap_or_else(|| "empty document".to_string());
+        return Err(UnityAssetError::parse(format!(
+            "Failed to parse YAML document {}(&{}): {}",
+            header.class_name, header.anchor, detail
+        )));
+    }
+    let mut class = classes.swap_remove(0);
+    class.extra_anchor_data = header.extra_anchor_data.clone();
+    Ok(class)
+}
+
+/// Split Unity YAML text into a directive prelude and one header-only entry per document.
+///
+/// This is a line scan only: the document bodies are not parsed.
+fn scan_document_headers(source: &str) -> (Range<usize>, Vec<(UnityClass, Range<usize>)>) {
+    let mut prelude_end = source.len();
+    let mut documents: Vec<(UnityClass, Range<usize>)> = Vec::new();
+    let mut awaiting_class_name = false;
+    // Comments between the directives and the first header belong to the first document.
+    let mut directives_end = 0usize;
+
+    let mut offset = 0usize;
+    for line in source.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+
+        if trimmed.starts_with("---") {
+            let mut start = line_start;
+            if documents.is_empty() {
+                prelude_end = directives_end;
+                start = directives_end;
+            } else if let Some((_, span)) = documents.last_mut() {
+                span.end = line_start;
+            }
+            let mut header = UnityClass::new(0, String::new(), String::new());
+            let mut extra: Vec<&str> = Vec::new();
+            for part in trimmed.trim_start_matches("---").split_whitespace() {
+                if let Some(id) = part.strip_prefix("!u!") {
+                    header.class_id = id.parse().unwrap_or(0);
+                } else if let Some(anchor) = part.strip_prefix('&') {
+                    header.anchor = anchor.to_string();
+                } else {
+                    extra.push(part);
+                }
+            }
+            header.extra_anchor_data = extra.join(" ");
+            documents.push((header, start..source.len()));
+            awaiting_class_name = true;
+            continue;
+        }
+
+        if documents.is_empty()
+            && !trimmed.trim_start().starts_with('#')
+            && !trimmed.trim().is_empty()
+        {
+            directives_end = offset;
+        }
+
+        if awaiting_class_name
+            && !trimmed.is_empty()
+            && !trimmed.starts_with([' ', '\t', '#'])
+            && let Some((header, _)) = documents.last_mut()
+        {
+            header.class_name = trimmed
+                .split(':')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+            awaiting_class_name = false;
+        }
+    }
+
+    for (index, (header, _)) in documents.iter_mut().enumerate() {
+        if header.anchor.is_empty() {
+            header.anchor = format!("doc_{}", index);
+        }
+    }
+
+    (0..prelude_end.min(source.len()), documents)
 }
 
 impl YamlDocument {
@@ -32,8 +222,11 @@ impl YamlDocument {
     pub fn new() -> Self {
         Self {
             data: Vec::new(),
+            lazy: None,
+            unparsed: None,
             metadata: DocumentMetadata::new(DocumentFormat::Yaml),
             newline: LineEnding::default(),
+            unity_version: None,
         }
     }
 
@@ -52,16 +245,17 @@ impl YamlDocument {
     /// let doc = YamlDocument::load_yaml("ProjectSettings.asset", false)?;
     /// # Ok::<(), unity_asset_core::UnityAssetError>(())
     /// ```
+    #[cfg(feature = "fs")]
     pub fn load_yaml<P: AsRef<Path>>(path: P, _preserve_types: bool) -> Result<Self> {
         Ok(Self::load_yaml_with_warnings(path, _preserve_types)?.0)
     }
 
     /// Load a Unity YAML file and return non-fatal conversion warnings.
+    #[cfg(feature = "fs")]
     pub fn load_yaml_with_warnings<P: AsRef<Path>>(
         path: P,
         _preserve_types: bool,
     ) -> Result<(Self, Vec<crate::serde_unity_loader::SerdeUnityWarning>)> {
-        use crate::serde_unity_loader::SerdeUnityLoader;
         use std::fs::File;
         use std::io::BufReader;
 
@@ -71,17 +265,28 @@ impl YamlDocument {
         let file = File::open(path).map_err(|e| {
             UnityAssetError::format(format!("Failed to open file {}: {}", path.display(), e))
         })?;
-        let reader = BufReader::new(file);
-
-        // Use serde-based loader
-        let loader = SerdeUnityLoader::new();
-        let (unity_classes, warnings) = loader.load_from_reader_detailed(reader)?;
 
-        // Create YamlDocument with metadata
-        let mut yaml_doc = YamlDocument::new();
+        let (mut yaml_doc, warnings) = Self::from_reader_with_warnings(BufReader::new(file))?;
         yaml_doc.metadata.file_path = Some(path.to_path_buf());
+        Ok((yaml_doc, warnings))
+    }
 
-        // Add all loaded classes
+    /// Parse Unity YAML text.
+    ///
+    /// The in-memory counterpart of [`load_yaml`](Self::load_yaml); the document has no file
+    /// path, so save it with [`dump_yaml`](Self::dump_yaml).
+    pub fn from_yaml_str(text: &str) -> Result<Self> {
+        Ok(Self::from_reader_with_warnings(text.as_bytes())?.0)
+    }
+
+    /// Parse Unity YAML from a reader and return non-fatal conversion warnings.
+    pub fn from_reader_with_warnings<R: std::io::Read>(
+        reader: R,
+    ) -> Result<(Self, Vec<crate::serde_unity_loader::SerdeUnityWarning>)> {
+        let (unity_classes, warnings) =
+            SerdeUnityLoader::new().load_from_reader_detailed(reader)?;
+
+        let mut yaml_doc = YamlDocument::new();
         for unity_class in unity_classes {
             yaml_doc.add_entry(unity_class);
         }
@@ -89,6 +294,266 @@ impl YamlDocument {
         Ok((yaml_doc, warnings))
     }
 
+    /// Load a Unity YAML file in lazy mode.
+    ///
+    /// Only a lightweight header scan is performed up front: every entry gets its class id,
+    /// class name and anchor, but document bodies are parsed on first property access
+    /// ([`entry_at`](Self::entry_at), [`get_property`](Self::get_property),
+    /// [`set_property`](Self::set_property), [`entry_properties`](Self::entry_properties)).
+    ///
+    /// [`UnityDocument::entries`] / [`UnityDocument::entry`] stay cheap and return the
+    /// header-only entries. Mutation forces a parse, and entries that were never mutated are
+    /// written back verbatim by [`dump_yaml`](Self::dump_yaml) / [`save_to`](Self::save_to).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use unity_asset_yaml::YamlDocument;
+    ///
+    /// let doc = YamlDocument::load_yaml_lazy("ProjectSettings/ProjectSettings.asset")?;
+    /// let name = doc.get_property(0, "productName")?;
+    /// # Ok::<(), unity_asset_core::UnityAssetError>(())
+    /// ```
+    #[cfg(feature = "fs")]
+    pub fn load_yaml_lazy<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| {
+            UnityAssetError::format(format!("Failed to open file {}: {}", path.display(), e))
+        })?;
+        let mut doc = Self::from_str_lazy(&text);
+        doc.metadata.file_path = Some(path.to_path_buf());
+        Ok(doc)
+    }
+
+    /// Build a lazy document from Unity YAML text (see [`load_yaml_lazy`](Self::load_yaml_lazy)).
+    pub fn from_str_lazy(text: &str) -> Self {
+        let (prelude, documents) = scan_document_headers(text);
+
+        let mut doc = Self::new();
+        if text.contains("\r\n") {
+            doc.newline = LineEnding::Windows;
+        } else if text.contains('\n') {
+            doc.newline = LineEnding::Unix;
+        }
+
+        let mut slots = Vec::with_capacity(documents.len());
+        for (header, span) in documents {
+            doc.data.push(header);
+            slots.push(Some(LazySlot {
+                span,
+                parsed: OnceLock::new(),
+            }));
+        }
+        doc.lazy = Some(LazyState {
+            source: Arc::from(text),
+            prelude,
+            slots,
+            parse_count: Arc::new(AtomicUsize::new(0)),
+        });
+        doc
+    }
+
+    /// Whether this document was loaded in lazy mode and still holds its source text.
+    pub fn is_lazy(&self) -> bool {
+        self.lazy.is_some()
+    }
+
+    /// Whether the body of the entry at `index` has been parsed.
+    ///
+    /// Always true for eagerly-loaded documents.
+    pub fn is_entry_parsed(&self, index: usize) -> bool {
+        match self.lazy.as_ref().and_then(|lazy| lazy.slot(index)) {
+            Some(slot) => slot.parsed.get().is_some(),
+            None => index < self.data.len(),
+        }
+    }
+
+    /// Get the fully parsed entry at `index`, parsing its body on first access.
+    pub fn entry_at(&self, index: usize) -> Result<&UnityClass> {
+        let header = self.data.get(index).ok_or_else(|| {
+            UnityAssetError::format(format!(
+                "Entry index {} out of range ({} entries)",
+                index,
+                self.data.len()
+            ))
+        })?;
+        match &self.lazy {
+            Some(lazy) => Ok(lazy.resolve(index, header)?.unwrap_or(header)),
+            None => Ok(header),
+        }
+    }
+
+    /// Get mutable access to the entry at `index`.
+    ///
+    /// In lazy mode this parses the entry (if needed) and detaches it from the source text, so
+    /// it is re-serialized on save.
+    pub fn entry_at_mut(&mut self, index: usize) -> Result<&mut UnityClass> {
+        self.materialize(index)?;
+        let len = self.data.len();
+        self.data.get_mut(index).ok_or_else(|| {
+            UnityAssetError::format(format!(
+                "Entry index {} out of range ({} entries)",
+                index, len
+            ))
+        })
+    }
+
+    /// Get all properties of the entry at `index`, parsing its body on first access.
+    pub fn entry_properties(&self, index: usize) -> Result<&IndexMap<String, UnityValue>> {
+        Ok(self.entry_at(index)?.properties())
+    }
+
+    /// Get a property of the entry at `index`, parsing its body on first access.
+    pub fn get_property(&self, index: usize, key: &str) -> Result<Option<&UnityValue>> {
+        Ok(self.entry_at(index)?.get(key))
+    }
+
+    /// Set a property of the entry at `index` (forces a parse in lazy mode).
+    pub fn set_property<V: Into<UnityValue>>(
+        &mut self,
+        index: usize,
+        key: &str,
+        value: V,
+    ) -> Result<()> {
+        self.entry_at_mut(index)?.set(key.to_string(), value);
+        Ok(())
+    }
+
+    /// Apply the registered [`upgraders`](crate::upgraders) to every entry.
+    ///
+    /// Each step rewrites an entry's layout to the next `serializedVersion` and bumps the key,
+    /// so calling this again is a no-op. Returns the number of steps applied.
+    ///
+    /// In lazy mode only entries whose class has registered upgraders are parsed, and only
+    /// entries that actually change are re-serialized on save.
+    pub fn upgrade_to_latest(&mut self) -> Result<usize> {
+        let mut steps = 0usize;
+        for index in 0..self.data.len() {
+            if !crate::upgraders::has_upgraders(&self.data[index].class_name) {
+                continue;
+            }
+            if !crate::upgraders::needs_upgrade(self.entry_at(index)?) {
+                continue;
+            }
+            steps += crate::upgraders::upgrade_class(self.entry_at_mut(index)?);
+        }
+        Ok(steps)
+    }
+
+    /// Parse every remaining entry and leave lazy mode.
+    ///
+    /// After this call the document behaves exactly like an eagerly-loaded one.
+    pub fn parse_all(&mut self) -> Result<()> {
+        for index in 0..self.data.len() {
+            self.materialize(index)?;
+        }
+        self.lazy = None;
+        Ok(())
+    }
+
+    /// Move the parsed body of a lazy entry into `data`, detaching it from the source text.
+    fn materialize(&mut self, index: usize) -> Result<()> {
+        let Some(lazy) = self.lazy.as_mut() else {
+            return Ok(());
+        };
+        let Some(Some(slot)) = lazy.slots.get_mut(index) else {
+            return Ok(());
+        };
+        let class = match slot.parsed.take() {
+            Some(class) => class,
+            None => parse_lazy_document(
+                &lazy.source,
+                &lazy.parse_count,
+                &slot.span,
+                &self.data[index],
+            )?,
+        };
+        lazy.slots[index] = None;
+        self.data[index] = class;
+        Ok(())
+    }
+
+    /// Best-effort variant of [`parse_all`](Self::parse_all) for trait methods that cannot fail.
+    ///
+    /// Entries whose body fails to parse keep their header only, and their original text is
+    /// kept so saving writes them back unchanged.
+    fn materialize_all_lossy(&mut self) {
+        let mut failed = Vec::new();
+        for index in 0..self.data.len() {
+            if self.materialize(index).is_err() {
+                failed.push(index);
+            }
+        }
+        let Some(lazy) = self.lazy.take() else {
+            return;
+        };
+        if failed.is_empty() {
+            return;
+        }
+        let spans = failed
+            .into_iter()
+            .filter_map(|index| {
+                let class = &self.data[index];
+                let span = lazy.slot(index)?.span.clone();
+                Some(((class.class_id, class.anchor.clone()), span))
+            })
+            .collect();
+        self.unparsed = Some(UnparsedBodies {
+            source: lazy.source,
+            prelude: lazy.prelude,
+            spans,
+        });
+    }
+
+    /// Original text of the entry at `index` if its body could not be parsed
+    fn unparsed_text(&self, index: usize) -> Option<&str> {
+        match (&self.lazy, &self.unparsed) {
+            (Some(lazy), _) => {
+                let slot = lazy.slot(index)?;
+                match slot.parsed.get() {
+                    Some(_) => None,
+                    None => Some(&lazy.source[slot.span.clone()]),
+                }
+            }
+            (None, Some(unparsed)) => unparsed.text_for(self.data.get(index)?),
+            (None, None) => None,
+        }
+    }
+
+    #[cfg(test)]
+    fn lazy_parse_count(&self) -> usize {
+        self.lazy
+            .as_ref()
+            .map(|lazy| lazy.parse_count.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    }
+
+    /// Serialize a document backed by source text, copying the documents `verbatim` returns
+    /// as-is.
+    fn dump_with_source<'a>(
+        &'a self,
+        source: &'a str,
+        prelude: Range<usize>,
+        verbatim: impl Fn(usize, &UnityClass) -> Option<&'a str>,
+    ) -> Result<String> {
+        let mut output = String::with_capacity(source.len());
+        output.push_str(&source[prelude]);
+
+        let mut serializer = UnityYamlSerializer::new().with_line_ending(self.newline);
+        for (index, class) in self.data.iter().enumerate() {
+            match verbatim(index, class) {
+                Some(text) => output.push_str(text),
+                None => {
+                    if !output.is_empty() && !output.ends_with(['\n', '\r']) {
+                        output.push_str(self.newline.as_str());
+                    }
+                    serializer.serialize_class_to_writer(&mut output, class)?;
+                }
+            }
+        }
+        Ok(output)
+    }
+
     /// Load a Unity YAML file asynchronously
     ///
     /// # Arguments
@@ -119,30 +584,31 @@ impl YamlDocument {
             .0)
     }
 
+    /// Load a Unity YAML file asynchronously, returning per-document warnings
+    ///
+    /// The file is read with `tokio::fs`; YAML parsing is CPU-bound and runs on
+    /// `tokio::task::spawn_blocking` so it does not stall the async worker threads.
     #[cfg(feature = "async")]
     pub async fn load_yaml_async_with_warnings<P: AsRef<Path> + Send>(
         path: P,
         _preserve_types: bool,
     ) -> Result<(Self, Vec<crate::serde_unity_loader::SerdeUnityWarning>)> {
-        use crate::serde_unity_loader::SerdeUnityLoader;
-        use tokio::fs::File;
-        use tokio::io::BufReader;
-
-        let path = path.as_ref();
+        let path = path.as_ref().to_path_buf();
 
         // Read the file asynchronously
-        let file = File::open(path).await.map_err(|e| {
+        let bytes = tokio::fs::read(&path).await.map_err(|e| {
             UnityAssetError::format(format!("Failed to open file {}: {}", path.display(), e))
         })?;
-        let reader = BufReader::new(file);
 
-        // Use serde-based loader (we'll need to make this async too)
-        let loader = SerdeUnityLoader::new();
-        let (unity_classes, warnings) = loader.load_from_async_reader_detailed(reader).await?;
+        let (unity_classes, warnings) = tokio::task::spawn_blocking(move || {
+            SerdeUnityLoader::new().load_from_reader_detailed(std::io::Cursor::new(bytes))
+        })
+        .await
+        .map_err(|e| UnityAssetError::with_source("YAML parse task failed", e))??;
 
         // Create YamlDocument with metadata
         let mut yaml_doc = YamlDocument::new();
-        yaml_doc.metadata.file_path = Some(path.to_path_buf());
+        yaml_doc.metadata.file_path = Some(path);
 
         // Add all loaded classes
         for unity_class in unity_classes {
@@ -162,6 +628,20 @@ impl YamlDocument {
         self.newline = newline;
     }
 
+    /// Get the Unity editor version this document is associated with, if known.
+    ///
+    /// Unity YAML files do not record the editor version themselves; it is usually taken from
+    /// the project's `ProjectSettings/ProjectVersion.txt`. Version-gated YAML handling (such as
+    /// `serializedVersion` layout differences) consults this value.
+    pub fn unity_version(&self) -> Option<&UnityVersion> {
+        self.unity_version.as_ref()
+    }
+
+    /// Associate this document with a Unity editor version.
+    pub fn set_unity_version(&mut self, version: Option<UnityVersion>) {
+        self.unity_version = version;
+    }
+
     /// Get the YAML version
     pub fn version(&self) -> Option<&str> {
         self.metadata.version.as_deref()
@@ -214,16 +694,20 @@ impl YamlDocument {
     pub fn save_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let path = path.as_ref();
 
-        // Create serializer with document settings
-        let mut serializer = UnityYamlSerializer::new().with_line_ending(self.newline);
-
         // Serialize to string
-        let yaml_content = serializer.serialize_to_string(&self.data)?;
+        let yaml_content = self.dump_yaml()?;
 
         // Write to file
-        fs::write(path, yaml_content).map_err(UnityAssetError::from)?;
-
-        Ok(())
+        #[cfg(feature = "fs")]
+        {
+            fs::write(path, yaml_content).map_err(UnityAssetError::from)?;
+            Ok(())
+        }
+        #[cfg(not(feature = "fs"))]
+        {
+            let _ = yaml_content;
+            Err(fs_disabled(path))
+        }
     }
 
     /// Get YAML content as string
@@ -242,6 +726,19 @@ impl YamlDocument {
     /// # Ok::<(), unity_asset_core::UnityAssetError>(())
     /// ```
     pub fn dump_yaml(&self) -> Result<String> {
+        if let Some(lazy) = &self.lazy {
+            return self.dump_with_source(&lazy.source, lazy.prelude.clone(), |index, _| {
+                lazy.slot(index).map(|slot| &lazy.source[slot.span.clone()])
+            });
+        }
+        if let Some(unparsed) = &self.unparsed {
+            return self.dump_with_source(
+                &unparsed.source,
+                unparsed.prelude.clone(),
+                |_, class| unparsed.text_for(class),
+            );
+        }
+
         let mut serializer = UnityYamlSerializer::new().with_line_ending(self.newline);
 
         serializer.serialize_to_string(&self.data)
@@ -257,6 +754,9 @@ impl YamlDocument {
     /// * `class_names` - Optional list of class names to filter by
     /// * `attributes` - Optional list of attribute names that entries must have
     ///
+    /// In lazy mode, attribute checks parse the candidate entries and the returned references
+    /// point at the parsed entries; class-name-only filters stay header-only.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
@@ -281,27 +781,47 @@ impl YamlDocument {
     ) -> Vec<&UnityClass> {
         self.data
             .iter()
-            .filter(|entry| {
+            .enumerate()
+            .filter_map(|(index, entry)| {
                 // Check class name filter
                 if let Some(names) = class_names
                     && !names.is_empty()
                     && !names.contains(&entry.class_name.as_str())
                 {
-                    return false;
+                    return None;
                 }
 
+                // Attribute checks need the parsed body (lazy mode parses on demand)
+                let wants_attributes = attributes.is_some_and(|attrs| !attrs.is_empty());
+                let entry = match &self.lazy {
+                    Some(lazy) if wants_attributes => {
+                        lazy.resolve(index, entry).ok().flatten().unwrap_or(entry)
+                    }
+                    _ => entry,
+                };
+                // Bodies that fail to parse are matched against their top-level keys instead
+                let unparsed = if wants_attributes {
+                    self.unparsed_text(index)
+                } else {
+                    None
+                };
+
                 // Check attribute filter
                 if let Some(attrs) = attributes
                     && !attrs.is_empty()
                 {
                     for attr in attrs {
-                        if !entry.has_property(attr) {
-                            return false;
+                        let found = match unparsed {
+                            Some(text) => has_top_level_key(text, attr),
+                            None => entry.has_property(attr),
+                        };
+                        if !found {
+                            return None;
                         }
                     }
                 }
 
-                true
+                Some(entry)
             })
             .collect()
     }
@@ -358,6 +878,11 @@ impl UnityDocument for YamlDocument {
     }
 
     fn entry_mut(&mut self) -> Option<&mut UnityClass> {
+        // Mutation forces a parse. An entry whose body cannot be parsed is not handed out:
+        // edits to its header-only placeholder would replace the original text on save.
+        if self.materialize(0).is_err() || self.unparsed_text(0).is_some() {
+            return None;
+        }
         self.data.first_mut()
     }
 
@@ -366,11 +891,17 @@ impl UnityDocument for YamlDocument {
     }
 
     fn entries_mut(&mut self) -> &mut Vec<UnityClass> {
+        // Callers may reorder or remove entries, so lazy mode cannot be kept. Entries that
+        // fail to parse stay header-only and are saved from their original text.
+        self.materialize_all_lossy();
         &mut self.data
     }
 
     fn add_entry(&mut self, entry: UnityClass) {
         self.data.push(entry);
+        if let Some(lazy) = self.lazy.as_mut() {
+            lazy.slots.push(None);
+        }
     }
 
     fn file_path(&self) -> Option<&Path> {
@@ -391,10 +922,18 @@ impl UnityDocument for YamlDocument {
         let yaml_content = self.dump_yaml()?;
 
         // Write to file
-        std::fs::write(path, yaml_content)
-            .map_err(|e| UnityAssetError::format(format!("Failed to write YAML file: {}", e)))?;
-
-        Ok(())
+        #[cfg(feature = "fs")]
+        {
+            std::fs::write(path, yaml_content).map_err(|e| {
+                UnityAssetError::format(format!("Failed to write YAML file: {}", e))
+            })?;
+            Ok(())
+        }
+        #[cfg(not(feature = "fs"))]
+        {
+            let _ = yaml_content;
+            Err(fs_disabled(path))
+        }
     }
 
     fn format(&self) -> DocumentFormat {
@@ -402,6 +941,15 @@ impl UnityDocument for YamlDocument {
     }
 }
 
+/// Error for path-based writes when the `fs` feature is off.
+#[cfg(not(feature = "fs"))]
+fn fs_disabled(path: &Path) -> UnityAssetError {
+    UnityAssetError::format(format!(
+        "Cannot write {}: file system support is disabled (enable the `fs` feature)",
+        path.display()
+    ))
+}
+
 impl Default for YamlDocument {
     fn default() -> Self {
         Self::new()
@@ -492,6 +1040,119 @@ mod tests {
         assert!(doc.version().is_none());
     }
 
+    const LAZY_SAMPLE: &str = "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n--- !u!1 &100\nGameObject:\n  m_Name: First\n  m_Layer: 0\n--- !u!4 &200 stripped\nTransform:\n  m_GameObject: {fileID: 100}\n--- !u!129 &300\nPlayerSettings:\n  productName: Demo\n  companyName:   Spaced   Co\n";
+
+    #[test]
+    fn test_lazy_header_scan_does_not_parse() {
+        let doc = YamlDocument::from_str_lazy(LAZY_SAMPLE);
+        assert!(doc.is_lazy());
+        assert_eq!(doc.len(), 3);
+
+        let headers: Vec<(i32, &str, &str)> = UnityDocument::entries(&doc)
+            .iter()
+            .map(|e| (e.class_id, e.class_name.as_str(), e.anchor.as_str()))
+            .collect();
+        assert_eq!(
+            headers,
+            vec![
+                (1, "GameObject", "100"),
+                (4, "Transform", "200"),
+                (129, "PlayerSettings", "300"),
+            ]
+        );
+        assert_eq!(
+            UnityDocument::entries(&doc)[1].extra_anchor_data,
+            "stripped"
+        );
+        assert_eq!(doc.lazy_parse_count(), 0);
+    }
+
+    #[test]
+    fn test_lazy_property_access_parses_only_that_entry() {
+        let doc = YamlDocument::from_str_lazy(LAZY_SAMPLE);
+
+        let name = doc.get_property(2, "productName").unwrap();
+        assert_eq!(name.and_then(|v| v.as_str()), Some("Demo"));
+        assert_eq!(doc.lazy_parse_count(), 1);
+        assert!(doc.is_entry_parsed(2));
+        assert!(!doc.is_entry_parsed(0));
+        assert!(!doc.is_entry_parsed(1));
+
+        // Repeated access reuses the parsed body.
+        let _ = doc.entry_properties(2).unwrap();
+        assert_eq!(doc.lazy_parse_count(), 1);
+    }
+
+    #[test]
+    fn test_lazy_untouched_roundtrip_is_verbatim() {
+        let doc = YamlDocument::from_str_lazy(LAZY_SAMPLE);
+        let _ = doc.get_property(0, "m_Name").unwrap();
+        assert_eq!(doc.dump_yaml().unwrap(), LAZY_SAMPLE);
+    }
+
+    #[test]
+    fn test_lazy_mutation_reserializes_only_touched_entry() {
+        let mut doc = YamlDocument::from_str_lazy(LAZY_SAMPLE);
+        doc.set_property(0, "m_Name", "Renamed").unwrap();
+        assert_eq!(doc.lazy_parse_count(), 1);
+
+        let out = doc.dump_yaml().unwrap();
+        assert!(out.contains("m_Name: Renamed"));
+        assert!(!out.contains("m_Name: First"));
+        // Untouched documents keep their original text, including odd spacing.
+        assert!(
+            out.contains("--- !u!4 &200 stripped\nTransform:\n  m_GameObject: {fileID: 100}\n")
+        );
+        assert!(out.contains("  companyName:   Spaced   Co\n"));
+    }
+
+    #[test]
+    fn test_lazy_filter_by_attribute_and_parse_all() {
+        let mut doc = YamlDocument::from_str_lazy(LAZY_SAMPLE);
+        let with_name = doc.filter(None, Some(&["m_Name"]));
+        assert_eq!(with_name.len(), 1);
+        assert_eq!(with_name[0].name(), Some("First"));
+
+        doc.parse_all().unwrap();
+        assert!(!doc.is_lazy());
+        assert_eq!(
+            UnityDocument::entries(&doc)[2]
+                .get("productName")
+                .and_then(|v| v.as_str()),
+            Some("Demo")
+        );
+    }
+
+    const BROKEN_SAMPLE: &str = "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n--- !u!1 &100\nGameObject:\n  m_Name: [unclosed\n  m_Layer: 0\n--- !u!1 &200\nGameObject:\n  m_Name: Second\n";
+
+    #[test]
+    fn test_lazy_unparseable_entry_is_saved_verbatim() {
+        let mut doc = YamlDocument::from_str_lazy(BROKEN_SAMPLE);
+        assert!(doc.entry_at(0).is_err());
+        // The broken body still matches on its top-level keys.
+        assert_eq!(doc.filter(None, Some(&["m_Layer"])).len(), 1);
+        assert!(doc.filter(None, Some(&["m_Missing"])).is_empty());
+        // Its header-only placeholder is not handed out for editing.
+        assert!(doc.entry_mut().is_none());
+
+        doc.entries_mut()[1].set("m_Name".to_string(), "Renamed");
+        assert!(!doc.is_lazy());
+        assert_eq!(doc.filter(None, Some(&["m_Layer"])).len(), 1);
+        assert!(doc.entry_mut().is_none());
+
+        let out = doc.dump_yaml().unwrap();
+        assert!(out.contains("--- !u!1 &100\nGameObject:\n  m_Name: [unclosed\n  m_Layer: 0\n"));
+        assert!(out.contains("m_Name: Renamed"));
+        assert!(!out.contains("m_Name: Second"));
+
+        // Reordering keeps the broken body attached to its entry.
+        doc.entries_mut().reverse();
+        let out = doc.dump_yaml().unwrap();
+        let broken = out.find("&100").unwrap();
+        assert!(out.find("&200").unwrap() < broken);
+        assert!(out[broken..].contains("  m_Name: [unclosed\n"));
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     async fn test_async_yaml_document_creation() {
@@ -0,0 +1,95 @@
+//! Comment preservation through load/save
+//!
+//! `Commented.asset` has document, key, trailing, sequence-item and end-of-file comments.
+
+use std::path::PathBuf;
+use unity_asset_core::{UnityDocument, UnityValue};
+use unity_asset_yaml::YamlDocument;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+fn comment_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .filter_map(|line| line.find('#').map(|i| line[i..].trim_end().to_string()))
+        .collect()
+}
+
+/// The line following the comment, trimmed.
+fn line_after<'a>(text: &'a str, comment: &str) -> &'a str {
+    let mut lines = text.lines();
+    lines.find(|line| line.trim() == comment).unwrap();
+    lines.next().unwrap().trim()
+}
+
+#[test]
+fn round_trip_keeps_every_comment_in_order() {
+    let source = std::fs::read_to_string(fixture("Commented.asset")).unwrap();
+    let doc = YamlDocument::load_yaml(fixture("Commented.asset"), false).unwrap();
+
+    let comments = doc.entries()[0].comments();
+    assert_eq!(
+        comments.document,
+        vec!["# Tuning values for the player controller."]
+    );
+    assert_eq!(comments.leading["m_MoveSpeed"].len(), 2);
+    assert_eq!(comments.trailing["m_JumpHeight"], "# metres");
+    assert_eq!(comments.leading["m_Curve[2]"], vec!["# hard mode"]);
+    assert_eq!(doc.entries()[1].comments().footer, vec!["# End of file."]);
+
+    let output = doc.dump_yaml().unwrap();
+    assert_eq!(comment_lines(&output), comment_lines(&source));
+    assert_eq!(
+        line_after(&output, "# Spawn configuration lives in its own object."),
+        "--- !u!114 &11400002"
+    );
+    assert_eq!(line_after(&output, "# hard mode"), "- 2.25");
+    assert!(output.contains("lag: 0.15 # seconds"));
+
+    // Saving twice is stable.
+    let reloaded = YamlDocument::from_str_lazy(&output);
+    let mut reparsed = reloaded.clone();
+    reparsed.parse_all().unwrap();
+    assert_eq!(reparsed.dump_yaml().unwrap(), output);
+}
+
+#[test]
+fn edits_to_other_keys_leave_comments_in_place() {
+    let mut doc = YamlDocument::load_yaml(fixture("Commented.asset"), false).unwrap();
+    doc.set_property(0, "m_JumpHeight", UnityValue::Float(3.0))
+        .unwrap();
+    doc.set_property(0, "m_Name", UnityValue::String("Renamed".into()))
+        .unwrap();
+    doc.set_property(1, "m_Extra", UnityValue::Integer(4))
+        .unwrap();
+
+    let output = doc.dump_yaml().unwrap();
+    let source = std::fs::read_to_string(fixture("Commented.asset")).unwrap();
+    assert_eq!(comment_lines(&output), comment_lines(&source));
+    assert_eq!(
+        line_after(
+            &output,
+            "# Do not raise above 9 (clips through thin walls)."
+        ),
+        "m_MoveSpeed: 7"
+    );
+    assert!(output.contains("m_JumpHeight: 3 # metres"));
+    assert_eq!(
+        line_after(&output, "# 0 = random, 1 = round robin"),
+        "m_Mode: 1"
+    );
+
+    // Lazy documents keep the comments of materialized entries too.
+    let mut lazy = YamlDocument::load_yaml_lazy(fixture("Commented.asset")).unwrap();
+    lazy.set_property(1, "m_Mode", UnityValue::Integer(0))
+        .unwrap();
+    let output = lazy.dump_yaml().unwrap();
+    assert_eq!(comment_lines(&output), comment_lines(&source));
+    assert_eq!(
+        line_after(&output, "# 0 = random, 1 = round robin"),
+        "m_Mode: 0"
+    );
+}
@@ -0,0 +1,27 @@
+//! Parsing and dumping without a filesystem; builds with `--no-default-features`.
+
+use unity_asset_core::{UnityDocument, UnityValue};
+use unity_asset_yaml::YamlDocument;
+
+const SINGLE_DOC: &str = include_str!("fixtures/SingleDoc.asset");
+
+#[test]
+fn yaml_str_round_trips_without_a_file() {
+    let doc = YamlDocument::from_yaml_str(SINGLE_DOC).unwrap();
+    assert!(doc.file_path().is_none());
+    let settings = doc.entry().unwrap();
+    assert_eq!(settings.class_name, "PlayerSettings");
+    assert_eq!(
+        settings.get("productName"),
+        Some(&UnityValue::String("TowerLoot".to_string()))
+    );
+
+    let reparsed = YamlDocument::from_yaml_str(&doc.dump_yaml().unwrap()).unwrap();
+    assert_eq!(reparsed.entries().len(), doc.entries().len());
+    assert_eq!(
+        reparsed.entry().unwrap().get("productName"),
+        settings.get("productName")
+    );
+
+    assert!(YamlDocument::from_yaml_str("--- !u!1 &1\nGameObject: [unclosed").is_err());
+}
@@ -0,0 +1,137 @@
+//! Byte-identical load/dump of Unity-written YAML
+//!
+//! Every fixture written by Unity (or in its exact style) must come back unchanged from
+//! `dump_yaml`, and editing one value must leave the rest of the text alone.
+
+use std::path::PathBuf;
+use unity_asset_core::{UnityDocument, UnityValue};
+use unity_asset_yaml::YamlDocument;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+// Unity writes empty strings as `m_Name: ` (the `\x20` below).
+const SCENE: &str = "%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!1 &1466937533
+GameObject:
+  m_ObjectHideFlags: 0
+  serializedVersion: 6
+  m_Component:
+  - component: {fileID: 1466937535}
+  - component: {fileID: 7340893207434416235}
+  m_Layer: 0
+  m_Name: 'Door: left'
+  m_TagString: Untagged
+  m_IsActive: 1
+--- !u!4 &7340893207434416235 stripped
+Transform:
+  m_CorrespondingSourceObject: {fileID: -8679921383154817045, guid: 0000000000000000e000000000000000,
+    type: 3}
+  m_PrefabInstance: {fileID: 1466937536}
+--- !u!114 &-3402181702745624518
+MonoBehaviour:
+  m_Script: {fileID: 11500000, guid: 5f7201a12d95ffc409449d95f23cf332, type: 3}
+  m_Name:\x20
+  m_Text: ~
+  m_Value: 1.5
+  m_Hash: 0x1F
+  m_Keys: []
+  m_Fields:
+    - key: speed
+      value: 3
+";
+
+fn assert_round_trip(text: &str) -> YamlDocument {
+    let doc = YamlDocument::from_yaml_str(text).unwrap();
+    assert_eq!(doc.dump_yaml().unwrap(), text);
+    doc
+}
+
+#[test]
+fn unity_fixtures_dump_byte_identical() {
+    for name in [
+        "SingleDoc.asset",
+        "MultiDoc.asset",
+        "MultipleTypesDoc.asset",
+        "TagManager.asset",
+        "MetaFileWithoutTags.meta",
+        "MinimalGameObjectTransform.prefab",
+        "UnityExtraAnchorData.prefab",
+        "LegacyLayouts.prefab",
+        "Commented.asset",
+    ] {
+        let text = std::fs::read_to_string(fixture(name)).unwrap();
+        let doc = YamlDocument::from_yaml_str(&text).unwrap();
+        assert_eq!(doc.dump_yaml().unwrap(), text, "{}", name);
+    }
+}
+
+#[test]
+fn scene_headers_anchors_and_scalars_round_trip() {
+    let doc = assert_round_trip(SCENE);
+    let entries = doc.entries();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[1].anchor, "7340893207434416235");
+    assert_eq!(entries[1].extra_anchor_data, "stripped");
+    assert_eq!(entries[2].anchor, "-3402181702745624518");
+
+    // Empty scalars are empty strings; `~` stays an explicit null.
+    assert_eq!(
+        entries[2].get("m_Name"),
+        Some(&UnityValue::String(String::new()))
+    );
+    assert_eq!(entries[2].get("m_Text"), Some(&UnityValue::Null));
+    assert_eq!(entries[0].name(), Some("Door: left"));
+}
+
+#[test]
+fn edits_keep_the_surrounding_formatting() {
+    let mut doc = YamlDocument::from_yaml_str(SCENE).unwrap();
+    doc.entries_mut()[0].set("m_Layer".to_string(), UnityValue::Integer(5));
+    doc.entries_mut()[2].set(
+        "m_Name".to_string(),
+        UnityValue::String("Door #2".to_string()),
+    );
+
+    let expected = SCENE
+        .replace("  m_Layer: 0\n", "  m_Layer: 5\n")
+        .replace("  m_Name: \n", "  m_Name: 'Door #2'\n");
+    assert_eq!(doc.dump_yaml().unwrap(), expected);
+}
+
+#[test]
+fn new_documents_use_unity_layout() {
+    let mut doc = YamlDocument::from_yaml_str(SCENE).unwrap();
+    let mut class = doc.entries()[0].clone();
+    class.anchor = "2".to_string();
+    *class.layout_mut() = Default::default();
+    class.set(
+        "m_LocalPosition".to_string(),
+        UnityValue::Object(
+            [("x", 0.0), ("y", 1.5), ("z", -2.0)]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), UnityValue::Float(v)))
+                .collect(),
+        ),
+    );
+    class.set("m_Enabled".to_string(), UnityValue::Bool(true));
+    class.set("m_Children".to_string(), UnityValue::Array(Vec::new()));
+    doc.add_entry(class);
+
+    let text = doc.dump_yaml().unwrap();
+    let added = &text[text.find("--- !u!1 &2\n").unwrap()..];
+    assert!(added.contains("\n  m_Component:\n  - component: {fileID: 1466937535}\n"));
+    assert!(added.contains("\n  m_Name: 'Door: left'\n"), "{}", added);
+    assert!(
+        added.contains("\n  m_LocalPosition: {x: 0, y: 1.5, z: -2}\n"),
+        "{}",
+        added
+    );
+    assert!(added.contains("\n  m_Enabled: 1\n"));
+    assert!(added.contains("\n  m_Children: []\n"));
+    assert_round_trip(&text);
+}
@@ -221,6 +221,25 @@ fn test_error_handling() {
     assert!(error_msg.contains("YAML parsing error"));
 }
 
+/// YAML syntax errors keep the parser error as their source
+#[test]
+fn test_yaml_error_preserves_source() {
+    use std::error::Error as _;
+    use unity_asset_core::UnityAssetError;
+
+    let loader = SerdeUnityLoader::new();
+    let error = loader
+        .load_from_str("invalid: yaml: content: [unclosed")
+        .unwrap_err();
+
+    assert!(matches!(error, UnityAssetError::Yaml { .. }));
+    let source = error.source().expect("source");
+    let yaml_error = source
+        .downcast_ref::<serde_yaml::Error>()
+        .expect("serde_yaml source");
+    assert!(error.to_string().ends_with(&yaml_error.to_string()));
+}
+
 /// Test empty YAML handling
 #[test]
 fn test_empty_yaml() {
@@ -250,3 +269,32 @@ fn test_whitespace_only_yaml() {
     let error_msg = format!("{}", error);
     assert!(error_msg.contains("YAML parsing error"));
 }
+
+/// Test that an untagged `.meta` file keeps every top-level key and dumps without Unity tags
+#[test]
+fn test_meta_file_loads_as_plain_mapping() {
+    let doc = YamlDocument::load_yaml(Path::new("tests/fixtures/MetaFileWithoutTags.meta"), false)
+        .unwrap();
+
+    assert_eq!(doc.entries().len(), 1);
+    let meta = &doc.entries()[0];
+    assert!(meta.is_plain());
+    assert_eq!(meta.get("fileFormatVersion"), Some(&UnityValue::Integer(2)));
+    assert_eq!(
+        meta.get("guid").and_then(|v| v.as_str()),
+        Some("7b042741569854557813adece4fc66e7")
+    );
+    assert!(
+        meta.get("PluginImporter")
+            .and_then(|v| v.as_object())
+            .is_some()
+    );
+
+    let dumped = doc.dump_yaml().unwrap();
+    assert!(dumped.starts_with("fileFormatVersion: 2\nguid: 7b042741569854557813adece4fc66e7\n"));
+    assert!(!dumped.contains("--- !u!"));
+    assert!(!dumped.contains("%YAML"));
+
+    let reparsed = YamlDocument::from_yaml_str(&dumped).unwrap();
+    assert_eq!(reparsed.entries()[0].properties(), meta.properties());
+}
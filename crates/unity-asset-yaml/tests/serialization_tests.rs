@@ -298,3 +298,50 @@ fn test_serialize_special_cases() {
 
     println!("Special cases YAML:\n{}", yaml_output);
 }
+
+/// Serialized property order follows `UnityClass` insertion order, including relative inserts
+#[test]
+fn test_serialize_follows_property_order() {
+    let mut transform = UnityClass::new(4, "Transform".to_string(), "42".to_string());
+    transform.set("m_ObjectHideFlags".to_string(), UnityValue::Integer(0));
+    transform.set("m_Children".to_string(), UnityValue::Array(Vec::new()));
+    transform.set("m_RootOrder".to_string(), UnityValue::Integer(0));
+
+    let position: indexmap::IndexMap<String, UnityValue> = [("z", 3.0), ("x", 1.0), ("y", 2.0)]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), UnityValue::Float(v)))
+        .collect();
+    transform
+        .insert_after(
+            "m_ObjectHideFlags",
+            "m_LocalPosition".to_string(),
+            UnityValue::Object(position),
+        )
+        .unwrap();
+    transform
+        .insert_before("m_RootOrder", "m_Father".to_string(), UnityValue::Null)
+        .unwrap();
+    transform.set("m_ObjectHideFlags".to_string(), UnityValue::Integer(1));
+    transform.reorder(&["m_RootOrder"]).unwrap();
+
+    let mut serializer = UnityYamlSerializer::new();
+    let yaml = serializer.serialize_to_string(&[transform]).unwrap();
+
+    let keys: Vec<&str> = yaml
+        .lines()
+        .filter_map(|line| line.strip_prefix("  "))
+        .filter_map(|line| line.split(':').next())
+        .collect();
+    assert_eq!(
+        keys,
+        [
+            "m_RootOrder",
+            "m_ObjectHideFlags",
+            "m_LocalPosition",
+            "m_Children",
+            "m_Father",
+        ]
+    );
+    assert!(yaml.contains("m_ObjectHideFlags: 1"));
+    assert!(yaml.contains("m_LocalPosition: {z: 3, x: 1, y: 2}"));
+}
@@ -0,0 +1,42 @@
+//! ProjectSettings parsing: `TagManager.asset` with two custom layers (8 `Ground`, 9 `Enemies`).
+
+use std::path::PathBuf;
+use unity_asset_yaml::settings::{SortingLayer, load_tag_manager};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+#[test]
+fn tag_manager_asset_exposes_layers_tags_and_sorting_layers() {
+    let manager = load_tag_manager(fixture("TagManager.asset")).unwrap();
+
+    assert_eq!(manager.layers.len(), 32);
+    assert_eq!(manager.layer_name(0), Some("Default"));
+    assert_eq!(manager.layer_name(5), Some("UI"));
+    assert_eq!(manager.layer_name(8), Some("Ground"));
+    assert_eq!(manager.layer_name(9), Some("Enemies"));
+    assert_eq!(manager.layer_name(3), None);
+    assert_eq!(manager.layer_name(10), None);
+    assert_eq!(manager.user_layers(), vec![(8, "Ground"), (9, "Enemies")]);
+
+    assert_eq!(manager.tags, vec!["Enemy", "Pickup"]);
+    assert!(manager.all_tags().contains(&"MainCamera"));
+
+    assert_eq!(manager.sorting_layers.len(), 2);
+    assert_eq!(
+        manager.sorting_layer(2438416305),
+        Some(&SortingLayer {
+            name: "Foreground".to_string(),
+            unique_id: 2438416305,
+            locked: true,
+        })
+    );
+}
+
+#[test]
+fn load_tag_manager_rejects_other_assets() {
+    assert!(load_tag_manager(fixture("SingleDoc.asset")).is_err());
+}
@@ -63,11 +63,10 @@ fn main() -> unity_asset::Result<()> {
 
     let closure_with_external = graph.closure_with_options(
         &roots,
-        DependencyGraphTraversalOptions {
-            max_depth: Some(2),
-            max_nodes: Some(50_000),
-            follow_resolved_external: true,
-        },
+        DependencyGraphTraversalOptions::default()
+            .with_max_depth(Some(2))
+            .with_max_nodes(Some(50_000))
+            .with_follow_resolved_external(true),
     );
     println!(
         "closure_nodes_with_external={} (depth=2)",
@@ -39,11 +39,10 @@ fn main() -> unity_asset::Result<()> {
     if let Some(root) = graph.nodes().first() {
         let closure = graph.closure_with_options(
             std::slice::from_ref(root),
-            ObjectGraphTraversalOptions {
-                max_depth: Some(2),
-                max_nodes: Some(10_000),
-                follow_resolved_external: false,
-            },
+            ObjectGraphTraversalOptions::default()
+                .with_max_depth(Some(2))
+                .with_max_nodes(Some(10_000))
+                .with_follow_resolved_external(false),
         );
         println!("closure_nodes={} (depth=2)", closure.len());
     }
@@ -39,11 +39,11 @@ fn main() -> unity_asset::Result<()> {
         stats.meta_guids_indexed
     );
 
-    let graph = env.build_object_graph(ObjectGraphBuildOptions {
-        include_yaml: load_yaml,
-        include_binary: true,
-        ..ObjectGraphBuildOptions::default()
-    });
+    let graph = env.build_object_graph(
+        ObjectGraphBuildOptions::default()
+            .with_include_yaml(load_yaml)
+            .with_include_binary(true),
+    );
 
     let internal_edges: usize = graph
         .nodes()
@@ -66,11 +66,10 @@ fn main() -> unity_asset::Result<()> {
     if let Some(root) = graph.nodes().first() {
         let closure = graph.closure_with_options(
             std::slice::from_ref(root),
-            ObjectGraphTraversalOptions {
-                max_depth: Some(2),
-                max_nodes: Some(10_000),
-                follow_resolved_external: true,
-            },
+            ObjectGraphTraversalOptions::default()
+                .with_max_depth(Some(2))
+                .with_max_nodes(Some(10_000))
+                .with_follow_resolved_external(true),
         );
         println!("closure_nodes={} (depth=2, follow_external)", closure.len());
     }
@@ -1,21 +1,26 @@
 //! Environment for managing multiple Unity assets.
 //!
 //! This module hosts the high-level `Environment` API, which provides:
-//! - multi-source loading (bundles, serialized files, web files)
+//! - multi-source loading (bundles, serialized files, web files, player Data folders,
+//!   `.unitypackage` archives)
 //! - container discovery (`m_Container`) and object key resolution
 //! - streamed resource reads (`.resS` / `.resource`) with best-effort fallbacks
 //! - strict/lenient TypeTree parsing knobs + structured warnings
 
 mod imp {
     use crate::{Result, YamlDocument};
+    use std::borrow::Cow;
     use std::collections::HashMap;
     use std::fmt;
     use std::path::{Path, PathBuf};
     use std::sync::{Arc, Mutex, RwLock};
     use unity_asset_binary::asset::SerializedFile;
+    use unity_asset_binary::builtin_resources::{BuiltinReference, BuiltinResourceFile};
     use unity_asset_binary::bundle::AssetBundle;
+    use unity_asset_binary::data_source::{DataSource, VirtualFileSystem};
     use unity_asset_binary::file::{UnityFile, load_unity_file, load_unity_file_from_shared_range};
     use unity_asset_binary::object::{ObjectHandle, UnityObject};
+    use unity_asset_binary::string_policy::{StringPolicy, name_matches};
     use unity_asset_binary::typetree::TypeTreeRegistry;
     use unity_asset_binary::typetree::{
         CompositeTypeTreeRegistry, JsonTypeTreeRegistry, ScriptTypeTreeGenerator,
@@ -26,40 +31,52 @@ mod imp {
     };
     use unity_asset_binary::webfile::WebFile;
     use unity_asset_core::UnityValue;
-    use unity_asset_core::{UnityAssetError, UnityClass, UnityDocument};
+    use unity_asset_core::{UnityAssetError, UnityClass, UnityDocument, UnityVersion};
 
+    #[cfg(feature = "async")]
+    mod async_loader;
     mod container;
     mod dependency_files;
     mod dependency_graph;
     mod edit;
+    mod guid_graph;
     mod key;
     mod loader;
     mod meta_guid;
     mod object_graph;
     mod object_query;
     mod path;
+    mod player_data;
     mod pptr;
     mod pptr_path;
     mod save;
+    mod settings;
+    mod shader_audit;
     mod stream;
     mod streamed_write;
     mod typed;
+    #[cfg(feature = "unitypackage")]
+    mod unitypackage;
     mod yaml_edit;
     mod yaml_pptr;
     mod yaml_query;
     mod yaml_ui;
 
+    #[cfg(feature = "async")]
+    pub use async_loader::{AsyncLoadOptions, AsyncLoadStats};
     pub use dependency_graph::{
         DependencyGraphBuildOptions, DependencyGraphTraversalOptions, DependencyGraphWarning,
         EnvironmentDependencyGraph, ExternalDependencyEdge,
     };
     pub use edit::{EnvironmentEditSession, StreamedResourceWrite};
+    pub use guid_graph::{GuidGraph, GuidGraphEdge, GuidGraphNode};
     pub use loader::{ProjectLoadOptions, ProjectLoadStats};
     pub use object_graph::{
         EnvironmentObjectGraph, EnvironmentObjectKey, ExternalObjectEdge, ObjectGraphBuildOptions,
         ObjectGraphTraversalOptions, YamlExternalEdge, YamlObjectKey,
     };
-    pub use pptr::{BinaryPptrReference, PptrReferenceSearchOptions};
+    pub use player_data::PlayerDataLoadStats;
+    pub use pptr::{BinaryPptrReference, PptrReferenceSearchOptions, ResolvedPptr};
     pub use yaml_pptr::{YamlPptrReference, YamlPptrReferenceSearchOptions};
 
     #[derive(Debug, Clone)]
@@ -73,6 +90,13 @@ mod imp {
             doc_index: usize,
             error: String,
         },
+        /// A bundle lists the same entry name more than once. All entries are kept; name-based
+        /// lookups see the first one.
+        DuplicateBundleEntry {
+            source: BinarySource,
+            name: String,
+            count: usize,
+        },
     }
 
     impl fmt::Display for EnvironmentWarning {
@@ -92,6 +116,11 @@ mod imp {
                     doc_index,
                     error
                 ),
+                EnvironmentWarning::DuplicateBundleEntry {
+                    source,
+                    name,
+                    count,
+                } => write!(f, "Bundle {} lists entry {} {} times", source, name, count),
             }
         }
     }
@@ -108,7 +137,8 @@ mod imp {
         fn warn(&self, _warning: &EnvironmentWarning) {}
     }
 
-    #[derive(Debug, Clone, Copy)]
+    #[derive(Debug, Clone)]
+    #[non_exhaustive]
     pub struct EnvironmentOptions {
         pub typetree: TypeTreeParseOptions,
     }
@@ -116,19 +146,25 @@ mod imp {
     impl EnvironmentOptions {
         pub fn strict() -> Self {
             Self {
-                typetree: TypeTreeParseOptions {
-                    mode: TypeTreeParseMode::Strict,
-                },
+                typetree: TypeTreeParseOptions::default()
+                    .with_mode(TypeTreeParseMode::Strict)
+                    .with_strings(StringPolicy::Utf8Strict),
             }
         }
 
         pub fn lenient() -> Self {
             Self {
-                typetree: TypeTreeParseOptions {
-                    mode: TypeTreeParseMode::Lenient,
-                },
+                typetree: TypeTreeParseOptions::default()
+                    .with_mode(TypeTreeParseMode::Lenient)
+                    .with_strings(StringPolicy::Utf8Lossy),
             }
         }
+
+        /// Set `typetree`.
+        pub fn with_typetree(mut self, typetree: TypeTreeParseOptions) -> Self {
+            self.typetree = typetree;
+            self
+        }
     }
 
     impl Default for EnvironmentOptions {
@@ -140,7 +176,8 @@ mod imp {
     #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
     pub enum BinarySource {
         Path(PathBuf),
-        /// An entry inside a `.zip/.apk` archive loaded via `Environment::load_file`.
+        /// An entry inside a `.zip/.apk` archive (or, with the `unitypackage` feature, a
+        /// `.unitypackage`) loaded via `Environment::load_file`.
         ///
         /// UnityPy's zip loader treats entries as independent inputs (not as a container that is
         /// re-packed on save), so we keep this distinct from `WebEntry` which represents entries
@@ -200,7 +237,7 @@ mod imp {
         /// Asset index within a bundle. `None` for standalone serialized files.
         pub asset_index: Option<usize>,
         pub object: ObjectHandle<'a>,
-        typetree_options: TypeTreeParseOptions,
+        typetree_options: &'a TypeTreeParseOptions,
         reporter: Option<Arc<dyn EnvironmentReporter>>,
     }
 
@@ -219,7 +256,7 @@ mod imp {
         pub fn read(&self) -> Result<UnityObject> {
             let obj = self
                 .object
-                .read_with_options(self.typetree_options)
+                .read_with_options(self.typetree_options.clone())
                 .map_err(|e| {
                     UnityAssetError::format(format!("Failed to parse binary object: {}", e))
                 })?;
@@ -234,6 +271,16 @@ mod imp {
             Ok(obj)
         }
 
+        pub fn class_id(&self) -> i32 {
+            self.object.class_id()
+        }
+
+        /// Class name for the object's class ID (`Class_<id>` when unknown), without parsing it.
+        pub fn class_name(&self) -> String {
+            unity_asset_core::get_class_name(self.class_id())
+                .unwrap_or_else(|| format!("Class_{}", self.class_id()))
+        }
+
         /// Create a globally-unique key for this object reference.
         pub fn key(&self) -> BinaryObjectKey {
             BinaryObjectKey {
@@ -252,6 +299,32 @@ mod imp {
         Binary(BinaryObjectRef<'a>),
     }
 
+    impl<'a> EnvironmentObjectRef<'a> {
+        pub fn class_id(&self) -> i32 {
+            match self {
+                Self::Yaml(class) => class.class_id,
+                Self::Binary(object) => object.class_id(),
+            }
+        }
+
+        /// Class name; binary objects are named from their class ID without being parsed.
+        pub fn class_name(&self) -> Cow<'a, str> {
+            match self {
+                Self::Yaml(class) => Cow::Borrowed(class.class_name.as_str()),
+                Self::Binary(object) => Cow::Owned(object.class_name()),
+            }
+        }
+
+        /// The object as a `UnityClass`: borrowed for YAML, parsed from the TypeTree for binary
+        /// objects (properties are empty when the TypeTree is stripped).
+        pub fn to_unity_class(&self) -> Result<Cow<'a, UnityClass>> {
+            match self {
+                Self::Yaml(class) => Ok(Cow::Borrowed(*class)),
+                Self::Binary(object) => Ok(Cow::Owned(object.read()?.class)),
+            }
+        }
+    }
+
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub enum BinarySourceKind {
         SerializedFile,
@@ -290,6 +363,10 @@ mod imp {
         /// Loaded AssetBundles (e.g. `.bundle`, `.unity3d`, `.ab`)
         bundles: HashMap<BinarySource, AssetBundle>,
         webfiles: HashMap<PathBuf, WebFile>,
+        /// Registered `.resS` / `.resource` files, keyed by lowercased file name
+        resource_files: HashMap<String, PathBuf>,
+        /// Registered in-memory / custom `.resS` / `.resource` sources
+        resource_sources: VirtualFileSystem,
         bundle_container_cache: RwLock<HashMap<BinarySource, Vec<BundleContainerEntry>>>,
         dependency_scan_cache: RwLock<dependency_graph::DependencyScanCache>,
         dependency_file_index: RwLock<dependency_files::DependencyFileIndex>,
@@ -301,6 +378,8 @@ mod imp {
         script_type_tree_registry: Option<Arc<dyn TypeTreeRegistry>>,
         type_tree_registry: Option<Arc<dyn TypeTreeRegistry>>,
         write_state: edit::EnvironmentWriteState,
+        /// Editor version of the loaded project (from `ProjectSettings/ProjectVersion.txt`)
+        unity_version: Option<UnityVersion>,
         /// Base path for relative file resolution
         #[allow(dead_code)]
         base_path: PathBuf,
@@ -318,6 +397,8 @@ mod imp {
                 binary_assets: HashMap::new(),
                 bundles: HashMap::new(),
                 webfiles: HashMap::new(),
+                resource_files: HashMap::new(),
+                resource_sources: VirtualFileSystem::new(),
                 bundle_container_cache: RwLock::new(HashMap::new()),
                 dependency_scan_cache: RwLock::new(HashMap::new()),
                 dependency_file_index: RwLock::new(dependency_files::DependencyFileIndex::default()),
@@ -329,6 +410,7 @@ mod imp {
                 script_type_tree_registry: None,
                 type_tree_registry: None,
                 write_state: edit::EnvironmentWriteState::default(),
+                unity_version: None,
                 base_path: std::env::current_dir().unwrap_or_default(),
             }
         }
@@ -432,8 +514,23 @@ mod imp {
             Ok(())
         }
 
+        /// The project's Unity editor version, if a `ProjectVersion.txt` has been loaded.
+        pub fn unity_version(&self) -> Option<&UnityVersion> {
+            self.unity_version.as_ref()
+        }
+
+        /// Override the project's Unity editor version.
+        ///
+        /// Loaded YAML documents are updated so version-gated YAML handling sees the same value.
+        pub fn set_unity_version(&mut self, version: Option<UnityVersion>) {
+            for doc in self.yaml_documents.values_mut() {
+                doc.set_unity_version(version.clone());
+            }
+            self.unity_version = version;
+        }
+
         pub fn options(&self) -> EnvironmentOptions {
-            self.options
+            self.options.clone()
         }
 
         pub fn warnings(&self) -> Vec<EnvironmentWarning> {
@@ -453,6 +550,16 @@ mod imp {
             }
         }
 
+        fn warn_duplicate_bundle_entries(&self, source: &BinarySource, bundle: &AssetBundle) {
+            for duplicate in bundle.duplicate_node_names() {
+                self.push_warning(EnvironmentWarning::DuplicateBundleEntry {
+                    source: source.clone(),
+                    name: duplicate.name,
+                    count: duplicate.indices.len(),
+                });
+            }
+        }
+
         fn push_warning(&self, warning: EnvironmentWarning) {
             match self.warnings.lock() {
                 Ok(mut warnings) => warnings.push(warning.clone()),
@@ -486,12 +593,25 @@ mod imp {
         }
 
         /// Filter YAML objects by class name.
+        ///
+        /// See [`Environment::objects_by_class`] to include binary objects.
         pub fn filter_by_class(&self, class_name: &str) -> Vec<&UnityClass> {
             self.yaml_objects()
                 .filter(|obj| obj.class_name == class_name)
                 .collect()
         }
 
+        /// Iterate YAML and binary objects of one class, whichever container they came from.
+        ///
+        /// Binary objects are matched on their class ID and only parsed when read.
+        pub fn objects_by_class<'a>(
+            &'a self,
+            class_name: &'a str,
+        ) -> impl Iterator<Item = EnvironmentObjectRef<'a>> + 'a {
+            self.objects()
+                .filter(move |object| object.class_name() == class_name)
+        }
+
         /// Get loaded YAML documents
         pub fn yaml_documents(&self) -> &HashMap<PathBuf, YamlDocument> {
             &self.yaml_documents
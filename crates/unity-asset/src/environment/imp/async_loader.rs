@@ -0,0 +1,257 @@
+use super::loader::{
+    PROJECT_VERSION_FILE_NAME, SKIPPED_DIRECTORY_NAMES, is_secondary_split_part, is_yaml_asset_path,
+};
+use super::path::canonicalize_if_exists;
+use super::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::Semaphore;
+use unity_asset_yaml::serde_unity_loader::SerdeUnityWarning;
+
+/// Options for [`Environment::load_directory_async_with_options`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct AsyncLoadOptions {
+    /// Maximum number of YAML files being read/parsed at the same time (clamped to at least 1).
+    pub max_concurrent_files: usize,
+}
+
+impl AsyncLoadOptions {
+    /// Set `max_concurrent_files`.
+    pub fn with_max_concurrent_files(mut self, max_concurrent_files: usize) -> Self {
+        self.max_concurrent_files = max_concurrent_files;
+        self
+    }
+}
+
+impl Default for AsyncLoadOptions {
+    fn default() -> Self {
+        Self {
+            max_concurrent_files: 16,
+        }
+    }
+}
+
+/// Counters reported by [`Environment::load_directory_async_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AsyncLoadStats {
+    /// Files found by the directory walk (after skipping `Library`, `Temp`, ...).
+    pub files_discovered: usize,
+    /// YAML files read concurrently (`.asset`, `.prefab`, `.unity`, `.meta`).
+    pub yaml_files: usize,
+    /// Files handed to the synchronous loader (bundles, serialized files, archives, ...).
+    pub other_files: usize,
+    /// Files that failed to load (recorded as [`EnvironmentWarning::LoadFailed`]).
+    pub failed_files: usize,
+    /// Highest number of YAML files in flight at once; never exceeds `max_concurrent_files`.
+    pub peak_concurrent_files: usize,
+}
+
+type YamlLoadResult = Result<(YamlDocument, Vec<SerdeUnityWarning>)>;
+
+impl Environment {
+    /// Async counterpart of [`Environment::load`].
+    pub async fn load_async<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let path = path.as_ref();
+
+        if path.is_file() {
+            if is_yaml_asset_path(path) {
+                let path = canonicalize_if_exists(path);
+                if let Some(parent) = path.parent() {
+                    self.base_path = parent.to_path_buf();
+                }
+                let (loaded, meta_text) = load_yaml_file(path.clone()).await;
+                if let Some(text) = meta_text {
+                    self.index_meta_guid_text(&path, &text);
+                }
+                self.insert_yaml_or_binary(path, loaded)?;
+            } else {
+                self.load_file(path)?;
+            }
+        } else if path.is_dir() {
+            self.load_directory_async(path).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Async counterpart of [`Environment::load_directory`].
+    ///
+    /// See [`Environment::load_directory_async_with_options`].
+    pub async fn load_directory_async<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        self.load_directory_async_with_options(path, AsyncLoadOptions::default())
+            .await
+            .map(|_| ())
+    }
+
+    /// Load all supported files from a directory, reading YAML files concurrently.
+    ///
+    /// - the directory is walked with `tokio::fs` (same skip list as `load_directory`)
+    /// - `ProjectVersion.txt` is applied first, so every YAML document carries the project version
+    /// - YAML files are read with `tokio::fs` and parsed on `spawn_blocking`, at most
+    ///   `options.max_concurrent_files` at a time
+    /// - results are merged in sorted path order, so documents and warnings do not depend on
+    ///   completion order
+    /// - remaining files (bundles, serialized files, archives) go through the synchronous loader
+    ///
+    /// Per-file failures are recorded as [`EnvironmentWarning::LoadFailed`], as in `load_directory`.
+    pub async fn load_directory_async_with_options<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        options: AsyncLoadOptions,
+    ) -> Result<AsyncLoadStats> {
+        let path = path.as_ref();
+
+        let metadata = tokio::fs::metadata(path).await.map_err(|_| {
+            UnityAssetError::format(format!("Directory does not exist: {:?}", path))
+        })?;
+        if !metadata.is_dir() {
+            return Err(UnityAssetError::format(format!(
+                "Path is not a directory: {:?}",
+                path
+            )));
+        }
+
+        let root = canonicalize_if_exists(path);
+        let files = collect_files(&root).await?;
+
+        let mut stats = AsyncLoadStats {
+            files_discovered: files.len(),
+            ..Default::default()
+        };
+
+        let (yaml_files, other_files): (Vec<PathBuf>, Vec<PathBuf>) =
+            files.into_iter().partition(|p| is_yaml_asset_path(p));
+        let (version_files, other_files): (Vec<PathBuf>, Vec<PathBuf>) =
+            other_files.into_iter().partition(|p| {
+                p.file_name()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(PROJECT_VERSION_FILE_NAME))
+            });
+
+        for path in version_files {
+            let loaded = match tokio::fs::read_to_string(&path).await {
+                Ok(text) => UnityVersion::parse_project_version(&text)
+                    .map(|version| self.set_unity_version(Some(version))),
+                Err(e) => Err(UnityAssetError::with_source(
+                    format!("Failed to read {:?}", path),
+                    e,
+                )),
+            };
+            if let Err(e) = loaded {
+                stats.failed_files += 1;
+                self.push_warning(EnvironmentWarning::LoadFailed {
+                    path,
+                    error: e.to_string(),
+                });
+            }
+        }
+
+        // Spawn every YAML load up front; the semaphore bounds how many actually run.
+        let semaphore = Arc::new(Semaphore::new(options.max_concurrent_files.max(1)));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let mut tasks = Vec::with_capacity(yaml_files.len());
+        for path in yaml_files {
+            let semaphore = Arc::clone(&semaphore);
+            let in_flight = Arc::clone(&in_flight);
+            let peak = Arc::clone(&peak);
+            let task_path = path.clone();
+            let handle = tokio::spawn(async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed");
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                let out = load_yaml_file(task_path).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                out
+            });
+            tasks.push((path, handle));
+        }
+
+        stats.yaml_files = tasks.len();
+        for (path, handle) in tasks {
+            let result = match handle.await {
+                Ok((loaded, meta_text)) => {
+                    if let Some(text) = meta_text {
+                        self.index_meta_guid_text(&path, &text);
+                    }
+                    self.insert_yaml_or_binary(path.clone(), loaded)
+                }
+                Err(e) => Err(UnityAssetError::with_source("YAML load task failed", e)),
+            };
+            if let Err(e) = result {
+                stats.failed_files += 1;
+                self.push_warning(EnvironmentWarning::LoadFailed {
+                    path,
+                    error: e.to_string(),
+                });
+            }
+        }
+        stats.peak_concurrent_files = peak.load(Ordering::SeqCst);
+
+        // Secondary `.splitN` chunks load with their `.split0`.
+        let other_files: Vec<PathBuf> = other_files
+            .into_iter()
+            .filter(|p| !is_secondary_split_part(p))
+            .collect();
+        stats.other_files = other_files.len();
+        for path in other_files {
+            if !self.load_file_or_warn(&path) {
+                stats.failed_files += 1;
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+/// Load one YAML file; `.meta` files also return their text for GUID indexing.
+async fn load_yaml_file(path: PathBuf) -> (YamlLoadResult, Option<String>) {
+    let meta_text = if path.extension().and_then(|e| e.to_str()) == Some("meta") {
+        tokio::fs::read_to_string(&path).await.ok()
+    } else {
+        None
+    };
+    let loaded = YamlDocument::load_yaml_async_with_warnings(&path, false).await;
+    (loaded, meta_text)
+}
+
+/// Walk `root` with `tokio::fs`, returning all files in sorted order.
+async fn collect_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&dir).await.map_err(|e| {
+            UnityAssetError::with_source(format!("Failed to read directory {:?}", dir), e)
+        })?;
+
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| UnityAssetError::with_source("Failed to read directory entry", e))?
+        {
+            let path = entry.path();
+            // Follow symlinks, like `Path::is_dir` / `Path::is_file` in the sync walk.
+            let Ok(metadata) = tokio::fs::metadata(&path).await else {
+                continue;
+            };
+            if metadata.is_dir() {
+                let skipped = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_none_or(|name| SKIPPED_DIRECTORY_NAMES.contains(&name));
+                if !skipped {
+                    pending.push(path);
+                }
+            } else if metadata.is_file() {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
@@ -201,7 +201,7 @@ impl Environment {
         })?;
 
         let mut out: Vec<BundleContainerEntry> = Vec::new();
-        let typetree_options = self.options.typetree;
+        let typetree_options = &self.options.typetree;
         let reporter = self.reporter.clone();
 
         for (asset_index, file) in bundle.assets.iter().enumerate() {
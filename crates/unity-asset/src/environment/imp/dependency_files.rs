@@ -93,6 +93,17 @@ impl DependencyFileIndex {
             let Some(simple) = simplify_name_for_lookup(&path) else {
                 continue;
             };
+            // `foo.assets.split0` also answers lookups for `foo.assets`; loading it reassembles
+            // the chunks.
+            if let Some(base) = unity_asset_binary::split::split_base_path(&path)
+                && path.extension().is_some_and(|ext| ext == "split0")
+                && let Some(base_simple) = simplify_name_for_lookup(&base)
+            {
+                self.by_simple_name
+                    .entry(base_simple)
+                    .or_default()
+                    .push(path.clone());
+            }
             self.by_simple_name.entry(simple).or_default().push(path);
             stats.files_indexed += 1;
         }
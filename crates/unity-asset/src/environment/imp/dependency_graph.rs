@@ -16,6 +16,7 @@ pub(crate) type DependencyScanCache = std::collections::HashMap<BinaryObjectKey,
 
 /// Build options for `Environment` dependency graph extraction.
 #[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub struct DependencyGraphBuildOptions {
     /// Include objects that cannot be scanned due to missing TypeTree.
     ///
@@ -28,6 +29,26 @@ pub struct DependencyGraphBuildOptions {
     pub max_objects: Option<usize>,
 }
 
+impl DependencyGraphBuildOptions {
+    /// Set `include_no_typetree_warnings`.
+    pub fn with_include_no_typetree_warnings(mut self, include_no_typetree_warnings: bool) -> Self {
+        self.include_no_typetree_warnings = include_no_typetree_warnings;
+        self
+    }
+
+    /// Set `continue_on_error`.
+    pub fn with_continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Set `max_objects`.
+    pub fn with_max_objects(mut self, max_objects: Option<usize>) -> Self {
+        self.max_objects = max_objects;
+        self
+    }
+}
+
 impl Default for DependencyGraphBuildOptions {
     fn default() -> Self {
         Self {
@@ -49,6 +70,8 @@ pub struct ExternalDependencyEdge {
     pub from: BinaryObjectKey,
     pub target: unity_asset_binary::metadata::ExternalObjectRef,
     pub resolved: Option<BinaryObjectKey>,
+    /// Set when the edge points into Unity's built-in resources and is not otherwise resolved.
+    pub builtin: Option<BuiltinReference>,
 }
 
 /// A best-effort dependency graph across all loaded binary sources in an `Environment`.
@@ -66,6 +89,7 @@ pub struct EnvironmentDependencyGraph {
 }
 
 #[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
 pub struct DependencyGraphTraversalOptions {
     pub max_depth: Option<usize>,
     pub max_nodes: Option<usize>,
@@ -73,6 +97,26 @@ pub struct DependencyGraphTraversalOptions {
     pub follow_resolved_external: bool,
 }
 
+impl DependencyGraphTraversalOptions {
+    /// Set `max_depth`.
+    pub fn with_max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Set `max_nodes`.
+    pub fn with_max_nodes(mut self, max_nodes: Option<usize>) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// Set `follow_resolved_external`.
+    pub fn with_follow_resolved_external(mut self, follow_resolved_external: bool) -> Self {
+        self.follow_resolved_external = follow_resolved_external;
+        self
+    }
+}
+
 impl EnvironmentDependencyGraph {
     pub fn nodes(&self) -> &[BinaryObjectKey] {
         &self.nodes
@@ -93,6 +137,13 @@ impl EnvironmentDependencyGraph {
             .sum()
     }
 
+    pub fn builtin_external_edge_count(&self) -> usize {
+        self.external_from
+            .values()
+            .map(|v| v.iter().filter(|e| e.builtin.is_some()).count())
+            .sum()
+    }
+
     pub fn warnings(&self) -> &[DependencyGraphWarning] {
         &self.warnings
     }
@@ -666,7 +717,7 @@ impl Environment {
                     source_kind,
                     asset_index,
                     object: handle,
-                    typetree_options: self.options.typetree,
+                    typetree_options: &self.options.typetree,
                     reporter: self.reporter.clone(),
                 };
 
@@ -756,13 +807,19 @@ impl Environment {
                         file_path,
                         guid,
                     };
-                    let resolved = self.resolve_binary_pptr(&obj_ref, file_id, path_id);
+                    let (resolved, builtin) =
+                        match self.resolve_binary_pptr_or_builtin(&obj_ref, file_id, path_id) {
+                            Some(ResolvedPptr::Object(key)) => (Some(key), None),
+                            Some(ResolvedPptr::Builtin(builtin)) => (None, Some(builtin)),
+                            None => (None, None),
+                        };
 
                     external_from.entry(from_key.clone()).or_default().push(
                         ExternalDependencyEdge {
                             from: from_key.clone(),
                             target,
                             resolved,
+                            builtin,
                         },
                     );
                 }
@@ -0,0 +1,310 @@
+use super::yaml_pptr::scan_yaml_pptrs_with_paths;
+use super::*;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use unity_asset_core::UnityGuid;
+
+/// An asset in a [`GuidGraph`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GuidGraphNode {
+    pub guid: UnityGuid,
+    /// Asset path from the `.meta` index; `None` for GUIDs that are only referenced (missing or
+    /// built-in assets).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Class of the asset's main object: `SceneAsset` for scenes, otherwise the class of the first
+    /// YAML object. `None` when the asset is not loaded as YAML (textures, scripts, models, ...).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub main_class: Option<String>,
+}
+
+/// One `{fileID, guid}` reference from an object of one asset into another asset.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct GuidGraphEdge {
+    pub from: UnityGuid,
+    pub to: UnityGuid,
+    /// YAML anchor of the referencing object.
+    pub anchor: String,
+    /// Field path of the reference inside that object (e.g. `m_Materials[0]`).
+    pub field_path: String,
+    /// `fileID` of the referenced object inside the target asset.
+    pub file_id: i64,
+}
+
+/// GUID-level dependency graph of a source project, built from `{fileID, guid}` references in
+/// loaded YAML assets (see [`Environment::guid_dependency_graph`]).
+///
+/// Nodes are keyed by asset GUID; edges keep the referencing object and field path, so an asset
+/// that references another from several fields has several edges to it.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GuidGraph {
+    nodes: Vec<GuidGraphNode>,
+    edges: Vec<GuidGraphEdge>,
+    roots: Vec<UnityGuid>,
+    #[serde(skip)]
+    node_index: HashMap<UnityGuid, usize>,
+}
+
+/// `.meta` GUIDs are indexed as the bytes of their hex digits; `UnityGuid` keeps the serialized
+/// file layout, with the two digits of each byte swapped.
+fn unity_guid(meta_bytes: [u8; 16]) -> UnityGuid {
+    UnityGuid(meta_bytes.map(|b| b.rotate_left(4)))
+}
+
+impl GuidGraph {
+    /// All nodes, sorted by GUID.
+    pub fn nodes(&self) -> &[GuidGraphNode] {
+        &self.nodes
+    }
+
+    pub fn node(&self, guid: &UnityGuid) -> Option<&GuidGraphNode> {
+        self.node_index.get(guid).map(|&i| &self.nodes[i])
+    }
+
+    /// All edges, sorted by `(from, to, anchor, field_path)`.
+    pub fn edges(&self) -> &[GuidGraphEdge] {
+        &self.edges
+    }
+
+    /// Scenes enabled in `EditorBuildSettings`, in build order.
+    pub fn roots(&self) -> &[UnityGuid] {
+        &self.roots
+    }
+
+    /// Edges leaving `guid`.
+    pub fn edges_from<'a>(
+        &'a self,
+        guid: &UnityGuid,
+    ) -> impl Iterator<Item = &'a GuidGraphEdge> + use<'a> {
+        let guid = *guid;
+        self.edges.iter().filter(move |e| e.from == guid)
+    }
+
+    /// Edges pointing at `guid`.
+    pub fn edges_to<'a>(
+        &'a self,
+        guid: &UnityGuid,
+    ) -> impl Iterator<Item = &'a GuidGraphEdge> + use<'a> {
+        let guid = *guid;
+        self.edges.iter().filter(move |e| e.to == guid)
+    }
+
+    /// Assets `guid` references directly, sorted and deduplicated.
+    pub fn dependencies_of(&self, guid: &UnityGuid) -> Vec<UnityGuid> {
+        let set: BTreeSet<UnityGuid> = self.edges_from(guid).map(|e| e.to).collect();
+        set.into_iter().collect()
+    }
+
+    /// Assets that reference `guid` directly, sorted and deduplicated.
+    pub fn dependents_of(&self, guid: &UnityGuid) -> Vec<UnityGuid> {
+        let set: BTreeSet<UnityGuid> = self.edges_to(guid).map(|e| e.from).collect();
+        set.into_iter().collect()
+    }
+
+    /// `roots` and every asset reachable from them.
+    pub fn reachable_from(&self, roots: &[UnityGuid]) -> HashSet<UnityGuid> {
+        let mut visited: HashSet<UnityGuid> = HashSet::new();
+        let mut queue: VecDeque<UnityGuid> = roots.iter().copied().collect();
+        while let Some(guid) = queue.pop_front() {
+            if !visited.insert(guid) {
+                continue;
+            }
+            for edge in self.edges_from(&guid) {
+                if !visited.contains(&edge.to) {
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+        visited
+    }
+
+    /// Assets on disk that are not reachable from the build scenes ([`GuidGraph::roots`]).
+    ///
+    /// Without an `EditorBuildSettings` there are no roots and every asset is reported.
+    pub fn unreferenced(&self) -> Vec<&GuidGraphNode> {
+        self.unreferenced_from(&self.roots)
+    }
+
+    /// Assets on disk that are not reachable from `roots`. Folders and GUIDs without a path are
+    /// never reported.
+    pub fn unreferenced_from(&self, roots: &[UnityGuid]) -> Vec<&GuidGraphNode> {
+        let reachable = self.reachable_from(roots);
+        self.nodes
+            .iter()
+            .filter(|node| !reachable.contains(&node.guid))
+            .filter(|node| node.path.as_ref().is_some_and(|p| !p.is_dir()))
+            .collect()
+    }
+
+    /// Graphviz DOT; nodes are labeled with their file name and main class, edges with the field
+    /// path.
+    pub fn to_dot(&self, max_edges: usize) -> String {
+        fn escape(s: &str) -> String {
+            s.replace('\\', "\\\\").replace('"', "\\\"")
+        }
+
+        let mut out = String::new();
+        out.push_str("digraph unity_asset_guid_graph {\n");
+        for node in &self.nodes {
+            let name = node
+                .path
+                .as_ref()
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| node.guid.to_string());
+            let label = match &node.main_class {
+                Some(class) => format!("{}\\n{}", escape(&name), escape(class)),
+                None => escape(&name),
+            };
+            out.push_str(&format!("  \"{}\" [label=\"{}\"];\n", node.guid, label));
+        }
+
+        for edge in self.edges.iter().take(max_edges) {
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                edge.from,
+                edge.to,
+                escape(&edge.field_path)
+            ));
+        }
+        if self.edges.len() > max_edges {
+            out.push_str(&format!("  // truncated: max_edges={max_edges}\n"));
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// JSON object with `nodes`, `edges` and `roots`.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| {
+            UnityAssetError::with_source("Failed to serialize GUID dependency graph", e)
+        })
+    }
+}
+
+impl Environment {
+    /// Build the GUID-level dependency graph of the loaded YAML assets.
+    ///
+    /// Every GUID in the `.meta` index is a node. Each loaded YAML document whose asset has a
+    /// `.meta` GUID contributes one edge per `{fileID, guid}` reference to another asset; references
+    /// to GUIDs outside the index add nodes without a path. Roots are the enabled scenes of a
+    /// loaded `ProjectSettings/EditorBuildSettings.asset`.
+    ///
+    /// Load the project with [`Environment::load_project`] (which indexes `.meta` GUIDs and loads
+    /// YAML assets) before calling this.
+    pub fn guid_dependency_graph(&self) -> GuidGraph {
+        let indexed: Vec<(UnityGuid, PathBuf)> = match self.meta_guid_cache.read() {
+            Ok(cache) => cache
+                .iter()
+                .map(|(g, p)| (unity_guid(*g), p.clone()))
+                .collect(),
+            Err(e) => e
+                .into_inner()
+                .iter()
+                .map(|(g, p)| (unity_guid(*g), p.clone()))
+                .collect(),
+        };
+        let guid_by_path: HashMap<&Path, UnityGuid> =
+            indexed.iter().map(|(g, p)| (p.as_path(), *g)).collect();
+
+        let mut nodes: HashMap<UnityGuid, GuidGraphNode> = indexed
+            .iter()
+            .map(|(guid, path)| {
+                (
+                    *guid,
+                    GuidGraphNode {
+                        guid: *guid,
+                        path: Some(path.clone()),
+                        main_class: None,
+                    },
+                )
+            })
+            .collect();
+        let mut edges: Vec<GuidGraphEdge> = Vec::new();
+
+        for (path, doc) in &self.yaml_documents {
+            let Some(&from) = guid_by_path.get(path.as_path()) else {
+                continue;
+            };
+            if let Some(node) = nodes.get_mut(&from) {
+                node.main_class = if path.extension().is_some_and(|e| e == "unity") {
+                    Some("SceneAsset".to_string())
+                } else {
+                    doc.entries().first().map(|obj| obj.class_name.clone())
+                };
+            }
+
+            for obj in doc.entries() {
+                for pptr in scan_yaml_pptrs_with_paths(obj, None) {
+                    let Some(to) = pptr.guid.map(unity_guid) else {
+                        continue;
+                    };
+                    if to == from {
+                        continue;
+                    }
+                    nodes.entry(to).or_insert_with(|| GuidGraphNode {
+                        guid: to,
+                        path: None,
+                        main_class: None,
+                    });
+                    edges.push(GuidGraphEdge {
+                        from,
+                        to,
+                        anchor: obj.anchor.clone(),
+                        field_path: pptr.path,
+                        file_id: pptr.file_id,
+                    });
+                }
+            }
+        }
+
+        let mut nodes: Vec<GuidGraphNode> = nodes.into_values().collect();
+        nodes.sort_by_key(|node| node.guid);
+        edges.sort();
+        let node_index = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.guid, i))
+            .collect();
+
+        GuidGraph {
+            nodes,
+            edges,
+            roots: self.build_scene_guids(),
+            node_index,
+        }
+    }
+
+    /// GUIDs of the enabled scenes in `EditorBuildSettings` (`m_Scenes`), in build order.
+    fn build_scene_guids(&self) -> Vec<UnityGuid> {
+        let Some(settings) = self
+            .yaml_objects()
+            .find(|obj| obj.class_name == "EditorBuildSettings")
+        else {
+            return Vec::new();
+        };
+        let Some(scenes) = settings.get("m_Scenes").and_then(|v| v.as_array()) else {
+            return Vec::new();
+        };
+
+        scenes
+            .iter()
+            .filter(|scene| {
+                scene
+                    .as_object()
+                    .and_then(|m| m.get("enabled"))
+                    .and_then(|v| v.as_i64())
+                    .is_none_or(|enabled| enabled != 0)
+            })
+            .filter_map(|scene| {
+                scene
+                    .as_object()
+                    .and_then(|m| m.get("guid"))
+                    .and_then(|v| v.as_str())
+                    .and_then(super::meta_guid::parse_guid_32_hex)
+                    .map(unity_guid)
+            })
+            .collect()
+    }
+}
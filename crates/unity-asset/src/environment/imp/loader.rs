@@ -3,11 +3,54 @@ use super::path::find_sensitive_path;
 use super::*;
 use std::fs::File;
 use std::io::Read;
+use unity_asset_binary::error::BinaryError;
 use unity_asset_binary::file::load_unity_file_from_memory;
 use unity_asset_binary::file::{UnityFileKind, sniff_unity_file_kind_prefix};
+use unity_asset_binary::split;
+use unity_asset_yaml::serde_unity_loader::SerdeUnityWarning;
 use zip::ZipArchive;
 
+/// Directory names skipped by directory traversal (they never hold loadable assets).
+pub(super) const SKIPPED_DIRECTORY_NAMES: &[&str] =
+    &["Library", "Temp", "Logs", ".git", ".vs", "obj", "bin"];
+
+/// Whether `path` has one of the Unity YAML text asset extensions.
+pub(super) fn is_yaml_asset_path(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some(
+            "asset"
+                | "prefab"
+                | "unity"
+                | "meta"
+                | "mat"
+                | "anim"
+                | "controller"
+                | "overrideController"
+                | "mask"
+                | "physicMaterial"
+                | "physicsMaterial2D"
+                | "mixer"
+                | "playable"
+                | "renderTexture"
+                | "spriteatlas"
+                | "lighting"
+                | "flare"
+                | "guiskin"
+                | "fontsettings"
+                | "terrainlayer"
+                | "brush"
+                | "preset"
+                | "signal"
+        )
+    )
+}
+
+/// File name of the editor version marker under `ProjectSettings/`.
+pub(super) const PROJECT_VERSION_FILE_NAME: &str = "ProjectVersion.txt";
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct MetaGuidIndexStats {
     pub dirs_visited: usize,
     pub files_visited: usize,
@@ -16,6 +59,7 @@ pub struct MetaGuidIndexStats {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub struct ProjectLoadOptions {
     /// Index `.meta` GUIDs under the project root for best-effort external reference resolution.
     pub index_meta_guids: bool,
@@ -61,6 +105,48 @@ impl ProjectLoadOptions {
             follow_symlinks: false,
         }
     }
+
+    /// Set `index_meta_guids`.
+    pub fn with_index_meta_guids(mut self, index_meta_guids: bool) -> Self {
+        self.index_meta_guids = index_meta_guids;
+        self
+    }
+
+    /// Set `load_yaml_documents`.
+    pub fn with_load_yaml_documents(mut self, load_yaml_documents: bool) -> Self {
+        self.load_yaml_documents = load_yaml_documents;
+        self
+    }
+
+    /// Set `load_meta_documents`.
+    pub fn with_load_meta_documents(mut self, load_meta_documents: bool) -> Self {
+        self.load_meta_documents = load_meta_documents;
+        self
+    }
+
+    /// Set `load_binary_files`.
+    pub fn with_load_binary_files(mut self, load_binary_files: bool) -> Self {
+        self.load_binary_files = load_binary_files;
+        self
+    }
+
+    /// Set `max_files`.
+    pub fn with_max_files(mut self, max_files: Option<usize>) -> Self {
+        self.max_files = max_files;
+        self
+    }
+
+    /// Set `respect_ignores`.
+    pub fn with_respect_ignores(mut self, respect_ignores: bool) -> Self {
+        self.respect_ignores = respect_ignores;
+        self
+    }
+
+    /// Set `follow_symlinks`.
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
 }
 
 impl Default for ProjectLoadOptions {
@@ -70,6 +156,7 @@ impl Default for ProjectLoadOptions {
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct ProjectLoadStats {
     pub files_visited: usize,
     pub files_loaded: usize,
@@ -112,12 +199,19 @@ impl Environment {
         // UnityPy split-file convention: `<base>.split0/.split1/...`.
         if !path.exists() {
             // If a base path is provided, attempt loading `<path>.split0`.
-            let split0 = append_suffix(&path, ".split0");
+            let split0 = split::split_part_path(&path, 0);
             if split0.exists() {
                 path = split0;
             }
         }
 
+        if path
+            .file_name()
+            .is_some_and(|name| name.eq_ignore_ascii_case(PROJECT_VERSION_FILE_NAME))
+        {
+            return self.load_project_version(&path);
+        }
+
         if let Some(ext) = path.extension().and_then(|e| e.to_str())
             && ext.starts_with("split")
             && ext[5..].parse::<usize>().is_ok()
@@ -135,35 +229,22 @@ impl Environment {
                 return Ok(());
             }
 
+            #[cfg(feature = "unitypackage")]
+            if ext.to_string_lossy().eq_ignore_ascii_case("unitypackage") {
+                return self.load_unitypackage(&path);
+            }
+
             if ext == "meta" {
                 // Index meta GUIDs even if YAML parsing fails (best-effort reference resolution).
                 let _ = self.index_meta_guid_path(&path);
             }
 
-            match ext.to_str() {
-                Some("asset") | Some("prefab") | Some("unity") | Some("meta") => {
-                    match YamlDocument::load_yaml_with_warnings(&path, false) {
-                        Ok((doc, warnings)) => {
-                            for w in warnings {
-                                self.push_warning(EnvironmentWarning::YamlDocumentSkipped {
-                                    path: path.clone(),
-                                    doc_index: w.doc_index,
-                                    error: w.error,
-                                });
-                            }
-                            self.yaml_documents.insert(path.clone(), doc);
-                        }
-                        Err(_) => {
-                            // Some Unity projects can store `.asset`-like files in binary form.
-                            // If YAML parsing fails, fall back to binary detection.
-                            self.try_load_binary(&path)?;
-                        }
-                    }
-                }
-                _ => {
-                    // Best-effort binary detection for common build outputs.
-                    self.try_load_binary(&path)?;
-                }
+            if is_yaml_asset_path(&path) {
+                let loaded = YamlDocument::load_yaml_with_warnings(&path, false);
+                self.insert_yaml_or_binary(path, loaded)?;
+            } else {
+                // Best-effort binary detection for common build outputs.
+                self.try_load_binary(&path)?;
             }
         } else {
             // Some Unity outputs (especially streamed resources and certain build artifacts)
@@ -174,6 +255,43 @@ impl Environment {
         Ok(())
     }
 
+    /// Store a parsed YAML document, or fall back to binary detection if parsing failed.
+    pub(super) fn insert_yaml_or_binary(
+        &mut self,
+        path: PathBuf,
+        loaded: Result<(YamlDocument, Vec<SerdeUnityWarning>)>,
+    ) -> Result<()> {
+        match loaded {
+            Ok((mut doc, warnings)) => {
+                doc.set_unity_version(self.unity_version.clone());
+                for w in warnings {
+                    self.push_warning(EnvironmentWarning::YamlDocumentSkipped {
+                        path: path.clone(),
+                        doc_index: w.doc_index,
+                        error: w.error,
+                    });
+                }
+                self.yaml_documents.insert(path, doc);
+                Ok(())
+            }
+            Err(_) => {
+                // Some Unity projects can store `.asset`-like files in binary form.
+                // If YAML parsing fails, fall back to binary detection.
+                self.try_load_binary(&path)
+            }
+        }
+    }
+
+    /// Load a `ProjectSettings/ProjectVersion.txt` file and record the project's editor version.
+    pub fn load_project_version<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| UnityAssetError::with_source(format!("Failed to read {:?}", path), e))?;
+        let version = UnityVersion::parse_project_version(&text)?;
+        self.set_unity_version(Some(version));
+        Ok(())
+    }
+
     fn try_load_split_file(&mut self, split_part_path: &Path) -> Result<()> {
         let base = split_part_path.with_extension("");
         let base_key = strip_verbatim_prefix(&base);
@@ -243,7 +361,7 @@ impl Environment {
         Ok(())
     }
 
-    fn try_load_unity_bytes(&mut self, source: BinarySource, bytes: Vec<u8>) {
+    pub(super) fn try_load_unity_bytes(&mut self, source: BinarySource, bytes: Vec<u8>) {
         let Ok(unity_file) = load_unity_file_from_memory(bytes) else {
             return;
         };
@@ -260,6 +378,7 @@ impl Environment {
                     BinarySourceKind::AssetBundle,
                     None,
                 );
+                self.warn_duplicate_bundle_entries(&source, &bundle);
                 self.bundles.insert(source.clone(), bundle);
                 match self.bundle_container_cache.write() {
                     Ok(mut cache) => {
@@ -378,7 +497,7 @@ impl Environment {
         Ok(stats)
     }
 
-    fn try_load_binary(&mut self, path: &Path) -> Result<()> {
+    pub(super) fn try_load_binary(&mut self, path: &Path) -> Result<()> {
         match load_unity_file(path) {
             Ok(UnityFile::AssetBundle(bundle)) => {
                 let mut bundle = bundle;
@@ -393,6 +512,7 @@ impl Environment {
                     BinarySourceKind::AssetBundle,
                     None,
                 );
+                self.warn_duplicate_bundle_entries(&source, &bundle);
                 self.bundles.insert(source.clone(), bundle);
                 match self.bundle_container_cache.write() {
                     Ok(mut cache) => {
@@ -426,7 +546,14 @@ impl Environment {
                 self.webfiles.insert(web_path.clone(), web);
                 self.load_webfile_entries(&web_path)?;
             }
-            Err(_) => {}
+            // Not a recognized Unity binary file: nothing to load.
+            Err(BinaryError::InvalidFormat(_)) => {}
+            Err(e) => {
+                return Err(UnityAssetError::with_source(
+                    format!("Failed to load binary file {:?}", path),
+                    e,
+                ));
+            }
         }
 
         Ok(())
@@ -470,6 +597,7 @@ impl Environment {
                         BinarySourceKind::AssetBundle,
                         None,
                     );
+                    self.warn_duplicate_bundle_entries(&source, &bundle);
                     self.bundles.insert(source.clone(), bundle);
                     match self.bundle_container_cache.write() {
                         Ok(mut cache) => {
@@ -566,6 +694,16 @@ impl Environment {
         let root = canonicalize_if_exists(root);
         let mut stats = ProjectLoadStats::default();
 
+        let project_version = root.join("ProjectSettings").join(PROJECT_VERSION_FILE_NAME);
+        if project_version.is_file()
+            && let Err(e) = self.load_project_version(&project_version)
+        {
+            self.push_warning(EnvironmentWarning::LoadFailed {
+                path: project_version,
+                error: e.to_string(),
+            });
+        }
+
         let mut builder = WalkBuilder::new(&root);
         builder.follow_links(options.follow_symlinks);
         builder.hidden(false);
@@ -630,7 +768,7 @@ impl Environment {
                     stats.meta_guids_indexed += 1;
                 }
                 if options.load_meta_documents {
-                    if self.load_file(&path).is_ok() {
+                    if self.load_file_or_warn(&path) {
                         stats.files_loaded += 1;
                         stats.yaml_loaded += 1;
                     }
@@ -638,14 +776,12 @@ impl Environment {
                 continue;
             }
 
-            if matches!(ext, "asset" | "prefab" | "unity") {
-                if options.load_yaml_documents {
-                    if self.load_file(&path).is_ok() {
-                        stats.files_loaded += 1;
-                        stats.yaml_loaded += 1;
-                    }
-                    continue;
+            if is_yaml_asset_path(&path) && options.load_yaml_documents {
+                if self.load_file_or_warn(&path) {
+                    stats.files_loaded += 1;
+                    stats.yaml_loaded += 1;
                 }
+                continue;
             }
 
             if !options.load_binary_files {
@@ -668,7 +804,7 @@ impl Environment {
             if matches!(
                 kind,
                 UnityFileKind::AssetBundle | UnityFileKind::SerializedFile | UnityFileKind::WebFile
-            ) && self.load_file(&path).is_ok()
+            ) && self.load_file_or_warn(&path)
             {
                 stats.files_loaded += 1;
                 stats.binary_loaded += 1;
@@ -678,6 +814,20 @@ impl Environment {
         Ok(stats)
     }
 
+    /// Load a file, recording a [`EnvironmentWarning::LoadFailed`] instead of failing.
+    pub(super) fn load_file_or_warn(&mut self, path: &Path) -> bool {
+        match self.load_file(path) {
+            Ok(()) => true,
+            Err(e) => {
+                self.push_warning(EnvironmentWarning::LoadFailed {
+                    path: path.to_path_buf(),
+                    error: e.to_string(),
+                });
+                false
+            }
+        }
+    }
+
     /// Recursively traverse directory and load Unity files.
     fn traverse_directory(&mut self, dir: &Path) -> Result<()> {
         let entries = std::fs::read_dir(dir).map_err(|e| {
@@ -692,17 +842,13 @@ impl Environment {
             if path.is_dir() {
                 // Skip common Unity directories that don't contain assets
                 if let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) {
-                    match dir_name {
-                        "Library" | "Temp" | "Logs" | ".git" | ".vs" | "obj" | "bin" => {
-                            continue; // Skip these directories
-                        }
-                        _ => {
-                            // Recursively process subdirectory
-                            self.traverse_directory(&path)?;
-                        }
+                    if SKIPPED_DIRECTORY_NAMES.contains(&dir_name) {
+                        continue; // Skip these directories
                     }
+                    // Recursively process subdirectory
+                    self.traverse_directory(&path)?;
                 }
-            } else if path.is_file() {
+            } else if path.is_file() && !is_secondary_split_part(&path) {
                 // Try to load the file
                 if let Err(e) = self.load_file(&path) {
                     // Record warning but continue processing other files
@@ -719,38 +865,23 @@ impl Environment {
 }
 
 fn load_split_bytes(base: &Path) -> Result<Vec<u8>> {
-    let mut out: Vec<u8> = Vec::new();
-    let mut found_any = false;
-
-    for i in 0..999usize {
-        let part = append_suffix(base, &format!(".split{i}"));
-        if !part.exists() {
-            if found_any {
-                break;
-            }
-            continue;
-        }
-        found_any = true;
-        let bytes = std::fs::read(&part).map_err(|e| {
-            UnityAssetError::with_source(format!("Failed to read split part {:?}", part), e)
-        })?;
-        out.extend_from_slice(&bytes);
-    }
-
-    if !found_any {
-        return Err(UnityAssetError::format(format!(
-            "No split parts found for base path: {:?}",
-            base
-        )));
-    }
-
-    Ok(out)
+    let parts = split::split_parts(base).ok_or_else(|| {
+        UnityAssetError::format(format!("No split parts found for base path: {:?}", base))
+    })?;
+    split::SplitReader::new(parts)
+        .and_then(split::SplitReader::read_all)
+        .map_err(|e| {
+            UnityAssetError::with_source(format!("Failed to read split file {:?}", base), e)
+        })
 }
 
-fn append_suffix(path: &Path, suffix: &str) -> PathBuf {
-    let mut s = path.as_os_str().to_os_string();
-    s.push(suffix);
-    PathBuf::from(s)
+/// A `.splitN` chunk with `N > 0` whose `.split0` exists: it is loaded together with `.split0`.
+pub(super) fn is_secondary_split_part(path: &Path) -> bool {
+    let Some(base) = split::split_base_path(path) else {
+        return false;
+    };
+    let first = split::split_part_path(&base, 0);
+    first != path && first.is_file()
 }
 
 fn strip_verbatim_prefix(path: &Path) -> PathBuf {
@@ -41,7 +41,12 @@ impl Environment {
         let Ok(text) = std::fs::read_to_string(meta_path) else {
             return None;
         };
-        let guid = read_guid_from_meta_text(&text)?;
+        self.index_meta_guid_text(meta_path, &text)
+    }
+
+    /// Index the GUID of a `.meta` file whose contents were already read.
+    pub(crate) fn index_meta_guid_text(&self, meta_path: &Path, text: &str) -> Option<[u8; 16]> {
+        let guid = read_guid_from_meta_text(text)?;
 
         // `foo.ext.meta` -> `foo.ext`
         let mut asset_path = meta_path.to_path_buf();
@@ -38,6 +38,8 @@ pub struct YamlExternalEdge {
     pub asset_path: Option<PathBuf>,
     /// Best-effort resolved key (currently only YAML targets are resolved).
     pub resolved: Option<EnvironmentObjectKey>,
+    /// Set when the GUID is one of Unity's built-in resource files.
+    pub builtin: Option<BuiltinReference>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -48,12 +50,33 @@ pub enum ExternalObjectEdge {
 
 /// Build options for `Environment` object graph extraction (YAML + binary).
 #[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub struct ObjectGraphBuildOptions {
     pub include_yaml: bool,
     pub include_binary: bool,
     pub binary: DependencyGraphBuildOptions,
 }
 
+impl ObjectGraphBuildOptions {
+    /// Set `include_yaml`.
+    pub fn with_include_yaml(mut self, include_yaml: bool) -> Self {
+        self.include_yaml = include_yaml;
+        self
+    }
+
+    /// Set `include_binary`.
+    pub fn with_include_binary(mut self, include_binary: bool) -> Self {
+        self.include_binary = include_binary;
+        self
+    }
+
+    /// Set `binary`.
+    pub fn with_binary(mut self, binary: DependencyGraphBuildOptions) -> Self {
+        self.binary = binary;
+        self
+    }
+}
+
 impl Default for ObjectGraphBuildOptions {
     fn default() -> Self {
         Self {
@@ -65,12 +88,33 @@ impl Default for ObjectGraphBuildOptions {
 }
 
 #[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
 pub struct ObjectGraphTraversalOptions {
     pub max_depth: Option<usize>,
     pub max_nodes: Option<usize>,
     pub follow_resolved_external: bool,
 }
 
+impl ObjectGraphTraversalOptions {
+    /// Set `max_depth`.
+    pub fn with_max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Set `max_nodes`.
+    pub fn with_max_nodes(mut self, max_nodes: Option<usize>) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// Set `follow_resolved_external`.
+    pub fn with_follow_resolved_external(mut self, follow_resolved_external: bool) -> Self {
+        self.follow_resolved_external = follow_resolved_external;
+        self
+    }
+}
+
 /// A best-effort object graph across all loaded sources in an `Environment`.
 ///
 /// - Nodes are `EnvironmentObjectKey` (globally unique within the loaded environment).
@@ -518,6 +562,8 @@ impl Environment {
                                     guid: Some(guid),
                                     asset_path,
                                     resolved,
+                                    builtin: BuiltinResourceFile::from_guid(&guid)
+                                        .map(|file| BuiltinReference::new(file, r.file_id)),
                                 }),
                             );
                             continue;
@@ -542,6 +588,7 @@ impl Environment {
                                     guid: None,
                                     asset_path: None,
                                     resolved: None,
+                                    builtin: None,
                                 }),
                             );
                         }
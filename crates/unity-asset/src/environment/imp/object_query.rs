@@ -4,7 +4,7 @@ use super::*;
 impl Environment {
     /// Iterate binary object references across all loaded bundles and standalone serialized files.
     pub fn binary_object_infos(&self) -> impl Iterator<Item = BinaryObjectRef<'_>> {
-        let typetree_options = self.options.typetree;
+        let typetree_options = &self.options.typetree;
         let standalone_reporter = self.reporter.clone();
         let bundled_reporter = self.reporter.clone();
 
@@ -73,7 +73,7 @@ impl Environment {
     /// Note: `path_id` is unique within a single `SerializedFile`, but not globally unique across files.
     pub fn find_binary_objects(&self, path_id: i64) -> Vec<BinaryObjectRef<'_>> {
         let mut out = Vec::new();
-        let typetree_options = self.options.typetree;
+        let typetree_options = &self.options.typetree;
         let reporter = self.reporter.clone();
 
         let mut asset_sources: Vec<&BinarySource> = self.binary_assets.keys().collect();
@@ -135,7 +135,7 @@ impl Environment {
         source: &BinarySource,
         path_id: i64,
     ) -> Vec<BinaryObjectRef<'_>> {
-        let typetree_options = self.options.typetree;
+        let typetree_options = &self.options.typetree;
         let reporter = self.reporter.clone();
 
         if let Some((key, file)) = self.binary_assets.get_key_value(source) {
@@ -211,7 +211,7 @@ impl Environment {
         asset_index: usize,
         path_id: i64,
     ) -> Option<BinaryObjectRef<'_>> {
-        let typetree_options = self.options.typetree;
+        let typetree_options = &self.options.typetree;
         let reporter = self.reporter.clone();
 
         let (key, bundle) = self.bundles.get_key_value(bundle_source)?;
@@ -249,7 +249,7 @@ impl Environment {
 
     /// Read a `UnityObject` from a globally-unique key.
     pub fn read_binary_object_key(&self, key: &BinaryObjectKey) -> Result<UnityObject> {
-        let typetree_options = self.options.typetree;
+        let typetree_options = &self.options.typetree;
         match key.source_kind {
             BinarySourceKind::SerializedFile => {
                 let file = match self.binary_assets.get(&key.source) {
@@ -273,9 +273,11 @@ impl Environment {
                         key.path_id
                     ))
                 })?;
-                let obj = object.read_with_options(typetree_options).map_err(|e| {
-                    UnityAssetError::with_source("Failed to parse binary object", e)
-                })?;
+                let obj = object
+                    .read_with_options(typetree_options.clone())
+                    .map_err(|e| {
+                        UnityAssetError::with_source("Failed to parse binary object", e)
+                    })?;
                 if let Some(reporter) = &self.reporter {
                     for w in obj.typetree_warnings() {
                         reporter.typetree_warning(key, w);
@@ -319,9 +321,11 @@ impl Environment {
                         key.path_id
                     ))
                 })?;
-                let obj = object.read_with_options(typetree_options).map_err(|e| {
-                    UnityAssetError::with_source("Failed to parse binary object", e)
-                })?;
+                let obj = object
+                    .read_with_options(typetree_options.clone())
+                    .map_err(|e| {
+                        UnityAssetError::with_source("Failed to parse binary object", e)
+                    })?;
                 if let Some(reporter) = &self.reporter {
                     for w in obj.typetree_warnings() {
                         reporter.typetree_warning(key, w);
@@ -337,7 +341,18 @@ impl Environment {
     /// This uses a TypeTree prefix fast path (when possible) and returns `Ok(None)` when the
     /// object has no TypeTree or does not expose a name field.
     pub fn peek_binary_object_name(&self, key: &BinaryObjectKey) -> Result<Option<String>> {
-        let typetree_options = self.options.typetree;
+        Ok(self
+            .peek_binary_object_name_with_raw(key)?
+            .map(|(name, _)| name))
+    }
+
+    /// Like [`Environment::peek_binary_object_name`], also returning the name's original bytes
+    /// when they were not valid UTF-8 and had to be decoded per `EnvironmentOptions::typetree`.
+    pub fn peek_binary_object_name_with_raw(
+        &self,
+        key: &BinaryObjectKey,
+    ) -> Result<Option<(String, Option<Vec<u8>>)>> {
+        let typetree_options = &self.options.typetree;
         match key.source_kind {
             BinarySourceKind::SerializedFile => {
                 let file = match self.binary_assets.get(&key.source) {
@@ -362,7 +377,7 @@ impl Environment {
                     ))
                 })?;
                 object
-                    .peek_name_with_options(typetree_options)
+                    .peek_name_with_raw(typetree_options.clone())
                     .map_err(|e| {
                         UnityAssetError::with_source("Failed to peek binary object name", e)
                     })
@@ -404,11 +419,22 @@ impl Environment {
                     ))
                 })?;
                 object
-                    .peek_name_with_options(typetree_options)
+                    .peek_name_with_raw(typetree_options.clone())
                     .map_err(|e| {
                         UnityAssetError::with_source("Failed to peek binary object name", e)
                     })
             }
         }
     }
+
+    /// Case-insensitive name match for a binary object, against both the decoded name and its
+    /// original bytes (see [`name_matches`]).
+    pub fn binary_object_name_matches(&self, key: &BinaryObjectKey, query: &str) -> Result<bool> {
+        Ok(match self.peek_binary_object_name_with_raw(key)? {
+            Some((name, raw)) => {
+                name_matches(query, &name, raw.as_deref(), &self.options.typetree.strings)
+            }
+            None => false,
+        })
+    }
 }
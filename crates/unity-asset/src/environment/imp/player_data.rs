@@ -0,0 +1,177 @@
+use super::path::canonicalize_if_exists;
+use super::*;
+
+/// Serialized files a player build keeps at the top of its `<Game>_Data` folder.
+const PLAYER_MANAGER_FILE_NAMES: &[&str] = &[
+    "globalgamemanagers",
+    "globalgamemanagers.assets",
+    "mainData",
+];
+
+/// Single-file player builds pack the whole Data folder into one bundle.
+const PLAYER_DATA_BUNDLE_FILE_NAME: &str = "data.unity3d";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PlayerDataLoadStats {
+    /// Serialized files (`globalgamemanagers`, `levelN`, `*.assets`) loaded.
+    pub serialized_loaded: usize,
+    /// Bundles loaded (`data.unity3d`).
+    pub bundles_loaded: usize,
+    /// `.resS` / `.resource` files registered for streamed reads.
+    pub resources_registered: usize,
+    /// Built-in resource files loaded from `Resources/`.
+    pub builtin_loaded: usize,
+    /// Files that failed to load (recorded as [`EnvironmentWarning::LoadFailed`]).
+    pub failed: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum PlayerFileKind {
+    Managers,
+    Level(u32),
+    Assets,
+    Bundle,
+    Resource,
+}
+
+/// Classify a Data folder entry by name, ignoring `.split0` suffixes (Unity split large files).
+fn player_file_kind(file_name: &str) -> Option<PlayerFileKind> {
+    let name = file_name.strip_suffix(".split0").unwrap_or(file_name);
+    let lower = name.to_ascii_lowercase();
+
+    if PLAYER_MANAGER_FILE_NAMES
+        .iter()
+        .any(|n| n.eq_ignore_ascii_case(name))
+    {
+        return Some(PlayerFileKind::Managers);
+    }
+    if lower == PLAYER_DATA_BUNDLE_FILE_NAME {
+        return Some(PlayerFileKind::Bundle);
+    }
+    if let Some(index) = lower.strip_prefix("level")
+        && let Ok(index) = index.parse::<u32>()
+    {
+        return Some(PlayerFileKind::Level(index));
+    }
+    if name.len() < file_name.len() {
+        // Only serialized files are split.
+        return lower.ends_with(".assets").then_some(PlayerFileKind::Assets);
+    }
+    if lower.ends_with(".ress") || lower.ends_with(".resource") {
+        return Some(PlayerFileKind::Resource);
+    }
+    lower.ends_with(".assets").then_some(PlayerFileKind::Assets)
+}
+
+impl Environment {
+    /// Load a built player's Data folder (`<Game>_Data`, or `assets/bin/Data` on Android).
+    ///
+    /// Loads `globalgamemanagers`, `levelN`, `sharedassetsN.assets`, `resources.assets` (and any
+    /// other top-level `.assets`), `data.unity3d` for single-file builds, and the built-in files
+    /// under `Resources/`. `.resS` / `.resource` files are registered for streamed reads (see
+    /// [`Environment::register_resource_file`]). The folder becomes the base path for external
+    /// resolution, so cross-file `PPtr`s resolve through the names the files use for each other
+    /// (`sharedassets0.assets`, `library/unity default resources`, ...).
+    ///
+    /// `StreamingAssets/` and `Managed/` are not loaded. Files that fail to parse are recorded as
+    /// warnings and skipped.
+    pub fn load_player_data<P: AsRef<Path>>(&mut self, dir: P) -> Result<PlayerDataLoadStats> {
+        let dir = dir.as_ref();
+        if !dir.is_dir() {
+            return Err(UnityAssetError::format(format!(
+                "Player data path is not a directory: {:?}",
+                dir
+            )));
+        }
+        let dir = canonicalize_if_exists(dir);
+
+        let entries = std::fs::read_dir(&dir).map_err(|e| {
+            UnityAssetError::with_source(format!("Failed to read directory {:?}", dir), e)
+        })?;
+        let mut files: Vec<(PlayerFileKind, PathBuf)> = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if let Some(kind) = player_file_kind(name) {
+                files.push((kind, path));
+            }
+        }
+        files.sort();
+
+        if !files.iter().any(|(kind, _)| {
+            matches!(
+                kind,
+                PlayerFileKind::Managers | PlayerFileKind::Level(_) | PlayerFileKind::Bundle
+            )
+        }) {
+            return Err(UnityAssetError::format(format!(
+                "Not a Unity player Data folder (no globalgamemanagers, levelN or data.unity3d): {:?}",
+                dir
+            )));
+        }
+
+        let mut stats = PlayerDataLoadStats::default();
+        for (kind, path) in files {
+            if kind == PlayerFileKind::Resource {
+                self.register_resource_file(&path);
+                stats.resources_registered += 1;
+                continue;
+            }
+            let bundles_before = self.bundles.len();
+            if !self.load_file_or_warn(&path) {
+                stats.failed += 1;
+            } else if self.bundles.len() > bundles_before {
+                stats.bundles_loaded += 1;
+            } else {
+                stats.serialized_loaded += 1;
+            }
+        }
+
+        let resources_dir = dir.join("Resources");
+        if resources_dir.is_dir() {
+            stats.builtin_loaded = self.load_builtin_resources(&resources_dir)?.len();
+        }
+
+        self.base_path = dir;
+        Ok(stats)
+    }
+
+    /// Register a `.resS` / `.resource` file for streamed reads by file name.
+    ///
+    /// `m_StreamData.path` values that name the file (case-insensitively, with or without an
+    /// `archive:/` or `library/` prefix) read from it before the on-disk sibling lookup.
+    pub fn register_resource_file<P: AsRef<Path>>(&mut self, path: P) {
+        let path = canonicalize_if_exists(path.as_ref());
+        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+            self.resource_files.insert(name.to_lowercase(), path);
+        }
+    }
+
+    /// Register a `.resS` / `.resource` [`DataSource`] under a logical path.
+    ///
+    /// Streamed reads resolve it like [`Environment::register_resource_file`] (full path, then
+    /// file name) and read only the requested range, ahead of registered and sibling files.
+    pub fn register_resource_source(&mut self, path: &str, source: impl DataSource + 'static) {
+        self.resource_sources.insert(path, source);
+    }
+
+    /// Registered resource sources.
+    pub fn resource_sources(&self) -> &VirtualFileSystem {
+        &self.resource_sources
+    }
+
+    /// The registered resource file a stream path refers to, if any.
+    pub fn resource_file_path(&self, stream_path: &str) -> Option<&Path> {
+        let normalized = unity_asset_binary::bundle::normalize_stream_path(stream_path);
+        let name = Path::new(&normalized).file_name()?.to_str()?;
+        self.resource_files
+            .get(&name.to_lowercase())
+            .map(|p| p.as_path())
+    }
+}
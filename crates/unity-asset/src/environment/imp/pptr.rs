@@ -1,6 +1,7 @@
 use super::*;
 
 #[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub struct PptrReferenceSearchOptions {
     pub continue_on_error: bool,
     pub max_objects: Option<usize>,
@@ -8,6 +9,32 @@ pub struct PptrReferenceSearchOptions {
     pub max_pptrs_per_object: Option<usize>,
 }
 
+impl PptrReferenceSearchOptions {
+    /// Set `continue_on_error`.
+    pub fn with_continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Set `max_objects`.
+    pub fn with_max_objects(mut self, max_objects: Option<usize>) -> Self {
+        self.max_objects = max_objects;
+        self
+    }
+
+    /// Set `max_results`.
+    pub fn with_max_results(mut self, max_results: Option<usize>) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    /// Set `max_pptrs_per_object`.
+    pub fn with_max_pptrs_per_object(mut self, max_pptrs_per_object: Option<usize>) -> Self {
+        self.max_pptrs_per_object = max_pptrs_per_object;
+        self
+    }
+}
+
 impl Default for PptrReferenceSearchOptions {
     fn default() -> Self {
         Self {
@@ -28,6 +55,26 @@ pub struct BinaryPptrReference {
     pub resolved: Option<BinaryObjectKey>,
 }
 
+/// Outcome of [`Environment::resolve_binary_pptr_or_builtin`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedPptr {
+    /// An object in a loaded source.
+    Object(BinaryObjectKey),
+    /// An object in Unity's built-in resource files, which are not loaded.
+    Builtin(BuiltinReference),
+}
+
+/// Classify an external `PPtr` (`file_id > 0`) pointing into a built-in resource file.
+pub(crate) fn builtin_reference_for_pptr(
+    file: &SerializedFile,
+    file_id: i32,
+    path_id: i64,
+) -> Option<BuiltinReference> {
+    let idx = usize::try_from(file_id.checked_sub(1)?).ok()?;
+    let external = file.externals.get(idx)?;
+    BuiltinReference::from_external_path(&external.path, path_id)
+}
+
 pub(crate) fn match_external_path_score(external_path: &str, candidate: &str) -> i32 {
     if external_path.is_empty() || candidate.is_empty() {
         return 0;
@@ -202,6 +249,25 @@ impl Environment {
             return Some(BinarySource::Path((*found).clone()));
         }
 
+        // Player builds reference each other by lowercased names, sometimes under `library/`
+        // (`library/sharedassets0.assets`, `Library/unity default resources`).
+        if let Some(name) = target_file_name {
+            let mut by_name_ci: Vec<&PathBuf> = self
+                .binary_assets
+                .keys()
+                .filter_map(|k| k.as_path())
+                .filter(|p| {
+                    p.file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.eq_ignore_ascii_case(name))
+                })
+                .collect();
+            by_name_ci.sort();
+            if let Some(found) = by_name_ci.first() {
+                return Some(BinarySource::Path((*found).clone()));
+            }
+        }
+
         let external_norm = external_path.replace('\\', "/");
         let mut by_suffix: Vec<&PathBuf> = self
             .binary_assets
@@ -323,6 +389,52 @@ impl Environment {
         })
     }
 
+    /// Resolve a Unity `PPtr` like [`Environment::resolve_binary_pptr`], falling back to
+    /// [`ResolvedPptr::Builtin`] for references into `unity default resources` /
+    /// `unity_builtin_extra`.
+    ///
+    /// Loaded copies of the built-in files (see [`Environment::load_builtin_resources`]) take
+    /// precedence and resolve to [`ResolvedPptr::Object`].
+    pub fn resolve_binary_pptr_or_builtin(
+        &self,
+        context: &BinaryObjectRef<'_>,
+        file_id: i32,
+        path_id: i64,
+    ) -> Option<ResolvedPptr> {
+        if let Some(key) = self.resolve_binary_pptr(context, file_id, path_id) {
+            return Some(ResolvedPptr::Object(key));
+        }
+        builtin_reference_for_pptr(context.object.file(), file_id, path_id)
+            .map(ResolvedPptr::Builtin)
+    }
+
+    /// Load Unity's built-in resource files from a directory (e.g. `<Editor>/Data/Resources` or a
+    /// player's `<Game>_Data/Resources`), so `PPtr`s into them resolve to real objects.
+    ///
+    /// Returns the paths that were loaded; missing files are skipped.
+    pub fn load_builtin_resources<P: AsRef<Path>>(&mut self, dir: P) -> Result<Vec<PathBuf>> {
+        let saved_base_path = self.base_path.clone();
+
+        let mut loaded = Vec::new();
+        for file in [
+            BuiltinResourceFile::DefaultResources,
+            BuiltinResourceFile::BuiltinExtra,
+        ] {
+            let path = dir.as_ref().join(file.file_name());
+            if !path.is_file() {
+                continue;
+            }
+            if let Err(e) = self.load_file(&path) {
+                self.base_path = saved_base_path;
+                return Err(e);
+            }
+            loaded.push(path);
+        }
+
+        self.base_path = saved_base_path;
+        Ok(loaded)
+    }
+
     /// Resolve and parse a Unity `PPtr` (`fileID`, `pathID`) using a context object for external mapping.
     pub fn read_binary_pptr(
         &self,
@@ -330,14 +442,21 @@ impl Environment {
         file_id: i32,
         path_id: i64,
     ) -> Result<UnityObject> {
-        let key = self
-            .resolve_binary_pptr(context, file_id, path_id)
-            .ok_or_else(|| {
-                UnityAssetError::format(format!(
+        let key = match self.resolve_binary_pptr_or_builtin(context, file_id, path_id) {
+            Some(ResolvedPptr::Object(key)) => key,
+            Some(ResolvedPptr::Builtin(builtin)) => {
+                return Err(UnityAssetError::format(format!(
+                    "PPtr points to built-in resource {} (file_id={}, path_id={}); load the built-in resource files to read it",
+                    builtin, file_id, path_id
+                )));
+            }
+            None => {
+                return Err(UnityAssetError::format(format!(
                     "Failed to resolve PPtr: file_id={}, path_id={}",
                     file_id, path_id
-                ))
-            })?;
+                )));
+            }
+        };
         self.read_binary_object_key(&key)
     }
 
@@ -137,16 +137,21 @@ fn save_impl(
                 ))
             })?;
 
-            let node_name = bundle.asset_names.get(*asset_index).ok_or_else(|| {
-                UnityAssetError::format(format!(
-                    "AssetBundle asset name missing: {} asset_index={}",
-                    bundle_source.describe(),
-                    asset_index
-                ))
-            })?;
+            let (node_index, node) = bundle
+                .asset_node_index(*asset_index)
+                .and_then(|i| Some((i, bundle.node_at(i)?)))
+                .ok_or_else(|| {
+                    UnityAssetError::format(format!(
+                        "AssetBundle asset node missing: {} asset_index={}",
+                        bundle_source.describe(),
+                        asset_index
+                    ))
+                })?;
+            let node_name = node.name.clone();
+            let occurrence = bundle.node_occurrence(node_index).unwrap_or(0);
 
             let bytes = SerializedFileWriter::save(asset, &asset_state.edits)?;
-            edits.replace_file_bytes(node_name.clone(), bytes);
+            edits.replace_file_bytes_at(node_name, occurrence, bytes);
         }
 
         for cab in bundle_state.cabs.values() {
@@ -0,0 +1,38 @@
+use super::*;
+use unity_asset_core::class_ids;
+use unity_asset_core::tag_manager::{BUILTIN_TAGS, TagManager, builtin_layer_name};
+
+impl Environment {
+    /// The project's TagManager: `ProjectSettings/TagManager.asset` when a project was loaded,
+    /// otherwise the TagManager of a player's `globalgamemanagers`.
+    pub fn tag_manager(&self) -> Option<TagManager> {
+        if let Some(class) = self
+            .yaml_objects()
+            .find(|obj| obj.class_id == class_ids::TAG_MANAGER || obj.class_name == "TagManager")
+        {
+            return Some(TagManager::from_properties(class.properties()));
+        }
+
+        self.binary_object_infos()
+            .filter(|obj| obj.object.class_id() == class_ids::TAG_MANAGER)
+            .find_map(|obj| obj.read().ok())
+            .map(|obj| TagManager::from_properties(obj.as_unity_class().properties()))
+    }
+
+    /// Name of layer `index` (`m_Layer`). Without a TagManager only the builtin layers
+    /// (`Default`, `TransparentFX`, `Ignore Raycast`, `Water`, `UI`) have names.
+    pub fn layer_name(&self, index: i32) -> Option<String> {
+        match self.tag_manager() {
+            Some(manager) => manager.layer_name(index).map(str::to_string),
+            None => builtin_layer_name(index).map(str::to_string),
+        }
+    }
+
+    /// Builtin tags followed by the project's custom tags.
+    pub fn tag_names(&self) -> Vec<String> {
+        match self.tag_manager() {
+            Some(manager) => manager.all_tags().into_iter().map(str::to_string).collect(),
+            None => BUILTIN_TAGS.iter().map(|tag| tag.to_string()).collect(),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use super::*;
+use unity_asset_binary::shader_audit::{ShaderAudit, ShaderAuditReport, ShaderId};
+use unity_asset_binary::unity_objects::ObjectRef;
+use unity_asset_core::class_ids;
+
+impl Environment {
+    /// Audit the ShaderVariantCollections and GraphicsSettings of all loaded binary sources.
+    ///
+    /// Shader `PPtr`s are resolved with [`Environment::resolve_binary_pptr_or_builtin`]; see
+    /// [`unity_asset_binary::shader_audit`] for how variants are counted.
+    pub fn shader_audit(&self) -> ShaderAuditReport {
+        let mut audit = ShaderAudit::new();
+        for context in self.binary_object_infos() {
+            let class_id = context.object.class_id();
+            if class_id != class_ids::SHADER_VARIANT_COLLECTION
+                && class_id != class_ids::GRAPHICS_SETTINGS
+            {
+                continue;
+            }
+            let Ok(object) = context.read() else {
+                continue;
+            };
+            let resolve = |shader: ObjectRef| self.resolve_shader_id(&context, shader);
+            if let Ok(collection) = object.as_shader_variant_collection() {
+                audit.add_variant_collection(&collection, resolve);
+            } else if let Ok(settings) = object.as_graphics_settings() {
+                audit.add_graphics_settings(&settings, resolve);
+            }
+        }
+        audit.finish()
+    }
+
+    fn resolve_shader_id(&self, context: &BinaryObjectRef<'_>, shader: ObjectRef) -> ShaderId {
+        match self.resolve_binary_pptr_or_builtin(context, shader.file_id, shader.path_id) {
+            Some(ResolvedPptr::Object(key)) => {
+                let name = self
+                    .read_binary_object_key(&key)
+                    .ok()
+                    .and_then(|o| o.as_shader().ok())
+                    .map(|s| s.name)
+                    .filter(|n| !n.is_empty());
+                let file = match key.asset_index {
+                    Some(index) => format!("{}[{}]", key.source, index),
+                    None => key.source.to_string(),
+                };
+                ShaderId {
+                    file,
+                    path_id: key.path_id,
+                    name,
+                }
+            }
+            Some(ResolvedPptr::Builtin(builtin)) => ShaderId {
+                file: builtin.file.file_name().to_string(),
+                path_id: builtin.path_id,
+                name: builtin.resource.map(|r| r.name.to_string()),
+            },
+            None => {
+                let external = usize::try_from(shader.file_id - 1)
+                    .ok()
+                    .and_then(|i| context.object.file().externals.get(i));
+                ShaderId {
+                    file: external
+                        .map(|e| e.path.clone())
+                        .unwrap_or_else(|| format!("<file_id {}>", shader.file_id)),
+                    path_id: shader.path_id,
+                    name: None,
+                }
+            }
+        }
+    }
+}
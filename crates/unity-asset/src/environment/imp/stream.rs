@@ -3,15 +3,7 @@ use super::*;
 
 impl Environment {
     fn normalize_stream_path(stream_path: &str) -> String {
-        let mut p = stream_path.trim().to_string();
-        if let Some(rest) = p.strip_prefix("archive:/") {
-            p = rest.to_string();
-        }
-        p = p.replace('\\', "/");
-        while p.starts_with("./") {
-            p = p.trim_start_matches("./").to_string();
-        }
-        p
+        unity_asset_binary::bundle::normalize_stream_path(stream_path)
     }
 
     fn cab_prefix_from_normalized(normalized: &str) -> Option<String> {
@@ -36,73 +28,7 @@ impl Environment {
         bundle: &'a AssetBundle,
         stream_path: &str,
     ) -> Option<&'a unity_asset_binary::bundle::types::DirectoryNode> {
-        let normalized = Self::normalize_stream_path(stream_path);
-        if normalized.is_empty() {
-            return None;
-        }
-
-        let file_name = Path::new(&normalized)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .map(|s| s.to_string());
-
-        let mut nodes: Vec<&unity_asset_binary::bundle::types::DirectoryNode> =
-            bundle.nodes.iter().filter(|n| n.is_file()).collect();
-        nodes.sort_by(|a, b| a.name.cmp(&b.name));
-
-        for node in &nodes {
-            let node_norm = node.name.replace('\\', "/");
-            if node_norm == normalized
-                || node_norm.ends_with(&normalized)
-                || normalized.ends_with(&node_norm)
-            {
-                return Some(*node);
-            }
-
-            if let Some(file_name) = &file_name
-                && Path::new(&node_norm).file_name().and_then(|n| n.to_str())
-                    == Some(file_name.as_str())
-            {
-                return Some(*node);
-            }
-        }
-
-        // Unity sometimes appends an index suffix to the CAB resource node name
-        // (e.g. `CAB-<hash>1.resource`) while the `StreamedResource.m_Source` path
-        // points to `CAB-<hash>.resource`. Best-effort: match by CAB prefix.
-        let cab_prefix = normalized
-            .split('/')
-            .find(|s| s.starts_with("CAB-"))
-            .and_then(|s| {
-                let hash: String = s
-                    .trim_start_matches("CAB-")
-                    .chars()
-                    .take_while(|c| c.is_ascii_hexdigit())
-                    .collect();
-                if hash.is_empty() {
-                    None
-                } else {
-                    Some(format!("CAB-{}", hash))
-                }
-            });
-
-        if let Some(cab_prefix) = cab_prefix {
-            for node in &nodes {
-                let node_norm = node.name.replace('\\', "/");
-                let is_resource = node_norm.ends_with(".resS") || node_norm.ends_with(".resource");
-                let base = Path::new(&node_norm)
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or(&node_norm);
-                if is_resource
-                    && (node_norm.starts_with(&cab_prefix) || base.starts_with(&cab_prefix))
-                {
-                    return Some(*node);
-                }
-            }
-        }
-
-        None
+        bundle.find_resource_node(stream_path)
     }
 
     fn stream_fs_candidates(source_path: &Path, stream_path: &str) -> Vec<PathBuf> {
@@ -431,7 +357,13 @@ impl Environment {
         use std::io::{Read, Seek, SeekFrom};
 
         let source_path = source_path.as_ref();
-        let candidates = Self::stream_fs_candidates(source_path, stream_path);
+        if let Some(source) = self.resource_sources.open(stream_path) {
+            return Ok(source.read_at(offset, size as usize)?);
+        }
+        let mut candidates = Self::stream_fs_candidates(source_path, stream_path);
+        if let Some(registered) = self.resource_file_path(stream_path) {
+            candidates.insert(0, registered.to_path_buf());
+        }
         for candidate in candidates {
             if !candidate.exists() {
                 continue;
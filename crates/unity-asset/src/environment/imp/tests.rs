@@ -232,9 +232,7 @@ fn environment_can_edit_binary_object_and_save_bundle() {
     .unwrap();
 
     env.save(
-        PackerOptions {
-            packer: UnityPyPacker::Original,
-        },
+        PackerOptions::default().with_packer(UnityPyPacker::Original),
         &out_dir,
     )
     .unwrap();
@@ -256,6 +254,86 @@ fn environment_can_edit_binary_object_and_save_bundle() {
     assert_eq!(saved_name, new_name);
 }
 
+#[test]
+fn environment_warns_on_duplicate_bundle_entries_and_saves_the_right_one() {
+    use unity_asset_binary::bundle::BundleParser;
+    use unity_asset_write::bundle::{BundleEdits, BundleWriter};
+    use unity_asset_write::{PackerOptions, UnityPyPacker};
+
+    // Repack the sample with a second directory entry pointing at its CAB.
+    let mut original =
+        BundleParser::from_bytes(include_bytes!("../../../../../tests/samples/banner_1").to_vec())
+            .unwrap();
+    let mut node = original.nodes[original.asset_node_index(0).unwrap()].clone();
+    let cab_name = node.name.clone();
+    node.flags = 0x4;
+    original.nodes.push(node);
+    let duplicated = BundleWriter::save(
+        &original,
+        &BundleEdits::new(),
+        PackerOptions::default().with_packer(UnityPyPacker::None),
+    )
+    .unwrap();
+
+    let tmp = tempfile::tempdir().unwrap();
+    let in_path = tmp.path().join("banner_1");
+    let out_dir = tmp.path().join("out");
+    std::fs::write(&in_path, duplicated).unwrap();
+    let in_path = canonicalize_path(in_path);
+
+    let mut env = Environment::new();
+    env.load_file(&in_path).unwrap();
+
+    let warnings = env.take_warnings();
+    assert!(
+        warnings.iter().any(|w| matches!(
+            w,
+            EnvironmentWarning::DuplicateBundleEntry { name, count: 2, .. } if *name == cab_name
+        )),
+        "{warnings:?}"
+    );
+
+    let bundle = env.bundles().get(&BinarySource::path(&in_path)).unwrap();
+    assert_eq!(bundle.assets.len(), 2);
+    let (path_id, old_name) = bundle.assets[1]
+        .object_handles()
+        .filter_map(|h| h.peek_name().ok().flatten().map(|n| (h.path_id(), n)))
+        .find(|(_id, name)| !name.is_empty())
+        .expect("expected an object with a peekable name in sample");
+
+    let key = BinaryObjectKey {
+        source: BinarySource::path(&in_path),
+        source_kind: BinarySourceKind::AssetBundle,
+        asset_index: Some(1),
+        path_id,
+    };
+    env.edit_binary_object_key(&key, |class| {
+        class.set(
+            "m_Name".to_string(),
+            UnityValue::String("RENAMED".to_string()),
+        );
+        Ok(())
+    })
+    .unwrap();
+    env.save(
+        PackerOptions::default().with_packer(UnityPyPacker::None),
+        &out_dir,
+    )
+    .unwrap();
+
+    let saved = BundleParser::from_bytes(std::fs::read(out_dir.join("banner_1")).unwrap()).unwrap();
+    let peek = |asset_index: usize| {
+        saved.assets[asset_index]
+            .find_object_handle(path_id)
+            .unwrap()
+            .peek_name()
+            .unwrap()
+            .unwrap()
+    };
+    assert_eq!(peek(0), old_name);
+    assert_eq!(peek(1), "RENAMED");
+}
+
 #[test]
 fn environment_edit_session_can_set_sprite_texture_pptr_and_save_bundle() {
     use unity_asset_write::{PackerOptions, UnityPyPacker};
@@ -313,9 +391,7 @@ fn environment_edit_session_can_set_sprite_texture_pptr_and_save_bundle() {
 
     session
         .save(
-            PackerOptions {
-                packer: UnityPyPacker::Original,
-            },
+            PackerOptions::default().with_packer(UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -403,9 +479,7 @@ fn environment_edit_session_can_set_sprite_atlas_alpha_texture_pptr_and_save_bun
 
     session
         .save(
-            PackerOptions {
-                packer: UnityPyPacker::Original,
-            },
+            PackerOptions::default().with_packer(UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -498,9 +572,7 @@ fn environment_edit_session_can_save_binary_object_class_and_save_bundle() {
     session.save_binary_object_class(&key, class).unwrap();
     session
         .save(
-            PackerOptions {
-                packer: UnityPyPacker::Original,
-            },
+            PackerOptions::default().with_packer(UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -590,9 +662,7 @@ fn environment_edit_session_can_set_binary_value_at_path_and_save_bundle() {
 
     session
         .save(
-            PackerOptions {
-                packer: UnityPyPacker::Original,
-            },
+            PackerOptions::default().with_packer(UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -843,6 +913,40 @@ fn environment_load_project_binaries_only_indexes_meta_without_loading_meta_docu
     );
 }
 
+#[test]
+fn environment_load_project_reads_project_version_and_tags_yaml_documents() {
+    let temp = tempfile::tempdir().unwrap();
+    let root = temp.path();
+
+    let settings_dir = root.join("ProjectSettings");
+    std::fs::create_dir_all(&settings_dir).unwrap();
+    std::fs::write(
+        settings_dir.join("ProjectVersion.txt"),
+        b"m_EditorVersion: 2021.3.5f1\nm_EditorVersionWithRevision: 2021.3.5f1 (40eb3a945986)\n",
+    )
+    .unwrap();
+    std::fs::write(
+        settings_dir.join("TagManager.asset"),
+        b"%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n--- !u!78 &1\nTagManager:\n  serializedVersion: 2\n  tags: []\n",
+    )
+    .unwrap();
+
+    let mut env = Environment::new();
+    let mut options = ProjectLoadOptions::everything();
+    options.respect_ignores = false;
+    env.load_project(root, options).unwrap();
+
+    let expected = UnityVersion::parse_version("2021.3.5f1").unwrap();
+    assert_eq!(env.unity_version(), Some(&expected));
+
+    let doc = env
+        .yaml_documents()
+        .values()
+        .next()
+        .expect("TagManager.asset should be loaded");
+    assert_eq!(doc.unity_version(), Some(&expected));
+}
+
 #[test]
 fn environment_typetree_registry_json_restores_parsing_for_stripped_assets() {
     use serde::Serialize;
@@ -1011,9 +1115,7 @@ fn environment_can_edit_and_save_stripped_assets_with_typetree_registry() {
 
     let out_dir = tmp.path().join("out");
     env.save(
-        PackerOptions {
-            packer: UnityPyPacker::Original,
-        },
+        PackerOptions::default().with_packer(UnityPyPacker::Original),
         &out_dir,
     )
     .unwrap();
@@ -1162,9 +1264,7 @@ fn environment_can_edit_zip_assetbundle_entry_and_save() {
     .unwrap();
 
     env.save(
-        PackerOptions {
-            packer: UnityPyPacker::Original,
-        },
+        PackerOptions::default().with_packer(UnityPyPacker::Original),
         &out_dir,
     )
     .unwrap();
@@ -1393,6 +1493,65 @@ fn environment_can_find_yaml_pptr_references_to_yaml_anchor_with_paths() {
     );
 }
 
+#[test]
+fn environment_yaml_anchors_and_pptrs_keep_negative_and_extreme_file_ids() {
+    let temp = tempfile::tempdir().unwrap();
+    let prefab_path = temp.path().join("Extreme.prefab");
+    std::fs::write(
+        &prefab_path,
+        r#"%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!1 &-9223372036854775808
+GameObject:
+  m_Name: Root
+  m_Component:
+  - component: {fileID: 9223372036854775807}
+--- !u!4 &9223372036854775807
+Transform:
+  m_GameObject: {fileID: -9223372036854775808}
+  m_Father: {fileID: 0}
+--- !u!114 &-8679921383154817045
+MonoBehaviour:
+  m_GameObject: {fileID: -9223372036854775808}
+"#,
+    )
+    .unwrap();
+    let prefab_path = canonicalize_path(prefab_path);
+
+    let mut env = Environment::new();
+    env.load_file(&prefab_path).unwrap();
+
+    let root = env
+        .find_yaml_by_anchor("-9223372036854775808")
+        .expect("i64::MIN anchor");
+    assert_eq!(root.class_id, 1);
+    assert_eq!(root.name(), Some("Root"));
+    let transform = env
+        .find_yaml_by_anchor("9223372036854775807")
+        .expect("i64::MAX anchor");
+    assert_eq!(
+        transform
+            .get("m_GameObject")
+            .and_then(|v| v.as_object())
+            .and_then(|m| m.get("fileID"))
+            .and_then(|v| v.as_i64()),
+        Some(i64::MIN)
+    );
+    assert!(env.find_yaml_by_anchor("-8679921383154817045").is_some());
+    assert!(env.find_yaml_by_anchor("8679921383154817045").is_none());
+
+    let target = EnvironmentObjectKey::Yaml(YamlObjectKey {
+        path: prefab_path.clone(),
+        anchor: i64::MIN.to_string(),
+    });
+    let refs = env
+        .find_yaml_pptr_references_to(&target, YamlPptrReferenceSearchOptions::default())
+        .unwrap();
+    let mut from: Vec<&str> = refs.iter().map(|r| r.from.anchor.as_str()).collect();
+    from.sort();
+    assert_eq!(from, vec!["-8679921383154817045", "9223372036854775807"]);
+}
+
 #[test]
 fn environment_can_find_yaml_pptr_references_to_binary_object_with_paths() {
     use unity_asset_binary::bundle::load_bundle;
@@ -1606,7 +1765,8 @@ fn environment_stream_data_falls_back_to_filesystem_for_bundles() {
     let bundle_path = temp.path().join("char_118_yuki.ab");
     link_or_copy_file(&bundle_src, &bundle_path).unwrap();
 
-    let cab = "8579bc75d50073df38987733a7cb3193";
+    // Not the bundle's own CAB (its `.resource` node would be read instead of the file system).
+    let cab = "0123456789abcdef0123456789abcdef";
     let stream_path = format!("archive:/CAB-{cab}/CAB-{cab}.resource");
     let resource_dir = temp.path().join(format!("CAB-{cab}"));
     fs::create_dir_all(&resource_dir).unwrap();
@@ -1708,7 +1868,8 @@ fn environment_loads_extless_webfile_entries_and_reads_resource_bytes() {
     );
     let bundle_bytes = fs::read(&sample_bundle_path).unwrap();
 
-    let cab = "8579bc75d50073df38987733a7cb3193";
+    // Not the bundle's own CAB, so the resource comes from the sibling web entry.
+    let cab = "0123456789abcdef0123456789abcdef";
     let resource_name = format!("CAB-{cab}.resource");
     let mut resource_bytes = vec![0u8; 4096 + 4];
     resource_bytes[4096..4096 + 4].copy_from_slice(b"OggS");
@@ -1815,9 +1976,8 @@ fn environment_save_repacks_webfile_after_editing_embedded_bundle() {
 
     let out_dir = temp.path().join("out");
     env.save(
-        unity_asset_write::PackerOptions {
-            packer: unity_asset_write::UnityPyPacker::Original,
-        },
+        unity_asset_write::PackerOptions::default()
+            .with_packer(unity_asset_write::UnityPyPacker::Original),
         &out_dir,
     )
     .unwrap();
@@ -1878,9 +2038,8 @@ fn environment_can_write_streamed_resource_cab_into_bundle_and_reload() {
     let out_dir = temp.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -1962,9 +2121,8 @@ fn environment_can_write_streamed_resource_cab_for_standalone_serialized_file_an
     let out_dir = temp.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -2030,9 +2188,7 @@ fn environment_typed_audio_clip_helper_can_repoint_streamed_resource_and_reload(
     let out_dir = temp.path().join("out");
     session
         .save(
-            PackerOptions {
-                packer: UnityPyPacker::Original,
-            },
+            PackerOptions::default().with_packer(UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -2115,9 +2271,7 @@ fn environment_typed_texture2d_helper_can_repoint_streamed_resource_and_reload()
     let out_dir = temp.path().join("out");
     session
         .save(
-            PackerOptions {
-                packer: UnityPyPacker::Original,
-            },
+            PackerOptions::default().with_packer(UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -2515,6 +2669,133 @@ fn environment_resolve_pptr_path_key_resolves_sprite_texture() {
     assert_eq!(texture.class_id(), 28, "expected Texture2D target");
 }
 
+/// Loads `banner_1` with an extra `Resources/unity_builtin_extra` external appended to its
+/// SerializedFile and returns the bundle source plus the new external's `file_id`.
+fn load_banner_with_builtin_extra_external(env: &mut Environment) -> (BinarySource, i32) {
+    let path = canonicalize_path(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples/banner_1"),
+    );
+    env.load_file(&path).unwrap();
+
+    let source = BinarySource::path(&path);
+    let file = &mut env.bundles.get_mut(&source).unwrap().assets[0];
+    file.externals
+        .push(unity_asset_binary::asset::FileIdentifier {
+            path: "Resources/unity_builtin_extra".to_string(),
+            ..Default::default()
+        });
+    (source, file.externals.len() as i32)
+}
+
+#[test]
+fn environment_resolves_material_shader_to_named_builtin() {
+    let mut env = Environment::new();
+    let (source, builtin_file_id) = load_banner_with_builtin_extra_external(&mut env);
+
+    let mut material = UnityClass::new(21, "Material".to_string(), "1".to_string());
+    material.set(
+        "m_Shader".to_string(),
+        UnityValue::Object(
+            [
+                (
+                    "m_FileID".to_string(),
+                    UnityValue::Integer(builtin_file_id as i64),
+                ),
+                ("m_PathID".to_string(), UnityValue::Integer(46)),
+            ]
+            .into_iter()
+            .collect(),
+        ),
+    );
+    let pptrs = super::pptr_path::scan_pptrs_with_paths(&material, None);
+    let shader = pptrs
+        .iter()
+        .find(|p| p.path == "m_Shader")
+        .expect("m_Shader is a PPtr");
+
+    let context = env
+        .binary_object_infos()
+        .find(|r| r.source == &source)
+        .expect("banner_1 has objects");
+
+    assert_eq!(
+        env.resolve_binary_pptr(&context, shader.file_id, shader.path_id),
+        None
+    );
+    let Some(ResolvedPptr::Builtin(builtin)) =
+        env.resolve_binary_pptr_or_builtin(&context, shader.file_id, shader.path_id)
+    else {
+        panic!("expected a builtin resolution for the default shader");
+    };
+    assert_eq!(builtin.file, BuiltinResourceFile::BuiltinExtra);
+    assert_eq!(builtin.name(), "Standard");
+    assert_eq!(builtin.resource.map(|r| r.class_id), Some(48));
+
+    let err = env
+        .read_binary_pptr(&context, shader.file_id, shader.path_id)
+        .unwrap_err();
+    assert!(err.to_string().contains("builtin:Standard"), "{err}");
+}
+
+#[test]
+fn environment_load_builtin_resources_resolves_to_loaded_copy() {
+    let mut env = Environment::new();
+    let (source, builtin_file_id) = load_banner_with_builtin_extra_external(&mut env);
+
+    let temp = tempfile::tempdir().unwrap();
+    link_or_copy_file(
+        &PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples/atlas_test"),
+        &temp.path().join("unity_builtin_extra"),
+    )
+    .unwrap();
+    let loaded = env.load_builtin_resources(temp.path()).unwrap();
+    assert_eq!(loaded.len(), 1);
+
+    let context = env
+        .binary_object_infos()
+        .find(|r| r.source == &source)
+        .expect("banner_1 has objects");
+    match env.resolve_binary_pptr_or_builtin(&context, builtin_file_id, 46) {
+        Some(ResolvedPptr::Object(key)) => {
+            assert_eq!(
+                key.source,
+                BinarySource::path(canonicalize_path(loaded[0].clone()))
+            );
+        }
+        other => panic!("expected the loaded copy to win, got {other:?}"),
+    }
+}
+
+#[test]
+fn environment_object_graph_labels_yaml_builtin_shader_edge() {
+    let temp = tempfile::tempdir().unwrap();
+    let mat_path = temp.path().join("Lit.asset");
+    std::fs::write(
+        &mat_path,
+        b"%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n--- !u!21 &2100000\nMaterial:\n  m_Name: Lit\n  m_Shader: {fileID: 46, guid: 0000000000000000f000000000000000, type: 0}\n",
+    )
+    .unwrap();
+    let mat_path = canonicalize_path(mat_path);
+
+    let mut env = Environment::new();
+    env.load_file(&mat_path).unwrap();
+    let graph = env.build_object_graph(ObjectGraphBuildOptions::default());
+
+    let from = EnvironmentObjectKey::Yaml(YamlObjectKey {
+        path: mat_path,
+        anchor: "2100000".to_string(),
+    });
+    let builtin = graph
+        .external_refs_from(&from)
+        .iter()
+        .find_map(|e| match e {
+            ExternalObjectEdge::Yaml(y) => y.builtin,
+            _ => None,
+        })
+        .expect("expected a builtin-labelled shader edge");
+    assert_eq!(builtin.to_string(), "builtin:Standard");
+}
+
 #[test]
 fn environment_can_set_pptr_path_to_key_and_reload() {
     use unity_asset_write::{PackerOptions, UnityPyPacker};
@@ -2547,9 +2828,7 @@ fn environment_can_set_pptr_path_to_key_and_reload() {
     let out_dir = temp.path().join("out");
     session
         .save(
-            PackerOptions {
-                packer: UnityPyPacker::Original,
-            },
+            PackerOptions::default().with_packer(UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -2609,9 +2888,7 @@ fn environment_set_pptr_path_to_key_adds_external_when_cross_source() {
     let out_dir = temp.path().join("out");
     session
         .save(
-            PackerOptions {
-                packer: UnityPyPacker::Original,
-            },
+            PackerOptions::default().with_packer(UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -2684,9 +2961,7 @@ fn environment_resolve_pptr_path_key_best_effort_loads_external_bundle_from_subd
     let out_dir = temp.path().join("out");
     session
         .save(
-            PackerOptions {
-                packer: UnityPyPacker::Original,
-            },
+            PackerOptions::default().with_packer(UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -3122,9 +3397,8 @@ fn external_bundle_can_edit_material_and_unitypy_observes_change() {
     let out_dir = temp.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -3246,9 +3520,8 @@ fn external_bundle_can_edit_mesh_renderer_materials_and_reload() {
     let out_dir = temp.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -3374,9 +3647,8 @@ fn external_bundle_can_edit_text_asset_script_and_unitypy_observes_change() {
     let out_dir = temp.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -3476,9 +3748,8 @@ fn external_bundle_can_edit_mesh_stream_data_and_unitypy_observes_change() {
     let out_dir = temp.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -3607,9 +3878,8 @@ fn external_bundle_can_edit_video_clip_external_resources_and_unitypy_observes_c
     let out_dir = temp.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -3722,9 +3992,8 @@ Transform:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -3798,9 +4067,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -3926,9 +4194,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -4059,9 +4326,8 @@ Transform:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -4157,9 +4423,8 @@ RectTransform:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -4314,9 +4579,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -4411,9 +4675,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -4516,9 +4779,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -4645,9 +4907,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -4760,9 +5021,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -4876,9 +5136,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -5002,9 +5261,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -5135,9 +5393,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -5266,9 +5523,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -5446,9 +5702,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -5637,9 +5892,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -5773,9 +6027,8 @@ CanvasGroup:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -5860,9 +6113,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -5931,9 +6183,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -5994,9 +6245,8 @@ ToggleGroup:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -6091,9 +6341,8 @@ MonoBehaviour:
     let out_dir = dir.path().join("out");
     session
         .save(
-            unity_asset_write::PackerOptions {
-                packer: unity_asset_write::UnityPyPacker::Original,
-            },
+            unity_asset_write::PackerOptions::default()
+                .with_packer(unity_asset_write::UnityPyPacker::Original),
             &out_dir,
         )
         .unwrap();
@@ -6152,3 +6401,333 @@ MonoBehaviour:
     });
     assert_eq!(target_file_id, Some(114002), "target={:?}", target);
 }
+
+#[test]
+fn environment_load_file_propagates_binary_errors_with_source() {
+    use std::error::Error as _;
+    use unity_asset_binary::error::BinaryError;
+
+    let bundle_path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples/char_118_yuki.ab");
+    let bytes = fs::read(bundle_path).unwrap();
+
+    let tmp = tempfile::tempdir().unwrap();
+    let truncated = tmp.path().join("truncated.ab");
+    fs::write(&truncated, &bytes[..256]).unwrap();
+
+    let mut env = Environment::new();
+    let err = env.load_file(&truncated).unwrap_err();
+    assert!(matches!(err, UnityAssetError::WithSource { .. }));
+    assert!(err.to_string().contains("truncated.ab"));
+    assert!(
+        err.source()
+            .and_then(|s| s.downcast_ref::<BinaryError>())
+            .is_some()
+    );
+
+    // Files that are not Unity binaries at all are still skipped silently.
+    let not_unity = tmp.path().join("notes.bin");
+    fs::write(&not_unity, b"just some bytes").unwrap();
+    env.load_file(&not_unity).unwrap();
+}
+
+#[test]
+fn facade_bundle_loaders_return_core_errors() {
+    use std::error::Error as _;
+    use unity_asset_binary::error::BinaryError;
+
+    let err = crate::load_bundle_from_memory(b"UnityFS\0garbage".to_vec()).unwrap_err();
+    assert!(matches!(err, UnityAssetError::Binary { .. }));
+    let source = err.source().and_then(|s| s.downcast_ref::<BinaryError>());
+    assert!(source.is_some());
+    assert_eq!(
+        err.to_string(),
+        format!("Binary error: {}", source.unwrap())
+    );
+
+    let err = crate::load_bundle("/definitely/not/here.bundle").unwrap_err();
+    let source = err.source().and_then(|s| s.downcast_ref::<BinaryError>());
+    assert!(matches!(source, Some(BinaryError::Io(_))));
+}
+
+/// The first SerializedFile of a sample bundle, re-saved with `externals` appended.
+fn sample_serialized_file_with_externals(sample: &str, externals: &[&str]) -> Vec<u8> {
+    use unity_asset_write::serialized_file::{SerializedFileEdits, SerializedFileWriter};
+
+    let bundle_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/samples")
+        .join(sample);
+    let bundle =
+        unity_asset_binary::bundle::BundleParser::from_bytes(fs::read(bundle_path).unwrap())
+            .unwrap();
+    let file = bundle
+        .assets
+        .first()
+        .expect("sample bundle has a SerializedFile");
+
+    let mut edits = SerializedFileEdits::new();
+    for path in externals {
+        edits.add_external(unity_asset_binary::asset::FileIdentifier {
+            path: path.to_string(),
+            ..Default::default()
+        });
+    }
+    SerializedFileWriter::save(file, &edits).unwrap()
+}
+
+#[test]
+fn environment_load_player_data_resolves_cross_file_pptrs_and_resources() {
+    let temp = tempfile::tempdir().unwrap();
+    let data = temp.path().join("Game_Data");
+    fs::create_dir_all(data.join("Resources")).unwrap();
+    fs::create_dir_all(data.join("Managed")).unwrap();
+
+    // Players name each other in lowercase, sometimes under `library/`.
+    fs::write(
+        data.join("level0"),
+        sample_serialized_file_with_externals(
+            "banner_1",
+            &[
+                "library/sharedassets0.assets",
+                "library/unity default resources",
+            ],
+        ),
+    )
+    .unwrap();
+    fs::write(
+        data.join("sharedassets0.assets"),
+        sample_serialized_file_with_externals("atlas_test", &["Level0"]),
+    )
+    .unwrap();
+    let mut resource = vec![0u8; 16];
+    resource.extend_from_slice(b"OggS");
+    fs::write(data.join("sharedassets0.assets.resS"), &resource).unwrap();
+    fs::write(data.join("boot.config"), b"gfx-enable-gfx-jobs=1\n").unwrap();
+    link_or_copy_file(
+        &PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples/char_118_yuki.ab"),
+        &data.join("Resources").join("unity default resources"),
+    )
+    .unwrap();
+
+    let mut env = Environment::new();
+    let stats = env.load_player_data(&data).unwrap();
+    assert_eq!(stats.serialized_loaded, 2);
+    assert_eq!(stats.resources_registered, 1);
+    assert_eq!(stats.builtin_loaded, 1);
+    assert_eq!(stats.failed, 0);
+
+    let data = canonicalize_path(data);
+    let level0 = BinarySource::path(data.join("level0"));
+    let shared = BinarySource::path(data.join("sharedassets0.assets"));
+    let builtin = BinarySource::path(data.join("Resources").join("unity default resources"));
+
+    let level0_ref = env
+        .binary_object_infos()
+        .find(|r| r.source == &level0)
+        .expect("level0 has objects");
+    let externals = &level0_ref.object.file().externals;
+    let shared_file_id = externals.len() as i32 - 1;
+    let resolved = env
+        .resolve_binary_pptr(&level0_ref, shared_file_id, 42)
+        .expect("sharedassets0.assets resolves");
+    assert_eq!(resolved.source, shared);
+    assert_eq!(resolved.path_id, 42);
+    let resolved = env
+        .resolve_binary_pptr(&level0_ref, shared_file_id + 1, 7)
+        .expect("built-in resources resolve to the loaded copy");
+    assert_eq!(resolved.source, builtin);
+
+    let shared_ref = env
+        .binary_object_infos()
+        .find(|r| r.source == &shared)
+        .expect("sharedassets0.assets has objects");
+    let level0_file_id = shared_ref.object.file().externals.len() as i32;
+    let resolved = env
+        .resolve_binary_pptr(&shared_ref, level0_file_id, 1)
+        .expect("level0 resolves");
+    assert_eq!(resolved.source, level0);
+
+    // Registered resources are found by name whatever the stream path's prefix or case.
+    assert_eq!(
+        env.resource_file_path("archive:/Library/SharedAssets0.assets.resS"),
+        Some(data.join("sharedassets0.assets.resS").as_path())
+    );
+    let bytes = env
+        .read_stream_data(
+            data.join("level0"),
+            BinarySourceKind::SerializedFile,
+            "SharedAssets0.assets.resS",
+            16,
+            4,
+        )
+        .unwrap();
+    assert_eq!(bytes, b"OggS");
+
+    let not_player = temp.path().join("empty");
+    fs::create_dir_all(&not_player).unwrap();
+    assert!(Environment::new().load_player_data(&not_player).is_err());
+}
+
+#[test]
+fn environment_loads_split_serialized_files_under_their_unsplit_name() {
+    let temp = tempfile::tempdir().unwrap();
+    let dir = temp.path();
+
+    fs::write(
+        dir.join("level0"),
+        sample_serialized_file_with_externals("banner_1", &["sharedassets1.assets"]),
+    )
+    .unwrap();
+    let shared = sample_serialized_file_with_externals("atlas_test", &[]);
+    let chunk = shared.len().div_ceil(3);
+    for (index, part) in shared.chunks(chunk).enumerate() {
+        fs::write(dir.join(format!("sharedassets1.assets.split{index}")), part).unwrap();
+    }
+
+    let mut env = Environment::new();
+    env.load_directory(dir).unwrap();
+    assert!(env.warnings().is_empty(), "{:?}", env.warnings());
+
+    // The three chunks load once, keyed by the unsplit path.
+    let dir = canonicalize_path(dir.to_path_buf());
+    let shared_source = BinarySource::path(dir.join("sharedassets1.assets"));
+    assert_eq!(env.binary_assets().len(), 2);
+    assert!(env.binary_assets().contains_key(&shared_source));
+
+    let level0 = BinarySource::path(dir.join("level0"));
+    let level0_ref = env
+        .binary_object_infos()
+        .find(|r| r.source == &level0)
+        .expect("level0 has objects");
+    let file_id = level0_ref.object.file().externals.len() as i32;
+    let resolved = env
+        .resolve_binary_pptr(&level0_ref, file_id, 5)
+        .expect("sharedassets1.assets resolves to the reassembled file");
+    assert_eq!(resolved.source, shared_source);
+
+    assert_eq!(
+        env.find_dependency_path_best_effort("sharedassets1.assets"),
+        Some(dir.join("sharedassets1.assets.split0"))
+    );
+}
+
+#[test]
+fn environment_resolves_layer_and_tag_names_from_project_tag_manager() {
+    let temp = tempfile::tempdir().unwrap();
+    let settings_dir = temp.path().join("ProjectSettings");
+    fs::create_dir_all(&settings_dir).unwrap();
+
+    // Only builtin layers and tags before the TagManager is loaded.
+    let mut env = Environment::new();
+    assert_eq!(env.layer_name(4).as_deref(), Some("Water"));
+    assert_eq!(env.layer_name(8), None);
+    assert_eq!(env.tag_names().len(), 7);
+
+    fs::copy(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../unity-asset-yaml/tests/fixtures/TagManager.asset"),
+        settings_dir.join("TagManager.asset"),
+    )
+    .unwrap();
+    let mut options = ProjectLoadOptions::everything();
+    options.respect_ignores = false;
+    env.load_project(temp.path(), options).unwrap();
+
+    assert_eq!(env.layer_name(8).as_deref(), Some("Ground"));
+    assert_eq!(env.layer_name(9).as_deref(), Some("Enemies"));
+    assert_eq!(env.layer_name(5).as_deref(), Some("UI"));
+    let tags = env.tag_names();
+    assert_eq!(tags.first().map(String::as_str), Some("Untagged"));
+    assert!(tags.ends_with(&["Enemy".to_string(), "Pickup".to_string()]));
+
+    let manager = env.tag_manager().unwrap();
+    assert_eq!(manager.user_layers(), vec![(8, "Ground"), (9, "Enemies")]);
+    let config =
+        unity_asset_binary::metadata::ExtractionConfig::default().with_tag_manager(Some(manager));
+    assert!(config.tag_manager.is_some());
+}
+
+#[test]
+fn environment_reads_streamed_resources_from_registered_sources() {
+    use unity_asset_binary::data_source::BytesSource;
+
+    let mut env = Environment::new();
+    let payload: Vec<u8> = (0u8..64).collect();
+    env.register_resource_source(
+        "CAB-0123456789abcdef0123456789abcdef.resS",
+        BytesSource::new("CAB-0123456789abcdef0123456789abcdef.resS", payload),
+    );
+    assert!(env.resource_sources().contains(
+        "archive:/CAB-0123456789abcdef0123456789abcdef/CAB-0123456789abcdef0123456789abcdef.resS"
+    ));
+
+    // No file exists on disk; the registered source answers the read.
+    let bytes = env
+        .read_stream_data(
+            "/nonexistent/level0",
+            BinarySourceKind::SerializedFile,
+            "archive:/CAB-0123456789abcdef0123456789abcdef/CAB-0123456789abcdef0123456789abcdef.resS",
+            10,
+            4,
+        )
+        .unwrap();
+    assert_eq!(bytes, vec![10, 11, 12, 13]);
+    assert!(
+        env.read_stream_data(
+            "/nonexistent/level0",
+            BinarySourceKind::SerializedFile,
+            "CAB-0123456789abcdef0123456789abcdef.resS",
+            62,
+            4,
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn objects_by_class_spans_yaml_and_binary_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    link_or_copy_file(
+        &root.join("tests/fixtures/guid_project/Assets/Materials/Crate.mat"),
+        &dir.path().join("Assets/Crate.mat"),
+    )
+    .unwrap();
+    link_or_copy_file(
+        &root.join("tests/fixtures/guid_project/Assets/Prefabs/Crate.prefab"),
+        &dir.path().join("Assets/Crate.prefab"),
+    )
+    .unwrap();
+    link_or_copy_file(
+        &root.join("../../tests/samples/banner_1"),
+        &dir.path().join("StreamingAssets/banner_1.bundle"),
+    )
+    .unwrap();
+
+    let mut env = Environment::new();
+    env.load(dir.path()).unwrap();
+    assert_eq!(env.bundles().len(), 1);
+
+    let textures: Vec<_> = env.objects_by_class("Texture2D").collect();
+    assert_eq!(textures.len(), 1);
+    assert!(matches!(textures[0], EnvironmentObjectRef::Binary(_)));
+    assert_eq!(textures[0].class_id(), 28);
+    let texture = textures[0].to_unity_class().unwrap();
+    assert!(matches!(texture, std::borrow::Cow::Owned(_)));
+    assert_eq!(texture.class_name, "Texture2D");
+    assert_eq!(texture.name(), Some("banner_1"));
+
+    // Materials come from the YAML `.mat` only; the bundle holds a Sprite, not a Material.
+    let materials: Vec<_> = env.objects_by_class("Material").collect();
+    assert_eq!(materials.len(), 1);
+    assert!(matches!(
+        materials[0].to_unity_class().unwrap(),
+        std::borrow::Cow::Borrowed(_)
+    ));
+    assert_eq!(env.filter_by_class("Material").len(), 1);
+    assert_eq!(env.objects_by_class("Sprite").count(), 1);
+    assert_eq!(
+        env.objects_by_class("GameObject").count(),
+        env.filter_by_class("GameObject").len()
+    );
+}
@@ -0,0 +1,53 @@
+use super::path::canonicalize_if_exists;
+use super::*;
+use crate::unitypackage::{UnityPackage, UnityPackageAssetKind};
+
+impl Environment {
+    /// Load the assets of a `.unitypackage` archive.
+    ///
+    /// Entries are keyed under the archive path joined with their project path: YAML assets are
+    /// stored as documents at `<archive>/<project path>`, bundles and serialized files as
+    /// [`BinarySource::ArchiveEntry`] named by project path, and `.meta` GUIDs are indexed so
+    /// references between the package's assets resolve. Entries without a `pathname` and
+    /// non-Unity payloads (textures, scripts, ...) are skipped.
+    pub fn load_unitypackage<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let archive_path = canonicalize_if_exists(path.as_ref());
+        let package = UnityPackage::open(&archive_path)?;
+        if let Some(parent) = archive_path.parent() {
+            self.base_path = parent.to_path_buf();
+        }
+
+        for entry in package.entries() {
+            let Some(project_path) = entry.path.as_deref() else {
+                continue;
+            };
+            let asset_path = archive_path.join(project_path);
+            if let Some(meta) = &entry.meta {
+                let mut meta_path = asset_path.clone().into_os_string();
+                meta_path.push(".meta");
+                self.index_meta_guid_text(Path::new(&meta_path), meta);
+            }
+
+            match entry.asset_kind() {
+                UnityPackageAssetKind::Yaml => match entry.load_yaml() {
+                    Ok(doc) => self.insert_yaml_or_binary(asset_path, Ok((doc, Vec::new())))?,
+                    Err(e) => self.push_warning(EnvironmentWarning::LoadFailed {
+                        path: asset_path,
+                        error: e.to_string(),
+                    }),
+                },
+                UnityPackageAssetKind::Binary(_) => {
+                    let source = BinarySource::ArchiveEntry {
+                        archive_path: archive_path.clone(),
+                        entry_name: project_path.to_string(),
+                    };
+                    let bytes = entry.asset.clone().unwrap_or_default();
+                    self.try_load_unity_bytes(source, bytes);
+                }
+                UnityPackageAssetKind::Folder | UnityPackageAssetKind::Other => {}
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -2,12 +2,33 @@ use super::object_graph::{EnvironmentObjectKey, YamlObjectKey};
 use super::*;
 
 #[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
 pub struct YamlPptrReferenceSearchOptions {
     pub max_objects: Option<usize>,
     pub max_results: Option<usize>,
     pub max_pptrs_per_object: Option<usize>,
 }
 
+impl YamlPptrReferenceSearchOptions {
+    /// Set `max_objects`.
+    pub fn with_max_objects(mut self, max_objects: Option<usize>) -> Self {
+        self.max_objects = max_objects;
+        self
+    }
+
+    /// Set `max_results`.
+    pub fn with_max_results(mut self, max_results: Option<usize>) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    /// Set `max_pptrs_per_object`.
+    pub fn with_max_pptrs_per_object(mut self, max_pptrs_per_object: Option<usize>) -> Self {
+        self.max_pptrs_per_object = max_pptrs_per_object;
+        self
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct YamlPptrReference {
     pub from: YamlObjectKey,
@@ -26,11 +47,11 @@ pub(crate) struct YamlPptrRef {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct YamlPptrAtPath {
-    path: String,
-    file_id: i64,
-    guid: Option<[u8; 16]>,
-    type_id: Option<i64>,
+pub(super) struct YamlPptrAtPath {
+    pub path: String,
+    pub file_id: i64,
+    pub guid: Option<[u8; 16]>,
+    pub type_id: Option<i64>,
 }
 
 pub(crate) fn parse_yaml_pptr(value: &unity_asset_core::UnityValue) -> Option<YamlPptrRef> {
@@ -79,7 +100,10 @@ pub(crate) fn scan_yaml_pptrs(value: &UnityValue, out: &mut Vec<YamlPptrRef>) {
     }
 }
 
-fn scan_yaml_pptrs_with_paths(class: &UnityClass, max_pptrs: Option<usize>) -> Vec<YamlPptrAtPath> {
+pub(super) fn scan_yaml_pptrs_with_paths(
+    class: &UnityClass,
+    max_pptrs: Option<usize>,
+) -> Vec<YamlPptrAtPath> {
     fn parse_at_path(value: &UnityValue, path: &str) -> Option<YamlPptrAtPath> {
         let map = value.as_object()?;
         if map.is_empty() || map.len() > 3 {
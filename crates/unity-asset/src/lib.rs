@@ -44,7 +44,7 @@
 //!     println!("Found asset with {} objects", asset.object_count());
 //! }
 //!
-//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # Ok::<(), unity_asset::UnityAssetError>(())
 //! ```
 //!
 //! ## Async Processing (requires `async` feature)
@@ -71,20 +71,45 @@
 // Re-export from core crate
 pub use unity_asset_core::{
     DocumentFormat, Result, UnityAssetError, UnityClass, UnityClassRegistry, UnityDocument,
-    UnityValue, constants::*,
+    UnityGuid, UnityValue, UnityVersion, constants::*,
 };
 
 pub use unity_asset_core::get_class_name;
 pub use unity_asset_core::get_class_name_str;
 
+// MessagePack/CBOR export (requires `interchange` feature)
+#[cfg(feature = "interchange")]
+pub use unity_asset_core::interchange;
+
 // Re-export from YAML crate
 pub use unity_asset_yaml::YamlDocument;
 
 // Re-export from binary crate
 pub use unity_asset_binary::asset::SerializedFile;
-pub use unity_asset_binary::bundle::{
-    AssetBundle, load_bundle, load_bundle_from_memory, load_bundle_with_options,
-};
+pub use unity_asset_binary::bundle::{AssetBundle, BundleLoadOptions};
+
+/// Load an AssetBundle from a file.
+///
+/// Binary parser failures surface as [`UnityAssetError::Binary`], with the original
+/// `BinaryError` available via `source()`.
+pub fn load_bundle<P: AsRef<std::path::Path>>(path: P) -> Result<AssetBundle> {
+    Ok(unity_asset_binary::bundle::load_bundle(path)?)
+}
+
+/// Load an AssetBundle from in-memory bytes.
+pub fn load_bundle_from_memory(data: Vec<u8>) -> Result<AssetBundle> {
+    Ok(unity_asset_binary::bundle::load_bundle_from_memory(data)?)
+}
+
+/// Load an AssetBundle from a file with explicit loader options.
+pub fn load_bundle_with_options<P: AsRef<std::path::Path>>(
+    path: P,
+    options: BundleLoadOptions,
+) -> Result<AssetBundle> {
+    Ok(unity_asset_binary::bundle::load_bundle_with_options(
+        path, options,
+    )?)
+}
 
 // Re-export async traits when async feature is enabled
 #[cfg(feature = "async")]
@@ -92,3 +117,15 @@ pub use unity_asset_core::document::AsyncUnityDocument;
 
 /// Environment for managing multiple Unity assets
 pub mod environment;
+
+pub mod prelude;
+
+/// Regex search over string values of loaded assets
+pub mod search;
+
+/// `.unitypackage` archive reading and writing
+#[cfg(feature = "unitypackage")]
+pub mod unitypackage;
+
+/// Integrity checks over files and directory trees, for CI gates
+pub mod validate;
@@ -0,0 +1,35 @@
+//! The stable core surface, for glob import.
+//!
+//! ```no_run
+//! use unity_asset::prelude::*;
+//!
+//! let mut env = Environment::with_options(EnvironmentOptions::strict());
+//! env.load_project(
+//!     "MyProject",
+//!     ProjectLoadOptions::default().with_max_files(Some(10_000)),
+//! )?;
+//! # Ok::<(), UnityAssetError>(())
+//! ```
+//!
+//! Option and statistics structs are `#[non_exhaustive]`, so new fields can be added in minor
+//! releases. Build them from `Default` (or a preset) plus the `with_*` methods; struct literals,
+//! even with `..Default::default()`, do not compile outside the defining crate:
+//!
+//! ```compile_fail,E0639
+//! use unity_asset::prelude::*;
+//!
+//! let options = ProjectLoadOptions {
+//!     max_files: Some(10_000),
+//!     ..Default::default()
+//! };
+//! ```
+
+pub use crate::environment::{
+    BinaryObjectKey, BinarySource, Environment, EnvironmentObjectRef, EnvironmentOptions,
+    ProjectLoadOptions,
+};
+pub use crate::{
+    AssetBundle, BundleLoadOptions, DocumentFormat, Result, SerializedFile, UnityAssetError,
+    UnityClass, UnityDocument, UnityValue, UnityVersion, YamlDocument, load_bundle,
+    load_bundle_from_memory, load_bundle_with_options,
+};
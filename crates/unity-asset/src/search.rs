@@ -0,0 +1,249 @@
+//! Text search over loaded assets.
+//!
+//! [`grep`] runs a regex over the string values of everything an [`Environment`] has loaded:
+//! YAML scalar strings and the `string` fields of binary objects with a TypeTree. Binary objects
+//! are scanned with [`ObjectHandle::scan_strings`](unity_asset_binary::object::ObjectHandle::scan_strings),
+//! so only string fields are decoded; values (e.g. `TextAsset.m_Script`) are read as lossy UTF-8 up
+//! to [`GrepOptions::max_value_len`] bytes. Matching is per line, like `grep`.
+
+use crate::environment::{BinaryObjectRef, BinarySource, Environment};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use unity_asset_core::{UnityDocument, UnityValue};
+
+/// Search options for [`grep`].
+#[derive(Debug, Clone)]
+pub struct GrepOptions {
+    /// Only search objects of this class name (case-insensitive).
+    pub class_name: Option<String>,
+    /// Lines of context to collect before and after each matching line.
+    pub context_lines: usize,
+    /// Longest value prefix to search, in bytes.
+    pub max_value_len: usize,
+}
+
+impl Default for GrepOptions {
+    fn default() -> Self {
+        Self {
+            class_name: None,
+            context_lines: 0,
+            max_value_len: 1024 * 1024,
+        }
+    }
+}
+
+/// Where a match was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchLocation {
+    Yaml {
+        path: PathBuf,
+        anchor: String,
+    },
+    Binary {
+        source: BinarySource,
+        /// Asset index within a bundle.
+        asset_index: Option<usize>,
+        path_id: i64,
+        /// `m_Container` asset path of the object, for bundles.
+        container: Option<String>,
+    },
+}
+
+/// A line of a string value that matched the pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    pub location: MatchLocation,
+    pub class_name: String,
+    /// Dotted field path, with array indices (`m_Items[2].name`).
+    pub field_path: String,
+    /// 1-based line number within the value.
+    pub line_number: usize,
+    /// The matching line.
+    pub line: String,
+    /// Byte range of the first match within `line`.
+    pub range: (usize, usize),
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+/// Search the string values of all objects loaded into `env`.
+///
+/// Matches are ordered by source path, then by object order within each file.
+pub fn grep(env: &Environment, pattern: &Regex, options: &GrepOptions) -> Vec<Match> {
+    let mut out = Vec::new();
+    let class_matches = |name: &str| {
+        options
+            .class_name
+            .as_deref()
+            .is_none_or(|filter| filter.eq_ignore_ascii_case(name))
+    };
+
+    let mut documents: Vec<_> = env.yaml_documents().iter().collect();
+    documents.sort_by(|a, b| a.0.cmp(b.0));
+    for (path, document) in documents {
+        for object in document.entries() {
+            if !class_matches(&object.class_name) {
+                continue;
+            }
+            let location = MatchLocation::Yaml {
+                path: path.clone(),
+                anchor: object.anchor.clone(),
+            };
+            let mut field_path = String::new();
+            for (key, value) in object.properties() {
+                field_path.clear();
+                field_path.push_str(key);
+                grep_yaml_value(
+                    value,
+                    &mut field_path,
+                    &mut |field_path, text| {
+                        grep_value(
+                            pattern,
+                            options,
+                            &location,
+                            &object.class_name,
+                            field_path,
+                            text,
+                            &mut out,
+                        )
+                    },
+                    options.max_value_len,
+                );
+            }
+        }
+    }
+
+    let mut objects: Vec<BinaryObjectRef<'_>> = env.binary_object_infos().collect();
+    objects.sort_by(|a, b| (a.source, a.asset_index).cmp(&(b.source, b.asset_index)));
+    let mut containers: HashMap<&BinarySource, HashMap<(usize, i64), String>> = HashMap::new();
+    for object in objects {
+        let class_name = unity_asset_core::get_class_name(object.object.class_id())
+            .unwrap_or_else(|| format!("Class_{}", object.object.class_id()));
+        if !class_matches(&class_name) {
+            continue;
+        }
+        let Ok(Some(fields)) = object.object.scan_strings(options.max_value_len) else {
+            continue;
+        };
+        if fields.iter().all(|field| !pattern.is_match(&field.value)) {
+            continue;
+        }
+
+        let container = object.asset_index.and_then(|asset_index| {
+            containers
+                .entry(object.source)
+                .or_insert_with(|| container_paths(env, object.source))
+                .get(&(asset_index, object.object.path_id()))
+                .cloned()
+        });
+        let location = MatchLocation::Binary {
+            source: object.source.clone(),
+            asset_index: object.asset_index,
+            path_id: object.object.path_id(),
+            container,
+        };
+        for field in &fields {
+            grep_value(
+                pattern,
+                options,
+                &location,
+                &class_name,
+                &field.path,
+                &field.value,
+                &mut out,
+            );
+        }
+    }
+    out
+}
+
+fn container_paths(env: &Environment, source: &BinarySource) -> HashMap<(usize, i64), String> {
+    let mut paths = HashMap::new();
+    for entry in env
+        .bundle_container_entries_source(source)
+        .unwrap_or_default()
+    {
+        if let Some(key) = entry.key
+            && &key.source == source
+            && let Some(asset_index) = key.asset_index
+        {
+            paths
+                .entry((asset_index, key.path_id))
+                .or_insert(entry.asset_path);
+        }
+    }
+    paths
+}
+
+fn grep_yaml_value(
+    value: &UnityValue,
+    path: &mut String,
+    visit: &mut dyn FnMut(&str, &str),
+    max_value_len: usize,
+) {
+    match value {
+        UnityValue::String(text) => {
+            let mut end = text.len().min(max_value_len);
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            visit(path, &text[..end]);
+        }
+        UnityValue::Array(items) => {
+            let base = path.len();
+            for (index, item) in items.iter().enumerate() {
+                path.push_str(&format!("[{}]", index));
+                grep_yaml_value(item, path, visit, max_value_len);
+                path.truncate(base);
+            }
+        }
+        UnityValue::Object(fields) => {
+            let base = path.len();
+            for (key, item) in fields {
+                path.push('.');
+                path.push_str(key);
+                grep_yaml_value(item, path, visit, max_value_len);
+                path.truncate(base);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn grep_value(
+    pattern: &Regex,
+    options: &GrepOptions,
+    location: &MatchLocation,
+    class_name: &str,
+    field_path: &str,
+    text: &str,
+    out: &mut Vec<Match>,
+) {
+    if !pattern.is_match(text) {
+        return;
+    }
+    let lines: Vec<&str> = text.lines().collect();
+    for (index, line) in lines.iter().enumerate() {
+        let Some(found) = pattern.find(line) else {
+            continue;
+        };
+        let context = options.context_lines;
+        out.push(Match {
+            location: location.clone(),
+            class_name: class_name.to_string(),
+            field_path: field_path.to_string(),
+            line_number: index + 1,
+            line: line.to_string(),
+            range: (found.start(), found.end()),
+            before: lines[index.saturating_sub(context)..index]
+                .iter()
+                .map(|l| l.to_string())
+                .collect(),
+            after: lines[index + 1..lines.len().min(index + 1 + context)]
+                .iter()
+                .map(|l| l.to_string())
+                .collect(),
+        });
+    }
+}
@@ -0,0 +1,313 @@
+//! `.unitypackage` archives (requires the `unitypackage` feature)
+//!
+//! A package is a gzip'd tar with one directory per asset, named after the asset's GUID:
+//!
+//! ```text
+//! <guid>/asset         the file's bytes (absent for folders)
+//! <guid>/asset.meta    its .meta text
+//! <guid>/pathname      project path, e.g. `Assets/Prefabs/Player.prefab`
+//! <guid>/preview.png   optional editor thumbnail
+//! ```
+//!
+//! [`UnityPackage::open`] reads the archive once and keeps each entry's raw bytes; YAML and
+//! binary payloads are only parsed when asked for ([`UnityPackageEntry::load_yaml`],
+//! [`UnityPackageEntry::load_binary`]). [`UnityPackage::builder`] writes packages the editor's
+//! *Import Package* accepts.
+//!
+//! Entries without a `pathname` (some exporters drop it for folders) are kept with `path: None`;
+//! files outside a GUID directory (such as an Asset Store `.icon.png`) are ignored.
+
+use crate::{Result, UnityAssetError, UnityGuid, YamlDocument};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use unity_asset_binary::file::{
+    UnityFile, UnityFileKind, load_unity_file_from_memory, sniff_unity_file_kind_prefix,
+};
+
+/// What an entry's `asset` payload holds, judged from its leading bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnityPackageAssetKind {
+    /// No `asset` file: a folder
+    Folder,
+    /// Unity YAML text (`%YAML` header)
+    Yaml,
+    /// An AssetBundle, SerializedFile or WebFile
+    Binary(UnityFileKind),
+    /// Anything else (textures, scripts, audio, ...)
+    Other,
+}
+
+/// One GUID directory of a package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnityPackageEntry {
+    pub guid: UnityGuid,
+    /// Project path from `pathname`, with `/` separators
+    pub path: Option<String>,
+    /// Contents of `asset`
+    pub asset: Option<Vec<u8>>,
+    /// Contents of `asset.meta`
+    pub meta: Option<String>,
+    /// Contents of `preview.png`
+    pub preview: Option<Vec<u8>>,
+}
+
+impl UnityPackageEntry {
+    fn new(guid: UnityGuid) -> Self {
+        Self {
+            guid,
+            path: None,
+            asset: None,
+            meta: None,
+            preview: None,
+        }
+    }
+
+    /// Classify the `asset` payload
+    pub fn asset_kind(&self) -> UnityPackageAssetKind {
+        let Some(asset) = &self.asset else {
+            return UnityPackageAssetKind::Folder;
+        };
+        let text = asset.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(asset);
+        if text.starts_with(b"%YAML") {
+            return UnityPackageAssetKind::Yaml;
+        }
+        match sniff_unity_file_kind_prefix(&asset[..asset.len().min(64)]) {
+            Some(kind) => UnityPackageAssetKind::Binary(kind),
+            None => UnityPackageAssetKind::Other,
+        }
+    }
+
+    /// Parse the `asset` payload as Unity YAML
+    pub fn load_yaml(&self) -> Result<YamlDocument> {
+        let asset = self.require_asset()?;
+        Ok(YamlDocument::from_reader_with_warnings(asset)?.0)
+    }
+
+    /// Parse the `asset` payload as a bundle, serialized file or web file
+    pub fn load_binary(&self) -> Result<UnityFile> {
+        let asset = self.require_asset()?;
+        Ok(load_unity_file_from_memory(asset.to_vec())?)
+    }
+
+    fn require_asset(&self) -> Result<&[u8]> {
+        self.asset.as_deref().ok_or_else(|| {
+            UnityAssetError::format(format!("Package entry {} has no asset file", self.guid))
+        })
+    }
+}
+
+/// A parsed `.unitypackage`
+#[derive(Debug, Clone, Default)]
+pub struct UnityPackage {
+    path: Option<PathBuf>,
+    entries: Vec<UnityPackageEntry>,
+}
+
+impl UnityPackage {
+    /// Read a package from disk
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path).map_err(|e| {
+            UnityAssetError::with_source(format!("Failed to open unitypackage {:?}", path), e)
+        })?;
+        let mut package = Self::from_reader(std::io::BufReader::new(file))?;
+        package.path = Some(path.to_path_buf());
+        Ok(package)
+    }
+
+    /// Read a package from its gzip'd bytes
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        Self::from_reader(data)
+    }
+
+    /// Read a package from a gzip'd tar stream
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self> {
+        let tar_error = |e| UnityAssetError::with_source("Failed to read unitypackage tar", e);
+        let mut archive = tar::Archive::new(GzDecoder::new(reader));
+        let mut entries: BTreeMap<UnityGuid, UnityPackageEntry> = BTreeMap::new();
+
+        for file in archive.entries().map_err(tar_error)? {
+            let mut file = file.map_err(tar_error)?;
+            if !file.header().entry_type().is_file() {
+                continue;
+            }
+            let name = file
+                .path()
+                .map_err(tar_error)?
+                .to_string_lossy()
+                .replace('\\', "/");
+            let mut parts = name.trim_start_matches("./").splitn(2, '/');
+            let (Some(dir), Some(leaf)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let Ok(guid) = dir.parse::<UnityGuid>() else {
+                continue;
+            };
+
+            let mut bytes = Vec::new();
+            file.read_to_end(&mut bytes).map_err(tar_error)?;
+            let entry = entries
+                .entry(guid)
+                .or_insert_with(|| UnityPackageEntry::new(guid));
+            match leaf {
+                "asset" => entry.asset = Some(bytes),
+                "asset.meta" => entry.meta = Some(String::from_utf8_lossy(&bytes).into_owned()),
+                "preview.png" => entry.preview = Some(bytes),
+                // Older exporters append a second line (`00`) after the path.
+                "pathname" => {
+                    entry.path = String::from_utf8_lossy(&bytes)
+                        .lines()
+                        .next()
+                        .map(|line| line.trim().replace('\\', "/"))
+                        .filter(|line| !line.is_empty());
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            path: None,
+            entries: entries.into_values().collect(),
+        })
+    }
+
+    /// A builder for writing a new package
+    pub fn builder() -> UnityPackageBuilder {
+        UnityPackageBuilder::new()
+    }
+
+    /// The file this package was opened from
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// All entries, ordered by GUID
+    pub fn entries(&self) -> &[UnityPackageEntry] {
+        &self.entries
+    }
+
+    /// Find an entry by project path
+    pub fn entry_by_path(&self, path: &str) -> Option<&UnityPackageEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.path.as_deref() == Some(path))
+    }
+
+    /// Find an entry by GUID
+    pub fn entry_by_guid(&self, guid: &UnityGuid) -> Option<&UnityPackageEntry> {
+        self.entries.iter().find(|entry| entry.guid == *guid)
+    }
+}
+
+/// Writes `.unitypackage` archives
+///
+/// Each entry's GUID is read from the `guid:` line of its `.meta` text.
+#[derive(Debug, Clone, Default)]
+pub struct UnityPackageBuilder {
+    entries: Vec<(String, Option<Vec<u8>>, String)>,
+}
+
+impl UnityPackageBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file at project path `path` with its `.meta` text
+    pub fn add_asset(
+        mut self,
+        path: impl Into<String>,
+        bytes: impl Into<Vec<u8>>,
+        meta: impl Into<String>,
+    ) -> Self {
+        self.entries
+            .push((path.into(), Some(bytes.into()), meta.into()));
+        self
+    }
+
+    /// Add a folder at project path `path` with its `.meta` text
+    pub fn add_folder(mut self, path: impl Into<String>, meta: impl Into<String>) -> Self {
+        self.entries.push((path.into(), None, meta.into()));
+        self
+    }
+
+    /// Write the gzip'd archive into `writer`
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<()> {
+        let tar_error = |e| UnityAssetError::with_source("Failed to write unitypackage tar", e);
+        let mut seen = BTreeMap::new();
+        let mut tar = tar::Builder::new(GzEncoder::new(writer, Compression::default()));
+
+        for (path, asset, meta) in &self.entries {
+            let guid = meta_guid(meta).ok_or_else(|| {
+                UnityAssetError::format(format!("Meta for {} has no guid line", path))
+            })?;
+            if let Some(other) = seen.insert(guid, path) {
+                return Err(UnityAssetError::format(format!(
+                    "{} and {} share guid {}",
+                    other, path, guid
+                )));
+            }
+
+            append_dir(&mut tar, &guid.to_string()).map_err(tar_error)?;
+            if let Some(asset) = asset {
+                append_file(&mut tar, &format!("{}/asset", guid), asset).map_err(tar_error)?;
+            }
+            append_file(&mut tar, &format!("{}/asset.meta", guid), meta.as_bytes())
+                .map_err(tar_error)?;
+            append_file(&mut tar, &format!("{}/pathname", guid), path.as_bytes())
+                .map_err(tar_error)?;
+        }
+
+        tar.into_inner()
+            .and_then(|gz| gz.finish())
+            .map_err(tar_error)?;
+        Ok(())
+    }
+
+    /// Build the archive in memory
+    pub fn build(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        self.write_to(&mut out)?;
+        Ok(out)
+    }
+
+    /// Write the archive to `path`
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let file = std::fs::File::create(path).map_err(|e| {
+            UnityAssetError::with_source(format!("Failed to create unitypackage {:?}", path), e)
+        })?;
+        self.write_to(std::io::BufWriter::new(file))
+    }
+}
+
+/// The GUID on a `.meta` file's `guid:` line.
+fn meta_guid(meta: &str) -> Option<UnityGuid> {
+    meta.lines()
+        .find_map(|line| line.trim_start().strip_prefix("guid:"))
+        .and_then(|guid| guid.parse().ok())
+}
+
+fn append_dir<W: Write>(tar: &mut tar::Builder<W>, name: &str) -> std::io::Result<()> {
+    let mut header = tar::Header::new_ustar();
+    header.set_entry_type(tar::EntryType::Directory);
+    header.set_mode(0o755);
+    header.set_size(0);
+    tar.append_data(&mut header, name, std::io::empty())
+}
+
+fn append_file<W: Write>(
+    tar: &mut tar::Builder<W>,
+    name: &str,
+    data: &[u8],
+) -> std::io::Result<()> {
+    let mut header = tar::Header::new_ustar();
+    header.set_entry_type(tar::EntryType::Regular);
+    header.set_mode(0o644);
+    header.set_size(data.len() as u64);
+    tar.append_data(&mut header, name, data)
+}
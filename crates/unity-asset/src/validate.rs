@@ -0,0 +1,435 @@
+//! Integrity checks over files and directory trees, for CI gates
+//!
+//! [`run`] walks the given paths, classifies every file by its signature (or a `%YAML` header
+//! for text assets), runs the checks for its kind and aggregates [`Finding`]s per file into a
+//! [`ValidationRun`]. Files that are not Unity assets are skipped and only counted.
+//!
+//! Checks (the `check` field of a finding):
+//!
+//! | check | kind | severity |
+//! |---|---|---|
+//! | `file.read` | any | error: the file could not be read |
+//! | `file.unrecognized` | explicitly listed file | warning: not a Unity asset |
+//! | `bundle.header` | AssetBundle | error: header does not parse or validate |
+//! | `bundle.decompress` | AssetBundle | error: blocks info or data blocks do not decompress |
+//! | `bundle.structure` | AssetBundle | error: directory entries outside the bundle data |
+//! | `bundle.crc` | AssetBundle with a `<bundle>.manifest` | warning: CRC32 of the uncompressed data differs from the manifest |
+//! | `serialized.header` | SerializedFile, bundle entry | error/info from header validation |
+//! | `serialized.parse` | SerializedFile, bundle entry | error: metadata does not parse |
+//! | `serialized.structure` | SerializedFile, bundle entry | error: [`SerializedFile::validate`] failed |
+//! | `serialized.object` | deep only | error: an object fails strict TypeTree parsing |
+//! | `serialized.typetree` | deep only | info: no TypeTree, objects not parsed |
+//! | `webfile.parse` | WebFile | error: the container does not parse |
+//! | `yaml.parse` | YAML | error: the document does not parse |
+//! | `yaml.conversion` | YAML | warning: a document was converted with warnings |
+//!
+//! The JSON form of [`ValidationRun`] (serde, `snake_case`) is a stable schema; additions bump
+//! [`SCHEMA_VERSION`] only when existing fields change meaning.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use unity_asset_binary::asset::{SerializedFile, SerializedFileParser, validate_header};
+use unity_asset_binary::bundle::{
+    AssetBundle, BundleHeader, BundleLoadOptions, BundleManifest, BundleParser,
+};
+use unity_asset_binary::file::{UnityFileKind, load_unity_file_from_memory};
+use unity_asset_binary::reader::{BinaryReader, ByteOrder};
+use unity_asset_yaml::YamlDocument;
+
+/// Version of the JSON schema of [`ValidationRun`]
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Directory names never descended into
+const SKIPPED_DIRECTORY_NAMES: &[&str] = &["Library", "Temp", "Logs", ".git", ".vs", "obj"];
+
+/// How bad a finding is; ordered `Info < Warning < Error`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// What a file was recognized as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileKind {
+    AssetBundle,
+    SerializedFile,
+    WebFile,
+    Yaml,
+    /// Unreadable or unrecognized
+    Unknown,
+}
+
+/// One problem found in a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Finding {
+    pub severity: Severity,
+    /// Stable check id, e.g. `bundle.header` (see the module docs)
+    pub check: String,
+    pub message: String,
+    /// Bundle entry the finding is about
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>,
+    /// Object path id, for `serialized.object`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_id: Option<i64>,
+}
+
+impl Finding {
+    fn new(severity: Severity, check: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            check: check.to_string(),
+            message: message.into(),
+            entry: None,
+            path_id: None,
+        }
+    }
+
+    fn error(check: &str, message: impl ToString) -> Self {
+        Self::new(Severity::Error, check, message.to_string())
+    }
+}
+
+/// Findings of one file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub kind: FileKind,
+    pub findings: Vec<Finding>,
+}
+
+impl FileReport {
+    /// Most severe finding, `None` when the file is clean
+    pub fn worst(&self) -> Option<Severity> {
+        self.findings.iter().map(|f| f.severity).max()
+    }
+}
+
+/// Counts over a whole run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationSummary {
+    /// Files checked (those with a [`FileReport`])
+    pub files: usize,
+    /// Files skipped as not Unity assets
+    pub skipped: usize,
+    pub files_with_errors: usize,
+    pub files_with_warnings: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+}
+
+/// Result of [`run`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationRun {
+    pub schema_version: u32,
+    pub deep: bool,
+    /// One report per checked file, sorted by path
+    pub files: Vec<FileReport>,
+    pub summary: ValidationSummary,
+}
+
+impl ValidationRun {
+    /// Whether any finding is at least as severe as `threshold` (the `--fail-on` policy)
+    pub fn fails(&self, threshold: Severity) -> bool {
+        self.files
+            .iter()
+            .any(|file| file.worst().is_some_and(|worst| worst >= threshold))
+    }
+
+    /// All findings with their file
+    pub fn findings(&self) -> impl Iterator<Item = (&Path, &Finding)> {
+        self.files
+            .iter()
+            .flat_map(|file| file.findings.iter().map(|f| (file.path.as_path(), f)))
+    }
+}
+
+/// Options for [`run`]
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ValidateOptions {
+    /// Also parse every object with a strict TypeTree ([`SerializedFile::validate_deep`])
+    pub deep: bool,
+}
+
+impl ValidateOptions {
+    /// Set `deep`.
+    pub fn with_deep(mut self, deep: bool) -> Self {
+        self.deep = deep;
+        self
+    }
+}
+
+/// Validate files and directory trees
+///
+/// Directories are walked recursively (skipping `Library`, `Temp`, `.git`, ...). Paths that do
+/// not exist get a `file.read` error; explicitly listed files that are not Unity assets get a
+/// `file.unrecognized` warning, while unrecognized files found in directories are only counted.
+pub fn run<P: AsRef<Path>>(paths: &[P], options: &ValidateOptions) -> ValidationRun {
+    let mut files = Vec::new();
+    let mut skipped = 0usize;
+    for path in paths {
+        let path = path.as_ref();
+        if path.is_dir() {
+            let mut found = Vec::new();
+            walk(path, &mut found);
+            found.sort();
+            for file in found {
+                match validate_file(&file, options) {
+                    Some(report) => files.push(report),
+                    None => skipped += 1,
+                }
+            }
+        } else {
+            files.push(validate_file(path, options).unwrap_or_else(|| FileReport {
+                path: path.to_path_buf(),
+                kind: FileKind::Unknown,
+                findings: vec![Finding::new(
+                    Severity::Warning,
+                    "file.unrecognized",
+                    "Not an AssetBundle, SerializedFile, WebFile or Unity YAML file",
+                )],
+            }));
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut summary = ValidationSummary {
+        files: files.len(),
+        skipped,
+        ..Default::default()
+    };
+    for file in &files {
+        match file.worst() {
+            Some(Severity::Error) => summary.files_with_errors += 1,
+            Some(Severity::Warning) => summary.files_with_warnings += 1,
+            _ => {}
+        }
+        for finding in &file.findings {
+            match finding.severity {
+                Severity::Error => summary.errors += 1,
+                Severity::Warning => summary.warnings += 1,
+                Severity::Info => summary.infos += 1,
+            }
+        }
+    }
+    ValidationRun {
+        schema_version: SCHEMA_VERSION,
+        deep: options.deep,
+        files,
+        summary,
+    }
+}
+
+/// Validate one file; `None` when it is not a Unity asset
+pub fn validate_file(path: &Path, options: &ValidateOptions) -> Option<FileReport> {
+    let report = |kind, findings| {
+        Some(FileReport {
+            path: path.to_path_buf(),
+            kind,
+            findings,
+        })
+    };
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) => return report(FileKind::Unknown, vec![Finding::error("file.read", e)]),
+    };
+    match unity_asset_binary::file::sniff_unity_file_kind_prefix(&data) {
+        Some(UnityFileKind::AssetBundle) => {
+            report(FileKind::AssetBundle, check_bundle(path, data, options))
+        }
+        Some(UnityFileKind::SerializedFile) => report(
+            FileKind::SerializedFile,
+            check_serialized(data, None, options),
+        ),
+        Some(UnityFileKind::WebFile) => {
+            let findings = match load_unity_file_from_memory(data) {
+                Ok(_) => Vec::new(),
+                Err(e) => vec![Finding::error("webfile.parse", e)],
+            };
+            report(FileKind::WebFile, findings)
+        }
+        None if data.starts_with(b"%YAML") => report(FileKind::Yaml, check_yaml(&data)),
+        None => None,
+    }
+}
+
+fn walk(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            let name = entry.file_name();
+            if !SKIPPED_DIRECTORY_NAMES.contains(&name.to_string_lossy().as_ref()) {
+                walk(&path, out);
+            }
+        } else if file_type.is_file() {
+            out.push(path);
+        }
+    }
+}
+
+fn check_bundle(path: &Path, data: Vec<u8>, options: &ValidateOptions) -> Vec<Finding> {
+    let mut reader = BinaryReader::new(&data, ByteOrder::Big);
+    let header = match BundleHeader::from_reader(&mut reader) {
+        Ok(header) => header,
+        Err(e) => return vec![Finding::error("bundle.header", e)],
+    };
+    if let Err(e) = header.validate() {
+        return vec![Finding::error("bundle.header", e)];
+    }
+    if header.size > data.len() as u64 {
+        return vec![Finding::error(
+            "bundle.header",
+            format!(
+                "Header declares {} bytes but the file has {}",
+                header.size,
+                data.len()
+            ),
+        )];
+    }
+
+    let load = BundleLoadOptions::default()
+        .with_load_assets(false)
+        .with_decompress_blocks(true);
+    let bundle = match BundleParser::from_bytes_with_options(data, load) {
+        Ok(bundle) => bundle,
+        Err(e) => return vec![Finding::error("bundle.decompress", e)],
+    };
+    let mut findings = Vec::new();
+    if let Err(e) = bundle.validate() {
+        findings.push(Finding::error("bundle.structure", e));
+    }
+    findings.extend(check_manifest_crc(path, &bundle));
+
+    for node in &bundle.nodes {
+        if !node.is_file() || node.name.ends_with(".resS") || node.name.ends_with(".resource") {
+            continue;
+        }
+        let entry_data = match bundle.extract_node_data(node) {
+            Ok(entry_data) => entry_data,
+            Err(e) => {
+                let mut finding = Finding::error("bundle.structure", e);
+                finding.entry = Some(node.name.clone());
+                findings.push(finding);
+                continue;
+            }
+        };
+        // Entries that are neither SerializedFiles nor bundles (raw TextAssets, ...) are fine.
+        if unity_asset_binary::file::sniff_unity_file_kind_prefix(&entry_data)
+            != Some(UnityFileKind::SerializedFile)
+        {
+            continue;
+        }
+        findings.extend(check_serialized(
+            entry_data,
+            Some(node.name.as_str()),
+            options,
+        ));
+    }
+    findings
+}
+
+/// Compare the CRC32 of the uncompressed bundle data with `<bundle>.manifest`, when present
+fn check_manifest_crc(path: &Path, bundle: &AssetBundle) -> Option<Finding> {
+    let mut manifest_path = path.as_os_str().to_owned();
+    manifest_path.push(".manifest");
+    let manifest = BundleManifest::from_path(PathBuf::from(manifest_path)).ok()?;
+    if manifest.crc == 0 {
+        return None;
+    }
+    let data = bundle.data_checked().ok()?;
+    let crc = crc32fast::hash(data);
+    (crc != manifest.crc).then(|| {
+        Finding::new(
+            Severity::Warning,
+            "bundle.crc",
+            format!(
+                "CRC32 of the uncompressed data is {} but the manifest says {}",
+                crc, manifest.crc
+            ),
+        )
+    })
+}
+
+fn check_serialized(data: Vec<u8>, entry: Option<&str>, options: &ValidateOptions) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut push = |mut finding: Finding| {
+        finding.entry = entry.map(str::to_string);
+        findings.push(finding);
+    };
+    let file: SerializedFile = match SerializedFileParser::from_bytes(data) {
+        Ok(file) => file,
+        Err(e) => {
+            push(Finding::error("serialized.parse", e));
+            return findings;
+        }
+    };
+
+    let header = validate_header(&file.header);
+    for error in &header.errors {
+        push(Finding::error("serialized.header", error));
+    }
+    for warning in &header.warnings {
+        push(Finding::new(
+            Severity::Info,
+            "serialized.header",
+            warning.clone(),
+        ));
+    }
+
+    if !options.deep {
+        if let Err(e) = file.validate() {
+            push(Finding::error("serialized.structure", e));
+        }
+        return findings;
+    }
+    if !file.enable_type_tree {
+        if let Err(e) = file.validate() {
+            push(Finding::error("serialized.structure", e));
+        }
+        push(Finding::new(
+            Severity::Info,
+            "serialized.typetree",
+            "No TypeTree; objects were not parsed",
+        ));
+        return findings;
+    }
+    match file.validate_deep() {
+        Ok(failures) => {
+            for (path_id, e) in failures {
+                let mut finding = Finding::error("serialized.object", e);
+                finding.path_id = Some(path_id);
+                push(finding);
+            }
+        }
+        Err(e) => push(Finding::error("serialized.structure", e)),
+    }
+    findings
+}
+
+fn check_yaml(data: &[u8]) -> Vec<Finding> {
+    match YamlDocument::from_reader_with_warnings(data) {
+        Ok((_, warnings)) => warnings
+            .into_iter()
+            .map(|warning| {
+                Finding::new(
+                    Severity::Warning,
+                    "yaml.conversion",
+                    format!("{:?}", warning),
+                )
+            })
+            .collect(),
+        Err(e) => vec![Finding::error("yaml.parse", e)],
+    }
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "async")]
+
+use std::fs;
+use std::path::Path;
+
+use unity_asset::UnityDocument;
+use unity_asset::environment::{AsyncLoadOptions, Environment};
+
+const FILE_COUNT: usize = 300;
+
+fn write_project(root: &Path) {
+    for i in 0..FILE_COUNT {
+        let dir = root.join("Assets").join(format!("Group{}", i % 12));
+        fs::create_dir_all(&dir).unwrap();
+
+        let (ext, body) = if i % 5 == 0 {
+            (
+                "prefab",
+                format!(
+                    "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n\
+                     --- !u!1 &{id}\nGameObject:\n  m_Name: Prefab{i}\n  m_IsActive: 1\n\
+                     --- !u!4 &{tid}\nTransform:\n  m_GameObject: {{fileID: {id}}}\n  m_LocalPosition: {{x: {i}, y: 0, z: 0}}\n",
+                    id = 1000 + i,
+                    tid = 4000 + i,
+                ),
+            )
+        } else {
+            (
+                "asset",
+                format!(
+                    "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n\
+                     --- !u!114 &11400000\nMonoBehaviour:\n  m_Name: Item{i}\n  value: {i}\n  tags:\n  - a{i}\n  - b{i}\n"
+                ),
+            )
+        };
+        let path = dir.join(format!("Item{i}.{ext}"));
+        fs::write(&path, body).unwrap();
+
+        if i % 3 == 0 {
+            let meta = format!("fileFormatVersion: 2\nguid: {:032x}\n", i + 1);
+            fs::write(dir.join(format!("Item{i}.{ext}.meta")), meta).unwrap();
+        }
+    }
+
+    // Not YAML: falls back to binary detection and is ignored.
+    fs::write(root.join("Assets/broken.asset"), b"\x00\x01\x02 not yaml").unwrap();
+    // Skipped directory.
+    fs::create_dir_all(root.join("Library")).unwrap();
+    fs::write(
+        root.join("Library/cached.asset"),
+        "%YAML 1.1\n--- !u!114 &1\nMonoBehaviour:\n  m_Name: Cached\n",
+    )
+    .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn load_directory_async_matches_sync_path() {
+    let dir = tempfile::tempdir().unwrap();
+    write_project(dir.path());
+
+    let mut sync_env = Environment::new();
+    sync_env.load_directory(dir.path()).unwrap();
+
+    let mut async_env = Environment::new();
+    let stats = async_env
+        .load_directory_async_with_options(
+            dir.path(),
+            AsyncLoadOptions::default().with_max_concurrent_files(4),
+        )
+        .await
+        .unwrap();
+
+    let sync_docs = sync_env.yaml_documents();
+    let async_docs = async_env.yaml_documents();
+    assert!(sync_docs.len() >= FILE_COUNT);
+    assert_eq!(async_docs.len(), sync_docs.len());
+    assert!(
+        !async_docs
+            .keys()
+            .any(|p| p.starts_with(dir.path().join("Library")))
+    );
+
+    for (path, expected) in sync_docs {
+        let actual = async_docs
+            .get(path)
+            .unwrap_or_else(|| panic!("missing {path:?}"));
+        assert_eq!(actual.entries().len(), expected.entries().len(), "{path:?}");
+        assert_eq!(
+            actual.dump_yaml().ok(),
+            expected.dump_yaml().ok(),
+            "{path:?}"
+        );
+    }
+
+    let mut sync_warnings: Vec<String> =
+        sync_env.warnings().iter().map(|w| w.to_string()).collect();
+    let async_warnings: Vec<String> = async_env.warnings().iter().map(|w| w.to_string()).collect();
+    sync_warnings.sort();
+    let mut sorted_async = async_warnings.clone();
+    sorted_async.sort();
+    assert_eq!(sorted_async, sync_warnings);
+
+    assert_eq!(stats.files_discovered, stats.yaml_files + stats.other_files);
+    assert!(stats.yaml_files > FILE_COUNT);
+    assert!(stats.peak_concurrent_files >= 1);
+    assert!(
+        stats.peak_concurrent_files <= 4,
+        "concurrency bound exceeded: {}",
+        stats.peak_concurrent_files
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn load_directory_async_is_deterministic() {
+    let dir = tempfile::tempdir().unwrap();
+    write_project(dir.path());
+
+    let mut first = Environment::new();
+    first.load_directory_async(dir.path()).await.unwrap();
+    let mut second = Environment::new();
+    second.load_async(dir.path()).await.unwrap();
+
+    let warnings = |env: &Environment| -> Vec<String> {
+        env.warnings().iter().map(|w| w.to_string()).collect()
+    };
+    assert_eq!(warnings(&first), warnings(&second));
+
+    let mut first_paths: Vec<_> = first.yaml_documents().keys().cloned().collect();
+    let mut second_paths: Vec<_> = second.yaml_documents().keys().cloned().collect();
+    first_paths.sort();
+    second_paths.sort();
+    assert_eq!(first_paths, second_paths);
+}
+
+#[tokio::test]
+async fn load_async_single_yaml_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Single.asset");
+    fs::write(
+        &path,
+        "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n--- !u!114 &1\nMonoBehaviour:\n  m_Name: Single\n",
+    )
+    .unwrap();
+
+    let mut env = Environment::new();
+    env.load_async(&path).await.unwrap();
+    assert_eq!(env.yaml_documents().len(), 1);
+
+    let missing = Environment::new()
+        .load_directory_async(dir.path().join("missing"))
+        .await;
+    assert!(missing.is_err());
+}
@@ -0,0 +1,141 @@
+//! GUID dependency graph over `tests/fixtures/guid_project`: a build scene instantiates a prefab
+//! whose renderer uses a material sampling a texture; a second material is never used.
+
+use std::path::{Path, PathBuf};
+
+use unity_asset::UnityGuid;
+use unity_asset::environment::{Environment, GuidGraph, ProjectLoadOptions};
+
+const SCENE: &str = "5b1c0f2e9d8a4c3b8e7f6a5d4c3b2a10";
+const PREFAB: &str = "8f3e2d1c0b9a48776655443322110011";
+const MATERIAL: &str = "c0ffee00c0ffee00c0ffee00c0ffee01";
+const TEXTURE: &str = "0badf00d0badf00d0badf00d0badf00d";
+const ORPHAN: &str = "dead0000beef0000dead0000beef0000";
+const DEFAULT_RESOURCES: &str = "0000000000000000f000000000000000";
+
+fn guid(hex: &str) -> UnityGuid {
+    hex.parse().unwrap()
+}
+
+fn project() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/guid_project")
+        .canonicalize()
+        .unwrap()
+}
+
+fn graph() -> GuidGraph {
+    let mut env = Environment::new();
+    env.load_project(
+        project(),
+        ProjectLoadOptions::binaries_only()
+            .with_load_yaml_documents(true)
+            .with_load_binary_files(false),
+    )
+    .unwrap();
+    env.guid_dependency_graph()
+}
+
+#[test]
+fn guid_graph_follows_scene_prefab_material_texture_chain() {
+    let graph = graph();
+
+    let scene = graph.node(&guid(SCENE)).unwrap();
+    assert_eq!(scene.main_class.as_deref(), Some("SceneAsset"));
+    assert_eq!(
+        scene.path.as_deref(),
+        Some(project().join("Assets/Scenes/Main.unity").as_path())
+    );
+    assert_eq!(
+        graph.node(&guid(PREFAB)).unwrap().main_class.as_deref(),
+        Some("GameObject")
+    );
+    assert_eq!(
+        graph.node(&guid(MATERIAL)).unwrap().main_class.as_deref(),
+        Some("Material")
+    );
+    let texture = graph.node(&guid(TEXTURE)).unwrap();
+    assert_eq!(texture.main_class, None);
+    assert!(texture.path.as_ref().unwrap().ends_with("crate.png"));
+    // Built-in shader GUIDs are nodes without a path.
+    assert_eq!(graph.node(&guid(DEFAULT_RESOURCES)).unwrap().path, None);
+
+    assert_eq!(graph.dependencies_of(&guid(SCENE)), [guid(PREFAB)]);
+    assert_eq!(graph.dependencies_of(&guid(PREFAB)), [guid(MATERIAL)]);
+
+    let edge = graph.edges_from(&guid(SCENE)).next().unwrap();
+    assert_eq!(edge.anchor, "100000");
+    assert_eq!(edge.field_path, "m_SourcePrefab");
+    assert_eq!(edge.file_id, 100100000);
+    let edge = graph.edges_from(&guid(PREFAB)).next().unwrap();
+    assert_eq!(
+        (edge.anchor.as_str(), edge.field_path.as_str()),
+        ("23000", "m_Materials[0]")
+    );
+    let texture_edges: Vec<_> = graph
+        .edges_from(&guid(MATERIAL))
+        .filter(|e| e.to == guid(TEXTURE))
+        .collect();
+    assert_eq!(texture_edges.len(), 1);
+    assert_eq!(
+        texture_edges[0].field_path,
+        "m_SavedProperties.m_TexEnvs[0]._MainTex.m_Texture"
+    );
+
+    assert_eq!(graph.dependents_of(&guid(PREFAB)), [guid(SCENE)]);
+    let mut users = vec![guid(MATERIAL), guid(ORPHAN)];
+    users.sort();
+    assert_eq!(graph.dependents_of(&guid(TEXTURE)), users);
+    assert!(graph.dependents_of(&guid(SCENE)).is_empty());
+}
+
+#[test]
+fn guid_graph_reports_assets_unreachable_from_build_scenes() {
+    let graph = graph();
+    assert_eq!(graph.roots(), [guid(SCENE)]);
+
+    let reachable = graph.reachable_from(graph.roots());
+    for hex in [SCENE, PREFAB, MATERIAL, TEXTURE] {
+        assert!(reachable.contains(&guid(hex)), "{hex}");
+    }
+
+    // The folder GUID (`Assets/Materials.meta`) is not an asset.
+    let orphans: Vec<UnityGuid> = graph.unreferenced().iter().map(|n| n.guid).collect();
+    assert_eq!(orphans, [guid(ORPHAN)]);
+
+    let orphans: Vec<UnityGuid> = graph
+        .unreferenced_from(&[guid(MATERIAL)])
+        .iter()
+        .map(|n| n.guid)
+        .collect();
+    let mut expected = vec![guid(SCENE), guid(PREFAB), guid(ORPHAN)];
+    expected.sort();
+    assert_eq!(orphans, expected);
+}
+
+#[test]
+fn guid_graph_exports_dot_and_json() {
+    let graph = graph();
+
+    let dot = graph.to_dot(usize::MAX);
+    assert!(dot.starts_with("digraph unity_asset_guid_graph {"));
+    assert!(dot.contains(&format!(
+        "\"{SCENE}\" -> \"{PREFAB}\" [label=\"m_SourcePrefab\"];"
+    )));
+    assert!(dot.contains(&format!("\"{MATERIAL}\" [label=\"Crate.mat\\nMaterial\"];")));
+    let truncated = graph.to_dot(1);
+    assert_eq!(truncated.matches(" -> ").count(), 1);
+    assert!(truncated.contains("// truncated: max_edges=1"));
+
+    let json: serde_json::Value = serde_json::from_str(&graph.to_json().unwrap()).unwrap();
+    assert_eq!(json["roots"], serde_json::json!([SCENE]));
+    let nodes = json["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), graph.nodes().len());
+    let texture = nodes.iter().find(|n| n["guid"] == TEXTURE).unwrap();
+    assert!(texture.get("main_class").is_none());
+    let edges = json["edges"].as_array().unwrap();
+    assert!(edges.iter().any(|e| e["from"] == PREFAB
+        && e["to"] == MATERIAL
+        && e["field_path"] == "m_Materials[0]"
+        && e["file_id"] == 2100000));
+}
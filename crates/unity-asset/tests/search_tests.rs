@@ -0,0 +1,159 @@
+//! `search::grep` over YAML and binary sources, asserting exact match locations.
+
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use unity_asset::environment::Environment;
+use unity_asset::search::{GrepOptions, Match, MatchLocation, grep};
+
+const YAML: &str = r#"%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!49 &4900000
+TextAsset:
+  m_Name: dialogue
+  m_Script: "intro\nwake boss_phase_3\noutro"
+--- !u!114 &11400000
+MonoBehaviour:
+  m_Name: BossConfig
+  phases:
+  - name: boss_phase_1
+    hp: 100
+  - name: boss_phase_3
+    hp: 300
+"#;
+
+fn sample(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/samples")
+        .join(name)
+}
+
+fn environment(dir: &Path) -> (Environment, PathBuf) {
+    let yaml = dir.join("Boss.asset");
+    std::fs::write(&yaml, YAML).unwrap();
+    let mut env = Environment::new();
+    env.load(&yaml).unwrap();
+    env.load(sample("banner_1")).unwrap();
+    let yaml = env.yaml_documents().keys().next().unwrap().clone();
+    (env, yaml)
+}
+
+fn yaml_location(path: &Path, anchor: &str) -> MatchLocation {
+    MatchLocation::Yaml {
+        path: path.to_path_buf(),
+        anchor: anchor.to_string(),
+    }
+}
+
+fn summary(m: &Match) -> (String, usize, String) {
+    (m.field_path.clone(), m.line_number, m.line.clone())
+}
+
+#[test]
+fn yaml_scalars_match_with_anchor_and_field_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let (env, yaml) = environment(dir.path());
+
+    let matches = grep(
+        &env,
+        &Regex::new("boss_phase_3").unwrap(),
+        &GrepOptions::default(),
+    );
+    assert_eq!(matches.len(), 2);
+
+    assert_eq!(matches[0].location, yaml_location(&yaml, "4900000"));
+    assert_eq!(matches[0].class_name, "TextAsset");
+    assert_eq!(
+        summary(&matches[0]),
+        ("m_Script".to_string(), 2, "wake boss_phase_3".to_string())
+    );
+    assert_eq!(matches[0].range, (5, 17));
+
+    assert_eq!(matches[1].location, yaml_location(&yaml, "11400000"));
+    assert_eq!(matches[1].class_name, "MonoBehaviour");
+    assert_eq!(
+        summary(&matches[1]),
+        ("phases[1].name".to_string(), 1, "boss_phase_3".to_string())
+    );
+}
+
+#[test]
+fn context_and_class_filter() {
+    let dir = tempfile::tempdir().unwrap();
+    let (env, _) = environment(dir.path());
+
+    let options = GrepOptions {
+        class_name: Some("textasset".to_string()),
+        context_lines: 1,
+        ..Default::default()
+    };
+    let matches = grep(&env, &Regex::new("boss_phase_").unwrap(), &options);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].before, vec!["intro"]);
+    assert_eq!(matches[0].after, vec!["outro"]);
+}
+
+#[test]
+fn binary_string_fields_match_with_container_and_path_id() {
+    let dir = tempfile::tempdir().unwrap();
+    let (env, _) = environment(dir.path());
+    let banner = env
+        .bundles()
+        .keys()
+        .next()
+        .cloned()
+        .expect("banner_1 is loaded");
+
+    let matches = grep(
+        &env,
+        &Regex::new("^banner_1$").unwrap(),
+        &GrepOptions::default(),
+    );
+    let found: Vec<(MatchLocation, String, String)> = matches
+        .iter()
+        .map(|m| {
+            (
+                m.location.clone(),
+                m.class_name.clone(),
+                m.field_path.clone(),
+            )
+        })
+        .collect();
+    let binary = |path_id: i64| MatchLocation::Binary {
+        source: banner.clone(),
+        asset_index: Some(0),
+        path_id,
+        container: Some("assets/assetbundles/images/banner/banner_1.png".to_string()),
+    };
+    assert_eq!(
+        found,
+        vec![
+            (
+                binary(-8325468307350463555),
+                "Sprite".to_string(),
+                "m_Name".to_string()
+            ),
+            (
+                binary(-3875358842991402074),
+                "Texture2D".to_string(),
+                "m_Name".to_string()
+            ),
+        ]
+    );
+
+    // The AssetBundle object has no container entry of its own.
+    let matches = grep(
+        &env,
+        &Regex::new("^images/banner/").unwrap(),
+        &GrepOptions::default(),
+    );
+    let fields: Vec<_> = matches.iter().map(|m| m.field_path.as_str()).collect();
+    assert_eq!(fields, vec!["m_Name", "m_AssetBundleName"]);
+    assert!(matches.iter().all(|m| matches!(
+        &m.location,
+        MatchLocation::Binary {
+            path_id: 1,
+            container: None,
+            ..
+        }
+    )));
+}
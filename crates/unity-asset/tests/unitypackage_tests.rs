@@ -0,0 +1,180 @@
+//! `.unitypackage` reading and writing: a package exported from `tests/fixtures/guid_project`
+//! (with a folder entry, a preview thumbnail, a legacy two-line `pathname`, an entry missing its
+//! `pathname` and a root `.icon.png`), builder round trips, and loading packages into an
+//! `Environment`.
+
+#![cfg(feature = "unitypackage")]
+
+use std::path::{Path, PathBuf};
+use unity_asset::environment::{BinarySource, Environment};
+use unity_asset::unitypackage::{UnityPackage, UnityPackageAssetKind};
+use unity_asset::{UnityDocument, UnityGuid};
+use unity_asset_binary::file::{UnityFile, UnityFileKind};
+
+const PREFAB: &str = "8f3e2d1c0b9a48776655443322110011";
+const MATERIAL: &str = "c0ffee00c0ffee00c0ffee00c0ffee01";
+const FOLDER: &str = "11112222333344445555666677778888";
+const TEXTURE: &str = "0badf00d0badf00d0badf00d0badf00d";
+const NO_PATHNAME: &str = "0badc0de0badc0de0badc0de0badc0de";
+
+fn guid(hex: &str) -> UnityGuid {
+    hex.parse().unwrap()
+}
+
+fn fixture() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/guid_project.unitypackage")
+        .canonicalize()
+        .unwrap()
+}
+
+fn project_file(path: &str) -> Vec<u8> {
+    std::fs::read(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/guid_project")
+            .join(path),
+    )
+    .unwrap()
+}
+
+fn meta(guid: &str) -> String {
+    format!(
+        "fileFormatVersion: 2\nguid: {}\nDefaultImporter:\n  userData: \n",
+        guid
+    )
+}
+
+#[test]
+fn exported_package_lists_entries_by_guid_and_path() {
+    let package = UnityPackage::open(fixture()).unwrap();
+    assert_eq!(package.path(), Some(fixture().as_path()));
+    // Six project assets plus the entry without a pathname; `.icon.png` is not an entry.
+    assert_eq!(package.entries().len(), 7);
+
+    let prefab = package
+        .entry_by_path("Assets/Prefabs/Crate.prefab")
+        .unwrap();
+    assert_eq!(prefab.guid, guid(PREFAB));
+    assert_eq!(prefab.asset_kind(), UnityPackageAssetKind::Yaml);
+    assert!(prefab.meta.as_deref().unwrap().contains(PREFAB));
+    let doc = prefab.load_yaml().unwrap();
+    assert_eq!(doc.entries()[0].class_name, "GameObject");
+    assert!(prefab.load_binary().is_err());
+
+    let folder = package.entry_by_guid(&guid(FOLDER)).unwrap();
+    assert_eq!(folder.path.as_deref(), Some("Assets/Materials"));
+    assert_eq!(folder.asset_kind(), UnityPackageAssetKind::Folder);
+    assert!(folder.load_yaml().is_err());
+
+    let texture = package.entry_by_guid(&guid(TEXTURE)).unwrap();
+    assert_eq!(texture.asset_kind(), UnityPackageAssetKind::Other);
+    assert!(texture.preview.as_ref().unwrap().starts_with(b"\x89PNG"));
+
+    // The legacy `00` line after the path is dropped.
+    assert!(
+        package
+            .entry_by_path("Assets/Materials/Unused.mat")
+            .is_some()
+    );
+
+    let orphan = package.entry_by_guid(&guid(NO_PATHNAME)).unwrap();
+    assert_eq!(orphan.path, None);
+    assert_eq!(orphan.asset_kind(), UnityPackageAssetKind::Yaml);
+}
+
+#[test]
+fn builder_round_trips_assets_folders_and_binaries() {
+    let prefab = project_file("Assets/Prefabs/Crate.prefab");
+    let bundle =
+        std::fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples/banner_1"))
+            .unwrap();
+    let bytes = UnityPackage::builder()
+        .add_folder("Assets/Prefabs", meta(FOLDER))
+        .add_asset("Assets/Prefabs/Crate.prefab", prefab.clone(), meta(PREFAB))
+        .add_asset("Assets/Bundles/banner_1", bundle.clone(), meta(MATERIAL))
+        .build()
+        .unwrap();
+
+    let package = UnityPackage::from_bytes(&bytes).unwrap();
+    assert_eq!(package.entries().len(), 3);
+    let entry = package.entry_by_guid(&guid(PREFAB)).unwrap();
+    assert_eq!(entry.path.as_deref(), Some("Assets/Prefabs/Crate.prefab"));
+    assert_eq!(entry.asset.as_deref(), Some(prefab.as_slice()));
+    assert_eq!(entry.meta.as_deref(), Some(meta(PREFAB).as_str()));
+    assert_eq!(entry.preview, None);
+    assert_eq!(
+        package.entry_by_guid(&guid(FOLDER)).unwrap().asset_kind(),
+        UnityPackageAssetKind::Folder
+    );
+
+    let binary = package.entry_by_path("Assets/Bundles/banner_1").unwrap();
+    assert_eq!(
+        binary.asset_kind(),
+        UnityPackageAssetKind::Binary(UnityFileKind::AssetBundle)
+    );
+    assert_eq!(binary.asset.as_deref(), Some(bundle.as_slice()));
+    assert!(matches!(
+        binary.load_binary().unwrap(),
+        UnityFile::AssetBundle(_)
+    ));
+
+    // Every entry needs a GUID in its meta, and GUIDs must be unique.
+    assert!(
+        UnityPackage::builder()
+            .add_asset("Assets/a.txt", b"a".to_vec(), "fileFormatVersion: 2\n")
+            .build()
+            .is_err()
+    );
+    assert!(
+        UnityPackage::builder()
+            .add_asset("Assets/a.txt", b"a".to_vec(), meta(PREFAB))
+            .add_asset("Assets/b.txt", b"b".to_vec(), meta(PREFAB))
+            .build()
+            .is_err()
+    );
+}
+
+#[test]
+fn environment_loads_a_package_as_a_source() {
+    let mut env = Environment::new();
+    env.load_file(fixture()).unwrap();
+
+    let prefab_path = fixture().join("Assets/Prefabs/Crate.prefab");
+    assert!(env.yaml_documents().contains_key(&prefab_path));
+    assert!(
+        env.yaml_documents()
+            .contains_key(&fixture().join("Assets/Scenes/Main.unity"))
+    );
+    // Four YAML assets with a pathname; the folder, the texture and the orphan are skipped.
+    assert_eq!(env.yaml_documents().len(), 4);
+
+    // `.meta` GUIDs are indexed, so references between the package's assets resolve.
+    let graph = env.guid_dependency_graph();
+    assert_eq!(
+        graph.node(&guid(PREFAB)).unwrap().path.as_deref(),
+        Some(prefab_path.as_path())
+    );
+    assert!(
+        graph
+            .dependencies_of(&guid(PREFAB))
+            .contains(&guid(MATERIAL))
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let package_path = dir.path().join("bundles.unitypackage");
+    let bundle =
+        std::fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/samples/banner_1"))
+            .unwrap();
+    UnityPackage::builder()
+        .add_asset("Assets/StreamingAssets/banner_1", bundle, meta(MATERIAL))
+        .save(&package_path)
+        .unwrap();
+
+    let mut env = Environment::new();
+    env.load(&package_path).unwrap();
+    let source = BinarySource::ArchiveEntry {
+        archive_path: package_path.canonicalize().unwrap(),
+        entry_name: "Assets/StreamingAssets/banner_1".to_string(),
+    };
+    assert!(env.bundles().contains_key(&source));
+}
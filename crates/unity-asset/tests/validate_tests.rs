@@ -0,0 +1,156 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use unity_asset::validate::{FileKind, Severity, ValidateOptions, ValidationRun};
+
+fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+fn banner() -> Vec<u8> {
+    fs::read(repo_root().join("tests/samples/banner_1")).unwrap()
+}
+
+fn checks(run: &ValidationRun, name: &str) -> Vec<(Severity, String)> {
+    let file = run
+        .files
+        .iter()
+        .find(|f| f.path.file_name().unwrap() == name)
+        .unwrap_or_else(|| panic!("no report for {name}"));
+    file.findings
+        .iter()
+        .map(|f| (f.severity, f.check.clone()))
+        .collect()
+}
+
+fn write_tree(root: &Path) {
+    let data = banner();
+    fs::write(root.join("good.bundle"), &data).unwrap();
+    fs::write(root.join("truncated.bundle"), &data[..data.len() / 2]).unwrap();
+    // banner_1 is a single LZ4HC block of 34546 bytes at the end of the file; flipping its first
+    // bytes breaks the LZ4 tokens.
+    let mut corrupt = data.clone();
+    let block = data.len() - 34546;
+    for byte in &mut corrupt[block..block + 64] {
+        *byte ^= 0x5a;
+    }
+    fs::write(root.join("corrupt.bundle"), &corrupt).unwrap();
+
+    fs::write(
+        root.join("good.bundle.manifest"),
+        "ManifestFileVersion: 0\nCRC: 1\n",
+    )
+    .unwrap();
+
+    let prefab = repo_root().join("crates/unity-asset-yaml/tests/fixtures/SingleDoc.asset");
+    let assets = root.join("Assets");
+    fs::create_dir_all(&assets).unwrap();
+    fs::copy(prefab, assets.join("SingleDoc.asset")).unwrap();
+    fs::write(
+        assets.join("Broken.prefab"),
+        "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n--- !u!1 &1\nGameObject:\n  m_Name: [unclosed\n",
+    )
+    .unwrap();
+    fs::write(assets.join("readme.txt"), "not a unity file").unwrap();
+
+    // Never descended into.
+    let library = root.join("Library");
+    fs::create_dir_all(&library).unwrap();
+    fs::write(library.join("cache.bundle"), &data[..100]).unwrap();
+}
+
+#[test]
+fn validate_reports_findings_per_file() {
+    let dir = tempfile::tempdir().unwrap();
+    write_tree(dir.path());
+    let run = unity_asset::validate::run(&[dir.path()], &ValidateOptions::default());
+
+    assert_eq!(run.schema_version, unity_asset::validate::SCHEMA_VERSION);
+    assert_eq!(run.summary.files, 5);
+    assert_eq!(run.summary.skipped, 2);
+    assert!(run.files.windows(2).all(|w| w[0].path < w[1].path));
+
+    let good = run
+        .files
+        .iter()
+        .find(|f| f.path.file_name().unwrap() == "good.bundle")
+        .unwrap();
+    assert_eq!(good.kind, FileKind::AssetBundle);
+    assert_eq!(
+        checks(&run, "good.bundle"),
+        [(Severity::Warning, "bundle.crc".to_string())]
+    );
+    assert_eq!(checks(&run, "SingleDoc.asset"), []);
+
+    for name in ["truncated.bundle", "corrupt.bundle"] {
+        let found = checks(&run, name);
+        assert!(
+            found.iter().any(|(s, _)| *s == Severity::Error),
+            "{name}: {found:?}"
+        );
+    }
+    assert_eq!(
+        checks(&run, "truncated.bundle"),
+        [(Severity::Error, "bundle.header".to_string())]
+    );
+    assert_eq!(
+        checks(&run, "Broken.prefab"),
+        [(Severity::Error, "yaml.parse".to_string())]
+    );
+
+    assert_eq!(run.summary.files_with_errors, 3);
+    assert_eq!(run.summary.files_with_warnings, 1);
+    assert!(run.fails(Severity::Error));
+    assert!(run.fails(Severity::Warning));
+}
+
+#[test]
+fn validate_clean_samples_and_explicit_files() {
+    let samples = repo_root().join("tests/samples");
+    let run = unity_asset::validate::run(
+        &[samples.join("banner_1"), samples.join("char_118_yuki.ab")],
+        &ValidateOptions::default().with_deep(true),
+    );
+    assert!(run.deep);
+    assert_eq!(run.summary.files, 2);
+    assert_eq!(run.summary.errors + run.summary.warnings, 0, "{run:?}");
+    assert!(!run.fails(Severity::Warning));
+
+    let dir = tempfile::tempdir().unwrap();
+    let text = dir.path().join("notes.txt");
+    fs::write(&text, "hello").unwrap();
+    let run = unity_asset::validate::run(
+        &[text, dir.path().join("missing.bundle")],
+        &ValidateOptions::default(),
+    );
+    let found: Vec<_> = run
+        .findings()
+        .map(|(_, f)| (f.severity, f.check.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            (Severity::Error, "file.read"),
+            (Severity::Warning, "file.unrecognized")
+        ]
+    );
+}
+
+#[test]
+fn validation_run_json_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("truncated.bundle"), &banner()[..200]).unwrap();
+    let run = unity_asset::validate::run(&[dir.path()], &ValidateOptions::default());
+    let value = serde_json::to_value(&run).unwrap();
+
+    assert_eq!(value["schema_version"], 1);
+    let file = &value["files"][0];
+    assert_eq!(file["kind"], "asset_bundle");
+    assert_eq!(file["findings"][0]["severity"], "error");
+    assert_eq!(file["findings"][0]["check"], "bundle.header");
+    assert!(file["findings"][0].get("entry").is_none());
+    assert_eq!(value["summary"]["errors"], 1);
+
+    let back: ValidationRun = serde_json::from_value(value).unwrap();
+    assert_eq!(back, run);
+}